@@ -2,16 +2,16 @@
 
 use crate::{
     args::LogArgs,
-    commands::debug_cmd,
+    commands::{debug_cmd, serve},
     version::{LONG_VERSION, SHORT_VERSION},
 };
 use clap::{value_parser, Parser, Subcommand};
 use reth_chainspec::ChainSpec;
 use reth_cli::chainspec::ChainSpecParser;
 use reth_cli_commands::{
-    config_cmd, db, dump_genesis, import, init_cmd, init_state,
+    config_cmd, datadir, db, dump_genesis, import, init_cmd, init_state,
     node::{self, NoArgs},
-    p2p, prune, recover, stage,
+    p2p, prune, recover, snapshot, stage, stats,
 };
 use reth_cli_runner::CliRunner;
 use reth_db::DatabaseEnv;
@@ -160,7 +160,7 @@ impl<C: ChainSpecParser<ChainSpec = ChainSpec>, Ext: clap::Args + fmt::Debug> Cl
                 runner.run_blocking_until_ctrl_c(command.execute::<EthereumNode>())
             }
             Commands::InitState(command) => {
-                runner.run_blocking_until_ctrl_c(command.execute::<EthereumNode>())
+                runner.run_blocking_until_completion(command.execute::<EthereumNode>())
             }
             Commands::Import(command) => runner.run_blocking_until_ctrl_c(
                 command.execute::<EthereumNode, _, _>(EthExecutorProvider::ethereum),
@@ -188,6 +188,12 @@ impl<C: ChainSpecParser<ChainSpec = ChainSpec>, Ext: clap::Args + fmt::Debug> Cl
                 runner.run_command_until_exit(|ctx| command.execute::<EthereumNode>(ctx))
             }
             Commands::Prune(command) => runner.run_until_ctrl_c(command.execute::<EthereumNode>()),
+            Commands::Datadir(command) => runner.run_until_ctrl_c(command.execute()),
+            Commands::Snapshot(command) => {
+                runner.run_command_until_exit(|ctx| command.execute::<EthereumNode>(ctx))
+            }
+            Commands::Stats(command) => runner.run_until_ctrl_c(command.execute::<EthereumNode>()),
+            Commands::Serve(command) => runner.run_command_until_exit(|ctx| command.execute(ctx)),
         }
     }
 
@@ -243,6 +249,18 @@ pub enum Commands<C: ChainSpecParser, Ext: clap::Args + fmt::Debug> {
     /// Prune according to the configuration without any limits
     #[command(name = "prune")]
     Prune(prune::PruneCommand<C>),
+    /// Data directory layout utilities
+    #[command(name = "datadir")]
+    Datadir(datadir::Command<C>),
+    /// Static file inspection and download utilities
+    #[command(name = "snapshot")]
+    Snapshot(snapshot::Command<C>),
+    /// Block range analytics computed from the database
+    #[command(name = "stats")]
+    Stats(stats::Command<C>),
+    /// Serve the RPC from an existing datadir without writing to it
+    #[command(name = "serve")]
+    Serve(serve::Command<C>),
 }
 
 #[cfg(test)]
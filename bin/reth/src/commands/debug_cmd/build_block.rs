@@ -1,56 +1,64 @@
 //! Command for debugging block building.
-use alloy_consensus::TxEip4844;
 use alloy_eips::{
-    eip2718::Encodable2718,
-    eip4844::{env_settings::EnvKzgSettings, BlobTransactionSidecar},
+    eip2718::Decodable2718,
+    eip4844::{env_settings::EnvKzgSettings, DATA_GAS_PER_BLOB},
+};
+use alloy_primitives::{utils::format_ether, Address, B256, U256};
+use alloy_rpc_types::engine::{
+    ExecutionPayloadEnvelopeV2, ExecutionPayloadEnvelopeV3, ExecutionPayloadEnvelopeV4,
+    ExecutionPayloadV1, PayloadAttributes,
 };
-use alloy_primitives::{Address, Bytes, B256, U256};
-use alloy_rlp::Decodable;
-use alloy_rpc_types::engine::{BlobsBundleV1, PayloadAttributes};
 use clap::Parser;
 use eyre::Context;
+use jsonrpsee::http_client::HttpClientBuilder;
 use reth_basic_payload_builder::{
     BuildArguments, BuildOutcome, Cancelled, PayloadBuilder, PayloadConfig,
 };
 use reth_beacon_consensus::EthBeaconConsensus;
-use reth_blockchain_tree::{
-    BlockchainTree, BlockchainTreeConfig, ShareableBlockchainTree, TreeExternals,
-};
-use reth_chainspec::ChainSpec;
+use reth_blockchain_tree::noop::NoopBlockchainTree;
+use reth_chainspec::{ChainSpec, EthereumHardforks};
 use reth_cli::chainspec::ChainSpecParser;
 use reth_cli_commands::common::{AccessRights, CliNodeTypes, Environment, EnvironmentArgs};
 use reth_cli_runner::CliContext;
-use reth_consensus::{Consensus, FullConsensus};
-use reth_errors::RethResult;
+use reth_cli_util::parse_duration_from_secs_or_ms;
+use reth_consensus::{Consensus, HeaderValidator};
 use reth_ethereum_payload_builder::EthereumBuilderConfig;
-use reth_evm::execute::{BlockExecutorProvider, Executor};
-use reth_execution_types::ExecutionOutcome;
 use reth_fs_util as fs;
-use reth_node_api::{BlockTy, EngineApiMessageVersion, PayloadBuilderAttributes};
-use reth_node_ethereum::{EthEvmConfig, EthExecutorProvider};
+use reth_node_api::{EngineApiMessageVersion, PayloadBuilderAttributes};
+use reth_node_ethereum::EthEvmConfig;
+use reth_payload_builder::EthBuiltPayload;
 use reth_primitives::{
-    BlockExt, EthPrimitives, SealedBlockFor, SealedBlockWithSenders, SealedHeader, Transaction,
-    TransactionSigned,
+    transaction::SignedTransactionIntoRecoveredExt, EthPrimitives, PooledTransaction,
+    PooledTransactionsElementEcRecovered, SealedHeader,
 };
 use reth_provider::{
     providers::{BlockchainProvider, ProviderNodeTypes},
-    BlockHashReader, BlockReader, BlockWriter, ChainSpecProvider, ProviderFactory,
-    StageCheckpointReader, StateProviderFactory,
+    BlockHashReader, ChainSpecProvider, HeaderProvider, ProviderFactory, StageCheckpointReader,
 };
-use reth_revm::{cached::CachedReads, database::StateProviderDatabase, primitives::KzgSettings};
+use reth_revm::{cached::CachedReads, primitives::KzgSettings};
+use reth_rpc_api::clients::{EthApiClient, TxPoolApiClient};
 use reth_stages::StageId;
+use reth_tasks::TaskExecutor;
 use reth_transaction_pool::{
-    blobstore::InMemoryBlobStore, BlobStore, EthPooledTransaction, PoolConfig, TransactionOrigin,
+    blobstore::InMemoryBlobStore, EthPooledTransaction, PoolConfig, TransactionOrigin,
     TransactionPool, TransactionValidationTaskExecutor,
 };
-use reth_trie::StateRoot;
-use reth_trie_db::DatabaseStateRoot;
-use std::{path::PathBuf, str::FromStr, sync::Arc};
+use std::{
+    path::PathBuf,
+    sync::Arc,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
 use tracing::*;
 
+/// Interval between two builds of the payload, the same as the default `--builder.interval` of
+/// the node.
+const BUILD_INTERVAL: Duration = Duration::from_secs(1);
+
 /// `reth debug build-block` command
-/// This debug routine requires that the node is positioned at the block before the target.
-/// The script will then parse the block and attempt to build a similar one.
+///
+/// Builds a block on top of a block in the database with the payload builder of the node, from the
+/// pending transactions in the pool of the running node. The built block is validated, and its gas
+/// used, number of transactions, fees and number of blobs are logged.
 #[derive(Debug, Parser)]
 pub struct Command<C: ChainSpecParser> {
     #[command(flatten)]
@@ -60,51 +68,70 @@ pub struct Command<C: ChainSpecParser> {
     #[arg(long, value_name = "PATH")]
     trusted_setup_file: Option<PathBuf>,
 
+    /// Hash of the block to build on top of. Defaults to the latest block.
+    #[arg(long, value_name = "HASH")]
+    parent: Option<B256>,
+
+    /// Parent beacon block root of the block. Defaults to the zero hash once Cancun is active.
     #[arg(long)]
     parent_beacon_block_root: Option<B256>,
 
+    /// Randomness value of the block. Defaults to the zero hash.
     #[arg(long)]
-    prev_randao: B256,
+    prev_randao: Option<B256>,
 
+    /// Timestamp of the block. Defaults to now, or one second after the parent if that's later.
     #[arg(long)]
-    timestamp: u64,
+    timestamp: Option<u64>,
 
-    #[arg(long)]
-    suggested_fee_recipient: Address,
+    /// Address that receives the fees of the block. Defaults to the zero address.
+    #[arg(long, alias = "suggested-fee-recipient", value_name = "ADDRESS")]
+    fee_recipient: Option<Address>,
 
-    /// Array of transactions.
-    /// NOTE: 4844 transactions must be provided in the same order as they appear in the blobs
-    /// bundle.
-    #[arg(long, value_delimiter = ',')]
-    transactions: Vec<String>,
+    /// Keeps building the block until the deadline, to account for the time the node has to build
+    /// a payload, instead of building it once.
+    #[arg(long, value_parser = parse_duration_from_secs_or_ms, value_name = "DURATION")]
+    deadline: Option<Duration>,
 
-    /// Path to the file that contains a corresponding blobs bundle.
-    #[arg(long)]
-    blobs_bundle_path: Option<PathBuf>,
+    /// Writes the built payload as JSON to the file, in the format of `engine_getPayload`.
+    #[arg(long, value_name = "PATH")]
+    output: Option<PathBuf>,
+
+    /// HTTP RPC URL of the running node to take the pending transactions of the pool from.
+    ///
+    /// The `txpool` and `eth` namespaces must be enabled on it.
+    #[arg(long, value_name = "URL", default_value = "http://localhost:8545")]
+    rpc_url: String,
 }
 
 impl<C: ChainSpecParser<ChainSpec = ChainSpec>> Command<C> {
-    /// Fetches the best block block from the database.
+    /// Fetches the header of the block to build on top of from the database.
     ///
-    /// If the database is empty, returns the genesis block.
-    fn lookup_best_block<N: ProviderNodeTypes<ChainSpec = C::ChainSpec>>(
+    /// If no parent is configured, this is the latest block, or the genesis block if the database
+    /// is empty.
+    fn lookup_parent<N: ProviderNodeTypes<Primitives = EthPrimitives>>(
         &self,
-        factory: ProviderFactory<N>,
-    ) -> RethResult<Arc<SealedBlockFor<BlockTy<N>>>> {
+        factory: &ProviderFactory<N>,
+    ) -> eyre::Result<SealedHeader> {
         let provider = factory.provider()?;
 
-        let best_number =
-            provider.get_stage_checkpoint(StageId::Finish)?.unwrap_or_default().block_number;
-        let best_hash = provider
-            .block_hash(best_number)?
-            .expect("the hash for the latest block is missing, database is corrupt");
-
-        Ok(Arc::new(
-            provider
-                .block(best_number.into())?
-                .expect("the header for the latest block is missing, database is corrupt")
-                .seal(best_hash),
-        ))
+        let hash = match self.parent {
+            Some(hash) => hash,
+            None => {
+                let best_number = provider
+                    .get_stage_checkpoint(StageId::Finish)?
+                    .unwrap_or_default()
+                    .block_number;
+                provider.block_hash(best_number)?.ok_or_else(|| {
+                    eyre::eyre!("the hash for the latest block {best_number} is missing")
+                })?
+            }
+        };
+        let header = provider
+            .header(&hash)?
+            .ok_or_else(|| eyre::eyre!("the parent block {hash} is not in the database"))?;
+
+        Ok(SealedHeader::new(header, hash))
     }
 
     /// Loads the trusted setup params from a given file path or falls back to
@@ -121,43 +148,114 @@ impl<C: ChainSpecParser<ChainSpec = ChainSpec>> Command<C> {
         }
     }
 
-    /// Execute `debug in-memory-merkle` command
+    /// Fetches the pending transactions from the pool of the running node.
+    ///
+    /// The transactions are fetched in their pooled encoding, so that blob transactions include
+    /// their sidecars.
+    async fn fetch_pool_transactions(
+        &self,
+    ) -> eyre::Result<Vec<PooledTransactionsElementEcRecovered>> {
+        let client = HttpClientBuilder::default().build(&self.rpc_url)?;
+        let content = TxPoolApiClient::<alloy_rpc_types::Transaction>::txpool_content(&client)
+            .await
+            .wrap_err_with(|| {
+                format!("failed to fetch the transaction pool from {}", self.rpc_url)
+            })?;
+
+        let mut transactions = Vec::new();
+        for tx in content.pending.into_values().flat_map(|txs| txs.into_values()) {
+            let hash = *tx.inner.tx_hash();
+            let Some(raw) = EthApiClient::<
+                alloy_rpc_types::Transaction,
+                alloy_rpc_types::Block,
+                alloy_rpc_types::Receipt,
+                alloy_rpc_types::Header,
+            >::raw_transaction_by_hash(&client, hash)
+            .await?
+            else {
+                // the transaction was removed from the pool in the meantime
+                debug!(target: "reth::cli", %hash, "Pool transaction is gone");
+                continue
+            };
+            let transaction = PooledTransaction::decode_2718(&mut raw.as_ref())
+                .wrap_err_with(|| format!("failed to decode pool transaction {hash}"))?
+                .try_into_ecrecovered()
+                .map_err(|_| {
+                    eyre::eyre!("failed to recover the signer of pool transaction {hash}")
+                })?;
+            transactions.push(transaction);
+        }
+        info!(
+            target: "reth::cli",
+            transactions = transactions.len(),
+            "Fetched pending pool transactions"
+        );
+
+        Ok(transactions)
+    }
+
+    /// Execute `debug build-block` command
     pub async fn execute<N: CliNodeTypes<ChainSpec = C::ChainSpec, Primitives = EthPrimitives>>(
         self,
         ctx: CliContext,
     ) -> eyre::Result<()> {
-        let Environment { provider_factory, .. } = self.env.init::<N>(AccessRights::RW)?;
-
-        let consensus: Arc<dyn FullConsensus> =
-            Arc::new(EthBeaconConsensus::new(provider_factory.chain_spec()));
-
-        let executor = EthExecutorProvider::ethereum(provider_factory.chain_spec());
+        let transactions = self.fetch_pool_transactions().await?;
+        let (payload, chain_spec) =
+            self.build_payload::<N>(ctx.task_executor, transactions).await?;
+
+        let block = payload.block();
+        let blob_count = block.blob_gas_used.unwrap_or_default() / DATA_GAS_PER_BLOB;
+        info!(
+            target: "reth::cli",
+            number = block.number,
+            hash = %block.hash(),
+            gas_used = block.gas_used,
+            gas_limit = block.gas_limit,
+            transactions = block.body.transactions.len(),
+            fees = %format!("{} ETH", format_ether(payload.fees())),
+            blobs = blob_count,
+            "Built block"
+        );
 
-        // configure blockchain tree
-        let tree_externals =
-            TreeExternals::new(provider_factory.clone(), Arc::clone(&consensus), executor);
-        let tree = BlockchainTree::new(tree_externals, BlockchainTreeConfig::default())?;
-        let blockchain_tree = Arc::new(ShareableBlockchainTree::new(tree));
+        if let Some(output) = &self.output {
+            let json = payload_json(&chain_spec, payload)?;
+            fs::write(output, serde_json::to_string_pretty(&json)?)?;
+            info!(target: "reth::cli", path = %output.display(), "Wrote payload");
+        }
 
-        // fetch the best block from the database
-        let best_block = self
-            .lookup_best_block(provider_factory.clone())
-            .wrap_err("the head block is missing")?;
+        Ok(())
+    }
 
-        let blockchain_db =
-            BlockchainProvider::new(provider_factory.clone(), blockchain_tree.clone())?;
+    /// Builds and validates the payload with the payload builder of the node, from a transaction
+    /// pool that is seeded with the given transactions.
+    ///
+    /// Returns the best payload that was built, and the chain spec of the database.
+    async fn build_payload<
+        N: CliNodeTypes<ChainSpec = C::ChainSpec, Primitives = EthPrimitives>,
+    >(
+        &self,
+        task_executor: TaskExecutor,
+        transactions: Vec<PooledTransactionsElementEcRecovered>,
+    ) -> eyre::Result<(EthBuiltPayload, Arc<ChainSpec>)> {
+        let Environment { provider_factory, .. } = self.env.init::<N>(AccessRights::RO)?;
+        let chain_spec = provider_factory.chain_spec();
+        let consensus = EthBeaconConsensus::new(chain_spec.clone());
+
+        let parent =
+            self.lookup_parent(&provider_factory).wrap_err("the parent block is missing")?;
+
+        // There is no blockchain tree, the block is built on top of a block in the database.
+        let blockchain_db = BlockchainProvider::new(
+            provider_factory.clone(),
+            Arc::new(NoopBlockchainTree::default()),
+        )?;
         let blob_store = InMemoryBlobStore::default();
 
-        let validator =
-            TransactionValidationTaskExecutor::eth_builder(provider_factory.chain_spec())
-                .with_head_timestamp(best_block.timestamp)
-                .kzg_settings(self.kzg_settings()?)
-                .with_additional_tasks(1)
-                .build_with_tasks(
-                    blockchain_db.clone(),
-                    ctx.task_executor.clone(),
-                    blob_store.clone(),
-                );
+        let validator = TransactionValidationTaskExecutor::eth_builder(chain_spec.clone())
+            .with_head_timestamp(parent.timestamp)
+            .kzg_settings(self.kzg_settings()?)
+            .with_additional_tasks(1)
+            .build_with_tasks(blockchain_db.clone(), task_executor, blob_store.clone());
 
         let transaction_pool = reth_transaction_pool::Pool::eth_pool(
             validator,
@@ -166,136 +264,200 @@ impl<C: ChainSpecParser<ChainSpec = ChainSpec>> Command<C> {
         );
         info!(target: "reth::cli", "Transaction pool initialized");
 
-        let mut blobs_bundle = self
-            .blobs_bundle_path
-            .map(|path| -> eyre::Result<BlobsBundleV1> {
-                let contents = fs::read_to_string(&path)
-                    .wrap_err(format!("could not read {}", path.display()))?;
-                serde_json::from_str(&contents).wrap_err("failed to deserialize blobs bundle")
-            })
-            .transpose()?;
-
-        for tx_bytes in &self.transactions {
-            debug!(target: "reth::cli", bytes = ?tx_bytes, "Decoding transaction");
-            let transaction = TransactionSigned::decode(&mut &Bytes::from_str(tx_bytes)?[..])?
-                .into_ecrecovered()
-                .ok_or_else(|| eyre::eyre!("failed to recover tx"))?;
-
-            let encoded_length = match &transaction.transaction {
-                Transaction::Eip4844(TxEip4844 { blob_versioned_hashes, .. }) => {
-                    let blobs_bundle = blobs_bundle.as_mut().ok_or_else(|| {
-                        eyre::eyre!("encountered a blob tx. `--blobs-bundle-path` must be provided")
-                    })?;
-
-                    let sidecar: BlobTransactionSidecar =
-                        blobs_bundle.pop_sidecar(blob_versioned_hashes.len());
-
-                    let pooled = transaction
-                        .clone()
-                        .into_signed()
-                        .try_into_pooled_eip4844(sidecar.clone())
-                        .expect("should not fail to convert blob tx if it is already eip4844");
-                    let encoded_length = pooled.encode_2718_len();
-
-                    // insert the blob into the store
-                    blob_store.insert(transaction.hash(), sidecar)?;
-
-                    encoded_length
-                }
-                _ => transaction.encode_2718_len(),
-            };
-
+        // Blob transactions carry their sidecars, which the pool inserts into the blob store.
+        for transaction in transactions {
             debug!(target: "reth::cli", ?transaction, "Adding transaction to the pool");
-            transaction_pool
+            let hash = *transaction.as_signed().hash();
+            if let Err(error) = transaction_pool
                 .add_transaction(
                     TransactionOrigin::External,
-                    EthPooledTransaction::new(transaction, encoded_length),
+                    EthPooledTransaction::from(transaction),
                 )
-                .await?;
+                .await
+            {
+                // the pool of the node may have moved on from the parent block
+                warn!(target: "reth::cli", %hash, %error, "Skipping invalid pool transaction");
+            }
         }
 
+        let timestamp = self.timestamp.unwrap_or_else(|| {
+            let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+            now.max(parent.timestamp + 1)
+        });
+        let parent_beacon_block_root = self
+            .parent_beacon_block_root
+            .or_else(|| chain_spec.is_cancun_active_at_timestamp(timestamp).then_some(B256::ZERO));
         let payload_attrs = PayloadAttributes {
-            parent_beacon_block_root: self.parent_beacon_block_root,
-            prev_randao: self.prev_randao,
-            timestamp: self.timestamp,
-            suggested_fee_recipient: self.suggested_fee_recipient,
+            parent_beacon_block_root,
+            prev_randao: self.prev_randao.unwrap_or_default(),
+            timestamp,
+            suggested_fee_recipient: self.fee_recipient.unwrap_or_default(),
             // TODO: add support for withdrawals
             withdrawals: None,
         };
         let payload_config = PayloadConfig::new(
-            Arc::new(SealedHeader::new(best_block.header().clone(), best_block.hash())),
+            Arc::new(parent.clone()),
             reth_payload_builder::EthPayloadBuilderAttributes::try_new(
-                best_block.hash(),
+                parent.hash(),
                 payload_attrs,
                 EngineApiMessageVersion::default() as u8,
             )?,
         );
 
-        let args = BuildArguments::new(
-            blockchain_db.clone(),
-            transaction_pool,
-            CachedReads::default(),
-            payload_config,
-            Cancelled::default(),
-            None,
-        );
-
         let payload_builder = reth_ethereum_payload_builder::EthereumPayloadBuilder::new(
-            EthEvmConfig::new(provider_factory.chain_spec()),
+            EthEvmConfig::new(chain_spec.clone()),
             EthereumBuilderConfig::new(Default::default()),
         );
 
-        match payload_builder.try_build(args)? {
-            BuildOutcome::Better { payload, .. } => {
-                let block = payload.block();
-                debug!(target: "reth::cli", ?block, "Built new payload");
-
-                consensus.validate_header_with_total_difficulty(block, U256::MAX)?;
-                consensus.validate_header(block)?;
-                consensus.validate_block_pre_execution(block)?;
-
-                let senders = block.senders().expect("sender recovery failed");
-                let block_with_senders =
-                    SealedBlockWithSenders::<BlockTy<N>>::new(block.clone(), senders).unwrap();
-
-                let state_provider = blockchain_db.latest()?;
-                let db = StateProviderDatabase::new(&state_provider);
-                let executor =
-                    EthExecutorProvider::ethereum(provider_factory.chain_spec()).executor(db);
-
-                let block_execution_output =
-                    executor.execute(&block_with_senders.clone().unseal())?;
-                let execution_outcome =
-                    ExecutionOutcome::from((block_execution_output, block.number));
-                debug!(target: "reth::cli", ?execution_outcome, "Executed block");
-
-                let hashed_post_state = state_provider.hashed_post_state(execution_outcome.state());
-                let (state_root, trie_updates) = StateRoot::overlay_root_with_updates(
-                    provider_factory.provider()?.tx_ref(),
-                    hashed_post_state.clone(),
-                )?;
-
-                if state_root != block_with_senders.state_root {
-                    eyre::bail!(
-                        "state root mismatch. expected: {}. got: {}",
-                        block_with_senders.state_root,
-                        state_root
-                    );
+        // Build the payload like the payload job of the node does: rebuild it in intervals until
+        // the deadline, reusing the cached reads, and keep the payload with the most fees.
+        let deadline = self.deadline.map(|deadline| Instant::now() + deadline);
+        let mut cached_reads = CachedReads::default();
+        let mut best_payload = None;
+        loop {
+            let args = BuildArguments::new(
+                blockchain_db.clone(),
+                transaction_pool.clone(),
+                std::mem::take(&mut cached_reads),
+                payload_config.clone(),
+                Cancelled::default(),
+                best_payload.clone(),
+            );
+
+            match payload_builder.try_build(args)? {
+                BuildOutcome::Better { payload, cached_reads: reads } => {
+                    debug!(target: "reth::cli", fees = %payload.fees(), "Built better payload");
+                    best_payload = Some(payload);
+                    cached_reads = reads;
+                }
+                BuildOutcome::Aborted { fees, cached_reads: reads } => {
+                    debug!(target: "reth::cli", %fees, "Built payload is not better");
+                    cached_reads = reads;
+                }
+                BuildOutcome::Freeze(payload) => {
+                    best_payload = Some(payload);
+                    break
                 }
+                BuildOutcome::Cancelled => unreachable!("the build is never cancelled"),
+            }
 
-                // Attempt to insert new block without committing
-                let provider_rw = provider_factory.provider_rw()?;
-                provider_rw.append_blocks_with_state(
-                    Vec::from([block_with_senders]),
-                    execution_outcome,
-                    hashed_post_state.into_sorted(),
-                    trie_updates,
-                )?;
-                info!(target: "reth::cli", "Successfully appended built block");
+            match deadline {
+                Some(deadline) if Instant::now() + BUILD_INTERVAL < deadline => {
+                    tokio::time::sleep(BUILD_INTERVAL).await
+                }
+                _ => break,
             }
-            _ => unreachable!("other outcomes are unreachable"),
-        };
+        }
+        let payload = best_payload.expect("the first build is always better");
 
-        Ok(())
+        let block = payload.block();
+        debug!(target: "reth::cli", ?block, "Built new payload");
+        consensus.validate_header_with_total_difficulty(block, U256::MAX)?;
+        consensus.validate_header(block)?;
+        consensus.validate_block_pre_execution(block)?;
+
+        Ok((payload, chain_spec))
+    }
+}
+
+/// Converts the payload to the `engine_getPayload` response of the fork that is active at the
+/// timestamp of the block.
+fn payload_json(
+    chain_spec: &ChainSpec,
+    payload: EthBuiltPayload,
+) -> serde_json::Result<serde_json::Value> {
+    let timestamp = payload.block().timestamp;
+    if chain_spec.is_prague_active_at_timestamp(timestamp) {
+        serde_json::to_value(ExecutionPayloadEnvelopeV4::from(payload))
+    } else if chain_spec.is_cancun_active_at_timestamp(timestamp) {
+        serde_json::to_value(ExecutionPayloadEnvelopeV3::from(payload))
+    } else if chain_spec.is_shanghai_active_at_timestamp(timestamp) {
+        serde_json::to_value(ExecutionPayloadEnvelopeV2::from(payload))
+    } else {
+        serde_json::to_value(ExecutionPayloadV1::from(payload))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::TxEip1559;
+    use alloy_primitives::{b256, TxKind};
+    use reth_chainspec::DEV;
+    use reth_cli_commands::init_cmd::InitCommand;
+    use reth_ethereum_cli::chainspec::EthereumChainSpecParser;
+    use reth_node_ethereum::EthereumNode;
+    use reth_primitives::{sign_message, Transaction, TransactionSigned};
+    use reth_tasks::TaskManager;
+
+    /// Private key of the first account that is funded in the genesis of the dev chain.
+    const DEV_SECRET: B256 =
+        b256!("ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80");
+
+    fn transfer(nonce: u64) -> TransactionSigned {
+        let transaction = Transaction::Eip1559(TxEip1559 {
+            chain_id: DEV.chain.id(),
+            nonce,
+            gas_limit: 21_000,
+            max_fee_per_gas: 20_000_000_000,
+            max_priority_fee_per_gas: 1_000_000_000,
+            to: TxKind::Call(Address::with_last_byte(1)),
+            value: U256::from(1),
+            ..Default::default()
+        });
+        let signature = sign_message(DEV_SECRET, transaction.signature_hash()).unwrap();
+        TransactionSigned::new_unhashed(transaction, signature)
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn builds_block_from_pool() {
+        let datadir = tempfile::tempdir().unwrap();
+        let datadir_arg = datadir.path().to_str().unwrap();
+        InitCommand::<EthereumChainSpecParser>::parse_from([
+            "reth",
+            "--chain",
+            "dev",
+            "--datadir",
+            datadir_arg,
+        ])
+        .execute::<EthereumNode>()
+        .await
+        .unwrap();
+
+        let transactions = [transfer(0), transfer(1)];
+        let pool_transactions = transactions
+            .iter()
+            .map(|tx| tx.clone().try_into_pooled().unwrap().try_into_ecrecovered().unwrap())
+            .collect();
+
+        let command = Command::<EthereumChainSpecParser>::parse_from([
+            "reth",
+            "--chain",
+            "dev",
+            "--datadir",
+            datadir_arg,
+            "--fee-recipient",
+            "0x0000000000000000000000000000000000000002",
+        ]);
+        let tasks = TaskManager::current();
+        let (payload, chain_spec) = command
+            .build_payload::<EthereumNode>(tasks.executor(), pool_transactions)
+            .await
+            .unwrap();
+
+        let block = payload.block();
+        assert_eq!(block.number, 1);
+        assert_eq!(block.parent_hash, DEV.genesis_hash());
+        assert_eq!(block.beneficiary, Address::with_last_byte(2));
+        assert_eq!(block.gas_used, 2 * 21_000);
+        assert_eq!(
+            block.body.transactions.iter().map(|tx| tx.hash()).collect::<Vec<_>>(),
+            transactions.iter().map(|tx| tx.hash()).collect::<Vec<_>>()
+        );
+        assert!(payload.fees() > U256::ZERO);
+
+        // the payload is written in the format of the fork of the block
+        let json = payload_json(&chain_spec, payload.clone()).unwrap();
+        assert_eq!(json["executionPayload"]["blockNumber"], "0x1");
     }
 }
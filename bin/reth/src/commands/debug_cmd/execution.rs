@@ -215,6 +215,8 @@ impl<C: ChainSpecParser<ChainSpec = ChainSpec>> Command<C> {
             "events task",
             reth_node_events::node::handle_events(
                 Some(Box::new(network)),
+                Some(Box::new(provider_factory.db_ref().clone())),
+                None,
                 latest_block_number,
                 pipeline.events().map(Into::<NodeEvent<N::Primitives>>::into),
             ),
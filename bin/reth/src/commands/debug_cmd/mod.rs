@@ -12,6 +12,7 @@ mod build_block;
 mod execution;
 mod in_memory_merkle;
 mod merkle;
+mod replay;
 mod replay_engine;
 
 /// `reth debug` command
@@ -34,6 +35,9 @@ pub enum Subcommands<C: ChainSpecParser> {
     BuildBlock(build_block::Command<C>),
     /// Debug engine API by replaying stored messages.
     ReplayEngine(replay_engine::Command<C>),
+    /// Replay recorded engine API messages against a copy of a datadir snapshot and compare the
+    /// outcomes.
+    Replay(replay::Command<C>),
 }
 
 impl<C: ChainSpecParser<ChainSpec = ChainSpec>> Command<C> {
@@ -50,6 +54,7 @@ impl<C: ChainSpecParser<ChainSpec = ChainSpec>> Command<C> {
             Subcommands::InMemoryMerkle(command) => command.execute::<N>(ctx).await,
             Subcommands::BuildBlock(command) => command.execute::<N>(ctx).await,
             Subcommands::ReplayEngine(command) => command.execute::<N>(ctx).await,
+            Subcommands::Replay(command) => command.execute(ctx).await,
         }
     }
 }
@@ -0,0 +1,191 @@
+use clap::Parser;
+use eyre::WrapErr;
+use reth_chainspec::ChainSpec;
+use reth_cli::chainspec::ChainSpecParser;
+use reth_cli_runner::CliContext;
+use reth_db::init_db;
+use reth_engine_util::{
+    engine_record::{EngineRecordReader, RecordedMessage},
+    engine_replay::EngineReplay,
+    engine_store::StoredEngineApiMessage,
+};
+use reth_fs_util as fs;
+use reth_node_api::PayloadTypes;
+use reth_node_builder::{EngineNodeLauncher, NodeBuilder, NodeConfig};
+use reth_node_core::args::{DatabaseArgs, DatadirArgs, DiscoveryArgs, NetworkArgs, RpcServerArgs};
+use reth_node_ethereum::{node::EthereumAddOns, EthEngineTypes, EthereumNode};
+use reth_provider::{providers::BlockchainProvider2, BlockNumReader};
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use tracing::*;
+
+/// `reth debug replay` command
+///
+/// Replays the engine API messages recorded with `--debug.record` through the engine of a node
+/// that runs on a copy of a datadir snapshot taken before the recording started, and checks that
+/// processing each message has the same outcome as when it was recorded.
+#[derive(Debug, Parser)]
+pub struct Command<C: ChainSpecParser> {
+    /// The directory the engine API messages were recorded in.
+    #[arg(value_name = "DIR")]
+    record: PathBuf,
+
+    /// The chain this node is running.
+    ///
+    /// Possible values are either a built-in chain or the path to a chain specification file.
+    #[arg(
+        long,
+        value_name = "CHAIN_OR_PATH",
+        long_help = C::help_message(),
+        default_value = C::SUPPORTED_CHAINS[0],
+        value_parser = C::parser()
+    )]
+    chain: Arc<C::ChainSpec>,
+
+    /// The datadir snapshot the messages are replayed against, e.g. a backup of the datadir of
+    /// the recording node taken before the recording started.
+    ///
+    /// The snapshot is copied, so that it can be replayed against again. It must not contain the
+    /// recorded payloads yet, otherwise their outcomes would be reproduced trivially.
+    #[arg(long, value_name = "PATH")]
+    snapshot: PathBuf,
+
+    /// The directory the snapshot is copied to.
+    ///
+    /// Defaults to a temporary directory that's removed after the replay.
+    #[arg(long = "replay-datadir", value_name = "PATH")]
+    replay_datadir: Option<PathBuf>,
+
+    /// Send each message with the delay it was received with after the previous one, instead of
+    /// as soon as the previous one was processed.
+    ///
+    /// This reproduces timing dependent behavior, e.g. which blocks are persisted in between.
+    #[arg(long = "recorded-timing")]
+    recorded_timing: bool,
+
+    /// All database related arguments
+    #[command(flatten)]
+    db: DatabaseArgs,
+}
+
+impl<C: ChainSpecParser<ChainSpec = ChainSpec>> Command<C> {
+    /// Execute `debug replay` command
+    pub async fn execute(self, ctx: CliContext) -> eyre::Result<()> {
+        let messages = EngineRecordReader::new(&self.record)
+            .messages::<<EthEngineTypes as PayloadTypes>::PayloadAttributes>()?;
+        info!(target: "reth::cli", record = %self.record.display(), messages = messages.len(), "Read recorded engine API messages");
+
+        // replay against a copy, so that the snapshot can be replayed against again
+        let chain = self.chain.chain;
+        let source = DatadirArgs { datadir: self.snapshot.clone().into(), ..Default::default() }
+            .resolve_datadir(chain);
+        let (replay_datadir, _tmp_dir) = match self.replay_datadir.clone() {
+            Some(dir) => (dir, None),
+            None => {
+                let dir = tempfile::tempdir()?;
+                (dir.path().to_path_buf(), Some(dir))
+            }
+        };
+        let datadir_args = DatadirArgs { datadir: replay_datadir.into(), ..Default::default() };
+        let target = datadir_args.clone().resolve_datadir(chain);
+        info!(target: "reth::cli", source = %source.data_dir().display(), target = %target.data_dir().display(), "Copying snapshot");
+        copy_dir(&source.db(), &target.db())?;
+        copy_dir(&source.static_files(), &target.static_files())?;
+
+        // the node is driven by the recorded messages only
+        let network = NetworkArgs {
+            discovery: DiscoveryArgs { disable_discovery: true, ..Default::default() },
+            max_outbound_peers: Some(0),
+            max_inbound_peers: Some(0),
+            no_persist_peers: true,
+            ..Default::default()
+        };
+        let rpc = RpcServerArgs { ipcdisable: true, ..Default::default() }.with_auth_disabled();
+        let node_config = NodeConfig::new(self.chain.clone())
+            .with_datadir_args(datadir_args)
+            .with_db(self.db)
+            .with_network(network)
+            .with_rpc(rpc)
+            .with_unused_ports();
+
+        let database = Arc::new(init_db(target.db(), self.db.database_args())?);
+        let handle = NodeBuilder::new(node_config)
+            .with_database(database)
+            .with_launch_context(ctx.task_executor)
+            .with_types_and_provider::<EthereumNode, BlockchainProvider2<_>>()
+            .with_components(EthereumNode::components())
+            .with_add_ons(EthereumAddOns::default())
+            .launch_with_fn(|builder| {
+                let launcher = EngineNodeLauncher::new(
+                    builder.task_executor().clone(),
+                    builder.config().datadir(),
+                    Default::default(),
+                );
+                builder.launch_with(launcher)
+            })
+            .await?;
+
+        let result = match ensure_predates_recording(&handle.node.provider, &messages) {
+            Ok(()) => Ok(EngineReplay::new(handle.consensus_engine_handle().clone())
+                .with_recorded_timing(self.recorded_timing)
+                .replay(messages)
+                .await),
+            Err(err) => Err(err),
+        };
+        handle.shutdown().await;
+
+        match result? {
+            Ok(summary) => {
+                info!(target: "reth::cli", replayed = summary.replayed, unverified = summary.unverified, head = ?summary.head, "Replayed engine API messages without divergence");
+                Ok(())
+            }
+            Err(divergence) => {
+                println!("{}", serde_json::to_string_pretty(&divergence)?);
+                Err(eyre::eyre!(divergence))
+            }
+        }
+    }
+}
+
+/// Returns an error if the snapshot already contains the first recorded payload, in which case
+/// its outcome can't diverge.
+fn ensure_predates_recording<Attributes>(
+    provider: &impl BlockNumReader,
+    messages: &[RecordedMessage<Attributes>],
+) -> eyre::Result<()> {
+    let first_payload = messages.iter().find_map(|recorded| match &recorded.message {
+        StoredEngineApiMessage::NewPayload { payload, .. } => Some(payload.block_hash()),
+        StoredEngineApiMessage::ForkchoiceUpdated { .. } => None,
+    });
+    if let Some(hash) = first_payload {
+        if let Some(number) = provider.block_number(hash)? {
+            eyre::bail!(
+                "the snapshot already contains the first recorded payload {hash} at block {number}, use a snapshot taken before the recording started"
+            )
+        }
+    }
+    Ok(())
+}
+
+/// Copies the directory recursively, if it exists.
+fn copy_dir(source: &Path, target: &Path) -> eyre::Result<()> {
+    if !source.exists() {
+        return Ok(())
+    }
+
+    fs::create_dir_all(target)?;
+    for entry in fs::read_dir(source)? {
+        let entry = entry?;
+        let target = target.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            copy_dir(&entry.path(), &target)?;
+        } else {
+            std::fs::copy(entry.path(), &target).wrap_err_with(|| {
+                format!("failed to copy {} to {}", entry.path().display(), target.display())
+            })?;
+        }
+    }
+    Ok(())
+}
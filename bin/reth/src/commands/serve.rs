@@ -0,0 +1,214 @@
+//! `reth serve` command. Serves the RPC of an existing datadir without writing to it.
+
+use crate::args::RpcServerArgs;
+use clap::Parser;
+use futures::future;
+use reth_blockchain_tree::noop::NoopBlockchainTree;
+use reth_chainspec::ChainSpec;
+use reth_cli::chainspec::ChainSpecParser;
+use reth_cli_commands::common::{AccessRights, Environment, EnvironmentArgs};
+use reth_cli_runner::CliContext;
+use reth_node_ethereum::{
+    node::EthereumEngineValidator, EthBeaconConsensus, EthEvmConfig, EthExecutorProvider,
+    EthereumNode,
+};
+use reth_provider::{providers::BlockchainProvider, ChainSpecProvider};
+use reth_rpc::EthApi;
+use reth_rpc_builder::{config::RethRpcServerConfig, RpcModuleBuilder, RpcServerHandle};
+use reth_tasks::TaskSpawner;
+use std::sync::Arc;
+use tracing::info;
+
+/// `reth serve` command
+///
+/// Serves the RPC from the datadir of another node, e.g. from a read-only snapshot of it. The
+/// database and static files are opened read-only, and neither networking, the engine API nor the
+/// pipeline are started.
+#[derive(Debug, Parser)]
+pub struct Command<C: ChainSpecParser> {
+    #[command(flatten)]
+    env: EnvironmentArgs<C>,
+
+    #[command(flatten)]
+    rpc: RpcServerArgs,
+
+    /// Not supported, blocks can't be mined without writing to the datadir.
+    #[arg(long, hide = true)]
+    dev: bool,
+
+    /// Not supported, pruning writes to the datadir.
+    #[arg(long, hide = true)]
+    full: bool,
+}
+
+impl<C: ChainSpecParser<ChainSpec = ChainSpec>> Command<C> {
+    /// Execute `serve` command
+    pub async fn execute(self, ctx: CliContext) -> eyre::Result<()> {
+        let _handle = self.start(ctx.task_executor).await?;
+
+        // The servers run until the command is interrupted.
+        future::pending::<()>().await;
+
+        Ok(())
+    }
+
+    /// Opens the datadir read-only and starts the configured RPC servers.
+    async fn start(
+        &self,
+        executor: impl TaskSpawner + Clone + 'static,
+    ) -> eyre::Result<RpcServerHandle> {
+        self.ensure_read_only()?;
+
+        let module_config = self.rpc.transport_rpc_module_config();
+        if module_config.is_empty() {
+            eyre::bail!(
+                "no RPC server is enabled: enable one with --http or --ws, or remove --ipcdisable"
+            )
+        }
+
+        // New static files written by the node that owns the datadir are picked up by watching the
+        // static files directory.
+        let Environment { provider_factory, .. } =
+            self.env.init::<EthereumNode>(AccessRights::RoInotify)?;
+        let chain_spec = provider_factory.chain_spec();
+
+        // There is no blockchain tree, all blocks are served from the datadir.
+        let provider =
+            BlockchainProvider::new(provider_factory, Arc::new(NoopBlockchainTree::default()))?;
+
+        let modules = RpcModuleBuilder::default()
+            .with_provider(provider.clone())
+            .with_noop_pool()
+            .with_noop_network()
+            .with_executor(executor)
+            .with_evm_config(EthEvmConfig::new(chain_spec.clone()))
+            .with_events(provider)
+            .with_block_executor(EthExecutorProvider::ethereum(chain_spec.clone()))
+            .with_consensus(EthBeaconConsensus::new(chain_spec.clone()))
+            .build(
+                module_config,
+                Box::new(EthApi::with_spawner),
+                Arc::new(EthereumEngineValidator::new(chain_spec)),
+            );
+
+        let handle = self.rpc.rpc_server_config().start(&modules).await?;
+        info!(
+            target: "reth::cli",
+            http = ?handle.http_local_addr(),
+            ws = ?handle.ws_local_addr(),
+            ipc = ?handle.ipc_endpoint(),
+            "Serving RPC from the read-only datadir"
+        );
+
+        Ok(handle)
+    }
+
+    /// Rejects the flags that require writing to the datadir.
+    fn ensure_read_only(&self) -> eyre::Result<()> {
+        if self.dev {
+            eyre::bail!(
+                "--dev is not supported by `reth serve`, which never writes to the datadir: use \
+                 `reth node --dev` instead"
+            )
+        }
+        if self.full {
+            eyre::bail!(
+                "--full is not supported by `reth serve`, which never writes to the datadir: prune \
+                 with the node that owns the datadir instead"
+            )
+        }
+        Ok(())
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use alloy_eips::BlockNumberOrTag;
+    use alloy_rpc_types::{Block, Header, Transaction};
+    use reth_chainspec::{EthChainSpec, DEV};
+    use reth_cli_commands::init_cmd::InitCommand;
+    use reth_ethereum_cli::chainspec::EthereumChainSpecParser;
+    use reth_primitives::Receipt;
+    use reth_rpc_api::clients::EthApiClient;
+    use reth_tasks::TokioTaskExecutor;
+    use std::{fs, os::unix::fs::PermissionsExt, path::Path};
+
+    /// Sets the permissions of the directory and everything in it to read-only or read-write.
+    fn set_read_only(path: &Path, read_only: bool) {
+        let metadata = fs::metadata(path).unwrap();
+        if metadata.is_dir() {
+            // directories need to stay writable while their content is changed
+            if !read_only {
+                fs::set_permissions(path, fs::Permissions::from_mode(0o755)).unwrap();
+            }
+            for entry in fs::read_dir(path).unwrap() {
+                set_read_only(&entry.unwrap().path(), read_only);
+            }
+            if read_only {
+                fs::set_permissions(path, fs::Permissions::from_mode(0o555)).unwrap();
+            }
+        } else {
+            let mode = if read_only { 0o444 } else { 0o644 };
+            fs::set_permissions(path, fs::Permissions::from_mode(mode)).unwrap();
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn serves_read_only_datadir() {
+        let datadir = tempfile::tempdir().unwrap();
+        let datadir_arg = datadir.path().to_str().unwrap();
+
+        InitCommand::<EthereumChainSpecParser>::parse_from([
+            "reth",
+            "--chain",
+            "dev",
+            "--datadir",
+            datadir_arg,
+        ])
+        .execute::<EthereumNode>()
+        .await
+        .unwrap();
+        set_read_only(datadir.path(), true);
+
+        let command = Command::<EthereumChainSpecParser>::parse_from([
+            "reth",
+            "--chain",
+            "dev",
+            "--datadir",
+            datadir_arg,
+            "--http",
+            "--http.port",
+            "0",
+            "--ipcdisable",
+            // configured after the provider is created, alongside the directory watcher
+            "--static-files.max-open-files",
+            "2",
+        ]);
+        let handle = command.start(TokioTaskExecutor::default()).await.unwrap();
+
+        let client = handle.http_client().unwrap();
+        let genesis = EthApiClient::<Transaction, Block, Receipt, Header>::block_by_number(
+            &client,
+            BlockNumberOrTag::Number(0),
+            false,
+        )
+        .await
+        .unwrap()
+        .unwrap();
+        assert_eq!(genesis.header.hash, DEV.genesis_hash());
+
+        handle.stop().unwrap();
+        set_read_only(datadir.path(), false);
+    }
+
+    #[tokio::test]
+    async fn rejects_flags_that_write() {
+        for flag in ["--dev", "--full"] {
+            let command =
+                Command::<EthereumChainSpecParser>::parse_from(["reth", "--chain", "dev", flag]);
+            let err = command.start(TokioTaskExecutor::default()).await.unwrap_err();
+            assert!(err.to_string().starts_with(flag));
+        }
+    }
+}
@@ -3,12 +3,14 @@
 #[global_allocator]
 static ALLOC: reth_cli_util::allocator::Allocator = reth_cli_util::allocator::new_allocator();
 
-use clap::{Args, Parser};
+use clap::{builder::RangedU64ValueParser, Args, Parser};
 use reth::cli::Cli;
 use reth_ethereum_cli::chainspec::EthereumChainSpecParser;
 use reth_node_builder::{
     engine_tree_config::{
-        TreeConfig, DEFAULT_MEMORY_BLOCK_BUFFER_TARGET, DEFAULT_PERSISTENCE_THRESHOLD,
+        TreeConfig, DEFAULT_DEEP_REORG_THRESHOLD, DEFAULT_ENGINE_MESSAGE_QUEUE_SIZE,
+        DEFAULT_MEMORY_BLOCK_BUFFER_TARGET, DEFAULT_PERSISTENCE_THRESHOLD,
+        DEFAULT_STATE_PREWARM_PARALLELISM, DEFAULT_TRIE_NODE_CACHE_SIZE,
     },
     EngineNodeLauncher,
 };
@@ -39,6 +41,31 @@ pub struct EngineArgs {
     /// Configure the target number of blocks to keep in memory.
     #[arg(long = "engine.memory-block-buffer-target", conflicts_with = "legacy", default_value_t = DEFAULT_MEMORY_BLOCK_BUFFER_TARGET)]
     pub memory_block_buffer_target: u64,
+
+    /// Read the state the transactions of a block are expected to touch in parallel before
+    /// executing it.
+    #[arg(long = "engine.state-prewarming", conflicts_with = "legacy", default_value = "false")]
+    pub state_prewarming: bool,
+
+    /// Configure the maximum number of threads used to pre-warm the state of a block.
+    #[arg(long = "engine.state-prewarm-parallelism", conflicts_with = "legacy", default_value_t = DEFAULT_STATE_PREWARM_PARALLELISM)]
+    pub state_prewarm_parallelism: usize,
+
+    /// Configure the number of reorged out blocks above which a reorg is logged as a warning.
+    ///
+    /// If invalid block hooks are enabled, these reorgs are also saved to their output directory.
+    #[arg(long = "engine.deep-reorg-threshold", conflicts_with = "legacy", default_value_t = DEFAULT_DEEP_REORG_THRESHOLD)]
+    pub deep_reorg_threshold: u64,
+
+    /// Configure the maximum number of trie nodes cached for state root computation, 0 disables
+    /// the cache.
+    #[arg(long = "engine.trie-node-cache-size", conflicts_with = "legacy", default_value_t = DEFAULT_TRIE_NODE_CACHE_SIZE)]
+    pub trie_node_cache_size: u32,
+
+    /// Configure the number of queued engine messages above which engine API requests are
+    /// answered with SYNCING instead of being queued, must be greater than 0.
+    #[arg(long = "engine.message-queue-size", conflicts_with = "legacy", default_value_t = DEFAULT_ENGINE_MESSAGE_QUEUE_SIZE, value_parser = RangedU64ValueParser::<usize>::new().range(1..))]
+    pub message_queue_size: usize,
 }
 
 impl Default for EngineArgs {
@@ -48,6 +75,11 @@ impl Default for EngineArgs {
             legacy: false,
             persistence_threshold: DEFAULT_PERSISTENCE_THRESHOLD,
             memory_block_buffer_target: DEFAULT_MEMORY_BLOCK_BUFFER_TARGET,
+            state_prewarming: false,
+            state_prewarm_parallelism: DEFAULT_STATE_PREWARM_PARALLELISM,
+            deep_reorg_threshold: DEFAULT_DEEP_REORG_THRESHOLD,
+            trie_node_cache_size: DEFAULT_TRIE_NODE_CACHE_SIZE,
+            message_queue_size: DEFAULT_ENGINE_MESSAGE_QUEUE_SIZE,
         }
     }
 }
@@ -71,7 +103,12 @@ fn main() {
                 false => {
                     let engine_tree_config = TreeConfig::default()
                         .with_persistence_threshold(engine_args.persistence_threshold)
-                        .with_memory_block_buffer_target(engine_args.memory_block_buffer_target);
+                        .with_memory_block_buffer_target(engine_args.memory_block_buffer_target)
+                        .with_state_prewarming(engine_args.state_prewarming)
+                        .with_state_prewarm_parallelism(engine_args.state_prewarm_parallelism)
+                        .with_deep_reorg_threshold(engine_args.deep_reorg_threshold)
+                        .with_trie_node_cache_size(engine_args.trie_node_cache_size)
+                        .with_engine_message_queue_size(engine_args.message_queue_size);
                     let handle = builder
                         .with_types_and_provider::<EthereumNode, BlockchainProvider2<_>>()
                         .with_components(EthereumNode::components())
@@ -118,4 +155,20 @@ mod tests {
         let args = CommandParser::<EngineArgs>::parse_from(["reth"]).args;
         assert_eq!(args, default_args);
     }
+
+    #[test]
+    fn test_parse_engine_message_queue_size() {
+        let args =
+            CommandParser::<EngineArgs>::parse_from(["reth", "--engine.message-queue-size", "1"])
+                .args;
+        assert_eq!(args.message_queue_size, 1);
+
+        // an empty queue would answer every engine API request with SYNCING
+        assert!(CommandParser::<EngineArgs>::try_parse_from([
+            "reth",
+            "--engine.message-queue-size",
+            "0"
+        ])
+        .is_err());
+    }
 }
@@ -190,14 +190,12 @@ mod tests {
     use alloy_eips::BlockNumHash;
     use alloy_primitives::BlockHash;
     use reth_primitives::SealedBlockWithSenders;
-    use reth_testing_utils::generators::{self, random_block, BlockParams, Rng};
+    use reth_testing_utils::TestBlockBuilder;
     use std::collections::HashMap;
 
-    /// Create random block with specified number and parent hash.
-    fn create_block<R: Rng>(rng: &mut R, number: u64, parent: BlockHash) -> SealedBlockWithSenders {
-        let block =
-            random_block(rng, number, BlockParams { parent: Some(parent), ..Default::default() });
-        block.seal_with_senders().unwrap()
+    /// Returns a builder of blocks with a transaction each, so that siblings have distinct hashes.
+    fn block_builder() -> TestBlockBuilder {
+        TestBlockBuilder::default().with_txs_per_block(1)
     }
 
     /// Assert that all buffer collections have the same data length.
@@ -231,9 +229,9 @@ mod tests {
 
     #[test]
     fn simple_insertion() {
-        let mut rng = generators::rng();
-        let parent = rng.gen();
-        let block1 = create_block(&mut rng, 10, parent);
+        let mut builder = block_builder();
+        let parent = BlockHash::repeat_byte(1);
+        let block1 = builder.block_at(10, parent);
         let mut buffer = BlockBuffer::new(3);
 
         buffer.insert_block(block1.clone());
@@ -243,14 +241,14 @@ mod tests {
 
     #[test]
     fn take_entire_chain_of_children() {
-        let mut rng = generators::rng();
+        let mut builder = block_builder();
 
-        let main_parent_hash = rng.gen();
-        let block1 = create_block(&mut rng, 10, main_parent_hash);
-        let block2 = create_block(&mut rng, 11, block1.hash());
-        let block3 = create_block(&mut rng, 12, block2.hash());
-        let parent4 = rng.gen();
-        let block4 = create_block(&mut rng, 14, parent4);
+        let main_parent_hash = BlockHash::repeat_byte(1);
+        let block1 = builder.block_at(10, main_parent_hash);
+        let block2 = builder.block_at(11, block1.hash());
+        let block3 = builder.block_at(12, block2.hash());
+        let parent4 = BlockHash::repeat_byte(2);
+        let block4 = builder.block_at(14, parent4);
 
         let mut buffer = BlockBuffer::new(5);
 
@@ -276,13 +274,13 @@ mod tests {
 
     #[test]
     fn take_all_multi_level_children() {
-        let mut rng = generators::rng();
+        let mut builder = block_builder();
 
-        let main_parent_hash = rng.gen();
-        let block1 = create_block(&mut rng, 10, main_parent_hash);
-        let block2 = create_block(&mut rng, 11, block1.hash());
-        let block3 = create_block(&mut rng, 11, block1.hash());
-        let block4 = create_block(&mut rng, 12, block2.hash());
+        let main_parent_hash = BlockHash::repeat_byte(1);
+        let block1 = builder.block_at(10, main_parent_hash);
+        let block2 = builder.block_at(11, block1.hash());
+        let block3 = builder.block_at(11, block1.hash());
+        let block4 = builder.block_at(12, block2.hash());
 
         let mut buffer = BlockBuffer::new(5);
 
@@ -310,13 +308,13 @@ mod tests {
 
     #[test]
     fn take_block_with_children() {
-        let mut rng = generators::rng();
+        let mut builder = block_builder();
 
-        let main_parent = BlockNumHash::new(9, rng.gen());
-        let block1 = create_block(&mut rng, 10, main_parent.hash);
-        let block2 = create_block(&mut rng, 11, block1.hash());
-        let block3 = create_block(&mut rng, 11, block1.hash());
-        let block4 = create_block(&mut rng, 12, block2.hash());
+        let main_parent = BlockNumHash::new(9, BlockHash::repeat_byte(1));
+        let block1 = builder.block_at(10, main_parent.hash);
+        let block2 = builder.block_at(11, block1.hash());
+        let block3 = builder.block_at(11, block1.hash());
+        let block4 = builder.block_at(12, block2.hash());
 
         let mut buffer = BlockBuffer::new(5);
 
@@ -344,14 +342,14 @@ mod tests {
 
     #[test]
     fn remove_chain_of_children() {
-        let mut rng = generators::rng();
+        let mut builder = block_builder();
 
-        let main_parent = BlockNumHash::new(9, rng.gen());
-        let block1 = create_block(&mut rng, 10, main_parent.hash);
-        let block2 = create_block(&mut rng, 11, block1.hash());
-        let block3 = create_block(&mut rng, 12, block2.hash());
-        let parent4 = rng.gen();
-        let block4 = create_block(&mut rng, 14, parent4);
+        let main_parent = BlockNumHash::new(9, BlockHash::repeat_byte(1));
+        let block1 = builder.block_at(10, main_parent.hash);
+        let block2 = builder.block_at(11, block1.hash());
+        let block3 = builder.block_at(12, block2.hash());
+        let parent4 = BlockHash::repeat_byte(2);
+        let block4 = builder.block_at(14, parent4);
 
         let mut buffer = BlockBuffer::new(5);
 
@@ -367,13 +365,13 @@ mod tests {
 
     #[test]
     fn remove_all_multi_level_children() {
-        let mut rng = generators::rng();
+        let mut builder = block_builder();
 
-        let main_parent = BlockNumHash::new(9, rng.gen());
-        let block1 = create_block(&mut rng, 10, main_parent.hash);
-        let block2 = create_block(&mut rng, 11, block1.hash());
-        let block3 = create_block(&mut rng, 11, block1.hash());
-        let block4 = create_block(&mut rng, 12, block2.hash());
+        let main_parent = BlockNumHash::new(9, BlockHash::repeat_byte(1));
+        let block1 = builder.block_at(10, main_parent.hash);
+        let block2 = builder.block_at(11, block1.hash());
+        let block3 = builder.block_at(11, block1.hash());
+        let block4 = builder.block_at(12, block2.hash());
 
         let mut buffer = BlockBuffer::new(5);
 
@@ -389,19 +387,19 @@ mod tests {
 
     #[test]
     fn remove_multi_chains() {
-        let mut rng = generators::rng();
-
-        let main_parent = BlockNumHash::new(9, rng.gen());
-        let block1 = create_block(&mut rng, 10, main_parent.hash);
-        let block1a = create_block(&mut rng, 10, main_parent.hash);
-        let block2 = create_block(&mut rng, 11, block1.hash());
-        let block2a = create_block(&mut rng, 11, block1.hash());
-        let random_parent1 = rng.gen();
-        let random_block1 = create_block(&mut rng, 10, random_parent1);
-        let random_parent2 = rng.gen();
-        let random_block2 = create_block(&mut rng, 11, random_parent2);
-        let random_parent3 = rng.gen();
-        let random_block3 = create_block(&mut rng, 12, random_parent3);
+        let mut builder = block_builder();
+
+        let main_parent = BlockNumHash::new(9, BlockHash::repeat_byte(1));
+        let block1 = builder.block_at(10, main_parent.hash);
+        let block1a = builder.block_at(10, main_parent.hash);
+        let block2 = builder.block_at(11, block1.hash());
+        let block2a = builder.block_at(11, block1.hash());
+        let random_parent1 = BlockHash::repeat_byte(2);
+        let random_block1 = builder.block_at(10, random_parent1);
+        let random_parent2 = BlockHash::repeat_byte(3);
+        let random_block2 = builder.block_at(11, random_parent2);
+        let random_parent3 = BlockHash::repeat_byte(4);
+        let random_block3 = builder.block_at(12, random_parent3);
 
         let mut buffer = BlockBuffer::new(10);
 
@@ -433,14 +431,14 @@ mod tests {
 
     #[test]
     fn evict_with_gap() {
-        let mut rng = generators::rng();
+        let mut builder = block_builder();
 
-        let main_parent = BlockNumHash::new(9, rng.gen());
-        let block1 = create_block(&mut rng, 10, main_parent.hash);
-        let block2 = create_block(&mut rng, 11, block1.hash());
-        let block3 = create_block(&mut rng, 12, block2.hash());
-        let parent4 = rng.gen();
-        let block4 = create_block(&mut rng, 13, parent4);
+        let main_parent = BlockNumHash::new(9, BlockHash::repeat_byte(1));
+        let block1 = builder.block_at(10, main_parent.hash);
+        let block2 = builder.block_at(11, block1.hash());
+        let block3 = builder.block_at(12, block2.hash());
+        let parent4 = BlockHash::repeat_byte(2);
+        let block4 = builder.block_at(13, parent4);
 
         let mut buffer = BlockBuffer::new(3);
 
@@ -470,14 +468,14 @@ mod tests {
 
     #[test]
     fn simple_eviction() {
-        let mut rng = generators::rng();
-
-        let main_parent = BlockNumHash::new(9, rng.gen());
-        let block1 = create_block(&mut rng, 10, main_parent.hash);
-        let block2 = create_block(&mut rng, 11, block1.hash());
-        let block3 = create_block(&mut rng, 12, block2.hash());
-        let parent4 = rng.gen();
-        let block4 = create_block(&mut rng, 13, parent4);
+        let mut builder = block_builder();
+
+        let main_parent = BlockNumHash::new(9, BlockHash::repeat_byte(1));
+        let block1 = builder.block_at(10, main_parent.hash);
+        let block2 = builder.block_at(11, block1.hash());
+        let block3 = builder.block_at(12, block2.hash());
+        let parent4 = BlockHash::repeat_byte(2);
+        let block4 = builder.block_at(13, parent4);
 
         let mut buffer = BlockBuffer::new(3);
 
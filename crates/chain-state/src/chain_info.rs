@@ -3,10 +3,11 @@ use alloy_eips::BlockNumHash;
 use alloy_primitives::BlockNumber;
 use parking_lot::RwLock;
 use reth_chainspec::ChainInfo;
+use reth_db_models::StoredForkchoiceState;
 use reth_primitives::{NodePrimitives, SealedHeader};
 use std::{
     sync::{
-        atomic::{AtomicU64, Ordering},
+        atomic::{AtomicU64, AtomicUsize, Ordering},
         Arc,
     },
     time::Instant,
@@ -31,17 +32,28 @@ where
         finalized: Option<SealedHeader<N::BlockHeader>>,
         safe: Option<SealedHeader<N::BlockHeader>>,
     ) -> Self {
+        let (chain_status, _) = watch::channel(ChainStatus {
+            head: head.num_hash(),
+            safe: safe.as_ref().map(SealedHeader::num_hash),
+            finalized: finalized.as_ref().map(SealedHeader::num_hash),
+            unconfirmed: false,
+        });
         let (finalized_block, _) = watch::channel(finalized);
         let (safe_block, _) = watch::channel(safe);
+        let (sync_target, _) = watch::channel(None);
 
         Self {
             inner: Arc::new(ChainInfoInner {
                 last_forkchoice_update: RwLock::new(None),
                 last_transition_configuration_exchange: RwLock::new(None),
+                restored_forkchoice_state: RwLock::new(None),
                 canonical_head_number: AtomicU64::new(head.number()),
                 canonical_head: RwLock::new(head),
                 safe_block,
                 finalized_block,
+                chain_status,
+                chain_status_batches: AtomicUsize::new(0),
+                sync_target,
             }),
         }
     }
@@ -72,6 +84,32 @@ where
         *self.inner.last_transition_configuration_exchange.read()
     }
 
+    /// Sets the forkchoice state that was restored from disk on startup.
+    ///
+    /// The restored state is considered unconfirmed until the consensus layer sends a valid
+    /// forkchoice update, see [`Self::on_forkchoice_state_confirmed`].
+    pub fn set_restored_forkchoice_state(&self, state: StoredForkchoiceState) {
+        self.inner.restored_forkchoice_state.write().replace(state);
+        self.inner
+            .chain_status
+            .send_if_modified(|status| !std::mem::replace(&mut status.unconfirmed, true));
+    }
+
+    /// Returns the forkchoice state that was restored from disk on startup, if it has not been
+    /// confirmed by a valid forkchoice update yet.
+    pub fn restored_forkchoice_state(&self) -> Option<StoredForkchoiceState> {
+        *self.inner.restored_forkchoice_state.read()
+    }
+
+    /// Marks the restored forkchoice state as superseded by a valid forkchoice update received
+    /// from the consensus layer.
+    pub fn on_forkchoice_state_confirmed(&self) {
+        self.inner.restored_forkchoice_state.write().take();
+        self.inner
+            .chain_status
+            .send_if_modified(|status| std::mem::replace(&mut status.unconfirmed, false));
+    }
+
     /// Returns the canonical head of the chain.
     pub fn get_canonical_head(&self) -> SealedHeader<N::BlockHeader> {
         self.inner.canonical_head.read().clone()
@@ -88,7 +126,6 @@ where
     }
 
     /// Returns the canonical head of the chain.
-    #[allow(dead_code)]
     pub fn get_canonical_num_hash(&self) -> BlockNumHash {
         self.inner.canonical_head.read().num_hash()
     }
@@ -115,6 +152,8 @@ where
 
         // also update the atomic number.
         self.inner.canonical_head_number.store(number, Ordering::Relaxed);
+
+        self.update_chain_status();
     }
 
     /// Sets the safe header of the chain.
@@ -127,6 +166,8 @@ where
 
             false
         });
+
+        self.update_chain_status();
     }
 
     /// Sets the finalized header of the chain.
@@ -139,6 +180,38 @@ where
 
             false
         });
+
+        self.update_chain_status();
+    }
+
+    /// Holds back changes of the [`ChainStatus`] until the returned [`ChainStatusBatch`] is
+    /// dropped, so that the canonical head, safe and finalized block set in between, e.g. by a
+    /// single forkchoice update, are published as one status.
+    pub fn batch_chain_status(&self) -> ChainStatusBatch<N> {
+        self.inner.chain_status_batches.fetch_add(1, Ordering::AcqRel);
+        ChainStatusBatch { tracker: self.clone() }
+    }
+
+    /// Publishes the current canonical head, safe and finalized block as the [`ChainStatus`],
+    /// unless a [`ChainStatusBatch`] is active.
+    fn update_chain_status(&self) {
+        if self.inner.chain_status_batches.load(Ordering::Acquire) > 0 {
+            return
+        }
+
+        let head = self.get_canonical_num_hash();
+        let safe = self.get_safe_num_hash();
+        let finalized = self.get_finalized_num_hash();
+        self.inner.chain_status.send_if_modified(|status| {
+            if (status.head, status.safe, status.finalized) == (head, safe, finalized) {
+                return false
+            }
+
+            status.head = head;
+            status.safe = safe;
+            status.finalized = finalized;
+            true
+        });
     }
 
     /// Subscribe to the finalized block.
@@ -152,6 +225,61 @@ where
     pub fn subscribe_safe_block(&self) -> watch::Receiver<Option<SealedHeader<N::BlockHeader>>> {
         self.inner.safe_block.subscribe()
     }
+
+    /// Returns the current [`ChainStatus`].
+    pub fn chain_status(&self) -> ChainStatus {
+        *self.inner.chain_status.borrow()
+    }
+
+    /// Sets the block the node syncs to, the head block of the latest forkchoice update.
+    pub fn set_sync_target(&self, target: BlockNumHash) {
+        self.inner.sync_target.send_if_modified(|current| current.replace(target) != Some(target));
+    }
+
+    /// Subscribe to the block the node syncs to, the head block of the latest forkchoice update
+    /// whose number is known.
+    pub fn subscribe_sync_target(&self) -> watch::Receiver<Option<BlockNumHash>> {
+        self.inner.sync_target.subscribe()
+    }
+
+    /// Subscribe to the [`ChainStatus`], which changes whenever the canonical head, safe or
+    /// finalized block changes.
+    ///
+    /// Changes made while a [`ChainStatusBatch`] is active are published as one status once the
+    /// batch is dropped, see [`Self::batch_chain_status`].
+    pub fn subscribe_chain_status(&self) -> watch::Receiver<ChainStatus> {
+        self.inner.chain_status.subscribe()
+    }
+}
+
+/// The canonical head, safe and finalized block of the chain, as tracked by the
+/// [`ChainInfoTracker`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ChainStatus {
+    /// The canonical head of the chain.
+    pub head: BlockNumHash,
+    /// The block that the beacon node considers safe, if known.
+    pub safe: Option<BlockNumHash>,
+    /// The block that the beacon node considers finalized, if known.
+    pub finalized: Option<BlockNumHash>,
+    /// Whether the status was restored from the last forkchoice state persisted before a restart,
+    /// and not yet confirmed by a forkchoice update of the consensus layer.
+    pub unconfirmed: bool,
+}
+
+/// Holds back changes of the [`ChainStatus`] of a [`ChainInfoTracker`] while alive, and publishes
+/// them as one status when dropped.
+#[derive(Debug)]
+#[must_use = "the chain status is published when the batch is dropped"]
+pub struct ChainStatusBatch<N: NodePrimitives> {
+    tracker: ChainInfoTracker<N>,
+}
+
+impl<N: NodePrimitives> Drop for ChainStatusBatch<N> {
+    fn drop(&mut self) {
+        self.tracker.inner.chain_status_batches.fetch_sub(1, Ordering::AcqRel);
+        self.tracker.update_chain_status();
+    }
 }
 
 /// Container type for all chain info fields
@@ -165,6 +293,9 @@ struct ChainInfoInner<N: NodePrimitives = reth_primitives::EthPrimitives> {
     ///
     /// This is mainly used to track if we're connected to a beacon node.
     last_transition_configuration_exchange: RwLock<Option<Instant>>,
+    /// The forkchoice state restored from disk on startup that has not been confirmed by the
+    /// consensus layer yet.
+    restored_forkchoice_state: RwLock<Option<StoredForkchoiceState>>,
     /// Tracks the number of the `canonical_head`.
     canonical_head_number: AtomicU64,
     /// The canonical head of the chain.
@@ -173,6 +304,12 @@ struct ChainInfoInner<N: NodePrimitives = reth_primitives::EthPrimitives> {
     safe_block: watch::Sender<Option<SealedHeader<N::BlockHeader>>>,
     /// The block that the beacon node considers finalized.
     finalized_block: watch::Sender<Option<SealedHeader<N::BlockHeader>>>,
+    /// The canonical head, safe and finalized block combined.
+    chain_status: watch::Sender<ChainStatus>,
+    /// The number of active [`ChainStatusBatch`]es holding back changes of the `chain_status`.
+    chain_status_batches: AtomicUsize,
+    /// The head block of the latest forkchoice update whose number is known.
+    sync_target: watch::Sender<Option<BlockNumHash>>,
 }
 
 #[cfg(test)]
@@ -218,6 +355,32 @@ mod tests {
         assert!(tracker.last_forkchoice_update_received_at().is_some());
     }
 
+    #[test]
+    fn test_restored_forkchoice_state() {
+        let mut rng = generators::rng();
+        let header = random_header(&mut rng, 10, None);
+
+        let tracker: ChainInfoTracker<EthPrimitives> =
+            ChainInfoTracker::new(header.clone(), None, None);
+        assert!(tracker.restored_forkchoice_state().is_none());
+
+        let restored = StoredForkchoiceState {
+            head_block_hash: header.hash(),
+            timestamp: 1_700_000_000,
+            ..Default::default()
+        };
+        let mut status = tracker.subscribe_chain_status();
+        tracker.set_restored_forkchoice_state(restored);
+        assert_eq!(tracker.restored_forkchoice_state(), Some(restored));
+        assert!(status.borrow_and_update().unconfirmed);
+
+        // a valid forkchoice update from the consensus layer supersedes the restored state
+        tracker.on_forkchoice_state_confirmed();
+        assert!(tracker.restored_forkchoice_state().is_none());
+        assert!(status.has_changed().unwrap());
+        assert!(!status.borrow_and_update().unconfirmed);
+    }
+
     #[test]
     fn test_on_transition_configuration_exchanged() {
         // Create a random header
@@ -373,4 +536,52 @@ mod tests {
         // Assert that the BlockNumHash returned matches the safe header
         assert_eq!(tracker.get_safe_num_hash(), Some(safe_header.num_hash()));
     }
+
+    #[test]
+    fn test_subscribe_chain_status() {
+        let mut rng = generators::rng();
+        let header1 = random_header(&mut rng, 10, None);
+        let header2 = random_header(&mut rng, 20, None);
+        let header3 = random_header(&mut rng, 30, None);
+
+        let tracker: ChainInfoTracker<EthPrimitives> =
+            ChainInfoTracker::new(header1.clone(), None, None);
+        let mut status = tracker.subscribe_chain_status();
+        assert_eq!(
+            *status.borrow_and_update(),
+            ChainStatus { head: header1.num_hash(), ..Default::default() }
+        );
+
+        // the changes of a forkchoice update are published together once the batch is dropped
+        let batch = tracker.batch_chain_status();
+        tracker.set_canonical_head(header3.clone());
+        tracker.set_safe(header2.clone());
+        assert!(!status.has_changed().unwrap());
+        tracker.set_finalized(header1.clone());
+        assert!(!status.has_changed().unwrap());
+        drop(batch);
+        assert!(status.has_changed().unwrap());
+        let expected = ChainStatus {
+            head: header3.num_hash(),
+            safe: Some(header2.num_hash()),
+            finalized: Some(header1.num_hash()),
+            unconfirmed: false,
+        };
+        assert_eq!(*status.borrow_and_update(), expected);
+        assert_eq!(tracker.chain_status(), expected);
+
+        // setting the same blocks again is not a change
+        tracker.set_canonical_head(header3.clone());
+        tracker.set_safe(header2.clone());
+        tracker.set_finalized(header1);
+        assert!(!status.has_changed().unwrap());
+
+        // advancing only the finalized block is a change
+        tracker.set_finalized(header2.clone());
+        assert!(status.has_changed().unwrap());
+        assert_eq!(
+            *status.borrow_and_update(),
+            ChainStatus { finalized: Some(header2.num_hash()), ..expected }
+        );
+    }
 }
@@ -2,13 +2,15 @@
 
 use crate::{
     CanonStateNotification, CanonStateNotificationSender, CanonStateNotifications,
-    ChainInfoTracker, MemoryOverlayStateProvider,
+    ChainInfoTracker, ChainReorg, ChainStatus, ChainStatusBatch, MemoryOverlayStateProvider,
+    OrphanedBlock, OrphanedBlocks, ReorgHistory,
 };
 use alloy_consensus::BlockHeader;
 use alloy_eips::{eip2718::Encodable2718, BlockHashOrNumber, BlockNumHash};
 use alloy_primitives::{map::HashMap, Address, TxHash, B256};
 use parking_lot::RwLock;
 use reth_chainspec::ChainInfo;
+use reth_db_models::StoredForkchoiceState;
 use reth_execution_types::{Chain, ExecutionOutcome};
 use reth_metrics::{metrics::Gauge, Metrics};
 use reth_primitives::{
@@ -18,7 +20,11 @@ use reth_primitives::{
 use reth_primitives_traits::{Block, BlockBody as _, SignedTransaction};
 use reth_storage_api::StateProviderBox;
 use reth_trie::{updates::TrieUpdates, HashedPostState};
-use std::{collections::BTreeMap, sync::Arc, time::Instant};
+use std::{
+    collections::BTreeMap,
+    sync::Arc,
+    time::{Instant, SystemTime, UNIX_EPOCH},
+};
 use tokio::sync::{broadcast, watch};
 
 /// Size of the broadcast channel used to notify canonical state events.
@@ -140,6 +146,10 @@ pub(crate) struct CanonicalInMemoryStateInner<N: NodePrimitives> {
     pub(crate) in_memory_state: InMemoryState<N>,
     /// A broadcast stream that emits events when the canonical chain is updated.
     pub(crate) canon_state_notification_sender: CanonStateNotificationSender<N>,
+    /// The most recent reorgs of the canonical chain.
+    pub(crate) reorg_history: ReorgHistory,
+    /// The most recent blocks that were reorged out or executed on a side chain.
+    pub(crate) orphaned_blocks: OrphanedBlocks<N::Block, N::Receipt>,
 }
 
 impl<N: NodePrimitives> CanonicalInMemoryStateInner<N> {
@@ -193,6 +203,8 @@ impl<N: NodePrimitives> CanonicalInMemoryState<N> {
                 chain_info_tracker,
                 in_memory_state,
                 canon_state_notification_sender,
+                reorg_history: ReorgHistory::default(),
+                orphaned_blocks: OrphanedBlocks::default(),
             }),
         }
     }
@@ -217,6 +229,8 @@ impl<N: NodePrimitives> CanonicalInMemoryState<N> {
             chain_info_tracker,
             in_memory_state,
             canon_state_notification_sender,
+            reorg_history: ReorgHistory::default(),
+            orphaned_blocks: OrphanedBlocks::default(),
         };
 
         Self { inner: Arc::new(inner) }
@@ -298,6 +312,7 @@ impl<N: NodePrimitives> CanonicalInMemoryState<N> {
                 self.update_blocks(new, vec![]);
             }
             NewCanonicalChain::Reorg { new, old } => {
+                self.inner.orphaned_blocks.record(old.iter().map(Into::into).collect());
                 self.update_blocks(new, old);
             }
         }
@@ -420,6 +435,22 @@ impl<N: NodePrimitives> CanonicalInMemoryState<N> {
         self.inner.chain_info_tracker.last_forkchoice_update_received_at()
     }
 
+    /// Sets the forkchoice state that was restored from disk on startup.
+    pub fn set_restored_forkchoice_state(&self, state: StoredForkchoiceState) {
+        self.inner.chain_info_tracker.set_restored_forkchoice_state(state);
+    }
+
+    /// Returns the forkchoice state that was restored from disk on startup, if no valid
+    /// forkchoice update has been received since.
+    pub fn restored_forkchoice_state(&self) -> Option<StoredForkchoiceState> {
+        self.inner.chain_info_tracker.restored_forkchoice_state()
+    }
+
+    /// Hook for a valid forkchoice update, which supersedes the restored forkchoice state.
+    pub fn on_forkchoice_state_confirmed(&self) {
+        self.inner.chain_info_tracker.on_forkchoice_state_confirmed();
+    }
+
     /// Hook for transition configuration exchanged.
     pub fn on_transition_configuration_exchanged(&self) {
         self.inner.chain_info_tracker.on_transition_configuration_exchanged();
@@ -492,6 +523,27 @@ impl<N: NodePrimitives> CanonicalInMemoryState<N> {
         })
     }
 
+    /// Returns the history of the most recent reorgs of the canonical chain.
+    pub fn reorg_history(&self) -> &ReorgHistory {
+        &self.inner.reorg_history
+    }
+
+    /// Returns the most recent blocks that are not part of the canonical chain, either because
+    /// they were reorged out or because they were executed on a side chain.
+    pub fn orphaned_blocks(&self) -> &OrphanedBlocks<N::Block, N::Receipt> {
+        &self.inner.orphaned_blocks
+    }
+
+    /// Records a block that was executed on a side chain, so that it can still be looked up after
+    /// it was removed from the tree.
+    ///
+    /// Blocks that are already recorded are skipped.
+    pub fn record_side_chain_block(&self, block: &ExecutedBlock<N>) {
+        if !self.inner.orphaned_blocks.contains(block.block().hash()) {
+            self.inner.orphaned_blocks.record(vec![block.into()]);
+        }
+    }
+
     /// Subscribe to new blocks events.
     pub fn subscribe_canon_state(&self) -> CanonStateNotifications<N> {
         self.inner.canon_state_notification_sender.subscribe()
@@ -509,6 +561,28 @@ impl<N: NodePrimitives> CanonicalInMemoryState<N> {
         self.inner.chain_info_tracker.subscribe_finalized_block()
     }
 
+    /// Holds back changes of the [`ChainStatus`] until the returned batch is dropped, so that the
+    /// canonical head, safe and finalized block of a forkchoice update are published together.
+    pub fn batch_chain_status(&self) -> ChainStatusBatch<N> {
+        self.inner.chain_info_tracker.batch_chain_status()
+    }
+
+    /// Subscribe to changes of the canonical head, safe and finalized block.
+    pub fn subscribe_chain_status(&self) -> watch::Receiver<ChainStatus> {
+        self.inner.chain_info_tracker.subscribe_chain_status()
+    }
+
+    /// Sets the block the node syncs to, the head block of the latest forkchoice update.
+    pub fn set_sync_target(&self, target: BlockNumHash) {
+        self.inner.chain_info_tracker.set_sync_target(target);
+    }
+
+    /// Subscribe to the block the node syncs to, the head block of the latest forkchoice update
+    /// whose number is known.
+    pub fn subscribe_sync_target(&self) -> watch::Receiver<Option<BlockNumHash>> {
+        self.inner.chain_info_tracker.subscribe_sync_target()
+    }
+
     /// Attempts to send a new [`CanonStateNotification`] to all active Receiver handles.
     pub fn notify_canon_state(&self, event: CanonStateNotification<N>) {
         self.inner.canon_state_notification_sender.send(event).ok();
@@ -859,6 +933,23 @@ impl<N: NodePrimitives> ExecutedBlock<N> {
     }
 }
 
+impl<N: NodePrimitives> From<&ExecutedBlock<N>> for OrphanedBlock<N::Block, N::Receipt> {
+    fn from(block: &ExecutedBlock<N>) -> Self {
+        Self {
+            block: block.block.clone(),
+            receipts: Arc::new(
+                block
+                    .execution_outcome()
+                    .receipts_by_block(block.block().number())
+                    .iter()
+                    .flatten()
+                    .cloned()
+                    .collect(),
+            ),
+        }
+    }
+}
+
 /// Non-empty chain of blocks.
 #[derive(Debug)]
 pub enum NewCanonicalChain<N: NodePrimitives = EthPrimitives> {
@@ -893,6 +984,24 @@ impl<N: NodePrimitives<SignedTx: SignedTransaction>> NewCanonicalChain<N> {
         }
     }
 
+    /// Returns the [`ChainReorg`] this update applies, stamped with the current time, or
+    /// [`None`] if it only appends to the canonical chain.
+    pub fn reorg(&self) -> Option<ChainReorg> {
+        let Self::Reorg { new, old } = self else { return None };
+        let new_first = new.first()?.block();
+        let common_ancestor =
+            BlockNumHash::new(new_first.number().saturating_sub(1), new_first.parent_hash());
+        let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+        Some(ChainReorg {
+            old_tip: old.last().map_or(common_ancestor, |tip| tip.block().num_hash()),
+            old_chain_length: old.len() as u64,
+            new_tip: self.tip().num_hash(),
+            new_chain_length: new.len() as u64,
+            common_ancestor,
+            timestamp,
+        })
+    }
+
     /// Converts the new chain into a notification that will be emitted to listeners
     pub fn to_chain_notification(&self) -> CanonStateNotification<N> {
         match self {
@@ -947,7 +1056,7 @@ mod tests {
     use alloy_primitives::{map::B256HashMap, BlockNumber, Bytes, StorageKey, StorageValue};
     use rand::Rng;
     use reth_errors::ProviderResult;
-    use reth_primitives::{Account, Bytecode, EthPrimitives, Receipt};
+    use reth_primitives::{Account, Bytecode, EthPrimitives, Receipt, StorageEntry};
     use reth_storage_api::{
         AccountReader, BlockHashReader, HashedPostStateProvider, StateProofProvider, StateProvider,
         StateRootProvider, StorageRootProvider,
@@ -1080,6 +1189,16 @@ mod tests {
         ) -> ProviderResult<StorageMultiProof> {
             Ok(StorageMultiProof::empty())
         }
+
+        fn storage_range(
+            &self,
+            _address: Address,
+            _range: std::ops::RangeInclusive<B256>,
+            _limit: usize,
+            _hashed_storage: HashedStorage,
+        ) -> ProviderResult<Vec<StorageEntry>> {
+            Ok(Vec::new())
+        }
     }
 
     impl StateProofProvider for MockStateProvider {
@@ -1281,6 +1400,27 @@ mod tests {
         assert_eq!(state.inner.in_memory_state.block_count(), 1);
     }
 
+    #[test]
+    fn test_in_memory_state_reorg_keeps_orphaned_blocks() {
+        let state: CanonicalInMemoryState = CanonicalInMemoryState::empty();
+        let mut test_block_builder: TestBlockBuilder = TestBlockBuilder::default();
+        let receipts = Receipts { receipt_vec: vec![vec![Some(Receipt::default())]] };
+        let block1 =
+            test_block_builder.get_executed_block_with_receipts(receipts.clone(), B256::random());
+        let block2 = test_block_builder.get_executed_block_with_number(0, B256::random());
+        state.update_chain(NewCanonicalChain::Commit { new: vec![block1.clone()] });
+        assert_eq!(state.orphaned_blocks().block_by_hash(block1.block().hash()), None);
+
+        state.update_chain(NewCanonicalChain::Reorg {
+            new: vec![block2.clone()],
+            old: vec![block1.clone()],
+        });
+        let orphaned = state.orphaned_blocks().block_by_hash(block1.block().hash()).unwrap();
+        assert_eq!(orphaned.block, block1.block);
+        assert_eq!(*orphaned.receipts, vec![Receipt::default()]);
+        assert_eq!(state.orphaned_blocks().block_by_hash(block2.block().hash()), None);
+    }
+
     #[test]
     fn test_in_memory_state_set_pending_block() {
         let state: CanonicalInMemoryState = CanonicalInMemoryState::empty();
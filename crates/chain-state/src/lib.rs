@@ -14,7 +14,13 @@ pub use in_memory::*;
 mod noop;
 
 mod chain_info;
-pub use chain_info::ChainInfoTracker;
+pub use chain_info::{ChainInfoTracker, ChainStatus, ChainStatusBatch};
+
+mod reorg;
+pub use reorg::{
+    ChainReorg, OrphanedBlock, OrphanedBlocks, OrphanedBlocksFor, ReorgHistory,
+    DEFAULT_REORG_HISTORY_SIZE,
+};
 
 mod notifications;
 pub use notifications::{
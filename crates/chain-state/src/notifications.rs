@@ -1,6 +1,7 @@
 //! Canonical chain state notification trait and types.
 
-use alloy_eips::eip2718::Encodable2718;
+use crate::{ChainStatus, OrphanedBlocksFor, ReorgHistory};
+use alloy_eips::{eip2718::Encodable2718, BlockNumHash};
 use derive_more::{Deref, DerefMut};
 use reth_execution_types::{BlockReceipts, Chain};
 use reth_primitives::{NodePrimitives, SealedBlockWithSenders, SealedHeader};
@@ -38,6 +39,29 @@ pub trait CanonStateSubscriptions: NodePrimitivesProvider + Send + Sync {
             st: BroadcastStream::new(self.subscribe_to_canonical_state()),
         }
     }
+
+    /// Returns the history of the most recent canonical chain reorgs, if it is tracked.
+    fn reorg_history(&self) -> Option<ReorgHistory> {
+        None
+    }
+
+    /// Returns the most recent blocks that were reorged out of the canonical chain or executed on
+    /// a side chain, if they are tracked.
+    fn orphaned_blocks(&self) -> Option<OrphanedBlocksFor<Self::Primitives>> {
+        None
+    }
+
+    /// Get notified when the canonical head, safe or finalized block changes, if they are
+    /// tracked.
+    fn subscribe_chain_status(&self) -> Option<watch::Receiver<ChainStatus>> {
+        None
+    }
+
+    /// Get notified when the block the node syncs to changes, the head block of the latest
+    /// forkchoice update, if it is tracked.
+    fn subscribe_sync_target(&self) -> Option<watch::Receiver<Option<BlockNumHash>>> {
+        None
+    }
 }
 
 impl<T: CanonStateSubscriptions> CanonStateSubscriptions for &T {
@@ -48,6 +72,22 @@ impl<T: CanonStateSubscriptions> CanonStateSubscriptions for &T {
     fn canonical_state_stream(&self) -> CanonStateNotificationStream<Self::Primitives> {
         (*self).canonical_state_stream()
     }
+
+    fn reorg_history(&self) -> Option<ReorgHistory> {
+        (*self).reorg_history()
+    }
+
+    fn orphaned_blocks(&self) -> Option<OrphanedBlocksFor<Self::Primitives>> {
+        (*self).orphaned_blocks()
+    }
+
+    fn subscribe_chain_status(&self) -> Option<watch::Receiver<ChainStatus>> {
+        (*self).subscribe_chain_status()
+    }
+
+    fn subscribe_sync_target(&self) -> Option<watch::Receiver<Option<BlockNumHash>>> {
+        (*self).subscribe_sync_target()
+    }
 }
 
 /// A Stream of [`CanonStateNotification`].
@@ -0,0 +1,193 @@
+//! Tracking of canonical chain reorgs.
+
+use alloy_eips::BlockNumHash;
+use alloy_primitives::B256;
+use parking_lot::RwLock;
+use reth_primitives::{NodePrimitives, SealedBlockFor};
+use reth_primitives_traits::Block;
+use std::{collections::VecDeque, sync::Arc};
+
+/// Default number of reorgs kept in the [`ReorgHistory`].
+pub const DEFAULT_REORG_HISTORY_SIZE: usize = 64;
+
+/// A reorg of the canonical chain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChainReorg {
+    /// The tip of the chain that was reorged out.
+    pub old_tip: BlockNumHash,
+    /// The number of blocks that were reorged out.
+    pub old_chain_length: u64,
+    /// The tip of the new canonical chain.
+    pub new_tip: BlockNumHash,
+    /// The number of blocks of the new canonical chain above the common ancestor.
+    pub new_chain_length: u64,
+    /// The last block both chains have in common.
+    pub common_ancestor: BlockNumHash,
+    /// Unix timestamp in seconds at which the reorg was applied.
+    pub timestamp: u64,
+}
+
+impl ChainReorg {
+    /// Returns the depth of the reorg, which is the number of blocks that were reorged out.
+    pub const fn depth(&self) -> u64 {
+        self.old_chain_length
+    }
+}
+
+/// A bounded history of the most recent canonical chain reorgs.
+///
+/// This is a cheaply cloneable handle, all clones share the same history. Once the history is
+/// full, recording a reorg evicts the oldest one.
+#[derive(Debug, Clone)]
+pub struct ReorgHistory {
+    reorgs: Arc<RwLock<VecDeque<ChainReorg>>>,
+    capacity: usize,
+}
+
+impl ReorgHistory {
+    /// Creates an empty history that keeps at most `capacity` reorgs.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self { reorgs: Arc::new(RwLock::new(VecDeque::with_capacity(capacity))), capacity }
+    }
+
+    /// Records a reorg, evicting the oldest one if the history is full.
+    pub fn record(&self, reorg: ChainReorg) {
+        if self.capacity == 0 {
+            return
+        }
+        let mut reorgs = self.reorgs.write();
+        if reorgs.len() == self.capacity {
+            reorgs.pop_front();
+        }
+        reorgs.push_back(reorg);
+    }
+
+    /// Returns up to `count` of the most recent reorgs, most recent first.
+    pub fn latest(&self, count: usize) -> Vec<ChainReorg> {
+        self.reorgs.read().iter().rev().take(count).copied().collect()
+    }
+
+    /// Returns the number of recorded reorgs.
+    pub fn len(&self) -> usize {
+        self.reorgs.read().len()
+    }
+
+    /// Returns `true` if no reorg was recorded.
+    pub fn is_empty(&self) -> bool {
+        self.reorgs.read().is_empty()
+    }
+}
+
+impl Default for ReorgHistory {
+    fn default() -> Self {
+        Self::with_capacity(DEFAULT_REORG_HISTORY_SIZE)
+    }
+}
+
+/// Type alias for the [`OrphanedBlocks`] of the given [`NodePrimitives`].
+pub type OrphanedBlocksFor<N> =
+    OrphanedBlocks<<N as NodePrimitives>::Block, <N as NodePrimitives>::Receipt>;
+
+/// A block that is not part of the canonical chain, with the receipts of its transactions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OrphanedBlock<B: Block, R> {
+    /// The block that is no longer part of the canonical chain.
+    pub block: Arc<SealedBlockFor<B>>,
+    /// The receipts of the block's transactions.
+    pub receipts: Arc<Vec<R>>,
+}
+
+/// The most recent blocks that are not part of the canonical chain, either because they were
+/// reorged out or because they were executed on a side chain.
+///
+/// This is a cheaply cloneable handle, all clones share the same blocks. The blocks are kept in
+/// the groups they were recorded in, e.g. the blocks reorged out by a single reorg. Once the store
+/// is full, recording a group evicts the blocks of the oldest one.
+#[derive(Debug, Clone)]
+pub struct OrphanedBlocks<B: Block, R> {
+    groups: Arc<RwLock<VecDeque<Vec<OrphanedBlock<B, R>>>>>,
+    capacity: usize,
+}
+
+impl<B: Block, R: Clone> OrphanedBlocks<B, R> {
+    /// Creates an empty store that keeps the blocks of at most `capacity` groups.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self { groups: Arc::new(RwLock::new(VecDeque::with_capacity(capacity))), capacity }
+    }
+
+    /// Records a group of blocks, e.g. the blocks reorged out by a single reorg, evicting the
+    /// blocks of the oldest group if the store is full.
+    pub fn record(&self, blocks: Vec<OrphanedBlock<B, R>>) {
+        if self.capacity == 0 || blocks.is_empty() {
+            return
+        }
+        let mut groups = self.groups.write();
+        if groups.len() == self.capacity {
+            groups.pop_front();
+        }
+        groups.push_back(blocks);
+    }
+
+    /// Returns the orphaned block with the given hash, if it was recorded recently.
+    pub fn block_by_hash(&self, hash: B256) -> Option<OrphanedBlock<B, R>> {
+        self.groups.read().iter().rev().flatten().find(|block| block.block.hash() == hash).cloned()
+    }
+
+    /// Returns `true` if the block with the given hash was recorded recently.
+    pub fn contains(&self, hash: B256) -> bool {
+        self.groups.read().iter().flatten().any(|block| block.block.hash() == hash)
+    }
+}
+
+impl<B: Block, R: Clone> Default for OrphanedBlocks<B, R> {
+    fn default() -> Self {
+        Self::with_capacity(DEFAULT_REORG_HISTORY_SIZE)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::{Header, SealedBlock, SealedHeader};
+
+    fn reorg(number: u64) -> ChainReorg {
+        ChainReorg {
+            old_tip: BlockNumHash::new(number, B256::with_last_byte(1)),
+            old_chain_length: 1,
+            new_tip: BlockNumHash::new(number, B256::with_last_byte(2)),
+            new_chain_length: 1,
+            common_ancestor: BlockNumHash::new(number - 1, B256::ZERO),
+            timestamp: 0,
+        }
+    }
+
+    #[test]
+    fn evicts_oldest_reorg() {
+        let history = ReorgHistory::with_capacity(2);
+        for number in 1..=3 {
+            history.record(reorg(number));
+        }
+
+        assert_eq!(history.len(), 2);
+        assert_eq!(history.latest(usize::MAX), vec![reorg(3), reorg(2)]);
+        assert_eq!(history.latest(1), vec![reorg(3)]);
+    }
+
+    #[test]
+    fn evicts_blocks_of_oldest_reorg() {
+        let orphaned = OrphanedBlocks::<reth_primitives::Block, ()>::with_capacity(2);
+        let blocks = (1..=3)
+            .map(|byte| {
+                let header = SealedHeader::new(Header::default(), B256::with_last_byte(byte));
+                let block = SealedBlock::new(header, Default::default());
+                OrphanedBlock { block: Arc::new(block), receipts: Arc::new(Vec::new()) }
+            })
+            .collect::<Vec<_>>();
+        for block in &blocks {
+            orphaned.record(vec![block.clone()]);
+        }
+
+        assert_eq!(orphaned.block_by_hash(blocks[0].block.hash()), None);
+        assert_eq!(orphaned.block_by_hash(blocks[2].block.hash()), Some(blocks[2].clone()));
+    }
+}
@@ -322,6 +322,11 @@ impl TestCanonStateSubscriptions {
         let event = CanonStateNotification::Reorg { old, new };
         self.canon_notif_tx.lock().as_mut().unwrap().retain(|tx| tx.send(event.clone()).is_ok())
     }
+
+    /// Returns the number of subscriptions whose receiver is still alive.
+    pub fn subscriber_count(&self) -> usize {
+        self.canon_notif_tx.lock().unwrap().iter().filter(|tx| tx.receiver_count() > 0).count()
+    }
 }
 
 impl NodePrimitivesProvider for TestCanonStateSubscriptions {
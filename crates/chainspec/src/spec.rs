@@ -1,7 +1,7 @@
 pub use alloy_eips::eip1559::BaseFeeParams;
 
 use crate::{constants::MAINNET_DEPOSIT_CONTRACT, once_cell_set, EthChainSpec, LazyLock, OnceLock};
-use alloc::{boxed::Box, sync::Arc, vec::Vec};
+use alloc::{boxed::Box, collections::BTreeMap, sync::Arc, vec::Vec};
 use alloy_chains::{Chain, NamedChain};
 use alloy_consensus::{
     constants::{
@@ -14,8 +14,8 @@ use alloy_eips::{
     eip1559::INITIAL_BASE_FEE, eip6110::MAINNET_DEPOSIT_CONTRACT_ADDRESS,
     eip7685::EMPTY_REQUESTS_HASH,
 };
-use alloy_genesis::Genesis;
-use alloy_primitives::{address, b256, Address, BlockNumber, B256, U256};
+use alloy_genesis::{Genesis, GenesisAccount};
+use alloy_primitives::{address, b256, Address, BlockNumber, Bytes, B256, U256};
 use derive_more::From;
 use reth_ethereum_forks::{
     ChainHardforks, DisplayHardforks, EthereumHardfork, EthereumHardforks, ForkCondition,
@@ -731,12 +731,35 @@ pub trait ChainSpecProvider: Send + Sync {
     fn chain_spec(&self) -> Arc<Self::ChainSpec>;
 }
 
+/// Ethereum hardforks that change the execution rules, in the order they activate.
+///
+/// The DAO fork and the difficulty bomb delays are left out, since custom chains commonly skip them
+/// or keep their mainnet activation.
+const ORDERED_ETHEREUM_HARDFORKS: [EthereumHardfork; 15] = [
+    EthereumHardfork::Frontier,
+    EthereumHardfork::Homestead,
+    EthereumHardfork::Tangerine,
+    EthereumHardfork::SpuriousDragon,
+    EthereumHardfork::Byzantium,
+    EthereumHardfork::Constantinople,
+    EthereumHardfork::Petersburg,
+    EthereumHardfork::Istanbul,
+    EthereumHardfork::Berlin,
+    EthereumHardfork::London,
+    EthereumHardfork::Paris,
+    EthereumHardfork::Shanghai,
+    EthereumHardfork::Cancun,
+    EthereumHardfork::Prague,
+    EthereumHardfork::Osaka,
+];
+
 /// A helper to build custom chain specs
 #[derive(Debug, Default, Clone)]
 pub struct ChainSpecBuilder {
     chain: Option<Chain>,
     genesis: Option<Genesis>,
     hardforks: ChainHardforks,
+    base_fee_params: BaseFeeParamsKind,
 }
 
 impl ChainSpecBuilder {
@@ -746,6 +769,7 @@ impl ChainSpecBuilder {
             chain: Some(MAINNET.chain),
             genesis: Some(MAINNET.genesis.clone()),
             hardforks: MAINNET.hardforks.clone(),
+            base_fee_params: MAINNET.base_fee_params.clone(),
         }
     }
 }
@@ -763,6 +787,34 @@ impl ChainSpecBuilder {
         self
     }
 
+    /// Add an account with the given balance, code and storage to the genesis allocation,
+    /// replacing any existing account at the same address.
+    ///
+    /// This has to be called after [`Self::genesis`], which replaces the whole genesis.
+    pub fn with_genesis_account(
+        mut self,
+        address: Address,
+        balance: U256,
+        code: Option<Bytes>,
+        storage: impl IntoIterator<Item = (B256, B256)>,
+    ) -> Self {
+        let storage = storage.into_iter().collect::<BTreeMap<_, _>>();
+        let account = GenesisAccount {
+            balance,
+            code,
+            storage: (!storage.is_empty()).then_some(storage),
+            ..Default::default()
+        };
+        self.genesis.get_or_insert_with(Default::default).alloc.insert(address, account);
+        self
+    }
+
+    /// Set the EIP-1559 base fee parameters.
+    pub fn with_base_fee_params(mut self, base_fee_params: impl Into<BaseFeeParamsKind>) -> Self {
+        self.base_fee_params = base_fee_params.into();
+        self
+    }
+
     /// Add the given fork with the given activation condition to the spec.
     pub fn with_fork<H: Hardfork>(mut self, fork: H, condition: ForkCondition) -> Self {
         self.hardforks.insert(fork, condition);
@@ -907,8 +959,11 @@ impl ChainSpecBuilder {
     /// # Panics
     ///
     /// This function panics if the chain ID and genesis is not set ([`Self::chain`] and
-    /// [`Self::genesis`])
+    /// [`Self::genesis`]), or if an Ethereum hardfork activates before a hardfork that precedes
+    /// it.
     pub fn build(self) -> ChainSpec {
+        self.assert_fork_order();
+
         let paris_block_and_final_difficulty = {
             self.hardforks.get(EthereumHardfork::Paris).and_then(|cond| {
                 if let ForkCondition::TTD { total_difficulty, activation_block_number, .. } = cond {
@@ -925,9 +980,46 @@ impl ChainSpecBuilder {
             hardforks: self.hardforks,
             paris_block_and_final_difficulty,
             deposit_contract: None,
+            base_fee_params: self.base_fee_params,
             ..Default::default()
         }
     }
+
+    /// Asserts that every configured hardfork of [`ORDERED_ETHEREUM_HARDFORKS`] activates no
+    /// earlier than the hardforks preceding it.
+    ///
+    /// Block based hardforks, including Paris if it has a merge netsplit block, and timestamp
+    /// based hardforks are checked separately. Hardforks following a hardfork that never activates
+    /// must never activate either.
+    fn assert_fork_order(&self) {
+        let mut last_block: Option<(EthereumHardfork, u64)> = None;
+        let mut last_timestamp: Option<(EthereumHardfork, u64)> = None;
+        let mut never: Option<EthereumHardfork> = None;
+        for fork in ORDERED_ETHEREUM_HARDFORKS {
+            let Some(condition) = self.hardforks.get(fork) else { continue };
+            if condition == ForkCondition::Never {
+                never.get_or_insert(fork);
+                continue
+            }
+            if let Some(previous) = never {
+                panic!("{fork} activates, but {previous} never activates");
+            }
+
+            let (last, activation) = match condition {
+                ForkCondition::Block(block) |
+                ForkCondition::TTD { fork_block: Some(block), .. } => (&mut last_block, block),
+                ForkCondition::Timestamp(timestamp) => (&mut last_timestamp, timestamp),
+                _ => continue,
+            };
+            if let Some((previous, previous_activation)) = *last {
+                assert!(
+                    previous_activation <= activation,
+                    "{fork} activates at {activation}, before {previous} at {previous_activation}"
+                );
+            }
+            *last = Some((fork, activation));
+        }
+    }
 }
 
 impl From<&Arc<ChainSpec>> for ChainSpecBuilder {
@@ -936,6 +1028,7 @@ impl From<&Arc<ChainSpec>> for ChainSpecBuilder {
             chain: Some(value.chain),
             genesis: Some(value.genesis.clone()),
             hardforks: value.hardforks.clone(),
+            base_fee_params: value.base_fee_params.clone(),
         }
     }
 }
@@ -2185,6 +2278,21 @@ Post-merge hard forks (timestamp based):
         assert_eq!(ForkHash::from(genesis_hash), expected_forkhash);
     }
 
+    #[test]
+    fn has_ommers_before_the_merge() {
+        let paris_block = MAINNET.paris_block().unwrap();
+        assert!(MAINNET.has_ommers(paris_block - 1));
+        assert!(!MAINNET.has_ommers(paris_block));
+        assert!(!HOLESKY.has_ommers(0));
+
+        // the merge block isn't known
+        let spec = ChainSpecBuilder::mainnet().paris_at_ttd(U256::from(1000), 0).build();
+        assert!(spec.has_ommers(1_000_000));
+        let spec = ChainSpecBuilder::mainnet().paris_at_ttd(U256::from(1000), 100).build();
+        assert!(spec.has_ommers(99));
+        assert!(!spec.has_ommers(100));
+    }
+
     #[test]
     fn holesky_paris_activated_at_genesis() {
         assert!(HOLESKY
@@ -2238,40 +2346,179 @@ Post-merge hard forks (timestamp based):
 
     #[test]
     fn check_fork_id_chainspec_with_fork_condition_never() {
-        let spec = ChainSpec {
-            chain: Chain::mainnet(),
-            genesis: Genesis::default(),
-            genesis_hash: OnceLock::new(),
-            hardforks: ChainHardforks::new(vec![(
-                EthereumHardfork::Frontier.boxed(),
-                ForkCondition::Never,
-            )]),
-            paris_block_and_final_difficulty: None,
-            deposit_contract: None,
-            ..Default::default()
-        };
+        let spec = ChainSpec::builder()
+            .chain(Chain::mainnet())
+            .genesis(Genesis::default())
+            .with_fork(EthereumHardfork::Frontier, ForkCondition::Never)
+            .build();
 
         assert_eq!(spec.hardfork_fork_id(EthereumHardfork::Frontier), None);
     }
 
     #[test]
     fn check_fork_filter_chainspec_with_fork_condition_never() {
-        let spec = ChainSpec {
-            chain: Chain::mainnet(),
-            genesis: Genesis::default(),
-            genesis_hash: OnceLock::new(),
-            hardforks: ChainHardforks::new(vec![(
-                EthereumHardfork::Shanghai.boxed(),
-                ForkCondition::Never,
-            )]),
-            paris_block_and_final_difficulty: None,
-            deposit_contract: None,
-            ..Default::default()
-        };
+        let spec = ChainSpec::builder()
+            .chain(Chain::mainnet())
+            .genesis(Genesis::default())
+            .with_fork(EthereumHardfork::Shanghai, ForkCondition::Never)
+            .build();
 
         assert_eq!(spec.hardfork_fork_filter(EthereumHardfork::Shanghai), None);
     }
 
+    #[test]
+    fn next_fork_with_future_timestamp_fork() {
+        let spec = ChainSpec::builder()
+            .chain(Chain::mainnet())
+            .genesis(Genesis::default())
+            .cancun_activated()
+            .with_fork(EthereumHardfork::Prague, ForkCondition::Timestamp(1_000))
+            .with_fork(EthereumHardfork::Osaka, ForkCondition::Never)
+            .build();
+
+        let head = Head { number: 10, timestamp: 500, ..Default::default() };
+        let (fork, condition) = spec.next_fork(&head).unwrap();
+        assert_eq!(fork.name(), EthereumHardfork::Prague.name());
+        assert_eq!(condition, ForkCondition::Timestamp(1_000));
+
+        let current = spec.fork_id(&head);
+        assert_eq!(current.next, 1_000);
+        let next = spec.next_fork_id(&head).unwrap();
+        assert_ne!(next.hash, current.hash);
+        assert_eq!(next, spec.hardfork_fork_id(EthereumHardfork::Prague).unwrap());
+
+        // Osaka is never activated, so there is nothing upcoming once Prague is active.
+        let head = Head { timestamp: 1_000, ..head };
+        assert!(spec.next_fork(&head).is_none());
+        assert!(spec.next_fork_id(&head).is_none());
+    }
+
+    #[test]
+    fn builder_matches_genesis_json() {
+        let json = r#"
+{
+  "config": {
+    "chainId": 1337,
+    "homesteadBlock": 0,
+    "eip150Block": 0,
+    "eip155Block": 0,
+    "eip158Block": 0,
+    "byzantiumBlock": 0,
+    "constantinopleBlock": 0,
+    "petersburgBlock": 0,
+    "istanbulBlock": 0,
+    "berlinBlock": 0,
+    "londonBlock": 0,
+    "terminalTotalDifficulty": 0,
+    "terminalTotalDifficultyPassed": true,
+    "shanghaiTime": 0,
+    "cancunTime": 0,
+    "pragueTime": 1000
+  },
+  "nonce": "0x0",
+  "timestamp": "0x0",
+  "gasLimit": "0x1c9c380",
+  "difficulty": "0x0",
+  "alloc": {
+    "0x6be02d1d3665660d22ff9624b7be0551ee1ac91b": {
+      "balance": "0x3635c9adc5dea00000"
+    },
+    "0x00000000000000000000000000000000000000aa": {
+      "balance": "0x1",
+      "code": "0x60016000526001601ff3",
+      "storage": {
+        "0x0000000000000000000000000000000000000000000000000000000000000001": "0x000000000000000000000000000000000000000000000000000000000000002a"
+      }
+    }
+  }
+}
+"#;
+        let genesis: Genesis = serde_json::from_str(json).unwrap();
+        let json_spec = ChainSpec::from(genesis.clone());
+
+        let spec = ChainSpec::builder()
+            .chain(Chain::from_id(1337))
+            .genesis(Genesis { alloc: Default::default(), ..genesis })
+            .with_genesis_account(
+                address!("6be02d1d3665660d22ff9624b7be0551ee1ac91b"),
+                U256::from(1_000_000_000_000_000_000_000_u128),
+                None,
+                [],
+            )
+            .with_genesis_account(
+                Address::with_last_byte(0xaa),
+                U256::from(1),
+                Some(hex!("60016000526001601ff3").into()),
+                [(B256::with_last_byte(1), B256::with_last_byte(42))],
+            )
+            .cancun_activated()
+            .with_fork(EthereumHardfork::Prague, ForkCondition::Timestamp(1000))
+            .build();
+
+        assert_eq!(spec.chain, json_spec.chain);
+        assert_eq!(spec.genesis.alloc, json_spec.genesis.alloc);
+        assert_eq!(spec.genesis_header(), json_spec.genesis_header());
+        assert_eq!(spec.genesis_hash(), json_spec.genesis_hash());
+        assert_eq!(spec.base_fee_params, json_spec.base_fee_params);
+        // Frontier is implied by genesis json
+        for fork in ORDERED_ETHEREUM_HARDFORKS.into_iter().skip(1) {
+            assert_eq!(spec.fork(fork), json_spec.fork(fork), "{fork}");
+        }
+        for timestamp in [0, 999, 1000] {
+            let head = Head { timestamp, ..Default::default() };
+            assert_eq!(spec.fork_id(&head), json_spec.fork_id(&head));
+        }
+        assert_eq!(spec.latest_fork_id(), json_spec.latest_fork_id());
+    }
+
+    #[test]
+    fn builder_with_base_fee_params() {
+        let params = BaseFeeParams::new(16, 4);
+        let spec = ChainSpec::builder()
+            .chain(Chain::from_id(1337))
+            .genesis(Genesis::default())
+            .london_activated()
+            .with_base_fee_params(params)
+            .build();
+
+        assert_eq!(spec.base_fee_params_at_block(0), params);
+        assert_eq!(spec.base_fee_params_at_timestamp(0), params);
+    }
+
+    #[test]
+    #[should_panic(expected = "London activates at 0, before Berlin at 10")]
+    fn builder_rejects_block_fork_before_predecessor() {
+        ChainSpec::builder()
+            .chain(Chain::from_id(1337))
+            .genesis(Genesis::default())
+            .london_activated()
+            .with_fork(EthereumHardfork::Berlin, ForkCondition::Block(10))
+            .build();
+    }
+
+    #[test]
+    #[should_panic(expected = "Cancun activates at 0, before Shanghai at 1000")]
+    fn builder_rejects_timestamp_fork_before_predecessor() {
+        ChainSpec::builder()
+            .chain(Chain::from_id(1337))
+            .genesis(Genesis::default())
+            .cancun_activated()
+            .with_fork(EthereumHardfork::Shanghai, ForkCondition::Timestamp(1000))
+            .build();
+    }
+
+    #[test]
+    #[should_panic(expected = "Prague activates, but Cancun never activates")]
+    fn builder_rejects_fork_after_never_activated_fork() {
+        ChainSpec::builder()
+            .chain(Chain::from_id(1337))
+            .genesis(Genesis::default())
+            .shanghai_activated()
+            .with_fork(EthereumHardfork::Cancun, ForkCondition::Never)
+            .with_fork(EthereumHardfork::Prague, ForkCondition::Timestamp(0))
+            .build();
+    }
+
     #[test]
     fn latest_eth_mainnet_fork_id() {
         assert_eq!(
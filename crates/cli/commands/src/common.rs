@@ -16,8 +16,8 @@ use reth_node_core::{
     dirs::{ChainPath, DataDirPath},
 };
 use reth_provider::{
-    providers::{NodeTypesForProvider, StaticFileProvider},
-    ProviderFactory, StaticFileProviderFactory,
+    providers::{ConsistencyCheck, NodeTypesForProvider, StaticFileProvider},
+    ProviderFactory,
 };
 use reth_stages::{sets::DefaultStages, Pipeline, PipelineTarget};
 use reth_static_file::StaticFileProducer;
@@ -83,16 +83,24 @@ impl<C: ChainSpecParser> EnvironmentArgs<C> {
         }
 
         info!(target: "reth::cli", ?db_path, ?sf_path, "Opening storage");
-        let (db, sfp) = match access {
+        let (db, mut sfp) = match access {
             AccessRights::RW => (
                 Arc::new(init_db(db_path, self.db.database_args())?),
                 StaticFileProvider::read_write(sf_path)?,
             ),
-            AccessRights::RO => (
+            AccessRights::RO | AccessRights::RoInotify => (
                 Arc::new(open_db_read_only(&db_path, self.db.database_args())?),
                 StaticFileProvider::read_only(sf_path, false)?,
             ),
         };
+        if let Some(max_open_files) = self.datadir.max_open_static_files() {
+            sfp = sfp.with_max_open_files(max_open_files);
+        }
+        // the watcher holds a clone of the provider, so it's only installed once the provider is
+        // configured
+        if access.is_read_only_inotify() {
+            sfp.watch_directory();
+        }
 
         let provider_factory = self.create_provider_factory(&config, db, sfp)?;
         if access.is_read_write() {
@@ -127,16 +135,17 @@ impl<C: ChainSpecParser> EnvironmentArgs<C> {
         )
         .with_prune_modes(prune_modes.clone());
 
-        // Check for consistency between database and static files.
-        if let Some(unwind_target) = factory
-            .static_file_provider()
-            .check_consistency(&factory.provider()?, has_receipt_pruning)?
-        {
-            if factory.db_ref().is_read_only()? {
-                warn!(target: "reth::cli", ?unwind_target, "Inconsistent storage. Restart node to heal.");
-                return Ok(factory)
-            }
+        // Check for consistency between the stage checkpoints, static files and prune
+        // checkpoints.
+        let read_only = factory.db_ref().is_read_only()?;
+        let report =
+            ConsistencyCheck::new(has_receipt_pruning).with_check_only(read_only).run(&factory)?;
+        if read_only && !report.is_consistent() {
+            warn!(target: "reth::cli", unwind_target = ?report.unwind_target(), "Inconsistent storage. Restart node to heal.");
+            return Ok(factory)
+        }
 
+        if let Some(unwind_target) = report.unwind_target() {
             // Highly unlikely to happen, and given its destructive nature, it's better to panic
             // instead.
             assert_ne!(unwind_target, PipelineTarget::Unwind(0), "A static file <> database inconsistency was found that would trigger an unwind to block 0");
@@ -186,6 +195,8 @@ pub enum AccessRights {
     RW,
     /// Read-only access
     RO,
+    /// Read-only access, watching the static files directory for changes made by another process
+    RoInotify,
 }
 
 impl AccessRights {
@@ -193,6 +204,11 @@ impl AccessRights {
     pub const fn is_read_write(&self) -> bool {
         matches!(self, Self::RW)
     }
+
+    /// Returns `true` if it requires watching the static files directory for changes.
+    pub const fn is_read_only_inotify(&self) -> bool {
+        matches!(self, Self::RoInotify)
+    }
 }
 
 /// Helper trait with a common set of requirements for the
@@ -0,0 +1,107 @@
+//! Command for inspecting and migrating the layout of the data directory.
+
+use clap::{Parser, Subcommand};
+use reth_chainspec::EthChainSpec;
+use reth_cli::chainspec::ChainSpecParser;
+use reth_node_core::{
+    args::DatadirArgs,
+    dirs::{ChainPath, DataDirPath, DatadirLayout},
+};
+use std::sync::Arc;
+use tracing::info;
+
+/// `reth datadir` command
+#[derive(Debug, Parser)]
+pub struct Command<C: ChainSpecParser> {
+    /// Parameters for datadir configuration
+    #[command(flatten)]
+    datadir: DatadirArgs,
+
+    /// The chain this node is running.
+    ///
+    /// Possible values are either a built-in chain or the path to a chain specification file.
+    #[arg(
+        long,
+        value_name = "CHAIN_OR_PATH",
+        long_help = C::help_message(),
+        default_value = C::SUPPORTED_CHAINS[0],
+        value_parser = C::parser()
+    )]
+    chain: Arc<C::ChainSpec>,
+
+    #[command(subcommand)]
+    command: Subcommands,
+}
+
+/// `reth datadir` subcommands
+#[derive(Subcommand, Debug)]
+pub enum Subcommands {
+    /// Prints the detected layout of the data directory
+    Layout,
+    /// Moves the files of a legacy data directory layout into the current one
+    Migrate {
+        /// Prints the moves without applying them
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
+impl<C: ChainSpecParser> Command<C> {
+    /// Execute `datadir` command
+    pub async fn execute(self) -> eyre::Result<()> {
+        let data_dir = self.datadir.resolve_datadir(self.chain.chain());
+
+        match self.command {
+            Subcommands::Layout => print_layout(&data_dir),
+            Subcommands::Migrate { dry_run } => {
+                let DatadirLayout::Legacy(migration) = data_dir.layout() else {
+                    println!("Nothing to migrate in {data_dir}");
+                    return Ok(())
+                };
+
+                for mv in migration.moves() {
+                    println!("{} -> {}", mv.from.display(), mv.to.display());
+                }
+
+                if !dry_run {
+                    migration.apply()?;
+                    info!(target: "reth::cli", %data_dir, "Migrated data directory to the current layout");
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn print_layout(data_dir: &ChainPath<DataDirPath>) {
+    let layout = match data_dir.layout() {
+        DatadirLayout::Empty => "empty",
+        DatadirLayout::Current => "current",
+        DatadirLayout::Legacy(_) => "legacy, run `reth datadir migrate` to migrate",
+    };
+    println!("Layout: {layout}");
+    println!("Data directory: {data_dir}");
+    println!("Database: {}", data_dir.db().display());
+    println!("Static files: {}", data_dir.static_files().display());
+    println!("Config: {}", data_dir.config().display());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_ethereum_cli::chainspec::EthereumChainSpecParser;
+
+    #[test]
+    fn parse_migrate() {
+        let cmd = Command::<EthereumChainSpecParser>::try_parse_from([
+            "reth",
+            "--datadir",
+            "my/path/to/datadir",
+            "migrate",
+            "--dry-run",
+        ])
+        .unwrap();
+        assert!(matches!(cmd.command, Subcommands::Migrate { dry_run: true }));
+    }
+}
@@ -0,0 +1,139 @@
+use alloy_consensus::TxReceipt;
+use alloy_primitives::BlockNumber;
+use clap::Parser;
+use human_bytes::human_bytes;
+use reth_config::Config;
+use reth_db::tables;
+use reth_db_api::transaction::{DbTx, DbTxMut};
+use reth_provider::{
+    providers::ProviderNodeTypes, BlockBodyIndicesProvider, DBProvider, DatabaseProviderFactory,
+    ProviderFactory, ReceiptProvider, StageCheckpointReader, StageCheckpointWriter,
+};
+use reth_prune::PruneSegment;
+use reth_stages::{stages::IndexLogAddressesStage, ExecInput, Stage, StageCheckpoint, StageId};
+use std::collections::HashSet;
+use tracing::info;
+
+/// Rough size of one block number in the index, including the amortized size of the shard keys.
+const ESTIMATED_BYTES_PER_ENTRY: u64 = 4;
+
+/// The arguments for the `reth db build-log-index` command
+#[derive(Parser, Debug)]
+pub struct Command {
+    /// Only prints the estimated size of the index without building it
+    #[arg(long, conflicts_with = "drop")]
+    estimate_only: bool,
+
+    /// Deletes the index, log queries scan the receipts again afterwards
+    #[arg(long)]
+    drop: bool,
+
+    /// Number of blocks sampled to estimate the size of the index
+    #[arg(long, default_value_t = 1_000)]
+    sample_blocks: u64,
+}
+
+impl Command {
+    /// Execute `db build-log-index` command
+    pub fn execute<N: ProviderNodeTypes>(
+        self,
+        provider_factory: ProviderFactory<N>,
+        config: &Config,
+    ) -> eyre::Result<()> {
+        if self.drop {
+            let provider_rw = provider_factory.database_provider_rw()?;
+            let tx = provider_rw.tx_ref();
+            tx.clear::<tables::LogAddressIndex>()?;
+            tx.delete::<tables::StageCheckpoints>(StageId::IndexLogAddresses.to_string(), None)?;
+            tx.delete::<tables::PruneCheckpoints>(PruneSegment::LogAddressIndex, None)?;
+            provider_rw.commit()?;
+
+            println!("Log address index dropped");
+            return Ok(())
+        }
+
+        // Receipts only exist up to the executed block.
+        let tip = provider_factory
+            .provider()?
+            .get_stage_checkpoint(StageId::Execution)?
+            .unwrap_or_default()
+            .block_number;
+
+        let estimated_entries = self.estimate_entries(&provider_factory, tip)?;
+        println!(
+            "Estimated log address index size for {} blocks: {} ({estimated_entries} entries)",
+            tip + 1,
+            human_bytes((estimated_entries * ESTIMATED_BYTES_PER_ENTRY) as f64)
+        );
+        if self.estimate_only {
+            return Ok(())
+        }
+
+        let mut checkpoint = provider_factory
+            .provider()?
+            .get_stage_checkpoint(StageId::IndexLogAddresses)?
+            .map(|checkpoint| checkpoint.block_number);
+        let prune_modes = config.prune.clone().map(|prune| prune.segments).unwrap_or_default();
+        let mut stage = IndexLogAddressesStage::new(
+            config.stages.index_log_addresses,
+            config.stages.etl.clone(),
+            prune_modes.receipts,
+        );
+
+        while checkpoint.is_none_or(|checkpoint| checkpoint < tip) {
+            let target =
+                checkpoint.unwrap_or_default().saturating_add(stage.commit_threshold).min(tip);
+            let input = ExecInput {
+                target: Some(target),
+                checkpoint: checkpoint.map(StageCheckpoint::new),
+            };
+
+            let provider_rw = provider_factory.database_provider_rw()?;
+            let output = stage.execute(&provider_rw, input)?;
+            provider_rw.save_stage_checkpoint(StageId::IndexLogAddresses, output.checkpoint)?;
+            provider_rw.commit()?;
+
+            info!(target: "reth::cli", block = output.checkpoint.block_number, tip, "Indexed log addresses");
+            checkpoint = Some(output.checkpoint.block_number);
+        }
+
+        println!("Log address index built up to block {tip}");
+        if !config.stages.index_log_addresses.enabled {
+            println!(
+                "Set `stages.index_log_addresses.enabled` in the config to keep the index up to date during pipeline sync"
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Estimates the number of (address, block) entries of the index by counting the addresses
+    /// that emitted logs in evenly spaced sample blocks.
+    fn estimate_entries<N: ProviderNodeTypes>(
+        &self,
+        provider_factory: &ProviderFactory<N>,
+        tip: BlockNumber,
+    ) -> eyre::Result<u64> {
+        let provider = provider_factory.provider()?;
+        let step = ((tip + 1) / self.sample_blocks.max(1)).max(1);
+
+        let mut sampled = 0u64;
+        let mut entries = 0u64;
+        for block_number in (0..=tip).step_by(step as usize) {
+            let Some(body) = provider.block_body_indices(block_number)? else { continue };
+            let addresses = provider
+                .receipts_by_tx_range(body.tx_num_range())?
+                .iter()
+                .flat_map(|receipt| receipt.logs().iter().map(|log| log.address))
+                .collect::<HashSet<_>>();
+
+            sampled += 1;
+            entries += addresses.len() as u64;
+        }
+
+        if sampled == 0 {
+            return Ok(0)
+        }
+        Ok(entries * (tip + 1) / sampled)
+    }
+}
@@ -0,0 +1,62 @@
+use clap::{Parser, ValueEnum};
+use reth_db_common::{
+    state_export::{export_state, StateExportFormat},
+    DbTool,
+};
+use reth_fs_util as fs;
+use reth_provider::{providers::ProviderNodeTypes, BlockNumReader};
+use std::{io::BufWriter, path::PathBuf};
+
+#[derive(Parser, Debug)]
+/// The arguments for the `reth db export-state` command
+pub struct Command {
+    /// The block to export the state at. Defaults to the tip.
+    #[arg(long)]
+    at: Option<u64>,
+
+    /// The file to write the state to.
+    #[arg(long, short)]
+    output: PathBuf,
+
+    /// The format of the export.
+    #[arg(long, value_enum, default_value_t = ExportFormat::Jsonl)]
+    format: ExportFormat,
+
+    /// Also exports the storage and bytecode of each account, which is required to import the
+    /// state with `init-state`. Storage is always exported for the genesis format.
+    #[arg(long)]
+    storage: bool,
+}
+
+/// The format of the state export.
+#[derive(Debug, Copy, Clone, ValueEnum, Eq, PartialEq)]
+pub enum ExportFormat {
+    /// One account JSON object per line.
+    Jsonl,
+    /// A genesis `alloc` JSON object.
+    Genesis,
+}
+
+impl From<ExportFormat> for StateExportFormat {
+    fn from(format: ExportFormat) -> Self {
+        match format {
+            ExportFormat::Jsonl => Self::Jsonl,
+            ExportFormat::Genesis => Self::Genesis,
+        }
+    }
+}
+
+impl Command {
+    /// Execute `db export-state` command
+    pub fn execute<N: ProviderNodeTypes>(self, tool: &DbTool<N>) -> eyre::Result<()> {
+        let block = match self.at {
+            Some(block) => block,
+            None => tool.provider_factory.best_block_number()?,
+        };
+
+        let writer = BufWriter::new(fs::create_file(&self.output)?);
+        export_state(&tool.provider_factory, block, self.format.into(), self.storage, writer)?;
+
+        Ok(())
+    }
+}
@@ -3,14 +3,17 @@ use clap::{Parser, Subcommand};
 use reth_chainspec::{EthChainSpec, EthereumHardforks};
 use reth_cli::chainspec::ChainSpecParser;
 use reth_db::version::{get_db_version, DatabaseVersionError, DB_VERSION};
-use reth_db_common::DbTool;
+use reth_db_common::{migration::Migrations, DbTool};
 use std::io::{self, Write};
 
+mod build_log_index;
 mod checksum;
 mod clear;
 mod diff;
+mod export_state;
 mod get;
 mod list;
+mod rebuild;
 mod stats;
 /// DB List TUI
 mod tui;
@@ -38,6 +41,8 @@ pub enum Subcommands {
     Diff(diff::Command),
     /// Gets the content of a table for the given key
     Get(get::Command),
+    /// Exports the state at a given block
+    ExportState(export_state::Command),
     /// Deletes all database entries
     Drop {
         /// Bypasses the interactive confirmation and drops the database directly
@@ -46,10 +51,31 @@ pub enum Subcommands {
     },
     /// Deletes all table entries
     Clear(clear::Command),
+    /// Builds the optional index of blocks with logs of each address, used to speed up
+    /// `eth_getLogs` queries that filter by address
+    BuildLogIndex(build_log_index::Command),
+    /// Rebuilds indices that were pruned or dropped from the stored data
+    Rebuild(rebuild::Command),
     /// Lists current and local database versions
     Version,
+    /// Applies the pending database schema migrations
+    Migrate {
+        /// Lists the pending migrations with an estimate of their work without applying them
+        #[arg(long)]
+        dry_run: bool,
+    },
     /// Returns the full database path
-    Path,
+    Path {
+        /// Returns the path of the database directory. This is the default.
+        #[arg(long, group = "path")]
+        db: bool,
+        /// Returns the path of the static files directory instead.
+        #[arg(long, group = "path")]
+        static_files: bool,
+        /// Returns the path of the configuration file instead.
+        #[arg(long, group = "path")]
+        config: bool,
+    },
 }
 
 /// `db_ro_exec` opens a database in read-only mode, and then execute with the provided command
@@ -106,6 +132,11 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C>
                     command.execute(&tool)?;
                 });
             }
+            Subcommands::ExportState(command) => {
+                db_ro_exec!(self.env, tool, N, {
+                    command.execute(&tool)?;
+                });
+            }
             Subcommands::Drop { force } => {
                 if !force {
                     // Ask for confirmation
@@ -130,6 +161,16 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C>
                 let Environment { provider_factory, .. } = self.env.init::<N>(AccessRights::RW)?;
                 command.execute(provider_factory)?;
             }
+            Subcommands::BuildLogIndex(command) => {
+                let Environment { provider_factory, config, .. } =
+                    self.env.init::<N>(AccessRights::RW)?;
+                command.execute(provider_factory, &config)?;
+            }
+            Subcommands::Rebuild(command) => {
+                let Environment { provider_factory, config, .. } =
+                    self.env.init::<N>(AccessRights::RW)?;
+                command.execute(provider_factory, &config)?;
+            }
             Subcommands::Version => {
                 let local_db_version = match get_db_version(&db_path) {
                     Ok(version) => Some(version),
@@ -145,8 +186,36 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C>
                     println!("Local database is uninitialized");
                 }
             }
-            Subcommands::Path => {
-                println!("{}", db_path.display());
+            Subcommands::Migrate { dry_run } => {
+                let access = if dry_run { AccessRights::RO } else { AccessRights::RW };
+                let Environment { provider_factory, config, .. } = self.env.init::<N>(access)?;
+                let migrations = Migrations::new(&config);
+
+                if dry_run {
+                    let pending = migrations.pending(&provider_factory)?;
+                    if pending.is_empty() {
+                        println!("No pending migrations");
+                    }
+                    for migration in pending {
+                        println!(
+                            "{}: {} (~{} entries)",
+                            migration.id, migration.description, migration.estimated_work
+                        );
+                    }
+                } else {
+                    let version = migrations.run(&provider_factory)?;
+                    println!("Database schema version: {version}");
+                }
+            }
+            Subcommands::Path { db: _, static_files, config } => {
+                let path = if static_files {
+                    static_files_path
+                } else if config {
+                    self.env.config.unwrap_or_else(|| data_dir.config())
+                } else {
+                    db_path
+                };
+                println!("{}", path.display());
             }
         }
 
@@ -172,4 +241,45 @@ mod tests {
         .unwrap();
         assert_eq!(cmd.env.datadir.resolve_datadir(cmd.env.chain.chain).as_ref(), Path::new(&path));
     }
+
+    #[test]
+    fn parse_path_flags() {
+        let cmd =
+            Command::<EthereumChainSpecParser>::try_parse_from(["reth", "path", "--static-files"])
+                .unwrap();
+        assert!(matches!(cmd.command, Subcommands::Path { static_files: true, .. }));
+
+        assert!(Command::<EthereumChainSpecParser>::try_parse_from([
+            "reth", "path", "--db", "--config"
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn parse_migrate_dry_run() {
+        let cmd =
+            Command::<EthereumChainSpecParser>::try_parse_from(["reth", "migrate", "--dry-run"])
+                .unwrap();
+        assert!(matches!(cmd.command, Subcommands::Migrate { dry_run: true }));
+    }
+
+    #[test]
+    fn parse_export_state() {
+        let cmd = Command::<EthereumChainSpecParser>::try_parse_from([
+            "reth",
+            "export-state",
+            "--at",
+            "100",
+            "--output",
+            "state.json",
+            "--format",
+            "genesis",
+        ])
+        .unwrap();
+        assert!(matches!(cmd.command, Subcommands::ExportState(_)));
+
+        assert!(
+            Command::<EthereumChainSpecParser>::try_parse_from(["reth", "export-state"]).is_err()
+        );
+    }
 }
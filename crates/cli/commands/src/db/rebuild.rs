@@ -0,0 +1,251 @@
+use clap::{Parser, Subcommand};
+use reth_config::Config;
+use reth_db::tables;
+use reth_db_api::transaction::{DbTx, DbTxMut};
+use reth_provider::{
+    providers::ProviderNodeTypes, DBProvider, ProviderFactory, StageCheckpointReader,
+    StageCheckpointWriter, StaticFileProviderFactory,
+};
+use reth_prune::PruneSegment;
+use reth_stages::{stages::TransactionLookupStage, ExecInput, Stage, StageId};
+use tracing::info;
+
+/// The arguments for the `reth db rebuild` command
+#[derive(Parser, Debug)]
+pub struct Command {
+    #[command(subcommand)]
+    subcommand: Subcommands,
+}
+
+impl Command {
+    /// Execute `db rebuild` command
+    pub fn execute<N: ProviderNodeTypes>(
+        self,
+        provider_factory: ProviderFactory<N>,
+        config: &Config,
+    ) -> eyre::Result<()> {
+        match self.subcommand {
+            Subcommands::TxLookup => rebuild_tx_lookup(provider_factory, config),
+            Subcommands::StaticFileOffsets => rebuild_static_file_offsets(provider_factory),
+        }
+    }
+}
+
+#[derive(Subcommand, Debug)]
+enum Subcommands {
+    /// Rebuilds the index of transaction hashes from the stored transactions, e.g. after the
+    /// transaction lookup segment was fully pruned
+    TxLookup,
+    /// Rewrites the offsets of the finished static files with delta encoding, which shrinks them
+    /// considerably. A static file gets its fixed-width offsets back once it's unwound into
+    StaticFileOffsets,
+}
+
+/// Rebuilds the [`tables::TransactionHashNumbers`] table up to the checkpoint of the transaction
+/// lookup stage, later blocks are indexed by the node.
+fn rebuild_tx_lookup<N: ProviderNodeTypes>(
+    provider_factory: ProviderFactory<N>,
+    config: &Config,
+) -> eyre::Result<()> {
+    let prune_modes = config.prune.clone().map(|prune| prune.segments).unwrap_or_default();
+    eyre::ensure!(
+        prune_modes.transaction_lookup.is_none_or(|mode| !mode.is_full()),
+        "The transaction lookup segment is fully pruned by the config, change `prune.segments.transaction_lookup` first"
+    );
+
+    let provider_rw = provider_factory.database_provider_rw()?;
+    let tip = provider_rw
+        .get_stage_checkpoint(StageId::TransactionLookup)?
+        .unwrap_or_default()
+        .block_number;
+
+    info!(target: "reth::cli", tip, "Rebuilding transaction lookup index");
+    let tx = provider_rw.tx_ref();
+    tx.clear::<tables::TransactionHashNumbers>()?;
+    tx.delete::<tables::PruneCheckpoints>(PruneSegment::TransactionLookup, None)?;
+
+    // The stage only indexes the transactions that the prune config keeps.
+    let mut stage = TransactionLookupStage::new(
+        config.stages.transaction_lookup,
+        config.stages.etl.clone(),
+        prune_modes.transaction_lookup,
+    );
+    let output = stage.execute(&provider_rw, ExecInput { target: Some(tip), checkpoint: None })?;
+    provider_rw.save_stage_checkpoint(StageId::TransactionLookup, output.checkpoint)?;
+    provider_rw.commit()?;
+
+    let entries =
+        provider_factory.provider()?.tx_ref().entries::<tables::TransactionHashNumbers>()?;
+    info!(target: "reth::cli", tip, entries, "Rebuilt transaction lookup index");
+    println!("Transaction lookup index rebuilt up to block {tip} with {entries} transactions");
+
+    Ok(())
+}
+
+/// Seals the finished static files, see [`OffsetsEncoding::Delta`].
+///
+/// Sealing is idempotent, so an interrupted run only seals the remaining static files when it's
+/// started again.
+///
+/// [`OffsetsEncoding::Delta`]: reth_nippy_jar::OffsetsEncoding::Delta
+fn rebuild_static_file_offsets<N: ProviderNodeTypes>(
+    provider_factory: ProviderFactory<N>,
+) -> eyre::Result<()> {
+    let static_file_provider = provider_factory.static_file_provider();
+    let unsealed = static_file_provider.unsealed_static_files()?;
+
+    info!(target: "reth::cli", static_files = unsealed.len(), "Sealing static files");
+    for (segment, fixed_block_range) in &unsealed {
+        static_file_provider.seal_static_file(*segment, *fixed_block_range)?;
+    }
+    info!(target: "reth::cli", static_files = unsealed.len(), "Sealed static files");
+    println!("Sealed {} static files", unsealed.len());
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::Header;
+    use alloy_primitives::{B256, U256};
+    use reth_chainspec::MAINNET;
+    use reth_config::PruneConfig;
+    use reth_db::test_utils::{create_test_rw_db, create_test_static_files_dir};
+    use reth_db_api::cursor::DbCursorRO;
+    use reth_nippy_jar::NippyJar;
+    use reth_primitives::{static_file::SegmentHeader, StaticFileSegment};
+    use reth_provider::{
+        providers::StaticFileProvider, test_utils::MockNodeTypesWithDB, HeaderProvider,
+        PruneCheckpointReader, PruneCheckpointWriter, StaticFileWriter,
+    };
+    use reth_prune::{PruneCheckpoint, PruneMode, PruneModes};
+    use reth_stages::{
+        test_utils::{StorageKind, TestStageDB},
+        StageCheckpoint,
+    };
+    use reth_testing_utils::generators::{self, random_block_range, BlockRangeParams};
+
+    const TIP: u64 = 30;
+
+    #[test]
+    fn rebuild_tx_lookup_after_full_prune() {
+        let db = TestStageDB::default();
+        let blocks = random_block_range(
+            &mut generators::rng(),
+            0..=TIP,
+            BlockRangeParams { parent: Some(B256::ZERO), tx_count: 0..4, ..Default::default() },
+        );
+        db.insert_blocks(blocks.iter(), StorageKind::Static).unwrap();
+
+        // the node ran with the transaction lookup segment fully pruned
+        let provider_rw = db.factory.database_provider_rw().unwrap();
+        provider_rw
+            .save_stage_checkpoint(StageId::TransactionLookup, StageCheckpoint::new(TIP))
+            .unwrap();
+        provider_rw
+            .save_prune_checkpoint(
+                PruneSegment::TransactionLookup,
+                PruneCheckpoint {
+                    block_number: Some(TIP),
+                    tx_number: None,
+                    prune_mode: PruneMode::Full,
+                },
+            )
+            .unwrap();
+        provider_rw.commit().unwrap();
+        assert!(db.table_is_empty::<tables::TransactionHashNumbers>().unwrap());
+
+        // refuses to rebuild while the config still prunes the index
+        let mut config = Config {
+            prune: Some(PruneConfig {
+                segments: PruneModes {
+                    transaction_lookup: Some(PruneMode::Full),
+                    ..Default::default()
+                },
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        assert!(rebuild_tx_lookup(db.factory.clone(), &config).is_err());
+
+        config.prune = None;
+        rebuild_tx_lookup(db.factory.clone(), &config).unwrap();
+
+        // the genesis block is never indexed
+        let mut expected = blocks
+            .iter()
+            .flat_map(|block| block.body.transactions.iter().map(|tx| (block.number, tx.hash())))
+            .enumerate()
+            .filter(|(_, (block_number, _))| *block_number > 0)
+            .map(|(tx_number, (_, hash))| (hash, tx_number as u64))
+            .collect::<Vec<_>>();
+        expected.sort_unstable();
+        let indexed = db
+            .query(|tx| {
+                Ok(tx
+                    .cursor_read::<tables::TransactionHashNumbers>()?
+                    .walk(None)?
+                    .collect::<Result<Vec<_>, _>>()?)
+            })
+            .unwrap();
+        assert_eq!(indexed, expected);
+
+        let provider = db.factory.provider().unwrap();
+        assert_eq!(provider.get_prune_checkpoint(PruneSegment::TransactionLookup).unwrap(), None);
+        assert_eq!(
+            provider
+                .get_stage_checkpoint(StageId::TransactionLookup)
+                .unwrap()
+                .map(|c| c.block_number),
+            Some(TIP)
+        );
+    }
+
+    #[test]
+    fn rebuild_static_file_offsets_seals_finished_static_files() {
+        let (static_dir, _) = create_test_static_files_dir();
+        let static_file_provider =
+            StaticFileProvider::read_write(&static_dir).unwrap().with_custom_blocks_per_file(10);
+        let factory = ProviderFactory::<MockNodeTypesWithDB>::new(
+            create_test_rw_db(),
+            MAINNET.clone(),
+            static_file_provider.clone(),
+        );
+        let load_jar = |block| {
+            NippyJar::<SegmentHeader>::load(&static_dir.as_ref().join(
+                StaticFileSegment::Headers.filename(&static_file_provider.find_fixed_range(block)),
+            ))
+            .unwrap()
+        };
+
+        let mut writer = static_file_provider.latest_writer(StaticFileSegment::Headers).unwrap();
+        let mut header = Header::default();
+        for number in 0..=TIP {
+            header.number = number;
+            writer.append_header(&header, U256::ZERO, &B256::ZERO).unwrap();
+        }
+        writer.commit().unwrap();
+        drop(writer);
+
+        // the writer leaves the offsets of finished static files as they are
+        assert_eq!(static_file_provider.unsealed_static_files().unwrap().len(), 3);
+        assert!(!load_jar(0).has_delta_offsets());
+
+        rebuild_static_file_offsets(factory.clone()).unwrap();
+        assert!(static_file_provider.unsealed_static_files().unwrap().is_empty());
+        assert!(load_jar(0).has_delta_offsets());
+        assert!(load_jar(20).has_delta_offsets());
+        // the latest static file is the one the writer appends to
+        assert!(!load_jar(TIP).has_delta_offsets());
+        for number in 0..=TIP {
+            assert_eq!(
+                static_file_provider.header_by_number(number).unwrap().unwrap().number,
+                number
+            );
+        }
+
+        // nothing left to seal
+        rebuild_static_file_offsets(factory).unwrap();
+    }
+}
@@ -48,6 +48,11 @@ pub struct ImportCommand<C: ChainSpecParser> {
     #[arg(long, value_name = "CHUNK_LEN", verbatim_doc_comment)]
     chunk_len: Option<u64>,
 
+    /// Verifies the Ethash proof-of-work seal of imported headers below the merge block.
+    #[cfg(feature = "ethash-verify")]
+    #[arg(long = "import.verify-pow", verbatim_doc_comment)]
+    verify_pow: bool,
+
     /// The path to a block file for import.
     ///
     /// The online stages (headers and bodies) are replaced by a file import, after which the
@@ -78,7 +83,15 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> ImportComm
         let Environment { provider_factory, config, .. } = self.env.init::<N>(AccessRights::RW)?;
 
         let executor = executor(provider_factory.chain_spec());
-        let consensus = Arc::new(EthBeaconConsensus::new(self.env.chain.clone()));
+        let consensus = EthBeaconConsensus::new(self.env.chain.clone());
+        #[cfg(feature = "ethash-verify")]
+        let consensus = if self.verify_pow {
+            info!(target: "reth::cli", "Enabled proof-of-work verification of pre-merge headers");
+            consensus.with_pow_verification(Default::default())
+        } else {
+            consensus
+        };
+        let consensus = Arc::new(consensus);
         info!(target: "reth::cli", "Consensus engine initialized");
 
         // open file
@@ -117,7 +130,13 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> ImportComm
 
             let latest_block_number =
                 provider.get_stage_checkpoint(StageId::Finish)?.map(|ch| ch.block_number);
-            tokio::spawn(reth_node_events::node::handle_events(None, latest_block_number, events));
+            tokio::spawn(reth_node_events::node::handle_events(
+                None,
+                Some(Box::new(provider_factory.db_ref().clone())),
+                None,
+                latest_block_number,
+                events,
+            ));
 
             // Run pipeline
             info!(target: "reth::cli", "Starting sync pipeline");
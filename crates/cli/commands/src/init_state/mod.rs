@@ -5,13 +5,21 @@ use alloy_primitives::{B256, U256};
 use clap::Parser;
 use reth_chainspec::{EthChainSpec, EthereumHardforks};
 use reth_cli::chainspec::ChainSpecParser;
-use reth_db_common::init::init_from_state_dump;
+use reth_db_common::init::{init_from_state_dump, StateImportConfig};
 use reth_node_api::NodePrimitives;
 use reth_primitives::SealedHeader;
 use reth_provider::{
     BlockNumReader, DatabaseProviderFactory, StaticFileProviderFactory, StaticFileWriter,
 };
-use std::{io::BufReader, path::PathBuf, str::FromStr};
+use std::{
+    io::BufReader,
+    path::PathBuf,
+    str::FromStr,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
 use tracing::info;
 
 pub mod without_evm;
@@ -22,10 +30,11 @@ pub struct InitStateCommand<C: ChainSpecParser> {
     #[command(flatten)]
     pub env: EnvironmentArgs<C>,
 
-    /// JSONL file with state dump.
+    /// File with state dump.
     ///
-    /// Must contain accounts in following format, additional account fields are ignored. Must
-    /// also contain { "root": \<state-root\> } as first line.
+    /// Either a JSONL file with one account per line, in the following format or as exported by
+    /// 'db export-state --storage', additional account fields are ignored. The first line can be
+    /// { "root": \<state-root\> }, which is checked against the state root of the block.
     /// {
     ///     "balance": "\<balance\>",
     ///     "nonce": \<nonce\>,
@@ -37,6 +46,12 @@ pub struct InitStateCommand<C: ChainSpecParser> {
     ///     "address": "\<address\>",
     /// }
     ///
+    /// Or a genesis alloc JSON file, mapping addresses to accounts.
+    ///
+    /// Accounts are written in batches that are committed as they go, ctrl-c stops the import
+    /// after the current batch. An interrupted import is resumed by running the command again with
+    /// the same file.
+    ///
     /// Allows init at a non-genesis block. Caution! Blocks must be manually imported up until
     /// and including the non-genesis block to init chain at. See 'import' command.
     #[arg(value_name = "STATE_DUMP_FILE", verbatim_doc_comment)]
@@ -79,7 +94,6 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> InitStateC
         let Environment { config, provider_factory, .. } = self.env.init::<N>(AccessRights::RW)?;
 
         let static_file_provider = provider_factory.static_file_provider();
-        let provider_rw = provider_factory.database_provider_rw()?;
 
         if self.without_evm {
             // ensure header, total difficulty and header hash are provided
@@ -95,6 +109,7 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> InitStateC
                 .ok_or_else(|| eyre::eyre!("Total difficulty must be provided"))?;
             let total_difficulty = U256::from_str(&total_difficulty)?;
 
+            let provider_rw = provider_factory.database_provider_rw()?;
             let last_block_number = provider_rw.last_block_number()?;
 
             if last_block_number == 0 {
@@ -109,9 +124,9 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> InitStateC
                 // SAFETY: it's safe to commit static files, since in the event of a crash, they
                 // will be unwound according to database checkpoints.
                 //
-                // Necessary to commit, so the header is accessible to provider_rw and
-                // init_state_dump
+                // Necessary to commit, so the header is accessible to init_state_dump
                 static_file_provider.commit()?;
+                provider_rw.commit()?;
             } else if last_block_number > 0 && last_block_number < header.number {
                 return Err(eyre::eyre!(
                     "Data directory should be empty when calling init-state with --without-evm-history."
@@ -123,11 +138,27 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> InitStateC
 
         let reader = BufReader::new(reth_fs_util::open(self.state)?);
 
-        let hash = init_from_state_dump(reader, &provider_rw, config.stages.etl)?;
-
-        provider_rw.commit()?;
+        let hash = init_from_state_dump(
+            reader,
+            &provider_factory,
+            StateImportConfig::new(config.stages.etl).with_interrupt(interrupt_on_ctrl_c()),
+        )?;
 
         info!(target: "reth::cli", hash = ?hash, "Genesis block written");
         Ok(())
     }
 }
+
+/// Returns a flag that's set on ctrl-c, to stop a state import after the batch that's being
+/// written. The import is resumed by running the command again.
+pub fn interrupt_on_ctrl_c() -> Arc<AtomicBool> {
+    let interrupt = Arc::new(AtomicBool::new(false));
+    let flag = interrupt.clone();
+    tokio::spawn(async move {
+        if tokio::signal::ctrl_c().await.is_ok() {
+            info!(target: "reth::cli", "Stopping the state import after the current batch");
+            flag.store(true, Ordering::Relaxed);
+        }
+    });
+    interrupt
+}
@@ -10,6 +10,7 @@
 
 pub mod common;
 pub mod config_cmd;
+pub mod datadir;
 pub mod db;
 pub mod dump_genesis;
 pub mod import;
@@ -19,7 +20,9 @@ pub mod node;
 pub mod p2p;
 pub mod prune;
 pub mod recover;
+pub mod snapshot;
 pub mod stage;
+pub mod stats;
 #[cfg(feature = "arbitrary")]
 pub mod test_vectors;
 
@@ -10,8 +10,8 @@ use reth_ethereum_cli::chainspec::EthereumChainSpecParser;
 use reth_node_builder::{NodeBuilder, WithLaunchContext};
 use reth_node_core::{
     args::{
-        DatabaseArgs, DatadirArgs, DebugArgs, DevArgs, NetworkArgs, PayloadBuilderArgs,
-        PruningArgs, RpcServerArgs, TxPoolArgs,
+        DatabaseArgs, DatadirArgs, DebugArgs, DevArgs, DiskSpaceArgs, NetworkArgs,
+        PayloadBuilderArgs, PruningArgs, RpcServerArgs, ScrubberArgs, StaticFilesArgs, TxPoolArgs,
     },
     node_config::NodeConfig,
     version,
@@ -71,6 +71,20 @@ pub struct NodeCommand<
     #[arg(long, conflicts_with = "instance", global = true)]
     pub with_unused_ports: bool,
 
+    /// Starts the node even if the configuration contains suspicious flag combinations, logging
+    /// them as warnings instead.
+    ///
+    /// Invalid flag combinations always prevent the node from starting.
+    #[arg(long)]
+    pub ignore_config_warnings: bool,
+
+    /// Only reports the inconsistencies between the stage checkpoints, static files and prune
+    /// checkpoints, and the planned repairs, without repairing them. Exits once they're reported.
+    ///
+    /// Fails if the storage is inconsistent.
+    #[arg(long)]
+    pub check_consistency_only: bool,
+
     /// All datadir related arguments
     #[command(flatten)]
     pub datadir: DatadirArgs,
@@ -99,6 +113,11 @@ pub struct NodeCommand<
     #[command(flatten)]
     pub db: DatabaseArgs,
 
+    /// Disable counting the writes to each table per stage in the database metrics, which adds a
+    /// small overhead to every write.
+    #[arg(long = "db.disable-write-metrics", help_heading = "Database")]
+    pub disable_db_write_metrics: bool,
+
     /// All dev related arguments with --dev prefix
     #[command(flatten)]
     pub dev: DevArgs,
@@ -107,6 +126,18 @@ pub struct NodeCommand<
     #[command(flatten)]
     pub pruning: PruningArgs,
 
+    /// All static files scrubber related arguments with --scrubber prefix
+    #[command(flatten)]
+    pub scrubber: ScrubberArgs,
+
+    /// All static files related arguments with --static-files prefix
+    #[command(flatten)]
+    pub static_files: StaticFilesArgs,
+
+    /// All disk space monitoring related arguments
+    #[command(flatten)]
+    pub disk_space: DiskSpaceArgs,
+
     /// Additional cli arguments
     #[command(flatten, next_help_heading = "Extension")]
     pub ext: Ext,
@@ -151,14 +182,20 @@ impl<
             metrics,
             instance,
             with_unused_ports,
+            ignore_config_warnings,
+            check_consistency_only,
             network,
             rpc,
             txpool,
             builder,
             debug,
             db,
+            disable_db_write_metrics,
             dev,
             pruning,
+            scrubber,
+            static_files,
+            disk_space,
             ext,
         } = self;
 
@@ -169,6 +206,7 @@ impl<
             chain,
             metrics,
             instance,
+            check_consistency_only,
             network,
             rpc,
             txpool,
@@ -177,13 +215,24 @@ impl<
             db,
             dev,
             pruning,
+            scrubber,
+            static_files,
+            disk_space,
         };
 
+        node_config.validate().ensure_valid(ignore_config_warnings)?;
+
         let data_dir = node_config.datadir();
         let db_path = data_dir.db();
 
         tracing::info!(target: "reth::cli", path = ?db_path, "Opening database");
-        let database = Arc::new(init_db(db_path.clone(), self.db.database_args())?.with_metrics());
+        let database = Arc::new(
+            init_db(
+                db_path.clone(),
+                self.db.database_args().with_write_metrics(!disable_db_write_metrics),
+            )?
+            .with_metrics(),
+        );
 
         if with_unused_ports {
             node_config = node_config.with_unused_ports();
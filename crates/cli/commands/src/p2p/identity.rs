@@ -0,0 +1,188 @@
+//! Node identity subcommand of P2P Debugging tool.
+
+use alloy_primitives::{hex, Bytes};
+use clap::{Parser, Subcommand};
+use eyre::WrapErr;
+use reth_chainspec::{EthChainSpec, Hardforks};
+use reth_cli_util::get_secret_key;
+use reth_config::Config;
+use reth_db::lockfile::StorageLock;
+use reth_discv5::{discv5::enr::Enr, NetworkStackId};
+use reth_fs_util as fs;
+use reth_network::config::{rng_secret_key, SecretKey};
+use reth_network_peers::{pk2id, NodeRecord, PeerId};
+use reth_node_core::args::NetworkArgs;
+use secp256k1::SECP256K1;
+use std::{net::SocketAddr, path::Path};
+
+/// Node identity commands
+#[derive(Parser, Debug)]
+pub struct Command {
+    #[clap(subcommand)]
+    subcommand: Subcommands,
+}
+
+impl Command {
+    /// Execute `p2p identity` command.
+    pub fn execute(
+        self,
+        chain: impl EthChainSpec + Hardforks,
+        secret_key_path: &Path,
+        config_path: &Path,
+        db_path: &Path,
+        network: &NetworkArgs,
+    ) -> eyre::Result<()> {
+        match self.subcommand {
+            Subcommands::Show => {
+                let secret_key = get_secret_key(secret_key_path)?;
+                let config = Config::from_path(config_path).unwrap_or_default();
+                let identity = NodeIdentity::new(&secret_key, chain, &config, network);
+                println!("Peer ID: {}", identity.peer_id);
+                println!("enode:   {}", identity.enode);
+                println!("ENR:     {}", identity.enr.to_base64());
+            }
+            Subcommands::Rotate => {
+                // the node holds the lock of its database while it's running
+                let _lock = db_path
+                    .exists()
+                    .then(|| StorageLock::try_acquire(db_path))
+                    .transpose()
+                    .wrap_err("the node must be stopped before rotating its identity")?;
+                let previous = get_secret_key(secret_key_path)?;
+                let secret_key = rotate_secret_key(secret_key_path)?;
+                println!("Previous peer ID: {}", pk2id(&previous.public_key(SECP256K1)));
+                println!("New peer ID:      {}", pk2id(&secret_key.public_key(SECP256K1)));
+            }
+            Subcommands::SetEnrEntry { key, value } => {
+                let mut config = Config::from_path(config_path)?;
+                config.update_enr_entry(key.clone(), value.clone())?;
+                config.save(config_path)?;
+                println!("Set ENR entry {key} = {value} in {}", config_path.display());
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Subcommand, Debug)]
+enum Subcommands {
+    /// Print the peer ID, enode URL and ENR of the node.
+    Show,
+    /// Replace the p2p secret key with a new random one, which changes the peer ID.
+    ///
+    /// The node must not be running, which is checked with the lock of its database. Discovery
+    /// sessions are only kept in memory and are keyed by the peer ID, so peers establish new
+    /// sessions with the new identity on the next start.
+    Rotate,
+    /// Set a custom entry of the ENR in the configuration file, which the node advertises from
+    /// the next start.
+    SetEnrEntry {
+        /// The key of the entry.
+        key: String,
+        /// The value of the entry, either `0x` prefixed hex or a string that's advertised as its
+        /// UTF-8 bytes.
+        #[arg(value_parser = parse_enr_value)]
+        value: Bytes,
+    },
+}
+
+/// Parses the value of a custom ENR entry.
+fn parse_enr_value(value: &str) -> eyre::Result<Bytes> {
+    if value.starts_with("0x") {
+        Ok(hex::decode(value)?.into())
+    } else {
+        Ok(Bytes::copy_from_slice(value.as_bytes()))
+    }
+}
+
+/// Generates a new secret key and replaces the one stored at the path with it.
+///
+/// The key is written to a temporary file first, so that the previous key is kept if writing the
+/// new one fails.
+fn rotate_secret_key(secret_key_path: &Path) -> eyre::Result<SecretKey> {
+    let secret_key = rng_secret_key();
+    let tmp_path = secret_key_path.with_extension("tmp");
+    fs::write(&tmp_path, hex::encode(secret_key.as_ref()))?;
+    fs::rename(&tmp_path, secret_key_path)?;
+    Ok(secret_key)
+}
+
+/// The identity the node advertises to peers.
+#[derive(Debug)]
+struct NodeIdentity {
+    /// The peer ID derived from the secret key.
+    peer_id: PeerId,
+    /// The discv4 node record.
+    enode: NodeRecord,
+    /// The node record, including the custom entries of the configuration.
+    enr: Enr<SecretKey>,
+}
+
+impl NodeIdentity {
+    /// Derives the identity from the secret key and the network settings of the node.
+    fn new(
+        secret_key: &SecretKey,
+        chain: impl EthChainSpec + Hardforks,
+        config: &Config,
+        network: &NetworkArgs,
+    ) -> Self {
+        let rlpx_socket = SocketAddr::new(network.resolved_addr(), network.port);
+        let mut enode = NodeRecord::from_secret_key(
+            SocketAddr::new(network.discovery.addr, network.discovery.port),
+            secret_key,
+        );
+        enode.tcp_port = rlpx_socket.port();
+
+        let mut builder = network.discovery.discovery_v5_builder(rlpx_socket, []);
+        if let Some(network_stack_id) = NetworkStackId::id(&chain) {
+            builder = builder.fork(network_stack_id, chain.latest_fork_id());
+        }
+        for (key, value) in config.enr_entries() {
+            builder = builder.add_enr_kv_pair(key.as_bytes(), alloy_rlp::encode(value).into());
+        }
+        let (enr, ..) = reth_discv5::build_local_enr(secret_key, &builder.build());
+
+        Self { peer_id: enode.id, enode, enr }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_chainspec::MAINNET;
+
+    #[test]
+    fn rotation_changes_peer_id() {
+        let dir = tempfile::tempdir().unwrap();
+        let secret_key_path = dir.path().join("discovery-secret");
+        let secret_key = get_secret_key(&secret_key_path).unwrap();
+
+        let rotated = rotate_secret_key(&secret_key_path).unwrap();
+        assert_ne!(pk2id(&rotated.public_key(SECP256K1)), pk2id(&secret_key.public_key(SECP256K1)));
+        assert_eq!(get_secret_key(&secret_key_path).unwrap(), rotated);
+        assert!(!secret_key_path.with_extension("tmp").exists());
+    }
+
+    #[test]
+    fn custom_entries_in_enr() {
+        let mut config = Config::default();
+        config.update_enr_entry("role".to_string(), parse_enr_value("archive").unwrap()).unwrap();
+        config.update_enr_entry("tier".to_string(), parse_enr_value("0x0102").unwrap()).unwrap();
+
+        let secret_key = rng_secret_key();
+        let identity =
+            NodeIdentity::new(&secret_key, MAINNET.clone(), &config, &NetworkArgs::default());
+        assert_eq!(identity.peer_id, pk2id(&secret_key.public_key(SECP256K1)));
+
+        let enr = identity.enr.to_base64().parse::<Enr<SecretKey>>().unwrap();
+        assert_eq!(
+            enr.get_raw_rlp(b"role"),
+            Some(alloy_rlp::encode(Bytes::from_static(b"archive")).as_slice())
+        );
+        assert_eq!(
+            enr.get_raw_rlp(b"tier"),
+            Some(alloy_rlp::encode(Bytes::from_static(&[1, 2])).as_slice())
+        );
+        assert!(enr.get_raw_rlp(b"eth").is_some());
+    }
+}
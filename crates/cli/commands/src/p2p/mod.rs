@@ -16,6 +16,7 @@ use reth_node_core::{
     utils::get_single_header,
 };
 
+mod identity;
 mod rlpx;
 
 /// `reth p2p` command
@@ -71,6 +72,8 @@ pub enum Subcommands {
     },
     // RLPx utilities
     Rlpx(rlpx::Command),
+    // Node identity utilities, which operate on the datadir without starting the network
+    Identity(identity::Command),
 }
 
 impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C> {
@@ -78,6 +81,19 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C>
     pub async fn execute<N: NetworkPrimitives>(self) -> eyre::Result<()> {
         let data_dir = self.datadir.clone().resolve_datadir(self.chain.chain());
         let config_path = self.config.clone().unwrap_or_else(|| data_dir.config());
+        let default_secret_key_path = data_dir.p2p_secret();
+        let secret_key_path =
+            self.network.p2p_secret_key.clone().unwrap_or(default_secret_key_path);
+
+        if let Subcommands::Identity(command) = self.command {
+            return command.execute(
+                self.chain,
+                &secret_key_path,
+                &config_path,
+                &data_dir.db(),
+                &self.network,
+            )
+        }
 
         // Load configuration
         let mut config = Config::from_path(&config_path).unwrap_or_default();
@@ -90,9 +106,6 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C>
 
         config.peers.trusted_nodes_only = self.network.trusted_only;
 
-        let default_secret_key_path = data_dir.p2p_secret();
-        let secret_key_path =
-            self.network.p2p_secret_key.clone().unwrap_or(default_secret_key_path);
         let p2p_secret_key = get_secret_key(&secret_key_path)?;
         let rlpx_socket = (self.network.addr, self.network.port).into();
         let boot_nodes = self.chain.bootnodes().unwrap_or_default();
@@ -158,6 +171,7 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C>
             Subcommands::Rlpx(command) => {
                 command.execute().await?;
             }
+            Subcommands::Identity(_) => unreachable!("executed without starting the network"),
         }
 
         Ok(())
@@ -3,10 +3,18 @@ use crate::common::{AccessRights, CliNodeTypes, Environment, EnvironmentArgs};
 use clap::Parser;
 use reth_chainspec::{EthChainSpec, EthereumHardforks};
 use reth_cli::chainspec::ChainSpecParser;
-use reth_prune::PrunerBuilder;
+use reth_db::DatabaseEnv;
+use reth_node_builder::NodeTypesWithDBAdapter;
+use reth_provider::{DatabaseProviderFactory, ProviderFactory};
+use reth_prune::{segments::PruneSegmentRegistry, PrunerBuilder};
 use reth_static_file::StaticFileProducer;
+use std::sync::Arc;
 use tracing::info;
 
+/// The read-write database provider that the `prune` command prunes the segments with.
+pub type PruneCommandProvider<N> =
+    <ProviderFactory<NodeTypesWithDBAdapter<N, Arc<DatabaseEnv>>> as DatabaseProviderFactory>::ProviderRW;
+
 /// Prunes according to the configuration without any limits
 #[derive(Debug, Parser)]
 pub struct PruneCommand<C: ChainSpecParser> {
@@ -16,7 +24,18 @@ pub struct PruneCommand<C: ChainSpecParser> {
 
 impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> PruneCommand<C> {
     /// Execute the `prune` command
+    ///
+    /// Fails if custom segments are configured, see [`Self::execute_with_custom_segments`].
     pub async fn execute<N: CliNodeTypes<ChainSpec = C::ChainSpec>>(self) -> eyre::Result<()> {
+        self.execute_with_custom_segments::<N>(PruneSegmentRegistry::new()).await
+    }
+
+    /// Execute the `prune` command, that also prunes the custom segments of the registry that are
+    /// configured.
+    pub async fn execute_with_custom_segments<N: CliNodeTypes<ChainSpec = C::ChainSpec>>(
+        self,
+        custom_segments: PruneSegmentRegistry<PruneCommandProvider<N>>,
+    ) -> eyre::Result<()> {
         let Environment { config, provider_factory, .. } = self.env.init::<N>(AccessRights::RW)?;
         let prune_config = config.prune.unwrap_or_default();
 
@@ -34,7 +53,7 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> PruneComma
             // Run the pruner according to the configuration, and don't enforce any limits on it
             let mut pruner = PrunerBuilder::new(prune_config)
                 .delete_limit(usize::MAX)
-                .build_with_provider_factory(provider_factory);
+                .build_with_registry(provider_factory, &custom_segments)?;
 
             pruner.run(prune_tip)?;
             info!(target: "reth::cli", "Pruned data from database");
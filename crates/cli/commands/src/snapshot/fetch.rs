@@ -0,0 +1,969 @@
+//! `reth snapshot fetch` command.
+
+use super::{
+    info::JarInfo,
+    manifest::{Manifest, ManifestFile, ManifestJar},
+};
+use crate::common::{AccessRights, CliNodeTypes, EnvironmentArgs};
+use alloy_consensus::BlockHeader;
+use alloy_primitives::{Address, BlockHash, BlockNumber, Sealable, B256};
+use clap::Parser;
+use eyre::{bail, ensure, OptionExt};
+use futures::{stream, StreamExt};
+use reqwest::{
+    header::{CONTENT_RANGE, RANGE},
+    Client, StatusCode, Url,
+};
+use reth_beacon_consensus::EthBeaconConsensus;
+use reth_chainspec::EthChainSpec;
+use reth_cli::chainspec::ChainSpecParser;
+use reth_consensus::HeaderValidator;
+use reth_db::{
+    init_db,
+    static_file::{HeaderWithHashMask, StaticFileCursor},
+    tables, DatabaseEnv,
+};
+use reth_db_api::{table::Value, transaction::DbTxMut};
+use reth_nippy_jar::{NippyJar, NippyJarChecker, NippyJarError};
+use reth_node_api::{HeaderTy, NodePrimitives};
+use reth_node_builder::NodeTypesWithDBAdapter;
+use reth_primitives::SealedHeader;
+use reth_provider::{
+    providers::{ProviderNodeTypes, StaticFileProvider},
+    BlockNumReader, ChainSpecProvider, DBProvider, HeaderProvider, ProviderError, ProviderFactory,
+    StageCheckpointWriter, StaticFileProviderFactory,
+};
+use reth_stages::{StageCheckpoint, StageId};
+use reth_static_file_types::{SegmentHeader, StaticFileSegment};
+use reth_tasks::shutdown::Shutdown;
+use reth_tokio_util::retry::{AttemptError, RetryError, RetryPolicy};
+use sha2::{Digest, Sha256};
+use std::{
+    io::Read,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use tokio::io::AsyncWriteExt;
+use tracing::{error, info, warn};
+
+/// Name of the directory in the data directory that static files are downloaded to.
+const DOWNLOAD_DIR: &str = "snapshot-download";
+
+/// `reth snapshot fetch` command
+#[derive(Debug, Parser)]
+pub struct Command {
+    /// URL of the manifest of the snapshot.
+    ///
+    /// The static files are downloaded relative to it, and its signature from the same URL with
+    /// a `.sig` suffix.
+    #[arg(long, value_name = "URL")]
+    manifest: Url,
+
+    /// Address that must have signed the manifest.
+    #[arg(long, value_name = "ADDRESS", required_unless_present = "allow_unsigned")]
+    signer: Option<Address>,
+
+    /// Accepts the manifest without verifying its signature.
+    ///
+    /// Requires `--tip`, so that only headers leading up to a trusted header are installed.
+    #[arg(long, conflicts_with = "signer", requires = "tip")]
+    allow_unsigned: bool,
+
+    /// Hash of the highest header of the snapshot.
+    ///
+    /// Nothing is installed unless the headers of the static files lead up to it, and the static
+    /// files above it are ignored.
+    #[arg(long, value_name = "HASH")]
+    tip: Option<BlockHash>,
+
+    /// Number of static files downloaded in parallel.
+    #[arg(long, default_value_t = 4)]
+    concurrency: usize,
+
+    /// Number of times a failed download is retried, resuming where it stopped.
+    #[arg(long, default_value_t = 5)]
+    retries: u32,
+
+    /// Only verifies the static files that were already downloaded or installed against the
+    /// manifest, without downloading or installing anything.
+    #[arg(long)]
+    verify_only: bool,
+}
+
+impl Command {
+    /// Execute `snapshot fetch` command
+    ///
+    /// Failed requests are no longer retried once the shutdown signal fires.
+    pub async fn execute<N, C>(
+        self,
+        env: EnvironmentArgs<C>,
+        shutdown: Shutdown,
+    ) -> eyre::Result<()>
+    where
+        N: CliNodeTypes,
+        C: ChainSpecParser<ChainSpec = N::ChainSpec>,
+    {
+        let data_dir = env.datadir.clone().resolve_datadir(env.chain.chain());
+        let fetcher = Fetcher {
+            client: Client::new(),
+            manifest_url: self.manifest,
+            download_dir: data_dir.data_dir().join(DOWNLOAD_DIR),
+            concurrency: self.concurrency.max(1),
+            retry_policy: RetryPolicy::new(self.retries.saturating_add(1)),
+            shutdown,
+        };
+
+        let manifest = fetcher.fetch_manifest(self.signer).await?;
+        let chain_id = env.chain.chain().id();
+        ensure!(
+            manifest.chain_id == chain_id,
+            "manifest is for chain {}, but the node runs chain {chain_id}",
+            manifest.chain_id
+        );
+
+        if self.verify_only {
+            return verify_jars(
+                &manifest.static_files,
+                &fetcher.download_dir,
+                &data_dir.static_files(),
+            )
+        }
+        let jars = headers_jars(&manifest);
+
+        // Initializes the genesis and repairs the storage before anything is installed. The
+        // storage is then reopened without the consistency checks, which would drop the installed
+        // headers before the stage checkpoints are updated, and without the static file writers
+        // of the genesis initialization, which would be committed over the installed files.
+        drop(env.init::<N>(AccessRights::RW)?);
+        let db = Arc::new(init_db(data_dir.db(), env.db.database_args())?);
+        let factory = ProviderFactory::<NodeTypesWithDBAdapter<N, Arc<DatabaseEnv>>>::new(
+            db,
+            env.chain.clone(),
+            StaticFileProvider::read_write(data_dir.static_files())?,
+        );
+
+        let consensus = EthBeaconConsensus::new(factory.chain_spec());
+        fetcher.fetch(&factory, &consensus, &jars, self.tip).await
+    }
+}
+
+/// Downloads the static files of a manifest and installs them.
+#[derive(Debug)]
+struct Fetcher {
+    client: Client,
+    /// URL of the manifest, which the static files are downloaded relative to.
+    manifest_url: Url,
+    /// Directory that static files are downloaded to, before they're installed.
+    download_dir: PathBuf,
+    /// Number of static files downloaded in parallel.
+    concurrency: usize,
+    /// How failed requests are retried.
+    retry_policy: RetryPolicy,
+    /// Stops retrying failed requests once it fires.
+    shutdown: Shutdown,
+}
+
+impl Fetcher {
+    /// Fetches the manifest, and verifies its signature if there's a signer.
+    async fn fetch_manifest(&self, signer: Option<Address>) -> eyre::Result<Manifest> {
+        let bytes = self.get(&self.manifest_url).await?;
+        match signer {
+            Some(signer) => {
+                let mut signature_url = self.manifest_url.clone();
+                signature_url.set_path(&format!("{}.sig", self.manifest_url.path()));
+                Manifest::verify_signature(&bytes, &self.get(&signature_url).await?, signer)?;
+            }
+            None => {
+                warn!(target: "reth::cli", "Skipping the verification of the manifest signature")
+            }
+        }
+        Manifest::decode(&bytes)
+    }
+
+    async fn get(&self, url: &Url) -> eyre::Result<Vec<u8>> {
+        self.retry_policy
+            .retry_until(self.shutdown.clone(), |attempt| async move {
+                if attempt > 1 {
+                    warn!(target: "reth::cli", %url, attempt, "Retrying request");
+                }
+                let response = self.client.get(url.clone()).send().await?.error_for_status()?;
+                Ok(response.bytes().await?.to_vec())
+            })
+            .await
+            .map_err(retry_error)
+    }
+
+    /// Downloads the headers static files above the installed headers, validates their headers
+    /// and installs them in order up to the first one that fails.
+    ///
+    /// If there's a `tip`, nothing is installed unless the headers lead up to it, and the static
+    /// files above it are ignored.
+    ///
+    /// Downloads that fail are resumed when this is run again. Static files that were downloaded
+    /// but not installed because of a preceding failure are kept, and installed when this is run
+    /// again.
+    async fn fetch<N: ProviderNodeTypes>(
+        &self,
+        factory: &ProviderFactory<N>,
+        consensus: &dyn HeaderValidator<HeaderTy<N>>,
+        jars: &[ManifestJar],
+        tip: Option<BlockHash>,
+    ) -> eyre::Result<()> {
+        if let Some(number) = tip.map(|tip| factory.block_number(tip)).transpose()?.flatten() {
+            info!(target: "reth::cli", number, "Tip is already installed");
+            return Ok(())
+        }
+        reth_fs_util::create_dir_all(&self.download_dir)?;
+
+        let static_file_provider = factory.static_file_provider();
+        let highest = static_file_provider
+            .get_highest_static_file_block(StaticFileSegment::Headers)
+            .ok_or_eyre("genesis header is missing")?;
+        let jars = jars.iter().filter(|jar| jar.block_range.end() > highest).collect::<Vec<_>>();
+        info!(target: "reth::cli", static_files = jars.len(), highest, "Downloading static files");
+
+        let results = stream::iter(&jars)
+            .map(|jar| self.download_jar(jar))
+            .buffered(self.concurrency)
+            .collect::<Vec<_>>()
+            .await;
+
+        let mut parent = static_file_provider
+            .sealed_header(highest)?
+            .ok_or(ProviderError::HeaderNotFound(highest.into()))?;
+        let mut validated = Vec::new();
+        let mut failed = 0;
+        for (jar, result) in jars.iter().zip(results) {
+            let result = match result {
+                Ok(()) if failed == 0 => self.validate_jar(consensus, jar, &parent),
+                Ok(()) => {
+                    warn!(target: "reth::cli", block_range = %jar.block_range, "Static file can't be installed above a failed one");
+                    continue
+                }
+                Err(err) => Err(err),
+            };
+            match result {
+                Ok((hashes, highest_header)) => {
+                    parent = highest_header;
+                    validated.push((*jar, hashes));
+                }
+                Err(err) => {
+                    error!(target: "reth::cli", block_range = %jar.block_range, %err, "Failed to fetch static file");
+                    failed += 1;
+                }
+            }
+        }
+
+        if let Some(tip) = tip {
+            // the headers are only trusted if they lead up to the tip
+            let position = validated
+                .iter()
+                .position(|(_, hashes)| hashes.last().is_some_and(|(_, hash)| *hash == tip));
+            let Some(position) = position else {
+                bail!("the static files don't lead up to the tip {tip}, run the command again")
+            };
+            validated.truncate(position + 1);
+            failed = 0;
+        }
+
+        let mut hashes = Vec::new();
+        let mut highest = highest;
+        for (jar, jar_hashes) in validated {
+            if let Err(err) = self.install_jar(&static_file_provider, jar) {
+                error!(target: "reth::cli", block_range = %jar.block_range, %err, "Failed to install static file");
+                failed += 1;
+                break
+            }
+            info!(target: "reth::cli", block_range = %jar.block_range, "Installed static file");
+            hashes.extend(jar_hashes);
+            highest = jar.block_range.end();
+        }
+
+        if !hashes.is_empty() {
+            let provider_rw = factory.provider_rw()?;
+            for (number, hash) in hashes {
+                provider_rw.tx_ref().put::<tables::HeaderNumbers>(hash, number)?;
+            }
+            // The pipeline resumes downloading headers above the installed ones.
+            provider_rw.save_stage_checkpoint(StageId::Headers, StageCheckpoint::new(highest))?;
+            provider_rw.commit()?;
+        }
+
+        ensure!(
+            failed == 0,
+            "{failed} of {} static files failed, run the command again to resume",
+            jars.len()
+        );
+        info!(target: "reth::cli", highest, "Fetched static files");
+        Ok(())
+    }
+
+    /// Downloads and verifies the files of a static file.
+    async fn download_jar(&self, jar: &ManifestJar) -> eyre::Result<()> {
+        for file in &jar.files {
+            self.download_file(file).await?;
+        }
+
+        let (jar, dir) = (jar.clone(), self.download_dir.clone());
+        tokio::task::spawn_blocking(move || check_jar(&jar, &dir)).await?
+    }
+
+    /// Downloads a file unless it was already downloaded, and verifies it. A file that doesn't
+    /// match the manifest is removed, so that it's downloaded again.
+    async fn download_file(&self, file: &ManifestFile) -> eyre::Result<()> {
+        let path = self.download_dir.join(&file.name);
+        if !path.exists() {
+            let url = self.manifest_url.join(&file.name)?;
+            self.retry_policy
+                .retry_until(self.shutdown.clone(), |attempt| {
+                    if attempt > 1 {
+                        warn!(target: "reth::cli", %url, attempt, "Retrying download");
+                    }
+                    self.download(&url, &path, file.size)
+                })
+                .await
+                .map_err(retry_error)?;
+        }
+
+        let (verified_file, verified_path) = (file.clone(), path.clone());
+        let result =
+            tokio::task::spawn_blocking(move || verify_file(&verified_file, &verified_path))
+                .await?;
+        if result.is_err() {
+            reth_fs_util::remove_file(&path)?;
+        }
+        result
+    }
+
+    /// Downloads a file, resuming the partially downloaded file if there's one.
+    async fn download(&self, url: &Url, path: &Path, size: u64) -> eyre::Result<()> {
+        let mut partial_path = path.as_os_str().to_owned();
+        partial_path.push(".part");
+        let partial_path = PathBuf::from(partial_path);
+
+        let mut offset = tokio::fs::metadata(&partial_path).await.map_or(0, |meta| meta.len());
+        if offset > size {
+            tokio::fs::remove_file(&partial_path).await?;
+            offset = 0;
+        }
+
+        if offset < size {
+            let mut request = self.client.get(url.clone());
+            if offset > 0 {
+                request = request.header(RANGE, format!("bytes={offset}-"));
+            }
+            let mut response = request.send().await?;
+            let mut file =
+                tokio::fs::OpenOptions::new().create(true).append(true).open(&partial_path).await?;
+
+            match response.status() {
+                StatusCode::PARTIAL_CONTENT => {
+                    let content_range = response
+                        .headers()
+                        .get(CONTENT_RANGE)
+                        .and_then(|range| range.to_str().ok())
+                        .unwrap_or_default();
+                    ensure!(
+                        content_range.starts_with(&format!("bytes {offset}-")),
+                        "{url}: unexpected content range {content_range:?}"
+                    );
+                }
+                // The server doesn't support ranges, so the file is downloaded from the start.
+                StatusCode::OK => {
+                    file.set_len(0).await?;
+                    offset = 0;
+                }
+                status => bail!("{url}: {status}"),
+            }
+
+            loop {
+                let chunk = match response.chunk().await {
+                    Ok(Some(chunk)) => chunk,
+                    Ok(None) => break,
+                    Err(err) => {
+                        // the received bytes are resumed from
+                        file.flush().await?;
+                        return Err(err.into())
+                    }
+                };
+                offset += chunk.len() as u64;
+                ensure!(offset <= size, "{url}: more than the expected {size} bytes");
+                file.write_all(&chunk).await?;
+            }
+            file.sync_all().await?;
+        }
+
+        ensure!(offset == size, "{url}: {offset} bytes, expected {size}");
+        tokio::fs::rename(&partial_path, path).await?;
+        Ok(())
+    }
+
+    /// Validates the headers of a downloaded headers static file on top of `parent`, the highest
+    /// installed or validated header, and returns the hashes of the headers above it and the
+    /// highest header.
+    ///
+    /// The hashes are recomputed, the headers must be linked to each other and to `parent`, and
+    /// the headers above `parent` must be valid against it.
+    fn validate_jar<H: BlockHeader + Sealable + Value>(
+        &self,
+        consensus: &dyn HeaderValidator<H>,
+        jar: &ManifestJar,
+        parent: &SealedHeader<H>,
+    ) -> eyre::Result<(Vec<(BlockNumber, BlockHash)>, SealedHeader<H>)> {
+        let (data_file, _) = jar.data_file()?;
+        let start = jar.block_range.start();
+        ensure!(
+            start <= parent.number() + 1,
+            "{} starts at block {start}, but the installed headers end at block {}",
+            data_file.name,
+            parent.number()
+        );
+
+        let nippy_jar = NippyJar::<SegmentHeader>::load(&self.download_dir.join(&data_file.name))?;
+        let mut cursor =
+            StaticFileCursor::new(&nippy_jar, Arc::new(nippy_jar.open_data_reader()?))?;
+
+        let mut hashes = Vec::new();
+        let mut previous: Option<SealedHeader<H>> = None;
+        for number in start..=jar.block_range.end() {
+            let (header, hash) = cursor
+                .get_two::<HeaderWithHashMask<H>>(number.into())?
+                .ok_or(ProviderError::HeaderNotFound(number.into()))?;
+            let header = SealedHeader::seal(header);
+            ensure!(
+                header.number() == number && header.hash() == hash,
+                "{}: header {number} doesn't match its hash",
+                data_file.name
+            );
+
+            if number <= parent.number() {
+                // the headers below the installed ones replace them, and must lead up to them
+                if let Some(previous) = &previous {
+                    ensure!(
+                        header.parent_hash() == previous.hash(),
+                        "{}: header {number} doesn't extend its parent",
+                        data_file.name
+                    );
+                }
+                ensure!(
+                    number < parent.number() || header.hash() == parent.hash(),
+                    "{} doesn't extend the installed headers",
+                    data_file.name
+                );
+            } else {
+                let parent = previous.as_ref().unwrap_or(parent);
+                consensus
+                    .validate_header(&header)
+                    .and_then(|()| consensus.validate_header_against_parent(&header, parent))
+                    .map_err(|err| {
+                        eyre::eyre!("{}: header {number} is invalid: {err}", data_file.name)
+                    })?;
+                hashes.push((number, header.hash()));
+            }
+            previous = Some(header);
+        }
+
+        Ok((hashes, previous.ok_or_eyre("static file has no headers")?))
+    }
+
+    /// Installs a downloaded and validated headers static file on top of the installed headers.
+    fn install_jar<N: NodePrimitives<BlockHeader: Value>>(
+        &self,
+        provider: &StaticFileProvider<N>,
+        jar: &ManifestJar,
+    ) -> eyre::Result<()> {
+        let (data_file, expected_block_range) = jar.data_file()?;
+        ensure!(
+            provider.find_fixed_range(jar.block_range.start()) == expected_block_range,
+            "{} doesn't match the block ranges of the installed static files",
+            data_file.name
+        );
+
+        // The static file replaces the installed one of the same block range, if there's one.
+        provider.remove_cached_provider(StaticFileSegment::Headers, expected_block_range.end());
+        for file in &jar.files {
+            reth_fs_util::rename(
+                self.download_dir.join(&file.name),
+                provider.directory().join(&file.name),
+            )?;
+        }
+        provider.initialize_index()?;
+
+        Ok(())
+    }
+}
+
+/// Returns the error of the last attempt of a retried request.
+fn retry_error(err: RetryError<eyre::Report>) -> eyre::Report {
+    match err {
+        RetryError::Exhausted { error: AttemptError::Failed(err), .. } => err,
+        RetryError::Exhausted { error: AttemptError::TimedOut(timeout), .. } => {
+            eyre::eyre!("request timed out after {timeout:?}")
+        }
+        RetryError::Cancelled => eyre::eyre!("request was cancelled at shutdown"),
+    }
+}
+
+/// Returns the headers static files of the manifest, sorted by block.
+///
+/// Only headers can be installed from static files alone. The transactions static files depend on
+/// the block body indices, ommers and withdrawals in the database, and the receipts static files
+/// on the state, so they're only verified with `--verify-only`.
+fn headers_jars(manifest: &Manifest) -> Vec<ManifestJar> {
+    let (mut headers, other): (Vec<_>, Vec<_>) = manifest
+        .static_files
+        .iter()
+        .cloned()
+        .partition(|jar| jar.segment == StaticFileSegment::Headers);
+    for jar in other {
+        warn!(target: "reth::cli", segment = %jar.segment, block_range = %jar.block_range, "Skipping static file, only headers can be installed");
+    }
+    headers.sort_unstable_by_key(|jar| jar.block_range.start());
+    headers
+}
+
+/// Verifies the static files that were downloaded, or installed otherwise, against the manifest.
+fn verify_jars(
+    jars: &[ManifestJar],
+    download_dir: &Path,
+    static_files_dir: &Path,
+) -> eyre::Result<()> {
+    let mut failed = 0;
+    for jar in jars {
+        let (data_file, _) = jar.data_file()?;
+        let dir = if download_dir.join(&data_file.name).exists() {
+            download_dir
+        } else {
+            static_files_dir
+        };
+        let path = dir.join(&data_file.name);
+
+        let result = jar.files.iter().try_for_each(|file| verify_file(file, &dir.join(&file.name)));
+        match result.and_then(|()| check_jar(jar, dir)) {
+            Ok(()) => info!(target: "reth::cli", ?path, "Static file is valid"),
+            Err(err) => {
+                error!(target: "reth::cli", ?path, %err, "Static file is invalid");
+                failed += 1;
+            }
+        }
+    }
+
+    ensure!(failed == 0, "{failed} of {} static files are invalid", jars.len());
+    Ok(())
+}
+
+/// Verifies the size and hash of a file.
+fn verify_file(file: &ManifestFile, path: &Path) -> eyre::Result<()> {
+    let size = reth_fs_util::metadata(path)?.len();
+    ensure!(size == file.size, "{} has {size} bytes, expected {}", file.name, file.size);
+
+    let mut reader = reth_fs_util::open(path)?;
+    let mut hasher = Sha256::new();
+    let mut buf = vec![0; 1 << 16];
+    loop {
+        let read = reader.read(&mut buf)?;
+        if read == 0 {
+            break
+        }
+        hasher.update(&buf[..read]);
+    }
+    let hash = B256::from_slice(&hasher.finalize());
+    ensure!(hash == file.sha256, "{} has hash {hash}, expected {}", file.name, file.sha256);
+
+    Ok(())
+}
+
+/// Checks that this binary can read the static file, that it holds the blocks of the manifest,
+/// and that its offsets are consistent with its data.
+fn check_jar(jar: &ManifestJar, dir: &Path) -> eyre::Result<()> {
+    let (data_file, _) = jar.data_file()?;
+    let path = dir.join(&data_file.name);
+
+    let info = JarInfo::inspect(&path);
+    if let Some(err) = info.compatibility.errors.first() {
+        bail!("{}: {err}", data_file.name)
+    }
+    let config = info.config.ok_or_eyre("configuration can't be decoded")?;
+    ensure!(
+        config.segment == jar.segment && config.block_range == Some(jar.block_range),
+        "{} holds {} blocks {:?}, expected blocks {}",
+        data_file.name,
+        config.segment,
+        config.block_range,
+        jar.block_range
+    );
+
+    match NippyJarChecker::new(NippyJar::<SegmentHeader>::load(&path)?).check_consistency() {
+        // The offsets of frozen jars are verified when they're written, and can't be healed.
+        Ok(()) | Err(NippyJarError::FrozenJar) => Ok(()),
+        Err(err) => bail!("{}: {err}", data_file.name),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::snapshot::info::list_jars;
+    use alloy_primitives::{eip191_hash_message, hex, U256};
+    use reth_chainspec::MAINNET;
+    use reth_consensus::test_utils::TestConsensus;
+    use reth_db::test_utils::create_test_rw_db;
+    use reth_db_common::init::init_genesis;
+    use reth_primitives::{public_key_to_address, sign_message, EthPrimitives};
+    use reth_provider::{
+        test_utils::MockNodeTypesWithDB, BlockHashReader, StageCheckpointReader, StaticFileWriter,
+    };
+    use reth_tasks::shutdown::signal;
+    use reth_testing_utils::generators;
+    use secp256k1::Keypair;
+    use std::{
+        collections::{HashMap, HashSet},
+        sync::Mutex,
+    };
+    use tokio::{
+        io::AsyncReadExt,
+        net::{TcpListener, TcpStream},
+    };
+
+    /// Number of blocks per static file of the generated snapshots.
+    const BLOCKS_PER_FILE: u64 = 5;
+
+    /// Paths and range offsets of the requests of a [`serve`] server.
+    type Requests = Arc<Mutex<Vec<(String, Option<u64>)>>>;
+
+    /// Returns the mainnet genesis header and random headers on top of it.
+    fn random_headers(count: u64) -> Vec<SealedHeader> {
+        let genesis = SealedHeader::new(MAINNET.genesis_header().clone(), MAINNET.genesis_hash());
+        let mut headers = vec![genesis];
+        headers.extend(generators::random_header_range(
+            &mut generators::rng(),
+            1..count,
+            MAINNET.genesis_hash(),
+        ));
+        headers
+    }
+
+    /// Writes headers static files with the headers.
+    fn write_headers(dir: &Path, headers: &[SealedHeader]) {
+        let provider = StaticFileProvider::<EthPrimitives>::read_write(dir)
+            .unwrap()
+            .with_custom_blocks_per_file(BLOCKS_PER_FILE);
+        let mut writer = provider.latest_writer(StaticFileSegment::Headers).unwrap();
+        for header in headers {
+            writer.append_header(header.header(), U256::ZERO, &header.hash()).unwrap();
+        }
+        writer.commit().unwrap();
+    }
+
+    /// Returns a provider factory with the genesis initialized, and the directory of its static
+    /// files.
+    fn genesis_factory(datadir: &Path) -> ProviderFactory<MockNodeTypesWithDB> {
+        let db = create_test_rw_db();
+        let static_file_provider = || {
+            StaticFileProvider::read_write(datadir.join("static_files"))
+                .unwrap()
+                .with_custom_blocks_per_file(BLOCKS_PER_FILE)
+        };
+        init_genesis(&ProviderFactory::<MockNodeTypesWithDB>::new(
+            db.clone(),
+            MAINNET.clone(),
+            static_file_provider(),
+        ))
+        .unwrap();
+        ProviderFactory::new(db, MAINNET.clone(), static_file_provider())
+    }
+
+    /// Returns the manifest of the static files in the directory.
+    fn manifest(dir: &Path) -> Manifest {
+        let static_files = list_jars(dir)
+            .unwrap()
+            .into_iter()
+            .map(|path| {
+                let config = JarInfo::inspect(&path).config.unwrap();
+                let files = [path.clone(), path.with_extension("off"), path.with_extension("conf")]
+                    .iter()
+                    .map(|path| {
+                        let bytes = reth_fs_util::read(path).unwrap();
+                        ManifestFile {
+                            name: path.file_name().unwrap().to_string_lossy().into_owned(),
+                            size: bytes.len() as u64,
+                            sha256: B256::from_slice(&Sha256::digest(&bytes)),
+                        }
+                    })
+                    .collect();
+                ManifestJar {
+                    segment: config.segment,
+                    block_range: config.block_range.unwrap(),
+                    files,
+                }
+            })
+            .collect();
+        Manifest { chain_id: MAINNET.chain().id(), static_files }
+    }
+
+    /// Returns the files to serve for the signed manifest and the static files in the directory.
+    fn snapshot_files(
+        dir: &Path,
+        manifest: &Manifest,
+        key_pair: &Keypair,
+    ) -> HashMap<String, Vec<u8>> {
+        let mut files = HashMap::new();
+        for jar in &manifest.static_files {
+            for file in &jar.files {
+                files.insert(file.name.clone(), reth_fs_util::read(dir.join(&file.name)).unwrap());
+            }
+        }
+
+        let bytes = serde_json::to_vec(manifest).unwrap();
+        let signature =
+            sign_message(B256::from_slice(&key_pair.secret_bytes()), eip191_hash_message(&bytes))
+                .unwrap();
+        files.insert("manifest.json.sig".to_string(), hex::encode(signature.as_bytes()).into());
+        files.insert("manifest.json".to_string(), bytes);
+        files
+    }
+
+    /// Serves the files over HTTP with support for range requests, and returns the URL of the
+    /// manifest. The first response for each of the `truncated` files is cut off halfway.
+    async fn serve(files: HashMap<String, Vec<u8>>, truncated: HashSet<String>) -> (Url, Requests) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/snapshot/manifest.json", listener.local_addr().unwrap());
+        let requests = Requests::default();
+
+        let files = Arc::new(files);
+        let truncated = Arc::new(Mutex::new(truncated));
+        let server_requests = requests.clone();
+        tokio::spawn(async move {
+            loop {
+                let (stream, _) = listener.accept().await.unwrap();
+                tokio::spawn(respond(
+                    stream,
+                    files.clone(),
+                    truncated.clone(),
+                    server_requests.clone(),
+                ));
+            }
+        });
+
+        (Url::parse(&url).unwrap(), requests)
+    }
+
+    async fn respond(
+        mut stream: TcpStream,
+        files: Arc<HashMap<String, Vec<u8>>>,
+        truncated: Arc<Mutex<HashSet<String>>>,
+        requests: Requests,
+    ) {
+        let mut request = Vec::new();
+        let mut buf = [0; 1024];
+        while !request.windows(4).any(|window| window == b"\r\n\r\n") {
+            let read = stream.read(&mut buf).await.unwrap();
+            if read == 0 {
+                return
+            }
+            request.extend_from_slice(&buf[..read]);
+        }
+
+        let request = String::from_utf8_lossy(&request).to_ascii_lowercase();
+        let path = request.split_whitespace().nth(1).unwrap();
+        let name = path.trim_start_matches("/snapshot/").to_string();
+        let offset = request.lines().find_map(|line| {
+            line.strip_prefix("range: bytes=")?.trim_end_matches('-').parse::<u64>().ok()
+        });
+        requests.lock().unwrap().push((name.clone(), offset));
+
+        let response = match files.get(&name) {
+            Some(body) => {
+                let offset = offset.unwrap_or_default() as usize;
+                let mut response = if offset > 0 {
+                    format!(
+                        "HTTP/1.1 206 Partial Content\r\ncontent-range: bytes {offset}-{}/{}\r\n",
+                        body.len() - 1,
+                        body.len()
+                    )
+                } else {
+                    "HTTP/1.1 200 OK\r\n".to_string()
+                };
+                let body = &body[offset..];
+                response += &format!("content-length: {}\r\nconnection: close\r\n\r\n", body.len());
+
+                // as if the connection dropped
+                let len = if truncated.lock().unwrap().remove(&name) {
+                    body.len() / 2
+                } else {
+                    body.len()
+                };
+                let mut response = response.into_bytes();
+                response.extend_from_slice(&body[..len]);
+                response
+            }
+            None => {
+                b"HTTP/1.1 404 Not Found\r\ncontent-length: 0\r\nconnection: close\r\n\r\n".to_vec()
+            }
+        };
+        let _ = stream.write_all(&response).await;
+        let _ = stream.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn fetches_and_installs_headers() {
+        let source = tempfile::tempdir().unwrap();
+        let headers = random_headers(3 * BLOCKS_PER_FILE);
+        write_headers(source.path(), &headers);
+        let mut manifest = manifest(source.path());
+        assert_eq!(manifest.static_files.len(), 3);
+
+        let key_pair = Keypair::new_global(&mut generators::rng());
+        let signer = public_key_to_address(key_pair.public_key());
+
+        // the last static file doesn't match its hash, and the first one is cut off once
+        let data_file =
+            |manifest: &Manifest, index: usize| manifest.static_files[index].files[0].clone();
+        let hash = data_file(&manifest, 2).sha256;
+        manifest.static_files[2].files[0].sha256 = B256::ZERO;
+        let truncated = data_file(&manifest, 0).name;
+        let (url, requests) = serve(
+            snapshot_files(source.path(), &manifest, &key_pair),
+            HashSet::from([truncated.clone()]),
+        )
+        .await;
+
+        let datadir = tempfile::tempdir().unwrap();
+        let factory = genesis_factory(datadir.path());
+        let consensus = TestConsensus::default();
+
+        let (_signal, shutdown) = signal();
+        let mut fetcher = Fetcher {
+            client: Client::new(),
+            manifest_url: url,
+            download_dir: datadir.path().join(DOWNLOAD_DIR),
+            concurrency: 2,
+            retry_policy: RetryPolicy::new(2),
+            shutdown,
+        };
+        assert!(fetcher.fetch_manifest(Some(Address::ZERO)).await.is_err());
+        let jars = headers_jars(&fetcher.fetch_manifest(Some(signer)).await.unwrap());
+
+        // the static files before the failed one are installed
+        let err = fetcher.fetch(&factory, &consensus, &jars, None).await.unwrap_err();
+        assert!(err.to_string().starts_with("1 of 3 static files failed"), "{err}");
+        assert_eq!(
+            factory
+                .static_file_provider()
+                .get_highest_static_file_block(StaticFileSegment::Headers),
+            Some(9)
+        );
+        assert!(!fetcher.download_dir.join(data_file(&manifest, 2).name).exists());
+
+        // the cut off file was resumed
+        let attempts =
+            requests.lock().unwrap().iter().filter(|(name, _)| *name == truncated).count();
+        assert_eq!(attempts, 2);
+        assert!(requests
+            .lock()
+            .unwrap()
+            .iter()
+            .any(|(name, offset)| *name == truncated && offset.is_some_and(|offset| offset > 0)));
+
+        let check_installed = |highest: BlockNumber| {
+            let provider = factory.provider().unwrap();
+            for header in &headers[..=highest as usize] {
+                assert_eq!(provider.block_hash(header.number).unwrap(), Some(header.hash()));
+                assert_eq!(provider.block_number(header.hash()).unwrap(), Some(header.number));
+            }
+            assert_eq!(
+                provider.get_stage_checkpoint(StageId::Headers).unwrap(),
+                Some(StageCheckpoint::new(highest))
+            );
+        };
+        check_installed(9);
+
+        // only the failed static file is downloaded again
+        manifest.static_files[2].files[0].sha256 = hash;
+        let (url, requests) =
+            serve(snapshot_files(source.path(), &manifest, &key_pair), HashSet::new()).await;
+        fetcher.manifest_url = url;
+        let jars = headers_jars(&fetcher.fetch_manifest(Some(signer)).await.unwrap());
+        fetcher.fetch(&factory, &consensus, &jars, None).await.unwrap();
+        check_installed(14);
+        let requested =
+            requests.lock().unwrap().iter().map(|(name, _)| name.clone()).collect::<HashSet<_>>();
+        assert!(requested.contains(&data_file(&manifest, 2).name));
+        assert!(!requested.contains(&data_file(&manifest, 1).name));
+
+        // the installed static files are verified
+        let static_files_dir = datadir.path().join("static_files");
+        verify_jars(&jars, &fetcher.download_dir, &static_files_dir).unwrap();
+
+        let path = static_files_dir.join(data_file(&manifest, 1).name);
+        let mut bytes = reth_fs_util::read(&path).unwrap();
+        bytes[0] ^= 1;
+        reth_fs_util::write(&path, bytes).unwrap();
+        let err = verify_jars(&jars, &fetcher.download_dir, &static_files_dir).unwrap_err();
+        assert_eq!(err.to_string(), "1 of 3 static files are invalid");
+    }
+
+    /// Serves a snapshot of the headers, fetches it with a consensus that fails the validation
+    /// or not, and returns the result and the highest installed header.
+    async fn fetch_headers(
+        headers: &[SealedHeader],
+        fail_validation: bool,
+        tip: Option<BlockHash>,
+    ) -> (eyre::Result<()>, BlockNumber) {
+        let source = tempfile::tempdir().unwrap();
+        write_headers(source.path(), headers);
+        let key_pair = Keypair::new_global(&mut generators::rng());
+        let files = snapshot_files(source.path(), &manifest(source.path()), &key_pair);
+        let (url, _) = serve(files, HashSet::new()).await;
+
+        let datadir = tempfile::tempdir().unwrap();
+        let factory = genesis_factory(datadir.path());
+        let consensus = TestConsensus::default();
+        consensus.set_fail_validation(fail_validation);
+        let (_signal, shutdown) = signal();
+        let fetcher = Fetcher {
+            client: Client::new(),
+            manifest_url: url,
+            download_dir: datadir.path().join(DOWNLOAD_DIR),
+            concurrency: 2,
+            retry_policy: RetryPolicy::new(1),
+            shutdown,
+        };
+        let jars = headers_jars(&fetcher.fetch_manifest(None).await.unwrap());
+        let result = fetcher.fetch(&factory, &consensus, &jars, tip).await;
+        let highest = factory
+            .static_file_provider()
+            .get_highest_static_file_block(StaticFileSegment::Headers)
+            .unwrap();
+        (result, highest)
+    }
+
+    #[tokio::test]
+    async fn rejects_invalid_headers() {
+        let headers = random_headers(3 * BLOCKS_PER_FILE);
+
+        // a header that doesn't match its hash
+        let mut tampered = headers.clone();
+        let (mut header, hash) = tampered[7].clone().split();
+        header.gas_used += 1;
+        tampered[7] = SealedHeader::new(header, hash);
+        let (result, highest) = fetch_headers(&tampered, false, None).await;
+        assert!(result.unwrap_err().to_string().starts_with("1 of 3 static files failed"));
+        assert_eq!(highest, 4);
+
+        // headers that aren't valid
+        let (result, highest) = fetch_headers(&headers, true, None).await;
+        assert!(result.unwrap_err().to_string().starts_with("1 of 3 static files failed"));
+        assert_eq!(highest, 0);
+
+        // nothing is installed unless the headers lead up to the tip
+        let (result, highest) = fetch_headers(&headers, false, Some(B256::ZERO)).await;
+        assert!(result.unwrap_err().to_string().starts_with("the static files don't lead up to"));
+        assert_eq!(highest, 0);
+
+        // the static files above the tip are ignored
+        let (result, highest) = fetch_headers(&headers, false, Some(headers[9].hash())).await;
+        result.unwrap();
+        assert_eq!(highest, 9);
+    }
+}
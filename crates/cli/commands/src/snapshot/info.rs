@@ -0,0 +1,480 @@
+//! `reth snapshot info` command.
+
+use clap::Parser;
+use comfy_table::{Cell, Row, Table as ComfyTable};
+use human_bytes::human_bytes;
+use reth_nippy_jar::{
+    compression::{Compression, Compressors},
+    NippyJar, CONFIG_FILE_EXTENSION,
+};
+use reth_static_file_types::{SegmentHeader, SegmentRangeInclusive, StaticFileSegment};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+/// `reth snapshot info` command
+#[derive(Debug, Parser)]
+pub struct Command {
+    /// Path to the data file of a static file, e.g.
+    /// `static_files/static_file_headers_0_499999`.
+    #[arg(value_name = "PATH", required_unless_present = "all", conflicts_with = "all")]
+    path: Option<PathBuf>,
+
+    /// Inspects all static files of the data directory.
+    #[arg(long)]
+    all: bool,
+
+    /// Prints the information as JSON.
+    #[arg(long)]
+    json: bool,
+}
+
+impl Command {
+    /// Execute `snapshot info` command
+    pub fn execute(self, static_files_dir: PathBuf) -> eyre::Result<()> {
+        let jars = match self.path {
+            Some(path) => vec![JarInfo::inspect(&data_path(path))],
+            None => {
+                list_jars(&static_files_dir)?.iter().map(|path| JarInfo::inspect(path)).collect()
+            }
+        };
+
+        if self.json {
+            println!("{}", serde_json::to_string_pretty(&jars)?);
+            return Ok(())
+        }
+
+        println!("{}", jars_table(&jars));
+        for jar in &jars {
+            for error in &jar.compatibility.errors {
+                println!("{}: error: {error}", jar.path.display());
+            }
+            for warning in &jar.compatibility.warnings {
+                println!("{}: warning: {warning}", jar.path.display());
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Configuration of a static file and whether this binary can read it.
+#[derive(Debug, Serialize)]
+pub struct JarInfo {
+    /// Path of the data file.
+    pub path: PathBuf,
+    /// Sizes of the files of the jar.
+    pub sizes: FileSizes,
+    /// Configuration of the jar, `None` if it couldn't be decoded.
+    pub config: Option<JarConfig>,
+    /// Whether this binary can read the jar.
+    pub compatibility: Compatibility,
+}
+
+/// Sizes of the files of a jar in bytes, `None` if the file is missing.
+#[derive(Debug, Default, Serialize)]
+pub struct FileSizes {
+    /// Size of the data file.
+    pub data: Option<u64>,
+    /// Size of the offsets file.
+    pub offsets: Option<u64>,
+    /// Size of the configuration file.
+    pub config: Option<u64>,
+}
+
+impl FileSizes {
+    /// Returns the total size of all files of the jar.
+    pub fn total(&self) -> u64 {
+        [self.data, self.offsets, self.config].into_iter().flatten().sum()
+    }
+}
+
+/// Configuration of a jar, as read from its configuration file.
+#[derive(Debug, Serialize)]
+pub struct JarConfig {
+    /// Segment of the static file.
+    pub segment: StaticFileSegment,
+    /// Block range the static file is expected to hold.
+    pub expected_block_range: SegmentRangeInclusive,
+    /// Block range the static file holds.
+    pub block_range: Option<SegmentRangeInclusive>,
+    /// Transaction range the static file holds.
+    pub tx_range: Option<SegmentRangeInclusive>,
+    /// Version of the jar format.
+    pub version: usize,
+    /// Number of rows.
+    pub rows: usize,
+    /// Number of columns.
+    pub columns: usize,
+    /// Maximum uncompressed row size.
+    pub max_row_size: usize,
+    /// Whether the offsets file is delta encoded.
+    pub delta_offsets: bool,
+    /// Compression of the jar, `None` if uncompressed.
+    pub compression: Option<CompressionInfo>,
+    /// Whether the jar has an inclusion filter.
+    pub filter: bool,
+}
+
+/// Compression settings of a jar.
+#[derive(Debug, Serialize)]
+pub struct CompressionInfo {
+    /// Name of the compression algorithm.
+    pub algorithm: &'static str,
+    /// Compression level, if the algorithm has one.
+    pub level: Option<i32>,
+    /// Whether the columns are compressed with dictionaries.
+    pub dictionaries: bool,
+}
+
+impl std::fmt::Display for CompressionInfo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.algorithm)?;
+        if let Some(level) = self.level.filter(|level| *level != 0) {
+            write!(f, " (level {level})")?;
+        }
+        if self.dictionaries {
+            write!(f, " (dictionaries)")?;
+        }
+        Ok(())
+    }
+}
+
+impl From<&Compressors> for CompressionInfo {
+    fn from(compressor: &Compressors) -> Self {
+        match compressor {
+            Compressors::Zstd(zstd) => Self {
+                algorithm: compressor.name(),
+                level: Some(zstd.level()),
+                dictionaries: zstd.use_dict,
+            },
+            Compressors::Lz4(_) => {
+                Self { algorithm: compressor.name(), level: None, dictionaries: false }
+            }
+        }
+    }
+}
+
+/// Whether a jar can be read by this binary.
+#[derive(Debug, Default, Serialize)]
+pub struct Compatibility {
+    /// Whether the jar can be read.
+    pub compatible: bool,
+    /// Reasons the jar can't be read.
+    pub errors: Vec<String>,
+    /// Problems that don't prevent reading the jar.
+    pub warnings: Vec<String>,
+}
+
+impl JarInfo {
+    /// Inspects the jar with the given data file path.
+    pub fn inspect(path: &Path) -> Self {
+        let jar = NippyJar::<SegmentHeader>::load(path);
+        let sizes = FileSizes {
+            data: file_size(path),
+            offsets: jar.as_ref().ok().and_then(|jar| file_size(&jar.offsets_path())),
+            config: file_size(&path.with_extension(CONFIG_FILE_EXTENSION)),
+        };
+        let mut compatibility = Compatibility::default();
+
+        let config = match jar {
+            Ok(jar) => {
+                check_jar(path, &jar, &sizes, &mut compatibility);
+                let header = jar.user_header();
+                Some(JarConfig {
+                    segment: header.segment(),
+                    expected_block_range: *header.expected_block_range(),
+                    block_range: header.block_range().copied(),
+                    tx_range: header.tx_range().copied(),
+                    version: jar.version(),
+                    rows: jar.rows(),
+                    columns: jar.columns(),
+                    max_row_size: jar.max_row_size(),
+                    delta_offsets: jar.has_delta_offsets(),
+                    compression: jar.compressor().map(Into::into),
+                    filter: jar.has_filter(),
+                })
+            }
+            Err(err) => {
+                compatibility.errors.push(format!("configuration can't be decoded: {err}"));
+                None
+            }
+        };
+        compatibility.compatible = compatibility.errors.is_empty();
+
+        Self { path: path.to_path_buf(), sizes, config, compatibility }
+    }
+}
+
+/// Checks the configuration of a jar against what this binary expects.
+fn check_jar(
+    path: &Path,
+    jar: &NippyJar<SegmentHeader>,
+    sizes: &FileSizes,
+    compatibility: &mut Compatibility,
+) {
+    let header = jar.user_header();
+    let segment = header.segment();
+
+    if jar.columns() != segment.columns() {
+        compatibility.errors.push(format!(
+            "{} columns, but {segment} static files have {}",
+            jar.columns(),
+            segment.columns()
+        ));
+    }
+    if jar.has_filter() {
+        compatibility.errors.push("inclusion filters are no longer supported".to_string());
+    }
+    if jar.compressor().is_some_and(|compressor| !compressor.is_ready()) {
+        compatibility.errors.push("compression dictionaries are missing".to_string());
+    }
+
+    match path
+        .file_name()
+        .and_then(|name| StaticFileSegment::parse_filename(&name.to_string_lossy()))
+    {
+        Some((name_segment, name_range)) => {
+            if name_segment != segment || name_range != *header.expected_block_range() {
+                compatibility.errors.push(format!(
+                    "file name doesn't match the header, which holds {segment} for blocks {}",
+                    header.expected_block_range()
+                ));
+            }
+        }
+        None => compatibility
+            .warnings
+            .push("file name doesn't follow the naming scheme, the node won't load it".to_string()),
+    }
+
+    if let Some(block_range) = header.block_range() {
+        let expected = header.expected_block_range();
+        if block_range.start() < expected.start() || block_range.end() > expected.end() {
+            compatibility.errors.push(format!(
+                "block range {block_range} exceeds the expected block range {expected}"
+            ));
+        }
+    }
+
+    match (sizes.data, jar.data_len()) {
+        (None, _) => compatibility.errors.push("data file is missing".to_string()),
+        (Some(size), Some(committed)) if size != committed => compatibility.warnings.push(format!(
+            "data file has {size} bytes, but {committed} were committed, the node heals it on \
+             startup"
+        )),
+        _ => {}
+    }
+    if sizes.offsets.is_none() {
+        compatibility.errors.push("offsets file is missing".to_string());
+    }
+}
+
+/// Returns the paths of the data files of all static files in the directory, sorted by segment
+/// and block range.
+pub fn list_jars(static_files_dir: &Path) -> eyre::Result<Vec<PathBuf>> {
+    let mut jars = Vec::new();
+    for entry in reth_fs_util::read_dir(static_files_dir)? {
+        let entry = entry?;
+        if let Some((segment, range)) =
+            StaticFileSegment::parse_filename(&entry.file_name().to_string_lossy())
+        {
+            jars.push((segment, range.start(), entry.path()));
+        }
+    }
+    jars.sort();
+
+    Ok(jars.into_iter().map(|(_, _, path)| path).collect())
+}
+
+/// Returns the data file path of a jar, given the path to any of its files.
+fn data_path(path: PathBuf) -> PathBuf {
+    let is_jar_file = path.extension().is_some_and(|extension| {
+        [CONFIG_FILE_EXTENSION, "off", "idx"].contains(&&*extension.to_string_lossy())
+    });
+    if is_jar_file {
+        path.with_extension("")
+    } else {
+        path
+    }
+}
+
+fn file_size(path: &Path) -> Option<u64> {
+    path.metadata().ok().map(|metadata| metadata.len())
+}
+
+fn jars_table(jars: &[JarInfo]) -> ComfyTable {
+    let mut table = ComfyTable::new();
+    table.load_preset(comfy_table::presets::ASCII_MARKDOWN);
+    table.set_header([
+        "File",
+        "Segment",
+        "Block Range",
+        "Transaction Range",
+        "Rows",
+        "Columns",
+        "Compression",
+        "Filter",
+        "Size",
+        "Compatible",
+    ]);
+
+    for jar in jars {
+        let file_name = jar
+            .path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let mut row = Row::new();
+        row.add_cell(Cell::new(file_name));
+        match &jar.config {
+            Some(config) => {
+                row.add_cell(Cell::new(config.segment))
+                    .add_cell(Cell::new(format_range(config.block_range)))
+                    .add_cell(Cell::new(format_range(config.tx_range)))
+                    .add_cell(Cell::new(config.rows))
+                    .add_cell(Cell::new(config.columns))
+                    .add_cell(Cell::new(
+                        config
+                            .compression
+                            .as_ref()
+                            .map_or_else(|| "none".to_string(), ToString::to_string),
+                    ))
+                    .add_cell(Cell::new(if config.filter { "unsupported" } else { "none" }));
+            }
+            None => {
+                for _ in 0..7 {
+                    row.add_cell(Cell::new("?"));
+                }
+            }
+        }
+        row.add_cell(Cell::new(human_bytes(jar.sizes.total() as f64)))
+            .add_cell(Cell::new(if jar.compatibility.compatible { "yes" } else { "no" }));
+        table.add_row(row);
+    }
+
+    table
+}
+
+fn format_range(range: Option<SegmentRangeInclusive>) -> String {
+    range.map_or_else(|| "-".to_string(), |range| range.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_nippy_jar::NippyJarWriter;
+
+    /// Writes a jar with the given header and rows of incrementing numbers.
+    fn write_jar(
+        dir: &Path,
+        header: SegmentHeader,
+        columns: usize,
+        rows: u64,
+        lz4: bool,
+    ) -> PathBuf {
+        let path = dir.join(header.segment().filename(header.expected_block_range()));
+        let mut jar = NippyJar::new(columns, &path, header);
+        if lz4 {
+            jar = jar.with_lz4();
+        }
+
+        let mut writer = NippyJarWriter::new(jar).unwrap();
+        let column = || (0..rows).map(|row| Ok(row.to_be_bytes()));
+        writer.append_rows((0..columns).map(|_| column()).collect(), rows).unwrap();
+        writer.commit().unwrap();
+        path
+    }
+
+    #[test]
+    fn inspects_generated_jars() {
+        let dir = tempfile::tempdir().unwrap();
+        let expected_range = SegmentRangeInclusive::new(0, 499_999);
+        let block_range = SegmentRangeInclusive::new(0, 9);
+        let tx_range = SegmentRangeInclusive::new(0, 19);
+
+        let headers = write_jar(
+            dir.path(),
+            SegmentHeader::new(expected_range, Some(block_range), None, StaticFileSegment::Headers),
+            StaticFileSegment::Headers.columns(),
+            10,
+            true,
+        );
+        let receipts = write_jar(
+            dir.path(),
+            SegmentHeader::new(
+                expected_range,
+                Some(block_range),
+                Some(tx_range),
+                StaticFileSegment::Receipts,
+            ),
+            StaticFileSegment::Receipts.columns(),
+            20,
+            false,
+        );
+        // a jar with more columns than the segment has
+        let transactions = write_jar(
+            dir.path(),
+            SegmentHeader::new(
+                expected_range,
+                Some(block_range),
+                Some(tx_range),
+                StaticFileSegment::Transactions,
+            ),
+            2,
+            20,
+            false,
+        );
+
+        let jars = list_jars(dir.path()).unwrap();
+        assert_eq!(jars, vec![headers.clone(), transactions, receipts]);
+        let jars = jars.iter().map(|path| JarInfo::inspect(path)).collect::<Vec<_>>();
+
+        let headers_info = &jars[0];
+        let config = headers_info.config.as_ref().unwrap();
+        assert_eq!(config.segment, StaticFileSegment::Headers);
+        assert_eq!(config.expected_block_range, expected_range);
+        assert_eq!(config.block_range, Some(block_range));
+        assert_eq!(config.tx_range, None);
+        assert_eq!(config.rows, 10);
+        assert_eq!(config.columns, 3);
+        assert_eq!(config.compression.as_ref().unwrap().algorithm, "lz4");
+        assert!(!config.filter);
+        assert!(headers_info.compatibility.compatible, "{:?}", headers_info.compatibility);
+        assert!(headers_info.compatibility.warnings.is_empty());
+        assert!(headers_info.sizes.data.unwrap() > 0);
+
+        let transactions_info = &jars[1];
+        assert!(!transactions_info.compatibility.compatible);
+        assert_eq!(transactions_info.compatibility.errors.len(), 1);
+
+        let receipts_info = &jars[2];
+        let config = receipts_info.config.as_ref().unwrap();
+        assert_eq!(config.segment, StaticFileSegment::Receipts);
+        assert_eq!(config.block_range, Some(block_range));
+        assert_eq!(config.tx_range, Some(tx_range));
+        assert_eq!(config.rows, 20);
+        assert_eq!(config.columns, 1);
+        assert!(config.compression.is_none());
+        assert!(receipts_info.compatibility.compatible, "{:?}", receipts_info.compatibility);
+
+        // any file of the jar can be passed
+        let info = JarInfo::inspect(&data_path(headers.with_extension(CONFIG_FILE_EXTENSION)));
+        assert_eq!(info.path, headers);
+
+        let json = serde_json::to_value(&jars).unwrap();
+        assert_eq!(json[2]["config"]["tx_range"]["end"], 19);
+        assert_eq!(json[1]["compatibility"]["compatible"], false);
+    }
+
+    #[test]
+    fn undecodable_config() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(StaticFileSegment::Headers.filename(&(0..=499_999).into()));
+        reth_fs_util::write(path.with_extension(CONFIG_FILE_EXTENSION), [0xff; 4]).unwrap();
+
+        let info = JarInfo::inspect(&path);
+        assert!(info.config.is_none());
+        assert!(!info.compatibility.compatible);
+        assert_eq!(info.sizes.config, Some(4));
+        assert_eq!(info.sizes.data, None);
+    }
+}
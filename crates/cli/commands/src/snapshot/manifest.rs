@@ -0,0 +1,164 @@
+//! Manifest of a snapshot fetched by `reth snapshot fetch`.
+
+use alloy_primitives::{eip191_hash_message, hex, Address, PrimitiveSignature as Signature, B256};
+use eyre::{bail, ensure, OptionExt};
+use reth_nippy_jar::CONFIG_FILE_EXTENSION;
+use reth_primitives::transaction::recover_signer;
+use reth_static_file_types::{SegmentRangeInclusive, StaticFileSegment};
+use serde::{Deserialize, Serialize};
+
+/// Extension of the offsets file of a jar.
+const OFFSETS_FILE_EXTENSION: &str = "off";
+
+/// Static files of a snapshot, with the sizes and hashes of their files.
+///
+/// The manifest is signed by the publisher of the snapshot. The signature is the
+/// [EIP-191](https://eips.ethereum.org/EIPS/eip-191) signature of the manifest bytes, encoded as
+/// hex, and served next to the manifest with a `.sig` suffix.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Manifest {
+    /// Chain id of the chain of the static files.
+    pub chain_id: u64,
+    /// Static files of the snapshot.
+    pub static_files: Vec<ManifestJar>,
+}
+
+/// A static file of a [`Manifest`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ManifestJar {
+    /// Segment of the static file.
+    pub segment: StaticFileSegment,
+    /// Blocks the static file holds.
+    pub block_range: SegmentRangeInclusive,
+    /// Data, offsets and configuration files of the static file.
+    pub files: Vec<ManifestFile>,
+}
+
+/// A file of a [`ManifestJar`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ManifestFile {
+    /// Name of the file, relative to the manifest.
+    pub name: String,
+    /// Size of the file in bytes.
+    pub size: u64,
+    /// SHA-256 hash of the file.
+    pub sha256: B256,
+}
+
+impl Manifest {
+    /// Decodes and validates a manifest.
+    pub fn decode(bytes: &[u8]) -> eyre::Result<Self> {
+        let manifest: Self = serde_json::from_slice(bytes)?;
+        for jar in &manifest.static_files {
+            jar.validate()?;
+        }
+        Ok(manifest)
+    }
+
+    /// Verifies that the manifest bytes were signed by the signer.
+    pub fn verify_signature(bytes: &[u8], signature: &[u8], signer: Address) -> eyre::Result<()> {
+        let signature = hex::decode(String::from_utf8_lossy(signature).trim())?;
+        let signature = Signature::try_from(signature.as_slice())?;
+        let recovered = recover_signer(&signature, eip191_hash_message(bytes))
+            .ok_or_eyre("invalid manifest signature")?;
+        ensure!(recovered == signer, "manifest is signed by {recovered}, expected {signer}");
+        Ok(())
+    }
+}
+
+impl ManifestJar {
+    /// Returns the data file of the static file and the block range it's expected to hold,
+    /// according to its name.
+    pub fn data_file(&self) -> eyre::Result<(&ManifestFile, SegmentRangeInclusive)> {
+        self.files
+            .iter()
+            .find_map(|file| {
+                StaticFileSegment::parse_filename(&file.name).map(|(_, range)| (file, range))
+            })
+            .ok_or_eyre("static file has no data file")
+    }
+
+    /// Validates that the static file consists of a data file following the naming scheme, and
+    /// its offsets and configuration files, which also keeps the files in the directory they're
+    /// downloaded to.
+    fn validate(&self) -> eyre::Result<()> {
+        let (data_file, expected_block_range) = self.data_file()?;
+        let name = self.segment.filename(&expected_block_range);
+        ensure!(data_file.name == name, "{} isn't a {} static file", data_file.name, self.segment);
+        if self.block_range.start() != expected_block_range.start() ||
+            self.block_range.end() > expected_block_range.end()
+        {
+            bail!("{name} can't hold blocks {}", self.block_range)
+        }
+
+        let mut names = self.files.iter().map(|file| file.name.as_str()).collect::<Vec<_>>();
+        names.sort_unstable();
+        let mut expected = vec![
+            name.clone(),
+            format!("{name}.{OFFSETS_FILE_EXTENSION}"),
+            format!("{name}.{CONFIG_FILE_EXTENSION}"),
+        ];
+        expected.sort_unstable();
+        ensure!(names == expected, "{name} must consist of the files {}", expected.join(", "));
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::{public_key_to_address, sign_message};
+    use reth_testing_utils::generators;
+    use secp256k1::Keypair;
+
+    fn jar(names: &[&str], block_range: SegmentRangeInclusive) -> ManifestJar {
+        ManifestJar {
+            segment: StaticFileSegment::Headers,
+            block_range,
+            files: names
+                .iter()
+                .map(|name| ManifestFile { name: name.to_string(), size: 1, sha256: B256::ZERO })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn validates_jar_files() {
+        let name = "static_file_headers_0_499999";
+        let files = [name, "static_file_headers_0_499999.off", "static_file_headers_0_499999.conf"];
+        assert!(jar(&files, SegmentRangeInclusive::new(0, 100)).validate().is_ok());
+
+        // blocks outside of the file
+        assert!(jar(&files, SegmentRangeInclusive::new(0, 500_000)).validate().is_err());
+        assert!(jar(&files, SegmentRangeInclusive::new(1, 100)).validate().is_err());
+
+        // missing and unexpected files
+        assert!(jar(&files[..2], SegmentRangeInclusive::new(0, 100)).validate().is_err());
+        let files = [name, "static_file_headers_0_499999.off", "../static_file_headers_0_499999"];
+        assert!(jar(&files, SegmentRangeInclusive::new(0, 100)).validate().is_err());
+
+        // other segment
+        let mut receipts = jar(&[name], SegmentRangeInclusive::new(0, 100));
+        receipts.segment = StaticFileSegment::Receipts;
+        assert!(receipts.validate().is_err());
+    }
+
+    #[test]
+    fn verifies_signature() {
+        let manifest = br#"{"chain_id":1,"static_files":[]}"#;
+        let key_pair = Keypair::new_global(&mut generators::rng());
+        let signer = public_key_to_address(key_pair.public_key());
+        let signature =
+            sign_message(B256::from_slice(&key_pair.secret_bytes()), eip191_hash_message(manifest))
+                .unwrap();
+        let signature = hex::encode_prefixed(signature.as_bytes());
+
+        assert!(Manifest::verify_signature(manifest, signature.as_bytes(), signer).is_ok());
+        assert!(Manifest::verify_signature(manifest, signature.as_bytes(), Address::ZERO).is_err());
+        assert!(Manifest::verify_signature(b"{}", signature.as_bytes(), signer).is_err());
+        assert!(Manifest::verify_signature(manifest, b"0x00", signer).is_err());
+
+        assert_eq!(Manifest::decode(manifest).unwrap().chain_id, 1);
+    }
+}
@@ -0,0 +1,51 @@
+//! `reth snapshot` command.
+
+use crate::common::{CliNodeTypes, EnvironmentArgs};
+use clap::{Parser, Subcommand};
+use reth_chainspec::{EthChainSpec, EthereumHardforks};
+use reth_cli::chainspec::ChainSpecParser;
+use reth_cli_runner::CliContext;
+
+pub mod fetch;
+pub mod info;
+pub mod manifest;
+
+/// `reth snapshot` command
+#[derive(Debug, Parser)]
+pub struct Command<C: ChainSpecParser> {
+    #[command(flatten)]
+    env: EnvironmentArgs<C>,
+
+    #[command(subcommand)]
+    command: Subcommands,
+}
+
+/// `reth snapshot` subcommands
+#[derive(Subcommand, Debug)]
+pub enum Subcommands {
+    /// Prints the configuration of static files and whether this binary can read them
+    Info(info::Command),
+    /// Downloads the static files of a signed snapshot manifest, verifies them and installs them,
+    /// so that the node syncs on top of them
+    Fetch(fetch::Command),
+}
+
+impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C> {
+    /// Execute `snapshot` command
+    pub async fn execute<N: CliNodeTypes<ChainSpec = C::ChainSpec>>(
+        self,
+        ctx: CliContext,
+    ) -> eyre::Result<()> {
+        match self.command {
+            Subcommands::Info(command) => {
+                let data_dir = self.env.datadir.clone().resolve_datadir(self.env.chain.chain());
+                command.execute(data_dir.static_files())
+            }
+            Subcommands::Fetch(command) => {
+                command
+                    .execute::<N, C>(self.env, ctx.task_executor.on_shutdown_signal().clone())
+                    .await
+            }
+        }
+    }
+}
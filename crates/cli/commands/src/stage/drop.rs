@@ -144,6 +144,16 @@ impl<C: ChainSpecParser> Command<C> {
 
                 insert_genesis_history(&provider_rw, self.env.chain.genesis().alloc.iter())?;
             }
+            StageEnum::IndexLogAddresses => {
+                // The index is only maintained while the stage has a checkpoint, so it's removed
+                // instead of reset.
+                tx.clear::<tables::LogAddressIndex>()?;
+                tx.delete::<tables::StageCheckpoints>(
+                    StageId::IndexLogAddresses.to_string(),
+                    None,
+                )?;
+                tx.delete::<tables::PruneCheckpoints>(PruneSegment::LogAddressIndex, None)?;
+            }
             StageEnum::TxLookup => {
                 tx.clear::<tables::TransactionHashNumbers>()?;
                 reset_prune_checkpoint(tx, PruneSegment::TransactionLookup)?;
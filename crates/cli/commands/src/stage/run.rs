@@ -6,12 +6,14 @@ use crate::common::{AccessRights, CliNodeTypes, Environment, EnvironmentArgs};
 use alloy_eips::BlockHashOrNumber;
 use alloy_primitives::Sealable;
 use clap::Parser;
+use human_bytes::human_bytes;
 use reth_beacon_consensus::EthBeaconConsensus;
 use reth_chainspec::{EthChainSpec, EthereumHardforks};
 use reth_cli::chainspec::ChainSpecParser;
 use reth_cli_runner::CliContext;
 use reth_cli_util::get_secret_key;
 use reth_config::config::{HashingConfig, SenderRecoveryConfig, TransactionLookupConfig};
+use reth_db::mdbx;
 use reth_db_api::database_metrics::DatabaseMetrics;
 use reth_downloaders::{
     bodies::bodies::BodiesDownloaderBuilder,
@@ -42,13 +44,19 @@ use reth_provider::{
 use reth_stages::{
     stages::{
         AccountHashingStage, BodyStage, ExecutionStage, HeaderStage, IndexAccountHistoryStage,
-        IndexStorageHistoryStage, MerkleStage, SenderRecoveryStage, StorageHashingStage,
-        TransactionLookupStage,
+        IndexLogAddressesStage, IndexStorageHistoryStage, MerkleStage, SenderRecoveryStage,
+        StorageHashingStage, TransactionLookupStage,
     },
-    ExecInput, ExecOutput, ExecutionStageThresholds, Stage, StageError, StageExt, UnwindInput,
-    UnwindOutput,
+    ExecInput, ExecOutput, ExecutionStageThresholds, Stage, StageCheckpoint, StageError, StageId,
+    UnwindInput, UnwindOutput,
+};
+use std::{
+    any::Any,
+    future::poll_fn,
+    net::SocketAddr,
+    sync::Arc,
+    time::{Duration, Instant},
 };
-use std::{any::Any, net::SocketAddr, sync::Arc, time::Instant};
 use tokio::sync::watch;
 use tracing::*;
 
@@ -93,9 +101,16 @@ pub struct Command<C: ChainSpecParser> {
     /// Useful when you want to run diagnostics on the database.
     // TODO: We should consider allowing to run hooks at the end of the stage run,
     // e.g. query the DB size, or any table data.
-    #[arg(long, short)]
+    #[arg(long, short, overrides_with = "no_commit")]
     commit: bool,
 
+    /// Discards all changes, including stage checkpoints, once the stage finished. This is the
+    /// default.
+    ///
+    /// Useful to benchmark a stage repeatedly over the same range.
+    #[arg(long, overrides_with = "commit")]
+    no_commit: bool,
+
     /// Save stage checkpoints
     #[arg(long)]
     checkpoints: bool,
@@ -120,8 +135,6 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C>
         let Environment { provider_factory, config, data_dir } =
             self.env.init::<N>(AccessRights::RW)?;
 
-        let mut provider_rw = provider_factory.database_provider_rw()?;
-
         if let Some(listen_addr) = self.metrics {
             info!(target: "reth::cli", "Starting metrics endpoint at {}", listen_addr);
             let config = MetricServerConfig::new(
@@ -160,189 +173,276 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C>
         let etl_config = config.stages.etl.clone();
         let prune_modes = config.prune.clone().map(|prune| prune.segments).unwrap_or_default();
 
-        let (mut exec_stage, mut unwind_stage): (Box<dyn Stage<_>>, Option<Box<dyn Stage<_>>>) =
-            match self.stage {
-                StageEnum::Headers => {
-                    let consensus =
-                        Arc::new(EthBeaconConsensus::new(provider_factory.chain_spec()));
-
-                    let network_secret_path = self
-                        .network
-                        .p2p_secret_key
-                        .clone()
-                        .unwrap_or_else(|| data_dir.p2p_secret());
-                    let p2p_secret_key = get_secret_key(&network_secret_path)?;
-
-                    let default_peers_path = data_dir.known_peers();
-
-                    let network = self
-                        .network
-                        .network_config::<P>(
-                            &config,
-                            provider_factory.chain_spec(),
-                            p2p_secret_key,
-                            default_peers_path,
-                        )
-                        .build(provider_factory.clone())
-                        .start_network()
-                        .await?;
-                    let fetch_client = Arc::new(network.fetch_client().await?);
-
-                    // Use `to` as the tip for the stage
-                    let tip: P::BlockHeader = fetch_client
-                        .get_header(BlockHashOrNumber::Number(self.to))
-                        .await?
-                        .into_data()
-                        .ok_or(StageError::MissingSyncGap)?;
-                    let (_, rx) = watch::channel(tip.hash_slow());
-                    (
-                        Box::new(HeaderStage::new(
-                            provider_factory.clone(),
-                            ReverseHeadersDownloaderBuilder::new(config.stages.headers)
-                                .build(fetch_client, consensus.clone()),
-                            rx,
-                            consensus,
-                            etl_config,
-                        )),
-                        None,
+        let stages: Vec<(Box<dyn Stage<_>>, Option<Box<dyn Stage<_>>>)> = match self.stage {
+            StageEnum::Headers => {
+                let consensus = Arc::new(EthBeaconConsensus::new(provider_factory.chain_spec()));
+
+                let network_secret_path =
+                    self.network.p2p_secret_key.clone().unwrap_or_else(|| data_dir.p2p_secret());
+                let p2p_secret_key = get_secret_key(&network_secret_path)?;
+
+                let default_peers_path = data_dir.known_peers();
+
+                let network = self
+                    .network
+                    .network_config::<P>(
+                        &config,
+                        provider_factory.chain_spec(),
+                        p2p_secret_key,
+                        default_peers_path,
                     )
-                }
-                StageEnum::Bodies => {
-                    let consensus =
-                        Arc::new(EthBeaconConsensus::new(provider_factory.chain_spec()));
-
-                    let mut config = config;
-                    config.peers.trusted_nodes_only = self.network.trusted_only;
-                    config.peers.trusted_nodes.extend(self.network.trusted_peers.clone());
-
-                    let network_secret_path = self
-                        .network
-                        .p2p_secret_key
-                        .clone()
-                        .unwrap_or_else(|| data_dir.p2p_secret());
-                    let p2p_secret_key = get_secret_key(&network_secret_path)?;
-
-                    let default_peers_path = data_dir.known_peers();
-
-                    let network = self
-                        .network
-                        .network_config::<P>(
-                            &config,
-                            provider_factory.chain_spec(),
-                            p2p_secret_key,
-                            default_peers_path,
-                        )
-                        .build(provider_factory.clone())
-                        .start_network()
-                        .await?;
-                    let fetch_client = Arc::new(network.fetch_client().await?);
-
-                    let stage = BodyStage::new(
-                        BodiesDownloaderBuilder::default()
-                            .with_stream_batch_size(batch_size as usize)
-                            .with_request_limit(config.stages.bodies.downloader_request_limit)
-                            .with_max_buffered_blocks_size_bytes(
-                                config.stages.bodies.downloader_max_buffered_blocks_size_bytes,
-                            )
-                            .with_concurrent_requests_range(
-                                config.stages.bodies.downloader_min_concurrent_requests..=
-                                    config.stages.bodies.downloader_max_concurrent_requests,
-                            )
-                            .build(fetch_client, consensus.clone(), provider_factory.clone()),
-                    );
-                    (Box::new(stage), None)
-                }
-                StageEnum::Senders => (
-                    Box::new(SenderRecoveryStage::new(SenderRecoveryConfig {
-                        commit_threshold: batch_size,
-                    })),
-                    None,
-                ),
-                StageEnum::Execution => (
-                    Box::new(ExecutionStage::new(
-                        executor(provider_factory.chain_spec()),
-                        ExecutionStageThresholds {
-                            max_blocks: Some(batch_size),
-                            max_changes: None,
-                            max_cumulative_gas: None,
-                            max_duration: None,
-                        },
-                        config.stages.merkle.clean_threshold,
-                        prune_modes,
-                        ExExManagerHandle::empty(),
-                    )),
-                    None,
-                ),
-                StageEnum::TxLookup => (
-                    Box::new(TransactionLookupStage::new(
-                        TransactionLookupConfig { chunk_size: batch_size },
+                    .build(provider_factory.clone())
+                    .start_network()
+                    .await?;
+                let fetch_client = Arc::new(network.fetch_client().await?);
+
+                // Use `to` as the tip for the stage
+                let tip: P::BlockHeader = fetch_client
+                    .get_header(BlockHashOrNumber::Number(self.to))
+                    .await?
+                    .into_data()
+                    .ok_or(StageError::MissingSyncGap)?;
+                let (_, rx) = watch::channel(tip.hash_slow());
+                vec![(
+                    Box::new(HeaderStage::new(
+                        provider_factory.clone(),
+                        ReverseHeadersDownloaderBuilder::new(config.stages.headers)
+                            .build(fetch_client, consensus.clone()),
+                        rx,
+                        consensus,
                         etl_config,
-                        prune_modes.transaction_lookup,
                     )),
                     None,
-                ),
-                StageEnum::AccountHashing => (
+                )]
+            }
+            StageEnum::Bodies => {
+                let consensus = Arc::new(EthBeaconConsensus::new(provider_factory.chain_spec()));
+
+                let mut config = config;
+                config.peers.trusted_nodes_only = self.network.trusted_only;
+                config.peers.trusted_nodes.extend(self.network.trusted_peers.clone());
+
+                let network_secret_path =
+                    self.network.p2p_secret_key.clone().unwrap_or_else(|| data_dir.p2p_secret());
+                let p2p_secret_key = get_secret_key(&network_secret_path)?;
+
+                let default_peers_path = data_dir.known_peers();
+
+                let network = self
+                    .network
+                    .network_config::<P>(
+                        &config,
+                        provider_factory.chain_spec(),
+                        p2p_secret_key,
+                        default_peers_path,
+                    )
+                    .build(provider_factory.clone())
+                    .start_network()
+                    .await?;
+                let fetch_client = Arc::new(network.fetch_client().await?);
+
+                let stage = BodyStage::new(
+                    BodiesDownloaderBuilder::default()
+                        .with_stream_batch_size(batch_size as usize)
+                        .with_request_limit(config.stages.bodies.downloader_request_limit)
+                        .with_max_buffered_blocks_size_bytes(
+                            config.stages.bodies.downloader_max_buffered_blocks_size_bytes,
+                        )
+                        .with_concurrent_requests_range(
+                            config.stages.bodies.downloader_min_concurrent_requests..=
+                                config.stages.bodies.downloader_max_concurrent_requests,
+                        )
+                        .with_validate_bodies(config.stages.bodies.downloader_validate_bodies)
+                        .build(fetch_client, consensus.clone(), provider_factory.clone()),
+                );
+                vec![(Box::new(stage), None)]
+            }
+            StageEnum::Senders => vec![(
+                Box::new(SenderRecoveryStage::new(SenderRecoveryConfig {
+                    commit_threshold: batch_size,
+                })),
+                None,
+            )],
+            StageEnum::Execution => vec![(
+                Box::new(ExecutionStage::new(
+                    executor(provider_factory.chain_spec()),
+                    ExecutionStageThresholds {
+                        max_blocks: Some(batch_size),
+                        max_changes: None,
+                        max_cumulative_gas: None,
+                        max_duration: None,
+                    },
+                    config.stages.merkle.clean_threshold,
+                    prune_modes,
+                    ExExManagerHandle::empty(),
+                )),
+                None,
+            )],
+            StageEnum::TxLookup => vec![(
+                Box::new(TransactionLookupStage::new(
+                    TransactionLookupConfig { chunk_size: batch_size },
+                    etl_config,
+                    prune_modes.transaction_lookup,
+                )),
+                None,
+            )],
+            StageEnum::AccountHashing => vec![(
+                Box::new(AccountHashingStage::new(
+                    HashingConfig { clean_threshold: 1, commit_threshold: batch_size },
+                    etl_config,
+                )),
+                None,
+            )],
+            StageEnum::StorageHashing => vec![(
+                Box::new(StorageHashingStage::new(
+                    HashingConfig { clean_threshold: 1, commit_threshold: batch_size },
+                    etl_config,
+                )),
+                None,
+            )],
+            StageEnum::Merkle => vec![(
+                Box::new(MerkleStage::new_execution(config.stages.merkle.clean_threshold)),
+                Some(Box::new(MerkleStage::default_unwind())),
+            )],
+            StageEnum::AccountHistory => vec![(
+                Box::new(IndexAccountHistoryStage::new(
+                    config.stages.index_account_history,
+                    etl_config,
+                    prune_modes.account_history,
+                )),
+                None,
+            )],
+            StageEnum::StorageHistory => vec![(
+                Box::new(IndexStorageHistoryStage::new(
+                    config.stages.index_storage_history,
+                    etl_config,
+                    prune_modes.storage_history,
+                )),
+                None,
+            )],
+            StageEnum::IndexLogAddresses => vec![(
+                Box::new(IndexLogAddressesStage::new(
+                    config.stages.index_log_addresses,
+                    etl_config,
+                    prune_modes.receipts,
+                )),
+                None,
+            )],
+            StageEnum::Hashing => vec![
+                (
                     Box::new(AccountHashingStage::new(
                         HashingConfig { clean_threshold: 1, commit_threshold: batch_size },
-                        etl_config,
+                        etl_config.clone(),
                     )),
                     None,
                 ),
-                StageEnum::StorageHashing => (
+                (
                     Box::new(StorageHashingStage::new(
                         HashingConfig { clean_threshold: 1, commit_threshold: batch_size },
                         etl_config,
                     )),
                     None,
                 ),
-                StageEnum::Merkle => (
-                    Box::new(MerkleStage::new_execution(config.stages.merkle.clean_threshold)),
-                    Some(Box::new(MerkleStage::default_unwind())),
-                ),
-                StageEnum::AccountHistory => (
-                    Box::new(IndexAccountHistoryStage::new(
-                        config.stages.index_account_history,
-                        etl_config,
-                        prune_modes.account_history,
-                    )),
-                    None,
-                ),
-                StageEnum::StorageHistory => (
-                    Box::new(IndexStorageHistoryStage::new(
-                        config.stages.index_storage_history,
-                        etl_config,
-                        prune_modes.storage_history,
-                    )),
-                    None,
-                ),
-                _ => return Ok(()),
-            };
-        if let Some(unwind_stage) = &unwind_stage {
-            assert_eq!((*exec_stage).type_id(), (**unwind_stage).type_id());
+            ],
+        };
+
+        let run = StageRun {
+            from: self.from,
+            to: self.to,
+            skip_unwind: self.skip_unwind,
+            commit: self.commit,
+            checkpoints: self.checkpoints,
+        };
+        for (mut exec_stage, mut unwind_stage) in stages {
+            if let Some(unwind_stage) = &unwind_stage {
+                assert_eq!((*exec_stage).type_id(), (**unwind_stage).type_id());
+            }
+
+            let stage_id = exec_stage.id();
+            info!(target: "reth::cli", stage = %stage_id, from = run.from, to = run.to, commit = run.commit, "Executing stage");
+            let summary = run
+                .run(
+                    &provider_factory,
+                    provider_factory.db_ref(),
+                    exec_stage.as_mut(),
+                    unwind_stage.as_mut().map(|stage| stage.as_mut() as &mut dyn Stage<_>),
+                )
+                .await?;
+            summary.log(stage_id);
         }
 
-        let checkpoint = provider_rw.get_stage_checkpoint(exec_stage.id())?.unwrap_or_default();
+        Ok(())
+    }
+}
 
-        let unwind_stage = unwind_stage.as_mut().unwrap_or(&mut exec_stage);
+/// Range and options of a single stage run.
+#[derive(Debug, Clone, Copy)]
+struct StageRun {
+    /// The height to start at.
+    from: u64,
+    /// The height to execute to.
+    to: u64,
+    /// Whether to skip unwinding the stage to `from` first.
+    skip_unwind: bool,
+    /// Whether to commit every batch. Otherwise all changes are discarded at the end.
+    commit: bool,
+    /// Whether to save stage checkpoints.
+    checkpoints: bool,
+}
 
-        let mut unwind = UnwindInput {
-            checkpoint: checkpoint.with_block_number(self.to),
-            unwind_to: self.from,
-            bad_block: None,
-        };
+impl StageRun {
+    /// Unwinds the stage to `from` unless skipped and executes it up to `to`.
+    ///
+    /// Without `commit`, the unwind and all executed batches share a single transaction which is
+    /// dropped at the end, so stage data and checkpoints are left untouched and the run can be
+    /// repeated over the same range.
+    ///
+    /// `env` is the database environment of `provider_factory`, which the database statistics of
+    /// the run are read from.
+    async fn run<F>(
+        self,
+        provider_factory: &F,
+        env: &mdbx::Environment,
+        exec_stage: &mut dyn Stage<F::ProviderRW>,
+        unwind_stage: Option<&mut dyn Stage<F::ProviderRW>>,
+    ) -> eyre::Result<StageRunSummary>
+    where
+        F: DatabaseProviderFactory<
+            ProviderRW: StageCheckpointReader + StageCheckpointWriter + StaticFileProviderFactory,
+        >,
+    {
+        let mut summary = StageRunSummary::default();
+        let db_start = DbStats::snapshot(env)?;
+        let mut provider_rw = provider_factory.database_provider_rw()?;
+
+        let checkpoint = provider_rw.get_stage_checkpoint(exec_stage.id())?.unwrap_or_default();
 
         if !self.skip_unwind {
+            let unwind_stage = match unwind_stage {
+                Some(unwind_stage) => unwind_stage,
+                None => &mut *exec_stage,
+            };
+            let mut unwind = UnwindInput {
+                checkpoint: checkpoint.with_block_number(self.to),
+                unwind_to: self.from,
+                bad_block: None,
+            };
+
             while unwind.checkpoint.block_number > self.from {
+                let start = Instant::now();
                 let UnwindOutput { checkpoint } = unwind_stage.unwind(&provider_rw, unwind)?;
                 unwind.checkpoint = checkpoint;
 
                 if self.checkpoints {
                     provider_rw.save_stage_checkpoint(unwind_stage.id(), checkpoint)?;
                 }
+                summary.unwind += start.elapsed();
 
                 if self.commit {
+                    let start = Instant::now();
                     UnifiedStorageWriter::commit_unwind(provider_rw)?;
                     provider_rw = provider_factory.database_provider_rw()?;
+                    summary.commit += start.elapsed();
                 }
             }
         }
@@ -352,28 +452,252 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C>
             checkpoint: Some(checkpoint.with_block_number(self.from)),
         };
 
-        let start = Instant::now();
-        info!(target: "reth::cli", stage = %self.stage, "Executing stage");
         loop {
-            exec_stage.execute_ready(input).await?;
-            let ExecOutput { checkpoint, done } = exec_stage.execute(&provider_rw, input)?;
+            let start = Instant::now();
+            poll_fn(|cx| exec_stage.poll_execute_ready(cx, input)).await?;
+            summary.ready += start.elapsed();
 
+            let start = Instant::now();
+            let ExecOutput { checkpoint, done } = exec_stage.execute(&provider_rw, input)?;
             input.checkpoint = Some(checkpoint);
 
             if self.checkpoints {
                 provider_rw.save_stage_checkpoint(exec_stage.id(), checkpoint)?;
             }
+            summary.execute += start.elapsed();
+            summary.batches += 1;
+            summary.checkpoint = checkpoint;
+
             if self.commit {
+                let start = Instant::now();
                 UnifiedStorageWriter::commit(provider_rw)?;
                 provider_rw = provider_factory.database_provider_rw()?;
+                summary.commit += start.elapsed();
             }
 
             if done {
                 break
             }
         }
-        info!(target: "reth::cli", stage = %self.stage, time = ?start.elapsed(), "Finished stage");
 
-        Ok(())
+        summary.db = DbStats::snapshot(env)?.since(db_start);
+        Ok(summary)
+    }
+}
+
+/// Statistics of a [`StageRun`].
+#[derive(Debug, Default)]
+struct StageRunSummary {
+    /// Checkpoint the stage reached.
+    checkpoint: StageCheckpoint,
+    /// Number of executed batches.
+    batches: usize,
+    /// Time spent unwinding the stage.
+    unwind: Duration,
+    /// Time spent waiting for the stage to be ready, e.g. downloading data.
+    ready: Duration,
+    /// Time spent executing the stage.
+    execute: Duration,
+    /// Time spent committing changes.
+    commit: Duration,
+    /// Database activity during the run.
+    db: DbStats,
+}
+
+impl StageRunSummary {
+    /// Logs the summary.
+    fn log(&self, stage_id: StageId) {
+        let entities = self.checkpoint.entities();
+        info!(
+            target: "reth::cli",
+            stage = %stage_id,
+            checkpoint = self.checkpoint.block_number,
+            processed = entities.map(|entities| entities.processed),
+            total = entities.map(|entities| entities.total),
+            batches = self.batches,
+            unwind = ?self.unwind,
+            ready = ?self.ready,
+            execute = ?self.execute,
+            commit = ?self.commit,
+            pages_allocated = self.db.pages_allocated,
+            pages_copied = self.db.pages_copied,
+            pages_spilled = self.db.pages_spilled,
+            pages_unspilled = self.db.pages_unspilled,
+            read = %human_bytes(self.db.read_bytes as f64),
+            written = %human_bytes(self.db.write_bytes as f64),
+            "Finished stage"
+        );
+    }
+}
+
+/// Cumulative counters of database activity, which are diffed to get the activity of a
+/// [`StageRun`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+struct DbStats {
+    /// Number of newly allocated database pages.
+    pages_allocated: u64,
+    /// Number of database pages copied to be updated.
+    pages_copied: u64,
+    /// Number of dirty database pages spilled to disk before the transaction was committed.
+    pages_spilled: u64,
+    /// Number of spilled database pages that were read back.
+    pages_unspilled: u64,
+    /// Bytes read from storage by the process. Only available on Linux.
+    read_bytes: u64,
+    /// Bytes written to storage by the process. Only available on Linux.
+    write_bytes: u64,
+}
+
+impl DbStats {
+    /// Reads the page operations of all transactions of the environment so far, committed or not,
+    /// and the storage I/O of the process.
+    fn snapshot(env: &mdbx::Environment) -> eyre::Result<Self> {
+        let page_ops = env.info()?.page_ops();
+        let (read_bytes, write_bytes) = process_io();
+        Ok(Self {
+            pages_allocated: page_ops.newly,
+            pages_copied: page_ops.cow,
+            pages_spilled: page_ops.spill,
+            pages_unspilled: page_ops.unspill,
+            read_bytes,
+            write_bytes,
+        })
+    }
+
+    /// Returns the activity since the `start` snapshot.
+    const fn since(self, start: Self) -> Self {
+        Self {
+            pages_allocated: self.pages_allocated.saturating_sub(start.pages_allocated),
+            pages_copied: self.pages_copied.saturating_sub(start.pages_copied),
+            pages_spilled: self.pages_spilled.saturating_sub(start.pages_spilled),
+            pages_unspilled: self.pages_unspilled.saturating_sub(start.pages_unspilled),
+            read_bytes: self.read_bytes.saturating_sub(start.read_bytes),
+            write_bytes: self.write_bytes.saturating_sub(start.write_bytes),
+        }
+    }
+}
+
+/// Returns the bytes read from and written to storage by the process so far.
+#[cfg(target_os = "linux")]
+fn process_io() -> (u64, u64) {
+    match procfs::process::Process::myself().and_then(|process| process.io()) {
+        Ok(io) => (io.read_bytes, io.write_bytes),
+        Err(error) => {
+            debug!(target: "reth::cli", %error, "Failed to get IO stats of the process");
+            (0, 0)
+        }
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+const fn process_io() -> (u64, u64) {
+    (0, 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::B256;
+    use reth_config::config::EtlConfig;
+    use reth_db::tables;
+    use reth_ethereum_cli::chainspec::EthereumChainSpecParser;
+    use reth_stages::test_utils::{StorageKind, TestStageDB};
+    use reth_testing_utils::generators::{self, random_block_range, BlockRangeParams};
+
+    const TO: u64 = 20;
+
+    /// Inserts a small chain with transactions into a fresh database.
+    fn seed_db() -> TestStageDB {
+        let db = TestStageDB::default();
+        let blocks = random_block_range(
+            &mut generators::rng(),
+            0..=TO,
+            BlockRangeParams { parent: Some(B256::ZERO), tx_count: 1..3, ..Default::default() },
+        );
+        db.insert_blocks(blocks.iter(), StorageKind::Static).expect("insert blocks");
+        db
+    }
+
+    fn stages<P>() -> Vec<Box<dyn Stage<P>>>
+    where
+        SenderRecoveryStage: Stage<P>,
+        TransactionLookupStage: Stage<P>,
+    {
+        vec![
+            Box::new(SenderRecoveryStage::new(SenderRecoveryConfig { commit_threshold: 5 })),
+            Box::new(TransactionLookupStage::new(
+                TransactionLookupConfig { chunk_size: 5 },
+                EtlConfig::default(),
+                None,
+            )),
+        ]
+    }
+
+    #[test]
+    fn parse_commit_flags() {
+        let parse = |args: &[&str]| {
+            Command::<EthereumChainSpecParser>::parse_from(
+                ["reth", "--datadir", "dir", "senders", "--from", "0", "--to", "10"]
+                    .iter()
+                    .chain(args),
+            )
+            .commit
+        };
+
+        assert!(!parse(&[]));
+        assert!(!parse(&["--no-commit"]));
+        assert!(parse(&["--commit"]));
+        assert!(parse(&["--no-commit", "--commit"]));
+        assert!(!parse(&["--commit", "--no-commit"]));
+    }
+
+    #[tokio::test]
+    async fn run_without_commit_leaves_db_untouched() {
+        let db = seed_db();
+        let run =
+            StageRun { from: 0, to: TO, skip_unwind: false, commit: false, checkpoints: true };
+
+        for mut stage in stages() {
+            let first =
+                run.run(&db.factory, db.factory.db_ref().db(), stage.as_mut(), None).await.unwrap();
+            assert_eq!(first.checkpoint.block_number, TO);
+            // Discarded writes are still accounted for.
+            assert!(first.db.pages_allocated + first.db.pages_copied > 0);
+
+            // Nothing was persisted, so a rerun processes the same range again.
+            let second =
+                run.run(&db.factory, db.factory.db_ref().db(), stage.as_mut(), None).await.unwrap();
+            assert_eq!(second.checkpoint, first.checkpoint);
+            assert_eq!(second.batches, first.batches);
+
+            let provider = db.factory.provider().unwrap();
+            assert_eq!(provider.get_stage_checkpoint(stage.id()).unwrap(), None);
+        }
+
+        assert!(db.table_is_empty::<tables::TransactionSenders>().unwrap());
+        assert!(db.table_is_empty::<tables::TransactionHashNumbers>().unwrap());
+    }
+
+    #[tokio::test]
+    async fn run_with_commit_persists_changes() {
+        let db = seed_db();
+        let run = StageRun { from: 0, to: TO, skip_unwind: false, commit: true, checkpoints: true };
+
+        for mut stage in stages() {
+            let summary =
+                run.run(&db.factory, db.factory.db_ref().db(), stage.as_mut(), None).await.unwrap();
+            assert_eq!(summary.checkpoint.block_number, TO);
+            assert!(summary.db.pages_allocated + summary.db.pages_copied > 0);
+
+            let provider = db.factory.provider().unwrap();
+            assert_eq!(
+                provider.get_stage_checkpoint(stage.id()).unwrap(),
+                Some(summary.checkpoint)
+            );
+        }
+
+        let senders = db.table::<tables::TransactionSenders>().unwrap();
+        assert!(!senders.is_empty());
+        assert_eq!(db.table::<tables::TransactionHashNumbers>().unwrap().len(), senders.len());
     }
 }
@@ -2,29 +2,34 @@
 
 use crate::common::{AccessRights, CliNodeTypes, Environment, EnvironmentArgs};
 use alloy_eips::BlockHashOrNumber;
-use alloy_primitives::B256;
+use alloy_primitives::{BlockNumber, B256};
 use clap::{Parser, Subcommand};
+use itertools::Itertools;
 use reth_chainspec::{EthChainSpec, EthereumHardforks};
 use reth_cli::chainspec::ChainSpecParser;
 use reth_config::Config;
 use reth_consensus::noop::NoopConsensus;
-use reth_db::DatabaseEnv;
+use reth_db::{DatabaseEnv, Tables};
 use reth_downloaders::{bodies::noop::NoopBodiesDownloader, headers::noop::NoopHeaderDownloader};
 use reth_evm::noop::NoopBlockExecutorProvider;
 use reth_exex::ExExManagerHandle;
-use reth_node_core::args::NetworkArgs;
+use reth_node_core::args::{NetworkArgs, StageEnum};
 use reth_provider::{
     providers::ProviderNodeTypes, BlockExecutionWriter, BlockNumReader, ChainStateBlockReader,
-    ChainStateBlockWriter, ProviderFactory, StaticFileProviderFactory, StorageLocation,
+    ChainStateBlockWriter, ProviderFactory, StageCheckpointReader, StaticFileProviderFactory,
+    StorageLocation,
 };
 use reth_prune::PruneModes;
 use reth_stages::{
     sets::{DefaultStages, OfflineStages},
     stages::ExecutionStage,
-    ExecutionStageThresholds, Pipeline, StageSet,
+    ExecutionStageThresholds, Pipeline, StageId, StageSet,
 };
 use reth_static_file::StaticFileProducer;
-use std::sync::Arc;
+use std::{
+    io::{self, Write},
+    sync::Arc,
+};
 use tokio::sync::watch;
 use tracing::info;
 
@@ -44,6 +49,18 @@ pub struct Command<C: ChainSpecParser> {
     /// unwound.
     #[arg(long)]
     offline: bool,
+
+    /// Only unwinds the given stage and the stages that depend on it, the checkpoints of all
+    /// other stages are kept.
+    ///
+    /// The stages catch up again on the next run of the node, without re-running the stages they
+    /// depend on.
+    #[arg(long, value_enum, conflicts_with = "offline")]
+    stage: Option<StageEnum>,
+
+    /// Bypasses the interactive confirmation of a stage unwind
+    #[arg(short, long)]
+    yes: bool,
 }
 
 impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C> {
@@ -59,16 +76,25 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C>
             .max_block_num()
             .filter(|highest_static_file_block| *highest_static_file_block > target);
 
+        if let Some(stage) = self.stage {
+            if !confirm_stage_unwind(&provider_factory, stage, target, self.yes)? {
+                return Ok(())
+            }
+        }
+
         // Execute a pipeline unwind if the start of the range overlaps the existing static
         // files. If that's the case, then copy all available data from MDBX to static files, and
         // only then, proceed with the unwind.
         //
-        // We also execute a pipeline unwind if `offline` is specified, because we need to only
-        // unwind the data associated with offline stages.
-        if highest_static_file_block.is_some() || self.offline {
+        // We also execute a pipeline unwind if `offline` or `stage` is specified, because we need
+        // to only unwind the data associated with these stages.
+        if highest_static_file_block.is_some() || self.offline || self.stage.is_some() {
             if self.offline {
                 info!(target: "reth::cli", "Performing an unwind for offline-only data!");
             }
+            if let Some(stage) = self.stage {
+                info!(target: "reth::cli", %stage, "Performing an unwind for the stage and its dependents!");
+            }
 
             if let Some(highest_static_file_block) = highest_static_file_block {
                 info!(target: "reth::cli", ?target, ?highest_static_file_block, "Executing a pipeline unwind.");
@@ -76,7 +102,8 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C>
                 info!(target: "reth::cli", ?target, "Executing a pipeline unwind.");
             }
 
-            // This will build an offline-only pipeline if the `offline` flag is enabled
+            // This will build an offline-only pipeline if the `offline` flag is enabled, and a
+            // pipeline of the unwound stages if `stage` is set
             let mut pipeline = self.build_pipeline(config, provider_factory)?;
 
             // Move all applicable data from database to static files.
@@ -107,9 +134,13 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C>
 
     fn build_pipeline<N: ProviderNodeTypes<ChainSpec = C::ChainSpec> + CliNodeTypes>(
         self,
-        config: Config,
+        mut config: Config,
         provider_factory: ProviderFactory<N>,
     ) -> Result<Pipeline<N>, eyre::Error> {
+        // The log address index can exist even if its stage is disabled, e.g. if it was built with
+        // `reth db build-log-index`, so it's always unwound. Unwinding it is a no-op if it doesn't
+        // exist.
+        config.stages.index_log_addresses.enabled = true;
         let stage_conf = &config.stages;
         let prune_modes = config.prune.clone().map(|prune| prune.segments).unwrap_or_default();
 
@@ -125,6 +156,19 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C>
                     .disable(reth_stages::StageId::SenderRecovery),
             )
         } else {
+            // All stages that aren't unwound along with `stage` keep their checkpoints
+            let disabled = self
+                .stage
+                .map(|stage| {
+                    let unwound = unwound_stages(stage);
+                    StageId::ALL
+                        .into_iter()
+                        .chain([StageId::IndexLogAddresses])
+                        .filter(|stage_id| !unwound.contains(stage_id))
+                        .collect::<Vec<_>>()
+                })
+                .unwrap_or_default();
+
             Pipeline::<N>::builder().with_tip_sender(tip_tx).add_stages(
                 DefaultStages::new(
                     provider_factory.clone(),
@@ -147,7 +191,8 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C>
                     stage_conf.execution_external_clean_threshold(),
                     prune_modes,
                     ExExManagerHandle::empty(),
-                )),
+                ))
+                .disable_all(&disabled),
             )
         };
 
@@ -159,6 +204,118 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C>
     }
 }
 
+/// Returns the stages of the given [`StageEnum`] along with the stages that depend on them, in the
+/// order the pipeline runs them.
+fn unwound_stages(stage: StageEnum) -> Vec<StageId> {
+    let stages: &[StageId] = match stage {
+        StageEnum::Headers => &[StageId::Headers],
+        StageEnum::Bodies => &[StageId::Bodies],
+        StageEnum::Senders => &[StageId::SenderRecovery],
+        StageEnum::Execution => &[StageId::Execution],
+        StageEnum::AccountHashing => &[StageId::AccountHashing],
+        StageEnum::StorageHashing => &[StageId::StorageHashing],
+        StageEnum::Hashing => &[StageId::AccountHashing, StageId::StorageHashing],
+        StageEnum::Merkle => &[StageId::MerkleUnwind, StageId::MerkleExecute],
+        StageEnum::TxLookup => &[StageId::TransactionLookup],
+        StageEnum::AccountHistory => &[StageId::IndexAccountHistory],
+        StageEnum::StorageHistory => &[StageId::IndexStorageHistory],
+        StageEnum::IndexLogAddresses => &[StageId::IndexLogAddresses],
+    };
+    StageId::with_dependents(stages)
+}
+
+/// Returns the tables whose entries above the unwind target are removed by the given stage.
+const fn unwound_tables(stage: StageId) -> &'static [Tables] {
+    match stage {
+        StageId::Headers => &[
+            Tables::Headers,
+            Tables::HeaderNumbers,
+            Tables::CanonicalHeaders,
+            Tables::HeaderTerminalDifficulties,
+        ],
+        StageId::Bodies => &[
+            Tables::BlockBodyIndices,
+            Tables::BlockOmmers,
+            Tables::BlockWithdrawals,
+            Tables::Transactions,
+            Tables::TransactionBlocks,
+        ],
+        StageId::SenderRecovery => &[Tables::TransactionSenders],
+        StageId::Execution => &[
+            Tables::PlainAccountState,
+            Tables::PlainStorageState,
+            Tables::Bytecodes,
+            Tables::AccountChangeSets,
+            Tables::StorageChangeSets,
+            Tables::Receipts,
+        ],
+        StageId::AccountHashing => &[Tables::HashedAccounts],
+        StageId::StorageHashing => &[Tables::HashedStorages],
+        StageId::MerkleUnwind | StageId::MerkleExecute => {
+            &[Tables::AccountsTrie, Tables::StoragesTrie]
+        }
+        StageId::TransactionLookup => &[Tables::TransactionHashNumbers],
+        StageId::IndexAccountHistory => &[Tables::AccountsHistory],
+        StageId::IndexStorageHistory => &[Tables::StoragesHistory],
+        StageId::IndexLogAddresses => &[Tables::LogAddressIndex],
+        _ => &[],
+    }
+}
+
+/// Prints the stages and tables that are unwound along with the given stage, and asks for
+/// confirmation unless `yes` is set.
+///
+/// Returns `false` if there is nothing to unwind or the unwind was aborted.
+fn confirm_stage_unwind<N: ProviderNodeTypes>(
+    provider_factory: &ProviderFactory<N>,
+    stage: StageEnum,
+    target: BlockNumber,
+    yes: bool,
+) -> eyre::Result<bool> {
+    let provider = provider_factory.provider()?;
+    let mut unwound = Vec::new();
+    for stage_id in unwound_stages(stage) {
+        let checkpoint = provider.get_stage_checkpoint(stage_id)?.unwrap_or_default().block_number;
+        if checkpoint > target {
+            unwound.push((stage_id, checkpoint));
+        }
+    }
+
+    if unwound.is_empty() {
+        println!("The {stage} stage and its dependents are already at or below block {target}");
+        return Ok(false)
+    }
+
+    println!("Unwinding the following stages to block {target}:");
+    for (stage_id, checkpoint) in unwound {
+        let tables = unwound_tables(stage_id);
+        let tables = if tables.is_empty() {
+            "checkpoint only".to_string()
+        } else {
+            tables.iter().map(Tables::name).join(", ")
+        };
+        println!("  {stage_id}: {checkpoint} -> {target} ({tables})");
+    }
+    println!("The checkpoints of all other stages are kept.");
+
+    if yes {
+        return Ok(true)
+    }
+
+    print!("Do you want to continue? (y/N): ");
+    // Flush the buffer to ensure the message is printed immediately
+    io::stdout().flush().unwrap();
+
+    let mut input = String::new();
+    io::stdin().read_line(&mut input).expect("Failed to read line");
+
+    if !input.trim().eq_ignore_ascii_case("y") {
+        println!("Stage unwind aborted!");
+        return Ok(false)
+    }
+    Ok(true)
+}
+
 /// `reth stage unwind` subcommand
 #[derive(Subcommand, Debug, Eq, PartialEq)]
 enum Subcommands {
@@ -201,6 +358,20 @@ mod tests {
     use reth_ethereum_cli::chainspec::EthereumChainSpecParser;
 
     use super::*;
+    use futures::StreamExt;
+    use reth_db::tables;
+    use reth_db_api::{
+        cursor::DbCursorRO,
+        transaction::{DbTx, DbTxMut},
+    };
+    use reth_primitives::EthPrimitives;
+    use reth_provider::{test_utils::MockNodeTypesWithDB, DBProvider, StageCheckpointWriter};
+    use reth_stages::{
+        stages::TransactionLookupStage,
+        test_utils::{StorageKind, TestStageDB},
+        ExecInput, PipelineEvent, Stage, StageCheckpoint,
+    };
+    use reth_testing_utils::generators::{self, random_block_range, BlockRangeParams};
 
     #[test]
     fn parse_unwind() {
@@ -221,5 +392,203 @@ mod tests {
             "100",
         ]);
         assert_eq!(cmd.command, Subcommands::NumBlocks { amount: 100 });
+
+        let cmd = Command::<EthereumChainSpecParser>::parse_from([
+            "reth",
+            "--datadir",
+            "dir",
+            "--stage",
+            "tx-lookup",
+            "--yes",
+            "to-block",
+            "100",
+        ]);
+        assert_eq!(cmd.stage, Some(StageEnum::TxLookup));
+        assert!(cmd.yes);
+
+        assert!(Command::<EthereumChainSpecParser>::try_parse_from([
+            "reth",
+            "--datadir",
+            "dir",
+            "--offline",
+            "--stage",
+            "tx-lookup",
+            "to-block",
+            "100",
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn unwound_stages_include_dependents() {
+        assert_eq!(
+            unwound_stages(StageEnum::TxLookup),
+            [StageId::TransactionLookup, StageId::Prune, StageId::Finish]
+        );
+        assert_eq!(
+            unwound_stages(StageEnum::Merkle),
+            [StageId::MerkleUnwind, StageId::MerkleExecute, StageId::Prune, StageId::Finish]
+        );
+    }
+
+    #[tokio::test]
+    async fn unwind_stage_with_dependents() {
+        const TIP: BlockNumber = 20;
+        const TARGET: BlockNumber = 10;
+
+        let db = TestStageDB::default();
+        let blocks = random_block_range(
+            &mut generators::rng(),
+            0..=TIP,
+            BlockRangeParams { parent: Some(B256::ZERO), tx_count: 1..3, ..Default::default() },
+        );
+        db.insert_blocks(blocks.iter(), StorageKind::Static).unwrap();
+
+        // the node is synced to the tip
+        let provider_rw = db.factory.database_provider_rw().unwrap();
+        TransactionLookupStage::default()
+            .execute(&provider_rw, ExecInput { target: Some(TIP), checkpoint: None })
+            .unwrap();
+        for stage_id in StageId::ALL {
+            provider_rw.save_stage_checkpoint(stage_id, StageCheckpoint::new(TIP)).unwrap();
+        }
+        provider_rw.commit().unwrap();
+
+        // the index of a transaction above the target is corrupted
+        let corrupted = blocks[TARGET as usize + 1].body.transactions[0].hash();
+        db.commit(|tx| Ok(tx.put::<tables::TransactionHashNumbers>(corrupted, u64::MAX)?)).unwrap();
+
+        let cmd = Command::<EthereumChainSpecParser>::parse_from([
+            "reth",
+            "--datadir",
+            "dir",
+            "--stage",
+            "tx-lookup",
+            "--yes",
+            "to-block",
+            &TARGET.to_string(),
+        ]);
+        let mut pipeline = cmd.build_pipeline(Config::default(), db.factory.clone()).unwrap();
+        pipeline.unwind(TARGET, None).unwrap();
+
+        let checkpoint = |stage_id: StageId| {
+            db.factory
+                .provider()
+                .unwrap()
+                .get_stage_checkpoint(stage_id)
+                .unwrap()
+                .unwrap_or_default()
+                .block_number
+        };
+        for stage_id in StageId::ALL {
+            let expected =
+                if unwound_stages(StageEnum::TxLookup).contains(&stage_id) { TARGET } else { TIP };
+            assert_eq!(checkpoint(stage_id), expected, "{stage_id}");
+        }
+
+        // resync to the tip
+        let (tip_tx, tip_rx) = watch::channel(B256::ZERO);
+        let executor = NoopBlockExecutorProvider::<EthPrimitives>::default();
+        let mut pipeline = Pipeline::<MockNodeTypesWithDB>::builder()
+            .with_tip_sender(tip_tx)
+            .with_max_block(TIP)
+            .add_stages(DefaultStages::new(
+                db.factory.clone(),
+                tip_rx,
+                Arc::new(NoopConsensus::default()),
+                NoopHeaderDownloader::default(),
+                NoopBodiesDownloader::default(),
+                executor,
+                Default::default(),
+                PruneModes::none(),
+            ))
+            .build(
+                db.factory.clone(),
+                StaticFileProducer::new(db.factory.clone(), PruneModes::default()),
+            );
+        let events = pipeline.events();
+        pipeline.run().await.unwrap();
+        drop(pipeline);
+
+        // only the unwound stages ran again
+        let ran = events
+            .filter_map(|event| async move {
+                match event {
+                    PipelineEvent::Run { stage_id, .. } => Some(stage_id),
+                    _ => None,
+                }
+            })
+            .collect::<Vec<_>>()
+            .await;
+        assert!(ran.contains(&StageId::TransactionLookup));
+        assert!(!ran.contains(&StageId::Execution));
+        assert!(ran.iter().all(|stage_id| unwound_stages(StageEnum::TxLookup).contains(stage_id)));
+        for stage_id in StageId::ALL {
+            assert_eq!(checkpoint(stage_id), TIP, "{stage_id}");
+        }
+
+        // the corrupted index is rebuilt, the genesis block is never indexed
+        let mut expected = blocks
+            .iter()
+            .flat_map(|block| block.body.transactions.iter().map(|tx| (block.number, tx.hash())))
+            .enumerate()
+            .filter(|(_, (block_number, _))| *block_number > 0)
+            .map(|(tx_number, (_, hash))| (hash, tx_number as u64))
+            .collect::<Vec<_>>();
+        expected.sort_unstable();
+        let indexed = db
+            .query(|tx| {
+                Ok(tx
+                    .cursor_read::<tables::TransactionHashNumbers>()?
+                    .walk(None)?
+                    .collect::<Result<Vec<_>, _>>()?)
+            })
+            .unwrap();
+        assert_eq!(indexed, expected);
+    }
+
+    #[test]
+    fn unwind_disabled_log_address_index() {
+        const TIP: BlockNumber = 20;
+        const TARGET: BlockNumber = 10;
+
+        let db = TestStageDB::default();
+        let blocks = random_block_range(
+            &mut generators::rng(),
+            0..=TIP,
+            BlockRangeParams { parent: Some(B256::ZERO), tx_count: 0..1, ..Default::default() },
+        );
+        db.insert_blocks(blocks.iter(), StorageKind::Static).unwrap();
+
+        // the index was built although its stage is disabled in the config
+        let provider_rw = db.factory.database_provider_rw().unwrap();
+        for stage_id in StageId::ALL.into_iter().chain([StageId::IndexLogAddresses]) {
+            provider_rw.save_stage_checkpoint(stage_id, StageCheckpoint::new(TIP)).unwrap();
+        }
+        provider_rw.commit().unwrap();
+        assert!(!Config::default().stages.index_log_addresses.enabled);
+
+        let cmd = Command::<EthereumChainSpecParser>::parse_from([
+            "reth",
+            "--datadir",
+            "dir",
+            "--stage",
+            "index-log-addresses",
+            "--yes",
+            "to-block",
+            &TARGET.to_string(),
+        ]);
+        let mut pipeline = cmd.build_pipeline(Config::default(), db.factory.clone()).unwrap();
+        pipeline.unwind(TARGET, None).unwrap();
+
+        let checkpoint = db
+            .factory
+            .provider()
+            .unwrap()
+            .get_stage_checkpoint(StageId::IndexLogAddresses)
+            .unwrap()
+            .unwrap()
+            .block_number;
+        assert_eq!(checkpoint, TARGET);
     }
 }
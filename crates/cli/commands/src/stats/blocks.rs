@@ -0,0 +1,53 @@
+use clap::Parser;
+use reth_db_common::block_stats::{write_block_stats, BlockStatsFormat};
+use reth_provider::{providers::ProviderNodeTypes, BlockNumReader, ProviderFactory};
+use std::io::{self, BufWriter};
+
+/// The arguments for the `reth stats blocks` command
+#[derive(Parser, Debug)]
+pub struct Command {
+    /// The first block of the range.
+    #[arg(long, default_value_t = 1)]
+    from: u64,
+
+    /// The last block of the range, inclusive. Defaults to the tip.
+    #[arg(long)]
+    to: Option<u64>,
+
+    /// Prints one JSON object per block, followed by a line with the summary.
+    #[arg(long, conflicts_with = "csv")]
+    json: bool,
+
+    /// Prints comma separated values with a header line. The summary is printed to stderr.
+    #[arg(long)]
+    csv: bool,
+}
+
+impl Command {
+    /// Execute `stats blocks` command
+    pub fn execute<N: ProviderNodeTypes>(self, factory: &ProviderFactory<N>) -> eyre::Result<()> {
+        let to = match self.to {
+            Some(to) => to,
+            None => factory.best_block_number()?,
+        };
+        if self.from > to {
+            eyre::bail!("the range {}..={to} is empty", self.from)
+        }
+
+        let format = if self.json {
+            BlockStatsFormat::Json
+        } else if self.csv {
+            BlockStatsFormat::Csv
+        } else {
+            BlockStatsFormat::Table
+        };
+
+        let writer = BufWriter::new(io::stdout().lock());
+        let summary = write_block_stats(factory, self.from..=to, format, writer)?;
+        if format == BlockStatsFormat::Csv {
+            eprint!("{summary}");
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,37 @@
+//! `reth stats` command.
+
+use crate::common::{AccessRights, CliNodeTypes, Environment, EnvironmentArgs};
+use clap::{Parser, Subcommand};
+use reth_chainspec::{EthChainSpec, EthereumHardforks};
+use reth_cli::chainspec::ChainSpecParser;
+
+pub mod blocks;
+
+/// `reth stats` command
+#[derive(Debug, Parser)]
+pub struct Command<C: ChainSpecParser> {
+    #[command(flatten)]
+    env: EnvironmentArgs<C>,
+
+    #[command(subcommand)]
+    command: Subcommands,
+}
+
+/// `reth stats` subcommands
+#[derive(Subcommand, Debug)]
+pub enum Subcommands {
+    /// Prints per-block gas, transaction and state growth statistics for a range of blocks,
+    /// followed by a summary with totals and percentiles
+    Blocks(blocks::Command),
+}
+
+impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C> {
+    /// Execute `stats` command
+    pub async fn execute<N: CliNodeTypes<ChainSpec = C::ChainSpec>>(self) -> eyre::Result<()> {
+        let Environment { provider_factory, .. } = self.env.init::<N>(AccessRights::RO)?;
+
+        match self.command {
+            Subcommands::Blocks(command) => command.execute(&provider_factory),
+        }
+    }
+}
@@ -17,7 +17,10 @@ use reth_codecs::alloy::{
     withdrawal::Withdrawal,
 };
 use reth_db::{
-    models::{AccountBeforeTx, StoredBlockBodyIndices, StoredBlockOmmers, StoredBlockWithdrawals},
+    models::{
+        AccountBeforeTx, StoredBlockBodyIndices, StoredBlockOmmers, StoredBlockWithdrawals,
+        VersionedStoredBlockBodyIndices,
+    },
     ClientVersion,
 };
 use reth_fs_util as fs;
@@ -109,6 +112,7 @@ compact_types!(
         // reth_db_api
         StoredBlockOmmers,
         StoredBlockBodyIndices,
+        VersionedStoredBlockBodyIndices,
         StoredBlockWithdrawals,
         // Manual implementations
         TransactionSigned,
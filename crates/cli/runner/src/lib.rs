@@ -111,6 +111,30 @@ impl CliRunner {
 
         Ok(())
     }
+
+    /// Executes a regular future as a spawned blocking task until completion.
+    ///
+    /// Unlike [`Self::run_blocking_until_ctrl_c`], the future isn't abandoned on ctrl-c, it's
+    /// expected to listen for it and stop gracefully.
+    pub fn run_blocking_until_completion<F, E>(self, fut: F) -> Result<(), E>
+    where
+        F: Future<Output = Result<(), E>> + Send + 'static,
+        E: Send + Sync + From<std::io::Error> + 'static,
+    {
+        let tokio_runtime = tokio_runtime()?;
+        let handle = tokio_runtime.handle().clone();
+        let fut = tokio_runtime.handle().spawn_blocking(move || handle.block_on(fut));
+        tokio_runtime.block_on(async move { fut.await.expect("Failed to join task") })?;
+
+        // drop the tokio runtime on a separate thread because drop blocks until its pools
+        // (including blocking pool) are shutdown.
+        std::thread::Builder::new()
+            .name("tokio-runtime-shutdown".to_string())
+            .spawn(move || drop(tokio_runtime))
+            .unwrap();
+
+        Ok(())
+    }
 }
 
 /// [`CliRunner`] configuration when executing commands asynchronously
@@ -1,11 +1,13 @@
 //! Configuration files.
 
+use alloy_primitives::{Address, Bytes};
 use eyre::eyre;
 use reth_network_types::{PeersConfig, SessionsConfig};
 use reth_prune_types::PruneModes;
 use reth_stages_types::ExecutionStageThresholds;
 use serde::{Deserialize, Deserializer, Serialize};
 use std::{
+    collections::BTreeMap,
     ffi::OsStr,
     fs,
     path::{Path, PathBuf},
@@ -31,6 +33,12 @@ pub struct Config {
     pub peers: PeersConfig,
     /// Configuration for peer sessions.
     pub sessions: SessionsConfig,
+    /// Configuration for the payload builder.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub builder: Option<BuilderConfig>,
+    /// Configuration for the local node record.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enr: Option<EnrConfig>,
 }
 
 impl Config {
@@ -92,6 +100,25 @@ impl Config {
     pub fn update_prune_config(&mut self, prune_config: PruneConfig) {
         self.prune = Some(prune_config);
     }
+
+    /// Sets the fee recipient of locally built payloads.
+    pub fn update_fee_recipient(&mut self, fee_recipient: Address) {
+        self.builder.get_or_insert_with(Default::default).fee_recipient = Some(fee_recipient);
+    }
+
+    /// Sets a custom entry of the local node record.
+    ///
+    /// Returns an error if the key is one of the [`RESERVED_ENR_KEYS`].
+    pub fn update_enr_entry(&mut self, key: String, value: Bytes) -> eyre::Result<()> {
+        ensure_enr_key_not_reserved(&key)?;
+        self.enr.get_or_insert_with(Default::default).entries.insert(key, value);
+        Ok(())
+    }
+
+    /// Returns the custom entries of the local node record.
+    pub fn enr_entries(&self) -> impl Iterator<Item = (&String, &Bytes)> {
+        self.enr.iter().flat_map(|enr| enr.entries.iter())
+    }
 }
 
 /// Configuration for each stage in the pipeline.
@@ -120,6 +147,8 @@ pub struct StageConfig {
     pub index_account_history: IndexHistoryConfig,
     /// Index Storage History stage configuration.
     pub index_storage_history: IndexHistoryConfig,
+    /// Index Log Addresses stage configuration.
+    pub index_log_addresses: IndexLogAddressesConfig,
     /// Common ETL related configuration.
     pub etl: EtlConfig,
 }
@@ -195,6 +224,13 @@ pub struct BodiesConfig {
     ///
     /// Default: 100
     pub downloader_max_concurrent_requests: usize,
+    /// Whether to validate the downloaded bodies against their headers before they are
+    /// returned by the downloader. Peers sending invalid bodies are penalized.
+    ///
+    /// Only disable this if the bodies are validated later on anyway.
+    ///
+    /// Default: true
+    pub downloader_validate_bodies: bool,
 }
 
 impl Default for BodiesConfig {
@@ -205,6 +241,7 @@ impl Default for BodiesConfig {
             downloader_max_buffered_blocks_size_bytes: 2 * 1024 * 1024 * 1024, // ~2GB
             downloader_min_concurrent_requests: 5,
             downloader_max_concurrent_requests: 100,
+            downloader_validate_bodies: true,
         }
     }
 }
@@ -239,6 +276,9 @@ pub struct ExecutionConfig {
         deserialize_with = "deserialize_duration"
     )]
     pub max_duration: Option<Duration>,
+    /// Whether to log every receipt of a block that fails receipts root or logs bloom validation,
+    /// and compare them against the stored receipts of the block, if any.
+    pub verbose_receipts_diagnostics: bool,
 }
 
 impl Default for ExecutionConfig {
@@ -250,6 +290,7 @@ impl Default for ExecutionConfig {
             max_cumulative_gas: Some(30_000_000 * 50_000),
             // 10 minutes
             max_duration: Some(Duration::from_secs(10 * 60)),
+            verbose_receipts_diagnostics: false,
         }
     }
 }
@@ -373,6 +414,23 @@ impl Default for IndexHistoryConfig {
     }
 }
 
+/// Log address index stage configuration.
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default)]
+pub struct IndexLogAddressesConfig {
+    /// Whether to build and maintain the index of blocks with logs emitted by each address, which
+    /// speeds up `eth_getLogs` queries that filter by address.
+    pub enabled: bool,
+    /// The maximum number of blocks to process before committing progress to the database.
+    pub commit_threshold: u64,
+}
+
+impl Default for IndexLogAddressesConfig {
+    fn default() -> Self {
+        Self { enabled: false, commit_threshold: 100_000 }
+    }
+}
+
 /// Pruning configuration.
 #[derive(Debug, Clone, Deserialize, PartialEq, Eq, Serialize)]
 #[serde(default)]
@@ -410,6 +468,7 @@ impl PruneConfig {
                     account_history,
                     storage_history,
                     receipts_log_filter,
+                    custom,
                 },
         } = other;
 
@@ -428,6 +487,10 @@ impl PruneConfig {
         if self.segments.receipts_log_filter.0.is_empty() && !receipts_log_filter.0.is_empty() {
             self.segments.receipts_log_filter = receipts_log_filter;
         }
+
+        for (name, mode) in custom {
+            self.segments.custom.entry(name).or_insert(mode);
+        }
     }
 }
 
@@ -449,11 +512,56 @@ where
     })
 }
 
+/// Payload builder configuration.
+#[derive(Debug, Clone, Default, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default)]
+pub struct BuilderConfig {
+    /// The fee recipient of locally built payloads.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fee_recipient: Option<Address>,
+}
+
+/// Keys of the local node record that are set by the node itself, and can't be set as custom
+/// entries.
+pub const RESERVED_ENR_KEYS: &[&str] =
+    &["id", "secp256k1", "ip", "ip6", "tcp", "tcp6", "udp", "udp6", "eth", "opstack", "opel"];
+
+/// Local node record configuration.
+#[derive(Debug, Clone, Default, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default)]
+pub struct EnrConfig {
+    /// Custom entries advertised in the local node record, in addition to the ones set by the
+    /// node. The values are advertised as RLP byte strings.
+    ///
+    /// None of the keys can be one of the [`RESERVED_ENR_KEYS`].
+    #[serde(deserialize_with = "deserialize_enr_entries")]
+    pub entries: BTreeMap<String, Bytes>,
+}
+
+/// Returns an error if the key of a custom node record entry is set by the node itself.
+fn ensure_enr_key_not_reserved(key: &str) -> eyre::Result<()> {
+    if RESERVED_ENR_KEYS.contains(&key) {
+        return Err(eyre!("ENR key `{key}` is set by the node and can't be overridden"))
+    }
+    Ok(())
+}
+
+fn deserialize_enr_entries<'de, D>(deserializer: D) -> Result<BTreeMap<String, Bytes>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let entries = BTreeMap::<String, Bytes>::deserialize(deserializer)?;
+    for key in entries.keys() {
+        ensure_enr_key_not_reserved(key).map_err(serde::de::Error::custom)?;
+    }
+    Ok(entries)
+}
+
 #[cfg(test)]
 mod tests {
     use super::{Config, EXTENSION};
     use crate::PruneConfig;
-    use alloy_primitives::Address;
+    use alloy_primitives::{Address, Bytes};
     use reth_network_peers::TrustedPeer;
     use reth_prune_types::{PruneMode, PruneModes, ReceiptsLogPruneConfig};
     use std::{collections::BTreeMap, path::Path, str::FromStr, time::Duration};
@@ -555,6 +663,47 @@ mod tests {
         })
     }
 
+    #[test]
+    fn test_store_fee_recipient() {
+        with_tempdir("config-store-fee-recipient", |config_path| {
+            let mut config = Config::default();
+            config.save(config_path).unwrap();
+            assert!(!std::fs::read_to_string(config_path).unwrap().contains("[builder]"));
+
+            config.update_fee_recipient(Address::with_last_byte(1));
+            config.save(config_path).unwrap();
+
+            let loaded_config = Config::from_path(config_path).unwrap();
+            assert_eq!(
+                loaded_config.builder.and_then(|builder| builder.fee_recipient),
+                Some(Address::with_last_byte(1))
+            );
+        })
+    }
+
+    #[test]
+    fn test_store_enr_entries() {
+        with_tempdir("config-store-enr-entries", |config_path| {
+            let mut config = Config::default();
+            config.save(config_path).unwrap();
+            assert!(!std::fs::read_to_string(config_path).unwrap().contains("[enr"));
+
+            config.update_enr_entry("role".to_string(), Bytes::from_static(b"archive")).unwrap();
+            config.save(config_path).unwrap();
+
+            let loaded_config = Config::from_path(config_path).unwrap();
+            assert_eq!(
+                loaded_config.enr_entries().collect::<Vec<_>>(),
+                vec![(&"role".to_string(), &Bytes::from_static(b"archive"))]
+            );
+
+            // entries set by the node can't be overridden
+            assert!(config.update_enr_entry("eth".to_string(), Bytes::new()).is_err());
+            std::fs::write(config_path, "[enr.entries]\neth = \"0x01\"\n").unwrap();
+            assert!(Config::from_path(config_path).is_err());
+        })
+    }
+
     #[test]
     fn test_store_config_method() {
         with_tempdir("config-store-test-method", |config_path| {
@@ -947,6 +1096,7 @@ receipts = 'full'
                     Address::random(),
                     PruneMode::Full,
                 )])),
+                custom: BTreeMap::from([("deposit_events".to_string(), PruneMode::Full)]),
             },
         };
 
@@ -962,6 +1112,10 @@ receipts = 'full'
                     (Address::random(), PruneMode::Distance(1000)),
                     (Address::random(), PruneMode::Before(2000)),
                 ])),
+                custom: BTreeMap::from([
+                    ("deposit_events".to_string(), PruneMode::Distance(100)),
+                    ("withdrawal_events".to_string(), PruneMode::Distance(200)),
+                ]),
             },
         };
 
@@ -977,6 +1131,13 @@ receipts = 'full'
         assert_eq!(config1.segments.account_history, Some(PruneMode::Distance(2000)));
         assert_eq!(config1.segments.storage_history, Some(PruneMode::Before(5000)));
         assert_eq!(config1.segments.receipts_log_filter, original_filter);
+        assert_eq!(
+            config1.segments.custom,
+            BTreeMap::from([
+                ("deposit_events".to_string(), PruneMode::Full),
+                ("withdrawal_events".to_string(), PruneMode::Distance(200)),
+            ])
+        );
     }
 
     #[test]
@@ -0,0 +1,39 @@
+#![allow(missing_docs)]
+use alloy_consensus::Header;
+use criterion::*;
+use reth_chainspec::MAINNET;
+use reth_consensus_common::validation::validate_body_ommers;
+use reth_primitives::{proofs, BlockBody};
+
+criterion_group!(benches, validate_ommers);
+criterion_main!(benches);
+
+/// Validates the ommers of bodies before the merge, where they're hashed, and past it, where only
+/// the header is checked.
+fn validate_ommers(c: &mut Criterion) {
+    let chain_spec = MAINNET.clone();
+    let paris_block = chain_spec.paris_block().unwrap();
+    let mut group = c.benchmark_group("Validate Body Ommers");
+
+    for (name, number, ommers) in [
+        ("pre-merge", paris_block - 1, 0),
+        ("pre-merge", paris_block - 1, 2),
+        ("post-merge", paris_block, 0),
+    ] {
+        let ommers = (0..ommers)
+            .map(|i| Header { number: number - 1 - i, ..Default::default() })
+            .collect::<Vec<_>>();
+        let header = Header {
+            number,
+            ommers_hash: proofs::calculate_ommers_root(&ommers),
+            ..Default::default()
+        };
+        let body = BlockBody { ommers, ..Default::default() };
+
+        group.bench_function(BenchmarkId::new(name, body.ommers.len()), |b| {
+            b.iter(|| validate_body_ommers(&body, &header, &chain_spec).unwrap())
+        });
+    }
+
+    group.finish();
+}
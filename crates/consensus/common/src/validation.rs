@@ -77,17 +77,31 @@ pub fn validate_cancun_gas<H: BlockHeader, B: BlockBody>(
     Ok(())
 }
 
-/// Ensures the block response data matches the header.
+/// Validates that the ommers of the body match the ommers hash of the header.
 ///
-/// This ensures the body response items match the header's hashes:
-///   - ommer hash
-///   - transaction root
-///   - withdrawals root
-pub fn validate_body_against_header<B, H>(body: &B, header: &H) -> Result<(), ConsensusError>
+/// Blocks can't have ommers once the merge is active, see [`EthereumHardforks::has_ommers`], so
+/// the header of such blocks must commit to the empty list, which is checked without hashing the
+/// ommers of the body.
+#[inline]
+pub fn validate_body_ommers<B, H, ChainSpec>(
+    body: &B,
+    header: &H,
+    chain_spec: &ChainSpec,
+) -> Result<(), ConsensusError>
 where
     B: BlockBody,
     H: BlockHeader,
+    ChainSpec: EthereumHardforks,
 {
+    if !chain_spec.has_ommers(header.number()) {
+        if header.ommers_hash() != EMPTY_OMMER_ROOT_HASH {
+            return Err(ConsensusError::TheMergeOmmerRootIsNotEmpty)
+        }
+        if body.ommers().is_none_or(|ommers| ommers.is_empty()) {
+            return Ok(())
+        }
+    }
+
     let ommers_hash = body.calculate_ommers_root();
     if Some(header.ommers_hash()) != ommers_hash {
         return Err(ConsensusError::BodyOmmersHashDiff(
@@ -99,6 +113,27 @@ where
         ))
     }
 
+    Ok(())
+}
+
+/// Ensures the block response data matches the header.
+///
+/// This ensures the body response items match the header's hashes:
+///   - ommer hash
+///   - transaction root
+///   - withdrawals root
+pub fn validate_body_against_header<B, H, ChainSpec>(
+    body: &B,
+    header: &H,
+    chain_spec: &ChainSpec,
+) -> Result<(), ConsensusError>
+where
+    B: BlockBody,
+    H: BlockHeader,
+    ChainSpec: EthereumHardforks,
+{
+    validate_body_ommers(body, header, chain_spec)?;
+
     let tx_root = body.calculate_tx_root();
     if header.transactions_root() != tx_root {
         return Err(ConsensusError::BodyTransactionRootDiff(
@@ -139,16 +174,7 @@ where
     ChainSpec: EthereumHardforks,
 {
     // Check ommers hash
-    let ommers_hash = block.body.calculate_ommers_root();
-    if Some(block.header.ommers_hash()) != ommers_hash {
-        return Err(ConsensusError::BodyOmmersHashDiff(
-            GotExpected {
-                got: ommers_hash.unwrap_or(EMPTY_OMMER_ROOT_HASH),
-                expected: block.header.ommers_hash(),
-            }
-            .into(),
-        ))
-    }
+    validate_body_ommers(&block.body, block.header(), chain_spec)?;
 
     // Check transaction root
     if let Err(error) = block.ensure_transaction_root_valid() {
@@ -173,10 +199,12 @@ where
 ///  * `excess_blob_gas` exists as a header field
 ///  * `parent_beacon_block_root` exists as a header field
 ///  * `blob_gas_used` is a multiple of `DATA_GAS_PER_BLOB`
+///  * `blob_gas_used` doesn't exceed the maximum blob gas per block of the given [`BlobParams`]
 ///  * `excess_blob_gas` is a multiple of `DATA_GAS_PER_BLOB`
-///
-/// Note: This does not enforce any restrictions on `blob_gas_used`
-pub fn validate_4844_header_standalone<H: BlockHeader>(header: &H) -> Result<(), ConsensusError> {
+pub fn validate_4844_header_standalone<H: BlockHeader>(
+    header: &H,
+    blob_params: BlobParams,
+) -> Result<(), ConsensusError> {
     let blob_gas_used = header.blob_gas_used().ok_or(ConsensusError::BlobGasUsedMissing)?;
     let excess_blob_gas = header.excess_blob_gas().ok_or(ConsensusError::ExcessBlobGasMissing)?;
 
@@ -191,6 +219,14 @@ pub fn validate_4844_header_standalone<H: BlockHeader>(header: &H) -> Result<(),
         })
     }
 
+    let max_blob_gas_per_block = blob_params.max_blob_count * DATA_GAS_PER_BLOB;
+    if blob_gas_used > max_blob_gas_per_block {
+        return Err(ConsensusError::BlobGasUsedExceedsMaxBlobGasPerBlock {
+            blob_gas_used,
+            max_blob_gas_per_block,
+        })
+    }
+
     // `excess_blob_gas` must also be a multiple of `DATA_GAS_PER_BLOB`. This will be checked later
     // (via `calc_excess_blob_gas`), but it doesn't hurt to catch the problem sooner.
     if excess_blob_gas % DATA_GAS_PER_BLOB != 0 {
@@ -600,4 +636,53 @@ mod tests {
             }))
         );
     }
+
+    #[test]
+    fn ommers_across_the_merge() {
+        let chain_spec = ChainSpecBuilder::mainnet().build();
+        let paris_block = chain_spec.paris_block().unwrap();
+
+        let ommer = Header { number: paris_block - 2, ..Default::default() };
+        let with_ommers = BlockBody { ommers: vec![ommer.clone()], ..Default::default() };
+        let without_ommers = BlockBody::default();
+        let header = |number, ommers_hash| Header { number, ommers_hash, ..Default::default() };
+        let ommers_hash = proofs::calculate_ommers_root(&[ommer]);
+
+        // ommers are hashed before the merge
+        let pre_merge = paris_block - 1;
+        assert_eq!(
+            validate_body_ommers(&with_ommers, &header(pre_merge, ommers_hash), &chain_spec),
+            Ok(())
+        );
+        assert_eq!(
+            validate_body_ommers(&without_ommers, &header(pre_merge, ommers_hash), &chain_spec),
+            Err(ConsensusError::BodyOmmersHashDiff(
+                GotExpected { got: EMPTY_OMMER_ROOT_HASH, expected: ommers_hash }.into()
+            ))
+        );
+
+        // blocks past the merge can't have ommers
+        assert_eq!(
+            validate_body_ommers(
+                &without_ommers,
+                &header(paris_block, EMPTY_OMMER_ROOT_HASH),
+                &chain_spec
+            ),
+            Ok(())
+        );
+        assert_eq!(
+            validate_body_ommers(&with_ommers, &header(paris_block, ommers_hash), &chain_spec),
+            Err(ConsensusError::TheMergeOmmerRootIsNotEmpty)
+        );
+        assert_eq!(
+            validate_body_ommers(
+                &with_ommers,
+                &header(paris_block, EMPTY_OMMER_ROOT_HASH),
+                &chain_spec
+            ),
+            Err(ConsensusError::BodyOmmersHashDiff(
+                GotExpected { got: ommers_hash, expected: EMPTY_OMMER_ROOT_HASH }.into()
+            ))
+        );
+    }
 }
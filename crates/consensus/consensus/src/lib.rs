@@ -11,7 +11,7 @@
 
 extern crate alloc;
 
-use alloc::{fmt::Debug, sync::Arc, vec::Vec};
+use alloc::{boxed::Box, fmt::Debug, sync::Arc, vec::Vec};
 use alloy_consensus::Header;
 use alloy_eips::eip7685::Requests;
 use alloy_primitives::{BlockHash, BlockNumber, Bloom, B256, U256};
@@ -24,6 +24,9 @@ use reth_primitives_traits::constants::MINIMUM_GAS_LIMIT;
 /// A consensus implementation that does nothing.
 pub mod noop;
 
+mod receipts;
+pub use receipts::{ReceiptDiff, ReceiptFieldDiff, ReceiptSummary, ReceiptsDiagnostics};
+
 #[cfg(any(test, feature = "test-utils"))]
 /// test helpers for mocking consensus
 pub mod test_utils;
@@ -221,12 +224,22 @@ pub enum ConsensusError {
     BodyTransactionRootDiff(GotExpectedBoxed<B256>),
 
     /// Error when the receipt root in the block is different from the expected receipt root.
-    #[display("receipt root mismatch: {_0}")]
-    BodyReceiptRootDiff(GotExpectedBoxed<B256>),
+    #[display("receipt root mismatch: {root}{diagnostics}")]
+    BodyReceiptRootDiff {
+        /// The receipt root diff.
+        root: GotExpectedBoxed<B256>,
+        /// Diagnostics of the receipts produced by execution.
+        diagnostics: Box<ReceiptsDiagnostics>,
+    },
 
     /// Error when header bloom filter is different from the expected bloom filter.
-    #[display("header bloom filter mismatch: {_0}")]
-    BodyBloomLogDiff(GotExpectedBoxed<Bloom>),
+    #[display("header bloom filter mismatch: {bloom}{diagnostics}")]
+    BodyBloomLogDiff {
+        /// The logs bloom diff.
+        bloom: GotExpectedBoxed<Bloom>,
+        /// Diagnostics of the receipts produced by execution.
+        diagnostics: Box<ReceiptsDiagnostics>,
+    },
 
     /// Error when the withdrawals root in the block is different from the expected withdrawals
     /// root.
@@ -307,6 +320,18 @@ pub enum ConsensusError {
     #[display("ommer root after merge is not empty")]
     TheMergeOmmerRootIsNotEmpty,
 
+    /// Error when the mix hash of a proof-of-work header differs from the one computed from its
+    /// seal.
+    #[display("mismatched proof-of-work mix hash: {_0}")]
+    InvalidMixHash(GotExpectedBoxed<B256>),
+
+    /// Error when the proof-of-work of a header does not meet its difficulty.
+    #[display("proof-of-work does not meet difficulty {difficulty}")]
+    InsufficientProofOfWork {
+        /// The difficulty of the header.
+        difficulty: U256,
+    },
+
     /// Error when the withdrawals root is missing.
     #[display("missing withdrawals root")]
     WithdrawalsRootMissing,
@@ -0,0 +1,267 @@
+//! Diagnostics of receipts root and logs bloom mismatches.
+
+use alloc::{vec, vec::Vec};
+use alloy_consensus::TxReceipt;
+use alloy_primitives::{Bloom, B256};
+use core::fmt;
+use reth_primitives::GotExpected;
+
+/// Summary of a receipt, holding the fields that are reported on a receipts root or logs bloom
+/// mismatch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReceiptSummary {
+    /// Hash of the transaction, if known.
+    pub tx_hash: Option<B256>,
+    /// Whether the transaction was executed successfully.
+    pub success: bool,
+    /// Gas used by the transaction.
+    pub gas_used: u64,
+    /// Number of logs emitted by the transaction.
+    pub logs_count: usize,
+}
+
+impl ReceiptSummary {
+    /// Summarizes the receipts of a block.
+    ///
+    /// Transaction hashes are matched to receipts by index, missing ones are left empty.
+    pub fn from_receipts<R: TxReceipt>(
+        receipts: &[R],
+        tx_hashes: impl IntoIterator<Item = B256>,
+    ) -> Vec<Self> {
+        let mut tx_hashes = tx_hashes.into_iter();
+        let mut cumulative_gas_used = 0;
+        receipts
+            .iter()
+            .map(|receipt| {
+                let gas_used = receipt.cumulative_gas_used().saturating_sub(cumulative_gas_used);
+                cumulative_gas_used = receipt.cumulative_gas_used();
+                Self {
+                    tx_hash: tx_hashes.next(),
+                    success: receipt.status(),
+                    gas_used,
+                    logs_count: receipt.logs().len(),
+                }
+            })
+            .collect()
+    }
+}
+
+/// A receipt field that differs from its expected value.
+#[derive(Debug, Clone, PartialEq, Eq, derive_more::Display)]
+pub enum ReceiptFieldDiff {
+    /// The execution status differs.
+    #[display("status {_0}")]
+    Success(GotExpected<bool>),
+    /// The gas used by the transaction differs.
+    #[display("gas used {_0}")]
+    GasUsed(GotExpected<u64>),
+    /// The number of logs differs.
+    #[display("logs count {_0}")]
+    LogsCount(GotExpected<usize>),
+    /// The receipt is missing or unexpected.
+    #[display("receipts count {_0}")]
+    ReceiptsCount(GotExpected<usize>),
+    /// The logs of the receipt set bits that are not part of the expected logs bloom.
+    #[display("logs bloom has bits that are not set in the expected bloom")]
+    LogsBloom,
+}
+
+/// The first receipt of a block that differs from its expected value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReceiptDiff {
+    /// Index of the transaction in the block.
+    pub index: usize,
+    /// Hash of the transaction, if known.
+    pub tx_hash: Option<B256>,
+    /// Fields of the receipt that differ.
+    pub fields: Vec<ReceiptFieldDiff>,
+}
+
+impl fmt::Display for ReceiptDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "transaction {}", self.index)?;
+        if let Some(tx_hash) = self.tx_hash {
+            write!(f, " [{tx_hash}]")?;
+        }
+        for (i, field) in self.fields.iter().enumerate() {
+            let separator = if i == 0 { ": " } else { ", " };
+            write!(f, "{separator}{field}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Structured diagnostics of a receipts root or logs bloom mismatch.
+///
+/// Without the expected receipts, only receipts whose logs are not covered by the expected logs
+/// bloom can be identified, see [`ReceiptsDiagnostics::new`]. If the expected receipts are known,
+/// [`ReceiptsDiagnostics::compare_with`] finds the first receipt that differs.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ReceiptsDiagnostics {
+    /// The first receipt that is known to differ, if any could be identified.
+    pub first_mismatch: Option<ReceiptDiff>,
+    /// Summaries of all receipts produced by execution.
+    pub receipts: Vec<ReceiptSummary>,
+}
+
+impl ReceiptsDiagnostics {
+    /// Summarizes the receipts produced by execution and finds the first receipt whose logs set
+    /// bits that are not part of the expected logs bloom.
+    pub fn new<R: TxReceipt>(
+        receipts: &[R],
+        tx_hashes: impl IntoIterator<Item = B256>,
+        expected_logs_bloom: Bloom,
+    ) -> Self {
+        let summaries = ReceiptSummary::from_receipts(receipts, tx_hashes);
+        let first_mismatch = receipts
+            .iter()
+            .position(|receipt| {
+                let bloom = receipt.bloom();
+                bloom | expected_logs_bloom != expected_logs_bloom
+            })
+            .map(|index| ReceiptDiff {
+                index,
+                tx_hash: summaries[index].tx_hash,
+                fields: vec![ReceiptFieldDiff::LogsBloom],
+            });
+
+        Self { first_mismatch, receipts: summaries }
+    }
+
+    /// Compares the receipts produced by execution against the expected ones and records the
+    /// first receipt that differs.
+    ///
+    /// Returns the first mismatch, if any was found.
+    pub fn compare_with(&mut self, expected: &[ReceiptSummary]) -> Option<&ReceiptDiff> {
+        let mismatch =
+            self.receipts.iter().zip(expected).enumerate().find_map(|(index, (got, expected))| {
+                let mut fields = Vec::new();
+                if got.success != expected.success {
+                    fields.push(ReceiptFieldDiff::Success(GotExpected::new(
+                        got.success,
+                        expected.success,
+                    )));
+                }
+                if got.gas_used != expected.gas_used {
+                    fields.push(ReceiptFieldDiff::GasUsed(GotExpected::new(
+                        got.gas_used,
+                        expected.gas_used,
+                    )));
+                }
+                if got.logs_count != expected.logs_count {
+                    fields.push(ReceiptFieldDiff::LogsCount(GotExpected::new(
+                        got.logs_count,
+                        expected.logs_count,
+                    )));
+                }
+                (!fields.is_empty()).then(|| ReceiptDiff {
+                    index,
+                    tx_hash: got.tx_hash.or(expected.tx_hash),
+                    fields,
+                })
+            });
+
+        let mismatch = mismatch.or_else(|| {
+            (self.receipts.len() != expected.len()).then(|| {
+                let index = self.receipts.len().min(expected.len());
+                ReceiptDiff {
+                    index,
+                    tx_hash: self
+                        .receipts
+                        .get(index)
+                        .or_else(|| expected.get(index))
+                        .and_then(|receipt| receipt.tx_hash),
+                    fields: vec![ReceiptFieldDiff::ReceiptsCount(GotExpected::new(
+                        self.receipts.len(),
+                        expected.len(),
+                    ))],
+                }
+            })
+        });
+
+        if mismatch.is_some() {
+            self.first_mismatch = mismatch;
+        }
+        self.first_mismatch.as_ref()
+    }
+}
+
+impl fmt::Display for ReceiptsDiagnostics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(mismatch) = &self.first_mismatch {
+            write!(f, "; first mismatching receipt at {mismatch}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{Address, Log, LogData};
+    use reth_primitives::Receipt;
+
+    fn receipts() -> Vec<Receipt> {
+        (0..4)
+            .map(|i| Receipt {
+                success: true,
+                cumulative_gas_used: 21_000 * (i + 1),
+                logs: vec![Log {
+                    address: Address::with_last_byte(i as u8),
+                    data: LogData::default(),
+                }],
+                ..Default::default()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn unexpected_logs_bloom() {
+        let receipts = receipts();
+        let expected_bloom =
+            receipts[..2].iter().fold(Bloom::ZERO, |bloom, receipt| bloom | receipt.bloom());
+        let tx_hashes = (0..4).map(|i| B256::with_last_byte(i)).collect::<Vec<_>>();
+
+        let diagnostics = ReceiptsDiagnostics::new(&receipts, tx_hashes.clone(), expected_bloom);
+        assert_eq!(
+            diagnostics.first_mismatch,
+            Some(ReceiptDiff {
+                index: 2,
+                tx_hash: Some(tx_hashes[2]),
+                fields: vec![ReceiptFieldDiff::LogsBloom]
+            })
+        );
+        assert_eq!(diagnostics.receipts.len(), 4);
+        assert!(diagnostics.receipts.iter().all(|receipt| receipt.gas_used == 21_000));
+    }
+
+    #[test]
+    fn compare_with_expected_receipts() {
+        let expected = receipts();
+        let mut got = expected.clone();
+        got[1].success = false;
+        got[1].cumulative_gas_used += 1;
+
+        let mut diagnostics = ReceiptsDiagnostics::new(&got, [], Bloom::repeat_byte(0xff));
+        assert_eq!(diagnostics.first_mismatch, None);
+
+        let mismatch =
+            diagnostics.compare_with(&ReceiptSummary::from_receipts(&expected, [])).unwrap();
+        assert_eq!(
+            mismatch,
+            &ReceiptDiff {
+                index: 1,
+                tx_hash: None,
+                fields: vec![
+                    ReceiptFieldDiff::Success(GotExpected::new(false, true)),
+                    ReceiptFieldDiff::GasUsed(GotExpected::new(21_001, 21_000)),
+                ]
+            }
+        );
+        assert_eq!(
+            diagnostics.to_string(),
+            "; first mismatching receipt at transaction 1: status got false, expected true, gas \
+             used got 21001, expected 21000"
+        );
+    }
+}
@@ -61,6 +61,7 @@ where
         attributes_generator: impl Fn(u64) -> Engine::PayloadBuilderAttributes + 'static,
     ) -> eyre::Result<Self> {
         let builder = node.payload_builder.clone();
+        eyre::ensure!(node.is_auth_server_enabled(), "auth server is disabled");
 
         Ok(Self {
             inner: node.clone(),
@@ -1,5 +1,7 @@
 //! Invalid block hook implementations.
 
+mod reorg;
 mod witness;
 
+pub use reorg::DeepReorgArtifacts;
 pub use witness::InvalidBlockWitnessHook;
@@ -0,0 +1,81 @@
+use alloy_primitives::B256;
+use futures::{Stream, StreamExt};
+use reth_primitives::NodePrimitives;
+use reth_provider::{CanonStateNotification, ReorgHistory};
+use reth_rpc_api::ReorgHistoryEntry;
+use reth_tracing::tracing::{info, warn};
+use serde::Serialize;
+use std::{fs::File, io::Write, path::PathBuf, pin::pin};
+
+/// A deep reorg and the reorgs that preceded it, as saved to a file.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct DeepReorgArtifact {
+    /// The deep reorg.
+    reorg: ReorgHistoryEntry,
+    /// The recorded reorgs up to and including the deep reorg, most recent first.
+    history: Vec<ReorgHistoryEntry>,
+}
+
+/// Saves every reorg of the canonical chain deeper than a threshold, together with the recorded
+/// reorg history, to a file.
+#[derive(Debug)]
+pub struct DeepReorgArtifacts {
+    /// The reorg history recorded by the engine.
+    reorg_history: ReorgHistory,
+    /// Reorgs that remove more than this number of blocks from the canonical chain are saved.
+    threshold: u64,
+    /// The directory to write the reorgs to.
+    output_directory: PathBuf,
+}
+
+impl DeepReorgArtifacts {
+    /// Creates a new writer of deep reorgs.
+    pub const fn new(
+        reorg_history: ReorgHistory,
+        threshold: u64,
+        output_directory: PathBuf,
+    ) -> Self {
+        Self { reorg_history, threshold, output_directory }
+    }
+
+    /// Saves the deep reorgs of the given canonical state notifications until the stream ends.
+    pub async fn run<N: NodePrimitives>(
+        self,
+        notifications: impl Stream<Item = CanonStateNotification<N>>,
+    ) {
+        let mut notifications = pin!(notifications);
+        while let Some(notification) = notifications.next().await {
+            let CanonStateNotification::Reorg { new, .. } = notification else { continue };
+            let Some((_, new_tip)) = new.blocks().last_key_value() else { continue };
+
+            if let Err(err) = self.on_reorg(new_tip.hash()) {
+                warn!(target: "engine::invalid_block_hooks::reorg", %err, "Failed to save deep reorg");
+            }
+        }
+    }
+
+    fn on_reorg(&self, new_tip: B256) -> eyre::Result<()> {
+        // the engine records the reorg before it notifies the canonical state subscriptions
+        let history = self.reorg_history.latest(usize::MAX);
+        let Some(position) = history.iter().position(|reorg| reorg.new_tip.hash == new_tip) else {
+            return Ok(())
+        };
+        let reorg = history[position];
+        if reorg.depth() <= self.threshold {
+            return Ok(())
+        }
+
+        let artifact = DeepReorgArtifact {
+            reorg: reorg.into(),
+            history: history[position..].iter().copied().map(Into::into).collect(),
+        };
+        let path = self
+            .output_directory
+            .join(format!("{}_{}.reorg.json", reorg.new_tip.number, reorg.new_tip.hash));
+        File::create(&path)?.write_all(serde_json::to_string(&artifact)?.as_bytes())?;
+        info!(target: "engine::invalid_block_hooks::reorg", depth = reorg.depth(), ?path, "Saved deep reorg");
+
+        Ok(())
+    }
+}
@@ -1,6 +1,5 @@
 use alloy_consensus::BlockHeader;
 use alloy_primitives::{keccak256, B256};
-use alloy_rpc_types_debug::ExecutionWitness;
 use eyre::OptionExt;
 use pretty_assertions::Comparison;
 use reth_chainspec::{EthChainSpec, EthereumHardforks};
@@ -16,11 +15,11 @@ use reth_revm::{
     database::StateProviderDatabase, db::states::bundle_state::BundleRetention,
     primitives::EnvWithHandlerCfg, DatabaseCommit, StateBuilder,
 };
-use reth_rpc_api::DebugApiClient;
+use reth_rpc_api::{DebugApiClient, OrderedExecutionWitness};
 use reth_tracing::tracing::warn;
 use reth_trie::{updates::TrieUpdates, HashedStorage};
 use serde::Serialize;
-use std::{collections::HashMap, fmt::Debug, fs::File, io::Write, path::PathBuf};
+use std::{collections::BTreeMap, fmt::Debug, fs::File, io::Write, path::PathBuf};
 
 /// Generates a witness for the given block and saves it to a file.
 #[derive(Debug)]
@@ -128,7 +127,7 @@ where
         let mut bundle_state = db.take_bundle();
 
         // Initialize a map of preimages.
-        let mut state_preimages = HashMap::default();
+        let mut state_preimages = BTreeMap::new();
 
         // Grab all account proofs for the data accessed during block execution.
         //
@@ -165,8 +164,8 @@ where
         let state = state_provider.witness(Default::default(), hashed_state.clone())?;
 
         // Write the witness to the output directory.
-        let response = ExecutionWitness {
-            state: HashMap::from_iter(state),
+        let response = OrderedExecutionWitness {
+            state: state.into_iter().collect(),
             codes: Default::default(),
             keys: state_preimages,
         };
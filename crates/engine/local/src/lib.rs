@@ -13,5 +13,5 @@ pub mod payload;
 pub mod service;
 
 pub use miner::MiningMode;
-pub use payload::LocalPayloadAttributesBuilder;
+pub use payload::{LocalPayloadAttributesBuilder, DEV_FEE_RECIPIENT};
 pub use service::LocalEngineService;
@@ -1,23 +1,39 @@
 //! The implementation of the [`PayloadAttributesBuilder`] for the
 //! [`LocalEngineService`](super::service::LocalEngineService).
 
-use alloy_primitives::{Address, B256};
+use alloy_primitives::{address, Address, B256};
 use reth_chainspec::EthereumHardforks;
 use reth_ethereum_engine_primitives::EthPayloadAttributes;
-use reth_payload_primitives::PayloadAttributesBuilder;
+use reth_payload_primitives::{PayloadAttributesBuilder, SuggestedFeeRecipient};
 use std::sync::Arc;
 
+/// The default fee recipient of locally built payloads, the first account derived from the dev
+/// mnemonic "test test test test test test test test test test test junk".
+pub const DEV_FEE_RECIPIENT: Address = address!("f39Fd6e51aad88F6F4ce6aB8827279cffFb92266");
+
 /// The attributes builder for local Ethereum payload.
 #[derive(Debug)]
 #[non_exhaustive]
 pub struct LocalPayloadAttributesBuilder<ChainSpec> {
     chain_spec: Arc<ChainSpec>,
+    fee_recipient: SuggestedFeeRecipient,
 }
 
 impl<ChainSpec> LocalPayloadAttributesBuilder<ChainSpec> {
-    /// Creates a new instance of the builder.
-    pub const fn new(chain_spec: Arc<ChainSpec>) -> Self {
-        Self { chain_spec }
+    /// Creates a new instance of the builder that pays fees to [`DEV_FEE_RECIPIENT`].
+    pub fn new(chain_spec: Arc<ChainSpec>) -> Self {
+        Self { chain_spec, fee_recipient: SuggestedFeeRecipient::new(DEV_FEE_RECIPIENT) }
+    }
+
+    /// Sets the handle to the fee recipient of the built payloads.
+    pub fn with_fee_recipient(mut self, fee_recipient: SuggestedFeeRecipient) -> Self {
+        self.fee_recipient = fee_recipient;
+        self
+    }
+
+    /// Returns the handle to the fee recipient of the built payloads.
+    pub const fn fee_recipient(&self) -> &SuggestedFeeRecipient {
+        &self.fee_recipient
     }
 }
 
@@ -30,7 +46,7 @@ where
         EthPayloadAttributes {
             timestamp,
             prev_randao: B256::random(),
-            suggested_fee_recipient: Address::random(),
+            suggested_fee_recipient: self.fee_recipient.get(),
             withdrawals: self
                 .chain_spec
                 .is_shanghai_active_at_timestamp(timestamp)
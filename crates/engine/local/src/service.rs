@@ -28,7 +28,7 @@ use reth_engine_tree::{
         RequestHandlerEvent,
     },
     persistence::PersistenceHandle,
-    tree::{EngineApiTreeHandler, InvalidBlockHook, TreeConfig},
+    tree::{EngineApiTreeHandler, InvalidBlockHook, SenderCache, TreeConfig},
 };
 use reth_evm::execute::BlockExecutorProvider;
 use reth_node_types::BlockTy;
@@ -73,6 +73,7 @@ where
         payload_validator: V,
         tree_config: TreeConfig,
         invalid_block_hook: Box<dyn InvalidBlockHook<N::Primitives>>,
+        sender_cache: Box<dyn SenderCache>,
         sync_metrics_tx: MetricEventsSender,
         to_engine: UnboundedSender<BeaconEngineMessage<N::Engine>>,
         from_engine: EngineMessageStream<N::Engine>,
@@ -91,18 +92,20 @@ where
             PersistenceHandle::<N::Primitives>::spawn_service(provider, pruner, sync_metrics_tx);
         let canonical_in_memory_state = blockchain_db.canonical_in_memory_state();
 
-        let (to_tree_tx, from_tree) = EngineApiTreeHandler::<N::Primitives, _, _, _, _>::spawn_new(
-            blockchain_db.clone(),
-            executor_factory,
-            consensus,
-            payload_validator,
-            persistence_handle,
-            payload_builder.clone(),
-            canonical_in_memory_state,
-            tree_config,
-            invalid_block_hook,
-            engine_kind,
-        );
+        let (to_tree_tx, from_tree) =
+            EngineApiTreeHandler::<N::Primitives, _, _, _, _>::spawn_new_with_sender_cache(
+                blockchain_db.clone(),
+                executor_factory,
+                consensus,
+                payload_validator,
+                persistence_handle,
+                payload_builder.clone(),
+                canonical_in_memory_state,
+                tree_config,
+                invalid_block_hook,
+                sender_cache,
+                engine_kind,
+            );
 
         let handler = EngineApiRequestHandler::new(to_tree_tx, from_tree);
 
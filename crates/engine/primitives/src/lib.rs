@@ -25,6 +25,9 @@ pub use message::{BeaconEngineMessage, OnForkChoiceUpdated};
 mod invalid_block_hook;
 pub use invalid_block_hook::InvalidBlockHook;
 
+mod sender_cache;
+pub use sender_cache::SenderCache;
+
 pub use reth_payload_primitives::{
     BuiltPayload, EngineApiMessageVersion, EngineObjectValidationError, PayloadOrAttributes,
     PayloadTypes,
@@ -135,6 +138,9 @@ pub trait EngineValidator<Types: EngineTypes>: PayloadValidator {
     ///   > of a block referenced by forkchoiceState.headBlockHash.
     ///
     /// See also [engine api spec](https://github.com/ethereum/execution-apis/tree/fe8e13c288c592ec154ce25c534e26cb7ce0530d/src/engine)
+    ///
+    /// Validators that know the chain spec should also enforce the fork rules, see
+    /// [`PayloadAttributes::validate`].
     fn validate_payload_attributes_against_header(
         &self,
         attr: &<Types as PayloadTypes>::PayloadAttributes,
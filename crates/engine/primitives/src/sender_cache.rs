@@ -0,0 +1,20 @@
+use alloy_primitives::{Address, TxHash};
+
+/// A source of already recovered transaction senders, e.g. the transaction pool.
+///
+/// Used to skip the signature recovery of the transactions of new payloads that are already
+/// known.
+pub trait SenderCache: Send + Sync {
+    /// Returns the cached sender of each of the given transactions, in the same order, or `None`
+    /// for the transactions that are not cached.
+    fn cached_senders(&self, tx_hashes: &[TxHash]) -> Vec<Option<Address>>;
+}
+
+impl<F> SenderCache for F
+where
+    F: Fn(&[TxHash]) -> Vec<Option<Address>> + Send + Sync,
+{
+    fn cached_senders(&self, tx_hashes: &[TxHash]) -> Vec<Option<Address>> {
+        self(tx_hashes)
+    }
+}
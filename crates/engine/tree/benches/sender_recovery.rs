@@ -0,0 +1,59 @@
+//! Benchmark comparing serial sender recovery of a block with the recovery that takes the senders
+//! of known transactions from a cache.
+
+#![allow(missing_docs)]
+
+use alloy_primitives::{map::HashMap, Address, TxHash};
+use criterion::{criterion_group, criterion_main, Criterion};
+use reth_engine_tree::tree::{recover_senders, NoopSenderCache};
+use reth_primitives::TransactionSigned;
+use reth_primitives_traits::SignedTransaction;
+use reth_testing_utils::generators::{self, random_signed_tx};
+use std::hint::black_box;
+
+/// Number of transactions of the block.
+const BLOCK_TRANSACTIONS: usize = 300;
+
+/// Share of the transactions of the block that are in the cache.
+const CACHE_HIT_RATE: f64 = 0.8;
+
+fn bench_sender_recovery(c: &mut Criterion) {
+    let mut rng = generators::rng();
+    let transactions =
+        (0..BLOCK_TRANSACTIONS).map(|_| random_signed_tx(&mut rng)).collect::<Vec<_>>();
+    let cached = (BLOCK_TRANSACTIONS as f64 * CACHE_HIT_RATE) as usize;
+    let cache = transactions
+        .iter()
+        .take(cached)
+        .map(|tx| (*tx.tx_hash(), tx.recover_signer().unwrap()))
+        .collect::<HashMap<TxHash, Address>>();
+    let cache = move |tx_hashes: &[TxHash]| {
+        tx_hashes.iter().map(|hash| cache.get(hash).copied()).collect::<Vec<_>>()
+    };
+
+    let mut group = c.benchmark_group("Sender Recovery");
+    group.bench_function("serial", |b| {
+        b.iter(|| {
+            black_box(
+                transactions
+                    .iter()
+                    .map(|tx| tx.recover_signer())
+                    .collect::<Option<Vec<_>>>()
+                    .unwrap(),
+            )
+        })
+    });
+    group.bench_function("parallel", |b| {
+        b.iter(|| black_box(TransactionSigned::recover_signers(&transactions, BLOCK_TRANSACTIONS)))
+    });
+    group.bench_function("parallel without cache", |b| {
+        b.iter(|| black_box(recover_senders(&transactions, &NoopSenderCache).unwrap()))
+    });
+    group.bench_function("parallel with 80% cached", |b| {
+        b.iter(|| black_box(recover_senders(&transactions, &cache).unwrap()))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_sender_recovery);
+criterion_main!(benches);
@@ -0,0 +1,174 @@
+use crate::metrics::EngineMessageChannelMetrics;
+use std::{
+    fmt,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc::{self, RecvError, RecvTimeoutError, SendError},
+        Arc,
+    },
+    time::Duration,
+};
+use tracing::warn;
+
+/// Creates a channel of messages to the engine tree that is considered full once `capacity`
+/// messages are queued.
+///
+/// Sending never blocks, so the tree can always queue messages to itself without deadlocking.
+/// Instead, senders that can drop or answer a message without involving the tree, like the
+/// [`EngineApiRequestHandler`](crate::engine::EngineApiRequestHandler) for engine API requests,
+/// check [`EngineMessageSender::is_full`] before sending.
+pub fn engine_message_channel<T>(
+    capacity: usize,
+) -> (EngineMessageSender<T>, EngineMessageReceiver<T>) {
+    let (tx, rx) = mpsc::channel();
+    let queued = Arc::new(AtomicUsize::new(0));
+    let metrics = EngineMessageChannelMetrics::default();
+    let sender = EngineMessageSender {
+        tx,
+        queued: queued.clone(),
+        capacity,
+        high_water_mark: capacity - capacity / 4,
+        metrics: metrics.clone(),
+    };
+    (sender, EngineMessageReceiver { rx, queued, metrics })
+}
+
+/// The sending half of an [`engine_message_channel`].
+pub struct EngineMessageSender<T> {
+    tx: mpsc::Sender<T>,
+    queued: Arc<AtomicUsize>,
+    capacity: usize,
+    /// Number of queued messages at which a warning is logged.
+    high_water_mark: usize,
+    metrics: EngineMessageChannelMetrics,
+}
+
+impl<T> EngineMessageSender<T> {
+    /// Queues a message, even if the channel is full.
+    pub fn send(&self, msg: T) -> Result<(), SendError<T>> {
+        // counted before sending, so that the receiver never sees more messages than are counted
+        let queued = self.queued.fetch_add(1, Ordering::Relaxed) + 1;
+        if let Err(err) = self.tx.send(msg) {
+            self.queued.fetch_sub(1, Ordering::Relaxed);
+            return Err(err)
+        }
+        self.metrics.queued_engine_messages.set(queued as f64);
+        if queued == self.high_water_mark {
+            warn!(target: "engine::tree", queued, capacity = self.capacity, "Engine message queue is filling up");
+        }
+        Ok(())
+    }
+
+    /// Returns the number of queued messages.
+    pub fn len(&self) -> usize {
+        self.queued.load(Ordering::Relaxed)
+    }
+
+    /// Returns `true` if no messages are queued.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns `true` if the number of queued messages reached the capacity of the channel.
+    pub fn is_full(&self) -> bool {
+        self.len() >= self.capacity
+    }
+
+    /// Returns the number of queued messages above which the channel is full.
+    pub const fn capacity(&self) -> usize {
+        self.capacity
+    }
+}
+
+impl<T> Clone for EngineMessageSender<T> {
+    fn clone(&self) -> Self {
+        Self {
+            tx: self.tx.clone(),
+            queued: self.queued.clone(),
+            capacity: self.capacity,
+            high_water_mark: self.high_water_mark,
+            metrics: self.metrics.clone(),
+        }
+    }
+}
+
+impl<T> fmt::Debug for EngineMessageSender<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EngineMessageSender")
+            .field("queued", &self.len())
+            .field("capacity", &self.capacity)
+            .finish_non_exhaustive()
+    }
+}
+
+/// The receiving half of an [`engine_message_channel`].
+pub struct EngineMessageReceiver<T> {
+    rx: mpsc::Receiver<T>,
+    queued: Arc<AtomicUsize>,
+    metrics: EngineMessageChannelMetrics,
+}
+
+impl<T> EngineMessageReceiver<T> {
+    /// Blocks until a message is received, see [`mpsc::Receiver::recv`].
+    pub fn recv(&self) -> Result<T, RecvError> {
+        self.rx.recv().inspect(|_| self.on_recv())
+    }
+
+    /// Blocks until a message is received or the timeout elapsed, see
+    /// [`mpsc::Receiver::recv_timeout`].
+    pub fn recv_timeout(&self, timeout: Duration) -> Result<T, RecvTimeoutError> {
+        self.rx.recv_timeout(timeout).inspect(|_| self.on_recv())
+    }
+
+    /// Returns the number of queued messages.
+    pub fn len(&self) -> usize {
+        self.queued.load(Ordering::Relaxed)
+    }
+
+    /// Returns `true` if no messages are queued.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn on_recv(&self) {
+        let queued = self.queued.fetch_sub(1, Ordering::Relaxed).saturating_sub(1);
+        self.metrics.queued_engine_messages.set(queued as f64);
+    }
+}
+
+impl<T> fmt::Debug for EngineMessageReceiver<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EngineMessageReceiver").field("queued", &self.len()).finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_queued_messages() {
+        let (tx, rx) = engine_message_channel(2);
+        assert!(tx.is_empty());
+
+        tx.send(1).unwrap();
+        assert!(!tx.is_full());
+        tx.clone().send(2).unwrap();
+        assert!(tx.is_full());
+
+        // sending to a full channel still queues the message
+        tx.send(3).unwrap();
+        assert_eq!(tx.len(), 3);
+
+        assert_eq!(rx.recv(), Ok(1));
+        assert_eq!(rx.recv_timeout(Duration::from_millis(10)), Ok(2));
+        assert!(!tx.is_full());
+        assert_eq!(rx.recv(), Ok(3));
+        assert!(rx.is_empty());
+        assert_eq!(rx.recv_timeout(Duration::from_millis(10)), Err(RecvTimeoutError::Timeout));
+        assert!(rx.is_empty());
+
+        drop(tx);
+        assert_eq!(rx.recv(), Err(RecvError));
+    }
+}
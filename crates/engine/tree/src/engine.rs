@@ -3,22 +3,25 @@
 use crate::{
     backfill::BackfillAction,
     chain::{ChainHandler, FromOrchestrator, HandlerEvent},
+    channel::EngineMessageSender,
     download::{BlockDownloader, DownloadAction, DownloadOutcome},
+    metrics::EngineApiRequestHandlerMetrics,
 };
 use alloy_primitives::B256;
+use alloy_rpc_types_engine::{PayloadStatus, PayloadStatusEnum};
 use futures::{Stream, StreamExt};
 use reth_beacon_consensus::BeaconConsensusEngineEvent;
 use reth_chain_state::ExecutedBlock;
-use reth_engine_primitives::{BeaconEngineMessage, EngineTypes};
+use reth_engine_primitives::{BeaconEngineMessage, EngineTypes, OnForkChoiceUpdated};
 use reth_primitives::{NodePrimitives, SealedBlockWithSenders};
 use reth_primitives_traits::Block;
 use std::{
     collections::HashSet,
     fmt::Display,
-    sync::mpsc::Sender,
     task::{ready, Context, Poll},
 };
 use tokio::sync::mpsc::UnboundedReceiver;
+use tracing::debug;
 
 /// A [`ChainHandler`] that advances the chain based on incoming requests (CL engine API).
 ///
@@ -169,33 +172,51 @@ pub trait EngineRequestHandler: Send + Sync {
 ///
 /// In case required blocks are missing, the handler will request them from the network, by emitting
 /// a download request upstream.
+///
+/// If the tree falls behind processing its messages, e.g. while it's busy persisting blocks and the
+/// CL keeps sending requests, requests are answered right away with [`EngineRequest::reject`]
+/// instead of being queued once the channel to the tree is full.
 #[derive(Debug)]
 pub struct EngineApiRequestHandler<Request, N: NodePrimitives> {
     /// channel to send messages to the tree to execute the payload.
-    to_tree: Sender<FromEngine<Request, N::Block>>,
+    to_tree: EngineMessageSender<FromEngine<Request, N::Block>>,
     /// channel to receive messages from the tree.
     from_tree: UnboundedReceiver<EngineApiEvent<N>>,
+    /// Metrics for the handler.
+    metrics: EngineApiRequestHandlerMetrics,
 }
 
 impl<Request, N: NodePrimitives> EngineApiRequestHandler<Request, N> {
     /// Creates a new `EngineApiRequestHandler`.
-    pub const fn new(
-        to_tree: Sender<FromEngine<Request, N::Block>>,
+    pub fn new(
+        to_tree: EngineMessageSender<FromEngine<Request, N::Block>>,
         from_tree: UnboundedReceiver<EngineApiEvent<N>>,
     ) -> Self {
-        Self { to_tree, from_tree }
+        Self { to_tree, from_tree, metrics: Default::default() }
     }
 }
 
 impl<Request, N: NodePrimitives> EngineRequestHandler for EngineApiRequestHandler<Request, N>
 where
-    Request: Send,
+    Request: EngineRequest,
 {
     type Event = BeaconConsensusEngineEvent<N>;
     type Request = Request;
     type Block = N::Block;
 
     fn on_event(&mut self, event: FromEngine<Self::Request, Self::Block>) {
+        let event = match event {
+            FromEngine::Request(request) if self.to_tree.is_full() => match request.reject() {
+                Ok(()) => {
+                    debug!(target: "engine::tree", queued = self.to_tree.len(), "Engine message queue is full, rejected request");
+                    self.metrics.rejected_engine_requests.increment(1);
+                    return
+                }
+                Err(request) => FromEngine::Request(request),
+            },
+            event => event,
+        };
+
         // delegate to the tree
         let _ = self.to_tree.send(event);
     }
@@ -218,6 +239,14 @@ where
     }
 }
 
+/// A request that can be answered without being processed, if the engine is too busy to accept it.
+pub trait EngineRequest: Send + Sized {
+    /// Answers the request as if the node was syncing, without processing it.
+    ///
+    /// Returns the request back if it must be processed.
+    fn reject(self) -> Result<(), Self>;
+}
+
 /// The type for specifying the kind of engine api.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum EngineApiKind {
@@ -260,6 +289,23 @@ impl<T: EngineTypes, N: NodePrimitives> Display for EngineApiRequest<T, N> {
     }
 }
 
+impl<T: EngineTypes, N: NodePrimitives> EngineRequest for EngineApiRequest<T, N> {
+    fn reject(self) -> Result<(), Self> {
+        match self {
+            Self::Beacon(BeaconEngineMessage::NewPayload { tx, .. }) => {
+                let _ = tx.send(Ok(PayloadStatus::from_status(PayloadStatusEnum::Syncing)));
+            }
+            Self::Beacon(BeaconEngineMessage::ForkchoiceUpdated { tx, .. }) => {
+                let _ = tx.send(Ok(OnForkChoiceUpdated::syncing()));
+            }
+            Self::Beacon(BeaconEngineMessage::TransitionConfigurationExchanged) => {}
+            // the block was already executed and must not be lost
+            Self::InsertExecutedBlock(_) => return Err(self),
+        }
+        Ok(())
+    }
+}
+
 impl<T: EngineTypes, N: NodePrimitives> From<BeaconEngineMessage<T>> for EngineApiRequest<T, N> {
     fn from(msg: BeaconEngineMessage<T>) -> Self {
         Self::Beacon(msg)
@@ -352,3 +398,76 @@ impl DownloadRequest {
         Self::BlockSet(HashSet::from([hash]))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::channel::engine_message_channel;
+    use alloy_rpc_types_engine::ForkchoiceState;
+    use reth_engine_primitives::EngineApiMessageVersion;
+    use reth_ethereum_engine_primitives::EthEngineTypes;
+    use reth_primitives::EthPrimitives;
+    use tokio::sync::{
+        mpsc::unbounded_channel,
+        oneshot::{self, error::TryRecvError},
+    };
+
+    #[test]
+    fn rejects_requests_while_tree_is_busy() {
+        let (to_tree, from_engine) = engine_message_channel(4);
+        let (_to_engine, from_tree) = unbounded_channel();
+        let mut handler = EngineApiRequestHandler::<
+            EngineApiRequest<EthEngineTypes, EthPrimitives>,
+            EthPrimitives,
+        >::new(to_tree, from_tree);
+
+        // the CL floods the engine with requests while the tree doesn't process any messages
+        let mut responses = (0..1000)
+            .map(|_| {
+                let (tx, rx) = oneshot::channel();
+                handler.on_event(FromEngine::Request(
+                    BeaconEngineMessage::ForkchoiceUpdated {
+                        state: ForkchoiceState::default(),
+                        payload_attrs: None,
+                        version: EngineApiMessageVersion::default(),
+                        tx,
+                    }
+                    .into(),
+                ));
+                rx
+            })
+            .collect::<Vec<_>>();
+
+        // only as many requests as fit into the channel are queued, the rest is answered right away
+        assert_eq!(from_engine.len(), 4);
+        let (queued, rejected) = responses.split_at_mut(4);
+        for rx in queued {
+            assert_eq!(rx.try_recv().unwrap_err(), TryRecvError::Empty);
+        }
+        for rx in rejected {
+            let response = rx.try_recv().unwrap().unwrap();
+            assert!(response.forkchoice_status().is_syncing());
+        }
+
+        // events that aren't requests are still queued
+        handler.on_event(FromEngine::DownloadedBlocks(Vec::new()));
+        assert_eq!(from_engine.len(), 5);
+
+        // requests are queued again once the tree caught up
+        while !from_engine.is_empty() {
+            from_engine.recv().unwrap();
+        }
+        let (tx, mut rx) = oneshot::channel();
+        handler.on_event(FromEngine::Request(
+            BeaconEngineMessage::ForkchoiceUpdated {
+                state: ForkchoiceState::default(),
+                payload_attrs: None,
+                version: EngineApiMessageVersion::default(),
+                tx,
+            }
+            .into(),
+        ));
+        assert_eq!(from_engine.len(), 1);
+        assert_eq!(rx.try_recv().unwrap_err(), TryRecvError::Empty);
+    }
+}
@@ -1,5 +1,5 @@
 use reth_metrics::{
-    metrics::{Gauge, Histogram},
+    metrics::{Counter, Gauge, Histogram},
     Metrics,
 };
 
@@ -11,6 +11,23 @@ pub(crate) struct BlockDownloaderMetrics {
     pub(crate) active_block_downloads: Gauge,
 }
 
+/// Metrics for the channel of messages to the engine tree.
+#[derive(Metrics, Clone)]
+#[metrics(scope = "consensus.engine.beacon")]
+pub(crate) struct EngineMessageChannelMetrics {
+    /// How many messages are queued for the engine tree.
+    pub(crate) queued_engine_messages: Gauge,
+}
+
+/// Metrics for the `EngineApiRequestHandler`.
+#[derive(Metrics)]
+#[metrics(scope = "consensus.engine.beacon")]
+pub(crate) struct EngineApiRequestHandlerMetrics {
+    /// How many engine API requests were answered with `SYNCING`, because too many messages were
+    /// queued for the engine tree.
+    pub(crate) rejected_engine_requests: Counter,
+}
+
 /// Metrics for the `PersistenceService`
 #[derive(Metrics)]
 #[metrics(scope = "consensus.engine.persistence")]
@@ -2,6 +2,7 @@ use crate::metrics::PersistenceMetrics;
 use alloy_consensus::BlockHeader;
 use alloy_eips::BlockNumHash;
 use reth_chain_state::ExecutedBlock;
+use reth_db_models::StoredForkchoiceState;
 use reth_errors::ProviderError;
 use reth_primitives::{EthPrimitives, NodePrimitives};
 use reth_provider::{
@@ -115,6 +116,11 @@ where
                     provider.save_safe_block_number(safe_block)?;
                     provider.commit()?;
                 }
+                PersistenceAction::SaveForkchoiceState(state) => {
+                    let provider = self.provider.database_provider_rw()?;
+                    provider.save_forkchoice_state(state)?;
+                    provider.commit()?;
+                }
             }
         }
         Ok(())
@@ -194,6 +200,9 @@ pub enum PersistenceAction<N: NodePrimitives = EthPrimitives> {
 
     /// Update the persisted safe block on disk
     SaveSafeBlock(u64),
+
+    /// Update the persisted last valid forkchoice state on disk
+    SaveForkchoiceState(StoredForkchoiceState),
 }
 
 /// A handle to the persistence service
@@ -280,6 +289,14 @@ impl<T: NodePrimitives> PersistenceHandle<T> {
         self.send_action(PersistenceAction::SaveSafeBlock(safe_block))
     }
 
+    /// Persists the last valid forkchoice state on disk.
+    pub fn save_forkchoice_state(
+        &self,
+        state: StoredForkchoiceState,
+    ) -> Result<(), SendError<PersistenceAction<T>>> {
+        self.send_action(PersistenceAction::SaveForkchoiceState(state))
+    }
+
     /// Tells the persistence service to remove blocks above a certain block number. The removed
     /// blocks are returned by the service.
     ///
@@ -11,6 +11,19 @@ const DEFAULT_MAX_INVALID_HEADER_CACHE_LENGTH: u32 = 256;
 
 const DEFAULT_MAX_EXECUTE_BLOCK_BATCH_SIZE: usize = 4;
 
+/// Default maximum number of threads used to pre-warm the state of a block before execution.
+pub const DEFAULT_STATE_PREWARM_PARALLELISM: usize = 8;
+
+/// Default depth above which a reorg of the canonical chain is logged as a warning.
+pub const DEFAULT_DEEP_REORG_THRESHOLD: u64 = 2;
+
+/// Default maximum number of branch nodes of the database cached for state root computation.
+pub const DEFAULT_TRIE_NODE_CACHE_SIZE: u32 = 100_000;
+
+/// Default number of queued messages to the engine tree above which engine API requests are
+/// answered with `SYNCING` instead of being queued.
+pub const DEFAULT_ENGINE_MESSAGE_QUEUE_SIZE: usize = 256;
+
 /// The configuration of the engine tree.
 #[derive(Debug)]
 pub struct TreeConfig {
@@ -32,6 +45,20 @@ pub struct TreeConfig {
     /// This is used as a cutoff to prevent long-running sequential block execution when we receive
     /// a batch of downloaded blocks.
     max_execute_block_batch_size: usize,
+    /// Whether to read the state the transactions of a block are expected to touch in parallel
+    /// before executing it.
+    state_prewarming: bool,
+    /// Maximum number of threads used to pre-warm the state of a block.
+    state_prewarm_parallelism: usize,
+    /// Reorgs that remove more than this number of blocks from the canonical chain are logged as
+    /// a warning.
+    deep_reorg_threshold: u64,
+    /// Maximum number of branch nodes of the database that are cached for state root computation.
+    /// The cache is disabled if this is 0.
+    trie_node_cache_size: u32,
+    /// Number of queued messages to the engine tree above which engine API requests are answered
+    /// with `SYNCING` instead of being queued.
+    engine_message_queue_size: usize,
 }
 
 impl Default for TreeConfig {
@@ -42,6 +69,11 @@ impl Default for TreeConfig {
             block_buffer_limit: DEFAULT_BLOCK_BUFFER_LIMIT,
             max_invalid_header_cache_length: DEFAULT_MAX_INVALID_HEADER_CACHE_LENGTH,
             max_execute_block_batch_size: DEFAULT_MAX_EXECUTE_BLOCK_BATCH_SIZE,
+            state_prewarming: false,
+            state_prewarm_parallelism: DEFAULT_STATE_PREWARM_PARALLELISM,
+            deep_reorg_threshold: DEFAULT_DEEP_REORG_THRESHOLD,
+            trie_node_cache_size: DEFAULT_TRIE_NODE_CACHE_SIZE,
+            engine_message_queue_size: DEFAULT_ENGINE_MESSAGE_QUEUE_SIZE,
         }
     }
 }
@@ -61,6 +93,11 @@ impl TreeConfig {
             block_buffer_limit,
             max_invalid_header_cache_length,
             max_execute_block_batch_size,
+            state_prewarming: false,
+            state_prewarm_parallelism: DEFAULT_STATE_PREWARM_PARALLELISM,
+            deep_reorg_threshold: DEFAULT_DEEP_REORG_THRESHOLD,
+            trie_node_cache_size: DEFAULT_TRIE_NODE_CACHE_SIZE,
+            engine_message_queue_size: DEFAULT_ENGINE_MESSAGE_QUEUE_SIZE,
         }
     }
 
@@ -89,6 +126,31 @@ impl TreeConfig {
         self.max_execute_block_batch_size
     }
 
+    /// Returns whether state pre-warming is enabled.
+    pub const fn state_prewarming(&self) -> bool {
+        self.state_prewarming
+    }
+
+    /// Return the maximum number of threads used to pre-warm the state of a block.
+    pub const fn state_prewarm_parallelism(&self) -> usize {
+        self.state_prewarm_parallelism
+    }
+
+    /// Return the depth above which a reorg is logged as a warning.
+    pub const fn deep_reorg_threshold(&self) -> u64 {
+        self.deep_reorg_threshold
+    }
+
+    /// Return the maximum number of cached branch nodes.
+    pub const fn trie_node_cache_size(&self) -> u32 {
+        self.trie_node_cache_size
+    }
+
+    /// Return the maximum number of queued messages to the engine tree.
+    pub const fn engine_message_queue_size(&self) -> usize {
+        self.engine_message_queue_size
+    }
+
     /// Setter for persistence threshold.
     pub const fn with_persistence_threshold(mut self, persistence_threshold: u64) -> Self {
         self.persistence_threshold = persistence_threshold;
@@ -127,4 +189,40 @@ impl TreeConfig {
         self.max_execute_block_batch_size = max_execute_block_batch_size;
         self
     }
+
+    /// Setter for whether to pre-warm the state of a block before executing it.
+    pub const fn with_state_prewarming(mut self, state_prewarming: bool) -> Self {
+        self.state_prewarming = state_prewarming;
+        self
+    }
+
+    /// Setter for the maximum number of threads used to pre-warm the state of a block.
+    pub const fn with_state_prewarm_parallelism(
+        mut self,
+        state_prewarm_parallelism: usize,
+    ) -> Self {
+        self.state_prewarm_parallelism = state_prewarm_parallelism;
+        self
+    }
+
+    /// Setter for the depth above which a reorg is logged as a warning.
+    pub const fn with_deep_reorg_threshold(mut self, deep_reorg_threshold: u64) -> Self {
+        self.deep_reorg_threshold = deep_reorg_threshold;
+        self
+    }
+
+    /// Setter for the maximum number of cached branch nodes.
+    pub const fn with_trie_node_cache_size(mut self, trie_node_cache_size: u32) -> Self {
+        self.trie_node_cache_size = trie_node_cache_size;
+        self
+    }
+
+    /// Setter for the maximum number of queued messages to the engine tree, must be greater than 0.
+    pub const fn with_engine_message_queue_size(
+        mut self,
+        engine_message_queue_size: usize,
+    ) -> Self {
+        self.engine_message_queue_size = engine_message_queue_size;
+        self
+    }
 }
@@ -1,3 +1,4 @@
+use super::RecoveredSenders;
 use reth_blockchain_tree::metrics::TreeMetrics;
 use reth_evm::metrics::ExecutorMetrics;
 use reth_metrics::{
@@ -43,6 +44,10 @@ pub(crate) struct EngineMetrics {
     pub(crate) failed_new_payload_response_deliveries: Counter,
     /// Tracks the how often we failed to deliver a forkchoice update response.
     pub(crate) failed_forkchoice_updated_response_deliveries: Counter,
+    /// Histogram of the number of blocks removed from the canonical chain by a reorg.
+    pub(crate) reorg_depth: Histogram,
+    /// The number of reorgs deeper than the configured deep reorg threshold.
+    pub(crate) deep_reorgs: Counter,
     // TODO add latency metrics
 }
 
@@ -56,6 +61,16 @@ pub(crate) struct BlockValidationMetrics {
     pub(crate) state_root_histogram: Histogram,
     /// Latest state root duration
     pub(crate) state_root_duration: Gauge,
+    /// Histogram of state pre-warming duration
+    pub(crate) state_prewarm_histogram: Histogram,
+    /// Histogram of the number of accounts pre-warmed per block
+    pub(crate) state_prewarm_accounts: Histogram,
+    /// Total number of transaction senders taken from the sender cache
+    pub(crate) sender_cache_hits: Counter,
+    /// Total number of transaction senders recovered from signatures
+    pub(crate) senders_recovered: Counter,
+    /// Histogram of sender recovery duration
+    pub(crate) sender_recovery_histogram: Histogram,
 }
 
 impl BlockValidationMetrics {
@@ -66,4 +81,17 @@ impl BlockValidationMetrics {
         self.state_root_duration.set(elapsed_as_secs);
         self.state_root_histogram.record(elapsed_as_secs);
     }
+
+    /// Records a state pre-warming of the given number of accounts
+    pub(crate) fn record_state_prewarm(&self, accounts: usize, elapsed_as_secs: f64) {
+        self.state_prewarm_accounts.record(accounts as f64);
+        self.state_prewarm_histogram.record(elapsed_as_secs);
+    }
+
+    /// Records the sender recovery of a block
+    pub(crate) fn record_sender_recovery(&self, senders: &RecoveredSenders, elapsed_as_secs: f64) {
+        self.sender_cache_hits.increment(senders.cached as u64);
+        self.senders_recovered.increment(senders.recovered() as u64);
+        self.sender_recovery_histogram.record(elapsed_as_secs);
+    }
 }
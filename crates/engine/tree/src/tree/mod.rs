@@ -1,6 +1,7 @@
 use crate::{
     backfill::{BackfillAction, BackfillSyncState},
     chain::FromOrchestrator,
+    channel::{engine_message_channel, EngineMessageReceiver, EngineMessageSender},
     engine::{DownloadRequest, EngineApiEvent, EngineApiKind, EngineApiRequest, FromEngine},
     persistence::PersistenceHandle,
     tree::metrics::EngineApiMetrics,
@@ -23,12 +24,14 @@ use reth_blockchain_tree::{
     BlockBuffer, BlockStatus2, InsertPayloadOk2,
 };
 use reth_chain_state::{
-    CanonicalInMemoryState, ExecutedBlock, MemoryOverlayStateProvider, NewCanonicalChain,
+    CanonicalInMemoryState, ChainReorg, ExecutedBlock, MemoryOverlayStateProvider,
+    NewCanonicalChain,
 };
 use reth_consensus::{Consensus, FullConsensus, PostExecutionInput};
+use reth_db_models::StoredForkchoiceState;
 use reth_engine_primitives::{
     BeaconEngineMessage, BeaconOnNewPayloadError, EngineApiMessageVersion, EngineTypes,
-    EngineValidator, ForkchoiceStateTracker, OnForkChoiceUpdated,
+    EngineValidator, ForkchoiceStateTracker, OnForkChoiceUpdated, SenderCache,
 };
 use reth_errors::{ConsensusError, ProviderResult};
 use reth_evm::execute::BlockExecutorProvider;
@@ -36,18 +39,23 @@ use reth_payload_builder::PayloadBuilderHandle;
 use reth_payload_builder_primitives::PayloadBuilder;
 use reth_payload_primitives::PayloadBuilderAttributes;
 use reth_primitives::{
-    EthPrimitives, GotExpected, NodePrimitives, SealedBlockFor, SealedBlockWithSenders,
-    SealedHeader,
+    BlockWithSenders, EthPrimitives, GotExpected, NodePrimitives, SealedBlockFor,
+    SealedBlockWithSenders, SealedHeader,
 };
-use reth_primitives_traits::Block;
+use reth_primitives_traits::{Block, BlockBody};
 use reth_provider::{
     providers::ConsistentDbView, BlockReader, DatabaseProviderFactory, ExecutionOutcome,
     HashedPostStateProvider, ProviderError, StateCommitmentProvider, StateProviderBox,
     StateProviderFactory, StateReader, StateRootProvider, TransactionVariant,
 };
-use reth_revm::database::StateProviderDatabase;
+use reth_revm::{
+    cached::CachedReads,
+    database::StateProviderDatabase,
+    either::Either,
+    prewarm::{prewarm, PrewarmTargets},
+};
 use reth_stages_api::ControlFlow;
-use reth_trie::{updates::TrieUpdates, HashedPostState, TrieInput};
+use reth_trie::{trie_cursor::TrieNodeCache, updates::TrieUpdates, HashedPostState, TrieInput};
 use reth_trie_parallel::root::{ParallelStateRoot, ParallelStateRootError};
 use revm_primitives::EvmState;
 use std::{
@@ -56,10 +64,10 @@ use std::{
     fmt::Debug,
     ops::Bound,
     sync::{
-        mpsc::{Receiver, RecvError, RecvTimeoutError, Sender},
+        mpsc::{RecvError, RecvTimeoutError},
         Arc,
     },
-    time::Instant,
+    time::{Instant, SystemTime, UNIX_EPOCH},
 };
 use tokio::sync::{
     mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender},
@@ -71,10 +79,12 @@ pub mod config;
 mod invalid_block_hook;
 mod metrics;
 mod persistence_state;
+mod senders;
 pub use config::TreeConfig;
 pub use invalid_block_hook::{InvalidBlockHooks, NoopInvalidBlockHook};
 pub use persistence_state::PersistenceState;
-pub use reth_engine_primitives::InvalidBlockHook;
+pub use reth_engine_primitives::{InvalidBlockHook, SenderCache};
+pub use senders::{recover_senders, NoopSenderCache, RecoveredSenders};
 
 pub mod root;
 
@@ -486,9 +496,9 @@ where
     /// them one by one so that we can handle incoming engine API in between and don't become
     /// unresponsive. This can happen during live sync transition where we're trying to close the
     /// gap (up to 3 epochs of blocks in the worst case).
-    incoming_tx: Sender<FromEngine<EngineApiRequest<T, N>, N::Block>>,
+    incoming_tx: EngineMessageSender<FromEngine<EngineApiRequest<T, N>, N::Block>>,
     /// Incoming engine API requests.
-    incoming: Receiver<FromEngine<EngineApiRequest<T, N>, N::Block>>,
+    incoming: EngineMessageReceiver<FromEngine<EngineApiRequest<T, N>, N::Block>>,
     /// Outgoing events that are emitted to the handler.
     outgoing: UnboundedSender<EngineApiEvent<N>>,
     /// Channels to the persistence layer.
@@ -509,8 +519,13 @@ where
     metrics: EngineApiMetrics,
     /// An invalid block hook.
     invalid_block_hook: Box<dyn InvalidBlockHook<N>>,
+    /// Senders of known transactions, used to skip their recovery when inserting new payloads.
+    sender_cache: Box<dyn SenderCache>,
     /// The engine API variant of this handler
     engine_kind: EngineApiKind,
+    /// Cache of the branch nodes of the database used for parallel state root computation, `None`
+    /// if disabled.
+    trie_node_cache: Option<TrieNodeCache>,
 }
 
 impl<N, P: Debug, E: Debug, T: EngineTypes + Debug, V: Debug> std::fmt::Debug
@@ -534,7 +549,9 @@ where
             .field("config", &self.config)
             .field("metrics", &self.metrics)
             .field("invalid_block_hook", &format!("{:p}", self.invalid_block_hook))
+            .field("sender_cache", &format!("{:p}", self.sender_cache))
             .field("engine_kind", &self.engine_kind)
+            .field("trie_node_cache", &self.trie_node_cache)
             .finish()
     }
 }
@@ -572,7 +589,9 @@ where
         config: TreeConfig,
         engine_kind: EngineApiKind,
     ) -> Self {
-        let (incoming_tx, incoming) = std::sync::mpsc::channel();
+        let (incoming_tx, incoming) = engine_message_channel(config.engine_message_queue_size());
+        let trie_node_cache = (config.trie_node_cache_size() > 0)
+            .then(|| TrieNodeCache::new(config.trie_node_cache_size()));
 
         Self {
             provider,
@@ -591,7 +610,9 @@ where
             metrics: Default::default(),
             incoming_tx,
             invalid_block_hook: Box::new(NoopInvalidBlockHook),
+            sender_cache: Box::new(NoopSenderCache),
             engine_kind,
+            trie_node_cache,
         }
     }
 
@@ -600,6 +621,11 @@ where
         self.invalid_block_hook = invalid_block_hook;
     }
 
+    /// Sets the cache of transaction senders.
+    fn set_sender_cache(&mut self, sender_cache: Box<dyn SenderCache>) {
+        self.sender_cache = sender_cache;
+    }
+
     /// Creates a new [`EngineApiTreeHandler`] instance and spawns it in its
     /// own thread.
     ///
@@ -617,8 +643,44 @@ where
         config: TreeConfig,
         invalid_block_hook: Box<dyn InvalidBlockHook<N>>,
         kind: EngineApiKind,
-    ) -> (Sender<FromEngine<EngineApiRequest<T, N>, N::Block>>, UnboundedReceiver<EngineApiEvent<N>>)
-    {
+    ) -> (
+        EngineMessageSender<FromEngine<EngineApiRequest<T, N>, N::Block>>,
+        UnboundedReceiver<EngineApiEvent<N>>,
+    ) {
+        Self::spawn_new_with_sender_cache(
+            provider,
+            executor_provider,
+            consensus,
+            payload_validator,
+            persistence,
+            payload_builder,
+            canonical_in_memory_state,
+            config,
+            invalid_block_hook,
+            Box::new(NoopSenderCache),
+            kind,
+        )
+    }
+
+    /// Same as [`Self::spawn_new`], but takes the senders of known transactions of new payloads
+    /// from the given [`SenderCache`] instead of recovering them.
+    #[expect(clippy::complexity)]
+    pub fn spawn_new_with_sender_cache(
+        provider: P,
+        executor_provider: E,
+        consensus: Arc<dyn FullConsensus<N>>,
+        payload_validator: V,
+        persistence: PersistenceHandle<N>,
+        payload_builder: PayloadBuilderHandle<T>,
+        canonical_in_memory_state: CanonicalInMemoryState<N>,
+        config: TreeConfig,
+        invalid_block_hook: Box<dyn InvalidBlockHook<N>>,
+        sender_cache: Box<dyn SenderCache>,
+        kind: EngineApiKind,
+    ) -> (
+        EngineMessageSender<FromEngine<EngineApiRequest<T, N>, N::Block>>,
+        UnboundedReceiver<EngineApiEvent<N>>,
+    ) {
         let best_block_number = provider.best_block_number().unwrap_or(0);
         let header = provider.sealed_header(best_block_number).ok().flatten().unwrap_or_default();
 
@@ -650,13 +712,14 @@ where
             kind,
         );
         task.set_invalid_block_hook(invalid_block_hook);
+        task.set_sender_cache(sender_cache);
         let incoming = task.incoming_tx.clone();
         std::thread::Builder::new().name("Tree Task".to_string()).spawn(|| task.run()).unwrap();
         (incoming, outgoing)
     }
 
-    /// Returns a new [`Sender`] to send messages to this type.
-    pub fn sender(&self) -> Sender<FromEngine<EngineApiRequest<T, N>, N::Block>> {
+    /// Returns a new [`EngineMessageSender`] to send messages to this type.
+    pub fn sender(&self) -> EngineMessageSender<FromEngine<EngineApiRequest<T, N>, N::Block>> {
         self.incoming_tx.clone()
     }
 
@@ -993,6 +1056,7 @@ where
         trace!(target: "engine::tree", ?attrs, "invoked forkchoice update");
         self.metrics.engine.forkchoice_updated_messages.increment(1);
         self.canonical_in_memory_state.on_forkchoice_update_received();
+        self.update_sync_target(state.head_block_hash)?;
 
         if let Some(on_updated) = self.pre_validate_forkchoice_update(state)? {
             return Ok(TreeOutcome::new(on_updated))
@@ -1154,6 +1218,9 @@ where
                 debug!(target: "engine::tree", ?new_tip_num, remove_state=?self.persistence_state.remove_above_state, last_persisted_block_number=?self.persistence_state.last_persisted_block.number, "Removing blocks using persistence task");
                 if new_tip_num < self.persistence_state.last_persisted_block.number {
                     debug!(target: "engine::tree", ?new_tip_num, "Starting remove blocks job");
+                    if let Some(cache) = &self.trie_node_cache {
+                        cache.clear();
+                    }
                     let (tx, rx) = oneshot::channel();
                     let _ = self.persistence.remove_blocks_above(new_tip_num, tx);
                     self.persistence_state.start(rx);
@@ -1164,6 +1231,13 @@ where
                     debug!(target: "engine::tree", "Returned empty set of blocks to persist");
                 } else {
                     debug!(target: "engine::tree", blocks = ?blocks_to_persist.iter().map(|block| block.block.num_hash()).collect::<Vec<_>>(), "Persisting blocks");
+                    // No state root is computed in parallel until the blocks are persisted, so the
+                    // cache can't be populated with the nodes they overwrite in the meantime.
+                    if let Some(cache) = &self.trie_node_cache {
+                        for block in &blocks_to_persist {
+                            cache.invalidate(block.trie_updates());
+                        }
+                    }
                     let (tx, rx) = oneshot::channel();
                     let _ = self.persistence.save_blocks(blocks_to_persist, tx);
                     self.persistence_state.start(rx);
@@ -1243,10 +1317,18 @@ where
                                 tx,
                                 version,
                             } => {
+                                // publish the head, safe and finalized block of the update as one
+                                // chain status
+                                let _chain_status =
+                                    self.canonical_in_memory_state.batch_chain_status();
                                 let mut output =
                                     self.on_forkchoice_updated(state, payload_attrs, version);
 
                                 if let Ok(res) = &mut output {
+                                    if res.outcome.forkchoice_status().is_valid() {
+                                        self.on_valid_forkchoice_state(state);
+                                    }
+
                                     // track last received forkchoice state
                                     self.state
                                         .forkchoice_state_tracker
@@ -1322,6 +1404,11 @@ where
         debug!(target: "engine::tree", "received backfill sync finished event");
         self.backfill_sync_state = BackfillSyncState::Idle;
 
+        // the trie tables were modified by the pipeline
+        if let Some(cache) = &self.trie_node_cache {
+            cache.clear();
+        }
+
         // Pipeline unwound, memorize the invalid block and wait for CL for next sync target.
         if let ControlFlow::Unwind { bad_block, .. } = ctrl {
             warn!(target: "engine::tree", invalid_block=?bad_block, "Bad block detected in unwind");
@@ -1827,7 +1914,7 @@ where
         &mut self,
         block: SealedBlockFor<N::Block>,
     ) -> Result<(), InsertBlockErrorTwo<N::Block>> {
-        match block.try_seal_with_senders() {
+        match self.seal_with_senders(block) {
             Ok(block) => self.buffer_block(block),
             Err(block) => Err(InsertBlockErrorTwo::sender_recovery_error(block)),
         }
@@ -1841,10 +1928,34 @@ where
         if let Err(err) = self.validate_block(&block) {
             return Err(InsertBlockErrorTwo::consensus_error(err, block.block))
         }
+        self.on_buffered_block(block.num_hash());
         self.state.buffer.insert_block(block);
         Ok(())
     }
 
+    /// Sets the sync target to the head block of a forkchoice update, if the number of the block
+    /// is known.
+    fn update_sync_target(&self, head: B256) -> ProviderResult<()> {
+        let number = match self.state.buffer.block(&head) {
+            Some(block) => Some(block.number()),
+            None => self.sealed_header_by_hash(head)?.map(|header| header.number()),
+        };
+        if let Some(number) = number {
+            self.canonical_in_memory_state.set_sync_target(BlockNumHash::new(number, head));
+        }
+        Ok(())
+    }
+
+    /// Sets the sync target to a buffered block, if it's the head block of the latest forkchoice
+    /// update, whose number wasn't known when the update was received.
+    fn on_buffered_block(&self, block: BlockNumHash) {
+        let latest_head =
+            self.state.forkchoice_state_tracker.latest_state().map(|state| state.head_block_hash);
+        if latest_head == Some(block.hash) {
+            self.canonical_in_memory_state.set_sync_target(block);
+        }
+    }
+
     /// Returns true if the distance from the local tip to the block is greater than the configured
     /// threshold.
     ///
@@ -2000,20 +2111,30 @@ where
         let tip = chain_update.tip().header.clone();
         let notification = chain_update.to_chain_notification();
 
+        if let Some(reorg) = chain_update.reorg() {
+            self.on_reorg(reorg);
+        }
+
         // reinsert any missing reorged blocks
         if let NewCanonicalChain::Reorg { new, old } = &chain_update {
             let new_first = new.first().map(|first| first.block.num_hash());
             let old_first = old.first().map(|first| first.block.num_hash());
             trace!(target: "engine::tree", ?new_first, ?old_first, "Reorg detected, new and old first blocks");
 
-            self.update_reorg_metrics(old.len());
             self.reinsert_reorged_blocks(new.clone());
             self.reinsert_reorged_blocks(old.clone());
         }
 
+        let new_blocks = match &chain_update {
+            NewCanonicalChain::Commit { new } | NewCanonicalChain::Reorg { new, .. } => {
+                new.iter().map(|block| block.block.num_hash()).collect::<Vec<_>>()
+            }
+        };
+
         // update the tracked in-memory state with the new chain
         self.canonical_in_memory_state.update_chain(chain_update);
         self.canonical_in_memory_state.set_canonical_head(tip.clone());
+        self.record_side_chain_blocks(&new_blocks);
 
         // Update metrics based on new tip
         self.metrics.tree.canonical_chain_height.set(tip.number() as f64);
@@ -2028,10 +2149,42 @@ where
         ));
     }
 
-    /// This updates metrics based on the given reorg length.
-    fn update_reorg_metrics(&self, old_chain_length: usize) {
+    /// Records the reorg in the reorg history and updates the reorg metrics.
+    ///
+    /// Reorgs deeper than the configured deep reorg threshold are logged as a warning.
+    fn on_reorg(&self, reorg: ChainReorg) {
+        let depth = reorg.depth();
         self.metrics.tree.reorgs.increment(1);
-        self.metrics.tree.latest_reorg_depth.set(old_chain_length as f64);
+        self.metrics.tree.latest_reorg_depth.set(depth as f64);
+        self.metrics.engine.reorg_depth.record(depth as f64);
+
+        if depth > self.config.deep_reorg_threshold() {
+            self.metrics.engine.deep_reorgs.increment(1);
+            warn!(
+                target: "engine::tree",
+                depth,
+                old_tip = ?reorg.old_tip,
+                new_tip = ?reorg.new_tip,
+                new_chain_length = reorg.new_chain_length,
+                common_ancestor = ?reorg.common_ancestor,
+                "Deep reorg of the canonical chain"
+            );
+        }
+
+        self.canonical_in_memory_state.reorg_history().record(reorg);
+    }
+
+    /// Records the executed blocks at the height of a block that became canonical, which are now
+    /// part of a side chain.
+    fn record_side_chain_blocks(&self, new_blocks: &[BlockNumHash]) {
+        for new_block in new_blocks {
+            let Some(blocks) = self.state.tree_state.blocks_by_number.get(&new_block.number) else {
+                continue
+            };
+            for block in blocks.iter().filter(|block| block.block.hash() != new_block.hash) {
+                self.canonical_in_memory_state.record_side_chain_block(block);
+            }
+        }
     }
 
     /// This reinserts any blocks in the new chain that do not already exist in the tree
@@ -2154,12 +2307,33 @@ where
         &mut self,
         block: SealedBlockFor<N::Block>,
     ) -> Result<InsertPayloadOk2, InsertBlockErrorTwo<N::Block>> {
-        match block.try_seal_with_senders() {
+        match self.seal_with_senders(block) {
             Ok(block) => self.insert_block(block),
             Err(block) => Err(InsertBlockErrorTwo::sender_recovery_error(block)),
         }
     }
 
+    /// Recovers the senders of the block's transactions, taking the senders of known
+    /// transactions from the sender cache.
+    ///
+    /// Returns the block back if the signature of a transaction is invalid.
+    fn seal_with_senders(
+        &self,
+        block: SealedBlockFor<N::Block>,
+    ) -> Result<SealedBlockWithSenders<N::Block>, SealedBlockFor<N::Block>> {
+        let start = Instant::now();
+        let Some(recovered) =
+            recover_senders(block.body().transactions(), self.sender_cache.as_ref())
+        else {
+            return Err(block)
+        };
+
+        let elapsed = start.elapsed();
+        self.metrics.block_validation.record_sender_recovery(&recovered, elapsed.as_secs_f64());
+        trace!(target: "engine::tree", number = block.number(), cached = recovered.cached, recovered = recovered.recovered(), ?elapsed, "Recovered block senders");
+        block.try_with_senders_unchecked(recovered.senders)
+    }
+
     fn insert_block(
         &mut self,
         block: SealedBlockWithSenders<N::Block>,
@@ -2168,6 +2342,37 @@ where
             .map_err(|kind| InsertBlockErrorTwo::new(block.block, kind))
     }
 
+    /// Reads the state the transactions of the block are expected to touch in parallel, so that
+    /// execution doesn't wait on database reads.
+    ///
+    /// Every task reads the state of the parent block through its own state provider, and with
+    /// that its own database transaction.
+    fn prewarm_state(&self, block: &BlockWithSenders<N::Block>) -> CachedReads {
+        let start = Instant::now();
+        let targets = PrewarmTargets::from_block(block);
+        let accounts = targets.accounts_len();
+
+        let parent_hash = block.header().parent_hash();
+        let in_memory = self.state.tree_state.blocks_by_hash(parent_hash);
+        let provider = &self.provider;
+        let open_state = || -> ProviderResult<_> {
+            let state_provider: StateProviderBox = match &in_memory {
+                Some((historical, blocks)) => Box::new(MemoryOverlayStateProvider::new(
+                    provider.state_by_block_hash(*historical)?,
+                    blocks.clone(),
+                )),
+                None => provider.state_by_block_hash(parent_hash)?,
+            };
+            Ok(StateProviderDatabase::new(state_provider))
+        };
+        let cached_reads = prewarm(open_state, targets, self.config.state_prewarm_parallelism());
+
+        let elapsed = start.elapsed();
+        self.metrics.block_validation.record_state_prewarm(accounts, elapsed.as_secs_f64());
+        trace!(target: "engine::tree", number = block.header().number(), accounts, ?elapsed, "Pre-warmed block state");
+        cached_reads
+    }
+
     fn insert_block_inner(
         &mut self,
         block: SealedBlockWithSenders<N::Block>,
@@ -2195,6 +2400,7 @@ where
                 .map(|block| block.parent_num_hash())
                 .unwrap_or_else(|| block.parent_num_hash());
 
+            self.on_buffered_block(block.num_hash());
             self.state.buffer.insert_block(block);
 
             return Ok(InsertPayloadOk2::Inserted(BlockStatus2::Disconnected {
@@ -2215,13 +2421,21 @@ where
         }
 
         trace!(target: "engine::tree", block=?block.num_hash(), "Executing block");
-        let executor = self.executor_provider.executor(StateProviderDatabase::new(&state_provider));
-
         let block_number = block.number();
         let block_hash = block.hash();
         let sealed_block = Arc::new(block.block.clone());
         let block = block.unseal();
 
+        let db = StateProviderDatabase::new(&state_provider);
+        let mut cached_reads = CachedReads::default();
+        let db = if self.config.state_prewarming() {
+            cached_reads = self.prewarm_state(&block);
+            Either::Right(cached_reads.as_db_mut(db))
+        } else {
+            Either::Left(db)
+        };
+        let executor = self.executor_provider.executor(db);
+
         let exec_time = Instant::now();
 
         let persistence_not_in_progress = !self.persistence_state.in_progress();
@@ -2365,6 +2579,10 @@ where
         // emit insert event
         let elapsed = start.elapsed();
         let engine_event = if self.is_fork(block_hash)? {
+            // keep the side chain block available after it's removed from the tree
+            if let Some(executed) = self.state.tree_state.executed_block_by_hash(block_hash) {
+                self.canonical_in_memory_state.record_side_chain_block(executed);
+            }
             BeaconConsensusEngineEvent::ForkBlockAdded(sealed_block, elapsed)
         } else {
             BeaconConsensusEngineEvent::CanonicalBlockAdded(sealed_block, elapsed)
@@ -2394,7 +2612,11 @@ where
         // Extend with block we are validating root for.
         input.append_ref(hashed_state);
 
-        ParallelStateRoot::new(consistent_view, input).incremental_root_with_updates()
+        let mut state_root = ParallelStateRoot::new(consistent_view, input);
+        if let Some(cache) = &self.trie_node_cache {
+            state_root = state_root.with_trie_node_cache(cache.clone());
+        }
+        state_root.incremental_root_with_updates()
     }
 
     /// Computes the trie input at the provided parent hash.
@@ -2473,6 +2695,25 @@ where
         Ok(canonical)
     }
 
+    /// Persists a forkchoice state that was processed as valid, so it can be restored on restart.
+    ///
+    /// This also marks the forkchoice state restored on startup, if any, as superseded.
+    fn on_valid_forkchoice_state(&self, state: ForkchoiceState) {
+        self.canonical_in_memory_state.on_forkchoice_state_confirmed();
+
+        if self.state.forkchoice_state_tracker.last_valid_state() == Some(state) {
+            return
+        }
+
+        let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+        let _ = self.persistence.save_forkchoice_state(StoredForkchoiceState {
+            head_block_hash: state.head_block_hash,
+            safe_block_hash: state.safe_block_hash,
+            finalized_block_hash: state.finalized_block_hash,
+            timestamp,
+        });
+    }
+
     /// Updates the tracked finalized block if we have it.
     fn update_finalized_block(
         &self,
@@ -2687,6 +2928,7 @@ mod tests {
     use super::*;
     use crate::persistence::PersistenceAction;
     use alloy_consensus::Header;
+    use alloy_eips::eip4844::DATA_GAS_PER_BLOB;
     use alloy_primitives::Bytes;
     use alloy_rlp::Decodable;
     use alloy_rpc_types_engine::{CancunPayloadFields, ExecutionPayloadSidecar};
@@ -2697,13 +2939,13 @@ mod tests {
     use reth_engine_primitives::ForkchoiceStatus;
     use reth_ethereum_engine_primitives::{EthEngineTypes, EthereumEngineValidator};
     use reth_evm::test_utils::MockExecutorProvider;
-    use reth_primitives::{Block, BlockExt, EthPrimitives};
+    use reth_primitives::{Block, BlockExt, EthPrimitives, SealedBlock};
     use reth_provider::test_utils::MockEthProvider;
     use reth_rpc_types_compat::engine::{block_to_payload_v1, payload::block_to_payload_v3};
     use reth_trie::{updates::TrieUpdates, HashedPostState};
     use std::{
         str::FromStr,
-        sync::mpsc::{channel, Sender},
+        sync::mpsc::{channel, Receiver, Sender},
     };
 
     /// This is a test channel that allows you to `release` any value that is in the channel.
@@ -2769,7 +3011,8 @@ mod tests {
             EthEngineTypes,
             EthereumEngineValidator,
         >,
-        to_tree_tx: Sender<FromEngine<EngineApiRequest<EthEngineTypes, EthPrimitives>, Block>>,
+        to_tree_tx:
+            EngineMessageSender<FromEngine<EngineApiRequest<EthEngineTypes, EthPrimitives>, Block>>,
         from_tree_rx: UnboundedReceiver<EngineApiEvent>,
         blocks: Vec<ExecutedBlock>,
         action_rx: Receiver<PersistenceAction>,
@@ -3227,12 +3470,17 @@ mod tests {
         assert!(resp.payload_status.is_syncing());
     }
 
+    /// Returns a block whose parent is unknown to the tree.
+    fn disconnected_block(chain_spec: Arc<ChainSpec>) -> SealedBlock {
+        reth_testing_utils::TestBlockBuilder::new(chain_spec)
+            .with_txs_per_block(1)
+            .block_at(2, B256::repeat_byte(1))
+            .block
+    }
+
     #[test]
     fn test_disconnected_payload() {
-        let s = include_str!("../../test-data/holesky/2.rlp");
-        let data = Bytes::from_str(s).unwrap();
-        let block = Block::decode(&mut data.as_ref()).unwrap();
-        let sealed = block.seal_slow();
+        let sealed = disconnected_block(HOLESKY.clone());
         let hash = sealed.hash();
         let payload = block_to_payload_v1(sealed.clone());
 
@@ -3251,10 +3499,7 @@ mod tests {
 
     #[test]
     fn test_disconnected_block() {
-        let s = include_str!("../../test-data/holesky/2.rlp");
-        let data = Bytes::from_str(s).unwrap();
-        let block = Block::decode(&mut data.as_ref()).unwrap();
-        let sealed = block.seal_slow();
+        let sealed = disconnected_block(HOLESKY.clone());
 
         let mut test_harness = TestHarness::new(HOLESKY.clone());
 
@@ -3296,6 +3541,67 @@ mod tests {
         assert!(resp.is_syncing());
     }
 
+    #[tokio::test]
+    async fn test_invalid_blob_gas_payload() {
+        reth_tracing::init_test_tracing();
+
+        let mut test_harness = TestHarness::new(MAINNET.clone());
+        let base_chain: Vec<_> = test_harness.block_builder.get_executed_blocks(0..1).collect();
+        test_harness = test_harness.with_blocks(base_chain.clone());
+        let parent = base_chain.last().unwrap().block().clone();
+
+        let cases: [(&str, fn(&mut Header), ConsensusError); 2] = [
+            // invalid on its own
+            (
+                "7 blobs",
+                |header| header.blob_gas_used = Some(7 * DATA_GAS_PER_BLOB),
+                ConsensusError::BlobGasUsedExceedsMaxBlobGasPerBlock {
+                    blob_gas_used: 7 * DATA_GAS_PER_BLOB,
+                    max_blob_gas_per_block: 6 * DATA_GAS_PER_BLOB,
+                },
+            ),
+            // invalid against the parent, which used no blob gas
+            (
+                "excess blob gas mismatch",
+                |header| header.excess_blob_gas = Some(DATA_GAS_PER_BLOB),
+                ConsensusError::ExcessBlobGasDiff {
+                    diff: GotExpected { got: DATA_GAS_PER_BLOB, expected: 0 },
+                    parent_excess_blob_gas: 0,
+                    parent_blob_gas_used: 0,
+                },
+            ),
+        ];
+
+        for (name, modify_header, error) in cases {
+            let block = test_harness.block_builder.create_fork(&parent, 1).remove(0);
+            let mut header = block.header.clone().unseal();
+            modify_header(&mut header);
+            let block = SealedBlock::new(SealedHeader::seal(header), block.body.clone());
+
+            let outcome = test_harness
+                .tree
+                .on_new_payload(
+                    block_to_payload_v3(block.clone()).into(),
+                    ExecutionPayloadSidecar::v3(CancunPayloadFields {
+                        parent_beacon_block_root: block.parent_beacon_block_root.unwrap(),
+                        versioned_hashes: vec![],
+                    }),
+                )
+                .unwrap();
+
+            // the parent is valid, so it's the latest valid ancestor of the invalid block
+            assert_eq!(
+                outcome.outcome,
+                PayloadStatus::new(
+                    PayloadStatusEnum::Invalid { validation_error: error.to_string() },
+                    Some(parent.hash())
+                ),
+                "{name}"
+            );
+            assert!(test_harness.tree.state.invalid_headers.get(&block.hash()).is_some(), "{name}");
+        }
+    }
+
     #[tokio::test]
     async fn test_tree_state_insert_executed() {
         let mut tree_state = TreeState::new(BlockNumHash::default());
@@ -3758,6 +4064,87 @@ mod tests {
         test_harness.check_canon_head(fork_chain_last_hash);
     }
 
+    #[tokio::test]
+    async fn test_engine_tree_fcu_reorg_recorded_in_history() {
+        let chain_spec = MAINNET.clone();
+        let mut test_harness = TestHarness::new(chain_spec.clone());
+
+        let main_chain: Vec<_> = test_harness.block_builder.get_executed_blocks(0..5).collect();
+        test_harness = test_harness.with_blocks(main_chain.clone());
+
+        // fork off block 1, reorging out blocks 2 to 4
+        let fork_chain = test_harness.block_builder.create_fork(main_chain[1].block(), 3);
+        let fork_chain_last_hash = fork_chain.last().unwrap().hash();
+        for block in &fork_chain {
+            test_harness.insert_block(block.clone()).unwrap();
+        }
+
+        test_harness.send_fcu(fork_chain_last_hash, ForkchoiceStatus::Valid).await;
+        test_harness.check_fork_chain_insertion(fork_chain.clone()).await;
+        test_harness.check_canon_commit(fork_chain_last_hash).await;
+        test_harness.check_fcu(fork_chain_last_hash, ForkchoiceStatus::Valid).await;
+
+        let history = test_harness.tree.canonical_in_memory_state.reorg_history();
+        assert_eq!(history.len(), 1);
+        let reorg = history.latest(1)[0];
+        assert_eq!(reorg.common_ancestor, main_chain[1].block.num_hash());
+        assert_eq!(reorg.old_tip, main_chain[4].block.num_hash());
+        assert_eq!(reorg.old_chain_length, 3);
+        assert_eq!(reorg.new_tip, fork_chain.last().unwrap().num_hash());
+        assert_eq!(reorg.new_chain_length, 3);
+        assert_eq!(reorg.depth(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_engine_tree_fcu_overrides_restored_forkchoice_state() {
+        let chain_spec = MAINNET.clone();
+        let mut test_harness = TestHarness::new(chain_spec.clone());
+
+        let main_chain: Vec<_> = test_harness.block_builder.get_executed_blocks(0..5).collect();
+        test_harness = test_harness.with_blocks(main_chain.clone());
+
+        // simulate a forkchoice state restored from disk that points at the old tip
+        let restored = StoredForkchoiceState {
+            head_block_hash: main_chain[4].block.hash(),
+            safe_block_hash: main_chain[3].block.hash(),
+            finalized_block_hash: main_chain[2].block.hash(),
+            timestamp: 1,
+        };
+        test_harness.tree.canonical_in_memory_state.set_restored_forkchoice_state(restored);
+
+        // the first live FCU conflicts with the restored state
+        let fork_chain = test_harness.block_builder.create_fork(main_chain[2].block(), 3);
+        let fork_chain_last_hash = fork_chain.last().unwrap().hash();
+        for block in &fork_chain {
+            test_harness.insert_block(block.clone()).unwrap();
+        }
+
+        test_harness.send_fcu(fork_chain_last_hash, ForkchoiceStatus::Valid).await;
+        test_harness.check_fork_chain_insertion(fork_chain.clone()).await;
+        test_harness.check_canon_commit(fork_chain_last_hash).await;
+        test_harness.check_fcu(fork_chain_last_hash, ForkchoiceStatus::Valid).await;
+
+        test_harness.check_canon_head(fork_chain_last_hash);
+        assert!(test_harness.tree.canonical_in_memory_state.restored_forkchoice_state().is_none());
+        assert_eq!(
+            test_harness.tree.canonical_in_memory_state.get_safe_num_hash(),
+            Some(fork_chain.last().unwrap().num_hash())
+        );
+
+        // the live forkchoice state is persisted in place of the restored one
+        let saved = test_harness
+            .action_rx
+            .try_iter()
+            .find_map(|action| match action {
+                PersistenceAction::SaveForkchoiceState(state) => Some(state),
+                _ => None,
+            })
+            .expect("forkchoice state was not persisted");
+        assert_eq!(saved.head_block_hash, fork_chain_last_hash);
+        assert_eq!(saved.safe_block_hash, fork_chain_last_hash);
+        assert_eq!(saved.finalized_block_hash, fork_chain_last_hash);
+    }
+
     #[tokio::test]
     async fn test_engine_tree_live_sync_transition_required_blocks_requested() {
         reth_tracing::init_test_tracing();
@@ -3803,6 +4190,10 @@ mod tests {
             _ => panic!("Unexpected event: {:#?}", event),
         }
 
+        // the head is unknown until it's downloaded
+        let sync_target = test_harness.tree.canonical_in_memory_state.subscribe_sync_target();
+        assert_eq!(*sync_target.borrow(), None);
+
         test_harness
             .tree
             .on_engine_message(FromEngine::DownloadedBlocks(vec![main_chain
@@ -3811,6 +4202,9 @@ mod tests {
                 .clone()]))
             .unwrap();
 
+        // the downloaded head is buffered and becomes the sync target
+        assert_eq!(*sync_target.borrow(), Some(main_chain.last().unwrap().num_hash()));
+
         let event = test_harness.from_tree_rx.recv().await.unwrap();
         match event {
             EngineApiEvent::Download(DownloadRequest::BlockRange(initial_hash, total_blocks)) => {
@@ -4053,6 +4447,49 @@ mod tests {
         assert!(test_harness.tree.is_fork(chain_a.last().unwrap().hash()).unwrap());
     }
 
+    #[tokio::test]
+    async fn test_engine_tree_records_side_chain_blocks() {
+        reth_tracing::init_test_tracing();
+
+        let chain_spec = MAINNET.clone();
+        let mut test_harness = TestHarness::new(chain_spec.clone());
+
+        let base_chain: Vec<_> = test_harness.block_builder.get_executed_blocks(0..1).collect();
+        test_harness = test_harness.with_blocks(base_chain.clone());
+        let base_tip = base_chain.last().unwrap().block().clone();
+        let orphaned_blocks = test_harness.tree.canonical_in_memory_state.orphaned_blocks().clone();
+
+        // two competing chains on top of the canonical head
+        let chain_a = test_harness.block_builder.create_fork(&base_tip, 2);
+        let chain_b = test_harness.block_builder.create_fork(&base_tip, 2);
+        for chain in [&chain_a, &chain_b] {
+            test_harness.setup_range_insertion_for_valid_chain(chain.clone());
+            for block in chain {
+                test_harness.send_new_payload(block.clone()).await;
+            }
+            test_harness.check_canon_chain_insertion(chain.clone()).await;
+        }
+        assert!(!orphaned_blocks.contains(chain_a[0].hash()));
+
+        // chain A becomes a side chain once chain B is canonical
+        let chain_b_tip_hash = chain_b.last().unwrap().hash();
+        test_harness.send_fcu(chain_b_tip_hash, ForkchoiceStatus::Valid).await;
+        test_harness.check_canon_commit(chain_b_tip_hash).await;
+        test_harness.check_fcu(chain_b_tip_hash, ForkchoiceStatus::Valid).await;
+        for block in &chain_a {
+            let orphaned = orphaned_blocks.block_by_hash(block.hash()).unwrap();
+            assert_eq!(*orphaned.block, block.block);
+        }
+        assert!(chain_b.iter().all(|block| !orphaned_blocks.contains(block.hash())));
+
+        // a block forking off below the canonical head is recorded when it's inserted
+        let chain_c = test_harness.block_builder.create_fork(&base_tip, 1);
+        test_harness.setup_range_insertion_for_valid_chain(chain_c.clone());
+        test_harness.send_new_payload(chain_c[0].clone()).await;
+        test_harness.check_fork_chain_insertion(chain_c.clone()).await;
+        assert!(orphaned_blocks.contains(chain_c[0].hash()));
+    }
+
     #[tokio::test]
     async fn test_engine_tree_buffered_blocks_are_eventually_connected() {
         let chain_spec = MAINNET.clone();
@@ -0,0 +1,110 @@
+//! Sender recovery of the transactions of new payloads.
+
+use alloy_primitives::{Address, TxHash};
+use rayon::prelude::*;
+use reth_engine_primitives::SenderCache;
+use reth_primitives_traits::SignedTransaction;
+
+/// A no-op [`SenderCache`] that doesn't know any senders.
+#[derive(Debug, Default)]
+#[non_exhaustive]
+pub struct NoopSenderCache;
+
+impl SenderCache for NoopSenderCache {
+    fn cached_senders(&self, tx_hashes: &[TxHash]) -> Vec<Option<Address>> {
+        vec![None; tx_hashes.len()]
+    }
+}
+
+/// The senders of the transactions of a block.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecoveredSenders {
+    /// The sender of each transaction, in order.
+    pub senders: Vec<Address>,
+    /// The number of senders that were taken from the [`SenderCache`].
+    pub cached: usize,
+}
+
+impl RecoveredSenders {
+    /// Returns the number of senders that were recovered from the transaction signatures.
+    pub fn recovered(&self) -> usize {
+        self.senders.len() - self.cached
+    }
+}
+
+/// Returns the senders of the transactions, taking the senders the cache knows from the cache and
+/// recovering the remaining ones from the signatures in parallel.
+///
+/// Returns `None` if the signature of a transaction that's not cached is invalid.
+pub fn recover_senders<T: SignedTransaction>(
+    transactions: &[T],
+    cache: &dyn SenderCache,
+) -> Option<RecoveredSenders> {
+    let tx_hashes = transactions.iter().map(|tx| *tx.tx_hash()).collect::<Vec<_>>();
+    let mut senders = cache.cached_senders(&tx_hashes);
+    // a cache that doesn't answer for every transaction is ignored
+    if senders.len() != transactions.len() {
+        senders = vec![None; transactions.len()];
+    }
+
+    let misses = senders
+        .iter()
+        .enumerate()
+        .filter_map(|(index, sender)| sender.is_none().then_some(index))
+        .collect::<Vec<_>>();
+    let recovered = misses
+        .par_iter()
+        .map(|index| transactions[*index].recover_signer())
+        .collect::<Option<Vec<_>>>()?;
+    for (index, sender) in misses.iter().zip(recovered) {
+        senders[*index] = Some(sender);
+    }
+
+    let cached = transactions.len() - misses.len();
+    Some(RecoveredSenders { senders: senders.into_iter().collect::<Option<_>>()?, cached })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::map::HashMap;
+    use reth_primitives::TransactionSigned;
+    use reth_testing_utils::generators::{self, random_signed_tx};
+
+    fn serial_senders(transactions: &[TransactionSigned]) -> Vec<Address> {
+        transactions.iter().map(|tx| tx.recover_signer().unwrap()).collect()
+    }
+
+    #[test]
+    fn recovered_senders_match_serial_recovery() {
+        let mut rng = generators::rng();
+        let transactions = (0..100).map(|_| random_signed_tx(&mut rng)).collect::<Vec<_>>();
+        let expected = serial_senders(&transactions);
+
+        // nothing cached
+        let recovered = recover_senders(&transactions, &NoopSenderCache).unwrap();
+        assert_eq!(recovered, RecoveredSenders { senders: expected.clone(), cached: 0 });
+
+        // every other transaction cached
+        let cache = transactions
+            .iter()
+            .zip(&expected)
+            .step_by(2)
+            .map(|(tx, sender)| (*tx.tx_hash(), *sender))
+            .collect::<HashMap<_, _>>();
+        let cache = move |tx_hashes: &[TxHash]| {
+            tx_hashes.iter().map(|hash| cache.get(hash).copied()).collect::<Vec<_>>()
+        };
+        let recovered = recover_senders(&transactions, &cache).unwrap();
+        assert_eq!(recovered.senders, expected);
+        assert_eq!(recovered.cached, 50);
+        assert_eq!(recovered.recovered(), 50);
+
+        // a cache that doesn't answer for every transaction
+        let cache = |_: &[TxHash]| vec![Some(Address::ZERO)];
+        assert_eq!(
+            recover_senders(&transactions, &cache).unwrap(),
+            RecoveredSenders { senders: expected, cached: 0 }
+        );
+    }
+}
@@ -0,0 +1,618 @@
+//! Records the engine API messages and the outcomes of processing them to an append-only log, for
+//! deterministic replay with [`EngineReplay`](crate::engine_replay::EngineReplay).
+//!
+//! The log is a directory of segments, each a file of JSON lines. A segment is rotated once it
+//! exceeds the configured size, and rotated segments are compressed with zstd in the background.
+//! The log is written on a dedicated thread, so that recording never blocks the engine on disk
+//! writes.
+
+use crate::engine_store::StoredEngineApiMessage;
+use alloy_primitives::B256;
+use alloy_rpc_types_engine::{PayloadStatus, PayloadStatusEnum};
+use futures::{stream::FuturesUnordered, Future, Stream, StreamExt};
+use reth_engine_primitives::{BeaconEngineMessage, EngineTypes, ForkchoiceStatus};
+use reth_fs_util as fs;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    fs::File,
+    io::{BufRead, BufReader, Read, Write},
+    path::{Path, PathBuf},
+    pin::Pin,
+    sync::mpsc,
+    task::{ready, Context, Poll},
+    time::SystemTime,
+};
+use tokio::sync::oneshot;
+use tracing::*;
+
+/// The default size of a segment, after which it's rotated.
+pub const DEFAULT_MAX_SEGMENT_SIZE: u64 = 64 * 1024 * 1024;
+
+/// The extension of a segment that's being written.
+const SEGMENT_EXTENSION: &str = "jsonl";
+
+/// The extension of a rotated and compressed segment.
+const COMPRESSED_SEGMENT_EXTENSION: &str = "jsonl.zst";
+
+/// The zstd compression level of rotated segments.
+const COMPRESSION_LEVEL: i32 = 3;
+
+/// The outcome of processing an engine API message.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", tag = "type")]
+pub enum RecordedOutcome {
+    /// The payload status returned for an `engine_newPayload` call.
+    NewPayload {
+        /// The status of the payload.
+        status: PayloadStatusEnum,
+        /// The hash of the most recent valid block in the branch of the payload.
+        latest_valid_hash: Option<B256>,
+    },
+    /// The status of the forkchoice state of an `engine_forkchoiceUpdated` call.
+    ForkchoiceUpdated {
+        /// The status of the forkchoice state.
+        status: RecordedForkchoiceStatus,
+    },
+    /// The engine returned an error, or dropped the response.
+    Error {
+        /// The error message.
+        message: String,
+    },
+}
+
+impl From<&PayloadStatus> for RecordedOutcome {
+    fn from(status: &PayloadStatus) -> Self {
+        Self::NewPayload {
+            status: status.status.clone(),
+            latest_valid_hash: status.latest_valid_hash,
+        }
+    }
+}
+
+impl From<ForkchoiceStatus> for RecordedOutcome {
+    fn from(status: ForkchoiceStatus) -> Self {
+        Self::ForkchoiceUpdated { status: status.into() }
+    }
+}
+
+impl RecordedOutcome {
+    /// Creates an outcome from an error.
+    pub fn error(error: impl std::fmt::Display) -> Self {
+        Self::Error { message: error.to_string() }
+    }
+}
+
+/// The serializable representation of [`ForkchoiceStatus`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RecordedForkchoiceStatus {
+    /// See [`ForkchoiceStatus::Valid`].
+    Valid,
+    /// See [`ForkchoiceStatus::Invalid`].
+    Invalid,
+    /// See [`ForkchoiceStatus::Syncing`].
+    Syncing,
+}
+
+impl RecordedForkchoiceStatus {
+    /// Returns `true` if the forkchoice state is [`RecordedForkchoiceStatus::Valid`].
+    pub const fn is_valid(&self) -> bool {
+        matches!(self, Self::Valid)
+    }
+}
+
+impl From<ForkchoiceStatus> for RecordedForkchoiceStatus {
+    fn from(status: ForkchoiceStatus) -> Self {
+        match status {
+            ForkchoiceStatus::Valid => Self::Valid,
+            ForkchoiceStatus::Invalid => Self::Invalid,
+            ForkchoiceStatus::Syncing => Self::Syncing,
+        }
+    }
+}
+
+/// An entry of the log.
+///
+/// The messages are recorded as soon as they're received, so that the message a node crashed on is
+/// recorded as well. Their outcomes are written once the engine responded, which may be after
+/// later messages were received.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", tag = "entry")]
+enum RecordEntry<Attributes> {
+    /// A received engine API message.
+    Message {
+        /// The recording session, which is the first segment written by the recorder.
+        session: u64,
+        /// The index of the message within the session.
+        index: u64,
+        /// The time the message was received at, in milliseconds since the unix epoch.
+        received_at: u64,
+        /// The message.
+        message: StoredEngineApiMessage<Attributes>,
+    },
+    /// The outcome of processing a message.
+    Outcome {
+        /// The recording session of the message.
+        session: u64,
+        /// The index of the message within the session.
+        index: u64,
+        /// The outcome.
+        outcome: RecordedOutcome,
+    },
+}
+
+/// An engine API message read from the log, along with the outcome of processing it.
+#[derive(Debug)]
+pub struct RecordedMessage<Attributes> {
+    /// The recording session, which is the first segment written by the recorder.
+    pub session: u64,
+    /// The index of the message within the session.
+    pub index: u64,
+    /// The time the message was received at, in milliseconds since the unix epoch.
+    pub received_at: u64,
+    /// The message.
+    pub message: StoredEngineApiMessage<Attributes>,
+    /// The outcome of processing the message, if it was recorded.
+    ///
+    /// This is `None` for the message a node crashed or was shut down on.
+    pub outcome: Option<RecordedOutcome>,
+}
+
+/// Writes engine API messages and their outcomes to the log in a directory.
+#[derive(Debug)]
+pub struct EngineRecorder {
+    /// The directory of the log.
+    dir: PathBuf,
+    /// The recording session.
+    session: u64,
+    /// The number of the segment that's being written.
+    segment: u64,
+    /// The segment that's being written.
+    file: File,
+    /// The size of the segment that's being written.
+    segment_size: u64,
+    /// The size after which a segment is rotated.
+    max_segment_size: u64,
+}
+
+impl EngineRecorder {
+    /// Opens the log in the given directory, creating it if it doesn't exist.
+    ///
+    /// The recorder starts a new session in a new segment, so existing segments are never
+    /// modified.
+    pub fn open(dir: impl Into<PathBuf>) -> eyre::Result<Self> {
+        let dir = dir.into();
+        fs::create_dir_all(&dir)?;
+        let segment = segments(&dir)?.last().map_or(0, |(segment, _)| segment + 1);
+        let file = create_segment(&dir, segment)?;
+        Ok(Self {
+            dir,
+            session: segment,
+            segment,
+            file,
+            segment_size: 0,
+            max_segment_size: DEFAULT_MAX_SEGMENT_SIZE,
+        })
+    }
+
+    /// Sets the size after which a segment is rotated.
+    pub const fn with_max_segment_size(mut self, max_segment_size: u64) -> Self {
+        self.max_segment_size = max_segment_size;
+        self
+    }
+
+    /// Returns the directory of the log.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Writes the message with the given index, received at the given time, to the log.
+    pub fn record_message<Attributes: Serialize>(
+        &mut self,
+        index: u64,
+        received_at: SystemTime,
+        message: StoredEngineApiMessage<Attributes>,
+    ) -> eyre::Result<()> {
+        let received_at = received_at.duration_since(SystemTime::UNIX_EPOCH)?.as_millis() as u64;
+        self.append(&RecordEntry::Message { session: self.session, index, received_at, message })
+    }
+
+    /// Writes the outcome of processing the message with the given index to the log.
+    pub fn record_outcome(&mut self, index: u64, outcome: RecordedOutcome) -> eyre::Result<()> {
+        self.append(&RecordEntry::<()>::Outcome { session: self.session, index, outcome })
+    }
+
+    /// Moves the recorder to a dedicated thread, and returns a handle to record on it.
+    ///
+    /// The thread writes the recorded entries in order, and exits once the handle is dropped and
+    /// all entries are written.
+    pub fn spawn(mut self) -> eyre::Result<EngineRecorderHandle> {
+        let (to_recorder, writes) = mpsc::channel::<RecorderWrite>();
+        std::thread::Builder::new().name("engine-record".to_string()).spawn(move || {
+            for write in writes {
+                if let Err(error) = write(&mut self) {
+                    error!(target: "engine::record", %error, "Error recording engine API message");
+                }
+            }
+        })?;
+        Ok(EngineRecorderHandle { to_recorder, next_index: 0 })
+    }
+
+    /// Appends an entry to the segment, and rotates the segment if it's full.
+    fn append<Attributes: Serialize>(
+        &mut self,
+        entry: &RecordEntry<Attributes>,
+    ) -> eyre::Result<()> {
+        let mut line = serde_json::to_vec(entry)?;
+        line.push(b'\n');
+        // a single write per entry, so that a crash leaves at most one partial line behind
+        self.file.write_all(&line)?;
+        self.segment_size += line.len() as u64;
+
+        if self.segment_size >= self.max_segment_size {
+            self.rotate()?;
+        }
+        Ok(())
+    }
+
+    /// Starts a new segment, and compresses the full one in the background.
+    fn rotate(&mut self) -> eyre::Result<()> {
+        self.file.sync_all()?;
+        let full = segment_path(&self.dir, self.segment, SEGMENT_EXTENSION);
+
+        self.segment += 1;
+        self.file = create_segment(&self.dir, self.segment)?;
+        self.segment_size = 0;
+
+        std::thread::Builder::new().name("engine-record-compress".to_string()).spawn(move || {
+            if let Err(error) = compress_segment(&full) {
+                error!(target: "engine::record", path = %full.display(), %error, "Failed to compress segment");
+            }
+        })?;
+        Ok(())
+    }
+}
+
+/// A write to the log, executed on the thread of the [`EngineRecorder`].
+type RecorderWrite = Box<dyn FnOnce(&mut EngineRecorder) -> eyre::Result<()> + Send>;
+
+/// Handle to an [`EngineRecorder`] running on its own thread, see [`EngineRecorder::spawn`].
+#[derive(Debug)]
+pub struct EngineRecorderHandle {
+    /// Sends the writes to the recorder thread.
+    to_recorder: mpsc::Sender<RecorderWrite>,
+    /// The index of the next message.
+    next_index: u64,
+}
+
+impl EngineRecorderHandle {
+    /// Records the received message, and returns its index if the message is replayed.
+    pub fn record_message<Engine: EngineTypes>(
+        &mut self,
+        msg: &BeaconEngineMessage<Engine>,
+        received_at: SystemTime,
+    ) -> Option<u64> {
+        let message = match msg {
+            BeaconEngineMessage::NewPayload { payload, sidecar, .. } => {
+                StoredEngineApiMessage::NewPayload {
+                    payload: payload.clone(),
+                    sidecar: sidecar.clone(),
+                }
+            }
+            BeaconEngineMessage::ForkchoiceUpdated { state, payload_attrs, .. } => {
+                StoredEngineApiMessage::ForkchoiceUpdated {
+                    state: *state,
+                    payload_attrs: payload_attrs.clone(),
+                }
+            }
+            BeaconEngineMessage::TransitionConfigurationExchanged => return None,
+        };
+
+        let index = self.next_index;
+        self.next_index += 1;
+        self.write(move |recorder| recorder.record_message(index, received_at, message));
+        Some(index)
+    }
+
+    /// Records the outcome of processing the message with the given index.
+    pub fn record_outcome(&self, index: u64, outcome: RecordedOutcome) {
+        self.write(move |recorder| recorder.record_outcome(index, outcome));
+    }
+
+    /// Sends the write to the recorder thread.
+    fn write(&self, write: impl FnOnce(&mut EngineRecorder) -> eyre::Result<()> + Send + 'static) {
+        if self.to_recorder.send(Box::new(write)).is_err() {
+            error!(target: "engine::record", "Engine API message recorder stopped");
+        }
+    }
+}
+
+/// Reads the engine API messages and their outcomes from the log in a directory.
+#[derive(Debug)]
+pub struct EngineRecordReader {
+    /// The directory of the log.
+    dir: PathBuf,
+}
+
+impl EngineRecordReader {
+    /// Creates a reader of the log in the given directory.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// Reads all messages of the log, in the order they were received.
+    ///
+    /// A partial last line of a segment, as left behind by a crash, is skipped.
+    pub fn messages<Attributes: DeserializeOwned>(
+        &self,
+    ) -> eyre::Result<Vec<RecordedMessage<Attributes>>> {
+        let mut messages = Vec::new();
+        let mut positions = HashMap::new();
+        for (_, path) in segments(&self.dir)? {
+            let reader: Box<dyn Read> = if is_compressed(&path) {
+                Box::new(zstd::Decoder::new(fs::open(&path)?)?)
+            } else {
+                Box::new(fs::open(&path)?)
+            };
+
+            let mut lines = BufReader::new(reader).lines().peekable();
+            while let Some(line) = lines.next() {
+                let line = line?;
+                let entry = match serde_json::from_str::<RecordEntry<Attributes>>(&line) {
+                    Ok(entry) => entry,
+                    Err(error) if lines.peek().is_none() => {
+                        warn!(target: "engine::record", path = %path.display(), %error, "Skipping partial entry");
+                        break
+                    }
+                    Err(error) => {
+                        return Err(eyre::eyre!("invalid entry in {}: {error}", path.display()))
+                    }
+                };
+
+                match entry {
+                    RecordEntry::Message { session, index, received_at, message } => {
+                        positions.insert((session, index), messages.len());
+                        messages.push(RecordedMessage {
+                            session,
+                            index,
+                            received_at,
+                            message,
+                            outcome: None,
+                        });
+                    }
+                    RecordEntry::Outcome { session, index, outcome } => {
+                        match positions.get(&(session, index)) {
+                            Some(position) => messages[*position].outcome = Some(outcome),
+                            None => {
+                                warn!(target: "engine::record", session, index, "Skipping outcome of unknown message")
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        Ok(messages)
+    }
+}
+
+/// Returns the segments in the directory, ordered by their number.
+///
+/// If a segment exists both uncompressed and compressed, e.g. because the node stopped while
+/// compressing it, the uncompressed one is returned.
+fn segments(dir: &Path) -> eyre::Result<Vec<(u64, PathBuf)>> {
+    let mut segments = std::collections::BTreeMap::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        let Some(filename) = path.file_name().and_then(|name| name.to_str()) else { continue };
+        let Some(number) = filename
+            .strip_suffix(COMPRESSED_SEGMENT_EXTENSION)
+            .or_else(|| filename.strip_suffix(SEGMENT_EXTENSION))
+            .and_then(|name| name.strip_suffix('.'))
+            .and_then(|number| number.parse::<u64>().ok())
+        else {
+            continue
+        };
+
+        let compressed = is_compressed(&path);
+        segments
+            .entry(number)
+            .and_modify(|existing: &mut PathBuf| {
+                if !compressed {
+                    *existing = path.clone();
+                }
+            })
+            .or_insert(path);
+    }
+    Ok(segments.into_iter().collect())
+}
+
+/// Returns the path of the segment with the given number and extension.
+fn segment_path(dir: &Path, segment: u64, extension: &str) -> PathBuf {
+    dir.join(format!("{segment:08}.{extension}"))
+}
+
+/// Returns `true` if the segment at the path is compressed.
+fn is_compressed(path: &Path) -> bool {
+    path.to_str().is_some_and(|path| path.ends_with(COMPRESSED_SEGMENT_EXTENSION))
+}
+
+/// Creates the segment with the given number.
+fn create_segment(dir: &Path, segment: u64) -> eyre::Result<File> {
+    let path = segment_path(dir, segment, SEGMENT_EXTENSION);
+    Ok(File::options()
+        .create_new(true)
+        .append(true)
+        .open(&path)
+        .map_err(|error| fs::FsPathError::create_file(error, &path))?)
+}
+
+/// Compresses the segment at the path, and removes the uncompressed segment.
+fn compress_segment(path: &Path) -> eyre::Result<()> {
+    let compressed = path.with_extension(COMPRESSED_SEGMENT_EXTENSION);
+    let tmp = compressed.with_extension("zst.tmp");
+    {
+        let mut encoder = zstd::Encoder::new(fs::create_file(&tmp)?, COMPRESSION_LEVEL)?;
+        std::io::copy(&mut fs::open(path)?, &mut encoder)?;
+        encoder.finish()?.sync_all()?;
+    }
+    fs::rename(&tmp, &compressed)?;
+    fs::remove_file(path)?;
+    Ok(())
+}
+
+type RecordedOutcomeFut = Pin<Box<dyn Future<Output = (u64, RecordedOutcome)> + Send + Sync>>;
+
+/// A wrapper stream that records engine API messages and the outcomes of processing them with an
+/// [`EngineRecorder`].
+#[derive(Debug)]
+#[pin_project::pin_project]
+pub struct EngineRecordStream<S> {
+    /// Inner message stream.
+    #[pin]
+    stream: S,
+    /// Engine message recorder.
+    recorder: EngineRecorderHandle,
+    /// Pending engine responses to the recorded messages.
+    outcomes: FuturesUnordered<RecordedOutcomeFut>,
+}
+
+impl<S> EngineRecordStream<S> {
+    /// Creates a new engine record stream wrapper.
+    pub fn new(stream: S, recorder: EngineRecorderHandle) -> Self {
+        Self { stream, recorder, outcomes: FuturesUnordered::new() }
+    }
+}
+
+impl<S, Engine> Stream for EngineRecordStream<S>
+where
+    S: Stream<Item = BeaconEngineMessage<Engine>>,
+    Engine: EngineTypes,
+{
+    type Item = S::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+
+        // record the outcomes of the messages the engine responded to
+        while let Poll::Ready(Some((index, outcome))) = this.outcomes.poll_next_unpin(cx) {
+            this.recorder.record_outcome(index, outcome);
+        }
+
+        let Some(msg) = ready!(this.stream.poll_next_unpin(cx)) else { return Poll::Ready(None) };
+        let Some(index) = this.recorder.record_message(&msg, SystemTime::now()) else {
+            return Poll::Ready(Some(msg))
+        };
+
+        // intercept the response to record the outcome, and forward it to the original sender
+        let msg = match msg {
+            BeaconEngineMessage::NewPayload { payload, sidecar, tx } => {
+                let (response_tx, response_rx) = oneshot::channel();
+                this.outcomes.push(Box::pin(async move {
+                    let Ok(response) = response_rx.await else {
+                        return (index, RecordedOutcome::error("engine dropped the response"))
+                    };
+                    let outcome = match &response {
+                        Ok(status) => RecordedOutcome::from(status),
+                        Err(error) => RecordedOutcome::error(error),
+                    };
+                    let _ = tx.send(response);
+                    (index, outcome)
+                }));
+                BeaconEngineMessage::NewPayload { payload, sidecar, tx: response_tx }
+            }
+            BeaconEngineMessage::ForkchoiceUpdated { state, payload_attrs, version, tx } => {
+                let (response_tx, response_rx) = oneshot::channel();
+                this.outcomes.push(Box::pin(async move {
+                    let Ok(response) = response_rx.await else {
+                        return (index, RecordedOutcome::error("engine dropped the response"))
+                    };
+                    let outcome = match &response {
+                        Ok(on_updated) => RecordedOutcome::from(on_updated.forkchoice_status()),
+                        Err(error) => RecordedOutcome::error(error),
+                    };
+                    let _ = tx.send(response);
+                    (index, outcome)
+                }));
+                BeaconEngineMessage::ForkchoiceUpdated {
+                    state,
+                    payload_attrs,
+                    version,
+                    tx: response_tx,
+                }
+            }
+            msg @ BeaconEngineMessage::TransitionConfigurationExchanged => msg,
+        };
+        Poll::Ready(Some(msg))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_rpc_types_engine::ForkchoiceState;
+
+    type Attributes = alloy_rpc_types_engine::PayloadAttributes;
+
+    fn record_fcus(recorder: &mut EngineRecorder, count: u64) {
+        for i in 0..count {
+            let state = ForkchoiceState {
+                head_block_hash: B256::with_last_byte(i as u8),
+                ..Default::default()
+            };
+            let entry = RecordEntry::<Attributes>::Message {
+                session: recorder.session,
+                index: i,
+                received_at: i,
+                message: StoredEngineApiMessage::ForkchoiceUpdated { state, payload_attrs: None },
+            };
+            recorder.append(&entry).unwrap();
+            recorder.record_outcome(i, ForkchoiceStatus::Valid.into()).unwrap();
+        }
+    }
+
+    #[test]
+    fn rotates_and_reads_segments() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut recorder = EngineRecorder::open(dir.path()).unwrap().with_max_segment_size(512);
+        record_fcus(&mut recorder, 20);
+        assert!(recorder.segment > 0);
+
+        // compress a rotated segment in place, as the background thread does
+        compress_segment(&segment_path(dir.path(), 0, SEGMENT_EXTENSION)).unwrap();
+        assert!(segment_path(dir.path(), 0, COMPRESSED_SEGMENT_EXTENSION).exists());
+
+        let messages = EngineRecordReader::new(dir.path()).messages::<Attributes>().unwrap();
+        assert_eq!(messages.len(), 20);
+        for (i, message) in messages.iter().enumerate() {
+            assert_eq!(message.index, i as u64);
+            assert_eq!(message.outcome, Some(ForkchoiceStatus::Valid.into()));
+        }
+    }
+
+    #[test]
+    fn appends_new_session() {
+        let dir = tempfile::tempdir().unwrap();
+        record_fcus(&mut EngineRecorder::open(dir.path()).unwrap(), 2);
+
+        let mut recorder = EngineRecorder::open(dir.path()).unwrap();
+        assert_eq!(recorder.session, 1);
+        record_fcus(&mut recorder, 3);
+
+        let messages = EngineRecordReader::new(dir.path()).messages::<Attributes>().unwrap();
+        let sessions = messages.iter().map(|m| (m.session, m.index)).collect::<Vec<_>>();
+        assert_eq!(sessions, vec![(0, 0), (0, 1), (1, 0), (1, 1), (1, 2)]);
+        assert!(messages.iter().all(|m| m.outcome.is_some()));
+    }
+
+    #[test]
+    fn skips_partial_entry() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut recorder = EngineRecorder::open(dir.path()).unwrap();
+        record_fcus(&mut recorder, 2);
+        recorder.file.write_all(b"{\"entry\":\"message\",\"sess").unwrap();
+
+        let messages = EngineRecordReader::new(dir.path()).messages::<Attributes>().unwrap();
+        assert_eq!(messages.len(), 2);
+    }
+}
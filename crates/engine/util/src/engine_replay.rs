@@ -0,0 +1,216 @@
+//! Replays engine API messages recorded with
+//! [`EngineRecorder`](crate::engine_record::EngineRecorder) through a consensus engine, and
+//! compares the outcomes with the recorded ones.
+
+use crate::{
+    engine_record::{RecordedForkchoiceStatus, RecordedMessage, RecordedOutcome},
+    engine_store::StoredEngineApiMessage,
+};
+use alloy_primitives::B256;
+use alloy_rpc_types_engine::{ForkchoiceUpdateError, ForkchoiceUpdated, PayloadStatusEnum};
+use reth_beacon_consensus::{BeaconConsensusEngineHandle, BeaconForkChoiceUpdateError};
+use reth_engine_primitives::{EngineApiMessageVersion, EngineTypes};
+use serde::Serialize;
+use std::{fmt, time::Duration};
+use tokio::time::Instant;
+use tracing::*;
+
+/// The first message whose replayed outcome differs from the recorded one.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplayDivergence {
+    /// The recording session of the message.
+    pub session: u64,
+    /// The index of the message within the session.
+    pub index: u64,
+    /// The time the message was originally received at, in milliseconds since the unix epoch.
+    pub received_at: u64,
+    /// A summary of the message.
+    pub message: String,
+    /// The outcome of processing the message when it was recorded.
+    pub recorded: RecordedOutcome,
+    /// The outcome of processing the message during the replay.
+    pub replayed: RecordedOutcome,
+}
+
+impl fmt::Display for ReplayDivergence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "replay of message {}/{} ({}) diverged: recorded {:?}, replayed {:?}",
+            self.session, self.index, self.message, self.recorded, self.replayed
+        )
+    }
+}
+
+impl std::error::Error for ReplayDivergence {}
+
+/// The result of a replay without divergence.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ReplaySummary {
+    /// The number of replayed messages.
+    pub replayed: usize,
+    /// The number of replayed messages without a recorded outcome, which couldn't be compared.
+    pub unverified: usize,
+    /// The head of the last forkchoice state the engine accepted as valid.
+    pub head: Option<B256>,
+}
+
+/// Feeds recorded engine API messages to a consensus engine, in the order they were received.
+#[derive(Debug)]
+pub struct EngineReplay<Engine: EngineTypes> {
+    /// Handle to the consensus engine the messages are replayed through.
+    handle: BeaconConsensusEngineHandle<Engine>,
+    /// Whether the messages are sent with the delays they were received with.
+    recorded_timing: bool,
+}
+
+impl<Engine: EngineTypes> EngineReplay<Engine> {
+    /// Creates a replay through the consensus engine of the given handle.
+    pub const fn new(handle: BeaconConsensusEngineHandle<Engine>) -> Self {
+        Self { handle, recorded_timing: false }
+    }
+
+    /// Sends each message with the delay it was received with after the previous message of its
+    /// session, instead of as soon as the previous message was processed.
+    ///
+    /// This reproduces timing dependent behavior, e.g. which blocks are persisted in between.
+    pub const fn with_recorded_timing(mut self, recorded_timing: bool) -> Self {
+        self.recorded_timing = recorded_timing;
+        self
+    }
+
+    /// Replays the messages, and stops at the first message whose outcome differs from the
+    /// recorded one.
+    pub async fn replay(
+        &self,
+        messages: impl IntoIterator<Item = RecordedMessage<Engine::PayloadAttributes>>,
+    ) -> Result<ReplaySummary, Box<ReplayDivergence>> {
+        let mut summary = ReplaySummary::default();
+        // the session, receive time and send time of the previous message
+        let mut previous: Option<(u64, u64, Instant)> = None;
+        for RecordedMessage { session, index, received_at, message, outcome: recorded } in messages
+        {
+            if self.recorded_timing {
+                if let Some((previous_session, previous_received_at, sent_at)) = previous {
+                    if previous_session == session {
+                        let delay = received_at.saturating_sub(previous_received_at);
+                        tokio::time::sleep_until(sent_at + Duration::from_millis(delay)).await;
+                    }
+                }
+                previous = Some((session, received_at, Instant::now()));
+            }
+
+            let description = describe(&message);
+            let head = match &message {
+                StoredEngineApiMessage::ForkchoiceUpdated { state, .. } => {
+                    Some(state.head_block_hash)
+                }
+                StoredEngineApiMessage::NewPayload { .. } => None,
+            };
+
+            let replayed = self.replay_message(message).await;
+            debug!(target: "engine::replay", session, index, message = %description, ?replayed, "Replayed engine API message");
+            summary.replayed += 1;
+
+            match recorded {
+                Some(recorded) if recorded != replayed => {
+                    return Err(Box::new(ReplayDivergence {
+                        session,
+                        index,
+                        received_at,
+                        message: description,
+                        recorded,
+                        replayed,
+                    }))
+                }
+                Some(_) => {}
+                None => {
+                    warn!(target: "engine::replay", session, index, message = %description, ?replayed, "No recorded outcome to compare with");
+                    summary.unverified += 1;
+                }
+            }
+
+            if matches!(replayed, RecordedOutcome::ForkchoiceUpdated { status } if status.is_valid())
+            {
+                summary.head = head;
+            }
+        }
+        Ok(summary)
+    }
+
+    /// Sends the message to the consensus engine, and returns the outcome of processing it.
+    pub async fn replay_message(
+        &self,
+        message: StoredEngineApiMessage<Engine::PayloadAttributes>,
+    ) -> RecordedOutcome {
+        match message {
+            StoredEngineApiMessage::NewPayload { payload, sidecar } => {
+                match self.handle.new_payload(payload, sidecar).await {
+                    Ok(status) => RecordedOutcome::from(&status),
+                    Err(error) => RecordedOutcome::error(error),
+                }
+            }
+            StoredEngineApiMessage::ForkchoiceUpdated { state, payload_attrs } => {
+                forkchoice_outcome(
+                    self.handle
+                        .fork_choice_updated(
+                            state,
+                            payload_attrs,
+                            EngineApiMessageVersion::default(),
+                        )
+                        .await,
+                )
+            }
+        }
+    }
+}
+
+/// Returns the outcome of a forkchoice update, as it's recorded.
+///
+/// The recorded status is the status of the forkchoice state, before the payload attributes are
+/// processed, so invalid payload attributes of a valid state are a valid outcome.
+fn forkchoice_outcome(
+    result: Result<ForkchoiceUpdated, BeaconForkChoiceUpdateError>,
+) -> RecordedOutcome {
+    let status = match result {
+        Ok(updated) => match updated.payload_status.status {
+            PayloadStatusEnum::Valid | PayloadStatusEnum::Accepted => {
+                RecordedForkchoiceStatus::Valid
+            }
+            PayloadStatusEnum::Invalid { .. } => RecordedForkchoiceStatus::Invalid,
+            PayloadStatusEnum::Syncing => RecordedForkchoiceStatus::Syncing,
+        },
+        Err(BeaconForkChoiceUpdateError::ForkchoiceUpdateError(
+            ForkchoiceUpdateError::UpdatedInvalidPayloadAttributes,
+        )) => RecordedForkchoiceStatus::Valid,
+        Err(BeaconForkChoiceUpdateError::ForkchoiceUpdateError(_)) => {
+            RecordedForkchoiceStatus::Invalid
+        }
+        Err(BeaconForkChoiceUpdateError::EngineUnavailable) => {
+            return RecordedOutcome::error("engine dropped the response")
+        }
+        Err(BeaconForkChoiceUpdateError::Internal(error)) => return RecordedOutcome::error(error),
+    };
+    RecordedOutcome::ForkchoiceUpdated { status }
+}
+
+/// Returns a summary of the message.
+fn describe<Attributes>(message: &StoredEngineApiMessage<Attributes>) -> String {
+    match message {
+        StoredEngineApiMessage::NewPayload { payload, .. } => {
+            format!(
+                "newPayload(number: {}, hash: {})",
+                payload.block_number(),
+                payload.block_hash()
+            )
+        }
+        StoredEngineApiMessage::ForkchoiceUpdated { state, payload_attrs } => format!(
+            "forkchoiceUpdated(head: {}, safe: {}, finalized: {}, attributes: {})",
+            state.head_block_hash,
+            state.safe_block_hash,
+            state.finalized_block_hash,
+            payload_attrs.is_some()
+        ),
+    }
+}
@@ -9,6 +9,11 @@ use tokio_util::either::Either;
 pub mod engine_store;
 use engine_store::EngineStoreStream;
 
+pub mod engine_record;
+use engine_record::{EngineRecordStream, EngineRecorderHandle};
+
+pub mod engine_replay;
+
 pub mod skip_fcu;
 use skip_fcu::EngineSkipFcu;
 
@@ -93,6 +98,30 @@ pub trait EngineMessageStreamExt<Engine: EngineTypes>:
         }
     }
 
+    /// Records engine messages and the outcomes of processing them with the recorder.
+    fn record(self, recorder: EngineRecorderHandle) -> EngineRecordStream<Self>
+    where
+        Self: Sized,
+    {
+        EngineRecordStream::new(self, recorder)
+    }
+
+    /// If the recorder is [Some], returns the stream that records engine messages and the outcomes
+    /// of processing them. Otherwise, returns `Self`.
+    fn maybe_record(
+        self,
+        maybe_recorder: Option<EngineRecorderHandle>,
+    ) -> Either<EngineRecordStream<Self>, Self>
+    where
+        Self: Sized,
+    {
+        if let Some(recorder) = maybe_recorder {
+            Either::Left(self.record(recorder))
+        } else {
+            Either::Right(self)
+        }
+    }
+
     /// Creates reorgs with specified frequency.
     fn reorg<Provider, Evm, Spec>(
         self,
@@ -57,6 +57,22 @@ pub trait EthereumHardforks: Hardforks {
         }
     }
 
+    /// Returns `true` if blocks at the given block number can have ommers.
+    ///
+    /// Ommers only exist before the merge, so this is `false` from the [`EthereumHardfork::Paris`]
+    /// activation block on. If the activation block isn't known, e.g. because a terminal total
+    /// difficulty was configured without a merge netsplit block, blocks are assumed to have ommers.
+    fn has_ommers(&self, block_number: u64) -> bool {
+        match self.fork(EthereumHardfork::Paris) {
+            ForkCondition::TTD {
+                activation_block_number: 0,
+                total_difficulty,
+                fork_block: None,
+            } if !total_difficulty.is_zero() => true,
+            _ => !self.is_paris_active_at_block(block_number).unwrap_or(false),
+        }
+    }
+
     /// Returns the final total difficulty if the Paris hardfork is known.
     fn get_final_paris_total_difficulty(&self) -> Option<U256>;
 
@@ -40,6 +40,31 @@ pub trait Hardforks: Clone {
 
     /// Creates a [`ForkFilter`] for the block described by [Head].
     fn fork_filter(&self, head: Head) -> ForkFilter;
+
+    /// Returns the first scheduled hardfork that is not yet active at the given [`Head`].
+    ///
+    /// Hardforks that are never activated are skipped.
+    fn next_fork(&self, head: &Head) -> Option<(&dyn Hardfork, ForkCondition)> {
+        self.forks_iter().find(|(_, condition)| {
+            *condition != ForkCondition::Never && !condition.active_at_head(head)
+        })
+    }
+
+    /// Returns the [`ForkId`] that will be advertised once the [next fork](Self::next_fork) after
+    /// the given [`Head`] activates.
+    ///
+    /// Returns `None` if there is no upcoming fork or its activation is only known by total
+    /// difficulty.
+    fn next_fork_id(&self, head: &Head) -> Option<ForkId> {
+        let head = match self.next_fork(head)?.1 {
+            ForkCondition::Block(number) | ForkCondition::TTD { fork_block: Some(number), .. } => {
+                Head { number, ..*head }
+            }
+            ForkCondition::Timestamp(timestamp) => Head { timestamp, ..*head },
+            ForkCondition::TTD { .. } | ForkCondition::Never => return None,
+        };
+        Some(self.fork_id(&head))
+    }
 }
 
 /// Ordered list of a chain hardforks that implement [`Hardfork`].
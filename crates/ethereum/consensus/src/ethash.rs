@@ -0,0 +1,402 @@
+//! Light verification of Ethash proof-of-work seals.
+//!
+//! Light verification only requires the cache of the epoch of a header, from which the items of
+//! the full dataset that are accessed by `hashimoto` are computed on demand. See the
+//! [Ethash specification](https://ethereum.org/en/developers/docs/consensus-mechanisms/pow/mining/mining-algorithms/ethash/).
+
+use alloy_consensus::BlockHeader;
+use alloy_primitives::{keccak256, B256, U256};
+use alloy_rlp::Encodable;
+use parking_lot::Mutex;
+use reth_consensus::ConsensusError;
+use reth_primitives::{GotExpected, SealedHeader};
+use schnellru::{ByLength, LruMap};
+use sha3::{Digest, Keccak512};
+use std::{
+    fmt,
+    sync::{Arc, OnceLock},
+    time::Instant,
+};
+use tracing::debug;
+
+/// Number of blocks per epoch.
+pub const EPOCH_LENGTH: u64 = 30_000;
+
+/// Default number of epoch caches kept in memory by the [`EthashVerifier`].
+///
+/// A cache is between 16 and 85 MB for pre-merge mainnet epochs.
+pub const DEFAULT_EPOCH_CACHE_CAPACITY: u32 = 3;
+
+/// Size of the cache in bytes at genesis.
+const CACHE_BYTES_INIT: u64 = 1 << 24;
+/// Growth of the cache in bytes per epoch.
+const CACHE_BYTES_GROWTH: u64 = 1 << 17;
+/// Size of the dataset in bytes at genesis.
+const DATASET_BYTES_INIT: u64 = 1 << 30;
+/// Growth of the dataset in bytes per epoch.
+const DATASET_BYTES_GROWTH: u64 = 1 << 23;
+/// Width of mix.
+const MIX_BYTES: u64 = 128;
+/// Hash length in bytes.
+const HASH_BYTES: u64 = 64;
+/// Number of 32 bit words in a hash.
+const HASH_WORDS: usize = 16;
+/// Number of 32 bit words in mix.
+const MIX_WORDS: usize = 32;
+/// Number of parents of each dataset element.
+const DATASET_PARENTS: u32 = 256;
+/// Number of rounds in cache production.
+const CACHE_ROUNDS: usize = 3;
+/// Number of accesses in hashimoto loop.
+const ACCESSES: u32 = 64;
+
+/// A 64 byte hash, as 32 bit little-endian words.
+type Hash512 = [u32; HASH_WORDS];
+
+/// Returns the epoch of the given block.
+pub const fn epoch(block_number: u64) -> u64 {
+    block_number / EPOCH_LENGTH
+}
+
+/// Verifies Ethash proof-of-work seals using the light, cache based, algorithm.
+///
+/// The caches of the most recently used epochs are kept in memory. A cache is generated once, by
+/// the first caller that needs it, while concurrent callers for the same epoch wait for it.
+pub struct EthashVerifier {
+    caches: Mutex<LruMap<u64, Arc<OnceLock<EpochCache>>>>,
+}
+
+impl EthashVerifier {
+    /// Creates a new verifier keeping at most `capacity` epoch caches in memory.
+    pub fn new(capacity: u32) -> Self {
+        Self { caches: Mutex::new(LruMap::new(ByLength::new(capacity.max(1)))) }
+    }
+
+    /// Verifies the mix hash and nonce of the header against its difficulty.
+    pub fn verify_seal<H: BlockHeader>(
+        &self,
+        header: &SealedHeader<H>,
+    ) -> Result<(), ConsensusError> {
+        let difficulty = header.difficulty();
+        if difficulty.is_zero() {
+            return Err(ConsensusError::InsufficientProofOfWork { difficulty })
+        }
+
+        let epoch = epoch(header.number());
+        let cache = self.epoch_cache(epoch);
+        let cache = cache.get_or_init(|| {
+            let start = Instant::now();
+            let cache = EpochCache::new(epoch);
+            debug!(target: "consensus::ethash", epoch, elapsed = ?start.elapsed(), "Generated epoch cache");
+            cache
+        });
+
+        let nonce = u64::from_be_bytes(header.nonce().unwrap_or_default().0);
+        let (mix_hash, result) = cache.hashimoto(seal_hash(header.header()), nonce);
+
+        let expected_mix_hash = header.mix_hash().unwrap_or_default();
+        if mix_hash != expected_mix_hash {
+            return Err(ConsensusError::InvalidMixHash(
+                GotExpected { got: mix_hash, expected: expected_mix_hash }.into(),
+            ))
+        }
+
+        if U256::from_be_bytes(result.0) > U256::MAX / difficulty {
+            return Err(ConsensusError::InsufficientProofOfWork { difficulty })
+        }
+
+        Ok(())
+    }
+
+    /// Returns the cache of the given epoch, which is inserted uninitialized if it's not in
+    /// memory.
+    fn epoch_cache(&self, epoch: u64) -> Arc<OnceLock<EpochCache>> {
+        self.caches.lock().get_or_insert(epoch, Default::default).cloned().unwrap_or_default()
+    }
+}
+
+impl Default for EthashVerifier {
+    fn default() -> Self {
+        Self::new(DEFAULT_EPOCH_CACHE_CAPACITY)
+    }
+}
+
+impl fmt::Debug for EthashVerifier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EthashVerifier")
+            .field("cached_epochs", &self.caches.lock().len())
+            .finish_non_exhaustive()
+    }
+}
+
+/// The cache of an epoch, from which the dataset items are computed.
+struct EpochCache {
+    /// Items of the cache.
+    items: Vec<Hash512>,
+    /// Size of the full dataset of the epoch in bytes.
+    dataset_size: u64,
+}
+
+impl EpochCache {
+    /// Generates the cache of the given epoch.
+    fn new(epoch: u64) -> Self {
+        let seed = (0..epoch).fold(B256::ZERO, |seed, _| keccak256(seed));
+        let len = (cache_size(epoch) / HASH_BYTES) as usize;
+
+        let mut items = Vec::with_capacity(len);
+        items.push(keccak512(seed.as_slice()));
+        for i in 1..len {
+            items.push(keccak512(&to_bytes(&items[i - 1])));
+        }
+
+        for _ in 0..CACHE_ROUNDS {
+            for i in 0..len {
+                let v = items[i][0] as usize % len;
+                let mut mix = items[(i + len - 1) % len];
+                for (word, other) in mix.iter_mut().zip(&items[v]) {
+                    *word ^= other;
+                }
+                items[i] = keccak512(&to_bytes(&mix));
+            }
+        }
+
+        Self { items, dataset_size: dataset_size(epoch) }
+    }
+
+    /// Computes the item of the full dataset at the given index.
+    fn dataset_item(&self, index: u32) -> Hash512 {
+        let len = self.items.len();
+        let mut mix = self.items[index as usize % len];
+        mix[0] ^= index;
+        mix = keccak512(&to_bytes(&mix));
+
+        for parent in 0..DATASET_PARENTS {
+            let cache_index = fnv(index ^ parent, mix[parent as usize % HASH_WORDS]) as usize % len;
+            for (word, other) in mix.iter_mut().zip(&self.items[cache_index]) {
+                *word = fnv(*word, *other);
+            }
+        }
+
+        keccak512(&to_bytes(&mix))
+    }
+
+    /// Computes the mix hash and the proof-of-work result of the given seal hash and nonce.
+    fn hashimoto(&self, seal_hash: B256, nonce: u64) -> (B256, B256) {
+        let mut seed = [0u8; 40];
+        seed[..32].copy_from_slice(seal_hash.as_slice());
+        seed[32..].copy_from_slice(&nonce.to_le_bytes());
+        let seed = keccak512(&seed);
+
+        let mut mix = [0u32; MIX_WORDS];
+        mix[..HASH_WORDS].copy_from_slice(&seed);
+        mix[HASH_WORDS..].copy_from_slice(&seed);
+
+        let pages = (self.dataset_size / MIX_BYTES) as u32;
+        for i in 0..ACCESSES {
+            let page = fnv(i ^ seed[0], mix[i as usize % MIX_WORDS]) % pages;
+            let index = page * 2;
+            let (low, high) = mix.split_at_mut(HASH_WORDS);
+            for (half, item) in [(low, index), (high, index + 1)] {
+                for (word, other) in half.iter_mut().zip(self.dataset_item(item)) {
+                    *word = fnv(*word, other);
+                }
+            }
+        }
+
+        let mut mix_hash = B256::ZERO;
+        for (chunk, words) in mix_hash.chunks_exact_mut(4).zip(mix.chunks_exact(4)) {
+            let word = fnv(fnv(fnv(words[0], words[1]), words[2]), words[3]);
+            chunk.copy_from_slice(&word.to_le_bytes());
+        }
+
+        let mut result = [0u8; 96];
+        result[..64].copy_from_slice(&to_bytes(&seed));
+        result[64..].copy_from_slice(mix_hash.as_slice());
+
+        (mix_hash, keccak256(result))
+    }
+}
+
+/// Returns the hash of the header without the mix hash and nonce, which the seal commits to.
+///
+/// Headers after the merge don't carry a seal, so only the base fee is included of all fields
+/// that were added to the header after genesis.
+pub fn seal_hash<H: BlockHeader>(header: &H) -> B256 {
+    let parent_hash = header.parent_hash();
+    let ommers_hash = header.ommers_hash();
+    let beneficiary = header.beneficiary();
+    let state_root = header.state_root();
+    let transactions_root = header.transactions_root();
+    let receipts_root = header.receipts_root();
+    let logs_bloom = header.logs_bloom();
+    let difficulty = header.difficulty();
+    let number = header.number();
+    let gas_limit = header.gas_limit();
+    let gas_used = header.gas_used();
+    let timestamp = header.timestamp();
+    let base_fee_per_gas = header.base_fee_per_gas();
+
+    let fields: [&dyn Encodable; 13] = [
+        &parent_hash,
+        &ommers_hash,
+        &beneficiary,
+        &state_root,
+        &transactions_root,
+        &receipts_root,
+        &logs_bloom,
+        &difficulty,
+        &number,
+        &gas_limit,
+        &gas_used,
+        &timestamp,
+        header.extra_data(),
+    ];
+
+    let payload_length = fields.iter().map(|field| field.length()).sum::<usize>() +
+        base_fee_per_gas.map_or(0, |base_fee| base_fee.length());
+    let mut out = Vec::with_capacity(alloy_rlp::length_of_length(payload_length) + payload_length);
+    alloy_rlp::Header { list: true, payload_length }.encode(&mut out);
+    for field in fields {
+        field.encode(&mut out);
+    }
+    if let Some(base_fee) = base_fee_per_gas {
+        base_fee.encode(&mut out);
+    }
+
+    keccak256(out)
+}
+
+/// Returns the size of the cache of the given epoch in bytes.
+fn cache_size(epoch: u64) -> u64 {
+    let mut size = CACHE_BYTES_INIT + CACHE_BYTES_GROWTH * epoch - HASH_BYTES;
+    while !is_prime(size / HASH_BYTES) {
+        size -= 2 * HASH_BYTES;
+    }
+    size
+}
+
+/// Returns the size of the full dataset of the given epoch in bytes.
+fn dataset_size(epoch: u64) -> u64 {
+    let mut size = DATASET_BYTES_INIT + DATASET_BYTES_GROWTH * epoch - MIX_BYTES;
+    while !is_prime(size / MIX_BYTES) {
+        size -= 2 * MIX_BYTES;
+    }
+    size
+}
+
+fn is_prime(n: u64) -> bool {
+    n >= 2 && (2..).take_while(|d| d * d <= n).all(|d| n % d != 0)
+}
+
+/// The FNV-1 inspired mixing function of Ethash.
+#[inline]
+const fn fnv(a: u32, b: u32) -> u32 {
+    a.wrapping_mul(0x01000193) ^ b
+}
+
+fn keccak512(data: &[u8]) -> Hash512 {
+    let hash = Keccak512::digest(data);
+    let mut words = [0u32; HASH_WORDS];
+    for (word, bytes) in words.iter_mut().zip(hash.chunks_exact(4)) {
+        *word = u32::from_le_bytes(bytes.try_into().expect("qed"));
+    }
+    words
+}
+
+fn to_bytes(words: &Hash512) -> [u8; 64] {
+    let mut bytes = [0u8; 64];
+    for (chunk, word) in bytes.chunks_exact_mut(4).zip(words) {
+        chunk.copy_from_slice(&word.to_le_bytes());
+    }
+    bytes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::{Header, EMPTY_OMMER_ROOT_HASH, EMPTY_ROOT_HASH};
+    use alloy_primitives::{address, b256, b64, bytes, Bloom, B64};
+
+    /// Mainnet blocks 1 and 2.
+    fn mainnet_headers() -> [Header; 2] {
+        [
+            Header {
+                parent_hash: b256!(
+                    "d4e56740f876aef8c010b86a40d5f56745a118d0906a34e69aec8c0db1cb8fa3"
+                ),
+                ommers_hash: EMPTY_OMMER_ROOT_HASH,
+                beneficiary: address!("05a56e2d52c817161883f50c441c3228cfe54d9f"),
+                state_root: b256!(
+                    "d67e4d450343046425ae4271474353857ab860dbc0a1dde64b41b5cd3a532bf3"
+                ),
+                transactions_root: EMPTY_ROOT_HASH,
+                receipts_root: EMPTY_ROOT_HASH,
+                logs_bloom: Bloom::ZERO,
+                difficulty: U256::from(0x3ff800000u64),
+                number: 1,
+                gas_limit: 5000,
+                gas_used: 0,
+                timestamp: 0x55ba4224,
+                extra_data: bytes!("476574682f76312e302e302f6c696e75782f676f312e342e32"),
+                mix_hash: b256!("969b900de27b6ac6a67742365dd65f55a0526c41fd18e1b16f1a1215c2e66f59"),
+                nonce: b64!("539bd4979fef1ec4"),
+                ..Default::default()
+            },
+            Header {
+                parent_hash: b256!(
+                    "88e96d4537bea4d9c05d12549907b32561d3bf31f45aae734cdc119f13406cb6"
+                ),
+                ommers_hash: EMPTY_OMMER_ROOT_HASH,
+                beneficiary: address!("dd2f1e6e498202e86d8f5442af596580a4f03c2c"),
+                state_root: b256!(
+                    "4943d941637411107494da9ec8bc04359d731bfd08b72b4d0edcbd4cd2ecb341"
+                ),
+                transactions_root: EMPTY_ROOT_HASH,
+                receipts_root: EMPTY_ROOT_HASH,
+                logs_bloom: Bloom::ZERO,
+                difficulty: U256::from(0x3ff001000u64),
+                number: 2,
+                gas_limit: 5000,
+                gas_used: 0,
+                timestamp: 0x55ba4241,
+                extra_data: bytes!(
+                    "476574682f76312e302e302d30636463373634372f6c696e75782f676f312e34"
+                ),
+                mix_hash: b256!("2f0790c5aa31ab94195e1f6443d645af5b75c46c04fbf9911711198a0ce8fdda"),
+                nonce: b64!("b853fa261a86aa9e"),
+                ..Default::default()
+            },
+        ]
+    }
+
+    #[test]
+    fn sizes() {
+        assert_eq!(cache_size(0), 16_776_896);
+        assert_eq!(dataset_size(0), 1_073_739_904);
+    }
+
+    #[test]
+    fn verify_mainnet_seals() {
+        let verifier = EthashVerifier::default();
+        let [block1, block2] = mainnet_headers();
+        assert_eq!(
+            block1.hash_slow(),
+            b256!("88e96d4537bea4d9c05d12549907b32561d3bf31f45aae734cdc119f13406cb6")
+        );
+        assert_eq!(
+            seal_hash(&block1),
+            b256!("85913a3057ea8bec78cd916871ca73802e77724e014dda65add3405d02240eb7")
+        );
+
+        for header in [block1.clone(), block2] {
+            verifier.verify_seal(&SealedHeader::seal(header)).unwrap();
+        }
+
+        // a tampered nonce yields a different mix hash
+        let tampered = Header { nonce: B64::from(0x539bd4979fef1ec5u64), ..block1.clone() };
+        assert!(matches!(
+            verifier.verify_seal(&SealedHeader::seal(tampered)),
+            Err(ConsensusError::InvalidMixHash(_))
+        ));
+    }
+}
@@ -31,6 +31,11 @@ use std::{fmt::Debug, sync::Arc, time::SystemTime};
 mod validation;
 pub use validation::validate_block_post_execution;
 
+#[cfg(feature = "ethash-verify")]
+pub mod ethash;
+#[cfg(feature = "ethash-verify")]
+pub use ethash::EthashVerifier;
+
 /// Ethereum beacon consensus
 ///
 /// This consensus engine does basic checks as outlined in the execution specs.
@@ -38,12 +43,35 @@ pub use validation::validate_block_post_execution;
 pub struct EthBeaconConsensus<ChainSpec> {
     /// Configuration
     chain_spec: Arc<ChainSpec>,
+    /// Verifier of the proof-of-work seal of pre-merge headers, if enabled.
+    #[cfg(feature = "ethash-verify")]
+    ethash: Option<Arc<EthashVerifier>>,
 }
 
 impl<ChainSpec: EthChainSpec + EthereumHardforks> EthBeaconConsensus<ChainSpec> {
     /// Create a new instance of [`EthBeaconConsensus`]
     pub const fn new(chain_spec: Arc<ChainSpec>) -> Self {
-        Self { chain_spec }
+        Self {
+            chain_spec,
+            #[cfg(feature = "ethash-verify")]
+            ethash: None,
+        }
+    }
+
+    /// Enables verification of the proof-of-work seal of headers below the merge block.
+    #[cfg(feature = "ethash-verify")]
+    pub fn with_pow_verification(mut self, verifier: Arc<EthashVerifier>) -> Self {
+        self.ethash = Some(verifier);
+        self
+    }
+
+    /// Returns the blob parameters of the fork that is active at the given timestamp.
+    fn blob_params_at_timestamp(&self, timestamp: u64) -> BlobParams {
+        if self.chain_spec.is_prague_active_at_timestamp(timestamp) {
+            BlobParams::prague()
+        } else {
+            BlobParams::cancun()
+        }
     }
 
     /// Checks the gas limit for consistency between parent and self headers.
@@ -121,7 +149,7 @@ where
         body: &B,
         header: &SealedHeader<H>,
     ) -> Result<(), ConsensusError> {
-        validate_body_against_header(body, header.header())
+        validate_body_against_header(body, header.header(), &self.chain_spec)
     }
 
     fn validate_block_pre_execution(
@@ -141,6 +169,13 @@ where
         validate_header_gas(header.header())?;
         validate_header_base_fee(header.header(), &self.chain_spec)?;
 
+        #[cfg(feature = "ethash-verify")]
+        if let Some(ethash) = &self.ethash {
+            if self.chain_spec.is_paris_active_at_block(header.number()) == Some(false) {
+                ethash.verify_seal(header)?;
+            }
+        }
+
         // EIP-4895: Beacon chain push withdrawals as operations
         if self.chain_spec.is_shanghai_active_at_timestamp(header.timestamp()) &&
             header.withdrawals_root().is_none()
@@ -154,7 +189,10 @@ where
 
         // Ensures that EIP-4844 fields are valid once cancun is active.
         if self.chain_spec.is_cancun_active_at_timestamp(header.timestamp()) {
-            validate_4844_header_standalone(header.header())?;
+            validate_4844_header_standalone(
+                header.header(),
+                self.blob_params_at_timestamp(header.timestamp()),
+            )?;
         } else if header.blob_gas_used().is_some() {
             return Err(ConsensusError::BlobGasUsedUnexpected)
         } else if header.excess_blob_gas().is_some() {
@@ -195,13 +233,11 @@ where
 
         // ensure that the blob gas fields for this block
         if self.chain_spec.is_cancun_active_at_timestamp(header.timestamp()) {
-            let blob_params = if self.chain_spec.is_prague_active_at_timestamp(header.timestamp()) {
-                BlobParams::prague()
-            } else {
-                BlobParams::cancun()
-            };
-
-            validate_against_parent_4844(header.header(), parent.header(), blob_params)?;
+            validate_against_parent_4844(
+                header.header(),
+                parent.header(),
+                self.blob_params_at_timestamp(header.timestamp()),
+            )?;
         }
 
         Ok(())
@@ -272,9 +308,16 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
+    use alloy_eips::{
+        eip1559::INITIAL_BASE_FEE,
+        eip4844::{DATA_GAS_PER_BLOB, MAX_DATA_GAS_PER_BLOCK},
+    };
     use alloy_primitives::B256;
-    use reth_chainspec::{ChainSpec, ChainSpecBuilder};
-    use reth_primitives::proofs;
+    use reth_chainspec::{ChainSpec, ChainSpecBuilder, ForkCondition};
+    use reth_primitives::{proofs, GotExpected, Header};
+
+    const SHANGHAI_TIMESTAMP: u64 = 1_000;
+    const CANCUN_TIMESTAMP: u64 = 2_000;
 
     fn header_with_gas_limit(gas_limit: u64) -> SealedHeader {
         let header = reth_primitives::Header { gas_limit, ..Default::default() };
@@ -368,4 +411,181 @@ mod tests {
             Ok(())
         );
     }
+
+    /// Returns a chain spec that activates Shanghai at [`SHANGHAI_TIMESTAMP`] and Cancun at
+    /// [`CANCUN_TIMESTAMP`].
+    fn fork_boundary_chain_spec() -> Arc<ChainSpec> {
+        Arc::new(
+            ChainSpecBuilder::mainnet()
+                .paris_activated()
+                .with_fork(EthereumHardfork::Shanghai, ForkCondition::Timestamp(SHANGHAI_TIMESTAMP))
+                .with_fork(EthereumHardfork::Cancun, ForkCondition::Timestamp(CANCUN_TIMESTAMP))
+                .without_fork(EthereumHardfork::Prague)
+                .build(),
+        )
+    }
+
+    /// Returns a valid header at the given timestamp, with the fields of the active forks set.
+    fn header_at(timestamp: u64) -> Header {
+        let shanghai = timestamp >= SHANGHAI_TIMESTAMP;
+        let cancun = timestamp >= CANCUN_TIMESTAMP;
+        Header {
+            number: 1,
+            timestamp,
+            gas_limit: 30_000_000,
+            base_fee_per_gas: Some(INITIAL_BASE_FEE),
+            withdrawals_root: shanghai.then(|| proofs::calculate_withdrawals_root(&[])),
+            blob_gas_used: cancun.then_some(0),
+            excess_blob_gas: cancun.then_some(0),
+            parent_beacon_block_root: cancun.then_some(B256::ZERO),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn fork_fields_around_cancun() {
+        let consensus = EthBeaconConsensus::new(fork_boundary_chain_spec());
+        let pre_shanghai = header_at(SHANGHAI_TIMESTAMP - 1);
+        let pre_cancun = header_at(CANCUN_TIMESTAMP - 1);
+        let cancun = header_at(CANCUN_TIMESTAMP);
+
+        let cases = [
+            ("pre-shanghai", pre_shanghai.clone(), Ok(())),
+            (
+                "pre-shanghai with withdrawals root",
+                Header { withdrawals_root: Some(B256::ZERO), ..pre_shanghai },
+                Err(ConsensusError::WithdrawalsRootUnexpected),
+            ),
+            (
+                "shanghai without withdrawals root",
+                Header { withdrawals_root: None, ..header_at(SHANGHAI_TIMESTAMP) },
+                Err(ConsensusError::WithdrawalsRootMissing),
+            ),
+            ("pre-cancun", pre_cancun.clone(), Ok(())),
+            (
+                "pre-cancun with blob gas used",
+                Header { blob_gas_used: Some(0), ..pre_cancun.clone() },
+                Err(ConsensusError::BlobGasUsedUnexpected),
+            ),
+            (
+                "pre-cancun with excess blob gas",
+                Header { excess_blob_gas: Some(0), ..pre_cancun.clone() },
+                Err(ConsensusError::ExcessBlobGasUnexpected),
+            ),
+            (
+                "pre-cancun with parent beacon block root",
+                Header { parent_beacon_block_root: Some(B256::ZERO), ..pre_cancun },
+                Err(ConsensusError::ParentBeaconBlockRootUnexpected),
+            ),
+            ("cancun", cancun.clone(), Ok(())),
+            (
+                "cancun without blob gas used",
+                Header { blob_gas_used: None, ..cancun.clone() },
+                Err(ConsensusError::BlobGasUsedMissing),
+            ),
+            (
+                "cancun without excess blob gas",
+                Header { excess_blob_gas: None, ..cancun.clone() },
+                Err(ConsensusError::ExcessBlobGasMissing),
+            ),
+            (
+                "cancun without parent beacon block root",
+                Header { parent_beacon_block_root: None, ..cancun.clone() },
+                Err(ConsensusError::ParentBeaconBlockRootMissing),
+            ),
+            (
+                "cancun with 6 blobs",
+                Header { blob_gas_used: Some(6 * DATA_GAS_PER_BLOB), ..cancun.clone() },
+                Ok(()),
+            ),
+            (
+                "cancun with 7 blobs",
+                Header { blob_gas_used: Some(7 * DATA_GAS_PER_BLOB), ..cancun },
+                Err(ConsensusError::BlobGasUsedExceedsMaxBlobGasPerBlock {
+                    blob_gas_used: 7 * DATA_GAS_PER_BLOB,
+                    max_blob_gas_per_block: MAX_DATA_GAS_PER_BLOCK,
+                }),
+            ),
+        ];
+
+        for (name, header, expected) in cases {
+            assert_eq!(consensus.validate_header(&SealedHeader::seal(header)), expected, "{name}");
+        }
+    }
+
+    #[test]
+    fn excess_blob_gas_against_parent() {
+        let chain_spec = fork_boundary_chain_spec();
+        let consensus = EthBeaconConsensus::new(chain_spec.clone());
+        let pre_cancun_parent = SealedHeader::seal(header_at(CANCUN_TIMESTAMP - 1));
+        let full_parent = SealedHeader::seal(Header {
+            blob_gas_used: Some(6 * DATA_GAS_PER_BLOB),
+            ..header_at(CANCUN_TIMESTAMP)
+        });
+        let excess_blob_gas_diff = |got, expected, parent: &SealedHeader| {
+            Err(ConsensusError::ExcessBlobGasDiff {
+                diff: GotExpected { got, expected },
+                parent_excess_blob_gas: parent.excess_blob_gas.unwrap_or_default(),
+                parent_blob_gas_used: parent.blob_gas_used.unwrap_or_default(),
+            })
+        };
+
+        let cases = [
+            // the fields of the parent of the first Cancun block are evaluated as 0
+            ("first cancun block", &pre_cancun_parent, 0, Ok(())),
+            (
+                "first cancun block with excess blob gas",
+                &pre_cancun_parent,
+                DATA_GAS_PER_BLOB,
+                excess_blob_gas_diff(DATA_GAS_PER_BLOB, 0, &pre_cancun_parent),
+            ),
+            // 6 blobs used by the parent are 3 blobs above the target
+            ("child of full block", &full_parent, 3 * DATA_GAS_PER_BLOB, Ok(())),
+            (
+                "child of full block without excess blob gas",
+                &full_parent,
+                0,
+                excess_blob_gas_diff(0, 3 * DATA_GAS_PER_BLOB, &full_parent),
+            ),
+        ];
+
+        for (name, parent, excess_blob_gas, expected) in cases {
+            let timestamp = parent.timestamp + 1;
+            let header = Header {
+                number: parent.number + 1,
+                parent_hash: parent.hash(),
+                base_fee_per_gas: parent
+                    .next_block_base_fee(chain_spec.base_fee_params_at_timestamp(timestamp)),
+                excess_blob_gas: Some(excess_blob_gas),
+                ..header_at(timestamp)
+            };
+            assert_eq!(
+                consensus.validate_header_against_parent(&SealedHeader::seal(header), parent),
+                expected,
+                "{name}"
+            );
+        }
+    }
+
+    #[cfg(feature = "ethash-verify")]
+    #[test]
+    fn pow_verification_below_merge_block() {
+        let consensus = EthBeaconConsensus::new(reth_chainspec::MAINNET.clone())
+            .with_pow_verification(Default::default());
+
+        // pre-merge headers must carry a valid seal
+        let header = reth_primitives::Header { number: 1, ..Default::default() };
+        assert_eq!(
+            consensus.validate_header(&SealedHeader::seal(header)),
+            Err(ConsensusError::InsufficientProofOfWork { difficulty: U256::ZERO })
+        );
+
+        // post-merge headers have no seal
+        let header = reth_primitives::Header {
+            number: 20_000_000,
+            base_fee_per_gas: Some(1337),
+            ..Default::default()
+        };
+        assert_eq!(consensus.validate_header(&SealedHeader::seal(header)), Ok(()));
+    }
 }
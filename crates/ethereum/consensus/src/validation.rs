@@ -2,9 +2,9 @@ use alloy_consensus::{proofs::calculate_receipt_root, BlockHeader, TxReceipt};
 use alloy_eips::eip7685::Requests;
 use alloy_primitives::{Bloom, B256};
 use reth_chainspec::EthereumHardforks;
-use reth_consensus::ConsensusError;
+use reth_consensus::{ConsensusError, ReceiptsDiagnostics};
 use reth_primitives::{gas_spent_by_transactions, BlockWithSenders, GotExpected, Receipt};
-use reth_primitives_traits::Block;
+use reth_primitives_traits::{Block, BlockBody, SignedTransaction};
 
 /// Validate a block with regard to execution results:
 ///
@@ -35,9 +35,12 @@ where
     // transaction This was replaced with is_success flag.
     // See more about EIP here: https://eips.ethereum.org/EIPS/eip-658
     if chain_spec.is_byzantium_active_at_block(block.header().number()) {
-        if let Err(error) =
-            verify_receipts(block.header().receipts_root(), block.header().logs_bloom(), receipts)
-        {
+        if let Err(error) = verify_receipts(
+            block.header().receipts_root(),
+            block.header().logs_bloom(),
+            receipts,
+            block.body().transactions().iter().map(|tx| *tx.tx_hash()),
+        ) {
             tracing::debug!(%error, ?receipts, "receipts verification failed");
             return Err(error)
         }
@@ -61,10 +64,14 @@ where
 
 /// Calculate the receipts root, and compare it against against the expected receipts root and logs
 /// bloom.
+///
+/// On mismatch, the error carries [`ReceiptsDiagnostics`] of the receipts, identified by the given
+/// transaction hashes.
 fn verify_receipts(
     expected_receipts_root: B256,
     expected_logs_bloom: Bloom,
     receipts: &[Receipt],
+    tx_hashes: impl IntoIterator<Item = B256>,
 ) -> Result<(), ConsensusError> {
     // Calculate receipts root.
     let receipts_with_bloom = receipts.iter().map(Receipt::with_bloom_ref).collect::<Vec<_>>();
@@ -78,9 +85,15 @@ fn verify_receipts(
         logs_bloom,
         expected_receipts_root,
         expected_logs_bloom,
-    )?;
-
-    Ok(())
+    )
+    .map_err(|mut error| {
+        if let ConsensusError::BodyReceiptRootDiff { diagnostics, .. } |
+        ConsensusError::BodyBloomLogDiff { diagnostics, .. } = &mut error
+        {
+            **diagnostics = ReceiptsDiagnostics::new(receipts, tx_hashes, expected_logs_bloom);
+        }
+        error
+    })
 }
 
 /// Compare the calculated receipts root with the expected receipts root, also compare
@@ -92,15 +105,18 @@ fn compare_receipts_root_and_logs_bloom(
     expected_logs_bloom: Bloom,
 ) -> Result<(), ConsensusError> {
     if calculated_receipts_root != expected_receipts_root {
-        return Err(ConsensusError::BodyReceiptRootDiff(
-            GotExpected { got: calculated_receipts_root, expected: expected_receipts_root }.into(),
-        ))
+        return Err(ConsensusError::BodyReceiptRootDiff {
+            root: GotExpected { got: calculated_receipts_root, expected: expected_receipts_root }
+                .into(),
+            diagnostics: Default::default(),
+        })
     }
 
     if calculated_logs_bloom != expected_logs_bloom {
-        return Err(ConsensusError::BodyBloomLogDiff(
-            GotExpected { got: calculated_logs_bloom, expected: expected_logs_bloom }.into(),
-        ))
+        return Err(ConsensusError::BodyBloomLogDiff {
+            bloom: GotExpected { got: calculated_logs_bloom, expected: expected_logs_bloom }.into(),
+            diagnostics: Default::default(),
+        })
     }
 
     Ok(())
@@ -108,7 +124,8 @@ fn compare_receipts_root_and_logs_bloom(
 
 #[cfg(test)]
 mod tests {
-    use alloy_primitives::hex;
+    use alloy_primitives::{hex, Address, Bytes, Log};
+    use reth_consensus::{ReceiptDiff, ReceiptFieldDiff, ReceiptSummary};
 
     use super::*;
 
@@ -121,7 +138,8 @@ mod tests {
         assert!(verify_receipts(
             B256::from(hex!("61353b4fb714dc1fccacbf7eafc4273e62f3d1eed716fe41b2a0cd2e12c63ebc")),
             Bloom::from(hex!("00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000")),
-            &receipts
+            &receipts,
+            []
         )
         .is_ok());
     }
@@ -135,7 +153,70 @@ mod tests {
         // Create a vector of 5 random Receipt instances
         let receipts = vec![Receipt::default(); 5];
 
-        assert!(verify_receipts(expected_receipts_root, expected_logs_bloom, &receipts).is_err());
+        assert!(
+            verify_receipts(expected_receipts_root, expected_logs_bloom, &receipts, []).is_err()
+        );
+    }
+
+    #[test]
+    fn test_verify_receipts_corrupted_receipt_diagnostics() {
+        let receipts = (0..4u64)
+            .map(|i| Receipt {
+                success: true,
+                cumulative_gas_used: 21_000 * (i + 1),
+                logs: vec![Log::new_unchecked(
+                    Address::with_last_byte(i as u8),
+                    vec![],
+                    Bytes::new(),
+                )],
+                ..Default::default()
+            })
+            .collect::<Vec<_>>();
+        let tx_hashes = (0..4).map(B256::with_last_byte).collect::<Vec<_>>();
+        let receipts_root = calculate_receipt_root(
+            &receipts.iter().map(Receipt::with_bloom_ref).collect::<Vec<_>>(),
+        );
+        let logs_bloom = receipts.iter().fold(Bloom::ZERO, |bloom, r| bloom | r.bloom_slow());
+
+        // A log that is not covered by the header bloom pinpoints the receipt.
+        let mut corrupted = receipts.clone();
+        corrupted[2].logs.push(Log::new_unchecked(
+            Address::repeat_byte(0xaa),
+            vec![],
+            Bytes::new(),
+        ));
+        let Err(ConsensusError::BodyReceiptRootDiff { diagnostics, .. }) =
+            verify_receipts(receipts_root, logs_bloom, &corrupted, tx_hashes.clone())
+        else {
+            panic!("expected receipt root mismatch")
+        };
+        assert_eq!(
+            diagnostics.first_mismatch,
+            Some(ReceiptDiff {
+                index: 2,
+                tx_hash: Some(tx_hashes[2]),
+                fields: vec![ReceiptFieldDiff::LogsBloom]
+            })
+        );
+
+        // A status flip keeps the bloom intact and needs the expected receipts.
+        let mut corrupted = receipts.clone();
+        corrupted[1].success = false;
+        let Err(ConsensusError::BodyReceiptRootDiff { mut diagnostics, .. }) =
+            verify_receipts(receipts_root, logs_bloom, &corrupted, tx_hashes.clone())
+        else {
+            panic!("expected receipt root mismatch")
+        };
+        assert_eq!(diagnostics.first_mismatch, None);
+        diagnostics.compare_with(&ReceiptSummary::from_receipts(&receipts, tx_hashes.clone()));
+        assert_eq!(
+            diagnostics.first_mismatch,
+            Some(ReceiptDiff {
+                index: 1,
+                tx_hash: Some(tx_hashes[1]),
+                fields: vec![ReceiptFieldDiff::Success(GotExpected::new(false, true))]
+            })
+        );
     }
 
     #[test]
@@ -170,10 +251,14 @@ mod tests {
                 expected_receipts_root,
                 expected_logs_bloom
             ),
-            Err(ConsensusError::BodyReceiptRootDiff(
-                GotExpected { got: calculated_receipts_root, expected: expected_receipts_root }
-                    .into()
-            ))
+            Err(ConsensusError::BodyReceiptRootDiff {
+                root: GotExpected {
+                    got: calculated_receipts_root,
+                    expected: expected_receipts_root
+                }
+                .into(),
+                diagnostics: Default::default(),
+            })
         );
     }
 
@@ -192,9 +277,11 @@ mod tests {
                 expected_receipts_root,
                 expected_logs_bloom
             ),
-            Err(ConsensusError::BodyBloomLogDiff(
-                GotExpected { got: calculated_logs_bloom, expected: expected_logs_bloom }.into()
-            ))
+            Err(ConsensusError::BodyBloomLogDiff {
+                bloom: GotExpected { got: calculated_logs_bloom, expected: expected_logs_bloom }
+                    .into(),
+                diagnostics: Default::default(),
+            })
         );
     }
 }
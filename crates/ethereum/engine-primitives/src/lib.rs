@@ -21,10 +21,10 @@ use reth_chainspec::ChainSpec;
 use reth_engine_primitives::{BuiltPayload, EngineTypes, EngineValidator, PayloadValidator};
 use reth_payload_primitives::{
     validate_version_specific_fields, EngineApiMessageVersion, EngineObjectValidationError,
-    PayloadOrAttributes, PayloadTypes,
+    InvalidPayloadAttributesError, PayloadAttributes, PayloadOrAttributes, PayloadTypes,
 };
 use reth_payload_validator::ExecutionPayloadValidator;
-use reth_primitives::{Block, NodePrimitives, SealedBlock, SealedBlockFor};
+use reth_primitives::{Block, Header, NodePrimitives, SealedBlock, SealedBlockFor};
 use reth_rpc_types_compat::engine::payload::block_to_payload;
 
 /// The types used in the default mainnet ethereum beacon consensus engine.
@@ -124,4 +124,12 @@ where
     ) -> Result<(), EngineObjectValidationError> {
         validate_version_specific_fields(self.chain_spec(), version, attributes.into())
     }
+
+    fn validate_payload_attributes_against_header(
+        &self,
+        attr: &EthPayloadAttributes,
+        header: &Header,
+    ) -> Result<(), InvalidPayloadAttributesError> {
+        attr.validate(header, self.chain_spec())
+    }
 }
@@ -23,8 +23,8 @@ use reth_provider::{CanonStateSubscriptions, EthStorage};
 use reth_rpc::EthApi;
 use reth_tracing::tracing::{debug, info};
 use reth_transaction_pool::{
-    blobstore::DiskFileBlobStore, EthTransactionPool, PoolTransaction, TransactionPool,
-    TransactionValidationTaskExecutor,
+    blobstore::{DiskFileBlobStore, DiskFileBlobStoreConfig, OpenDiskFileBlobStore},
+    EthTransactionPool, PoolTransaction, TransactionPool, TransactionValidationTaskExecutor,
 };
 use reth_trie_db::MerklePatriciaTrie;
 use std::sync::Arc;
@@ -160,7 +160,16 @@ where
     async fn build_pool(self, ctx: &BuilderContext<Node>) -> eyre::Result<Self::Pool> {
         let data_dir = ctx.config().datadir();
         let pool_config = ctx.pool_config();
-        let blob_store = DiskFileBlobStore::open(data_dir.blobstore(), Default::default())?;
+        // keep the retained sidecars of finalized blocks across restarts
+        let open = if ctx.maintain_pool_config().blob_retention.blocks > 0 {
+            OpenDiskFileBlobStore::ReIndex
+        } else {
+            OpenDiskFileBlobStore::Clear
+        };
+        let blob_store = DiskFileBlobStore::open(
+            data_dir.blobstore(),
+            DiskFileBlobStoreConfig::default().with_open(open),
+        )?;
         let validator = TransactionValidationTaskExecutor::eth_builder(ctx.chain_spec())
             .with_head_timestamp(ctx.head().timestamp)
             .kzg_settings(ctx.kzg_settings()?)
@@ -204,7 +213,7 @@ where
                     pool,
                     chain_events,
                     ctx.task_executor().clone(),
-                    Default::default(),
+                    ctx.maintain_pool_config(),
                 ),
             );
             debug!(target: "reth::cli", "Spawned txpool maintenance task");
@@ -52,6 +52,7 @@ impl EthereumPayloadBuilder {
         let payload_job_config = BasicPayloadJobGeneratorConfig::default()
             .interval(conf.interval())
             .deadline(conf.deadline())
+            .getpayload_wait(conf.getpayload_wait())
             .max_payload_tasks(conf.max_payload_tasks());
 
         let payload_generator = BasicPayloadJobGenerator::with_builder(
@@ -1,17 +1,22 @@
 use crate::utils::eth_payload_attributes;
 use alloy_consensus::constants::MAINNET_GENESIS_HASH;
+use alloy_eips::{eip4844::BlobTransactionSidecar, merge::EPOCH_SLOTS, BlockId};
 use alloy_genesis::Genesis;
+use alloy_provider::{Provider, ProviderBuilder};
 use alloy_rpc_types_engine::PayloadStatusEnum;
 use reth_chainspec::{ChainSpecBuilder, MAINNET};
 use reth_e2e_test_utils::{
     node::NodeTestContext, transaction::TransactionTestContext, wallet::Wallet,
 };
 use reth_node_builder::{NodeBuilder, NodeHandle};
-use reth_node_core::{args::RpcServerArgs, node_config::NodeConfig};
+use reth_node_core::{
+    args::{RpcServerArgs, TxPoolArgs},
+    node_config::NodeConfig,
+};
 use reth_node_ethereum::EthereumNode;
 use reth_tasks::TaskManager;
-use reth_transaction_pool::TransactionPool;
-use std::sync::Arc;
+use reth_transaction_pool::{blobstore::BlobStore, TransactionPool};
+use std::{sync::Arc, time::Duration};
 
 #[tokio::test]
 async fn can_handle_blobs() -> eyre::Result<()> {
@@ -88,3 +93,79 @@ async fn can_handle_blobs() -> eyre::Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn can_fetch_retained_blob_sidecars() -> eyre::Result<()> {
+    reth_tracing::init_test_tracing();
+    let tasks = TaskManager::current();
+    let exec = tasks.executor();
+
+    let genesis: Genesis = serde_json::from_str(include_str!("../assets/genesis.json")).unwrap();
+    let chain_spec = Arc::new(
+        ChainSpecBuilder::default()
+            .chain(MAINNET.chain)
+            .genesis(genesis)
+            .cancun_activated()
+            .build(),
+    );
+    let node_config = NodeConfig::test()
+        .with_chain(chain_spec)
+        .with_unused_ports()
+        .with_rpc(RpcServerArgs::default().with_unused_ports().with_http())
+        .with_txpool(TxPoolArgs { blob_retention_epochs: 1, ..Default::default() });
+    let NodeHandle { node, node_exit_future: _ } = NodeBuilder::new(node_config)
+        .testing_node(exec.clone())
+        .node(EthereumNode::default())
+        .launch()
+        .await?;
+
+    let mut node = NodeTestContext::new(node, eth_payload_attributes).await?;
+    let provider = ProviderBuilder::new().on_http(node.rpc_url());
+
+    let wallet = Wallet::default().gen().swap_remove(0);
+    let blob_tx = TransactionTestContext::tx_with_blobs_bytes(1, wallet).await?;
+    let blob_tx_hash = node.rpc.inject_tx(blob_tx).await?;
+
+    // include the blob tx in a block, every block is finalized right away
+    let (payload, _) = node.advance_block().await?;
+    let block_number = payload.block().number;
+    assert_eq!(payload.block().body.transactions.len(), 1);
+
+    let sidecars: Option<Vec<BlobTransactionSidecar>> = provider
+        .raw_request("eth_getBlobSidecars".into(), (BlockId::number(block_number),))
+        .await?;
+    let sidecars = sidecars.expect("sidecars are retained");
+    assert_eq!(sidecars.len(), 1);
+    assert!(!sidecars[0].blobs.is_empty());
+    assert_eq!(sidecars[0].blobs.len(), sidecars[0].commitments.len());
+    assert_eq!(sidecars[0].blobs.len(), sidecars[0].proofs.len());
+
+    let sidecar: Option<BlobTransactionSidecar> =
+        provider.raw_request("eth_getBlobSidecarByTxHash".into(), (blob_tx_hash,)).await?;
+    assert_eq!(sidecar.as_ref(), sidecars.first());
+
+    let retained = node.inner.pool.blob_store().blobs_len();
+    assert_eq!(retained, 1);
+
+    // advance the finalized block past the retention window
+    for _ in 0..=EPOCH_SLOTS {
+        node.advance_block().await?;
+    }
+    // wait for the maintenance task to delete the sidecars
+    tokio::time::timeout(Duration::from_secs(10), async {
+        while node.inner.pool.blob_store().blobs_len() >= retained {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await?;
+
+    let sidecars: Option<Vec<BlobTransactionSidecar>> = provider
+        .raw_request("eth_getBlobSidecars".into(), (BlockId::number(block_number),))
+        .await?;
+    assert_eq!(sidecars, None);
+    let sidecar: Option<BlobTransactionSidecar> =
+        provider.raw_request("eth_getBlobSidecarByTxHash".into(), (blob_tx_hash,)).await?;
+    assert_eq!(sidecar, None);
+
+    Ok(())
+}
@@ -1,18 +1,31 @@
+use crate::utils::eth_payload_attributes;
 use alloy_eips::eip2718::Encodable2718;
 use alloy_genesis::Genesis;
-use alloy_primitives::{b256, hex};
+use alloy_primitives::{b256, hex, Address, U256};
+use alloy_provider::{
+    network::{EthereumWallet, TransactionBuilder},
+    Provider, ProviderBuilder,
+};
+use alloy_rpc_types_eth::TransactionRequest;
 use futures::StreamExt;
-use reth_chainspec::ChainSpec;
+use reth_chainspec::{ChainSpec, ChainSpecBuilder, MAINNET};
+use reth_e2e_test_utils::setup_engine;
+use reth_engine_util::{
+    engine_record::EngineRecordReader, engine_replay::EngineReplay,
+    engine_store::StoredEngineApiMessage,
+};
 use reth_node_api::{FullNodeComponents, FullNodePrimitives, NodeTypes};
 use reth_node_builder::{
     rpc::RethRpcAddOns, EngineNodeLauncher, FullNode, NodeBuilder, NodeConfig, NodeHandle,
 };
 use reth_node_core::args::DevArgs;
 use reth_node_ethereum::{node::EthereumAddOns, EthereumNode};
-use reth_provider::{providers::BlockchainProvider2, CanonStateSubscriptions};
+use reth_provider::{
+    providers::BlockchainProvider2, BlockHashReader, BlockNumReader, CanonStateSubscriptions,
+};
 use reth_rpc_eth_api::helpers::EthTransactions;
 use reth_tasks::TaskManager;
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 
 #[tokio::test]
 async fn can_run_dev_node() -> eyre::Result<()> {
@@ -43,6 +56,139 @@ async fn can_run_dev_node() -> eyre::Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn can_change_dev_fee_recipient() -> eyre::Result<()> {
+    reth_tracing::init_test_tracing();
+
+    let chain_spec = Arc::new(
+        ChainSpecBuilder::default()
+            .chain(MAINNET.chain)
+            .genesis(serde_json::from_str(include_str!("../assets/genesis.json")).unwrap())
+            .cancun_activated()
+            .build(),
+    );
+
+    let (mut nodes, _tasks, wallet) =
+        setup_engine::<EthereumNode>(1, chain_spec, true, eth_payload_attributes).await?;
+    let node = nodes.pop().unwrap();
+    let signer = wallet.gen().swap_remove(0);
+    let dev_account = signer.address();
+    let provider = ProviderBuilder::new()
+        .with_recommended_fillers()
+        .wallet(EthereumWallet::new(signer))
+        .on_http(node.rpc_url());
+
+    // the fee recipient defaults to the first dev account
+    let coinbase: Address = provider.raw_request("eth_coinbase".into(), ()).await?;
+    assert_eq!(coinbase, dev_account);
+
+    for fee_recipient in [Address::with_last_byte(1), Address::with_last_byte(2)] {
+        let updated: bool =
+            provider.raw_request("miner_setEtherbase".into(), (fee_recipient,)).await?;
+        assert!(updated);
+        let coinbase: Address = provider.raw_request("eth_coinbase".into(), ()).await?;
+        assert_eq!(coinbase, fee_recipient);
+
+        let tx = TransactionRequest::default()
+            .with_to(Address::with_last_byte(0xff))
+            .with_value(U256::from(1));
+        let receipt = provider.send_transaction(tx).await?.get_receipt().await?;
+        let block = provider
+            .get_block_by_number(receipt.block_number.unwrap().into(), false.into())
+            .await?
+            .unwrap();
+        assert_eq!(block.header.beneficiary, fee_recipient);
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn can_replay_recorded_dev_run() -> eyre::Result<()> {
+    reth_tracing::init_test_tracing();
+    let tasks = TaskManager::current();
+    let exec = tasks.executor();
+    let record = tempfile::tempdir()?;
+
+    // record a dev run that mines a block every 100ms
+    let mut node_config = NodeConfig::test().with_chain(custom_chain()).with_dev(DevArgs {
+        dev: true,
+        block_time: Some(Duration::from_millis(100)),
+        ..Default::default()
+    });
+    node_config.debug.record = Some(record.path().to_path_buf());
+    let handle = NodeBuilder::new(node_config)
+        .testing_node(exec.clone())
+        .with_types_and_provider::<EthereumNode, BlockchainProvider2<_>>()
+        .with_components(EthereumNode::components())
+        .with_add_ons(EthereumAddOns::default())
+        .launch_with_fn(|builder| {
+            let launcher = EngineNodeLauncher::new(
+                builder.task_executor().clone(),
+                builder.config().datadir(),
+                Default::default(),
+            );
+            builder.launch_with(launcher)
+        })
+        .await?;
+    let recorded_provider = handle.node.provider.clone();
+    let mut notifications = handle.node.provider.canonical_state_stream();
+    // wait for the next block, so that the update to block 5 was processed before shutting down
+    while notifications.next().await.unwrap().tip().number < 6 {}
+    handle.shutdown().await;
+
+    // the recorder writes on its own thread, so wait until the update to block 5 was written
+    let head = recorded_provider.block_hash(5)?;
+    let reader = EngineRecordReader::new(record.path());
+    let mut messages = loop {
+        let messages = reader.messages::<alloy_rpc_types_engine::PayloadAttributes>()?;
+        let written = messages.iter().any(|recorded| match &recorded.message {
+            StoredEngineApiMessage::ForkchoiceUpdated { state, .. } => {
+                recorded.outcome.is_some() && Some(state.head_block_hash) == head
+            }
+            StoredEngineApiMessage::NewPayload { .. } => false,
+        });
+        if written {
+            break messages
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    };
+    // the outcomes of the messages processed while shutting down may not have been recorded
+    messages.retain(|message| message.outcome.is_some());
+
+    // replay against a fresh datadir, which is the state the dev run started from
+    let handle = NodeBuilder::new(NodeConfig::test().with_chain(custom_chain()))
+        .testing_node(exec)
+        .with_types_and_provider::<EthereumNode, BlockchainProvider2<_>>()
+        .with_components(EthereumNode::components())
+        .with_add_ons(EthereumAddOns::default())
+        .launch_with_fn(|builder| {
+            let launcher = EngineNodeLauncher::new(
+                builder.task_executor().clone(),
+                builder.config().datadir(),
+                Default::default(),
+            );
+            builder.launch_with(launcher)
+        })
+        .await?;
+    let summary = EngineReplay::new(handle.consensus_engine_handle().clone())
+        .replay(messages)
+        .await
+        .map_err(|divergence| eyre::eyre!(divergence))?;
+    assert_eq!(summary.unverified, 0);
+
+    // the replayed chain is the recorded one
+    let provider = &handle.node.provider;
+    let number = provider.best_block_number()?;
+    assert!(number >= 5);
+    let replayed_head = provider.block_hash(number)?;
+    assert_eq!(replayed_head, summary.head);
+    assert_eq!(replayed_head, recorded_provider.block_hash(number)?);
+
+    handle.shutdown().await;
+    Ok(())
+}
+
 async fn assert_chain_advances<N, AddOns>(node: FullNode<N, AddOns>)
 where
     N: FullNodeComponents<Provider: CanonStateSubscriptions>,
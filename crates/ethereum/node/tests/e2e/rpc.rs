@@ -1,13 +1,25 @@
 use crate::utils::eth_payload_attributes;
-use alloy_eips::{calc_next_block_base_fee, eip2718::Encodable2718};
-use alloy_primitives::{Address, B256, U256};
-use alloy_provider::{network::EthereumWallet, Provider, ProviderBuilder, SendableTx};
+use alloy_eips::{calc_next_block_base_fee, eip2718::Encodable2718, BlockId};
+use alloy_primitives::{bytes, Address, Bytes, B256, U256};
+use alloy_provider::{
+    network::{EthereumWallet, TransactionBuilder},
+    Provider, ProviderBuilder, SendableTx,
+};
 use alloy_rpc_types_beacon::relay::{
     BidTrace, BuilderBlockValidationRequestV3, BuilderBlockValidationRequestV4,
     SignedBidSubmissionV3, SignedBidSubmissionV4,
 };
 use alloy_rpc_types_engine::BlobsBundleV1;
-use alloy_rpc_types_eth::TransactionRequest;
+use alloy_rpc_types_eth::{BlockNumberOrTag, TransactionRequest};
+use alloy_rpc_types_mev::{EthCallBundle, EthCallBundleResponse, EthSendBundle};
+use alloy_rpc_types_trace::{
+    geth::{
+        GethDebugBuiltInTracerType, GethDebugTracerType, GethDebugTracingCallOptions,
+        GethDebugTracingOptions, GethTrace,
+    },
+    parity::{TraceCallRequest, TraceType},
+};
+use alloy_signer_local::PrivateKeySigner;
 use rand::{rngs::StdRng, Rng, SeedableRng};
 use reth_chainspec::{ChainSpecBuilder, MAINNET};
 use reth_e2e_test_utils::setup_engine;
@@ -117,6 +129,215 @@ async fn test_fee_history() -> eyre::Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_debug_trace_block_range() -> eyre::Result<()> {
+    reth_tracing::init_test_tracing();
+
+    let seed: [u8; 32] = rand::thread_rng().gen();
+    let mut rng = StdRng::from_seed(seed);
+    println!("Seed: {:?}", seed);
+
+    let chain_spec = Arc::new(
+        ChainSpecBuilder::default()
+            .chain(MAINNET.chain)
+            .genesis(serde_json::from_str(include_str!("../assets/genesis.json")).unwrap())
+            .cancun_activated()
+            .build(),
+    );
+
+    let (mut nodes, _tasks, wallet) =
+        setup_engine::<EthereumNode>(1, chain_spec.clone(), false, eth_payload_attributes).await?;
+    let mut node = nodes.pop().unwrap();
+    let provider = ProviderBuilder::new()
+        .with_recommended_fillers()
+        .wallet(EthereumWallet::new(wallet.gen().swap_remove(0)))
+        .on_http(node.rpc_url());
+
+    for _ in 0..50 {
+        let _ =
+            GasWaster::deploy_builder(&provider, U256::from(rng.gen_range(0..100))).send().await?;
+        let _ = provider
+            .send_transaction(
+                TransactionRequest::default()
+                    .to(Address::with_last_byte(rng.gen()))
+                    .value(U256::from(1)),
+            )
+            .await?;
+
+        node.advance_block().await?;
+    }
+
+    let debug_api = node.rpc.inner.debug_api();
+    for tracer in
+        [GethDebugBuiltInTracerType::PreStateTracer, GethDebugBuiltInTracerType::CallTracer]
+    {
+        let opts = GethDebugTracingOptions {
+            tracer: Some(GethDebugTracerType::BuiltInTracer(tracer)),
+            ..Default::default()
+        };
+
+        // a memory budget of zero spills the state changes of every block to disk
+        let (tx, mut rx) = tokio::sync::mpsc::channel(50);
+        debug_api.debug_trace_block_range(1, 50, opts.clone(), 0, u64::MAX, tx).await?;
+
+        let mut traced = 0;
+        while let Some(result) = rx.recv().await {
+            let expected =
+                debug_api.debug_trace_block(result.block_number.into(), opts.clone()).await?;
+            assert_eq!(
+                serde_json::to_value(&result.traces)?,
+                serde_json::to_value(&expected)?,
+                "block {}",
+                result.block_number
+            );
+            traced += 1;
+        }
+        assert_eq!(traced, 50);
+    }
+
+    // fails once the state changes of the blocks exceed the spill limit
+    let (tx, _rx) = tokio::sync::mpsc::channel(50);
+    assert!(debug_api
+        .debug_trace_block_range(1, 50, GethDebugTracingOptions::default(), 0, 1, tx)
+        .await
+        .is_err());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_call_pending_includes_pool_transactions() -> eyre::Result<()> {
+    reth_tracing::init_test_tracing();
+
+    let chain_spec = Arc::new(
+        ChainSpecBuilder::default()
+            .chain(MAINNET.chain)
+            .genesis(serde_json::from_str(include_str!("../assets/genesis.json")).unwrap())
+            .cancun_activated()
+            .build(),
+    );
+
+    let (mut nodes, _tasks, wallet) =
+        setup_engine::<EthereumNode>(1, chain_spec.clone(), false, eth_payload_attributes).await?;
+    let node = nodes.pop().unwrap();
+    let signer = wallet.gen().swap_remove(0);
+    let sender = signer.address();
+    let provider = ProviderBuilder::new()
+        .with_recommended_fillers()
+        .wallet(EthereumWallet::new(signer))
+        .on_http(node.rpc_url());
+
+    // deploys a contract that returns 42 for every call
+    let code = bytes!("69602a60005260206000f3600052600a6016f3");
+    let nonce = provider.get_transaction_count(sender).await?;
+    let contract = sender.create(nonce);
+    let _ = provider.send_transaction(TransactionRequest::default().with_deploy_code(code)).await?;
+
+    let call = TransactionRequest::default().to(contract);
+    assert_eq!(provider.call(&call).await?, Bytes::new());
+    assert_eq!(
+        provider.call(&call).block(BlockId::pending()).await?,
+        Bytes::from(U256::from(42).to_be_bytes::<32>())
+    );
+    assert!(provider.estimate_gas(&call).block(BlockId::pending()).await? > 21_000);
+
+    assert_eq!(
+        provider.get_transaction_count(sender).block_id(BlockId::pending()).await?,
+        nonce + 1
+    );
+    assert!(
+        provider.get_balance(sender).block_id(BlockId::pending()).await? <
+            provider.get_balance(sender).await?
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_trace_call_at_transaction_index() -> eyre::Result<()> {
+    reth_tracing::init_test_tracing();
+
+    let chain_spec = Arc::new(
+        ChainSpecBuilder::default()
+            .chain(MAINNET.chain)
+            .genesis(serde_json::from_str(include_str!("../assets/genesis.json")).unwrap())
+            .cancun_activated()
+            .build(),
+    );
+
+    let (mut nodes, _tasks, wallet) =
+        setup_engine::<EthereumNode>(1, chain_spec.clone(), false, eth_payload_attributes).await?;
+    let mut node = nodes.pop().unwrap();
+    let signer = wallet.gen().swap_remove(0);
+    let sender = signer.address();
+    let provider = ProviderBuilder::new()
+        .with_recommended_fillers()
+        .wallet(EthereumWallet::new(signer))
+        .on_http(node.rpc_url());
+
+    // deploys a contract that stores the calldata in slot 0 and returns slot 0 for empty calldata
+    let code = bytes!("601780600b6000396000f336600f5760005460005260206000f35b60003560005500");
+    let nonce = provider.get_transaction_count(sender).await?;
+    let contract = sender.create(nonce);
+    let _ = provider.send_transaction(TransactionRequest::default().with_deploy_code(code)).await?;
+    node.advance_block().await?;
+
+    // the second block stores 1 and then 2
+    for value in [1u64, 2] {
+        let _ = provider
+            .send_transaction(
+                TransactionRequest::default()
+                    .to(contract)
+                    .input(Bytes::from(U256::from(value).to_be_bytes::<32>()).into()),
+            )
+            .await?;
+    }
+    node.advance_block().await?;
+
+    let call = TransactionRequest::default().to(contract);
+    let debug_api = node.rpc.inner.debug_api();
+    let trace_api = node.rpc.inner.trace_api();
+    let opts = GethDebugTracingCallOptions {
+        tracing_options: GethDebugTracingOptions {
+            tracer: Some(GethDebugTracerType::BuiltInTracer(
+                GethDebugBuiltInTracerType::CallTracer,
+            )),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    // iterating twice serves the second round from the replayed states cached in the first
+    for _ in 0..2 {
+        for (tx_index, expected) in [(0, 0u64), (1, 1), (2, 2)] {
+            let expected = Bytes::from(U256::from(expected).to_be_bytes::<32>());
+
+            let GethTrace::CallTracer(frame) = debug_api
+                .debug_trace_call(call.clone(), Some(2.into()), opts.clone(), Some(tx_index))
+                .await?
+            else {
+                panic!("expected call frame")
+            };
+            assert_eq!(frame.output, Some(expected.clone()), "tx index {tx_index}");
+
+            let request = TraceCallRequest {
+                call: call.clone(),
+                trace_types: [TraceType::Trace].into_iter().collect(),
+                block_id: Some(2.into()),
+                state_overrides: None,
+                block_overrides: None,
+            };
+            let results = trace_api.trace_call(request, Some(tx_index)).await?;
+            assert_eq!(results.output, expected, "tx index {tx_index}");
+        }
+    }
+
+    // the index must be within the block
+    assert!(debug_api.debug_trace_call(call, Some(2.into()), opts, Some(3)).await.is_err());
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_flashbots_validate_v3() -> eyre::Result<()> {
     reth_tracing::init_test_tracing();
@@ -265,3 +486,87 @@ async fn test_flashbots_validate_v4() -> eyre::Result<()> {
         .is_err());
     Ok(())
 }
+
+#[tokio::test]
+async fn test_call_bundle_dependent_transactions() -> eyre::Result<()> {
+    reth_tracing::init_test_tracing();
+
+    let chain_spec = Arc::new(
+        ChainSpecBuilder::default()
+            .chain(MAINNET.chain)
+            .genesis(serde_json::from_str(include_str!("../assets/genesis.json")).unwrap())
+            .cancun_activated()
+            .build(),
+    );
+
+    let (mut nodes, _tasks, wallet) =
+        setup_engine::<EthereumNode>(1, chain_spec.clone(), false, eth_payload_attributes).await?;
+    let node = nodes.pop().unwrap();
+    let provider = ProviderBuilder::new()
+        .with_recommended_fillers()
+        .wallet(EthereumWallet::new(wallet.gen().swap_remove(0)))
+        .on_http(node.rpc_url());
+
+    // the first transaction funds a fresh account that sends the second transaction
+    let recipient = PrivateKeySigner::random();
+    let recipient_provider = ProviderBuilder::new()
+        .with_recommended_fillers()
+        .wallet(EthereumWallet::new(recipient.clone()))
+        .on_http(node.rpc_url());
+
+    let SendableTx::Envelope(tx_1) = provider
+        .fill(
+            TransactionRequest::default()
+                .to(recipient.address())
+                .value(U256::from(1_000_000_000_000_000_000u128)),
+        )
+        .await?
+    else {
+        unreachable!()
+    };
+    // gas and fees are set upfront, because the unfunded account can't estimate them
+    let SendableTx::Envelope(tx_2) = recipient_provider
+        .fill(
+            TransactionRequest::default()
+                .to(Address::ZERO)
+                .value(U256::from(1))
+                .gas_limit(21_000)
+                .max_fee_per_gas(20_000_000_000)
+                .max_priority_fee_per_gas(1_000_000_000),
+        )
+        .await?
+    else {
+        unreachable!()
+    };
+    let tx_1: Bytes = tx_1.encoded_2718().into();
+    let tx_2: Bytes = tx_2.encoded_2718().into();
+
+    let bundle = |txs| EthCallBundle {
+        txs,
+        block_number: 1,
+        state_block_number: BlockNumberOrTag::Latest,
+        ..Default::default()
+    };
+
+    let response: EthCallBundleResponse = provider
+        .raw_request("eth_callBundle".into(), (bundle(vec![tx_1.clone(), tx_2.clone()]),))
+        .await?;
+    assert_eq!(response.results.len(), 2);
+    assert!(response.results.iter().all(|result| result.revert.is_none()));
+    assert_eq!(response.results[1].from_address, recipient.address());
+
+    // without the first transaction the account can't pay for the second
+    assert!(provider
+        .raw_request::<_, EthCallBundleResponse>("eth_callBundle".into(), (bundle(vec![tx_2]),))
+        .await
+        .is_err());
+
+    // bundles aren't accepted into the local pool by default
+    let send_bundle = EthSendBundle { txs: vec![tx_1], block_number: 1, ..Default::default() };
+    assert!(provider
+        .raw_request::<_, serde_json::Value>("eth_sendBundle".into(), (send_bundle,))
+        .await
+        .is_err());
+
+    Ok(())
+}
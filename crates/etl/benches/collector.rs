@@ -0,0 +1,44 @@
+#![allow(missing_docs)]
+use alloy_primitives::{TxHash, TxNumber};
+use criterion::*;
+use reth_etl::Collector;
+
+criterion_group! {
+    name = benches;
+    config = Criterion::default().sample_size(10);
+    targets = collect_and_iterate
+}
+criterion_main!(benches);
+
+/// Number of synthetic entries collected per iteration.
+const ENTRIES: u64 = 50_000_000;
+
+/// Collects transaction hash lookups in random order and iterates over them sorted, with a buffer
+/// that spills to many files and with one that spills to a few.
+fn collect_and_iterate(c: &mut Criterion) {
+    let mut group = c.benchmark_group("ETL Collector");
+
+    for buffer_capacity_mb in [64, 500] {
+        let id = BenchmarkId::new("collect+iterate", format!("{buffer_capacity_mb}MB"));
+        group.bench_function(id, |b| {
+            b.iter(|| {
+                let mut collector =
+                    Collector::<TxHash, TxNumber>::new(buffer_capacity_mb * 1024 * 1024, None);
+                for number in 0..ENTRIES {
+                    collector.insert(synthetic_hash(number), number).unwrap();
+                }
+
+                for entry in collector.iter().unwrap() {
+                    black_box(entry.unwrap());
+                }
+            })
+        });
+    }
+
+    group.finish();
+}
+
+/// Scatters the numbers over the key space, so that every spilled file covers all of it.
+fn synthetic_hash(number: u64) -> TxHash {
+    TxHash::left_padding_from(&number.wrapping_mul(0x9E37_79B9_7F4A_7C15).to_be_bytes())
+}
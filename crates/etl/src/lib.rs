@@ -5,6 +5,27 @@
 //!
 //! This has multiple uses, such as optimizing database inserts (for Btree based databases) and
 //! memory management (as it moves the buffer to disk instead of memory).
+//!
+//! ## Usage
+//!
+//! A [`Collector`] is created with the size of its in-memory buffer and the directory in which to
+//! create temporary files. Entries are inserted in any order, and once the buffer is full it's
+//! sorted and spilled to a temporary file. Iterating over the collector merges all files into a
+//! single sorted stream of encoded keys and compressed values.
+//!
+//! ```
+//! use alloy_primitives::{TxHash, TxNumber};
+//! use reth_etl::Collector;
+//!
+//! let mut collector = Collector::<TxHash, TxNumber>::new(1024, None);
+//! collector.insert(TxHash::with_last_byte(2), 2).unwrap();
+//! collector.insert(TxHash::with_last_byte(1), 1).unwrap();
+//!
+//! let keys = collector.iter().unwrap().map(|entry| entry.unwrap().0).collect::<Vec<_>>();
+//! assert_eq!(keys, vec![TxHash::with_last_byte(1).to_vec(), TxHash::with_last_byte(2).to_vec()]);
+//! ```
+//!
+//! The temporary files are removed when the collector is cleared or dropped.
 
 #![doc(
     html_logo_url = "https://raw.githubusercontent.com/paradigmxyz/reth/main/assets/reth-docs.png",
@@ -21,6 +42,9 @@ use std::{
     path::{Path, PathBuf},
 };
 
+mod metrics;
+use metrics::CollectorMetrics;
+
 /// Key len and Value len encode use [`usize::to_be_bytes()`] the length is 8.
 const KV_LEN: usize = 8;
 
@@ -57,6 +81,10 @@ where
     buffer: Vec<(<K as Encode>::Encoded, <V as Compress>::Compressed)>,
     /// Total number of elements in the collector, including all files
     len: usize,
+    /// Total size in bytes of the temporary ETL files
+    spilled_bytes: u64,
+    /// Collector metrics, created on the first spill unless labeled
+    metrics: Option<CollectorMetrics>,
 }
 
 impl<K, V> Collector<K, V>
@@ -66,7 +94,9 @@ where
 {
     /// Create a new collector with some capacity.
     ///
-    /// Once the capacity (in bytes) is reached, the data is sorted and flushed to disk.
+    /// Once the capacity (in bytes) is reached, the data is sorted and flushed to disk. The
+    /// temporary files are created in a new directory inside `parent_dir`, or inside the system
+    /// temporary directory if it's `None`.
     pub const fn new(buffer_capacity_bytes: usize, parent_dir: Option<PathBuf>) -> Self {
         Self {
             parent_dir,
@@ -76,9 +106,18 @@ where
             buffer_capacity_bytes,
             buffer: Vec::new(),
             len: 0,
+            spilled_bytes: 0,
+            metrics: None,
         }
     }
 
+    /// Reports the metrics of the collector with the given `collector` label, instead of
+    /// aggregating them with the unlabeled collectors.
+    pub fn with_metrics_label(mut self, label: &'static str) -> Self {
+        self.metrics = Some(CollectorMetrics::new_with_labels(&[("collector", label)]));
+        self
+    }
+
     /// Returns number of elements currently in the collector.
     pub const fn len(&self) -> usize {
         self.len
@@ -89,6 +128,16 @@ where
         self.len == 0
     }
 
+    /// Returns the number of temporary files the collector has spilled to disk.
+    pub fn spilled_files(&self) -> usize {
+        self.files.len()
+    }
+
+    /// Returns the total size in bytes of the temporary files the collector has spilled to disk.
+    pub const fn spilled_bytes(&self) -> u64 {
+        self.spilled_bytes
+    }
+
     /// Clears the collector, removing all data, including the temporary directory.
     pub fn clear(&mut self) {
         self.dir = None;
@@ -97,6 +146,7 @@ where
         self.buffer = Vec::new();
         self.buffer_size_bytes = 0;
         self.len = 0;
+        self.spilled_bytes = 0;
     }
 
     /// Insert an entry into the collector.
@@ -130,14 +180,25 @@ where
         Ok(self.dir.as_ref().unwrap())
     }
 
-    fn flush(&mut self) -> io::Result<()> {
+    /// Sorts the in-memory buffer and spills it to a temporary file.
+    ///
+    /// This is done automatically once the buffer capacity is reached, and before iterating.
+    pub fn flush(&mut self) -> io::Result<()> {
+        if self.buffer.is_empty() {
+            return Ok(())
+        }
+
         self.buffer_size_bytes = 0;
         self.buffer.par_sort_unstable_by(|a, b| a.0.cmp(&b.0));
-        let mut buf = Vec::with_capacity(self.buffer.len());
-        std::mem::swap(&mut buf, &mut self.buffer);
+        let buffer = std::mem::replace(&mut self.buffer, Vec::with_capacity(self.buffer.len()));
 
         let path = self.dir()?.path().to_path_buf();
-        self.files.push(EtlFile::new(path.as_path(), buf)?);
+        let file = EtlFile::new(path.as_path(), buffer)?;
+        self.spilled_bytes += file.size;
+        let metrics = self.metrics.get_or_insert_with(CollectorMetrics::default);
+        metrics.spilled_files.increment(1);
+        metrics.spilled_bytes.increment(file.size);
+        self.files.push(file);
 
         Ok(())
     }
@@ -152,8 +213,9 @@ where
     /// compressed again.
     pub fn iter(&mut self) -> std::io::Result<EtlIter<'_>> {
         // Flush the remaining items to disk
-        if self.buffer_size_bytes > 0 {
-            self.flush()?;
+        self.flush()?;
+        if let Some(metrics) = self.metrics.as_ref().filter(|_| !self.files.is_empty()) {
+            metrics.merge_passes.increment(1);
         }
 
         let mut heap = BinaryHeap::new();
@@ -221,6 +283,7 @@ impl Iterator for EtlIter<'_> {
 struct EtlFile {
     file: BufReader<NamedTempFile>,
     len: usize,
+    size: u64,
 }
 
 impl EtlFile {
@@ -234,6 +297,14 @@ impl EtlFile {
         V: AsRef<[u8]>,
     {
         let file = NamedTempFile::new_in(dir)?;
+
+        // The size of the file is known upfront, so its disk space is reserved at once
+        let size = buffer
+            .iter()
+            .map(|(k, v)| (2 * KV_LEN + k.as_ref().len() + v.as_ref().len()) as u64)
+            .sum();
+        reth_fs_util::preallocate(file.as_file(), 0, size)?;
+
         let mut w = BufWriter::new(file);
         for entry in &buffer {
             let k = entry.0.as_ref();
@@ -248,7 +319,7 @@ impl EtlFile {
         let mut file = BufReader::new(w.into_inner()?);
         file.seek(SeekFrom::Start(0))?;
         let len = buffer.len();
-        Ok(Self { file, len })
+        Ok(Self { file, len, size })
     }
 
     /// Read the next entry in the file.
@@ -316,4 +387,55 @@ mod tests {
         assert!(collector.is_empty());
         assert!(!temp_dir_path.exists());
     }
+
+    #[test]
+    fn etl_explicit_flush() {
+        let mut entries: Vec<_> = (0..1_000).map(|id| (TxHash::random(), id as TxNumber)).collect();
+
+        let mut collector = Collector::new(usize::MAX, None);
+        for (k, v) in entries.clone() {
+            collector.insert(k, v).unwrap();
+        }
+        assert_eq!(collector.spilled_files(), 0);
+
+        collector.flush().unwrap();
+        collector.flush().unwrap();
+        assert_eq!(collector.spilled_files(), 1);
+        let size = entries
+            .iter()
+            .map(|(k, v)| (2 * KV_LEN + k.encode().len() + v.compress().len()) as u64)
+            .sum::<u64>();
+        assert_eq!(collector.spilled_bytes(), size);
+        assert_eq!(collector.len(), entries.len());
+
+        entries.sort_unstable_by_key(|entry| entry.0);
+        for (id, entry) in collector.iter().unwrap().enumerate() {
+            let expected = entries[id];
+            assert_eq!(
+                entry.unwrap(),
+                (expected.0.encode().to_vec(), expected.1.compress().clone())
+            );
+        }
+    }
+
+    #[test]
+    fn etl_files_removed_on_panic() {
+        let (tx, rx) = std::sync::mpsc::channel();
+
+        let result = std::thread::spawn(move || {
+            let mut collector = Collector::new(1024, None);
+            for id in 0..1_000 {
+                collector.insert(TxHash::random(), id as TxNumber).unwrap();
+            }
+            assert!(collector.spilled_files() > 1);
+
+            tx.send(collector.dir.as_ref().unwrap().path().to_path_buf()).unwrap();
+            panic!("collector is dropped while unwinding");
+        })
+        .join();
+        assert!(result.is_err());
+
+        let temp_dir_path = rx.recv().unwrap();
+        assert!(!temp_dir_path.exists());
+    }
 }
@@ -0,0 +1,14 @@
+use metrics::Counter;
+use reth_metrics::Metrics;
+
+/// Metrics of a [`Collector`](crate::Collector).
+#[derive(Metrics)]
+#[metrics(scope = "etl.collector")]
+pub(crate) struct CollectorMetrics {
+    /// Number of files spilled to disk
+    pub(crate) spilled_files: Counter,
+    /// Number of bytes spilled to disk
+    pub(crate) spilled_bytes: Counter,
+    /// Number of merges of the spilled files into a sorted iterator
+    pub(crate) merge_passes: Counter,
+}
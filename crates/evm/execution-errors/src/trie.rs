@@ -187,6 +187,9 @@ pub enum TrieWitnessError {
     /// Missing account.
     #[error("missing account {_0}")]
     MissingAccount(B256),
+    /// Trie node is missing from the witness.
+    #[error("missing witness node {_0}")]
+    MissingNode(B256),
 }
 
 impl From<SparseStateTrieErrorKind> for TrieWitnessError {
@@ -19,31 +19,45 @@ pub fn post_block_balance_increments<ChainSpec, Block>(
 where
     ChainSpec: EthereumHardforks,
     Block: reth_primitives_traits::Block,
+{
+    header_post_block_balance_increments(chain_spec, block.header(), block.body())
+}
+
+/// Same as [`post_block_balance_increments`], but for the header and body of a block, e.g. of a
+/// sealed block.
+#[inline]
+pub fn header_post_block_balance_increments<ChainSpec, Header, Body>(
+    chain_spec: &ChainSpec,
+    header: &Header,
+    body: &Body,
+) -> HashMap<Address, u128>
+where
+    ChainSpec: EthereumHardforks,
+    Header: BlockHeader,
+    Body: BlockBody,
 {
     let mut balance_increments = HashMap::default();
 
     // Add block rewards if they are enabled.
-    if let Some(base_block_reward) = calc::base_block_reward(chain_spec, block.header().number()) {
+    if let Some(base_block_reward) = calc::base_block_reward(chain_spec, header.number()) {
         // Ommer rewards
-        if let Some(ommers) = block.body().ommers() {
+        if let Some(ommers) = body.ommers() {
             for ommer in ommers {
                 *balance_increments.entry(ommer.beneficiary()).or_default() +=
-                    calc::ommer_reward(base_block_reward, block.header().number(), ommer.number());
+                    calc::ommer_reward(base_block_reward, header.number(), ommer.number());
             }
         }
 
         // Full block reward
-        *balance_increments.entry(block.header().beneficiary()).or_default() += calc::block_reward(
-            base_block_reward,
-            block.body().ommers().map(|s| s.len()).unwrap_or(0),
-        );
+        *balance_increments.entry(header.beneficiary()).or_default() +=
+            calc::block_reward(base_block_reward, body.ommers().map(|s| s.len()).unwrap_or(0));
     }
 
     // process withdrawals
     insert_post_block_withdrawals_balance_increments(
         chain_spec,
-        block.header().timestamp(),
-        block.body().withdrawals().as_ref().map(|w| w.as_slice()),
+        header.timestamp(),
+        body.withdrawals().as_ref().map(|w| w.as_slice()),
         &mut balance_increments,
     );
 
@@ -0,0 +1,236 @@
+//! Reserving disk space ahead of appends, and syncing appended data to disk.
+
+use std::{fs::File, io};
+
+/// Reserves `len` bytes of disk space for the file, starting at `offset`, without changing the
+/// length of the file.
+///
+/// Returns `false` if nothing was reserved, because the platform or filesystem doesn't support it
+/// or doesn't benefit from it:
+/// - On Linux, space is reserved with `fallocate(FALLOC_FL_KEEP_SIZE)`, except on ZFS, which is
+///   copy-on-write and never writes into reserved space in place.
+/// - On macOS, space is reserved with `fcntl(F_PREALLOCATE)`, which reserves past the physical end
+///   of the file, so `offset` should be the end of the previously reserved space.
+/// - Elsewhere, nothing is reserved.
+///
+/// Since the length of the file is kept, the reserved space is not visible to readers, and is
+/// released when the file is truncated.
+pub fn preallocate(file: &File, offset: u64, len: u64) -> io::Result<bool> {
+    if len == 0 {
+        return Ok(false)
+    }
+    imp::preallocate(file, offset, len)
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+mod imp {
+    use std::{fs::File, io, os::fd::AsRawFd};
+
+    /// `f_type` of ZFS filesystems reported by `fstatfs`.
+    const ZFS_SUPER_MAGIC: i64 = 0x2fc12fc1;
+
+    pub(super) fn preallocate(file: &File, offset: u64, len: u64) -> io::Result<bool> {
+        let fd = file.as_raw_fd();
+
+        let mut stat = std::mem::MaybeUninit::<libc::statfs>::uninit();
+        // SAFETY: `fd` is a valid file descriptor and `stat` is a valid pointer to a `statfs`.
+        if unsafe { libc::fstatfs(fd, stat.as_mut_ptr()) } == 0 {
+            // SAFETY: `fstatfs` succeeded, so `stat` is initialized.
+            let stat = unsafe { stat.assume_init() };
+            // the type of `f_type` differs between targets
+            #[allow(clippy::unnecessary_cast)]
+            let f_type = stat.f_type as i64;
+            if f_type == ZFS_SUPER_MAGIC {
+                return Ok(false)
+            }
+        }
+
+        // SAFETY: `fd` is a valid file descriptor.
+        let res = unsafe {
+            libc::fallocate(
+                fd,
+                libc::FALLOC_FL_KEEP_SIZE,
+                offset as libc::off_t,
+                len as libc::off_t,
+            )
+        };
+        if res == 0 {
+            return Ok(true)
+        }
+
+        let err = io::Error::last_os_error();
+        match err.raw_os_error() {
+            Some(libc::EOPNOTSUPP | libc::ENOSYS) => Ok(false),
+            _ => Err(err),
+        }
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod imp {
+    use std::{fs::File, io, os::fd::AsRawFd};
+
+    pub(super) fn preallocate(file: &File, _offset: u64, len: u64) -> io::Result<bool> {
+        let fd = file.as_raw_fd();
+
+        // Contiguous space is preferred, but any space is taken if there's not enough of it.
+        for flags in [libc::F_ALLOCATECONTIG | libc::F_ALLOCATEALL, libc::F_ALLOCATEALL] {
+            let mut store = libc::fstore_t {
+                fst_flags: flags,
+                fst_posmode: libc::F_PEOFPOSMODE,
+                fst_offset: 0,
+                fst_length: len as libc::off_t,
+                fst_bytesalloc: 0,
+            };
+            // SAFETY: `fd` is a valid file descriptor and `store` is a valid `fstore_t`.
+            if unsafe { libc::fcntl(fd, libc::F_PREALLOCATE, &mut store) } != -1 {
+                return Ok(true)
+            }
+        }
+
+        let err = io::Error::last_os_error();
+        match err.raw_os_error() {
+            Some(libc::ENOTSUP | libc::ENOSPC) => Ok(false),
+            _ => Err(err),
+        }
+    }
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "android", target_os = "macos")))]
+mod imp {
+    use std::{fs::File, io};
+
+    pub(super) fn preallocate(_file: &File, _offset: u64, _len: u64) -> io::Result<bool> {
+        Ok(false)
+    }
+}
+
+/// Reserves disk space of a file that's appended to, a chunk at a time ahead of the appends.
+///
+/// Stops reserving once the platform or filesystem doesn't support it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Preallocator {
+    /// Number of bytes reserved at a time. Disabled if `0`.
+    chunk_size: u64,
+    /// End of the reserved space.
+    reserved_end: u64,
+    /// Number of times space was reserved.
+    calls: u64,
+}
+
+impl Preallocator {
+    /// Creates a new [`Preallocator`] reserving `chunk_size` bytes at a time. Disabled if
+    /// `chunk_size` is `0`.
+    pub const fn new(chunk_size: u64) -> Self {
+        Self { chunk_size, reserved_end: 0, calls: 0 }
+    }
+
+    /// Returns `true` if space is reserved ahead of appends.
+    pub const fn is_enabled(&self) -> bool {
+        self.chunk_size > 0
+    }
+
+    /// Returns the end of the reserved space.
+    pub const fn reserved_end(&self) -> u64 {
+        self.reserved_end
+    }
+
+    /// Returns the number of times space was reserved, and resets it.
+    pub fn take_calls(&mut self) -> u64 {
+        std::mem::take(&mut self.calls)
+    }
+
+    /// Makes sure space is reserved for appending `len` bytes at `offset`, reserving up to the
+    /// next multiple of the chunk size past them if it's not.
+    pub fn reserve(&mut self, file: &File, offset: u64, len: u64) -> io::Result<()> {
+        let end = offset + len;
+        if !self.is_enabled() || end <= self.reserved_end {
+            return Ok(())
+        }
+
+        let start = self.reserved_end.max(offset);
+        let reserved_end = end.next_multiple_of(self.chunk_size);
+        if preallocate(file, start, reserved_end - start)? {
+            self.reserved_end = reserved_end;
+            self.calls += 1;
+        } else {
+            self.chunk_size = 0;
+        }
+
+        Ok(())
+    }
+
+    /// Forgets the reserved space, after the file was truncated or replaced.
+    pub fn reset(&mut self) {
+        self.reserved_end = 0;
+    }
+}
+
+/// When data appended to a file is synced to disk, on top of the syncs that make it durable.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FsyncPolicy {
+    /// Appended data is only synced when it's committed.
+    #[default]
+    Commit,
+    /// Appended data is synced every time this many bytes were appended since the last sync, so
+    /// that a commit doesn't have to write out all of it at once.
+    Bytes(u64),
+}
+
+impl FsyncPolicy {
+    /// Returns `true` if data should be synced with `unsynced` bytes appended since the last sync.
+    pub const fn should_sync(&self, unsynced: u64) -> bool {
+        match self {
+            Self::Commit => false,
+            Self::Bytes(bytes) => unsynced >= *bytes,
+        }
+    }
+}
+
+impl std::fmt::Display for FsyncPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Commit => f.write_str("commit"),
+            Self::Bytes(bytes) => write!(f, "{bytes}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    #[test]
+    fn reserve_keeps_file_length() {
+        let mut file = tempfile::tempfile().unwrap();
+        let mut preallocator = Preallocator::new(4096);
+
+        preallocator.reserve(&file, 0, 100).unwrap();
+        file.write_all(&[1; 100]).unwrap();
+        assert_eq!(file.metadata().unwrap().len(), 100);
+
+        // already reserved
+        let calls = preallocator.take_calls();
+        preallocator.reserve(&file, 100, 1000).unwrap();
+        assert_eq!(preallocator.take_calls(), 0);
+
+        if preallocator.is_enabled() {
+            assert_eq!(calls, 1);
+            assert_eq!(preallocator.reserved_end(), 4096);
+
+            // the next chunk past the appended bytes
+            preallocator.reserve(&file, 4000, 200).unwrap();
+            assert_eq!(preallocator.take_calls(), 1);
+            assert_eq!(preallocator.reserved_end(), 8192);
+        }
+        assert_eq!(file.metadata().unwrap().len(), 100);
+    }
+
+    #[test]
+    fn fsync_policy() {
+        assert!(!FsyncPolicy::Commit.should_sync(u64::MAX));
+        assert!(!FsyncPolicy::Bytes(100).should_sync(99));
+        assert!(FsyncPolicy::Bytes(100).should_sync(100));
+    }
+}
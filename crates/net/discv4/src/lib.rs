@@ -87,6 +87,7 @@ use crate::table::PongTable;
 use reth_net_nat::ResolveNatInterval;
 /// reexport to get public ip.
 pub use reth_net_nat::{external_ip, NatResolver};
+use reth_tasks::shutdown::Shutdown;
 
 /// The default address for discv4 via UDP
 ///
@@ -613,6 +614,15 @@ impl Discv4Service {
         self.lookup_interval = tokio::time::interval(duration);
     }
 
+    /// Sets the shutdown signal of the task executor, after which failed resolutions of the
+    /// external IP are no longer retried.
+    pub fn set_shutdown(&mut self, shutdown: Shutdown) {
+        self.resolve_external_ip_interval = self
+            .resolve_external_ip_interval
+            .take()
+            .map(|interval| interval.with_shutdown(shutdown));
+    }
+
     /// Sets the given ip address as the node's external IP in the node record announced in
     /// discovery
     pub fn set_external_ip_addr(&mut self, external_ip: IpAddr) {
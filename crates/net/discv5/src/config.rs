@@ -74,7 +74,7 @@ pub struct ConfigBuilder {
     tcp_socket: SocketAddr,
     /// List of `(key, rlp-encoded-value)` tuples that should be advertised in local node record
     /// (in addition to tcp port, udp port and fork).
-    other_enr_kv_pairs: Vec<(&'static [u8], Bytes)>,
+    other_enr_kv_pairs: Vec<(Vec<u8>, Bytes)>,
     /// Interval in seconds at which to run a lookup up query to populate kbuckets.
     lookup_interval: Option<u64>,
     /// Interval in seconds at which to run pulse lookup queries at bootstrap to boost kbucket
@@ -179,8 +179,8 @@ impl ConfigBuilder {
 
     /// Adds an additional kv-pair to include in the local [`Enr`](discv5::enr::Enr). Takes the key
     /// to use for the kv-pair and the rlp encoded value.
-    pub fn add_enr_kv_pair(mut self, key: &'static [u8], value: Bytes) -> Self {
-        self.other_enr_kv_pairs.push((key, value));
+    pub fn add_enr_kv_pair(mut self, key: impl Into<Vec<u8>>, value: Bytes) -> Self {
+        self.other_enr_kv_pairs.push((key.into(), value));
         self
     }
 
@@ -278,7 +278,7 @@ pub struct Config {
     pub(super) tcp_socket: SocketAddr,
     /// Additional kv-pairs (besides tcp port, udp port and fork) that should be advertised to
     /// peers by including in local node record.
-    pub(super) other_enr_kv_pairs: Vec<(&'static [u8], Bytes)>,
+    pub(super) other_enr_kv_pairs: Vec<(Vec<u8>, Bytes)>,
     /// Interval in seconds at which to run a lookup up query with to populate kbuckets.
     pub(super) lookup_interval: u64,
     /// Interval in seconds at which to run pulse lookup queries at bootstrap to boost kbucket
@@ -1,4 +1,5 @@
 use crate::tree::LinkEntry;
+use reth_tokio_util::retry::RetryPolicy;
 use std::{
     collections::HashSet,
     num::{NonZeroU32, NonZeroUsize},
@@ -16,6 +17,10 @@ pub struct DnsDiscoveryConfig {
     ///
     /// Default: 5s
     pub lookup_timeout: Duration,
+    /// Maximum number of attempts of a DNS lookup, lookups that time out or fail are retried.
+    ///
+    /// Default: 3
+    pub max_lookup_attempts: NonZeroU32,
     /// The DNS request rate limit
     ///
     /// Default: 3
@@ -30,10 +35,18 @@ pub struct DnsDiscoveryConfig {
     pub bootstrap_dns_networks: Option<HashSet<LinkEntry>>,
 }
 
+impl DnsDiscoveryConfig {
+    /// Returns the [`RetryPolicy`] of DNS lookups.
+    pub const fn lookup_retry_policy(&self) -> RetryPolicy {
+        RetryPolicy::new(self.max_lookup_attempts.get()).with_attempt_timeout(self.lookup_timeout)
+    }
+}
+
 impl Default for DnsDiscoveryConfig {
     fn default() -> Self {
         Self {
             lookup_timeout: Duration::from_secs(5),
+            max_lookup_attempts: NonZeroU32::new(3).unwrap(),
             max_requests_per_sec: NonZeroUsize::new(3).unwrap(),
             recheck_interval: Duration::from_secs(60 * 30),
             dns_record_cache_limit: NonZeroU32::new(1_000).unwrap(),
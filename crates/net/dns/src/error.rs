@@ -1,4 +1,5 @@
 use crate::tree::TreeRootEntry;
+use hickory_resolver::ResolveError;
 
 /// Alias for a parse result
 pub(crate) type ParseEntryResult<T> = Result<T, ParseDnsEntryError>;
@@ -54,6 +55,14 @@ pub(crate) enum LookupError {
     #[error("request timed out")]
     /// Indicates a timeout occurred during the request.
     RequestTimedOut,
+    /// Request failed error.
+    #[error("request failed: {0}")]
+    /// Indicates the resolver failed to perform the request.
+    RequestFailed(ResolveError),
+    /// Request cancelled error.
+    #[error("request cancelled")]
+    /// Indicates the request was cancelled at shutdown.
+    RequestCancelled,
     /// Entry not found error.
     #[error("entry not found")]
     /// Indicates the requested entry was not found.
@@ -24,6 +24,7 @@ use enr::Enr;
 pub use error::ParseDnsEntryError;
 use reth_ethereum_forks::{EnrForkIdEntry, ForkId};
 use reth_network_peers::{pk2id, NodeRecord};
+use reth_tasks::shutdown::Shutdown;
 use schnellru::{ByLength, LruMap};
 use secp256k1::SecretKey;
 use std::{
@@ -51,6 +52,7 @@ use tracing::{debug, trace};
 
 mod config;
 mod error;
+mod metrics;
 mod query;
 pub mod resolver;
 mod sync;
@@ -128,14 +130,16 @@ impl<R: Resolver> DnsDiscoveryService<R> {
     /// # }
     /// ```
     pub fn new(resolver: Arc<R>, config: DnsDiscoveryConfig) -> Self {
+        let lookup_retry_policy = config.lookup_retry_policy();
         let DnsDiscoveryConfig {
-            lookup_timeout,
+            lookup_timeout: _,
+            max_lookup_attempts: _,
             max_requests_per_sec,
             recheck_interval,
             dns_record_cache_limit,
             bootstrap_dns_networks,
         } = config;
-        let queries = QueryPool::new(resolver, max_requests_per_sec, lookup_timeout);
+        let queries = QueryPool::new(resolver, max_requests_per_sec, lookup_retry_policy);
         let (command_tx, command_rx) = mpsc::unbounded_channel();
         Self {
             command_tx,
@@ -150,6 +154,13 @@ impl<R: Resolver> DnsDiscoveryService<R> {
         }
     }
 
+    /// Sets the shutdown signal of the task executor, after which failed lookups are no longer
+    /// retried.
+    pub fn with_shutdown(mut self, shutdown: Shutdown) -> Self {
+        self.queries.set_shutdown(shutdown);
+        self
+    }
+
     /// Spawns this services onto a new task
     ///
     /// Note: requires a running runtime
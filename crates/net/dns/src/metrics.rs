@@ -0,0 +1,14 @@
+//! Metrics of DNS discovery.
+
+use metrics::Counter;
+use reth_metrics::Metrics;
+
+/// Metrics of the DNS lookups of the tree sync.
+#[derive(Metrics, Clone)]
+#[metrics(scope = "discovery.dns")]
+pub(crate) struct DnsLookupMetrics {
+    /// Number of DNS lookup attempts.
+    pub(crate) lookup_attempts: Counter,
+    /// Number of DNS lookups that timed out or failed on all attempts.
+    pub(crate) lookup_failures: Counter,
+}
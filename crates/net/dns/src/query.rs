@@ -2,12 +2,17 @@
 
 use crate::{
     error::{LookupError, LookupResult},
+    metrics::DnsLookupMetrics,
     resolver::Resolver,
     sync::ResolveKind,
     tree::{DnsEntry, LinkEntry, TreeRootEntry},
 };
 use enr::EnrKeyUnambiguous;
-use reth_tokio_util::ratelimit::{Rate, RateLimit};
+use reth_tasks::shutdown::Shutdown;
+use reth_tokio_util::{
+    ratelimit::{Rate, RateLimit},
+    retry::{AttemptError, RetryError, RetryPolicy},
+};
 use std::{
     collections::VecDeque,
     future::Future,
@@ -30,8 +35,12 @@ pub(crate) struct QueryPool<R: Resolver, K: EnrKeyUnambiguous> {
     queued_outcomes: VecDeque<QueryOutcome<K>>,
     /// Rate limit for DNS requests
     rate_limit: RateLimit,
-    /// Timeout for DNS lookups.
-    lookup_timeout: Duration,
+    /// How DNS lookups are retried, including their timeout.
+    lookup_retry_policy: RetryPolicy,
+    /// Metrics of DNS lookups.
+    metrics: DnsLookupMetrics,
+    /// Stops retrying lookups once it fires.
+    shutdown: Option<Shutdown>,
 }
 
 // === impl QueryPool ===
@@ -40,7 +49,7 @@ impl<R: Resolver, K: EnrKeyUnambiguous> QueryPool<R, K> {
     pub(crate) fn new(
         resolver: Arc<R>,
         max_requests_per_sec: NonZeroUsize,
-        lookup_timeout: Duration,
+        lookup_retry_policy: RetryPolicy,
     ) -> Self {
         Self {
             resolver,
@@ -51,23 +60,28 @@ impl<R: Resolver, K: EnrKeyUnambiguous> QueryPool<R, K> {
                 max_requests_per_sec.get() as u64,
                 Duration::from_secs(1),
             )),
-            lookup_timeout,
+            lookup_retry_policy,
+            metrics: DnsLookupMetrics::default(),
+            shutdown: None,
         }
     }
 
+    /// Sets the shutdown signal, after which failed lookups are no longer retried.
+    pub(crate) fn set_shutdown(&mut self, shutdown: Shutdown) {
+        self.shutdown = Some(shutdown);
+    }
+
     /// Resolves the root the link's domain references
     pub(crate) fn resolve_root(&mut self, link: LinkEntry<K>) {
-        let resolver = Arc::clone(&self.resolver);
-        let timeout = self.lookup_timeout;
-        self.queued_queries.push_back(Query::Root(Box::pin(resolve_root(resolver, link, timeout))))
+        let lookup = Lookup::new(self);
+        self.queued_queries.push_back(Query::Root(Box::pin(resolve_root(lookup, link))))
     }
 
     /// Resolves the [`DnsEntry`] for `<hash.domain>`
     pub(crate) fn resolve_entry(&mut self, link: LinkEntry<K>, hash: String, kind: ResolveKind) {
-        let resolver = Arc::clone(&self.resolver);
-        let timeout = self.lookup_timeout;
+        let lookup = Lookup::new(self);
         self.queued_queries
-            .push_back(Query::Entry(Box::pin(resolve_entry(resolver, link, hash, kind, timeout))))
+            .push_back(Query::Entry(Box::pin(resolve_entry(lookup, link, hash, kind))))
     }
 
     /// Advances the state of the queries
@@ -155,15 +169,14 @@ pub(crate) enum QueryOutcome<K: EnrKeyUnambiguous> {
 
 /// Retrieves the [`DnsEntry`]
 async fn resolve_entry<K: EnrKeyUnambiguous, R: Resolver>(
-    resolver: Arc<R>,
+    lookup: Lookup<R>,
     link: LinkEntry<K>,
     hash: String,
     kind: ResolveKind,
-    timeout: Duration,
 ) -> ResolveEntryResult<K> {
     let fqn = format!("{hash}.{}", link.domain);
     let mut resp = ResolveEntryResult { entry: None, link, hash, kind };
-    match lookup_with_timeout::<R>(&resolver, &fqn, timeout).await {
+    match lookup.lookup_txt(&fqn).await {
         Ok(Some(entry)) => {
             resp.entry = Some(entry.parse::<DnsEntry<K>>().map_err(|err| err.into()))
         }
@@ -178,11 +191,10 @@ async fn resolve_entry<K: EnrKeyUnambiguous, R: Resolver>(
 /// Returns an error if the record could be retrieved but is not a root entry or failed to be
 /// verified.
 async fn resolve_root<K: EnrKeyUnambiguous, R: Resolver>(
-    resolver: Arc<R>,
+    lookup: Lookup<R>,
     link: LinkEntry<K>,
-    timeout: Duration,
 ) -> ResolveRootResult<K> {
-    let root = match lookup_with_timeout::<R>(&resolver, &link.domain, timeout).await {
+    let root = match lookup.lookup_txt(&link.domain).await {
         Ok(Some(root)) => root,
         Ok(_) => return Err((LookupError::EntryNotFound, link)),
         Err(err) => return Err((err, link)),
@@ -200,27 +212,67 @@ async fn resolve_root<K: EnrKeyUnambiguous, R: Resolver>(
     }
 }
 
-async fn lookup_with_timeout<R: Resolver>(
-    r: &R,
-    query: &str,
-    timeout: Duration,
-) -> LookupResult<Option<String>> {
-    tokio::time::timeout(timeout, r.lookup_txt(query))
-        .await
-        .map_err(|_| LookupError::RequestTimedOut)
+/// A DNS lookup with the [`RetryPolicy`] of the [`QueryPool`].
+struct Lookup<R> {
+    resolver: Arc<R>,
+    retry_policy: RetryPolicy,
+    metrics: DnsLookupMetrics,
+    shutdown: Option<Shutdown>,
+}
+
+impl<R: Resolver> Lookup<R> {
+    fn new<K: EnrKeyUnambiguous>(pool: &QueryPool<R, K>) -> Self {
+        Self {
+            resolver: Arc::clone(&pool.resolver),
+            retry_policy: pool.lookup_retry_policy,
+            metrics: pool.metrics.clone(),
+            shutdown: pool.shutdown.clone(),
+        }
+    }
+
+    /// Looks up the text record, retrying lookups that time out or fail until the shutdown signal
+    /// fires.
+    async fn lookup_txt(&self, query: &str) -> LookupResult<Option<String>> {
+        let shutdown = async {
+            match self.shutdown.clone() {
+                Some(shutdown) => shutdown.await,
+                None => std::future::pending().await,
+            }
+        };
+        self.retry_policy
+            .retry_until(shutdown, |_| {
+                self.metrics.lookup_attempts.increment(1);
+                self.resolver.try_lookup_txt(query)
+            })
+            .await
+            .map_err(|err| match err {
+                RetryError::Exhausted { error, .. } => {
+                    self.metrics.lookup_failures.increment(1);
+                    match error {
+                        AttemptError::Failed(err) => LookupError::RequestFailed(err),
+                        AttemptError::TimedOut(_) => LookupError::RequestTimedOut,
+                    }
+                }
+                RetryError::Cancelled => LookupError::RequestCancelled,
+            })
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::{resolver::TimeoutResolver, DnsDiscoveryConfig, MapResolver};
-    use std::future::poll_fn;
+    use std::{
+        future::poll_fn,
+        sync::atomic::{AtomicUsize, Ordering},
+    };
 
     #[tokio::test]
     async fn test_rate_limit() {
         let resolver = Arc::new(MapResolver::default());
         let config = DnsDiscoveryConfig::default();
-        let mut pool = QueryPool::new(resolver, config.max_requests_per_sec, config.lookup_timeout);
+        let mut pool =
+            QueryPool::new(resolver, config.max_requests_per_sec, config.lookup_retry_policy());
 
         let s = "enrtree://AM5FCQLWIZX2QFPNJAP7VUERCCRNGRHWZG3YYHIUV7BVDQ5FDPRT2@nodes.example.org";
         let entry: LinkEntry = s.parse().unwrap();
@@ -253,7 +305,8 @@ mod tests {
         let config =
             DnsDiscoveryConfig { lookup_timeout: Duration::from_millis(500), ..Default::default() };
         let resolver = Arc::new(TimeoutResolver(config.lookup_timeout * 2));
-        let mut pool = QueryPool::new(resolver, config.max_requests_per_sec, config.lookup_timeout);
+        let mut pool =
+            QueryPool::new(resolver, config.max_requests_per_sec, config.lookup_retry_policy());
 
         let s = "enrtree://AM5FCQLWIZX2QFPNJAP7VUERCCRNGRHWZG3YYHIUV7BVDQ5FDPRT2@nodes.example.org";
         let entry: LinkEntry = s.parse().unwrap();
@@ -274,4 +327,81 @@ mod tests {
             }
         }
     }
+
+    #[tokio::test]
+    async fn test_retry_timed_out_lookup() {
+        let config =
+            DnsDiscoveryConfig { lookup_timeout: Duration::from_millis(100), ..Default::default() };
+        let resolver = Arc::new(FlakyResolver::default());
+        let pool = QueryPool::<_, secp256k1::SecretKey>::new(
+            resolver.clone(),
+            config.max_requests_per_sec,
+            config.lookup_retry_policy(),
+        );
+
+        let lookup = Lookup::new(&pool);
+        assert_eq!(lookup.lookup_txt("nodes.example.org").await.unwrap().as_deref(), Some("entry"));
+        assert_eq!(resolver.lookups.load(Ordering::Relaxed), 2);
+    }
+
+    #[tokio::test]
+    async fn test_retry_failed_lookup() {
+        let config = DnsDiscoveryConfig::default();
+        let resolver = Arc::new(FlakyResolver { fail: true, ..Default::default() });
+        let pool = QueryPool::<_, secp256k1::SecretKey>::new(
+            resolver.clone(),
+            config.max_requests_per_sec,
+            config.lookup_retry_policy(),
+        );
+
+        let lookup = Lookup::new(&pool);
+        assert_eq!(lookup.lookup_txt("nodes.example.org").await.unwrap().as_deref(), Some("entry"));
+        assert_eq!(resolver.lookups.load(Ordering::Relaxed), 2);
+    }
+
+    #[tokio::test]
+    async fn test_cancel_lookup_at_shutdown() {
+        let config = DnsDiscoveryConfig::default();
+        let resolver = Arc::new(FlakyResolver::default());
+        let mut pool = QueryPool::<_, secp256k1::SecretKey>::new(
+            resolver.clone(),
+            config.max_requests_per_sec,
+            config.lookup_retry_policy(),
+        );
+        let (signal, shutdown) = reth_tasks::shutdown::signal();
+        pool.set_shutdown(shutdown);
+        signal.fire();
+
+        let lookup = Lookup::new(&pool);
+        assert!(matches!(
+            lookup.lookup_txt("nodes.example.org").await,
+            Err(LookupError::RequestCancelled)
+        ));
+    }
+
+    /// A [Resolver] that times out, or fails if `fail` is set, on the first lookup.
+    #[derive(Default)]
+    struct FlakyResolver {
+        lookups: AtomicUsize,
+        fail: bool,
+    }
+
+    impl Resolver for FlakyResolver {
+        async fn lookup_txt(&self, query: &str) -> Option<String> {
+            self.try_lookup_txt(query).await.ok().flatten()
+        }
+
+        async fn try_lookup_txt(
+            &self,
+            _query: &str,
+        ) -> Result<Option<String>, crate::resolver::ResolveError> {
+            if self.lookups.fetch_add(1, Ordering::Relaxed) == 0 {
+                if self.fail {
+                    return Err("connection refused".into())
+                }
+                std::future::pending::<()>().await;
+            }
+            Ok(Some("entry".to_string()))
+        }
+    }
 }
@@ -10,23 +10,39 @@ use tracing::trace;
 pub trait Resolver: Send + Sync + Unpin + 'static {
     /// Performs a textual lookup and returns the first text
     fn lookup_txt(&self, query: &str) -> impl Future<Output = Option<String>> + Send;
+
+    /// Performs a textual lookup and returns the first text, or the error if the lookup failed.
+    ///
+    /// A lookup that finds no record isn't an error. By default, lookups never fail, so failures
+    /// can't be told apart from missing records.
+    fn try_lookup_txt(
+        &self,
+        query: &str,
+    ) -> impl Future<Output = Result<Option<String>, ResolveError>> + Send {
+        async move { Ok(self.lookup_txt(query).await) }
+    }
 }
 
 impl<P: ConnectionProvider> Resolver for hickory_resolver::Resolver<P> {
     async fn lookup_txt(&self, query: &str) -> Option<String> {
+        Resolver::try_lookup_txt(self, query).await.unwrap_or_else(|err| {
+            trace!(target: "disc::dns", %err, ?query, "dns lookup failed");
+            None
+        })
+    }
+
+    async fn try_lookup_txt(&self, query: &str) -> Result<Option<String>, ResolveError> {
         // See: [AsyncResolver::txt_lookup]
         // > *hint* queries that end with a '.' are fully qualified names and are cheaper lookups
         let fqn = if query.ends_with('.') { query.to_string() } else { format!("{query}.") };
         match self.txt_lookup(fqn).await {
-            Err(err) => {
-                trace!(target: "disc::dns", %err, ?query, "dns lookup failed");
-                None
-            }
-            Ok(lookup) => {
-                let txt = lookup.into_iter().next()?;
-                let entry = txt.iter().next()?;
-                String::from_utf8(entry.to_vec()).ok()
-            }
+            Err(err) if err.is_no_records_found() => Ok(None),
+            Err(err) => Err(err),
+            Ok(lookup) => Ok(lookup
+                .into_iter()
+                .next()
+                .and_then(|txt| txt.iter().next().map(|entry| entry.to_vec()))
+                .and_then(|entry| String::from_utf8(entry).ok())),
         }
     }
 }
@@ -68,6 +84,10 @@ impl Resolver for DnsResolver {
     async fn lookup_txt(&self, query: &str) -> Option<String> {
         Resolver::lookup_txt(&self.0, query).await
     }
+
+    async fn try_lookup_txt(&self, query: &str) -> Result<Option<String>, ResolveError> {
+        Resolver::try_lookup_txt(&self.0, query).await
+    }
 }
 
 /// A [Resolver] that uses an in memory map to lookup entries
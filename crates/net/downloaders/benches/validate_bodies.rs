@@ -0,0 +1,70 @@
+#![allow(missing_docs)]
+use alloy_consensus::{Header, EMPTY_OMMER_ROOT_HASH};
+use criterion::*;
+use reth_chainspec::MAINNET;
+use reth_consensus::Consensus;
+use reth_downloaders::bodies::validation::validate_block_responses;
+use reth_ethereum_consensus::EthBeaconConsensus;
+use reth_network_p2p::bodies::response::BlockResponse;
+use reth_primitives::{BlockBody, SealedBlock, SealedHeader};
+use reth_testing_utils::generators::{self, random_block_range, BlockRangeParams};
+
+/// Timestamp of the Cancun activation on mainnet.
+const CANCUN_TIMESTAMP: u64 = 1_710_338_135;
+
+criterion_group!(benches, validate_bodies);
+criterion_main!(benches);
+
+/// Validates a downloaded batch of Cancun-era blocks sequentially, like the bodies downloader used
+/// to, and in parallel.
+fn validate_bodies(c: &mut Criterion) {
+    let consensus = EthBeaconConsensus::new(MAINNET.clone());
+    let mut group = c.benchmark_group("Validate Bodies");
+
+    for size in [100, 500] {
+        let responses = cancun_blocks(size);
+        group.throughput(Throughput::Elements(size));
+
+        group.bench_function(BenchmarkId::new("sequential", size), |b| {
+            b.iter(|| {
+                for response in &responses {
+                    if let BlockResponse::Full(block) = response {
+                        consensus.validate_block_pre_execution(block).unwrap();
+                    }
+                }
+            })
+        });
+
+        group.bench_function(BenchmarkId::new("parallel", size), |b| {
+            b.iter(|| validate_block_responses(&consensus, &responses).unwrap())
+        });
+    }
+
+    group.finish();
+}
+
+/// Generates blocks with a full list of transactions and withdrawals, with Cancun active.
+fn cancun_blocks(size: u64) -> Vec<BlockResponse<Header>> {
+    let mut rng = generators::rng();
+    random_block_range(
+        &mut rng,
+        1..=size,
+        BlockRangeParams {
+            tx_count: 100..200,
+            withdrawals_count: Some(16..17),
+            ..Default::default()
+        },
+    )
+    .into_iter()
+    .map(|block| {
+        let mut header = block.header.unseal();
+        header.timestamp = CANCUN_TIMESTAMP;
+        header.blob_gas_used = Some(0);
+        header.excess_blob_gas = Some(0);
+        header.parent_beacon_block_root = Some(Default::default());
+        header.ommers_hash = EMPTY_OMMER_ROOT_HASH;
+        let body = BlockBody { ommers: Vec::new(), ..block.body };
+        BlockResponse::Full(SealedBlock::new(SealedHeader::seal(header), body))
+    })
+    .collect()
+}
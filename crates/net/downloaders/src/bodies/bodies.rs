@@ -40,6 +40,8 @@ pub struct BodiesDownloader<B: BodiesClient, Provider: HeaderProvider> {
     client: Arc<B>,
     /// The consensus client
     consensus: Arc<dyn Consensus<Provider::Header, B::Body>>,
+    /// Whether to validate the downloaded bodies against their headers
+    validate_bodies: bool,
     /// The database handle
     provider: Provider,
     /// The maximum number of non-empty blocks per one request
@@ -400,6 +402,7 @@ where
                         this.in_progress_queue.push_new_request(
                             Arc::clone(&this.client),
                             Arc::clone(&this.consensus),
+                            this.validate_bodies,
                             request,
                         );
                         new_request_submitted = true;
@@ -516,6 +519,8 @@ pub struct BodiesDownloaderBuilder {
     pub max_buffered_blocks_size_bytes: usize,
     /// The maximum number of requests to send concurrently.
     pub concurrent_requests_range: RangeInclusive<usize>,
+    /// Whether to validate the downloaded bodies against their headers.
+    pub validate_bodies: bool,
 }
 
 impl BodiesDownloaderBuilder {
@@ -530,6 +535,7 @@ impl BodiesDownloaderBuilder {
                 config.downloader_min_concurrent_requests..=
                     config.downloader_max_concurrent_requests,
             )
+            .with_validate_bodies(config.downloader_validate_bodies)
     }
 }
 
@@ -540,6 +546,7 @@ impl Default for BodiesDownloaderBuilder {
             stream_batch_size: 1_000,
             max_buffered_blocks_size_bytes: 2 * 1024 * 1024 * 1024, // ~2GB
             concurrent_requests_range: 5..=100,
+            validate_bodies: true,
         }
     }
 }
@@ -575,6 +582,15 @@ impl BodiesDownloaderBuilder {
         self
     }
 
+    /// Set whether the downloader validates the downloaded bodies against their headers.
+    ///
+    /// Invalid bodies are only detected by the consumer of the downloader if this is disabled, and
+    /// the peers that sent them aren't penalized.
+    pub const fn with_validate_bodies(mut self, validate_bodies: bool) -> Self {
+        self.validate_bodies = validate_bodies;
+        self
+    }
+
     /// Consume self and return the concurrent downloader.
     pub fn build<B, Provider>(
         self,
@@ -591,12 +607,14 @@ impl BodiesDownloaderBuilder {
             stream_batch_size,
             concurrent_requests_range,
             max_buffered_blocks_size_bytes,
+            validate_bodies,
         } = self;
         let metrics = BodyDownloaderMetrics::default();
         let in_progress_queue = BodiesRequestQueue::new(metrics.clone());
         BodiesDownloader {
             client: Arc::new(client),
             consensus,
+            validate_bodies,
             provider,
             request_limit,
             stream_batch_size,
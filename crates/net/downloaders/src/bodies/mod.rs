@@ -8,6 +8,9 @@ pub mod noop;
 /// A downloader implementation that spawns a downloader to a task
 pub mod task;
 
+/// Validation of downloaded bodies.
+pub mod validation;
+
 mod queue;
 mod request;
 
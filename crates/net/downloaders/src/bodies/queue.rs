@@ -60,6 +60,7 @@ where
         &mut self,
         client: Arc<B>,
         consensus: Arc<dyn Consensus<H, B::Body>>,
+        validate_bodies: bool,
         request: Vec<SealedHeader<H>>,
     ) {
         // Set last max requested block number
@@ -73,7 +74,9 @@ where
 
         // Create request and push into the queue.
         self.inner.push(
-            BodiesRequestFuture::new(client, consensus, self.metrics.clone()).with_headers(request),
+            BodiesRequestFuture::new(client, consensus, self.metrics.clone())
+                .with_validate_bodies(validate_bodies)
+                .with_headers(request),
         )
     }
 }
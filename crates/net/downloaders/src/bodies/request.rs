@@ -1,11 +1,12 @@
+use super::validation::validate_block_responses;
 use crate::metrics::{BodyDownloaderMetrics, ResponseMetrics};
 use alloy_consensus::BlockHeader;
 use alloy_primitives::B256;
 use futures::{Future, FutureExt};
-use reth_consensus::Consensus;
+use reth_consensus::{Consensus, ConsensusError};
 use reth_network_p2p::{
     bodies::{client::BodiesClient, response::BlockResponse},
-    error::{DownloadError, DownloadResult},
+    error::{DownloadError, DownloadResult, RequestError},
     priority::Priority,
 };
 use reth_network_peers::{PeerId, WithPeerId};
@@ -18,6 +19,17 @@ use std::{
     sync::Arc,
     task::{ready, Context, Poll},
 };
+use tokio::sync::oneshot;
+
+/// The outcome of validating the bodies of a response on the rayon pool.
+struct ValidatedResponses<H, B> {
+    /// The peer that sent the bodies.
+    peer_id: PeerId,
+    /// The validated block responses, in the order of the request.
+    responses: Vec<BlockResponse<H, B>>,
+    /// The index of the first invalid block with its validation error, if any.
+    result: Result<(), (usize, ConsensusError)>,
+}
 
 /// Body request implemented as a [Future].
 ///
@@ -25,8 +37,10 @@ use std::{
 /// If the response arrived with insufficient number of bodies, the future
 /// will issue another request until all bodies are collected.
 ///
-/// It then proceeds to verify the downloaded bodies. In case of an validation error,
-/// the future will start over.
+/// It then proceeds to verify the downloaded bodies in parallel on the rayon pool, unless
+/// validation is disabled, so polling the future never blocks on the validation. In case of an
+/// validation error, the bodies preceding the invalid one are kept and the future requests the
+/// remaining bodies again.
 ///
 /// The future will filter out any empty headers (see [`alloy_consensus::Header::is_empty`]) from
 /// the request. If [`BodiesRequestFuture`] was initialized with all empty headers, no request will
@@ -41,6 +55,8 @@ use std::{
 pub(crate) struct BodiesRequestFuture<H, B: BodiesClient> {
     client: Arc<B>,
     consensus: Arc<dyn Consensus<H, B::Body>>,
+    /// Whether to validate the downloaded bodies against their headers.
+    validate_bodies: bool,
     metrics: BodyDownloaderMetrics,
     /// Metrics for individual responses. This can be used to observe how the size (in bytes) of
     /// responses change while bodies are being downloaded.
@@ -50,6 +66,8 @@ pub(crate) struct BodiesRequestFuture<H, B: BodiesClient> {
     /// Internal buffer for all blocks
     buffer: Vec<BlockResponse<H, B::Body>>,
     fut: Option<B::Output>,
+    /// The bodies of the last response that are being validated.
+    validation: Option<oneshot::Receiver<ValidatedResponses<H, B::Body>>>,
     /// Tracks how many bodies we requested in the last request.
     last_request_len: Option<usize>,
 }
@@ -68,15 +86,23 @@ where
         Self {
             client,
             consensus,
+            validate_bodies: true,
             metrics,
             response_metrics: Default::default(),
             pending_headers: Default::default(),
             buffer: Default::default(),
             last_request_len: None,
             fut: None,
+            validation: None,
         }
     }
 
+    /// Sets whether the downloaded bodies are validated against their headers.
+    pub(crate) const fn with_validate_bodies(mut self, validate_bodies: bool) -> Self {
+        self.validate_bodies = validate_bodies;
+        self
+    }
+
     pub(crate) fn with_headers(mut self, headers: Vec<SealedHeader<H>>) -> Self {
         self.buffer.reserve_exact(headers.len());
         self.pending_headers = VecDeque::from(headers);
@@ -117,8 +143,12 @@ where
 
     /// Process block response.
     /// Returns an error if the response is invalid.
+    ///
+    /// If the bodies are validated, they're buffered and the next request is submitted once the
+    /// validation completes, see [`Self::on_validated_responses`].
     fn on_block_response(&mut self, response: WithPeerId<Vec<B::Body>>) -> DownloadResult<()>
     where
+        H: Send + Sync + 'static,
         B::Body: InMemorySize,
     {
         let (peer_id, bodies) = response.split();
@@ -145,25 +175,73 @@ where
             }))
         }
 
-        // Buffer block responses
-        self.try_buffer_blocks(bodies)?;
+        let responses = self.take_block_responses(bodies);
+        if self.validate_bodies {
+            self.fut = None;
+            self.spawn_validation(peer_id, responses);
+        } else {
+            self.buffer_blocks(responses);
+        }
+
+        Ok(())
+    }
+
+    /// Buffers the valid block responses and submits the next request, if any.
+    fn buffer_blocks(&mut self, responses: Vec<BlockResponse<H, B::Body>>) {
+        self.buffer.extend(responses);
 
-        // Submit next request if any
         if let Some(req) = self.next_request() {
             self.submit_request(req, Priority::High);
         } else {
             self.fut = None;
         }
+    }
+
+    /// Validates the block responses on the rayon pool, see [`validate_block_responses`].
+    fn spawn_validation(&mut self, peer_id: PeerId, responses: Vec<BlockResponse<H, B::Body>>)
+    where
+        H: Send + Sync + 'static,
+    {
+        let consensus = Arc::clone(&self.consensus);
+        let (tx, rx) = oneshot::channel();
+        rayon::spawn(move || {
+            let result = validate_block_responses(&*consensus, &responses);
+            let _ = tx.send(ValidatedResponses { peer_id, responses, result });
+        });
+        self.validation = Some(rx);
+    }
+
+    /// Buffers the validated block responses. Returns an error if a body failed validation, in
+    /// which case every body preceding the failed one is buffered, and the headers of the failed
+    /// body and all following bodies are put back.
+    fn on_validated_responses(
+        &mut self,
+        mut responses: Vec<BlockResponse<H, B::Body>>,
+        result: Result<(), (usize, ConsensusError)>,
+    ) -> DownloadResult<()> {
+        if let Err((idx, error)) = result {
+            let invalid = responses.split_off(idx);
+            let hash = invalid[0].header().hash();
+            let number = invalid[0].block_number();
+            self.buffer.extend(responses);
+            for response in invalid.into_iter().rev() {
+                let header = match response {
+                    BlockResponse::Full(block) => block.header,
+                    BlockResponse::Empty(header) => header,
+                };
+                self.pending_headers.push_front(header);
+            }
+            return Err(DownloadError::BodyValidation { hash, number, error: Box::new(error) })
+        }
 
+        self.buffer_blocks(responses);
         Ok(())
     }
 
-    /// Attempt to buffer body responses. Returns an error if body response fails validation.
-    /// Every body preceding the failed one will be buffered.
+    /// Matches the bodies with the pending headers and returns the block responses.
     ///
     /// This method removes headers from the internal collection.
-    /// If the response fails validation, then the header will be put back.
-    fn try_buffer_blocks(&mut self, bodies: Vec<B::Body>) -> DownloadResult<()>
+    fn take_block_responses(&mut self, bodies: Vec<B::Body>) -> Vec<BlockResponse<H, B::Body>>
     where
         B::Body: InMemorySize,
     {
@@ -172,37 +250,24 @@ where
         let mut bodies = bodies.into_iter().peekable();
 
         let mut total_size = bodies_capacity * mem::size_of::<BlockBody>();
+        let mut responses = Vec::with_capacity(bodies_len);
         while bodies.peek().is_some() {
             let next_header = match self.pending_headers.pop_front() {
                 Some(header) => header,
-                None => return Ok(()), // no more headers
+                None => break, // no more headers
             };
 
             if next_header.is_empty() {
                 // increment empty block body metric
                 total_size += mem::size_of::<BlockBody>();
-                self.buffer.push(BlockResponse::Empty(next_header));
+                responses.push(BlockResponse::Empty(next_header));
             } else {
                 let next_body = bodies.next().unwrap();
 
                 // increment full block body metric
                 total_size += next_body.size();
 
-                let block = SealedBlock::new(next_header, next_body);
-
-                if let Err(error) = self.consensus.validate_block_pre_execution(&block) {
-                    // Body is invalid, put the header back and return an error
-                    let hash = block.hash();
-                    let number = block.number();
-                    self.pending_headers.push_front(block.header);
-                    return Err(DownloadError::BodyValidation {
-                        hash,
-                        number,
-                        error: Box::new(error),
-                    })
-                }
-
-                self.buffer.push(BlockResponse::Full(block));
+                responses.push(BlockResponse::Full(SealedBlock::new(next_header, next_body)));
             }
         }
 
@@ -210,7 +275,7 @@ where
         self.response_metrics.response_size_bytes.set(total_size as f64);
         self.response_metrics.response_length.set(bodies_len as f64);
 
-        Ok(())
+        responses
     }
 }
 
@@ -225,6 +290,22 @@ where
         let this = self.get_mut();
 
         loop {
+            // The headers of the bodies being validated were already taken, so the validation has
+            // to complete first.
+            if let Some(validation) = this.validation.as_mut() {
+                let validated = ready!(validation.poll_unpin(cx));
+                this.validation = None;
+                match validated {
+                    Ok(ValidatedResponses { peer_id, responses, result }) => {
+                        if let Err(error) = this.on_validated_responses(responses, result) {
+                            this.on_error(error, Some(peer_id));
+                        }
+                    }
+                    // the validation panicked
+                    Err(_) => return Poll::Ready(Err(RequestError::ChannelClosed.into())),
+                }
+            }
+
             if this.pending_headers.is_empty() {
                 return Poll::Ready(Ok(std::mem::take(&mut this.buffer)))
             }
@@ -249,8 +330,10 @@ where
                 }
             }
 
-            // Buffer any empty headers
-            while this.pending_headers.front().is_some_and(|h| h.is_empty()) {
+            // Buffer any empty headers, unless they follow the bodies being validated
+            while this.validation.is_none() &&
+                this.pending_headers.front().is_some_and(|h| h.is_empty())
+            {
                 let header = this.pending_headers.pop_front().unwrap();
                 this.buffer.push(BlockResponse::Empty(header));
             }
@@ -265,8 +348,15 @@ mod tests {
         bodies::test_utils::zip_blocks,
         test_utils::{generate_bodies, TestBodiesClient},
     };
+    use assert_matches::assert_matches;
+    use reth_chainspec::MAINNET;
     use reth_consensus::test_utils::TestConsensus;
-    use reth_testing_utils::{generators, generators::random_header_range};
+    use reth_ethereum_consensus::EthBeaconConsensus;
+    use reth_testing_utils::{
+        generators,
+        generators::{random_header_range, random_signed_tx},
+    };
+    use std::collections::HashMap;
 
     /// Check if future returns empty bodies without dispatching any requests.
     #[tokio::test]
@@ -313,4 +403,67 @@ mod tests {
             (headers.into_iter().filter(|h| !h.is_empty()).count() as u64 + 1) / 2
         );
     }
+
+    /// Check that an invalid body is attributed to its block, and that the bodies preceding it
+    /// are kept.
+    #[tokio::test]
+    async fn request_attributes_invalid_body() {
+        let (headers, mut bodies) = generate_bodies(0..=19);
+        let non_empty = headers.iter().filter(|h| !h.is_empty()).collect::<Vec<_>>();
+        let invalid_idx = non_empty.len() / 2;
+        let invalid = non_empty[invalid_idx].clone();
+
+        let mut fut = BodiesRequestFuture::new(
+            Arc::new(TestBodiesClient::default()),
+            Arc::new(EthBeaconConsensus::new(MAINNET.clone())),
+            BodyDownloaderMetrics::default(),
+        )
+        .with_headers(headers.clone());
+
+        let mut response =
+            non_empty.iter().map(|header| bodies[&header.hash()].clone()).collect::<Vec<_>>();
+        response[invalid_idx].transactions.push(random_signed_tx(&mut generators::rng()));
+
+        // the bodies are validated without blocking the future
+        fut.on_block_response((PeerId::default(), response).into()).unwrap();
+        assert!(fut.buffer.is_empty());
+        let ValidatedResponses { responses, result, .. } =
+            fut.validation.take().unwrap().await.unwrap();
+        assert_matches!(
+            fut.on_validated_responses(responses, result),
+            Err(DownloadError::BodyValidation { hash, number, .. })
+                if hash == invalid.hash() && number == invalid.number
+        );
+        assert_eq!(
+            fut.buffer,
+            zip_blocks(headers.iter().take_while(|h| h.hash() != invalid.hash()), &mut bodies)
+        );
+        assert_eq!(fut.pending_headers.front(), Some(&invalid));
+        assert_eq!(fut.next_request().unwrap()[0], invalid.hash());
+    }
+
+    /// Check that the peer that sent an invalid body is penalized and the body is requested again.
+    #[tokio::test]
+    async fn request_penalizes_invalid_body() {
+        let (headers, mut bodies) = generate_bodies(0..=19);
+        let invalid = headers.iter().rev().find(|h| !h.is_empty()).unwrap().hash();
+        let mut corrupted = bodies[&invalid].clone();
+        corrupted.transactions.push(random_signed_tx(&mut generators::rng()));
+
+        let client = Arc::new(
+            TestBodiesClient::default()
+                .with_bodies(bodies.clone())
+                .with_corrupted_bodies(HashMap::from([(invalid, corrupted)])),
+        );
+        let fut = BodiesRequestFuture::new(
+            client.clone(),
+            Arc::new(EthBeaconConsensus::new(MAINNET.clone())),
+            BodyDownloaderMetrics::default(),
+        )
+        .with_headers(headers.clone());
+
+        assert_eq!(fut.await.unwrap(), zip_blocks(headers.iter(), &mut bodies));
+        assert_eq!(client.bad_messages(), 1);
+        assert_eq!(client.times_requested(), 2);
+    }
 }
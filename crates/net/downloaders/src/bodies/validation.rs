@@ -0,0 +1,30 @@
+use rayon::prelude::*;
+use reth_consensus::{Consensus, ConsensusError};
+use reth_network_p2p::bodies::response::BlockResponse;
+
+/// Validates the downloaded bodies against their headers with
+/// [`Consensus::validate_block_pre_execution`].
+///
+/// This recomputes the transactions root, ommers hash and withdrawals root of every body, which is
+/// expensive for full blocks, but doesn't depend on the other blocks. So the blocks are validated
+/// in parallel.
+///
+/// Returns the index of the first invalid block in `responses` with its validation error.
+pub fn validate_block_responses<H, B>(
+    consensus: &dyn Consensus<H, B>,
+    responses: &[BlockResponse<H, B>],
+) -> Result<(), (usize, ConsensusError)>
+where
+    H: Sync,
+    B: Sync,
+{
+    let invalid = responses.par_iter().enumerate().find_map_first(|(idx, response)| {
+        let BlockResponse::Full(block) = response else { return None };
+        consensus.validate_block_pre_execution(block).err().map(|error| (idx, error))
+    });
+
+    match invalid {
+        Some(invalid) => Err(invalid),
+        None => Ok(()),
+    }
+}
@@ -14,7 +14,7 @@ use reth_network_p2p::{
     error::{DownloadError, DownloadResult, PeerRequestResult},
     headers::{
         client::{HeadersClient, HeadersRequest},
-        downloader::{validate_header_download, HeaderDownloader, SyncTarget},
+        downloader::{validate_header_download_against_parent, HeaderDownloader, SyncTarget},
         error::{HeadersDownloaderError, HeadersDownloaderResult},
     },
     priority::Priority,
@@ -252,6 +252,26 @@ where
         let mut validated = Vec::with_capacity(headers.len());
 
         let sealed_headers = headers.into_par_iter().map(SealedHeader::seal).collect::<Vec<_>>();
+
+        // Every header must be valid on its own. This doesn't depend on the other headers and can
+        // be expensive, e.g. verifying proof-of-work seals, so it's done in parallel.
+        let consensus = &self.consensus;
+        if let Err((header, error)) = sealed_headers.par_iter().try_for_each(|header| {
+            consensus.validate_header(header).map_err(|error| (header, error))
+        }) {
+            trace!(target: "downloaders::headers", %error, "Failed to validate header");
+            return Err(HeadersResponseError {
+                request,
+                peer_id: Some(peer_id),
+                error: DownloadError::HeaderValidation {
+                    hash: header.hash(),
+                    number: header.number(),
+                    error: Box::new(error),
+                },
+            }
+            .into())
+        }
+
         for parent in sealed_headers {
             // Validate that the header is the parent header of the last validated header.
             if let Some(validated_header) =
@@ -276,21 +296,6 @@ where
             .zip(self.local_head.as_ref())
             .filter(|(last, head)| last.number() == head.number() + 1)
         {
-            // Every header must be valid on its own
-            if let Err(error) = self.consensus.validate_header(&*last_header) {
-                trace!(target: "downloaders::headers", %error, "Failed to validate header");
-                return Err(HeadersResponseError {
-                    request,
-                    peer_id: Some(peer_id),
-                    error: DownloadError::HeaderValidation {
-                        hash: head.hash(),
-                        number: head.number(),
-                        error: Box::new(error),
-                    },
-                }
-                .into())
-            }
-
             // If the header is valid on its own, but not against its parent, we return it as
             // detached head error.
             if let Err(error) = self.consensus.validate_header_against_parent(&*last_header, head) {
@@ -591,12 +596,15 @@ where
     }
 
     /// Validate whether the header is valid in relation to it's parent
+    ///
+    /// The headers of a response are validated on their own in parallel before, see
+    /// [`Self::process_next_headers`].
     fn validate(
         &self,
         header: &SealedHeader<H::Header>,
         parent: &SealedHeader<H::Header>,
     ) -> DownloadResult<()> {
-        validate_header_download(&self.consensus, header, parent)
+        validate_header_download_against_parent(&self.consensus, header, parent)
     }
 
     /// Clears all requests/responses.
@@ -21,9 +21,11 @@ use tokio::sync::Mutex;
 #[derive(Debug, Default)]
 pub struct TestBodiesClient {
     bodies: Arc<Mutex<HashMap<B256, BlockBody>>>,
+    corrupted_bodies: Arc<Mutex<HashMap<B256, BlockBody>>>,
     should_delay: bool,
     max_batch_size: Option<usize>,
     times_requested: AtomicU64,
+    bad_messages: AtomicU64,
     empty_response_mod: Option<u64>,
 }
 
@@ -33,6 +35,13 @@ impl TestBodiesClient {
         self
     }
 
+    /// Instructs the client to respond with the given bodies instead of the actual ones, once per
+    /// block hash.
+    pub(crate) fn with_corrupted_bodies(mut self, bodies: HashMap<B256, BlockBody>) -> Self {
+        self.corrupted_bodies = Arc::new(Mutex::new(bodies));
+        self
+    }
+
     pub(crate) const fn with_should_delay(mut self, should_delay: bool) -> Self {
         self.should_delay = should_delay;
         self
@@ -54,6 +63,10 @@ impl TestBodiesClient {
         self.times_requested.load(Ordering::Relaxed)
     }
 
+    pub(crate) fn bad_messages(&self) -> u64 {
+        self.bad_messages.load(Ordering::Relaxed)
+    }
+
     /// Returns whether or not the client should respond with an empty response.
     ///
     /// This will only return true if `empty_response_mod` is `Some`, and `times_requested %
@@ -69,7 +82,7 @@ impl TestBodiesClient {
 
 impl DownloadClient for TestBodiesClient {
     fn report_bad_message(&self, _peer_id: PeerId) {
-        // noop
+        self.bad_messages.fetch_add(1, Ordering::Relaxed);
     }
 
     fn num_connected_peers(&self) -> usize {
@@ -88,6 +101,7 @@ impl BodiesClient for TestBodiesClient {
     ) -> Self::Output {
         let should_delay = self.should_delay;
         let bodies = self.bodies.clone();
+        let corrupted_bodies = self.corrupted_bodies.clone();
         let max_batch_size = self.max_batch_size;
 
         self.times_requested.fetch_add(1, Ordering::Relaxed);
@@ -103,15 +117,18 @@ impl BodiesClient for TestBodiesClient {
             }
 
             let bodies = &mut *bodies.lock().await;
+            let corrupted_bodies = &mut *corrupted_bodies.lock().await;
             Ok((
                 PeerId::default(),
                 hashes
                     .into_iter()
                     .take(max_batch_size.unwrap_or(usize::MAX))
                     .map(|hash| {
-                        bodies
-                            .remove(&hash)
-                            .expect("Downloader asked for a block it should not ask for")
+                        corrupted_bodies.remove(&hash).unwrap_or_else(|| {
+                            bodies
+                                .remove(&hash)
+                                .expect("Downloader asked for a block it should not ask for")
+                        })
                     })
                     .collect(),
             )
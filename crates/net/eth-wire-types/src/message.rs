@@ -1,4 +1,4 @@
-//! Implements Ethereum wire protocol for versions 66, 67, and 68.
+//! Implements Ethereum wire protocol for versions 66, 67, 68 and 69.
 //! Defines structs/enums for messages, request-response pairs, and broadcasts.
 //! Handles compatibility with [`EthVersion`].
 //!
@@ -7,9 +7,10 @@
 //! Reference: [Ethereum Wire Protocol](https://github.com/ethereum/wiki/wiki/Ethereum-Wire-Protocol).
 
 use super::{
-    broadcast::NewBlockHashes, BlockBodies, BlockHeaders, GetBlockBodies, GetBlockHeaders,
-    GetNodeData, GetPooledTransactions, GetReceipts, NewBlock, NewPooledTransactionHashes66,
-    NewPooledTransactionHashes68, NodeData, PooledTransactions, Receipts, Status, Transactions,
+    broadcast::NewBlockHashes, BlockBodies, BlockHeaders, BlockRangeUpdate, GetBlockBodies,
+    GetBlockHeaders, GetNodeData, GetPooledTransactions, GetReceipts, NewBlock,
+    NewPooledTransactionHashes66, NewPooledTransactionHashes68, NodeData, PooledTransactions,
+    Receipts, Status, StatusEth69, StatusMessage, Transactions,
 };
 use crate::{EthNetworkPrimitives, EthVersion, NetworkPrimitives, SharedTransactions};
 use alloc::{boxed::Box, sync::Arc};
@@ -52,7 +53,14 @@ impl<N: NetworkPrimitives> ProtocolMessage<N> {
         let message_type = EthMessageID::decode(buf)?;
 
         let message = match message_type {
-            EthMessageID::Status => EthMessage::Status(Status::decode(buf)?),
+            EthMessageID::Status => {
+                let status = if version.is_eth69() {
+                    StatusMessage::Eth69(StatusEth69::decode(buf)?)
+                } else {
+                    StatusMessage::Legacy(Status::decode(buf)?)
+                };
+                EthMessage::Status(status)
+            }
             EthMessageID::NewBlockHashes => {
                 if version.is_eth69() {
                     return Err(MessageError::Invalid(version, EthMessageID::NewBlockHashes));
@@ -101,6 +109,12 @@ impl<N: NetworkPrimitives> ProtocolMessage<N> {
             }
             EthMessageID::GetReceipts => EthMessage::GetReceipts(RequestPair::decode(buf)?),
             EthMessageID::Receipts => EthMessage::Receipts(RequestPair::decode(buf)?),
+            EthMessageID::BlockRangeUpdate => {
+                if !version.is_eth69() {
+                    return Err(MessageError::Invalid(version, EthMessageID::BlockRangeUpdate))
+                }
+                EthMessage::BlockRangeUpdate(BlockRangeUpdate::decode(buf)?)
+            }
         };
         Ok(Self { message_type, message })
     }
@@ -152,7 +166,7 @@ impl<N: NetworkPrimitives> From<EthBroadcastMessage<N>> for ProtocolBroadcastMes
     }
 }
 
-/// Represents a message in the eth wire protocol, versions 66, 67 and 68.
+/// Represents a message in the eth wire protocol, versions 66, 67, 68 and 69.
 ///
 /// The ethereum wire protocol is a set of messages that are broadcast to the network in two
 /// styles:
@@ -169,11 +183,15 @@ impl<N: NetworkPrimitives> From<EthBroadcastMessage<N>> for ProtocolBroadcastMes
 /// The `eth/68` changes only `NewPooledTransactionHashes` to include `types` and `sized`. For
 /// it, `NewPooledTransactionHashes` is renamed as [`NewPooledTransactionHashes66`] and
 /// [`NewPooledTransactionHashes68`] is defined.
+///
+/// The `eth/69` drops the total difficulty from the [`Status`] in favor of the range of blocks the
+/// peer can serve, see [`StatusEth69`], and adds the [`BlockRangeUpdate`] announcement. It also
+/// removes the [`NewBlockHashes`] and [`NewBlock`] broadcasts.
 #[derive(Clone, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum EthMessage<N: NetworkPrimitives = EthNetworkPrimitives> {
     /// Represents a Status message required for the protocol handshake.
-    Status(Status),
+    Status(StatusMessage),
     /// Represents a `NewBlockHashes` message broadcast to the network.
     NewBlockHashes(NewBlockHashes),
     /// Represents a `NewBlock` message broadcast to the network.
@@ -229,6 +247,8 @@ pub enum EthMessage<N: NetworkPrimitives = EthNetworkPrimitives> {
         serde(bound = "N::Receipt: serde::Serialize + serde::de::DeserializeOwned")
     )]
     Receipts(RequestPair<Receipts<N::Receipt>>),
+    /// Represents a `BlockRangeUpdate` message broadcast to the network.
+    BlockRangeUpdate(BlockRangeUpdate),
 }
 
 impl<N: NetworkPrimitives> EthMessage<N> {
@@ -252,6 +272,7 @@ impl<N: NetworkPrimitives> EthMessage<N> {
             Self::NodeData(_) => EthMessageID::NodeData,
             Self::GetReceipts(_) => EthMessageID::GetReceipts,
             Self::Receipts(_) => EthMessageID::Receipts,
+            Self::BlockRangeUpdate(_) => EthMessageID::BlockRangeUpdate,
         }
     }
 }
@@ -275,6 +296,7 @@ impl<N: NetworkPrimitives> Encodable for EthMessage<N> {
             Self::NodeData(data) => data.encode(out),
             Self::GetReceipts(request) => request.encode(out),
             Self::Receipts(receipts) => receipts.encode(out),
+            Self::BlockRangeUpdate(update) => update.encode(out),
         }
     }
     fn length(&self) -> usize {
@@ -295,6 +317,7 @@ impl<N: NetworkPrimitives> Encodable for EthMessage<N> {
             Self::NodeData(data) => data.length(),
             Self::GetReceipts(request) => request.length(),
             Self::Receipts(receipts) => receipts.length(),
+            Self::BlockRangeUpdate(update) => update.length(),
         }
     }
 }
@@ -377,12 +400,18 @@ pub enum EthMessageID {
     GetReceipts = 0x0f,
     /// Represents receipts.
     Receipts = 0x10,
+    /// Block range update message, introduced in `eth/69`.
+    BlockRangeUpdate = 0x11,
 }
 
 impl EthMessageID {
-    /// Returns the max value.
-    pub const fn max() -> u8 {
-        Self::Receipts as u8
+    /// Returns the max value for the given version.
+    pub const fn max(version: EthVersion) -> u8 {
+        if version.is_eth69() {
+            Self::BlockRangeUpdate as u8
+        } else {
+            Self::Receipts as u8
+        }
     }
 }
 
@@ -413,6 +442,7 @@ impl Decodable for EthMessageID {
             0x0e => Self::NodeData,
             0x0f => Self::GetReceipts,
             0x10 => Self::Receipts,
+            0x11 => Self::BlockRangeUpdate,
             _ => return Err(alloy_rlp::Error::Custom("Invalid message ID")),
         };
         buf.advance(1);
@@ -440,6 +470,7 @@ impl TryFrom<usize> for EthMessageID {
             0x0e => Ok(Self::NodeData),
             0x0f => Ok(Self::GetReceipts),
             0x10 => Ok(Self::Receipts),
+            0x11 => Ok(Self::BlockRangeUpdate),
             _ => Err("Invalid message ID"),
         }
     }
@@ -488,6 +519,9 @@ where
 {
     fn decode(buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
         let header = Header::decode(buf)?;
+        if !header.list {
+            return Err(alloy_rlp::Error::UnexpectedString)
+        }
 
         let initial_length = buf.len();
         let request_id = u64::decode(buf)?;
@@ -508,10 +542,10 @@ where
 mod tests {
     use super::MessageError;
     use crate::{
-        message::RequestPair, EthMessage, EthMessageID, EthNetworkPrimitives, EthVersion,
-        GetNodeData, NodeData, ProtocolMessage,
+        message::RequestPair, BlockRangeUpdate, EthMessage, EthMessageID, EthNetworkPrimitives,
+        EthVersion, GetNodeData, NodeData, ProtocolMessage, Status, StatusMessage,
     };
-    use alloy_primitives::hex;
+    use alloy_primitives::{hex, B256};
     use alloy_rlp::{Decodable, Encodable, Error};
 
     fn encode<T: Encodable>(value: T) -> Vec<u8> {
@@ -592,6 +626,16 @@ mod tests {
         assert!(matches!(result, Err(Error::UnexpectedLength)));
     }
 
+    #[test]
+    fn string_request_pair_decode() {
+        // the request pair from `request_pair_decode`, but with a string header (85) instead of a
+        // list header (c5)
+        let raw_pair = &hex!("85820539c105")[..];
+
+        let result = RequestPair::<Vec<u8>>::decode(&mut &*raw_pair);
+        assert!(matches!(result, Err(Error::UnexpectedString)));
+    }
+
     #[test]
     fn empty_block_bodies_protocol() {
         let empty_block_bodies =
@@ -605,4 +649,56 @@ mod tests {
             ProtocolMessage::decode_message(EthVersion::Eth68, &mut buf.as_slice()).unwrap();
         assert_eq!(empty_block_bodies, decoded);
     }
+
+    #[test]
+    fn status_message_version_gated() {
+        let block_range =
+            BlockRangeUpdate { earliest: 1, latest: 2, latest_hash: B256::with_last_byte(2) };
+        for version in [EthVersion::Eth68, EthVersion::Eth69] {
+            let status = StatusMessage::new(Status { version, ..Default::default() }, block_range);
+            let message = ProtocolMessage::from(EthMessage::<EthNetworkPrimitives>::Status(status));
+            let buf = encode(message.clone());
+            let decoded = ProtocolMessage::decode_message(version, &mut &buf[..]).unwrap();
+            assert_eq!(decoded, message);
+        }
+
+        // an eth/69 status can't be decoded by an eth/68 peer and vice versa
+        let status = StatusMessage::new(
+            Status { version: EthVersion::Eth69, ..Default::default() },
+            block_range,
+        );
+        let buf = encode(ProtocolMessage::from(EthMessage::<EthNetworkPrimitives>::Status(status)));
+        assert!(ProtocolMessage::<EthNetworkPrimitives>::decode_message(
+            EthVersion::Eth68,
+            &mut &buf[..]
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn block_range_update_roundtrip() {
+        let message = ProtocolMessage::from(EthMessage::<EthNetworkPrimitives>::BlockRangeUpdate(
+            BlockRangeUpdate { earliest: 0, latest: 1337, latest_hash: B256::repeat_byte(1) },
+        ));
+        assert_eq!(message.message_type, EthMessageID::BlockRangeUpdate);
+
+        let buf = encode(message.clone());
+        assert_eq!(buf.len(), message.length());
+        let decoded = ProtocolMessage::decode_message(EthVersion::Eth69, &mut &buf[..]).unwrap();
+        assert_eq!(decoded, message);
+
+        // the message doesn't exist before eth/69
+        let msg = ProtocolMessage::<EthNetworkPrimitives>::decode_message(
+            EthVersion::Eth68,
+            &mut &buf[..],
+        );
+        assert!(matches!(msg, Err(MessageError::Invalid(..))));
+    }
+
+    #[test]
+    fn message_id_space() {
+        assert_eq!(EthMessageID::max(EthVersion::Eth68), 0x10);
+        assert_eq!(EthMessageID::max(EthVersion::Eth69), 0x11);
+        assert_eq!(EthMessageID::try_from(0x11usize), Ok(EthMessageID::BlockRangeUpdate));
+    }
 }
@@ -1,7 +1,8 @@
 use crate::EthVersion;
 use alloy_chains::{Chain, NamedChain};
 use alloy_primitives::{hex, B256, U256};
-use alloy_rlp::{RlpDecodable, RlpEncodable};
+use alloy_rlp::{Encodable, RlpDecodable, RlpEncodable};
+use bytes::BufMut;
 use core::fmt::{Debug, Display};
 use reth_chainspec::{EthChainSpec, Hardforks, MAINNET};
 use reth_codecs_derive::add_arbitrary_tests;
@@ -213,9 +214,242 @@ impl StatusBuilder {
     }
 }
 
+/// The status message of the `eth/69` protocol.
+///
+/// Compared to the [`Status`] of previous versions, this drops the total difficulty and instead
+/// announces the range of blocks the peer can serve.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, RlpEncodable, RlpDecodable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[add_arbitrary_tests(rlp)]
+pub struct StatusEth69 {
+    /// The current protocol version, 69.
+    pub version: EthVersion,
+
+    /// The chain id, as introduced in
+    /// [EIP155](https://eips.ethereum.org/EIPS/eip-155#list-of-chain-ids).
+    pub chain: Chain,
+
+    /// The genesis hash of the peer's chain.
+    pub genesis: B256,
+
+    /// The fork identifier as defined by
+    /// [EIP-2124](https://github.com/ethereum/EIPs/blob/master/EIPS/eip-2124.md).
+    pub forkid: ForkId,
+
+    /// The earliest block the peer can serve.
+    pub earliest: u64,
+
+    /// The latest block the peer can serve.
+    pub latest: u64,
+
+    /// The hash of the latest block.
+    pub blockhash: B256,
+}
+
+impl StatusEth69 {
+    /// Creates the `eth/69` status from the given [`Status`] and the range of blocks that can be
+    /// served. The total difficulty of the [`Status`] is dropped.
+    pub const fn from_status(status: Status, block_range: BlockRangeUpdate) -> Self {
+        Self {
+            version: status.version,
+            chain: status.chain,
+            genesis: status.genesis,
+            forkid: status.forkid,
+            earliest: block_range.earliest,
+            latest: block_range.latest,
+            blockhash: block_range.latest_hash,
+        }
+    }
+
+    /// Returns the range of blocks the peer can serve.
+    pub const fn block_range(&self) -> BlockRangeUpdate {
+        BlockRangeUpdate {
+            earliest: self.earliest,
+            latest: self.latest,
+            latest_hash: self.blockhash,
+        }
+    }
+}
+
+impl Display for StatusEth69 {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "Status {{ version: {}, chain: {}, genesis: {}, forkid: {:X?}, earliest: {}, latest: {}, blockhash: {} }}",
+            self.version,
+            self.chain,
+            hex::encode(self.genesis),
+            self.forkid,
+            self.earliest,
+            self.latest,
+            hex::encode(self.blockhash),
+        )
+    }
+}
+
+/// The `Status` message of any supported `eth` version.
+///
+/// The layout of the message depends on the negotiated version, `eth/69` peers exchange a
+/// [`StatusEth69`], all previous versions a [`Status`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum StatusMessage {
+    /// The status message of `eth/66` to `eth/68`.
+    Legacy(Status),
+    /// The status message of `eth/69`.
+    Eth69(StatusEth69),
+}
+
+impl StatusMessage {
+    /// Creates the status message for the version of the given [`Status`].
+    ///
+    /// The block range is only sent to `eth/69` peers.
+    pub const fn new(status: Status, block_range: BlockRangeUpdate) -> Self {
+        if status.version.is_eth69() {
+            Self::Eth69(StatusEth69::from_status(status, block_range))
+        } else {
+            Self::Legacy(status)
+        }
+    }
+
+    /// Returns the protocol version.
+    pub const fn version(&self) -> EthVersion {
+        match self {
+            Self::Legacy(status) => status.version,
+            Self::Eth69(status) => status.version,
+        }
+    }
+
+    /// Returns the chain id.
+    pub const fn chain(&self) -> Chain {
+        match self {
+            Self::Legacy(status) => status.chain,
+            Self::Eth69(status) => status.chain,
+        }
+    }
+
+    /// Returns the genesis hash.
+    pub const fn genesis(&self) -> B256 {
+        match self {
+            Self::Legacy(status) => status.genesis,
+            Self::Eth69(status) => status.genesis,
+        }
+    }
+
+    /// Returns the fork identifier.
+    pub const fn forkid(&self) -> ForkId {
+        match self {
+            Self::Legacy(status) => status.forkid,
+            Self::Eth69(status) => status.forkid,
+        }
+    }
+
+    /// Returns the hash of the best block.
+    pub const fn blockhash(&self) -> B256 {
+        match self {
+            Self::Legacy(status) => status.blockhash,
+            Self::Eth69(status) => status.blockhash,
+        }
+    }
+
+    /// Returns the total difficulty, which is not sent by `eth/69` peers.
+    pub const fn total_difficulty(&self) -> Option<U256> {
+        match self {
+            Self::Legacy(status) => Some(status.total_difficulty),
+            Self::Eth69(_) => None,
+        }
+    }
+
+    /// Returns the range of blocks the peer can serve, which is only sent by `eth/69` peers.
+    pub const fn block_range(&self) -> Option<BlockRangeUpdate> {
+        match self {
+            Self::Legacy(_) => None,
+            Self::Eth69(status) => Some(status.block_range()),
+        }
+    }
+
+    /// Converts the message into a [`Status`].
+    ///
+    /// The total difficulty of an `eth/69` status is set to zero.
+    pub const fn to_legacy(&self) -> Status {
+        match self {
+            Self::Legacy(status) => *status,
+            Self::Eth69(status) => Status {
+                version: status.version,
+                chain: status.chain,
+                total_difficulty: U256::ZERO,
+                blockhash: status.blockhash,
+                genesis: status.genesis,
+                forkid: status.forkid,
+            },
+        }
+    }
+}
+
+impl From<Status> for StatusMessage {
+    fn from(status: Status) -> Self {
+        Self::Legacy(status)
+    }
+}
+
+impl From<StatusEth69> for StatusMessage {
+    fn from(status: StatusEth69) -> Self {
+        Self::Eth69(status)
+    }
+}
+
+impl Encodable for StatusMessage {
+    fn encode(&self, out: &mut dyn BufMut) {
+        match self {
+            Self::Legacy(status) => status.encode(out),
+            Self::Eth69(status) => status.encode(out),
+        }
+    }
+
+    fn length(&self) -> usize {
+        match self {
+            Self::Legacy(status) => status.length(),
+            Self::Eth69(status) => status.length(),
+        }
+    }
+}
+
+impl Display for StatusMessage {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Legacy(status) => Display::fmt(status, f),
+            Self::Eth69(status) => Display::fmt(status, f),
+        }
+    }
+}
+
+/// Announces the range of blocks a peer can serve, introduced in `eth/69`.
+///
+/// Peers send this whenever their range changes, but not more often than once per epoch.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, RlpEncodable, RlpDecodable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[add_arbitrary_tests(rlp)]
+pub struct BlockRangeUpdate {
+    /// The earliest block the peer can serve.
+    pub earliest: u64,
+    /// The latest block the peer can serve.
+    pub latest: u64,
+    /// The hash of the latest block.
+    pub latest_hash: B256,
+}
+
+impl BlockRangeUpdate {
+    /// Returns `true` if the earliest block is not after the latest block.
+    pub const fn is_valid(&self) -> bool {
+        self.earliest <= self.latest
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::{EthVersion, Status};
+    use crate::{BlockRangeUpdate, EthVersion, Status, StatusEth69, StatusMessage};
     use alloy_consensus::constants::MAINNET_GENESIS_HASH;
     use alloy_genesis::Genesis;
     use alloy_primitives::{hex, B256, U256};
@@ -387,4 +621,69 @@ mod tests {
         assert_eq!(status.blockhash, head_hash);
         assert_eq!(status.genesis, genesis_hash);
     }
+
+    #[test]
+    fn eth69_status_roundtrip() {
+        let status = StatusEth69 {
+            version: EthVersion::Eth69,
+            chain: Chain::from_named(NamedChain::Mainnet),
+            genesis: MAINNET_GENESIS_HASH,
+            forkid: ForkId { hash: ForkHash([0xb7, 0x15, 0x07, 0x7d]), next: 0 },
+            earliest: 15_537_394,
+            latest: 21_000_000,
+            blockhash: B256::from_str(
+                "feb27336ca7923f8fab3bd617fcb6e75841538f71c1bcfc267d7838489d9e13d",
+            )
+            .unwrap(),
+        };
+
+        let mut rlp_status = vec![];
+        status.encode(&mut rlp_status);
+        assert_eq!(rlp_status.len(), status.length());
+        assert_eq!(StatusEth69::decode(&mut &rlp_status[..]).unwrap(), status);
+
+        // the eth/69 status can't be decoded as a legacy status
+        assert!(Status::decode(&mut &rlp_status[..]).is_err());
+    }
+
+    #[test]
+    fn status_message_for_version() {
+        let block_range =
+            BlockRangeUpdate { earliest: 10, latest: 100, latest_hash: B256::with_last_byte(1) };
+        let status = Status {
+            version: EthVersion::Eth68,
+            total_difficulty: U256::from(1337),
+            blockhash: B256::with_last_byte(1),
+            ..Default::default()
+        };
+
+        let message = StatusMessage::new(status, block_range);
+        assert_eq!(message, StatusMessage::Legacy(status));
+        assert_eq!(message.total_difficulty(), Some(U256::from(1337)));
+        assert_eq!(message.block_range(), None);
+
+        let status = Status { version: EthVersion::Eth69, ..status };
+        let message = StatusMessage::new(status, block_range);
+        assert!(matches!(message, StatusMessage::Eth69(_)));
+        assert_eq!(message.version(), EthVersion::Eth69);
+        assert_eq!(message.genesis(), status.genesis);
+        assert_eq!(message.forkid(), status.forkid);
+        assert_eq!(message.total_difficulty(), None);
+        assert_eq!(message.block_range(), Some(block_range));
+        assert_eq!(message.to_legacy(), Status { total_difficulty: U256::ZERO, ..status });
+    }
+
+    #[test]
+    fn block_range_update_roundtrip() {
+        let update =
+            BlockRangeUpdate { earliest: 0, latest: 21_000_000, latest_hash: B256::repeat_byte(7) };
+
+        let mut encoded = vec![];
+        update.encode(&mut encoded);
+        assert_eq!(encoded.len(), update.length());
+        assert_eq!(BlockRangeUpdate::decode(&mut &encoded[..]).unwrap(), update);
+
+        assert!(update.is_valid());
+        assert!(!BlockRangeUpdate { earliest: 2, latest: 1, ..update }.is_valid());
+    }
 }
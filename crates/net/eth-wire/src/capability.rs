@@ -179,7 +179,7 @@ impl SharedCapability {
     /// Returns the number of protocol messages supported by this capability.
     pub const fn num_messages(&self) -> u8 {
         match self {
-            Self::Eth { version: _version, .. } => EthMessageID::max() + 1,
+            Self::Eth { version, .. } => EthMessageID::max(*version) + 1,
             Self::UnknownCapability { messages, .. } => *messages,
         }
     }
@@ -453,6 +453,7 @@ mod tests {
             Capability::new_static("eth", 66),
             Capability::new_static("eth", 67),
             Capability::new_static("eth", 68),
+            Capability::new_static("eth", 69),
         ]
         .into();
 
@@ -460,6 +461,53 @@ mod tests {
         assert!(capabilities.supports_eth_v66());
         assert!(capabilities.supports_eth_v67());
         assert!(capabilities.supports_eth_v68());
+        assert!(capabilities.supports_eth_v69());
+    }
+
+    #[test]
+    fn test_highest_mutual_eth_version() {
+        let local_capabilities: Vec<Protocol> =
+            vec![EthVersion::Eth69.into(), EthVersion::Eth68.into(), EthVersion::Eth67.into()];
+
+        let shared = shared_capability_offsets(
+            local_capabilities.clone(),
+            vec![EthVersion::Eth68.into(), EthVersion::Eth69.into()],
+        )
+        .unwrap();
+        assert_eq!(shared[0].eth_version(), Some(EthVersion::Eth69));
+
+        // peers that don't support eth/69 yet still negotiate eth/68
+        let shared = shared_capability_offsets(
+            local_capabilities,
+            vec![EthVersion::Eth67.into(), EthVersion::Eth68.into()],
+        )
+        .unwrap();
+        assert_eq!(shared[0].eth_version(), Some(EthVersion::Eth68));
+    }
+
+    #[test]
+    fn test_eth_message_id_space() {
+        let cap = Capability::new_static("snap", 1);
+        let snap = Protocol::new(cap.clone(), 8);
+
+        // the eth/68 message id space is unchanged, so the offset of following capabilities is
+        // the same as before
+        let shared = SharedCapabilities::try_new(
+            vec![EthVersion::Eth68.into(), snap.clone()],
+            vec![EthVersion::Eth68.into(), cap.clone()],
+        )
+        .unwrap();
+        assert_eq!(shared[0].num_messages(), 0x11);
+        assert_eq!(shared[1].message_id_offset(), MAX_RESERVED_MESSAGE_ID + 1 + 0x11);
+
+        // eth/69 adds the `BlockRangeUpdate` message
+        let shared = SharedCapabilities::try_new(
+            vec![EthVersion::Eth69.into(), snap],
+            vec![EthVersion::Eth69.into(), cap],
+        )
+        .unwrap();
+        assert_eq!(shared[0].num_messages(), 0x12);
+        assert_eq!(shared[1].message_id_offset(), MAX_RESERVED_MESSAGE_ID + 1 + 0x12);
     }
 
     #[test]
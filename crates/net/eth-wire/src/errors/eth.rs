@@ -41,6 +41,14 @@ pub enum EthStreamError {
     /// Error when data is not received from peer for a prolonged period.
     #[error("never received data from remote peer")]
     StreamTimeout,
+    #[error("invalid block range: earliest {earliest} > latest {latest}")]
+    /// Received a `BlockRangeUpdate` message with an invalid block range.
+    InvalidBlockRange {
+        /// The announced earliest available block.
+        earliest: u64,
+        /// The announced latest available block.
+        latest: u64,
+    },
 }
 
 // === impl EthStreamError ===
@@ -102,4 +110,12 @@ pub enum EthHandshakeError {
         /// The maximum allowed bit length for the total difficulty.
         maximum: usize,
     },
+    #[error("invalid block range in status message: earliest {earliest} > latest {latest}")]
+    /// The block range announced in an eth/69 status message is invalid.
+    InvalidBlockRange {
+        /// The announced earliest available block.
+        earliest: u64,
+        /// The announced latest available block.
+        latest: u64,
+    },
 }
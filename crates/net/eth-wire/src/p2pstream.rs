@@ -17,8 +17,9 @@ use reth_metrics::metrics::counter;
 use reth_primitives_traits::GotExpected;
 use std::{
     collections::VecDeque,
-    io,
+    fmt, io,
     pin::Pin,
+    sync::Arc,
     task::{ready, Context, Poll},
     time::Duration,
 };
@@ -32,6 +33,13 @@ use serde::{Deserialize, Serialize};
 /// This is defined in [EIP-706](https://eips.ethereum.org/EIPS/eip-706).
 const MAX_PAYLOAD_SIZE: usize = 16 * 1024 * 1024;
 
+/// [`MAX_SNAPPY_EXPANSION`] bounds the ratio of the decompressed to the compressed size of a snappy
+/// message.
+///
+/// The most compact snappy element is a copy of 64 bytes encoded in 3 bytes, so a payload can't
+/// decompress to more than 22 times its size.
+const MAX_SNAPPY_EXPANSION: usize = 22;
+
 /// [`MAX_RESERVED_MESSAGE_ID`] is the maximum message ID reserved for the `p2p` subprotocol. If
 /// there are any incoming messages with an ID greater than this, they are subprotocol messages.
 pub const MAX_RESERVED_MESSAGE_ID: u8 = 0x0f;
@@ -219,6 +227,18 @@ where
 ///
 /// See also <https://github.com/ethereum/devp2p/blob/master/rlpx.md#message-id-based-multiplexing>
 ///
+/// Counts the bytes of the messages exchanged over a [`P2PStream`].
+///
+/// Messages are measured as they're framed on the wire, i.e. snappy compressed and including the
+/// message id, and identified by their message id on the wire, before it's normalized.
+pub trait MessageMeter: fmt::Debug + Send + Sync {
+    /// Records a message received from the peer.
+    fn on_inbound(&self, id: u8, size: usize);
+
+    /// Records a message sent to the peer.
+    fn on_outbound(&self, id: u8, size: usize);
+}
+
 /// This stream emits _non-empty_ Bytes that start with the normalized message id, so that the first
 /// byte of each message starts from 0. If this stream only supports a single capability, for
 /// example `eth` then the first byte of each message will match
@@ -251,6 +271,9 @@ pub struct P2PStream<S> {
     /// Whether this stream is currently in the process of disconnecting by sending a disconnect
     /// message.
     disconnecting: bool,
+
+    /// Counts the bytes of the messages exchanged, if set.
+    meter: Option<Arc<dyn MessageMeter>>,
 }
 
 impl<S> P2PStream<S> {
@@ -267,6 +290,7 @@ impl<S> P2PStream<S> {
             outgoing_messages: VecDeque::new(),
             outgoing_message_buffer_capacity: MAX_P2P_CAPACITY,
             disconnecting: false,
+            meter: None,
         }
     }
 
@@ -292,6 +316,11 @@ impl<S> P2PStream<S> {
         &self.shared_capabilities
     }
 
+    /// Sets the [`MessageMeter`] that counts the bytes of all messages exchanged from now on.
+    pub fn set_meter(&mut self, meter: Arc<dyn MessageMeter>) {
+        self.meter = Some(meter);
+    }
+
     /// Returns `true` if the stream has outgoing capacity.
     fn has_outgoing_capacity(&self) -> bool {
         self.outgoing_messages.len() < self.outgoing_message_buffer_capacity
@@ -407,6 +436,10 @@ where
                 return Poll::Ready(Some(Err(P2PStreamError::EmptyProtocolMessage)))
             }
 
+            if let Some(meter) = &this.meter {
+                meter.on_inbound(bytes[0], bytes.len());
+            }
+
             // first decode disconnect reasons, because they can be encoded in a variety of forms
             // over the wire, in both snappy compressed and uncompressed forms.
             //
@@ -429,15 +462,9 @@ where
                 }
             }
 
-            // first check that the compressed message length does not exceed the max
-            // payload size
-            let decompressed_len = snap::raw::decompress_len(&bytes[1..])?;
-            if decompressed_len > MAX_PAYLOAD_SIZE {
-                return Poll::Ready(Some(Err(P2PStreamError::MessageTooBig {
-                    message_size: decompressed_len,
-                    max_size: MAX_PAYLOAD_SIZE,
-                })))
-            }
+            // first check that the declared decompressed length is valid, before allocating the
+            // buffer for it
+            let decompressed_len = decompressed_len(&bytes[1..])?;
 
             // create a buffer to hold the decompressed message, adding a byte to the length for
             // the message ID byte, which is the first byte in this buffer
@@ -622,6 +649,9 @@ where
                     let Some(message) = this.outgoing_messages.pop_front() else {
                         break Poll::Ready(Ok(()))
                     };
+                    if let Some(meter) = this.meter.as_ref() {
+                        meter.on_outbound(message[0], message.len());
+                    }
                     if let Err(err) = this.inner.as_mut().start_send(message) {
                         break Poll::Ready(Err(err.into()))
                     }
@@ -642,6 +672,20 @@ where
     }
 }
 
+/// Returns the decompressed length of the snappy compressed message.
+///
+/// The length is declared by the peer in the message header, so it's validated against both the
+/// [`MAX_PAYLOAD_SIZE`] and the length the compressed payload can actually decompress to, because
+/// the buffer for the decompressed message is allocated before the payload is decompressed.
+fn decompressed_len(compressed: &[u8]) -> Result<usize, P2PStreamError> {
+    let decompressed_len = snap::raw::decompress_len(compressed)?;
+    let max_size = compressed.len().saturating_mul(MAX_SNAPPY_EXPANSION).min(MAX_PAYLOAD_SIZE);
+    if decompressed_len > max_size {
+        return Err(P2PStreamError::MessageTooBig { message_size: decompressed_len, max_size })
+    }
+    Ok(decompressed_len)
+}
+
 /// This represents only the reserved `p2p` subprotocol messages.
 #[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -880,6 +924,69 @@ mod tests {
         handle.await.unwrap();
     }
 
+    #[derive(Debug, Default)]
+    struct TestMeter {
+        inbound: std::sync::Mutex<Vec<(u8, usize)>>,
+        outbound: std::sync::Mutex<Vec<(u8, usize)>>,
+    }
+
+    impl MessageMeter for TestMeter {
+        fn on_inbound(&self, id: u8, size: usize) {
+            self.inbound.lock().unwrap().push((id, size));
+        }
+
+        fn on_outbound(&self, id: u8, size: usize) {
+            self.outbound.lock().unwrap().push((id, size));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_meter_counts_compressed_messages() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let local_addr = listener.local_addr().unwrap();
+
+        let server_meter = Arc::new(TestMeter::default());
+        let meter = Arc::clone(&server_meter);
+        let handle = tokio::spawn(async move {
+            let (incoming, _) = listener.accept().await.unwrap();
+            let stream = crate::PassthroughCodec::default().framed(incoming);
+
+            let (server_hello, _) = eth_hello();
+
+            let (mut p2p_stream, _) =
+                UnauthedP2PStream::new(stream).handshake(server_hello).await.unwrap();
+            p2p_stream.set_meter(meter);
+
+            // a subprotocol message that compresses well
+            let mut message = vec![0u8; 1001];
+            message[0] = 0x01;
+            p2p_stream.send(message.into()).await.unwrap();
+        });
+
+        let outgoing = TcpStream::connect(local_addr).await.unwrap();
+        let sink = crate::PassthroughCodec::default().framed(outgoing);
+
+        let (client_hello, _) = eth_hello();
+
+        let (mut p2p_stream, _) =
+            UnauthedP2PStream::new(sink).handshake(client_hello).await.unwrap();
+        let client_meter = Arc::new(TestMeter::default());
+        p2p_stream.set_meter(client_meter.clone());
+
+        let message = p2p_stream.next().await.unwrap().unwrap();
+        assert_eq!(message.len(), 1001);
+        handle.await.unwrap();
+
+        // the message is counted with its id on the wire, and its compressed size
+        let outbound = server_meter.outbound.lock().unwrap().clone();
+        let inbound = client_meter.inbound.lock().unwrap().clone();
+        assert_eq!(outbound.len(), 1);
+        assert_eq!(outbound, inbound);
+        let (id, size) = outbound[0];
+        assert_eq!(id, MAX_RESERVED_MESSAGE_ID + 2);
+        assert!(size < 1001);
+    }
+
     #[tokio::test]
     async fn test_handshake_passthrough() {
         // create a p2p stream and server, then confirm that the two are authed
@@ -983,6 +1090,40 @@ mod tests {
         handle.await.unwrap();
     }
 
+    #[test]
+    fn decompressed_len_is_bounded_by_input() {
+        let payload = alloy_rlp::encode(vec![0u8; 1_000]);
+        let mut encoder = snap::raw::Encoder::new();
+        let compressed = encoder.compress_vec(&payload).unwrap();
+        assert_eq!(decompressed_len(&compressed).unwrap(), payload.len());
+
+        // a few bytes that declare a decompressed length just below the max payload size
+        let mut declared = Vec::new();
+        let mut len = MAX_PAYLOAD_SIZE - 1;
+        while len >= 0x80 {
+            declared.push((len as u8) | 0x80);
+            len >>= 7;
+        }
+        declared.push(len as u8);
+        declared.extend_from_slice(&[0xfe, 0xff, 0xff]);
+        assert_eq!(snap::raw::decompress_len(&declared).unwrap(), MAX_PAYLOAD_SIZE - 1);
+        let Err(P2PStreamError::MessageTooBig { message_size, max_size }) =
+            decompressed_len(&declared)
+        else {
+            panic!("expected declared length to be rejected")
+        };
+        assert_eq!(message_size, MAX_PAYLOAD_SIZE - 1);
+        assert_eq!(max_size, declared.len() * MAX_SNAPPY_EXPANSION);
+
+        // lengths above the max payload size are rejected regardless of the input
+        let mut declared = vec![0x81, 0x80, 0x80, 0x08];
+        declared.resize(MAX_PAYLOAD_SIZE / MAX_SNAPPY_EXPANSION + 1, 0);
+        assert!(matches!(
+            decompressed_len(&declared),
+            Err(P2PStreamError::MessageTooBig { max_size: MAX_PAYLOAD_SIZE, .. })
+        ));
+    }
+
     #[test]
     fn snappy_decode_encode_ping() {
         let snappy_ping = b"\x02\x01\0\xc0";
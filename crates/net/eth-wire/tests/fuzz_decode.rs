@@ -0,0 +1,200 @@
+//! Decoding fuzzing for the messages the `eth-wire` crate receives from peers.
+//!
+//! Every target decodes arbitrary bytes as a message, which must never panic but return an error,
+//! so the peer that sent the message is disconnected. Run a target with
+//! `cargo test-fuzz --test fuzz_decode <target>`.
+//!
+//! The seed corpus in `tests/fuzz_corpus` contains a valid message for each target. The smoke
+//! tests decode the seeds and deterministic mutations of them, so that the targets also run in CI
+//! without a fuzzer.
+
+use alloy_rlp::Decodable;
+use reth_eth_wire::{
+    BlockBodies, EthMessageID, EthNetworkPrimitives, EthVersion, GetBlockHeaders,
+    NewPooledTransactionHashes68, ProtocolMessage, Receipts, RequestPair, Status,
+};
+
+/// All versions of the `eth` protocol the messages are decoded with.
+const VERSIONS: [EthVersion; 4] =
+    [EthVersion::Eth66, EthVersion::Eth67, EthVersion::Eth68, EthVersion::Eth69];
+
+/// Decodes the bytes as a `T`, which must not panic.
+fn decode<T: Decodable>(data: &[u8]) {
+    let _ = T::decode(&mut &data[..]);
+}
+
+/// Decodes the bytes as a protocol message of every version, which must not panic.
+fn decode_message(data: &[u8]) {
+    for version in VERSIONS {
+        let _ = ProtocolMessage::<EthNetworkPrimitives>::decode_message(version, &mut &data[..]);
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod fuzz_decode {
+    use super::*;
+    use test_fuzz::test_fuzz;
+
+    #[test_fuzz]
+    fn fuzz_decode_Status(data: Vec<u8>) {
+        decode::<Status>(&data)
+    }
+
+    #[test_fuzz]
+    fn fuzz_decode_NewPooledTransactionHashes68(data: Vec<u8>) {
+        decode::<NewPooledTransactionHashes68>(&data)
+    }
+
+    #[test_fuzz]
+    fn fuzz_decode_GetBlockHeaders(data: Vec<u8>) {
+        decode::<RequestPair<GetBlockHeaders>>(&data)
+    }
+
+    #[test_fuzz]
+    fn fuzz_decode_BlockBodies(data: Vec<u8>) {
+        decode::<RequestPair<BlockBodies>>(&data)
+    }
+
+    #[test_fuzz]
+    fn fuzz_decode_Receipts(data: Vec<u8>) {
+        decode::<RequestPair<Receipts>>(&data)
+    }
+
+    #[test_fuzz]
+    fn fuzz_decode_ProtocolMessage(data: Vec<u8>) {
+        decode_message(&data)
+    }
+
+    mod smoke {
+        use super::*;
+        use alloy_primitives::hex;
+        use std::path::Path;
+
+        /// Returns the seeds of the corpus of the given target.
+        fn seeds(target: &str) -> Vec<Vec<u8>> {
+            let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fuzz_corpus").join(target);
+            let mut seeds = std::fs::read_dir(&dir)
+                .unwrap_or_else(|err| panic!("failed to read corpus {}: {err}", dir.display()))
+                .map(|entry| {
+                    let path = entry.unwrap().path();
+                    hex::decode(std::fs::read_to_string(&path).unwrap().trim())
+                        .unwrap_or_else(|err| panic!("invalid seed {}: {err}", path.display()))
+                })
+                .collect::<Vec<_>>();
+            assert!(!seeds.is_empty(), "empty corpus {}", dir.display());
+            seeds.sort();
+            seeds
+        }
+
+        /// Returns the seed along with every truncation of it, and the variants in which a single
+        /// byte is replaced by a value that's likely to break the RLP structure, e.g. by declaring
+        /// a huge list or string length.
+        fn mutations(seed: &[u8]) -> impl Iterator<Item = Vec<u8>> + '_ {
+            let truncated = (0..seed.len()).map(|len| seed[..len].to_vec());
+            let replaced = (0..seed.len()).flat_map(move |index| {
+                [0x00, 0x7f, 0x80, 0xb7, 0xbf, 0xc0, 0xf7, 0xff].into_iter().map(move |byte| {
+                    let mut mutated = seed.to_vec();
+                    mutated[index] = byte;
+                    mutated
+                })
+            });
+            std::iter::once(seed.to_vec()).chain(truncated).chain(replaced)
+        }
+
+        /// Runs the fuzz target on the seeds of the corpus and their mutations.
+        fn smoke(target: &str, fuzz: fn(Vec<u8>)) {
+            for seed in seeds(target) {
+                mutations(&seed).for_each(fuzz);
+            }
+        }
+
+        #[test]
+        fn seeds_are_valid() {
+            for seed in seeds("status") {
+                Status::decode(&mut &seed[..]).unwrap();
+            }
+            for seed in seeds("new_pooled_transaction_hashes_68") {
+                NewPooledTransactionHashes68::decode(&mut &seed[..]).unwrap();
+            }
+            for seed in seeds("get_block_headers") {
+                RequestPair::<GetBlockHeaders>::decode(&mut &seed[..]).unwrap();
+            }
+            for seed in seeds("block_bodies") {
+                RequestPair::<BlockBodies>::decode(&mut &seed[..]).unwrap();
+            }
+            for seed in seeds("receipts") {
+                RequestPair::<Receipts>::decode(&mut &seed[..]).unwrap();
+            }
+        }
+
+        #[test]
+        fn smoke_status() {
+            smoke("status", fuzz_decode_Status);
+        }
+
+        #[test]
+        fn smoke_new_pooled_transaction_hashes_68() {
+            smoke("new_pooled_transaction_hashes_68", fuzz_decode_NewPooledTransactionHashes68);
+        }
+
+        #[test]
+        fn smoke_get_block_headers() {
+            smoke("get_block_headers", fuzz_decode_GetBlockHeaders);
+        }
+
+        #[test]
+        fn smoke_block_bodies() {
+            smoke("block_bodies", fuzz_decode_BlockBodies);
+        }
+
+        #[test]
+        fn smoke_receipts() {
+            smoke("receipts", fuzz_decode_Receipts);
+        }
+
+        #[test]
+        fn smoke_protocol_message() {
+            let targets = [
+                ("status", EthMessageID::Status),
+                ("new_pooled_transaction_hashes_68", EthMessageID::NewPooledTransactionHashes),
+                ("get_block_headers", EthMessageID::GetBlockHeaders),
+                ("block_bodies", EthMessageID::BlockBodies),
+                ("receipts", EthMessageID::Receipts),
+            ];
+            for (target, id) in targets {
+                for seed in seeds(target) {
+                    let message = [&[id as u8][..], &seed].concat();
+                    mutations(&message).for_each(fuzz_decode_ProtocolMessage);
+                }
+            }
+        }
+
+        /// Lists and strings that declare a length far beyond the input must be rejected without
+        /// allocating for the declared length.
+        #[test]
+        fn huge_declared_lengths() {
+            for declared in [
+                &[0xbb, 0xff, 0xff, 0xff, 0xff][..],
+                &[0xfb, 0xff, 0xff, 0xff, 0xff],
+                &[0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff],
+                &[0xfb, 0xff, 0xff, 0xff, 0xff, 0x82, 0x04, 0x57, 0xc0],
+            ] {
+                assert!(Status::decode(&mut &declared[..]).is_err());
+                assert!(NewPooledTransactionHashes68::decode(&mut &declared[..]).is_err());
+                assert!(RequestPair::<GetBlockHeaders>::decode(&mut &declared[..]).is_err());
+                assert!(RequestPair::<BlockBodies>::decode(&mut &declared[..]).is_err());
+                assert!(RequestPair::<Receipts>::decode(&mut &declared[..]).is_err());
+
+                let message = [&[EthMessageID::BlockBodies as u8][..], declared].concat();
+                for version in VERSIONS {
+                    assert!(ProtocolMessage::<EthNetworkPrimitives>::decode_message(
+                        version,
+                        &mut &message[..]
+                    )
+                    .is_err());
+                }
+            }
+        }
+    }
+}
@@ -12,6 +12,7 @@
 #![cfg_attr(not(test), warn(unused_crate_dependencies))]
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
 
+mod metrics;
 pub mod net_if;
 
 pub use net_if::{NetInterfaceError, DEFAULT_NET_IF_NAME};
@@ -22,12 +23,15 @@ use std::{
     net::{AddrParseError, IpAddr},
     pin::Pin,
     str::FromStr,
+    sync::LazyLock,
     task::{Context, Poll},
     time::Duration,
 };
 use tracing::{debug, error};
 
-use crate::net_if::resolve_net_if_ip;
+use crate::{metrics::NatMetrics, net_if::resolve_net_if_ip};
+use reth_tasks::shutdown::Shutdown;
+use reth_tokio_util::retry::{RetryError, RetryPolicy};
 #[cfg(feature = "serde")]
 use serde_with::{DeserializeFromStr, SerializeDisplay};
 
@@ -37,6 +41,15 @@ use serde_with::{DeserializeFromStr, SerializeDisplay};
 const EXTERNAL_IP_APIS: &[&str] =
     &["https://ipinfo.io/ip", "https://icanhazip.com", "https://ifconfig.me"];
 
+/// How resolving the external IP via a network request is retried, if none of the
+/// [`EXTERNAL_IP_APIS`] responds.
+const EXTERNAL_IP_RETRY_POLICY: RetryPolicy = RetryPolicy::new(3)
+    .with_initial_backoff(Duration::from_secs(1))
+    .with_attempt_timeout(Duration::from_secs(10));
+
+/// Metrics of resolving the external IP via a network request.
+static NAT_METRICS: LazyLock<NatMetrics> = LazyLock::new(NatMetrics::default);
+
 /// All builtin resolvers.
 #[derive(Debug, Clone, Copy, Eq, PartialEq, Default, Hash)]
 #[cfg_attr(feature = "serde", derive(SerializeDisplay, DeserializeFromStr))]
@@ -124,6 +137,8 @@ pub struct ResolveNatInterval {
     resolver: NatResolver,
     future: Option<Pin<Box<dyn Future<Output = Option<IpAddr>> + Send>>>,
     interval: tokio::time::Interval,
+    /// Stops retrying the resolution once it fires.
+    shutdown: Option<Shutdown>,
 }
 
 impl fmt::Debug for ResolveNatInterval {
@@ -132,13 +147,20 @@ impl fmt::Debug for ResolveNatInterval {
             .field("resolver", &self.resolver)
             .field("future", &self.future.as_ref().map(drop))
             .field("interval", &self.interval)
+            .field("shutdown", &self.shutdown)
             .finish()
     }
 }
 
 impl ResolveNatInterval {
     fn with_interval(resolver: NatResolver, interval: tokio::time::Interval) -> Self {
-        Self { resolver, future: None, interval }
+        Self { resolver, future: None, interval, shutdown: None }
+    }
+
+    /// Sets the shutdown signal, after which failed resolutions are no longer retried.
+    pub fn with_shutdown(mut self, shutdown: Shutdown) -> Self {
+        self.shutdown = Some(shutdown);
+        self
     }
 
     /// Creates a new [`ResolveNatInterval`] that attempts to resolve the public IP with interval of
@@ -175,7 +197,8 @@ impl ResolveNatInterval {
     ///    `None` if the attempt was unsuccessful.
     pub fn poll_tick(&mut self, cx: &mut Context<'_>) -> Poll<Option<IpAddr>> {
         if self.interval.poll_tick(cx).is_ready() {
-            self.future = Some(Box::pin(self.resolver.external_addr()));
+            self.future =
+                Some(Box::pin(external_addr_with_shutdown(self.resolver, self.shutdown.clone())));
         }
 
         if let Some(mut fut) = self.future.take() {
@@ -196,8 +219,19 @@ pub async fn external_ip() -> Option<IpAddr> {
 
 /// Given a [`NatResolver`] attempts to produce an IP address (best effort).
 pub async fn external_addr_with(resolver: NatResolver) -> Option<IpAddr> {
+    external_addr_with_shutdown(resolver, None).await
+}
+
+/// Given a [`NatResolver`] attempts to produce an IP address (best effort), without retrying
+/// failed network requests once the shutdown signal, if any, fired.
+async fn external_addr_with_shutdown(
+    resolver: NatResolver,
+    shutdown: Option<Shutdown>,
+) -> Option<IpAddr> {
     match resolver {
-        NatResolver::Any | NatResolver::Upnp | NatResolver::PublicIp => resolve_external_ip().await,
+        NatResolver::Any | NatResolver::Upnp | NatResolver::PublicIp => {
+            resolve_external_ip(shutdown).await
+        }
         NatResolver::ExternalIp(ip) => Some(ip),
         NatResolver::NetIf => resolve_net_if_ip(DEFAULT_NET_IF_NAME)
             .inspect_err(|err| {
@@ -211,11 +245,26 @@ pub async fn external_addr_with(resolver: NatResolver) -> Option<IpAddr> {
     }
 }
 
-async fn resolve_external_ip() -> Option<IpAddr> {
-    let futures = EXTERNAL_IP_APIS.iter().copied().map(resolve_external_ip_url_res).map(Box::pin);
-    futures_util::future::select_ok(futures)
+async fn resolve_external_ip(shutdown: Option<Shutdown>) -> Option<IpAddr> {
+    let metrics = &*NAT_METRICS;
+    let shutdown = async move {
+        match shutdown {
+            Some(shutdown) => shutdown.await,
+            None => std::future::pending().await,
+        }
+    };
+    EXTERNAL_IP_RETRY_POLICY
+        .retry_until(shutdown, |_| {
+            metrics.external_ip_attempts.increment(1);
+            let futures =
+                EXTERNAL_IP_APIS.iter().copied().map(resolve_external_ip_url_res).map(Box::pin);
+            futures_util::future::select_ok(futures)
+        })
         .await
         .inspect_err(|err| {
+            if matches!(err, RetryError::Exhausted { .. }) {
+                metrics.external_ip_failures.increment(1);
+            }
             debug!(target: "net::nat",
             ?err,
                 external_ip_apis=?EXTERNAL_IP_APIS,
@@ -0,0 +1,14 @@
+//! Metrics of resolving the external IP.
+
+use metrics::Counter;
+use reth_metrics::Metrics;
+
+/// Metrics of resolving the external IP via a network request.
+#[derive(Metrics)]
+#[metrics(scope = "net.nat")]
+pub(crate) struct NatMetrics {
+    /// Number of attempts to resolve the external IP.
+    pub(crate) external_ip_attempts: Counter,
+    /// Number of times the external IP couldn't be resolved after all attempts.
+    pub(crate) external_ip_failures: Counter,
+}
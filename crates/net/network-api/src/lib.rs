@@ -24,7 +24,9 @@ pub mod test_utils;
 pub use alloy_rpc_types_admin::EthProtocolInfo;
 use reth_network_p2p::sync::NetworkSyncUpdater;
 pub use reth_network_p2p::BlockClient;
-pub use reth_network_types::{PeerKind, Reputation, ReputationChangeKind};
+pub use reth_network_types::{
+    MessageClass, PeerBandwidth, PeerKind, Reputation, ReputationChangeKind, TrafficCounters,
+};
 
 pub use downloaders::BlockDownloaderProvider;
 pub use error::NetworkError;
@@ -207,6 +209,10 @@ pub trait Peers: PeersInfo {
         &self,
         peer_id: PeerId,
     ) -> impl Future<Output = Result<Option<Reputation>, NetworkError>> + Send;
+
+    /// Returns the [`PeerStats`] of all peers that bytes were exchanged with, ordered by the total
+    /// number of exchanged bytes, descending.
+    fn get_peer_stats(&self) -> impl Future<Output = Result<Vec<PeerStats>, NetworkError>> + Send;
 }
 
 /// Info about an active peer session.
@@ -236,6 +242,42 @@ pub struct PeerInfo {
     pub session_established: Instant,
     /// The peer's connection kind
     pub kind: PeerKind,
+    /// The reconnect state of the peer, if it's trusted or static
+    pub reconnect: Option<PeerReconnectInfo>,
+}
+
+/// Reconnect state of a trusted or static peer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct PeerReconnectInfo {
+    /// Number of consecutive failed reconnection attempts
+    pub attempts: u32,
+    /// Whether the next reconnect is currently backed off
+    pub backed_off: bool,
+}
+
+/// Bandwidth statistics of a peer, accumulated over all its sessions.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct PeerStats {
+    /// The identifier of the peer
+    pub peer_id: PeerId,
+    /// Whether there's an active session to the peer
+    pub connected: bool,
+    /// Bytes exchanged with the peer
+    pub bandwidth: PeerBandwidth,
+    /// Share of the received bytes that answered our requests for block data, see
+    /// [`PeerBandwidth::usefulness`].
+    pub usefulness: Option<f64>,
+}
+
+impl PeerStats {
+    /// Creates the stats of a peer from its bandwidth.
+    pub fn new(peer_id: PeerId, connected: bool, bandwidth: PeerBandwidth) -> Self {
+        Self { peer_id, connected, bandwidth, usefulness: bandwidth.usefulness() }
+    }
 }
 
 /// The direction of the connection.
@@ -11,7 +11,9 @@ use reth_eth_wire_types::{DisconnectReason, ProtocolVersion};
 use reth_network_peers::NodeRecord;
 use reth_network_types::{PeerKind, Reputation, ReputationChangeKind};
 
-use crate::{NetworkError, NetworkInfo, NetworkStatus, PeerId, PeerInfo, Peers, PeersInfo};
+use crate::{
+    NetworkError, NetworkInfo, NetworkStatus, PeerId, PeerInfo, PeerStats, Peers, PeersInfo,
+};
 
 /// A type that implements all network trait that does nothing.
 ///
@@ -117,4 +119,8 @@ impl Peers for NoopNetwork {
     async fn reputation_by_id(&self, _peer_id: PeerId) -> Result<Option<Reputation>, NetworkError> {
         Ok(None)
     }
+
+    async fn get_peer_stats(&self) -> Result<Vec<PeerStats>, NetworkError> {
+        Ok(vec![])
+    }
 }
@@ -24,6 +24,7 @@ pub use peers::reputation::{Reputation, ReputationChangeKind, ReputationChangeWe
 pub use backoff::BackoffKind;
 pub use peers::{
     addr::PeerAddr,
+    bandwidth::{MessageClass, PeerBandwidth, TrafficCounters},
     kind::PeerKind,
     reputation::{is_banned_reputation, ReputationChangeOutcome, DEFAULT_REPUTATION},
     state::PeerConnectionState,
@@ -0,0 +1,120 @@
+//! Bandwidth accounting of a peer.
+
+/// The class of `eth` messages that exchanged bytes are accounted to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MessageClass {
+    /// Header requests, responses and announcements.
+    Headers,
+    /// Body requests and responses, and block announcements.
+    Bodies,
+    /// Transaction broadcasts, announcements, requests and responses.
+    Transactions,
+    /// Receipt requests and responses.
+    Receipts,
+    /// All other messages, including messages of other capabilities.
+    Other,
+}
+
+impl MessageClass {
+    /// All message classes.
+    pub const ALL: [Self; 5] =
+        [Self::Headers, Self::Bodies, Self::Transactions, Self::Receipts, Self::Other];
+
+    /// Returns the name of the class.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Headers => "headers",
+            Self::Bodies => "bodies",
+            Self::Transactions => "transactions",
+            Self::Receipts => "receipts",
+            Self::Other => "other",
+        }
+    }
+}
+
+/// Number of bytes exchanged with a peer, per [`MessageClass`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TrafficCounters {
+    /// Bytes of [`MessageClass::Headers`] messages.
+    pub headers: u64,
+    /// Bytes of [`MessageClass::Bodies`] messages.
+    pub bodies: u64,
+    /// Bytes of [`MessageClass::Transactions`] messages.
+    pub transactions: u64,
+    /// Bytes of [`MessageClass::Receipts`] messages.
+    pub receipts: u64,
+    /// Bytes of [`MessageClass::Other`] messages.
+    pub other: u64,
+}
+
+impl TrafficCounters {
+    /// Returns the bytes of the given class.
+    pub const fn get(&self, class: MessageClass) -> u64 {
+        match class {
+            MessageClass::Headers => self.headers,
+            MessageClass::Bodies => self.bodies,
+            MessageClass::Transactions => self.transactions,
+            MessageClass::Receipts => self.receipts,
+            MessageClass::Other => self.other,
+        }
+    }
+
+    /// Adds bytes to the given class.
+    pub fn add(&mut self, class: MessageClass, bytes: u64) {
+        let counter = match class {
+            MessageClass::Headers => &mut self.headers,
+            MessageClass::Bodies => &mut self.bodies,
+            MessageClass::Transactions => &mut self.transactions,
+            MessageClass::Receipts => &mut self.receipts,
+            MessageClass::Other => &mut self.other,
+        };
+        *counter = counter.saturating_add(bytes);
+    }
+
+    /// Returns the bytes of all classes.
+    pub fn total(&self) -> u64 {
+        MessageClass::ALL.iter().fold(0u64, |total, class| total.saturating_add(self.get(*class)))
+    }
+
+    /// Adds the bytes of all classes of `other`.
+    pub fn merge(&mut self, other: &Self) {
+        for class in MessageClass::ALL {
+            self.add(class, other.get(class));
+        }
+    }
+}
+
+/// Bytes exchanged with a peer.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct PeerBandwidth {
+    /// Bytes received from the peer.
+    pub inbound: TrafficCounters,
+    /// Bytes sent to the peer.
+    pub outbound: TrafficCounters,
+    /// Bytes of headers and bodies received from the peer that validly answered our requests.
+    pub useful_inbound: u64,
+}
+
+impl PeerBandwidth {
+    /// Returns the bytes exchanged in both directions.
+    pub fn total(&self) -> u64 {
+        self.inbound.total().saturating_add(self.outbound.total())
+    }
+
+    /// Returns the share of the received bytes that answered our requests for block data, if any
+    /// bytes were received.
+    pub fn usefulness(&self) -> Option<f64> {
+        let inbound = self.inbound.total();
+        (inbound > 0).then(|| self.useful_inbound as f64 / inbound as f64)
+    }
+
+    /// Adds the bytes of `other`, e.g. of another session to the peer.
+    pub fn merge(&mut self, other: &Self) {
+        self.inbound.merge(&other.inbound);
+        self.outbound.merge(&other.outbound);
+        self.useful_inbound = self.useful_inbound.saturating_add(other.useful_inbound);
+    }
+}
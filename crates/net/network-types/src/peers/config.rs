@@ -47,6 +47,20 @@ pub struct PeerBackoffDurations {
     /// Maximum total backoff duration.
     #[cfg_attr(feature = "serde", serde(with = "humantime_serde"))]
     pub max: Duration,
+    /// Initial delay before reconnecting to a trusted or static peer.
+    ///
+    /// The delay doubles with every failed reconnection attempt.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default = "default_persistent_low", with = "humantime_serde")
+    )]
+    pub persistent_low: Duration,
+    /// Maximum delay between reconnection attempts to a trusted or static peer.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default = "default_persistent_max", with = "humantime_serde")
+    )]
+    pub persistent_max: Duration,
 }
 
 impl PeerBackoffDurations {
@@ -69,6 +83,15 @@ impl PeerBackoffDurations {
         now + backoff_time.min(self.max)
     }
 
+    /// Returns the delay before the next reconnection attempt to a trusted or static peer.
+    ///
+    /// This grows exponentially with the number of consecutive failed attempts and is capped by
+    /// the configured [`Self::persistent_max`].
+    pub fn persistent_backoff(&self, reconnect_attempts: u32) -> Duration {
+        let factor = 1u32.checked_shl(reconnect_attempts).unwrap_or(u32::MAX);
+        self.persistent_low.saturating_mul(factor).min(self.persistent_max)
+    }
+
     /// Returns durations for testing.
     #[cfg(any(test, feature = "test-utils"))]
     pub const fn test() -> Self {
@@ -77,6 +100,8 @@ impl PeerBackoffDurations {
             medium: Duration::from_millis(200),
             high: Duration::from_millis(200),
             max: Duration::from_millis(200),
+            persistent_low: Duration::from_millis(100),
+            persistent_max: Duration::from_millis(400),
         }
     }
 }
@@ -91,10 +116,21 @@ impl Default for PeerBackoffDurations {
             high: Duration::from_secs(60 * 15),
             // 1h
             max: Duration::from_secs(60 * 60),
+            persistent_low: default_persistent_low(),
+            persistent_max: default_persistent_max(),
         }
     }
 }
 
+const fn default_persistent_low() -> Duration {
+    Duration::from_secs(1)
+}
+
+const fn default_persistent_max() -> Duration {
+    // 2min
+    Duration::from_secs(60 * 2)
+}
+
 /// Tracks stats about connected nodes
 #[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(default))]
@@ -1,9 +1,11 @@
 pub mod addr;
+pub mod bandwidth;
 pub mod config;
 pub mod kind;
 pub mod reputation;
 pub mod state;
 
+pub use bandwidth::{MessageClass, PeerBandwidth, TrafficCounters};
 pub use config::{ConnectionsConfig, PeersConfig};
 pub use reputation::{Reputation, ReputationChange, ReputationChangeKind, ReputationChangeWeights};
 
@@ -35,6 +37,11 @@ pub struct Peer {
     /// Counts number of times the peer was backed off due to a severe
     /// [`BackoffKind`](crate::BackoffKind).
     pub severe_backoff_counter: u8,
+    /// Number of consecutive failed reconnection attempts to this peer, if it is trusted or
+    /// static.
+    ///
+    /// Kept while a session is established, and reset once it's lost.
+    pub reconnect_attempts: u32,
 }
 
 // === impl Peer ===
@@ -66,6 +73,7 @@ impl Peer {
             kind: Default::default(),
             backed_off: false,
             severe_backoff_counter: 0,
+            reconnect_attempts: 0,
         }
     }
 
@@ -135,4 +143,11 @@ impl Peer {
     pub const fn is_static(&self) -> bool {
         matches!(self.kind, PeerKind::Static)
     }
+
+    /// Returns whether this peer is trusted or static, and should therefore always be reconnected
+    /// to.
+    #[inline]
+    pub const fn is_persistent(&self) -> bool {
+        self.is_trusted() || self.is_static()
+    }
 }
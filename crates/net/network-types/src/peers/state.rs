@@ -16,6 +16,9 @@ pub enum PeerConnectionState {
     Out,
     /// Pending outgoing connection.
     PendingOut,
+    /// Pending outgoing reconnect to a trusted or static peer, which doesn't occupy an outbound
+    /// slot.
+    PendingReconnect,
 }
 
 // === impl PeerConnectionState ===
@@ -46,7 +49,7 @@ impl PeerConnectionState {
     /// Returns whether we're currently connected with this peer
     #[inline]
     pub const fn is_connected(&self) -> bool {
-        matches!(self, Self::In | Self::Out | Self::PendingOut)
+        matches!(self, Self::In | Self::Out | Self::PendingOut | Self::PendingReconnect)
     }
 
     /// Returns if there's currently no connection to that peer.
@@ -58,6 +61,6 @@ impl PeerConnectionState {
     /// Returns true if there's currently an outbound dial to that peer.
     #[inline]
     pub const fn is_pending_out(&self) -> bool {
-        matches!(self, Self::PendingOut)
+        matches!(self, Self::PendingOut | Self::PendingReconnect)
     }
 }
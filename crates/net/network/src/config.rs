@@ -6,6 +6,7 @@ use crate::{
     transactions::TransactionsManagerConfig,
     NetworkHandle, NetworkManager,
 };
+use alloy_primitives::Bytes;
 use reth_chainspec::{ChainSpecProvider, EthChainSpec, Hardforks};
 use reth_discv4::{Discv4Config, Discv4ConfigBuilder, NatResolver, DEFAULT_DISCOVERY_ADDRESS};
 use reth_discv5::NetworkStackId;
@@ -17,7 +18,7 @@ use reth_ethereum_forks::{ForkFilter, Head};
 use reth_network_peers::{mainnet_nodes, pk2id, sepolia_nodes, PeerId, TrustedPeer};
 use reth_network_types::{PeersConfig, SessionsConfig};
 use reth_storage_api::{noop::NoopProvider, BlockNumReader, BlockReader, HeaderProvider};
-use reth_tasks::{TaskSpawner, TokioTaskExecutor};
+use reth_tasks::{shutdown::Shutdown, TaskSpawner, TokioTaskExecutor};
 use secp256k1::SECP256K1;
 use std::{collections::HashSet, net::SocketAddr, sync::Arc};
 
@@ -83,6 +84,9 @@ pub struct NetworkConfig<C, N: NetworkPrimitives = EthNetworkPrimitives> {
     pub transactions_manager_config: TransactionsManagerConfig,
     /// The NAT resolver for external IP
     pub nat: Option<NatResolver>,
+    /// The shutdown signal of the task executor, after which the discovery services no longer
+    /// retry failed requests.
+    pub shutdown: Option<Shutdown>,
 }
 
 // === impl NetworkConfig ===
@@ -207,6 +211,10 @@ pub struct NetworkConfigBuilder<N: NetworkPrimitives = EthNetworkPrimitives> {
     transactions_manager_config: TransactionsManagerConfig,
     /// The NAT resolver for external IP
     nat: Option<NatResolver>,
+    /// Custom entries of the local node record, advertised by all discovery services.
+    enr_entries: Vec<(Vec<u8>, Bytes)>,
+    /// The shutdown signal of the task executor.
+    shutdown: Option<Shutdown>,
 }
 
 // === impl NetworkConfigBuilder ===
@@ -239,6 +247,8 @@ impl<N: NetworkPrimitives> NetworkConfigBuilder<N> {
             block_import: None,
             transactions_manager_config: Default::default(),
             nat: None,
+            enr_entries: Vec::new(),
+            shutdown: None,
         }
     }
 
@@ -316,6 +326,13 @@ impl<N: NetworkPrimitives> NetworkConfigBuilder<N> {
         self
     }
 
+    /// Sets the shutdown signal of the task executor, after which the discovery services no
+    /// longer retry failed requests, like DNS lookups or resolving the external IP.
+    pub fn with_shutdown(mut self, shutdown: Shutdown) -> Self {
+        self.shutdown = Some(shutdown);
+        self
+    }
+
     /// Sets a custom config for how sessions are handled.
     pub const fn sessions_config(mut self, config: SessionsConfig) -> Self {
         self.sessions_config = Some(config);
@@ -446,6 +463,13 @@ impl<N: NetworkPrimitives> NetworkConfigBuilder<N> {
         self
     }
 
+    /// Adds a custom entry to the local node record advertised by discv4 and discv5. The value is
+    /// advertised as an RLP byte string.
+    pub fn add_enr_entry(mut self, key: impl Into<Vec<u8>>, value: Bytes) -> Self {
+        self.enr_entries.push((key.into(), value));
+        self
+    }
+
     /// Disables all discovery.
     pub fn disable_discovery(self) -> Self {
         self.disable_discv4_discovery().disable_dns_discovery().disable_nat()
@@ -535,7 +559,7 @@ impl<N: NetworkPrimitives> NetworkConfigBuilder<N> {
         let Self {
             secret_key,
             mut dns_discovery_config,
-            discovery_v4_builder,
+            mut discovery_v4_builder,
             mut discovery_v5_builder,
             boot_nodes,
             discovery_addr,
@@ -551,13 +575,24 @@ impl<N: NetworkPrimitives> NetworkConfigBuilder<N> {
             block_import,
             transactions_manager_config,
             nat,
+            enr_entries,
+            shutdown,
         } = self;
 
+        if let Some(builder) = discovery_v4_builder.as_mut() {
+            for (key, value) in &enr_entries {
+                builder.add_eip868_pair(key.clone(), value);
+            }
+        }
+
         discovery_v5_builder = discovery_v5_builder.map(|mut builder| {
             if let Some(network_stack_id) = NetworkStackId::id(&chain_spec) {
                 let fork_id = chain_spec.latest_fork_id();
                 builder = builder.fork(network_stack_id, fork_id)
             }
+            for (key, value) in enr_entries {
+                builder = builder.add_enr_kv_pair(key, alloy_rlp::encode(value).into());
+            }
 
             builder
         });
@@ -618,6 +653,7 @@ impl<N: NetworkPrimitives> NetworkConfigBuilder<N> {
             tx_gossip_disabled,
             transactions_manager_config,
             nat,
+            shutdown,
         }
     }
 }
@@ -699,4 +735,21 @@ mod tests {
         assert_eq!(status.forkid.hash, genesis_fork_hash);
         assert_eq!(fork_filter.current().hash, genesis_fork_hash);
     }
+
+    #[test]
+    fn test_network_enr_entries() {
+        let value = Bytes::from_static(b"archive");
+        let rlp = Bytes::from(alloy_rlp::encode(&value));
+        let config = builder()
+            .discovery_v5(reth_discv5::Config::builder(DEFAULT_DISCOVERY_ADDRESS))
+            .add_enr_entry(b"role".to_vec(), value)
+            .build_with_noop_provider(MAINNET.clone());
+
+        let discv4 = config.discovery_v4_config.unwrap();
+        assert_eq!(discv4.additional_eip868_rlp_pairs.get(b"role".as_slice()), Some(&rlp));
+
+        let discv5 = config.discovery_v5_config.unwrap();
+        let (enr, ..) = reth_discv5::build_local_enr(&config.secret_key, &discv5);
+        assert_eq!(enr.get_raw_rlp(b"role"), Some(rlp.as_ref()));
+    }
 }
@@ -15,6 +15,7 @@ use reth_ethereum_forks::{EnrForkIdEntry, ForkId};
 use reth_network_api::{DiscoveredEvent, DiscoveryEvent};
 use reth_network_peers::{NodeRecord, PeerId};
 use reth_network_types::PeerAddr;
+use reth_tasks::shutdown::Shutdown;
 use secp256k1::SecretKey;
 use std::{
     collections::VecDeque,
@@ -78,6 +79,7 @@ impl Discovery {
         discv4_config: Option<Discv4Config>,
         discv5_config: Option<reth_discv5::Config>, // contains discv5 listen address
         dns_discovery_config: Option<DnsDiscoveryConfig>,
+        shutdown: Option<Shutdown>,
     ) -> Result<Self, NetworkError> {
         // setup discv4 with the discovery address and tcp port
         let local_enr =
@@ -92,6 +94,9 @@ impl Discovery {
                     },
                 )?;
             let discv4_updates = discv4_service.update_stream();
+            if let Some(shutdown) = shutdown.clone() {
+                discv4_service.set_shutdown(shutdown);
+            }
             // spawn the service
             let discv4_service = discv4_service.spawn();
 
@@ -114,6 +119,9 @@ impl Discovery {
                     Arc::new(DnsResolver::from_system_conf()?),
                     dns_config,
                 );
+                if let Some(shutdown) = shutdown {
+                    service = service.with_shutdown(shutdown);
+                }
                 let dns_discovery_updates = service.node_record_stream();
                 let dns_disc_service = service.spawn();
                 (Some(dns_disc), Some(dns_discovery_updates), Some(dns_disc_service))
@@ -354,6 +362,7 @@ mod tests {
             Default::default(),
             None,
             Default::default(),
+            None,
         )
         .await
         .unwrap();
@@ -384,6 +393,7 @@ mod tests {
             Some(discv4_config),
             Some(discv5_config),
             None,
+            None,
         )
         .await
         .expect("should build discv5 with discv4 downgrade")
@@ -24,7 +24,10 @@ use crate::{
     import::{BlockImport, BlockImportOutcome, BlockValidation},
     listener::ConnectionListener,
     message::{NewBlockMessage, PeerMessage},
-    metrics::{DisconnectMetrics, NetworkMetrics, NETWORK_POOL_TRANSACTIONS_SCOPE},
+    metrics::{
+        DisconnectMetrics, NetworkMetrics, PeerBandwidthMetrics, NETWORK_POOL_TRANSACTIONS_SCOPE,
+        PEER_BANDWIDTH_METRICS_INTERVAL,
+    },
     network::{NetworkHandle, NetworkHandleMessage},
     peers::PeersManager,
     poll_nested_stream_with_budget,
@@ -38,21 +41,22 @@ use crate::{
 use futures::{Future, StreamExt};
 use parking_lot::Mutex;
 use reth_eth_wire::{
-    capability::CapabilityMessage, Capabilities, DisconnectReason, EthNetworkPrimitives,
-    NetworkPrimitives,
+    capability::CapabilityMessage, BlockRangeUpdate, Capabilities, DisconnectReason,
+    EthNetworkPrimitives, NetworkPrimitives,
 };
 use reth_fs_util::{self as fs, FsPathError};
 use reth_metrics::common::mpsc::UnboundedMeteredSender;
 use reth_network_api::{
     events::{PeerEvent, SessionInfo},
     test_utils::PeersHandle,
-    EthProtocolInfo, NetworkEvent, NetworkStatus, PeerInfo, PeerRequest,
+    EthProtocolInfo, NetworkEvent, NetworkStatus, PeerInfo, PeerRequest, PeerStats,
 };
 use reth_network_peers::{NodeRecord, PeerId};
 use reth_network_types::ReputationChangeKind;
 use reth_storage_api::BlockNumReader;
 use reth_tasks::shutdown::GracefulShutdown;
 use reth_tokio_util::EventSender;
+use rustc_hash::FxHashMap;
 use secp256k1::SecretKey;
 use std::{
     net::SocketAddr,
@@ -65,7 +69,10 @@ use std::{
     task::{Context, Poll},
     time::{Duration, Instant},
 };
-use tokio::sync::mpsc::{self, error::TrySendError};
+use tokio::{
+    sync::mpsc::{self, error::TrySendError},
+    time::Interval,
+};
 use tokio_stream::wrappers::UnboundedReceiverStream;
 use tracing::{debug, error, trace, warn};
 
@@ -140,6 +147,10 @@ pub struct NetworkManager<N: NetworkPrimitives = EthNetworkPrimitives> {
     metrics: NetworkMetrics,
     /// Disconnect metrics for the Network
     disconnect_metrics: DisconnectMetrics,
+    /// Bandwidth metrics of the peers that exchanged the most bytes with the node
+    peer_bandwidth_metrics: PeerBandwidthMetrics,
+    /// Interval at which the peer bandwidth metrics are updated.
+    peer_bandwidth_metrics_interval: Interval,
 }
 
 // === impl NetworkManager ===
@@ -216,6 +227,7 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
             tx_gossip_disabled,
             transactions_manager_config: _,
             nat,
+            shutdown,
         } = config;
 
         let peers_manager = PeersManager::new(peers_config);
@@ -250,6 +262,7 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
             discovery_v4_config,
             discovery_v5_config,
             dns_discovery_config,
+            shutdown,
         )
         .await?;
         // need to retrieve the addr here since provided port could be `0`
@@ -259,11 +272,19 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
 
         let num_active_peers = Arc::new(AtomicUsize::new(0));
 
+        // the range of blocks we can serve, announced to eth/69 peers
+        let block_range = BlockRangeUpdate {
+            earliest: client.earliest_block_number().unwrap_or_default(),
+            latest: client.block_number(status.blockhash).ok().flatten().unwrap_or_default(),
+            latest_hash: status.blockhash,
+        };
+
         let sessions = SessionManager::new(
             secret_key,
             sessions_config,
             executor,
             status,
+            block_range,
             hello_message,
             fork_filter,
             extra_protocols,
@@ -309,6 +330,8 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
             num_active_peers,
             metrics: Default::default(),
             disconnect_metrics: Default::default(),
+            peer_bandwidth_metrics: Default::default(),
+            peer_bandwidth_metrics_interval: tokio::time::interval(PEER_BANDWIDTH_METRICS_INTERVAL),
         })
     }
 
@@ -572,6 +595,10 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
                     msg,
                 });
             }
+            PeerMessage::BlockRangeUpdate(range) => {
+                // track the latest block the peer can serve
+                self.swarm.state_mut().update_peer_block(&peer_id, range.latest_hash, range.latest);
+            }
             PeerMessage::SendTransactions(_) => {
                 unreachable!("Not emitted by session")
             }
@@ -649,7 +676,9 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
                 let _ = tx.send(self.status());
             }
             NetworkHandleMessage::StatusUpdate { head } => {
-                if let Some(transition) = self.swarm.sessions_mut().on_status_update(head) {
+                let earliest = self.swarm.state().earliest_block_number();
+                if let Some(transition) = self.swarm.sessions_mut().on_status_update(head, earliest)
+                {
                     self.swarm.state_mut().update_fork_id(transition.current);
                 }
             }
@@ -666,6 +695,9 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
                 let peer_ids = self.swarm.state().peers().peers_by_kind(kind);
                 let _ = tx.send(self.get_peer_infos_by_ids(peer_ids));
             }
+            NetworkHandleMessage::GetPeerStats(tx) => {
+                let _ = tx.send(self.get_peer_stats());
+            }
             NetworkHandleMessage::AddRlpxSubProtocol(proto) => self.add_rlpx_sub_protocol(proto),
             NetworkHandleMessage::GetTransactionsHandle(tx) => {
                 if let Some(ref tx_inner) = self.to_transactions_manager {
@@ -764,7 +796,7 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
                 self.event_sender.notify(NetworkEvent::Peer(PeerEvent::PeerRemoved(peer_id)));
                 self.metrics.tracked_peers.set(self.swarm.state().peers().num_known_peers() as f64);
             }
-            SwarmEvent::SessionClosed { peer_id, remote_addr, error } => {
+            SwarmEvent::SessionClosed { peer_id, remote_addr, error, bandwidth } => {
                 let total_active = self.num_active_peers.fetch_sub(1, Ordering::Relaxed) - 1;
                 self.metrics.connected_peers.set(total_active as f64);
                 trace!(
@@ -776,6 +808,8 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
                     "Session disconnected"
                 );
 
+                self.swarm.state_mut().peers_mut().on_session_bandwidth(peer_id, &bandwidth);
+
                 let mut reason = None;
                 if let Some(ref err) = error {
                     // If the connection was closed due to an error, we report
@@ -928,11 +962,10 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
             .active_sessions()
             .iter()
             .filter_map(|(&peer_id, session)| {
-                self.swarm
-                    .state()
-                    .peers()
-                    .peer_by_id(peer_id)
-                    .map(|(record, kind)| session.peer_info(&record, kind))
+                let peers = self.swarm.state().peers();
+                peers.peer_by_id(peer_id).map(|(record, kind)| {
+                    session.peer_info(&record, kind, peers.reconnect_info(peer_id))
+                })
             })
             .collect()
     }
@@ -942,11 +975,10 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
     /// Returns `None` if there's no active session to the peer.
     fn get_peer_info_by_id(&self, peer_id: PeerId) -> Option<PeerInfo> {
         self.swarm.sessions().active_sessions().get(&peer_id).and_then(|session| {
-            self.swarm
-                .state()
-                .peers()
-                .peer_by_id(peer_id)
-                .map(|(record, kind)| session.peer_info(&record, kind))
+            let peers = self.swarm.state().peers();
+            peers.peer_by_id(peer_id).map(|(record, kind)| {
+                session.peer_info(&record, kind, peers.reconnect_info(peer_id))
+            })
         })
     }
 
@@ -957,6 +989,27 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
         peer_ids.into_iter().filter_map(|peer_id| self.get_peer_info_by_id(peer_id)).collect()
     }
 
+    /// Returns [`PeerStats`] for all peers that exchanged bytes with the node, ordered by the bytes
+    /// exchanged.
+    ///
+    /// The stats include the bytes of the closed sessions and of the active session to the peer.
+    fn get_peer_stats(&self) -> Vec<PeerStats> {
+        let active_sessions = self.swarm.sessions().active_sessions();
+        let mut bandwidth = self.swarm.state().peers().bandwidth().collect::<FxHashMap<_, _>>();
+        for (peer_id, session) in active_sessions {
+            bandwidth.entry(*peer_id).or_default().merge(&session.bandwidth());
+        }
+
+        let mut stats = bandwidth
+            .into_iter()
+            .map(|(peer_id, bandwidth)| {
+                PeerStats::new(peer_id, active_sessions.contains_key(&peer_id), bandwidth)
+            })
+            .collect::<Vec<_>>();
+        stats.sort_unstable_by_key(|stats| std::cmp::Reverse(stats.bandwidth.total()));
+        stats
+    }
+
     /// Updates the metrics for active,established connections
     #[inline]
     fn update_active_connection_metrics(&self) {
@@ -1027,6 +1080,10 @@ impl<N: NetworkPrimitives> Future for NetworkManager<N> {
 
         let this = self.get_mut();
 
+        if this.peer_bandwidth_metrics_interval.poll_tick(cx).is_ready() {
+            this.peer_bandwidth_metrics.update(&this.get_peer_stats());
+        }
+
         // poll new block imports (expected to be a noop for POS)
         while let Poll::Ready(outcome) = this.block_import.poll(cx) {
             this.on_block_import_result(outcome);
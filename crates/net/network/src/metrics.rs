@@ -4,7 +4,9 @@ use reth_metrics::{
     metrics::{Counter, Gauge},
     Metrics,
 };
+use reth_network_api::{PeerBandwidth, PeerStats};
 use reth_primitives::TxType;
+use std::time::Duration;
 
 /// Scope for monitoring transactions sent from the manager to the tx manager
 pub(crate) const NETWORK_POOL_TRANSACTIONS_SCOPE: &str = "network.pool.transactions";
@@ -113,12 +115,25 @@ pub struct TransactionsManagerMetrics {
     /// seen by that peer.
     pub(crate) occurrences_of_transaction_already_seen_by_peer: Counter,
 
+    /* -- New peer announcements -- */
+    /// Number of transaction hashes that are yet to be announced to new peers.
+    pub(crate) new_peer_announcement_backlog: Gauge,
+    /// Number of transaction hashes that are yet to be announced to a new peer, recorded per peer
+    /// on every announcement tick.
+    pub(crate) new_peer_announcement_backlog_per_peer: Histogram,
+
     /* -- Freq txns already in pool -- */
     /// Total number of times a hash is announced that is already in the local pool.
     pub(crate) occurrences_hashes_already_in_pool: Counter,
     /// Total number of times a transaction is sent that is already in the local pool.
     pub(crate) occurrences_transactions_already_in_pool: Counter,
 
+    /* -- Freq txns recently rejected by pool -- */
+    /// Total number of times a hash is announced that was recently rejected by the local pool.
+    pub(crate) occurrences_hashes_rejected_by_pool: Counter,
+    /// Total number of times a transaction is sent that was recently rejected by the local pool.
+    pub(crate) occurrences_transactions_rejected_by_pool: Counter,
+
     /* ================ POOL IMPORTS ================ */
     /// Number of transactions about to be imported into the pool.
     pub(crate) pending_pool_imports: Gauge,
@@ -408,3 +423,116 @@ impl AnnouncedTxTypesMetrics {
         self.eip7702.record(tx_types_counter.eip7702 as f64);
     }
 }
+
+/// Interval at which the bandwidth metrics of the peers are updated.
+pub(crate) const PEER_BANDWIDTH_METRICS_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Number of peers whose bandwidth is recorded with their own label.
+pub(crate) const PEER_BANDWIDTH_TOP_PEERS: usize = 10;
+
+/// Bandwidth of the peers that exchanged the most bytes with the node.
+///
+/// To bound the number of series, peers are labeled by their `rank` (`1` to
+/// [`PEER_BANDWIDTH_TOP_PEERS`]) instead of their id, which matches the order of
+/// `admin_peerStats`. The bandwidth of all other peers is aggregated with the rank `other`.
+#[derive(Debug)]
+pub(crate) struct PeerBandwidthMetrics {
+    /// Metrics of the top peers, by rank.
+    top: Vec<RankedPeerBandwidthMetrics>,
+    /// Aggregated metrics of all other peers.
+    other: RankedPeerBandwidthMetrics,
+}
+
+impl PeerBandwidthMetrics {
+    /// Creates the metrics for the given number of top peers.
+    pub(crate) fn new(top_peers: usize) -> Self {
+        let top = (1..=top_peers)
+            .map(|rank| RankedPeerBandwidthMetrics::new_with_labels(&[("rank", rank.to_string())]))
+            .collect();
+        let other = RankedPeerBandwidthMetrics::new_with_labels(&[("rank", "other")]);
+        Self { top, other }
+    }
+
+    /// Updates the metrics with the stats of all peers, ordered by the bytes exchanged.
+    pub(crate) fn update(&self, stats: &[PeerStats]) {
+        let (top, other) = split_top_peers(stats, self.top.len());
+        for (rank, metrics) in self.top.iter().enumerate() {
+            metrics.set(&top.get(rank).map(|stats| stats.bandwidth).unwrap_or_default());
+        }
+        self.other.set(&other);
+    }
+}
+
+impl Default for PeerBandwidthMetrics {
+    fn default() -> Self {
+        Self::new(PEER_BANDWIDTH_TOP_PEERS)
+    }
+}
+
+/// Bandwidth of a single rank of [`PeerBandwidthMetrics`].
+#[derive(Metrics)]
+#[metrics(scope = "network.peer_bandwidth")]
+pub(crate) struct RankedPeerBandwidthMetrics {
+    /// Number of bytes received from the peer
+    inbound_bytes: Gauge,
+    /// Number of bytes sent to the peer
+    outbound_bytes: Gauge,
+    /// Number of bytes of headers and bodies received from the peer that answered our requests
+    useful_inbound_bytes: Gauge,
+}
+
+impl RankedPeerBandwidthMetrics {
+    fn set(&self, bandwidth: &PeerBandwidth) {
+        self.inbound_bytes.set(bandwidth.inbound.total() as f64);
+        self.outbound_bytes.set(bandwidth.outbound.total() as f64);
+        self.useful_inbound_bytes.set(bandwidth.useful_inbound as f64);
+    }
+}
+
+/// Splits the stats, ordered by the bytes exchanged, into the first `top_peers` entries and the
+/// aggregated bandwidth of all remaining peers.
+pub(crate) fn split_top_peers(
+    stats: &[PeerStats],
+    top_peers: usize,
+) -> (&[PeerStats], PeerBandwidth) {
+    let (top, rest) = stats.split_at(top_peers.min(stats.len()));
+    let other = rest.iter().fold(PeerBandwidth::default(), |mut other, stats| {
+        other.merge(&stats.bandwidth);
+        other
+    });
+    (top, other)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_network_api::MessageClass;
+    use reth_network_peers::PeerId;
+
+    fn stats(inbound: u64) -> PeerStats {
+        let mut bandwidth = PeerBandwidth::default();
+        bandwidth.inbound.add(MessageClass::Headers, inbound);
+        bandwidth.useful_inbound = inbound / 2;
+        PeerStats::new(PeerId::random(), true, bandwidth)
+    }
+
+    #[test]
+    fn test_split_top_peers() {
+        let stats = (1..=5).rev().map(|i| stats(i * 100)).collect::<Vec<_>>();
+
+        let (top, other) = split_top_peers(&stats, 3);
+        assert_eq!(top, &stats[..3]);
+        assert_eq!(other.inbound.headers, 200 + 100);
+        assert_eq!(other.useful_inbound, 100 + 50);
+        assert_eq!(other.outbound.total(), 0);
+
+        // fewer peers than ranks
+        let (top, other) = split_top_peers(&stats, 10);
+        assert_eq!(top, &stats[..]);
+        assert_eq!(other, PeerBandwidth::default());
+
+        let (top, other) = split_top_peers(&stats, 0);
+        assert!(top.is_empty());
+        assert_eq!(other.inbound.total(), 1500);
+    }
+}
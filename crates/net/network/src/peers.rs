@@ -1,6 +1,7 @@
 //! Peer related implementations
 
 use crate::{
+    cache::LruMap,
     error::SessionError,
     session::{Direction, PendingSessionHandshakeError},
     swarm::NetworkConnectionState,
@@ -9,14 +10,17 @@ use futures::StreamExt;
 use reth_eth_wire::{errors::EthStreamError, DisconnectReason};
 use reth_ethereum_forks::ForkId;
 use reth_net_banlist::BanList;
-use reth_network_api::test_utils::{PeerCommand, PeersHandle};
+use reth_network_api::{
+    test_utils::{PeerCommand, PeersHandle},
+    PeerReconnectInfo,
+};
 use reth_network_peers::{NodeRecord, PeerId};
 use reth_network_types::{
     peers::{
         config::PeerBackoffDurations,
-        reputation::{DEFAULT_REPUTATION, MAX_TRUSTED_PEER_REPUTATION_CHANGE},
+        reputation::{BANNED_REPUTATION, DEFAULT_REPUTATION, MAX_TRUSTED_PEER_REPUTATION_CHANGE},
     },
-    ConnectionsConfig, Peer, PeerAddr, PeerConnectionState, PeerKind, PeersConfig,
+    ConnectionsConfig, Peer, PeerAddr, PeerBandwidth, PeerConnectionState, PeerKind, PeersConfig,
     ReputationChangeKind, ReputationChangeOutcome, ReputationChangeWeights,
 };
 use std::{
@@ -35,6 +39,9 @@ use tokio::{
 use tokio_stream::wrappers::UnboundedReceiverStream;
 use tracing::{trace, warn};
 
+/// Maximum number of peers the bytes exchanged with are remembered for.
+const MAX_PEER_BANDWIDTH_ENTRIES: u32 = 4096;
+
 /// Maintains the state of _all_ the peers known to the network.
 ///
 /// This is supposed to be owned by the network itself, but can be reached via the [`PeersHandle`].
@@ -84,6 +91,10 @@ pub struct PeersManager {
     net_connection_state: NetworkConnectionState,
     /// How long to temporarily ban ip on an incoming connection attempt.
     incoming_ip_throttle_duration: Duration,
+    /// Bytes exchanged in the closed sessions to the peers that were most recently disconnected.
+    ///
+    /// This is kept apart from [`Self::peers`], so that it outlives the removal of a peer.
+    bandwidth: LruMap<PeerId, PeerBandwidth>,
 }
 
 impl PeersManager {
@@ -150,6 +161,7 @@ impl PeersManager {
             max_backoff_count,
             net_connection_state: NetworkConnectionState::default(),
             incoming_ip_throttle_duration,
+            bandwidth: LruMap::new(MAX_PEER_BANDWIDTH_ENTRIES),
         }
     }
 
@@ -191,6 +203,14 @@ impl PeersManager {
         })
     }
 
+    /// Returns the [`PeerReconnectInfo`] for the given peer id, if it's trusted or static
+    pub(crate) fn reconnect_info(&self, peer_id: PeerId) -> Option<PeerReconnectInfo> {
+        self.peers.get(&peer_id).filter(|peer| peer.is_persistent()).map(|peer| PeerReconnectInfo {
+            attempts: peer.reconnect_attempts,
+            backed_off: peer.backed_off,
+        })
+    }
+
     /// Returns an iterator over all peer ids for peers with the given kind
     pub(crate) fn peers_by_kind(&self, kind: PeerKind) -> impl Iterator<Item = PeerId> + '_ {
         self.peers.iter().filter_map(move |(peer_id, peer)| (peer.kind == kind).then_some(*peer_id))
@@ -371,16 +391,25 @@ impl PeersManager {
     /// Bans the peer temporarily with the configured ban timeout
     fn ban_peer(&mut self, peer_id: PeerId) {
         let mut ban_duration = self.ban_duration;
+        let mut is_persistent = false;
         if let Some(peer) = self.peers.get(&peer_id) {
-            if peer.is_trusted() || peer.is_static() {
+            if peer.is_persistent() {
                 // For misbehaving trusted or static peers, we provide a bit more leeway when
                 // penalizing them.
                 ban_duration = self.backoff_durations.low / 2;
+                is_persistent = true;
             }
         }
 
-        self.ban_list.ban_peer_until(peer_id, std::time::Instant::now() + ban_duration);
+        let until = std::time::Instant::now() + ban_duration;
+        self.ban_list.ban_peer_until(peer_id, until);
         self.queued_actions.push_back(PeerAction::BanPeer { peer_id });
+
+        if is_persistent {
+            // trusted and static peers are kept in the set and reconnected to, so the ban is
+            // enforced by not dialing them until it expires
+            self.backoff_peer_until(peer_id, until);
+        }
     }
 
     /// Bans the IP temporarily with the configured ban timeout
@@ -438,6 +467,22 @@ impl PeersManager {
         self.peers.get(peer_id).map(|peer| peer.reputation)
     }
 
+    /// Adds the bytes exchanged in a closed session to the totals of the peer.
+    pub(crate) fn on_session_bandwidth(&mut self, peer_id: PeerId, bandwidth: &PeerBandwidth) {
+        if bandwidth.total() == 0 {
+            return
+        }
+        if let Some(total) = self.bandwidth.get_or_insert(peer_id, Default::default) {
+            total.merge(bandwidth);
+        }
+    }
+
+    /// Returns the bytes exchanged in the closed sessions of the peers that were most recently
+    /// disconnected, including peers that were removed since.
+    pub(crate) fn bandwidth(&self) -> impl Iterator<Item = (PeerId, PeerBandwidth)> + '_ {
+        self.bandwidth.iter().map(|(peer_id, bandwidth)| (*peer_id, *bandwidth))
+    }
+
     /// Apply the corresponding reputation change to the given peer.
     ///
     /// If the peer is a trusted peer, it will be exempt from reputation slashing for certain
@@ -467,6 +512,14 @@ impl PeersManager {
                         // this caps the reputation change to the maximum allowed for trusted peers
                         reputation_change = MAX_TRUSTED_PEER_REPUTATION_CHANGE;
                     }
+
+                    if peer.is_trusted() {
+                        // trusted peers are only banned for fatal protocol violations, see
+                        // `on_connection_failure`, so their reputation never drops below the
+                        // banned threshold
+                        reputation_change = reputation_change
+                            .max(BANNED_REPUTATION.saturating_sub(peer.reputation));
+                    }
                 }
                 peer.apply_reputation(reputation_change)
             }
@@ -525,7 +578,11 @@ impl PeersManager {
                     // session to that peer
                     entry.get_mut().severe_backoff_counter = 0;
                     entry.get_mut().state = PeerConnectionState::Idle;
-                    return
+                    entry.get_mut().reconnect_attempts = 0;
+                    if !entry.get().is_persistent() {
+                        return
+                    }
+                    // trusted and static peers are reconnected to right away
                 }
             }
             Entry::Vacant(_) => return,
@@ -592,8 +649,9 @@ impl PeersManager {
             // issues.
             if let Entry::Occupied(mut entry) = self.peers.entry(*peer_id) {
                 self.connection_info.decr_state(entry.get().state);
-                // only remove if the peer is not trusted
-                if entry.get().is_trusted() {
+                // only remove if the peer is not trusted or static, those are reconnected to once
+                // the ban expired
+                if entry.get().is_persistent() {
                     entry.get_mut().state = PeerConnectionState::Idle;
                 } else {
                     entry.remove();
@@ -634,12 +692,24 @@ impl PeersManager {
                     peer.reputation = peer.reputation.saturating_add(reputation_change.as_i32());
                 };
 
+                // a lost session starts a new series of reconnection attempts
+                if !peer.state.is_unconnected() && !peer.state.is_pending_out() {
+                    peer.reconnect_attempts = 0;
+                }
                 self.connection_info.decr_state(peer.state);
                 peer.state = PeerConnectionState::Idle;
 
-                if peer.severe_backoff_counter > self.max_backoff_count && !peer.is_trusted() {
+                if peer.is_persistent() {
+                    // trusted and static peers are never given up on, instead they're reconnected
+                    // to with an exponential backoff
+                    backoff_until = Some(
+                        std::time::Instant::now() +
+                            self.backoff_durations.persistent_backoff(peer.reconnect_attempts),
+                    );
+                    peer.reconnect_attempts = peer.reconnect_attempts.saturating_add(1);
+                } else if peer.severe_backoff_counter > self.max_backoff_count {
                     // mark peer for removal if it has been backoff too many times and is _not_
-                    // trusted
+                    // trusted or static
                     remove_peer = true;
                 }
             }
@@ -806,12 +876,25 @@ impl PeersManager {
                 peer.state = PeerConnectionState::PendingOut;
                 peer.fork_id = fork_id;
                 entry.insert(peer);
-                self.queued_actions
-                    .push_back(PeerAction::Connect { peer_id, remote_addr: addr.tcp() });
             }
-            _ => return,
+            Entry::Occupied(mut entry) => {
+                let peer = entry.get_mut();
+                if !peer.state.is_unconnected() {
+                    return
+                }
+                trace!(target: "net::peers", ?peer_id, addr=?addr.tcp(), "connects known node");
+                peer.kind = kind;
+                peer.addr = addr;
+                peer.state = PeerConnectionState::PendingOut;
+                // an explicit dial request overrides any pending backoff
+                peer.backed_off = false;
+                self.backed_off_peers.remove(&peer_id);
+            }
         }
 
+        self.connection_info.inc_pending_out();
+        self.queued_actions.push_back(PeerAction::Connect { peer_id, remote_addr: addr.tcp() });
+
         if kind.is_trusted() {
             self.trusted_peer_ids.insert(peer_id);
         }
@@ -881,6 +964,24 @@ impl PeersManager {
             return
         }
 
+        // trusted and static peers are always reconnected to, the dials are counted separately so
+        // they don't take the outbound slots of other peers
+        let trusted_nodes_only = self.trusted_nodes_only;
+        for (peer_id, peer) in self.peers.iter_mut().filter(|(_, peer)| {
+            peer.is_persistent() &&
+                !peer.is_backed_off() &&
+                !peer.is_banned() &&
+                peer.state.is_unconnected() &&
+                (!trusted_nodes_only || peer.is_trusted())
+        }) {
+            trace!(target: "net::peers", ?peer_id, addr=?peer.addr, reconnect_attempts=peer.reconnect_attempts, "schedule persistent outbound connection");
+
+            peer.state = PeerConnectionState::PendingReconnect;
+            self.connection_info.inc_pending_reconnect();
+            self.queued_actions
+                .push_back(PeerAction::Connect { peer_id: *peer_id, remote_addr: peer.addr.tcp() });
+        }
+
         // as long as there are slots available fill them with the best peers
         while self.connection_info.has_out_capacity() {
             let action = {
@@ -993,6 +1094,9 @@ pub struct ConnectionInfo {
     num_outbound: usize,
     /// Counter for pending outbound connections.
     num_pending_out: usize,
+    /// Counter for pending reconnects to trusted and static peers, which don't occupy outbound
+    /// slots.
+    num_pending_reconnects: usize,
     /// Counter for currently occupied slots for active inbound connections.
     num_inbound: usize,
     /// Counter for pending inbound connections.
@@ -1006,7 +1110,14 @@ pub struct ConnectionInfo {
 impl ConnectionInfo {
     /// Returns a new [`ConnectionInfo`] with the given config.
     const fn new(config: ConnectionsConfig) -> Self {
-        Self { config, num_outbound: 0, num_pending_out: 0, num_inbound: 0, num_pending_in: 0 }
+        Self {
+            config,
+            num_outbound: 0,
+            num_pending_out: 0,
+            num_pending_reconnects: 0,
+            num_inbound: 0,
+            num_pending_in: 0,
+        }
     }
 
     ///  Returns `true` if there's still capacity to perform an outgoing connection.
@@ -1031,6 +1142,7 @@ impl ConnectionInfo {
             PeerConnectionState::DisconnectingIn | PeerConnectionState::In => self.decr_in(),
             PeerConnectionState::DisconnectingOut | PeerConnectionState::Out => self.decr_out(),
             PeerConnectionState::PendingOut => self.decr_pending_out(),
+            PeerConnectionState::PendingReconnect => self.decr_pending_reconnect(),
         }
     }
 
@@ -1046,6 +1158,10 @@ impl ConnectionInfo {
         self.num_pending_out += 1;
     }
 
+    fn inc_pending_reconnect(&mut self) {
+        self.num_pending_reconnects += 1;
+    }
+
     fn inc_in(&mut self) {
         self.num_inbound += 1;
     }
@@ -1062,6 +1178,10 @@ impl ConnectionInfo {
         self.num_pending_out -= 1;
     }
 
+    fn decr_pending_reconnect(&mut self) {
+        self.num_pending_reconnects -= 1;
+    }
+
     fn decr_pending_in(&mut self) {
         self.num_pending_in -= 1;
     }
@@ -1149,7 +1269,7 @@ mod tests {
     use reth_network_api::Direction;
     use reth_network_peers::{PeerId, TrustedPeer};
     use reth_network_types::{
-        peers::reputation::DEFAULT_REPUTATION, BackoffKind, ReputationChangeKind,
+        peers::reputation::DEFAULT_REPUTATION, BackoffKind, PeerKind, ReputationChangeKind,
     };
     use std::{
         future::{poll_fn, Future},
@@ -1964,6 +2084,29 @@ mod tests {
         assert!(!peers.peers.contains_key(&peer));
     }
 
+    #[tokio::test]
+    async fn test_bandwidth_outlives_removed_peer() {
+        let peer = PeerId::random();
+        let socket_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 1, 2)), 8008);
+        let mut peers = PeersManager::default();
+        peers.add_peer(peer, PeerAddr::from_tcp(socket_addr), None);
+
+        let mut bandwidth = PeerBandwidth::default();
+        bandwidth.inbound.headers = 100;
+        bandwidth.useful_inbound = 50;
+        peers.on_session_bandwidth(peer, &bandwidth);
+        peers.on_session_bandwidth(peer, &bandwidth);
+
+        peers.remove_peer(peer);
+        assert!(!peers.peers.contains_key(&peer));
+
+        let totals = peers.bandwidth().collect::<Vec<_>>();
+        assert_eq!(totals.len(), 1);
+        assert_eq!(totals[0].0, peer);
+        assert_eq!(totals[0].1.inbound.headers, 200);
+        assert_eq!(totals[0].1.useful_inbound, 100);
+    }
+
     #[tokio::test]
     async fn test_fatal_outgoing_connection_error_trusted() {
         let peer = PeerId::random();
@@ -2609,4 +2752,166 @@ mod tests {
         assert_eq!(record.tcp_addr(), socket_addr);
         assert_eq!(record.udp_addr(), socket_addr);
     }
+
+    #[tokio::test]
+    async fn test_persistent_peer_reconnect_backoff() {
+        let peer = PeerId::random();
+        let socket_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 1, 2)), 8008);
+        let mut peers = PeersManager::new(PeersConfig::test());
+        peers.add_peer_kind(peer, PeerKind::Static, PeerAddr::from_tcp(socket_addr), None);
+
+        match event!(peers) {
+            PeerAction::PeerAdded(peer_id) => assert_eq!(peer_id, peer),
+            action => unreachable!("{action:?}"),
+        }
+
+        let durations = peers.backoff_durations;
+        let mut expected_backoffs = vec![];
+        for attempt in 0..5 {
+            match event!(peers) {
+                PeerAction::Connect { peer_id, remote_addr } => {
+                    assert_eq!(peer_id, peer);
+                    assert_eq!(remote_addr, socket_addr);
+                }
+                action => unreachable!("{action:?}"),
+            }
+
+            let now = std::time::Instant::now();
+            peers.on_outgoing_connection_failure(
+                &socket_addr,
+                &peer,
+                &io::Error::new(io::ErrorKind::ConnectionRefused, ""),
+            );
+
+            // the peer is never removed and backed off exponentially
+            let p = peers.peers.get(&peer).unwrap();
+            assert!(p.is_backed_off());
+            assert_eq!(p.reconnect_attempts, attempt + 1);
+            let backoff = *peers.backed_off_peers.get(&peer).unwrap() - now;
+            let expected = durations.persistent_backoff(attempt);
+            assert!(backoff >= expected && backoff < expected + Duration::from_millis(50));
+            expected_backoffs.push(expected);
+        }
+
+        assert_eq!(
+            expected_backoffs,
+            vec![
+                Duration::from_millis(100),
+                Duration::from_millis(200),
+                Duration::from_millis(400),
+                Duration::from_millis(400),
+                Duration::from_millis(400),
+            ]
+        );
+
+        // the attempts are kept while the session is established
+        match event!(peers) {
+            PeerAction::Connect { peer_id, .. } => assert_eq!(peer_id, peer),
+            action => unreachable!("{action:?}"),
+        }
+        peers.on_active_outgoing_established(peer);
+        assert_eq!(peers.peers.get(&peer).unwrap().reconnect_attempts, 5);
+
+        // and a lost session starts over
+        peers.on_active_session_dropped(
+            &socket_addr,
+            &peer,
+            &EthStreamError::P2PStreamError(P2PStreamError::Disconnected(
+                DisconnectReason::UselessPeer,
+            )),
+        );
+        assert_eq!(peers.peers.get(&peer).unwrap().reconnect_attempts, 1);
+        let backoff = *peers.backed_off_peers.get(&peer).unwrap() - std::time::Instant::now();
+        assert!(backoff <= durations.persistent_backoff(0));
+    }
+
+    #[tokio::test]
+    async fn test_persistent_peer_ignores_outbound_slots() {
+        let peer = PeerId::random();
+        let socket_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 1, 2)), 8008);
+        let mut config = PeersConfig::test();
+        config.connection_info.max_outbound = 0;
+        let mut peers = PeersManager::new(config);
+
+        // basic peers are not dialed without outbound slots
+        let basic = PeerId::random();
+        peers.add_peer(basic, PeerAddr::from_tcp(socket_addr), None);
+        peers.add_trusted_peer(peer, PeerAddr::from_tcp(socket_addr));
+
+        let mut connected = vec![];
+        while connected.is_empty() {
+            match event!(peers) {
+                PeerAction::PeerAdded(_) => {}
+                PeerAction::Connect { peer_id, .. } => connected.push(peer_id),
+                action => unreachable!("{action:?}"),
+            }
+        }
+        assert_eq!(connected, vec![peer]);
+        assert_eq!(peers.peers.get(&basic).unwrap().state, PeerConnectionState::Idle);
+
+        // the reconnect is counted separately from the outbound dials
+        assert_eq!(peers.peers.get(&peer).unwrap().state, PeerConnectionState::PendingReconnect);
+        assert_eq!(peers.connection_info.num_pending_out, 0);
+        assert_eq!(peers.connection_info.num_pending_reconnects, 1);
+
+        peers.on_active_outgoing_established(peer);
+        assert_eq!(peers.connection_info.num_pending_reconnects, 0);
+        assert_eq!(peers.connection_info.num_outbound, 1);
+    }
+
+    #[tokio::test]
+    async fn test_trusted_peer_reputation_ban() {
+        let peer = PeerId::random();
+        let socket_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 1, 2)), 8008);
+        let mut peers = PeersManager::new(PeersConfig::test());
+        peers.add_trusted_peer(peer, PeerAddr::from_tcp(socket_addr));
+
+        // bad messages can't get a trusted peer banned
+        for _ in 0..100 {
+            peers.apply_reputation_change(&peer, ReputationChangeKind::BadMessage);
+        }
+        let p = peers.peers.get(&peer).unwrap();
+        assert!(!p.is_banned());
+        assert!(!peers
+            .queued_actions
+            .iter()
+            .any(|action| matches!(action, PeerAction::BanPeer { .. })));
+
+        // but fatal protocol violations are respected
+        peers.peers.get_mut(&peer).unwrap().state = PeerConnectionState::PendingOut;
+        peers.connection_info.inc_pending_out();
+        let err = PendingSessionHandshakeError::Eth(EthStreamError::EthHandshakeError(
+            EthHandshakeError::NonStatusMessageInHandshake,
+        ));
+        peers.on_outgoing_pending_session_dropped(&socket_addr, &peer, &err);
+
+        assert!(peers.ban_list.is_banned_peer(&peer));
+        assert!(peers.peers.get(&peer).unwrap().is_backed_off());
+        assert!(peers
+            .queued_actions
+            .iter()
+            .any(|action| matches!(action, PeerAction::BanPeer { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_connect_known_peer() {
+        let peer = PeerId::random();
+        let socket_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 1, 2)), 8008);
+        let mut config = PeersConfig::test();
+        config.connection_info.max_outbound = 0;
+        let mut peers = PeersManager::new(config);
+        peers.add_peer(peer, PeerAddr::from_tcp(socket_addr), None);
+        peers.backoff_peer_until(peer, std::time::Instant::now() + Duration::from_secs(60));
+
+        // explicitly connecting upgrades the peer and dials it immediately
+        peers.add_and_connect_kind(peer, PeerKind::Static, PeerAddr::from_tcp(socket_addr), None);
+        let p = peers.peers.get(&peer).unwrap();
+        assert!(p.is_static());
+        assert!(!p.is_backed_off());
+        assert_eq!(p.state, PeerConnectionState::PendingOut);
+        assert_eq!(peers.num_pending_outbound_connections(), 1);
+        assert!(peers.queued_actions.iter().any(
+            |action| matches!(action, PeerAction::Connect { peer_id, .. } if *peer_id == peer)
+        ));
+    }
 }
@@ -14,11 +14,14 @@ use std::{
 use crate::{
     message::{NewBlockMessage, PeerMessage, PeerResponse, PeerResponseResult},
     session::{
+        bandwidth::SessionBandwidth,
         conn::EthRlpxConnection,
         handle::{ActiveSessionMessage, SessionCommand},
         SessionId,
     },
 };
+use alloy_consensus::BlockHeader;
+use alloy_eips::BlockHashOrNumber;
 use alloy_primitives::Sealable;
 use futures::{stream::Fuse, SinkExt, StreamExt};
 use metrics::Gauge;
@@ -26,7 +29,8 @@ use reth_eth_wire::{
     capability::RawCapabilityMessage,
     errors::{EthHandshakeError, EthStreamError},
     message::{EthBroadcastMessage, RequestPair},
-    Capabilities, DisconnectP2P, DisconnectReason, EthMessage, NetworkPrimitives,
+    Capabilities, DisconnectP2P, DisconnectReason, EthMessage, GetBlockBodies, GetBlockHeaders,
+    NetworkPrimitives,
 };
 use reth_metrics::common::mpsc::MeteredPollSender;
 use reth_network_api::PeerRequest;
@@ -101,6 +105,8 @@ pub(crate) struct ActiveSession<N: NetworkPrimitives> {
     /// Used to reserve a slot to guarantee that the termination message is delivered
     pub(crate) terminate_message:
         Option<(PollSender<ActiveSessionMessage<N>>, ActiveSessionMessage<N>)>,
+    /// Bytes exchanged with the peer, shared with the [`SessionManager`](super::SessionManager).
+    pub(crate) bandwidth: Arc<SessionBandwidth>,
 }
 
 impl<N: NetworkPrimitives> ActiveSession<N> {
@@ -211,12 +217,28 @@ impl<N: NetworkPrimitives> ActiveSession<N> {
                 on_request!(req, BlockHeaders, GetBlockHeaders)
             }
             EthMessage::BlockHeaders(resp) => {
+                let valid = self.inflight_requests.get(&resp.request_id).is_some_and(|req| {
+                    matches!(
+                        &req.request,
+                        RequestState::Waiting(PeerRequest::GetBlockHeaders { request, .. })
+                            if is_valid_headers_response(request, &resp.message.0)
+                    )
+                });
+                self.bandwidth.on_block_data_response(valid);
                 on_response!(resp, GetBlockHeaders)
             }
             EthMessage::GetBlockBodies(req) => {
                 on_request!(req, BlockBodies, GetBlockBodies)
             }
             EthMessage::BlockBodies(resp) => {
+                let valid = self.inflight_requests.get(&resp.request_id).is_some_and(|req| {
+                    matches!(
+                        &req.request,
+                        RequestState::Waiting(PeerRequest::GetBlockBodies { request, .. })
+                            if is_valid_bodies_response(request, &resp.message.0)
+                    )
+                });
+                self.bandwidth.on_block_data_response(valid);
                 on_response!(resp, GetBlockBodies)
             }
             EthMessage::GetPooledTransactions(req) => {
@@ -237,6 +259,18 @@ impl<N: NetworkPrimitives> ActiveSession<N> {
             EthMessage::Receipts(resp) => {
                 on_response!(resp, GetReceipts)
             }
+            EthMessage::BlockRangeUpdate(msg) => {
+                if !msg.is_valid() {
+                    return OnIncomingMessageOutcome::BadMessage {
+                        error: EthStreamError::InvalidBlockRange {
+                            earliest: msg.earliest,
+                            latest: msg.latest,
+                        },
+                        message: EthMessage::BlockRangeUpdate(msg),
+                    }
+                }
+                self.try_emit_broadcast(PeerMessage::BlockRangeUpdate(msg)).into()
+            }
         }
     }
 
@@ -257,10 +291,15 @@ impl<N: NetworkPrimitives> ActiveSession<N> {
     fn on_internal_peer_message(&mut self, msg: PeerMessage<N>) {
         match msg {
             PeerMessage::NewBlockHashes(msg) => {
-                self.queued_outgoing.push_back(EthMessage::NewBlockHashes(msg).into());
+                // block announcements were removed in eth/69
+                if !self.conn.version().is_eth69() {
+                    self.queued_outgoing.push_back(EthMessage::NewBlockHashes(msg).into());
+                }
             }
             PeerMessage::NewBlock(msg) => {
-                self.queued_outgoing.push_back(EthBroadcastMessage::NewBlock(msg.block).into());
+                if !self.conn.version().is_eth69() {
+                    self.queued_outgoing.push_back(EthBroadcastMessage::NewBlock(msg.block).into());
+                }
             }
             PeerMessage::PooledTransactions(msg) => {
                 if msg.is_valid_for_version(self.conn.version()) {
@@ -274,6 +313,12 @@ impl<N: NetworkPrimitives> ActiveSession<N> {
             PeerMessage::SendTransactions(msg) => {
                 self.queued_outgoing.push_back(EthBroadcastMessage::Transactions(msg).into());
             }
+            PeerMessage::BlockRangeUpdate(msg) => {
+                // only supported by eth/69 peers
+                if self.conn.version().is_eth69() {
+                    self.queued_outgoing.push_back(EthMessage::BlockRangeUpdate(msg).into());
+                }
+            }
             PeerMessage::ReceivedTransaction(_) => {
                 unreachable!("Not emitted by network")
             }
@@ -753,6 +798,28 @@ impl<N: NetworkPrimitives> From<EthBroadcastMessage<N>> for OutgoingMessage<N> {
     }
 }
 
+/// Returns `true` if the headers answer the request, judged like the
+/// [`StateFetcher`](crate::fetch::StateFetcher) does.
+fn is_valid_headers_response<H: BlockHeader>(request: &GetBlockHeaders, headers: &[H]) -> bool {
+    let len = headers.len() as u64;
+    if len > request.limit || (len <= 1 && len != request.limit) {
+        return false
+    }
+    match request.start_block {
+        BlockHashOrNumber::Number(number) => {
+            headers.first().is_none_or(|header| header.number() == number)
+        }
+        // we don't want to hash the header
+        BlockHashOrNumber::Hash(_) => true,
+    }
+}
+
+/// Returns `true` if the bodies answer the request, i.e. there's at least one and at most one per
+/// requested block.
+const fn is_valid_bodies_response<B>(request: &GetBlockBodies, bodies: &[B]) -> bool {
+    !bodies.is_empty() && bodies.len() <= request.0.len()
+}
+
 /// Calculates a new timeout using an updated estimation of the RTT
 #[inline]
 fn calculate_new_timeout(current_timeout: Duration, estimated_rtt: Duration) -> Duration {
@@ -793,14 +860,17 @@ impl<N: NetworkPrimitives> QueuedOutgoingMessages<N> {
 mod tests {
     use super::*;
     use crate::session::{handle::PendingSessionEvent, start_pending_incoming_session};
+    use alloy_consensus::Header;
+    use alloy_primitives::B256;
     use reth_chainspec::MAINNET;
     use reth_ecies::stream::ECIESStream;
     use reth_eth_wire::{
-        EthNetworkPrimitives, EthStream, GetBlockBodies, HelloMessageWithProtocols, P2PStream,
-        Status, StatusBuilder, UnauthedEthStream, UnauthedP2PStream,
+        BlockHeaders, BlockRangeUpdate, EthNetworkPrimitives, EthStream, EthVersion,
+        GetBlockBodies, GetBlockHeaders, GetReceipts, HeadersDirection, HelloMessageWithProtocols,
+        P2PStream, Status, StatusBuilder, StatusMessage, UnauthedEthStream, UnauthedP2PStream,
     };
     use reth_network_peers::pk2id;
-    use reth_network_types::session::config::PROTOCOL_BREACH_REQUEST_TIMEOUT;
+    use reth_network_types::{session::config::PROTOCOL_BREACH_REQUEST_TIMEOUT, TrafficCounters};
     use reth_primitives::{EthereumHardfork, ForkFilter};
     use secp256k1::{SecretKey, SECP256K1};
     use tokio::{
@@ -855,6 +925,9 @@ mod tests {
 
                 let (p2p_stream, _) = UnauthedP2PStream::new(sink).handshake(hello).await.unwrap();
 
+                let version = p2p_stream.shared_capabilities().eth_version().unwrap();
+                let status =
+                    StatusMessage::new(Status { version, ..status }, BlockRangeUpdate::default());
                 let (client_stream, _) = UnauthedEthStream::new(p2p_stream)
                     .handshake(status, fork_filter)
                     .await
@@ -878,6 +951,7 @@ mod tests {
                 self.secret_key,
                 self.hello.clone(),
                 self.status,
+                BlockRangeUpdate::default(),
                 self.fork_filter.clone(),
                 Default::default(),
             ));
@@ -890,9 +964,12 @@ mod tests {
                     remote_addr,
                     peer_id,
                     capabilities,
-                    conn,
+                    mut conn,
                     ..
                 } => {
+                    let bandwidth =
+                        Arc::new(SessionBandwidth::new(conn.inner().shared_capabilities()));
+                    conn.inner_mut().set_meter(Arc::clone(&bandwidth) as _);
                     let (_to_session_tx, messages_rx) = mpsc::channel(10);
                     let (commands_to_session, commands_rx) = mpsc::channel(10);
                     let poll_sender = PollSender::new(self.active_session_tx.clone());
@@ -924,6 +1001,7 @@ mod tests {
                         )),
                         protocol_breach_request_timeout: PROTOCOL_BREACH_REQUEST_TIMEOUT,
                         terminate_message: None,
+                        bandwidth,
                     }
                 }
                 ev => {
@@ -1169,6 +1247,124 @@ mod tests {
         }
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_bandwidth_per_message_class() {
+        let mut builder = SessionBuilder::default();
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let local_addr = listener.local_addr().unwrap();
+
+        let request = GetBlockHeaders {
+            start_block: 0u64.into(),
+            limit: 1,
+            skip: 0,
+            direction: HeadersDirection::Rising,
+        };
+        let get_headers = EthMessage::<EthNetworkPrimitives>::GetBlockHeaders(RequestPair {
+            request_id: 0,
+            message: request,
+        });
+        let headers = EthMessage::<EthNetworkPrimitives>::BlockHeaders(RequestPair {
+            request_id: 0,
+            message: BlockHeaders(vec![Header::default()]),
+        });
+        let hashes =
+            EthMessage::<EthNetworkPrimitives>::NewPooledTransactionHashes68(Default::default());
+        let get_receipts = EthMessage::<EthNetworkPrimitives>::GetReceipts(RequestPair {
+            request_id: 0,
+            message: GetReceipts(vec![B256::ZERO]),
+        });
+        let headers_len = alloy_rlp::Encodable::length(&headers) as u64;
+
+        let fut = builder.with_client_stream(local_addr, move |mut client_stream| async move {
+            let msg = client_stream.next().await.unwrap().unwrap();
+            assert_eq!(msg, get_headers);
+            client_stream.send(headers).await.unwrap();
+            client_stream.send(hashes).await.unwrap();
+            client_stream.send(get_receipts).await.unwrap();
+            let _ = tokio::time::timeout(Duration::from_secs(100), client_stream.next()).await;
+        });
+        tokio::task::spawn(fut);
+
+        let (incoming, _) = listener.accept().await.unwrap();
+        let mut session = builder.connect_incoming(incoming).await;
+        let bandwidth = Arc::clone(&session.bandwidth);
+        let (tx, rx) = oneshot::channel();
+        let req = PeerRequest::GetBlockHeaders { request, response: tx };
+        session.on_internal_peer_request(req, Instant::now() + Duration::from_secs(100));
+        tokio::task::spawn(session);
+
+        assert_eq!(rx.await.unwrap().unwrap().0.len(), 1);
+        // the announcement and the request are forwarded to the manager
+        for _ in 0..2 {
+            match builder.active_session_rx.next().await.unwrap() {
+                ActiveSessionMessage::ValidMessage { .. } => {}
+                ev => unreachable!("{ev:?}"),
+            }
+        }
+
+        let bandwidth = bandwidth.snapshot();
+        assert!(bandwidth.inbound.headers > 0);
+        // messages are counted as they're framed on the wire, i.e. compressed
+        assert!(bandwidth.inbound.headers < headers_len);
+        assert!(bandwidth.inbound.transactions > 0);
+        assert!(bandwidth.inbound.receipts > 0);
+        assert_eq!(bandwidth.inbound.bodies, 0);
+        assert!(bandwidth.outbound.headers > 0);
+        assert_eq!(
+            bandwidth.outbound,
+            TrafficCounters {
+                headers: bandwidth.outbound.headers,
+                other: bandwidth.outbound.other,
+                ..Default::default()
+            }
+        );
+        // only the requested headers are useful
+        assert_eq!(bandwidth.useful_inbound, bandwidth.inbound.headers);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_bandwidth_invalid_response_not_useful() {
+        let mut builder = SessionBuilder::default();
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let local_addr = listener.local_addr().unwrap();
+
+        let request = GetBlockHeaders {
+            start_block: 0u64.into(),
+            limit: 1,
+            skip: 0,
+            direction: HeadersDirection::Rising,
+        };
+        // the header doesn't start at the requested block
+        let headers = EthMessage::<EthNetworkPrimitives>::BlockHeaders(RequestPair {
+            request_id: 0,
+            message: BlockHeaders(vec![Header { number: 1, ..Default::default() }]),
+        });
+
+        let fut = builder.with_client_stream(local_addr, move |mut client_stream| async move {
+            let _ = client_stream.next().await.unwrap().unwrap();
+            client_stream.send(headers).await.unwrap();
+            let _ = tokio::time::timeout(Duration::from_secs(100), client_stream.next()).await;
+        });
+        tokio::task::spawn(fut);
+
+        let (incoming, _) = listener.accept().await.unwrap();
+        let mut session = builder.connect_incoming(incoming).await;
+        let bandwidth = Arc::clone(&session.bandwidth);
+        let (tx, rx) = oneshot::channel();
+        let req = PeerRequest::GetBlockHeaders { request, response: tx };
+        session.on_internal_peer_request(req, Instant::now() + Duration::from_secs(100));
+        tokio::task::spawn(session);
+
+        // the response is still delivered, it's up to the requester to judge it
+        assert_eq!(rx.await.unwrap().unwrap().0.len(), 1);
+
+        let bandwidth = bandwidth.snapshot();
+        assert!(bandwidth.inbound.headers > 0);
+        assert_eq!(bandwidth.useful_inbound, 0);
+    }
+
     #[test]
     fn timeout_calculation_sanity_tests() {
         let rtt = Duration::from_secs(5);
@@ -1184,4 +1380,43 @@ mod tests {
         assert!(calculate_new_timeout(timeout, rtt * 2) > timeout);
         assert!(calculate_new_timeout(timeout, rtt * 2) < timeout * 2);
     }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_block_range_update() {
+        let mut builder = SessionBuilder::default();
+        // eth/69 isn't advertised by default
+        builder.hello.protocols.insert(0, EthVersion::Eth69.into());
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let local_addr = listener.local_addr().unwrap();
+
+        let valid = BlockRangeUpdate { earliest: 1, latest: 100, latest_hash: B256::random() };
+        let invalid = BlockRangeUpdate { earliest: 100, latest: 1, latest_hash: B256::random() };
+        let fut = builder.with_client_stream(local_addr, move |mut client_stream| async move {
+            assert_eq!(client_stream.version(), EthVersion::Eth69);
+            client_stream.send(EthMessage::BlockRangeUpdate(valid)).await.unwrap();
+            client_stream.send(EthMessage::BlockRangeUpdate(invalid)).await.unwrap();
+            let _ = tokio::time::timeout(Duration::from_secs(100), client_stream.next()).await;
+        });
+        tokio::task::spawn(fut);
+
+        let (incoming, _) = listener.accept().await.unwrap();
+        let session = builder.connect_incoming(incoming).await;
+        tokio::task::spawn(session);
+
+        match builder.active_session_rx.next().await.unwrap() {
+            ActiveSessionMessage::ValidMessage {
+                message: PeerMessage::BlockRangeUpdate(range),
+                ..
+            } => assert_eq!(range, valid),
+            ev => unreachable!("{ev:?}"),
+        }
+        match builder.active_session_rx.next().await.unwrap() {
+            ActiveSessionMessage::ClosedOnConnectionError {
+                error: EthStreamError::InvalidBlockRange { earliest: 100, latest: 1 },
+                ..
+            } => {}
+            ev => unreachable!("{ev:?}"),
+        }
+    }
 }
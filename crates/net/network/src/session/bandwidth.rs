@@ -0,0 +1,117 @@
+//! Bandwidth accounting of an active session.
+
+use parking_lot::Mutex;
+use reth_eth_wire::{capability::SharedCapabilities, message::EthMessageID, MessageMeter};
+use reth_network_types::{MessageClass, PeerBandwidth, TrafficCounters};
+use std::{
+    collections::VecDeque,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+/// Counts the bytes of the messages exchanged in an active session.
+///
+/// Messages are counted by the session's [`P2PStream`](reth_eth_wire::P2PStream) as they're framed
+/// on the wire, and read by the [`SessionManager`](super::SessionManager), so the counters are
+/// shared via an `Arc`.
+#[derive(Debug)]
+pub(crate) struct SessionBandwidth {
+    /// The message id of the first `eth` message on the wire.
+    eth_offset: u8,
+    /// The number of `eth` messages of the negotiated version.
+    eth_messages: u8,
+    /// Bytes received, indexed by [`MessageClass`].
+    inbound: [AtomicU64; 5],
+    /// Bytes sent, indexed by [`MessageClass`].
+    outbound: [AtomicU64; 5],
+    /// Bytes of valid headers and bodies received in response to our requests.
+    useful_inbound: AtomicU64,
+    /// Sizes of the received headers and bodies responses that the session hasn't handled yet, in
+    /// the order they were received.
+    pending_block_data: Mutex<VecDeque<usize>>,
+}
+
+impl SessionBandwidth {
+    /// Creates the counters of a session with the given shared capabilities.
+    ///
+    /// If `eth` isn't shared, all messages are accounted to [`MessageClass::Other`].
+    pub(crate) fn new(capabilities: &SharedCapabilities) -> Self {
+        let (eth_offset, eth_messages) =
+            capabilities.eth().map_or((0, 0), |eth| (eth.message_id_offset(), eth.num_messages()));
+        Self {
+            eth_offset,
+            eth_messages,
+            inbound: Default::default(),
+            outbound: Default::default(),
+            useful_inbound: Default::default(),
+            pending_block_data: Default::default(),
+        }
+    }
+
+    /// Returns the `eth` message with the given id on the wire, if it's one.
+    fn eth_message_id(&self, id: u8) -> Option<EthMessageID> {
+        let id = id.checked_sub(self.eth_offset).filter(|id| *id < self.eth_messages)?;
+        EthMessageID::try_from(id as usize).ok()
+    }
+
+    /// Takes the size of the next headers or bodies response the session handles, and counts it as
+    /// useful if the response is valid for our request.
+    pub(crate) fn on_block_data_response(&self, valid: bool) {
+        let Some(size) = self.pending_block_data.lock().pop_front() else { return };
+        if valid {
+            self.useful_inbound.fetch_add(size as u64, Ordering::Relaxed);
+        }
+    }
+
+    /// Returns the bytes exchanged so far.
+    pub(crate) fn snapshot(&self) -> PeerBandwidth {
+        let load = |counters: &[AtomicU64; 5]| {
+            let mut traffic = TrafficCounters::default();
+            for class in MessageClass::ALL {
+                traffic.add(class, counters[class as usize].load(Ordering::Relaxed));
+            }
+            traffic
+        };
+        PeerBandwidth {
+            inbound: load(&self.inbound),
+            outbound: load(&self.outbound),
+            useful_inbound: self.useful_inbound.load(Ordering::Relaxed),
+        }
+    }
+}
+
+impl MessageMeter for SessionBandwidth {
+    fn on_inbound(&self, id: u8, size: usize) {
+        let id = self.eth_message_id(id);
+        if matches!(id, Some(EthMessageID::BlockHeaders | EthMessageID::BlockBodies)) {
+            self.pending_block_data.lock().push_back(size);
+        }
+        let class = id.map_or(MessageClass::Other, message_class);
+        self.inbound[class as usize].fetch_add(size as u64, Ordering::Relaxed);
+    }
+
+    fn on_outbound(&self, id: u8, size: usize) {
+        let class = self.eth_message_id(id).map_or(MessageClass::Other, message_class);
+        self.outbound[class as usize].fetch_add(size as u64, Ordering::Relaxed);
+    }
+}
+
+/// Returns the class an `eth` message is accounted to.
+const fn message_class(id: EthMessageID) -> MessageClass {
+    match id {
+        EthMessageID::NewBlockHashes |
+        EthMessageID::GetBlockHeaders |
+        EthMessageID::BlockHeaders => MessageClass::Headers,
+        EthMessageID::NewBlock | EthMessageID::GetBlockBodies | EthMessageID::BlockBodies => {
+            MessageClass::Bodies
+        }
+        EthMessageID::Transactions |
+        EthMessageID::NewPooledTransactionHashes |
+        EthMessageID::GetPooledTransactions |
+        EthMessageID::PooledTransactions => MessageClass::Transactions,
+        EthMessageID::GetReceipts | EthMessageID::Receipts => MessageClass::Receipts,
+        EthMessageID::Status |
+        EthMessageID::GetNodeData |
+        EthMessageID::NodeData |
+        EthMessageID::BlockRangeUpdate => MessageClass::Other,
+    }
+}
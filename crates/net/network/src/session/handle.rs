@@ -2,7 +2,7 @@
 
 use crate::{
     message::PeerMessage,
-    session::{conn::EthRlpxConnection, Direction, SessionId},
+    session::{bandwidth::SessionBandwidth, conn::EthRlpxConnection, Direction, SessionId},
     PendingSessionHandshakeError,
 };
 use reth_ecies::ECIESError;
@@ -10,9 +10,9 @@ use reth_eth_wire::{
     capability::CapabilityMessage, errors::EthStreamError, Capabilities, DisconnectReason,
     EthVersion, NetworkPrimitives, Status,
 };
-use reth_network_api::PeerInfo;
+use reth_network_api::{PeerInfo, PeerReconnectInfo};
 use reth_network_peers::{NodeRecord, PeerId};
-use reth_network_types::PeerKind;
+use reth_network_types::{PeerBandwidth, PeerKind};
 use std::{io, net::SocketAddr, sync::Arc, time::Instant};
 use tokio::sync::{
     mpsc::{self, error::SendError},
@@ -75,6 +75,8 @@ pub struct ActiveSessionHandle<N: NetworkPrimitives> {
     pub(crate) local_addr: Option<SocketAddr>,
     /// The Status message the peer sent for the `eth` handshake
     pub(crate) status: Arc<Status>,
+    /// Bytes exchanged in the session
+    pub(crate) bandwidth: Arc<SessionBandwidth>,
 }
 
 // === impl ActiveSessionHandle ===
@@ -135,8 +137,18 @@ impl<N: NetworkPrimitives> ActiveSessionHandle<N> {
         self.remote_addr
     }
 
+    /// Returns the bytes exchanged in the session so far.
+    pub fn bandwidth(&self) -> PeerBandwidth {
+        self.bandwidth.snapshot()
+    }
+
     /// Extracts the [`PeerInfo`] from the session handle.
-    pub(crate) fn peer_info(&self, record: &NodeRecord, kind: PeerKind) -> PeerInfo {
+    pub(crate) fn peer_info(
+        &self,
+        record: &NodeRecord,
+        kind: PeerKind,
+        reconnect: Option<PeerReconnectInfo>,
+    ) -> PeerInfo {
         PeerInfo {
             remote_id: self.remote_id,
             direction: self.direction,
@@ -150,6 +162,7 @@ impl<N: NetworkPrimitives> ActiveSessionHandle<N> {
             status: self.status.clone(),
             session_established: self.established,
             kind,
+            reconnect,
         }
     }
 }
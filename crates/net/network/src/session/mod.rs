@@ -1,6 +1,7 @@
 //! Support for handling peer sessions.
 
 mod active;
+mod bandwidth;
 mod conn;
 mod counter;
 mod handle;
@@ -29,19 +30,21 @@ use crate::{
     protocol::{IntoRlpxSubProtocol, RlpxSubProtocolHandlers, RlpxSubProtocols},
     session::active::ActiveSession,
 };
+use alloy_primitives::BlockNumber;
+use bandwidth::SessionBandwidth;
 use counter::SessionCounter;
 use futures::{future::Either, io, FutureExt, StreamExt};
 use reth_ecies::{stream::ECIESStream, ECIESError};
 use reth_eth_wire::{
     capability::CapabilityMessage, errors::EthStreamError, multiplex::RlpxProtocolMultiplexer,
-    Capabilities, DisconnectReason, EthVersion, HelloMessageWithProtocols, NetworkPrimitives,
-    Status, UnauthedEthStream, UnauthedP2PStream,
+    BlockRangeUpdate, Capabilities, DisconnectReason, EthVersion, HelloMessageWithProtocols,
+    NetworkPrimitives, Status, StatusMessage, UnauthedEthStream, UnauthedP2PStream,
 };
 use reth_ethereum_forks::{ForkFilter, ForkId, ForkTransition, Head};
 use reth_metrics::common::mpsc::MeteredPollSender;
 use reth_network_api::{PeerRequest, PeerRequestSender};
 use reth_network_peers::PeerId;
-use reth_network_types::SessionsConfig;
+use reth_network_types::{PeerBandwidth, SessionsConfig};
 use reth_tasks::TaskSpawner;
 use rustc_hash::FxHashMap;
 use secp256k1::SecretKey;
@@ -54,6 +57,10 @@ use tokio_stream::wrappers::ReceiverStream;
 use tokio_util::sync::PollSender;
 use tracing::{debug, instrument, trace};
 
+/// The number of blocks the head has to advance before a new [`BlockRangeUpdate`] is announced to
+/// `eth/69` peers, which is roughly once per epoch.
+const BLOCK_RANGE_UPDATE_INTERVAL: u64 = 32;
+
 /// Internal identifier for active sessions.
 #[derive(Debug, Clone, Copy, PartialOrd, PartialEq, Eq, Hash)]
 pub struct SessionId(usize);
@@ -78,6 +85,11 @@ pub struct SessionManager<N: NetworkPrimitives> {
     secret_key: SecretKey,
     /// The `Status` message to send to peers.
     status: Status,
+    /// The range of blocks we can serve, sent to `eth/69` peers in the `Status` message and
+    /// announced via [`BlockRangeUpdate`] messages.
+    block_range: BlockRangeUpdate,
+    /// The latest block of the last [`BlockRangeUpdate`] announced to `eth/69` peers.
+    last_announced_block: BlockNumber,
     /// The `HelloMessage` message to send to peers.
     hello_message: HelloMessageWithProtocols,
     /// The [`ForkFilter`] used to validate the peer's `Status` message.
@@ -125,6 +137,7 @@ impl<N: NetworkPrimitives> SessionManager<N> {
         config: SessionsConfig,
         executor: Box<dyn TaskSpawner>,
         status: Status,
+        block_range: BlockRangeUpdate,
         hello_message: HelloMessageWithProtocols,
         fork_filter: ForkFilter,
         extra_protocols: RlpxSubProtocols,
@@ -141,6 +154,8 @@ impl<N: NetworkPrimitives> SessionManager<N> {
             pending_session_timeout: config.pending_session_timeout,
             secret_key,
             status,
+            block_range,
+            last_announced_block: block_range.latest,
             hello_message,
             fork_filter,
             session_command_buffer: config.session_command_buffer,
@@ -175,6 +190,11 @@ impl<N: NetworkPrimitives> SessionManager<N> {
         self.status
     }
 
+    /// Returns the range of blocks we can serve.
+    pub const fn block_range(&self) -> BlockRangeUpdate {
+        self.block_range
+    }
+
     /// Returns the secret key used for authenticating sessions.
     pub const fn secret_key(&self) -> SecretKey {
         self.secret_key
@@ -214,14 +234,47 @@ impl<N: NetworkPrimitives> SessionManager<N> {
     ///
     /// If the updated activated another fork, this will return a [`ForkTransition`] and updates the
     /// active [`ForkId`]. See also [`ForkFilter::set_head`].
-    pub(crate) fn on_status_update(&mut self, head: Head) -> Option<ForkTransition> {
+    ///
+    /// This also updates the range of blocks we can serve, which is announced to all `eth/69`
+    /// peers if the earliest block changed or the head advanced by at least
+    /// [`BLOCK_RANGE_UPDATE_INTERVAL`] blocks since the last announcement.
+    pub(crate) fn on_status_update(
+        &mut self,
+        head: Head,
+        earliest: BlockNumber,
+    ) -> Option<ForkTransition> {
         self.status.blockhash = head.hash;
         self.status.total_difficulty = head.total_difficulty;
         let transition = self.fork_filter.set_head(head);
         self.status.forkid = self.fork_filter.current();
+
+        let earliest_changed = earliest != self.block_range.earliest;
+        self.block_range =
+            BlockRangeUpdate { earliest, latest: head.number, latest_hash: head.hash };
+        if earliest_changed ||
+            head.number.abs_diff(self.last_announced_block) >= BLOCK_RANGE_UPDATE_INTERVAL
+        {
+            self.announce_block_range();
+        }
+
         transition
     }
 
+    /// Announces the range of blocks we can serve to all `eth/69` peers.
+    fn announce_block_range(&mut self) {
+        let block_range = self.block_range;
+        self.last_announced_block = block_range.latest;
+        let peers = self
+            .active_sessions
+            .iter()
+            .filter(|(_, session)| session.version.is_eth69())
+            .map(|(peer_id, _)| *peer_id)
+            .collect::<Vec<_>>();
+        for peer_id in peers {
+            self.send_message(&peer_id, PeerMessage::BlockRangeUpdate(block_range));
+        }
+    }
+
     /// An incoming TCP connection was received. This starts the authentication process to turn this
     /// stream into an active peer session.
     ///
@@ -247,6 +300,7 @@ impl<N: NetworkPrimitives> SessionManager<N> {
         let secret_key = self.secret_key;
         let hello_message = self.hello_message.clone();
         let status = self.status;
+        let block_range = self.block_range;
         let fork_filter = self.fork_filter.clone();
         let extra_handlers = self.extra_protocols.on_incoming(remote_addr);
         self.spawn(pending_session_with_timeout(
@@ -264,6 +318,7 @@ impl<N: NetworkPrimitives> SessionManager<N> {
                 secret_key,
                 hello_message,
                 status,
+                block_range,
                 fork_filter,
                 extra_handlers,
             ),
@@ -289,6 +344,7 @@ impl<N: NetworkPrimitives> SessionManager<N> {
             let hello_message = self.hello_message.clone();
             let fork_filter = self.fork_filter.clone();
             let status = self.status;
+            let block_range = self.block_range;
             let extra_handlers = self.extra_protocols.on_outgoing(remote_addr, remote_peer_id);
             self.spawn(pending_session_with_timeout(
                 self.pending_session_timeout,
@@ -305,6 +361,7 @@ impl<N: NetworkPrimitives> SessionManager<N> {
                     secret_key,
                     hello_message,
                     status,
+                    block_range,
                     fork_filter,
                     extra_handlers,
                 ),
@@ -425,8 +482,12 @@ impl<N: NetworkPrimitives> SessionManager<N> {
                             ?peer_id,
                             "gracefully disconnected active session."
                         );
-                        self.remove_active_session(&peer_id);
-                        Poll::Ready(SessionEvent::Disconnected { peer_id, remote_addr })
+                        let bandwidth = self.remove_active_session(&peer_id).map(|s| s.bandwidth());
+                        Poll::Ready(SessionEvent::Disconnected {
+                            peer_id,
+                            remote_addr,
+                            bandwidth: bandwidth.unwrap_or_default(),
+                        })
                     }
                     ActiveSessionMessage::ClosedOnConnectionError {
                         peer_id,
@@ -434,11 +495,12 @@ impl<N: NetworkPrimitives> SessionManager<N> {
                         error,
                     } => {
                         trace!(target: "net::session", ?peer_id, %error,"closed session.");
-                        self.remove_active_session(&peer_id);
+                        let bandwidth = self.remove_active_session(&peer_id).map(|s| s.bandwidth());
                         Poll::Ready(SessionEvent::SessionClosedOnConnectionError {
                             remote_addr,
                             peer_id,
                             error,
+                            bandwidth: bandwidth.unwrap_or_default(),
                         })
                     }
                     ActiveSessionMessage::ValidMessage { peer_id, message } => {
@@ -470,7 +532,7 @@ impl<N: NetworkPrimitives> SessionManager<N> {
                 local_addr,
                 peer_id,
                 capabilities,
-                conn,
+                mut conn,
                 status,
                 direction,
                 client_id,
@@ -512,6 +574,9 @@ impl<N: NetworkPrimitives> SessionManager<N> {
                     self.initial_internal_request_timeout.as_millis() as u64,
                 ));
 
+                let bandwidth = Arc::new(SessionBandwidth::new(conn.inner().shared_capabilities()));
+                conn.inner_mut().set_meter(Arc::clone(&bandwidth) as _);
+
                 // negotiated version
                 let version = conn.version();
 
@@ -537,6 +602,7 @@ impl<N: NetworkPrimitives> SessionManager<N> {
                     internal_request_timeout: Arc::clone(&timeout),
                     protocol_breach_request_timeout: self.protocol_breach_request_timeout,
                     terminate_message: None,
+                    bandwidth: Arc::clone(&bandwidth),
                 };
 
                 self.spawn(session);
@@ -554,6 +620,7 @@ impl<N: NetworkPrimitives> SessionManager<N> {
                     client_version: Arc::clone(&client_version),
                     remote_addr,
                     local_addr,
+                    bandwidth,
                 };
 
                 self.active_sessions.insert(peer_id, handle);
@@ -755,6 +822,8 @@ pub enum SessionEvent<N: NetworkPrimitives> {
         remote_addr: SocketAddr,
         /// The error that caused the session to close
         error: EthStreamError,
+        /// Bytes exchanged in the session.
+        bandwidth: PeerBandwidth,
     },
     /// Active session was gracefully disconnected.
     Disconnected {
@@ -762,6 +831,8 @@ pub enum SessionEvent<N: NetworkPrimitives> {
         peer_id: PeerId,
         /// The remote node's socket address that we were connected to
         remote_addr: SocketAddr,
+        /// Bytes exchanged in the session.
+        bandwidth: PeerBandwidth,
     },
 }
 
@@ -831,6 +902,7 @@ pub(crate) async fn start_pending_incoming_session<N: NetworkPrimitives>(
     secret_key: SecretKey,
     hello: HelloMessageWithProtocols,
     status: Status,
+    block_range: BlockRangeUpdate,
     fork_filter: ForkFilter,
     extra_handlers: RlpxSubProtocolHandlers,
 ) {
@@ -844,6 +916,7 @@ pub(crate) async fn start_pending_incoming_session<N: NetworkPrimitives>(
         Direction::Incoming,
         hello,
         status,
+        block_range,
         fork_filter,
         extra_handlers,
     )
@@ -862,6 +935,7 @@ async fn start_pending_outbound_session<N: NetworkPrimitives>(
     secret_key: SecretKey,
     hello: HelloMessageWithProtocols,
     status: Status,
+    block_range: BlockRangeUpdate,
     fork_filter: ForkFilter,
     extra_handlers: RlpxSubProtocolHandlers,
 ) {
@@ -894,6 +968,7 @@ async fn start_pending_outbound_session<N: NetworkPrimitives>(
         Direction::Outgoing(remote_peer_id),
         hello,
         status,
+        block_range,
         fork_filter,
         extra_handlers,
     )
@@ -912,6 +987,7 @@ async fn authenticate<N: NetworkPrimitives>(
     direction: Direction,
     hello: HelloMessageWithProtocols,
     status: Status,
+    block_range: BlockRangeUpdate,
     fork_filter: ForkFilter,
     extra_handlers: RlpxSubProtocolHandlers,
 ) {
@@ -941,6 +1017,7 @@ async fn authenticate<N: NetworkPrimitives>(
         direction,
         hello,
         status,
+        block_range,
         fork_filter,
         extra_handlers,
     )
@@ -993,6 +1070,7 @@ async fn authenticate_stream<N: NetworkPrimitives>(
     direction: Direction,
     mut hello: HelloMessageWithProtocols,
     mut status: Status,
+    block_range: BlockRangeUpdate,
     fork_filter: ForkFilter,
     mut extra_handlers: RlpxSubProtocolHandlers,
 ) -> PendingSessionEvent<N> {
@@ -1025,11 +1103,14 @@ async fn authenticate_stream<N: NetworkPrimitives>(
         }
     };
 
+    // Before trying status handshake, set up the version to negotiated shared version. The
+    // `Status` message of eth/69 announces the range of blocks we can serve instead of the total
+    // difficulty.
+    status.set_eth_version(eth_version);
+    let status = StatusMessage::new(status, block_range);
+
     let (conn, their_status) = if p2p_stream.shared_capabilities().len() == 1 {
         // if the hello handshake was successful we can try status handshake
-        //
-        // Before trying status handshake, set up the version to negotiated shared version
-        status.set_eth_version(eth_version);
         let eth_unauthed = UnauthedEthStream::new(p2p_stream);
         let (eth_stream, their_status) = match eth_unauthed.handshake(status, fork_filter).await {
             Ok(stream_res) => stream_res,
@@ -1080,7 +1161,7 @@ async fn authenticate_stream<N: NetworkPrimitives>(
         local_addr,
         peer_id: their_hello.id,
         capabilities: Arc::new(Capabilities::from(their_hello.capabilities)),
-        status: Arc::new(their_status),
+        status: Arc::new(their_status.to_legacy()),
         conn,
         direction,
         client_id: their_hello.client_version,
@@ -133,6 +133,11 @@ impl<N: NetworkPrimitives> NetworkState<N> {
         self.state_fetcher.client()
     }
 
+    /// Returns the earliest block we can serve in full.
+    pub(crate) fn earliest_block_number(&self) -> u64 {
+        self.client.earliest_block_number().unwrap_or_default()
+    }
+
     /// How many peers we're currently connected to.
     pub fn num_active_peers(&self) -> usize {
         self.active_peers.len()
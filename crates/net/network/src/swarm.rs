@@ -13,6 +13,7 @@ use reth_eth_wire::{
 };
 use reth_network_api::{PeerRequest, PeerRequestSender};
 use reth_network_peers::PeerId;
+use reth_network_types::PeerBandwidth;
 use std::{
     io,
     net::SocketAddr,
@@ -158,13 +159,23 @@ impl<N: NetworkPrimitives> Swarm<N> {
             SessionEvent::OutgoingPendingSessionClosed { remote_addr, peer_id, error } => {
                 Some(SwarmEvent::OutgoingPendingSessionClosed { remote_addr, peer_id, error })
             }
-            SessionEvent::Disconnected { peer_id, remote_addr } => {
+            SessionEvent::Disconnected { peer_id, remote_addr, bandwidth } => {
                 self.state.on_session_closed(peer_id);
-                Some(SwarmEvent::SessionClosed { peer_id, remote_addr, error: None })
+                Some(SwarmEvent::SessionClosed { peer_id, remote_addr, error: None, bandwidth })
             }
-            SessionEvent::SessionClosedOnConnectionError { peer_id, remote_addr, error } => {
+            SessionEvent::SessionClosedOnConnectionError {
+                peer_id,
+                remote_addr,
+                error,
+                bandwidth,
+            } => {
                 self.state.on_session_closed(peer_id);
-                Some(SwarmEvent::SessionClosed { peer_id, remote_addr, error: Some(error) })
+                Some(SwarmEvent::SessionClosed {
+                    peer_id,
+                    remote_addr,
+                    error: Some(error),
+                    bandwidth,
+                })
             }
             SessionEvent::OutgoingConnectionError { remote_addr, peer_id, error } => {
                 Some(SwarmEvent::OutgoingConnectionError { peer_id, remote_addr, error })
@@ -401,6 +412,8 @@ pub(crate) enum SwarmEvent<N: NetworkPrimitives = EthNetworkPrimitives> {
         remote_addr: SocketAddr,
         /// Whether the session was closed due to an error
         error: Option<EthStreamError>,
+        /// Bytes exchanged in the session.
+        bandwidth: PeerBandwidth,
     },
     /// Admin rpc: new peer added
     PeerAdded(PeerId),
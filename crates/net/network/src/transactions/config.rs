@@ -1,5 +1,5 @@
 use super::{
-    DEFAULT_MAX_COUNT_TRANSACTIONS_SEEN_BY_PEER,
+    DEFAULT_MAX_COUNT_TRANSACTIONS_SEEN_BY_PEER, DEFAULT_MAX_NEW_PEER_ANNOUNCEMENTS,
     DEFAULT_SOFT_LIMIT_BYTE_SIZE_POOLED_TRANSACTIONS_RESP_ON_PACK_GET_POOLED_TRANSACTIONS_REQ,
     SOFT_LIMIT_BYTE_SIZE_POOLED_TRANSACTIONS_RESPONSE,
 };
@@ -20,6 +20,13 @@ pub struct TransactionsManagerConfig {
     /// How new pending transactions are propagated.
     #[cfg_attr(feature = "serde", serde(default))]
     pub propagation_mode: TransactionPropagationMode,
+    /// Max number of transaction hashes announced to a new peer at once.
+    ///
+    /// The pending pool is announced to a new peer in batches of this size, highest effective tip
+    /// first. The first batch is announced when the session is established, the rest trickles out
+    /// one batch per announcement interval.
+    #[cfg_attr(feature = "serde", serde(default = "default_max_new_peer_announcements"))]
+    pub max_new_peer_announcements: usize,
 }
 
 impl Default for TransactionsManagerConfig {
@@ -28,10 +35,16 @@ impl Default for TransactionsManagerConfig {
             transaction_fetcher_config: TransactionFetcherConfig::default(),
             max_transactions_seen_by_peer_history: DEFAULT_MAX_COUNT_TRANSACTIONS_SEEN_BY_PEER,
             propagation_mode: TransactionPropagationMode::default(),
+            max_new_peer_announcements: DEFAULT_MAX_NEW_PEER_ANNOUNCEMENTS,
         }
     }
 }
 
+#[cfg(feature = "serde")]
+const fn default_max_new_peer_announcements() -> usize {
+    DEFAULT_MAX_NEW_PEER_ANNOUNCEMENTS
+}
+
 /// Determines how new pending transactions are propagated to other peers in full.
 #[derive(Debug, Clone, Default)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
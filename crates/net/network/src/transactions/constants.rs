@@ -36,6 +36,7 @@ pub const SOFT_LIMIT_BYTE_SIZE_POOLED_TRANSACTIONS_RESPONSE: usize = 2 * 1024 *
 /// Constants used by [`TransactionsManager`](super::TransactionsManager).
 pub mod tx_manager {
     use super::SOFT_LIMIT_COUNT_HASHES_IN_NEW_POOLED_TRANSACTIONS_BROADCAST_MESSAGE;
+    use std::time::Duration;
 
     /// Default limit for number of transactions to keep track of for a single peer.
     ///
@@ -53,6 +54,18 @@ pub mod tx_manager {
     ///
     /// Default is 100 KiB, i.e. 3 200 transaction hashes.
     pub const DEFAULT_MAX_COUNT_BAD_IMPORTS: u32 = 100 * 1024 / 32;
+
+    /// Default limit for the number of transaction hashes announced to a new peer at once, when
+    /// the session is established and on every
+    /// [`NEW_PEER_ANNOUNCEMENT_INTERVAL`] thereafter, until the pool is announced.
+    ///
+    /// Default is 1024 transaction hashes.
+    pub const DEFAULT_MAX_NEW_PEER_ANNOUNCEMENTS: usize = 1024;
+
+    /// Interval at which the rest of the pool is announced to new peers.
+    ///
+    /// Default is 1 second.
+    pub const NEW_PEER_ANNOUNCEMENT_INTERVAL: Duration = Duration::from_secs(1);
 }
 
 /// Constants used by [`TransactionFetcher`](super::TransactionFetcher).
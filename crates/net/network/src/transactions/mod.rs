@@ -58,7 +58,7 @@ use reth_transaction_pool::{
     TransactionPool, ValidPoolTransaction,
 };
 use std::{
-    collections::{hash_map::Entry, HashMap, HashSet},
+    collections::{hash_map::Entry, HashMap, HashSet, VecDeque},
     pin::Pin,
     sync::{
         atomic::{AtomicUsize, Ordering},
@@ -67,7 +67,10 @@ use std::{
     task::{Context, Poll},
     time::{Duration, Instant},
 };
-use tokio::sync::{mpsc, oneshot, oneshot::error::RecvError};
+use tokio::{
+    sync::{mpsc, oneshot, oneshot::error::RecvError},
+    time::Interval,
+};
 use tokio_stream::wrappers::{ReceiverStream, UnboundedReceiverStream};
 use tracing::{debug, trace};
 
@@ -288,6 +291,8 @@ pub struct TransactionsManager<Pool, N: NetworkPrimitives = EthNetworkPrimitives
     pending_transactions: ReceiverStream<TxHash>,
     /// Incoming events from the [`NetworkManager`](crate::NetworkManager).
     transaction_events: UnboundedMeteredReceiver<NetworkTransactionEvent<N>>,
+    /// Interval at which the rest of the pool is announced to new peers.
+    new_peer_announcement_interval: Interval,
     /// How the `TransactionsManager` is configured.
     config: TransactionsManagerConfig,
     /// `TransactionsManager` metrics
@@ -340,6 +345,7 @@ impl<Pool: TransactionPool, N: NetworkPrimitives> TransactionsManager<Pool, N> {
                 from_network,
                 NETWORK_POOL_TRANSACTIONS_SCOPE,
             ),
+            new_peer_announcement_interval: tokio::time::interval(NEW_PEER_ANNOUNCEMENT_INTERVAL),
             config: transactions_manager_config,
             metrics,
         }
@@ -571,6 +577,17 @@ where
                 .increment(already_known_hashes_count as u64);
         }
 
+        // don't fetch transactions that would be rejected again
+        let hashes_count_pre_rejected_filter = partially_valid_msg.len();
+        self.pool.retain_not_rejected(&mut partially_valid_msg);
+        if hashes_count_pre_rejected_filter > partially_valid_msg.len() {
+            let rejected_hashes_count =
+                hashes_count_pre_rejected_filter - partially_valid_msg.len();
+            self.metrics
+                .occurrences_hashes_rejected_by_pool
+                .increment(rejected_hashes_count as u64);
+        }
+
         if partially_valid_msg.is_empty() {
             // nothing to request
             return
@@ -1073,33 +1090,79 @@ where
             Entry::Vacant(entry) => entry.insert(peer),
         };
 
-        // Send a `NewPooledTransactionHashes` to the peer with up to
-        // `SOFT_LIMIT_COUNT_HASHES_IN_NEW_POOLED_TRANSACTIONS_BROADCAST_MESSAGE`
-        // transactions in the pool.
+        // Announce the pending transactions in the pool to the peer, highest effective tip
+        // first. Only the first `max_new_peer_announcements` are announced right away, the rest
+        // is announced on the following announcement ticks.
         if self.network.is_initially_syncing() || self.network.tx_gossip_disabled() {
             trace!(target: "net::tx", ?peer_id, "Skipping transaction broadcast: node syncing or gossip disabled");
             return
         }
 
-        // Get transactions to broadcast
-        let pooled_txs = self.pool.pooled_transactions_max(
-            SOFT_LIMIT_COUNT_HASHES_IN_NEW_POOLED_TRANSACTIONS_BROADCAST_MESSAGE,
-        );
-        if pooled_txs.is_empty() {
+        peer.announcement_backlog =
+            self.pool.best_transactions().filter(|tx| tx.propagate).map(|tx| *tx.hash()).collect();
+        if peer.announcement_backlog.is_empty() {
             trace!(target: "net::tx", ?peer_id, "No transactions in the pool to broadcast");
             return;
         }
 
-        // Build and send transaction hashes message
-        let mut msg_builder = PooledTransactionsHashesBuilder::new(version);
-        for pooled_tx in pooled_txs {
+        self.announce_backlog(peer_id);
+    }
+
+    /// Announces the next transactions of the peer's announcement backlog, up to
+    /// [`TransactionsManagerConfig::max_new_peer_announcements`], skipping transactions that the
+    /// peer has already seen or that left the pool in the meantime.
+    fn announce_backlog(&mut self, peer_id: PeerId) {
+        if let Some(msg) = self.next_backlog_announcement(peer_id) {
+            debug!(target: "net::tx", ?peer_id, tx_count = msg.len(), "Broadcasting transaction hashes");
+            self.network.send_transactions_hashes(peer_id, msg);
+        }
+    }
+
+    /// Takes the next transactions to announce from the peer's announcement backlog and returns
+    /// the announcement message, if there's anything to announce.
+    fn next_backlog_announcement(&mut self, peer_id: PeerId) -> Option<NewPooledTransactionHashes> {
+        let peer = self.peers.get_mut(&peer_id)?;
+
+        let mut hashes = Vec::new();
+        while hashes.len() < self.config.max_new_peer_announcements {
+            let Some(hash) = peer.announcement_backlog.pop_front() else { break };
+            if !peer.seen_transactions.contains(&hash) {
+                hashes.push(hash);
+            }
+        }
+        if hashes.is_empty() {
+            return None
+        }
+
+        let mut msg_builder = PooledTransactionsHashesBuilder::new(peer.version);
+        for pooled_tx in self.pool.get_all(hashes) {
             peer.seen_transactions.insert(*pooled_tx.hash());
             msg_builder.push_pooled(pooled_tx);
         }
 
-        debug!(target: "net::tx", ?peer_id, tx_count = msg_builder.is_empty(), "Broadcasting transaction hashes");
-        let msg = msg_builder.build();
-        self.network.send_transactions_hashes(peer_id, msg);
+        (!msg_builder.is_empty()).then(|| msg_builder.build())
+    }
+
+    /// Announces the next part of the pool to all peers with an announcement backlog.
+    fn on_new_peer_announcement_tick(&mut self) {
+        let peers = self
+            .peers
+            .iter()
+            .filter(|(_, peer)| !peer.announcement_backlog.is_empty())
+            .map(|(peer_id, _)| *peer_id)
+            .collect::<Vec<_>>();
+
+        for peer_id in peers {
+            self.announce_backlog(peer_id);
+        }
+
+        let mut backlog = 0;
+        for peer in self.peers.values().filter(|peer| !peer.announcement_backlog.is_empty()) {
+            let peer_backlog = peer.announcement_backlog.len();
+            self.metrics.new_peer_announcement_backlog_per_peer.record(peer_backlog as f64);
+            backlog += peer_backlog;
+        }
+        self.metrics.new_peer_announcement_backlog.set(backlog as f64);
     }
 
     /// Handles a received event related to common network events.
@@ -1208,6 +1271,16 @@ where
                 .increment(already_known_txns_count as u64);
         }
 
+        // skip recovering and validating transactions that would be rejected again
+        let txns_count_pre_rejected_filter = transactions.len();
+        self.pool.retain_not_rejected(&mut transactions);
+        if txns_count_pre_rejected_filter > transactions.len() {
+            let rejected_txns_count = txns_count_pre_rejected_filter - transactions.len();
+            self.metrics
+                .occurrences_transactions_rejected_by_pool
+                .increment(rejected_txns_count as u64);
+        }
+
         // tracks the quality of the given transactions
         let mut has_bad_transactions = false;
 
@@ -1463,6 +1536,11 @@ where
             poll_durations.acc_pending_fetch
         );
 
+        // Announce the next part of the pool to new peers.
+        if this.new_peer_announcement_interval.poll_tick(cx).is_ready() {
+            this.on_new_peer_announcement_tick();
+        }
+
         // Advance commands (propagate/fetch/serve txns).
         let maybe_more_commands = metered_poll_nested_stream_with_budget!(
             poll_durations.acc_cmds,
@@ -1790,6 +1868,9 @@ pub struct PeerMetadata<N: NetworkPrimitives = EthNetworkPrimitives> {
     version: EthVersion,
     /// The peer's client version.
     client_version: Arc<str>,
+    /// Hashes of pooled transactions that are yet to be announced to the peer after the session
+    /// was established, highest effective tip first.
+    announcement_backlog: VecDeque<TxHash>,
 }
 
 impl<N: NetworkPrimitives> PeerMetadata<N> {
@@ -1805,6 +1886,7 @@ impl<N: NetworkPrimitives> PeerMetadata<N> {
             request_tx,
             version,
             client_version,
+            announcement_backlog: VecDeque::new(),
         }
     }
 }
@@ -1910,6 +1992,7 @@ struct TxManagerPollDurations {
 mod tests {
     use super::*;
     use crate::{test_utils::Testnet, NetworkConfigBuilder, NetworkManager};
+    use alloy_eips::eip1559::MIN_PROTOCOL_BASE_FEE;
     use alloy_primitives::hex;
     use alloy_rlp::Decodable;
     use constants::tx_fetcher::DEFAULT_MAX_COUNT_FALLBACK_PEERS;
@@ -1920,8 +2003,11 @@ mod tests {
         sync::{NetworkSyncUpdater, SyncState},
     };
     use reth_storage_api::noop::NoopProvider;
-    use reth_transaction_pool::test_utils::{
-        testing_pool, MockTransaction, MockTransactionFactory, TestPool,
+    use reth_transaction_pool::{
+        test_utils::{
+            testing_pool, MockTransaction, MockTransactionFactory, TestPool, TestPoolBuilder,
+        },
+        PoolConfig, SubPoolLimit,
     };
     use secp256k1::SecretKey;
     use std::{
@@ -1935,6 +2021,13 @@ mod tests {
 
     async fn new_tx_manager(
     ) -> (TransactionsManager<TestPool, EthNetworkPrimitives>, NetworkManager<EthNetworkPrimitives>)
+    {
+        new_tx_manager_with_pool(testing_pool()).await
+    }
+
+    async fn new_tx_manager_with_pool(
+        pool: TestPool,
+    ) -> (TransactionsManager<TestPool, EthNetworkPrimitives>, NetworkManager<EthNetworkPrimitives>)
     {
         let secret_key = SecretKey::new(&mut rand::thread_rng());
         let client = NoopProvider::default();
@@ -1945,8 +2038,6 @@ mod tests {
             .disable_discovery()
             .build(client);
 
-        let pool = testing_pool();
-
         let transactions_manager_config = config.transactions_manager_config.clone();
         let (_network_handle, network, transactions, _) = NetworkManager::new(config)
             .await
@@ -2510,4 +2601,71 @@ mod tests {
         let propagated = tx_manager.propagate_transactions(propagate, PropagationMode::Basic);
         assert!(propagated.0.is_empty());
     }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_new_peer_announcement_budget() {
+        reth_tracing::init_test_tracing();
+
+        const POOL_SIZE: usize = 50_000;
+        let limit = SubPoolLimit { max_txs: POOL_SIZE, max_size: usize::MAX };
+        let pool: TestPool = TestPoolBuilder::default()
+            .with_config(PoolConfig {
+                pending_limit: limit,
+                basefee_limit: limit,
+                queued_limit: limit,
+                ..Default::default()
+            })
+            .into();
+        let txs = (0..POOL_SIZE as u128)
+            .map(|tip| {
+                MockTransaction::eip1559()
+                    .with_priority_fee(tip)
+                    .with_max_fee(MIN_PROTOCOL_BASE_FEE as u128 + tip)
+            })
+            .collect();
+        assert!(pool.add_external_transactions(txs).await.iter().all(Result::is_ok));
+        let best = pool.best_transactions().map(|tx| *tx.hash()).collect::<Vec<_>>();
+        assert_eq!(best.len(), POOL_SIZE);
+
+        let (mut tx_manager, network) = new_tx_manager_with_pool(pool).await;
+        let budget = tx_manager.config.max_new_peer_announcements;
+        network.handle().update_sync_state(SyncState::Idle);
+
+        // mock a peer
+        let peer_id = PeerId::random();
+        let (tx, _rx) = mpsc::channel::<PeerRequest>(1);
+        let session_info = SessionInfo {
+            peer_id,
+            remote_addr: SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 0),
+            client_version: Arc::from(""),
+            capabilities: Arc::new(vec![].into()),
+            status: Arc::new(Default::default()),
+            version: EthVersion::Eth68,
+        };
+        let messages: PeerRequestSender<PeerRequest> = PeerRequestSender::new(peer_id, tx);
+        tx_manager
+            .on_network_event(NetworkEvent::ActivePeerSession { info: session_info, messages });
+
+        // only the transactions with the highest tips are announced right away
+        let peer = tx_manager.peers.get_mut(&peer_id).unwrap();
+        assert_eq!(peer.announcement_backlog.len(), POOL_SIZE - budget);
+        assert!(peer.announcement_backlog.iter().eq(&best[budget..]));
+
+        // a transaction the peer already knows about isn't announced again
+        let known = best[budget];
+        peer.seen_transactions.insert(known);
+
+        // the rest trickles out, at most `budget` hashes at a time
+        let mut announced = best[..budget].iter().copied().collect::<HashSet<_>>();
+        while let Some(msg) = tx_manager.next_backlog_announcement(peer_id) {
+            assert!(msg.len() <= budget);
+            for hash in msg.iter_hashes() {
+                assert!(announced.insert(*hash));
+            }
+        }
+        assert!(tx_manager.peers[&peer_id].announcement_backlog.is_empty());
+        assert!(!announced.contains(&known));
+        announced.insert(known);
+        assert_eq!(announced, best.into_iter().collect());
+    }
 }
@@ -89,13 +89,7 @@ pub fn validate_header_download<H: BlockHeader>(
     parent: &SealedHeader<H>,
 ) -> DownloadResult<()> {
     // validate header against parent
-    consensus.validate_header_against_parent(header, parent).map_err(|error| {
-        DownloadError::HeaderValidation {
-            hash: header.hash(),
-            number: header.number(),
-            error: Box::new(error),
-        }
-    })?;
+    validate_header_download_against_parent(consensus, header, parent)?;
     // validate header standalone
     consensus.validate_header(header).map_err(|error| DownloadError::HeaderValidation {
         hash: header.hash(),
@@ -104,3 +98,22 @@ pub fn validate_header_download<H: BlockHeader>(
     })?;
     Ok(())
 }
+
+/// Validate whether the header is valid in relation to it's parent, without validating the header
+/// on its own.
+///
+/// This is for headers that were already validated with [`HeaderValidator::validate_header`],
+/// e.g. in parallel with the other headers of a response.
+pub fn validate_header_download_against_parent<H: BlockHeader>(
+    consensus: &dyn HeaderValidator<H>,
+    header: &SealedHeader<H>,
+    parent: &SealedHeader<H>,
+) -> DownloadResult<()> {
+    consensus.validate_header_against_parent(header, parent).map_err(|error| {
+        DownloadError::HeaderValidation {
+            hash: header.hash(),
+            number: header.number(),
+            error: Box::new(error),
+        }
+    })
+}
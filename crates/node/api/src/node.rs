@@ -13,6 +13,7 @@ use reth_network_api::FullNetwork;
 use reth_node_core::node_config::NodeConfig;
 use reth_node_types::{HeaderTy, NodeTypes, NodeTypesWithDBAdapter, NodeTypesWithEngine, TxTy};
 use reth_payload_builder_primitives::PayloadBuilder;
+use reth_payload_primitives::SuggestedFeeRecipient;
 use reth_provider::FullProvider;
 use reth_tasks::TaskExecutor;
 use reth_transaction_pool::{PoolTransaction, TransactionPool};
@@ -92,6 +93,13 @@ pub trait FullNodeComponents: FullNodeTypes + Clone + 'static {
     fn task_executor(&self) -> &TaskExecutor;
 }
 
+/// Handle to send `newPayload` and `forkchoiceUpdated` messages to the consensus engine of a node.
+///
+/// The engine API of the auth server forwards the calls of the consensus layer client through this
+/// handle. A consensus driver that runs in the same process as the node, e.g. the sealer of a proof
+/// of authority chain, can use it to advance the chain without going through the engine API.
+pub type ConsensusEngineHandle<Engine> = BeaconConsensusEngineHandle<Engine>;
+
 /// Context passed to [`NodeAddOns::launch_add_ons`],
 #[derive(Debug, Clone)]
 pub struct AddOnsContext<'a, N: FullNodeComponents> {
@@ -104,6 +112,8 @@ pub struct AddOnsContext<'a, N: FullNodeComponents> {
         BeaconConsensusEngineHandle<<N::Types as NodeTypesWithEngine>::Engine>,
     /// JWT secret for the node.
     pub jwt_secret: JwtSecret,
+    /// Handle to the suggested fee recipient of the payloads the node builds by itself, if any.
+    pub fee_recipient: Option<SuggestedFeeRecipient>,
 }
 
 /// Customizable node add-on types.
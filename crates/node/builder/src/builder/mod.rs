@@ -3,6 +3,7 @@
 #![allow(clippy::type_complexity, missing_debug_implementations)]
 
 use crate::{
+    add_ons::PruneSegmentProvider,
     common::WithConfigs,
     components::NodeComponentsBuilder,
     node::FullNode,
@@ -37,8 +38,11 @@ use reth_provider::{
     providers::{BlockchainProvider, NodeTypesForProvider},
     ChainSpecProvider, FullProvider,
 };
+use reth_prune::{segments::Segment, PruneMode};
 use reth_tasks::TaskExecutor;
-use reth_transaction_pool::{PoolConfig, PoolTransaction, TransactionPool};
+use reth_transaction_pool::{
+    maintain::MaintainPoolConfig, PoolConfig, PoolTransaction, TransactionPool,
+};
 use secp256k1::SecretKey;
 use std::sync::Arc;
 use tracing::{info, trace, warn};
@@ -194,6 +198,11 @@ impl<DB, ChainSpec> NodeBuilder<DB, ChainSpec> {
     pub fn config_mut(&mut self) -> &mut NodeConfig<ChainSpec> {
         &mut self.config
     }
+
+    /// Returns a reference to the node builder's database.
+    pub const fn db(&self) -> &DB {
+        &self.database
+    }
 }
 
 impl<DB, ChainSpec: EthChainSpec> NodeBuilder<DB, ChainSpec> {
@@ -293,6 +302,11 @@ impl<DB, ChainSpec> WithLaunchContext<NodeBuilder<DB, ChainSpec>> {
     pub const fn config(&self) -> &NodeConfig<ChainSpec> {
         self.builder.config()
     }
+
+    /// Returns a reference to the node builder's database.
+    pub const fn db(&self) -> &DB {
+        self.builder.db()
+    }
 }
 
 impl<DB, ChainSpec> WithLaunchContext<NodeBuilder<DB, ChainSpec>>
@@ -505,6 +519,24 @@ where
         }
     }
 
+    /// Installs a custom prune segment in the node, which is built with the prune mode configured
+    /// for it by name in the `[prune.segments.custom]` section of the config.
+    ///
+    /// # Note
+    ///
+    /// The segment has to identify as [`PruneSegmentId::Custom`](reth_prune::PruneSegmentId) with
+    /// the same name.
+    pub fn install_prune_segment<S, F>(self, name: &'static str, segment: F) -> Self
+    where
+        S: Segment<PruneSegmentProvider<NodeAdapter<T, CB::Components>>> + 'static,
+        F: Fn(PruneMode) -> S + Send + Sync + 'static,
+    {
+        Self {
+            builder: self.builder.install_prune_segment(name, segment),
+            task_executor: self.task_executor,
+        }
+    }
+
     /// Installs an `ExEx` (Execution Extension) in the node if the condition is true.
     ///
     /// # Note
@@ -630,6 +662,11 @@ impl<Node: FullNodeTypes> BuilderContext<Node> {
         self.config().txpool.pool_config()
     }
 
+    /// Returns the config of the transaction pool maintenance task of the node.
+    pub fn maintain_pool_config(&self) -> MaintainPoolConfig {
+        self.config().txpool.maintain_pool_config()
+    }
+
     /// Loads `EnvKzgSettings::Default`.
     pub const fn kzg_settings(&self) -> eyre::Result<EnvKzgSettings> {
         Ok(EnvKzgSettings::Default)
@@ -644,6 +681,9 @@ impl<Node: FullNodeTypes> BuilderContext<Node> {
     ///
     /// Spawns the configured network and associated tasks and returns the [`NetworkHandle`]
     /// connected to that network.
+    ///
+    /// The transactions task uses the default config, except for the limit of transactions
+    /// announced to new peers, see `--txpool.max-new-peer-announcements`.
     pub fn start_network<N, Pool>(
         &self,
         builder: NetworkBuilder<(), (), N>,
@@ -660,7 +700,11 @@ impl<Node: FullNodeTypes> BuilderContext<Node> {
             + 'static,
         Node::Provider: BlockReaderFor<N>,
     {
-        self.start_network_with(builder, pool, Default::default())
+        let tx_config = TransactionsManagerConfig {
+            max_new_peer_announcements: self.config().txpool.max_new_peer_announcements,
+            ..Default::default()
+        };
+        self.start_network_with(builder, pool, tx_config)
     }
 
     /// Convenience function to start the network tasks.
@@ -777,6 +821,7 @@ impl<Node: FullNodeTypes<Types: NodeTypes<ChainSpec: Hardforks>>> BuilderContext
                 default_peers_path,
             )
             .with_task_executor(Box::new(self.executor.clone()))
+            .with_shutdown(self.executor.on_shutdown_signal().clone())
             .set_head(self.head);
 
         Ok(builder)
@@ -6,6 +6,7 @@
 //! before the node can be launched.
 
 use crate::{
+    add_ons::PruneSegmentProvider,
     components::{NodeComponents, NodeComponentsBuilder},
     hooks::NodeHooks,
     launch::LaunchNode,
@@ -15,6 +16,7 @@ use crate::{
 use reth_exex::ExExContext;
 use reth_node_api::{FullNodeComponents, FullNodeTypes, NodeAddOns, NodeTypes};
 use reth_node_core::node_config::NodeConfig;
+use reth_prune::{segments::Segment, PruneMode};
 use reth_tasks::TaskExecutor;
 use std::{fmt, future::Future};
 
@@ -46,7 +48,12 @@ impl<T: FullNodeTypes> NodeBuilderWithTypes<T> {
             config,
             adapter,
             components_builder,
-            add_ons: AddOns { hooks: NodeHooks::default(), exexs: Vec::new(), add_ons: () },
+            add_ons: AddOns {
+                hooks: NodeHooks::default(),
+                exexs: Vec::new(),
+                prune_segments: Default::default(),
+                add_ons: (),
+            },
         }
     }
 }
@@ -173,7 +180,12 @@ where
             config,
             adapter,
             components_builder,
-            add_ons: AddOns { hooks: NodeHooks::default(), exexs: Vec::new(), add_ons },
+            add_ons: AddOns {
+                hooks: NodeHooks::default(),
+                exexs: Vec::new(),
+                prune_segments: Default::default(),
+                add_ons,
+            },
         }
     }
 }
@@ -219,6 +231,22 @@ where
         self
     }
 
+    /// Installs a custom prune segment in the node, which is built with the prune mode configured
+    /// for it by name in the `[prune.segments.custom]` section of the config.
+    ///
+    /// # Note
+    ///
+    /// The segment has to identify as [`PruneSegmentId::Custom`](reth_prune::PruneSegmentId) with
+    /// the same name.
+    pub fn install_prune_segment<S, F>(mut self, name: &'static str, segment: F) -> Self
+    where
+        S: Segment<PruneSegmentProvider<NodeAdapter<T, CB::Components>>> + 'static,
+        F: Fn(PruneMode) -> S + Send + Sync + 'static,
+    {
+        self.add_ons.prune_segments.register(name, segment);
+        self
+    }
+
     /// Launches the node with the given closure.
     pub fn launch_with_fn<L, R>(self, launcher: L) -> R
     where
@@ -0,0 +1,51 @@
+//! Embedding a node into another program.
+//!
+//! A node can be launched in-process, e.g. from tests or from a binary that bundles a node, and
+//! driven through the handles to its components instead of its RPC servers:
+//!
+//! ```no_run
+//! # use reth_node_builder::{embedded::NodeHandle, FullNodeComponents, rpc::RethRpcAddOns};
+//! # use reth_provider::BlockNumReader;
+//! # use reth_transaction_pool::TransactionPool;
+//! # use futures::StreamExt;
+//! async fn drive<N: FullNodeComponents, A: RethRpcAddOns<N>>(
+//!     handle: NodeHandle<N, A>,
+//! ) -> eyre::Result<()> {
+//!     // read from the database
+//!     let best_block = handle.provider_factory().provider()?.best_block_number()?;
+//!     println!("best block: {best_block}");
+//!
+//!     // inspect the transaction pool
+//!     println!("pending transactions: {}", handle.pool().pool_size().pending);
+//!
+//!     // follow the canonical chain
+//!     let mut notifications = handle.canonical_state_stream();
+//!     if let Some(notification) = notifications.next().await {
+//!         println!("new canonical blocks: {}", notification.committed().len());
+//!     }
+//!
+//!     handle.shutdown().await;
+//!     Ok(())
+//! }
+//! ```
+//!
+//! Instead of a consensus layer client connected to the engine API, the node can also be driven by
+//! a consensus that runs in the same process, e.g. the sealer of a proof of authority chain. It
+//! sends its `newPayload` and `forkchoiceUpdated` messages through the
+//! [`ConsensusEngineHandle`] returned by [`NodeHandle::consensus_engine_handle`], and the auth
+//! server can be disabled with `--authrpc.disable`. See the `custom-consensus-driver` example.
+//!
+//! The items of this module are part of the public API of the crate and follow its semver
+//! guarantees: breaking changes to them are only made in releases that bump the major version, or
+//! the minor version before `1.0`.
+
+pub use crate::{handle::NodeHandle, node::FullNode};
+pub use reth_network_api::FullNetwork;
+pub use reth_node_api::ConsensusEngineHandle;
+pub use reth_payload_builder::PayloadBuilderHandle;
+pub use reth_provider::{
+    CanonStateNotification, CanonStateNotificationStream, CanonStateNotifications,
+    CanonStateSubscriptions, ProviderFactory,
+};
+pub use reth_tasks::{TaskExecutor, TaskManager};
+pub use reth_transaction_pool::TransactionPool;
@@ -1,11 +1,22 @@
 use std::fmt;
 
-use reth_node_api::FullNodeComponents;
+use reth_node_api::{
+    ConsensusEngineHandle, FullNodeComponents, NodeTypesWithDBAdapter, NodeTypesWithEngine,
+};
 use reth_node_core::exit::NodeExitFuture;
+use reth_payload_builder::PayloadBuilderHandle;
+use reth_provider::{
+    CanonStateNotificationStream, CanonStateNotifications, CanonStateSubscriptions,
+    NodePrimitivesProvider, ProviderFactory,
+};
 
 use crate::{node::FullNode, rpc::RethRpcAddOns};
 
 /// A Handle to the launched node.
+///
+/// This is what [`NodeBuilder`](crate::NodeBuilder) returns once the node is launched. Besides the
+/// RPC servers, the node can be driven in-process through the handles to its components. See the
+/// [`embedded`](crate::embedded) module.
 #[must_use = "Needs to await the node exit future"]
 pub struct NodeHandle<Node: FullNodeComponents, AddOns: RethRpcAddOns<Node>> {
     /// All node components.
@@ -19,10 +30,75 @@ where
     Node: FullNodeComponents,
     AddOns: RethRpcAddOns<Node>,
 {
+    /// Returns the factory to open transactions on the node's database and static files.
+    pub const fn provider_factory(
+        &self,
+    ) -> &ProviderFactory<NodeTypesWithDBAdapter<Node::Types, Node::DB>> {
+        &self.node.provider_factory
+    }
+
+    /// Returns the node's transaction pool.
+    pub const fn pool(&self) -> &Node::Pool {
+        &self.node.pool
+    }
+
+    /// Returns the handle to the node's network.
+    pub const fn network(&self) -> &Node::Network {
+        &self.node.network
+    }
+
+    /// Returns the handle to the node's payload builder service.
+    pub const fn payload_builder(
+        &self,
+    ) -> &PayloadBuilderHandle<<Node::Types as NodeTypesWithEngine>::Engine> {
+        &self.node.payload_builder
+    }
+
+    /// Returns the handle to send `newPayload` and `forkchoiceUpdated` messages to the node's
+    /// consensus engine.
+    ///
+    /// This allows driving the node with a consensus that runs in the same process, instead of a
+    /// consensus layer client connected to the engine API.
+    pub const fn consensus_engine_handle(
+        &self,
+    ) -> &ConsensusEngineHandle<<Node::Types as NodeTypesWithEngine>::Engine> {
+        &self.node.consensus_engine_handle
+    }
+
+    /// Subscribes to notifications about changes of the canonical chain of the node.
+    pub fn subscribe_to_canonical_state(
+        &self,
+    ) -> CanonStateNotifications<<Node::Provider as NodePrimitivesProvider>::Primitives> {
+        self.node.provider.subscribe_to_canonical_state()
+    }
+
+    /// Returns a stream of notifications about changes of the canonical chain of the node.
+    pub fn canonical_state_stream(
+        &self,
+    ) -> CanonStateNotificationStream<<Node::Provider as NodePrimitivesProvider>::Primitives> {
+        self.node.provider.canonical_state_stream()
+    }
+
     /// Waits for the node to exit, if it was configured to exit.
     pub async fn wait_for_node_exit(self) -> eyre::Result<()> {
         self.node_exit_future.await
     }
+
+    /// Shuts the node down gracefully.
+    ///
+    /// Stops the RPC servers and fires the shutdown signal of the node's tasks, then waits until
+    /// the tasks that handle the signal, e.g. to persist blocks to disk, have finished.
+    ///
+    /// This shuts down all tasks that were spawned by the [`TaskManager`](reth_tasks::TaskManager)
+    /// the node was launched with.
+    pub async fn shutdown(self) {
+        let rpc_server_handles = &self.node.add_ons_handle.rpc_server_handles;
+        // the servers may have been stopped through their handles already
+        let _ = rpc_server_handles.rpc.clone().stop();
+        let _ = rpc_server_handles.auth.clone().stop();
+
+        self.node.task_executor.initiate_graceful_shutdown().await;
+    }
 }
 
 impl<Node, AddOns> fmt::Debug for NodeHandle<Node, AddOns>
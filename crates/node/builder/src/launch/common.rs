@@ -7,7 +7,7 @@ use crate::{
     hooks::OnComponentInitializedHook,
     BuilderContext, NodeAdapter,
 };
-use alloy_primitives::{BlockNumber, B256};
+use alloy_primitives::{map::B256HashMap, BlockNumber, TxHash, B256};
 use eyre::{Context, OptionExt};
 use rayon::ThreadPoolBuilder;
 use reth_chainspec::{Chain, EthChainSpec, EthereumHardforks};
@@ -17,15 +17,22 @@ use reth_db_api::{
     database::Database,
     database_metrics::{DatabaseMetadata, DatabaseMetrics},
 };
-use reth_db_common::init::{init_genesis, InitStorageError};
+use reth_db_common::{
+    init::{init_genesis, InitStorageError},
+    migration::{MigrationError, Migrations},
+};
 use reth_downloaders::{bodies::noop::NoopBodiesDownloader, headers::noop::NoopHeaderDownloader};
-use reth_engine_local::MiningMode;
-use reth_engine_tree::tree::{InvalidBlockHook, InvalidBlockHooks, NoopInvalidBlockHook};
+use reth_engine_local::{MiningMode, DEV_FEE_RECIPIENT};
+use reth_engine_tree::tree::{
+    InvalidBlockHook, InvalidBlockHooks, NoopInvalidBlockHook, SenderCache,
+};
 use reth_evm::noop::NoopBlockExecutorProvider;
 use reth_fs_util as fs;
-use reth_invalid_block_hooks::InvalidBlockWitnessHook;
+use reth_invalid_block_hooks::{DeepReorgArtifacts, InvalidBlockWitnessHook};
 use reth_network_p2p::headers::client::HeadersClient;
-use reth_node_api::{FullNodeTypes, NodeTypes, NodeTypesWithDB, NodeTypesWithDBAdapter};
+use reth_node_api::{
+    FullNodeTypes, NodeTypes, NodeTypesWithDB, NodeTypesWithDBAdapter, SuggestedFeeRecipient,
+};
 use reth_node_core::{
     args::InvalidBlockHookType,
     dirs::{ChainPath, DataDirPath},
@@ -36,6 +43,7 @@ use reth_node_core::{
         VERGEN_CARGO_TARGET_TRIPLE, VERGEN_GIT_SHA,
     },
 };
+use reth_node_events::disk::{DiskSpaceMonitor, DiskSpaceThresholds};
 use reth_node_metrics::{
     chain::ChainSpecInfo,
     hooks::Hooks,
@@ -45,16 +53,17 @@ use reth_node_metrics::{
 };
 use reth_primitives::Head;
 use reth_provider::{
-    providers::{NodeTypesForProvider, ProviderNodeTypes, StaticFileProvider},
-    BlockHashReader, BlockNumReader, ChainSpecProvider, ProviderError, ProviderFactory,
-    ProviderResult, StageCheckpointReader, StateProviderFactory, StaticFileProviderFactory,
+    providers::{ConsistencyCheck, NodeTypesForProvider, ProviderNodeTypes, StaticFileProvider},
+    BlockHashReader, BlockNumReader, CanonStateSubscriptions, ChainSpecProvider, ProviderError,
+    ProviderFactory, ProviderResult, StageCheckpointReader, StateProviderFactory,
+    StaticFileProviderFactory,
 };
 use reth_prune::{PruneModes, PrunerBuilder};
 use reth_rpc_api::clients::EthApiClient;
 use reth_rpc_builder::config::RethRpcServerConfig;
 use reth_rpc_layer::JwtSecret;
 use reth_stages::{sets::DefaultStages, MetricEvent, PipelineBuilder, PipelineTarget, StageId};
-use reth_static_file::StaticFileProducer;
+use reth_static_file::{StaticFileProducer, StaticFileScrubber, DEFAULT_SCRUBBER_RATE};
 use reth_tasks::TaskExecutor;
 use reth_tracing::tracing::{debug, error, info, warn};
 use reth_transaction_pool::TransactionPool;
@@ -357,6 +366,24 @@ impl<R, ChainSpec: EthChainSpec> LaunchContextWith<Attached<WithConfigs<ChainSpe
             .timeout(PrunerBuilder::DEFAULT_TIMEOUT)
     }
 
+    /// Returns the [`DiskSpaceMonitor`] of the database and static files directories, unless all
+    /// of its thresholds are disabled.
+    pub fn disk_space_monitor(&self) -> Option<DiskSpaceMonitor> {
+        let disk_space = &self.node_config().disk_space;
+        let thresholds = DiskSpaceThresholds {
+            warning: disk_space.min_free_space_warning(),
+            critical: disk_space.min_free_space(),
+        };
+        if thresholds.warning == 0 && thresholds.critical == 0 {
+            return None
+        }
+
+        Some(DiskSpaceMonitor::new(
+            [self.data_dir().db(), self.data_dir().static_files()],
+            thresholds,
+        ))
+    }
+
     /// Loads the JWT secret for the engine API
     pub fn auth_jwt_secret(&self) -> eyre::Result<JwtSecret> {
         let default_jwt_path = self.data_dir().jwt();
@@ -372,6 +399,42 @@ impl<R, ChainSpec: EthChainSpec> LaunchContextWith<Attached<WithConfigs<ChainSpe
             MiningMode::instant(pool)
         }
     }
+
+    /// Returns the [`SuggestedFeeRecipient`] of the payloads built in --dev mode.
+    ///
+    /// The fee recipient set in CLI takes precedence over the one stored in the toml config,
+    /// falling back to the first dev account. If enabled, every change of the fee recipient is
+    /// saved to the toml config.
+    pub fn dev_fee_recipient(&self) -> SuggestedFeeRecipient {
+        let builder_args = &self.node_config().builder;
+        let fee_recipient = SuggestedFeeRecipient::new(
+            builder_args
+                .fee_recipient
+                .or_else(|| self.toml_config().builder.as_ref()?.fee_recipient)
+                .unwrap_or(DEV_FEE_RECIPIENT),
+        );
+
+        if builder_args.persist_fee_recipient {
+            let config_path =
+                self.node_config().config.clone().unwrap_or_else(|| self.data_dir().config());
+            let mut updates = fee_recipient.subscribe();
+            self.task_executor().spawn(async move {
+                while updates.changed().await.is_ok() {
+                    let fee_recipient = *updates.borrow_and_update();
+                    let saved =
+                        reth_config::Config::from_path(&config_path).and_then(|mut config| {
+                            config.update_fee_recipient(fee_recipient);
+                            Ok(config.save(&config_path)?)
+                        });
+                    if let Err(err) = saved {
+                        warn!(target: "reth::cli", %err, "Failed to save fee recipient");
+                    }
+                }
+            });
+        }
+
+        fee_recipient
+    }
 }
 
 impl<DB, ChainSpec> LaunchContextWith<Attached<WithConfigs<ChainSpec>, DB>>
@@ -382,26 +445,50 @@ where
     /// Returns the [`ProviderFactory`] for the attached storage after executing a consistent check
     /// between the database and static files. **It may execute a pipeline unwind if it fails this
     /// check.**
+    ///
+    /// If [`NodeConfig::check_consistency_only`] is set, the process exits after reporting the
+    /// inconsistencies instead, without repairing them.
     pub async fn create_provider_factory<N>(&self) -> eyre::Result<ProviderFactory<N>>
     where
         N: ProviderNodeTypes<DB = DB, ChainSpec = ChainSpec>,
     {
-        let factory = ProviderFactory::new(
-            self.right().clone(),
-            self.chain_spec(),
-            StaticFileProvider::read_write(self.data_dir().static_files())?,
-        )
-        .with_prune_modes(self.prune_modes())
-        .with_static_files_metrics();
+        let mut static_file_provider =
+            StaticFileProvider::read_write(self.data_dir().static_files())?;
+        if let Some(max_open_files) = self.node_config().datadir.max_open_static_files() {
+            static_file_provider = static_file_provider.with_max_open_files(max_open_files);
+        }
+        if self.node_config().static_files.verify_on_open {
+            static_file_provider = static_file_provider.with_verify_on_open();
+        }
+        static_file_provider = static_file_provider
+            .with_preallocation(self.node_config().static_files.preallocation())
+            .with_fsync_policy(self.node_config().static_files.fsync_policy);
+
+        let factory =
+            ProviderFactory::new(self.right().clone(), self.chain_spec(), static_file_provider)
+                .with_prune_modes(self.prune_modes())
+                .with_static_files_metrics();
 
         let has_receipt_pruning =
             self.toml_config().prune.as_ref().is_some_and(|a| a.has_receipts_pruning());
 
-        // Check for consistency between database and static files. If it fails, it unwinds to
-        // the first block that's consistent between database and static files.
-        if let Some(unwind_target) = factory
-            .static_file_provider()
-            .check_consistency(&factory.provider()?, has_receipt_pruning)?
+        if self.node_config().check_consistency_only {
+            let report =
+                ConsistencyCheck::new(has_receipt_pruning).with_check_only(true).run(&factory)?;
+            eyre::ensure!(
+                report.is_consistent(),
+                "Inconsistent storage, {} repairs planned.",
+                report.plan().len()
+            );
+            info!(target: "reth::cli", "Storage is consistent.");
+            std::process::exit(0)
+        }
+
+        // Check for consistency between the stage checkpoints, static files and prune
+        // checkpoints. If data was lost, it unwinds to the first block that's consistent between
+        // database and static files.
+        if let Some(unwind_target) =
+            ConsistencyCheck::new(has_receipt_pruning).run(&factory)?.unwind_target()
         {
             // Highly unlikely to happen, and given its destructive nature, it's better to panic
             // instead.
@@ -542,6 +629,15 @@ where
         init_genesis(self.provider_factory())
     }
 
+    /// Applies the pending database schema [`Migrations`], except for the manual ones that are
+    /// applied by `reth db migrate`.
+    ///
+    /// Fails if the database was migrated by a newer version of the node.
+    pub fn with_migrations(self) -> Result<Self, MigrationError> {
+        Migrations::new(self.toml_config()).run_on_startup(self.provider_factory())?;
+        Ok(self)
+    }
+
     /// Creates a new `WithMeteredProvider` container and attaches it to the
     /// launch context.
     ///
@@ -744,6 +840,18 @@ where
         StaticFileProducer::new(self.provider_factory().clone(), self.prune_modes())
     }
 
+    /// Creates a new [`StaticFileScrubber`] with the attached database, if enabled.
+    pub fn static_file_scrubber(
+        &self,
+    ) -> Option<StaticFileScrubber<ProviderFactory<NodeTypesWithDBAdapter<T::Types, T::DB>>>> {
+        let args = self.node_config().scrubber;
+        args.enable.then(|| {
+            let rate = args.rate.map_or(DEFAULT_SCRUBBER_RATE, |rate| rate as u64);
+            StaticFileScrubber::new(self.provider_factory().clone(), rate)
+                .with_progress_path(self.data_dir().static_file_scrubber())
+        })
+    }
+
     /// Returns the current head block.
     pub const fn head(&self) -> Head {
         self.right().head
@@ -912,6 +1020,40 @@ where
         Ok(Box::new(InvalidBlockHooks(hooks)))
     }
 
+    /// Spawns a task that saves every reorg of the canonical chain that removes more than
+    /// `deep_reorg_threshold` blocks, if invalid block hooks are enabled.
+    ///
+    /// The reorgs are written next to the output of the invalid block hooks.
+    pub fn spawn_deep_reorg_artifacts(&self, deep_reorg_threshold: u64) -> eyre::Result<()> {
+        if self.node_config().debug.invalid_block_hook.is_none() {
+            return Ok(())
+        }
+        let Some(reorg_history) = self.blockchain_db().reorg_history() else { return Ok(()) };
+
+        let output_directory = self.data_dir().invalid_block_hooks().join("reorg");
+        fs::create_dir_all(&output_directory)?;
+
+        let artifacts =
+            DeepReorgArtifacts::new(reorg_history, deep_reorg_threshold, output_directory);
+        self.task_executor().spawn(artifacts.run(self.blockchain_db().canonical_state_stream()));
+
+        Ok(())
+    }
+
+    /// Returns the [`SenderCache`] that serves the senders of the transactions in the pool, which
+    /// were recovered when the transactions were validated.
+    pub fn pool_sender_cache(&self) -> Box<dyn SenderCache> {
+        let pool = self.components().pool().clone();
+        Box::new(move |tx_hashes: &[TxHash]| {
+            let senders: B256HashMap<_> = pool
+                .get_all(tx_hashes.to_vec())
+                .into_iter()
+                .map(|tx| (*tx.hash(), tx.sender()))
+                .collect();
+            tx_hashes.iter().map(|tx_hash| senders.get(tx_hash).copied()).collect()
+        })
+    }
+
     /// Returns an RPC client for the healthy node, if configured in the node config.
     fn get_healthy_node_client(&self) -> eyre::Result<Option<jsonrpsee::http_client::HttpClient>> {
         self.node_config()
@@ -1056,18 +1198,23 @@ mod tests {
                 pruning: PruningArgs {
                     full: true,
                     block_interval: None,
+                    sender_recovery: None,
                     sender_recovery_full: false,
                     sender_recovery_distance: None,
                     sender_recovery_before: None,
+                    transaction_lookup: None,
                     transaction_lookup_full: false,
                     transaction_lookup_distance: None,
                     transaction_lookup_before: None,
+                    receipts: None,
                     receipts_full: false,
                     receipts_distance: None,
                     receipts_before: None,
+                    account_history: None,
                     account_history_full: false,
                     account_history_distance: None,
                     account_history_before: None,
+                    storage_history: None,
                     storage_history_full: false,
                     storage_history_distance: None,
                     storage_history_before: None,
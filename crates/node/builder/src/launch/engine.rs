@@ -1,6 +1,7 @@
 //! Engine node related functionality.
 
 use alloy_consensus::BlockHeader;
+use alloy_rpc_types::engine::ForkchoiceState;
 use futures::{future::Either, stream, stream_select, StreamExt};
 use reth_beacon_consensus::{
     hooks::{EngineHooks, StaticFileHook},
@@ -18,20 +19,20 @@ use reth_engine_tree::{
     engine::{EngineApiRequest, EngineRequestHandler},
     tree::TreeConfig,
 };
-use reth_engine_util::EngineMessageStreamExt;
+use reth_engine_util::{engine_record::EngineRecorder, EngineMessageStreamExt};
 use reth_exex::ExExManagerHandle;
 use reth_network::{NetworkSyncUpdater, SyncState};
 use reth_network_api::BlockDownloaderProvider;
 use reth_node_api::{
-    BuiltPayload, FullNodeTypes, NodeTypesWithDBAdapter, NodeTypesWithEngine,
-    PayloadAttributesBuilder, PayloadBuilder, PayloadTypes,
+    BuiltPayload, EngineApiMessageVersion, FullNodeTypes, NodeTypesWithDBAdapter,
+    NodeTypesWithEngine, PayloadAttributesBuilder, PayloadBuilder, PayloadTypes,
 };
 use reth_node_core::{
     dirs::{ChainPath, DataDirPath},
     exit::NodeExitFuture,
     primitives::Head,
 };
-use reth_node_events::{cl::ConsensusLayerHealthEvents, node};
+use reth_node_events::{cl::ConsensusLayerHealthEvents, fork::ForkCountdownEvents, node};
 use reth_primitives::EthereumHardforks;
 use reth_provider::providers::{BlockchainProvider2, NodeTypesForProvider};
 use reth_tasks::TaskExecutor;
@@ -98,7 +99,7 @@ where
         let NodeBuilderWithComponents {
             adapter: NodeTypesAdapter { database },
             components_builder,
-            add_ons: AddOns { hooks, exexs: installed_exex, add_ons },
+            add_ons: AddOns { hooks, exexs: installed_exex, prune_segments, add_ons },
             config,
         } = target;
         let NodeHooks { on_component_initialized, on_node_started, .. } = hooks;
@@ -124,6 +125,8 @@ where
                 debug!(target: "reth::cli", chain=%this.chain_id(), genesis=?this.genesis_hash(), "Initializing genesis");
             })
             .with_genesis()?
+            // apply pending database migrations
+            .with_migrations()?
             .inspect(|this: &LaunchContextWith<Attached<WithConfigs<Types::ChainSpec>, _>>| {
                 info!(target: "reth::cli", "\n{}", this.chain_spec().display_hardforks());
             })
@@ -150,6 +153,16 @@ where
         let (consensus_engine_tx, consensus_engine_rx) = unbounded_channel();
 
         let node_config = ctx.node_config();
+        let engine_recorder = node_config
+            .debug
+            .record
+            .clone()
+            .map(|dir| {
+                let recorder = EngineRecorder::open(dir)?;
+                info!(target: "reth::cli", dir = %recorder.dir().display(), "Recording engine API messages");
+                recorder.spawn()
+            })
+            .transpose()?;
         let consensus_engine_stream = UnboundedReceiverStream::from(consensus_engine_rx)
             .maybe_skip_fcu(node_config.debug.skip_fcu)
             .maybe_skip_new_payload(node_config.debug.skip_new_payload)
@@ -163,12 +176,21 @@ where
             // Store messages _after_ skipping so that `replay-engine` command
             // would replay only the messages that were observed by the engine
             // during this run.
-            .maybe_store_messages(node_config.debug.engine_api_store.clone());
+            .maybe_store_messages(node_config.debug.engine_api_store.clone())
+            // Record messages last, so that the recorded outcomes are those of the engine.
+            .maybe_record(engine_recorder);
 
         let max_block = ctx.max_block(network_client.clone()).await?;
         let mut hooks = EngineHooks::new();
 
+        // pauses syncing, pruning and writing static files when the disk is almost full
+        let disk_space_monitor = ctx.disk_space_monitor();
+        let pause_signal = disk_space_monitor.as_ref().map(|monitor| monitor.pause_signal());
+
         let static_file_producer = ctx.static_file_producer();
+        if let Some(pause_signal) = &pause_signal {
+            static_file_producer.set_pause_signal(pause_signal.clone());
+        }
         let static_file_producer_events = static_file_producer.lock().events();
         hooks.add(StaticFileHook::new(
             static_file_producer.clone(),
@@ -176,12 +198,19 @@ where
         ));
         info!(target: "reth::cli", "StaticFileProducer initialized");
 
+        if let Some(mut scrubber) = ctx.static_file_scrubber() {
+            if let Some(pause_signal) = &pause_signal {
+                scrubber = scrubber.with_pause_signal(pause_signal.clone());
+            }
+            ctx.task_executor().spawn_blocking(scrubber.run());
+        }
+
         let consensus = Arc::new(ctx.components().consensus().clone());
 
         // Configure the pipeline
         let pipeline_exex_handle =
             exex_manager_handle.clone().unwrap_or_else(ExExManagerHandle::empty);
-        let pipeline = build_networked_pipeline(
+        let mut pipeline = build_networked_pipeline(
             &ctx.toml_config().stages,
             network_client.clone(),
             consensus.clone(),
@@ -193,8 +222,13 @@ where
             static_file_producer,
             ctx.components().block_executor().clone(),
             pipeline_exex_handle,
+            prune_segments.clone(),
         )?;
 
+        if let Some(pause_signal) = &pause_signal {
+            pipeline.set_pause_signal(pause_signal.clone());
+        }
+
         // The new engine writes directly to static files. This ensures that they're up to the tip.
         pipeline.move_to_static_files()?;
 
@@ -205,7 +239,11 @@ where
             pruner_builder =
                 pruner_builder.finished_exex_height(exex_manager_handle.finished_height());
         }
-        let pruner = pruner_builder.build_with_provider_factory(ctx.provider_factory().clone());
+        if let Some(pause_signal) = pause_signal {
+            pruner_builder = pruner_builder.pause_signal(pause_signal);
+        }
+        let pruner =
+            pruner_builder.build_with_registry(ctx.provider_factory().clone(), &prune_segments)?;
         let pruner_events = pruner.events();
         info!(target: "reth::cli", prune_config=?ctx.prune_config().unwrap_or_default(), "Pruner initialized");
 
@@ -215,14 +253,20 @@ where
         // extract the jwt secret from the args if possible
         let jwt_secret = ctx.auth_jwt_secret()?;
 
+        // in dev mode the node builds its own payloads
+        let fee_recipient = ctx.is_dev().then(|| ctx.dev_fee_recipient());
+
         let add_ons_ctx = AddOnsContext {
             node: ctx.node_adapter().clone(),
             config: ctx.node_config(),
             beacon_engine_handle: beacon_engine_handle.clone(),
             jwt_secret,
+            fee_recipient: fee_recipient.clone(),
         };
         let engine_payload_validator = add_ons.engine_validator(&add_ons_ctx).await?;
 
+        ctx.spawn_deep_reorg_artifacts(engine_tree_config.deep_reorg_threshold())?;
+
         let mut engine_service = if ctx.is_dev() {
             let eth_service = LocalEngineService::new(
                 consensus.clone(),
@@ -234,11 +278,13 @@ where
                 engine_payload_validator,
                 engine_tree_config,
                 ctx.invalid_block_hook()?,
+                ctx.pool_sender_cache(),
                 ctx.sync_metrics_tx(),
                 consensus_engine_tx.clone(),
                 Box::pin(consensus_engine_stream),
                 ctx.dev_mining_mode(ctx.components().pool()),
-                LocalPayloadAttributesBuilder::new(ctx.chain_spec()),
+                LocalPayloadAttributesBuilder::new(ctx.chain_spec())
+                    .with_fee_recipient(fee_recipient.unwrap_or_default()),
             );
 
             Either::Left(eth_service)
@@ -258,6 +304,7 @@ where
                 engine_payload_validator,
                 engine_tree_config,
                 ctx.invalid_block_hook()?,
+                ctx.pool_sender_cache(),
                 ctx.sync_metrics_tx(),
             );
 
@@ -277,13 +324,20 @@ where
             } else {
                 Either::Right(stream::empty())
             },
+            ForkCountdownEvents::new(ctx.chain_spec()).map(Into::into),
             pruner_events.map(Into::into),
             static_file_producer_events.map(Into::into),
+            disk_space_monitor.map_or_else(
+                || Either::Right(stream::empty()),
+                |monitor| Either::Left(monitor.map(Into::into))
+            ),
         );
         ctx.task_executor().spawn_critical(
             "events task",
             node::handle_events(
                 Some(Box::new(ctx.components().network().clone())),
+                Some(Box::new(ctx.provider_factory().db_ref().clone())),
+                Some(Box::new(ctx.provider_factory().clone())),
                 Some(ctx.head().number),
                 events,
             ),
@@ -313,6 +367,10 @@ where
                     )
                 })?,
             );
+            eyre::ensure!(
+                !ctx.node_config().rpc.auth_disable,
+                "etherscan consensus client requires the auth server"
+            );
             let rpc_consensus_client = DebugConsensusClient::new(
                 rpc_server_handles.auth.clone(),
                 Arc::new(block_provider),
@@ -402,13 +460,37 @@ where
             let _ = exit.send(res);
         });
 
+        if ctx.node_config().debug.resume_forkchoice {
+            if let Some(restored) =
+                ctx.blockchain_db().canonical_in_memory_state().restored_forkchoice_state()
+            {
+                info!(target: "reth::cli", head = %restored.head_block_hash, "Resuming from the restored forkchoice state");
+                let state = ForkchoiceState {
+                    head_block_hash: restored.head_block_hash,
+                    safe_block_hash: restored.safe_block_hash,
+                    finalized_block_hash: restored.finalized_block_hash,
+                };
+                let beacon_engine_handle = beacon_engine_handle.clone();
+                ctx.task_executor().spawn(async move {
+                    if let Err(err) = beacon_engine_handle
+                        .fork_choice_updated(state, None, EngineApiMessageVersion::default())
+                        .await
+                    {
+                        error!(target: "reth::cli", %err, "Failed to resume from the restored forkchoice state");
+                    }
+                });
+            }
+        }
+
         let full_node = FullNode {
             evm_config: ctx.components().evm_config().clone(),
             block_executor: ctx.components().block_executor().clone(),
             pool: ctx.components().pool().clone(),
             network: ctx.components().network().clone(),
             provider: ctx.node_adapter().provider.clone(),
+            provider_factory: ctx.provider_factory().clone(),
             payload_builder: ctx.components().payload_builder().clone(),
+            consensus_engine_handle: beacon_engine_handle,
             task_executor: ctx.task_executor().clone(),
             config: ctx.node_config().clone(),
             data_dir: ctx.data_dir().clone(),
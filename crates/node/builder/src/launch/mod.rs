@@ -26,7 +26,7 @@ use reth_blockchain_tree::{
 };
 use reth_chainspec::EthChainSpec;
 use reth_consensus_debug_client::{DebugConsensusClient, EtherscanBlockProvider, RpcBlockProvider};
-use reth_engine_util::EngineMessageStreamExt;
+use reth_engine_util::{engine_record::EngineRecorder, EngineMessageStreamExt};
 use reth_exex::ExExManagerHandle;
 use reth_network::BlockDownloaderProvider;
 use reth_node_api::{AddOnsContext, FullNodeTypes, NodeTypesWithDBAdapter, NodeTypesWithEngine};
@@ -34,7 +34,9 @@ use reth_node_core::{
     dirs::{ChainPath, DataDirPath},
     exit::NodeExitFuture,
 };
-use reth_node_events::{cl::ConsensusLayerHealthEvents, node, node::NodeEvent};
+use reth_node_events::{
+    cl::ConsensusLayerHealthEvents, fork::ForkCountdownEvents, node, node::NodeEvent,
+};
 use reth_provider::providers::{BlockchainProvider, NodeTypesForTree};
 use reth_rpc::eth::RpcNodeCore;
 use reth_tasks::TaskExecutor;
@@ -125,7 +127,7 @@ where
         let NodeBuilderWithComponents {
             adapter: NodeTypesAdapter { database },
             components_builder,
-            add_ons: AddOns { hooks, exexs: installed_exex, add_ons },
+            add_ons: AddOns { hooks, exexs: installed_exex, prune_segments, add_ons },
             config,
         } = target;
         let NodeHooks { on_component_initialized, on_node_started, .. } = hooks;
@@ -164,6 +166,8 @@ where
                 debug!(target: "reth::cli", chain=%this.chain_id(), genesis=?this.genesis_hash(), "Initializing genesis");
             })
             .with_genesis()?
+            // apply pending database migrations
+            .with_migrations()?
             .inspect(|this: &LaunchContextWith<Attached<WithConfigs<Types::ChainSpec>, _>>| {
                 info!(target: "reth::cli", "\n{}", this.chain_spec().display_hardforks());
             })
@@ -210,6 +214,16 @@ where
         let (consensus_engine_tx, consensus_engine_rx) = unbounded_channel();
 
         let node_config = ctx.node_config();
+        let engine_recorder = node_config
+            .debug
+            .record
+            .clone()
+            .map(|dir| {
+                let recorder = EngineRecorder::open(dir)?;
+                info!(target: "reth::cli", dir = %recorder.dir().display(), "Recording engine API messages");
+                recorder.spawn()
+            })
+            .transpose()?;
         let consensus_engine_stream = UnboundedReceiverStream::from(consensus_engine_rx)
             .maybe_skip_fcu(node_config.debug.skip_fcu)
             .maybe_skip_new_payload(node_config.debug.skip_new_payload)
@@ -223,12 +237,21 @@ where
             // Store messages _after_ skipping so that `replay-engine` command
             // would replay only the messages that were observed by the engine
             // during this run.
-            .maybe_store_messages(node_config.debug.engine_api_store.clone());
+            .maybe_store_messages(node_config.debug.engine_api_store.clone())
+            // Record messages last, so that the recorded outcomes are those of the engine.
+            .maybe_record(engine_recorder);
 
         let max_block = ctx.max_block(network_client.clone()).await?;
         let mut hooks = EngineHooks::new();
 
+        // pauses syncing, pruning and writing static files when the disk is almost full
+        let disk_space_monitor = ctx.disk_space_monitor();
+        let pause_signal = disk_space_monitor.as_ref().map(|monitor| monitor.pause_signal());
+
         let static_file_producer = ctx.static_file_producer();
+        if let Some(pause_signal) = &pause_signal {
+            static_file_producer.set_pause_signal(pause_signal.clone());
+        }
         let static_file_producer_events = static_file_producer.lock().events();
         hooks.add(StaticFileHook::new(
             static_file_producer.clone(),
@@ -236,10 +259,17 @@ where
         ));
         info!(target: "reth::cli", "StaticFileProducer initialized");
 
+        if let Some(mut scrubber) = ctx.static_file_scrubber() {
+            if let Some(pause_signal) = &pause_signal {
+                scrubber = scrubber.with_pause_signal(pause_signal.clone());
+            }
+            ctx.task_executor().spawn_blocking(scrubber.run());
+        }
+
         // Configure the pipeline
         let pipeline_exex_handle =
             exex_manager_handle.clone().unwrap_or_else(ExExManagerHandle::empty);
-        let (pipeline, client) = if ctx.is_dev() {
+        let (mut pipeline, client) = if ctx.is_dev() {
             eyre::bail!("Dev mode is not supported for legacy engine")
         } else {
             let pipeline = crate::setup::build_networked_pipeline(
@@ -254,10 +284,14 @@ where
                 static_file_producer,
                 ctx.components().block_executor().clone(),
                 pipeline_exex_handle,
+                prune_segments.clone(),
             )?;
 
             (pipeline, network_client.clone())
         };
+        if let Some(pause_signal) = &pause_signal {
+            pipeline.set_pause_signal(pause_signal.clone());
+        }
 
         let pipeline_events = pipeline.events();
 
@@ -268,7 +302,11 @@ where
             pruner_builder =
                 pruner_builder.finished_exex_height(exex_manager_handle.finished_height());
         }
-        let pruner = pruner_builder.build_with_provider_factory(ctx.provider_factory().clone());
+        if let Some(pause_signal) = pause_signal {
+            pruner_builder = pruner_builder.pause_signal(pause_signal);
+        }
+        let pruner =
+            pruner_builder.build_with_registry(ctx.provider_factory().clone(), &prune_segments)?;
 
         let pruner_events = pruner.events();
         info!(target: "reth::cli", prune_config=?ctx.prune_config().unwrap_or_default(), "Pruner initialized");
@@ -301,13 +339,20 @@ where
             } else {
                 Either::Right(stream::empty())
             },
+            ForkCountdownEvents::new(ctx.chain_spec()).map(Into::into),
             pruner_events.map(Into::into),
             static_file_producer_events.map(Into::into),
+            disk_space_monitor.map_or_else(
+                || Either::Right(stream::empty()),
+                |monitor| Either::Left(monitor.map(Into::into))
+            ),
         );
         ctx.task_executor().spawn_critical(
             "events task",
             node::handle_events(
                 Some(Box::new(ctx.components().network().clone())),
+                Some(Box::new(ctx.provider_factory().db_ref().clone())),
+                Some(Box::new(ctx.provider_factory().clone())),
                 Some(ctx.head().number),
                 events,
             ),
@@ -319,8 +364,9 @@ where
         let add_ons_ctx = AddOnsContext {
             node: ctx.node_adapter().clone(),
             config: ctx.node_config(),
-            beacon_engine_handle,
+            beacon_engine_handle: beacon_engine_handle.clone(),
             jwt_secret,
+            fee_recipient: None,
         };
 
         let RpcHandle { rpc_server_handles, rpc_registry } =
@@ -354,6 +400,10 @@ where
                     )
                 })?,
             );
+            eyre::ensure!(
+                !ctx.node_config().rpc.auth_disable,
+                "etherscan consensus client requires the auth server"
+            );
             let rpc_consensus_client = DebugConsensusClient::new(
                 rpc_server_handles.auth.clone(),
                 Arc::new(block_provider),
@@ -367,6 +417,10 @@ where
             info!(target: "reth::cli", "Using rpc provider as consensus client");
 
             let block_provider = RpcBlockProvider::new(rpc_ws_url);
+            eyre::ensure!(
+                !ctx.node_config().rpc.auth_disable,
+                "rpc consensus client requires the auth server"
+            );
             let rpc_consensus_client = DebugConsensusClient::new(
                 rpc_server_handles.auth.clone(),
                 Arc::new(block_provider),
@@ -382,7 +436,9 @@ where
             pool: ctx.components().pool().clone(),
             network: ctx.components().network().clone(),
             provider: ctx.node_adapter().provider.clone(),
+            provider_factory: ctx.provider_factory().clone(),
             payload_builder: ctx.components().payload_builder().clone(),
+            consensus_engine_handle: beacon_engine_handle,
             task_executor: ctx.task_executor().clone(),
             config: ctx.node_config().clone(),
             data_dir: ctx.data_dir().clone(),
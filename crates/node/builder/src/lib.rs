@@ -34,6 +34,8 @@ pub use reth_engine_tree::tree::config as engine_tree_config;
 mod handle;
 pub use handle::NodeHandle;
 
+pub mod embedded;
+
 pub mod rpc;
 
 pub mod setup;
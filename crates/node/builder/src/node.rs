@@ -8,13 +8,15 @@ use std::{
 };
 
 use reth_network::NetworkPrimitives;
-use reth_node_api::{BlockBody, EngineTypes, FullNodeComponents};
+use reth_node_api::{
+    BlockBody, ConsensusEngineHandle, EngineTypes, FullNodeComponents, NodeTypesWithDBAdapter,
+};
 use reth_node_core::{
     dirs::{ChainPath, DataDirPath},
     node_config::NodeConfig,
 };
 use reth_payload_builder::PayloadBuilderHandle;
-use reth_provider::{BlockReader, ChainSpecProvider};
+use reth_provider::{BlockReader, ChainSpecProvider, ProviderFactory};
 use reth_rpc_api::EngineApiClient;
 use reth_rpc_builder::{auth::AuthServerHandle, RpcServerHandle};
 use reth_tasks::TaskExecutor;
@@ -118,8 +120,18 @@ pub struct FullNode<Node: FullNodeComponents, AddOns: NodeAddOns<Node>> {
     pub network: Node::Network,
     /// Provider to interact with the node's database
     pub provider: Node::Provider,
+    /// Factory to open transactions on the node's database and static files.
+    pub provider_factory: ProviderFactory<NodeTypesWithDBAdapter<Node::Types, Node::DB>>,
     /// Handle to the node's payload builder service.
     pub payload_builder: PayloadBuilderHandle<<Node::Types as NodeTypesWithEngine>::Engine>,
+    /// Handle to send engine messages to the node's consensus engine.
+    ///
+    /// A consensus driver running in the node process can use this instead of the engine API, in
+    /// which case the auth server can be disabled with [`RpcServerArgs::with_auth_disabled`].
+    ///
+    /// [`RpcServerArgs::with_auth_disabled`]: reth_node_core::args::RpcServerArgs::with_auth_disabled
+    pub consensus_engine_handle:
+        ConsensusEngineHandle<<Node::Types as NodeTypesWithEngine>::Engine>,
     /// Task executor for the node.
     pub task_executor: TaskExecutor,
     /// The initial node config.
@@ -138,7 +150,9 @@ impl<Node: FullNodeComponents, AddOns: NodeAddOns<Node>> Clone for FullNode<Node
             pool: self.pool.clone(),
             network: self.network.clone(),
             provider: self.provider.clone(),
+            provider_factory: self.provider_factory.clone(),
             payload_builder: self.payload_builder.clone(),
+            consensus_engine_handle: self.consensus_engine_handle.clone(),
             task_executor: self.task_executor.clone(),
             config: self.config.clone(),
             data_dir: self.data_dir.clone(),
@@ -171,29 +185,49 @@ where
     }
 
     /// Returns the [`AuthServerHandle`] to the started authenticated engine API server.
+    ///
+    /// If the auth server is disabled, this is a [`AuthServerHandle::noop`] handle, see
+    /// [`Self::is_auth_server_enabled`].
     pub const fn auth_server_handle(&self) -> &AuthServerHandle {
         &self.add_ons_handle.rpc_server_handles.auth
     }
 
+    /// Returns `true` if the authenticated engine API server was started.
+    pub const fn is_auth_server_enabled(&self) -> bool {
+        !self.config.rpc.auth_disable
+    }
+
     /// Returns the [`EngineApiClient`] interface for the authenticated engine API.
     ///
     /// This will send authenticated http requests to the node's auth server.
-    pub fn engine_http_client(&self) -> impl EngineApiClient<Engine> {
-        self.auth_server_handle().http_client()
+    ///
+    /// Returns `None` if the auth server is disabled.
+    pub fn engine_http_client(&self) -> Option<impl EngineApiClient<Engine>> {
+        self.is_auth_server_enabled().then(|| self.auth_server_handle().http_client())
     }
 
     /// Returns the [`EngineApiClient`] interface for the authenticated engine API.
     ///
     /// This will send authenticated ws requests to the node's auth server.
-    pub async fn engine_ws_client(&self) -> impl EngineApiClient<Engine> {
-        self.auth_server_handle().ws_client().await
+    ///
+    /// Returns `None` if the auth server is disabled.
+    pub async fn engine_ws_client(&self) -> Option<impl EngineApiClient<Engine>> {
+        if !self.is_auth_server_enabled() {
+            return None
+        }
+        Some(self.auth_server_handle().ws_client().await)
     }
 
     /// Returns the [`EngineApiClient`] interface for the authenticated engine API.
     ///
     /// This will send not authenticated IPC requests to the node's auth server.
+    ///
+    /// Returns `None` if the auth server or its IPC endpoint is disabled.
     #[cfg(unix)]
     pub async fn engine_ipc_client(&self) -> Option<impl EngineApiClient<Engine>> {
+        if !self.is_auth_server_enabled() {
+            return None
+        }
         self.auth_server_handle().ipc_client().await
     }
 }
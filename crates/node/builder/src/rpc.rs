@@ -8,7 +8,7 @@ use std::{
 };
 
 use alloy_rpc_types::engine::ClientVersionV1;
-use futures::TryFutureExt;
+use futures::{future::Either, TryFutureExt};
 use reth_node_api::{
     AddOnsContext, BlockTy, EngineValidator, FullNodeComponents, NodeAddOns, NodeTypes,
     NodeTypesWithEngine,
@@ -19,18 +19,22 @@ use reth_node_core::{
 };
 use reth_payload_builder::PayloadStore;
 use reth_primitives::EthPrimitives;
+use reth_provider::CanonStateSubscriptions;
 use reth_rpc::{
     eth::{EthApiTypes, FullEthApiServer},
-    EthApi,
+    DebugDbApi, DebugLogApi, DebugReorgApi, EthApi, MinerApi,
+};
+use reth_rpc_api::{
+    eth::helpers::{AddDevSigners, EthApiSpec},
+    DebugDbApiServer, DebugLogApiServer, DebugReorgApiServer, MinerApiServer, RethApiServer,
 };
-use reth_rpc_api::eth::helpers::AddDevSigners;
 use reth_rpc_builder::{
     auth::{AuthRpcModule, AuthServerHandle},
     config::RethRpcServerConfig,
-    RpcModuleBuilder, RpcRegistryInner, RpcServerHandle, TransportRpcModules,
+    RethRpcModule, RpcModuleBuilder, RpcRegistryInner, RpcServerHandle, TransportRpcModules,
 };
 use reth_rpc_engine_api::{capabilities::EngineCapabilities, EngineApi};
-use reth_tasks::TaskExecutor;
+use reth_tasks::{pool::BlockingTaskPool, TaskExecutor};
 use reth_tracing::tracing::{debug, info};
 use std::sync::Arc;
 
@@ -43,7 +47,10 @@ use crate::EthApiBuilderCtx;
 pub struct RethRpcServerHandles {
     /// The regular RPC server handle to all configured transports.
     pub rpc: RpcServerHandle,
-    /// The handle to the auth server (engine API)
+    /// The handle to the auth server (engine API).
+    ///
+    /// If the auth server is disabled with `--authrpc.disable`, this is a
+    /// [`AuthServerHandle::noop`] handle.
     pub auth: AuthServerHandle,
 }
 
@@ -423,7 +430,7 @@ where
         let Self { eth_api_builder, engine_validator_builder, hooks, _pd: _ } = self;
 
         let engine_validator = engine_validator_builder.build(&ctx).await?;
-        let AddOnsContext { node, config, beacon_engine_handle, jwt_secret } = ctx;
+        let AddOnsContext { node, config, beacon_engine_handle, jwt_secret, fee_recipient } = ctx;
 
         let client = ClientVersionV1 {
             code: CLIENT_CODE,
@@ -432,6 +439,7 @@ where
             commit: VERGEN_GIT_SHA.to_string(),
         };
 
+        let engine_capabilities = EngineCapabilities::default();
         let engine_api = EngineApi::new(
             node.provider().clone(),
             config.chain.clone(),
@@ -440,7 +448,7 @@ where
             node.pool().clone(),
             Box::new(node.task_executor().clone()),
             client,
-            EngineCapabilities::default(),
+            engine_capabilities.clone(),
             engine_validator.clone(),
         );
         info!(target: "reth::cli", "Engine API handler initialized");
@@ -449,6 +457,19 @@ where
         let module_config = config.rpc.transport_rpc_module_config();
         debug!(target: "reth::cli", http=?module_config.http(), ws=?module_config.ws(), "Using RPC module config");
 
+        // compute heavy RPC requests run on a dedicated pool, apart from the critical tasks
+        let compute_threads = config.rpc.rpc_compute_threads;
+        let compute_pool = BlockingTaskPool::build_compute(compute_threads)?;
+        if let Err(compute_pool) = node.task_executor().set_compute_pool(compute_pool) {
+            let configured = node.task_executor().compute_pool().num_threads();
+            if compute_threads != 0 && configured != compute_pool.num_threads() {
+                eyre::bail!(
+                    "--rpc.compute-threads {compute_threads} can't be applied, the compute pool is already running with {configured} threads"
+                )
+            }
+            debug!(target: "reth::cli", threads = configured, "Using the configured compute pool");
+        }
+
         let (mut modules, mut auth_module, registry) = RpcModuleBuilder::default()
             .with_provider(node.provider().clone())
             .with_pool(node.pool().clone())
@@ -470,6 +491,38 @@ where
             registry.eth_api().with_dev_accounts();
         }
 
+        // expose the fee recipient of locally built payloads via `eth_coinbase` and allow changing
+        // it with `miner_setEtherbase`
+        if let Some(fee_recipient) = fee_recipient {
+            *EthApiSpec::fee_recipient(registry.eth_api()).write() =
+                Some(fee_recipient.subscribe());
+            modules.replace_if_module_configured(
+                RethRpcModule::Miner,
+                MinerApi::new(fee_recipient).into_rpc(),
+            )?;
+        }
+
+        // the fork readiness is checked against the engine API served by the auth server
+        modules.replace_if_module_configured(
+            RethRpcModule::Reth,
+            registry.reth_api().with_engine_capabilities(engine_capabilities).into_rpc(),
+        )?;
+
+        // changing the logging of the node is only served by the auth server
+        auth_module.merge_auth_methods(DebugLogApi::new().into_rpc())?;
+
+        // the reorg history is only served by the auth server
+        auth_module
+            .merge_auth_methods(DebugReorgApi::new(node.provider().reorg_history()).into_rpc())?;
+
+        // raw database access is only served by the auth server, and only if enabled
+        if config.rpc.rpc_enable_db_debug {
+            auth_module.merge_auth_methods(
+                DebugDbApi::new(node.provider().clone(), Box::new(node.task_executor().clone()))
+                    .into_rpc(),
+            )?;
+        }
+
         let mut registry = RpcRegistry { registry };
         let ctx = RpcContext {
             node: node.clone(),
@@ -484,7 +537,8 @@ where
         ext(ctx.modules, ctx.auth_module)?;
         extend_rpc_modules.extend_rpc_modules(ctx)?;
 
-        let server_config = config.rpc.rpc_server_config();
+        let server_config =
+            config.rpc.rpc_server_config().with_block_confirmations(node.provider().clone());
         let cloned_modules = modules.clone();
         let launch_rpc = server_config.start(&cloned_modules).map_ok(|handle| {
             if let Some(path) = handle.ipc_endpoint() {
@@ -499,15 +553,21 @@ where
             handle
         });
 
-        let launch_auth = auth_module.clone().start_server(auth_config).map_ok(|handle| {
-            let addr = handle.local_addr();
-            if let Some(ipc_endpoint) = handle.ipc_endpoint() {
-                info!(target: "reth::cli", url=%addr, ipc_endpoint=%ipc_endpoint,"RPC auth server started");
-            } else {
-                info!(target: "reth::cli", url=%addr, "RPC auth server started");
-            }
-            handle
-        });
+        // without the auth server the engine is driven through the consensus engine handle
+        let launch_auth = if config.rpc.auth_disable {
+            info!(target: "reth::cli", "RPC auth server disabled");
+            Either::Left(futures::future::ok(AuthServerHandle::noop()))
+        } else {
+            Either::Right(auth_module.clone().start_server(auth_config).map_ok(|handle| {
+                let addr = handle.local_addr();
+                if let Some(ipc_endpoint) = handle.ipc_endpoint() {
+                    info!(target: "reth::cli", url=%addr, ipc_endpoint=%ipc_endpoint,"RPC auth server started");
+                } else {
+                    info!(target: "reth::cli", url=%addr, "RPC auth server started");
+                }
+                handle
+            }))
+        };
 
         // launch servers concurrently
         let (rpc, auth) = futures::future::try_join(launch_rpc, launch_auth).await?;
@@ -15,8 +15,13 @@ use reth_network_p2p::{
     bodies::downloader::BodyDownloader, headers::downloader::HeaderDownloader, BlockClient,
 };
 use reth_node_api::{BodyTy, HeaderTy};
-use reth_provider::{providers::ProviderNodeTypes, ProviderFactory};
-use reth_stages::{prelude::DefaultStages, stages::ExecutionStage, Pipeline, StageSet};
+use reth_provider::{providers::ProviderNodeTypes, DatabaseProviderFactory, ProviderFactory};
+use reth_prune::segments::PruneSegmentRegistry;
+use reth_stages::{
+    prelude::DefaultStages,
+    stages::{ExecutionStage, PruneStage},
+    Pipeline, StageSet,
+};
 use reth_static_file::StaticFileProducer;
 use reth_tasks::TaskExecutor;
 use reth_tracing::tracing::debug;
@@ -36,6 +41,9 @@ pub fn build_networked_pipeline<N, Client, Executor>(
     static_file_producer: StaticFileProducer<ProviderFactory<N>>,
     executor: Executor,
     exex_manager_handle: ExExManagerHandle<N::Primitives>,
+    custom_prune_segments: PruneSegmentRegistry<
+        <ProviderFactory<N> as DatabaseProviderFactory>::ProviderRW,
+    >,
 ) -> eyre::Result<Pipeline<N>>
 where
     N: ProviderNodeTypes,
@@ -63,6 +71,7 @@ where
         static_file_producer,
         executor,
         exex_manager_handle,
+        custom_prune_segments,
     )?;
 
     Ok(pipeline)
@@ -82,6 +91,9 @@ pub fn build_pipeline<N, H, B, Executor>(
     static_file_producer: StaticFileProducer<ProviderFactory<N>>,
     executor: Executor,
     exex_manager_handle: ExExManagerHandle<N::Primitives>,
+    custom_prune_segments: PruneSegmentRegistry<
+        <ProviderFactory<N> as DatabaseProviderFactory>::ProviderRW,
+    >,
 ) -> eyre::Result<Pipeline<N>>
 where
     N: ProviderNodeTypes,
@@ -100,28 +112,35 @@ where
 
     let prune_modes = prune_config.map(|prune| prune.segments).unwrap_or_default();
 
+    let mut stages = DefaultStages::new(
+        provider_factory.clone(),
+        tip_rx,
+        Arc::clone(&consensus),
+        header_downloader,
+        body_downloader,
+        executor.clone(),
+        stage_config.clone(),
+        prune_modes.clone(),
+    )
+    .set(ExecutionStage::new(
+        executor,
+        stage_config.execution.into(),
+        stage_config.execution_external_clean_threshold(),
+        prune_modes.clone(),
+        exex_manager_handle,
+    ));
+    // the prune stage is only part of the pipeline if any segment is configured
+    if !prune_modes.is_empty() {
+        stages = stages.set(
+            PruneStage::new(prune_modes, stage_config.prune.commit_threshold)
+                .with_custom_segments(custom_prune_segments),
+        );
+    }
+
     let pipeline = builder
         .with_tip_sender(tip_tx)
         .with_metrics_tx(metrics_tx)
-        .add_stages(
-            DefaultStages::new(
-                provider_factory.clone(),
-                tip_rx,
-                Arc::clone(&consensus),
-                header_downloader,
-                body_downloader,
-                executor.clone(),
-                stage_config.clone(),
-                prune_modes.clone(),
-            )
-            .set(ExecutionStage::new(
-                executor,
-                stage_config.execution.into(),
-                stage_config.execution_external_clean_threshold(),
-                prune_modes,
-                exex_manager_handle,
-            )),
-        )
+        .add_stages(stages)
         .build(provider_factory, static_file_producer);
 
     Ok(pipeline)
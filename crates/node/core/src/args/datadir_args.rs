@@ -27,6 +27,13 @@ pub struct DatadirArgs {
         verbatim_doc_comment
     )]
     pub static_files_path: Option<PathBuf>,
+
+    /// Maximum number of static files to keep open at the same time.
+    ///
+    /// Least recently used static files are closed once the limit is reached and reopened on
+    /// their next access. Unlimited if unset or 0.
+    #[arg(long = "static-files.max-open-files", value_name = "COUNT")]
+    pub static_files_max_open_files: Option<usize>,
 }
 
 impl DatadirArgs {
@@ -35,6 +42,11 @@ impl DatadirArgs {
         let datadir = self.datadir.clone();
         datadir.unwrap_or_chain_default(chain, self)
     }
+
+    /// Returns the maximum number of open static files, if limited.
+    pub fn max_open_static_files(&self) -> Option<usize> {
+        self.static_files_max_open_files.filter(|max| *max > 0)
+    }
 }
 
 #[cfg(test)]
@@ -55,4 +67,23 @@ mod tests {
         let args = CommandParser::<DatadirArgs>::parse_from(["reth"]).args;
         assert_eq!(args, default_args);
     }
+
+    #[test]
+    fn test_parse_static_files_max_open_files() {
+        let args = CommandParser::<DatadirArgs>::parse_from([
+            "reth",
+            "--static-files.max-open-files",
+            "128",
+        ])
+        .args;
+        assert_eq!(args.max_open_static_files(), Some(128));
+
+        let args = CommandParser::<DatadirArgs>::parse_from([
+            "reth",
+            "--static-files.max-open-files",
+            "0",
+        ])
+        .args;
+        assert_eq!(args.max_open_static_files(), None);
+    }
 }
@@ -68,6 +68,14 @@ pub struct DebugArgs {
     #[arg(long = "debug.engine-api-store", help_heading = "Debug", value_name = "PATH")]
     pub engine_api_store: Option<PathBuf>,
 
+    /// The directory to record the engine API messages and the outcomes of processing them in,
+    /// for reproducing issues with `reth debug replay`.
+    ///
+    /// The messages are appended to a log of segments, which are rotated and compressed once they
+    /// exceed 64 MB.
+    #[arg(long = "debug.record", help_heading = "Debug", value_name = "DIR")]
+    pub record: Option<PathBuf>,
+
     /// Determines which type of invalid block hook to install
     ///
     /// Example: `witness,prestate`
@@ -87,6 +95,11 @@ pub struct DebugArgs {
         verbatim_doc_comment
     )]
     pub healthy_node_rpc_url: Option<String>,
+
+    /// Resume from the forkchoice state persisted before the last shutdown by sending it to the
+    /// engine on startup, without waiting for the consensus layer.
+    #[arg(long = "debug.resume-forkchoice", help_heading = "Debug")]
+    pub resume_forkchoice: bool,
 }
 
 impl Default for DebugArgs {
@@ -102,8 +115,10 @@ impl Default for DebugArgs {
             reorg_frequency: None,
             reorg_depth: None,
             engine_api_store: None,
+            record: None,
             invalid_block_hook: Some(InvalidBlockSelection::default()),
             healthy_node_rpc_url: None,
+            resume_forkchoice: false,
         }
     }
 }
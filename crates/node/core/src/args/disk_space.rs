@@ -0,0 +1,73 @@
+//! clap [Args](clap::Args) for monitoring the available disk space
+
+use crate::args::database::parse_byte_size;
+use clap::Args;
+
+/// Default available disk space below which the node warns about it, 50GB.
+pub const DEFAULT_MIN_FREE_SPACE_WARNING: u64 = 50 * 1024 * 1024 * 1024;
+
+/// Parameters for monitoring the available disk space of the data dir and the static files
+#[derive(Debug, Args, PartialEq, Eq, Default, Clone, Copy)]
+#[command(next_help_heading = "Disk space")]
+pub struct DiskSpaceArgs {
+    /// Warn when the available disk space of the data dir or the static files falls below this
+    /// size.
+    ///
+    /// Defaults to 50GB. Disabled if 0.
+    #[arg(long = "datadir.min-free-space-warning", value_name = "SIZE", value_parser = parse_byte_size)]
+    pub min_free_space_warning: Option<usize>,
+
+    /// Pause syncing, pruning and writing static files when the available disk space of the data
+    /// dir or the static files falls below this size, until space is freed up. The engine API and
+    /// RPC keep running.
+    ///
+    /// Disabled if unset or 0.
+    #[arg(long = "datadir.min-free-space", value_name = "SIZE", value_parser = parse_byte_size)]
+    pub min_free_space: Option<usize>,
+}
+
+impl DiskSpaceArgs {
+    /// Returns the available disk space in bytes below which the node warns about it, `0` if
+    /// disabled.
+    pub fn min_free_space_warning(&self) -> u64 {
+        self.min_free_space_warning.map_or(DEFAULT_MIN_FREE_SPACE_WARNING, |size| size as u64)
+    }
+
+    /// Returns the available disk space in bytes below which the node pauses writes, `0` if
+    /// disabled.
+    pub fn min_free_space(&self) -> u64 {
+        self.min_free_space.unwrap_or_default() as u64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    /// A helper type to parse Args more easily
+    #[derive(Parser)]
+    struct CommandParser<T: Args> {
+        #[command(flatten)]
+        args: T,
+    }
+
+    #[test]
+    fn test_parse_disk_space_args() {
+        let args = CommandParser::<DiskSpaceArgs>::parse_from(["reth"]).args;
+        assert_eq!(args, DiskSpaceArgs::default());
+        assert_eq!(args.min_free_space_warning(), DEFAULT_MIN_FREE_SPACE_WARNING);
+        assert_eq!(args.min_free_space(), 0);
+
+        let args = CommandParser::<DiskSpaceArgs>::parse_from([
+            "reth",
+            "--datadir.min-free-space-warning",
+            "0",
+            "--datadir.min-free-space",
+            "10GB",
+        ])
+        .args;
+        assert_eq!(args.min_free_space_warning(), 0);
+        assert_eq!(args.min_free_space(), 10 * 1024 * 1024 * 1024);
+    }
+}
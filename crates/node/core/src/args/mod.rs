@@ -48,6 +48,18 @@ pub use dev::DevArgs;
 mod pruning;
 pub use pruning::PruningArgs;
 
+/// ScrubberArgs for configuring the background static files scrubber
+mod scrubber;
+pub use scrubber::ScrubberArgs;
+
+/// StaticFilesArgs for configuring the static files of the node
+mod static_files;
+pub use static_files::StaticFilesArgs;
+
+/// DiskSpaceArgs for monitoring the available disk space
+mod disk_space;
+pub use disk_space::DiskSpaceArgs;
+
 /// DatadirArgs for configuring data storage paths
 mod datadir_args;
 pub use datadir_args::DatadirArgs;
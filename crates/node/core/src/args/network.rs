@@ -226,7 +226,7 @@ impl NetworkArgs {
                 self.max_capacity_cache_txns_pending_fetch,
             ),
             max_transactions_seen_by_peer_history: self.max_seen_tx_history,
-            propagation_mode: Default::default(),
+            ..Default::default()
         };
 
         // Configure basic network stack
@@ -255,6 +255,12 @@ impl NetworkArgs {
                 let rlpx_socket = (addr, self.port).into();
                 self.discovery.apply_to_builder(builder, rlpx_socket, chain_bootnodes)
             })
+            // advertise the custom node record entries
+            .apply(|builder| {
+                config.enr_entries().fold(builder, |builder, (key, value)| {
+                    builder.add_enr_entry(key.as_bytes(), value.clone())
+                })
+            })
             .listener_addr(SocketAddr::new(
                 addr, // set discovery port based on instance number
                 self.port,
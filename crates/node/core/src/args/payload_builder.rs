@@ -1,6 +1,7 @@
 use crate::{cli::config::PayloadBuilderConfig, version::default_extra_data};
 use alloy_consensus::constants::MAXIMUM_EXTRA_DATA_SIZE;
 use alloy_eips::{eip1559::ETHEREUM_BLOCK_GAS_LIMIT, merge::SLOT_DURATION};
+use alloy_primitives::Address;
 use clap::{
     builder::{RangedU64ValueParser, TypedValueParser},
     Arg, Args, Command,
@@ -32,9 +33,26 @@ pub struct PayloadBuilderArgs {
     #[arg(long = "builder.deadline", value_parser = parse_duration_from_secs, default_value = "12", value_name = "SECONDS")]
     pub deadline: Duration,
 
+    /// How long `engine_getPayload` may wait for the build iteration in progress to complete.
+    ///
+    /// The handler only waits if the iteration is expected to complete within this window, based
+    /// on the duration of the previous iteration. Capped at 500ms, `0` disables waiting.
+    #[arg(long = "builder.getpayload-wait", value_parser = parse_duration_from_secs_or_ms, default_value = "200ms", value_name = "DURATION")]
+    pub getpayload_wait: Duration,
+
     /// Maximum number of tasks to spawn for building a payload.
     #[arg(long = "builder.max-tasks", default_value = "3", value_parser = RangedU64ValueParser::<usize>::new().range(1..))]
     pub max_payload_tasks: usize,
+
+    /// The fee recipient of payloads built by the node itself, e.g. in dev mode.
+    ///
+    /// Can be changed at runtime with `miner_setEtherbase`. Defaults to the first dev account.
+    #[arg(long = "builder.fee-recipient", value_name = "ADDRESS")]
+    pub fee_recipient: Option<Address>,
+
+    /// Persist changes of the fee recipient made at runtime to the config file.
+    #[arg(long = "builder.persist-fee-recipient")]
+    pub persist_fee_recipient: bool,
 }
 
 impl Default for PayloadBuilderArgs {
@@ -44,7 +62,10 @@ impl Default for PayloadBuilderArgs {
             gas_limit: ETHEREUM_BLOCK_GAS_LIMIT,
             interval: Duration::from_secs(1),
             deadline: SLOT_DURATION,
+            getpayload_wait: Duration::from_millis(200),
             max_payload_tasks: 3,
+            fee_recipient: None,
+            persist_fee_recipient: false,
         }
     }
 }
@@ -62,6 +83,10 @@ impl PayloadBuilderConfig for PayloadBuilderArgs {
         self.deadline
     }
 
+    fn getpayload_wait(&self) -> Duration {
+        self.getpayload_wait
+    }
+
     fn gas_limit(&self) -> u64 {
         self.gas_limit
     }
@@ -151,6 +176,22 @@ mod tests {
         assert!(args.is_err());
     }
 
+    #[test]
+    fn test_args_with_fee_recipient() {
+        let args = CommandParser::<PayloadBuilderArgs>::parse_from([
+            "reth",
+            "--builder.fee-recipient",
+            "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266",
+            "--builder.persist-fee-recipient",
+        ])
+        .args;
+        assert_eq!(
+            args.fee_recipient,
+            Some(alloy_primitives::address!("f39Fd6e51aad88F6F4ce6aB8827279cffFb92266"))
+        );
+        assert!(args.persist_fee_recipient);
+    }
+
     #[test]
     fn payload_builder_args_default_sanity_check() {
         let default_args = PayloadBuilderArgs::default();
@@ -166,6 +207,17 @@ mod tests {
         assert_eq!(args.interval, Duration::from_secs(50));
     }
 
+    #[test]
+    fn test_args_with_getpayload_wait() {
+        let args = CommandParser::<PayloadBuilderArgs>::parse_from([
+            "reth",
+            "--builder.getpayload-wait",
+            "50ms",
+        ])
+        .args;
+        assert_eq!(args.getpayload_wait, Duration::from_millis(50));
+    }
+
     #[test]
     fn test_args_with_ms_interval() {
         let args =
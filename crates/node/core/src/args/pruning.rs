@@ -1,12 +1,19 @@
 //! Pruning and full node arguments
 
-use crate::args::error::ReceiptsLogError;
+use crate::args::{error::ReceiptsLogError, types::PruneModeArg};
 use alloy_primitives::{Address, BlockNumber};
-use clap::{builder::RangedU64ValueParser, Args};
+use clap::{
+    builder::{RangedU64ValueParser, TypedValueParser},
+    error::ErrorKind,
+    Arg, Args, Command, Error,
+};
 use reth_chainspec::EthChainSpec;
 use reth_config::config::PruneConfig;
-use reth_prune_types::{PruneMode, PruneModes, ReceiptsLogPruneConfig, MINIMUM_PRUNING_DISTANCE};
-use std::collections::BTreeMap;
+use reth_prune_types::{
+    PruneMode, PruneModes, PrunePurpose, PruneSegment, ReceiptsLogPruneConfig,
+    MINIMUM_PRUNING_DISTANCE,
+};
+use std::{collections::BTreeMap, ffi::OsStr};
 
 /// Parameters for pruning and full node
 #[derive(Debug, Clone, Args, PartialEq, Eq, Default)]
@@ -21,6 +28,10 @@ pub struct PruningArgs {
     pub block_interval: Option<u64>,
 
     // Sender Recovery
+    /// Prune mode of sender recovery data: 'full', 'distance:<`blocks`>' or
+    /// 'before:<`block_number`>'.
+    #[arg(long = "prune.senderrecovery", value_name = "MODE", value_parser = PruneModeValueParser::new(PruneSegment::SenderRecovery), conflicts_with_all = &["sender_recovery_full", "sender_recovery_distance", "sender_recovery_before"])]
+    pub sender_recovery: Option<PruneMode>,
     /// Prunes all sender recovery data.
     #[arg(long = "prune.senderrecovery.full", conflicts_with_all = &["sender_recovery_distance", "sender_recovery_before"])]
     pub sender_recovery_full: bool,
@@ -34,6 +45,10 @@ pub struct PruningArgs {
     pub sender_recovery_before: Option<BlockNumber>,
 
     // Transaction Lookup
+    /// Prune mode of transaction lookup data: 'full', 'distance:<`blocks`>' or
+    /// 'before:<`block_number`>'.
+    #[arg(long = "prune.transactionlookup", value_name = "MODE", value_parser = PruneModeValueParser::new(PruneSegment::TransactionLookup), conflicts_with_all = &["transaction_lookup_full", "transaction_lookup_distance", "transaction_lookup_before"])]
+    pub transaction_lookup: Option<PruneMode>,
     /// Prunes all transaction lookup data.
     #[arg(long = "prune.transactionlookup.full", conflicts_with_all = &["transaction_lookup_distance", "transaction_lookup_before"])]
     pub transaction_lookup_full: bool,
@@ -47,6 +62,9 @@ pub struct PruningArgs {
     pub transaction_lookup_before: Option<BlockNumber>,
 
     // Receipts
+    /// Prune mode of receipts: 'full', 'distance:<`blocks`>' or 'before:<`block_number`>'.
+    #[arg(long = "prune.receipts", value_name = "MODE", value_parser = PruneModeValueParser::new(PruneSegment::Receipts), conflicts_with_all = &["receipts_full", "receipts_distance", "receipts_before"])]
+    pub receipts: Option<PruneMode>,
     /// Prunes all receipt data.
     #[arg(long = "prune.receipts.full", conflicts_with_all = &["receipts_distance", "receipts_before"])]
     pub receipts_full: bool,
@@ -58,6 +76,9 @@ pub struct PruningArgs {
     pub receipts_before: Option<BlockNumber>,
 
     // Account History
+    /// Prune mode of account history: 'full', 'distance:<`blocks`>' or 'before:<`block_number`>'.
+    #[arg(long = "prune.accounthistory", value_name = "MODE", value_parser = PruneModeValueParser::new(PruneSegment::AccountHistory), conflicts_with_all = &["account_history_full", "account_history_distance", "account_history_before"])]
+    pub account_history: Option<PruneMode>,
     /// Prunes all account history.
     #[arg(long = "prune.accounthistory.full", conflicts_with_all = &["account_history_distance", "account_history_before"])]
     pub account_history_full: bool,
@@ -70,6 +91,9 @@ pub struct PruningArgs {
     pub account_history_before: Option<BlockNumber>,
 
     // Storage History
+    /// Prune mode of storage history: 'full', 'distance:<`blocks`>' or 'before:<`block_number`>'.
+    #[arg(long = "prune.storagehistory", value_name = "MODE", value_parser = PruneModeValueParser::new(PruneSegment::StorageHistory), conflicts_with_all = &["storage_history_full", "storage_history_distance", "storage_history_before"])]
+    pub storage_history: Option<PruneMode>,
     /// Prunes all storage history data.
     #[arg(long = "prune.storagehistory.full", conflicts_with_all = &["storage_history_distance", "storage_history_before"])]
     pub storage_history_full: bool,
@@ -92,6 +116,10 @@ pub struct PruningArgs {
 
 impl PruningArgs {
     /// Returns pruning configuration.
+    ///
+    /// The prune mode of a segment is taken from its `--prune.<segment>` flags if set, and
+    /// otherwise from the `--full` node defaults. Segments without a prune mode are filled in
+    /// from the `[prune]` section of the config file with [`PruneConfig::merge`].
     pub fn prune_config(&self, chain_spec: &impl EthChainSpec) -> Option<PruneConfig> {
         // Initialise with a default prune configuration.
         let mut config = PruneConfig::default();
@@ -118,6 +146,7 @@ impl PruningArgs {
                             .into_iter()
                             .collect(),
                     ),
+                    custom: BTreeMap::new(),
                 },
             }
         }
@@ -144,8 +173,41 @@ impl PruningArgs {
 
         Some(config)
     }
+
+    /// Returns the prune mode set by the `--prune.<segment>` flags of each segment, along with the
+    /// common prefix of the flags.
+    pub(crate) const fn segment_prune_modes(
+        &self,
+    ) -> [(PruneSegment, &'static str, Option<PruneMode>); 5] {
+        [
+            (
+                PruneSegment::SenderRecovery,
+                "prune.senderrecovery",
+                self.sender_recovery_prune_mode(),
+            ),
+            (
+                PruneSegment::TransactionLookup,
+                "prune.transactionlookup",
+                self.transaction_lookup_prune_mode(),
+            ),
+            (PruneSegment::Receipts, "prune.receipts", self.receipts_prune_mode()),
+            (
+                PruneSegment::AccountHistory,
+                "prune.accounthistory",
+                self.account_history_prune_mode(),
+            ),
+            (
+                PruneSegment::StorageHistory,
+                "prune.storagehistory",
+                self.storage_history_prune_mode(),
+            ),
+        ]
+    }
+
     const fn sender_recovery_prune_mode(&self) -> Option<PruneMode> {
-        if self.sender_recovery_full {
+        if let Some(mode) = self.sender_recovery {
+            Some(mode)
+        } else if self.sender_recovery_full {
             Some(PruneMode::Full)
         } else if let Some(distance) = self.sender_recovery_distance {
             Some(PruneMode::Distance(distance))
@@ -157,7 +219,9 @@ impl PruningArgs {
     }
 
     const fn transaction_lookup_prune_mode(&self) -> Option<PruneMode> {
-        if self.transaction_lookup_full {
+        if let Some(mode) = self.transaction_lookup {
+            Some(mode)
+        } else if self.transaction_lookup_full {
             Some(PruneMode::Full)
         } else if let Some(distance) = self.transaction_lookup_distance {
             Some(PruneMode::Distance(distance))
@@ -169,7 +233,9 @@ impl PruningArgs {
     }
 
     const fn receipts_prune_mode(&self) -> Option<PruneMode> {
-        if self.receipts_full {
+        if let Some(mode) = self.receipts {
+            Some(mode)
+        } else if self.receipts_full {
             Some(PruneMode::Full)
         } else if let Some(distance) = self.receipts_distance {
             Some(PruneMode::Distance(distance))
@@ -181,7 +247,9 @@ impl PruningArgs {
     }
 
     const fn account_history_prune_mode(&self) -> Option<PruneMode> {
-        if self.account_history_full {
+        if let Some(mode) = self.account_history {
+            Some(mode)
+        } else if self.account_history_full {
             Some(PruneMode::Full)
         } else if let Some(distance) = self.account_history_distance {
             Some(PruneMode::Distance(distance))
@@ -193,7 +261,9 @@ impl PruningArgs {
     }
 
     const fn storage_history_prune_mode(&self) -> Option<PruneMode> {
-        if self.storage_history_full {
+        if let Some(mode) = self.storage_history {
+            Some(mode)
+        } else if self.storage_history_full {
             Some(PruneMode::Full)
         } else if let Some(distance) = self.storage_history_distance {
             Some(PruneMode::Distance(distance))
@@ -205,6 +275,60 @@ impl PruningArgs {
     }
 }
 
+/// Parses a [`PruneModeArg`] and checks that the prune mode is allowed for the segment.
+#[derive(Clone, Debug)]
+struct PruneModeValueParser {
+    segment: PruneSegment,
+}
+
+impl PruneModeValueParser {
+    const fn new(segment: PruneSegment) -> Self {
+        Self { segment }
+    }
+
+    /// Checks that the prune mode prunes something and keeps the minimum number of blocks of the
+    /// segment.
+    fn validate(&self, mode: PruneMode) -> Result<(), String> {
+        let segment = self.segment;
+        let min_blocks = segment.min_blocks(PrunePurpose::User);
+        match mode {
+            PruneMode::Before(0) => Err(format!(
+                "{segment} data before block 0 doesn't exist, so nothing would be pruned"
+            )),
+            PruneMode::Full if min_blocks > 0 => Err(format!(
+                "{segment} can't be fully pruned, at least {min_blocks} blocks must be kept"
+            )),
+            PruneMode::Distance(distance) if distance < min_blocks => Err(format!(
+                "{segment} must keep at least {min_blocks} blocks, but distance is {distance}"
+            )),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl TypedValueParser for PruneModeValueParser {
+    type Value = PruneMode;
+
+    fn parse_ref(
+        &self,
+        _cmd: &Command,
+        arg: Option<&Arg>,
+        value: &OsStr,
+    ) -> Result<Self::Value, Error> {
+        let val =
+            value.to_str().ok_or_else(|| Error::raw(ErrorKind::InvalidUtf8, "Invalid UTF-8"))?;
+
+        let invalid_value = |err: String| {
+            let arg = arg.map(|a| a.to_string()).unwrap_or_else(|| "...".to_owned());
+            Error::raw(ErrorKind::InvalidValue, format!("Invalid value '{val}' for {arg}: {err}"))
+        };
+
+        let mode = val.parse::<PruneModeArg>().map_err(|err| invalid_value(err.to_string()))?.get();
+        self.validate(mode).map_err(invalid_value)?;
+        Ok(mode)
+    }
+}
+
 pub(crate) fn parse_receipts_log_filter(
     value: &str,
 ) -> Result<ReceiptsLogPruneConfig, ReceiptsLogError> {
@@ -267,6 +391,129 @@ mod tests {
         assert_eq!(args, default_args);
     }
 
+    #[test]
+    fn parse_segment_prune_modes() {
+        let args = CommandParser::<PruningArgs>::parse_from([
+            "reth",
+            "--prune.senderrecovery",
+            "full",
+            "--prune.transactionlookup",
+            "before:17m",
+            "--prune.receipts",
+            "distance:10_064",
+            "--prune.accounthistory",
+            "distance:100000",
+            "--prune.storagehistory",
+            "before:17000000",
+        ])
+        .args;
+
+        assert_eq!(args.sender_recovery, Some(PruneMode::Full));
+        assert_eq!(args.transaction_lookup, Some(PruneMode::Before(17_000_000)));
+        assert_eq!(args.receipts, Some(PruneMode::Distance(10_064)));
+        assert_eq!(args.account_history, Some(PruneMode::Distance(100_000)));
+        assert_eq!(args.storage_history, Some(PruneMode::Before(17_000_000)));
+
+        // Every mode is parsed back from its display form.
+        for mode in [PruneMode::Full, PruneMode::Distance(10_064), PruneMode::Before(17_000_000)] {
+            let arg = PruneModeArg(mode).to_string();
+            let args =
+                CommandParser::<PruningArgs>::parse_from(["reth", "--prune.senderrecovery", &arg])
+                    .args;
+            assert_eq!(args.sender_recovery, Some(mode));
+        }
+    }
+
+    #[test]
+    fn reject_invalid_segment_prune_modes() {
+        for (flag, value, message) in [
+            ("--prune.receipts", "before:0", "Receipts data before block 0"),
+            ("--prune.senderrecovery", "before:0", "SenderRecovery data before block 0"),
+            ("--prune.receipts", "distance:100", "Receipts must keep at least 10064 blocks"),
+            ("--prune.accounthistory", "full", "AccountHistory can't be fully pruned"),
+            ("--prune.storagehistory", "distance:10063", "StorageHistory must keep at least"),
+            ("--prune.transactionlookup", "distance:-1", "must not be negative"),
+            ("--prune.receipts", "latest", "invalid prune mode 'latest'"),
+        ] {
+            let err = CommandParser::<PruningArgs>::try_parse_from(["reth", flag, value])
+                .unwrap_err()
+                .to_string();
+            assert!(err.contains(message), "{flag} {value}: {err}");
+        }
+
+        // Segments without a minimum can be pruned at any distance.
+        let args = CommandParser::<PruningArgs>::parse_from([
+            "reth",
+            "--prune.transactionlookup",
+            "distance:0",
+        ])
+        .args;
+        assert_eq!(args.transaction_lookup, Some(PruneMode::Distance(0)));
+    }
+
+    #[test]
+    fn segment_prune_mode_conflicts_with_segment_flags() {
+        assert!(CommandParser::<PruningArgs>::try_parse_from([
+            "reth",
+            "--prune.receipts",
+            "full",
+            "--prune.receipts.distance",
+            "10064",
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn segment_prune_mode_precedence() {
+        let toml = PruneConfig {
+            block_interval: 10,
+            segments: PruneModes {
+                sender_recovery: Some(PruneMode::Distance(100)),
+                transaction_lookup: Some(PruneMode::Before(100)),
+                receipts: Some(PruneMode::Before(100)),
+                account_history: Some(PruneMode::Distance(20_000)),
+                storage_history: Some(PruneMode::Distance(20_000)),
+                receipts_log_filter: Default::default(),
+                custom: Default::default(),
+            },
+        };
+
+        // CLI flags override the `--full` defaults, which override the config file.
+        let args = CommandParser::<PruningArgs>::parse_from([
+            "reth",
+            "--full",
+            "--prune.receipts",
+            "distance:50000",
+            "--prune.transactionlookup",
+            "full",
+        ])
+        .args;
+        let mut config = args.prune_config(&reth_chainspec::ChainSpec::default()).unwrap();
+        config.merge(Some(toml.clone()));
+
+        assert_eq!(config.segments.receipts, Some(PruneMode::Distance(50_000)));
+        assert_eq!(config.segments.transaction_lookup, Some(PruneMode::Full));
+        assert_eq!(config.segments.sender_recovery, Some(PruneMode::Full));
+        assert_eq!(
+            config.segments.account_history,
+            Some(PruneMode::Distance(MINIMUM_PRUNING_DISTANCE))
+        );
+
+        // Without `--full`, segments without a flag are taken from the config file.
+        let args = CommandParser::<PruningArgs>::parse_from([
+            "reth",
+            "--prune.receipts",
+            "distance:50000",
+        ])
+        .args;
+        let mut config = args.prune_config(&reth_chainspec::ChainSpec::default()).unwrap();
+        config.merge(Some(toml));
+
+        assert_eq!(config.segments.receipts, Some(PruneMode::Distance(50_000)));
+        assert_eq!(config.segments.sender_recovery, Some(PruneMode::Distance(100)));
+        assert_eq!(config.segments.account_history, Some(PruneMode::Distance(20_000)));
+    }
+
     #[test]
     fn test_parse_receipts_log_filter() {
         let filter1 = "0x0000000000000000000000000000000000000001:full";
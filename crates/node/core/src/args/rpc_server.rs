@@ -5,6 +5,7 @@ use std::{
     ffi::OsStr,
     net::{IpAddr, Ipv4Addr},
     path::PathBuf,
+    time::Duration,
 };
 
 use alloy_primitives::Address;
@@ -14,6 +15,8 @@ use clap::{
     Arg, Args, Command,
 };
 use rand::Rng;
+use reth_cli_util::parse_duration_from_secs_or_ms;
+use reth_rpc_eth_types::HistoryCutoff;
 use reth_rpc_server_types::{constants, RethRpcModule, RpcModuleSelection};
 
 use crate::args::{
@@ -56,9 +59,21 @@ pub struct RpcServerArgs {
     pub http_api: Option<RpcModuleSelection>,
 
     /// Http Corsdomain to allow request from
+    ///
+    /// Comma separated list of origins, or `*` for any origin. The leftmost label of the domain
+    /// of an origin can be a wildcard, e.g. `https://*.example.com`.
     #[arg(long = "http.corsdomain")]
     pub http_corsdomain: Option<String>,
 
+    /// Hosts to accept HTTP requests and WebSocket handshakes for
+    ///
+    /// Comma separated list of domains, or `*` for any domain, that's compared with the `Host`
+    /// header to protect against DNS rebinding. The leftmost label can be a wildcard, e.g.
+    /// `*.example.com`. Requests to an IP address are always accepted. By default, requests for
+    /// any host are accepted.
+    #[arg(long = "http.vhosts")]
+    pub http_vhosts: Option<String>,
+
     /// Enable the WS-RPC server
     #[arg(long)]
     pub ws: bool,
@@ -72,6 +87,10 @@ pub struct RpcServerArgs {
     pub ws_port: u16,
 
     /// Origins from which to accept `WebSocket` requests
+    ///
+    /// Comma separated list of origins, or `*` for any origin. The leftmost label of the domain
+    /// of an origin can be a wildcard, e.g. `https://*.example.com`. Handshakes from other origins
+    /// are rejected.
     #[arg(id = "ws.origins", long = "ws.origins")]
     pub ws_allowed_origins: Option<String>,
 
@@ -79,6 +98,25 @@ pub struct RpcServerArgs {
     #[arg(long = "ws.api", value_parser = RpcModuleSelectionValueParser::default())]
     pub ws_api: Option<RpcModuleSelection>,
 
+    /// Interval at which pings are sent to WS clients, e.g. `30s` or `500ms`.
+    ///
+    /// Clients that leave `--ws.ping-max-missed` consecutive pings unanswered are disconnected,
+    /// which ends their subscriptions. By default, no pings are sent.
+    #[arg(long = "ws.ping-interval", value_name = "DURATION", value_parser = parse_duration_from_secs_or_ms)]
+    pub ws_ping_interval: Option<Duration>,
+
+    /// Number of consecutive pings a WS client may leave unanswered before it's disconnected.
+    #[arg(long = "ws.ping-max-missed", value_name = "COUNT", value_parser = RangedU64ValueParser::<usize>::new().range(1..), default_value_t = constants::DEFAULT_WS_MAX_MISSED_PONGS)]
+    pub ws_ping_max_missed: usize,
+
+    /// Maximum duration of a WS connection, e.g. `3600s`.
+    ///
+    /// Connections are closed once they reach this duration, so that long-lived clients reconnect
+    /// and are spread over the available nodes. By default, connections are kept open
+    /// indefinitely.
+    #[arg(long = "ws.max-connection-duration", value_name = "DURATION", value_parser = parse_duration_from_secs_or_ms)]
+    pub ws_max_connection_duration: Option<Duration>,
+
     /// Disable the IPC-RPC server
     #[arg(long)]
     pub ipcdisable: bool,
@@ -87,6 +125,16 @@ pub struct RpcServerArgs {
     #[arg(long, default_value_t = constants::DEFAULT_IPC_ENDPOINT.to_string())]
     pub ipcpath: String,
 
+    /// Rpc Modules to be configured for the IPC server
+    #[arg(long = "ipc.api", value_parser = RpcModuleSelectionValueParser::default())]
+    pub ipc_api: Option<RpcModuleSelection>,
+
+    /// Octal permission bits of the IPC socket file, e.g. 660
+    ///
+    /// Has no effect on Windows.
+    #[arg(long = "ipc.chmod", value_name = "MODE", value_parser = parse_file_mode)]
+    pub ipc_chmod: Option<u32>,
+
     /// Auth server address to listen on
     #[arg(long = "authrpc.addr", default_value_t = IpAddr::V4(Ipv4Addr::LOCALHOST))]
     pub auth_addr: IpAddr,
@@ -112,6 +160,20 @@ pub struct RpcServerArgs {
     #[arg(long = "auth-ipc.path", default_value_t = constants::DEFAULT_ENGINE_API_IPC_ENDPOINT.to_string())]
     pub auth_ipc_path: String,
 
+    /// Disable the auth server, including the auth IPC endpoint.
+    ///
+    /// Without the engine API, the node can only be driven by a consensus client that runs in the
+    /// same process and sends its messages through the consensus engine handle of the node.
+    #[arg(long = "authrpc.disable")]
+    pub auth_disable: bool,
+
+    /// Serve `debug_dbGet` and `debug_staticFileGet` on the auth server, which return raw entries
+    /// of the database and the static files.
+    ///
+    /// These methods are only served by the auth server, never by the regular RPC servers.
+    #[arg(long = "rpc.enable-db-debug")]
+    pub rpc_enable_db_debug: bool,
+
     /// Hex encoded JWT secret to authenticate the regular RPC server(s), see `--http.api` and
     /// `--ws.api`.
     ///
@@ -145,6 +207,14 @@ pub struct RpcServerArgs {
     #[arg(long = "rpc.max-tracing-requests", alias = "rpc-max-tracing-requests", value_name = "COUNT", default_value_t = constants::default_max_tracing_requests())]
     pub rpc_max_tracing_requests: usize,
 
+    /// Number of threads of the pool that runs tracing, debug and call requests. (0 = one per CPU
+    /// core)
+    ///
+    /// The pool is separate from the threads that keep the node in sync. Requests are rejected
+    /// with a rate limit error if too many of them are waiting for a thread.
+    #[arg(long = "rpc.compute-threads", value_name = "COUNT", default_value_t = 0)]
+    pub rpc_compute_threads: usize,
+
     /// Maximum number of blocks that could be scanned per filter request. (0 = entire chain)
     #[arg(long = "rpc.max-blocks-per-filter", alias = "rpc-max-blocks-per-filter", value_name = "COUNT", default_value_t = ZeroAsNoneU64::new(constants::DEFAULT_MAX_BLOCKS_PER_FILTER))]
     pub rpc_max_blocks_per_filter: ZeroAsNoneU64,
@@ -185,11 +255,54 @@ pub struct RpcServerArgs {
     #[arg(long = "rpc.proof-permits", alias = "rpc-proof-permits", value_name = "COUNT", default_value_t = constants::DEFAULT_PROOF_PERMITS)]
     pub rpc_proof_permits: usize,
 
+    /// Oldest block for which historical state, traces and logs are served.
+    ///
+    /// Either a block number, or `distance:N` to serve the last N blocks. Defaults to `auto`, the
+    /// oldest block with complete state and receipts according to the prune checkpoints.
+    #[arg(long = "rpc.history-cutoff", value_name = "BLOCK|distance:N", default_value_t = HistoryCutoff::Auto)]
+    pub rpc_history_cutoff: HistoryCutoff,
+
+    /// Maximum total size in bytes of the call data (`input`/`data`) of the transaction requests
+    /// of a request, e.g. of all calls of an `eth_simulateV1` request.
+    ///
+    /// Requests exceeding this limit are rejected before their parameters are decoded.
+    #[arg(long = "rpc.max-calldata-size", value_name = "BYTES", default_value_t = constants::DEFAULT_MAX_CALL_DATA_SIZE)]
+    pub rpc_max_call_data_size: usize,
+
+    /// Maximum total number of access list entries of the transaction requests of a request.
+    #[arg(long = "rpc.max-access-list-entries", value_name = "COUNT", default_value_t = constants::DEFAULT_MAX_ACCESS_LIST_ENTRIES)]
+    pub rpc_max_access_list_entries: usize,
+
+    /// Maximum total number of accounts in the state overrides of a request.
+    #[arg(long = "rpc.max-state-override-accounts", value_name = "COUNT", default_value_t = constants::DEFAULT_MAX_STATE_OVERRIDE_ACCOUNTS)]
+    pub rpc_max_state_override_accounts: usize,
+
+    /// Reject transactions submitted via RPC while the node is syncing and more than
+    /// `--rpc.max-sync-distance` blocks behind the head of the latest forkchoice update, or
+    /// hasn't received that head yet.
+    #[arg(long = "rpc.reject-tx-while-syncing")]
+    pub rpc_reject_tx_while_syncing: bool,
+
+    /// Maximum number of blocks the node may be behind the head of the latest forkchoice update
+    /// for transactions submitted via RPC to be accepted, if `--rpc.reject-tx-while-syncing` is
+    /// set.
+    #[arg(long = "rpc.max-sync-distance", value_name = "BLOCKS", default_value_t = constants::DEFAULT_MAX_TX_SYNC_DISTANCE)]
+    pub rpc_max_sync_distance: u64,
+
     /// Path to file containing disallowed addresses, json-encoded list of strings. Block
     /// validation API will reject blocks containing transactions from these addresses.
     #[arg(long = "builder.disallow", value_name = "PATH", value_parser = reth_cli_util::parsers::read_json_from_file::<HashSet<Address>>)]
     pub builder_disallow: Option<HashSet<Address>>,
 
+    /// Add the transactions of bundles sent with `eth_sendBundle` of the flashbots namespace to
+    /// the local transaction pool, instead of rejecting the bundles.
+    #[arg(long = "flashbots.accept-bundles-locally")]
+    pub flashbots_accept_bundles_locally: bool,
+
+    /// Maximum number of transactions in a bundle of `eth_callBundle` and `eth_sendBundle`.
+    #[arg(long = "flashbots.max-bundle-txs", value_name = "COUNT", default_value_t = constants::DEFAULT_MAX_BUNDLE_TXS)]
+    pub flashbots_max_bundle_txs: usize,
+
     /// State cache configuration.
     #[command(flatten)]
     pub rpc_state_cache: RpcStateCacheArgs,
@@ -224,6 +337,12 @@ impl RpcServerArgs {
         self
     }
 
+    /// Disables the auth server, see [`RpcServerArgs::auth_disable`].
+    pub const fn with_auth_disabled(mut self) -> Self {
+        self.auth_disable = true;
+        self
+    }
+
     /// Change rpc port numbers based on the instance number.
     /// * The `auth_port` is scaled by a factor of `instance * 100`
     /// * The `http_port` is scaled by a factor of `-instance`
@@ -305,24 +424,33 @@ impl Default for RpcServerArgs {
             http_port: constants::DEFAULT_HTTP_RPC_PORT,
             http_api: None,
             http_corsdomain: None,
+            http_vhosts: None,
             ws: false,
             ws_addr: Ipv4Addr::LOCALHOST.into(),
             ws_port: constants::DEFAULT_WS_RPC_PORT,
             ws_allowed_origins: None,
             ws_api: None,
+            ws_ping_interval: None,
+            ws_ping_max_missed: constants::DEFAULT_WS_MAX_MISSED_PONGS,
+            ws_max_connection_duration: None,
             ipcdisable: false,
             ipcpath: constants::DEFAULT_IPC_ENDPOINT.to_string(),
+            ipc_api: None,
+            ipc_chmod: None,
             auth_addr: Ipv4Addr::LOCALHOST.into(),
             auth_port: constants::DEFAULT_AUTH_PORT,
             auth_jwtsecret: None,
             auth_ipc: false,
             auth_ipc_path: constants::DEFAULT_ENGINE_API_IPC_ENDPOINT.to_string(),
+            auth_disable: false,
+            rpc_enable_db_debug: false,
             rpc_jwtsecret: None,
             rpc_max_request_size: RPC_DEFAULT_MAX_REQUEST_SIZE_MB.into(),
             rpc_max_response_size: RPC_DEFAULT_MAX_RESPONSE_SIZE_MB.into(),
             rpc_max_subscriptions_per_connection: RPC_DEFAULT_MAX_SUBS_PER_CONN.into(),
             rpc_max_connections: RPC_DEFAULT_MAX_CONNECTIONS.into(),
             rpc_max_tracing_requests: constants::default_max_tracing_requests(),
+            rpc_compute_threads: 0,
             rpc_max_blocks_per_filter: constants::DEFAULT_MAX_BLOCKS_PER_FILTER.into(),
             rpc_max_logs_per_response: (constants::DEFAULT_MAX_LOGS_PER_RESPONSE as u64).into(),
             rpc_gas_cap: constants::gas_oracle::RPC_DEFAULT_GAS_CAP,
@@ -331,7 +459,15 @@ impl Default for RpcServerArgs {
             gas_price_oracle: GasPriceOracleArgs::default(),
             rpc_state_cache: RpcStateCacheArgs::default(),
             rpc_proof_permits: constants::DEFAULT_PROOF_PERMITS,
+            rpc_history_cutoff: HistoryCutoff::Auto,
+            rpc_max_call_data_size: constants::DEFAULT_MAX_CALL_DATA_SIZE,
+            rpc_max_access_list_entries: constants::DEFAULT_MAX_ACCESS_LIST_ENTRIES,
+            rpc_max_state_override_accounts: constants::DEFAULT_MAX_STATE_OVERRIDE_ACCOUNTS,
+            rpc_reject_tx_while_syncing: false,
+            rpc_max_sync_distance: constants::DEFAULT_MAX_TX_SYNC_DISTANCE,
             builder_disallow: Default::default(),
+            flashbots_accept_bundles_locally: false,
+            flashbots_max_bundle_txs: constants::DEFAULT_MAX_BUNDLE_TXS,
         }
     }
 }
@@ -368,6 +504,16 @@ impl TypedValueParser for RpcModuleSelectionValueParser {
     }
 }
 
+/// Parses octal file permission bits, with or without a leading `0o` or `0`.
+fn parse_file_mode(value: &str) -> Result<u32, String> {
+    let digits = value.strip_prefix("0o").unwrap_or(value);
+    let mode = u32::from_str_radix(digits, 8).map_err(|err| err.to_string())?;
+    if mode > 0o7777 {
+        return Err(format!("{value} is not a valid file mode"))
+    }
+    Ok(mode)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -412,6 +558,92 @@ mod tests {
         assert_eq!(apis, expected);
     }
 
+    #[test]
+    fn test_rpc_server_args_ipc() {
+        let args = CommandParser::<RpcServerArgs>::parse_from([
+            "reth",
+            "--ipc.api",
+            "eth,net",
+            "--ipc.chmod",
+            "660",
+        ])
+        .args;
+
+        let expected = RpcModuleSelection::try_from_selection(["eth", "net"]).unwrap();
+        assert_eq!(args.ipc_api.unwrap(), expected);
+        assert_eq!(args.ipc_chmod, Some(0o660));
+
+        let args =
+            CommandParser::<RpcServerArgs>::parse_from(["reth", "--ipc.chmod", "0o600"]).args;
+        assert_eq!(args.ipc_chmod, Some(0o600));
+
+        assert!(
+            CommandParser::<RpcServerArgs>::try_parse_from(["reth", "--ipc.chmod", "8"]).is_err()
+        );
+        assert!(CommandParser::<RpcServerArgs>::try_parse_from(["reth", "--ipc.chmod", "17777"])
+            .is_err());
+    }
+
+    #[test]
+    fn test_rpc_server_args_history_cutoff() {
+        let args = CommandParser::<RpcServerArgs>::parse_from(["reth"]).args;
+        assert_eq!(args.rpc_history_cutoff, HistoryCutoff::Auto);
+
+        let args =
+            CommandParser::<RpcServerArgs>::parse_from(["reth", "--rpc.history-cutoff", "1000"])
+                .args;
+        assert_eq!(args.rpc_history_cutoff, HistoryCutoff::Block(1000));
+
+        let args = CommandParser::<RpcServerArgs>::parse_from([
+            "reth",
+            "--rpc.history-cutoff",
+            "distance:10064",
+        ])
+        .args;
+        assert_eq!(args.rpc_history_cutoff, HistoryCutoff::Distance(10064));
+
+        assert!(CommandParser::<RpcServerArgs>::try_parse_from([
+            "reth",
+            "--rpc.history-cutoff",
+            "distance"
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn test_rpc_server_args_reject_tx_while_syncing() {
+        let args = CommandParser::<RpcServerArgs>::parse_from(["reth"]).args;
+        assert!(!args.rpc_reject_tx_while_syncing);
+        assert_eq!(args.rpc_max_sync_distance, constants::DEFAULT_MAX_TX_SYNC_DISTANCE);
+
+        let args = CommandParser::<RpcServerArgs>::parse_from([
+            "reth",
+            "--rpc.reject-tx-while-syncing",
+            "--rpc.max-sync-distance",
+            "128",
+        ])
+        .args;
+        assert!(args.rpc_reject_tx_while_syncing);
+        assert_eq!(args.rpc_max_sync_distance, 128);
+    }
+
+    #[test]
+    fn test_rpc_server_args_flashbots_bundles() {
+        let args = CommandParser::<RpcServerArgs>::parse_from(["reth"]).args;
+        assert!(!args.flashbots_accept_bundles_locally);
+        assert_eq!(args.flashbots_max_bundle_txs, constants::DEFAULT_MAX_BUNDLE_TXS);
+
+        let args = CommandParser::<RpcServerArgs>::parse_from([
+            "reth",
+            "--flashbots.accept-bundles-locally",
+            "--flashbots.max-bundle-txs",
+            "10",
+        ])
+        .args;
+        assert!(args.flashbots_accept_bundles_locally);
+        assert_eq!(args.flashbots_max_bundle_txs, 10);
+    }
+
     #[test]
     fn rpc_server_args_default_sanity_test() {
         let default_args = RpcServerArgs::default();
@@ -1,7 +1,8 @@
 use clap::Args;
 use reth_rpc_server_types::constants::cache::{
-    DEFAULT_BLOCK_CACHE_MAX_LEN, DEFAULT_CONCURRENT_DB_REQUESTS, DEFAULT_HEADER_CACHE_MAX_LEN,
-    DEFAULT_RECEIPT_CACHE_MAX_LEN,
+    DEFAULT_ACCOUNT_CACHE_MAX_LEN, DEFAULT_BLOCK_CACHE_MAX_LEN, DEFAULT_CONCURRENT_DB_REQUESTS,
+    DEFAULT_HEADER_CACHE_MAX_LEN, DEFAULT_RECEIPT_CACHE_MAX_LEN, DEFAULT_STORAGE_CACHE_MAX_LEN,
+    DEFAULT_TX_JSON_CACHE_MAX_BLOCKS,
 };
 
 /// Parameters to configure RPC state cache.
@@ -35,6 +36,29 @@ pub struct RpcStateCacheArgs {
         default_value_t = DEFAULT_CONCURRENT_DB_REQUESTS,
     )]
     pub max_concurrent_db_requests: usize,
+
+    /// Max number of accounts of the latest state in cache. 0 disables the latest state cache if
+    /// `--rpc-cache.max-storage-slots` is 0 as well.
+    #[arg(
+        long = "rpc-cache.max-accounts",
+        default_value_t = DEFAULT_ACCOUNT_CACHE_MAX_LEN,
+    )]
+    pub max_accounts: u32,
+
+    /// Max number of storage slots of the latest state in cache.
+    #[arg(
+        long = "rpc-cache.max-storage-slots",
+        default_value_t = DEFAULT_STORAGE_CACHE_MAX_LEN,
+    )]
+    pub max_storage_slots: u32,
+
+    /// Max number of blocks whose serialized transaction objects are kept in cache, shared by the
+    /// block and transaction endpoints. 0 disables the cache.
+    #[arg(
+        long = "rpc-cache.max-tx-blocks",
+        default_value_t = DEFAULT_TX_JSON_CACHE_MAX_BLOCKS,
+    )]
+    pub max_tx_blocks: u32,
 }
 
 impl Default for RpcStateCacheArgs {
@@ -44,6 +68,9 @@ impl Default for RpcStateCacheArgs {
             max_receipts: DEFAULT_RECEIPT_CACHE_MAX_LEN,
             max_headers: DEFAULT_HEADER_CACHE_MAX_LEN,
             max_concurrent_db_requests: DEFAULT_CONCURRENT_DB_REQUESTS,
+            max_accounts: DEFAULT_ACCOUNT_CACHE_MAX_LEN,
+            max_storage_slots: DEFAULT_STORAGE_CACHE_MAX_LEN,
+            max_tx_blocks: DEFAULT_TX_JSON_CACHE_MAX_BLOCKS,
         }
     }
 }
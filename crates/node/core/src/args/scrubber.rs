@@ -0,0 +1,50 @@
+//! clap [Args](clap::Args) for the static files scrubber
+
+use crate::args::database::parse_byte_size;
+use clap::Args;
+
+/// Parameters for the background scrubber verifying static files
+#[derive(Debug, Args, PartialEq, Eq, Default, Clone, Copy)]
+#[command(next_help_heading = "Static files scrubber")]
+pub struct ScrubberArgs {
+    /// Continuously verify the static files and the transaction lookups in the background.
+    ///
+    /// Corrupted rows are logged as errors together with how to repair them. The scrubber pauses
+    /// while syncing, pruning and writing static files are paused for lack of disk space.
+    #[arg(long = "scrubber.enable")]
+    pub enable: bool,
+
+    /// Static file data verified per second by the scrubber.
+    ///
+    /// Defaults to 4MB.
+    #[arg(long = "scrubber.rate", value_name = "SIZE", value_parser = parse_byte_size)]
+    pub rate: Option<usize>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    /// A helper type to parse Args more easily
+    #[derive(Parser)]
+    struct CommandParser<T: Args> {
+        #[command(flatten)]
+        args: T,
+    }
+
+    #[test]
+    fn test_parse_scrubber_args() {
+        let args = CommandParser::<ScrubberArgs>::parse_from(["reth"]).args;
+        assert_eq!(args, ScrubberArgs::default());
+
+        let args = CommandParser::<ScrubberArgs>::parse_from([
+            "reth",
+            "--scrubber.enable",
+            "--scrubber.rate",
+            "16MB",
+        ])
+        .args;
+        assert_eq!(args, ScrubberArgs { enable: true, rate: Some(16 * 1024 * 1024) });
+    }
+}
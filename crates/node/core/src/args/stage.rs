@@ -50,4 +50,8 @@ pub enum StageEnum {
     ///
     /// Manages historical data related to storage.
     StorageHistory,
+    /// The optional log address index stage within the pipeline.
+    ///
+    /// Indexes the blocks in which each address emitted logs.
+    IndexLogAddresses,
 }
@@ -0,0 +1,96 @@
+//! clap [Args](clap::Args) for static files
+
+use crate::args::database::parse_byte_size;
+use clap::Args;
+use reth_fs_util::FsyncPolicy;
+
+/// Parameters for the static files written and read by the node
+#[derive(Debug, Args, PartialEq, Eq, Default, Clone, Copy)]
+#[command(next_help_heading = "Static Files")]
+pub struct StaticFilesArgs {
+    /// Disk space reserved at a time ahead of the appends to a static file, so that it's laid out
+    /// contiguously.
+    ///
+    /// Only reserved on Linux, except on ZFS, and on macOS. Disabled if unset or 0.
+    #[arg(long = "static-files.preallocate", value_name = "SIZE", value_parser = parse_byte_size)]
+    pub preallocate: Option<usize>,
+
+    /// When data appended to static files is synced to disk before it's committed.
+    ///
+    /// Either `commit` to only sync it when it's committed, or a size to also sync it every time
+    /// that much was appended, which spreads out the writes to disk.
+    #[arg(
+        long = "static-files.fsync-policy",
+        value_name = "POLICY",
+        value_parser = parse_fsync_policy,
+        default_value_t
+    )]
+    pub fsync_policy: FsyncPolicy,
+
+    /// Verify static files against their configuration and checksums when they're opened, so
+    /// that a damaged file fails to open instead of returning wrong data.
+    ///
+    /// Reads every static file with checksums in full when it's opened.
+    #[arg(long = "static-files.verify-on-open")]
+    pub verify_on_open: bool,
+}
+
+impl StaticFilesArgs {
+    /// Returns the disk space in bytes reserved at a time ahead of the appends to a static file,
+    /// `0` if disabled.
+    pub fn preallocation(&self) -> u64 {
+        self.preallocate.unwrap_or_default() as u64
+    }
+}
+
+/// Parses a [`FsyncPolicy`], either `commit` or a byte size.
+fn parse_fsync_policy(s: &str) -> Result<FsyncPolicy, String> {
+    if s.eq_ignore_ascii_case("commit") {
+        return Ok(FsyncPolicy::Commit)
+    }
+    match parse_byte_size(s)? {
+        0 => Err("fsync policy size must be greater than 0".to_string()),
+        size => Ok(FsyncPolicy::Bytes(size as u64)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    /// A helper type to parse Args more easily
+    #[derive(Parser)]
+    struct CommandParser<T: Args> {
+        #[command(flatten)]
+        args: T,
+    }
+
+    #[test]
+    fn test_parse_static_files_args() {
+        let args = CommandParser::<StaticFilesArgs>::parse_from(["reth"]).args;
+        assert_eq!(args, StaticFilesArgs::default());
+        assert_eq!(args.preallocation(), 0);
+        assert_eq!(args.fsync_policy, FsyncPolicy::Commit);
+
+        let args = CommandParser::<StaticFilesArgs>::parse_from([
+            "reth",
+            "--static-files.preallocate",
+            "64MB",
+            "--static-files.fsync-policy",
+            "16MB",
+            "--static-files.verify-on-open",
+        ])
+        .args;
+        assert_eq!(args.preallocation(), 64 * 1024 * 1024);
+        assert_eq!(args.fsync_policy, FsyncPolicy::Bytes(16 * 1024 * 1024));
+        assert!(args.verify_on_open);
+
+        assert!(CommandParser::<StaticFilesArgs>::try_parse_from([
+            "reth",
+            "--static-files.fsync-policy",
+            "0"
+        ])
+        .is_err());
+    }
+}
@@ -1,14 +1,20 @@
 //! Transaction pool arguments
 
 use crate::cli::config::RethTransactionPoolConfig;
-use alloy_eips::eip1559::{ETHEREUM_BLOCK_GAS_LIMIT, MIN_PROTOCOL_BASE_FEE};
+use alloy_eips::{
+    eip1559::{ETHEREUM_BLOCK_GAS_LIMIT, MIN_PROTOCOL_BASE_FEE},
+    eip4844::BYTES_PER_BLOB,
+};
 use alloy_primitives::Address;
 use clap::Args;
+use reth_network::transactions::constants::tx_manager::DEFAULT_MAX_NEW_PEER_ANNOUNCEMENTS;
 use reth_transaction_pool::{
-    blobstore::disk::DEFAULT_MAX_CACHED_BLOBS,
+    blobstore::{disk::DEFAULT_MAX_CACHED_BLOBS, BlobRetentionConfig},
+    maintain::MaintainPoolConfig,
     pool::{NEW_TX_LISTENER_BUFFER_SIZE, PENDING_TX_LISTENER_BUFFER_SIZE},
     validate::DEFAULT_MAX_TX_INPUT_BYTES,
-    LocalTransactionConfig, PoolConfig, PriceBumpConfig, SubPoolLimit, DEFAULT_PRICE_BUMP,
+    LocalTransactionConfig, PoolConfig, PriceBumpConfig, RejectedTransactionsConfig, SubPoolLimit,
+    DEFAULT_BLOB_RETENTION_MAX_SIZE_MB, DEFAULT_MAX_REJECTED_TRANSACTIONS, DEFAULT_PRICE_BUMP,
     DEFAULT_TXPOOL_ADDITIONAL_VALIDATION_TASKS, MAX_NEW_PENDING_TXS_NOTIFICATIONS,
     REPLACE_BLOB_PRICE_BUMP, TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER,
     TXPOOL_SUBPOOL_MAX_SIZE_MB_DEFAULT, TXPOOL_SUBPOOL_MAX_TXS_DEFAULT,
@@ -66,6 +72,19 @@ pub struct TxPoolArgs {
     #[arg(long = "txpool.max-cached-entries", alias = "txpool.max_cached_entries", default_value_t = DEFAULT_MAX_CACHED_BLOBS)]
     pub max_cached_entries: u32,
 
+    /// Number of epochs below the finalized block for which the blob sidecars of mined
+    /// transactions are kept, so they can be fetched with `eth_getBlobSidecars`. By default the
+    /// sidecars are deleted once their block is finalized.
+    ///
+    /// If enabled, the blob store is kept across restarts.
+    #[arg(long = "txpool.blob-retention-epochs", default_value_t = 0)]
+    pub blob_retention_epochs: u64,
+
+    /// Max size in megabytes of the retained blob sidecars, the sidecars of the oldest blocks are
+    /// deleted first once exceeded.
+    #[arg(long = "txpool.blob-retention-max-size", default_value_t = DEFAULT_BLOB_RETENTION_MAX_SIZE_MB)]
+    pub blob_retention_max_size: usize,
+
     /// Flag to disable local transaction exemptions.
     #[arg(long = "txpool.nolocals")]
     pub no_locals: bool,
@@ -91,6 +110,16 @@ pub struct TxPoolArgs {
     /// iterators.
     #[arg(long = "txpool.max-new-pending-txs-notifications", alias = "txpool.max-new-pending-txs-notifications", default_value_t = MAX_NEW_PENDING_TXS_NOTIFICATIONS)]
     pub max_new_pending_txs_notifications: usize,
+
+    /// Max number of recently rejected transactions to remember, so they are not validated again
+    /// when they are re-announced. Set to 0 to disable.
+    #[arg(long = "txpool.max-rejected-transactions", alias = "txpool.max_rejected_transactions", default_value_t = DEFAULT_MAX_REJECTED_TRANSACTIONS)]
+    pub max_rejected_transactions: u32,
+
+    /// Max number of pending transaction hashes to announce to a new peer at once. The rest of
+    /// the pool is announced to the peer in batches of the same size afterwards.
+    #[arg(long = "txpool.max-new-peer-announcements", default_value_t = DEFAULT_MAX_NEW_PEER_ANNOUNCEMENTS)]
+    pub max_new_peer_announcements: usize,
 }
 
 impl Default for TxPoolArgs {
@@ -109,6 +138,8 @@ impl Default for TxPoolArgs {
             blob_transaction_price_bump: REPLACE_BLOB_PRICE_BUMP,
             max_tx_input_bytes: DEFAULT_MAX_TX_INPUT_BYTES,
             max_cached_entries: DEFAULT_MAX_CACHED_BLOBS,
+            blob_retention_epochs: 0,
+            blob_retention_max_size: DEFAULT_BLOB_RETENTION_MAX_SIZE_MB,
             no_locals: false,
             locals: Default::default(),
             no_local_transactions_propagation: false,
@@ -116,6 +147,8 @@ impl Default for TxPoolArgs {
             pending_tx_listener_buffer_size: PENDING_TX_LISTENER_BUFFER_SIZE,
             new_tx_listener_buffer_size: NEW_TX_LISTENER_BUFFER_SIZE,
             max_new_pending_txs_notifications: MAX_NEW_PENDING_TXS_NOTIFICATIONS,
+            max_rejected_transactions: DEFAULT_MAX_REJECTED_TRANSACTIONS,
+            max_new_peer_announcements: DEFAULT_MAX_NEW_PEER_ANNOUNCEMENTS,
         }
     }
 }
@@ -155,6 +188,20 @@ impl RethTransactionPoolConfig for TxPoolArgs {
             pending_tx_listener_buffer_size: self.pending_tx_listener_buffer_size,
             new_tx_listener_buffer_size: self.new_tx_listener_buffer_size,
             max_new_pending_txs_notifications: self.max_new_pending_txs_notifications,
+            rejected_transactions: RejectedTransactionsConfig {
+                max_len: self.max_rejected_transactions,
+                ..Default::default()
+            },
+        }
+    }
+
+    /// Returns the configuration of the transaction pool maintenance task.
+    fn maintain_pool_config(&self) -> MaintainPoolConfig {
+        let max_blobs = self.blob_retention_max_size.saturating_mul(1024 * 1024) / BYTES_PER_BLOB;
+        MaintainPoolConfig {
+            blob_retention: BlobRetentionConfig::epochs(self.blob_retention_epochs)
+                .with_max_blobs(max_blobs),
+            ..Default::default()
         }
     }
 }
@@ -188,4 +235,21 @@ mod tests {
         .args;
         assert_eq!(args.locals, vec![Address::ZERO]);
     }
+
+    #[test]
+    fn txpool_parse_blob_retention() {
+        let args = CommandParser::<TxPoolArgs>::parse_from([
+            "reth",
+            "--txpool.blob-retention-epochs",
+            "2",
+            "--txpool.blob-retention-max-size",
+            "1",
+        ])
+        .args;
+        assert_eq!(args.blob_retention_epochs, 2);
+        assert_eq!(
+            args.maintain_pool_config().blob_retention,
+            BlobRetentionConfig { blocks: 64, max_blobs: 8 }
+        );
+    }
 }
@@ -1,5 +1,6 @@
 //! Additional helper types for CLI parsing.
 
+use reth_prune_types::PruneMode;
 use std::{fmt, num::ParseIntError, str::FromStr};
 
 /// A macro that generates types that maps "0" to "None" when parsing CLI arguments.
@@ -95,6 +96,108 @@ macro_rules! max_values {
 max_values!(MaxU32, u32);
 max_values!(MaxU64, u64);
 
+/// A helper type for parsing a [`PruneMode`] from `full`, `distance:<BLOCKS>` or
+/// `before:<BLOCK_NUMBER>`.
+///
+/// Numbers may contain `_` separators and end with a `k` (thousand) or `m` (million) suffix, e.g.
+/// `distance:10_064` or `before:17m`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PruneModeArg(pub PruneMode);
+
+impl PruneModeArg {
+    /// Returns the inner value.
+    pub const fn get(&self) -> PruneMode {
+        self.0
+    }
+}
+
+impl fmt::Display for PruneModeArg {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            PruneMode::Full => write!(f, "full"),
+            PruneMode::Distance(distance) => write!(f, "distance:{distance}"),
+            PruneMode::Before(block_number) => write!(f, "before:{block_number}"),
+        }
+    }
+}
+
+impl From<PruneMode> for PruneModeArg {
+    #[inline]
+    fn from(value: PruneMode) -> Self {
+        Self(value)
+    }
+}
+
+impl From<PruneModeArg> for PruneMode {
+    #[inline]
+    fn from(value: PruneModeArg) -> Self {
+        value.0
+    }
+}
+
+impl FromStr for PruneModeArg {
+    type Err = PruneModeArgError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (mode, value) = match s.split_once(':') {
+            Some((mode, value)) => (mode, Some(value)),
+            None => (s, None),
+        };
+
+        let mode = match (mode.to_ascii_lowercase().as_str(), value) {
+            ("full", None) => PruneMode::Full,
+            ("distance", Some(value)) => PruneMode::Distance(parse_block_count(value)?),
+            ("before", Some(value)) => PruneMode::Before(parse_block_count(value)?),
+            ("distance" | "before", None) => {
+                return Err(PruneModeArgError::MissingNumber(s.to_string()))
+            }
+            _ => return Err(PruneModeArgError::InvalidMode(s.to_string())),
+        };
+        Ok(Self(mode))
+    }
+}
+
+/// Parses a number of blocks or a block number, allowing `_` separators and a `k` or `m` suffix.
+fn parse_block_count(s: &str) -> Result<u64, PruneModeArgError> {
+    let digits = s.replace('_', "");
+    let (digits, multiplier) = match digits.as_bytes().last() {
+        Some(b'k' | b'K') => (&digits[..digits.len() - 1], 1_000),
+        Some(b'm' | b'M') => (&digits[..digits.len() - 1], 1_000_000),
+        _ => (digits.as_str(), 1),
+    };
+
+    if digits.starts_with('-') {
+        return Err(PruneModeArgError::NegativeNumber(s.to_string()))
+    }
+    let value = digits
+        .parse::<u64>()
+        .map_err(|err| PruneModeArgError::InvalidNumber(s.to_string(), err))?;
+    value.checked_mul(multiplier).ok_or_else(|| PruneModeArgError::NumberTooLarge(s.to_string()))
+}
+
+/// Error while parsing a [`PruneModeArg`].
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum PruneModeArgError {
+    /// The prune mode is not one of full, distance, before.
+    #[error(
+        "invalid prune mode '{0}', expected 'full', 'distance:<BLOCKS>' or \
+         'before:<BLOCK_NUMBER>'"
+    )]
+    InvalidMode(String),
+    /// The distance or block number is missing.
+    #[error("missing number of blocks in '{0}'")]
+    MissingNumber(String),
+    /// The distance or block number is negative.
+    #[error("'{0}' must not be negative")]
+    NegativeNumber(String),
+    /// The distance or block number is not a number.
+    #[error("'{0}' is not a valid number: {1}")]
+    InvalidNumber(String, ParseIntError),
+    /// The distance or block number doesn't fit in a `u64`.
+    #[error("'{0}' is too large")]
+    NumberTooLarge(String),
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -116,4 +219,57 @@ mod tests {
         let expected = ZeroAsNoneU64(None);
         assert_eq!(ZeroAsNoneU64::from(original), expected);
     }
+
+    #[test]
+    fn test_parse_prune_mode() {
+        for (arg, mode) in [
+            ("full", PruneMode::Full),
+            ("FULL", PruneMode::Full),
+            ("distance:10064", PruneMode::Distance(10064)),
+            ("distance:10_064", PruneMode::Distance(10064)),
+            ("Distance:10k", PruneMode::Distance(10_000)),
+            ("before:17000000", PruneMode::Before(17_000_000)),
+            ("before:17M", PruneMode::Before(17_000_000)),
+            ("before:0", PruneMode::Before(0)),
+        ] {
+            assert_eq!(arg.parse::<PruneModeArg>().unwrap().get(), mode, "{arg}");
+        }
+    }
+
+    #[test]
+    fn test_prune_mode_round_trip() {
+        for mode in [PruneMode::Full, PruneMode::Distance(10064), PruneMode::Before(17_000_000)] {
+            let arg = PruneModeArg(mode);
+            assert_eq!(arg.to_string().parse::<PruneModeArg>().unwrap(), arg);
+        }
+    }
+
+    #[test]
+    fn test_parse_prune_mode_invalid() {
+        assert!(matches!("none".parse::<PruneModeArg>(), Err(PruneModeArgError::InvalidMode(_))));
+        assert!(matches!(
+            "full:10".parse::<PruneModeArg>(),
+            Err(PruneModeArgError::InvalidMode(_))
+        ));
+        assert!(matches!(
+            "distance".parse::<PruneModeArg>(),
+            Err(PruneModeArgError::MissingNumber(_))
+        ));
+        assert!(matches!(
+            "distance:-10".parse::<PruneModeArg>(),
+            Err(PruneModeArgError::NegativeNumber(_))
+        ));
+        assert!(matches!(
+            "before:ten".parse::<PruneModeArg>(),
+            Err(PruneModeArgError::InvalidNumber(..))
+        ));
+        assert!(matches!(
+            "before:k".parse::<PruneModeArg>(),
+            Err(PruneModeArgError::InvalidNumber(..))
+        ));
+        assert!(matches!(
+            "before:18446744073709552k".parse::<PruneModeArg>(),
+            Err(PruneModeArgError::NumberTooLarge(_))
+        ));
+    }
 }
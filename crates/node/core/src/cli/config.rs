@@ -2,7 +2,7 @@
 
 use alloy_primitives::Bytes;
 use reth_network::{protocol::IntoRlpxSubProtocol, NetworkPrimitives};
-use reth_transaction_pool::PoolConfig;
+use reth_transaction_pool::{maintain::MaintainPoolConfig, PoolConfig};
 use std::{borrow::Cow, time::Duration};
 
 /// A trait that provides payload builder settings.
@@ -24,6 +24,13 @@ pub trait PayloadBuilderConfig {
     /// The deadline for when the payload builder job should resolve.
     fn deadline(&self) -> Duration;
 
+    /// How long resolving a payload may wait for the build iteration in progress to complete.
+    ///
+    /// Defaults to zero, which resolves the best payload built so far without waiting.
+    fn getpayload_wait(&self) -> Duration {
+        Duration::ZERO
+    }
+
     /// Target gas limit for built blocks.
     fn gas_limit(&self) -> u64;
 
@@ -64,4 +71,9 @@ impl<N: NetworkPrimitives> RethNetworkConfig for reth_network::NetworkManager<N>
 pub trait RethTransactionPoolConfig {
     /// Returns transaction pool configuration.
     fn pool_config(&self) -> PoolConfig;
+
+    /// Returns the configuration of the transaction pool maintenance task.
+    fn maintain_pool_config(&self) -> MaintainPoolConfig {
+        MaintainPoolConfig::default()
+    }
 }
@@ -2,6 +2,7 @@
 
 use crate::{args::DatadirArgs, utils::parse_path};
 use reth_chainspec::Chain;
+use reth_fs_util::FsPathError;
 use std::{
     env::VarError,
     fmt::{Debug, Display, Formatter},
@@ -9,6 +10,15 @@ use std::{
     str::FromStr,
 };
 
+/// Environment variable that overrides the default reth data directory.
+///
+/// It takes precedence over the OS-specific data directory, but not over an explicitly provided
+/// `--datadir`.
+pub const DATADIR_ENV: &str = "RETH_DATADIR";
+
+/// Name of the directory static files were stored in before they were renamed from snapshots.
+pub const LEGACY_STATIC_FILES_DIR: &str = "snapshots";
+
 /// Constructs a string to be used as a path for configuration and db paths.
 pub fn config_path_prefix(chain: Chain) -> String {
     chain.to_string()
@@ -16,8 +26,12 @@ pub fn config_path_prefix(chain: Chain) -> String {
 
 /// Returns the path to the reth data directory.
 ///
-/// Refer to [`dirs_next::data_dir`] for cross-platform behavior.
+/// This is the value of [`DATADIR_ENV`] if set, otherwise refer to [`dirs_next::data_dir`] for
+/// cross-platform behavior.
 pub fn data_dir() -> Option<PathBuf> {
+    if let Some(dir) = std::env::var_os(DATADIR_ENV).filter(|dir| !dir.is_empty()) {
+        return Some(PathBuf::from(dir))
+    }
     dirs_next::data_dir().map(|root| root.join("reth"))
 }
 
@@ -275,6 +289,16 @@ impl<D> ChainPath<D> {
         Self(path, chain, datadir_args)
     }
 
+    /// Returns the chain of this path.
+    pub const fn chain(&self) -> Chain {
+        self.1
+    }
+
+    /// Returns the datadir arguments this path was resolved with.
+    pub const fn datadir_args(&self) -> &DatadirArgs {
+        &self.2
+    }
+
     /// Returns the path to the reth data directory for this chain.
     ///
     /// `<DIR>/<CHAIN_ID>`
@@ -315,6 +339,13 @@ impl<D> ChainPath<D> {
         self.data_dir().join("known-peers.json")
     }
 
+    /// Returns the path to the progress file of the static file scrubber for this chain.
+    ///
+    /// `<DIR>/<CHAIN_ID>/static-file-scrubber.json`
+    pub fn static_file_scrubber(&self) -> PathBuf {
+        self.data_dir().join("static-file-scrubber.json")
+    }
+
     /// Returns the path to the blobstore directory for this chain where blobs of unfinalized
     /// transactions are stored.
     ///
@@ -355,6 +386,84 @@ impl<D> ChainPath<D> {
     pub fn exex_wal(&self) -> PathBuf {
         self.data_dir().join("exex/wal")
     }
+
+    /// Detects the layout of the data directory for this chain.
+    ///
+    /// Static files of a legacy layout are stored in `<DIR>/<CHAIN_ID>/snapshots`. The layout is
+    /// only considered legacy if the static files directory is missing or empty, so that nothing
+    /// is ever overwritten by [`DatadirMigration::apply`].
+    pub fn layout(&self) -> DatadirLayout {
+        let legacy_static_files = self.data_dir().join(LEGACY_STATIC_FILES_DIR);
+        let static_files = self.static_files();
+        if legacy_static_files.is_dir() && is_missing_or_empty_dir(&static_files) {
+            return DatadirLayout::Legacy(DatadirMigration {
+                moves: vec![DatadirMove { from: legacy_static_files, to: static_files }],
+            })
+        }
+
+        if self.db().is_dir() {
+            DatadirLayout::Current
+        } else {
+            DatadirLayout::Empty
+        }
+    }
+}
+
+/// Returns `true` if the path does not exist or is an empty directory.
+fn is_missing_or_empty_dir(path: &Path) -> bool {
+    match std::fs::read_dir(path) {
+        Ok(mut entries) => entries.next().is_none(),
+        Err(err) => err.kind() == std::io::ErrorKind::NotFound,
+    }
+}
+
+/// Layout of the data directory of a chain, see [`ChainPath::layout`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DatadirLayout {
+    /// There is no database in the data directory yet.
+    Empty,
+    /// The data directory uses the current layout.
+    Current,
+    /// The data directory uses a legacy layout and can be migrated to the current one.
+    Legacy(DatadirMigration),
+}
+
+/// A single file or directory move of a [`DatadirMigration`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DatadirMove {
+    /// Path in the legacy layout.
+    pub from: PathBuf,
+    /// Path in the current layout.
+    pub to: PathBuf,
+}
+
+/// Moves that turn a legacy data directory layout into the current one.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DatadirMigration {
+    moves: Vec<DatadirMove>,
+}
+
+impl DatadirMigration {
+    /// Returns the moves of this migration.
+    pub fn moves(&self) -> &[DatadirMove] {
+        &self.moves
+    }
+
+    /// Moves all files into the current layout.
+    ///
+    /// Empty directories at the destination are replaced. Moves are renames, so the source and
+    /// the destination must be on the same filesystem.
+    pub fn apply(self) -> Result<(), FsPathError> {
+        for DatadirMove { from, to } in self.moves {
+            if to.is_dir() {
+                std::fs::remove_dir(&to).map_err(|err| FsPathError::remove_dir(err, &to))?;
+            } else if let Some(parent) = to.parent() {
+                reth_fs_util::create_dir_all(parent)?;
+            }
+            reth_fs_util::rename(&from, &to)?;
+        }
+        Ok(())
+    }
 }
 
 impl<D> AsRef<Path> for ChainPath<D> {
@@ -393,6 +502,54 @@ mod tests {
         assert!(path.as_ref().ends_with("my/path/to/datadir"), "{path:?}");
     }
 
+    #[test]
+    fn test_legacy_static_files_layout() {
+        let root = tempfile::tempdir().unwrap();
+        let path = MaybePlatformPath::<DataDirPath>::from(root.path().to_path_buf())
+            .unwrap_or_chain_default(Chain::mainnet(), DatadirArgs::default());
+        assert_eq!(path.layout(), DatadirLayout::Empty);
+
+        // synthetic layout of a version that stored static files as snapshots
+        let snapshots = root.path().join(LEGACY_STATIC_FILES_DIR);
+        std::fs::create_dir_all(path.db()).unwrap();
+        std::fs::create_dir_all(&snapshots).unwrap();
+        std::fs::write(snapshots.join("static_file_headers_0_499999"), b"headers").unwrap();
+        std::fs::write(path.db().join("mdbx.dat"), b"db").unwrap();
+        // created by a newer version that was started on the legacy layout
+        std::fs::create_dir_all(path.static_files()).unwrap();
+
+        let DatadirLayout::Legacy(migration) = path.layout() else {
+            panic!("expected legacy layout, got {:?}", path.layout())
+        };
+        assert_eq!(
+            migration.moves(),
+            [DatadirMove { from: snapshots.clone(), to: path.static_files() }]
+        );
+
+        migration.apply().unwrap();
+        assert_eq!(path.layout(), DatadirLayout::Current);
+        assert!(!snapshots.exists());
+        assert_eq!(
+            std::fs::read(path.static_files().join("static_file_headers_0_499999")).unwrap(),
+            b"headers"
+        );
+        assert_eq!(std::fs::read(path.db().join("mdbx.dat")).unwrap(), b"db");
+    }
+
+    #[test]
+    fn test_legacy_layout_never_overwrites_static_files() {
+        let root = tempfile::tempdir().unwrap();
+        let path = MaybePlatformPath::<DataDirPath>::from(root.path().to_path_buf())
+            .unwrap_or_chain_default(Chain::mainnet(), DatadirArgs::default());
+
+        std::fs::create_dir_all(path.db()).unwrap();
+        std::fs::create_dir_all(root.path().join(LEGACY_STATIC_FILES_DIR)).unwrap();
+        std::fs::create_dir_all(path.static_files()).unwrap();
+        std::fs::write(path.static_files().join("static_file_headers_0_499999"), b"").unwrap();
+
+        assert_eq!(path.layout(), DatadirLayout::Current);
+    }
+
     #[test]
     fn test_maybe_testnet_datadir_path() {
         let path = MaybePlatformPath::<DataDirPath>::default();
@@ -14,6 +14,7 @@ pub mod dirs;
 pub mod exit;
 pub mod node_config;
 pub mod utils;
+pub mod validation;
 pub mod version;
 
 /// Re-exported primitive types
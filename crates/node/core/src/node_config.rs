@@ -2,8 +2,8 @@
 
 use crate::{
     args::{
-        DatabaseArgs, DatadirArgs, DebugArgs, DevArgs, NetworkArgs, PayloadBuilderArgs,
-        PruningArgs, RpcServerArgs, TxPoolArgs,
+        DatabaseArgs, DatadirArgs, DebugArgs, DevArgs, DiskSpaceArgs, NetworkArgs,
+        PayloadBuilderArgs, PruningArgs, RpcServerArgs, ScrubberArgs, StaticFilesArgs, TxPoolArgs,
     },
     dirs::{ChainPath, DataDirPath},
     utils::get_single_header,
@@ -110,6 +110,10 @@ pub struct NodeConfig<ChainSpec> {
     /// - `WS_RPC_PORT`: default + `instance` * 2 - 2
     pub instance: u16,
 
+    /// Only reports the inconsistencies between the stage checkpoints, static files and prune
+    /// checkpoints, and the planned repairs, without repairing them. Exits once they're reported.
+    pub check_consistency_only: bool,
+
     /// All networking related arguments
     pub network: NetworkArgs,
 
@@ -133,6 +137,15 @@ pub struct NodeConfig<ChainSpec> {
 
     /// All pruning related arguments
     pub pruning: PruningArgs,
+
+    /// All static files scrubber related arguments with --scrubber prefix
+    pub scrubber: ScrubberArgs,
+
+    /// All static files related arguments with --static-files prefix
+    pub static_files: StaticFilesArgs,
+
+    /// All disk space monitoring related arguments
+    pub disk_space: DiskSpaceArgs,
 }
 
 impl NodeConfig<ChainSpec> {
@@ -152,6 +165,7 @@ impl<ChainSpec> NodeConfig<ChainSpec> {
             chain,
             metrics: None,
             instance: 1,
+            check_consistency_only: false,
             network: NetworkArgs::default(),
             rpc: RpcServerArgs::default(),
             txpool: TxPoolArgs::default(),
@@ -160,6 +174,9 @@ impl<ChainSpec> NodeConfig<ChainSpec> {
             db: DatabaseArgs::default(),
             dev: DevArgs::default(),
             pruning: PruningArgs::default(),
+            scrubber: ScrubberArgs::default(),
+            static_files: StaticFilesArgs::default(),
+            disk_space: DiskSpaceArgs::default(),
             datadir: DatadirArgs::default(),
         }
     }
@@ -213,6 +230,12 @@ impl<ChainSpec> NodeConfig<ChainSpec> {
         self
     }
 
+    /// Only report the storage inconsistencies on launch, without repairing them
+    pub const fn with_check_consistency_only(mut self, check_consistency_only: bool) -> Self {
+        self.check_consistency_only = check_consistency_only;
+        self
+    }
+
     /// Set the network args for the node
     pub fn with_network(mut self, network: NetworkArgs) -> Self {
         self.network = network;
@@ -261,6 +284,24 @@ impl<ChainSpec> NodeConfig<ChainSpec> {
         self
     }
 
+    /// Set the static files scrubber args for the node
+    pub const fn with_scrubber(mut self, scrubber: ScrubberArgs) -> Self {
+        self.scrubber = scrubber;
+        self
+    }
+
+    /// Set the static files args for the node
+    pub const fn with_static_files(mut self, static_files: StaticFilesArgs) -> Self {
+        self.static_files = static_files;
+        self
+    }
+
+    /// Set the disk space monitoring args for the node
+    pub const fn with_disk_space(mut self, disk_space: DiskSpaceArgs) -> Self {
+        self.disk_space = disk_space;
+        self
+    }
+
     /// Returns pruning configuration.
     pub fn prune_config(&self) -> Option<PruneConfig>
     where
@@ -441,6 +482,7 @@ impl<ChainSpec> NodeConfig<ChainSpec> {
             config: self.config,
             metrics: self.metrics,
             instance: self.instance,
+            check_consistency_only: self.check_consistency_only,
             network: self.network,
             rpc: self.rpc,
             txpool: self.txpool,
@@ -449,6 +491,9 @@ impl<ChainSpec> NodeConfig<ChainSpec> {
             db: self.db,
             dev: self.dev,
             pruning: self.pruning,
+            scrubber: self.scrubber,
+            static_files: self.static_files,
+            disk_space: self.disk_space,
         }
     }
 }
@@ -466,6 +511,7 @@ impl<ChainSpec> Clone for NodeConfig<ChainSpec> {
             config: self.config.clone(),
             metrics: self.metrics,
             instance: self.instance,
+            check_consistency_only: self.check_consistency_only,
             network: self.network.clone(),
             rpc: self.rpc.clone(),
             txpool: self.txpool.clone(),
@@ -474,6 +520,9 @@ impl<ChainSpec> Clone for NodeConfig<ChainSpec> {
             db: self.db,
             dev: self.dev,
             pruning: self.pruning.clone(),
+            scrubber: self.scrubber,
+            static_files: self.static_files,
+            disk_space: self.disk_space,
             datadir: self.datadir.clone(),
         }
     }
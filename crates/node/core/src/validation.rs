@@ -0,0 +1,332 @@
+//! Validation of flag combinations of the [`NodeConfig`].
+
+use crate::node_config::NodeConfig;
+use reth_prune_types::{PruneMode, PrunePurpose};
+use std::fmt;
+use tracing::warn;
+
+/// Severity of a [`ConfigIssue`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigIssueSeverity {
+    /// The node can't start with this configuration.
+    Error,
+    /// The configuration is suspicious, but the node can start with it.
+    Warning,
+}
+
+/// A combination of flags of the [`NodeConfig`] that is nonsensical or suspicious.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ConfigIssue {
+    /// `--full` is set, but the prune flags of a segment keep its entire history.
+    #[error(
+        "--full is set, but --{flag} prunes nothing before block 0 and keeps the entire history \
+         of the segment: remove the --{flag} flags or --full"
+    )]
+    FullNodeWithoutPruning {
+        /// The common prefix of the prune flags of the segment, e.g. `prune.receipts`.
+        flag: &'static str,
+    },
+    /// The prune flags of a segment keep fewer blocks than required to handle reorgs.
+    #[error(
+        "--{flag} keeps {distance} blocks, which is below the minimum pruning distance of \
+         {minimum} blocks required to handle reorgs: keep at least {minimum} blocks"
+    )]
+    PruneDistanceBelowMinimum {
+        /// The common prefix of the prune flags of the segment, e.g. `prune.receipts`.
+        flag: &'static str,
+        /// The number of blocks kept, `0` if the segment is fully pruned.
+        distance: u64,
+        /// The minimum number of blocks to keep.
+        minimum: u64,
+    },
+    /// An RPC server is enabled without any API modules.
+    #[error(
+        "--{server} is set, but --{server}.api selects no modules: select modules with \
+         --{server}.api or remove --{server}"
+    )]
+    RpcServerWithoutModules {
+        /// The RPC server, `http` or `ws`.
+        server: &'static str,
+    },
+    /// Local transaction exemptions are disabled in dev mode, where all transactions are sent
+    /// locally.
+    #[error(
+        "--txpool.nolocals is set in --dev mode, where all transactions are local: remove \
+         --txpool.nolocals unless local transactions should be treated as remote"
+    )]
+    NoLocalsInDevMode,
+}
+
+impl ConfigIssue {
+    /// Returns the severity of the issue.
+    pub const fn severity(&self) -> ConfigIssueSeverity {
+        match self {
+            Self::FullNodeWithoutPruning { .. } |
+            Self::PruneDistanceBelowMinimum { .. } |
+            Self::RpcServerWithoutModules { .. } => ConfigIssueSeverity::Error,
+            Self::NoLocalsInDevMode => ConfigIssueSeverity::Warning,
+        }
+    }
+
+    /// Returns `true` if the node can't start with this issue.
+    pub const fn is_error(&self) -> bool {
+        matches!(self.severity(), ConfigIssueSeverity::Error)
+    }
+}
+
+/// All issues found by [`NodeConfig::validate`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConfigIssues {
+    issues: Vec<ConfigIssue>,
+}
+
+impl ConfigIssues {
+    /// Returns all issues.
+    pub fn issues(&self) -> &[ConfigIssue] {
+        &self.issues
+    }
+
+    /// Returns `true` if no issues were found.
+    pub fn is_empty(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    /// Returns an iterator over the issues the node can't start with.
+    pub fn errors(&self) -> impl Iterator<Item = &ConfigIssue> {
+        self.issues.iter().filter(|issue| issue.is_error())
+    }
+
+    /// Returns an iterator over the suspicious, but not fatal issues.
+    pub fn warnings(&self) -> impl Iterator<Item = &ConfigIssue> {
+        self.issues.iter().filter(|issue| !issue.is_error())
+    }
+
+    /// Fails if there are any issues, or only if there are errors when `ignore_warnings` is set,
+    /// in which case the warnings are logged instead.
+    pub fn ensure_valid(self, ignore_warnings: bool) -> Result<(), Self> {
+        if self.errors().next().is_some() || (!ignore_warnings && !self.is_empty()) {
+            return Err(self)
+        }
+
+        for issue in self.warnings() {
+            warn!(target: "reth::cli", "{issue}");
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for ConfigIssues {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid node configuration")?;
+
+        let mut errors = self.errors().peekable();
+        if errors.peek().is_some() {
+            write!(f, "\nerrors:")?;
+            for issue in errors {
+                write!(f, "\n  - {issue}")?;
+            }
+        }
+
+        let mut warnings = self.warnings().peekable();
+        if warnings.peek().is_some() {
+            write!(f, "\nwarnings (pass --ignore-config-warnings to start anyway):")?;
+            for issue in warnings {
+                write!(f, "\n  - {issue}")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl std::error::Error for ConfigIssues {}
+
+impl<ChainSpec> NodeConfig<ChainSpec> {
+    /// Checks the combinations of flags of the config that can't be rejected while parsing them
+    /// and returns all issues found.
+    ///
+    /// This should be called before any component of the node is started.
+    pub fn validate(&self) -> ConfigIssues {
+        let mut issues = Vec::new();
+        self.validate_pruning(&mut issues);
+        self.validate_rpc(&mut issues);
+        self.validate_txpool(&mut issues);
+        ConfigIssues { issues }
+    }
+
+    fn validate_pruning(&self, issues: &mut Vec<ConfigIssue>) {
+        // the reorgs injected with `--debug.reorg-depth` must be handled as well
+        let reorg_depth = self.debug.reorg_depth.unwrap_or_default() as u64;
+
+        for (segment, flag, mode) in self.pruning.segment_prune_modes() {
+            let Some(mode) = mode else { continue };
+
+            if self.pruning.full && mode == PruneMode::Before(0) {
+                issues.push(ConfigIssue::FullNodeWithoutPruning { flag });
+            }
+
+            // only the segments that are needed to unwind blocks have a minimum
+            let min_blocks = segment.min_blocks(PrunePurpose::User);
+            if min_blocks == 0 {
+                continue
+            }
+            let minimum = min_blocks.max(reorg_depth);
+            let distance = match mode {
+                PruneMode::Full => 0,
+                PruneMode::Distance(distance) => distance,
+                PruneMode::Before(_) => continue,
+            };
+            if distance < minimum {
+                issues.push(ConfigIssue::PruneDistanceBelowMinimum { flag, distance, minimum });
+            }
+        }
+    }
+
+    fn validate_rpc(&self, issues: &mut Vec<ConfigIssue>) {
+        let rpc = &self.rpc;
+
+        if rpc.http && rpc.http_api.as_ref().is_some_and(|api| api.is_empty()) {
+            issues.push(ConfigIssue::RpcServerWithoutModules { server: "http" });
+        }
+        if rpc.ws && rpc.ws_api.as_ref().is_some_and(|api| api.is_empty()) {
+            issues.push(ConfigIssue::RpcServerWithoutModules { server: "ws" });
+        }
+    }
+
+    fn validate_txpool(&self, issues: &mut Vec<ConfigIssue>) {
+        if self.dev.dev && self.txpool.no_locals {
+            issues.push(ConfigIssue::NoLocalsInDevMode);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_prune_types::MINIMUM_PRUNING_DISTANCE;
+    use reth_rpc_server_types::RpcModuleSelection;
+
+    #[test]
+    fn valid_default_config() {
+        let issues = NodeConfig::test().validate();
+        assert!(issues.is_empty());
+        assert_eq!(issues.ensure_valid(false), Ok(()));
+    }
+
+    #[test]
+    fn full_node_without_pruning() {
+        let mut config = NodeConfig::test();
+        config.pruning.full = true;
+        config.pruning.receipts_before = Some(0);
+        assert_eq!(
+            config.validate().issues(),
+            [ConfigIssue::FullNodeWithoutPruning { flag: "prune.receipts" }]
+        );
+
+        config.pruning.receipts_before = Some(1);
+        assert!(config.validate().is_empty());
+
+        config.pruning.full = false;
+        config.pruning.receipts_before = Some(0);
+        assert!(config.validate().is_empty());
+    }
+
+    #[test]
+    fn prune_distance_below_minimum() {
+        let mut config = NodeConfig::test();
+        config.pruning.account_history_distance = Some(MINIMUM_PRUNING_DISTANCE - 1);
+        assert_eq!(
+            config.validate().issues(),
+            [ConfigIssue::PruneDistanceBelowMinimum {
+                flag: "prune.accounthistory",
+                distance: MINIMUM_PRUNING_DISTANCE - 1,
+                minimum: MINIMUM_PRUNING_DISTANCE,
+            }]
+        );
+
+        config.pruning.account_history_distance = Some(MINIMUM_PRUNING_DISTANCE);
+        assert!(config.validate().is_empty());
+
+        // fully pruning a segment keeps no blocks
+        config.pruning.account_history_distance = None;
+        config.pruning.storage_history_full = true;
+        assert_eq!(
+            config.validate().issues(),
+            [ConfigIssue::PruneDistanceBelowMinimum {
+                flag: "prune.storagehistory",
+                distance: 0,
+                minimum: MINIMUM_PRUNING_DISTANCE,
+            }]
+        );
+
+        // segments that aren't needed to unwind blocks can be fully pruned
+        config.pruning.storage_history_full = false;
+        config.pruning.sender_recovery_full = true;
+        config.pruning.transaction_lookup_distance = Some(0);
+        assert!(config.validate().is_empty());
+    }
+
+    #[test]
+    fn prune_distance_below_debug_reorg_depth() {
+        let mut config = NodeConfig::test();
+        let reorg_depth = MINIMUM_PRUNING_DISTANCE + 10;
+        config.debug.reorg_depth = Some(reorg_depth as usize);
+        config.pruning.receipts = Some(PruneMode::Distance(MINIMUM_PRUNING_DISTANCE));
+        assert_eq!(
+            config.validate().issues(),
+            [ConfigIssue::PruneDistanceBelowMinimum {
+                flag: "prune.receipts",
+                distance: MINIMUM_PRUNING_DISTANCE,
+                minimum: reorg_depth,
+            }]
+        );
+
+        config.pruning.receipts = Some(PruneMode::Distance(reorg_depth));
+        assert!(config.validate().is_empty());
+    }
+
+    #[test]
+    fn rpc_server_without_modules() {
+        let mut config = NodeConfig::test();
+        config.rpc.ws = true;
+        config.rpc.ws_api = Some(RpcModuleSelection::Selection(Default::default()));
+        assert_eq!(
+            config.validate().issues(),
+            [ConfigIssue::RpcServerWithoutModules { server: "ws" }]
+        );
+
+        config.rpc.ws_api = None;
+        assert!(config.validate().is_empty());
+
+        config.rpc.ws = false;
+        config.rpc.ws_api = Some(RpcModuleSelection::Selection(Default::default()));
+        assert!(config.validate().is_empty());
+    }
+
+    #[test]
+    fn no_locals_in_dev_mode() {
+        let mut config = NodeConfig::test().dev();
+        config.txpool.no_locals = true;
+        assert_eq!(config.validate().issues(), [ConfigIssue::NoLocalsInDevMode]);
+
+        config.txpool.no_locals = false;
+        assert!(config.validate().is_empty());
+    }
+
+    #[test]
+    fn ignore_warnings() {
+        let mut config = NodeConfig::test().dev();
+        config.txpool.no_locals = true;
+        assert!(config.validate().ensure_valid(false).is_err());
+        assert_eq!(config.validate().ensure_valid(true), Ok(()));
+
+        // errors can't be ignored
+        config.rpc.http = true;
+        config.rpc.http_api = Some(RpcModuleSelection::Selection(Default::default()));
+        let issues = config.validate();
+        assert_eq!(issues.errors().count(), 1);
+        assert_eq!(issues.warnings().count(), 1);
+        assert!(issues.ensure_valid(true).is_err());
+    }
+}
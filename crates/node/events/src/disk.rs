@@ -0,0 +1,385 @@
+//! Monitoring of the free disk space of the node's data directories.
+
+use futures::Stream;
+use reth_metrics::{metrics::Gauge, Metrics};
+use std::{
+    fmt,
+    future::Future,
+    io,
+    path::{Path, PathBuf},
+    pin::Pin,
+    task::{ready, Context, Poll},
+    time::Duration,
+};
+use tokio::{sync::watch, task::JoinHandle, time::Interval};
+use tracing::debug;
+
+/// Interval of checking the available disk space.
+const CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Reports the disk space that is available for writing to a path.
+pub trait DiskSpaceReporter: Send + Sync {
+    /// Returns the number of bytes available on the volume the path is located on.
+    fn available_space(&self, path: &Path) -> io::Result<u64>;
+}
+
+/// A [`DiskSpaceReporter`] that looks up the volumes of the operating system.
+#[derive(Debug, Default, Clone, Copy)]
+#[non_exhaustive]
+pub struct SystemDiskSpaceReporter;
+
+impl DiskSpaceReporter for SystemDiskSpaceReporter {
+    fn available_space(&self, path: &Path) -> io::Result<u64> {
+        let path = path.canonicalize()?;
+        let disks = sysinfo::Disks::new_with_refreshed_list();
+
+        // the volume with the longest mount point that contains the path holds it
+        disks
+            .list()
+            .iter()
+            .filter(|disk| path.starts_with(disk.mount_point()))
+            .max_by_key(|disk| disk.mount_point().as_os_str().len())
+            .map(|disk| disk.available_space())
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::NotFound,
+                    format!("no volume found for {}", path.display()),
+                )
+            })
+    }
+}
+
+/// Thresholds of available disk space, in bytes, below which the node reacts. A threshold of `0`
+/// is disabled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DiskSpaceThresholds {
+    /// Below this, the node warns about the low disk space.
+    pub warning: u64,
+    /// Below this, the node pauses writing synced data to disk until space is freed up.
+    pub critical: u64,
+}
+
+impl DiskSpaceThresholds {
+    /// Returns the level of the available disk space.
+    const fn level(&self, available: u64) -> DiskSpaceLevel {
+        if available < self.critical {
+            DiskSpaceLevel::Critical
+        } else if available < self.warning {
+            DiskSpaceLevel::Low
+        } else {
+            DiskSpaceLevel::Normal
+        }
+    }
+}
+
+/// How much disk space is left, relative to the [`DiskSpaceThresholds`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DiskSpaceLevel {
+    Normal,
+    Low,
+    Critical,
+}
+
+/// A Stream of [`DiskSpaceEvent`].
+///
+/// Checks the available disk space of the watched paths once a minute. If it falls below the
+/// critical threshold on any of them, the pause signal returned by
+/// [`DiskSpaceMonitor::pause_signal`] is set, which pauses the pipeline, the pruner and the static
+/// file producer at their next commit. The signal is cleared once enough space is available again.
+///
+/// The available disk space is looked up on a blocking task, since it touches the filesystem.
+pub struct DiskSpaceMonitor<R = SystemDiskSpaceReporter> {
+    interval: Interval,
+    reporter: R,
+    /// Lookup of the available disk space of the watched paths, in their order.
+    lookup: Option<JoinHandle<Vec<io::Result<u64>>>>,
+    paths: Vec<WatchedPath>,
+    thresholds: DiskSpaceThresholds,
+    level: DiskSpaceLevel,
+    pause_tx: watch::Sender<bool>,
+    metrics: DiskSpaceMetrics,
+}
+
+impl<R> fmt::Debug for DiskSpaceMonitor<R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DiskSpaceMonitor")
+            .field("interval", &self.interval)
+            .field("paths", &self.paths)
+            .field("thresholds", &self.thresholds)
+            .field("level", &self.level)
+            .finish()
+    }
+}
+
+impl DiskSpaceMonitor {
+    /// Creates a new [`DiskSpaceMonitor`] for the given paths that uses the
+    /// [`SystemDiskSpaceReporter`].
+    pub fn new(paths: impl IntoIterator<Item = PathBuf>, thresholds: DiskSpaceThresholds) -> Self {
+        Self::with_reporter(paths, thresholds, SystemDiskSpaceReporter)
+    }
+}
+
+impl<R: DiskSpaceReporter> DiskSpaceMonitor<R> {
+    /// Creates a new [`DiskSpaceMonitor`] for the given paths that uses the given reporter.
+    pub fn with_reporter(
+        paths: impl IntoIterator<Item = PathBuf>,
+        thresholds: DiskSpaceThresholds,
+        reporter: R,
+    ) -> Self {
+        let paths = paths
+            .into_iter()
+            .map(|path| {
+                let metrics =
+                    DiskSpacePathMetrics::new_with_labels(&[("path", path.display().to_string())]);
+                WatchedPath { path, metrics }
+            })
+            .collect();
+        let (pause_tx, _) = watch::channel(false);
+        Self {
+            interval: tokio::time::interval(CHECK_INTERVAL),
+            reporter,
+            lookup: None,
+            paths,
+            thresholds,
+            level: DiskSpaceLevel::Normal,
+            pause_tx,
+            metrics: DiskSpaceMetrics::default(),
+        }
+    }
+
+    /// Returns a receiver of the pause signal, which is `true` while writes to disk should be
+    /// paused.
+    pub fn pause_signal(&self) -> watch::Receiver<bool> {
+        self.pause_tx.subscribe()
+    }
+
+    /// Updates the level with the available disk space of all watched paths, in their order, and
+    /// returns an event if it changed.
+    fn on_available_space(&mut self, available: Vec<io::Result<u64>>) -> Option<DiskSpaceEvent> {
+        // the path with the least available space determines the level
+        let mut lowest: Option<(&Path, u64)> = None;
+        for (watched, available) in self.paths.iter().zip(available) {
+            let available = match available {
+                Ok(available) => available,
+                Err(err) => {
+                    debug!(target: "reth::cli", path = %watched.path.display(), %err, "Failed to get available disk space");
+                    continue
+                }
+            };
+            watched.metrics.available_bytes.set(available as f64);
+            if lowest.is_none_or(|(_, lowest)| available < lowest) {
+                lowest = Some((&watched.path, available));
+            }
+        }
+        let (path, available) = lowest?;
+
+        let level = self.thresholds.level(available);
+        let previous = std::mem::replace(&mut self.level, level);
+        let paused = level == DiskSpaceLevel::Critical;
+        self.pause_tx.send_replace(paused);
+        self.metrics.writes_paused.set(paused as u8 as f64);
+
+        let path = path.to_path_buf();
+        match (previous, level) {
+            (DiskSpaceLevel::Critical, DiskSpaceLevel::Critical) => None,
+            (_, DiskSpaceLevel::Critical) => Some(DiskSpaceEvent::Critical { path, available }),
+            (DiskSpaceLevel::Critical, _) => Some(DiskSpaceEvent::Resumed { path, available }),
+            (DiskSpaceLevel::Normal, DiskSpaceLevel::Low) => {
+                Some(DiskSpaceEvent::Low { path, available })
+            }
+            _ => None,
+        }
+    }
+}
+
+impl<R: DiskSpaceReporter + Clone + Unpin + 'static> Stream for DiskSpaceMonitor<R> {
+    type Item = DiskSpaceEvent;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        loop {
+            if let Some(lookup) = &mut this.lookup {
+                let available = ready!(Pin::new(lookup).poll(cx));
+                this.lookup = None;
+
+                match available {
+                    Ok(available) => {
+                        if let Some(event) = this.on_available_space(available) {
+                            return Poll::Ready(Some(event));
+                        }
+                    }
+                    Err(err) => {
+                        debug!(target: "reth::cli", %err, "Failed to look up available disk space")
+                    }
+                }
+            }
+
+            ready!(this.interval.poll_tick(cx));
+
+            let reporter = this.reporter.clone();
+            let paths = this.paths.iter().map(|watched| watched.path.clone()).collect::<Vec<_>>();
+            this.lookup = Some(tokio::task::spawn_blocking(move || {
+                paths.iter().map(|path| reporter.available_space(path)).collect()
+            }));
+        }
+    }
+}
+
+/// A path watched by the [`DiskSpaceMonitor`].
+#[derive(Debug)]
+struct WatchedPath {
+    path: PathBuf,
+    metrics: DiskSpacePathMetrics,
+}
+
+/// Event that is triggered when the available disk space crosses a threshold.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DiskSpaceEvent {
+    /// The available disk space fell below the warning threshold.
+    Low {
+        /// The path with the least available space.
+        path: PathBuf,
+        /// Available bytes.
+        available: u64,
+    },
+    /// The available disk space fell below the critical threshold, writes are paused.
+    Critical {
+        /// The path with the least available space.
+        path: PathBuf,
+        /// Available bytes.
+        available: u64,
+    },
+    /// The available disk space rose above the critical threshold again, writes are resumed.
+    Resumed {
+        /// The path with the least available space.
+        path: PathBuf,
+        /// Available bytes.
+        available: u64,
+    },
+}
+
+#[derive(Metrics)]
+#[metrics(scope = "disk_space")]
+struct DiskSpaceMetrics {
+    /// Whether writes are paused because of low disk space
+    writes_paused: Gauge,
+}
+
+#[derive(Metrics)]
+#[metrics(scope = "disk_space")]
+struct DiskSpacePathMetrics {
+    /// Available disk space of the volume the path is located on
+    available_bytes: Gauge,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    };
+
+    /// Reports the same, adjustable available space for all paths.
+    #[derive(Debug, Default, Clone)]
+    struct MockDiskSpaceReporter(Arc<AtomicU64>);
+
+    impl MockDiskSpaceReporter {
+        fn set(&self, available: u64) {
+            self.0.store(available, Ordering::Relaxed);
+        }
+    }
+
+    impl DiskSpaceReporter for MockDiskSpaceReporter {
+        fn available_space(&self, _path: &Path) -> io::Result<u64> {
+            Ok(self.0.load(Ordering::Relaxed))
+        }
+    }
+
+    impl<R: DiskSpaceReporter> DiskSpaceMonitor<R> {
+        /// Looks up the available disk space in place and updates the level.
+        fn check(&mut self) -> Option<DiskSpaceEvent> {
+            let available = self
+                .paths
+                .iter()
+                .map(|watched| self.reporter.available_space(&watched.path))
+                .collect();
+            self.on_available_space(available)
+        }
+    }
+
+    #[tokio::test]
+    async fn pauses_and_resumes_writes() {
+        let reporter = MockDiskSpaceReporter::default();
+        let path = PathBuf::from("db");
+        let mut monitor = DiskSpaceMonitor::with_reporter(
+            [path.clone()],
+            DiskSpaceThresholds { warning: 100, critical: 10 },
+            reporter.clone(),
+        );
+        let pause_signal = monitor.pause_signal();
+
+        reporter.set(1000);
+        assert_eq!(monitor.check(), None);
+        assert!(!*pause_signal.borrow());
+
+        reporter.set(50);
+        assert_eq!(
+            monitor.check(),
+            Some(DiskSpaceEvent::Low { path: path.clone(), available: 50 })
+        );
+        assert_eq!(monitor.check(), None);
+        assert!(!*pause_signal.borrow());
+
+        reporter.set(5);
+        assert_eq!(
+            monitor.check(),
+            Some(DiskSpaceEvent::Critical { path: path.clone(), available: 5 })
+        );
+        assert_eq!(monitor.check(), None);
+        assert!(*pause_signal.borrow());
+
+        reporter.set(50);
+        assert_eq!(
+            monitor.check(),
+            Some(DiskSpaceEvent::Resumed { path: path.clone(), available: 50 })
+        );
+        assert!(!*pause_signal.borrow());
+
+        reporter.set(1000);
+        assert_eq!(monitor.check(), None);
+        assert!(!*pause_signal.borrow());
+    }
+
+    #[tokio::test]
+    async fn disabled_thresholds() {
+        let reporter = MockDiskSpaceReporter::default();
+        let mut monitor = DiskSpaceMonitor::with_reporter(
+            [PathBuf::from("db")],
+            DiskSpaceThresholds { warning: 0, critical: 0 },
+            reporter,
+        );
+
+        assert_eq!(monitor.check(), None);
+        assert!(!*monitor.pause_signal().borrow());
+    }
+
+    #[tokio::test]
+    async fn reports_events_from_stream() {
+        // the first check is right away
+        use futures::StreamExt;
+
+        let reporter = MockDiskSpaceReporter::default();
+        let path = PathBuf::from("db");
+        let mut monitor = DiskSpaceMonitor::with_reporter(
+            [path.clone()],
+            DiskSpaceThresholds { warning: 100, critical: 10 },
+            reporter.clone(),
+        );
+
+        reporter.set(5);
+        assert_eq!(monitor.next().await, Some(DiskSpaceEvent::Critical { path, available: 5 }));
+        assert!(*monitor.pause_signal().borrow());
+    }
+}
@@ -0,0 +1,114 @@
+//! Reminders about upcoming hardfork activations.
+
+use futures::Stream;
+use reth_ethereum_forks::Hardforks;
+use std::{
+    fmt,
+    pin::Pin,
+    task::{ready, Context, Poll},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use tokio::time::Interval;
+
+/// Interval of checking for upcoming hardforks.
+const CHECK_INTERVAL: Duration = Duration::from_secs(24 * 60 * 60);
+/// Period before a hardfork activation during which reminders are issued.
+const REMINDER_PERIOD: Duration = Duration::from_secs(14 * 24 * 60 * 60);
+
+/// A Stream of [`ForkCountdownEvent`].
+///
+/// Checks the chain spec once a day and yields an event if a timestamp based hardfork activates
+/// within the next two weeks.
+pub struct ForkCountdownEvents<C> {
+    interval: Interval,
+    chain_spec: C,
+}
+
+impl<C> fmt::Debug for ForkCountdownEvents<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ForkCountdownEvents").field("interval", &self.interval).finish()
+    }
+}
+
+impl<C> ForkCountdownEvents<C> {
+    /// Creates a new [`ForkCountdownEvents`] for the given chain spec.
+    pub fn new(chain_spec: C) -> Self {
+        Self { interval: tokio::time::interval(CHECK_INTERVAL), chain_spec }
+    }
+}
+
+impl<C: Hardforks + Unpin> Stream for ForkCountdownEvents<C> {
+    type Item = ForkCountdownEvent;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        loop {
+            ready!(this.interval.poll_tick(cx));
+
+            let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+            if let Some(event) = upcoming_fork(&this.chain_spec, now) {
+                return Poll::Ready(Some(event));
+            }
+        }
+    }
+}
+
+/// Returns the first timestamp based hardfork that activates within [`REMINDER_PERIOD`] after the
+/// unix timestamp `now`.
+fn upcoming_fork(chain_spec: &impl Hardforks, now: u64) -> Option<ForkCountdownEvent> {
+    chain_spec.forks_iter().find_map(|(fork, condition)| {
+        let timestamp = condition.as_timestamp().filter(|timestamp| *timestamp > now)?;
+        let remaining = Duration::from_secs(timestamp - now);
+        if remaining > REMINDER_PERIOD {
+            return None;
+        }
+        Some(ForkCountdownEvent { fork: fork.name(), timestamp, remaining })
+    })
+}
+
+/// Event that is triggered when a hardfork activation is approaching.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ForkCountdownEvent {
+    /// Name of the hardfork.
+    pub fork: &'static str,
+    /// Activation timestamp of the hardfork.
+    pub timestamp: u64,
+    /// Time left until the activation.
+    pub remaining: Duration,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_genesis::Genesis;
+    use reth_chainspec::{Chain, ChainSpec, EthereumHardfork, ForkCondition};
+
+    #[test]
+    fn upcoming_fork_within_reminder_period() {
+        let activation = 100 * 24 * 60 * 60;
+        let chain_spec = ChainSpec::builder()
+            .chain(Chain::mainnet())
+            .genesis(Genesis::default())
+            .cancun_activated()
+            .with_fork(EthereumHardfork::Prague, ForkCondition::Timestamp(activation))
+            .build();
+
+        // too far away
+        let now = activation - REMINDER_PERIOD.as_secs() - 1;
+        assert_eq!(upcoming_fork(&chain_spec, now), None);
+
+        let now = activation - 60;
+        assert_eq!(
+            upcoming_fork(&chain_spec, now),
+            Some(ForkCountdownEvent {
+                fork: "Prague",
+                timestamp: activation,
+                remaining: Duration::from_secs(60),
+            })
+        );
+
+        // already active
+        assert_eq!(upcoming_fork(&chain_spec, activation), None);
+    }
+}
@@ -9,4 +9,7 @@
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
 
 pub mod cl;
+pub mod disk;
+pub mod fork;
 pub mod node;
+mod progress;
@@ -1,17 +1,22 @@
 //! Support for handling events emitted by node components.
 
-use crate::cl::ConsensusLayerHealthEvent;
+use crate::{
+    cl::ConsensusLayerHealthEvent, disk::DiskSpaceEvent, fork::ForkCountdownEvent,
+    progress::SyncProgress,
+};
 use alloy_consensus::{constants::GWEI_TO_WEI, BlockHeader};
 use alloy_primitives::{BlockNumber, B256};
 use alloy_rpc_types_engine::ForkchoiceState;
 use futures::Stream;
 use reth_beacon_consensus::{BeaconConsensusEngineEvent, ConsensusEngineLiveSyncProgress};
+use reth_db_api::database_metrics::DatabaseMetadata;
 use reth_engine_primitives::ForkchoiceStatus;
 use reth_network_api::PeersInfo;
 use reth_primitives_traits::{format_gas, format_gas_throughput, BlockBody, NodePrimitives};
 use reth_prune_types::PrunerEvent;
 use reth_stages::{EntitiesCheckpoint, ExecOutput, PipelineEvent, StageCheckpoint, StageId};
 use reth_static_file_types::StaticFileProducerEvent;
+use reth_storage_api::BlockNumReader;
 use std::{
     fmt::{Display, Formatter},
     future::Future,
@@ -20,7 +25,7 @@ use std::{
     time::{Duration, Instant, SystemTime, UNIX_EPOCH},
 };
 use tokio::time::Interval;
-use tracing::{debug, info, warn};
+use tracing::{debug, error, info, warn};
 
 /// Interval of reporting node state.
 const INFO_MESSAGE_INTERVAL: Duration = Duration::from_secs(25);
@@ -32,8 +37,14 @@ const INFO_MESSAGE_INTERVAL: Duration = Duration::from_secs(25);
 struct NodeState {
     /// Information about connected peers.
     peers_info: Option<Box<dyn PeersInfo>>,
+    /// The database, to report the size of its freelist.
+    database: Option<Box<dyn DatabaseMetadata + Send + Sync>>,
+    /// Looks up the number of the forkchoice head, to estimate when the sync reaches it.
+    block_numbers: Option<Box<dyn BlockNumReader>>,
     /// The stage currently being executed.
     current_stage: Option<CurrentStage>,
+    /// Throughput and ETA of the stage currently being executed.
+    sync_progress: SyncProgress,
     /// The latest block reached by either pipeline or consensus engine.
     latest_block: Option<BlockNumber>,
     /// The time of the latest block seen by the pipeline
@@ -47,13 +58,18 @@ struct NodeState {
 }
 
 impl NodeState {
-    const fn new(
+    fn new(
         peers_info: Option<Box<dyn PeersInfo>>,
+        database: Option<Box<dyn DatabaseMetadata + Send + Sync>>,
+        block_numbers: Option<Box<dyn BlockNumReader>>,
         latest_block: Option<BlockNumber>,
     ) -> Self {
         Self {
             peers_info,
+            database,
+            block_numbers,
             current_stage: None,
+            sync_progress: SyncProgress::default(),
             latest_block,
             latest_block_time: None,
             head_block_hash: None,
@@ -66,6 +82,25 @@ impl NodeState {
         self.peers_info.as_ref().map(|info| info.num_connected_peers()).unwrap_or_default()
     }
 
+    fn freelist_size(&self) -> Option<usize> {
+        self.database.as_ref().and_then(|database| database.metadata().freelist_size())
+    }
+
+    /// Updates the sync tip with the number of the forkchoice head.
+    ///
+    /// The number is only known once the pipeline downloaded the header of the head.
+    fn update_sync_tip(&mut self) {
+        let Some((block_numbers, head)) = self.block_numbers.as_ref().zip(self.head_block_hash)
+        else {
+            return
+        };
+        match block_numbers.block_number(head) {
+            Ok(Some(number)) => self.sync_progress.set_tip(number),
+            Ok(None) => {}
+            Err(err) => debug!(?head, %err, "Failed to look up the forkchoice head"),
+        }
+    }
+
     fn build_current_stage(
         &self,
         stage_id: StageId,
@@ -104,6 +139,7 @@ impl NodeState {
             PipelineEvent::Run { pipeline_stages_progress, stage_id, checkpoint, target } => {
                 let checkpoint = checkpoint.unwrap_or_default();
                 let current_stage = self.build_current_stage(stage_id, checkpoint, target);
+                self.sync_progress.record(stage_id, checkpoint, target, Instant::now());
 
                 if let Some(stage_eta) = current_stage.eta.fmt_for_stage(stage_id) {
                     info!(
@@ -133,6 +169,9 @@ impl NodeState {
             } => {
                 if stage_id.is_finish() {
                     self.latest_block = Some(checkpoint.block_number);
+                    self.sync_progress.clear();
+                } else {
+                    self.sync_progress.record(stage_id, checkpoint, None, Instant::now());
                 }
 
                 if let Some(current_stage) = self.current_stage.as_mut() {
@@ -197,6 +236,7 @@ impl NodeState {
                 }
             }
             PipelineEvent::Unwind { stage_id, input } => {
+                self.sync_progress.clear();
                 let current_stage = CurrentStage {
                     stage_id,
                     eta: Eta::default(),
@@ -277,6 +317,42 @@ impl NodeState {
         }
     }
 
+    fn handle_fork_countdown_event(&self, event: ForkCountdownEvent) {
+        let ForkCountdownEvent { fork, timestamp, remaining } = event;
+        info!(
+            fork,
+            timestamp,
+            remaining = %humantime::format_duration(remaining),
+            "Upcoming hardfork activation. Please make sure your consensus client supports it!"
+        );
+    }
+
+    fn handle_disk_space_event(&self, event: DiskSpaceEvent) {
+        match event {
+            DiskSpaceEvent::Low { path, available } => {
+                warn!(
+                    path = %path.display(),
+                    available_bytes = available,
+                    "Low disk space. Writes will be paused when it falls below the critical threshold"
+                );
+            }
+            DiskSpaceEvent::Critical { path, available } => {
+                error!(
+                    path = %path.display(),
+                    available_bytes = available,
+                    "Critically low disk space. Paused syncing, pruning and writing static files until space is freed up"
+                );
+            }
+            DiskSpaceEvent::Resumed { path, available } => {
+                info!(
+                    path = %path.display(),
+                    available_bytes = available,
+                    "Disk space freed up. Resumed syncing, pruning and writing static files"
+                );
+            }
+        }
+    }
+
     fn handle_consensus_layer_health_event(&self, event: ConsensusLayerHealthEvent) {
         // If pipeline is running, it's fine to not receive any messages from the CL.
         // So we need to report about CL health only when pipeline is idle.
@@ -361,6 +437,10 @@ pub enum NodeEvent<N: NodePrimitives> {
     ConsensusEngine(BeaconConsensusEngineEvent<N>),
     /// A Consensus Layer health event.
     ConsensusLayerHealth(ConsensusLayerHealthEvent),
+    /// An upcoming hardfork event.
+    ForkCountdown(ForkCountdownEvent),
+    /// A disk space event.
+    DiskSpace(DiskSpaceEvent),
     /// A pruner event
     Pruner(PrunerEvent),
     /// A `static_file_producer` event
@@ -372,14 +452,20 @@ pub enum NodeEvent<N: NodePrimitives> {
 
 /// Displays relevant information to the user from components of the node, and periodically
 /// displays the high-level status of the node.
+///
+/// While the pipeline is syncing, the status contains the progress, throughput and ETA of the
+/// running stage, which are also exposed as gauges. The ETA is estimated to the head of the latest
+/// forkchoice update once its number can be looked up with `block_numbers`.
 pub async fn handle_events<E, N: NodePrimitives>(
     peers_info: Option<Box<dyn PeersInfo>>,
+    database: Option<Box<dyn DatabaseMetadata + Send + Sync>>,
+    block_numbers: Option<Box<dyn BlockNumReader>>,
     latest_block_number: Option<BlockNumber>,
     events: E,
 ) where
     E: Stream<Item = NodeEvent<N>> + Unpin,
 {
-    let state = NodeState::new(peers_info, latest_block_number);
+    let state = NodeState::new(peers_info, database, block_numbers, latest_block_number);
 
     let start = tokio::time::Instant::now() + Duration::from_secs(3);
     let mut info_interval = tokio::time::interval_at(start, INFO_MESSAGE_INTERVAL);
@@ -409,59 +495,31 @@ where
         let mut this = self.project();
 
         while this.info_interval.poll_tick(cx).is_ready() {
-            if let Some(CurrentStage { stage_id, eta, checkpoint, entities_checkpoint, target }) =
-                &this.state.current_stage
-            {
-                let stage_progress =
-                    entities_checkpoint.and_then(|entities| entities.fmt_percentage());
-                let stage_eta = eta.fmt_for_stage(*stage_id);
-
-                match (stage_progress, stage_eta) {
-                    (Some(stage_progress), Some(stage_eta)) => {
-                        info!(
-                            target: "reth::cli",
-                            connected_peers = this.state.num_connected_peers(),
-                            stage = %stage_id,
-                            checkpoint = checkpoint.block_number,
-                            target = %OptionalField(*target),
-                            %stage_progress,
-                            %stage_eta,
-                            "Status"
-                        )
-                    }
-                    (Some(stage_progress), None) => {
-                        info!(
-                            target: "reth::cli",
-                            connected_peers = this.state.num_connected_peers(),
-                            stage = %stage_id,
-                            checkpoint = checkpoint.block_number,
-                            target = %OptionalField(*target),
-                            %stage_progress,
-                            "Status"
-                        )
-                    }
-                    (None, Some(stage_eta)) => {
-                        info!(
-                            target: "reth::cli",
-                            connected_peers = this.state.num_connected_peers(),
-                            stage = %stage_id,
-                            checkpoint = checkpoint.block_number,
-                            target = %OptionalField(*target),
-                            %stage_eta,
-                            "Status"
-                        )
-                    }
-                    (None, None) => {
-                        info!(
-                            target: "reth::cli",
-                            connected_peers = this.state.num_connected_peers(),
-                            stage = %stage_id,
-                            checkpoint = checkpoint.block_number,
-                            target = %OptionalField(*target),
-                            "Status"
-                        )
-                    }
-                }
+            if this.state.current_stage.is_some() {
+                this.state.update_sync_tip();
+            }
+            let status = this
+                .state
+                .current_stage
+                .as_ref()
+                .and_then(|_| this.state.sync_progress.status(Instant::now()));
+            if let Some(status) = status {
+                let blocks_per_second = status.blocks_per_second.map(|rate| format!("{rate:.1}"));
+                let entities_per_second =
+                    status.entities_per_second.map(|rate| format!("{rate:.1}"));
+                info!(
+                    target: "reth::cli",
+                    connected_peers = this.state.num_connected_peers(),
+                    stage = %status.stage,
+                    checkpoint = status.checkpoint,
+                    target = %OptionalField(status.target),
+                    stage_progress = %OptionalField(status.fmt_progress()),
+                    stage_eta = %OptionalField(status.fmt_eta()),
+                    blocks_per_second = %OptionalField(blocks_per_second),
+                    entities_per_second = %OptionalField(entities_per_second),
+                    freelist = %OptionalField(this.state.freelist_size()),
+                    "Status"
+                );
             } else if let Some(latest_block) = this.state.latest_block {
                 let now =
                     SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
@@ -495,6 +553,12 @@ where
                 NodeEvent::ConsensusLayerHealth(event) => {
                     this.state.handle_consensus_layer_health_event(event)
                 }
+                NodeEvent::ForkCountdown(event) => {
+                    this.state.handle_fork_countdown_event(event);
+                }
+                NodeEvent::DiskSpace(event) => {
+                    this.state.handle_disk_space_event(event);
+                }
                 NodeEvent::Pruner(event) => {
                     this.state.handle_pruner_event(event);
                 }
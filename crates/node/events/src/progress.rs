@@ -0,0 +1,376 @@
+//! Sync progress of the running stage, reported in the periodic status line.
+
+use alloy_primitives::BlockNumber;
+use reth_metrics::{metrics::Gauge, Metrics};
+use reth_stages::{EntitiesCheckpoint, StageCheckpoint, StageId};
+use std::{
+    collections::VecDeque,
+    time::{Duration, Instant},
+};
+
+/// Time window over which the throughput of a stage is averaged.
+const THROUGHPUT_WINDOW: Duration = Duration::from_secs(5 * 60);
+
+/// A checkpoint reported by the running stage.
+#[derive(Debug, Clone, Copy)]
+struct Sample {
+    at: Instant,
+    block_number: BlockNumber,
+    entities: Option<EntitiesCheckpoint>,
+}
+
+/// Tracks the rolling throughput of the running stage from the checkpoints reported by the
+/// pipeline, and estimates when the stage reaches the target of the sync.
+#[derive(Debug, Default)]
+pub(crate) struct SyncProgress {
+    /// The stage the samples belong to.
+    stage: Option<StageId>,
+    /// The block the pipeline syncs to.
+    target: Option<BlockNumber>,
+    /// The head block of the latest forkchoice update, once its number is known.
+    tip: Option<BlockNumber>,
+    /// Checkpoints of the stage within the [`THROUGHPUT_WINDOW`], oldest first.
+    samples: VecDeque<Sample>,
+    metrics: SyncProgressMetrics,
+}
+
+impl SyncProgress {
+    /// Records a checkpoint of the given stage reported at `now`.
+    ///
+    /// The samples of the previously running stage are discarded. If `target` is `None`, the last
+    /// known target is kept, because all stages of a pipeline run sync to the same target.
+    pub(crate) fn record(
+        &mut self,
+        stage: StageId,
+        checkpoint: StageCheckpoint,
+        target: Option<BlockNumber>,
+        now: Instant,
+    ) {
+        if self.stage != Some(stage) {
+            self.stage = Some(stage);
+            self.samples.clear();
+        }
+        if target.is_some() {
+            self.target = target;
+        }
+
+        // A lower checkpoint means that the stage was unwound, so the throughput starts over.
+        if self.samples.back().is_some_and(|last| checkpoint.block_number < last.block_number) {
+            self.samples.clear();
+        }
+        self.samples.push_back(Sample {
+            at: now,
+            block_number: checkpoint.block_number,
+            entities: checkpoint.entities(),
+        });
+
+        // Keep the oldest sample that still covers the whole window.
+        while self.samples.get(1).is_some_and(|s| now.duration_since(s.at) >= THROUGHPUT_WINDOW) {
+            self.samples.pop_front();
+        }
+    }
+
+    /// Sets the number of the head block of the latest forkchoice update.
+    ///
+    /// The pipeline only syncs to the tip that was known when it started, but the sync is not done
+    /// before it reached the head of the consensus layer, so the ETA is estimated to the higher of
+    /// both.
+    pub(crate) fn set_tip(&mut self, tip: BlockNumber) {
+        self.tip = Some(tip);
+    }
+
+    /// Forgets the running stage, e.g. once the pipeline is finished.
+    pub(crate) fn clear(&mut self) {
+        self.stage = None;
+        self.samples.clear();
+        self.update_metrics(None);
+    }
+
+    /// Returns the progress of the running stage at `now`, and updates the gauges with it.
+    pub(crate) fn status(&self, now: Instant) -> Option<SyncStatus> {
+        let status = self.calculate(now);
+        self.update_metrics(status.as_ref());
+        status
+    }
+
+    fn calculate(&self, now: Instant) -> Option<SyncStatus> {
+        let stage = self.stage?;
+        let first = self.samples.front()?;
+        let last = self.samples.back()?;
+
+        let elapsed = last.at.duration_since(first.at).as_secs_f64();
+        let per_second = |processed: u64| (elapsed > 0.0).then(|| processed as f64 / elapsed);
+        let blocks_per_second = per_second(last.block_number.saturating_sub(first.block_number));
+        let entities_per_second = first
+            .entities
+            .zip(last.entities)
+            .and_then(|(first, last)| per_second(last.processed.checked_sub(first.processed)?));
+
+        // Entities reflect the work of a stage more accurately than blocks, because the work per
+        // block differs a lot over the history of the chain.
+        let target = self.target.max(self.tip);
+        let entities = last.entities.filter(|entities| entities.total > 0);
+        let (progress, remaining) = match (entities, target) {
+            (Some(entities), _) => (
+                Some(100.0 * entities.processed as f64 / entities.total as f64),
+                entities_per_second
+                    .map(|rate| (entities.total.saturating_sub(entities.processed), rate)),
+            ),
+            (None, Some(target)) if target > 0 => (
+                Some(100.0 * last.block_number.min(target) as f64 / target as f64),
+                blocks_per_second.map(|rate| (target.saturating_sub(last.block_number), rate)),
+            ),
+            _ => (None, None),
+        };
+        let eta = remaining
+            .filter(|(_, rate)| *rate > 0.0)
+            .and_then(|(remaining, rate)| Duration::try_from_secs_f64(remaining as f64 / rate).ok())
+            .map(|eta| eta.saturating_sub(now.saturating_duration_since(last.at)));
+
+        Some(SyncStatus {
+            stage,
+            checkpoint: last.block_number,
+            target,
+            progress,
+            eta,
+            blocks_per_second,
+            entities_per_second,
+        })
+    }
+
+    fn update_metrics(&self, status: Option<&SyncStatus>) {
+        self.metrics.stage_progress.set(status.and_then(|s| s.progress).unwrap_or_default());
+        self.metrics
+            .stage_eta_seconds
+            .set(status.and_then(|s| s.eta).map(|eta| eta.as_secs_f64()).unwrap_or_default());
+        self.metrics
+            .blocks_per_second
+            .set(status.and_then(|s| s.blocks_per_second).unwrap_or_default());
+        self.metrics
+            .entities_per_second
+            .set(status.and_then(|s| s.entities_per_second).unwrap_or_default());
+    }
+}
+
+/// The progress of the running stage, see [`SyncProgress::status`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct SyncStatus {
+    pub(crate) stage: StageId,
+    pub(crate) checkpoint: BlockNumber,
+    /// The block the pipeline syncs to, if known.
+    pub(crate) target: Option<BlockNumber>,
+    /// Progress of the stage in percent, if the stage reports entities or the target is known.
+    pub(crate) progress: Option<f64>,
+    /// Estimated time until the stage reaches the target.
+    pub(crate) eta: Option<Duration>,
+    pub(crate) blocks_per_second: Option<f64>,
+    pub(crate) entities_per_second: Option<f64>,
+}
+
+impl SyncStatus {
+    /// Formats the progress with two decimals, rounding down so that 99.999% is not shown as
+    /// 100%.
+    pub(crate) fn fmt_progress(&self) -> Option<String> {
+        self.progress.map(|progress| format!("{:.2}%", (progress * 100.0).floor() / 100.0))
+    }
+
+    /// Formats the ETA without the sub-second part.
+    pub(crate) fn fmt_eta(&self) -> Option<String> {
+        self.eta
+            .map(|eta| humantime::format_duration(Duration::from_secs(eta.as_secs())).to_string())
+    }
+}
+
+#[derive(Metrics)]
+#[metrics(scope = "sync.progress")]
+struct SyncProgressMetrics {
+    /// Progress of the running stage in percent
+    stage_progress: Gauge,
+    /// Estimated number of seconds until the running stage reaches the sync target
+    stage_eta_seconds: Gauge,
+    /// Number of blocks per second processed by the running stage
+    blocks_per_second: Gauge,
+    /// Number of entities per second processed by the running stage
+    entities_per_second: Gauge,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entities_checkpoint(
+        block_number: BlockNumber,
+        processed: u64,
+        total: u64,
+    ) -> StageCheckpoint {
+        StageCheckpoint::new(block_number)
+            .with_entities_stage_checkpoint(EntitiesCheckpoint { processed, total })
+    }
+
+    #[test]
+    fn eta_from_entities() {
+        let start = Instant::now();
+        let mut progress = SyncProgress::default();
+        assert_eq!(progress.status(start), None);
+
+        progress.record(
+            StageId::SenderRecovery,
+            entities_checkpoint(0, 0, 1_000),
+            Some(100),
+            start,
+        );
+        let status = progress.status(start).unwrap();
+        assert_eq!(status.progress, Some(0.0));
+        assert_eq!(status.eta, None);
+        assert_eq!(status.entities_per_second, None);
+
+        let now = start + Duration::from_secs(10);
+        progress.record(StageId::SenderRecovery, entities_checkpoint(10, 100, 1_000), None, now);
+        let status = progress.status(now).unwrap();
+        assert_eq!(status.target, Some(100));
+        assert_eq!(status.progress, Some(10.0));
+        assert_eq!(status.entities_per_second, Some(10.0));
+        assert_eq!(status.blocks_per_second, Some(1.0));
+        assert_eq!(status.eta, Some(Duration::from_secs(90)));
+        assert_eq!(status.fmt_progress().as_deref(), Some("10.00%"));
+        assert_eq!(status.fmt_eta().as_deref(), Some("1m 30s"));
+
+        // the ETA counts down between checkpoints
+        let status = progress.status(now + Duration::from_secs(30)).unwrap();
+        assert_eq!(status.eta, Some(Duration::from_secs(60)));
+    }
+
+    #[test]
+    fn eta_from_blocks_to_target() {
+        let start = Instant::now();
+        let mut progress = SyncProgress::default();
+
+        progress.record(StageId::Execution, StageCheckpoint::new(100), Some(1_000), start);
+        let now = start + Duration::from_secs(20);
+        progress.record(StageId::Execution, StageCheckpoint::new(200), Some(1_000), now);
+
+        let status = progress.status(now).unwrap();
+        assert_eq!(status.stage, StageId::Execution);
+        assert_eq!(status.checkpoint, 200);
+        assert_eq!(status.progress, Some(20.0));
+        assert_eq!(status.blocks_per_second, Some(5.0));
+        assert_eq!(status.entities_per_second, None);
+        assert_eq!(status.eta, Some(Duration::from_secs(160)));
+    }
+
+    #[test]
+    fn eta_to_forkchoice_tip() {
+        let start = Instant::now();
+        let mut progress = SyncProgress::default();
+
+        // the tip is unknown until the forkchoice head was downloaded
+        progress.record(StageId::Execution, StageCheckpoint::new(100), None, start);
+        let now = start + Duration::from_secs(20);
+        progress.record(StageId::Execution, StageCheckpoint::new(200), None, now);
+        assert_eq!(progress.status(now).unwrap().eta, None);
+
+        progress.set_tip(1_000);
+        let status = progress.status(now).unwrap();
+        assert_eq!(status.target, Some(1_000));
+        assert_eq!(status.progress, Some(20.0));
+        assert_eq!(status.eta, Some(Duration::from_secs(160)));
+
+        // the tip moved past the target of the pipeline
+        progress.record(StageId::Execution, StageCheckpoint::new(300), Some(500), now);
+        progress.set_tip(1_100);
+        let status = progress.status(now).unwrap();
+        assert_eq!(status.target, Some(1_100));
+        assert_eq!(status.eta, Some(Duration::from_secs(80)));
+
+        // the pipeline target is used while it is ahead of the tip
+        progress.record(StageId::Execution, StageCheckpoint::new(300), Some(2_000), now);
+        assert_eq!(progress.status(now).unwrap().target, Some(2_000));
+    }
+
+    #[test]
+    fn unknown_target() {
+        let start = Instant::now();
+        let mut progress = SyncProgress::default();
+
+        progress.record(StageId::Execution, StageCheckpoint::new(100), None, start);
+        let now = start + Duration::from_secs(10);
+        progress.record(StageId::Execution, StageCheckpoint::new(150), None, now);
+
+        let status = progress.status(now).unwrap();
+        assert_eq!(status.target, None);
+        assert_eq!(status.blocks_per_second, Some(5.0));
+        assert_eq!(status.progress, None);
+        assert_eq!(status.eta, None);
+        assert_eq!(status.fmt_progress(), None);
+        assert_eq!(status.fmt_eta(), None);
+    }
+
+    #[test]
+    fn stalled_stage() {
+        let start = Instant::now();
+        let mut progress = SyncProgress::default();
+
+        progress.record(StageId::Execution, StageCheckpoint::new(100), Some(1_000), start);
+        let now = start + Duration::from_secs(10);
+        progress.record(StageId::Execution, StageCheckpoint::new(100), Some(1_000), now);
+
+        let status = progress.status(now).unwrap();
+        assert_eq!(status.blocks_per_second, Some(0.0));
+        assert_eq!(status.progress, Some(10.0));
+        assert_eq!(status.eta, None);
+    }
+
+    #[test]
+    fn resets_on_stage_change_and_unwind() {
+        let start = Instant::now();
+        let mut progress = SyncProgress::default();
+
+        progress.record(StageId::Execution, StageCheckpoint::new(0), Some(1_000), start);
+        progress.record(
+            StageId::Execution,
+            StageCheckpoint::new(500),
+            None,
+            start + Duration::from_secs(10),
+        );
+
+        // the next stage has no throughput yet, but keeps the target
+        let now = start + Duration::from_secs(11);
+        progress.record(StageId::MerkleExecute, StageCheckpoint::new(0), None, now);
+        let status = progress.status(now).unwrap();
+        assert_eq!(status.stage, StageId::MerkleExecute);
+        assert_eq!(status.target, Some(1_000));
+        assert_eq!(status.blocks_per_second, None);
+
+        // unwinding drops the samples from before the unwind
+        progress.record(
+            StageId::MerkleExecute,
+            StageCheckpoint::new(400),
+            None,
+            start + Duration::from_secs(20),
+        );
+        let now = start + Duration::from_secs(30);
+        progress.record(StageId::MerkleExecute, StageCheckpoint::new(300), None, now);
+        assert_eq!(progress.status(now).unwrap().blocks_per_second, None);
+
+        progress.clear();
+        assert_eq!(progress.status(now), None);
+    }
+
+    #[test]
+    fn throughput_over_window() {
+        let start = Instant::now();
+        let mut progress = SyncProgress::default();
+
+        // 10 blocks per second for 10 minutes, then 1 block per second for 10 minutes
+        let mut block_number = 0;
+        for minute in 0..=20 {
+            let now = start + Duration::from_secs(minute * 60);
+            progress.record(StageId::Execution, StageCheckpoint::new(block_number), None, now);
+            block_number += if minute < 10 { 600 } else { 60 };
+        }
+
+        let now = start + Duration::from_secs(20 * 60);
+        assert_eq!(progress.status(now).unwrap().blocks_per_second, Some(1.0));
+        assert_eq!(progress.samples.len(), 6);
+    }
+}
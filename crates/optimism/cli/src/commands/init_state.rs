@@ -2,8 +2,11 @@
 
 use clap::Parser;
 use reth_cli::chainspec::ChainSpecParser;
-use reth_cli_commands::common::{AccessRights, CliNodeTypes, Environment};
-use reth_db_common::init::init_from_state_dump;
+use reth_cli_commands::{
+    common::{AccessRights, CliNodeTypes, Environment},
+    init_state::interrupt_on_ctrl_c,
+};
+use reth_db_common::init::{init_from_state_dump, StateImportConfig};
 use reth_optimism_chainspec::OpChainSpec;
 use reth_optimism_primitives::{
     bedrock::{BEDROCK_HEADER, BEDROCK_HEADER_HASH, BEDROCK_HEADER_TTD},
@@ -47,10 +50,10 @@ impl<C: ChainSpecParser<ChainSpec = OpChainSpec>> InitStateCommandOp<C> {
             self.init_state.env.init::<N>(AccessRights::RW)?;
 
         let static_file_provider = provider_factory.static_file_provider();
-        let provider_rw = provider_factory.database_provider_rw()?;
 
         // OP-Mainnet may want to bootstrap a chain without OVM historical data
         if provider_factory.chain_spec().is_optimism_mainnet() && self.without_ovm {
+            let provider_rw = provider_factory.database_provider_rw()?;
             let last_block_number = provider_rw.last_block_number()?;
 
             if last_block_number == 0 {
@@ -63,9 +66,9 @@ impl<C: ChainSpecParser<ChainSpec = OpChainSpec>> InitStateCommandOp<C> {
                 // SAFETY: it's safe to commit static files, since in the event of a crash, they
                 // will be unwinded according to database checkpoints.
                 //
-                // Necessary to commit, so the BEDROCK_HEADER is accessible to provider_rw and
-                // init_state_dump
+                // Necessary to commit, so the BEDROCK_HEADER is accessible to init_state_dump
                 static_file_provider.commit()?;
+                provider_rw.commit()?;
             } else if last_block_number > 0 && last_block_number < BEDROCK_HEADER.number {
                 return Err(eyre::eyre!(
                     "Data directory should be empty when calling init-state with --without-ovm."
@@ -76,9 +79,11 @@ impl<C: ChainSpecParser<ChainSpec = OpChainSpec>> InitStateCommandOp<C> {
         info!(target: "reth::cli", "Initiating state dump");
 
         let reader = BufReader::new(reth_fs_util::open(self.init_state.state)?);
-        let hash = init_from_state_dump(reader, &provider_rw, config.stages.etl)?;
-
-        provider_rw.commit()?;
+        let hash = init_from_state_dump(
+            reader,
+            &provider_factory,
+            StateImportConfig::new(config.stages.etl).with_interrupt(interrupt_on_ctrl_c()),
+        )?;
 
         info!(target: "reth::cli", hash = ?hash, "Genesis block written");
         Ok(())
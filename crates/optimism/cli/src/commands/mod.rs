@@ -4,9 +4,9 @@ use import::ImportOpCommand;
 use import_receipts::ImportReceiptsOpCommand;
 use reth_cli::chainspec::ChainSpecParser;
 use reth_cli_commands::{
-    config_cmd, db, dump_genesis, init_cmd,
+    config_cmd, datadir, db, dump_genesis, init_cmd,
     node::{self, NoArgs},
-    p2p, prune, recover, stage,
+    p2p, prune, recover, snapshot, stage, stats,
 };
 use std::fmt;
 
@@ -56,6 +56,15 @@ pub enum Commands<Spec: ChainSpecParser = OpChainSpecParser, Ext: clap::Args + f
     /// Prune according to the configuration without any limits
     #[command(name = "prune")]
     Prune(prune::PruneCommand<Spec>),
+    /// Data directory layout utilities
+    #[command(name = "datadir")]
+    Datadir(datadir::Command<Spec>),
+    /// Static file inspection and download utilities
+    #[command(name = "snapshot")]
+    Snapshot(snapshot::Command<Spec>),
+    /// Block range analytics computed from the database
+    #[command(name = "stats")]
+    Stats(stats::Command<Spec>),
     /// Generate Test Vectors
     #[cfg(feature = "dev")]
     #[command(name = "test-vectors")]
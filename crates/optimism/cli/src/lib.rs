@@ -153,7 +153,7 @@ where
                 runner.run_blocking_until_ctrl_c(command.execute::<OpNode>())
             }
             Commands::InitState(command) => {
-                runner.run_blocking_until_ctrl_c(command.execute::<OpNode>())
+                runner.run_blocking_until_completion(command.execute::<OpNode>())
             }
             Commands::ImportOp(command) => {
                 runner.run_blocking_until_ctrl_c(command.execute::<OpNode>())
@@ -175,6 +175,11 @@ where
                 runner.run_command_until_exit(|ctx| command.execute::<OpNode>(ctx))
             }
             Commands::Prune(command) => runner.run_until_ctrl_c(command.execute::<OpNode>()),
+            Commands::Datadir(command) => runner.run_until_ctrl_c(command.execute()),
+            Commands::Snapshot(command) => {
+                runner.run_command_until_exit(|ctx| command.execute::<OpNode>(ctx))
+            }
+            Commands::Stats(command) => runner.run_until_ctrl_c(command.execute::<OpNode>()),
             #[cfg(feature = "dev")]
             Commands::TestVectors(command) => runner.run_until_ctrl_c(command.execute()),
         }
@@ -19,8 +19,9 @@ use reth_consensus::{
 use reth_consensus_common::validation::{
     validate_against_parent_4844, validate_against_parent_eip1559_base_fee,
     validate_against_parent_hash_number, validate_against_parent_timestamp,
-    validate_body_against_header, validate_cancun_gas, validate_header_base_fee,
-    validate_header_extra_data, validate_header_gas, validate_shanghai_withdrawals,
+    validate_body_against_header, validate_body_ommers, validate_cancun_gas,
+    validate_header_base_fee, validate_header_extra_data, validate_header_gas,
+    validate_shanghai_withdrawals,
 };
 use reth_optimism_chainspec::OpChainSpec;
 use reth_optimism_forks::OpHardforks;
@@ -66,7 +67,7 @@ impl Consensus<Header, OpBlockBody> for OpBeaconConsensus {
         body: &OpBlockBody,
         header: &SealedHeader,
     ) -> Result<(), ConsensusError> {
-        validate_body_against_header(body, header.header())
+        validate_body_against_header(body, header.header(), &self.chain_spec)
     }
 
     fn validate_block_pre_execution(
@@ -74,12 +75,7 @@ impl Consensus<Header, OpBlockBody> for OpBeaconConsensus {
         block: &SealedBlockFor<OpBlock>,
     ) -> Result<(), ConsensusError> {
         // Check ommers hash
-        let ommers_hash = reth_primitives::proofs::calculate_ommers_root(&block.body.ommers);
-        if block.header.ommers_hash != ommers_hash {
-            return Err(ConsensusError::BodyOmmersHashDiff(
-                GotExpected { got: ommers_hash, expected: block.header.ommers_hash }.into(),
-            ))
-        }
+        validate_body_ommers(&block.body, block.header(), &self.chain_spec)?;
 
         // Check transaction root
         if let Err(error) = block.ensure_transaction_root_valid() {
@@ -2,9 +2,10 @@ use crate::proof::calculate_receipt_root_optimism;
 use alloy_consensus::TxReceipt;
 use alloy_primitives::{Bloom, B256};
 use reth_chainspec::{ChainSpec, EthereumHardforks};
-use reth_consensus::ConsensusError;
+use reth_consensus::{ConsensusError, ReceiptsDiagnostics};
 use reth_optimism_primitives::{OpBlock, OpReceipt};
 use reth_primitives::{gas_spent_by_transactions, BlockWithSenders, GotExpected};
+use reth_primitives_traits::SignedTransaction;
 
 /// Validate a block with regard to execution results:
 ///
@@ -26,6 +27,7 @@ pub fn validate_block_post_execution(
             receipts,
             chain_spec,
             block.header.timestamp,
+            block.body.transactions.iter().map(|tx| *tx.tx_hash()),
         ) {
             tracing::debug!(%error, ?receipts, "receipts verification failed");
             return Err(error)
@@ -46,12 +48,16 @@ pub fn validate_block_post_execution(
 }
 
 /// Verify the calculated receipts root against the expected receipts root.
+///
+/// On mismatch, the error carries [`ReceiptsDiagnostics`] of the receipts, identified by the given
+/// transaction hashes.
 fn verify_receipts(
     expected_receipts_root: B256,
     expected_logs_bloom: Bloom,
     receipts: &[OpReceipt],
     chain_spec: &ChainSpec,
     timestamp: u64,
+    tx_hashes: impl IntoIterator<Item = B256>,
 ) -> Result<(), ConsensusError> {
     // Calculate receipts root.
     let receipts_with_bloom = receipts.iter().cloned().map(Into::into).collect::<Vec<_>>();
@@ -66,9 +72,15 @@ fn verify_receipts(
         logs_bloom,
         expected_receipts_root,
         expected_logs_bloom,
-    )?;
-
-    Ok(())
+    )
+    .map_err(|mut error| {
+        if let ConsensusError::BodyReceiptRootDiff { diagnostics, .. } |
+        ConsensusError::BodyBloomLogDiff { diagnostics, .. } = &mut error
+        {
+            **diagnostics = ReceiptsDiagnostics::new(receipts, tx_hashes, expected_logs_bloom);
+        }
+        error
+    })
 }
 
 /// Compare the calculated receipts root with the expected receipts root, also compare
@@ -80,15 +92,18 @@ fn compare_receipts_root_and_logs_bloom(
     expected_logs_bloom: Bloom,
 ) -> Result<(), ConsensusError> {
     if calculated_receipts_root != expected_receipts_root {
-        return Err(ConsensusError::BodyReceiptRootDiff(
-            GotExpected { got: calculated_receipts_root, expected: expected_receipts_root }.into(),
-        ))
+        return Err(ConsensusError::BodyReceiptRootDiff {
+            root: GotExpected { got: calculated_receipts_root, expected: expected_receipts_root }
+                .into(),
+            diagnostics: Default::default(),
+        })
     }
 
     if calculated_logs_bloom != expected_logs_bloom {
-        return Err(ConsensusError::BodyBloomLogDiff(
-            GotExpected { got: calculated_logs_bloom, expected: expected_logs_bloom }.into(),
-        ))
+        return Err(ConsensusError::BodyBloomLogDiff {
+            bloom: GotExpected { got: calculated_logs_bloom, expected: expected_logs_bloom }.into(),
+            diagnostics: Default::default(),
+        })
     }
 
     Ok(())
@@ -9,8 +9,8 @@ use reth_chainspec::ChainSpec;
 use reth_node_api::{
     payload::{
         validate_parent_beacon_block_root_presence, EngineApiMessageVersion,
-        EngineObjectValidationError, MessageValidationKind, PayloadOrAttributes, PayloadTypes,
-        VersionSpecificValidationError,
+        EngineObjectValidationError, InvalidPayloadAttributesError, MessageValidationKind,
+        PayloadAttributes, PayloadOrAttributes, PayloadTypes, VersionSpecificValidationError,
     },
     validate_version_specific_fields, BuiltPayload, EngineTypes, EngineValidator, NodePrimitives,
     PayloadValidator,
@@ -158,6 +158,14 @@ where
 
         Ok(())
     }
+
+    fn validate_payload_attributes_against_header(
+        &self,
+        attr: &OpPayloadAttributes,
+        header: &alloy_consensus::Header,
+    ) -> Result<(), InvalidPayloadAttributesError> {
+        attr.validate(header, self.chain_spec())
+    }
 }
 
 /// Validates the presence of the `withdrawals` field according to the payload timestamp.
@@ -8,7 +8,7 @@ use reth_rpc_eth_api::{
     helpers::{estimate::EstimateCall, Call, EthCall, LoadBlock, LoadState, SpawnBlocking},
     FromEthApiError, FullEthApiTypes, IntoEthApiError,
 };
-use reth_rpc_eth_types::{revm_utils::CallFees, RpcInvalidTransactionError};
+use reth_rpc_eth_types::{revm_utils::CallFees, ReplayCache, RpcInvalidTransactionError};
 use revm::primitives::{BlockEnv, OptimismFields, TxEnv};
 
 impl<N> EthCall for OpEthApi<N>
@@ -42,6 +42,11 @@ where
         self.inner.eth_api.max_simulate_blocks()
     }
 
+    #[inline]
+    fn replay_cache(&self) -> &ReplayCache {
+        self.inner.eth_api.replay_cache()
+    }
+
     fn create_txn_env(
         &self,
         block_env: &BlockEnv,
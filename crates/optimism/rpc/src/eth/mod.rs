@@ -13,7 +13,8 @@ use reth_optimism_primitives::OpPrimitives;
 
 use std::{fmt, sync::Arc};
 
-use alloy_primitives::U256;
+use alloy_eips::BlockNumHash;
+use alloy_primitives::{Address, BlockNumber, U256};
 use op_alloy_network::Optimism;
 use reth_chainspec::{EthChainSpec, EthereumHardforks};
 use reth_evm::ConfigureEvm;
@@ -21,8 +22,8 @@ use reth_network_api::NetworkInfo;
 use reth_node_builder::EthApiBuilderCtx;
 use reth_provider::{
     BlockNumReader, BlockReader, BlockReaderIdExt, CanonStateSubscriptions, ChainSpecProvider,
-    NodePrimitivesProvider, ProviderBlock, ProviderHeader, ProviderReceipt, ProviderTx,
-    StageCheckpointReader, StateProviderFactory,
+    NodePrimitivesProvider, ProviderBlock, ProviderHeader, ProviderReceipt, ProviderResult,
+    ProviderTx, PruneCheckpointReader, StageCheckpointReader, StateProviderFactory,
 };
 use reth_rpc::eth::{core::EthApiInner, DevSigner};
 use reth_rpc_eth_api::{
@@ -32,12 +33,13 @@ use reth_rpc_eth_api::{
     },
     EthApiTypes, RpcNodeCore, RpcNodeCoreExt,
 };
-use reth_rpc_eth_types::{EthStateCache, FeeHistoryCache, GasPriceOracle};
+use reth_rpc_eth_types::{EthStateCache, FeeHistoryCache, GasPriceOracle, TransactionJsonCache};
 use reth_tasks::{
     pool::{BlockingTaskGuard, BlockingTaskPool},
     TaskSpawner,
 };
 use reth_transaction_pool::TransactionPool;
+use tokio::sync::watch;
 
 use crate::{OpEthApiError, SequencerClient};
 
@@ -107,6 +109,11 @@ where
     fn tx_resp_builder(&self) -> &Self::TransactionCompat {
         self
     }
+
+    #[inline]
+    fn tx_json_cache(&self) -> Option<&TransactionJsonCache> {
+        self.inner.tx_json_cache.as_ref()
+    }
 }
 
 impl<N> RpcNodeCore for OpEthApi<N>
@@ -175,6 +182,20 @@ where
     fn signers(&self) -> &parking_lot::RwLock<Vec<Box<dyn EthSigner<ProviderTx<Self::Provider>>>>> {
         self.inner.eth_api.signers()
     }
+
+    #[inline]
+    fn fee_recipient(&self) -> &parking_lot::RwLock<Option<watch::Receiver<Address>>> {
+        self.inner.eth_api.fee_recipient()
+    }
+
+    #[inline]
+    fn sync_target_updates(&self) -> Option<&watch::Receiver<Option<BlockNumHash>>> {
+        self.inner.eth_api.sync_target_updates()
+    }
+
+    fn max_tx_sync_distance(&self) -> Option<u64> {
+        self.inner.eth_api.max_tx_sync_distance()
+    }
 }
 
 impl<N> SpawnBlocking for OpEthApi<N>
@@ -218,12 +239,18 @@ where
     }
 }
 
-impl<N> LoadState for OpEthApi<N> where
+impl<N> LoadState for OpEthApi<N>
+where
     N: OpNodeCore<
-        Provider: StateProviderFactory + ChainSpecProvider<ChainSpec: EthereumHardforks>,
+        Provider: StateProviderFactory
+                      + ChainSpecProvider<ChainSpec: EthereumHardforks>
+                      + PruneCheckpointReader,
         Pool: TransactionPool,
-    >
+    >,
 {
+    fn history_cutoff(&self) -> ProviderResult<Option<BlockNumber>> {
+        self.inner.eth_api.history_cutoff().oldest_block(RpcNodeCore::provider(self))
+    }
 }
 
 impl<N> EthState for OpEthApi<N>
@@ -280,6 +307,9 @@ struct OpEthApiInner<N: OpNodeCore> {
     /// Sequencer client, configured to forward submitted transactions to sequencer of given OP
     /// network.
     sequencer_client: Option<SequencerClient>,
+    /// Cache for the serialized RPC objects of mined transactions, shared by block and
+    /// transaction endpoints, if enabled.
+    tx_json_cache: Option<TransactionJsonCache>,
 }
 
 impl<N: OpNodeCore> OpEthApiInner<N> {
@@ -329,10 +359,9 @@ impl OpEthApiBuilder {
                           + 'static,
         >,
     {
-        let blocking_task_pool =
-            BlockingTaskPool::build().expect("failed to build blocking task pool");
+        let blocking_task_pool = ctx.executor.compute_pool().clone();
 
-        let eth_api = EthApiInner::new(
+        let mut eth_api = EthApiInner::new(
             ctx.provider.clone(),
             ctx.pool.clone(),
             ctx.network.clone(),
@@ -347,9 +376,16 @@ impl OpEthApiBuilder {
             ctx.executor.clone(),
             ctx.config.proof_permits,
         );
+        eth_api.set_history_cutoff(ctx.config.history_cutoff);
+        eth_api.set_max_tx_sync_distance(ctx.config.max_tx_sync_distance);
+        eth_api.set_sync_target_updates(ctx.events.subscribe_sync_target());
 
         OpEthApi {
-            inner: Arc::new(OpEthApiInner { eth_api, sequencer_client: self.sequencer_client }),
+            inner: Arc::new(OpEthApiInner {
+                eth_api,
+                sequencer_client: self.sequencer_client,
+                tx_json_cache: ctx.new_tx_json_cache(),
+            }),
         }
     }
 }
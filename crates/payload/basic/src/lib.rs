@@ -18,7 +18,9 @@ use reth_chainspec::EthereumHardforks;
 use reth_evm::state_change::post_block_withdrawals_balance_increments;
 use reth_payload_builder::{KeepPayloadJobAlive, PayloadId, PayloadJob, PayloadJobGenerator};
 use reth_payload_builder_primitives::PayloadBuilderError;
-use reth_payload_primitives::{BuiltPayload, PayloadBuilderAttributes, PayloadKind};
+use reth_payload_primitives::{
+    validate_timestamp_against_parent, BuiltPayload, PayloadBuilderAttributes, PayloadKind,
+};
 use reth_primitives::{proofs, NodePrimitives, SealedHeader};
 use reth_provider::{BlockReaderIdExt, CanonStateNotification, StateProviderFactory};
 use reth_revm::cached::CachedReads;
@@ -36,7 +38,7 @@ use std::{
 };
 use tokio::{
     sync::{oneshot, Semaphore},
-    time::{Interval, Sleep},
+    time::{Instant, Interval, Sleep},
 };
 use tracing::{debug, trace, warn};
 
@@ -45,6 +47,12 @@ mod stack;
 
 pub use stack::PayloadBuilderStack;
 
+/// The upper bound of [`BasicPayloadJobGeneratorConfig::getpayload_wait`].
+///
+/// The engine API timeout for `engine_getPayload` is 1s, so waiting for a build iteration must
+/// leave enough time to serve the payload.
+pub const MAX_GETPAYLOAD_WAIT: Duration = Duration::from_millis(500);
+
 /// The [`PayloadJobGenerator`] that creates [`BasicPayloadJob`]s.
 #[derive(Debug)]
 pub struct BasicPayloadJobGenerator<Client, Pool, Tasks, Builder> {
@@ -162,6 +170,9 @@ where
                 .ok_or_else(|| PayloadBuilderError::MissingParentHeader(attributes.parent()))?
         };
 
+        // never start building on top of a parent the attributes can't extend
+        validate_timestamp_against_parent(attributes.timestamp(), parent_header.header())?;
+
         let config = PayloadConfig::new(Arc::new(parent_header.clone()), attributes);
 
         let until = self.job_deadline(config.attributes.timestamp());
@@ -183,6 +194,9 @@ where
             payload_task_guard: self.payload_task_guard.clone(),
             metrics: Default::default(),
             builder: self.builder.clone(),
+            getpayload_wait: self.config.getpayload_wait,
+            build_started_at: None,
+            last_build_duration: None,
         };
 
         // start the first job right away
@@ -254,6 +268,8 @@ pub struct BasicPayloadJobGeneratorConfig {
     deadline: Duration,
     /// Maximum number of tasks to spawn for building a payload.
     max_payload_tasks: usize,
+    /// How long resolving a payload may wait for the build iteration in progress to complete.
+    getpayload_wait: Duration,
 }
 
 // === impl BasicPayloadJobGeneratorConfig ===
@@ -281,6 +297,15 @@ impl BasicPayloadJobGeneratorConfig {
         self.max_payload_tasks = max_payload_tasks;
         self
     }
+
+    /// Sets how long resolving a payload may wait for the build iteration in progress, if it's
+    /// expected to complete within this window.
+    ///
+    /// This is capped at [`MAX_GETPAYLOAD_WAIT`], a zero duration disables waiting.
+    pub fn getpayload_wait(mut self, getpayload_wait: Duration) -> Self {
+        self.getpayload_wait = getpayload_wait.min(MAX_GETPAYLOAD_WAIT);
+        self
+    }
 }
 
 impl Default for BasicPayloadJobGeneratorConfig {
@@ -290,6 +315,7 @@ impl Default for BasicPayloadJobGeneratorConfig {
             // 12s slot time
             deadline: SLOT_DURATION,
             max_payload_tasks: 3,
+            getpayload_wait: Duration::from_millis(200),
         }
     }
 }
@@ -329,6 +355,12 @@ where
     ///
     /// See [`PayloadBuilder`]
     builder: Builder,
+    /// How long resolving the payload may wait for the build iteration in progress.
+    getpayload_wait: Duration,
+    /// When the build iteration in progress was started.
+    build_started_at: Option<Instant>,
+    /// How long the last completed build iteration took.
+    last_build_duration: Option<Duration>,
 }
 
 impl<Client, Pool, Tasks, Builder> BasicPayloadJob<Client, Pool, Tasks, Builder>
@@ -370,6 +402,30 @@ where
         }));
 
         self.pending_block = Some(PendingPayload { _cancel, payload: rx });
+        self.build_started_at = Some(Instant::now());
+    }
+
+    /// Returns the build iteration that is currently in progress, if any.
+    pub fn build_in_progress(&self) -> Option<BuildInProgress> {
+        self.pending_block.as_ref()?;
+        let started_at = self.build_started_at?;
+        Some(BuildInProgress {
+            started_at,
+            expected_completion: self.last_build_duration.map(|duration| started_at + duration),
+        })
+    }
+
+    /// Returns the deadline until which resolving the payload should wait for the build iteration
+    /// in progress, if it's expected to complete within the `getpayload_wait` window.
+    fn getpayload_wait_deadline(&self) -> Option<Instant> {
+        if self.getpayload_wait.is_zero() {
+            return None
+        }
+        let deadline = Instant::now() + self.getpayload_wait;
+        self.build_in_progress()?
+            .expected_completion
+            .filter(|expected_completion| *expected_completion <= deadline)
+            .map(|_| deadline)
     }
 }
 
@@ -404,7 +460,12 @@ where
 
         // poll the pending block
         if let Some(mut fut) = this.pending_block.take() {
-            match fut.poll_unpin(cx) {
+            let poll = fut.poll_unpin(cx);
+            if poll.is_ready() {
+                this.last_build_duration =
+                    this.build_started_at.take().map(|started_at| started_at.elapsed());
+            }
+            match poll {
                 Poll::Ready(Ok(outcome)) => match outcome {
                     BuildOutcome::Better { payload, cached_reads } => {
                         this.cached_reads = Some(cached_reads);
@@ -480,6 +541,16 @@ where
             self.spawn_build_job();
         }
 
+        // give the build iteration in progress a chance to improve the best payload if it's about
+        // to complete
+        let wait_for_better = best_payload
+            .as_ref()
+            .and_then(|_| self.getpayload_wait_deadline())
+            .map(|deadline| Box::pin(tokio::time::sleep_until(deadline)));
+        if wait_for_better.is_some() {
+            debug!(target: "payload_builder", id=%self.config.payload_id(), "awaiting in progress payload build job before resolving");
+        }
+
         let maybe_better = self.pending_block.take();
         let mut empty_payload = None;
 
@@ -532,12 +603,25 @@ where
             best_payload,
             maybe_better,
             empty_payload: empty_payload.filter(|_| kind != PayloadKind::WaitForPending),
+            wait_for_better,
         };
 
         (fut, KeepPayloadJobAlive::No)
     }
 }
 
+/// A build iteration of a [`BasicPayloadJob`] that is in progress.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BuildInProgress {
+    /// When the iteration was started.
+    pub started_at: Instant,
+    /// When the iteration is expected to complete, based on the duration of the previous
+    /// iteration.
+    ///
+    /// This is `None` if no iteration has completed yet.
+    pub expected_completion: Option<Instant>,
+}
+
 /// Represents the current state of a payload being built.
 #[derive(Debug, Clone)]
 pub enum PayloadState<P> {
@@ -575,6 +659,9 @@ impl<P> PayloadState<P> {
 ///
 /// If no payload has been built so far, it will either return an empty payload or the result of the
 /// in progress build job, whatever finishes first.
+///
+/// If `wait_for_better` is set, it waits for the in progress build job until the timer elapses
+/// before it falls back to the best payload.
 #[derive(Debug)]
 pub struct ResolveBestPayload<Payload> {
     /// Best payload so far.
@@ -583,6 +670,8 @@ pub struct ResolveBestPayload<Payload> {
     pub maybe_better: Option<PendingPayload<Payload>>,
     /// The empty payload building job in progress, if any.
     pub empty_payload: Option<oneshot::Receiver<Result<Payload, PayloadBuilderError>>>,
+    /// Timer until which the in progress build job is awaited if there's a best payload already.
+    pub wait_for_better: Option<Pin<Box<Sleep>>>,
 }
 
 impl<Payload> ResolveBestPayload<Payload> {
@@ -613,6 +702,16 @@ where
             }
         }
 
+        if this.maybe_better.is_some() && this.best_payload.is_some() {
+            if let Some(wait) = this.wait_for_better.as_mut() {
+                if wait.as_mut().poll(cx).is_pending() {
+                    return Poll::Pending
+                }
+                debug!(target: "payload_builder", "in progress payload build job did not complete in time");
+                this.wait_for_better = None;
+            }
+        }
+
         if let Some(best) = this.best_payload.take() {
             debug!(target: "payload_builder", "resolving best payload");
             return Poll::Ready(Ok(best))
@@ -1005,3 +1104,163 @@ fn duration_until(unix_timestamp_secs: u64) -> Duration {
     let timestamp = Duration::from_secs(unix_timestamp_secs);
     timestamp.saturating_sub(unix_now)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_payload_builder::{EthBuiltPayload, EthPayloadBuilderAttributes};
+    use reth_primitives::SealedBlock;
+    use reth_provider::test_utils::NoopProvider;
+    use reth_tasks::TokioTaskExecutor;
+    use reth_transaction_pool::noop::NoopTransactionPool;
+    use std::{collections::VecDeque, future::poll_fn, sync::Mutex};
+
+    type TestJob =
+        BasicPayloadJob<NoopProvider, NoopTransactionPool, TokioTaskExecutor, SlowPayloadBuilder>;
+
+    /// A payload builder whose build iterations take the given durations. Every iteration builds
+    /// a payload with higher fees than the previous one.
+    #[derive(Debug, Clone)]
+    struct SlowPayloadBuilder {
+        durations: Arc<Mutex<VecDeque<Duration>>>,
+    }
+
+    impl SlowPayloadBuilder {
+        fn new(durations: impl IntoIterator<Item = Duration>) -> Self {
+            Self { durations: Arc::new(Mutex::new(durations.into_iter().collect())) }
+        }
+    }
+
+    impl<Pool, Client> PayloadBuilder<Pool, Client> for SlowPayloadBuilder {
+        type Attributes = EthPayloadBuilderAttributes;
+        type BuiltPayload = EthBuiltPayload;
+
+        fn try_build(
+            &self,
+            args: BuildArguments<Pool, Client, Self::Attributes, Self::BuiltPayload>,
+        ) -> Result<BuildOutcome<Self::BuiltPayload>, PayloadBuilderError> {
+            let duration = self.durations.lock().unwrap().pop_front().unwrap_or_default();
+            std::thread::sleep(duration);
+
+            let fees =
+                args.best_payload.map(|best| best.fees()).unwrap_or_default() + U256::from(1);
+            Ok(BuildOutcome::Better {
+                payload: payload(args.config.payload_id(), fees),
+                cached_reads: args.cached_reads,
+            })
+        }
+
+        fn build_empty_payload(
+            &self,
+            _client: &Client,
+            config: PayloadConfig<Self::Attributes>,
+        ) -> Result<Self::BuiltPayload, PayloadBuilderError> {
+            Ok(payload(config.payload_id(), U256::ZERO))
+        }
+    }
+
+    fn payload(id: PayloadId, fees: U256) -> EthBuiltPayload {
+        EthBuiltPayload::new(id, Arc::new(SealedBlock::default()), fees, None, None)
+    }
+
+    fn job(builder: SlowPayloadBuilder, getpayload_wait: Duration) -> TestJob {
+        BasicPayloadJob {
+            config: PayloadConfig::new(
+                Arc::new(SealedHeader::default()),
+                EthPayloadBuilderAttributes::default(),
+            ),
+            client: NoopProvider::default(),
+            pool: NoopTransactionPool::default(),
+            executor: TokioTaskExecutor::default(),
+            deadline: Box::pin(tokio::time::sleep(Duration::from_secs(60))),
+            // only the iterations spawned by the tests are built
+            interval: tokio::time::interval(Duration::from_secs(60)),
+            best_payload: PayloadState::Missing,
+            pending_block: None,
+            payload_task_guard: PayloadTaskGuard::new(1),
+            cached_reads: None,
+            metrics: Default::default(),
+            builder,
+            getpayload_wait,
+            build_started_at: None,
+            last_build_duration: None,
+        }
+    }
+
+    /// Polls the job until the build iteration in progress completed.
+    async fn complete_iteration(job: &mut TestJob) {
+        poll_fn(|cx| {
+            let _ = job.poll_unpin(cx);
+            if job.pending_block.is_none() {
+                Poll::Ready(())
+            } else {
+                Poll::Pending
+            }
+        })
+        .await;
+    }
+
+    #[test]
+    fn getpayload_wait_is_capped() {
+        let config =
+            BasicPayloadJobGeneratorConfig::default().getpayload_wait(Duration::from_secs(5));
+        assert_eq!(config.getpayload_wait, MAX_GETPAYLOAD_WAIT);
+    }
+
+    #[tokio::test]
+    async fn resolve_waits_for_build_in_progress() {
+        let builder =
+            SlowPayloadBuilder::new([Duration::from_millis(50), Duration::from_millis(100)]);
+        let mut job = job(builder, Duration::from_millis(400));
+
+        // no estimate before the first iteration completed
+        job.spawn_build_job();
+        assert_eq!(job.build_in_progress().unwrap().expected_completion, None);
+        complete_iteration(&mut job).await;
+        assert_eq!(job.best_payload.payload().unwrap().fees(), U256::from(1));
+        assert_eq!(job.build_in_progress(), None);
+
+        job.spawn_build_job();
+        let in_progress = job.build_in_progress().unwrap();
+        assert!(in_progress.expected_completion.unwrap() > in_progress.started_at);
+
+        let (resolve, _) = job.resolve_kind(PayloadKind::Earliest);
+        let resolved = resolve.await.unwrap();
+        assert_eq!(resolved.fees(), U256::from(2));
+    }
+
+    #[tokio::test]
+    async fn resolve_returns_best_payload_after_wait() {
+        let builder = SlowPayloadBuilder::new([Duration::from_millis(50), Duration::from_secs(1)]);
+        let mut job = job(builder, Duration::from_millis(200));
+
+        job.spawn_build_job();
+        complete_iteration(&mut job).await;
+        // the iteration is expected to complete within the window, but takes much longer
+        job.spawn_build_job();
+
+        let start = Instant::now();
+        let (resolve, _) = job.resolve_kind(PayloadKind::Earliest);
+        let resolved = resolve.await.unwrap();
+        assert_eq!(resolved.fees(), U256::from(1));
+        assert!(start.elapsed() >= Duration::from_millis(200));
+        assert!(start.elapsed() < Duration::from_secs(1));
+    }
+
+    #[tokio::test]
+    async fn resolve_does_not_wait_beyond_window() {
+        let builder =
+            SlowPayloadBuilder::new([Duration::from_millis(300), Duration::from_millis(300)]);
+        let mut job = job(builder, Duration::from_millis(100));
+
+        job.spawn_build_job();
+        complete_iteration(&mut job).await;
+        // the previous iteration took longer than the window
+        job.spawn_build_job();
+        assert_eq!(job.getpayload_wait_deadline(), None);
+
+        let (resolve, _) = job.resolve_kind(PayloadKind::Earliest);
+        assert!(resolve.wait_for_better.is_none());
+        assert_eq!(resolve.await.unwrap().fees(), U256::from(1));
+    }
+}
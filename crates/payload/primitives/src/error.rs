@@ -27,6 +27,9 @@ pub enum PayloadBuilderError {
     /// Unrecoverable error during evm execution.
     #[error("evm execution error: {0}")]
     EvmExecutionError(EVMError<ProviderError>),
+    /// The payload attributes are invalid for the parent block.
+    #[error(transparent)]
+    InvalidPayloadAttributes(#[from] InvalidPayloadAttributesError),
     /// Any other payload building errors.
     #[error(transparent)]
     Other(Box<dyn core::error::Error + Send + Sync>),
@@ -120,9 +123,22 @@ impl EngineObjectValidationError {
 /// Thrown when validating the correctness of a payloadattributes object.
 #[derive(thiserror::Error, Debug)]
 pub enum InvalidPayloadAttributesError {
-    /// Thrown if the timestamp of the payload attributes is invalid according to the engine specs.
-    #[error("parent beacon block root not supported before V3")]
+    /// Thrown if the timestamp of the payload attributes is not greater than the timestamp of the
+    /// parent block.
+    #[error("payload attributes timestamp is not greater than the parent timestamp")]
     InvalidTimestamp,
+    /// Thrown if the payload attributes contain no withdrawals after Shanghai.
+    #[error("no withdrawals post-Shanghai")]
+    MissingWithdrawals,
+    /// Thrown if the payload attributes contain withdrawals before Shanghai.
+    #[error("withdrawals pre-Shanghai")]
+    UnexpectedWithdrawals,
+    /// Thrown if the payload attributes contain no parent beacon block root after Cancun.
+    #[error("no parent beacon block root post-Cancun")]
+    MissingParentBeaconBlockRoot,
+    /// Thrown if the payload attributes contain a parent beacon block root before Cancun.
+    #[error("parent beacon block root pre-Cancun")]
+    UnexpectedParentBeaconBlockRoot,
     /// Another type of error that is not covered by the above variants.
     #[error("Invalid params: {0}")]
     InvalidParams(#[from] Box<dyn core::error::Error + Send + Sync>),
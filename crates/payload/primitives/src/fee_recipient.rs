@@ -0,0 +1,42 @@
+use alloy_primitives::Address;
+use std::sync::Arc;
+use tokio::sync::watch;
+
+/// A handle to the suggested fee recipient of the payloads the node builds by itself, that can be
+/// changed at runtime.
+///
+/// All clones share the same value. The value is read when the attributes of a payload are
+/// created, so payload jobs that are already in flight keep the fee recipient they were started
+/// with.
+#[derive(Debug, Clone)]
+pub struct SuggestedFeeRecipient {
+    tx: Arc<watch::Sender<Address>>,
+}
+
+impl SuggestedFeeRecipient {
+    /// Creates a new handle with the given initial fee recipient.
+    pub fn new(fee_recipient: Address) -> Self {
+        Self { tx: Arc::new(watch::Sender::new(fee_recipient)) }
+    }
+
+    /// Returns the current fee recipient.
+    pub fn get(&self) -> Address {
+        *self.tx.borrow()
+    }
+
+    /// Sets the fee recipient of payloads created from now on and returns the previous one.
+    pub fn set(&self, fee_recipient: Address) -> Address {
+        self.tx.send_replace(fee_recipient)
+    }
+
+    /// Returns a receiver that is notified whenever the fee recipient changes.
+    pub fn subscribe(&self) -> watch::Receiver<Address> {
+        self.tx.subscribe()
+    }
+}
+
+impl Default for SuggestedFeeRecipient {
+    fn default() -> Self {
+        Self::new(Address::ZERO)
+    }
+}
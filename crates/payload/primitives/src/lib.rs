@@ -18,12 +18,17 @@ pub use error::{
 /// [`PayloadAttributes`] trait for ethereum mainnet and optimism types.
 mod traits;
 pub use traits::{
-    BuiltPayload, PayloadAttributes, PayloadAttributesBuilder, PayloadBuilderAttributes,
+    BuiltPayload, ExtendedPayloadAttributes, PayloadAttributes, PayloadAttributesBuilder,
+    PayloadAttributesExtension, PayloadBuilderAttributes,
 };
 
 mod payload;
 pub use payload::PayloadOrAttributes;
 
+mod fee_recipient;
+pub use fee_recipient::SuggestedFeeRecipient;
+
+use alloy_consensus::BlockHeader;
 use reth_chainspec::EthereumHardforks;
 /// The types that are used by the engine API.
 pub trait PayloadTypes: Send + Sync + Unpin + core::fmt::Debug + Clone + 'static {
@@ -321,6 +326,59 @@ where
     )
 }
 
+/// Validates payload attributes for a payload built on top of the `parent` block.
+///
+/// This ensures that:
+/// * the timestamp is greater than the timestamp of the parent, see
+///   [`validate_timestamp_against_parent`],
+/// * withdrawals are present if and only if Shanghai is active at the timestamp,
+/// * the parent beacon block root is present if and only if Cancun is active at the timestamp.
+///
+/// This is the default implementation of [`PayloadAttributes::validate`].
+pub fn validate_payload_attributes<Attributes, H, ChainSpec>(
+    attributes: &Attributes,
+    parent: &H,
+    chain_spec: &ChainSpec,
+) -> Result<(), InvalidPayloadAttributesError>
+where
+    Attributes: PayloadAttributes + ?Sized,
+    H: BlockHeader,
+    ChainSpec: EthereumHardforks,
+{
+    let timestamp = attributes.timestamp();
+    validate_timestamp_against_parent(timestamp, parent)?;
+
+    let is_shanghai = chain_spec.is_shanghai_active_at_timestamp(timestamp);
+    match (is_shanghai, attributes.withdrawals().is_some()) {
+        (true, false) => return Err(InvalidPayloadAttributesError::MissingWithdrawals),
+        (false, true) => return Err(InvalidPayloadAttributesError::UnexpectedWithdrawals),
+        _ => {}
+    }
+
+    let is_cancun = chain_spec.is_cancun_active_at_timestamp(timestamp);
+    match (is_cancun, attributes.parent_beacon_block_root().is_some()) {
+        (true, false) => Err(InvalidPayloadAttributesError::MissingParentBeaconBlockRoot),
+        (false, true) => Err(InvalidPayloadAttributesError::UnexpectedParentBeaconBlockRoot),
+        _ => Ok(()),
+    }
+}
+
+/// Ensures that the timestamp of a payload is greater than the timestamp of its `parent`.
+///
+/// From the Engine API spec:
+///
+/// > Client software MUST ensure that payloadAttributes.timestamp is greater than timestamp of a
+/// > block referenced by forkchoiceState.headBlockHash.
+pub fn validate_timestamp_against_parent<H: BlockHeader>(
+    timestamp: u64,
+    parent: &H,
+) -> Result<(), InvalidPayloadAttributesError> {
+    if timestamp <= parent.timestamp() {
+        return Err(InvalidPayloadAttributesError::InvalidTimestamp)
+    }
+    Ok(())
+}
+
 /// The version of Engine API message.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
 pub enum EngineApiMessageVersion {
@@ -367,8 +425,163 @@ pub enum PayloadKind {
 mod tests {
     use super::*;
 
+    use alloy_consensus::Header;
+    use alloy_primitives::B256;
+    use alloy_rpc_types_engine::PayloadAttributes as EthPayloadAttributes;
+    use reth_chainspec::{ChainSpec, EthereumHardfork, ForkCondition, MAINNET};
+
     #[test]
     fn version_ord() {
         assert!(EngineApiMessageVersion::V4 > EngineApiMessageVersion::V3);
     }
+
+    #[test]
+    fn payload_attributes_validation() {
+        const SHANGHAI: u64 = 1_000;
+        const CANCUN: u64 = 2_000;
+        let chain_spec = ChainSpec::builder()
+            .chain(MAINNET.chain)
+            .genesis(MAINNET.genesis.clone())
+            .paris_activated()
+            .with_fork(EthereumHardfork::Shanghai, ForkCondition::Timestamp(SHANGHAI))
+            .with_fork(EthereumHardfork::Cancun, ForkCondition::Timestamp(CANCUN))
+            .build();
+
+        let withdrawals = Some(Vec::new());
+        let root = Some(B256::ZERO);
+        let cases = [
+            // (parent timestamp, timestamp, withdrawals, parent beacon block root, expected)
+            (SHANGHAI - 2, SHANGHAI - 1, None, None, Ok(())),
+            (
+                SHANGHAI - 1,
+                SHANGHAI - 1,
+                None,
+                None,
+                Err(InvalidPayloadAttributesError::InvalidTimestamp),
+            ),
+            (
+                SHANGHAI - 1,
+                SHANGHAI - 2,
+                None,
+                None,
+                Err(InvalidPayloadAttributesError::InvalidTimestamp),
+            ),
+            (
+                SHANGHAI - 2,
+                SHANGHAI - 1,
+                withdrawals.clone(),
+                None,
+                Err(InvalidPayloadAttributesError::UnexpectedWithdrawals),
+            ),
+            (
+                SHANGHAI - 1,
+                SHANGHAI,
+                None,
+                None,
+                Err(InvalidPayloadAttributesError::MissingWithdrawals),
+            ),
+            (SHANGHAI - 1, SHANGHAI, withdrawals.clone(), None, Ok(())),
+            (
+                SHANGHAI,
+                SHANGHAI,
+                withdrawals.clone(),
+                None,
+                Err(InvalidPayloadAttributesError::InvalidTimestamp),
+            ),
+            (
+                CANCUN - 2,
+                CANCUN - 1,
+                withdrawals.clone(),
+                root,
+                Err(InvalidPayloadAttributesError::UnexpectedParentBeaconBlockRoot),
+            ),
+            (
+                CANCUN - 1,
+                CANCUN,
+                withdrawals.clone(),
+                None,
+                Err(InvalidPayloadAttributesError::MissingParentBeaconBlockRoot),
+            ),
+            (CANCUN - 1, CANCUN, withdrawals.clone(), root, Ok(())),
+            (
+                CANCUN,
+                CANCUN,
+                withdrawals,
+                root,
+                Err(InvalidPayloadAttributesError::InvalidTimestamp),
+            ),
+        ];
+
+        for (parent_timestamp, timestamp, withdrawals, parent_beacon_block_root, expected) in cases
+        {
+            let parent = Header { timestamp: parent_timestamp, ..Default::default() };
+            let attributes = EthPayloadAttributes {
+                timestamp,
+                prev_randao: B256::ZERO,
+                suggested_fee_recipient: Default::default(),
+                withdrawals,
+                parent_beacon_block_root,
+            };
+            assert_eq!(
+                attributes.validate(&parent, &chain_spec).map_err(|err| err.to_string()),
+                expected.map_err(|err| err.to_string()),
+                "parent timestamp {parent_timestamp}, timestamp {timestamp}"
+            );
+        }
+    }
+
+    #[test]
+    fn extended_payload_attributes_validation() {
+        #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+        struct GasLimit {
+            gas_limit: Option<u64>,
+        }
+
+        impl PayloadAttributesExtension for GasLimit {
+            fn validate<H, ChainSpec>(
+                &self,
+                _attributes: &EthPayloadAttributes,
+                _parent: &H,
+                _chain_spec: &ChainSpec,
+            ) -> Result<(), InvalidPayloadAttributesError>
+            where
+                H: BlockHeader,
+                ChainSpec: EthereumHardforks,
+            {
+                if self.gas_limit.is_none() {
+                    return Err(InvalidPayloadAttributesError::InvalidParams(
+                        "missing gas limit".into(),
+                    ))
+                }
+                Ok(())
+            }
+        }
+
+        let parent = Header { timestamp: 1, ..Default::default() };
+        let mut attributes = ExtendedPayloadAttributes {
+            inner: EthPayloadAttributes {
+                timestamp: 1,
+                prev_randao: B256::ZERO,
+                suggested_fee_recipient: Default::default(),
+                withdrawals: None,
+                parent_beacon_block_root: None,
+            },
+            extension: GasLimit { gas_limit: None },
+        };
+
+        // ethereum attributes are validated first
+        assert!(matches!(
+            attributes.validate(&parent, &*MAINNET),
+            Err(InvalidPayloadAttributesError::InvalidTimestamp)
+        ));
+
+        attributes.inner.timestamp = 2;
+        assert!(matches!(
+            attributes.validate(&parent, &*MAINNET),
+            Err(InvalidPayloadAttributesError::InvalidParams(_))
+        ));
+
+        attributes.extension.gas_limit = Some(30_000_000);
+        assert!(attributes.validate(&parent, &*MAINNET).is_ok());
+    }
 }
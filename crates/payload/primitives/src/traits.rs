@@ -1,3 +1,5 @@
+use crate::{validate_payload_attributes, InvalidPayloadAttributesError};
+use alloy_consensus::BlockHeader;
 use alloy_eips::{
     eip4895::{Withdrawal, Withdrawals},
     eip7685::Requests,
@@ -5,7 +7,9 @@ use alloy_eips::{
 use alloy_primitives::{Address, B256, U256};
 use alloy_rpc_types_engine::{PayloadAttributes as EthPayloadAttributes, PayloadId};
 use reth_chain_state::ExecutedBlock;
+use reth_chainspec::EthereumHardforks;
 use reth_primitives::{NodePrimitives, SealedBlockFor};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 
 /// Represents a built payload type that contains a built `SealedBlock` and can be converted into
 /// engine API execution payloads.
@@ -87,6 +91,22 @@ pub trait PayloadAttributes:
 
     /// Return the parent beacon block root for the payload attributes.
     fn parent_beacon_block_root(&self) -> Option<B256>;
+
+    /// Validates the payload attributes for a payload built on top of the `parent` block.
+    ///
+    /// By default, this enforces the rules of [`validate_payload_attributes`]. Attributes with
+    /// additional fields should override this to validate them as well.
+    fn validate<H, ChainSpec>(
+        &self,
+        parent: &H,
+        chain_spec: &ChainSpec,
+    ) -> Result<(), InvalidPayloadAttributesError>
+    where
+        H: BlockHeader,
+        ChainSpec: EthereumHardforks,
+    {
+        validate_payload_attributes(self, parent, chain_spec)
+    }
 }
 
 impl PayloadAttributes for EthPayloadAttributes {
@@ -118,6 +138,69 @@ impl PayloadAttributes for op_alloy_rpc_types_engine::OpPayloadAttributes {
     }
 }
 
+/// Chain specific fields of [`ExtendedPayloadAttributes`].
+pub trait PayloadAttributesExtension:
+    DeserializeOwned + Serialize + std::fmt::Debug + Clone + Send + Sync + 'static
+{
+    /// Validates the fields of the extension for a payload built on top of the `parent` block.
+    ///
+    /// This is only called if the ethereum payload attributes are valid.
+    fn validate<H, ChainSpec>(
+        &self,
+        _attributes: &EthPayloadAttributes,
+        _parent: &H,
+        _chain_spec: &ChainSpec,
+    ) -> Result<(), InvalidPayloadAttributesError>
+    where
+        H: BlockHeader,
+        ChainSpec: EthereumHardforks,
+    {
+        Ok(())
+    }
+}
+
+/// Ethereum payload attributes extended with chain specific fields.
+///
+/// The fields of the extension are flattened into the attributes object of the engine API, which
+/// allows chains to add fields to the payload attributes without a custom [`PayloadAttributes`]
+/// implementation.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExtendedPayloadAttributes<Ext> {
+    /// The ethereum payload attributes.
+    #[serde(flatten)]
+    pub inner: EthPayloadAttributes,
+    /// The chain specific fields.
+    #[serde(flatten)]
+    pub extension: Ext,
+}
+
+impl<Ext: PayloadAttributesExtension> PayloadAttributes for ExtendedPayloadAttributes<Ext> {
+    fn timestamp(&self) -> u64 {
+        self.inner.timestamp
+    }
+
+    fn withdrawals(&self) -> Option<&Vec<Withdrawal>> {
+        self.inner.withdrawals.as_ref()
+    }
+
+    fn parent_beacon_block_root(&self) -> Option<B256> {
+        self.inner.parent_beacon_block_root
+    }
+
+    fn validate<H, ChainSpec>(
+        &self,
+        parent: &H,
+        chain_spec: &ChainSpec,
+    ) -> Result<(), InvalidPayloadAttributesError>
+    where
+        H: BlockHeader,
+        ChainSpec: EthereumHardforks,
+    {
+        validate_payload_attributes(self, parent, chain_spec)?;
+        self.extension.validate(&self.inner, parent, chain_spec)
+    }
+}
+
 /// A builder that can return the current payload attribute.
 pub trait PayloadAttributesBuilder<Attributes>: Send + Sync + 'static {
     /// Return a new payload attribute from the builder.
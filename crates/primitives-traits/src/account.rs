@@ -188,6 +188,16 @@ impl From<&GenesisAccount> for Account {
     }
 }
 
+impl From<TrieAccount> for Account {
+    fn from(trie_acc: TrieAccount) -> Self {
+        Self {
+            nonce: trie_acc.nonce,
+            balance: trie_acc.balance,
+            bytecode_hash: (trie_acc.code_hash != KECCAK_EMPTY).then_some(trie_acc.code_hash),
+        }
+    }
+}
+
 impl From<AccountInfo> for Account {
     fn from(revm_acc: AccountInfo) -> Self {
         Self {
@@ -0,0 +1,40 @@
+#![allow(missing_docs)]
+use alloy_primitives::{keccak256, Address, B256};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use pprof::criterion::{Output, PProfProfiler};
+use reth_primitives::keccak256_batch;
+use std::hint::black_box;
+
+/// Benchmarks hashing addresses and storage slots one by one against hashing them in a batch.
+pub fn keccak_batch(c: &mut Criterion) {
+    let mut group = c.benchmark_group("keccak256");
+
+    for size in [4, 8, 16] {
+        let addresses = (0..size).map(|_| Address::random()).collect::<Vec<_>>();
+        let slots = (0..size).map(|_| B256::random()).collect::<Vec<_>>();
+
+        group.bench_function(BenchmarkId::new("single addresses", size), |b| {
+            b.iter(|| {
+                addresses.iter().map(|address| keccak256(black_box(address))).collect::<Vec<_>>()
+            })
+        });
+        group.bench_function(BenchmarkId::new("batch addresses", size), |b| {
+            b.iter(|| keccak256_batch(black_box(&addresses)))
+        });
+        group.bench_function(BenchmarkId::new("single slots", size), |b| {
+            b.iter(|| slots.iter().map(|slot| keccak256(black_box(slot))).collect::<Vec<_>>())
+        });
+        group.bench_function(BenchmarkId::new("batch slots", size), |b| {
+            b.iter(|| keccak256_batch(black_box(&slots)))
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group! {
+    name = benches;
+    config = Criterion::default().with_profiler(PProfProfiler::new(100, Output::Flamegraph(None)));
+    targets = keccak_batch
+}
+criterion_main!(benches);
@@ -0,0 +1,279 @@
+//! Fee arithmetic shared by the transaction pool, the payload builders and the RPC.
+//!
+//! Every function comes in a checked variant, which returns `None` if the result is not
+//! representable or not defined, and a saturating variant, which clamps to the nearest valid
+//! value instead.
+
+use alloy_eips::eip4844::{BLOB_GASPRICE_UPDATE_FRACTION, BLOB_TX_MIN_BLOB_GASPRICE};
+use alloy_primitives::U256;
+
+/// Returns the effective miner gas tip for the given base fee:
+/// `min(maxFeePerGas - baseFee, maxPriorityFeePerGas)`
+///
+/// For transactions without a priority fee (pre EIP-1559), `max_fee_per_gas` is the gas price and
+/// `max_priority_fee_per_gas` is `None`, so the entire fee above the base fee is the tip.
+///
+/// Returns `None` if the base fee is higher than `max_fee_per_gas`.
+#[inline]
+pub fn effective_gas_tip(
+    max_fee_per_gas: u128,
+    max_priority_fee_per_gas: Option<u128>,
+    base_fee: u64,
+) -> Option<u128> {
+    let fee = max_fee_per_gas.checked_sub(base_fee as u128)?;
+    Some(max_priority_fee_per_gas.map_or(fee, |priority_fee| fee.min(priority_fee)))
+}
+
+/// Returns the effective miner gas tip for the given base fee, see [`effective_gas_tip`].
+///
+/// Returns `0` if the base fee is higher than `max_fee_per_gas`.
+#[inline]
+pub fn saturating_effective_gas_tip(
+    max_fee_per_gas: u128,
+    max_priority_fee_per_gas: Option<u128>,
+    base_fee: u64,
+) -> u128 {
+    effective_gas_tip(max_fee_per_gas, max_priority_fee_per_gas, base_fee).unwrap_or_default()
+}
+
+/// Returns the price per gas a transaction pays in a block with the given base fee:
+/// `baseFee + min(maxFeePerGas - baseFee, maxPriorityFeePerGas)`
+///
+/// Without a base fee (pre EIP-1559 blocks), or if the base fee is higher than `max_fee_per_gas`,
+/// which no included transaction can have, this is `max_fee_per_gas`.
+#[inline]
+pub fn effective_gas_price(
+    max_fee_per_gas: u128,
+    max_priority_fee_per_gas: Option<u128>,
+    base_fee: Option<u64>,
+) -> u128 {
+    base_fee
+        .and_then(|base_fee| {
+            let tip = effective_gas_tip(max_fee_per_gas, max_priority_fee_per_gas, base_fee)?;
+            // can't overflow, because the tip is at most `max_fee_per_gas - base_fee`
+            Some(base_fee as u128 + tip)
+        })
+        .unwrap_or(max_fee_per_gas)
+}
+
+/// Returns the blob gas price for the given excess blob gas according to EIP-4844.
+///
+/// Returns `None` if the approximation of the price overflows a `u128`. Its intermediate values
+/// overflow before the price does, from an excess blob gas of 192,204,553 on, where the price is
+/// about 10^25 wei.
+#[inline]
+pub fn blob_fee(excess_blob_gas: u64) -> Option<u128> {
+    checked_fake_exponential(
+        BLOB_TX_MIN_BLOB_GASPRICE,
+        excess_blob_gas as u128,
+        BLOB_GASPRICE_UPDATE_FRACTION,
+    )
+}
+
+/// Returns the blob gas price for the given excess blob gas, see [`blob_fee`].
+///
+/// Returns [`u128::MAX`] if the approximation of the price overflows.
+#[inline]
+pub fn saturating_blob_fee(excess_blob_gas: u64) -> u128 {
+    blob_fee(excess_blob_gas).unwrap_or(u128::MAX)
+}
+
+/// Returns the amount of gas an account with the given balance can pay for at the given fee per
+/// gas, after transferring `value`.
+///
+/// Returns `None` if the value exceeds the balance or the fee per gas is zero.
+#[inline]
+pub fn max_affordable_gas(balance: U256, value: U256, fee_per_gas: U256) -> Option<U256> {
+    balance.checked_sub(value)?.checked_div(fee_per_gas)
+}
+
+/// Returns the amount of gas an account with the given balance can pay for at the given fee per
+/// gas, after transferring `value`, see [`max_affordable_gas`].
+///
+/// Returns `0` if the value exceeds the balance and [`U256::MAX`] if the fee per gas is zero.
+#[inline]
+pub fn saturating_max_affordable_gas(balance: U256, value: U256, fee_per_gas: U256) -> U256 {
+    let Some(available) = balance.checked_sub(value) else { return U256::ZERO };
+    available.checked_div(fee_per_gas).unwrap_or(U256::MAX)
+}
+
+/// Approximates `factor * e ** (numerator / denominator)` using Taylor expansion, returning `None`
+/// on overflow.
+///
+/// See also [the EIP-4844 helpers](https://eips.ethereum.org/EIPS/eip-4844#helpers).
+fn checked_fake_exponential(factor: u128, numerator: u128, denominator: u128) -> Option<u128> {
+    assert_ne!(denominator, 0, "attempt to divide by zero");
+
+    let mut i = 1;
+    let mut output = 0u128;
+    let mut numerator_accum = factor.checked_mul(denominator)?;
+    while numerator_accum > 0 {
+        output = output.checked_add(numerator_accum)?;
+        numerator_accum = numerator_accum.checked_mul(numerator)? / denominator.checked_mul(i)?;
+        i += 1;
+    }
+    Some(output / denominator)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Transaction;
+    use alloy_consensus::{Transaction as _, TxEip1559, TxLegacy};
+    use alloy_eips::eip4844::calc_blob_gasprice;
+    use proptest::prelude::*;
+
+    #[test]
+    fn effective_gas_tip_boundaries() {
+        // base fee above max fee
+        assert_eq!(effective_gas_tip(10, Some(1), 11), None);
+        assert_eq!(saturating_effective_gas_tip(10, Some(1), 11), 0);
+        assert_eq!(effective_gas_tip(0, None, u64::MAX), None);
+
+        // base fee equal to max fee
+        assert_eq!(effective_gas_tip(10, Some(5), 10), Some(0));
+        assert_eq!(effective_gas_tip(10, None, 10), Some(0));
+
+        // priority fee caps the tip
+        assert_eq!(effective_gas_tip(10, Some(5), 3), Some(5));
+        assert_eq!(effective_gas_tip(10, Some(5), 7), Some(3));
+        assert_eq!(effective_gas_tip(10, Some(0), 0), Some(0));
+
+        // legacy transactions tip everything above the base fee
+        assert_eq!(effective_gas_tip(10, None, 3), Some(7));
+
+        // u128 extremes
+        assert_eq!(effective_gas_tip(u128::MAX, None, 0), Some(u128::MAX));
+        assert_eq!(
+            effective_gas_tip(u128::MAX, Some(u128::MAX), u64::MAX),
+            Some(u128::MAX - u64::MAX as u128)
+        );
+        assert_eq!(effective_gas_tip(u128::MAX, Some(u128::MAX), 0), Some(u128::MAX));
+        assert_eq!(effective_gas_tip(u64::MAX as u128, Some(u128::MAX), u64::MAX), Some(0));
+        assert_eq!(saturating_effective_gas_tip(u128::MAX, Some(1), u64::MAX), 1);
+    }
+
+    #[test]
+    fn blob_fee_boundaries() {
+        assert_eq!(blob_fee(0), Some(BLOB_TX_MIN_BLOB_GASPRICE));
+        assert_eq!(blob_fee(2314057), Some(1));
+        assert_eq!(blob_fee(2314058), Some(2));
+        assert_eq!(blob_fee(10 * 1024 * 1024), Some(23));
+
+        // the approximation overflows long before the price would
+        assert_eq!(blob_fee(192_204_552), Some(10_079_293_834_132_079_738_693_097));
+        assert_eq!(blob_fee(192_204_553), None);
+        assert_eq!(saturating_blob_fee(192_204_553), u128::MAX);
+        assert_eq!(blob_fee(u64::MAX), None);
+        assert_eq!(saturating_blob_fee(u64::MAX), u128::MAX);
+
+        // the price is monotonic up to the overflow
+        let mut previous = 0;
+        for excess_blob_gas in (0..u64::MAX).step_by(1 << 20) {
+            match blob_fee(excess_blob_gas) {
+                Some(fee) => {
+                    assert!(fee >= previous);
+                    previous = fee;
+                }
+                None => break,
+            }
+        }
+    }
+
+    #[test]
+    fn max_affordable_gas_boundaries() {
+        fn u(value: u64) -> U256 {
+            U256::from(value)
+        }
+
+        // value above balance
+        assert_eq!(max_affordable_gas(u(10), u(11), u(1)), None);
+        assert_eq!(saturating_max_affordable_gas(u(10), u(11), u(1)), U256::ZERO);
+
+        // value equal to balance
+        assert_eq!(max_affordable_gas(u(10), u(10), u(1)), Some(U256::ZERO));
+
+        // zero fee
+        assert_eq!(max_affordable_gas(u(10), u(0), U256::ZERO), None);
+        assert_eq!(saturating_max_affordable_gas(u(10), u(0), U256::ZERO), U256::MAX);
+        assert_eq!(saturating_max_affordable_gas(u(10), u(11), U256::ZERO), U256::ZERO);
+
+        // rounds down
+        assert_eq!(max_affordable_gas(u(10), u(1), u(2)), Some(u(4)));
+
+        // U256 extremes
+        assert_eq!(max_affordable_gas(U256::MAX, U256::ZERO, u(1)), Some(U256::MAX));
+        assert_eq!(max_affordable_gas(U256::MAX, U256::MAX, u(1)), Some(U256::ZERO));
+        assert_eq!(max_affordable_gas(U256::MAX, U256::ZERO, U256::MAX), Some(u(1)));
+        assert_eq!(
+            max_affordable_gas(U256::MAX, U256::ZERO, U256::from(u128::MAX)),
+            Some(U256::from(u128::MAX) + u(2))
+        );
+    }
+
+    proptest! {
+        /// The fee math agrees with the methods of the transaction types.
+        #[test]
+        fn effective_gas_tip_agrees_with_transactions(
+            max_fee_per_gas in any::<u128>(),
+            max_priority_fee_per_gas in any::<u128>(),
+            base_fee in any::<u64>(),
+        ) {
+            let expected = effective_gas_tip(max_fee_per_gas, Some(max_priority_fee_per_gas), base_fee);
+            let tx = TxEip1559 { max_fee_per_gas, max_priority_fee_per_gas, ..Default::default() };
+            prop_assert_eq!(tx.effective_tip_per_gas(base_fee), expected);
+            prop_assert_eq!(Transaction::Eip1559(tx).effective_tip_per_gas(Some(base_fee)), expected);
+
+            let expected = effective_gas_tip(max_fee_per_gas, None, base_fee);
+            let tx = TxLegacy { gas_price: max_fee_per_gas, ..Default::default() };
+            prop_assert_eq!(tx.effective_tip_per_gas(base_fee), expected);
+            prop_assert_eq!(Transaction::Legacy(tx).effective_tip_per_gas(Some(base_fee)), expected);
+        }
+
+        /// The effective gas price agrees with the methods of the transaction types.
+        #[test]
+        fn effective_gas_price_agrees_with_transactions(
+            max_fee_per_gas in any::<u128>(),
+            max_priority_fee_per_gas in any::<u128>(),
+            base_fee in any::<Option<u64>>(),
+        ) {
+            let tx = TxEip1559 { max_fee_per_gas, max_priority_fee_per_gas, ..Default::default() };
+            prop_assert_eq!(
+                effective_gas_price(max_fee_per_gas, Some(max_priority_fee_per_gas), base_fee),
+                tx.effective_gas_price(base_fee)
+            );
+
+            let tx = TxLegacy { gas_price: max_fee_per_gas, ..Default::default() };
+            prop_assert_eq!(
+                effective_gas_price(max_fee_per_gas, None, base_fee),
+                tx.effective_gas_price(base_fee)
+            );
+        }
+
+        /// The blob fee agrees with the EIP-4844 reference implementation wherever it doesn't
+        /// overflow.
+        #[test]
+        fn blob_fee_agrees_with_reference(excess_blob_gas in 0u64..100_000_000) {
+            prop_assert_eq!(blob_fee(excess_blob_gas), Some(calc_blob_gasprice(excess_blob_gas)));
+        }
+
+        /// The affordable gas never costs more than the balance left after the value transfer, and
+        /// one more unit of gas always does.
+        #[test]
+        fn max_affordable_gas_is_tight(
+            balance in any::<U256>(),
+            value in any::<U256>(),
+            fee_per_gas in 1..=u128::MAX,
+        ) {
+            let fee_per_gas = U256::from(fee_per_gas);
+            match max_affordable_gas(balance, value, fee_per_gas) {
+                None => prop_assert!(value > balance),
+                Some(gas) => {
+                    let available = balance - value;
+                    prop_assert!(gas * fee_per_gas <= available);
+                    prop_assert!((gas + U256::from(1)).saturating_mul(fee_per_gas) > available);
+                }
+            }
+        }
+    }
+}
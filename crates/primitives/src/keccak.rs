@@ -0,0 +1,212 @@
+//! Batched keccak256 hashing.
+//!
+//! [`keccak256_batch`] hashes many independent inputs in one call. By default every input is
+//! hashed with [`keccak256`], which uses the assembly implementation if the `asm-keccak` feature
+//! is enabled.
+//!
+//! With the `keccak-multi-buffer` feature, inputs that fit into a single keccak block, like
+//! addresses, storage slots and most transactions, are hashed [`LANES`] at a time by a permutation
+//! that operates on all of them at once and that the compiler vectorizes.
+
+use alloc::{vec, vec::Vec};
+use alloy_primitives::{keccak256, B256};
+
+/// Number of inputs hashed at once by the multi-buffer implementation.
+pub const LANES: usize = 4;
+
+/// Rate of keccak256 in bytes, inputs shorter than this fit into a single block.
+const RATE: usize = 136;
+
+/// Round constants of keccak-f\[1600\].
+const RC: [u64; 24] = [
+    0x0000000000000001,
+    0x0000000000008082,
+    0x800000000000808a,
+    0x8000000080008000,
+    0x000000000000808b,
+    0x0000000080000001,
+    0x8000000080008081,
+    0x8000000000008009,
+    0x000000000000008a,
+    0x0000000000000088,
+    0x0000000080008009,
+    0x000000008000000a,
+    0x000000008000808b,
+    0x800000000000008b,
+    0x8000000000008089,
+    0x8000000000008003,
+    0x8000000000008002,
+    0x8000000000000080,
+    0x000000000000800a,
+    0x800000008000000a,
+    0x8000000080008081,
+    0x8000000000008080,
+    0x0000000080000001,
+    0x8000000080008008,
+];
+
+/// Rotation offsets of the rho step, in the order of [`PI`].
+const RHO: [u32; 24] =
+    [1, 3, 6, 10, 15, 21, 28, 36, 45, 55, 2, 14, 27, 41, 56, 8, 25, 43, 62, 18, 39, 61, 20, 44];
+
+/// Lane permutation of the pi step.
+const PI: [usize; 24] =
+    [10, 7, 11, 17, 18, 3, 5, 16, 8, 21, 24, 4, 15, 23, 19, 13, 12, 2, 20, 14, 22, 9, 6, 1];
+
+/// One word of the keccak state for each of the [`LANES`] hashed inputs.
+type Lanes = [u64; LANES];
+
+/// Hashes all inputs with keccak256, returning the hashes in the same order.
+///
+/// This is equivalent to hashing every input with [`keccak256`], but faster with the
+/// `keccak-multi-buffer` feature.
+pub fn keccak256_batch<T: AsRef<[u8]>>(inputs: &[T]) -> Vec<B256> {
+    #[cfg(feature = "keccak-multi-buffer")]
+    {
+        keccak256_multi_buffer(inputs)
+    }
+    #[cfg(not(feature = "keccak-multi-buffer"))]
+    {
+        inputs.iter().map(keccak256).collect()
+    }
+}
+
+/// Hashes single block inputs [`LANES`] at a time and all other inputs with [`keccak256`].
+#[cfg_attr(not(feature = "keccak-multi-buffer"), allow(dead_code))]
+fn keccak256_multi_buffer<T: AsRef<[u8]>>(inputs: &[T]) -> Vec<B256> {
+    let mut hashes = vec![B256::ZERO; inputs.len()];
+    let mut pending = [0; LANES];
+    let mut pending_len = 0;
+
+    for (index, input) in inputs.iter().enumerate() {
+        if input.as_ref().len() < RATE {
+            pending[pending_len] = index;
+            pending_len += 1;
+            if pending_len == LANES {
+                hash_single_blocks(inputs, &pending, &mut hashes);
+                pending_len = 0;
+            }
+        } else {
+            hashes[index] = keccak256(input);
+        }
+    }
+
+    if pending_len > 0 {
+        hash_single_blocks(inputs, &pending[..pending_len], &mut hashes);
+    }
+
+    hashes
+}
+
+/// Hashes the inputs at the given indices, which must all be shorter than [`RATE`], with a single
+/// permutation.
+fn hash_single_blocks<T: AsRef<[u8]>>(inputs: &[T], indices: &[usize], hashes: &mut [B256]) {
+    let mut state = [[0u64; LANES]; 25];
+
+    for (lane, &index) in indices.iter().enumerate() {
+        let input = inputs[index].as_ref();
+        let mut block = [0u8; RATE];
+        block[..input.len()].copy_from_slice(input);
+        block[input.len()] ^= 0x01;
+        block[RATE - 1] ^= 0x80;
+        for (word, chunk) in state.iter_mut().zip(block.chunks_exact(8)) {
+            word[lane] = u64::from_le_bytes(chunk.try_into().unwrap());
+        }
+    }
+
+    keccak_f1600(&mut state);
+
+    for (lane, &index) in indices.iter().enumerate() {
+        for (chunk, word) in hashes[index].0.chunks_exact_mut(8).zip(&state) {
+            chunk.copy_from_slice(&word[lane].to_le_bytes());
+        }
+    }
+}
+
+/// Applies keccak-f\[1600\] to the interleaved states of [`LANES`] inputs.
+#[inline(always)]
+fn keccak_f1600(state: &mut [Lanes; 25]) {
+    for rc in RC {
+        // theta
+        let mut columns = [[0u64; LANES]; 5];
+        for (x, column) in columns.iter_mut().enumerate() {
+            *column = xor5(state[x], state[x + 5], state[x + 10], state[x + 15], state[x + 20]);
+        }
+        for x in 0..5 {
+            let d =
+                zip_with(columns[(x + 4) % 5], columns[(x + 1) % 5], |a, b| a ^ b.rotate_left(1));
+            for y in 0..5 {
+                state[5 * y + x] = zip_with(state[5 * y + x], d, |a, b| a ^ b);
+            }
+        }
+
+        // rho and pi
+        let mut last = state[1];
+        for (&pi, &rho) in PI.iter().zip(&RHO) {
+            let current = state[pi];
+            state[pi] = last.map(|word| word.rotate_left(rho));
+            last = current;
+        }
+
+        // chi
+        for y in 0..5 {
+            let row: [Lanes; 5] = core::array::from_fn(|x| state[5 * y + x]);
+            for x in 0..5 {
+                state[5 * y + x] = core::array::from_fn(|lane| {
+                    row[x][lane] ^ (!row[(x + 1) % 5][lane] & row[(x + 2) % 5][lane])
+                });
+            }
+        }
+
+        // iota
+        state[0] = state[0].map(|word| word ^ rc);
+    }
+}
+
+#[inline(always)]
+fn zip_with(a: Lanes, b: Lanes, f: impl Fn(u64, u64) -> u64) -> Lanes {
+    core::array::from_fn(|lane| f(a[lane], b[lane]))
+}
+
+#[inline(always)]
+fn xor5(a: Lanes, b: Lanes, c: Lanes, d: Lanes, e: Lanes) -> Lanes {
+    core::array::from_fn(|lane| a[lane] ^ b[lane] ^ c[lane] ^ d[lane] ^ e[lane])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::b256;
+    use proptest::prelude::*;
+
+    #[test]
+    fn known_hashes() {
+        let hashes = keccak256_multi_buffer(&[&b""[..], b"abc"]);
+        assert_eq!(
+            hashes,
+            [
+                b256!("c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"),
+                b256!("4e03657aea45a94fc7d47ba826c8d667c0d1e6e33a64a036ec44f58fa12d6c45"),
+            ]
+        );
+    }
+
+    #[test]
+    fn block_boundaries() {
+        // every length around the rate, in a batch that doesn't fill the last group of lanes
+        let inputs = (0..=2 * RATE + 1).map(|len| vec![len as u8; len]).collect::<Vec<_>>();
+        let expected = inputs.iter().map(keccak256).collect::<Vec<_>>();
+        assert_eq!(keccak256_multi_buffer(&inputs), expected);
+        assert_eq!(keccak256_batch(&inputs), expected);
+    }
+
+    proptest! {
+        /// The batched hashes agree with the scalar implementation.
+        #[test]
+        fn agrees_with_scalar(inputs in prop::collection::vec(any::<Vec<u8>>(), 0..4 * LANES)) {
+            let expected = inputs.iter().map(keccak256).collect::<Vec<_>>();
+            prop_assert_eq!(keccak256_multi_buffer(&inputs), expected.clone());
+            prop_assert_eq!(keccak256_batch(&inputs), expected);
+        }
+    }
+}
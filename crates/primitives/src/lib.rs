@@ -9,6 +9,8 @@
 //! - `test-utils`: Export utilities for testing
 //! - `reth-codec`: Enables db codec support for reth types including zstd compression for certain
 //!   types.
+//! - `asm-keccak`: Uses the assembly implementation of keccak256.
+//! - `keccak-multi-buffer`: Hashes short inputs to [`keccak256_batch`] several at a time.
 
 #![doc(
     html_logo_url = "https://raw.githubusercontent.com/paradigmxyz/reth/main/assets/reth-docs.png",
@@ -27,6 +29,9 @@ pub use traits::*;
 #[cfg(feature = "alloy-compat")]
 mod alloy_compat;
 mod block;
+pub mod fee_math;
+pub mod keccak;
+pub use keccak::keccak256_batch;
 pub mod proofs;
 mod receipt;
 pub use reth_static_file_types as static_file;
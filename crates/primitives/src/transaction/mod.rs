@@ -264,27 +264,12 @@ impl Transaction {
     ///
     /// Returns `None` if the basefee is higher than the [`Transaction::max_fee_per_gas`].
     pub fn effective_tip_per_gas(&self, base_fee: Option<u64>) -> Option<u128> {
-        let base_fee = match base_fee {
-            Some(base_fee) => base_fee as u128,
-            None => return Some(self.priority_fee_or_price()),
-        };
-
-        let max_fee_per_gas = self.max_fee_per_gas();
-
-        // Check if max_fee_per_gas is less than base_fee
-        if max_fee_per_gas < base_fee {
-            return None
-        }
-
-        // Calculate the difference between max_fee_per_gas and base_fee
-        let fee = max_fee_per_gas - base_fee;
-
-        // Compare the fee with max_priority_fee_per_gas (or gas price for non-EIP1559 transactions)
-        if let Some(priority_fee) = self.max_priority_fee_per_gas() {
-            Some(fee.min(priority_fee))
-        } else {
-            Some(fee)
-        }
+        let Some(base_fee) = base_fee else { return Some(self.priority_fee_or_price()) };
+        crate::fee_math::effective_gas_tip(
+            self.max_fee_per_gas(),
+            self.max_priority_fee_per_gas(),
+            base_fee,
+        )
     }
 
     /// This encodes the transaction _without_ the signature, and is only suitable for creating a
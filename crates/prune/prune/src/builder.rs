@@ -1,4 +1,7 @@
-use crate::{segments::SegmentSet, Pruner};
+use crate::{
+    segments::{PruneSegmentRegistry, SegmentSet},
+    Pruner,
+};
 use alloy_eips::eip2718::Encodable2718;
 use reth_chainspec::MAINNET;
 use reth_config::PruneConfig;
@@ -9,7 +12,7 @@ use reth_provider::{
     providers::StaticFileProvider, BlockReader, DBProvider, DatabaseProviderFactory,
     NodePrimitivesProvider, PruneCheckpointWriter, StaticFileProviderFactory,
 };
-use reth_prune_types::PruneModes;
+use reth_prune_types::{PruneModes, PruneSegmentError};
 use std::time::Duration;
 use tokio::sync::watch;
 
@@ -26,6 +29,8 @@ pub struct PrunerBuilder {
     timeout: Option<Duration>,
     /// The finished height of all `ExEx`'s.
     finished_exex_height: watch::Receiver<FinishedExExHeight>,
+    /// The signal to pause pruning.
+    pause_signal: Option<watch::Receiver<bool>>,
 }
 
 impl PrunerBuilder {
@@ -75,7 +80,16 @@ impl PrunerBuilder {
         self
     }
 
+    /// Sets the receiver of the signal to pause pruning, e.g. when the disk is almost full.
+    pub fn pause_signal(mut self, pause_signal: watch::Receiver<bool>) -> Self {
+        self.pause_signal = Some(pause_signal);
+        self
+    }
+
     /// Builds a [Pruner] from the current configuration with the given provider factory.
+    ///
+    /// Custom segments aren't pruned, see [`Self::build_with_registry`]. Configured custom segments
+    /// are ignored, so only use this if no custom segments can be registered.
     pub fn build_with_provider_factory<PF>(self, provider_factory: PF) -> Pruner<PF::ProviderRW, PF>
     where
         PF: DatabaseProviderFactory<
@@ -88,17 +102,64 @@ impl PrunerBuilder {
                 Primitives = <PF::ProviderRW as NodePrimitivesProvider>::Primitives,
             >,
     {
-        let segments =
-            SegmentSet::from_components(provider_factory.static_file_provider(), self.segments);
+        let segments = SegmentSet::from_components(
+            provider_factory.static_file_provider(),
+            self.segments.clone(),
+        );
+        self.build_with_segments(provider_factory, segments)
+    }
+
+    /// Builds a [Pruner] from the current configuration with the given provider factory, that also
+    /// prunes the custom segments of the registry that are configured.
+    ///
+    /// Returns an error if a custom segment is configured that isn't registered.
+    pub fn build_with_registry<PF>(
+        mut self,
+        provider_factory: PF,
+        registry: &PruneSegmentRegistry<PF::ProviderRW>,
+    ) -> Result<Pruner<PF::ProviderRW, PF>, PruneSegmentError>
+    where
+        PF: DatabaseProviderFactory<
+                ProviderRW: PruneCheckpointWriter
+                                + BlockReader<Transaction: Encodable2718>
+                                + StaticFileProviderFactory<
+                    Primitives: NodePrimitives<SignedTx: Value, Receipt: Value>,
+                >,
+            > + StaticFileProviderFactory<
+                Primitives = <PF::ProviderRW as NodePrimitivesProvider>::Primitives,
+            >,
+    {
+        let custom = std::mem::take(&mut self.segments.custom);
+        let segments = SegmentSet::from_components(
+            provider_factory.static_file_provider(),
+            self.segments.clone(),
+        )
+        .custom_segments(registry, &custom)?;
+        Ok(self.build_with_segments(provider_factory, segments))
+    }
 
-        Pruner::new_with_factory(
+    /// Builds a [Pruner] from the current configuration with the given provider factory and
+    /// segments.
+    fn build_with_segments<PF>(
+        self,
+        provider_factory: PF,
+        segments: SegmentSet<PF::ProviderRW>,
+    ) -> Pruner<PF::ProviderRW, PF>
+    where
+        PF: DatabaseProviderFactory,
+    {
+        let pruner = Pruner::new_with_factory(
             provider_factory,
             segments.into_vec(),
             self.block_interval,
             self.delete_limit,
             self.timeout,
             self.finished_exex_height,
-        )
+        );
+        match self.pause_signal {
+            Some(pause_signal) => pruner.with_pause_signal(pause_signal),
+            None => pruner,
+        }
     }
 
     /// Builds a [Pruner] from the current configuration with the given static file provider.
@@ -113,14 +174,48 @@ impl PrunerBuilder {
             + PruneCheckpointWriter,
     {
         let segments = SegmentSet::<Provider>::from_components(static_file_provider, self.segments);
+        self.build_with_provider_segments(segments)
+    }
+
+    /// Builds a [Pruner] from the current configuration with the given static file provider, that
+    /// also prunes the custom segments of the registry that are configured.
+    ///
+    /// Returns an error if a custom segment is configured that isn't registered.
+    pub fn build_with_custom_segments<Provider>(
+        mut self,
+        static_file_provider: StaticFileProvider<Provider::Primitives>,
+        registry: &PruneSegmentRegistry<Provider>,
+    ) -> Result<Pruner<Provider, ()>, PruneSegmentError>
+    where
+        Provider: StaticFileProviderFactory<Primitives: NodePrimitives<SignedTx: Value, Receipt: Value>>
+            + DBProvider<Tx: DbTxMut>
+            + BlockReader<Transaction: Encodable2718>
+            + PruneCheckpointWriter,
+    {
+        let custom = std::mem::take(&mut self.segments.custom);
+        let segments =
+            SegmentSet::<Provider>::from_components(static_file_provider, self.segments.clone())
+                .custom_segments(registry, &custom)?;
+        Ok(self.build_with_provider_segments(segments))
+    }
 
-        Pruner::new(
+    /// Builds a [Pruner] from the current configuration with the given segments, that's run with a
+    /// provider.
+    fn build_with_provider_segments<Provider>(
+        self,
+        segments: SegmentSet<Provider>,
+    ) -> Pruner<Provider, ()> {
+        let pruner = Pruner::new(
             segments.into_vec(),
             self.block_interval,
             self.delete_limit,
             self.timeout,
             self.finished_exex_height,
-        )
+        );
+        match self.pause_signal {
+            Some(pause_signal) => pruner.with_pause_signal(pause_signal),
+            None => pruner,
+        }
     }
 }
 
@@ -132,6 +227,7 @@ impl Default for PrunerBuilder {
             delete_limit: MAINNET.prune_delete_limit,
             timeout: None,
             finished_exex_height: watch::channel(FinishedExExHeight::NoExExs).1,
+            pause_signal: None,
         }
     }
 }
@@ -9,7 +9,8 @@ use reth_db::{
 };
 use tracing::debug;
 
-pub(crate) trait DbTxPruneExt: DbTxMut {
+/// Extension of [`DbTxMut`] to prune tables within the limits of a [`PruneLimiter`].
+pub trait DbTxPruneExt: DbTxMut {
     /// Prune the table for the specified pre-sorted key iterator.
     ///
     /// Returns number of rows pruned.
@@ -19,6 +19,7 @@ pub mod segments;
 
 use crate::metrics::Metrics;
 pub use builder::PrunerBuilder;
+pub use db_ext::DbTxPruneExt;
 pub use error::PrunerError;
 pub use limiter::PruneLimiter;
 pub use pruner::{Pruner, PrunerResult, PrunerWithFactory, PrunerWithResult};
@@ -2,7 +2,7 @@ use reth_metrics::{
     metrics::{Gauge, Histogram},
     Metrics,
 };
-use reth_prune_types::PruneSegment;
+use reth_prune_types::PruneSegmentId;
 use std::collections::HashMap;
 
 #[derive(Metrics)]
@@ -11,15 +11,15 @@ pub(crate) struct Metrics {
     /// Pruning duration
     pub(crate) duration_seconds: Histogram,
     #[metric(skip)]
-    prune_segments: HashMap<PruneSegment, PrunerSegmentMetrics>,
+    prune_segments: HashMap<PruneSegmentId, PrunerSegmentMetrics>,
 }
 
 impl Metrics {
     /// Returns existing or initializes a new instance of [`PrunerSegmentMetrics`] for the provided
-    /// [`PruneSegment`].
+    /// [`PruneSegmentId`].
     pub(crate) fn get_prune_segment_metrics(
         &mut self,
-        segment: PruneSegment,
+        segment: PruneSegmentId,
     ) -> &mut PrunerSegmentMetrics {
         self.prune_segments.entry(segment).or_insert_with(|| {
             PrunerSegmentMetrics::new_with_labels(&[("segment", segment.to_string())])
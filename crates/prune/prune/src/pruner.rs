@@ -43,6 +43,8 @@ pub struct Pruner<Provider, PF> {
     timeout: Option<Duration>,
     /// The finished height of all `ExEx`'s.
     finished_exex_height: watch::Receiver<FinishedExExHeight>,
+    /// The signal to pause pruning, e.g. when the disk is almost full.
+    pause_signal: Option<watch::Receiver<bool>>,
     #[doc(hidden)]
     metrics: Metrics,
     event_sender: EventSender<PrunerEvent>,
@@ -65,6 +67,7 @@ impl<Provider> Pruner<Provider, ()> {
             delete_limit,
             timeout,
             finished_exex_height,
+            pause_signal: None,
             metrics: Metrics::default(),
             event_sender: Default::default(),
         }
@@ -92,12 +95,21 @@ where
             delete_limit,
             timeout,
             finished_exex_height,
+            pause_signal: None,
             metrics: Metrics::default(),
             event_sender: Default::default(),
         }
     }
 }
 
+impl<Provider, PF> Pruner<Provider, PF> {
+    /// Sets the signal to pause pruning. While the signal is `true`, no pruning is needed.
+    pub fn with_pause_signal(mut self, pause_signal: watch::Receiver<bool>) -> Self {
+        self.pause_signal = Some(pause_signal);
+        self
+    }
+}
+
 impl<Provider, S> Pruner<Provider, S>
 where
     Provider: PruneCheckpointReader + PruneCheckpointWriter,
@@ -210,7 +222,7 @@ where
                 );
 
                 let segment_start = Instant::now();
-                let previous_checkpoint = provider.get_prune_checkpoint(segment.segment())?;
+                let previous_checkpoint = segment.checkpoint(provider)?;
                 let segment_output = segment.prune(
                     provider,
                     PruneInput { previous_checkpoint, to_block, limiter: limiter.clone() },
@@ -266,6 +278,11 @@ where
     /// Returns `true` if the pruning is needed at the provided tip block number.
     /// This determined by the check against minimum pruning interval and last pruned block number.
     pub fn is_pruning_needed(&self, tip_block_number: BlockNumber) -> bool {
+        if self.pause_signal.as_ref().is_some_and(|paused| *paused.borrow()) {
+            debug!(target: "pruner", %tip_block_number, "Pruning is paused");
+            return false
+        }
+
         let Some(tip_block_number) =
             self.adjust_tip_block_number_to_finished_exex_height(tip_block_number)
         else {
@@ -376,5 +393,14 @@ mod tests {
         // Adjust tip block number to the finished ExEx height that reaches the threshold
         finished_exex_height_tx.send(FinishedExExHeight::Height(third_block_number)).unwrap();
         assert!(pruner.is_pruning_needed(third_block_number));
+
+        // Pruning is paused
+        let (pause_tx, pause_rx) = tokio::sync::watch::channel(true);
+        let pruner = pruner.with_pause_signal(pause_rx);
+        assert!(!pruner.is_pruning_needed(third_block_number));
+
+        // Pruning is resumed
+        pause_tx.send(false).unwrap();
+        assert!(pruner.is_pruning_needed(third_block_number));
     }
 }
@@ -1,12 +1,16 @@
 mod receipts;
+mod registry;
 mod set;
 mod static_file;
 mod user;
 
 use crate::{PruneLimiter, PrunerError};
 use alloy_primitives::{BlockNumber, TxNumber};
-use reth_provider::{errors::provider::ProviderResult, BlockReader, PruneCheckpointWriter};
-use reth_prune_types::{PruneCheckpoint, PruneMode, PrunePurpose, PruneSegment, SegmentOutput};
+pub use registry::PruneSegmentRegistry;
+use reth_provider::{
+    errors::provider::ProviderResult, BlockReader, PruneCheckpointReader, PruneCheckpointWriter,
+};
+use reth_prune_types::{PruneCheckpoint, PruneMode, PrunePurpose, PruneSegmentId, SegmentOutput};
 pub use set::SegmentSet;
 pub use static_file::{
     Headers as StaticFileHeaders, Receipts as StaticFileReceipts,
@@ -15,20 +19,27 @@ pub use static_file::{
 use std::{fmt::Debug, ops::RangeInclusive};
 use tracing::error;
 pub use user::{
-    AccountHistory, Receipts as UserReceipts, ReceiptsByLogs, SenderRecovery, StorageHistory,
-    TransactionLookup,
+    AccountHistory, LogAddressIndex, Receipts as UserReceipts, ReceiptsByLogs, SenderRecovery,
+    StorageHistory, TransactionLookup,
 };
 
 /// A segment represents a pruning of some portion of the data.
 ///
 /// Segments are called from [`Pruner`](crate::Pruner) with the following lifecycle:
-/// 1. Call [`Segment::prune`] with `delete_limit` of [`PruneInput`].
+/// 1. Call [`Segment::prune`] with `delete_limit` of [`PruneInput`] and the checkpoint returned by
+///    [`Segment::checkpoint`].
 /// 2. If [`Segment::prune`] returned a [`Some`] in `checkpoint` of [`SegmentOutput`], call
 ///    [`Segment::save_checkpoint`].
 /// 3. Subtract `pruned` of [`SegmentOutput`] from `delete_limit` of next [`PruneInput`].
+///
+/// Besides the built-in segments, node builders can prune their own tables with custom segments
+/// that are registered in a [`PruneSegmentRegistry`]. A custom segment identifies as
+/// [`PruneSegmentId::Custom`] with the name it's registered and configured with, and its
+/// checkpoints are saved by that name. When the chain is unwound, the checkpoints of custom
+/// segments are moved to the unwind target like the built-in ones, so pruning resumes from there.
 pub trait Segment<Provider>: Debug + Send + Sync {
-    /// Segment of data that's pruned.
-    fn segment(&self) -> PruneSegment;
+    /// Segment of data that's pruned, under which its checkpoint is saved.
+    fn segment(&self) -> PruneSegmentId;
 
     /// Prune mode with which the segment was initialized.
     fn mode(&self) -> Option<PruneMode>;
@@ -39,6 +50,17 @@ pub trait Segment<Provider>: Debug + Send + Sync {
     /// Prune data for [`Self::segment`] using the provided input.
     fn prune(&self, provider: &Provider, input: PruneInput) -> Result<SegmentOutput, PrunerError>;
 
+    /// Fetch checkpoint for [`Self::segment`] from the database.
+    fn checkpoint(&self, provider: &Provider) -> ProviderResult<Option<PruneCheckpoint>>
+    where
+        Provider: PruneCheckpointReader,
+    {
+        match self.segment() {
+            PruneSegmentId::Builtin(segment) => provider.get_prune_checkpoint(segment),
+            PruneSegmentId::Custom(name) => provider.get_custom_prune_checkpoint(name),
+        }
+    }
+
     /// Save checkpoint for [`Self::segment`] to the database.
     fn save_checkpoint(
         &self,
@@ -48,7 +70,10 @@ pub trait Segment<Provider>: Debug + Send + Sync {
     where
         Provider: PruneCheckpointWriter,
     {
-        provider.save_prune_checkpoint(self.segment(), checkpoint)
+        match self.segment() {
+            PruneSegmentId::Builtin(segment) => provider.save_prune_checkpoint(segment, checkpoint),
+            PruneSegmentId::Custom(name) => provider.save_custom_prune_checkpoint(name, checkpoint),
+        }
     }
 }
 
@@ -56,11 +81,12 @@ pub trait Segment<Provider>: Debug + Send + Sync {
 #[derive(Debug)]
 #[cfg_attr(test, derive(Clone))]
 pub struct PruneInput {
-    pub(crate) previous_checkpoint: Option<PruneCheckpoint>,
+    /// Checkpoint of the previous pruning of the segment, if any.
+    pub previous_checkpoint: Option<PruneCheckpoint>,
     /// Target block up to which the pruning needs to be done, inclusive.
-    pub(crate) to_block: BlockNumber,
+    pub to_block: BlockNumber,
     /// Limits pruning of a segment.
-    pub(crate) limiter: PruneLimiter,
+    pub limiter: PruneLimiter,
 }
 
 impl PruneInput {
@@ -72,7 +98,7 @@ impl PruneInput {
     /// 2. If checkpoint doesn't exist, return 0.
     ///
     /// To get the range end: get last tx number for `to_block`.
-    pub(crate) fn get_next_tx_num_range<Provider: BlockReader>(
+    pub fn get_next_tx_num_range<Provider: BlockReader>(
         &self,
         provider: &Provider,
     ) -> ProviderResult<Option<RangeInclusive<TxNumber>>> {
@@ -118,7 +144,7 @@ impl PruneInput {
     /// 2. If checkpoint doesn't exist, use block 0.
     ///
     /// To get the range end: use block `to_block`.
-    pub(crate) fn get_next_block_range(&self) -> Option<RangeInclusive<BlockNumber>> {
+    pub fn get_next_block_range(&self) -> Option<RangeInclusive<BlockNumber>> {
         let from_block = self.get_start_next_block_range();
         let range = from_block..=self.to_block;
         if range.is_empty() {
@@ -132,7 +158,7 @@ impl PruneInput {
     ///
     /// 1. If checkpoint exists, use next block.
     /// 2. If checkpoint doesn't exist, use block 0.
-    pub(crate) fn get_start_next_block_range(&self) -> u64 {
+    pub fn get_start_next_block_range(&self) -> u64 {
         self.previous_checkpoint
             .and_then(|checkpoint| checkpoint.block_number)
             // Checkpoint exists, prune from the next block after the highest pruned one
@@ -150,7 +176,7 @@ mod tests {
         providers::BlockchainProvider2,
         test_utils::{create_test_provider_factory, MockEthProvider},
     };
-    use reth_testing_utils::generators::{self, random_block_range, BlockRangeParams};
+    use reth_testing_utils::TestBlockBuilder;
 
     #[test]
     fn test_prune_input_get_next_tx_num_range_no_to_block() {
@@ -176,24 +202,16 @@ mod tests {
             limiter: PruneLimiter::default(),
         };
 
-        let mut rng = generators::rng();
         let factory = create_test_provider_factory();
 
-        // Generate 10 random blocks with no transactions
-        let blocks = random_block_range(
-            &mut rng,
-            0..=10,
-            BlockRangeParams { parent: Some(B256::ZERO), tx_count: 0..1, ..Default::default() },
-        );
+        // Generate 11 blocks with no transactions
+        let blocks =
+            TestBlockBuilder::default().with_txs_per_block(0).starting_at(0, B256::ZERO).extend(11);
 
         // Insert the blocks into the database
         let provider_rw = factory.provider_rw().expect("failed to get provider_rw");
         for block in &blocks {
-            provider_rw
-                .insert_historical_block(
-                    block.clone().seal_with_senders().expect("failed to seal block with senders"),
-                )
-                .expect("failed to insert block");
+            provider_rw.insert_historical_block(block.clone()).expect("failed to insert block");
         }
         provider_rw.commit().expect("failed to commit");
 
@@ -214,24 +232,16 @@ mod tests {
             limiter: PruneLimiter::default(),
         };
 
-        let mut rng = generators::rng();
         let factory = create_test_provider_factory();
 
-        // Generate 10 random blocks with some transactions
-        let blocks = random_block_range(
-            &mut rng,
-            0..=10,
-            BlockRangeParams { parent: Some(B256::ZERO), tx_count: 0..5, ..Default::default() },
-        );
+        // Generate 11 blocks with some transactions
+        let blocks =
+            TestBlockBuilder::default().with_txs_per_block(2).starting_at(0, B256::ZERO).extend(11);
 
         // Insert the blocks into the database
         let provider_rw = factory.provider_rw().expect("failed to get provider_rw");
         for block in &blocks {
-            provider_rw
-                .insert_historical_block(
-                    block.clone().seal_with_senders().expect("failed to seal block with senders"),
-                )
-                .expect("failed to insert block");
+            provider_rw.insert_historical_block(block.clone()).expect("failed to insert block");
         }
         provider_rw.commit().expect("failed to commit");
 
@@ -260,24 +270,16 @@ mod tests {
             limiter: PruneLimiter::default(),
         };
 
-        let mut rng = generators::rng();
         let factory = create_test_provider_factory();
 
-        // Generate 10 random blocks
-        let blocks = random_block_range(
-            &mut rng,
-            0..=10,
-            BlockRangeParams { parent: Some(B256::ZERO), tx_count: 0..5, ..Default::default() },
-        );
+        // Generate 11 blocks with some transactions
+        let blocks =
+            TestBlockBuilder::default().with_txs_per_block(2).starting_at(0, B256::ZERO).extend(11);
 
         // Insert the blocks into the database
         let provider_rw = factory.provider_rw().expect("failed to get provider_rw");
         for block in &blocks {
-            provider_rw
-                .insert_historical_block(
-                    block.clone().seal_with_senders().expect("failed to seal block with senders"),
-                )
-                .expect("failed to insert block");
+            provider_rw.insert_historical_block(block.clone()).expect("failed to insert block");
         }
         provider_rw.commit().expect("failed to commit");
 
@@ -296,24 +298,16 @@ mod tests {
     #[test]
     fn test_prune_input_get_next_tx_empty_range() {
         // Create a new provider via factory
-        let mut rng = generators::rng();
         let factory = create_test_provider_factory();
 
-        // Generate 10 random blocks
-        let blocks = random_block_range(
-            &mut rng,
-            0..=10,
-            BlockRangeParams { parent: Some(B256::ZERO), tx_count: 0..5, ..Default::default() },
-        );
+        // Generate 11 blocks with some transactions
+        let blocks =
+            TestBlockBuilder::default().with_txs_per_block(2).starting_at(0, B256::ZERO).extend(11);
 
         // Insert the blocks into the database
         let provider_rw = factory.provider_rw().expect("failed to get provider_rw");
         for block in &blocks {
-            provider_rw
-                .insert_historical_block(
-                    block.clone().seal_with_senders().expect("failed to seal block with senders"),
-                )
-                .expect("failed to insert block");
+            provider_rw.insert_historical_block(block.clone()).expect("failed to insert block");
         }
         provider_rw.commit().expect("failed to commit");
 
@@ -82,61 +82,54 @@ pub(crate) fn save_checkpoint(
 #[cfg(test)]
 mod tests {
     use crate::segments::{PruneInput, PruneLimiter, SegmentOutput};
-    use alloy_primitives::{BlockNumber, TxNumber, B256};
+    use alloy_primitives::{BlockNumber, TxNumber};
     use assert_matches::assert_matches;
     use itertools::{
         FoldWhile::{Continue, Done},
         Itertools,
     };
     use reth_db::tables;
-    use reth_provider::{DatabaseProviderFactory, PruneCheckpointReader};
+    use reth_provider::{
+        test_utils::{TestChainParams, TestDatadir},
+        DatabaseProviderFactory, PruneCheckpointReader, StorageLocation,
+    };
     use reth_prune_types::{
         PruneCheckpoint, PruneInterruptReason, PruneMode, PruneProgress, PruneSegment,
     };
-    use reth_stages::test_utils::{StorageKind, TestStageDB};
-    use reth_testing_utils::generators::{
-        self, random_block_range, random_receipt, BlockRangeParams,
-    };
     use std::ops::Sub;
 
     #[test]
     fn prune() {
-        let db = TestStageDB::default();
-        let mut rng = generators::rng();
-
-        let blocks = random_block_range(
-            &mut rng,
-            1..=10,
-            BlockRangeParams { parent: Some(B256::ZERO), tx_count: 2..3, ..Default::default() },
-        );
-        db.insert_blocks(blocks.iter(), StorageKind::Database(None)).expect("insert blocks");
-
-        let mut receipts = Vec::new();
-        for block in &blocks {
-            receipts.reserve_exact(block.body.transactions.len());
-            for transaction in &block.body.transactions {
-                receipts
-                    .push((receipts.len() as u64, random_receipt(&mut rng, transaction, Some(0))));
-            }
-        }
-        let receipts_len = receipts.len();
-        db.insert_receipts(receipts).expect("insert receipts");
+        let datadir = TestDatadir::new(TestChainParams {
+            blocks: 10,
+            tx_count: 2..3,
+            blob_tx_count: 0..0,
+            withdrawals_count: None,
+            logs_count: 0,
+            storage: StorageLocation::Database,
+            ..Default::default()
+        })
+        .expect("create datadir");
+        // skip the empty genesis block
+        let blocks = &datadir.blocks()[1..];
+
+        let receipts_len = datadir.table::<tables::Receipts>().unwrap().len();
 
         assert_eq!(
-            db.table::<tables::Transactions>().unwrap().len(),
+            datadir.table::<tables::Transactions>().unwrap().len(),
             blocks.iter().map(|block| block.body.transactions.len()).sum::<usize>()
         );
         assert_eq!(
-            db.table::<tables::Transactions>().unwrap().len(),
-            db.table::<tables::Receipts>().unwrap().len()
+            datadir.table::<tables::Transactions>().unwrap().len(),
+            datadir.table::<tables::Receipts>().unwrap().len()
         );
 
         let test_prune = |to_block: BlockNumber, expected_result: (PruneProgress, usize)| {
             let prune_mode = PruneMode::Before(to_block);
             let mut limiter = PruneLimiter::default().set_deleted_entries_limit(10);
             let input = PruneInput {
-                previous_checkpoint: db
-                    .factory
+                previous_checkpoint: datadir
+                    .provider_factory()
                     .provider()
                     .unwrap()
                     .get_prune_checkpoint(PruneSegment::Receipts)
@@ -145,8 +138,8 @@ mod tests {
                 limiter: limiter.clone(),
             };
 
-            let next_tx_number_to_prune = db
-                .factory
+            let next_tx_number_to_prune = datadir
+                .provider_factory()
                 .provider()
                 .unwrap()
                 .get_prune_checkpoint(PruneSegment::Receipts)
@@ -166,7 +159,7 @@ mod tests {
                 )
                 .sub(1);
 
-            let provider = db.factory.database_provider_rw().unwrap();
+            let provider = datadir.provider_factory().database_provider_rw().unwrap();
             let result = super::prune(&provider, input).unwrap();
             limiter.increment_deleted_entries_count_by(result.pruned);
 
@@ -199,11 +192,12 @@ mod tests {
                 .checked_sub(if result.progress.is_finished() { 0 } else { 1 });
 
             assert_eq!(
-                db.table::<tables::Receipts>().unwrap().len(),
+                datadir.table::<tables::Receipts>().unwrap().len(),
                 receipts_len - (last_pruned_tx_number + 1)
             );
             assert_eq!(
-                db.factory
+                datadir
+                    .provider_factory()
                     .provider()
                     .unwrap()
                     .get_prune_checkpoint(PruneSegment::Receipts)
@@ -0,0 +1,86 @@
+use crate::segments::Segment;
+use reth_prune_types::{PruneMode, PruneSegmentError, PruneSegmentId};
+use std::{collections::BTreeMap, fmt, sync::Arc};
+
+/// Builds a custom [`Segment`] with the prune mode it's configured with.
+type SegmentFactory<Provider> = Arc<dyn Fn(PruneMode) -> Box<dyn Segment<Provider>> + Send + Sync>;
+
+/// Registry of the custom [`Segment`]s that node builders add to the pruner, by the name that
+/// configures them in the `[prune.segments.custom]` section of the config:
+///
+/// ```toml
+/// [prune.segments.custom]
+/// deposit_events = { distance = 10064 }
+/// ```
+///
+/// Only the registered segments that are configured are built and pruned, after the built-in ones.
+/// The registry is cheap to clone, so the pruner of the node and the prune stage of the pipeline
+/// can each build the segments.
+pub struct PruneSegmentRegistry<Provider> {
+    segments: BTreeMap<&'static str, SegmentFactory<Provider>>,
+}
+
+impl<Provider> PruneSegmentRegistry<Provider> {
+    /// Returns an empty [`PruneSegmentRegistry`].
+    pub const fn new() -> Self {
+        Self { segments: BTreeMap::new() }
+    }
+
+    /// Registers a custom segment under the given name, which is built with the prune mode it's
+    /// configured with.
+    ///
+    /// The segment has to identify as [`PruneSegmentId::Custom`] with the same name. A segment
+    /// that's already registered under the name is replaced.
+    pub fn register<S, F>(&mut self, name: &'static str, segment: F)
+    where
+        S: Segment<Provider> + 'static,
+        F: Fn(PruneMode) -> S + Send + Sync + 'static,
+    {
+        self.segments.insert(
+            name,
+            Arc::new(move |mode| Box::new(segment(mode)) as Box<dyn Segment<Provider>>),
+        );
+    }
+
+    /// Builds the registered segments that are configured with the given prune modes, by the name
+    /// of the segment.
+    ///
+    /// Returns an error if a prune mode is configured for a segment that isn't registered, or if a
+    /// segment doesn't identify as [`PruneSegmentId::Custom`] with the name it's registered with.
+    pub fn build(
+        &self,
+        modes: &BTreeMap<String, PruneMode>,
+    ) -> Result<Vec<Box<dyn Segment<Provider>>>, PruneSegmentError> {
+        modes
+            .iter()
+            .map(|(name, mode)| {
+                let Some((&name, factory)) = self.segments.get_key_value(name.as_str()) else {
+                    return Err(PruneSegmentError::Unregistered(name.clone()))
+                };
+                let segment = factory(*mode);
+                if segment.segment() != PruneSegmentId::Custom(name) {
+                    return Err(PruneSegmentError::MismatchedId { name, id: segment.segment() })
+                }
+                Ok(segment)
+            })
+            .collect()
+    }
+}
+
+impl<Provider> Clone for PruneSegmentRegistry<Provider> {
+    fn clone(&self) -> Self {
+        Self { segments: self.segments.clone() }
+    }
+}
+
+impl<Provider> Default for PruneSegmentRegistry<Provider> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<Provider> fmt::Debug for PruneSegmentRegistry<Provider> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PruneSegmentRegistry").field("segments", &self.segments.keys()).finish()
+    }
+}
@@ -1,6 +1,6 @@
 use crate::segments::{
-    AccountHistory, ReceiptsByLogs, Segment, SenderRecovery, StorageHistory, TransactionLookup,
-    UserReceipts,
+    AccountHistory, LogAddressIndex, PruneSegmentRegistry, ReceiptsByLogs, Segment, SenderRecovery,
+    StorageHistory, TransactionLookup, UserReceipts,
 };
 use alloy_eips::eip2718::Encodable2718;
 use reth_db::{table::Value, transaction::DbTxMut};
@@ -9,7 +9,8 @@ use reth_provider::{
     providers::StaticFileProvider, BlockReader, DBProvider, PruneCheckpointWriter,
     StaticFileProviderFactory,
 };
-use reth_prune_types::PruneModes;
+use reth_prune_types::{PruneMode, PruneModes, PruneSegmentError};
+use std::collections::BTreeMap;
 
 use super::{StaticFileHeaders, StaticFileReceipts, StaticFileTransactions};
 
@@ -39,6 +40,19 @@ impl<Provider> SegmentSet<Provider> {
         self
     }
 
+    /// Adds the custom segments of the registry that are configured with the given prune modes, by
+    /// the name of the segment.
+    ///
+    /// Returns an error if a prune mode is configured for a segment that isn't registered.
+    pub fn custom_segments(
+        mut self,
+        registry: &PruneSegmentRegistry<Provider>,
+        modes: &BTreeMap<String, PruneMode>,
+    ) -> Result<Self, PruneSegmentError> {
+        self.inner.extend(registry.build(modes)?);
+        Ok(self)
+    }
+
     /// Consumes [`SegmentSet`] and returns a [Vec].
     pub fn into_vec(self) -> Vec<Box<dyn Segment<Provider>>> {
         self.inner
@@ -54,6 +68,8 @@ where
 {
     /// Creates a [`SegmentSet`] from an existing components, such as [`StaticFileProvider`] and
     /// [`PruneModes`].
+    ///
+    /// Custom segments are added with [`SegmentSet::custom_segments`].
     pub fn from_components(
         static_file_provider: StaticFileProvider<Provider::Primitives>,
         prune_modes: PruneModes,
@@ -65,6 +81,7 @@ where
             account_history,
             storage_history,
             receipts_log_filter,
+            custom: _,
         } = prune_modes;
 
         Self::default()
@@ -78,6 +95,8 @@ where
             .segment_opt(account_history.map(AccountHistory::new))
             // Storage history
             .segment_opt(storage_history.map(StorageHistory::new))
+            // Log address index, reads the receipts so it has to be pruned before them
+            .segment_opt(receipts.map(LogAddressIndex::new))
             // User receipts
             .segment_opt(receipts.map(UserReceipts::new))
             // Receipts by logs
@@ -14,7 +14,7 @@ use reth_db::{
 };
 use reth_provider::{providers::StaticFileProvider, DBProvider, StaticFileProviderFactory};
 use reth_prune_types::{
-    PruneMode, PrunePurpose, PruneSegment, SegmentOutput, SegmentOutputCheckpoint,
+    PruneMode, PrunePurpose, PruneSegment, PruneSegmentId, SegmentOutput, SegmentOutputCheckpoint,
 };
 use reth_static_file_types::StaticFileSegment;
 use tracing::trace;
@@ -36,8 +36,8 @@ impl<N> Headers<N> {
 impl<Provider: StaticFileProviderFactory + DBProvider<Tx: DbTxMut>> Segment<Provider>
     for Headers<Provider::Primitives>
 {
-    fn segment(&self) -> PruneSegment {
-        PruneSegment::Headers
+    fn segment(&self) -> PruneSegmentId {
+        PruneSegment::Headers.into()
     }
 
     fn mode(&self) -> Option<PruneMode> {
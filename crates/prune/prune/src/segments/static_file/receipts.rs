@@ -8,7 +8,9 @@ use reth_provider::{
     errors::provider::ProviderResult, providers::StaticFileProvider, BlockReader, DBProvider,
     PruneCheckpointWriter, StaticFileProviderFactory, TransactionsProvider,
 };
-use reth_prune_types::{PruneCheckpoint, PruneMode, PrunePurpose, PruneSegment, SegmentOutput};
+use reth_prune_types::{
+    PruneCheckpoint, PruneMode, PrunePurpose, PruneSegment, PruneSegmentId, SegmentOutput,
+};
 use reth_static_file_types::StaticFileSegment;
 
 #[derive(Debug)]
@@ -30,8 +32,8 @@ where
         + TransactionsProvider
         + BlockReader,
 {
-    fn segment(&self) -> PruneSegment {
-        PruneSegment::Receipts
+    fn segment(&self) -> PruneSegmentId {
+        PruneSegment::Receipts.into()
     }
 
     fn mode(&self) -> Option<PruneMode> {
@@ -10,7 +10,7 @@ use reth_provider::{
     TransactionsProvider,
 };
 use reth_prune_types::{
-    PruneMode, PrunePurpose, PruneSegment, SegmentOutput, SegmentOutputCheckpoint,
+    PruneMode, PrunePurpose, PruneSegment, PruneSegmentId, SegmentOutput, SegmentOutputCheckpoint,
 };
 use reth_static_file_types::StaticFileSegment;
 use tracing::trace;
@@ -33,8 +33,8 @@ where
         + BlockReader
         + StaticFileProviderFactory<Primitives: NodePrimitives<SignedTx: Value>>,
 {
-    fn segment(&self) -> PruneSegment {
-        PruneSegment::Transactions
+    fn segment(&self) -> PruneSegmentId {
+        PruneSegment::Transactions.into()
     }
 
     fn mode(&self) -> Option<PruneMode> {
@@ -92,7 +92,7 @@ where
 #[cfg(test)]
 mod tests {
     use crate::segments::{PruneInput, PruneLimiter, Segment};
-    use alloy_primitives::{BlockNumber, TxNumber, B256};
+    use alloy_primitives::{BlockNumber, TxNumber};
     use assert_matches::assert_matches;
     use itertools::{
         FoldWhile::{Continue, Done},
@@ -108,20 +108,16 @@ mod tests {
         SegmentOutput,
     };
     use reth_stages::test_utils::{StorageKind, TestStageDB};
-    use reth_testing_utils::generators::{self, random_block_range, BlockRangeParams};
+    use reth_testing_utils::TestBlockBuilder;
     use std::ops::Sub;
 
     #[test]
     fn prune() {
         let db = TestStageDB::default();
-        let mut rng = generators::rng();
 
-        let blocks = random_block_range(
-            &mut rng,
-            1..=100,
-            BlockRangeParams { parent: Some(B256::ZERO), tx_count: 2..3, ..Default::default() },
-        );
-        db.insert_blocks(blocks.iter(), StorageKind::Database(None)).expect("insert blocks");
+        let blocks = TestBlockBuilder::default().with_txs_per_block(2).extend(100);
+        db.insert_blocks(blocks.iter().map(|block| &block.block), StorageKind::Database(None))
+            .expect("insert blocks");
 
         let transactions =
             blocks.iter().flat_map(|block| &block.body.transactions).collect::<Vec<_>>();
@@ -8,7 +8,7 @@ use reth_db::{tables, transaction::DbTxMut};
 use reth_db_api::models::ShardedKey;
 use reth_provider::DBProvider;
 use reth_prune_types::{
-    PruneMode, PrunePurpose, PruneSegment, SegmentOutput, SegmentOutputCheckpoint,
+    PruneMode, PrunePurpose, PruneSegment, PruneSegmentId, SegmentOutput, SegmentOutputCheckpoint,
 };
 use rustc_hash::FxHashMap;
 use tracing::{instrument, trace};
@@ -34,8 +34,8 @@ impl<Provider> Segment<Provider> for AccountHistory
 where
     Provider: DBProvider<Tx: DbTxMut>,
 {
-    fn segment(&self) -> PruneSegment {
-        PruneSegment::AccountHistory
+    fn segment(&self) -> PruneSegmentId {
+        PruneSegment::AccountHistory.into()
     }
 
     fn mode(&self) -> Option<PruneMode> {
@@ -131,7 +131,7 @@ mod tests {
         user::account_history::ACCOUNT_HISTORY_TABLES_TO_PRUNE, AccountHistory, PruneInput,
         PruneLimiter, Segment, SegmentOutput,
     };
-    use alloy_primitives::{BlockNumber, B256};
+    use alloy_primitives::BlockNumber;
     use assert_matches::assert_matches;
     use reth_db::{tables, BlockNumberList};
     use reth_provider::{DatabaseProviderFactory, PruneCheckpointReader};
@@ -139,8 +139,9 @@ mod tests {
         PruneCheckpoint, PruneInterruptReason, PruneMode, PruneProgress, PruneSegment,
     };
     use reth_stages::test_utils::{StorageKind, TestStageDB};
-    use reth_testing_utils::generators::{
-        self, random_block_range, random_changeset_range, random_eoa_accounts, BlockRangeParams,
+    use reth_testing_utils::{
+        generators::{self, random_changeset_range, random_eoa_accounts},
+        TestBlockBuilder,
     };
     use std::{collections::BTreeMap, ops::AddAssign};
 
@@ -149,18 +150,15 @@ mod tests {
         let db = TestStageDB::default();
         let mut rng = generators::rng();
 
-        let blocks = random_block_range(
-            &mut rng,
-            1..=5000,
-            BlockRangeParams { parent: Some(B256::ZERO), tx_count: 0..1, ..Default::default() },
-        );
-        db.insert_blocks(blocks.iter(), StorageKind::Database(None)).expect("insert blocks");
+        let blocks = TestBlockBuilder::default().extend(5000);
+        db.insert_blocks(blocks.iter().map(|block| &block.block), StorageKind::Database(None))
+            .expect("insert blocks");
 
         let accounts = random_eoa_accounts(&mut rng, 2).into_iter().collect::<BTreeMap<_, _>>();
 
         let (changesets, _) = random_changeset_range(
             &mut rng,
-            blocks.iter(),
+            blocks.iter().map(|block| &block.block),
             accounts.into_iter().map(|(addr, acc)| (addr, (acc, Vec::new()))),
             0..0,
             0..0,
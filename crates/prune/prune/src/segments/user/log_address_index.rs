@@ -0,0 +1,123 @@
+use crate::{
+    segments::{user::history::prune_history_indices, PruneInput, Segment},
+    PrunerError,
+};
+use alloy_consensus::TxReceipt;
+use itertools::Itertools;
+use reth_db::{tables, transaction::DbTxMut};
+use reth_db_api::{models::ShardedKey, transaction::DbTx};
+use reth_provider::{BlockReader, DBProvider};
+use reth_prune_types::{
+    PruneMode, PrunePurpose, PruneSegment, PruneSegmentId, SegmentOutput, SegmentOutputCheckpoint,
+};
+use rustc_hash::FxHashMap;
+use tracing::{instrument, trace};
+
+/// Prunes the [`tables::LogAddressIndex`] along with the receipts it was built from.
+///
+/// The addresses to prune are read from the receipts of the pruned blocks, so this segment has
+/// to run before the receipts are pruned.
+#[derive(Debug)]
+pub struct LogAddressIndex {
+    mode: PruneMode,
+}
+
+impl LogAddressIndex {
+    pub const fn new(mode: PruneMode) -> Self {
+        Self { mode }
+    }
+}
+
+impl<Provider> Segment<Provider> for LogAddressIndex
+where
+    Provider: DBProvider<Tx: DbTxMut> + BlockReader,
+{
+    fn segment(&self) -> PruneSegmentId {
+        PruneSegment::LogAddressIndex.into()
+    }
+
+    fn mode(&self) -> Option<PruneMode> {
+        Some(self.mode)
+    }
+
+    fn purpose(&self) -> PrunePurpose {
+        PrunePurpose::User
+    }
+
+    #[instrument(level = "trace", target = "pruner", skip(self, provider), ret)]
+    fn prune(&self, provider: &Provider, input: PruneInput) -> Result<SegmentOutput, PrunerError> {
+        let range = match input.get_next_block_range() {
+            Some(range) => range,
+            None => {
+                trace!(target: "pruner", "No log address index to prune");
+                return Ok(SegmentOutput::done())
+            }
+        };
+        let range_end = *range.end();
+
+        // The index is optional, don't read the receipts if it's not maintained.
+        if provider.tx_ref().entries::<tables::LogAddressIndex>()? == 0 {
+            return Ok(SegmentOutput {
+                progress: input.limiter.progress(true),
+                pruned: 0,
+                checkpoint: Some(SegmentOutputCheckpoint {
+                    block_number: Some(range_end),
+                    tx_number: None,
+                }),
+            })
+        }
+
+        let mut limiter = input.limiter;
+        if limiter.is_limit_reached() {
+            return Ok(SegmentOutput::not_done(
+                limiter.interrupt_reason(),
+                input.previous_checkpoint.map(SegmentOutputCheckpoint::from_prune_checkpoint),
+            ))
+        }
+
+        // Addresses that emitted logs in the pruned blocks with the highest such block. Every
+        // address can delete at most one shard, so collecting stops once there are as many
+        // addresses as entries left to delete.
+        let mut highest_blocks = FxHashMap::default();
+        let mut last_pruned_block = None;
+        let mut done = true;
+        for block_number in range {
+            if limiter.deleted_entries_limit_left().is_some_and(|left| highest_blocks.len() >= left)
+            {
+                done = false;
+                break
+            }
+
+            if let Some(receipts) = provider.receipts_by_block(block_number.into())? {
+                for receipt in &receipts {
+                    for log in receipt.logs() {
+                        highest_blocks.insert(log.address, block_number);
+                    }
+                }
+            }
+            last_pruned_block = Some(block_number);
+        }
+        let last_pruned_block = last_pruned_block.unwrap_or(range_end);
+
+        let highest_sharded_keys = highest_blocks
+            .into_iter()
+            .sorted_unstable() // Unstable is fine because no equal keys exist in the map
+            .map(|(address, block_number)| ShardedKey::new(address, block_number));
+        let outcomes = prune_history_indices::<Provider, tables::LogAddressIndex, _>(
+            provider,
+            highest_sharded_keys,
+            |a, b| a.key == b.key,
+        )?;
+        trace!(target: "pruner", ?outcomes, %done, "Pruned log address index");
+        limiter.increment_deleted_entries_count_by(outcomes.deleted);
+
+        Ok(SegmentOutput {
+            progress: limiter.progress(done),
+            pruned: outcomes.deleted,
+            checkpoint: Some(SegmentOutputCheckpoint {
+                block_number: Some(last_pruned_block),
+                tx_number: None,
+            }),
+        })
+    }
+}
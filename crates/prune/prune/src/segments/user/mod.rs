@@ -1,5 +1,6 @@
 mod account_history;
 mod history;
+mod log_address_index;
 mod receipts;
 mod receipts_by_logs;
 mod sender_recovery;
@@ -7,6 +8,7 @@ mod storage_history;
 mod transaction_lookup;
 
 pub use account_history::AccountHistory;
+pub use log_address_index::LogAddressIndex;
 pub use receipts::Receipts;
 pub use receipts_by_logs::ReceiptsByLogs;
 pub use sender_recovery::SenderRecovery;
@@ -8,7 +8,9 @@ use reth_provider::{
     errors::provider::ProviderResult, BlockReader, DBProvider, NodePrimitivesProvider,
     PruneCheckpointWriter, TransactionsProvider,
 };
-use reth_prune_types::{PruneCheckpoint, PruneMode, PrunePurpose, PruneSegment, SegmentOutput};
+use reth_prune_types::{
+    PruneCheckpoint, PruneMode, PrunePurpose, PruneSegment, PruneSegmentId, SegmentOutput,
+};
 use tracing::instrument;
 
 #[derive(Debug)]
@@ -30,8 +32,8 @@ where
         + BlockReader
         + NodePrimitivesProvider<Primitives: NodePrimitives<Receipt: Value>>,
 {
-    fn segment(&self) -> PruneSegment {
-        PruneSegment::Receipts
+    fn segment(&self) -> PruneSegmentId {
+        PruneSegment::Receipts.into()
     }
 
     fn mode(&self) -> Option<PruneMode> {
@@ -10,8 +10,8 @@ use reth_provider::{
     BlockReader, DBProvider, NodePrimitivesProvider, PruneCheckpointWriter, TransactionsProvider,
 };
 use reth_prune_types::{
-    PruneCheckpoint, PruneMode, PrunePurpose, PruneSegment, ReceiptsLogPruneConfig, SegmentOutput,
-    MINIMUM_PRUNING_DISTANCE,
+    PruneCheckpoint, PruneMode, PrunePurpose, PruneSegment, PruneSegmentId, ReceiptsLogPruneConfig,
+    SegmentOutput, MINIMUM_PRUNING_DISTANCE,
 };
 use tracing::{instrument, trace};
 #[derive(Debug)]
@@ -33,8 +33,8 @@ where
         + BlockReader
         + NodePrimitivesProvider<Primitives: NodePrimitives<Receipt: Value>>,
 {
-    fn segment(&self) -> PruneSegment {
-        PruneSegment::ContractLogs
+    fn segment(&self) -> PruneSegmentId {
+        PruneSegment::ContractLogs.into()
     }
 
     fn mode(&self) -> Option<PruneMode> {
@@ -236,8 +236,9 @@ mod tests {
     use reth_provider::{DatabaseProviderFactory, PruneCheckpointReader, TransactionsProvider};
     use reth_prune_types::{PruneMode, PruneSegment, ReceiptsLogPruneConfig};
     use reth_stages::test_utils::{StorageKind, TestStageDB};
-    use reth_testing_utils::generators::{
-        self, random_block_range, random_eoa_account, random_log, random_receipt, BlockRangeParams,
+    use reth_testing_utils::{
+        generators::{self, random_eoa_account, random_log, random_receipt},
+        TestBlockBuilder,
     };
     use std::collections::BTreeMap;
 
@@ -249,25 +250,15 @@ mod tests {
         let mut rng = generators::rng();
 
         let tip = 20000;
-        let blocks = [
-            random_block_range(
-                &mut rng,
-                0..=100,
-                BlockRangeParams { parent: Some(B256::ZERO), tx_count: 1..5, ..Default::default() },
-            ),
-            random_block_range(
-                &mut rng,
-                (100 + 1)..=(tip - 100),
-                BlockRangeParams { parent: Some(B256::ZERO), tx_count: 0..1, ..Default::default() },
-            ),
-            random_block_range(
-                &mut rng,
-                (tip - 100 + 1)..=tip,
-                BlockRangeParams { parent: Some(B256::ZERO), tx_count: 1..5, ..Default::default() },
-            ),
-        ]
-        .concat();
-        db.insert_blocks(blocks.iter(), StorageKind::Database(None)).expect("insert blocks");
+        let mut builder =
+            TestBlockBuilder::default().with_txs_per_block(2).starting_at(0, B256::ZERO);
+        let mut blocks = builder.extend(101);
+        let mut builder = builder.with_txs_per_block(0);
+        blocks.extend(builder.extend(tip as usize - 200));
+        let mut builder = builder.with_txs_per_block(2);
+        blocks.extend(builder.extend(100));
+        db.insert_blocks(blocks.iter().map(|block| &block.block), StorageKind::Database(None))
+            .expect("insert blocks");
 
         let mut receipts = Vec::new();
 
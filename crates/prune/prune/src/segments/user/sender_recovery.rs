@@ -6,7 +6,7 @@ use crate::{
 use reth_db::{tables, transaction::DbTxMut};
 use reth_provider::{BlockReader, DBProvider, TransactionsProvider};
 use reth_prune_types::{
-    PruneMode, PrunePurpose, PruneSegment, SegmentOutput, SegmentOutputCheckpoint,
+    PruneMode, PrunePurpose, PruneSegment, PruneSegmentId, SegmentOutput, SegmentOutputCheckpoint,
 };
 use tracing::{instrument, trace};
 
@@ -25,8 +25,8 @@ impl<Provider> Segment<Provider> for SenderRecovery
 where
     Provider: DBProvider<Tx: DbTxMut> + TransactionsProvider + BlockReader,
 {
-    fn segment(&self) -> PruneSegment {
-        PruneSegment::SenderRecovery
+    fn segment(&self) -> PruneSegmentId {
+        PruneSegment::SenderRecovery.into()
     }
 
     fn mode(&self) -> Option<PruneMode> {
@@ -83,52 +83,44 @@ where
 #[cfg(test)]
 mod tests {
     use crate::segments::{PruneInput, PruneLimiter, Segment, SegmentOutput, SenderRecovery};
-    use alloy_primitives::{BlockNumber, TxNumber, B256};
+    use alloy_primitives::{BlockNumber, TxNumber};
     use assert_matches::assert_matches;
     use itertools::{
         FoldWhile::{Continue, Done},
         Itertools,
     };
     use reth_db::tables;
-    use reth_primitives_traits::SignedTransaction;
-    use reth_provider::{DatabaseProviderFactory, PruneCheckpointReader};
+    use reth_provider::{
+        test_utils::{TestChainParams, TestDatadir},
+        DatabaseProviderFactory, PruneCheckpointReader, StorageLocation,
+    };
     use reth_prune_types::{PruneCheckpoint, PruneMode, PruneProgress, PruneSegment};
-    use reth_stages::test_utils::{StorageKind, TestStageDB};
-    use reth_testing_utils::generators::{self, random_block_range, BlockRangeParams};
     use std::ops::Sub;
 
     #[test]
     fn prune() {
-        let db = TestStageDB::default();
-        let mut rng = generators::rng();
+        let datadir = TestDatadir::new(TestChainParams {
+            blocks: 10,
+            tx_count: 2..3,
+            blob_tx_count: 0..0,
+            withdrawals_count: None,
+            logs_count: 0,
+            storage: StorageLocation::Database,
+            ..Default::default()
+        })
+        .expect("create datadir");
+        // skip the empty genesis block
+        let blocks = &datadir.blocks()[1..];
 
-        let blocks = random_block_range(
-            &mut rng,
-            1..=10,
-            BlockRangeParams { parent: Some(B256::ZERO), tx_count: 2..3, ..Default::default() },
-        );
-        db.insert_blocks(blocks.iter(), StorageKind::Database(None)).expect("insert blocks");
-
-        let mut transaction_senders = Vec::new();
-        for block in &blocks {
-            transaction_senders.reserve_exact(block.body.transactions.len());
-            for transaction in &block.body.transactions {
-                transaction_senders.push((
-                    transaction_senders.len() as u64,
-                    transaction.recover_signer().expect("recover signer"),
-                ));
-            }
-        }
-        let transaction_senders_len = transaction_senders.len();
-        db.insert_transaction_senders(transaction_senders).expect("insert transaction senders");
+        let transaction_senders_len = datadir.table::<tables::TransactionSenders>().unwrap().len();
 
         assert_eq!(
-            db.table::<tables::Transactions>().unwrap().len(),
+            datadir.table::<tables::Transactions>().unwrap().len(),
             blocks.iter().map(|block| block.body.transactions.len()).sum::<usize>()
         );
         assert_eq!(
-            db.table::<tables::Transactions>().unwrap().len(),
-            db.table::<tables::TransactionSenders>().unwrap().len()
+            datadir.table::<tables::Transactions>().unwrap().len(),
+            datadir.table::<tables::TransactionSenders>().unwrap().len()
         );
 
         let test_prune = |to_block: BlockNumber, expected_result: (PruneProgress, usize)| {
@@ -136,8 +128,8 @@ mod tests {
             let segment = SenderRecovery::new(prune_mode);
             let mut limiter = PruneLimiter::default().set_deleted_entries_limit(10);
             let input = PruneInput {
-                previous_checkpoint: db
-                    .factory
+                previous_checkpoint: datadir
+                    .provider_factory()
                     .provider()
                     .unwrap()
                     .get_prune_checkpoint(PruneSegment::SenderRecovery)
@@ -146,8 +138,8 @@ mod tests {
                 limiter: limiter.clone(),
             };
 
-            let next_tx_number_to_prune = db
-                .factory
+            let next_tx_number_to_prune = datadir
+                .provider_factory()
                 .provider()
                 .unwrap()
                 .get_prune_checkpoint(PruneSegment::SenderRecovery)
@@ -181,7 +173,7 @@ mod tests {
                 .into_inner()
                 .0;
 
-            let provider = db.factory.database_provider_rw().unwrap();
+            let provider = datadir.provider_factory().database_provider_rw().unwrap();
             let result = segment.prune(&provider, input).unwrap();
             limiter.increment_deleted_entries_count_by(result.pruned);
 
@@ -203,11 +195,12 @@ mod tests {
                 .checked_sub(if result.progress.is_finished() { 0 } else { 1 });
 
             assert_eq!(
-                db.table::<tables::TransactionSenders>().unwrap().len(),
+                datadir.table::<tables::TransactionSenders>().unwrap().len(),
                 transaction_senders_len - (last_pruned_tx_number + 1)
             );
             assert_eq!(
-                db.factory
+                datadir
+                    .provider_factory()
                     .provider()
                     .unwrap()
                     .get_prune_checkpoint(PruneSegment::SenderRecovery)
@@ -7,7 +7,9 @@ use itertools::Itertools;
 use reth_db::{tables, transaction::DbTxMut};
 use reth_db_api::models::{storage_sharded_key::StorageShardedKey, BlockNumberAddress};
 use reth_provider::DBProvider;
-use reth_prune_types::{PruneMode, PrunePurpose, PruneSegment, SegmentOutputCheckpoint};
+use reth_prune_types::{
+    PruneMode, PrunePurpose, PruneSegment, PruneSegmentId, SegmentOutputCheckpoint,
+};
 use rustc_hash::FxHashMap;
 use tracing::{instrument, trace};
 
@@ -32,8 +34,8 @@ impl<Provider> Segment<Provider> for StorageHistory
 where
     Provider: DBProvider<Tx: DbTxMut>,
 {
-    fn segment(&self) -> PruneSegment {
-        PruneSegment::StorageHistory
+    fn segment(&self) -> PruneSegmentId {
+        PruneSegment::StorageHistory.into()
     }
 
     fn mode(&self) -> Option<PruneMode> {
@@ -140,8 +142,9 @@ mod tests {
     use reth_provider::{DatabaseProviderFactory, PruneCheckpointReader};
     use reth_prune_types::{PruneCheckpoint, PruneMode, PruneProgress, PruneSegment};
     use reth_stages::test_utils::{StorageKind, TestStageDB};
-    use reth_testing_utils::generators::{
-        self, random_block_range, random_changeset_range, random_eoa_accounts, BlockRangeParams,
+    use reth_testing_utils::{
+        generators::{self, random_changeset_range, random_eoa_accounts},
+        TestBlockBuilder,
     };
     use std::{collections::BTreeMap, ops::AddAssign};
 
@@ -150,18 +153,15 @@ mod tests {
         let db = TestStageDB::default();
         let mut rng = generators::rng();
 
-        let blocks = random_block_range(
-            &mut rng,
-            0..=5000,
-            BlockRangeParams { parent: Some(B256::ZERO), tx_count: 0..1, ..Default::default() },
-        );
-        db.insert_blocks(blocks.iter(), StorageKind::Database(None)).expect("insert blocks");
+        let blocks = TestBlockBuilder::default().starting_at(0, B256::ZERO).extend(5001);
+        db.insert_blocks(blocks.iter().map(|block| &block.block), StorageKind::Database(None))
+            .expect("insert blocks");
 
         let accounts = random_eoa_accounts(&mut rng, 2).into_iter().collect::<BTreeMap<_, _>>();
 
         let (changesets, _) = random_changeset_range(
             &mut rng,
-            blocks.iter(),
+            blocks.iter().map(|block| &block.block),
             accounts.into_iter().map(|(addr, acc)| (addr, (acc, Vec::new()))),
             1..2,
             1..2,
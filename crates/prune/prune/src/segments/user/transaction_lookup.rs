@@ -7,7 +7,9 @@ use alloy_eips::eip2718::Encodable2718;
 use rayon::prelude::*;
 use reth_db::{tables, transaction::DbTxMut};
 use reth_provider::{BlockReader, DBProvider};
-use reth_prune_types::{PruneMode, PrunePurpose, PruneSegment, SegmentOutputCheckpoint};
+use reth_prune_types::{
+    PruneMode, PrunePurpose, PruneSegment, PruneSegmentId, SegmentOutputCheckpoint,
+};
 use tracing::{instrument, trace};
 
 #[derive(Debug)]
@@ -25,8 +27,8 @@ impl<Provider> Segment<Provider> for TransactionLookup
 where
     Provider: DBProvider<Tx: DbTxMut> + BlockReader<Transaction: Encodable2718>,
 {
-    fn segment(&self) -> PruneSegment {
-        PruneSegment::TransactionLookup
+    fn segment(&self) -> PruneSegmentId {
+        PruneSegment::TransactionLookup.into()
     }
 
     fn mode(&self) -> Option<PruneMode> {
@@ -110,50 +112,46 @@ where
 #[cfg(test)]
 mod tests {
     use crate::segments::{PruneInput, PruneLimiter, Segment, SegmentOutput, TransactionLookup};
-    use alloy_primitives::{BlockNumber, TxNumber, B256};
+    use alloy_primitives::{BlockNumber, TxNumber};
     use assert_matches::assert_matches;
     use itertools::{
         FoldWhile::{Continue, Done},
         Itertools,
     };
     use reth_db::tables;
-    use reth_provider::{DatabaseProviderFactory, PruneCheckpointReader};
+    use reth_provider::{
+        test_utils::{TestChainParams, TestDatadir},
+        DatabaseProviderFactory, PruneCheckpointReader, StorageLocation,
+    };
     use reth_prune_types::{
         PruneCheckpoint, PruneInterruptReason, PruneMode, PruneProgress, PruneSegment,
     };
-    use reth_stages::test_utils::{StorageKind, TestStageDB};
-    use reth_testing_utils::generators::{self, random_block_range, BlockRangeParams};
     use std::ops::Sub;
 
     #[test]
     fn prune() {
-        let db = TestStageDB::default();
-        let mut rng = generators::rng();
-
-        let blocks = random_block_range(
-            &mut rng,
-            1..=10,
-            BlockRangeParams { parent: Some(B256::ZERO), tx_count: 2..3, ..Default::default() },
-        );
-        db.insert_blocks(blocks.iter(), StorageKind::Database(None)).expect("insert blocks");
+        let datadir = TestDatadir::new(TestChainParams {
+            blocks: 10,
+            tx_count: 2..3,
+            blob_tx_count: 0..0,
+            withdrawals_count: None,
+            logs_count: 0,
+            storage: StorageLocation::Database,
+            ..Default::default()
+        })
+        .expect("create datadir");
+        // skip the empty genesis block
+        let blocks = &datadir.blocks()[1..];
 
-        let mut tx_hash_numbers = Vec::new();
-        for block in &blocks {
-            tx_hash_numbers.reserve_exact(block.body.transactions.len());
-            for transaction in &block.body.transactions {
-                tx_hash_numbers.push((transaction.hash(), tx_hash_numbers.len() as u64));
-            }
-        }
-        let tx_hash_numbers_len = tx_hash_numbers.len();
-        db.insert_tx_hash_numbers(tx_hash_numbers).expect("insert tx hash numbers");
+        let tx_hash_numbers_len = datadir.table::<tables::TransactionHashNumbers>().unwrap().len();
 
         assert_eq!(
-            db.table::<tables::Transactions>().unwrap().len(),
+            datadir.table::<tables::Transactions>().unwrap().len(),
             blocks.iter().map(|block| block.body.transactions.len()).sum::<usize>()
         );
         assert_eq!(
-            db.table::<tables::Transactions>().unwrap().len(),
-            db.table::<tables::TransactionHashNumbers>().unwrap().len()
+            datadir.table::<tables::Transactions>().unwrap().len(),
+            datadir.table::<tables::TransactionHashNumbers>().unwrap().len()
         );
 
         let test_prune = |to_block: BlockNumber, expected_result: (PruneProgress, usize)| {
@@ -161,8 +159,8 @@ mod tests {
             let segment = TransactionLookup::new(prune_mode);
             let mut limiter = PruneLimiter::default().set_deleted_entries_limit(10);
             let input = PruneInput {
-                previous_checkpoint: db
-                    .factory
+                previous_checkpoint: datadir
+                    .provider_factory()
                     .provider()
                     .unwrap()
                     .get_prune_checkpoint(PruneSegment::TransactionLookup)
@@ -171,8 +169,8 @@ mod tests {
                 limiter: limiter.clone(),
             };
 
-            let next_tx_number_to_prune = db
-                .factory
+            let next_tx_number_to_prune = datadir
+                .provider_factory()
                 .provider()
                 .unwrap()
                 .get_prune_checkpoint(PruneSegment::TransactionLookup)
@@ -206,7 +204,7 @@ mod tests {
                 .into_inner()
                 .0;
 
-            let provider = db.factory.database_provider_rw().unwrap();
+            let provider = datadir.provider_factory().database_provider_rw().unwrap();
             let result = segment.prune(&provider, input).unwrap();
             limiter.increment_deleted_entries_count_by(result.pruned);
 
@@ -228,11 +226,12 @@ mod tests {
                 .checked_sub(if result.progress.is_finished() { 0 } else { 1 });
 
             assert_eq!(
-                db.table::<tables::TransactionHashNumbers>().unwrap().len(),
+                datadir.table::<tables::TransactionHashNumbers>().unwrap().len(),
                 tx_hash_numbers_len - (last_pruned_tx_number + 1)
             );
             assert_eq!(
-                db.factory
+                datadir
+                    .provider_factory()
                     .provider()
                     .unwrap()
                     .get_prune_checkpoint(PruneSegment::TransactionLookup)
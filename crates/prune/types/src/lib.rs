@@ -22,7 +22,7 @@ pub use pruner::{
     PruneInterruptReason, PruneProgress, PrunedSegmentInfo, PrunerOutput, SegmentOutput,
     SegmentOutputCheckpoint,
 };
-pub use segment::{PrunePurpose, PruneSegment, PruneSegmentError};
+pub use segment::{PrunePurpose, PruneSegment, PruneSegmentError, PruneSegmentId};
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
 pub use target::{PruneModes, MINIMUM_PRUNING_DISTANCE};
@@ -1,4 +1,4 @@
-use crate::{segment::PrunePurpose, PruneSegment, PruneSegmentError};
+use crate::{segment::PrunePurpose, PruneSegmentError, PruneSegmentId};
 use alloy_primitives::BlockNumber;
 use reth_codecs::{add_arbitrary_tests, Compact};
 use serde::{Deserialize, Serialize};
@@ -37,9 +37,10 @@ impl PruneMode {
     pub fn prune_target_block(
         &self,
         tip: BlockNumber,
-        segment: PruneSegment,
+        segment: impl Into<PruneSegmentId>,
         purpose: PrunePurpose,
     ) -> Result<Option<(BlockNumber, Self)>, PruneSegmentError> {
+        let segment = segment.into();
         let result = match self {
             Self::Full if segment.min_blocks(purpose) == 0 => Some((tip, *self)),
             Self::Distance(distance) if *distance > tip => None, // Nothing to prune yet
@@ -96,7 +97,7 @@ mod tests {
 
         let tests = vec![
             // MINIMUM_PRUNING_DISTANCE makes this impossible
-            (PruneMode::Full, Err(PruneSegmentError::Configuration(segment))),
+            (PruneMode::Full, Err(PruneSegmentError::Configuration(segment.into()))),
             // Nothing to prune
             (PruneMode::Distance(tip + 1), Ok(None)),
             (
@@ -113,7 +114,7 @@ mod tests {
                 PruneMode::Before(tip - MINIMUM_PRUNING_DISTANCE - 1),
                 Ok(Some(tip - MINIMUM_PRUNING_DISTANCE - 2)),
             ),
-            (PruneMode::Before(tip - 1), Err(PruneSegmentError::Configuration(segment))),
+            (PruneMode::Before(tip - 1), Err(PruneSegmentError::Configuration(segment.into()))),
         ];
 
         for (index, (mode, expected_result)) in tests.into_iter().enumerate() {
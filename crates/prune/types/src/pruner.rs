@@ -1,4 +1,4 @@
-use crate::{PruneCheckpoint, PruneMode, PruneSegment};
+use crate::{PruneCheckpoint, PruneMode, PruneSegmentId};
 use alloy_primitives::{BlockNumber, TxNumber};
 use derive_more::Display;
 
@@ -8,7 +8,7 @@ pub struct PrunerOutput {
     /// Pruning progress.
     pub progress: PruneProgress,
     /// Pruning output for each segment.
-    pub segments: Vec<(PruneSegment, SegmentOutput)>,
+    pub segments: Vec<(PruneSegmentId, SegmentOutput)>,
 }
 
 impl From<PruneProgress> for PrunerOutput {
@@ -22,7 +22,7 @@ impl From<PruneProgress> for PrunerOutput {
 #[display("(table={segment}, pruned={pruned}, status={progress})")]
 pub struct PrunedSegmentInfo {
     /// The pruned segment
-    pub segment: PruneSegment,
+    pub segment: PruneSegmentId,
     /// Number of pruned entries
     pub pruned: usize,
     /// Prune progress
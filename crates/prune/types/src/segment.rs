@@ -39,6 +39,8 @@ pub enum PruneSegment {
     Headers,
     /// Prune segment responsible for the `Transactions` table.
     Transactions,
+    /// Prune segment responsible for the `LogAddressIndex` table.
+    LogAddressIndex,
 }
 
 impl PruneSegment {
@@ -49,14 +51,45 @@ impl PruneSegment {
                 0
             }
             Self::Receipts if purpose.is_static_file() => 0,
-            Self::ContractLogs | Self::AccountHistory | Self::StorageHistory => {
-                MINIMUM_PRUNING_DISTANCE
-            }
+            Self::ContractLogs |
+            Self::AccountHistory |
+            Self::StorageHistory |
+            Self::LogAddressIndex => MINIMUM_PRUNING_DISTANCE,
             Self::Receipts => MINIMUM_PRUNING_DISTANCE,
         }
     }
 }
 
+/// Identifier of a segment of the data that can be pruned, under which its checkpoint is saved.
+#[derive(Debug, Display, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum PruneSegmentId {
+    /// Segment built into the pruner.
+    #[display("{_0}")]
+    Builtin(PruneSegment),
+    /// Segment registered by the node builder, identified by the name it's configured with in the
+    /// `[prune.segments.custom]` section of the config.
+    #[display("{_0}")]
+    Custom(&'static str),
+}
+
+impl PruneSegmentId {
+    /// Returns minimum number of blocks to left in the database for this segment.
+    ///
+    /// Custom segments don't require any blocks to be left.
+    pub const fn min_blocks(&self, purpose: PrunePurpose) -> u64 {
+        match self {
+            Self::Builtin(segment) => segment.min_blocks(purpose),
+            Self::Custom(_) => 0,
+        }
+    }
+}
+
+impl From<PruneSegment> for PruneSegmentId {
+    fn from(segment: PruneSegment) -> Self {
+        Self::Builtin(segment)
+    }
+}
+
 /// Prune purpose.
 #[derive(Debug, Clone, Copy)]
 pub enum PrunePurpose {
@@ -83,7 +116,18 @@ impl PrunePurpose {
 pub enum PruneSegmentError {
     /// Invalid configuration of a prune segment.
     #[error("the configuration provided for {0} is invalid")]
-    Configuration(PruneSegment),
+    Configuration(PruneSegmentId),
+    /// A prune mode is configured for a custom segment that isn't registered.
+    #[error("prune segment {0} is configured, but no segment with this name is registered")]
+    Unregistered(String),
+    /// A custom segment doesn't identify with the name it's registered with.
+    #[error("prune segment registered as {name} identifies as {id}")]
+    MismatchedId {
+        /// The name the segment is registered with.
+        name: &'static str,
+        /// The identifier of the segment.
+        id: PruneSegmentId,
+    },
     /// Receipts have been pruned
     #[error("receipts have been pruned")]
     ReceiptsPruned,
@@ -1,5 +1,6 @@
 use crate::{PruneMode, ReceiptsLogPruneConfig};
 use serde::{Deserialize, Deserializer, Serialize};
+use std::collections::BTreeMap;
 
 /// Minimum distance from the tip necessary for the node to work correctly:
 /// 1. Minimum 2 epochs (32 blocks per epoch) required to handle any reorg according to the
@@ -43,6 +44,13 @@ pub struct PruneModes {
     /// The [`BlockNumber`](`crate::BlockNumber`) represents the starting block from which point
     /// onwards the receipts are preserved.
     pub receipts_log_filter: ReceiptsLogPruneConfig,
+    /// Pruning configuration of the custom segments registered by the node builder, by the name
+    /// of the segment.
+    ///
+    /// The segments are configured in their own table, so that a misspelled built-in segment isn't
+    /// taken for a custom one.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub custom: BTreeMap<String, PruneMode>,
 }
 
 impl PruneModes {
@@ -60,6 +68,7 @@ impl PruneModes {
             account_history: Some(PruneMode::Full),
             storage_history: Some(PruneMode::Full),
             receipts_log_filter: Default::default(),
+            custom: BTreeMap::new(),
         }
     }
 
@@ -133,4 +142,30 @@ mod tests {
             Err(err) if err.to_string() == "invalid value: string \"full\", expected prune mode that leaves at least 10 blocks in the database"
         );
     }
+
+    #[test]
+    fn test_deserialize_custom_segments() {
+        let modes: PruneModes = toml::from_str(
+            r#"
+            sender_recovery = "full"
+
+            [custom]
+            deposit_events = { distance = 100 }
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            modes,
+            PruneModes {
+                sender_recovery: Some(PruneMode::Full),
+                custom: BTreeMap::from([("deposit_events".to_string(), PruneMode::Distance(100))]),
+                ..PruneModes::none()
+            }
+        );
+        assert_eq!(toml::from_str::<PruneModes>(&toml::to_string(&modes).unwrap()).unwrap(), modes);
+
+        // a misspelled built-in segment isn't taken for a custom one
+        let modes: PruneModes = toml::from_str(r#"sender_recovry = "full""#).unwrap();
+        assert_eq!(modes, PruneModes::none());
+    }
 }
@@ -0,0 +1,147 @@
+#![allow(missing_docs)]
+
+use alloy_primitives::{Address, Bytes, TxKind, B256, U256};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use reth_revm::{
+    cached::CachedReads,
+    db::{CacheDB, EmptyDB, State},
+    prewarm::{prewarm, PrewarmTargets},
+    primitives::{db::DatabaseRef, AccountInfo, Bytecode, SpecId, TxEnv},
+    Database, Evm,
+};
+use std::{
+    convert::Infallible,
+    fmt::Debug,
+    time::{Duration, Instant},
+};
+
+criterion_group!(benches, execute_block);
+criterion_main!(benches);
+
+const TRANSFERS: usize = 150;
+const CONTRACT_CALLS: usize = 100;
+const CONTRACTS: u64 = 20;
+const PREWARM_PARALLELISM: usize = 8;
+
+/// Latency of every read of the underlying database, roughly that of a cold MDBX page read.
+const READ_LATENCY: Duration = Duration::from_micros(20);
+
+/// Loads two storage slots, given in the calldata.
+const CONTRACT_CODE: [u8; 11] = [
+    0x60, 0x00, 0x35, 0x54, 0x50, // SLOAD(CALLDATALOAD(0))
+    0x60, 0x20, 0x35, 0x54, 0x50, // SLOAD(CALLDATALOAD(32))
+    0x00, // STOP
+];
+
+/// A database that spins for [`READ_LATENCY`] on every read, simulating a cold cache.
+#[derive(Debug)]
+struct ColdDb(CacheDB<EmptyDB>);
+
+impl ColdDb {
+    fn wait() {
+        let start = Instant::now();
+        while start.elapsed() < READ_LATENCY {
+            std::hint::spin_loop();
+        }
+    }
+}
+
+impl DatabaseRef for ColdDb {
+    type Error = <EmptyDB as DatabaseRef>::Error;
+
+    fn basic_ref(&self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+        Self::wait();
+        self.0.basic_ref(address)
+    }
+
+    fn code_by_hash_ref(&self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+        Self::wait();
+        self.0.code_by_hash_ref(code_hash)
+    }
+
+    fn storage_ref(&self, address: Address, index: U256) -> Result<U256, Self::Error> {
+        Self::wait();
+        self.0.storage_ref(address, index)
+    }
+
+    fn block_hash_ref(&self, number: u64) -> Result<B256, Self::Error> {
+        self.0.block_hash_ref(number)
+    }
+}
+
+/// Creates the state and the transactions of a block made of value transfers between distinct
+/// accounts and calls to a few contracts reading two storage slots each, along with the targets
+/// a block with access lists for the contract calls would yield.
+fn mainnet_like_block() -> (ColdDb, Vec<TxEnv>, PrewarmTargets) {
+    let mut db = CacheDB::new(EmptyDB::default());
+    let mut txs = Vec::new();
+    let mut targets = PrewarmTargets::default();
+
+    let code = Bytecode::new_raw(Bytes::from_static(&CONTRACT_CODE));
+    for i in 0..CONTRACTS {
+        let contract = Address::from_word(B256::from(U256::from(0xc0de_0000 + i)));
+        db.insert_account_info(
+            contract,
+            AccountInfo::new(U256::ZERO, 1, code.hash_slow(), code.clone()),
+        );
+    }
+
+    for i in 0..(TRANSFERS + CONTRACT_CALLS) as u64 {
+        let sender = Address::from_word(B256::from(U256::from(0x5e4d_0000 + i)));
+        db.insert_account_info(sender, AccountInfo::from_balance(U256::from(u64::MAX)));
+        targets.add_account(sender);
+
+        let mut tx = TxEnv { caller: sender, gas_limit: 100_000, ..Default::default() };
+        if i < TRANSFERS as u64 {
+            let recipient = Address::from_word(B256::from(U256::from(0x7ec0_0000 + i)));
+            tx.transact_to = TxKind::Call(recipient);
+            tx.value = U256::from(1);
+            targets.add_account(recipient);
+        } else {
+            let contract = Address::from_word(B256::from(U256::from(0xc0de_0000 + i % CONTRACTS)));
+            let slots = [U256::from(i), U256::from(i + 1_000)];
+            for slot in slots {
+                db.insert_account_storage(contract, slot, U256::from(1)).unwrap();
+                targets.add_storage(contract, slot);
+            }
+            tx.transact_to = TxKind::Call(contract);
+            tx.data = slots.iter().flat_map(|slot| slot.to_be_bytes::<32>()).collect();
+        }
+        txs.push(tx);
+    }
+
+    (ColdDb(db), txs, targets)
+}
+
+fn execute<DB>(db: DB, txs: &[TxEnv])
+where
+    DB: Database<Error: Debug>,
+{
+    let state = State::builder().with_database(db).build();
+    let mut evm = Evm::builder().with_db(state).with_spec_id(SpecId::CANCUN).build();
+    for tx in txs {
+        *evm.tx_mut() = tx.clone();
+        evm.transact_commit().unwrap();
+    }
+}
+
+fn execute_block(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Block execution with cold cache");
+    group.sample_size(20);
+    let (db, txs, targets) = mainnet_like_block();
+
+    group.bench_function(BenchmarkId::new("sequential", txs.len()), |b| {
+        b.iter(|| {
+            let mut cached = CachedReads::default();
+            execute(cached.as_db_mut(&db), &txs);
+        })
+    });
+
+    group.bench_function(BenchmarkId::new("prewarmed", txs.len()), |b| {
+        b.iter(|| {
+            let mut cached =
+                prewarm(|| Ok::<_, Infallible>(&db), targets.clone(), PREWARM_PARALLELISM);
+            execute(cached.as_db_mut(&db), &txs);
+        })
+    });
+}
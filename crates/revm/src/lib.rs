@@ -30,6 +30,10 @@ pub use revm::{self, *};
 /// Either type for flexible usage of different database types in the same context.
 pub mod either;
 
-/// Helper types for execution witness generation.
+/// Parallel pre-warming of state reads ahead of block execution.
+#[cfg(feature = "std")]
+pub mod prewarm;
+
+/// Helper types for execution witness generation and stateless execution.
 #[cfg(feature = "witness")]
 pub mod witness;
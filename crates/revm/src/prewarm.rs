@@ -0,0 +1,213 @@
+//! Parallel pre-warming of state reads ahead of sequential block execution.
+
+use crate::cached::CachedReads;
+use alloy_consensus::{BlockHeader, Transaction};
+use alloy_primitives::{
+    map::{HashMap, HashSet},
+    Address, U256,
+};
+use rayon::prelude::*;
+use reth_primitives::BlockWithSenders;
+use reth_primitives_traits::{Block, BlockBody};
+use revm::{
+    primitives::{db::DatabaseRef, KECCAK_EMPTY},
+    Database,
+};
+
+/// Accounts and storage slots that are expected to be read while executing a block.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PrewarmTargets {
+    accounts: HashMap<Address, HashSet<U256>>,
+}
+
+impl PrewarmTargets {
+    /// Collects the accounts and storage slots the transactions of the block are expected to
+    /// touch.
+    ///
+    /// These are the beneficiary, the senders and recipients of all transactions and the entries
+    /// of their access lists. Transactions without an access list only contribute their sender
+    /// and recipient.
+    pub fn from_block<B: Block>(block: &BlockWithSenders<B>) -> Self {
+        let mut targets = Self::default();
+        targets.add_account(block.header().beneficiary());
+        for (tx, sender) in block.body().transactions().iter().zip(&block.senders) {
+            targets.add_account(*sender);
+            if let Some(to) = tx.to() {
+                targets.add_account(to);
+            }
+            for item in tx.access_list().map(|list| list.0.as_slice()).unwrap_or_default() {
+                targets.add_account(item.address);
+                for key in &item.storage_keys {
+                    targets.add_storage(item.address, (*key).into());
+                }
+            }
+        }
+        targets
+    }
+
+    /// Adds an account to the targets.
+    pub fn add_account(&mut self, address: Address) {
+        self.accounts.entry(address).or_default();
+    }
+
+    /// Adds a storage slot, and its account, to the targets.
+    pub fn add_storage(&mut self, address: Address, slot: U256) {
+        self.accounts.entry(address).or_default().insert(slot);
+    }
+
+    /// Returns the number of accounts.
+    pub fn accounts_len(&self) -> usize {
+        self.accounts.len()
+    }
+
+    /// Returns the number of storage slots.
+    pub fn storage_len(&self) -> usize {
+        self.accounts.values().map(HashSet::len).sum()
+    }
+
+    /// Returns `true` if there is nothing to pre-warm.
+    pub fn is_empty(&self) -> bool {
+        self.accounts.is_empty()
+    }
+}
+
+/// Reads all targets from the database in at most `max_parallelism` parallel tasks and returns
+/// the reads, to be used as the cache of the database the block is then executed on.
+///
+/// The tasks run on the rayon thread pool. Every task opens its own database with `open_db`, so
+/// that the reads don't contend on a single database transaction, and reads its accounts together
+/// with their storage slots and bytecode. Errors are ignored: the entries are left out of the
+/// cache and the error surfaces again once execution reads them.
+///
+/// Since the cache only holds values read from databases opened at the same state, executing on
+/// top of it yields the same result as executing on the database directly.
+pub fn prewarm<DB, E>(
+    open_db: impl Fn() -> Result<DB, E> + Sync,
+    targets: PrewarmTargets,
+    max_parallelism: usize,
+) -> CachedReads
+where
+    DB: DatabaseRef,
+{
+    let accounts = targets.accounts.into_iter().collect::<Vec<_>>();
+    if accounts.is_empty() {
+        return CachedReads::default()
+    }
+
+    let chunk_size = accounts.len().div_ceil(max_parallelism.max(1));
+    let reads = accounts
+        .par_chunks(chunk_size)
+        .filter_map(|chunk| Some(prewarm_accounts(&open_db().ok()?, chunk)))
+        .collect::<Vec<_>>();
+
+    let mut cached = CachedReads::default();
+    for reads in reads {
+        cached.extend(reads);
+    }
+    cached
+}
+
+/// Reads the given accounts, their storage slots and their bytecode.
+fn prewarm_accounts<DB: DatabaseRef>(
+    db: &DB,
+    accounts: &[(Address, HashSet<U256>)],
+) -> CachedReads {
+    let mut cached = CachedReads::default();
+    let mut cached_db = cached.as_db_mut(db);
+    for (address, slots) in accounts {
+        let Ok(Some(info)) = cached_db.basic(*address) else { continue };
+        if info.code.is_none() && info.code_hash != KECCAK_EMPTY {
+            let _ = cached_db.code_by_hash(info.code_hash);
+        }
+        for slot in slots {
+            let _ = cached_db.storage(*address, *slot);
+        }
+    }
+    cached
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{Bytes, B256};
+    use revm::{
+        db::{CacheDB, EmptyDB},
+        primitives::{AccountInfo, Bytecode},
+    };
+    use std::{
+        convert::Infallible,
+        sync::atomic::{AtomicUsize, Ordering},
+    };
+
+    /// Counts reads that reach the underlying database.
+    #[derive(Debug)]
+    struct CountingDb {
+        inner: CacheDB<EmptyDB>,
+        reads: AtomicUsize,
+    }
+
+    impl DatabaseRef for CountingDb {
+        type Error = <EmptyDB as DatabaseRef>::Error;
+
+        fn basic_ref(&self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+            self.reads.fetch_add(1, Ordering::Relaxed);
+            self.inner.basic_ref(address)
+        }
+
+        fn code_by_hash_ref(&self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+            self.reads.fetch_add(1, Ordering::Relaxed);
+            self.inner.code_by_hash_ref(code_hash)
+        }
+
+        fn storage_ref(&self, address: Address, index: U256) -> Result<U256, Self::Error> {
+            self.reads.fetch_add(1, Ordering::Relaxed);
+            self.inner.storage_ref(address, index)
+        }
+
+        fn block_hash_ref(&self, number: u64) -> Result<B256, Self::Error> {
+            self.inner.block_hash_ref(number)
+        }
+    }
+
+    #[test]
+    fn prewarmed_reads_match_database() {
+        let mut inner = CacheDB::new(EmptyDB::default());
+        let mut targets = PrewarmTargets::default();
+        for i in 0..64u8 {
+            let address = Address::with_last_byte(i);
+            let code = Bytecode::new_raw(Bytes::from(vec![0x60, i]));
+            let info = AccountInfo::new(U256::from(i), 0, code.hash_slow(), code);
+            inner.insert_account_info(address, info);
+            inner.insert_account_storage(address, U256::from(1), U256::from(i)).unwrap();
+
+            targets.add_storage(address, U256::from(1));
+            targets.add_storage(address, U256::from(2));
+        }
+        // accounts that don't exist are cached as well
+        targets.add_account(Address::repeat_byte(0xff));
+
+        let db = CountingDb { inner, reads: AtomicUsize::new(0) };
+        let opened = AtomicUsize::new(0);
+        let open_db = || {
+            opened.fetch_add(1, Ordering::Relaxed);
+            Ok::<_, Infallible>(&db)
+        };
+        let mut cached = prewarm(open_db, targets.clone(), 4);
+        let warm_reads = db.reads.swap(0, Ordering::Relaxed);
+        assert!(warm_reads > 0);
+        // every task opens its own database
+        assert_eq!(opened.load(Ordering::Relaxed), 4);
+
+        let mut cached_db = cached.as_db_mut(&db);
+        for (address, slots) in &targets.accounts {
+            assert_eq!(cached_db.basic(*address).unwrap(), db.inner.basic_ref(*address).unwrap());
+            for slot in slots {
+                assert_eq!(
+                    cached_db.storage(*address, *slot).unwrap(),
+                    db.inner.storage_ref(*address, *slot).unwrap()
+                );
+            }
+        }
+        assert_eq!(db.reads.load(Ordering::Relaxed), 0);
+    }
+}
@@ -4,7 +4,8 @@ use alloy_primitives::{
     map::{B256HashMap, HashMap},
     Address, BlockNumber, Bytes, StorageKey, B256, U256,
 };
-use reth_primitives::{Account, Bytecode};
+use core::ops::RangeInclusive;
+use reth_primitives::{Account, Bytecode, StorageEntry};
 use reth_storage_api::{
     AccountReader, BlockHashReader, HashedPostStateProvider, StateProofProvider, StateProvider,
     StateRootProvider, StorageRootProvider,
@@ -121,6 +122,16 @@ impl StorageRootProvider for StateProviderTest {
     ) -> ProviderResult<StorageMultiProof> {
         unimplemented!("proof generation is not supported")
     }
+
+    fn storage_range(
+        &self,
+        _address: Address,
+        _range: RangeInclusive<B256>,
+        _limit: usize,
+        _hashed_storage: HashedStorage,
+    ) -> ProviderResult<Vec<StorageEntry>> {
+        unimplemented!("storage range is not supported")
+    }
 }
 
 impl StateProofProvider for StateProviderTest {
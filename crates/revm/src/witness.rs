@@ -1,5 +1,11 @@
-use alloy_primitives::{keccak256, map::B256HashMap, Bytes, B256};
-use reth_trie::{HashedPostState, HashedStorage};
+use crate::database::EvmStateProvider;
+use alloc::{collections::BTreeMap, format};
+use alloy_primitives::{
+    keccak256, map::B256HashMap, Address, BlockNumber, Bytes, StorageKey, StorageValue, B256,
+};
+use reth_primitives::{Account, Bytecode};
+use reth_storage_errors::provider::{ProviderError, ProviderResult};
+use reth_trie::{stateless::StatelessTrie, HashedPostState, HashedStorage};
 use revm::State;
 
 /// Tracks state changes during execution.
@@ -74,3 +80,76 @@ impl ExecutionWitnessRecord {
         record
     }
 }
+
+/// The state of an execution witness.
+///
+/// Serves the accounts, storage slots and bytecodes of the witness to execute the block it was
+/// generated for without access to the database, e.g. by wrapping it in a
+/// [`StateProviderDatabase`](crate::database::StateProviderDatabase). Reads of state that is not
+/// part of the witness fail.
+#[derive(Debug, Clone)]
+pub struct WitnessState {
+    /// The state trie of the witness.
+    trie: StatelessTrie,
+    /// Map of all contract codes of the witness.
+    ///
+    /// `keccak(bytecodes) => bytecodes`
+    codes: B256HashMap<Bytes>,
+    /// Hashes of the ancestor blocks that can be accessed during execution.
+    block_hashes: BTreeMap<BlockNumber, B256>,
+}
+
+impl WitnessState {
+    /// Creates the state from the state root of the parent block and the trie nodes and contract
+    /// codes of the witness.
+    pub fn new(state_root: B256, state: B256HashMap<Bytes>, codes: B256HashMap<Bytes>) -> Self {
+        Self {
+            trie: StatelessTrie::new(state_root, state),
+            codes,
+            block_hashes: BTreeMap::default(),
+        }
+    }
+
+    /// Sets the hashes of the ancestor blocks that can be accessed during execution.
+    pub fn with_block_hashes(
+        mut self,
+        block_hashes: impl IntoIterator<Item = (BlockNumber, B256)>,
+    ) -> Self {
+        self.block_hashes = block_hashes.into_iter().collect();
+        self
+    }
+
+    /// Computes the state root after applying the state transition of the executed block.
+    pub fn state_root(&self, hashed_state: &HashedPostState) -> ProviderResult<B256> {
+        Ok(self.trie.root_with_state(hashed_state)?)
+    }
+}
+
+impl EvmStateProvider for WitnessState {
+    fn basic_account(&self, address: &Address) -> ProviderResult<Option<Account>> {
+        Ok(self.trie.account(keccak256(address))?.map(Into::into))
+    }
+
+    fn block_hash(&self, number: BlockNumber) -> ProviderResult<Option<B256>> {
+        let hash = self
+            .block_hashes
+            .get(&number)
+            .ok_or_else(|| ProviderError::HeaderNotFound(number.into()))?;
+        Ok(Some(*hash))
+    }
+
+    fn bytecode_by_hash(&self, code_hash: &B256) -> ProviderResult<Option<Bytecode>> {
+        let code = self.codes.get(code_hash).ok_or_else(|| {
+            ProviderError::TrieWitnessError(format!("missing witness bytecode {code_hash}"))
+        })?;
+        Ok(Some(Bytecode::new_raw(code.clone())))
+    }
+
+    fn storage(
+        &self,
+        account: Address,
+        storage_key: StorageKey,
+    ) -> ProviderResult<Option<StorageValue>> {
+        Ok(Some(self.trie.storage(keccak256(account), keccak256(storage_key))?))
+    }
+}
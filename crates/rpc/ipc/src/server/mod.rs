@@ -44,6 +44,7 @@ mod ipc;
 mod rpc_service;
 
 pub use rpc_service::RpcService;
+use rpc_service::SharedRpcService;
 
 /// Ipc Server implementation
 ///
@@ -127,18 +128,31 @@ where
     ) {
         trace!(endpoint = ?self.endpoint, "starting ipc server");
 
-        if cfg!(unix) {
-            // ensure the file does not exist
-            if std::fs::remove_file(&self.endpoint).is_ok() {
-                debug!(endpoint = ?self.endpoint, "removed existing IPC endpoint file");
-            }
+        #[cfg(unix)]
+        if let Err(err) = remove_stale_socket(&self.endpoint) {
+            on_ready
+                .send(Err(IpcServerStartError { endpoint: self.endpoint.clone(), source: err }))
+                .ok();
+            return;
         }
 
+        let options = ListenerOptions::new();
+        // the socket file is created with the permissions instead of changing them after it's
+        // bound, so it's never accessible with the default ones
+        #[cfg(unix)]
+        let options = match self.cfg.socket_permissions {
+            Some(mode) => {
+                use interprocess::os::unix::local_socket::ListenerOptionsExt;
+                options.mode(mode as _)
+            }
+            None => options,
+        };
+
         let listener = match self
             .endpoint
             .as_str()
             .to_fs_name::<GenericFilePath>()
-            .and_then(|name| ListenerOptions::new().name(name).create_tokio())
+            .and_then(|name| options.name(name).create_tokio())
         {
             Ok(listener) => listener,
             Err(err) => {
@@ -217,6 +231,24 @@ where
     }
 }
 
+/// Removes the socket file left behind by a server that didn't shut down gracefully.
+///
+/// Returns an [`io::ErrorKind::AddrInUse`] error if a server is still listening on the endpoint.
+#[cfg(unix)]
+fn remove_stale_socket(endpoint: &str) -> io::Result<()> {
+    if std::os::unix::net::UnixStream::connect(endpoint).is_ok() {
+        return Err(io::Error::new(
+            io::ErrorKind::AddrInUse,
+            "another server is listening on the endpoint",
+        ))
+    }
+
+    if std::fs::remove_file(endpoint).is_ok() {
+        debug!(endpoint, "removed stale IPC endpoint file");
+    }
+    Ok(())
+}
+
 enum AcceptConnection<S> {
     Shutdown,
     Established { local_socket_stream: LocalSocketStream, stop: S },
@@ -348,10 +380,22 @@ impl<L> RpcServiceBuilder<L> {
 ///
 /// # Note
 /// This is similar to [`hyper::service::service_fn`](https://docs.rs/hyper/latest/hyper/service/fn.service_fn.html).
-#[derive(Debug, Clone)]
-pub struct TowerServiceNoHttp<L> {
+pub struct TowerServiceNoHttp<L: Layer<RpcService>> {
     inner: ServiceData,
-    rpc_middleware: RpcServiceBuilder<L>,
+    /// The RPC service of the connection, shared by all of its calls.
+    rpc_service: Arc<L::Service>,
+}
+
+impl<L: Layer<RpcService>> Clone for TowerServiceNoHttp<L> {
+    fn clone(&self) -> Self {
+        Self { inner: self.inner.clone(), rpc_service: self.rpc_service.clone() }
+    }
+}
+
+impl<L: Layer<RpcService>> std::fmt::Debug for TowerServiceNoHttp<L> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TowerServiceNoHttp").field("inner", &self.inner).finish_non_exhaustive()
+    }
 }
 
 impl<RpcMiddleware> Service<String> for TowerServiceNoHttp<RpcMiddleware>
@@ -378,23 +422,10 @@ where
     fn call(&mut self, request: String) -> Self::Future {
         trace!("{:?}", request);
 
-        let cfg = RpcServiceCfg::CallsAndSubscriptions {
-            bounded_subscriptions: BoundedSubscriptions::new(
-                self.inner.server_cfg.max_subscriptions_per_connection,
-            ),
-            id_provider: self.inner.id_provider.clone(),
-            sink: self.inner.method_sink.clone(),
-        };
-
         let max_response_body_size = self.inner.server_cfg.max_response_body_size as usize;
         let max_request_body_size = self.inner.server_cfg.max_request_body_size as usize;
         let conn = self.inner.conn_permit.clone();
-        let rpc_service = self.rpc_middleware.service(RpcService::new(
-            self.inner.methods.clone(),
-            max_response_body_size,
-            self.inner.conn_id.into(),
-            cfg,
-        ));
+        let rpc_service = SharedRpcService::new(self.rpc_service.clone());
         // an ipc connection needs to handle read+write concurrently
         // even if the underlying rpc handler spawns the actual work or is does a lot of async any
         // additional overhead performed by `handle_request` can result in I/O latencies, for
@@ -466,6 +497,22 @@ fn process_connection<'b, RpcMiddleware, HttpMiddleware>(
 
     let (tx, rx) = mpsc::channel::<String>(server_cfg.message_buffer_capacity as usize);
     let method_sink = MethodSink::new_with_limit(tx, server_cfg.max_response_body_size);
+    let bounded_subscriptions =
+        BoundedSubscriptions::new(server_cfg.max_subscriptions_per_connection);
+
+    // the rpc service is created once per connection, so that the subscription limit and the
+    // state of the rpc middleware apply to the connection rather than to a single call
+    let rpc_service = rpc_middleware.service(RpcService::new(
+        methods.clone(),
+        server_cfg.max_response_body_size as usize,
+        conn_id.into(),
+        RpcServiceCfg::CallsAndSubscriptions {
+            bounded_subscriptions: bounded_subscriptions.clone(),
+            sink: method_sink.clone(),
+            id_provider: id_provider.clone(),
+        },
+    ));
+
     let tower_service = TowerServiceNoHttp {
         inner: ServiceData {
             methods,
@@ -474,12 +521,10 @@ fn process_connection<'b, RpcMiddleware, HttpMiddleware>(
             server_cfg: server_cfg.clone(),
             conn_id,
             conn_permit,
-            bounded_subscriptions: BoundedSubscriptions::new(
-                server_cfg.max_subscriptions_per_connection,
-            ),
+            bounded_subscriptions,
             method_sink,
         },
-        rpc_middleware,
+        rpc_service: Arc::new(rpc_service),
     };
 
     let service = http_middleware.service(tower_service);
@@ -550,6 +595,11 @@ pub struct Settings {
     message_buffer_capacity: u32,
     /// Custom tokio runtime to run the server on.
     tokio_runtime: Option<tokio::runtime::Handle>,
+    /// Permissions the socket file is set to after it was created.
+    ///
+    /// Only applies on unix.
+    #[cfg_attr(not(unix), allow(dead_code))]
+    socket_permissions: Option<u32>,
 }
 
 impl Default for Settings {
@@ -562,6 +612,7 @@ impl Default for Settings {
             max_subscriptions_per_connection: 1024,
             message_buffer_capacity: 1024,
             tokio_runtime: None,
+            socket_permissions: None,
         }
     }
 }
@@ -640,6 +691,16 @@ impl<HttpMiddleware, RpcMiddleware> Builder<HttpMiddleware, RpcMiddleware> {
         self
     }
 
+    /// Set the permissions of the socket file, e.g. `0o660` to restrict access to the owner and
+    /// its group. The socket file is created with these permissions. Default is to create it with
+    /// the permissions given by the umask.
+    ///
+    /// This has no effect on Windows.
+    pub const fn socket_permissions(mut self, mode: u32) -> Self {
+        self.settings.socket_permissions = Some(mode);
+        self
+    }
+
     /// Configure a custom [`tokio::runtime::Handle`] to run the server on.
     ///
     /// Default: [`tokio::spawn`]
@@ -1029,6 +1090,91 @@ mod tests {
         assert_eq!(items.len(), 16);
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    async fn can_set_max_subscriptions_per_connection() {
+        let endpoint = &dummy_name();
+        let server = Builder::default().max_subscriptions_per_connection(1).build(endpoint.clone());
+        let (tx, _rx) = broadcast::channel::<usize>(16);
+
+        let mut module = RpcModule::new(tx);
+        module
+            .register_subscription(
+                "subscribe_hello",
+                "s_hello",
+                "unsubscribe_hello",
+                |_, pending, tx, _| async move {
+                    let stream = BroadcastStream::new(tx.subscribe());
+                    pipe_from_stream_with_bounded_buffer(pending, stream).await?;
+                    Ok(())
+                },
+            )
+            .unwrap();
+
+        let handle = server.start(module).await.unwrap();
+        tokio::spawn(handle.stopped());
+
+        let client = IpcClientBuilder::default().build(endpoint).await.unwrap();
+        let sub: Result<Subscription<usize>, Error> =
+            client.subscribe("subscribe_hello", rpc_params![], "unsubscribe_hello").await;
+        assert!(sub.is_ok());
+
+        // the limit applies to the connection, not to a single call
+        let sub: Result<Subscription<usize>, Error> =
+            client.subscribe("subscribe_hello", rpc_params![], "unsubscribe_hello").await;
+        assert!(sub.is_err());
+
+        // other connections have their own limit
+        let client2 = IpcClientBuilder::default().build(endpoint).await.unwrap();
+        let sub: Result<Subscription<usize>, Error> =
+            client2.subscribe("subscribe_hello", rpc_params![], "unsubscribe_hello").await;
+        assert!(sub.is_ok());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn can_set_socket_permissions() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let endpoint = &dummy_name();
+        let server = Builder::default().socket_permissions(0o600).build(endpoint.clone());
+        let handle = server.start(RpcModule::new(())).await.unwrap();
+        tokio::spawn(handle.stopped());
+
+        let mode = std::fs::metadata(endpoint).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn removes_stale_socket_file() {
+        let endpoint = &dummy_name();
+        // bind a socket and drop the listener without removing the file
+        drop(std::os::unix::net::UnixListener::bind(endpoint).unwrap());
+        assert!(std::path::Path::new(endpoint).exists());
+
+        let server = Builder::default().build(endpoint.clone());
+        let mut module = RpcModule::new(());
+        module.register_method("anything", |_, _, _| "succeed").unwrap();
+        let handle = server.start(module).await.unwrap();
+        tokio::spawn(handle.stopped());
+
+        let client = IpcClientBuilder::default().build(endpoint).await.unwrap();
+        let response: String = client.request("anything", rpc_params![]).await.unwrap();
+        assert_eq!(response, "succeed");
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn rejects_endpoint_in_use() {
+        let endpoint = &dummy_name();
+        let server = Builder::default().build(endpoint.clone());
+        let handle = server.start(RpcModule::new(())).await.unwrap();
+        tokio::spawn(handle.stopped());
+
+        let err = Builder::default().build(endpoint.clone()).start(RpcModule::new(())).await;
+        assert_eq!(err.unwrap_err().source.kind(), io::ErrorKind::AddrInUse);
+    }
+
     #[tokio::test]
     async fn test_rpc_middleware() {
         #[derive(Clone)]
@@ -123,3 +123,25 @@ impl<'a> RpcServiceT<'a> for RpcService {
         }
     }
 }
+
+/// Shares the RPC service of a connection between the tasks that handle its calls.
+#[derive(Debug)]
+pub(crate) struct SharedRpcService<S>(Arc<S>);
+
+impl<S> SharedRpcService<S> {
+    /// Create a new shared service.
+    pub(crate) const fn new(service: Arc<S>) -> Self {
+        Self(service)
+    }
+}
+
+impl<'a, S> RpcServiceT<'a> for SharedRpcService<S>
+where
+    S: RpcServiceT<'a>,
+{
+    type Future = S::Future;
+
+    fn call(&self, req: Request<'a>) -> Self::Future {
+        self.0.call(req)
+    }
+}
@@ -1,6 +1,8 @@
 use alloy_rpc_types_admin::{NodeInfo, PeerInfo};
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use reth_network_api::{PeerReconnectInfo, PeerStats};
 use reth_network_peers::{AnyNode, NodeRecord};
+use serde::{Deserialize, Serialize};
 
 /// Admin namespace rpc interface that gives access to several non-standard RPC methods.
 #[cfg_attr(not(feature = "client"), rpc(server, namespace = "admin"))]
@@ -32,7 +34,14 @@ pub trait AdminApi {
     /// about the nodes themselves as participants of the devp2p P2P overlay protocol, as well as
     /// specialized information added by each of the running application protocols
     #[method(name = "peers")]
-    async fn peers(&self) -> RpcResult<Vec<PeerInfo>>;
+    async fn peers(&self) -> RpcResult<Vec<AdminPeerInfo>>;
+
+    /// Returns the number of bytes exchanged with every peer the node exchanged messages with,
+    /// split by direction and message class, along with the share of the received bytes that
+    /// answered our requests for headers and bodies. The peers are ordered by the total number of
+    /// exchanged bytes, descending.
+    #[method(name = "peerStats")]
+    async fn peer_stats(&self) -> RpcResult<Vec<PeerStats>>;
 
     /// Creates an RPC subscription which serves events received from the network.
     #[subscription(
@@ -46,3 +55,16 @@ pub trait AdminApi {
     #[method(name = "nodeInfo")]
     async fn node_info(&self) -> RpcResult<NodeInfo>;
 }
+
+/// Entry of `admin_peers`.
+///
+/// Encoded like a [`PeerInfo`], with the reconnect state of trusted and static peers.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AdminPeerInfo {
+    /// The info about the peer.
+    #[serde(flatten)]
+    pub info: PeerInfo,
+    /// The reconnect state of the peer, if it's trusted or static.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reconnect: Option<PeerReconnectInfo>,
+}
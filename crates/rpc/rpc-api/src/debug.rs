@@ -1,11 +1,14 @@
 use alloy_eips::{BlockId, BlockNumberOrTag};
-use alloy_primitives::{Address, Bytes, B256};
+use alloy_primitives::{Address, BlockNumber, Bytes, B256, U64};
 use alloy_rpc_types_debug::ExecutionWitness;
-use alloy_rpc_types_eth::{transaction::TransactionRequest, Block, Bundle, StateContext};
+use alloy_rpc_types_eth::{transaction::TransactionRequest, Block, Bundle, Index, StateContext};
 use alloy_rpc_types_trace::geth::{
     BlockTraceResult, GethDebugTracingCallOptions, GethDebugTracingOptions, GethTrace, TraceResult,
 };
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use reth_chain_state::ChainReorg;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 /// Debug rpc interface.
 #[cfg_attr(not(feature = "client"), rpc(server, namespace = "debug"))]
@@ -80,6 +83,27 @@ pub trait DebugApi {
         opts: Option<GethDebugTracingOptions>,
     ) -> RpcResult<Vec<TraceResult>>;
 
+    /// Traces all transactions of the blocks in the inclusive range `[start_block, end_block]`,
+    /// emitting the traces of each block as a subscription item.
+    ///
+    /// Unlike tracing every block with `debug_traceBlockByNumber`, the historical state is only
+    /// opened once, for the parent of `start_block`, and the state changes of every traced block
+    /// are carried forward to the next one. The subscription is closed with an error if tracing a
+    /// block fails. For the last parameter see [GethDebugTracingOptions].
+    ///
+    /// This is a reth extension only available over WS and IPC.
+    #[subscription(
+        name = "traceBlockRange",
+        unsubscribe = "traceBlockRange_unsubscribe",
+        item = BlockRangeTraceResult
+    )]
+    async fn debug_trace_block_range(
+        &self,
+        start_block: BlockNumberOrTag,
+        end_block: BlockNumberOrTag,
+        opts: Option<GethDebugTracingOptions>,
+    ) -> jsonrpsee::core::SubscriptionResult;
+
     /// The `debug_traceTransaction` debugging method will attempt to run the transaction in the
     /// exact same manner as it was executed on the network. It will replay any transaction that
     /// may have been executed prior to this one before it will finally attempt to execute the
@@ -100,12 +124,15 @@ pub trait DebugApi {
     /// The trace can be configured similar to `debug_traceTransaction`,
     /// see [GethDebugTracingOptions]. The method returns the same output as
     /// `debug_traceTransaction`.
+    /// The optional fourth argument is the index of a transaction in the block, in which case the
+    /// call is executed right before that transaction instead of at the end of the block.
     #[method(name = "traceCall")]
     async fn debug_trace_call(
         &self,
         request: TransactionRequest,
         block_id: Option<BlockId>,
         opts: Option<GethDebugTracingCallOptions>,
+        tx_index: Option<Index>,
     ) -> RpcResult<GethTrace>;
 
     /// The `debug_traceCallMany` method lets you run an `eth_callMany` within the context of the
@@ -137,9 +164,32 @@ pub trait DebugApi {
     /// state root recomputation.
     ///
     /// The first argument is the block number or block hash.
+    ///
+    /// The entries of the witness are ordered by hash, so the witness of a block is always encoded
+    /// the same way. Witnesses larger than the limit of the node have to be streamed with
+    /// `debug_executionWitnessChunks`.
     #[method(name = "executionWitness")]
-    async fn debug_execution_witness(&self, block: BlockNumberOrTag)
-        -> RpcResult<ExecutionWitness>;
+    async fn debug_execution_witness(
+        &self,
+        block: BlockNumberOrTag,
+    ) -> RpcResult<OrderedExecutionWitness>;
+
+    /// Generates the execution witness of a block like `debug_executionWitness`, emitting it in
+    /// chunks of bounded size as subscription items.
+    ///
+    /// The entries are emitted in the order of their hash, and merging the chunks yields the
+    /// witness returned by `debug_executionWitness`, regardless of its size.
+    ///
+    /// This is a reth extension only available over WS and IPC.
+    #[subscription(
+        name = "executionWitnessChunks",
+        unsubscribe = "executionWitnessChunks_unsubscribe",
+        item = OrderedExecutionWitness
+    )]
+    async fn debug_execution_witness_chunks(
+        &self,
+        block: BlockNumberOrTag,
+    ) -> jsonrpsee::core::SubscriptionResult;
 
     /// Sets the logging backtrace location. When a backtrace location is set and a log message is
     /// emitted at that location, the stack of the goroutine executing the log statement will
@@ -352,7 +402,7 @@ pub trait DebugApi {
         contract_address: Address,
         key_start: B256,
         max_result: u64,
-    ) -> RpcResult<()>;
+    ) -> RpcResult<StorageRangeResult>;
 
     /// Returns the structured logs created during the execution of EVM against a block pulled
     /// from the pool of bad ones and returns them as a JSON object. For the second parameter see
@@ -408,3 +458,217 @@ pub trait DebugExecutionWitnessApi<Attributes> {
         attributes: Attributes,
     ) -> RpcResult<ExecutionWitness>;
 }
+
+/// An extension to the `debug_` namespace that changes the logging of the node at runtime.
+///
+/// This is only served by the authenticated server.
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "debug"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "debug"))]
+pub trait DebugLogApi {
+    /// Replaces the filter directives of the stdout log of the node, e.g.
+    /// `info,engine::tree=trace`.
+    ///
+    /// The directives have the same syntax as `--log.stdout.filter` and take effect immediately.
+    #[method(name = "setLogFilter")]
+    async fn debug_set_log_filter(&self, filter: String) -> RpcResult<()>;
+}
+
+/// An extension to the `debug_` namespace that returns the reorgs of the canonical chain.
+///
+/// This is only served by the authenticated server.
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "debug"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "debug"))]
+pub trait DebugReorgApi {
+    /// Returns up to `count` of the most recent reorgs of the canonical chain, most recent first.
+    ///
+    /// Only reorgs applied since the node started are returned.
+    #[method(name = "getReorgHistory")]
+    async fn debug_get_reorg_history(&self, count: usize) -> RpcResult<Vec<ReorgHistoryEntry>>;
+}
+
+/// An extension to the `debug_` namespace that provides raw access to the database and the static
+/// files of the node.
+///
+/// This is only served by the authenticated server, and only if enabled with
+/// `--rpc.enable-db-debug`.
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "debug"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "debug"))]
+pub trait DebugDbApi {
+    /// Returns the value stored under the encoded key in the database table with the given name,
+    /// or `null` if there is none.
+    ///
+    /// For dupsort tables, this is the first value of the key. Returns an error if the table is
+    /// unknown, if the key isn't a valid encoded key of the table, or if the value exceeds the
+    /// size limit.
+    #[method(name = "dbGet")]
+    async fn db_get(&self, table: String, key: Bytes) -> RpcResult<Option<DbValue>>;
+
+    /// Returns the columns of the row of the static file segment with the given number, or `null`
+    /// if there is none.
+    ///
+    /// The number is a block number for the `headers` segment, and a transaction number for the
+    /// `transactions` and `receipts` segments.
+    #[method(name = "staticFileGet")]
+    async fn static_file_get(&self, segment: String, number: U64) -> RpcResult<Option<Vec<Bytes>>>;
+}
+
+/// Response of `debug_dbGet`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DbValue {
+    /// The value as it's stored in the database.
+    pub raw: Bytes,
+    /// The decoded value, or `None` if it can't be decoded.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub decoded: Option<serde_json::Value>,
+}
+
+/// An execution witness whose entries are ordered by hash, so that its encoding is deterministic.
+///
+/// Encoded like an [`ExecutionWitness`].
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OrderedExecutionWitness {
+    /// Trie nodes keyed by their hash.
+    pub state: BTreeMap<B256, Bytes>,
+    /// Bytecodes keyed by their hash.
+    pub codes: BTreeMap<B256, Bytes>,
+    /// Unhashed account addresses and storage slots keyed by their hash.
+    pub keys: BTreeMap<B256, Bytes>,
+}
+
+impl OrderedExecutionWitness {
+    /// Returns the total size of the entries of the witness in bytes.
+    pub fn size(&self) -> usize {
+        [&self.state, &self.codes, &self.keys]
+            .into_iter()
+            .flat_map(|entries| entries.values())
+            .map(|value| B256::len_bytes() + value.len())
+            .sum()
+    }
+
+    /// Splits the witness into chunks of at most `max_chunk_size` bytes each, see
+    /// [`Self::size`].
+    ///
+    /// The trie nodes are emitted first, then the bytecodes and then the keys, each ordered by
+    /// hash. An entry larger than `max_chunk_size` is emitted in a chunk of its own.
+    pub fn into_chunks(self, max_chunk_size: usize) -> Vec<Self> {
+        let state = split_entries(self.state, max_chunk_size)
+            .map(|state| Self { state, ..Default::default() });
+        let codes = split_entries(self.codes, max_chunk_size)
+            .map(|codes| Self { codes, ..Default::default() });
+        let keys = split_entries(self.keys, max_chunk_size)
+            .map(|keys| Self { keys, ..Default::default() });
+        state.chain(codes).chain(keys).collect()
+    }
+}
+
+/// Splits the entries into maps of at most `max_size` bytes each.
+fn split_entries(
+    entries: BTreeMap<B256, Bytes>,
+    max_size: usize,
+) -> impl Iterator<Item = BTreeMap<B256, Bytes>> {
+    let mut chunks = Vec::new();
+    let mut chunk = BTreeMap::new();
+    let mut chunk_size = 0;
+    for (hash, value) in entries {
+        let size = B256::len_bytes() + value.len();
+        if chunk_size > 0 && chunk_size + size > max_size {
+            chunks.push(std::mem::take(&mut chunk));
+            chunk_size = 0;
+        }
+        chunk.insert(hash, value);
+        chunk_size += size;
+    }
+    if chunk_size > 0 {
+        chunks.push(chunk);
+    }
+    chunks.into_iter()
+}
+
+impl From<ExecutionWitness> for OrderedExecutionWitness {
+    fn from(witness: ExecutionWitness) -> Self {
+        Self {
+            state: witness.state.into_iter().collect(),
+            codes: witness.codes.into_iter().collect(),
+            keys: witness.keys.into_iter().collect(),
+        }
+    }
+}
+
+/// Traces of a block in the `debug_traceBlockRange` subscription.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockRangeTraceResult {
+    /// Number of the traced block.
+    #[serde(with = "alloy_serde::quantity")]
+    pub block_number: BlockNumber,
+    /// Hash of the traced block.
+    pub block_hash: B256,
+    /// Traces of the transactions of the block.
+    pub traces: Vec<TraceResult>,
+}
+
+/// A reorg of the canonical chain in the response of `debug_getReorgHistory`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReorgHistoryEntry {
+    /// Number of the tip of the chain that was reorged out.
+    #[serde(with = "alloy_serde::quantity")]
+    pub old_tip_number: BlockNumber,
+    /// Hash of the tip of the chain that was reorged out.
+    pub old_tip_hash: B256,
+    /// Number of blocks that were reorged out.
+    #[serde(with = "alloy_serde::quantity")]
+    pub old_chain_length: u64,
+    /// Number of the tip of the new canonical chain.
+    #[serde(with = "alloy_serde::quantity")]
+    pub new_tip_number: BlockNumber,
+    /// Hash of the tip of the new canonical chain.
+    pub new_tip_hash: B256,
+    /// Number of blocks of the new canonical chain above the common ancestor.
+    #[serde(with = "alloy_serde::quantity")]
+    pub new_chain_length: u64,
+    /// Number of the last block both chains have in common.
+    #[serde(with = "alloy_serde::quantity")]
+    pub common_ancestor_number: BlockNumber,
+    /// Hash of the last block both chains have in common.
+    pub common_ancestor_hash: B256,
+    /// Unix timestamp in seconds at which the reorg was applied.
+    #[serde(with = "alloy_serde::quantity")]
+    pub timestamp: u64,
+}
+
+impl From<ChainReorg> for ReorgHistoryEntry {
+    fn from(reorg: ChainReorg) -> Self {
+        Self {
+            old_tip_number: reorg.old_tip.number,
+            old_tip_hash: reorg.old_tip.hash,
+            old_chain_length: reorg.old_chain_length,
+            new_tip_number: reorg.new_tip.number,
+            new_tip_hash: reorg.new_tip.hash,
+            new_chain_length: reorg.new_chain_length,
+            common_ancestor_number: reorg.common_ancestor.number,
+            common_ancestor_hash: reorg.common_ancestor.hash,
+            timestamp: reorg.timestamp,
+        }
+    }
+}
+
+/// Response of `debug_storageRangeAt`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageRangeResult {
+    /// Storage slots of the page, keyed by hashed slot.
+    pub storage: BTreeMap<B256, StorageRangeEntry>,
+    /// Hashed slot the next page starts at, or `None` if this is the last page.
+    pub next_key: Option<B256>,
+}
+
+/// A storage slot in the response of `debug_storageRangeAt`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StorageRangeEntry {
+    /// The slot, or `None` if its preimage is unknown.
+    pub key: Option<B256>,
+    /// Value of the slot.
+    pub value: B256,
+}
@@ -178,6 +178,26 @@ pub trait EngineApi<Engine: EngineTypes> {
         count: U64,
     ) -> RpcResult<ExecutionPayloadBodiesV1>;
 
+    /// Same as `getPayloadBodiesByHashV1`, the withdrawals of blocks before Shanghai are `null`.
+    ///
+    /// Unknown blocks are returned as `null`.
+    #[method(name = "getPayloadBodiesByHashV2")]
+    async fn get_payload_bodies_by_hash_v2(
+        &self,
+        block_hashes: Vec<BlockHash>,
+    ) -> RpcResult<ExecutionPayloadBodiesV1>;
+
+    /// Same as `getPayloadBodiesByRangeV1`, the withdrawals of blocks before Shanghai are `null`.
+    ///
+    /// The same caution as for `getPayloadBodiesByRangeV1` applies: the input should be treated as
+    /// untrusted.
+    #[method(name = "getPayloadBodiesByRangeV2")]
+    async fn get_payload_bodies_by_range_v2(
+        &self,
+        start: U64,
+        count: U64,
+    ) -> RpcResult<ExecutionPayloadBodiesV1>;
+
     /// See also <https://github.com/ethereum/execution-apis/blob/6709c2a795b707202e93c4f2867fa0bf2640a84f/src/engine/paris.md#engine_exchangetransitionconfigurationv1>
     ///
     /// Note: This method will be deprecated after the cancun hardfork:
@@ -31,6 +31,17 @@ mod txpool;
 mod validation;
 mod web3;
 
+pub use admin::AdminPeerInfo;
+pub use debug::{
+    BlockRangeTraceResult, DbValue, OrderedExecutionWitness, ReorgHistoryEntry, StorageRangeEntry,
+    StorageRangeResult,
+};
+pub use reth::{
+    BalanceChange, BaseFeeForecast, BaseFeeForecastBlock, BlockTransactionReceipt,
+    ChainStatusBlock, ChainStatusNotification, ForkActivation, ForkReadiness, MultiProofResponse,
+    StorageMultiProofResponse, TimestampDirection, UpcomingFork,
+};
+
 /// re-export of all server traits
 pub use servers::*;
 
@@ -38,7 +49,10 @@ pub use servers::*;
 pub mod servers {
     pub use crate::{
         admin::AdminApiServer,
-        debug::{DebugApiServer, DebugExecutionWitnessApiServer},
+        debug::{
+            DebugApiServer, DebugDbApiServer, DebugExecutionWitnessApiServer, DebugLogApiServer,
+            DebugReorgApiServer,
+        },
         engine::{EngineApiServer, EngineEthApiServer},
         mev::{MevFullApiServer, MevSimApiServer},
         miner::MinerApiServer,
@@ -67,7 +81,10 @@ pub mod clients {
     pub use crate::{
         admin::AdminApiClient,
         anvil::AnvilApiClient,
-        debug::{DebugApiClient, DebugExecutionWitnessApiClient},
+        debug::{
+            DebugApiClient, DebugDbApiClient, DebugExecutionWitnessApiClient, DebugLogApiClient,
+            DebugReorgApiClient,
+        },
         engine::{EngineApiClient, EngineEthApiClient},
         ganache::GanacheApiClient,
         hardhat::HardhatApiClient,
@@ -76,7 +93,7 @@ pub mod clients {
         net::NetApiClient,
         otterscan::OtterscanClient,
         reth::RethApiClient,
-        rpc::RpcApiServer,
+        rpc::RpcApiClient,
         trace::TraceApiClient,
         txpool::TxPoolApiClient,
         validation::BlockSubmissionValidationApiClient,
@@ -84,6 +101,6 @@ pub mod clients {
     };
     pub use reth_rpc_eth_api::{
         EthApiClient, EthBundleApiClient, EthCallBundleApiClient, EthFilterApiClient,
-        L2EthApiExtServer,
+        L2EthApiExtClient,
     };
 }
@@ -1,4 +1,4 @@
-use alloy_primitives::{Bytes, U128};
+use alloy_primitives::{Address, Bytes, U128};
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
 
 /// Miner namespace rpc interface that can control miner/builder settings
@@ -18,4 +18,10 @@ pub trait MinerApi {
     /// Sets the gaslimit to target towards during mining.
     #[method(name = "setGasLimit")]
     fn set_gas_limit(&self, gas_price: U128) -> RpcResult<bool>;
+
+    /// Sets the fee recipient of the blocks built by this node.
+    ///
+    /// Returns `false` if the node doesn't build its own blocks.
+    #[method(name = "setEtherbase")]
+    fn set_etherbase(&self, etherbase: Address) -> RpcResult<bool>;
 }
@@ -1,16 +1,256 @@
 use alloy_eips::BlockId;
-use alloy_primitives::{Address, U256};
+use alloy_json_rpc::RpcObject;
+use alloy_primitives::{map::AddressHashMap, Address, Bytes, TxHash, B256, U256, U64};
+use alloy_rpc_types_eth::{AnyReceiptEnvelope, Log, TransactionReceipt};
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
-use std::collections::HashMap;
+use reth_ethereum_forks::ForkId;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
 
 /// Reth API namespace for reth-specific methods
 #[cfg_attr(not(feature = "client"), rpc(server, namespace = "reth"))]
 #[cfg_attr(feature = "client", rpc(server, client, namespace = "reth"))]
-pub trait RethApi {
-    /// Returns all ETH balance changes in a block
+pub trait RethApi<H: RpcObject> {
+    /// Returns the balances before and after the block of all accounts whose ETH balance changed
+    /// in the block.
+    ///
+    /// This includes withdrawal recipients, the block beneficiary and selfdestructed accounts.
     #[method(name = "getBalanceChangesInBlock")]
     async fn reth_get_balance_changes_in_block(
         &self,
         block_id: BlockId,
-    ) -> RpcResult<HashMap<Address, U256>>;
+    ) -> RpcResult<HashMap<Address, BalanceChange>>;
+
+    /// Returns a single merkle multiproof for all given accounts and their storage slots.
+    ///
+    /// Individual account proofs can be extracted by following the path of the hashed address
+    /// through the returned nodes. At most 1024 accounts and storage slots can be proven at once.
+    #[method(name = "getMultiProof")]
+    async fn reth_get_multi_proof(
+        &self,
+        targets: AddressHashMap<Vec<B256>>,
+        block_id: Option<BlockId>,
+    ) -> RpcResult<MultiProofResponse>;
+
+    /// Returns the fork schedule of the chain and whether the node is ready for the next fork.
+    #[method(name = "forkReadiness")]
+    async fn reth_fork_readiness(&self) -> RpcResult<ForkReadiness>;
+
+    /// Returns the receipt of a transaction in the block with the given hash, which doesn't need
+    /// to be part of the canonical chain.
+    ///
+    /// Besides canonical blocks, this serves the most recent blocks that were reorged out of the
+    /// canonical chain or executed on a side chain. Returns an error if the block is unknown, or
+    /// was recorded too long ago, and `null` if the transaction is not part of the block.
+    #[method(name = "getTransactionReceiptInBlock")]
+    async fn reth_get_transaction_receipt_in_block(
+        &self,
+        tx_hash: TxHash,
+        block_hash: B256,
+    ) -> RpcResult<Option<BlockTransactionReceipt>>;
+
+    /// Projects the base fee and blob base fee of the next `horizon_blocks` blocks, assuming the
+    /// executable transactions of the pool are included greedily by fee, and no other
+    /// transactions arrive.
+    ///
+    /// The forecast is computed once per head and returns an error if the horizon exceeds the
+    /// supported maximum.
+    #[method(name = "baseFeeForecast")]
+    async fn reth_base_fee_forecast(&self, horizon_blocks: u64) -> RpcResult<BaseFeeForecast>;
+
+    /// Returns the header of the last canonical block with a timestamp at or before the given
+    /// timestamp, or of the first one with a timestamp at or after it, depending on the direction.
+    ///
+    /// Returns `null` if there is no such block, i.e. the timestamp is before the genesis block or
+    /// after the latest block.
+    #[method(name = "getBlockByTimestamp")]
+    async fn reth_get_block_by_timestamp(
+        &self,
+        timestamp: U64,
+        direction: TimestampDirection,
+    ) -> RpcResult<Option<H>>;
+
+    /// Creates a subscription that emits the canonical head, safe and finalized block of the
+    /// chain, first the current ones and then whenever any of them changes.
+    ///
+    /// Changes that happen in quick succession, e.g. all changes of a single forkchoice update,
+    /// are coalesced into a single item with the latest status.
+    #[subscription(
+        name = "subscribeChainStatus",
+        unsubscribe = "unsubscribeChainStatus",
+        item = ChainStatusNotification
+    )]
+    async fn reth_subscribe_chain_status(&self) -> jsonrpsee::core::SubscriptionResult;
+}
+
+/// Item of the `reth_subscribeChainStatus` subscription.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChainStatusNotification {
+    /// The canonical head of the chain.
+    pub head: ChainStatusBlock,
+    /// The latest safe block, if known.
+    pub safe: Option<ChainStatusBlock>,
+    /// The latest finalized block, if known.
+    pub finalized: Option<ChainStatusBlock>,
+    /// Whether the blocks were restored from the last forkchoice state persisted before a restart
+    /// of the node, and not yet confirmed by a forkchoice update of the consensus layer.
+    #[serde(default)]
+    pub unconfirmed: bool,
+}
+
+/// Number and hash of a block in [`ChainStatusNotification`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChainStatusBlock {
+    /// Number of the block.
+    #[serde(with = "alloy_serde::quantity")]
+    pub number: u64,
+    /// Hash of the block.
+    pub hash: B256,
+}
+
+/// Direction of `reth_getBlockByTimestamp`, relative to the timestamp.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TimestampDirection {
+    /// The last block with a timestamp at or before the timestamp.
+    Before,
+    /// The first block with a timestamp at or after the timestamp.
+    After,
+}
+
+/// Response of `reth_baseFeeForecast`.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BaseFeeForecast {
+    /// Number of the head the forecast starts from.
+    #[serde(with = "alloy_serde::quantity")]
+    pub head_number: u64,
+    /// Hash of the head the forecast starts from.
+    pub head_hash: B256,
+    /// Projected fees of the blocks following the head, in order.
+    pub blocks: Vec<BaseFeeForecastBlock>,
+}
+
+/// Projected fees of a single block in [`BaseFeeForecast`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BaseFeeForecastBlock {
+    /// Number of the block.
+    #[serde(with = "alloy_serde::quantity")]
+    pub number: u64,
+    /// Projected base fee per gas of the block.
+    #[serde(with = "alloy_serde::quantity")]
+    pub base_fee: u64,
+    /// Projected base fee per blob gas of the block, if blobs are supported.
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "alloy_serde::quantity::opt")]
+    pub blob_base_fee: Option<u128>,
+    /// Projected share of the gas limit used by the block.
+    pub projected_gas_used_ratio: f64,
+    /// Projected share of the maximum blob gas used by the block.
+    pub projected_blob_gas_used_ratio: f64,
+}
+
+/// Balance change of a single account in the response of `reth_getBalanceChangesInBlock`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BalanceChange {
+    /// Balance before the block, zero if the account did not exist.
+    pub pre: U256,
+    /// Balance after the block, zero if the account was destroyed.
+    pub post: U256,
+}
+
+/// Response of `reth_getTransactionReceiptInBlock`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockTransactionReceipt {
+    /// The receipt of the transaction.
+    #[serde(flatten)]
+    pub receipt: TransactionReceipt<AnyReceiptEnvelope<Log>>,
+    /// Whether the block of the transaction is part of the canonical chain.
+    pub canonical: bool,
+}
+
+/// Response of `reth_getMultiProof`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MultiProofResponse {
+    /// State root the proof was generated against.
+    pub state_root: B256,
+    /// Deduplicated account trie nodes on the paths of all requested accounts, ordered by path.
+    pub account_proof: Vec<Bytes>,
+    /// Storage multiproofs of the requested accounts.
+    pub storage_proofs: BTreeMap<Address, StorageMultiProofResponse>,
+}
+
+/// Storage trie multiproof of a single account in [`MultiProofResponse`].
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageMultiProofResponse {
+    /// Storage root of the account.
+    pub storage_hash: B256,
+    /// Deduplicated storage trie nodes on the paths of all requested slots, ordered by path.
+    pub storage_proof: Vec<Bytes>,
+}
+
+/// Response of `reth_forkReadiness`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ForkReadiness {
+    /// Number of the latest block.
+    pub head_number: u64,
+    /// Timestamp of the latest block.
+    pub head_timestamp: u64,
+    /// All scheduled hardforks of the chain, ordered by activation.
+    pub forks: Vec<ForkActivation>,
+    /// Fork id the node currently advertises.
+    pub fork_id: ForkId,
+    /// The first scheduled hardfork that is not yet active, if any.
+    pub next_fork: Option<UpcomingFork>,
+}
+
+/// Activation condition of a single hardfork in [`ForkReadiness`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ForkActivation {
+    /// Name of the hardfork.
+    pub name: String,
+    /// Activation block, for block based forks.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub block: Option<u64>,
+    /// Activation timestamp, for timestamp based forks.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<u64>,
+    /// Terminal total difficulty, for the merge.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub total_difficulty: Option<U256>,
+    /// Whether the hardfork is active at the latest block.
+    pub active: bool,
+}
+
+/// The next upcoming hardfork in [`ForkReadiness`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpcomingFork {
+    /// Name and activation condition of the hardfork.
+    #[serde(flatten)]
+    pub fork: ForkActivation,
+    /// Number of blocks until activation, for block based forks.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub blocks_remaining: Option<u64>,
+    /// Number of seconds until activation, for timestamp based forks.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seconds_remaining: Option<u64>,
+    /// Fork id the node will advertise once the hardfork is active.
+    pub fork_id: Option<ForkId>,
+    /// Engine API version the hardfork requires, if any.
+    pub engine_api_version: Option<u8>,
+    /// Whether the engine API served by the node supports everything the hardfork requires.
+    ///
+    /// `None` for block based forks that don't require a new engine API version, or if the served
+    /// engine API is unknown.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub engine_api_ready: Option<bool>,
 }
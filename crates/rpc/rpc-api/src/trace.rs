@@ -15,6 +15,9 @@ use jsonrpsee::{core::RpcResult, proc_macros::rpc};
 #[cfg_attr(feature = "client", rpc(server, client, namespace = "trace"))]
 pub trait TraceApi {
     /// Executes the given call and returns a number of possible traces for it.
+    ///
+    /// If a transaction index is given, the call is executed right before the transaction at that
+    /// index of the block instead of at the end of the block.
     #[method(name = "call")]
     async fn trace_call(
         &self,
@@ -23,6 +26,7 @@ pub trait TraceApi {
         block_id: Option<BlockId>,
         state_overrides: Option<StateOverride>,
         block_overrides: Option<Box<BlockOverrides>>,
+        tx_index: Option<Index>,
     ) -> RpcResult<TraceResults>;
 
     /// Performs multiple call traces on top of the same block. i.e. transaction n will be executed
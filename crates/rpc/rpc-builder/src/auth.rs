@@ -1,4 +1,7 @@
-use crate::error::{RpcError, ServerKind};
+use crate::{
+    cors::OriginValidator,
+    error::{RpcError, ServerKind},
+};
 use http::header::AUTHORIZATION;
 use jsonrpsee::{
     core::RegisterMethodError,
@@ -52,9 +55,10 @@ impl AuthServerConfig {
     pub async fn start(self, module: AuthRpcModule) -> Result<AuthServerHandle, RpcError> {
         let Self { socket_addr, secret, server_config, ipc_server_config, ipc_endpoint } = self;
 
-        // Create auth middleware.
-        let middleware =
-            tower::ServiceBuilder::new().layer(AuthLayer::new(JwtAuthValidator::new(secret)));
+        // Create auth middleware, the engine API is never called from a browser.
+        let middleware = tower::ServiceBuilder::new()
+            .layer(AuthLayer::new(OriginValidator::deny_all()))
+            .layer(AuthLayer::new(JwtAuthValidator::new(secret)));
 
         // By default, both http and ws are enabled.
         let server = server_config
@@ -268,6 +272,23 @@ pub struct AuthServerHandle {
 // === impl AuthServerHandle ===
 
 impl AuthServerHandle {
+    /// Creates a new handle that isn't connected to any server.
+    ///
+    /// This can be used to satisfy types that require an engine API, if the auth server is
+    /// disabled.
+    pub fn noop() -> Self {
+        Self {
+            local_addr: SocketAddr::new(
+                IpAddr::V4(Ipv4Addr::LOCALHOST),
+                constants::DEFAULT_AUTH_PORT,
+            ),
+            handle: jsonrpsee::server::ServerHandle::new(tokio::sync::watch::channel(()).0),
+            secret: JwtSecret::random(),
+            ipc_endpoint: None,
+            ipc_handle: None,
+        }
+    }
+
     /// Returns the [`SocketAddr`] of the http server if started.
     pub const fn local_addr(&self) -> SocketAddr {
         self.local_addr
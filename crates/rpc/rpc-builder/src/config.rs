@@ -2,7 +2,7 @@ use std::{net::SocketAddr, path::PathBuf};
 
 use jsonrpsee::server::ServerBuilder;
 use reth_node_core::{args::RpcServerArgs, utils::get_or_create_jwt_secret_from_path};
-use reth_rpc::ValidationApiConfig;
+use reth_rpc::{EthBundleConfig, ValidationApiConfig};
 use reth_rpc_eth_types::{EthConfig, EthStateCacheConfig, GasPriceOracleConfig};
 use reth_rpc_layer::{JwtError, JwtSecret};
 use reth_rpc_server_types::RpcModuleSelection;
@@ -10,8 +10,8 @@ use tower::layer::util::Identity;
 use tracing::{debug, warn};
 
 use crate::{
-    auth::AuthServerConfig, error::RpcError, IpcServerBuilder, RpcModuleConfig, RpcServerConfig,
-    TransportRpcModuleConfig,
+    auth::AuthServerConfig, error::RpcError, IpcServerBuilder, RpcModuleConfig, RpcRequestLimits,
+    RpcServerConfig, TransportRpcModuleConfig, WsConnectionConfig,
 };
 
 /// A trait that provides a configured RPC server.
@@ -31,6 +31,9 @@ pub trait RethRpcServerConfig {
     /// The configured ethereum RPC settings.
     fn flashbots_config(&self) -> ValidationApiConfig;
 
+    /// The configured settings of the bundle endpoints of the flashbots namespace.
+    fn bundle_config(&self) -> EthBundleConfig;
+
     /// Returns state cache configuration.
     fn state_cache_config(&self) -> EthStateCacheConfig;
 
@@ -43,6 +46,12 @@ pub trait RethRpcServerConfig {
     /// Extracts the gas price oracle config from the args.
     fn gas_price_oracle_config(&self) -> GasPriceOracleConfig;
 
+    /// Returns the limits applied to the parameters of incoming requests.
+    fn rpc_request_limits(&self) -> RpcRequestLimits;
+
+    /// Returns the keepalive and maximum duration of ws connections.
+    fn ws_connection_config(&self) -> WsConnectionConfig;
+
     /// Creates the [`TransportRpcModuleConfig`] from cli args.
     ///
     /// This sets all the api modules, and configures additional settings like gas price oracle
@@ -103,18 +112,32 @@ impl RethRpcServerConfig for RpcServerArgs {
             .state_cache(self.state_cache_config())
             .gpo_config(self.gas_price_oracle_config())
             .proof_permits(self.rpc_proof_permits)
+            .history_cutoff(self.rpc_history_cutoff)
+            .max_tx_sync_distance(
+                self.rpc_reject_tx_while_syncing.then_some(self.rpc_max_sync_distance),
+            )
     }
 
     fn flashbots_config(&self) -> ValidationApiConfig {
         ValidationApiConfig { disallow: self.builder_disallow.clone().unwrap_or_default() }
     }
 
+    fn bundle_config(&self) -> EthBundleConfig {
+        EthBundleConfig {
+            accept_bundles_locally: self.flashbots_accept_bundles_locally,
+            max_bundle_txs: self.flashbots_max_bundle_txs,
+        }
+    }
+
     fn state_cache_config(&self) -> EthStateCacheConfig {
         EthStateCacheConfig {
             max_blocks: self.rpc_state_cache.max_blocks,
             max_receipts: self.rpc_state_cache.max_receipts,
             max_headers: self.rpc_state_cache.max_headers,
             max_concurrent_db_requests: self.rpc_state_cache.max_concurrent_db_requests,
+            max_accounts: self.rpc_state_cache.max_accounts,
+            max_storage_slots: self.rpc_state_cache.max_storage_slots,
+            max_tx_blocks: self.rpc_state_cache.max_tx_blocks,
         }
     }
 
@@ -130,9 +153,26 @@ impl RethRpcServerConfig for RpcServerArgs {
         self.gas_price_oracle.gas_price_oracle_config()
     }
 
+    fn rpc_request_limits(&self) -> RpcRequestLimits {
+        RpcRequestLimits::default()
+            .with_max_call_data_size(self.rpc_max_call_data_size)
+            .with_max_access_list_entries(self.rpc_max_access_list_entries)
+            .with_max_state_override_accounts(self.rpc_max_state_override_accounts)
+    }
+
+    fn ws_connection_config(&self) -> WsConnectionConfig {
+        WsConnectionConfig::default()
+            .with_ping_interval(self.ws_ping_interval.filter(|interval| !interval.is_zero()))
+            .with_max_missed_pongs(self.ws_ping_max_missed)
+            .with_max_connection_duration(self.ws_max_connection_duration)
+    }
+
     fn transport_rpc_module_config(&self) -> TransportRpcModuleConfig {
-        let mut config = TransportRpcModuleConfig::default()
-            .with_config(RpcModuleConfig::new(self.eth_config(), self.flashbots_config()));
+        let mut config = TransportRpcModuleConfig::default().with_config(RpcModuleConfig::new(
+            self.eth_config(),
+            self.flashbots_config(),
+            self.bundle_config(),
+        ));
 
         if self.http {
             config = config.with_http(
@@ -151,7 +191,11 @@ impl RethRpcServerConfig for RpcServerArgs {
         }
 
         if self.is_ipc_enabled() {
-            config = config.with_ipc(RpcModuleSelection::default_ipc_modules());
+            config = config.with_ipc(
+                self.ipc_api
+                    .clone()
+                    .unwrap_or_else(|| RpcModuleSelection::default_ipc_modules().into()),
+            );
         }
 
         config
@@ -174,7 +218,10 @@ impl RethRpcServerConfig for RpcServerArgs {
     }
 
     fn rpc_server_config(&self) -> RpcServerConfig {
-        let mut config = RpcServerConfig::default().with_jwt_secret(self.rpc_secret_key());
+        let mut config = RpcServerConfig::default()
+            .with_jwt_secret(self.rpc_secret_key())
+            .with_request_limits(self.rpc_request_limits())
+            .with_http_vhosts(self.http_vhosts.clone());
 
         if self.http_api.is_some() && !self.http {
             warn!(
@@ -183,6 +230,13 @@ impl RethRpcServerConfig for RpcServerArgs {
             );
         }
 
+        if (self.ipc_api.is_some() || self.ipc_chmod.is_some()) && !self.is_ipc_enabled() {
+            warn!(
+                target: "reth::cli",
+                "The --ipc.api or --ipc.chmod flag is set but --ipcdisable is set. IPC RPC API will not be exposed."
+            );
+        }
+
         if self.http {
             let socket_address = SocketAddr::new(self.http_addr, self.http_port);
             config = config
@@ -194,12 +248,19 @@ impl RethRpcServerConfig for RpcServerArgs {
 
         if self.ws {
             let socket_address = SocketAddr::new(self.ws_addr, self.ws_port);
-            config = config.with_ws_address(socket_address).with_ws(self.http_ws_server_builder());
+            config = config
+                .with_ws_address(socket_address)
+                .with_ws(self.http_ws_server_builder())
+                .with_ws_cors(self.ws_allowed_origins.clone())
+                .with_ws_connection_config(self.ws_connection_config());
         }
 
         if self.is_ipc_enabled() {
-            config =
-                config.with_ipc(self.ipc_server_builder()).with_ipc_endpoint(self.ipcpath.clone());
+            let mut builder = self.ipc_server_builder();
+            if let Some(mode) = self.ipc_chmod {
+                builder = builder.socket_permissions(mode);
+            }
+            config = config.with_ipc(builder).with_ipc_endpoint(self.ipcpath.clone());
         }
 
         config
@@ -238,7 +299,10 @@ mod tests {
     use reth_node_core::args::RpcServerArgs;
     use reth_rpc_eth_types::RPC_DEFAULT_GAS_CAP;
     use reth_rpc_server_types::{constants, RethRpcModule, RpcModuleSelection};
-    use std::net::{Ipv4Addr, SocketAddr, SocketAddrV4};
+    use std::{
+        net::{Ipv4Addr, SocketAddr, SocketAddrV4},
+        time::Duration,
+    };
 
     use crate::config::RethRpcServerConfig;
 
@@ -264,6 +328,62 @@ mod tests {
         assert!(args.is_err());
     }
 
+    #[test]
+    fn test_rpc_request_limits() {
+        let args = CommandParser::<RpcServerArgs>::parse_from(["reth"]).args;
+        assert_eq!(args.rpc_request_limits(), Default::default());
+
+        let args = CommandParser::<RpcServerArgs>::parse_from([
+            "reth",
+            "--rpc.max-calldata-size",
+            "1024",
+            "--rpc.max-access-list-entries",
+            "10",
+            "--rpc.max-state-override-accounts",
+            "5",
+        ])
+        .args;
+        let limits = args.rpc_request_limits();
+        assert_eq!(limits.max_call_data_size, 1024);
+        assert_eq!(limits.max_access_list_entries, 10);
+        assert_eq!(limits.max_state_override_accounts, 5);
+    }
+
+    #[test]
+    fn test_ws_connection_config() {
+        let args = CommandParser::<RpcServerArgs>::parse_from(["reth"]).args;
+        assert_eq!(args.ws_connection_config(), Default::default());
+        assert_eq!(args.ws_connection_config().ping_interval, None);
+
+        let args = CommandParser::<RpcServerArgs>::parse_from([
+            "reth",
+            "--ws.ping-interval",
+            "0",
+            "--ws.max-connection-duration",
+            "3600",
+        ])
+        .args;
+        let config = args.ws_connection_config();
+        assert_eq!(config.ping_interval, None);
+        assert_eq!(config.max_connection_duration, Some(Duration::from_secs(3600)));
+
+        let args = CommandParser::<RpcServerArgs>::parse_from([
+            "reth",
+            "--ws.ping-interval",
+            "500ms",
+            "--ws.ping-max-missed",
+            "3",
+        ])
+        .args;
+        let config = args.ws_connection_config();
+        assert_eq!(config.ping_interval, Some(Duration::from_millis(500)));
+        assert_eq!(config.max_missed_pongs, 3);
+
+        let args =
+            CommandParser::<RpcServerArgs>::try_parse_from(["reth", "--ws.ping-max-missed", "0"]);
+        assert!(args.is_err());
+    }
+
     #[test]
     fn test_transport_rpc_module_config() {
         let args = CommandParser::<RpcServerArgs>::parse_from([
@@ -321,6 +441,31 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_ipc_rpc_modules() {
+        let args =
+            CommandParser::<RpcServerArgs>::parse_from(["reth", "--ipc.api", "eth,net"]).args;
+        let config = args.transport_rpc_module_config();
+        let expected = [RethRpcModule::Eth, RethRpcModule::Net];
+        assert_eq!(config.ipc().cloned().unwrap().into_selection(), expected.into());
+
+        let args = CommandParser::<RpcServerArgs>::parse_from(["reth"]).args;
+        let config = args.transport_rpc_module_config();
+        assert_eq!(
+            config.ipc().cloned().unwrap().into_selection(),
+            RpcModuleSelection::default_ipc_modules()
+        );
+
+        let args = CommandParser::<RpcServerArgs>::parse_from([
+            "reth",
+            "--ipc.api",
+            "eth",
+            "--ipcdisable",
+        ])
+        .args;
+        assert!(args.transport_rpc_module_config().ipc().is_none());
+    }
+
     #[test]
     fn test_rpc_server_config() {
         let args = CommandParser::<RpcServerArgs>::parse_from([
@@ -0,0 +1,277 @@
+//! [`jsonrpsee`] helper layer for resolving block parameters relative to the canonical head.
+
+use jsonrpsee::{
+    server::middleware::rpc::RpcServiceT,
+    types::{error::INTERNAL_ERROR_CODE, ErrorObject, ErrorObjectOwned, Request},
+    MethodResponse,
+};
+use reth_provider::BlockNumReader;
+use reth_rpc_eth_types::ExtendedBlockId;
+use serde::Deserialize;
+use serde_json::{value::RawValue, Value};
+use std::{borrow::Cow, fmt, sync::Arc};
+use tower::Layer;
+
+use crate::limits::LimitedRequestFuture;
+
+/// Fields of a filter that hold block parameters.
+const FILTER_BLOCK_FIELDS: [&str; 2] = ["fromBlock", "toBlock"];
+
+/// Resolves `{"confirmations": K}` block parameters to the block `K` blocks below the canonical
+/// head, before the request is dispatched.
+///
+/// Only the block parameters of the `eth` methods that accept a block, see [`block_params`], are
+/// resolved, so the parameter is left as is everywhere else, e.g. the engine API keeps rejecting
+/// it. The head is read once per request, so all such parameters of a request, e.g. the
+/// `fromBlock` and `toBlock` of a filter, resolve against the same head.
+#[derive(Clone, Default)]
+pub struct BlockConfirmationsLayer {
+    /// Provider of the canonical head, resolution is disabled if unset.
+    provider: Option<Arc<dyn BlockNumReader>>,
+}
+
+impl BlockConfirmationsLayer {
+    /// Creates a new layer resolving block parameters against the head of the given provider.
+    pub fn new(provider: impl BlockNumReader + 'static) -> Self {
+        Self { provider: Some(Arc::new(provider)) }
+    }
+
+    /// Resolves the block parameters of the raw JSON parameters of the given method.
+    ///
+    /// Returns the rewritten parameters if any block parameter was resolved.
+    pub fn resolve(
+        &self,
+        method: &str,
+        params: Option<&str>,
+    ) -> Result<Option<Box<RawValue>>, ErrorObjectOwned> {
+        let Some(provider) = self.provider.as_deref() else { return Ok(None) };
+        let block_params = block_params(method);
+        if block_params.is_empty() {
+            return Ok(None)
+        }
+        let Some(params) = params.filter(|params| params.contains("\"confirmations\"")) else {
+            return Ok(None)
+        };
+        let Ok(mut params) = serde_json::from_str::<Value>(params) else { return Ok(None) };
+
+        let mut resolver = Resolver { provider, head: None };
+        for &(index, name, kind) in block_params {
+            let param = match &mut params {
+                Value::Array(params) => params.get_mut(index),
+                Value::Object(params) => params.get_mut(name),
+                _ => None,
+            };
+            if let Some(param) = param {
+                resolver.param(param, kind)?;
+            }
+        }
+        if resolver.head.is_none() {
+            return Ok(None)
+        }
+
+        serde_json::value::to_raw_value(&params).map(Some).map_err(internal_error)
+    }
+}
+
+impl fmt::Debug for BlockConfirmationsLayer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BlockConfirmationsLayer")
+            .field("enabled", &self.provider.is_some())
+            .finish()
+    }
+}
+
+impl<S> Layer<S> for BlockConfirmationsLayer {
+    type Service = BlockConfirmationsService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        BlockConfirmationsService::new(inner, self.clone())
+    }
+}
+
+/// A [`RpcServiceT`] middleware that resolves block parameters with confirmations, see
+/// [`BlockConfirmationsLayer`].
+#[derive(Debug, Clone)]
+pub struct BlockConfirmationsService<S> {
+    /// The resolving layer
+    layer: BlockConfirmationsLayer,
+    /// The inner service being wrapped
+    inner: S,
+}
+
+impl<S> BlockConfirmationsService<S> {
+    /// Create a new service resolving block parameters with the given layer.
+    pub const fn new(service: S, layer: BlockConfirmationsLayer) -> Self {
+        Self { inner: service, layer }
+    }
+}
+
+impl<'a, S> RpcServiceT<'a> for BlockConfirmationsService<S>
+where
+    S: RpcServiceT<'a> + Send + Sync + Clone + 'static,
+{
+    type Future = LimitedRequestFuture<S::Future>;
+
+    fn call(&self, mut req: Request<'a>) -> Self::Future {
+        let params = req.params.as_ref().map(|params| params.get());
+        match self.layer.resolve(req.method_name(), params) {
+            Ok(params) => {
+                if let Some(params) = params {
+                    req.params = Some(Cow::Owned(params));
+                }
+                LimitedRequestFuture::pending(self.inner.call(req))
+            }
+            Err(err) => LimitedRequestFuture::rejected(MethodResponse::error(req.id, err)),
+        }
+    }
+}
+
+/// A parameter that holds block parameters.
+#[derive(Debug, Clone, Copy)]
+enum BlockParam {
+    /// A block parameter.
+    Block,
+    /// A filter, with the block parameters [`FILTER_BLOCK_FIELDS`].
+    Filter,
+}
+
+/// Returns the position, name and kind of the parameters of the method that hold block
+/// parameters, empty if block parameters of the method aren't resolved.
+///
+/// The names are the argument names of the method, which are used for named parameters.
+fn block_params(method: &str) -> &'static [(usize, &'static str, BlockParam)] {
+    match method {
+        "eth_getBalance" |
+        "eth_getTransactionCount" |
+        "eth_getCode" |
+        "eth_call" |
+        "eth_estimateGas" |
+        "eth_createAccessList" => &[(1, "block_number", BlockParam::Block)],
+        "eth_getStorageAt" | "eth_getProof" => &[(2, "block_number", BlockParam::Block)],
+        "eth_getAccount" => &[(1, "block", BlockParam::Block)],
+        "eth_getBlockByNumber" |
+        "eth_getBlockTransactionCountByNumber" |
+        "eth_getHeaderByNumber" => &[(0, "number", BlockParam::Block)],
+        "eth_getBlockReceipts" => &[(0, "block_id", BlockParam::Block)],
+        "eth_getLogs" => &[(0, "filter", BlockParam::Filter)],
+        _ => &[],
+    }
+}
+
+/// Resolves block parameters against a head that's read at most once.
+struct Resolver<'a> {
+    provider: &'a dyn BlockNumReader,
+    head: Option<u64>,
+}
+
+impl Resolver<'_> {
+    /// Resolves the block parameters of the parameter.
+    fn param(&mut self, param: &mut Value, kind: BlockParam) -> Result<(), ErrorObjectOwned> {
+        match kind {
+            BlockParam::Block => self.block(param),
+            BlockParam::Filter => {
+                let Value::Object(filter) = param else { return Ok(()) };
+                for field in FILTER_BLOCK_FIELDS {
+                    if let Some(block) = filter.get_mut(field) {
+                        self.block(block)?;
+                    }
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// Replaces the block parameter with the block number if it has confirmations.
+    fn block(&mut self, block: &mut Value) -> Result<(), ErrorObjectOwned> {
+        let Ok(block_id @ ExtendedBlockId::Confirmations(_)) =
+            ExtendedBlockId::deserialize(&*block)
+        else {
+            return Ok(())
+        };
+        let head = match self.head {
+            Some(head) => head,
+            None => *self.head.insert(self.provider.best_block_number().map_err(internal_error)?),
+        };
+        *block = serde_json::to_value(block_id.resolve(head)).map_err(internal_error)?;
+        Ok(())
+    }
+}
+
+fn internal_error(err: impl ToString) -> ErrorObjectOwned {
+    ErrorObject::owned(INTERNAL_ERROR_CODE, err.to_string(), None::<()>)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::B256;
+    use reth_primitives::Header;
+    use reth_provider::test_utils::MockEthProvider;
+    use serde_json::json;
+
+    fn resolve(layer: &BlockConfirmationsLayer, method: &str, params: Value) -> Option<Value> {
+        layer
+            .resolve(method, Some(&params.to_string()))
+            .unwrap()
+            .map(|params| serde_json::from_str(params.get()).unwrap())
+    }
+
+    #[test]
+    fn resolve_block_parameters() {
+        let provider = MockEthProvider::default();
+        provider.add_header(B256::with_last_byte(1), Header { number: 100, ..Default::default() });
+        let layer = BlockConfirmationsLayer::new(provider);
+        let address = "0x0000000000000000000000000000000000000000";
+
+        assert_eq!(
+            resolve(&layer, "eth_getBalance", json!([address, { "confirmations": 6 }])),
+            Some(json!([address, "0x5e"]))
+        );
+        assert_eq!(
+            resolve(
+                &layer,
+                "eth_getLogs",
+                json!([{ "fromBlock": { "confirmations": 200 }, "toBlock": { "confirmations": 0 } }])
+            ),
+            Some(json!([{ "fromBlock": "0x0", "toBlock": "0x64" }]))
+        );
+
+        assert_eq!(
+            resolve(
+                &layer,
+                "eth_getBalance",
+                json!({ "address": address, "block_number": { "confirmations": 6 } })
+            ),
+            Some(json!({ "address": address, "block_number": "0x5e" }))
+        );
+
+        // nothing to resolve
+        assert_eq!(resolve(&layer, "eth_getBalance", json!([address, "latest"])), None);
+        assert_eq!(
+            resolve(&layer, "eth_call", json!([{ "confirmations": "0x1" }, "latest"])),
+            None
+        );
+        // only block parameters are resolved
+        assert_eq!(resolve(&layer, "eth_call", json!([{ "confirmations": 6 }, "latest"])), None);
+        assert_eq!(
+            resolve(&layer, "eth_getLogs", json!([{ "address": { "confirmations": 6 } }])),
+            None
+        );
+        // methods without block parameters are left as is
+        assert_eq!(resolve(&layer, "eth_sendTransaction", json!([{ "confirmations": 6 }])), None);
+        // engine API methods are left as is
+        assert_eq!(
+            resolve(&layer, "engine_getPayloadBodiesByRangeV1", json!([{ "confirmations": 6 }])),
+            None
+        );
+        // disabled without a provider
+        assert_eq!(
+            resolve(
+                &BlockConfirmationsLayer::default(),
+                "eth_getBalance",
+                json!([{ "confirmations": 6 }])
+            ),
+            None
+        );
+    }
+}
@@ -1,4 +1,7 @@
-use http::{HeaderValue, Method};
+use http::{header, HeaderMap, HeaderValue, Method, Response, StatusCode};
+use jsonrpsee::http_client::{HttpBody, HttpResponse};
+use reth_rpc_layer::AuthValidator;
+use std::{net::IpAddr, sync::Arc};
 use tower_http::cors::{AllowOrigin, Any, CorsLayer};
 
 /// Error thrown when parsing cors domains went wrong
@@ -17,37 +20,342 @@ pub enum CorsDomainError {
         /// The input string containing the incorrectly used wildcard
         input: String,
     },
+
+    /// Indicates that a wildcard was used anywhere but as the leftmost label of a domain
+    #[error(
+        "{pattern} is an invalid pattern, a wildcard is only allowed as the leftmost label of a \
+         domain, e.g. `https://*.example.com`"
+    )]
+    InvalidWildcard {
+        /// The pattern containing the incorrectly used wildcard
+        pattern: String,
+    },
+
+    /// Indicates an empty entry in a list
+    #[error("empty entry in list: {input}")]
+    EmptyEntry {
+        /// The input string containing the empty entry
+        input: String,
+    },
 }
 
-/// Creates a [`CorsLayer`] from the given domains
-pub(crate) fn create_cors_layer(http_cors_domains: &str) -> Result<CorsLayer, CorsDomainError> {
-    let cors = match http_cors_domains.trim() {
-        "*" => CorsLayer::new()
-            .allow_methods([Method::GET, Method::POST])
-            .allow_origin(Any)
-            .allow_headers(Any),
-        _ => {
-            let iter = http_cors_domains.split(',');
-            if iter.clone().any(|o| o == "*") {
-                return Err(CorsDomainError::WildCardNotAllowed {
-                    input: http_cors_domains.to_string(),
-                })
+/// Allowed values of the `Origin` or `Host` header of requests, parsed from a comma separated list
+/// of patterns, or `*` for any value.
+///
+/// A pattern either matches a value exactly, ignoring case, or matches all subdomains of a domain
+/// if its leftmost label is a wildcard, e.g. `https://*.example.com` matches
+/// `https://app.example.com` but not `https://example.com`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum AllowList {
+    /// Any value is allowed.
+    Any,
+    /// Values matching one of the patterns are allowed.
+    Patterns(Vec<DomainPattern>),
+}
+
+impl AllowList {
+    /// Parses the comma separated list of patterns.
+    pub(crate) fn parse(input: &str) -> Result<Self, CorsDomainError> {
+        if input.trim() == "*" {
+            return Ok(Self::Any)
+        }
+
+        let entries = input.split(',').map(str::trim).collect::<Vec<_>>();
+        if entries.contains(&"*") {
+            return Err(CorsDomainError::WildCardNotAllowed { input: input.to_string() })
+        }
+        entries
+            .into_iter()
+            .map(|entry| {
+                if entry.is_empty() {
+                    return Err(CorsDomainError::EmptyEntry { input: input.to_string() })
+                }
+                DomainPattern::parse(entry)
+            })
+            .collect::<Result<_, _>>()
+            .map(Self::Patterns)
+    }
+
+    /// Returns true if the value matches any of the patterns.
+    pub(crate) fn allows(&self, value: &str) -> bool {
+        match self {
+            Self::Any => true,
+            Self::Patterns(patterns) => patterns.iter().any(|pattern| pattern.matches(value)),
+        }
+    }
+}
+
+/// A single pattern of an [`AllowList`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum DomainPattern {
+    /// Matches the value exactly, ignoring case.
+    Exact(String),
+    /// Matches values that consist of the prefix, one or more subdomain labels and the suffix,
+    /// e.g. `https://` and `.example.com`.
+    Subdomain {
+        /// Lowercase part before the wildcard, empty or a scheme.
+        prefix: String,
+        /// Lowercase part after the wildcard, starting with a dot.
+        suffix: String,
+    },
+}
+
+impl DomainPattern {
+    /// Parses a single pattern.
+    fn parse(pattern: &str) -> Result<Self, CorsDomainError> {
+        if pattern.parse::<HeaderValue>().is_err() {
+            return Err(CorsDomainError::InvalidHeader { domain: pattern.to_string() })
+        }
+
+        let pattern_lowercase = pattern.to_ascii_lowercase();
+        let Some((prefix, suffix)) = pattern_lowercase.split_once('*') else {
+            return Ok(Self::Exact(pattern_lowercase))
+        };
+
+        // the wildcard must be the leftmost label of the domain, optionally preceded by a scheme
+        let valid_prefix = prefix.is_empty() ||
+            prefix.strip_suffix("://").is_some_and(|scheme| {
+                !scheme.is_empty() &&
+                    scheme.chars().all(|c| c.is_ascii_alphanumeric() || "+-.".contains(c))
+            });
+        let valid_suffix = suffix.len() > 1 &&
+            suffix.starts_with('.') &&
+            !suffix.starts_with("..") &&
+            !suffix.contains(['*', '/']);
+        if !valid_prefix || !valid_suffix {
+            return Err(CorsDomainError::InvalidWildcard { pattern: pattern.to_string() })
+        }
+
+        Ok(Self::Subdomain { prefix: prefix.to_string(), suffix: suffix.to_string() })
+    }
+
+    /// Returns true if the value matches the pattern.
+    fn matches(&self, value: &str) -> bool {
+        match self {
+            Self::Exact(exact) => value.eq_ignore_ascii_case(exact),
+            Self::Subdomain { prefix, suffix } => {
+                let value = value.to_ascii_lowercase();
+                value
+                    .strip_prefix(prefix.as_str())
+                    .and_then(|value| value.strip_suffix(suffix.as_str()))
+                    .is_some_and(|subdomain| {
+                        subdomain.split('.').all(|label| {
+                            !label.is_empty() &&
+                                label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+                        })
+                    })
             }
+        }
+    }
+}
 
-            let origins = iter
+/// Creates a [`CorsLayer`] from the given domains
+pub(crate) fn create_cors_layer(http_cors_domains: &str) -> Result<CorsLayer, CorsDomainError> {
+    let origin = match AllowList::parse(http_cors_domains)? {
+        AllowList::Any => AllowOrigin::from(Any),
+        AllowList::Patterns(patterns)
+            if patterns.iter().all(|pattern| matches!(pattern, DomainPattern::Exact(_))) =>
+        {
+            let origins = http_cors_domains
+                .split(',')
                 .map(|domain| {
                     domain
+                        .trim()
                         .parse::<HeaderValue>()
                         .map_err(|_| CorsDomainError::InvalidHeader { domain: domain.to_string() })
                 })
                 .collect::<Result<Vec<HeaderValue>, _>>()?;
-
-            let origin = AllowOrigin::list(origins);
-            CorsLayer::new()
-                .allow_methods([Method::GET, Method::POST])
-                .allow_origin(origin)
-                .allow_headers(Any)
+            AllowOrigin::list(origins)
         }
+        allowed => AllowOrigin::predicate(move |origin, _| {
+            origin.to_str().is_ok_and(|origin| allowed.allows(origin))
+        }),
     };
-    Ok(cors)
+    Ok(CorsLayer::new()
+        .allow_methods([Method::GET, Method::POST])
+        .allow_origin(origin)
+        .allow_headers(Any))
+}
+
+/// Rejects requests with an `Origin` header that is not allowed.
+///
+/// Browsers don't apply CORS to `WebSocket` handshakes, so unlike the [`CorsLayer`], which only
+/// instructs browsers, this rejects the requests on the server. Requests without an `Origin`
+/// header don't come from a browser and are always accepted.
+#[derive(Debug, Clone)]
+pub(crate) struct OriginValidator {
+    /// The allowed origins, `None` if requests from browsers are rejected entirely.
+    allowed: Option<Arc<AllowList>>,
+    /// Whether only `WebSocket` handshakes are checked.
+    websocket_only: bool,
+}
+
+impl OriginValidator {
+    /// Creates a validator that checks the origin of `WebSocket` handshakes against the given
+    /// allowed origins.
+    pub(crate) fn websocket(allowed: &str) -> Result<Self, CorsDomainError> {
+        Ok(Self { allowed: Some(Arc::new(AllowList::parse(allowed)?)), websocket_only: true })
+    }
+
+    /// Creates a validator that rejects all requests from browsers.
+    pub(crate) const fn deny_all() -> Self {
+        Self { allowed: None, websocket_only: false }
+    }
+}
+
+impl AuthValidator for OriginValidator {
+    fn validate(&self, headers: &HeaderMap) -> Result<(), HttpResponse> {
+        if self.websocket_only && !is_websocket_handshake(headers) {
+            return Ok(())
+        }
+        let Some(origin) = headers.get(header::ORIGIN) else { return Ok(()) };
+
+        let allowed = self
+            .allowed
+            .as_ref()
+            .is_some_and(|allowed| origin.to_str().is_ok_and(|origin| allowed.allows(origin)));
+        if allowed {
+            return Ok(())
+        }
+        Err(forbidden(format!("origin {} is not allowed", origin.to_str().unwrap_or_default())))
+    }
+}
+
+/// Rejects requests with a `Host` header that is not allowed, which protects against DNS
+/// rebinding attacks.
+///
+/// Requests to an IP address are always accepted, since DNS rebinding requires a domain name.
+#[derive(Debug, Clone)]
+pub(crate) struct HostValidator {
+    /// The allowed hosts, without ports.
+    allowed: Arc<AllowList>,
+}
+
+impl HostValidator {
+    /// Creates a validator that checks the host of requests against the given allowed hosts.
+    pub(crate) fn new(allowed: &str) -> Result<Self, CorsDomainError> {
+        Ok(Self { allowed: Arc::new(AllowList::parse(allowed)?) })
+    }
+}
+
+impl AuthValidator for HostValidator {
+    fn validate(&self, headers: &HeaderMap) -> Result<(), HttpResponse> {
+        let Some(host) = headers.get(header::HOST) else { return Ok(()) };
+        let allowed = host.to_str().is_ok_and(|host| {
+            let host = strip_port(host);
+            host.parse::<IpAddr>().is_ok() || self.allowed.allows(host)
+        });
+        if allowed {
+            return Ok(())
+        }
+        Err(forbidden(format!("host {} is not allowed", host.to_str().unwrap_or_default())))
+    }
+}
+
+/// Returns true if the request is a `WebSocket` handshake.
+fn is_websocket_handshake(headers: &HeaderMap) -> bool {
+    headers
+        .get(header::UPGRADE)
+        .and_then(|upgrade| upgrade.to_str().ok())
+        .is_some_and(|upgrade| upgrade.eq_ignore_ascii_case("websocket"))
+}
+
+/// Returns the host without the port, and IPv6 addresses without brackets.
+fn strip_port(host: &str) -> &str {
+    if let Some(host) = host.strip_prefix('[') {
+        return host.split_once(']').map_or(host, |(host, _)| host)
+    }
+    host.split_once(':').map_or(host, |(host, _)| host)
+}
+
+/// Returns a `403 Forbidden` response with the given message.
+fn forbidden(message: String) -> HttpResponse {
+    Response::builder()
+        .status(StatusCode::FORBIDDEN)
+        .body(HttpBody::new(message))
+        .expect("This should never happen")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_allow_list() {
+        assert_eq!(AllowList::parse(" * ").unwrap(), AllowList::Any);
+        assert_eq!(
+            AllowList::parse("https://a.com, http://*.B.com").unwrap(),
+            AllowList::Patterns(vec![
+                DomainPattern::Exact("https://a.com".to_string()),
+                DomainPattern::Subdomain {
+                    prefix: "http://".to_string(),
+                    suffix: ".b.com".to_string()
+                },
+            ])
+        );
+
+        for (input, offending) in [
+            ("https://a.com,https://*", "https://*"),
+            ("https://a.*.com", "https://a.*.com"),
+            ("*.a.com,https://*a.com", "https://*a.com"),
+            ("https://*.a.com/*", "https://*.a.com/*"),
+            ("*.*.a.com", "*.*.a.com"),
+        ] {
+            let err = AllowList::parse(input).unwrap_err();
+            assert!(
+                matches!(&err, CorsDomainError::InvalidWildcard { pattern } if pattern == offending),
+                "{input}: {err}"
+            );
+        }
+        assert!(matches!(
+            AllowList::parse("https://a.com,*"),
+            Err(CorsDomainError::WildCardNotAllowed { .. })
+        ));
+        assert!(matches!(
+            AllowList::parse("https://a.com,,https://b.com"),
+            Err(CorsDomainError::EmptyEntry { .. })
+        ));
+        assert!(matches!(
+            AllowList::parse("https://a.com\n"),
+            Err(CorsDomainError::InvalidHeader { .. })
+        ));
+    }
+
+    #[test]
+    fn match_patterns() {
+        let allowed = AllowList::parse("https://a.com,https://*.b.com,*.c.com").unwrap();
+        assert!(allowed.allows("https://a.com"));
+        assert!(allowed.allows("HTTPS://A.com"));
+        assert!(!allowed.allows("http://a.com"));
+        assert!(!allowed.allows("https://x.a.com"));
+
+        assert!(allowed.allows("https://app.b.com"));
+        assert!(allowed.allows("https://eu.app.b.com"));
+        assert!(!allowed.allows("https://b.com"));
+        assert!(!allowed.allows("https://evil-b.com"));
+        assert!(!allowed.allows("https://app.b.com.evil.com"));
+        assert!(!allowed.allows("https://evil.com/.b.com"));
+        assert!(!allowed.allows("http://app.b.com"));
+        assert!(!allowed.allows("https://app.b.com:8080"));
+
+        assert!(allowed.allows("app.c.com"));
+        assert!(!allowed.allows("https://app.c.com"));
+    }
+
+    #[test]
+    fn validate_host() {
+        let validator = HostValidator::new("localhost,*.internal").unwrap();
+        let validate = |host: &str| {
+            let mut headers = HeaderMap::new();
+            headers.insert(header::HOST, host.parse().unwrap());
+            validator.validate(&headers).is_ok()
+        };
+        assert!(validate("localhost:8545"));
+        assert!(validate("reth.internal"));
+        assert!(validate("127.0.0.1:8545"));
+        assert!(validate("[::1]:8545"));
+        assert!(!validate("attacker.com:8545"));
+        assert!(!validate("internal"));
+        assert!(validator.validate(&HeaderMap::new()).is_ok());
+    }
 }
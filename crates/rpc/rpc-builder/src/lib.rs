@@ -213,7 +213,12 @@ use std::{
     time::{Duration, SystemTime, UNIX_EPOCH},
 };
 
-use crate::{auth::AuthRpcModule, error::WsHttpSamePortError, metrics::RpcRequestMetrics};
+use crate::{
+    auth::AuthRpcModule,
+    cors::{HostValidator, OriginValidator},
+    error::WsHttpSamePortError,
+    metrics::RpcRequestMetrics,
+};
 use error::{ConflictingModules, RpcError, ServerKind};
 use eth::DynEthApiBuilder;
 use http::{header::AUTHORIZATION, HeaderMap};
@@ -232,23 +237,26 @@ use reth_evm::{execute::BlockExecutorProvider, ConfigureEvm};
 use reth_network_api::{noop::NoopNetwork, NetworkInfo, Peers};
 use reth_primitives::NodePrimitives;
 use reth_provider::{
-    AccountReader, BlockReader, CanonStateSubscriptions, ChainSpecProvider, ChangeSetReader,
-    FullRpcProvider, ProviderBlock, ProviderHeader, ProviderReceipt, StateProviderFactory,
+    AccountReader, BlockNumReader, BlockReader, CanonStateSubscriptions, ChainSpecProvider,
+    ChangeSetReader, FullRpcProvider, ProviderBlock, ProviderHeader, ProviderReceipt,
+    StateProviderFactory,
 };
 use reth_rpc::{
-    AdminApi, DebugApi, EngineEthApi, EthBundle, MinerApi, NetApi, OtterscanApi, RPCApi, RethApi,
-    TraceApi, TxPoolApi, ValidationApi, ValidationApiConfig, Web3Api,
+    AdminApi, DebugApi, EngineEthApi, EthBundle, EthBundleConfig, MinerApi, NetApi, OtterscanApi,
+    RPCApi, RethApi, TraceApi, TxPoolApi, ValidationApi, ValidationApiConfig, Web3Api,
 };
 use reth_rpc_api::servers::*;
 use reth_rpc_eth_api::{
     helpers::{Call, EthApiSpec, EthTransactions, LoadPendingBlock, TraceExt},
-    EthApiServer, EthApiTypes, FullEthApiServer, RpcBlock, RpcHeader, RpcReceipt, RpcTransaction,
+    EthApiServer, EthApiTypes, EthCallBundleApiServer, EthSendBundleApiServer, FullEthApiServer,
+    RpcBlockJson, RpcHeader, RpcReceipt, RpcTransactionJson,
 };
 use reth_rpc_eth_types::{EthConfig, EthStateCache, EthSubscriptionIdProvider};
 use reth_rpc_layer::{AuthLayer, Claims, CompressionLayer, JwtAuthValidator, JwtSecret};
 use reth_tasks::{pool::BlockingTaskGuard, TaskSpawner, TokioTaskExecutor};
 use reth_transaction_pool::{noop::NoopTransactionPool, TransactionPool};
 use serde::{Deserialize, Serialize};
+use tokio::net::TcpListener;
 use tower::Layer;
 use tower_http::cors::CorsLayer;
 
@@ -285,6 +293,18 @@ pub use metrics::{MeteredRequestFuture, RpcRequestMetricsService};
 // Rpc rate limiter
 pub mod rate_limiter;
 
+// Rpc request parameter limits
+pub mod limits;
+pub use limits::{RpcRequestLimits, RpcRequestLimitsService};
+
+/// Resolution of block parameters with confirmations.
+pub mod confirmations;
+pub use confirmations::{BlockConfirmationsLayer, BlockConfirmationsService};
+
+/// Lifecycle of `WebSocket` connections.
+pub mod ws;
+pub use ws::WsConnectionConfig;
+
 /// Convenience function for starting a server in one step.
 #[allow(clippy::too_many_arguments)]
 pub async fn launch<Provider, Pool, Network, Tasks, Events, EvmConfig, EthApi, BlockExecutor>(
@@ -894,6 +914,8 @@ pub struct RpcModuleConfig {
     eth: EthConfig,
     /// `flashbots` namespace settings
     flashbots: ValidationApiConfig,
+    /// Settings of the bundle endpoints of the `flashbots` namespace
+    bundle: EthBundleConfig,
 }
 
 // === impl RpcModuleConfig ===
@@ -904,9 +926,13 @@ impl RpcModuleConfig {
         RpcModuleConfigBuilder::default()
     }
 
-    /// Returns a new RPC module config given the eth namespace config
-    pub const fn new(eth: EthConfig, flashbots: ValidationApiConfig) -> Self {
-        Self { eth, flashbots }
+    /// Returns a new RPC module config given the eth and flashbots namespace configs
+    pub const fn new(
+        eth: EthConfig,
+        flashbots: ValidationApiConfig,
+        bundle: EthBundleConfig,
+    ) -> Self {
+        Self { eth, flashbots, bundle }
     }
 
     /// Get a reference to the eth namespace config
@@ -925,6 +951,7 @@ impl RpcModuleConfig {
 pub struct RpcModuleConfigBuilder {
     eth: Option<EthConfig>,
     flashbots: Option<ValidationApiConfig>,
+    bundle: Option<EthBundleConfig>,
 }
 
 // === impl RpcModuleConfigBuilder ===
@@ -942,10 +969,20 @@ impl RpcModuleConfigBuilder {
         self
     }
 
+    /// Configures a custom config of the bundle endpoints of the flashbots namespace
+    pub const fn bundle(mut self, bundle: EthBundleConfig) -> Self {
+        self.bundle = Some(bundle);
+        self
+    }
+
     /// Consumes the type and creates the [`RpcModuleConfig`]
     pub fn build(self) -> RpcModuleConfig {
-        let Self { eth, flashbots } = self;
-        RpcModuleConfig { eth: eth.unwrap_or_default(), flashbots: flashbots.unwrap_or_default() }
+        let Self { eth, flashbots, bundle } = self;
+        RpcModuleConfig {
+            eth: eth.unwrap_or_default(),
+            flashbots: flashbots.unwrap_or_default(),
+            bundle: bundle.unwrap_or_default(),
+        }
     }
 
     /// Get a reference to the eth namespace config, if any
@@ -1174,8 +1211,8 @@ where
     Network: NetworkInfo + Peers + Clone + 'static,
     Tasks: TaskSpawner + Clone + 'static,
     EthApi: EthApiServer<
-            RpcTransaction<EthApi::NetworkTypes>,
-            RpcBlock<EthApi::NetworkTypes>,
+            RpcTransactionJson<EthApi::NetworkTypes>,
+            RpcBlockJson<EthApi::NetworkTypes>,
             RpcReceipt<EthApi::NetworkTypes>,
             RpcHeader<EthApi::NetworkTypes>,
         > + EthApiTypes,
@@ -1258,7 +1295,16 @@ where
     /// # Panics
     ///
     /// If called outside of the tokio runtime.
-    pub fn register_reth(&mut self) -> &mut Self {
+    pub fn register_reth(&mut self) -> &mut Self
+    where
+        Events: CanonStateSubscriptions<
+            Primitives: NodePrimitives<
+                Block = ProviderBlock<Provider>,
+                Receipt = ProviderReceipt<Provider>,
+            >,
+        >,
+        Pool: TransactionPool + 'static,
+    {
         let rethapi = self.reth_api();
         self.modules.insert(RethRpcModule::Reth, rethapi.into_rpc().into());
         self
@@ -1306,7 +1352,7 @@ where
         EthApi: EthTransactions + LoadPendingBlock + Call,
     {
         let eth_api = self.eth_api().clone();
-        EthBundle::new(eth_api, self.blocking_pool_guard.clone())
+        EthBundle::new(eth_api, self.blocking_pool_guard.clone(), self.config.bundle)
     }
 
     /// Instantiates `DebugApi`
@@ -1340,8 +1386,20 @@ where
     }
 
     /// Instantiates `RethApi`
-    pub fn reth_api(&self) -> RethApi<Provider> {
+    pub fn reth_api(&self) -> RethApi<Provider>
+    where
+        Events: CanonStateSubscriptions<
+            Primitives: NodePrimitives<
+                Block = ProviderBlock<Provider>,
+                Receipt = ProviderReceipt<Provider>,
+            >,
+        >,
+        Pool: TransactionPool + 'static,
+    {
         RethApi::new(self.provider.clone(), Box::new(self.executor.clone()))
+            .with_orphaned_blocks(self.events.orphaned_blocks())
+            .with_chain_status(self.events.subscribe_chain_status())
+            .with_pool(self.pool.clone())
     }
 
     /// Instantiates `ValidationApi`
@@ -1364,8 +1422,10 @@ where
 impl<Provider, Pool, Network, Tasks, Events, EthApi, BlockExecutor, Consensus>
     RpcRegistryInner<Provider, Pool, Network, Tasks, Events, EthApi, BlockExecutor, Consensus>
 where
-    Provider: FullRpcProvider<Block = <BlockExecutor::Primitives as NodePrimitives>::Block>
-        + AccountReader
+    Provider: FullRpcProvider<
+            Block = <BlockExecutor::Primitives as NodePrimitives>::Block,
+            Receipt = <BlockExecutor::Primitives as NodePrimitives>::Receipt,
+        > + AccountReader
         + ChangeSetReader,
     Pool: TransactionPool + 'static,
     Network: NetworkInfo + Peers + Clone + 'static,
@@ -1481,13 +1541,11 @@ where
                             module.merge(eth_filter.clone().into_rpc()).expect("No conflicts");
                             module.merge(eth_pubsub.clone().into_rpc()).expect("No conflicts");
                             module
-                                .merge(
-                                    EthBundle::new(
-                                        eth_api.clone(),
-                                        self.blocking_pool_guard.clone(),
-                                    )
-                                    .into_rpc(),
-                                )
+                                .merge(EthCallBundleApiServer::into_rpc(EthBundle::new(
+                                    eth_api.clone(),
+                                    self.blocking_pool_guard.clone(),
+                                    self.config.bundle,
+                                )))
                                 .expect("No conflicts");
 
                             module.into()
@@ -1518,19 +1576,34 @@ where
                         RethRpcModule::Ots => OtterscanApi::new(eth_api.clone()).into_rpc().into(),
                         RethRpcModule::Reth => {
                             RethApi::new(self.provider.clone(), Box::new(self.executor.clone()))
+                                .with_orphaned_blocks(self.events.orphaned_blocks())
+                                .with_chain_status(self.events.subscribe_chain_status())
+                                .with_pool(self.pool.clone())
                                 .into_rpc()
                                 .into()
                         }
-                        RethRpcModule::Flashbots => ValidationApi::new(
-                            eth_api.provider().clone(),
-                            Arc::new(self.consensus.clone()),
-                            self.block_executor.clone(),
-                            self.config.flashbots.clone(),
-                            Box::new(self.executor.clone()),
-                            self.payload_validator.clone(),
-                        )
-                        .into_rpc()
-                        .into(),
+                        RethRpcModule::Flashbots => {
+                            let mut module = ValidationApi::new(
+                                eth_api.provider().clone(),
+                                Arc::new(self.consensus.clone()),
+                                self.block_executor.clone(),
+                                self.config.flashbots.clone(),
+                                Box::new(self.executor.clone()),
+                                self.payload_validator.clone(),
+                            )
+                            .into_rpc();
+
+                            // `eth_callBundle` is served by the eth namespace
+                            module
+                                .merge(EthSendBundleApiServer::into_rpc(EthBundle::new(
+                                    eth_api.clone(),
+                                    self.blocking_pool_guard.clone(),
+                                    self.config.bundle,
+                                )))
+                                .expect("No conflicts");
+
+                            module.into()
+                        }
                         RethRpcModule::Miner => MinerApi::default().into_rpc().into(),
                     })
                     .clone()
@@ -1556,6 +1629,8 @@ pub struct RpcServerConfig<RpcMiddleware = Identity> {
     http_server_config: Option<ServerBuilder<Identity, Identity>>,
     /// Allowed CORS Domains for http
     http_cors_domains: Option<String>,
+    /// Allowed values of the `Host` header of http requests and ws handshakes
+    http_vhosts: Option<String>,
     /// Address where to bind the http server to
     http_addr: Option<SocketAddr>,
     /// Configs for WS server
@@ -1564,12 +1639,18 @@ pub struct RpcServerConfig<RpcMiddleware = Identity> {
     ws_cors_domains: Option<String>,
     /// Address where to bind the ws server to
     ws_addr: Option<SocketAddr>,
+    /// Keepalive and maximum duration of ws connections
+    ws_connection_config: WsConnectionConfig,
     /// Configs for JSON-RPC IPC server
     ipc_server_config: Option<IpcServerBuilder<Identity, Identity>>,
     /// The Endpoint where to launch the ipc server
     ipc_endpoint: Option<String>,
     /// JWT secret for authentication
     jwt_secret: Option<JwtSecret>,
+    /// Limits applied to the parameters of incoming requests
+    request_limits: RpcRequestLimits,
+    /// Resolves block parameters with confirmations of incoming requests
+    block_confirmations: BlockConfirmationsLayer,
     /// Configurable RPC middleware
     rpc_middleware: RpcServiceBuilder<RpcMiddleware>,
 }
@@ -1582,13 +1663,17 @@ impl Default for RpcServerConfig<Identity> {
         Self {
             http_server_config: None,
             http_cors_domains: None,
+            http_vhosts: None,
             http_addr: None,
             ws_server_config: None,
             ws_cors_domains: None,
             ws_addr: None,
+            ws_connection_config: WsConnectionConfig::default(),
             ipc_server_config: None,
             ipc_endpoint: None,
             jwt_secret: None,
+            request_limits: RpcRequestLimits::default(),
+            block_confirmations: BlockConfirmationsLayer::default(),
             rpc_middleware: RpcServiceBuilder::new(),
         }
     }
@@ -1645,13 +1730,17 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
         RpcServerConfig {
             http_server_config: self.http_server_config,
             http_cors_domains: self.http_cors_domains,
+            http_vhosts: self.http_vhosts,
             http_addr: self.http_addr,
             ws_server_config: self.ws_server_config,
             ws_cors_domains: self.ws_cors_domains,
             ws_addr: self.ws_addr,
+            ws_connection_config: self.ws_connection_config,
             ipc_server_config: self.ipc_server_config,
             ipc_endpoint: self.ipc_endpoint,
             jwt_secret: self.jwt_secret,
+            request_limits: self.request_limits,
+            block_confirmations: self.block_confirmations,
             rpc_middleware,
         }
     }
@@ -1673,6 +1762,16 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
         self
     }
 
+    /// Configure the hosts http requests and ws handshakes are accepted for, as a comma separated
+    /// list of domains that may start with a wildcard label, e.g. `localhost,*.example.com`.
+    ///
+    /// Requests to an IP address are always accepted. By default, requests for any host are
+    /// accepted.
+    pub fn with_http_vhosts(mut self, vhosts: Option<String>) -> Self {
+        self.http_vhosts = vhosts;
+        self
+    }
+
     /// Configures the [`SocketAddr`] of the http server
     ///
     /// Default is [`Ipv4Addr::LOCALHOST`] and
@@ -1725,6 +1824,31 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
         self
     }
 
+    /// Configures the keepalive pings and the maximum duration of ws connections.
+    ///
+    /// Default is [`WsConnectionConfig::default`]
+    pub const fn with_ws_connection_config(mut self, config: WsConnectionConfig) -> Self {
+        self.ws_connection_config = config;
+        self
+    }
+
+    /// Configures the limits applied to the parameters of incoming requests.
+    ///
+    /// Default is [`RpcRequestLimits::default`]
+    pub const fn with_request_limits(mut self, limits: RpcRequestLimits) -> Self {
+        self.request_limits = limits;
+        self
+    }
+
+    /// Configures the provider whose canonical head `{"confirmations": K}` block parameters are
+    /// resolved against.
+    ///
+    /// By default, such block parameters are not resolved.
+    pub fn with_block_confirmations(mut self, provider: impl BlockNumReader + 'static) -> Self {
+        self.block_confirmations = BlockConfirmationsLayer::new(provider);
+        self
+    }
+
     /// Returns true if any server is configured.
     ///
     /// If no server is configured, no server will be launched on [`RpcServerConfig::start`].
@@ -1754,11 +1878,39 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
         cors.as_deref().map(cors::create_cors_layer).transpose()
     }
 
+    /// Creates the [`AuthLayer`] that rejects `WebSocket` handshakes from disallowed origins, if
+    /// any
+    fn maybe_ws_origin_layer(
+        ws_origins: Option<&str>,
+    ) -> Result<Option<AuthLayer<OriginValidator>>, CorsDomainError> {
+        ws_origins
+            .map(|origins| Ok(AuthLayer::new(OriginValidator::websocket(origins)?)))
+            .transpose()
+    }
+
+    /// Creates the [`AuthLayer`] that rejects requests for disallowed hosts, if any
+    fn maybe_host_layer(
+        vhosts: Option<&str>,
+    ) -> Result<Option<AuthLayer<HostValidator>>, CorsDomainError> {
+        vhosts.map(|vhosts| Ok(AuthLayer::new(HostValidator::new(vhosts)?))).transpose()
+    }
+
     /// Creates the [`AuthLayer`] if any
     fn maybe_jwt_layer(jwt_secret: Option<JwtSecret>) -> Option<AuthLayer<JwtAuthValidator>> {
         jwt_secret.map(|secret| AuthLayer::new(JwtAuthValidator::new(secret)))
     }
 
+    /// Enables keepalive pings on the server, if configured
+    fn maybe_ws_ping(
+        builder: ServerBuilder<Identity, Identity>,
+        config: &WsConnectionConfig,
+    ) -> ServerBuilder<Identity, Identity> {
+        match config.ping_config() {
+            Some(ping) => builder.enable_ws_ping(ping),
+            None => builder,
+        }
+    }
+
     /// Returns a [`CompressionLayer`] that adds compression support (gzip, deflate, brotli, zstd)
     /// based on the client's `Accept-Encoding` header
     fn maybe_compression_layer() -> Option<CompressionLayer> {
@@ -1772,9 +1924,18 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
     /// Returns the [`RpcServerHandle`] with the handle to the started servers.
     pub async fn start(self, modules: &TransportRpcModules) -> Result<RpcServerHandle, RpcError>
     where
-        RpcMiddleware: Layer<RpcRequestMetricsService<RpcService>> + Clone + Send + 'static,
-        for<'a> <RpcMiddleware as Layer<RpcRequestMetricsService<RpcService>>>::Service:
-            Send + Sync + 'static + RpcServiceT<'a>,
+        RpcMiddleware: Layer<
+                RpcRequestMetricsService<
+                    RpcRequestLimitsService<BlockConfirmationsService<RpcService>>,
+                >,
+            > + Clone
+            + Send
+            + 'static,
+        for<'a> <RpcMiddleware as Layer<
+            RpcRequestMetricsService<
+                RpcRequestLimitsService<BlockConfirmationsService<RpcService>>,
+            >,
+        >>::Service: Send + Sync + 'static + RpcServiceT<'a>,
     {
         let mut http_handle = None;
         let mut ws_handle = None;
@@ -1794,11 +1955,18 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
         let ipc_path =
             self.ipc_endpoint.clone().unwrap_or_else(|| constants::DEFAULT_IPC_ENDPOINT.into());
 
+        let mut ipc_endpoint = None;
         if let Some(builder) = self.ipc_server_config {
             let ipc = builder
-                .set_rpc_middleware(IpcRpcServiceBuilder::new().layer(metrics))
-                .build(ipc_path);
+                .set_rpc_middleware(
+                    IpcRpcServiceBuilder::new()
+                        .layer(metrics)
+                        .layer(self.request_limits)
+                        .layer(self.block_confirmations.clone()),
+                )
+                .build(ipc_path.clone());
             ipc_handle = Some(ipc.start(modules.ipc.clone().expect("ipc server error")).await?);
+            ipc_endpoint = Some(ipc_path);
         }
 
         // If both are configured on the same port, we combine them into one server.
@@ -1825,33 +1993,43 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
             modules.config.ensure_ws_http_identical()?;
 
             if let Some(builder) = self.http_server_config {
-                let server = builder
+                let service_builder = Self::maybe_ws_ping(builder, &self.ws_connection_config)
                     .set_http_middleware(
                         tower::ServiceBuilder::new()
-                            .option_layer(Self::maybe_cors_layer(cors)?)
+                            .option_layer(Self::maybe_host_layer(self.http_vhosts.as_deref())?)
+                            .option_layer(Self::maybe_cors_layer(cors.clone())?)
+                            .option_layer(Self::maybe_ws_origin_layer(cors.as_deref())?)
                             .option_layer(Self::maybe_jwt_layer(self.jwt_secret))
                             .option_layer(Self::maybe_compression_layer()),
                     )
                     .set_rpc_middleware(
-                        self.rpc_middleware.clone().layer(
-                            modules
-                                .http
-                                .as_ref()
-                                .or(modules.ws.as_ref())
-                                .map(RpcRequestMetrics::same_port)
-                                .unwrap_or_default(),
-                        ),
+                        self.rpc_middleware
+                            .clone()
+                            .layer(
+                                modules
+                                    .http
+                                    .as_ref()
+                                    .or(modules.ws.as_ref())
+                                    .map(RpcRequestMetrics::same_port)
+                                    .unwrap_or_default(),
+                            )
+                            .layer(self.request_limits)
+                            .layer(self.block_confirmations.clone()),
                     )
-                    .build(http_socket_addr)
-                    .await
-                    .map_err(|err| {
-                        RpcError::server_error(err, ServerKind::WsHttp(http_socket_addr))
-                    })?;
-                let addr = server.local_addr().map_err(|err| {
+                    .to_service_builder();
+                let listener = TcpListener::bind(http_socket_addr).await.map_err(|err| {
+                    RpcError::server_error(err, ServerKind::WsHttp(http_socket_addr))
+                })?;
+                let addr = listener.local_addr().map_err(|err| {
                     RpcError::server_error(err, ServerKind::WsHttp(http_socket_addr))
                 })?;
                 if let Some(module) = modules.http.as_ref().or(modules.ws.as_ref()) {
-                    let handle = server.start(module.clone());
+                    let handle = ws::serve(
+                        listener,
+                        service_builder,
+                        module.clone(),
+                        self.ws_connection_config,
+                    );
                     http_handle = Some(handle.clone());
                     ws_handle = Some(handle);
                 }
@@ -1860,7 +2038,7 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
                     ws_local_addr: Some(addr),
                     http: http_handle,
                     ws: ws_handle,
-                    ipc_endpoint: self.ipc_endpoint.clone(),
+                    ipc_endpoint,
                     ipc: ipc_handle,
                     jwt_secret: self.jwt_secret,
                 });
@@ -1873,28 +2051,33 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
         let mut http_server = None;
 
         if let Some(builder) = self.ws_server_config {
-            let server = builder
+            let service_builder = Self::maybe_ws_ping(builder, &self.ws_connection_config)
                 .ws_only()
                 .set_http_middleware(
                     tower::ServiceBuilder::new()
+                        .option_layer(Self::maybe_host_layer(self.http_vhosts.as_deref())?)
                         .option_layer(Self::maybe_cors_layer(self.ws_cors_domains.clone())?)
+                        .option_layer(Self::maybe_ws_origin_layer(self.ws_cors_domains.as_deref())?)
                         .option_layer(Self::maybe_jwt_layer(self.jwt_secret)),
                 )
                 .set_rpc_middleware(
                     self.rpc_middleware
                         .clone()
-                        .layer(modules.ws.as_ref().map(RpcRequestMetrics::ws).unwrap_or_default()),
+                        .layer(modules.ws.as_ref().map(RpcRequestMetrics::ws).unwrap_or_default())
+                        .layer(self.request_limits)
+                        .layer(self.block_confirmations.clone()),
                 )
-                .build(ws_socket_addr)
+                .to_service_builder();
+            let listener = TcpListener::bind(ws_socket_addr)
                 .await
                 .map_err(|err| RpcError::server_error(err, ServerKind::WS(ws_socket_addr)))?;
 
-            let addr = server
+            let addr = listener
                 .local_addr()
                 .map_err(|err| RpcError::server_error(err, ServerKind::WS(ws_socket_addr)))?;
 
             ws_local_addr = Some(addr);
-            ws_server = Some(server);
+            ws_server = Some((listener, service_builder));
         }
 
         if let Some(builder) = self.http_server_config {
@@ -1902,14 +2085,19 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
                 .http_only()
                 .set_http_middleware(
                     tower::ServiceBuilder::new()
-                        .option_layer(Self::maybe_cors_layer(self.ws_cors_domains.clone())?)
+                        .option_layer(Self::maybe_host_layer(self.http_vhosts.as_deref())?)
+                        .option_layer(Self::maybe_cors_layer(self.http_cors_domains.clone())?)
                         .option_layer(Self::maybe_jwt_layer(self.jwt_secret))
                         .option_layer(Self::maybe_compression_layer()),
                 )
                 .set_rpc_middleware(
-                    self.rpc_middleware.clone().layer(
-                        modules.http.as_ref().map(RpcRequestMetrics::http).unwrap_or_default(),
-                    ),
+                    self.rpc_middleware
+                        .clone()
+                        .layer(
+                            modules.http.as_ref().map(RpcRequestMetrics::http).unwrap_or_default(),
+                        )
+                        .layer(self.request_limits)
+                        .layer(self.block_confirmations.clone()),
                 )
                 .build(http_socket_addr)
                 .await
@@ -1923,14 +2111,20 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
 
         http_handle = http_server
             .map(|http_server| http_server.start(modules.http.clone().expect("http server error")));
-        ws_handle = ws_server
-            .map(|ws_server| ws_server.start(modules.ws.clone().expect("ws server error")));
+        ws_handle = ws_server.map(|(listener, service_builder)| {
+            ws::serve(
+                listener,
+                service_builder,
+                modules.ws.clone().expect("ws server error"),
+                self.ws_connection_config,
+            )
+        });
         Ok(RpcServerHandle {
             http_local_addr,
             ws_local_addr,
             http: http_handle,
             ws: ws_handle,
-            ipc_endpoint: self.ipc_endpoint.clone(),
+            ipc_endpoint,
             ipc: ipc_handle,
             jwt_secret: self.jwt_secret,
         })
@@ -2326,6 +2520,27 @@ impl TransportRpcModules {
         self.replace_ipc(other)?;
         Ok(true)
     }
+
+    /// Replace the given [`Methods`] in all configured transport modules if the given
+    /// [`RethRpcModule`] is configured for the transport.
+    pub fn replace_if_module_configured(
+        &mut self,
+        module: RethRpcModule,
+        other: impl Into<Methods>,
+    ) -> Result<(), RegisterMethodError> {
+        let other = other.into();
+        if self.module_config().contains_http(&module) {
+            self.replace_http(other.clone())?;
+        }
+        if self.module_config().contains_ws(&module) {
+            self.replace_ws(other.clone())?;
+        }
+        if self.module_config().contains_ipc(&module) {
+            self.replace_ipc(other)?;
+        }
+
+        Ok(())
+    }
 }
 
 /// A handle to the spawned servers.
@@ -2434,6 +2649,17 @@ impl RpcServerHandle {
         let client = builder.build(url).await.expect("failed to create ws client");
         Some(client)
     }
+
+    /// Returns an ipc client connected to the server.
+    #[cfg(unix)]
+    pub async fn ipc_client(&self) -> Option<jsonrpsee::async_client::Client> {
+        use reth_ipc::client::IpcClientBuilder;
+
+        let endpoint = self.ipc_endpoint.as_ref()?;
+        let client =
+            IpcClientBuilder::default().build(endpoint).await.expect("failed to create ipc client");
+        Some(client)
+    }
 }
 
 #[cfg(test)]
@@ -2760,4 +2986,26 @@ mod tests {
         assert!(modules.ipc.as_ref().unwrap().method("anything").is_some());
         assert!(modules.ws.as_ref().unwrap().method("anything").is_some());
     }
+
+    #[test]
+    fn test_replace_if_module_configured() {
+        let mut modules = TransportRpcModules {
+            config: TransportRpcModuleConfig::set_http([RethRpcModule::Miner])
+                .with_ws([RethRpcModule::Eth]),
+            http: Some(create_test_module()),
+            ws: Some(create_test_module()),
+            ..Default::default()
+        };
+        let mut other_module = RpcModule::new(());
+        other_module.register_method("anything", |_, _, _| "fails").unwrap();
+        other_module.register_method("something", |_, _, _| "fails").unwrap();
+
+        modules.replace_if_module_configured(RethRpcModule::Miner, other_module).unwrap();
+
+        // Verify that the methods were only replaced for the transport the module is configured for
+        assert!(modules.http.as_ref().unwrap().method("anything").is_some());
+        assert!(modules.http.as_ref().unwrap().method("something").is_some());
+        assert!(modules.ws.as_ref().unwrap().method("anything").is_some());
+        assert!(modules.ws.as_ref().unwrap().method("something").is_none());
+    }
 }
@@ -0,0 +1,720 @@
+//! [`jsonrpsee`] helper layer for enforcing size limits on request parameters.
+
+use jsonrpsee::{
+    server::middleware::rpc::RpcServiceT,
+    types::{error::INVALID_PARAMS_CODE, ErrorObject, ErrorObjectOwned, Request},
+    MethodResponse,
+};
+use reth_rpc_server_types::constants::{
+    DEFAULT_MAX_ACCESS_LIST_ENTRIES, DEFAULT_MAX_CALL_DATA_SIZE,
+    DEFAULT_MAX_STATE_OVERRIDE_ACCOUNTS,
+};
+use serde::{
+    de::{self, IgnoredAny, MapAccess, SeqAccess, Visitor},
+    Deserialize, Deserializer,
+};
+use serde_json::value::RawValue;
+use std::{
+    collections::HashMap,
+    fmt,
+    future::Future,
+    marker::PhantomData,
+    pin::Pin,
+    task::{Context, Poll},
+};
+use tower::Layer;
+
+/// Limits for the parameters of RPC methods that accept transaction requests.
+///
+/// The limits are checked on the raw request parameters before they are deserialized into their
+/// actual types, so oversized fields are rejected without decoding them. They apply to the decoded
+/// size of all transaction requests of a single RPC request, e.g. all calls of an
+/// `eth_simulateV1` request, and are checked for positional and named parameters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RpcRequestLimits {
+    /// Maximum total size in bytes of the call data of the transaction requests.
+    pub max_call_data_size: usize,
+    /// Maximum total number of entries in the access lists of the transaction requests.
+    pub max_access_list_entries: usize,
+    /// Maximum total number of accounts in state overrides.
+    pub max_state_override_accounts: usize,
+}
+
+impl Default for RpcRequestLimits {
+    fn default() -> Self {
+        Self {
+            max_call_data_size: DEFAULT_MAX_CALL_DATA_SIZE,
+            max_access_list_entries: DEFAULT_MAX_ACCESS_LIST_ENTRIES,
+            max_state_override_accounts: DEFAULT_MAX_STATE_OVERRIDE_ACCOUNTS,
+        }
+    }
+}
+
+impl RpcRequestLimits {
+    /// Sets the maximum total size of the call data of the transaction requests.
+    pub const fn with_max_call_data_size(mut self, max_call_data_size: usize) -> Self {
+        self.max_call_data_size = max_call_data_size;
+        self
+    }
+
+    /// Sets the maximum total number of access list entries of the transaction requests.
+    pub const fn with_max_access_list_entries(mut self, max_access_list_entries: usize) -> Self {
+        self.max_access_list_entries = max_access_list_entries;
+        self
+    }
+
+    /// Sets the maximum total number of accounts in state overrides.
+    pub const fn with_max_state_override_accounts(
+        mut self,
+        max_state_override_accounts: usize,
+    ) -> Self {
+        self.max_state_override_accounts = max_state_override_accounts;
+        self
+    }
+
+    /// Checks the raw JSON parameters of the given method against the limits.
+    ///
+    /// Methods that don't accept transaction requests and parameters that are neither a JSON
+    /// array nor a JSON object are ignored, those are rejected by the method itself if invalid.
+    pub fn check(&self, method: &str, params: Option<&str>) -> Result<(), ErrorObjectOwned> {
+        let limited = limited_params(method);
+        if limited.is_empty() {
+            return Ok(())
+        }
+        let Some(params) = params.and_then(Params::parse) else { return Ok(()) };
+
+        let mut size = RequestSize::default();
+        for &(index, name, kind) in limited {
+            if let Some(param) = params.get(index, name) {
+                size.add(kind.size(param)?);
+            }
+        }
+
+        if size.call_data > self.max_call_data_size {
+            return Err(limit_exceeded(
+                size.call_data_field,
+                size.call_data,
+                self.max_call_data_size,
+            ))
+        }
+        if size.access_list_entries > self.max_access_list_entries {
+            return Err(limit_exceeded(
+                "accessList",
+                size.access_list_entries,
+                self.max_access_list_entries,
+            ))
+        }
+        if size.state_override_accounts > self.max_state_override_accounts {
+            return Err(limit_exceeded(
+                "stateOverrides",
+                size.state_override_accounts,
+                self.max_state_override_accounts,
+            ))
+        }
+
+        Ok(())
+    }
+}
+
+impl<S> Layer<S> for RpcRequestLimits {
+    type Service = RpcRequestLimitsService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RpcRequestLimitsService::new(inner, *self)
+    }
+}
+
+/// A [`RpcServiceT`] middleware that rejects requests exceeding the [`RpcRequestLimits`].
+#[derive(Debug, Clone)]
+pub struct RpcRequestLimitsService<S> {
+    /// The enforced limits
+    limits: RpcRequestLimits,
+    /// The inner service being wrapped
+    inner: S,
+}
+
+impl<S> RpcRequestLimitsService<S> {
+    /// Create a new service enforcing the given limits.
+    pub const fn new(service: S, limits: RpcRequestLimits) -> Self {
+        Self { inner: service, limits }
+    }
+}
+
+impl<'a, S> RpcServiceT<'a> for RpcRequestLimitsService<S>
+where
+    S: RpcServiceT<'a> + Send + Sync + Clone + 'static,
+{
+    type Future = LimitedRequestFuture<S::Future>;
+
+    fn call(&self, req: Request<'a>) -> Self::Future {
+        let params = req.params.as_ref().map(|params| params.get());
+        match self.limits.check(req.method_name(), params) {
+            Ok(()) => LimitedRequestFuture::pending(self.inner.call(req)),
+            Err(err) => LimitedRequestFuture::rejected(MethodResponse::error(req.id, err)),
+        }
+    }
+}
+
+/// Response future.
+#[pin_project::pin_project]
+pub struct LimitedRequestFuture<F> {
+    #[pin]
+    fut: Option<F>,
+    rejected: Option<MethodResponse>,
+}
+
+impl<F> LimitedRequestFuture<F> {
+    /// Creates a future that resolves to the response of the inner service.
+    pub(crate) const fn pending(fut: F) -> Self {
+        Self { fut: Some(fut), rejected: None }
+    }
+
+    /// Creates a future that resolves to the response of a rejected request.
+    pub(crate) const fn rejected(response: MethodResponse) -> Self {
+        Self { fut: None, rejected: Some(response) }
+    }
+}
+
+impl<F> fmt::Debug for LimitedRequestFuture<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("LimitedRequestFuture")
+    }
+}
+
+impl<F: Future<Output = MethodResponse>> Future for LimitedRequestFuture<F> {
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        if let Some(fut) = this.fut.as_pin_mut() {
+            return fut.poll(cx)
+        }
+        Poll::Ready(this.rejected.take().expect("polled after completion"))
+    }
+}
+
+/// The positional or named parameters of a request.
+enum Params<'a> {
+    /// Parameters in a JSON array.
+    Positional(Vec<&'a RawValue>),
+    /// Parameters in a JSON object, by name.
+    Named(HashMap<String, &'a RawValue>),
+}
+
+impl<'a> Params<'a> {
+    /// Parses the raw parameters, returns `None` if they're neither a JSON array nor an object.
+    fn parse(params: &'a str) -> Option<Self> {
+        if let Ok(params) = serde_json::from_str(params) {
+            return Some(Self::Positional(params))
+        }
+        serde_json::from_str(params).ok().map(Self::Named)
+    }
+
+    /// Returns the parameter at the given position or with the given name.
+    fn get(&self, index: usize, name: &str) -> Option<&'a RawValue> {
+        match self {
+            Self::Positional(params) => params.get(index).copied(),
+            Self::Named(params) => params.get(name).copied(),
+        }
+    }
+}
+
+/// A parameter that is subject to limits.
+#[derive(Debug, Clone, Copy)]
+enum LimitedParam {
+    /// A transaction request.
+    Transaction,
+    /// A bundle of transaction requests, see `eth_callMany`.
+    Bundle,
+    /// A list of bundles of transaction requests, see `debug_traceCallMany`.
+    Bundles,
+    /// A list of transaction requests with their trace types, see `trace_callMany`.
+    TraceCalls,
+    /// The simulated blocks of `eth_simulateV1`, with their calls and state overrides.
+    SimulatePayload,
+    /// A bundle of raw transactions, see `eth_callBundle`.
+    CallBundle,
+    /// State overrides.
+    StateOverrides,
+    /// Tracing options with state overrides.
+    TracingOptions,
+}
+
+impl LimitedParam {
+    /// Returns the size of the limited fields of the parameter.
+    fn size(self, param: &RawValue) -> Result<RequestSize, ErrorObjectOwned> {
+        Ok(match self {
+            Self::Transaction => {
+                parse::<Option<TransactionRequestLimits>>(param, "transaction request")?.into()
+            }
+            Self::Bundle => parse::<Option<BundleLimits>>(param, "bundle")?.into(),
+            Self::Bundles => parse::<Option<Sum<BundleLimits>>>(param, "bundles")?.into(),
+            Self::TraceCalls => parse::<Option<Sum<TraceCallLimits>>>(param, "calls")?.into(),
+            Self::SimulatePayload => {
+                parse::<Option<SimulatePayloadLimits>>(param, "simulate payload")?.into()
+            }
+            Self::CallBundle => parse::<Option<CallBundleLimits>>(param, "bundle")?.into(),
+            Self::StateOverrides => parse::<Option<Count>>(param, "state overrides")?
+                .map(RequestSize::state_overrides)
+                .unwrap_or_default(),
+            Self::TracingOptions => {
+                parse::<Option<TracingOptionsLimits>>(param, "tracing options")?.into()
+            }
+        })
+    }
+}
+
+/// Returns the position, name and kind of the parameters of the method that are subject to
+/// limits, empty if the method doesn't accept transaction requests.
+///
+/// The names are the argument names of the method, which are used for named parameters.
+fn limited_params(method: &str) -> &'static [(usize, &'static str, LimitedParam)] {
+    match method {
+        "eth_call" => &[
+            (0, "request", LimitedParam::Transaction),
+            (2, "state_overrides", LimitedParam::StateOverrides),
+        ],
+        "eth_estimateGas" => &[
+            (0, "request", LimitedParam::Transaction),
+            (2, "state_override", LimitedParam::StateOverrides),
+        ],
+        "eth_createAccessList" | "eth_sendTransaction" => {
+            &[(0, "request", LimitedParam::Transaction)]
+        }
+        "eth_signTransaction" => &[(0, "transaction", LimitedParam::Transaction)],
+        "eth_callMany" => &[
+            (0, "bundle", LimitedParam::Bundle),
+            (2, "state_override", LimitedParam::StateOverrides),
+        ],
+        "eth_simulateV1" => &[(0, "opts", LimitedParam::SimulatePayload)],
+        "eth_callBundle" => &[(0, "request", LimitedParam::CallBundle)],
+        "trace_call" => &[
+            (0, "call", LimitedParam::Transaction),
+            (3, "state_overrides", LimitedParam::StateOverrides),
+        ],
+        "trace_callMany" => &[(0, "calls", LimitedParam::TraceCalls)],
+        "debug_traceCall" => {
+            &[(0, "request", LimitedParam::Transaction), (2, "opts", LimitedParam::TracingOptions)]
+        }
+        "debug_traceCallMany" => {
+            &[(0, "bundles", LimitedParam::Bundles), (2, "opts", LimitedParam::TracingOptions)]
+        }
+        _ => &[],
+    }
+}
+
+/// Parses a single parameter, mapping failures to an invalid params error.
+fn parse<'a, T: Deserialize<'a>>(param: &'a RawValue, name: &str) -> Result<T, ErrorObjectOwned> {
+    serde_json::from_str(param.get()).map_err(|err| {
+        ErrorObject::owned(INVALID_PARAMS_CODE, format!("invalid {name}: {err}"), None::<()>)
+    })
+}
+
+fn limit_exceeded(field: &str, value: usize, limit: usize) -> ErrorObjectOwned {
+    ErrorObject::owned(
+        INVALID_PARAMS_CODE,
+        format!("{field} exceeds the limit: {value} > {limit}"),
+        None::<()>,
+    )
+}
+
+/// The decoded size of the limited fields of a request, summed over all of its parameters.
+#[derive(Debug, Clone, Copy, Default)]
+struct RequestSize {
+    /// Total size of the call data in bytes.
+    call_data: usize,
+    /// Name of the field of the call data that was added last.
+    call_data_field: &'static str,
+    /// Total number of access list entries.
+    access_list_entries: usize,
+    /// Total number of accounts in state overrides.
+    state_override_accounts: usize,
+}
+
+impl RequestSize {
+    /// Returns the size of state overrides with the given number of accounts.
+    fn state_overrides(Count(accounts): Count) -> Self {
+        Self { state_override_accounts: accounts, ..Default::default() }
+    }
+
+    /// Adds the size of other fields to this size.
+    fn add(&mut self, other: Self) {
+        if other.call_data > 0 {
+            self.call_data = self.call_data.saturating_add(other.call_data);
+            self.call_data_field = other.call_data_field;
+        }
+        self.access_list_entries =
+            self.access_list_entries.saturating_add(other.access_list_entries);
+        self.state_override_accounts =
+            self.state_override_accounts.saturating_add(other.state_override_accounts);
+    }
+}
+
+impl<T: Into<Self>> From<Option<T>> for RequestSize {
+    fn from(value: Option<T>) -> Self {
+        value.map(Into::into).unwrap_or_default()
+    }
+}
+
+/// The fields of a transaction request that are subject to limits.
+///
+/// Only the length of strings and sequences is recorded, so this doesn't allocate for large
+/// fields.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TransactionRequestLimits {
+    #[serde(default)]
+    input: Option<HexLen>,
+    #[serde(default)]
+    data: Option<HexLen>,
+    #[serde(default)]
+    access_list: Option<Count>,
+}
+
+impl From<TransactionRequestLimits> for RequestSize {
+    fn from(request: TransactionRequestLimits) -> Self {
+        // if both are set, the larger of the `input` and `data` fields is the call data
+        let (call_data_field, call_data) = match (request.input, request.data) {
+            (Some(HexLen(input)), Some(HexLen(data))) if data > input => ("data", data),
+            (Some(HexLen(input)), _) => ("input", input),
+            (None, Some(HexLen(data))) => ("data", data),
+            (None, None) => ("input", 0),
+        };
+        Self {
+            call_data,
+            call_data_field,
+            access_list_entries: request.access_list.map_or(0, |Count(entries)| entries),
+            state_override_accounts: 0,
+        }
+    }
+}
+
+/// The transaction requests of a bundle.
+#[derive(Deserialize)]
+struct BundleLimits {
+    #[serde(default)]
+    transactions: Option<Sum<TransactionRequestLimits>>,
+}
+
+impl From<BundleLimits> for RequestSize {
+    fn from(bundle: BundleLimits) -> Self {
+        bundle.transactions.into()
+    }
+}
+
+/// A transaction request with its trace types.
+#[derive(Deserialize)]
+struct TraceCallLimits(TransactionRequestLimits, IgnoredAny);
+
+impl From<TraceCallLimits> for RequestSize {
+    fn from(call: TraceCallLimits) -> Self {
+        call.0.into()
+    }
+}
+
+/// The simulated blocks of `eth_simulateV1`.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SimulatePayloadLimits {
+    #[serde(default)]
+    block_state_calls: Option<Sum<SimulatedBlockLimits>>,
+}
+
+impl From<SimulatePayloadLimits> for RequestSize {
+    fn from(payload: SimulatePayloadLimits) -> Self {
+        payload.block_state_calls.into()
+    }
+}
+
+/// The calls and state overrides of a simulated block.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SimulatedBlockLimits {
+    #[serde(default)]
+    state_overrides: Option<Count>,
+    #[serde(default)]
+    calls: Option<Sum<TransactionRequestLimits>>,
+}
+
+impl From<SimulatedBlockLimits> for RequestSize {
+    fn from(block: SimulatedBlockLimits) -> Self {
+        let mut size = Self::from(block.calls);
+        size.add(block.state_overrides.map(Self::state_overrides).unwrap_or_default());
+        size
+    }
+}
+
+/// The raw transactions of an `eth_callBundle` bundle, which are limited like call data.
+#[derive(Deserialize)]
+struct CallBundleLimits {
+    #[serde(default)]
+    txs: Option<Sum<RawTransactionLimits>>,
+}
+
+impl From<CallBundleLimits> for RequestSize {
+    fn from(bundle: CallBundleLimits) -> Self {
+        bundle.txs.into()
+    }
+}
+
+/// A raw transaction.
+#[derive(Deserialize)]
+struct RawTransactionLimits(HexLen);
+
+impl From<RawTransactionLimits> for RequestSize {
+    fn from(RawTransactionLimits(HexLen(len)): RawTransactionLimits) -> Self {
+        Self { call_data: len, call_data_field: "txs", ..Default::default() }
+    }
+}
+
+/// The fields of tracing call options that are subject to limits.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TracingOptionsLimits {
+    #[serde(default)]
+    state_overrides: Option<Count>,
+}
+
+impl From<TracingOptionsLimits> for RequestSize {
+    fn from(opts: TracingOptionsLimits) -> Self {
+        opts.state_overrides.map(Self::state_overrides).unwrap_or_default()
+    }
+}
+
+/// The summed size of the elements of a JSON array.
+///
+/// The elements are added up while they're deserialized, so this doesn't allocate for large
+/// arrays.
+struct Sum<T>(RequestSize, PhantomData<T>);
+
+impl<T> From<Sum<T>> for RequestSize {
+    fn from(sum: Sum<T>) -> Self {
+        sum.0
+    }
+}
+
+impl<'de, T: Deserialize<'de> + Into<RequestSize>> Deserialize<'de> for Sum<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct SumVisitor<T>(PhantomData<T>);
+
+        impl<'de, T: Deserialize<'de> + Into<RequestSize>> Visitor<'de> for SumVisitor<T> {
+            type Value = Sum<T>;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("a sequence")
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Sum<T>, A::Error> {
+                let mut size = RequestSize::default();
+                while let Some(element) = seq.next_element::<T>()? {
+                    size.add(element.into());
+                }
+                Ok(Sum(size, PhantomData))
+            }
+        }
+
+        deserializer.deserialize_seq(SumVisitor(PhantomData))
+    }
+}
+
+/// Number of bytes encoded by a hex string.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct HexLen(usize);
+
+impl<'de> Deserialize<'de> for HexLen {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct HexLenVisitor;
+
+        impl Visitor<'_> for HexLenVisitor {
+            type Value = HexLen;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("a hex string")
+            }
+
+            fn visit_str<E: de::Error>(self, hex: &str) -> Result<HexLen, E> {
+                Ok(HexLen(hex.strip_prefix("0x").unwrap_or(hex).len().div_ceil(2)))
+            }
+        }
+
+        deserializer.deserialize_str(HexLenVisitor)
+    }
+}
+
+/// Number of elements of a JSON array or entries of a JSON object.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Count(usize);
+
+impl<'de> Deserialize<'de> for Count {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct CountVisitor;
+
+        impl<'de> Visitor<'de> for CountVisitor {
+            type Value = Count;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("a sequence or map")
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Count, A::Error> {
+                let mut count = 0;
+                while seq.next_element::<IgnoredAny>()?.is_some() {
+                    count += 1;
+                }
+                Ok(Count(count))
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Count, A::Error> {
+                let mut count = 0;
+                while map.next_entry::<IgnoredAny, IgnoredAny>()?.is_some() {
+                    count += 1;
+                }
+                Ok(Count(count))
+            }
+        }
+
+        deserializer.deserialize_any(CountVisitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn check(limits: RpcRequestLimits, method: &str, params: serde_json::Value) -> Option<String> {
+        limits.check(method, Some(&params.to_string())).err().map(|err| err.message().to_string())
+    }
+
+    #[test]
+    fn call_data_limit() {
+        let limits = RpcRequestLimits::default().with_max_call_data_size(4);
+        let tx = |field: &str, input: &str| json!([{ field: input }, "latest"]);
+
+        assert_eq!(check(limits, "eth_call", tx("input", "0x01020304")), None);
+        assert_eq!(
+            check(limits, "eth_call", tx("input", "0x0102030405")).unwrap(),
+            "input exceeds the limit: 5 > 4"
+        );
+        assert_eq!(
+            check(limits, "eth_estimateGas", tx("data", "0x0102030405")).unwrap(),
+            "data exceeds the limit: 5 > 4"
+        );
+        // other methods are not limited
+        assert_eq!(check(limits, "eth_getBalance", tx("input", "0x0102030405")), None);
+    }
+
+    #[test]
+    fn access_list_limit() {
+        let limits = RpcRequestLimits::default().with_max_access_list_entries(1);
+        let entry =
+            json!({ "address": "0x0000000000000000000000000000000000000000", "storageKeys": [] });
+
+        assert_eq!(check(limits, "eth_sendTransaction", json!([{ "accessList": [entry] }])), None);
+        assert_eq!(
+            check(limits, "eth_sendTransaction", json!([{ "accessList": [entry, entry] }]))
+                .unwrap(),
+            "accessList exceeds the limit: 2 > 1"
+        );
+    }
+
+    #[test]
+    fn state_override_limit() {
+        let limits = RpcRequestLimits::default().with_max_state_override_accounts(1);
+        let overrides = json!({
+            "0x0000000000000000000000000000000000000001": {},
+            "0x0000000000000000000000000000000000000002": {},
+        });
+
+        assert_eq!(check(limits, "eth_call", json!([{}, "latest", null])), None);
+        assert_eq!(
+            check(limits, "eth_call", json!([{}, "latest", overrides])).unwrap(),
+            "stateOverrides exceeds the limit: 2 > 1"
+        );
+        assert_eq!(
+            check(limits, "trace_call", json!([{}, ["trace"], "latest", overrides])).unwrap(),
+            "stateOverrides exceeds the limit: 2 > 1"
+        );
+        assert_eq!(
+            check(
+                limits,
+                "debug_traceCall",
+                json!([{}, "latest", { "stateOverrides": overrides }])
+            )
+            .unwrap(),
+            "stateOverrides exceeds the limit: 2 > 1"
+        );
+    }
+
+    #[test]
+    fn named_params() {
+        let limits = RpcRequestLimits::default()
+            .with_max_call_data_size(4)
+            .with_max_state_override_accounts(0);
+
+        assert_eq!(check(limits, "eth_call", json!({ "request": { "input": "0x01" } })), None);
+        assert_eq!(
+            check(limits, "eth_call", json!({ "request": { "input": "0x0102030405" } })).unwrap(),
+            "input exceeds the limit: 5 > 4"
+        );
+        let overrides = json!({ "0x0000000000000000000000000000000000000001": {} });
+        assert_eq!(
+            check(limits, "eth_estimateGas", json!({ "request": {}, "state_override": overrides }))
+                .unwrap(),
+            "stateOverrides exceeds the limit: 1 > 0"
+        );
+    }
+
+    #[test]
+    fn limits_apply_to_all_transactions_of_a_request() {
+        let limits = RpcRequestLimits::default().with_max_call_data_size(4);
+        let tx = json!({ "input": "0x010203" });
+
+        assert_eq!(
+            check(limits, "eth_callMany", json!([{ "transactions": [tx, tx] }])).unwrap(),
+            "input exceeds the limit: 6 > 4"
+        );
+        assert_eq!(
+            check(
+                limits,
+                "debug_traceCallMany",
+                json!([[{ "transactions": [tx] }, { "transactions": [tx] }]])
+            )
+            .unwrap(),
+            "input exceeds the limit: 6 > 4"
+        );
+        assert_eq!(
+            check(limits, "trace_callMany", json!([[[tx, ["trace"]], [tx, ["trace"]]], "latest"]))
+                .unwrap(),
+            "input exceeds the limit: 6 > 4"
+        );
+        assert_eq!(
+            check(
+                limits,
+                "eth_simulateV1",
+                json!([{ "blockStateCalls": [{ "calls": [tx] }, { "calls": [tx] }] }])
+            )
+            .unwrap(),
+            "input exceeds the limit: 6 > 4"
+        );
+        assert_eq!(
+            check(limits, "eth_callBundle", json!([{ "txs": ["0x010203", "0x010203"] }])).unwrap(),
+            "txs exceeds the limit: 6 > 4"
+        );
+        assert_eq!(check(limits, "eth_callMany", json!([{ "transactions": [tx] }])), None);
+    }
+
+    #[test]
+    fn state_override_limit_of_simulated_blocks() {
+        let limits = RpcRequestLimits::default().with_max_state_override_accounts(1);
+        let overrides = json!({ "0x0000000000000000000000000000000000000001": {} });
+        let block = json!({ "stateOverrides": overrides, "calls": [] });
+
+        assert_eq!(check(limits, "eth_simulateV1", json!([{ "blockStateCalls": [block] }])), None);
+        assert_eq!(
+            check(limits, "eth_simulateV1", json!([{ "blockStateCalls": [block, block] }]))
+                .unwrap(),
+            "stateOverrides exceeds the limit: 2 > 1"
+        );
+    }
+}
@@ -0,0 +1,242 @@
+//! Lifecycle of `WebSocket` connections: keepalive pings, a maximum connection duration and
+//! connection metrics.
+//!
+//! Clients behind proxies can disappear without closing their connection. Without keepalive pings
+//! such connections, and the subscriptions they hold, are only released once TCP notices that the
+//! peer is gone. Pings are opt-in: with a [`WsConnectionConfig::ping_interval`], a client that
+//! leaves [`WsConnectionConfig::max_missed_pongs`] consecutive pings unanswered is disconnected.
+
+use bytes::Bytes;
+use hyper::{body::Incoming, StatusCode};
+use jsonrpsee::{
+    core::BoxError,
+    server::{
+        serve_with_graceful_shutdown, stop_channel, ws, HttpRequest, HttpResponse, PingConfig,
+        ServerHandle, StopHandle, TowerService, TowerServiceBuilder,
+    },
+    Methods,
+};
+use reth_metrics::{
+    metrics::{Counter, Gauge},
+    Metrics,
+};
+use reth_rpc_server_types::constants::DEFAULT_WS_MAX_MISSED_PONGS;
+use std::{future::Future, time::Duration};
+use tokio::net::TcpListener;
+use tower::Service;
+use tracing::{debug, trace};
+
+/// Configuration of the lifecycle of `WebSocket` connections.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WsConnectionConfig {
+    /// Interval at which pings are sent to clients. Pings are disabled if unset.
+    pub ping_interval: Option<Duration>,
+    /// Number of consecutive pings a client may leave unanswered before it's disconnected.
+    pub max_missed_pongs: usize,
+    /// Duration after which a connection is closed, so that clients reconnect and are balanced
+    /// over the available servers. Connections are kept open indefinitely if unset.
+    pub max_connection_duration: Option<Duration>,
+}
+
+impl Default for WsConnectionConfig {
+    fn default() -> Self {
+        Self {
+            ping_interval: None,
+            max_missed_pongs: DEFAULT_WS_MAX_MISSED_PONGS,
+            max_connection_duration: None,
+        }
+    }
+}
+
+impl WsConnectionConfig {
+    /// Sets the interval at which pings are sent to clients, or disables pings.
+    pub const fn with_ping_interval(mut self, ping_interval: Option<Duration>) -> Self {
+        self.ping_interval = ping_interval;
+        self
+    }
+
+    /// Sets the number of consecutive pings a client may leave unanswered.
+    pub const fn with_max_missed_pongs(mut self, max_missed_pongs: usize) -> Self {
+        self.max_missed_pongs = max_missed_pongs;
+        self
+    }
+
+    /// Sets the duration after which a connection is closed.
+    pub const fn with_max_connection_duration(
+        mut self,
+        max_connection_duration: Option<Duration>,
+    ) -> Self {
+        self.max_connection_duration = max_connection_duration;
+        self
+    }
+
+    /// Returns the [`PingConfig`] of the server, if pings are enabled.
+    ///
+    /// A pong must be received before the next ping is sent, otherwise the ping counts as missed.
+    pub fn ping_config(&self) -> Option<PingConfig> {
+        self.ping_interval.map(|interval| {
+            PingConfig::new()
+                .ping_interval(interval)
+                .inactive_limit(interval)
+                .max_failures(self.max_missed_pongs.max(1))
+        })
+    }
+}
+
+/// The reason a `WebSocket` connection was closed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CloseReason {
+    /// The client closed the connection, the connection failed or the client missed too many
+    /// pongs.
+    Remote,
+    /// The connection reached its maximum duration.
+    MaxDuration,
+    /// The server was stopped.
+    Shutdown,
+}
+
+/// Serves the methods over the listener, tracking the lifecycle of every `WebSocket` connection.
+///
+/// Each connection gets its own stop handle, which is triggered once the connection reaches
+/// [`WsConnectionConfig::max_connection_duration`] or the server is stopped. This closes the
+/// connection gracefully, like the server would on shutdown, and ends all of its subscriptions.
+///
+/// Returns the handle of the server.
+pub(crate) fn serve<RpcMiddleware, HttpMiddleware, Body>(
+    listener: TcpListener,
+    service_builder: TowerServiceBuilder<RpcMiddleware, HttpMiddleware>,
+    methods: impl Into<Methods>,
+    config: WsConnectionConfig,
+) -> ServerHandle
+where
+    RpcMiddleware: Clone + Send + Sync + 'static,
+    HttpMiddleware: Clone + Send + Sync + 'static,
+    TowerService<RpcMiddleware, HttpMiddleware>:
+        Service<HttpRequest<Incoming>, Response = HttpResponse<Body>> + Send + 'static,
+    <TowerService<RpcMiddleware, HttpMiddleware> as Service<HttpRequest<Incoming>>>::Error:
+        Into<BoxError>,
+    <TowerService<RpcMiddleware, HttpMiddleware> as Service<HttpRequest<Incoming>>>::Future: Send,
+    Body: http_body::Body<Data = Bytes> + Send + 'static,
+    Body::Error: Into<BoxError>,
+{
+    let methods = methods.into();
+    let metrics = WsConnectionMetrics::default();
+    let (stop_handle, server_handle) = stop_channel();
+
+    tokio::spawn(async move {
+        loop {
+            let io = tokio::select! {
+                _ = stop_handle.clone().shutdown() => break,
+                io = listener.accept() => {
+                    match io {
+                        Ok((stream, _remote_addr)) => stream,
+                        Err(err) => {
+                            debug!(target: "rpc::ws", %err, "Failed to accept connection");
+                            continue
+                        }
+                    }
+                }
+            };
+
+            let service_builder = service_builder.clone();
+            let methods = methods.clone();
+            let server_stop = stop_handle.clone();
+            let metrics = metrics.clone();
+            let service = tower::service_fn(move |req: HttpRequest<Incoming>| {
+                let (mut service, connection) = if ws::is_upgrade_request(&req) {
+                    // The connection is stopped on its own, either when it reaches its maximum
+                    // duration or when the server is stopped.
+                    let (connection_stop, connection_handle) = stop_channel();
+                    let mut service =
+                        service_builder.clone().build(methods.clone(), connection_stop);
+                    let session_closed = service.on_session_closed();
+                    (service, Some((session_closed, connection_handle)))
+                } else {
+                    (service_builder.clone().build(methods.clone(), server_stop.clone()), None)
+                };
+                let server_stop = server_stop.clone();
+                let metrics = metrics.clone();
+                async move {
+                    let response = service.call(req).await.map_err(Into::<BoxError>::into)?;
+                    // Only connections that were upgraded are tracked, a rejected upgrade never
+                    // opens a session.
+                    if let Some((session_closed, connection_handle)) = connection {
+                        if response.status() == StatusCode::SWITCHING_PROTOCOLS {
+                            tokio::spawn(track_connection(
+                                session_closed,
+                                connection_handle,
+                                server_stop,
+                                config.max_connection_duration,
+                                metrics,
+                            ));
+                        }
+                    }
+                    Ok::<_, BoxError>(response)
+                }
+            });
+
+            let shutdown = stop_handle.clone().shutdown();
+            tokio::spawn(async move {
+                if let Err(err) = serve_with_graceful_shutdown(io, service, shutdown).await {
+                    trace!(target: "rpc::ws", %err, "Failed to serve connection");
+                }
+            });
+        }
+    });
+
+    server_handle
+}
+
+/// Tracks a `WebSocket` connection until it's closed, and closes it once it reaches its maximum
+/// duration or the server is stopped.
+async fn track_connection(
+    session_closed: impl Future<Output = ()> + Send,
+    connection_handle: ServerHandle,
+    server_stop: StopHandle,
+    max_duration: Option<Duration>,
+    metrics: WsConnectionMetrics,
+) {
+    metrics.active_connections.increment(1.);
+
+    let max_duration_reached = async {
+        match max_duration {
+            Some(duration) => tokio::time::sleep(duration).await,
+            None => std::future::pending().await,
+        }
+    };
+    tokio::pin!(session_closed);
+
+    let reason = tokio::select! {
+        _ = &mut session_closed => CloseReason::Remote,
+        _ = max_duration_reached => CloseReason::MaxDuration,
+        _ = server_stop.shutdown() => CloseReason::Shutdown,
+    };
+
+    if reason != CloseReason::Remote {
+        trace!(target: "rpc::ws", ?reason, "Closing connection");
+        let _ = connection_handle.stop();
+        session_closed.await;
+    }
+
+    metrics.active_connections.decrement(1.);
+    match reason {
+        CloseReason::Remote => metrics.closed_remote_total.increment(1),
+        CloseReason::MaxDuration => metrics.closed_max_duration_total.increment(1),
+        CloseReason::Shutdown => metrics.closed_shutdown_total.increment(1),
+    }
+}
+
+/// Metrics for the `WebSocket` connections.
+#[derive(Metrics, Clone)]
+#[metrics(scope = "rpc_server.ws")]
+struct WsConnectionMetrics {
+    /// The number of open connections
+    active_connections: Gauge,
+    /// The number of connections closed by the client, including the clients that missed too
+    /// many pongs and failed connections
+    closed_remote_total: Counter,
+    /// The number of connections closed after reaching the maximum connection duration
+    closed_max_duration_total: Counter,
+    /// The number of connections closed because the server was stopped
+    closed_shutdown_total: Counter,
+}
@@ -0,0 +1,87 @@
+//! Tests for the allocations of the request parameter limits of the rpc server.
+//!
+//! This is a separate test binary, because it replaces the global allocator.
+
+#![allow(missing_docs)]
+
+use jsonrpsee::types::error::INVALID_PARAMS_CODE;
+use reth_rpc_builder::RpcRequestLimits;
+use serde_json::{json, Value};
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+};
+
+/// Allocator that tracks the number of bytes allocated by the current thread.
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATED: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let _ = ALLOCATED.try_with(|allocated| allocated.set(allocated.get() + layout.size()));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+/// Returns the number of bytes allocated by the current thread while running `f`.
+fn allocated_by<T>(f: impl FnOnce() -> T) -> (T, usize) {
+    let before = ALLOCATED.with(Cell::get);
+    let out = f();
+    (out, ALLOCATED.with(Cell::get) - before)
+}
+
+const LIMITS: RpcRequestLimits = RpcRequestLimits {
+    max_call_data_size: 1024,
+    max_access_list_entries: 8,
+    max_state_override_accounts: 8,
+};
+
+fn oversized_input() -> Value {
+    json!({ "input": format!("0x{}", "ab".repeat(4 * 1024 * 1024)) })
+}
+
+fn oversized_access_list() -> Value {
+    let entry =
+        json!({ "address": "0x0000000000000000000000000000000000000001", "storageKeys": [] });
+    json!({ "accessList": vec![entry; 10_000] })
+}
+
+fn oversized_state_overrides() -> Value {
+    let overrides = (0..10_000u64)
+        .map(|i| (format!("0x{i:040x}"), json!({ "balance": "0x1" })))
+        .collect::<serde_json::Map<_, _>>();
+    Value::Object(overrides)
+}
+
+#[test]
+fn test_oversized_params_rejected_without_large_allocations() {
+    let small_calls = vec![json!({ "input": format!("0x{}", "ab".repeat(512)) }); 10_000];
+    let cases = [
+        ("input", "eth_call", json!([oversized_input()])),
+        ("accessList", "eth_call", json!([oversized_access_list()])),
+        ("stateOverrides", "eth_call", json!([{}, "latest", oversized_state_overrides()])),
+        ("input", "eth_call", json!({ "request": oversized_input() })),
+        ("input", "eth_callMany", json!([{ "transactions": small_calls }])),
+        ("input", "eth_simulateV1", json!([{ "blockStateCalls": [{ "calls": small_calls }] }])),
+    ];
+
+    for (field, method, params) in cases {
+        let params = serde_json::to_string(&params).unwrap();
+        let (res, allocated) = allocated_by(|| LIMITS.check(method, Some(&params)));
+        let err = res.unwrap_err();
+        assert_eq!(err.code(), INVALID_PARAMS_CODE);
+        assert!(err.message().contains(field), "{method} {field}: {}", err.message());
+        // the rejected fields must not be decoded
+        assert!(allocated < 64 * 1024, "{method} {field}: allocated {allocated} bytes");
+    }
+}
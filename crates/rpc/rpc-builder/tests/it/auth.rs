@@ -1,32 +1,42 @@
 //! Auth server tests
 
-use crate::utils::launch_auth;
-use alloy_primitives::U64;
+use crate::utils::{launch_auth, launch_auth_with_methods};
+use alloy_primitives::{Bytes, B256, U256, U64};
 use alloy_rpc_types_engine::{ForkchoiceState, PayloadId, TransitionConfiguration};
 use jsonrpsee::core::client::{ClientT, SubscriptionClientT};
-use reth_ethereum_engine_primitives::EthEngineTypes;
-use reth_primitives::{Block, BlockExt};
-use reth_rpc_api::clients::EngineApiClient;
+use reth_db::tables;
+use reth_db_api::transaction::DbTxMut;
+use reth_primitives::{Block, BlockExt, Header, StaticFileSegment};
+use reth_provider::{
+    providers::StaticFileWriter, test_utils::create_test_provider_factory, DBProvider,
+    HeaderProvider, StaticFileProviderFactory,
+};
+use reth_rpc::DebugDbApi;
+use reth_rpc_api::{clients::DebugDbApiClient, DebugDbApiServer};
+use reth_rpc_api_testing_util::client::EthereumEngineApiClient;
 use reth_rpc_layer::JwtSecret;
 use reth_rpc_types_compat::engine::payload::{
     block_to_payload_v1, convert_block_to_payload_input_v2,
 };
+use reth_tasks::TokioTaskExecutor;
+
 #[allow(unused_must_use)]
 async fn test_basic_engine_calls<C>(client: &C)
 where
-    C: ClientT + SubscriptionClientT + Sync + EngineApiClient<EthEngineTypes>,
+    C: ClientT + SubscriptionClientT + Sync + EthereumEngineApiClient,
 {
     let block = Block::default().seal_slow();
-    EngineApiClient::new_payload_v1(client, block_to_payload_v1(block.clone())).await;
-    EngineApiClient::new_payload_v2(client, convert_block_to_payload_input_v2(block)).await;
-    EngineApiClient::fork_choice_updated_v1(client, ForkchoiceState::default(), None).await;
-    EngineApiClient::get_payload_v1(client, PayloadId::new([0, 0, 0, 0, 0, 0, 0, 0])).await;
-    EngineApiClient::get_payload_v2(client, PayloadId::new([0, 0, 0, 0, 0, 0, 0, 0])).await;
-    EngineApiClient::get_payload_bodies_by_hash_v1(client, vec![]).await;
-    EngineApiClient::get_payload_bodies_by_range_v1(client, U64::ZERO, U64::from(1u64)).await;
-    EngineApiClient::exchange_transition_configuration(client, TransitionConfiguration::default())
-        .await;
-    EngineApiClient::exchange_capabilities(client, vec![]).await;
+    client.new_payload_v1(block_to_payload_v1(block.clone())).await;
+    client.new_payload_v2(convert_block_to_payload_input_v2(block)).await;
+    client.fork_choice_updated_v1(ForkchoiceState::default(), None).await;
+    client.get_payload_v1(PayloadId::new([0, 0, 0, 0, 0, 0, 0, 0])).await;
+    client.get_payload_v2(PayloadId::new([0, 0, 0, 0, 0, 0, 0, 0])).await;
+    client.get_payload_bodies_by_hash_v1(vec![]).await;
+    client.get_payload_bodies_by_range_v1(U64::ZERO, U64::from(1u64)).await;
+    client.get_payload_bodies_by_hash_v2(vec![]).await;
+    client.get_payload_bodies_by_range_v2(U64::ZERO, U64::from(1u64)).await;
+    client.exchange_transition_configuration(TransitionConfiguration::default()).await;
+    client.exchange_capabilities(vec![]).await;
 }
 
 #[tokio::test(flavor = "multi_thread")]
@@ -46,3 +56,63 @@ async fn test_auth_endpoints_ws() {
     let client = handle.ws_client().await;
     test_basic_engine_calls(&client).await
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_auth_debug_db() {
+    reth_tracing::init_test_tracing();
+    let factory = create_test_provider_factory();
+
+    // headers 0 and 1 in static files, header 2 in the database
+    let static_file_hash = B256::with_last_byte(1);
+    let mut header = Header::default();
+    {
+        let static_file_provider = factory.static_file_provider();
+        let mut writer = static_file_provider.latest_writer(StaticFileSegment::Headers).unwrap();
+        for number in 0..=1 {
+            header.number = number;
+            writer.append_header(&header, U256::ZERO, &static_file_hash).unwrap();
+        }
+        writer.commit().unwrap();
+    }
+    header.number = 2;
+    header.gas_limit = 30_000_000;
+    let provider_rw = factory.provider_rw().unwrap();
+    provider_rw.tx_ref().put::<tables::Headers>(2, header.clone()).unwrap();
+    provider_rw.commit().unwrap();
+
+    let api = DebugDbApi::new(factory.clone(), Box::<TokioTaskExecutor>::default());
+    let handle = launch_auth_with_methods(JwtSecret::random(), api.into_rpc().into()).await;
+    let client = handle.http_client();
+
+    // a known header by its table key matches the provider
+    let key = Bytes::copy_from_slice(&2u64.to_be_bytes());
+    let value =
+        DebugDbApiClient::db_get(&client, "Headers".to_string(), key).await.unwrap().unwrap();
+    let expected = factory.header_by_number(2).unwrap().unwrap();
+    assert_eq!(value.decoded, Some(serde_json::to_value(&expected).unwrap()));
+
+    // a missing key
+    let key = Bytes::copy_from_slice(&3u64.to_be_bytes());
+    assert!(DebugDbApiClient::db_get(&client, "Headers".to_string(), key).await.unwrap().is_none());
+
+    // an unknown table and a key that's not a block number are rejected
+    let key = Bytes::copy_from_slice(&2u64.to_be_bytes());
+    assert!(DebugDbApiClient::db_get(&client, "NoSuchTable".to_string(), key).await.is_err());
+    let key = Bytes::copy_from_slice(&2u32.to_be_bytes());
+    assert!(DebugDbApiClient::db_get(&client, "Headers".to_string(), key).await.is_err());
+
+    // the header, total difficulty and hash columns of a static file header
+    let columns = DebugDbApiClient::static_file_get(&client, "headers".to_string(), U64::from(1))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(columns.len(), 3);
+    assert_eq!(columns[2].as_ref(), static_file_hash.as_slice());
+    assert!(DebugDbApiClient::static_file_get(&client, "headers".to_string(), U64::from(2))
+        .await
+        .unwrap()
+        .is_none());
+    assert!(DebugDbApiClient::static_file_get(&client, "bodies".to_string(), U64::from(1))
+        .await
+        .is_err());
+}
@@ -0,0 +1,67 @@
+//! Round trips of the typed RPC clients
+
+use crate::utils::{launch_http, launch_ws};
+use alloy_eips::{BlockId, BlockNumberOrTag};
+use alloy_primitives::U64;
+use reth_rpc_api::TimestampDirection;
+use reth_rpc_api_testing_util::client::{
+    AdminApiClient, DebugApiClient, EthereumEthApiClient, EthereumRethApiClient, TraceApiClient,
+};
+use reth_rpc_server_types::RethRpcModule;
+
+/// Modules with a method called by [`test_typed_calls`].
+const MODULES: [RethRpcModule; 5] = [
+    RethRpcModule::Eth,
+    RethRpcModule::Debug,
+    RethRpcModule::Trace,
+    RethRpcModule::Admin,
+    RethRpcModule::Reth,
+];
+
+async fn test_typed_calls<C>(client: &C)
+where
+    C: EthereumEthApiClient
+        + EthereumRethApiClient
+        + DebugApiClient
+        + TraceApiClient
+        + AdminApiClient
+        + Sync,
+{
+    let latest = BlockId::from(BlockNumberOrTag::Latest);
+
+    // eth
+    assert!(client.chain_id().await.unwrap().is_some());
+    client.block_by_number(BlockNumberOrTag::Latest, false).await.unwrap();
+
+    // debug
+    assert!(client.raw_header(latest).await.unwrap().is_empty());
+
+    // trace
+    client.trace_block(latest).await.unwrap_err();
+
+    // admin
+    client.node_info().await.unwrap();
+
+    // reth
+    let block =
+        client.reth_get_block_by_timestamp(U64::ZERO, TimestampDirection::After).await.unwrap();
+    assert!(block.is_none());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_typed_calls_http() {
+    reth_tracing::init_test_tracing();
+
+    let handle = launch_http(MODULES).await;
+    let client = handle.http_client().unwrap();
+    test_typed_calls(&client).await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_typed_calls_ws() {
+    reth_tracing::init_test_tracing();
+
+    let handle = launch_ws(MODULES).await;
+    let client = handle.ws_client().await.unwrap();
+    test_typed_calls(&client).await;
+}
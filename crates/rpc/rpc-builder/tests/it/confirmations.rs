@@ -0,0 +1,181 @@
+//! Tests for block parameters with confirmations.
+
+use crate::utils::{test_address, test_rpc_builder};
+use alloy_primitives::{bytes, logs_bloom, Address, Bytes, U256};
+use alloy_rpc_types_eth::Log;
+use jsonrpsee::{
+    core::client::{ClientT, Error},
+    http_client::HttpClient,
+    rpc_params,
+    types::error::INVALID_PARAMS_CODE,
+};
+use reth_chainspec::MAINNET;
+use reth_ethereum_engine_primitives::EthereumEngineValidator;
+use reth_primitives::{Block, BlockBody, Header};
+use reth_provider::{
+    test_utils::{ExtendedAccount, MockEthProvider},
+    BlockNumReader,
+};
+use reth_rpc::EthApi;
+use reth_rpc_builder::{RpcServerConfig, TransportRpcModuleConfig};
+use reth_rpc_server_types::RethRpcModule;
+use reth_testing_utils::generators::{self, random_log, random_receipt, random_signed_tx};
+use serde_json::{json, Value};
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+
+const CONFIRMATIONS: u64 = 6;
+
+/// Address of a contract that returns the number of the block it's called at, see
+/// [`BLOCK_NUMBER_CODE`].
+const BLOCK_NUMBER_CONTRACT: Address = Address::with_last_byte(0x43);
+
+/// `NUMBER PUSH1 0 MSTORE PUSH1 32 PUSH1 0 RETURN`
+const BLOCK_NUMBER_CODE: Bytes = bytes!("4360005260206000f3");
+
+/// Address of the logs of every block.
+const LOG_ADDRESS: Address = Address::with_last_byte(0x10);
+
+/// Address of an account with a balance.
+const FUNDED_ADDRESS: Address = Address::with_last_byte(0x20);
+
+/// Adds the block with the given number, with a single transaction that emits a log of
+/// [`LOG_ADDRESS`].
+fn add_block(provider: &MockEthProvider, number: u64) {
+    let mut rng = generators::rng();
+    let transaction = random_signed_tx(&mut rng);
+    let mut receipt = random_receipt(&mut rng, &transaction, Some(0));
+    receipt.logs.push(random_log(&mut rng, Some(LOG_ADDRESS), Some(0)));
+    let header = Header {
+        number,
+        gas_limit: 30_000_000,
+        logs_bloom: logs_bloom(&receipt.logs),
+        ..Default::default()
+    };
+    let hash = header.hash_slow();
+    provider.add_block(
+        hash,
+        Block { header, body: BlockBody { transactions: vec![transaction], ..Default::default() } },
+    );
+    provider.add_receipts(hash, vec![receipt]);
+}
+
+/// Returns the numbers of the blocks that `eth_call`, `eth_getLogs` and `eth_getBalance` were
+/// resolved to, the latter only checked for success.
+async fn resolved_blocks(client: &HttpClient) -> (u64, Vec<u64>) {
+    let confirmations = json!({ "confirmations": CONFIRMATIONS });
+
+    let output: Bytes = client
+        .request(
+            "eth_call",
+            rpc_params![json!({ "to": BLOCK_NUMBER_CONTRACT }), confirmations.clone()],
+        )
+        .await
+        .unwrap();
+    let call_block = U256::from_be_slice(&output).to::<u64>();
+
+    let logs: Vec<Log> = client
+        .request(
+            "eth_getLogs",
+            rpc_params![json!({
+                "address": LOG_ADDRESS,
+                "fromBlock": confirmations.clone(),
+                "toBlock": confirmations.clone()
+            })],
+        )
+        .await
+        .unwrap();
+    let log_blocks = logs.iter().map(|log| log.block_number.unwrap()).collect();
+
+    let balance: U256 =
+        client.request("eth_getBalance", rpc_params![FUNDED_ADDRESS, confirmations]).await.unwrap();
+    assert_eq!(balance, U256::from(1));
+
+    (call_block, log_blocks)
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_confirmations_resolved_against_single_head() {
+    reth_tracing::init_test_tracing();
+
+    let provider = MockEthProvider::default();
+    provider.add_account(
+        BLOCK_NUMBER_CONTRACT,
+        ExtendedAccount::new(0, U256::ZERO).with_bytecode(BLOCK_NUMBER_CODE),
+    );
+    provider.add_account(FUNDED_ADDRESS, ExtendedAccount::new(0, U256::from(1)));
+    for number in 0..=3 {
+        add_block(&provider, number);
+    }
+
+    let modules = test_rpc_builder().with_provider(provider.clone()).build(
+        TransportRpcModuleConfig::set_http([RethRpcModule::Eth]),
+        Box::new(EthApi::with_spawner),
+        Arc::new(EthereumEngineValidator::new(MAINNET.clone())),
+    );
+    let handle = RpcServerConfig::http(Default::default())
+        .with_http_address(test_address())
+        .with_block_confirmations(provider.clone())
+        .start(&modules)
+        .await
+        .unwrap();
+    let client = handle.http_client().unwrap();
+
+    // clamped at genesis
+    assert_eq!(resolved_blocks(&client).await, (0, vec![0]));
+
+    for number in 4..=CONFIRMATIONS * 2 {
+        add_block(&provider, number);
+    }
+
+    // advance the head concurrently
+    let stop = Arc::new(AtomicBool::new(false));
+    let miner = tokio::spawn({
+        let provider = provider.clone();
+        let stop = stop.clone();
+        async move {
+            let mut number = CONFIRMATIONS * 2;
+            while !stop.load(Ordering::Relaxed) {
+                number += 1;
+                add_block(&provider, number);
+                tokio::task::yield_now().await;
+            }
+        }
+    });
+
+    for _ in 0..50 {
+        let before = provider.best_block_number().unwrap();
+        let (call_block, log_blocks) = resolved_blocks(&client).await;
+        let after = provider.best_block_number().unwrap();
+
+        // the block parameters of each request resolve against a head of the request
+        for block in std::iter::once(call_block).chain(log_blocks.iter().copied()) {
+            let head = block + CONFIRMATIONS;
+            assert!(
+                (before..=after).contains(&head),
+                "resolved {block} outside of heads {before}..={after}"
+            );
+        }
+        // `fromBlock` and `toBlock` resolve against the same head
+        assert_eq!(log_blocks.len(), 1);
+    }
+
+    stop.store(true, Ordering::Relaxed);
+    miner.await.unwrap();
+
+    // once the head settles, the block is exactly head minus the confirmations
+    let block = provider.best_block_number().unwrap() - CONFIRMATIONS;
+    assert_eq!(resolved_blocks(&client).await, (block, vec![block]));
+
+    // other parameters are left as is
+    let err = client
+        .request::<Value, _>(
+            "eth_getBalance",
+            rpc_params![json!({ "confirmations": CONFIRMATIONS }), "latest"],
+        )
+        .await
+        .unwrap_err();
+    assert!(matches!(err, Error::Call(err) if err.code() == INVALID_PARAMS_CODE));
+}
@@ -0,0 +1,185 @@
+//! CORS, origin and host checks of the servers
+
+use crate::utils::{launch_auth, test_address, test_rpc_builder};
+use http::{
+    header::{ACCESS_CONTROL_ALLOW_ORIGIN, CONTENT_TYPE, HOST, ORIGIN},
+    HeaderMap, StatusCode,
+};
+use jsonrpsee::{core::client::ClientT, rpc_params, ws_client::WsClientBuilder};
+use reth_chainspec::MAINNET;
+use reth_ethereum_engine_primitives::{EthEngineTypes, EthereumEngineValidator};
+use reth_rpc::EthApi;
+use reth_rpc_api::clients::EngineApiClient;
+use reth_rpc_builder::{
+    error::RpcError, RpcServerConfig, RpcServerHandle, TransportRpcModuleConfig,
+};
+use reth_rpc_layer::JwtSecret;
+use reth_rpc_server_types::RethRpcModule;
+use std::sync::Arc;
+
+const REQUEST: &str = r#"{"jsonrpc":"2.0","id":1,"method":"web3_clientVersion","params":[]}"#;
+
+/// Launches a http only server with the given config.
+async fn launch_http(config: RpcServerConfig) -> Result<RpcServerHandle, RpcError> {
+    let server = test_rpc_builder().build(
+        TransportRpcModuleConfig::set_http([RethRpcModule::Web3]),
+        Box::new(EthApi::with_spawner),
+        Arc::new(EthereumEngineValidator::new(MAINNET.clone())),
+    );
+    config.with_http(Default::default()).with_http_address(test_address()).start(&server).await
+}
+
+/// Launches a ws only server with the given config.
+async fn launch_ws(config: RpcServerConfig) -> Result<RpcServerHandle, RpcError> {
+    let server = test_rpc_builder().build(
+        TransportRpcModuleConfig::set_ws([RethRpcModule::Web3]),
+        Box::new(EthApi::with_spawner),
+        Arc::new(EthereumEngineValidator::new(MAINNET.clone())),
+    );
+    config.with_ws(Default::default()).with_ws_address(test_address()).start(&server).await
+}
+
+/// Sends a request with the given headers to the http server.
+async fn post(handle: &RpcServerHandle, headers: &[(http::HeaderName, &str)]) -> reqwest::Response {
+    let mut request = reqwest::Client::new()
+        .post(handle.http_url().unwrap())
+        .header(CONTENT_TYPE, "application/json")
+        .body(REQUEST);
+    for (name, value) in headers {
+        request = request.header(name, *value);
+    }
+    request.send().await.unwrap()
+}
+
+/// Returns the `Access-Control-Allow-Origin` header of the response to a request from the origin.
+async fn allowed_origin(handle: &RpcServerHandle, origin: &str) -> Option<String> {
+    let response = post(handle, &[(ORIGIN, origin)]).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    response
+        .headers()
+        .get(ACCESS_CONTROL_ALLOW_ORIGIN)
+        .map(|value| value.to_str().unwrap().to_string())
+}
+
+/// Connects to the ws server with the given origin and sends a request.
+async fn ws_call(handle: &RpcServerHandle, origin: &str) -> bool {
+    let headers = HeaderMap::from_iter([(ORIGIN, origin.parse().unwrap())]);
+    let Ok(client) =
+        WsClientBuilder::default().set_headers(headers).build(handle.ws_url().unwrap()).await
+    else {
+        return false
+    };
+    client.request::<String, _>("web3_clientVersion", rpc_params![]).await.is_ok()
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_http_cors_origins() {
+    reth_tracing::init_test_tracing();
+    let handle = launch_http(
+        RpcServerConfig::default().with_http_cors(Some("https://a.com, https://*.b.com".into())),
+    )
+    .await
+    .unwrap();
+
+    // allowed origin
+    assert_eq!(allowed_origin(&handle, "https://a.com").await.as_deref(), Some("https://a.com"));
+    // wildcard subdomain match
+    assert_eq!(
+        allowed_origin(&handle, "https://app.b.com").await.as_deref(),
+        Some("https://app.b.com")
+    );
+    // disallowed origins
+    assert_eq!(allowed_origin(&handle, "https://evil.com").await, None);
+    assert_eq!(allowed_origin(&handle, "https://b.com").await, None);
+    assert_eq!(allowed_origin(&handle, "https://app.b.com.evil.com").await, None);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_ws_origins() {
+    reth_tracing::init_test_tracing();
+    let handle = launch_ws(RpcServerConfig::default().with_ws_cors(Some("https://*.b.com".into())))
+        .await
+        .unwrap();
+
+    assert!(ws_call(&handle, "https://app.b.com").await);
+    assert!(!ws_call(&handle, "https://evil.com").await);
+    assert!(!ws_call(&handle, "https://b.com").await);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_http_vhosts() {
+    reth_tracing::init_test_tracing();
+    let handle = launch_http(
+        RpcServerConfig::default().with_http_vhosts(Some("localhost,*.node.internal".into())),
+    )
+    .await
+    .unwrap();
+
+    for host in ["localhost:8545", "reth.node.internal", "127.0.0.1:8545"] {
+        assert_eq!(post(&handle, &[(HOST, host)]).await.status(), StatusCode::OK, "{host}");
+    }
+    // a page of the attacker that rebinds its domain to the node
+    for host in ["attacker.com", "attacker.com:8545", "node.internal"] {
+        assert_eq!(post(&handle, &[(HOST, host)]).await.status(), StatusCode::FORBIDDEN, "{host}");
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_ws_vhosts() {
+    reth_tracing::init_test_tracing();
+    let handle = launch_ws(RpcServerConfig::default().with_http_vhosts(Some("localhost".into())))
+        .await
+        .unwrap();
+    let url = handle.ws_url().unwrap().replacen("ws://", "http://", 1);
+
+    // the handshake is rejected before it's upgraded
+    let response =
+        reqwest::Client::new().get(&url).header(HOST, "attacker.com").send().await.unwrap();
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    let response = reqwest::Client::new().get(&url).header(HOST, "localhost").send().await.unwrap();
+    assert_ne!(response.status(), StatusCode::FORBIDDEN);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_invalid_patterns_fail_startup() {
+    reth_tracing::init_test_tracing();
+    let err = launch_http(
+        RpcServerConfig::default().with_http_cors(Some("https://a.com,https://a.*.com".into())),
+    )
+    .await
+    .unwrap_err();
+    assert!(matches!(err, RpcError::Cors(_)), "{err}");
+    assert!(err.to_string().contains("https://a.*.com"), "{err}");
+
+    let err = launch_http(RpcServerConfig::default().with_http_vhosts(Some("localhost,*".into())))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, RpcError::Cors(_)), "{err}");
+
+    let err = launch_ws(RpcServerConfig::default().with_ws_cors(Some("https://*a.com".into())))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, RpcError::Cors(_)), "{err}");
+    assert!(err.to_string().contains("https://*a.com"), "{err}");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_auth_server_rejects_browsers() {
+    reth_tracing::init_test_tracing();
+    let secret = JwtSecret::random();
+    let handle = launch_auth(secret).await;
+
+    let response = reqwest::Client::new()
+        .post(handle.http_url())
+        .header(CONTENT_TYPE, "application/json")
+        .header(ORIGIN, "http://localhost:3000")
+        .body(REQUEST)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    // requests without an origin are only checked for the jwt
+    let client = handle.http_client();
+    EngineApiClient::<EthEngineTypes>::exchange_capabilities(&client, vec![]).await.unwrap();
+}
@@ -0,0 +1,117 @@
+//! Standalone ipc tests
+
+use crate::utils::test_rpc_builder;
+use alloy_rpc_types_eth::Header;
+use jsonrpsee::{
+    core::client::{ClientT, SubscriptionClientT},
+    rpc_params,
+};
+use reth_chainspec::MAINNET;
+use reth_ethereum_engine_primitives::EthereumEngineValidator;
+use reth_primitives::SealedBlockWithSenders;
+use reth_provider::{test_utils::TestCanonStateSubscriptions, Chain, ExecutionOutcome};
+use reth_rpc::EthApi;
+use reth_rpc_builder::{IpcServerBuilder, RpcServerConfig, TransportRpcModuleConfig};
+use reth_rpc_server_types::RethRpcModule;
+use std::{
+    os::unix::fs::PermissionsExt,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+/// Returns a socket path that is unique to the test.
+fn test_ipc_path() -> String {
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+    let id = COUNTER.fetch_add(1, Ordering::Relaxed);
+    std::env::temp_dir()
+        .join(format!("reth-test-{}-{id}.ipc", std::process::id()))
+        .to_string_lossy()
+        .into_owned()
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_eth_subscribe_ipc() {
+    let events = TestCanonStateSubscriptions::default();
+    let server = test_rpc_builder().with_events(events.clone()).build(
+        TransportRpcModuleConfig::set_ipc(vec![RethRpcModule::Eth]),
+        Box::new(EthApi::with_spawner),
+        Arc::new(EthereumEngineValidator::new(MAINNET.clone())),
+    );
+    let handle = RpcServerConfig::ipc(Default::default())
+        .with_ipc_endpoint(test_ipc_path())
+        .start(&server)
+        .await
+        .unwrap();
+
+    let client = handle.ipc_client().await.unwrap();
+    let mut sub = client
+        .subscribe::<Header, _>("eth_subscribe", rpc_params!["newHeads"], "eth_unsubscribe")
+        .await
+        .unwrap();
+
+    // the canonical state stream is subscribed to after the subscription was accepted, so keep
+    // committing the block until the header is received
+    let block = SealedBlockWithSenders::default();
+    let chain = Arc::new(Chain::from_block(block.clone(), ExecutionOutcome::default(), None));
+    let header = tokio::time::timeout(Duration::from_secs(10), async {
+        loop {
+            events.add_next_commit(chain.clone());
+            tokio::select! {
+                header = sub.next() => break header,
+                _ = tokio::time::sleep(Duration::from_millis(50)) => {}
+            }
+        }
+    })
+    .await
+    .unwrap()
+    .unwrap()
+    .unwrap();
+    assert_eq!(header.hash, block.hash());
+
+    sub.unsubscribe().await.unwrap();
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_ipc_modules_selection() {
+    let server = test_rpc_builder().build(
+        TransportRpcModuleConfig::set_ipc(vec![RethRpcModule::Web3]),
+        Box::new(EthApi::with_spawner),
+        Arc::new(EthereumEngineValidator::new(MAINNET.clone())),
+    );
+    let handle = RpcServerConfig::ipc(Default::default())
+        .with_ipc_endpoint(test_ipc_path())
+        .start(&server)
+        .await
+        .unwrap();
+
+    let client = handle.ipc_client().await.unwrap();
+    let version: Result<String, _> = client.request("web3_clientVersion", rpc_params![]).await;
+    assert!(version.is_ok());
+    let chain_id: Result<String, _> = client.request("eth_chainId", rpc_params![]).await;
+    assert!(chain_id.is_err());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_ipc_socket_permissions() {
+    let server = test_rpc_builder().build(
+        TransportRpcModuleConfig::set_ipc(vec![RethRpcModule::Web3]),
+        Box::new(EthApi::with_spawner),
+        Arc::new(EthereumEngineValidator::new(MAINNET.clone())),
+    );
+    let path = test_ipc_path();
+    let handle = RpcServerConfig::ipc(IpcServerBuilder::default().socket_permissions(0o660))
+        .with_ipc_endpoint(path.clone())
+        .start(&server)
+        .await
+        .unwrap();
+
+    let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+    assert_eq!(mode & 0o777, 0o660);
+
+    let client = handle.ipc_client().await.unwrap();
+    let version: Result<String, _> = client.request("web3_clientVersion", rpc_params![]).await;
+    assert!(version.is_ok());
+}
@@ -0,0 +1,102 @@
+//! Tests for the request parameter limits of the rpc server.
+
+use crate::utils::{test_address, test_rpc_builder};
+use jsonrpsee::{
+    core::client::{ClientT, Error},
+    rpc_params,
+    types::error::INVALID_PARAMS_CODE,
+};
+use reth_chainspec::MAINNET;
+use reth_ethereum_engine_primitives::EthereumEngineValidator;
+use reth_rpc::EthApi;
+use reth_rpc_builder::{RpcRequestLimits, RpcServerConfig, TransportRpcModuleConfig};
+use reth_rpc_server_types::RethRpcModule;
+use serde_json::{json, Value};
+use std::sync::Arc;
+
+const LIMITS: RpcRequestLimits = RpcRequestLimits {
+    max_call_data_size: 1024,
+    max_access_list_entries: 8,
+    max_state_override_accounts: 8,
+};
+
+fn oversized_input() -> Value {
+    json!({ "input": format!("0x{}", "ab".repeat(4 * 1024 * 1024)) })
+}
+
+fn oversized_access_list() -> Value {
+    let entry =
+        json!({ "address": "0x0000000000000000000000000000000000000001", "storageKeys": [] });
+    json!({ "accessList": vec![entry; 10_000] })
+}
+
+fn oversized_state_overrides() -> Value {
+    let overrides = (0..10_000u64)
+        .map(|i| (format!("0x{i:040x}"), json!({ "balance": "0x1" })))
+        .collect::<serde_json::Map<_, _>>();
+    Value::Object(overrides)
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_oversized_call_params_rejected() {
+    reth_tracing::init_test_tracing();
+
+    let builder = test_rpc_builder();
+    let modules = builder.build(
+        TransportRpcModuleConfig::set_http([RethRpcModule::Eth, RethRpcModule::Debug]),
+        Box::new(EthApi::with_spawner),
+        Arc::new(EthereumEngineValidator::new(MAINNET.clone())),
+    );
+    let handle = RpcServerConfig::http(Default::default())
+        .with_http_address(test_address())
+        .with_request_limits(LIMITS)
+        .start(&modules)
+        .await
+        .unwrap();
+    let client = handle.http_client().unwrap();
+
+    let cases = [
+        ("input", "eth_call", rpc_params![oversized_input(), "latest"]),
+        ("accessList", "eth_estimateGas", rpc_params![oversized_access_list(), "latest"]),
+        ("accessList", "eth_createAccessList", rpc_params![oversized_access_list()]),
+        (
+            "stateOverrides",
+            "eth_call",
+            rpc_params![json!({}), "latest", oversized_state_overrides()],
+        ),
+        (
+            "input",
+            "eth_callMany",
+            rpc_params![json!({ "transactions": [oversized_input()] }), json!({})],
+        ),
+        (
+            "input",
+            "debug_traceCallMany",
+            rpc_params![json!([{ "transactions": [oversized_input()] }]), json!({})],
+        ),
+        (
+            "input",
+            "eth_simulateV1",
+            rpc_params![json!({ "blockStateCalls": [{ "calls": [oversized_input()] }] }), "latest"],
+        ),
+        (
+            "stateOverrides",
+            "debug_traceCall",
+            rpc_params![
+                json!({}),
+                "latest",
+                json!({ "stateOverrides": oversized_state_overrides() })
+            ],
+        ),
+    ];
+
+    for (field, method, params) in cases {
+        match client.request::<Value, _>(method, params).await {
+            Err(Error::Call(err)) => {
+                assert_eq!(err.code(), INVALID_PARAMS_CODE, "{method}");
+                assert!(err.message().contains(field), "{method}: {}", err.message());
+            }
+            res => panic!("{method}: expected invalid params error, got {res:?}"),
+        }
+    }
+}
@@ -1,10 +1,17 @@
 #![allow(missing_docs)]
 
 mod auth;
+mod client;
+mod confirmations;
+mod cors;
 mod http;
+#[cfg(unix)]
+mod ipc;
+mod limits;
 mod middleware;
 mod serde;
 mod startup;
 pub mod utils;
+mod ws;
 
 const fn main() {}
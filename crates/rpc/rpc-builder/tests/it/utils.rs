@@ -4,6 +4,7 @@ use std::{
 };
 
 use alloy_rpc_types_engine::{ClientCode, ClientVersionV1};
+use jsonrpsee::Methods;
 use reth_beacon_consensus::BeaconConsensusEngineHandle;
 use reth_chainspec::MAINNET;
 use reth_consensus::noop::NoopConsensus;
@@ -35,6 +36,11 @@ pub const fn test_address() -> SocketAddr {
 
 /// Launches a new server for the auth module
 pub async fn launch_auth(secret: JwtSecret) -> AuthServerHandle {
+    launch_auth_with_methods(secret, Methods::new()).await
+}
+
+/// Launches a new server for the auth module with the additional methods
+pub async fn launch_auth_with_methods(secret: JwtSecret, methods: Methods) -> AuthServerHandle {
     let config = AuthServerConfig::builder(secret).socket_addr(test_address()).build();
     let (tx, _rx) = unbounded_channel();
     let beacon_engine_handle = BeaconConsensusEngineHandle::<EthEngineTypes>::new(tx);
@@ -56,7 +62,8 @@ pub async fn launch_auth(secret: JwtSecret) -> AuthServerHandle {
         EngineCapabilities::default(),
         EthereumEngineValidator::new(MAINNET.clone()),
     );
-    let module = AuthRpcModule::new(engine_api);
+    let mut module = AuthRpcModule::new(engine_api);
+    module.merge_auth_methods(methods).unwrap();
     module.start_server(config).await.unwrap()
 }
 
@@ -0,0 +1,122 @@
+//! Tests for the lifecycle of ws connections.
+
+use crate::utils::{test_address, test_rpc_builder};
+use jsonrpsee::{
+    core::client::{Subscription, SubscriptionClientT},
+    rpc_params,
+};
+use reth_chainspec::MAINNET;
+use reth_ethereum_engine_primitives::EthereumEngineValidator;
+use reth_provider::test_utils::TestCanonStateSubscriptions;
+use reth_rpc::EthApi;
+use reth_rpc_builder::{
+    RpcServerConfig, RpcServerHandle, TransportRpcModuleConfig, WsConnectionConfig,
+};
+use reth_rpc_server_types::RethRpcModule;
+use std::{net::SocketAddr, sync::Arc, time::Duration};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::TcpStream,
+    time::{sleep, timeout, Instant},
+};
+
+async fn launch_ws_with_config(
+    events: TestCanonStateSubscriptions,
+    config: WsConnectionConfig,
+) -> RpcServerHandle {
+    let server = test_rpc_builder().with_events(events).build(
+        TransportRpcModuleConfig::set_ws([RethRpcModule::Eth]),
+        Box::new(EthApi::with_spawner),
+        Arc::new(EthereumEngineValidator::new(MAINNET.clone())),
+    );
+    RpcServerConfig::ws(Default::default())
+        .with_ws_address(test_address())
+        .with_ws_connection_config(config)
+        .start(&server)
+        .await
+        .unwrap()
+}
+
+/// Waits until the condition holds, panics if it doesn't within 5 seconds.
+async fn wait_until(condition: impl Fn() -> bool) {
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while !condition() {
+        assert!(Instant::now() < deadline, "condition not met in time");
+        sleep(Duration::from_millis(10)).await;
+    }
+}
+
+/// Opens a `WebSocket` connection that never answers pings.
+async fn connect_unresponsive(addr: SocketAddr) -> TcpStream {
+    let mut stream = TcpStream::connect(addr).await.unwrap();
+    let handshake = format!(
+        "GET / HTTP/1.1\r\nHost: {addr}\r\nUpgrade: websocket\r\nConnection: Upgrade\r\n\
+         Sec-WebSocket-Key: dGhlIHNhbXBsZSBub25jZQ==\r\nSec-WebSocket-Version: 13\r\n\r\n"
+    );
+    stream.write_all(handshake.as_bytes()).await.unwrap();
+
+    let mut response = Vec::new();
+    while !response.ends_with(b"\r\n\r\n") {
+        response.push(stream.read_u8().await.unwrap());
+    }
+    assert!(response.starts_with(b"HTTP/1.1 101"), "{}", String::from_utf8_lossy(&response));
+    stream
+}
+
+/// Sends a text frame, masked with a zero key so that the payload is sent as is.
+async fn send_text(stream: &mut TcpStream, text: &str) {
+    assert!(text.len() < 126);
+    let mut frame = vec![0x81, 0x80 | text.len() as u8, 0, 0, 0, 0];
+    frame.extend_from_slice(text.as_bytes());
+    stream.write_all(&frame).await.unwrap();
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_unresponsive_client_is_disconnected() {
+    reth_tracing::init_test_tracing();
+
+    let events = TestCanonStateSubscriptions::default();
+    let config = WsConnectionConfig::default()
+        .with_ping_interval(Some(Duration::from_millis(100)))
+        .with_max_missed_pongs(2);
+    let handle = launch_ws_with_config(events.clone(), config).await;
+
+    let mut stream = connect_unresponsive(handle.ws_local_addr().unwrap()).await;
+    send_text(
+        &mut stream,
+        r#"{"jsonrpc":"2.0","id":1,"method":"eth_subscribe","params":["newHeads"]}"#,
+    )
+    .await;
+    wait_until(|| events.subscriber_count() == 1).await;
+
+    // the client never reads the pings, so it's disconnected after missing two pongs and the
+    // listener of its subscription is released right away
+    let subscribed = Instant::now();
+    wait_until(|| events.subscriber_count() == 0).await;
+    assert!(subscribed.elapsed() < Duration::from_secs(2));
+
+    // the server closed the connection
+    let mut remaining = Vec::new();
+    assert!(timeout(Duration::from_secs(5), stream.read_to_end(&mut remaining)).await.is_ok());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_max_connection_duration() {
+    reth_tracing::init_test_tracing();
+
+    let events = TestCanonStateSubscriptions::default();
+    let config = WsConnectionConfig::default()
+        .with_max_connection_duration(Some(Duration::from_millis(500)));
+    let handle = launch_ws_with_config(events.clone(), config).await;
+
+    let client = handle.ws_client().await.unwrap();
+    let _subscription: Subscription<serde_json::Value> = client
+        .subscribe("eth_subscribe", rpc_params!["newHeads"], "eth_unsubscribe")
+        .await
+        .unwrap();
+    wait_until(|| events.subscriber_count() == 1).await;
+
+    timeout(Duration::from_secs(5), client.on_disconnect()).await.unwrap();
+    assert!(!client.is_connected());
+    wait_until(|| events.subscriber_count() == 0).await;
+}
@@ -17,6 +17,8 @@ pub const CAPABILITIES: &[&str] = &[
     "engine_newPayloadV4",
     "engine_getPayloadBodiesByHashV1",
     "engine_getPayloadBodiesByRangeV1",
+    "engine_getPayloadBodiesByHashV2",
+    "engine_getPayloadBodiesByRangeV2",
     "engine_getBlobsV1",
 ];
 
@@ -29,6 +31,11 @@ pub struct EngineCapabilities {
 }
 
 impl EngineCapabilities {
+    /// Creates the capabilities for the given engine API methods.
+    pub fn new(methods: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self { inner: methods.into_iter().map(Into::into).collect() }
+    }
+
     /// Returns the list of all supported Engine capabilities for Prague spec.
     fn prague() -> Self {
         Self { inner: CAPABILITIES.iter().copied().map(str::to_owned).collect() }
@@ -38,6 +45,11 @@ impl EngineCapabilities {
     pub fn list(&self) -> Vec<String> {
         self.inner.iter().cloned().collect()
     }
+
+    /// Returns whether the given engine API method is supported.
+    pub fn contains(&self, method: &str) -> bool {
+        self.inner.contains(method)
+    }
 }
 
 impl Default for EngineCapabilities {
@@ -1,17 +1,20 @@
 use crate::{
     capabilities::EngineCapabilities, metrics::EngineApiMetrics, EngineApiError, EngineApiResult,
 };
+use alloy_consensus::BlockHeader;
 use alloy_eips::{
     eip1898::BlockHashOrNumber,
+    eip2718::Encodable2718,
     eip4844::BlobAndProofV1,
+    eip4895::Withdrawals,
     eip7685::{Requests, RequestsOrHash},
 };
 use alloy_primitives::{BlockHash, BlockNumber, B256, U64};
 use alloy_rpc_types_engine::{
     CancunPayloadFields, ClientVersionV1, ExecutionPayload, ExecutionPayloadBodiesV1,
-    ExecutionPayloadInputV2, ExecutionPayloadSidecar, ExecutionPayloadV1, ExecutionPayloadV3,
-    ForkchoiceState, ForkchoiceUpdated, PayloadId, PayloadStatus, PraguePayloadFields,
-    TransitionConfiguration,
+    ExecutionPayloadBodyV1, ExecutionPayloadInputV2, ExecutionPayloadSidecar, ExecutionPayloadV1,
+    ExecutionPayloadV3, ForkchoiceState, ForkchoiceUpdated, PayloadId, PayloadStatus,
+    PraguePayloadFields, TransitionConfiguration,
 };
 use async_trait::async_trait;
 use jsonrpsee_core::RpcResult;
@@ -25,6 +28,7 @@ use reth_payload_primitives::{
     PayloadOrAttributes,
 };
 use reth_primitives::EthereumHardfork;
+use reth_primitives_traits::{Block, BlockBody};
 use reth_rpc_api::EngineApiServer;
 use reth_rpc_types_compat::engine::payload::{
     convert_payload_input_v2_to_payload, convert_to_payload_body_v1,
@@ -508,8 +512,6 @@ where
                 return;
             }
 
-            let mut result = Vec::with_capacity(count as usize);
-
             // -1 so range is inclusive
             let mut end = start.saturating_add(count - 1);
 
@@ -521,17 +523,22 @@ where
                 }
             }
 
-            for num in start..=end {
-                let block_result = inner.provider.block(BlockHashOrNumber::Number(num));
-                match block_result {
-                    Ok(block) => {
-                        result.push(block.map(&f));
-                    }
-                    Err(err) => {
-                        tx.send(Err(EngineApiError::Internal(Box::new(err)))).ok();
-                        return;
-                    }
-                };
+            // A single range read serves the headers and transactions from static files, the
+            // blocks missing from the result are returned as `null`.
+            let blocks = match inner.provider.block_range(start..=end) {
+                Ok(blocks) => blocks,
+                Err(err) => {
+                    tx.send(Err(EngineApiError::Internal(Box::new(err)))).ok();
+                    return;
+                }
+            };
+
+            let mut result = (start..=end).map(|_| None).collect::<Vec<_>>();
+            for block in blocks {
+                let index = block.header().number().checked_sub(start);
+                if let Some(body) = index.and_then(|index| result.get_mut(index as usize)) {
+                    *body = Some(f(block));
+                }
             }
             tx.send(Ok(result)).ok();
         }));
@@ -557,6 +564,22 @@ where
         self.get_payload_bodies_by_range_with(start, count, convert_to_payload_body_v1).await
     }
 
+    /// Returns the execution payload bodies by the range starting at `start`, containing `count`
+    /// blocks, with `null` withdrawals for blocks before Shanghai.
+    ///
+    /// The same caution as for [`Self::get_payload_bodies_by_range_v1`] applies.
+    pub async fn get_payload_bodies_by_range_v2(
+        &self,
+        start: BlockNumber,
+        count: u64,
+    ) -> EngineApiResult<ExecutionPayloadBodiesV1> {
+        let chain_spec = self.inner.chain_spec.clone();
+        self.get_payload_bodies_by_range_with(start, count, move |block| {
+            convert_to_payload_body_v2(block, &*chain_spec)
+        })
+        .await
+    }
+
     /// Called to retrieve execution payload bodies by hashes.
     async fn get_payload_bodies_by_hash_with<F, R>(
         &self,
@@ -603,6 +626,19 @@ where
         self.get_payload_bodies_by_hash_with(hashes, convert_to_payload_body_v1).await
     }
 
+    /// Called to retrieve execution payload bodies by hashes, with `null` withdrawals for blocks
+    /// before Shanghai.
+    pub async fn get_payload_bodies_by_hash_v2(
+        &self,
+        hashes: Vec<BlockHash>,
+    ) -> EngineApiResult<ExecutionPayloadBodiesV1> {
+        let chain_spec = self.inner.chain_spec.clone();
+        self.get_payload_bodies_by_hash_with(hashes, move |block| {
+            convert_to_payload_body_v2(block, &*chain_spec)
+        })
+        .await
+    }
+
     /// Called to verify network configuration parameters and ensure that Consensus and Execution
     /// layers are using the latest configuration.
     pub fn exchange_transition_configuration(
@@ -963,6 +999,39 @@ where
         Ok(res?)
     }
 
+    /// Handler for `engine_getPayloadBodiesByHashV2`
+    ///
+    /// Unknown blocks are returned as `null` and pre Shanghai blocks have `null` withdrawals.
+    async fn get_payload_bodies_by_hash_v2(
+        &self,
+        block_hashes: Vec<BlockHash>,
+    ) -> RpcResult<ExecutionPayloadBodiesV1> {
+        trace!(target: "rpc::engine", "Serving engine_getPayloadBodiesByHashV2");
+        let start = Instant::now();
+        let res = Self::get_payload_bodies_by_hash_v2(self, block_hashes);
+        self.inner.metrics.latency.get_payload_bodies_by_hash_v2.record(start.elapsed());
+        Ok(res.await?)
+    }
+
+    /// Handler for `engine_getPayloadBodiesByRangeV2`
+    ///
+    /// Returns the execution payload bodies by the range starting at `start`, containing `count`
+    /// blocks, see [`EngineApiServer::get_payload_bodies_by_range_v1`] for the handling of the
+    /// range.
+    ///
+    /// Note: If a block is pre shanghai, `withdrawals` field will be `null`.
+    async fn get_payload_bodies_by_range_v2(
+        &self,
+        start: U64,
+        count: U64,
+    ) -> RpcResult<ExecutionPayloadBodiesV1> {
+        trace!(target: "rpc::engine", "Serving engine_getPayloadBodiesByRangeV2");
+        let start_time = Instant::now();
+        let res = Self::get_payload_bodies_by_range_v2(self, start.to(), count.to()).await;
+        self.inner.metrics.latency.get_payload_bodies_by_range_v2.record(start_time.elapsed());
+        Ok(res?)
+    }
+
     /// Handler for `engine_exchangeTransitionConfigurationV1`
     /// See also <https://github.com/ethereum/execution-apis/blob/3d627c95a4d3510a8187dd02e0250ecb4331d27e/src/engine/paris.md#engine_exchangeTransitionConfigurationV1>
     async fn exchange_transition_configuration(
@@ -1012,6 +1081,34 @@ where
     }
 }
 
+/// Converts a block into the [`ExecutionPayloadBodyV1`] of `engine_getPayloadBodiesByHashV2` and
+/// `engine_getPayloadBodiesByRangeV2`.
+///
+/// The withdrawals of blocks after Shanghai are always set, even if the block has none.
+fn convert_to_payload_body_v2<B, ChainSpec>(
+    block: B,
+    chain_spec: &ChainSpec,
+) -> ExecutionPayloadBodyV1
+where
+    B: Block,
+    ChainSpec: EthereumHardforks,
+{
+    let withdrawals =
+        chain_spec.is_shanghai_active_at_timestamp(block.header().timestamp()).then(|| {
+            block.body().withdrawals().cloned().map(Withdrawals::into_inner).unwrap_or_default()
+        });
+
+    ExecutionPayloadBodyV1 {
+        transactions: block
+            .body()
+            .transactions()
+            .iter()
+            .map(|tx| tx.encoded_2718().into())
+            .collect(),
+        withdrawals,
+    }
+}
+
 impl<Provider, EngineT, Pool, Validator, ChainSpec> std::fmt::Debug
     for EngineApi<Provider, EngineT, Pool, Validator, ChainSpec>
 where
@@ -1027,7 +1124,7 @@ mod tests {
     use super::*;
     use alloy_rpc_types_engine::{ClientCode, ClientVersionV1};
     use assert_matches::assert_matches;
-    use reth_chainspec::{ChainSpec, MAINNET};
+    use reth_chainspec::{ChainSpec, ChainSpecBuilder, MAINNET};
     use reth_engine_primitives::BeaconEngineMessage;
     use reth_ethereum_engine_primitives::{EthEngineTypes, EthereumEngineValidator};
     use reth_payload_builder::test_utils::spawn_test_payload_service;
@@ -1035,7 +1132,7 @@ mod tests {
     use reth_provider::test_utils::MockEthProvider;
     use reth_rpc_types_compat::engine::payload::execution_payload_from_sealed_block;
     use reth_tasks::TokioTaskExecutor;
-    use reth_testing_utils::generators::random_block;
+    use reth_testing_utils::generators::{random_block, BlockParams};
     use reth_transaction_pool::noop::NoopTransactionPool;
     use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver};
 
@@ -1225,6 +1322,106 @@ mod tests {
             let res = api.get_payload_bodies_by_hash_v1(hashes).await.unwrap();
             assert_eq!(res, expected);
         }
+
+        #[tokio::test]
+        async fn v2_request_too_large() {
+            let (_, api) = setup_engine_api();
+
+            let request_count = MAX_PAYLOAD_BODIES_LIMIT + 1;
+            let res = api.get_payload_bodies_by_range_v2(1, request_count).await;
+            assert_matches!(res, Err(EngineApiError::PayloadRequestTooLarge { .. }));
+
+            let hashes = vec![B256::ZERO; request_count as usize];
+            let res = api.get_payload_bodies_by_hash_v2(hashes).await;
+            assert_matches!(res, Err(EngineApiError::PayloadRequestTooLarge { .. }));
+
+            // the limit itself is accepted
+            let res = api.get_payload_bodies_by_range_v2(1, MAX_PAYLOAD_BODIES_LIMIT).await;
+            assert_matches!(res, Ok(_));
+        }
+
+        #[tokio::test]
+        async fn v2_unknown_hashes_interleaved() {
+            let mut rng = generators::rng();
+            let (handle, api) = setup_engine_api();
+
+            let blocks = random_block_range(
+                &mut rng,
+                1..=10,
+                BlockRangeParams { tx_count: 0..2, ..Default::default() },
+            );
+            handle.provider.extend_blocks(blocks.iter().cloned().map(|b| (b.hash(), b.unseal())));
+
+            let mut hashes = Vec::new();
+            let mut expected = Vec::new();
+            for (i, block) in blocks.iter().enumerate() {
+                hashes.push(block.hash());
+                expected.push(Some(convert_to_payload_body_v2(
+                    block.clone().unseal::<Block>(),
+                    &*handle.chain_spec,
+                )));
+                hashes.push(B256::with_last_byte(i as u8));
+                expected.push(None);
+            }
+
+            let res = api.get_payload_bodies_by_hash_v2(hashes).await.unwrap();
+            assert_eq!(res, expected);
+        }
+
+        #[tokio::test]
+        async fn v2_range_past_tip() {
+            let mut rng = generators::rng();
+            let (handle, api) = setup_engine_api();
+
+            let blocks = random_block_range(
+                &mut rng,
+                1..=10,
+                BlockRangeParams { tx_count: 0..2, ..Default::default() },
+            );
+            handle.provider.extend_blocks(blocks.iter().cloned().map(|b| (b.hash(), b.unseal())));
+
+            // no trailing `null`s for the blocks after the tip
+            let expected = blocks
+                .iter()
+                .skip(4)
+                .cloned()
+                .map(|b| Some(convert_to_payload_body_v2(b.unseal::<Block>(), &*handle.chain_spec)))
+                .collect::<Vec<_>>();
+            let res = api.get_payload_bodies_by_range_v2(5, 20).await.unwrap();
+            assert_eq!(res, expected);
+
+            // the range starts after the tip
+            let res = api.get_payload_bodies_by_range_v2(11, 5).await.unwrap();
+            assert!(res.is_empty());
+        }
+
+        #[test]
+        fn v2_fork_gated_withdrawals() {
+            let mut block = random_block(
+                &mut generators::rng(),
+                1,
+                BlockParams { tx_count: Some(1), ..Default::default() },
+            )
+            .unseal::<Block>();
+            block.header.timestamp = 0;
+            block.header.requests_hash = Some(B256::with_last_byte(1));
+            block.body.withdrawals = None;
+
+            // before Shanghai the withdrawals are `null`
+            let body = convert_to_payload_body_v2(block.clone(), &*MAINNET);
+            assert_eq!(body.withdrawals, None);
+            let json = serde_json::to_value(&body).unwrap();
+            assert!(json["withdrawals"].is_null());
+
+            // after Shanghai the withdrawals are always a list, and the body has no other fields
+            let prague = ChainSpecBuilder::mainnet().prague_activated().build();
+            let body = convert_to_payload_body_v2(block, &prague);
+            assert_eq!(body.withdrawals, Some(Vec::new()));
+            assert_eq!(body.transactions.len(), 1);
+            let json = serde_json::to_value(&body).unwrap();
+            assert_eq!(json["withdrawals"], serde_json::json!([]));
+            assert_eq!(json.as_object().unwrap().len(), 2);
+        }
     }
 
     // https://github.com/ethereum/execution-apis/blob/main/src/engine/paris.md#specification-3
@@ -48,6 +48,10 @@ pub(crate) struct EngineApiLatencyMetrics {
     pub(crate) get_payload_bodies_by_range_v1: Histogram,
     /// Latency for `engine_getPayloadBodiesByHashV1`
     pub(crate) get_payload_bodies_by_hash_v1: Histogram,
+    /// Latency for `engine_getPayloadBodiesByRangeV2`
+    pub(crate) get_payload_bodies_by_range_v2: Histogram,
+    /// Latency for `engine_getPayloadBodiesByHashV2`
+    pub(crate) get_payload_bodies_by_hash_v2: Histogram,
     /// Latency for `engine_exchangeTransitionConfigurationV1`
     pub(crate) exchange_transition_configuration: Histogram,
 }
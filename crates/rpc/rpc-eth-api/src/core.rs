@@ -1,7 +1,9 @@
 //! Implementation of the [`jsonrpsee`] generated [`EthApiServer`] trait. Handles RPC requests for
 //! the `eth_` namespace.
 use alloy_dyn_abi::TypedData;
-use alloy_eips::{eip2930::AccessListResult, BlockId, BlockNumberOrTag};
+use alloy_eips::{
+    eip2930::AccessListResult, eip4844::BlobTransactionSidecar, BlockId, BlockNumberOrTag,
+};
 use alloy_json_rpc::RpcObject;
 use alloy_primitives::{Address, Bytes, B256, B64, U256, U64};
 use alloy_rpc_types_eth::{
@@ -13,20 +15,21 @@ use alloy_rpc_types_eth::{
 };
 use alloy_serde::JsonStorageKey;
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use reth_rpc_eth_types::cache::tx_json::tx_json_from_source;
 use reth_rpc_server_types::{result::internal_rpc_err, ToRpcResult};
 use tracing::trace;
 
 use crate::{
     helpers::{EthApiSpec, EthBlocks, EthCall, EthFees, EthState, EthTransactions, FullEthApi},
-    RpcBlock, RpcHeader, RpcReceipt, RpcTransaction,
+    RpcBlockJson, RpcHeader, RpcReceipt, RpcTransactionJson,
 };
 
 /// Helper trait, unifies functionality that must be supported to implement all RPC methods for
 /// server.
 pub trait FullEthApiServer:
     EthApiServer<
-        RpcTransaction<Self::NetworkTypes>,
-        RpcBlock<Self::NetworkTypes>,
+        RpcTransactionJson<Self::NetworkTypes>,
+        RpcBlockJson<Self::NetworkTypes>,
         RpcReceipt<Self::NetworkTypes>,
         RpcHeader<Self::NetworkTypes>,
     > + FullEthApi
@@ -36,8 +39,8 @@ pub trait FullEthApiServer:
 
 impl<T> FullEthApiServer for T where
     T: EthApiServer<
-            RpcTransaction<T::NetworkTypes>,
-            RpcBlock<T::NetworkTypes>,
+            RpcTransactionJson<T::NetworkTypes>,
+            RpcBlockJson<T::NetworkTypes>,
             RpcReceipt<T::NetworkTypes>,
             RpcHeader<T::NetworkTypes>,
         > + FullEthApi
@@ -175,6 +178,22 @@ pub trait EthApi<T: RpcObject, B: RpcObject, R: RpcObject, H: RpcObject> {
     #[method(name = "getTransactionReceipt")]
     async fn transaction_receipt(&self, hash: B256) -> RpcResult<Option<R>>;
 
+    /// Returns the blob sidecars of the EIP-4844 transactions in the given block, in the order of
+    /// the transactions. Returns `null` if the sidecars are no longer retained by the node.
+    #[method(name = "getBlobSidecars")]
+    async fn blob_sidecars(
+        &self,
+        block_id: BlockId,
+    ) -> RpcResult<Option<Vec<BlobTransactionSidecar>>>;
+
+    /// Returns the blob sidecar of the EIP-4844 transaction with the given hash, if it is pending
+    /// or still retained by the node.
+    #[method(name = "getBlobSidecarByTxHash")]
+    async fn blob_sidecar_by_tx_hash(
+        &self,
+        hash: B256,
+    ) -> RpcResult<Option<BlobTransactionSidecar>>;
+
     /// Returns the balance of the account of given address.
     #[method(name = "getBalance")]
     async fn balance(&self, address: Address, block_number: Option<BlockId>) -> RpcResult<U256>;
@@ -365,8 +384,8 @@ pub trait EthApi<T: RpcObject, B: RpcObject, R: RpcObject, H: RpcObject> {
 #[async_trait::async_trait]
 impl<T>
     EthApiServer<
-        RpcTransaction<T::NetworkTypes>,
-        RpcBlock<T::NetworkTypes>,
+        RpcTransactionJson<T::NetworkTypes>,
+        RpcBlockJson<T::NetworkTypes>,
         RpcReceipt<T::NetworkTypes>,
         RpcHeader<T::NetworkTypes>,
     > for T
@@ -388,7 +407,8 @@ where
 
     /// Handler for: `eth_coinbase`
     async fn author(&self) -> RpcResult<Address> {
-        Err(internal_rpc_err("unimplemented"))
+        trace!(target: "rpc::eth", "Serving eth_coinbase");
+        EthApiSpec::coinbase(self).ok_or_else(|| internal_rpc_err("unimplemented"))
     }
 
     /// Handler for: `eth_accounts`
@@ -416,7 +436,7 @@ where
         &self,
         hash: B256,
         full: bool,
-    ) -> RpcResult<Option<RpcBlock<T::NetworkTypes>>> {
+    ) -> RpcResult<Option<RpcBlockJson<T::NetworkTypes>>> {
         trace!(target: "rpc::eth", ?hash, ?full, "Serving eth_getBlockByHash");
         Ok(EthBlocks::rpc_block(self, hash.into(), full).await?)
     }
@@ -426,7 +446,7 @@ where
         &self,
         number: BlockNumberOrTag,
         full: bool,
-    ) -> RpcResult<Option<RpcBlock<T::NetworkTypes>>> {
+    ) -> RpcResult<Option<RpcBlockJson<T::NetworkTypes>>> {
         trace!(target: "rpc::eth", ?number, ?full, "Serving eth_getBlockByNumber");
         Ok(EthBlocks::rpc_block(self, number.into(), full).await?)
     }
@@ -475,9 +495,11 @@ where
         &self,
         hash: B256,
         index: Index,
-    ) -> RpcResult<Option<RpcBlock<T::NetworkTypes>>> {
+    ) -> RpcResult<Option<RpcBlockJson<T::NetworkTypes>>> {
         trace!(target: "rpc::eth", ?hash, ?index, "Serving eth_getUncleByBlockHashAndIndex");
-        Ok(EthBlocks::ommer_by_block_and_index(self, hash.into(), index).await?)
+        Ok(EthBlocks::ommer_by_block_and_index(self, hash.into(), index)
+            .await?
+            .map(|block| block.map_transactions(Into::into)))
     }
 
     /// Handler for: `eth_getUncleByBlockNumberAndIndex`
@@ -485,9 +507,11 @@ where
         &self,
         number: BlockNumberOrTag,
         index: Index,
-    ) -> RpcResult<Option<RpcBlock<T::NetworkTypes>>> {
+    ) -> RpcResult<Option<RpcBlockJson<T::NetworkTypes>>> {
         trace!(target: "rpc::eth", ?number, ?index, "Serving eth_getUncleByBlockNumberAndIndex");
-        Ok(EthBlocks::ommer_by_block_and_index(self, number.into(), index).await?)
+        Ok(EthBlocks::ommer_by_block_and_index(self, number.into(), index)
+            .await?
+            .map(|block| block.map_transactions(Into::into)))
     }
 
     /// Handler for: `eth_getRawTransactionByHash`
@@ -500,11 +524,11 @@ where
     async fn transaction_by_hash(
         &self,
         hash: B256,
-    ) -> RpcResult<Option<RpcTransaction<T::NetworkTypes>>> {
+    ) -> RpcResult<Option<RpcTransactionJson<T::NetworkTypes>>> {
         trace!(target: "rpc::eth", ?hash, "Serving eth_getTransactionByHash");
         Ok(EthTransactions::transaction_by_hash(self, hash)
             .await?
-            .map(|tx| tx.into_transaction(self.tx_resp_builder()))
+            .map(|tx| tx_json_from_source(tx, self.tx_resp_builder(), self.tx_json_cache()))
             .transpose()?)
     }
 
@@ -524,10 +548,11 @@ where
         &self,
         hash: B256,
         index: Index,
-    ) -> RpcResult<Option<RpcTransaction<T::NetworkTypes>>> {
+    ) -> RpcResult<Option<RpcTransactionJson<T::NetworkTypes>>> {
         trace!(target: "rpc::eth", ?hash, ?index, "Serving eth_getTransactionByBlockHashAndIndex");
         Ok(EthTransactions::transaction_by_block_and_tx_index(self, hash.into(), index.into())
-            .await?)
+            .await?
+            .map(Into::into))
     }
 
     /// Handler for: `eth_getRawTransactionByBlockNumberAndIndex`
@@ -550,10 +575,11 @@ where
         &self,
         number: BlockNumberOrTag,
         index: Index,
-    ) -> RpcResult<Option<RpcTransaction<T::NetworkTypes>>> {
+    ) -> RpcResult<Option<RpcTransactionJson<T::NetworkTypes>>> {
         trace!(target: "rpc::eth", ?number, ?index, "Serving eth_getTransactionByBlockNumberAndIndex");
         Ok(EthTransactions::transaction_by_block_and_tx_index(self, number.into(), index.into())
-            .await?)
+            .await?
+            .map(Into::into))
     }
 
     /// Handler for: `eth_getTransactionBySenderAndNonce`
@@ -561,10 +587,11 @@ where
         &self,
         sender: Address,
         nonce: U64,
-    ) -> RpcResult<Option<RpcTransaction<T::NetworkTypes>>> {
+    ) -> RpcResult<Option<RpcTransactionJson<T::NetworkTypes>>> {
         trace!(target: "rpc::eth", ?sender, ?nonce, "Serving eth_getTransactionBySenderAndNonce");
         Ok(EthTransactions::get_transaction_by_sender_and_nonce(self, sender, nonce.to(), true)
-            .await?)
+            .await?
+            .map(Into::into))
     }
 
     /// Handler for: `eth_getTransactionReceipt`
@@ -576,6 +603,24 @@ where
         Ok(EthTransactions::transaction_receipt(self, hash).await?)
     }
 
+    /// Handler for: `eth_getBlobSidecars`
+    async fn blob_sidecars(
+        &self,
+        block_id: BlockId,
+    ) -> RpcResult<Option<Vec<BlobTransactionSidecar>>> {
+        trace!(target: "rpc::eth", ?block_id, "Serving eth_getBlobSidecars");
+        Ok(EthTransactions::blob_sidecars_by_block(self, block_id).await?)
+    }
+
+    /// Handler for: `eth_getBlobSidecarByTxHash`
+    async fn blob_sidecar_by_tx_hash(
+        &self,
+        hash: B256,
+    ) -> RpcResult<Option<BlobTransactionSidecar>> {
+        trace!(target: "rpc::eth", ?hash, "Serving eth_getBlobSidecarByTxHash");
+        Ok(EthTransactions::blob_sidecar_by_hash(self, hash).await?)
+    }
+
     /// Handler for: `eth_getBalance`
     async fn balance(&self, address: Address, block_number: Option<BlockId>) -> RpcResult<U256> {
         trace!(target: "rpc::eth", ?address, ?block_number, "Serving eth_getBalance");
@@ -629,10 +674,17 @@ where
         &self,
         payload: SimulatePayload,
         block_number: Option<BlockId>,
-    ) -> RpcResult<Vec<SimulatedBlock<RpcBlock<T::NetworkTypes>>>> {
+    ) -> RpcResult<Vec<SimulatedBlock<RpcBlockJson<T::NetworkTypes>>>> {
         trace!(target: "rpc::eth", ?block_number, "Serving eth_simulateV1");
         let _permit = self.tracing_task_guard().clone().acquire_owned().await;
-        Ok(EthCall::simulate_v1(self, payload, block_number).await?)
+        Ok(EthCall::simulate_v1(self, payload, block_number)
+            .await?
+            .into_iter()
+            .map(|block| SimulatedBlock {
+                inner: block.inner.map_transactions(Into::into),
+                calls: block.calls,
+            })
+            .collect())
     }
 
     /// Handler for: `eth_call`
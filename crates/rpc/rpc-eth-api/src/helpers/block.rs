@@ -12,12 +12,12 @@ use reth_primitives::{SealedBlockFor, SealedBlockWithSenders};
 use reth_provider::{
     BlockIdReader, BlockReader, BlockReaderIdExt, ProviderHeader, ProviderReceipt,
 };
-use reth_rpc_types_compat::block::from_block;
+use reth_rpc_eth_types::cache::tx_json::from_block_with_tx_json;
 use revm_primitives::U256;
 
 use crate::{
-    node::RpcNodeCoreExt, EthApiTypes, FromEthApiError, FullEthApiTypes, RpcBlock, RpcNodeCore,
-    RpcReceipt,
+    node::RpcNodeCoreExt, EthApiTypes, FromEthApiError, FullEthApiTypes, RpcBlock, RpcBlockJson,
+    RpcNodeCore, RpcReceipt,
 };
 
 use super::{LoadPendingBlock, LoadReceipt, SpawnBlocking};
@@ -51,25 +51,23 @@ pub trait EthBlocks: LoadBlock {
     /// Returns the populated rpc block object for the given block id.
     ///
     /// If `full` is true, the block object will contain all transaction objects, otherwise it will
-    /// only contain the transaction hashes.
+    /// only contain the transaction hashes. Transaction objects of non-pending blocks are served
+    /// already serialized from the
+    /// [`TransactionJsonCache`](reth_rpc_eth_types::TransactionJsonCache), if enabled.
     fn rpc_block(
         &self,
         block_id: BlockId,
         full: bool,
-    ) -> impl Future<Output = Result<Option<RpcBlock<Self::NetworkTypes>>, Self::Error>> + Send
+    ) -> impl Future<Output = Result<Option<RpcBlockJson<Self::NetworkTypes>>, Self::Error>> + Send
     where
         Self: FullEthApiTypes,
     {
         async move {
             let Some(block) = self.block_with_senders(block_id).await? else { return Ok(None) };
-            let block_hash = block.hash();
+            let cache = self.tx_json_cache().filter(|_| !block_id.is_pending());
 
-            let block = from_block(
-                (*block).clone().unseal(),
-                full.into(),
-                Some(block_hash),
-                self.tx_resp_builder(),
-            )?;
+            let block =
+                from_block_with_tx_json(&block, full.into(), self.tx_resp_builder(), cache)?;
             Ok(Some(block))
         }
     }
@@ -20,7 +20,9 @@ pub trait SpawnBlocking: EthApiTypes + Clone + Send + Sync + 'static {
 
     /// Returns a handle for spawning CPU heavy blocking tasks.
     ///
-    /// Thread pool access in default trait method implementations.
+    /// Thread pool access in default trait method implementations. This is usually the compute
+    /// pool of the task executor, see
+    /// [`TaskExecutor::compute_pool`](reth_tasks::TaskExecutor::compute_pool).
     fn tracing_task_pool(&self) -> &BlockingTaskPool;
 
     /// Returns handle to semaphore for pool of CPU heavy blocking tasks.
@@ -65,13 +67,23 @@ pub trait SpawnBlocking: EthApiTypes + Clone + Send + Sync + 'static {
     /// Note: This is expected for futures that are predominantly CPU bound, as it uses `rayon`
     /// under the hood, for blocking IO futures use [`spawn_blocking`](Self::spawn_blocking_io). See
     /// <https://ryhl.io/blog/async-what-is-blocking/>.
+    ///
+    /// Tracing, debug and call requests are spawned with this, so they run on the compute pool of
+    /// the task executor like
+    /// [`TaskExecutor::spawn_compute`](reth_tasks::TaskExecutor::spawn_compute).
+    ///
+    /// Fails with [`EthApiError::ComputePoolSaturated`] if the pool is bounded and too many tasks
+    /// are already waiting for a thread.
     fn spawn_tracing<F, R>(&self, f: F) -> impl Future<Output = Result<R, Self::Error>> + Send
     where
         F: FnOnce(Self) -> Result<R, Self::Error> + Send + 'static,
         R: Send + 'static,
     {
         let this = self.clone();
-        let fut = self.tracing_task_pool().spawn(move || f(this));
-        async move { fut.await.map_err(|_| EthApiError::InternalBlockingTaskError)? }
+        let fut = self.tracing_task_pool().try_spawn(move || f(this));
+        async move {
+            let fut = fut.map_err(|_| EthApiError::ComputePoolSaturated)?;
+            fut.await.map_err(|_| EthApiError::InternalBlockingTaskError)?
+        }
     }
 }
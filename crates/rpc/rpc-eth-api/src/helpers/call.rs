@@ -17,8 +17,9 @@ use alloy_rpc_types_eth::{
 };
 use futures::Future;
 use reth_chainspec::EthChainSpec;
-use reth_evm::{env::EvmEnv, ConfigureEvm, ConfigureEvmEnv};
+use reth_evm::{env::EvmEnv, system_calls::SystemCaller, ConfigureEvm, ConfigureEvmEnv};
 use reth_node_api::BlockBody;
+use reth_primitives::SealedHeader;
 use reth_primitives_traits::SignedTransaction;
 use reth_provider::{BlockIdReader, ChainSpecProvider, ProviderHeader};
 use reth_revm::{
@@ -37,10 +38,11 @@ use reth_rpc_eth_types::{
         CallFees,
     },
     simulate::{self, EthSimulateError},
-    EthApiError, RevertError, RpcInvalidTransactionError, StateCacheDb,
+    EthApiError, ReplayCache, ReplayedState, RevertError, RpcInvalidTransactionError, StateCacheDb,
 };
 use revm::{Database, DatabaseCommit, GetInspector};
 use revm_inspectors::{access_list::AccessListInspector, transfer::TransferInspector};
+use std::fmt::Display;
 use tracing::trace;
 
 /// Result type for `eth_simulateV1` RPC method.
@@ -400,7 +402,7 @@ pub trait EthCall: EstimateCall + Call + LoadPendingBlock + LoadBlock + FullEthA
             let (evm_env, at) = self.evm_env_at(block_id).await?;
             let EvmEnv { cfg_env_with_handler_cfg, block_env } = evm_env;
 
-            self.spawn_blocking_io(move |this| {
+            self.spawn_tracing(move |this| {
                 this.create_access_list_with(cfg_env_with_handler_cfg, block_env, at, request)
             })
             .await
@@ -504,6 +506,12 @@ pub trait Call:
     /// Returns the maximum number of blocks accepted for `eth_simulateV1`.
     fn max_simulate_blocks(&self) -> u64;
 
+    /// Returns the cache of partially replayed blocks, used to execute calls right before a
+    /// transaction of a block.
+    ///
+    /// Data access in default trait method implementations.
+    fn replay_cache(&self) -> &ReplayCache;
+
     /// Executes the closure with the state that corresponds to the given [`BlockId`].
     fn with_state_at_block<F, R>(&self, at: BlockId, f: F) -> Result<R, Self::Error>
     where
@@ -588,11 +596,10 @@ pub trait Call:
     ///
     /// # Blocking behaviour
     ///
-    /// This assumes executing the call is relatively more expensive on IO than CPU because it
-    /// transacts a single transaction on an empty in memory database. Because `eth_call`s are
-    /// usually allowed to consume a lot of gas, this also allows a lot of memory operations so
-    /// we assume this is not primarily CPU bound and instead spawn the call on a regular tokio task
-    /// instead, where blocking IO is less problematic.
+    /// Because `eth_call`s are usually allowed to consume a lot of gas, executing the call can keep
+    /// a thread busy for a long time, so the call is spawned on the compute pool with
+    /// [`spawn_tracing`](SpawnBlocking::spawn_tracing), apart from the tasks that keep the node in
+    /// sync. The request is rejected if the pool is saturated.
     fn spawn_with_call_at<F, R>(
         &self,
         request: TransactionRequest,
@@ -610,8 +617,7 @@ pub trait Call:
         async move {
             let (evm_env, at) = self.evm_env_at(at).await?;
             let EvmEnv { cfg_env_with_handler_cfg, block_env } = evm_env;
-            let this = self.clone();
-            self.spawn_blocking_io(move |_| {
+            self.spawn_tracing(move |this| {
                 let state = this.state_at_block_id(at)?;
                 let mut db =
                     CacheDB::new(StateProviderDatabase::new(StateProviderTraitObjWrapper(&state)));
@@ -630,6 +636,172 @@ pub trait Call:
         }
     }
 
+    /// Prepares the state right before the transaction at the given index of the block and the env
+    /// for the given [`TransactionRequest`] and executes the closure on a new task returning the
+    /// result of the closure.
+    ///
+    /// The first `tx_index` transactions of the block are replayed on top of the state of its
+    /// parent, see [`Call::replay_block_transactions_until_index`]. An index equal to the number of
+    /// transactions in the block executes the call at the end of the block, and so does no index,
+    /// see [`Call::spawn_with_call_at`].
+    ///
+    /// This is used by `debug_traceCall` and `trace_call`.
+    fn spawn_with_call_at_transaction<F, R>(
+        &self,
+        request: TransactionRequest,
+        at: BlockId,
+        tx_index: Option<usize>,
+        overrides: EvmOverrides,
+        f: F,
+    ) -> impl Future<Output = Result<R, Self::Error>> + Send
+    where
+        Self: LoadBlock,
+        F: FnOnce(StateCacheDbRefMutWrapper<'_, '_>, EnvWithHandlerCfg) -> Result<R, Self::Error>
+            + Send
+            + 'static,
+        R: Send + 'static,
+    {
+        async move {
+            let Some(tx_index) = tx_index else {
+                return self.spawn_with_call_at(request, at, overrides, f).await
+            };
+
+            let mut target_block = at;
+            let is_block_target_pending = target_block.is_pending();
+
+            // if it's not pending, we should always use block_hash over block_number to ensure that
+            // different provider calls query data related to the same block.
+            if !is_block_target_pending {
+                target_block = self
+                    .provider()
+                    .block_hash_for_id(target_block)
+                    .map_err(|_| EthApiError::HeaderNotFound(target_block))?
+                    .ok_or_else(|| EthApiError::HeaderNotFound(target_block))?
+                    .into();
+            }
+
+            let ((evm_env, _), block) = futures::try_join!(
+                self.evm_env_at(target_block),
+                self.block_with_senders(target_block)
+            )?;
+            let EvmEnv { cfg_env_with_handler_cfg, block_env } = evm_env;
+
+            let block = block.ok_or(EthApiError::HeaderNotFound(target_block))?;
+            let num_txs = block.body.transactions().len();
+            if tx_index > num_txs {
+                return Err(EthApiError::InvalidParams(format!(
+                    "transaction index {tx_index} is out of bounds for a block with {num_txs} \
+                     transactions"
+                ))
+                .into())
+            }
+
+            // if all transactions are to be replayed, we can use the state at the block itself,
+            // except for the pending block whose state might not exist
+            let (at, replay_block_txs) = if !is_block_target_pending && tx_index == num_txs {
+                (block.hash(), false)
+            } else {
+                (block.parent_hash(), true)
+            };
+
+            let this = self.clone();
+            self.spawn_with_state_at_block(at.into(), move |state| {
+                let mut db = CacheDB::new(StateProviderDatabase::new(state));
+
+                if replay_block_txs {
+                    this.replay_block_transactions_until_index(
+                        &mut db,
+                        cfg_env_with_handler_cfg.clone(),
+                        block_env.clone(),
+                        &block.header,
+                        block.transactions_with_sender(),
+                        tx_index,
+                    )?;
+                }
+
+                let env = this.prepare_call_env(
+                    cfg_env_with_handler_cfg,
+                    block_env,
+                    request,
+                    &mut db,
+                    overrides,
+                )?;
+
+                f(StateCacheDbRefMutWrapper(&mut db), env)
+            })
+            .await
+        }
+    }
+
+    /// Replays the first `tx_index` transactions of the block with the given hash.
+    ///
+    /// The given database must point to the state of the parent of the block. Replaying starts
+    /// from the closest state of the block in the [`Call::replay_cache`], and the state after
+    /// `tx_index` transactions is cached, so that iterating over the indices of a block doesn't
+    /// replay the block from the start for every index.
+    ///
+    /// The pre-execution changes of the block, like the system calls of EIP-4788 and EIP-2935, are
+    /// applied before the first transaction, also if none is replayed.
+    fn replay_block_transactions_until_index<'a, ExtDB, I>(
+        &self,
+        db: &mut CacheDB<ExtDB>,
+        cfg: CfgEnvWithHandlerCfg,
+        block_env: BlockEnv,
+        header: &SealedHeader<ProviderHeader<Self::Provider>>,
+        transactions: I,
+        tx_index: usize,
+    ) -> Result<(), Self::Error>
+    where
+        ExtDB: DatabaseRef<Error: Display>,
+        EthApiError: From<ExtDB::Error>,
+        I: IntoIterator<Item = (&'a Address, &'a <Self::Evm as ConfigureEvmEnv>::Transaction)>,
+        <Self::Evm as ConfigureEvmEnv>::Transaction: SignedTransaction,
+    {
+        let mut replayed = None;
+        if let Some(state) = self.replay_cache().get(header.hash(), tx_index) {
+            state.apply_to(db);
+            replayed = Some(state.tx_index());
+        }
+        if replayed == Some(tx_index) {
+            return Ok(())
+        }
+
+        // cached states already include the pre-execution changes
+        let replayed = if let Some(replayed) = replayed {
+            replayed
+        } else {
+            let mut system_caller =
+                SystemCaller::new(self.evm_config().clone(), self.provider().chain_spec());
+            system_caller
+                .pre_block_beacon_root_contract_call(
+                    db,
+                    &cfg,
+                    &block_env,
+                    header.parent_beacon_block_root(),
+                )
+                .map_err(|_| {
+                    EthApiError::EvmCustom("failed to apply 4788 system call".to_string())
+                })?;
+            system_caller
+                .pre_block_blockhashes_contract_call(db, &cfg, &block_env, header.parent_hash())
+                .map_err(|_| {
+                    EthApiError::EvmCustom("failed to apply blockhashes system call".to_string())
+                })?;
+            0
+        };
+
+        let env = EnvWithHandlerCfg::new_with_cfg_env(cfg, block_env, Default::default());
+        let mut evm = self.evm_config().evm_with_env(&mut *db, env);
+        for (sender, tx) in transactions.into_iter().skip(replayed).take(tx_index - replayed) {
+            self.evm_config().fill_tx_env(evm.tx_mut(), tx, *sender);
+            evm.transact_commit().map_err(Self::Error::from_evm_err)?;
+        }
+        drop(evm);
+
+        self.replay_cache().insert(header.hash(), ReplayedState::new(tx_index, db));
+        Ok(())
+    }
+
     /// Retrieves the transaction if it exists and executes it.
     ///
     /// Before the transaction is executed, all previous transaction in the block are applied to the
@@ -263,7 +263,7 @@ pub trait EstimateCall: Call {
             let (evm_env, at) = self.evm_env_at(at).await?;
             let EvmEnv { cfg_env_with_handler_cfg, block_env } = evm_env;
 
-            self.spawn_blocking_io(move |this| {
+            self.spawn_tracing(move |this| {
                 let state = this.state_at_block_id(at)?;
                 EstimateCall::estimate_gas_with(
                     &this,
@@ -18,8 +18,9 @@ use reth_evm::{
 use reth_primitives::{BlockExt, InvalidTransactionError, SealedBlockWithSenders};
 use reth_primitives_traits::Receipt;
 use reth_provider::{
-    BlockReader, BlockReaderIdExt, ChainSpecProvider, ProviderBlock, ProviderError, ProviderHeader,
-    ProviderReceipt, ProviderTx, ReceiptProvider, StateProviderFactory,
+    BlockReader, BlockReaderIdExt, ChainSpecProvider, ExecutionOutcome, ProviderBlock,
+    ProviderError, ProviderHeader, ProviderReceipt, ProviderTx, ReceiptProvider,
+    StateProviderFactory,
 };
 use reth_revm::{
     database::StateProviderDatabase,
@@ -28,14 +29,19 @@ use reth_revm::{
         ResultAndState,
     },
 };
-use reth_rpc_eth_types::{EthApiError, PendingBlock, PendingBlockEnv, PendingBlockEnvOrigin};
+use reth_rpc_eth_types::{
+    EthApiError, PendingBlock, PendingBlockEnv, PendingBlockEnvOrigin, PENDING_BLOCK_BUILD_TIMEOUT,
+};
 use reth_transaction_pool::{
     error::InvalidPoolTransactionError, BestTransactionsAttributes, PoolTransaction,
     TransactionPool,
 };
 use revm::{db::states::bundle_state::BundleRetention, DatabaseCommit, State};
-use std::time::{Duration, Instant};
-use tokio::sync::Mutex;
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use tokio::sync::{Mutex, MutexGuard};
 use tracing::debug;
 
 /// Loads a pending block from database.
@@ -126,70 +132,113 @@ pub trait LoadPendingBlock:
         ))
     }
 
-    /// Returns the locally built pending block
+    /// Returns the generation the locally built pending block is currently valid for, if tracked.
+    ///
+    /// The generation changes on every new canonical head and pending pool transaction, see
+    /// [`PendingBlockGeneration`](reth_rpc_eth_types::PendingBlockGeneration). If this returns
+    /// `None`, the locally built pending block expires after a fixed time instead.
+    fn pending_block_generation(&self) -> Option<u64> {
+        None
+    }
+
+    /// Returns the locked [`LoadPendingBlock::pending_block`] after rebuilding it on top of the
+    /// given parent, unless the cached pending block is still up to date.
+    ///
+    /// If the pending block couldn't be built, the lock holds `None`.
     #[expect(clippy::type_complexity)]
-    fn local_pending_block(
+    fn lock_local_pending_block(
         &self,
+        cfg: CfgEnvWithHandlerCfg,
+        block_env: BlockEnv,
+        parent_hash: B256,
     ) -> impl Future<
-        Output = Result<
-            Option<(
-                SealedBlockWithSenders<<Self::Provider as BlockReader>::Block>,
-                Vec<ProviderReceipt<Self::Provider>>,
-            )>,
-            Self::Error,
+        Output = MutexGuard<
+            '_,
+            Option<PendingBlock<ProviderBlock<Self::Provider>, ProviderReceipt<Self::Provider>>>,
         >,
     > + Send
     where
         Self: SpawnBlocking,
     {
         async move {
-            let pending = self.pending_block_env_and_cfg()?;
-            let parent_hash = match pending.origin {
-                PendingBlockEnvOrigin::ActualPending(block, receipts) => {
-                    return Ok(Some((block, receipts)));
-                }
-                PendingBlockEnvOrigin::DerivedFromLatest(parent_hash) => parent_hash,
-            };
-
-            // we couldn't find the real pending block, so we need to build it ourselves
             let mut lock = self.pending_block().lock().await;
 
-            let now = Instant::now();
+            // the generation must be fetched before building, so that any change during the
+            // build outdates the new pending block
+            let generation = self.pending_block_generation();
 
             // check if the block is still good
             if let Some(pending_block) = lock.as_ref() {
+                let up_to_date = match generation {
+                    Some(generation) => pending_block.generation == Some(generation),
+                    None => Instant::now() <= pending_block.expires_at,
+                };
                 // this is guaranteed to be the `latest` header
-                if pending.block_env.number.to::<u64>() == pending_block.block.number() &&
+                if block_env.number.to::<u64>() == pending_block.block.number() &&
                     parent_hash == pending_block.block.parent_hash() &&
-                    now <= pending_block.expires_at
+                    up_to_date
                 {
-                    return Ok(Some((pending_block.block.clone(), pending_block.receipts.clone())));
+                    return lock
                 }
             }
 
             // no pending block from the CL yet, so we need to build it ourselves via txpool
-            let (sealed_block, receipts) = match self
+            *lock = match self
                 .spawn_blocking_io(move |this| {
                     // we rebuild the block
-                    this.build_block(pending.cfg, pending.block_env, parent_hash)
+                    this.build_block(cfg, block_env, parent_hash)
                 })
                 .await
             {
-                Ok(block) => block,
+                Ok((block, receipts, state)) => Some(PendingBlock::new(
+                    Instant::now() + Duration::from_secs(1),
+                    block,
+                    receipts,
+                    Arc::new(state),
+                    generation,
+                )),
                 Err(err) => {
                     debug!(target: "rpc", "Failed to build pending block: {:?}", err);
-                    return Ok(None)
+                    None
                 }
             };
 
-            let now = Instant::now();
-            *lock = Some(PendingBlock::new(
-                now + Duration::from_secs(1),
-                sealed_block.clone(),
-                receipts.clone(),
-            ));
+            lock
+        }
+    }
+
+    /// Returns the locally built pending block
+    #[expect(clippy::type_complexity)]
+    fn local_pending_block(
+        &self,
+    ) -> impl Future<
+        Output = Result<
+            Option<(
+                SealedBlockWithSenders<<Self::Provider as BlockReader>::Block>,
+                Vec<ProviderReceipt<Self::Provider>>,
+            )>,
+            Self::Error,
+        >,
+    > + Send
+    where
+        Self: SpawnBlocking,
+    {
+        async move {
+            let pending = self.pending_block_env_and_cfg()?;
+            let parent_hash = match pending.origin {
+                PendingBlockEnvOrigin::ActualPending(block, receipts) => {
+                    return Ok(Some((block, receipts)));
+                }
+                PendingBlockEnvOrigin::DerivedFromLatest(parent_hash) => parent_hash,
+            };
 
-            Ok(Some((sealed_block, receipts)))
+            // we couldn't find the real pending block, so we need to build it ourselves
+            let lock =
+                self.lock_local_pending_block(pending.cfg, pending.block_env, parent_hash).await;
+
+            Ok(lock
+                .as_ref()
+                .map(|pending_block| (pending_block.block.clone(), pending_block.receipts.clone())))
         }
     }
 
@@ -240,6 +289,10 @@ pub trait LoadPendingBlock:
     ///
     /// After Cancun, if the origin is the actual pending block, the block includes the EIP-4788 pre
     /// block contract call using the parent beacon block root received from the CL.
+    ///
+    /// Pool transactions are only executed until [`PENDING_BLOCK_BUILD_TIMEOUT`] has elapsed.
+    ///
+    /// Returns the block, its receipts and its state changes on top of the parent.
     #[expect(clippy::type_complexity)]
     fn build_block(
         &self,
@@ -250,12 +303,15 @@ pub trait LoadPendingBlock:
         (
             SealedBlockWithSenders<ProviderBlock<Self::Provider>>,
             Vec<ProviderReceipt<Self::Provider>>,
+            ExecutionOutcome,
         ),
         Self::Error,
     >
     where
         EthApiError: From<ProviderError>,
     {
+        let deadline = Instant::now() + PENDING_BLOCK_BUILD_TIMEOUT;
+
         let state_provider = self
             .provider()
             .history_by_block_hash(parent_hash)
@@ -287,6 +343,11 @@ pub trait LoadPendingBlock:
         let mut results = Vec::new();
 
         while let Some(pool_tx) = best_txs.next() {
+            // stop including transactions once the time for building the block is up
+            if Instant::now() >= deadline {
+                break
+            }
+
             // ensure we still have capacity for this transaction
             if cumulative_gas_used + pool_tx.gas_limit() > block_gas_limit {
                 // we can't fit this transaction into the block, so we need to mark it as invalid
@@ -418,6 +479,7 @@ pub trait LoadPendingBlock:
         // calculate the state root
         let state_root = db.database.state_root(hashed_state).map_err(Self::Error::from_eth_err)?;
 
+        let block_number = block_env.number.to::<u64>();
         let (block, receipts) = self.assemble_block_and_receipts(
             &block_env,
             parent_hash,
@@ -426,6 +488,9 @@ pub trait LoadPendingBlock:
             results,
         );
 
-        Ok((SealedBlockWithSenders { block: block.seal_slow(), senders }, receipts))
+        let state =
+            ExecutionOutcome::new(bundle_state, Default::default(), block_number, Vec::new());
+
+        Ok((SealedBlockWithSenders { block: block.seal_slow(), senders }, receipts, state))
     }
 }
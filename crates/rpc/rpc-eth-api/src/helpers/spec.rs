@@ -1,5 +1,6 @@
 //! Loads chain metadata.
 
+use alloy_eips::BlockNumHash;
 use alloy_primitives::{Address, U256, U64};
 use alloy_rpc_types_eth::{Stage, SyncInfo, SyncStatus};
 use futures::Future;
@@ -7,6 +8,8 @@ use reth_chainspec::{ChainInfo, EthereumHardforks};
 use reth_errors::{RethError, RethResult};
 use reth_network_api::NetworkInfo;
 use reth_provider::{BlockNumReader, ChainSpecProvider, StageCheckpointReader};
+use reth_rpc_eth_types::EthApiError;
+use tokio::sync::watch;
 
 use crate::{helpers::EthSigner, RpcNodeCore};
 
@@ -31,6 +34,14 @@ pub trait EthApiSpec:
     /// Returns a handle to the signers owned by provider.
     fn signers(&self) -> &parking_lot::RwLock<Vec<Box<dyn EthSigner<Self::Transaction>>>>;
 
+    /// Returns a handle to the suggested fee recipient of locally built payloads, if the node
+    /// builds its own blocks.
+    fn fee_recipient(&self) -> &parking_lot::RwLock<Option<watch::Receiver<Address>>>;
+
+    /// Returns a handle to the head of the latest forkchoice update received from the consensus
+    /// layer, if the node tracks it.
+    fn sync_target_updates(&self) -> Option<&watch::Receiver<Option<BlockNumHash>>>;
+
     /// Returns the current ethereum protocol version.
     fn protocol_version(&self) -> impl Future<Output = RethResult<U64>> + Send {
         async move {
@@ -54,17 +65,70 @@ pub trait EthApiSpec:
         self.signers().read().iter().flat_map(|s| s.accounts()).collect()
     }
 
+    /// Returns the current suggested fee recipient of locally built payloads, if any.
+    fn coinbase(&self) -> Option<Address> {
+        self.fee_recipient().read().as_ref().map(|fee_recipient| *fee_recipient.borrow())
+    }
+
     /// Returns `true` if the network is undergoing sync.
     fn is_syncing(&self) -> bool {
         self.network().is_syncing()
     }
 
+    /// Returns the block the node is syncing to, `None` if the network isn't undergoing sync or
+    /// the target isn't known.
+    ///
+    /// This is the head of the latest forkchoice update received from the consensus layer, once
+    /// the node knows its block number.
+    fn sync_target(&self) -> Option<u64> {
+        if !self.is_syncing() {
+            return None
+        }
+        self.sync_target_updates()?.borrow().map(|target| target.number)
+    }
+
+    /// Returns how many blocks the node is behind its [`Self::sync_target`], `None` if the network
+    /// isn't undergoing sync or the target isn't known.
+    fn sync_distance(&self) -> Option<u64> {
+        let target = self.sync_target()?;
+        let current_block =
+            self.provider().chain_info().map(|info| info.best_number).unwrap_or_default();
+        Some(target.saturating_sub(current_block))
+    }
+
+    /// Returns the maximum number of blocks the node may be behind its [`Self::sync_target`], for
+    /// transactions submitted via RPC to be accepted.
+    ///
+    /// If `None`, transactions are accepted regardless of the sync state.
+    fn max_tx_sync_distance(&self) -> Option<u64> {
+        None
+    }
+
+    /// Returns an error if the node is syncing and further behind its [`Self::sync_target`] than
+    /// the [`Self::max_tx_sync_distance`], in which case transactions submitted via RPC are
+    /// rejected.
+    ///
+    /// While syncing without a known target, e.g. before the consensus layer sent a forkchoice
+    /// update, the distance is unknown and transactions are rejected too.
+    fn ensure_tx_sync_distance(&self) -> Result<(), EthApiError> {
+        let Some(max_distance) = self.max_tx_sync_distance() else { return Ok(()) };
+        if !self.is_syncing() {
+            return Ok(())
+        }
+        match self.sync_distance() {
+            Some(distance) if distance <= max_distance => Ok(()),
+            distance => Err(EthApiError::NodeSyncing(distance)),
+        }
+    }
+
     /// Returns the [`SyncStatus`] of the network
     fn sync_status(&self) -> RethResult<SyncStatus> {
         let status = if self.is_syncing() {
-            let current_block = U256::from(
-                self.provider().chain_info().map(|info| info.best_number).unwrap_or_default(),
-            );
+            let current_block =
+                self.provider().chain_info().map(|info| info.best_number).unwrap_or_default();
+            let highest_block =
+                U256::from(self.sync_target().unwrap_or(current_block).max(current_block));
+            let current_block = U256::from(current_block);
 
             let stages = self
                 .provider()
@@ -77,7 +141,7 @@ pub trait EthApiSpec:
             SyncStatus::Info(Box::new(SyncInfo {
                 starting_block: self.starting_block(),
                 current_block,
-                highest_block: current_block,
+                highest_block,
                 warp_chunks_amount: None,
                 warp_chunks_processed: None,
                 stages: Some(stages),
@@ -4,7 +4,7 @@ use super::{EthApiSpec, LoadPendingBlock, SpawnBlocking};
 use crate::{EthApiTypes, FromEthApiError, RpcNodeCore, RpcNodeCoreExt};
 use alloy_consensus::constants::KECCAK_EMPTY;
 use alloy_eips::BlockId;
-use alloy_primitives::{Address, Bytes, B256, U256};
+use alloy_primitives::{Address, BlockNumber, Bytes, B256, U256};
 use alloy_rpc_types_eth::{Account, EIP1186AccountProofResponse};
 use alloy_serde::JsonStorageKey;
 use futures::Future;
@@ -12,8 +12,8 @@ use reth_chainspec::{EthChainSpec, EthereumHardforks};
 use reth_errors::RethError;
 use reth_evm::{env::EvmEnv, ConfigureEvmEnv};
 use reth_provider::{
-    BlockIdReader, BlockNumReader, ChainSpecProvider, StateProvider, StateProviderBox,
-    StateProviderFactory,
+    BlockIdReader, BlockNumReader, ChainSpecProvider, ProviderResult, StateProvider,
+    StateProviderBox, StateProviderFactory,
 };
 use reth_rpc_eth_types::{EthApiError, PendingBlockEnv, RpcInvalidTransactionError};
 use reth_transaction_pool::TransactionPool;
@@ -50,13 +50,20 @@ pub trait EthState: LoadState + SpawnBlocking {
         address: Address,
         block_id: Option<BlockId>,
     ) -> impl Future<Output = Result<U256, Self::Error>> + Send {
-        self.spawn_blocking_io(move |this| {
-            Ok(this
-                .state_at_block_id_or_latest(block_id)?
-                .account_balance(&address)
-                .map_err(Self::Error::from_eth_err)?
-                .unwrap_or_default())
-        })
+        async move {
+            if block_id.is_some_and(|block_id| block_id.is_pending()) {
+                self.update_pending_state().await?;
+            }
+
+            self.spawn_blocking_io(move |this| {
+                Ok(this
+                    .state_at_block_id_or_latest(block_id)?
+                    .account_balance(&address)
+                    .map_err(Self::Error::from_eth_err)?
+                    .unwrap_or_default())
+            })
+            .await
+        }
     }
 
     /// Returns values stored of given account, at given blocknumber.
@@ -172,8 +179,33 @@ pub trait LoadState:
         Pool: TransactionPool,
     >
 {
+    /// Returns the oldest block for which historical state, traces and logs are served, if the
+    /// history is cut off.
+    ///
+    /// See [`HistoryCutoff`](reth_rpc_eth_types::HistoryCutoff).
+    fn history_cutoff(&self) -> ProviderResult<Option<BlockNumber>> {
+        Ok(None)
+    }
+
+    /// Returns [`EthApiError::HistoryUnavailable`] if the given block is below the
+    /// [`LoadState::history_cutoff`].
+    ///
+    /// Unknown blocks are left to the caller to report.
+    fn ensure_history_available(&self, at: BlockId) -> Result<(), Self::Error> {
+        let Some(cutoff) = self.history_cutoff().map_err(Self::Error::from_eth_err)? else {
+            return Ok(())
+        };
+        let block_number =
+            self.provider().block_number_for_id(at).map_err(Self::Error::from_eth_err)?;
+        if block_number.is_some_and(|block_number| block_number < cutoff) {
+            return Err(EthApiError::HistoryUnavailable(cutoff).into())
+        }
+        Ok(())
+    }
+
     /// Returns the state at the given block number
     fn state_at_hash(&self, block_hash: B256) -> Result<StateProviderBox, Self::Error> {
+        self.ensure_history_available(block_hash.into())?;
         self.provider().history_by_block_hash(block_hash).map_err(Self::Error::from_eth_err)
     }
 
@@ -181,10 +213,40 @@ pub trait LoadState:
     ///
     /// Note: if not [`BlockNumberOrTag::Pending`](alloy_eips::BlockNumberOrTag) then this
     /// will only return canonical state. See also <https://github.com/paradigmxyz/reth/issues/4515>
+    ///
+    /// For the pending tag, this prefers the [`LoadState::cached_pending_state`]. The latest tag
+    /// is served by [`LoadState::latest_state`]. Blocks below the [`LoadState::history_cutoff`]
+    /// are refused.
     fn state_at_block_id(&self, at: BlockId) -> Result<StateProviderBox, Self::Error> {
+        if at == BlockId::latest() {
+            return self.latest_state()
+        }
+        if at.is_pending() {
+            if let Some(state) = self.cached_pending_state()? {
+                return Ok(state)
+            }
+        }
+        self.ensure_history_available(at)?;
         self.provider().state_by_block_id(at).map_err(Self::Error::from_eth_err)
     }
 
+    /// Returns the state of the locally built pending block, that includes the pool's best
+    /// transactions on top of the _latest_ state, if it is cached and built on the current latest
+    /// block.
+    ///
+    /// This never builds the pending block, see [`LoadState::update_pending_state`].
+    fn cached_pending_state(&self) -> Result<Option<StateProviderBox>, Self::Error> {
+        Ok(None)
+    }
+
+    /// Makes sure the [`LoadState::cached_pending_state`] is up to date with the latest block and
+    /// the pool, building it if necessary.
+    ///
+    /// Returns `true` if the pending state is available.
+    fn update_pending_state(&self) -> impl Future<Output = Result<bool, Self::Error>> + Send {
+        async { Ok(false) }
+    }
+
     /// Returns the _latest_ state
     fn latest_state(&self) -> Result<StateProviderBox, Self::Error> {
         self.provider().latest().map_err(Self::Error::from_eth_err)
@@ -221,6 +283,10 @@ pub trait LoadState:
             if at.is_pending() {
                 let PendingBlockEnv { cfg, block_env, origin } =
                     self.pending_block_env_and_cfg()?;
+                if !origin.is_actual_pending() && self.update_pending_state().await? {
+                    // execute on top of the pool's transactions
+                    return Ok(((cfg, block_env).into(), BlockId::pending()))
+                }
                 Ok(((cfg, block_env).into(), origin.state_block_id()))
             } else {
                 // Use cached values if there is no pending block
@@ -286,19 +352,23 @@ pub trait LoadState:
     where
         Self: SpawnBlocking,
     {
-        self.spawn_blocking_io(move |this| {
-            // first fetch the on chain nonce of the account
-            let on_chain_account_nonce = this
-                .state_at_block_id_or_latest(block_id)?
-                .account_nonce(&address)
-                .map_err(Self::Error::from_eth_err)?
-                .unwrap_or_default();
-
+        async move {
             if block_id == Some(BlockId::pending()) {
-                // for pending tag we need to find the highest nonce in the pool
-                if let Some(highest_pool_tx) =
-                    this.pool().get_highest_transaction_by_sender(address)
-                {
+                self.update_pending_state().await?;
+            }
+
+            self.spawn_blocking_io(move |this| {
+                // first fetch the on chain nonce of the account
+                let on_chain_account_nonce = this
+                    .state_at_block_id_or_latest(block_id)?
+                    .account_nonce(&address)
+                    .map_err(Self::Error::from_eth_err)?
+                    .unwrap_or_default();
+
+                if block_id == Some(BlockId::pending()) {
+                    // for pending tag we need to find the highest nonce in the pool
+                    if let Some(highest_pool_tx) =
+                        this.pool().get_highest_transaction_by_sender(address)
                     {
                         // and the corresponding txcount is nonce + 1 of the highest tx in the pool
                         // (on chain nonce is increased after tx)
@@ -316,9 +386,10 @@ pub trait LoadState:
                         return Ok(U256::from(tx_count));
                     }
                 }
-            }
-            Ok(U256::from(on_chain_account_nonce))
-        })
+                Ok(U256::from(on_chain_account_nonce))
+            })
+            .await
+        }
     }
 
     /// Returns code of given account, at the given identifier.
@@ -4,11 +4,14 @@ use std::{fmt::Display, sync::Arc};
 
 use crate::{FromEvmError, RpcNodeCore};
 use alloy_consensus::BlockHeader;
-use alloy_primitives::B256;
+use alloy_primitives::{B256, U256};
 use alloy_rpc_types_eth::{BlockId, TransactionInfo};
 use futures::Future;
-use reth_chainspec::ChainSpecProvider;
-use reth_evm::{env::EvmEnv, system_calls::SystemCaller, ConfigureEvm, ConfigureEvmEnv};
+use reth_chainspec::{ChainSpecProvider, EthereumHardfork};
+use reth_evm::{
+    env::EvmEnv, state_change::header_post_block_balance_increments, system_calls::SystemCaller,
+    ConfigureEvm, ConfigureEvmEnv,
+};
 use reth_primitives::SealedBlockWithSenders;
 use reth_primitives_traits::{BlockBody, SignedTransaction};
 use reth_provider::{BlockReader, ProviderBlock, ProviderHeader, ProviderTx};
@@ -20,7 +23,8 @@ use reth_rpc_eth_types::{
 use revm::{db::CacheDB, Database, DatabaseCommit, GetInspector, Inspector};
 use revm_inspectors::tracing::{TracingInspector, TracingInspectorConfig};
 use revm_primitives::{
-    BlockEnv, CfgEnvWithHandlerCfg, EnvWithHandlerCfg, EvmState, ExecutionResult, ResultAndState,
+    Account, BlockEnv, CfgEnvWithHandlerCfg, EnvWithHandlerCfg, EvmState, ExecutionResult,
+    ResultAndState,
 };
 
 use super::{Call, LoadBlock, LoadPendingBlock, LoadState, LoadTransaction};
@@ -508,4 +512,60 @@ pub trait Trace:
 
         Ok(())
     }
+
+    /// Applies chain-specific state transitions required after executing all transactions of a
+    /// block: the EIP-7002 and EIP-7251 system calls, and the block rewards and withdrawals.
+    ///
+    /// Note: This should only be called after all transactions of the block were committed to
+    /// `db`, to advance it to the state of the block. The irregular state change of the DAO
+    /// hardfork isn't supported.
+    fn apply_post_execution_changes<DB: Send + Database<Error: Display> + DatabaseCommit>(
+        &self,
+        block: &SealedBlockWithSenders<ProviderBlock<Self::Provider>>,
+        db: &mut DB,
+        cfg: &CfgEnvWithHandlerCfg,
+        block_env: &BlockEnv,
+    ) -> Result<(), Self::Error> {
+        let chain_spec = self.provider().chain_spec();
+        if chain_spec.fork(EthereumHardfork::Dao).transitions_at_block(block.number()) {
+            return Err(EthApiError::EvmCustom(
+                "the DAO hardfork state change can't be applied".to_string(),
+            )
+            .into())
+        }
+
+        let mut system_caller = SystemCaller::new(self.evm_config().clone(), chain_spec.clone());
+        if chain_spec.is_prague_active_at_timestamp(block.timestamp()) {
+            system_caller
+                .post_block_withdrawal_requests_contract_call(db, cfg, block_env)
+                .map_err(|_| {
+                    EthApiError::EvmCustom("failed to apply 7002 system call".to_string())
+                })?;
+            system_caller
+                .post_block_consolidation_requests_contract_call(db, cfg, block_env)
+                .map_err(|_| {
+                    EthApiError::EvmCustom("failed to apply 7251 system call".to_string())
+                })?;
+        }
+
+        let balance_increments =
+            header_post_block_balance_increments(&*chain_spec, block.header(), block.body());
+        let mut state = EvmState::default();
+        for (address, increment) in balance_increments {
+            if increment == 0 {
+                continue
+            }
+            let mut info = db
+                .basic(address)
+                .map_err(|err| EthApiError::EvmCustom(err.to_string()))?
+                .unwrap_or_default();
+            info.balance = info.balance.saturating_add(U256::from(increment));
+            let mut account = Account::from(info);
+            account.mark_touch();
+            state.insert(address, account);
+        }
+        db.commit(state);
+
+        Ok(())
+    }
 }
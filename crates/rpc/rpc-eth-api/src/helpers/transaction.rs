@@ -1,25 +1,29 @@
 //! Database access for `eth_` transaction RPC methods. Loads transaction and receipt data w.r.t.
 //! network.
 
-use alloy_consensus::{BlockHeader, Transaction};
+use alloy_consensus::{BlockHeader, Transaction, Typed2718};
 use alloy_dyn_abi::TypedData;
-use alloy_eips::{eip2718::Encodable2718, BlockId};
+use alloy_eips::{eip2718::Encodable2718, eip4844::BlobTransactionSidecar, BlockId};
 use alloy_network::TransactionBuilder;
 use alloy_primitives::{Address, Bytes, TxHash, B256};
 use alloy_rpc_types_eth::{transaction::TransactionRequest, BlockNumberOrTag, TransactionInfo};
 use futures::Future;
+use reth_errors::RethError;
 use reth_node_api::BlockBody;
 use reth_primitives::{
     transaction::SignedTransactionIntoRecoveredExt, SealedBlockWithSenders, TransactionMeta,
 };
 use reth_primitives_traits::SignedTransaction;
 use reth_provider::{
-    BlockNumReader, BlockReaderIdExt, ProviderBlock, ProviderReceipt, ProviderTx, ReceiptProvider,
-    TransactionsProvider,
+    BlockNumReader, BlockReaderIdExt, ProviderBlock, ProviderReceipt, ProviderTx,
+    PruneCheckpointReader, ReceiptProvider, TransactionsProvider,
 };
+use reth_prune_types::PruneSegment;
 use reth_rpc_eth_types::{utils::binary_search, EthApiError, SignError, TransactionSource};
 use reth_rpc_types_compat::transaction::{from_recovered, from_recovered_with_block_context};
-use reth_transaction_pool::{PoolTransaction, TransactionOrigin, TransactionPool};
+use reth_transaction_pool::{
+    blobstore::BlobStoreError, PoolTransaction, TransactionOrigin, TransactionPool,
+};
 use std::sync::Arc;
 
 use super::{EthApiSpec, EthSigner, LoadBlock, LoadReceipt, LoadState, SpawnBlocking};
@@ -119,11 +123,69 @@ pub trait EthTransactions: LoadTransaction<Provider: BlockReaderIdExt> {
             }
 
             self.spawn_blocking_io(move |ref this| {
-                Ok(this
+                match this
                     .provider()
                     .transaction_by_hash(hash)
                     .map_err(Self::Error::from_eth_err)?
-                    .map(|tx| tx.encoded_2718().into()))
+                {
+                    Some(tx) => Ok(Some(tx.encoded_2718().into())),
+                    None if this.transaction_lookup_disabled()? => {
+                        Err(EthApiError::TransactionLookupDisabled.into())
+                    }
+                    None => Ok(None),
+                }
+            })
+            .await
+        }
+    }
+
+    /// Returns the blob sidecar of the EIP-4844 transaction with the given hash.
+    ///
+    /// Sidecars are kept in the blob store of the pool while the transaction is pending, and after
+    /// it was mined until its block is finalized or leaves the retention window of the node.
+    ///
+    /// Returns `Ok(None)` if the sidecar isn't available.
+    fn blob_sidecar_by_hash(
+        &self,
+        hash: B256,
+    ) -> impl Future<Output = Result<Option<BlobTransactionSidecar>, Self::Error>> + Send {
+        self.spawn_blocking_io(move |this| {
+            this.pool()
+                .get_blob(hash)
+                .map(|sidecar| sidecar.map(Arc::unwrap_or_clone))
+                .map_err(|err| EthApiError::Internal(RethError::other(err)).into())
+        })
+    }
+
+    /// Returns the blob sidecars of the EIP-4844 transactions in the block, in the order of the
+    /// transactions.
+    ///
+    /// Returns `Ok(None)` if the block does not exist, or if the sidecar of any of its blob
+    /// transactions isn't available, see [`Self::blob_sidecar_by_hash`].
+    fn blob_sidecars_by_block(
+        &self,
+        block_id: BlockId,
+    ) -> impl Future<Output = Result<Option<Vec<BlobTransactionSidecar>>, Self::Error>> + Send
+    where
+        Self: LoadBlock,
+    {
+        async move {
+            let Some(block) = self.block_with_senders(block_id).await? else { return Ok(None) };
+            let blob_txs = block
+                .body
+                .transactions()
+                .iter()
+                .filter(|tx| tx.is_eip4844())
+                .map(|tx| *tx.tx_hash())
+                .collect::<Vec<_>>();
+            if blob_txs.is_empty() {
+                return Ok(Some(Vec::new()))
+            }
+
+            self.spawn_blocking_io(move |this| match this.pool().get_all_blobs_exact(blob_txs) {
+                Ok(sidecars) => Ok(Some(sidecars.into_iter().map(Arc::unwrap_or_clone).collect())),
+                Err(BlobStoreError::MissingSidecar(_)) => Ok(None),
+                Err(err) => Err(EthApiError::Internal(RethError::other(err)).into()),
             })
             .await
         }
@@ -181,12 +243,16 @@ pub trait EthTransactions: LoadTransaction<Provider: BlockReaderIdExt> {
         Self: 'static,
     {
         let provider = self.provider().clone();
-        self.spawn_blocking_io(move |_| {
+        self.spawn_blocking_io(move |this| {
             let (tx, meta) = match provider
                 .transaction_by_hash_with_meta(hash)
                 .map_err(Self::Error::from_eth_err)?
             {
                 Some((tx, meta)) => (tx, meta),
+                // pending transactions don't have a receipt yet
+                None if this.transaction_lookup_disabled()? && !this.pool().contains(&hash) => {
+                    return Err(EthApiError::TransactionLookupDisabled.into())
+                }
                 None => return Ok(None),
             };
 
@@ -348,6 +414,8 @@ pub trait EthTransactions: LoadTransaction<Provider: BlockReaderIdExt> {
         Self: EthApiSpec + LoadBlock + EstimateCall,
     {
         async move {
+            self.ensure_tx_sync_distance().map_err(Self::Error::from_eth_err)?;
+
             let from = match request.from {
                 Some(from) => from,
                 None => return Err(SignError::NoAccount.into_eth_err()),
@@ -466,12 +534,28 @@ pub trait EthTransactions: LoadTransaction<Provider: BlockReaderIdExt> {
 ///
 /// Behaviour shared by several `eth_` RPC methods, not exclusive to `eth_` transactions RPC
 /// methods.
-pub trait LoadTransaction: SpawnBlocking + FullEthApiTypes + RpcNodeCoreExt {
+pub trait LoadTransaction:
+    SpawnBlocking + FullEthApiTypes + RpcNodeCoreExt<Provider: PruneCheckpointReader>
+{
+    /// Returns `true` if the node doesn't keep the index of transaction hashes, so mined
+    /// transactions can't be looked up by hash.
+    ///
+    /// This is the case if the transaction lookup segment is fully pruned.
+    fn transaction_lookup_disabled(&self) -> Result<bool, Self::Error> {
+        Ok(self
+            .provider()
+            .get_prune_checkpoint(PruneSegment::TransactionLookup)
+            .map_err(Self::Error::from_eth_err)?
+            .is_some_and(|checkpoint| checkpoint.prune_mode.is_full()))
+    }
+
     /// Returns the transaction by hash.
     ///
     /// Checks the pool and state.
     ///
-    /// Returns `Ok(None)` if no matching transaction was found.
+    /// Returns `Ok(None)` if no matching transaction was found, or
+    /// [`EthApiError::TransactionLookupDisabled`] if the transaction can't be looked up because
+    /// the index of transaction hashes is disabled.
     #[expect(clippy::complexity)]
     fn transaction_by_hash(
         &self,
@@ -516,6 +600,8 @@ pub trait LoadTransaction: SpawnBlocking + FullEthApiTypes + RpcNodeCoreExt {
                     self.pool().get(&hash).map(|tx| tx.transaction.clone().into_consensus())
                 {
                     resp = Some(TransactionSource::Pool(tx.into()));
+                } else if self.transaction_lookup_disabled()? {
+                    return Err(EthApiError::TransactionLookupDisabled.into())
                 }
             }
 
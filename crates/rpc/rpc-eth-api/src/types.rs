@@ -8,6 +8,7 @@ use std::{
 use alloy_network::Network;
 use alloy_rpc_types_eth::Block;
 use reth_provider::{ProviderTx, ReceiptProvider, TransactionsProvider};
+use reth_rpc_eth_types::{MaybeSerialized, TransactionJsonCache};
 use reth_rpc_types_compat::TransactionCompat;
 use reth_transaction_pool::{PoolTransaction, TransactionPool};
 
@@ -30,6 +31,12 @@ pub trait EthApiTypes: Send + Sync + Clone {
 
     /// Returns reference to transaction response builder.
     fn tx_resp_builder(&self) -> &Self::TransactionCompat;
+
+    /// Returns handle to the cache of the serialized RPC objects of mined transactions, if
+    /// enabled.
+    fn tx_json_cache(&self) -> Option<&TransactionJsonCache> {
+        None
+    }
 }
 
 /// Adapter for network specific transaction type.
@@ -38,6 +45,12 @@ pub type RpcTransaction<T> = <T as Network>::TransactionResponse;
 /// Adapter for network specific block type.
 pub type RpcBlock<T> = Block<RpcTransaction<T>, <T as Network>::HeaderResponse>;
 
+/// Adapter for network specific transaction type, which may already be serialized.
+pub type RpcTransactionJson<T> = MaybeSerialized<RpcTransaction<T>>;
+
+/// Adapter for network specific block type, whose transactions may already be serialized.
+pub type RpcBlockJson<T> = Block<RpcTransactionJson<T>, <T as Network>::HeaderResponse>;
+
 /// Adapter for network specific receipt type.
 pub type RpcReceipt<T> = <T as Network>::ReceiptResponse;
 
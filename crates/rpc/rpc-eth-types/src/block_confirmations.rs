@@ -0,0 +1,114 @@
+//! Block parameters relative to the canonical head.
+//!
+//! On top of the standard block parameters, reth accepts `{"confirmations": K}` wherever a block
+//! is expected, which refers to the block `K` blocks below the canonical head. The head is read
+//! once per request, so that all such parameters of a request refer to the same block.
+
+use alloy_eips::{BlockId, BlockNumberOrTag};
+use alloy_primitives::BlockNumber;
+use serde::{Deserialize, Serialize};
+
+/// The block with the given number of confirmations, i.e. that many blocks below the canonical
+/// head.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BlockConfirmations {
+    /// Number of blocks on top of the block.
+    pub confirmations: u64,
+}
+
+impl BlockConfirmations {
+    /// Returns the number of the block, given the number of the canonical head, clamped at
+    /// genesis.
+    pub const fn resolve(&self, head: BlockNumber) -> BlockNumber {
+        head.saturating_sub(self.confirmations)
+    }
+}
+
+/// A [`BlockId`], extended with [`BlockConfirmations`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ExtendedBlockId {
+    /// A block with a number of confirmations.
+    Confirmations(BlockConfirmations),
+    /// A standard block parameter.
+    BlockId(BlockId),
+}
+
+impl ExtendedBlockId {
+    /// Returns the [`BlockId`], given the number of the canonical head.
+    pub const fn resolve(self, head: BlockNumber) -> BlockId {
+        match self {
+            Self::Confirmations(confirmations) => {
+                BlockId::Number(BlockNumberOrTag::Number(confirmations.resolve(head)))
+            }
+            Self::BlockId(block_id) => block_id,
+        }
+    }
+}
+
+impl From<BlockId> for ExtendedBlockId {
+    fn from(block_id: BlockId) -> Self {
+        Self::BlockId(block_id)
+    }
+}
+
+impl From<BlockConfirmations> for ExtendedBlockId {
+    fn from(confirmations: BlockConfirmations) -> Self {
+        Self::Confirmations(confirmations)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::B256;
+    use serde_json::json;
+
+    #[test]
+    fn serde_extended_block_id() {
+        let confirmations = ExtendedBlockId::Confirmations(BlockConfirmations { confirmations: 6 });
+        assert_eq!(serde_json::to_value(confirmations).unwrap(), json!({ "confirmations": 6 }));
+        assert_eq!(
+            serde_json::from_value::<ExtendedBlockId>(json!({ "confirmations": 6 })).unwrap(),
+            confirmations
+        );
+
+        for block_id in [
+            BlockId::latest(),
+            BlockId::number(5),
+            BlockId::hash(B256::with_last_byte(1)),
+            BlockId::Number(BlockNumberOrTag::Safe),
+        ] {
+            let json = serde_json::to_value(block_id).unwrap();
+            assert_eq!(
+                serde_json::from_value::<ExtendedBlockId>(json).unwrap(),
+                ExtendedBlockId::BlockId(block_id)
+            );
+        }
+        assert_eq!(
+            serde_json::from_value::<ExtendedBlockId>(json!({ "blockNumber": "0x5" })).unwrap(),
+            ExtendedBlockId::BlockId(BlockId::number(5))
+        );
+
+        // other fields and other value types are rejected
+        assert!(serde_json::from_value::<BlockConfirmations>(
+            json!({ "confirmations": 6, "blockNumber": "0x5" })
+        )
+        .is_err());
+        assert!(
+            serde_json::from_value::<ExtendedBlockId>(json!({ "confirmations": "six" })).is_err()
+        );
+        assert!(serde_json::from_value::<ExtendedBlockId>(json!({ "confirmations": -1 })).is_err());
+    }
+
+    #[test]
+    fn resolve_clamped_at_genesis() {
+        let confirmations = BlockConfirmations { confirmations: 6 };
+        assert_eq!(confirmations.resolve(100), 94);
+        assert_eq!(confirmations.resolve(6), 0);
+        assert_eq!(confirmations.resolve(3), 0);
+        assert_eq!(ExtendedBlockId::from(confirmations).resolve(10), BlockId::number(4));
+        assert_eq!(ExtendedBlockId::from(BlockId::latest()).resolve(10), BlockId::latest());
+    }
+}
@@ -3,7 +3,8 @@
 use std::time::Duration;
 
 use crate::{
-    EthStateCacheConfig, FeeHistoryCacheConfig, GasPriceOracleConfig, RPC_DEFAULT_GAS_CAP,
+    EthStateCacheConfig, FeeHistoryCacheConfig, GasPriceOracleConfig, HistoryCutoff,
+    RPC_DEFAULT_GAS_CAP,
 };
 use reth_rpc_server_types::constants::{
     default_max_tracing_requests, DEFAULT_ETH_PROOF_WINDOW, DEFAULT_MAX_BLOCKS_PER_FILTER,
@@ -42,6 +43,13 @@ pub struct EthConfig {
     pub fee_history_cache: FeeHistoryCacheConfig,
     /// The maximum number of getproof calls that can be executed concurrently.
     pub proof_permits: usize,
+    /// Oldest block for which historical state, traces and logs are served.
+    pub history_cutoff: HistoryCutoff,
+    /// Maximum number of blocks the node may be behind its sync target, for transactions
+    /// submitted via RPC to be accepted.
+    ///
+    /// If `None`, transactions are accepted regardless of the sync state.
+    pub max_tx_sync_distance: Option<u64>,
 }
 
 impl EthConfig {
@@ -68,6 +76,8 @@ impl Default for EthConfig {
             stale_filter_ttl: DEFAULT_STALE_FILTER_TTL,
             fee_history_cache: FeeHistoryCacheConfig::default(),
             proof_permits: DEFAULT_PROOF_PERMITS,
+            history_cutoff: HistoryCutoff::default(),
+            max_tx_sync_distance: None,
         }
     }
 }
@@ -126,6 +136,19 @@ impl EthConfig {
         self.proof_permits = permits;
         self
     }
+
+    /// Configures the oldest block for which historical state, traces and logs are served.
+    pub const fn history_cutoff(mut self, cutoff: HistoryCutoff) -> Self {
+        self.history_cutoff = cutoff;
+        self
+    }
+
+    /// Configures the maximum number of blocks the node may be behind its sync target, for
+    /// transactions submitted via RPC to be accepted.
+    pub const fn max_tx_sync_distance(mut self, max_distance: Option<u64>) -> Self {
+        self.max_tx_sync_distance = max_distance;
+        self
+    }
 }
 
 /// Config for the filter
@@ -5,10 +5,15 @@ use reth_chainspec::ChainSpecProvider;
 use reth_primitives::NodePrimitives;
 use reth_storage_api::{BlockReader, BlockReaderIdExt};
 use reth_tasks::TaskSpawner;
+use reth_transaction_pool::TransactionPool;
+use tokio_stream::wrappers::ReceiverStream;
 
 use crate::{
-    fee_history::fee_history_cache_new_blocks_task, EthConfig, EthStateCache, FeeHistoryCache,
-    GasPriceOracle,
+    cache::{latest_state::latest_state_cache_task, tx_json::tx_json_cache_task},
+    fee_history::fee_history_cache_new_blocks_task,
+    pending_block::pending_block_generation_task,
+    EthConfig, EthStateCache, FeeHistoryCache, GasPriceOracle, LatestStateCache,
+    PendingBlockGeneration, TransactionJsonCache,
 };
 
 /// Context for building the `eth` namespace API.
@@ -65,6 +70,83 @@ where
         fee_history_cache
     }
 
+    /// Returns a new [`LatestStateCache`] for the context that is kept up to date with the
+    /// canonical chain, or `None` if it is disabled.
+    pub fn new_latest_state_cache(&self) -> Option<LatestStateCache>
+    where
+        Tasks: TaskSpawner,
+        Events: CanonStateSubscriptions,
+    {
+        let max_accounts = self.config.cache.max_accounts;
+        let max_storage_slots = self.config.cache.max_storage_slots;
+        if max_accounts == 0 && max_storage_slots == 0 {
+            return None
+        }
+
+        let head = self.provider.chain_info().ok().map(|info| info.best_hash);
+        let latest_state_cache = LatestStateCache::new(max_accounts, max_storage_slots, head);
+
+        let new_canonical_blocks = self.events.canonical_state_stream();
+        let cache = latest_state_cache.clone();
+        self.executor.spawn_critical(
+            "invalidate latest state cache task",
+            Box::pin(async move {
+                latest_state_cache_task(cache, new_canonical_blocks).await;
+            }),
+        );
+
+        Some(latest_state_cache)
+    }
+
+    /// Returns a new [`TransactionJsonCache`] for the context that drops the transactions of
+    /// reorged out blocks, or `None` if it is disabled.
+    pub fn new_tx_json_cache(&self) -> Option<TransactionJsonCache>
+    where
+        Tasks: TaskSpawner,
+        Events: CanonStateSubscriptions,
+    {
+        let max_tx_blocks = self.config.cache.max_tx_blocks;
+        if max_tx_blocks == 0 {
+            return None
+        }
+
+        let tx_json_cache = TransactionJsonCache::new(max_tx_blocks);
+
+        let new_canonical_blocks = self.events.canonical_state_stream();
+        let cache = tx_json_cache.clone();
+        self.executor.spawn_critical(
+            "invalidate transaction json cache task",
+            Box::pin(async move {
+                tx_json_cache_task(cache, new_canonical_blocks).await;
+            }),
+        );
+
+        Some(tx_json_cache)
+    }
+
+    /// Returns a new [`PendingBlockGeneration`] for the context that is bumped on every new
+    /// pending pool transaction and canonical chain event.
+    pub fn new_pending_block_generation(&self) -> PendingBlockGeneration
+    where
+        Tasks: TaskSpawner,
+        Events: CanonStateSubscriptions,
+        Pool: TransactionPool,
+    {
+        let generation = PendingBlockGeneration::default();
+
+        let pool_events = ReceiverStream::new(self.pool.pending_transactions_listener());
+        let canon_events = self.events.canonical_state_stream();
+        let task_generation = generation.clone();
+        self.executor.spawn_critical(
+            "track pending block generation task",
+            Box::pin(async move {
+                pending_block_generation_task(task_generation, pool_events, canon_events).await;
+            }),
+        );
+
+        generation
+    }
+
     /// Returns a new [`GasPriceOracle`] for the context.
     pub fn new_gas_price_oracle(&self) -> GasPriceOracle<Provider> {
         GasPriceOracle::new(self.provider.clone(), self.config.gas_oracle, self.cache.clone())
@@ -3,8 +3,9 @@
 use serde::{Deserialize, Serialize};
 
 use reth_rpc_server_types::constants::cache::{
-    DEFAULT_BLOCK_CACHE_MAX_LEN, DEFAULT_CONCURRENT_DB_REQUESTS, DEFAULT_HEADER_CACHE_MAX_LEN,
-    DEFAULT_RECEIPT_CACHE_MAX_LEN,
+    DEFAULT_ACCOUNT_CACHE_MAX_LEN, DEFAULT_BLOCK_CACHE_MAX_LEN, DEFAULT_CONCURRENT_DB_REQUESTS,
+    DEFAULT_HEADER_CACHE_MAX_LEN, DEFAULT_RECEIPT_CACHE_MAX_LEN, DEFAULT_STORAGE_CACHE_MAX_LEN,
+    DEFAULT_TX_JSON_CACHE_MAX_BLOCKS,
 };
 
 /// Settings for the [`EthStateCache`](super::EthStateCache).
@@ -27,6 +28,21 @@ pub struct EthStateCacheConfig {
     ///
     /// Default is 512.
     pub max_concurrent_db_requests: usize,
+    /// Max number of accounts of the latest state in the
+    /// [`LatestStateCache`](super::latest_state::LatestStateCache).
+    ///
+    /// Default is 1024.
+    pub max_accounts: u32,
+    /// Max number of storage slots of the latest state in the
+    /// [`LatestStateCache`](super::latest_state::LatestStateCache).
+    ///
+    /// Default is 8192.
+    pub max_storage_slots: u32,
+    /// Max number of blocks whose serialized transactions are kept in the
+    /// [`TransactionJsonCache`](super::tx_json::TransactionJsonCache). 0 disables the cache.
+    ///
+    /// Default is 64.
+    pub max_tx_blocks: u32,
 }
 
 impl Default for EthStateCacheConfig {
@@ -36,6 +52,9 @@ impl Default for EthStateCacheConfig {
             max_receipts: DEFAULT_RECEIPT_CACHE_MAX_LEN,
             max_headers: DEFAULT_HEADER_CACHE_MAX_LEN,
             max_concurrent_db_requests: DEFAULT_CONCURRENT_DB_REQUESTS,
+            max_accounts: DEFAULT_ACCOUNT_CACHE_MAX_LEN,
+            max_storage_slots: DEFAULT_STORAGE_CACHE_MAX_LEN,
+            max_tx_blocks: DEFAULT_TX_JSON_CACHE_MAX_BLOCKS,
         }
     }
 }
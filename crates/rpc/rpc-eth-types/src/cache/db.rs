@@ -73,6 +73,16 @@ impl reth_storage_api::StorageRootProvider for StateProviderTraitObjWrapper<'_>
     ) -> ProviderResult<reth_trie::StorageMultiProof> {
         self.0.storage_multiproof(address, slots, hashed_storage)
     }
+
+    fn storage_range(
+        &self,
+        address: Address,
+        range: std::ops::RangeInclusive<B256>,
+        limit: usize,
+        hashed_storage: HashedStorage,
+    ) -> ProviderResult<Vec<reth_primitives::StorageEntry>> {
+        self.0.storage_range(address, range, limit, hashed_storage)
+    }
 }
 
 impl reth_storage_api::StateProofProvider for StateProviderTraitObjWrapper<'_> {
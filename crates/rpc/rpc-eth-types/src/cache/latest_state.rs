@@ -0,0 +1,468 @@
+//! Cache for the accounts and storage slots of the latest state.
+//!
+//! Serves repeated reads of hot accounts, e.g. `eth_getBalance` of a sequencer, without hitting
+//! the database. Entries are invalidated precisely with the state changes of every canonical state
+//! notification, including the reverted chain of a reorg.
+
+use super::metrics::LatestStateCacheMetrics;
+use alloy_primitives::{
+    map::B256HashMap, Address, BlockNumber, Bytes, StorageKey, StorageValue, B256,
+};
+use futures::{Stream, StreamExt};
+use parking_lot::{Mutex, RwLock};
+use reth_chain_state::CanonStateNotification;
+use reth_errors::ProviderResult;
+use reth_execution_types::Chain;
+use reth_primitives::{Account, Bytecode, NodePrimitives, StorageEntry};
+use reth_revm::db::BundleState;
+use reth_storage_api::{
+    AccountReader, BlockHashReader, HashedPostStateProvider, StateProofProvider, StateProvider,
+    StateProviderBox, StateRootProvider, StorageRootProvider,
+};
+use reth_trie::{
+    updates::TrieUpdates, AccountProof, HashedPostState, HashedStorage, MultiProof,
+    MultiProofTargets, StorageMultiProof, StorageProof, TrieInput,
+};
+use schnellru::{ByLength, LruMap};
+use std::{fmt, ops::RangeInclusive, sync::Arc};
+
+/// Number of shards of the [`LatestStateCache`], each with its own lock.
+const SHARDS: usize = 16;
+
+/// Sharded LRU cache for the accounts and storage slots of the latest state.
+///
+/// The cache is valid for a single head block. Reads through a [`LatestStateCacheProvider`] that
+/// was created for another block bypass the cache, so it never serves state of a block other than
+/// the one it was created for.
+#[derive(Clone)]
+pub struct LatestStateCache {
+    inner: Arc<LatestStateCacheInner>,
+}
+
+struct LatestStateCacheInner {
+    /// The head block the cached entries belong to, `None` if unknown.
+    ///
+    /// Must be locked before any shard.
+    head: RwLock<Option<B256>>,
+    shards: Box<[Mutex<Shard>]>,
+    metrics: LatestStateCacheMetrics,
+}
+
+/// A shard of the cache. All storage slots of an account are in the same shard as the account.
+struct Shard {
+    accounts: LruMap<Address, Option<Account>, ByLength>,
+    storage: LruMap<(Address, StorageKey), Option<StorageValue>, ByLength>,
+}
+
+impl LatestStateCache {
+    /// Creates a new cache that holds up to the given number of accounts and storage slots for the
+    /// given head block.
+    pub fn new(max_accounts: u32, max_storage_slots: u32, head: Option<B256>) -> Self {
+        let shard_len = |max: u32| ByLength::new(max.div_ceil(SHARDS as u32));
+        let shards = (0..SHARDS)
+            .map(|_| {
+                Mutex::new(Shard {
+                    accounts: LruMap::new(shard_len(max_accounts)),
+                    storage: LruMap::new(shard_len(max_storage_slots)),
+                })
+            })
+            .collect();
+        Self {
+            inner: Arc::new(LatestStateCacheInner {
+                head: RwLock::new(head),
+                shards,
+                metrics: LatestStateCacheMetrics::default(),
+            }),
+        }
+    }
+
+    /// Returns the head block the cached entries belong to.
+    pub fn head(&self) -> Option<B256> {
+        *self.inner.head.read()
+    }
+
+    /// Wraps the given latest state of the given block, serving its account and storage reads from
+    /// the cache.
+    pub fn provider(&self, state: StateProviderBox, block_hash: B256) -> LatestStateCacheProvider {
+        LatestStateCacheProvider { state, cache: self.clone(), block_hash }
+    }
+
+    fn shard(&self, address: &Address) -> &Mutex<Shard> {
+        &self.inner.shards[usize::from(address[19]) % SHARDS]
+    }
+
+    /// Returns the cached account of the given block, reading and caching it on a miss.
+    fn account(
+        &self,
+        block_hash: B256,
+        address: Address,
+        read: impl FnOnce() -> ProviderResult<Option<Account>>,
+    ) -> ProviderResult<Option<Account>> {
+        // held until the read account is cached, so the head can't change in between
+        let head = self.inner.head.read();
+        if *head != Some(block_hash) {
+            return read()
+        }
+
+        let shard = self.shard(&address);
+        if let Some(account) = shard.lock().accounts.get(&address) {
+            self.inner.metrics.account_hits_total.increment(1);
+            return Ok(*account)
+        }
+        self.inner.metrics.account_misses_total.increment(1);
+
+        let account = read()?;
+        shard.lock().accounts.insert(address, account);
+        Ok(account)
+    }
+
+    /// Returns the cached storage slot of the given block, reading and caching it on a miss.
+    fn storage(
+        &self,
+        block_hash: B256,
+        address: Address,
+        slot: StorageKey,
+        read: impl FnOnce() -> ProviderResult<Option<StorageValue>>,
+    ) -> ProviderResult<Option<StorageValue>> {
+        // held until the read slot is cached, so the head can't change in between
+        let head = self.inner.head.read();
+        if *head != Some(block_hash) {
+            return read()
+        }
+
+        let shard = self.shard(&address);
+        if let Some(value) = shard.lock().storage.get(&(address, slot)) {
+            self.inner.metrics.storage_hits_total.increment(1);
+            return Ok(*value)
+        }
+        self.inner.metrics.storage_misses_total.increment(1);
+
+        let value = read()?;
+        shard.lock().storage.insert((address, slot), value);
+        Ok(value)
+    }
+
+    /// Invalidates all entries changed by the notification and moves the cache to the new tip.
+    ///
+    /// The state changes of both the committed and the reverted chain are invalidated. If the
+    /// notification doesn't follow the current head, e.g. because notifications were missed, the
+    /// entire cache is cleared.
+    pub fn on_canon_state_notification<N: NodePrimitives>(
+        &self,
+        notification: &CanonStateNotification<N>,
+    ) {
+        let committed = notification.committed();
+        let reverted = notification.reverted();
+
+        let mut head = self.inner.head.write();
+        let follows_head = match &reverted {
+            Some(reverted) => *head == Some(reverted.tip().hash()),
+            None => *head == Some(committed.fork_block().hash),
+        };
+
+        if follows_head {
+            for chain in reverted.iter().chain(std::iter::once(&committed)) {
+                self.invalidate::<N>(chain);
+            }
+        } else {
+            self.clear();
+        }
+
+        *head = Some(committed.tip().hash());
+    }
+
+    /// Removes all accounts and storage slots changed in the chain.
+    fn invalidate<N: NodePrimitives>(&self, chain: &Chain<N>) {
+        let bundle: &BundleState = chain.execution_outcome().state();
+        for (address, account) in bundle.state() {
+            let mut shard = self.shard(address).lock();
+            shard.accounts.remove(address);
+            if account.was_destroyed() {
+                // the slots of the account can't be enumerated, but they're all in this shard
+                shard.storage.clear();
+            } else {
+                for slot in account.storage.keys() {
+                    shard.storage.remove(&(*address, B256::from(*slot)));
+                }
+            }
+        }
+        self.inner.metrics.invalidated_accounts_total.increment(bundle.state().len() as u64);
+    }
+
+    /// Removes all entries.
+    fn clear(&self) {
+        for shard in &self.inner.shards {
+            let mut shard = shard.lock();
+            shard.accounts.clear();
+            shard.storage.clear();
+        }
+        self.inner.metrics.clears_total.increment(1);
+    }
+}
+
+impl fmt::Debug for LatestStateCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LatestStateCache").field("head", &self.head()).finish_non_exhaustive()
+    }
+}
+
+/// Keeps the [`LatestStateCache`] up to date with the canonical chain.
+pub async fn latest_state_cache_task<St, N>(cache: LatestStateCache, mut events: St)
+where
+    St: Stream<Item = CanonStateNotification<N>> + Unpin + 'static,
+    N: NodePrimitives,
+{
+    while let Some(event) = events.next().await {
+        cache.on_canon_state_notification(&event);
+    }
+}
+
+/// A latest [`StateProvider`] that serves account and storage reads from the
+/// [`LatestStateCache`].
+#[expect(missing_debug_implementations)]
+pub struct LatestStateCacheProvider {
+    state: StateProviderBox,
+    cache: LatestStateCache,
+    /// The block of the latest state.
+    block_hash: B256,
+}
+
+impl AccountReader for LatestStateCacheProvider {
+    fn basic_account(&self, address: &Address) -> ProviderResult<Option<Account>> {
+        self.cache.account(self.block_hash, *address, || self.state.basic_account(address))
+    }
+}
+
+impl BlockHashReader for LatestStateCacheProvider {
+    fn block_hash(&self, number: BlockNumber) -> ProviderResult<Option<B256>> {
+        self.state.block_hash(number)
+    }
+
+    fn canonical_hashes_range(
+        &self,
+        start: BlockNumber,
+        end: BlockNumber,
+    ) -> ProviderResult<Vec<B256>> {
+        self.state.canonical_hashes_range(start, end)
+    }
+}
+
+impl StateRootProvider for LatestStateCacheProvider {
+    fn state_root(&self, hashed_state: HashedPostState) -> ProviderResult<B256> {
+        self.state.state_root(hashed_state)
+    }
+
+    fn state_root_from_nodes(&self, input: TrieInput) -> ProviderResult<B256> {
+        self.state.state_root_from_nodes(input)
+    }
+
+    fn state_root_with_updates(
+        &self,
+        hashed_state: HashedPostState,
+    ) -> ProviderResult<(B256, TrieUpdates)> {
+        self.state.state_root_with_updates(hashed_state)
+    }
+
+    fn state_root_from_nodes_with_updates(
+        &self,
+        input: TrieInput,
+    ) -> ProviderResult<(B256, TrieUpdates)> {
+        self.state.state_root_from_nodes_with_updates(input)
+    }
+}
+
+impl StorageRootProvider for LatestStateCacheProvider {
+    fn storage_root(
+        &self,
+        address: Address,
+        hashed_storage: HashedStorage,
+    ) -> ProviderResult<B256> {
+        self.state.storage_root(address, hashed_storage)
+    }
+
+    fn storage_proof(
+        &self,
+        address: Address,
+        slot: B256,
+        hashed_storage: HashedStorage,
+    ) -> ProviderResult<StorageProof> {
+        self.state.storage_proof(address, slot, hashed_storage)
+    }
+
+    fn storage_multiproof(
+        &self,
+        address: Address,
+        slots: &[B256],
+        hashed_storage: HashedStorage,
+    ) -> ProviderResult<StorageMultiProof> {
+        self.state.storage_multiproof(address, slots, hashed_storage)
+    }
+
+    fn storage_range(
+        &self,
+        address: Address,
+        range: RangeInclusive<B256>,
+        limit: usize,
+        hashed_storage: HashedStorage,
+    ) -> ProviderResult<Vec<StorageEntry>> {
+        self.state.storage_range(address, range, limit, hashed_storage)
+    }
+}
+
+impl StateProofProvider for LatestStateCacheProvider {
+    fn proof(
+        &self,
+        input: TrieInput,
+        address: Address,
+        slots: &[B256],
+    ) -> ProviderResult<AccountProof> {
+        self.state.proof(input, address, slots)
+    }
+
+    fn multiproof(
+        &self,
+        input: TrieInput,
+        targets: MultiProofTargets,
+    ) -> ProviderResult<MultiProof> {
+        self.state.multiproof(input, targets)
+    }
+
+    fn witness(
+        &self,
+        input: TrieInput,
+        target: HashedPostState,
+    ) -> ProviderResult<B256HashMap<Bytes>> {
+        self.state.witness(input, target)
+    }
+}
+
+impl HashedPostStateProvider for LatestStateCacheProvider {
+    fn hashed_post_state(&self, bundle_state: &BundleState) -> HashedPostState {
+        self.state.hashed_post_state(bundle_state)
+    }
+}
+
+impl StateProvider for LatestStateCacheProvider {
+    fn storage(
+        &self,
+        account: Address,
+        storage_key: StorageKey,
+    ) -> ProviderResult<Option<StorageValue>> {
+        self.cache.storage(self.block_hash, account, storage_key, || {
+            self.state.storage(account, storage_key)
+        })
+    }
+
+    fn bytecode_by_hash(&self, code_hash: &B256) -> ProviderResult<Option<Bytecode>> {
+        self.state.bytecode_by_hash(code_hash)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::U256;
+    use reth_chain_state::test_utils::TestBlockBuilder;
+    use reth_execution_types::ExecutionOutcome;
+    use reth_primitives::{EthPrimitives, SealedBlockWithSenders};
+    use reth_provider::test_utils::{ExtendedAccount, MockEthProvider};
+    use reth_revm::db::{AccountStatus, BundleAccount};
+    use revm_primitives::{AccountInfo, HashMap};
+
+    /// Returns a chain of a single block on top of the given parent that changes the balance of
+    /// the given accounts.
+    fn chain(
+        builder: &mut TestBlockBuilder,
+        number: BlockNumber,
+        parent: B256,
+        changed: &[(Address, u64)],
+    ) -> (Arc<Chain<EthPrimitives>>, SealedBlockWithSenders) {
+        let block = builder.generate_random_block(number, parent);
+        let mut bundle = BundleState::default();
+        for (address, balance) in changed {
+            bundle.state.insert(
+                *address,
+                BundleAccount::new(
+                    None,
+                    Some(AccountInfo { balance: U256::from(*balance), ..Default::default() }),
+                    HashMap::default(),
+                    AccountStatus::Changed,
+                ),
+            );
+        }
+        let outcome = ExecutionOutcome::new(bundle, Default::default(), number, Vec::new());
+        (Arc::new(Chain::new([block.clone()], outcome, None)), block)
+    }
+
+    fn balance(cache: &LatestStateCache, state: &MockEthProvider, address: Address) -> U256 {
+        let head = cache.head().unwrap();
+        cache.provider(Box::new(state.clone()), head).account_balance(&address).unwrap().unwrap()
+    }
+
+    #[test]
+    fn reorg_invalidates_cached_accounts() {
+        let mut builder = TestBlockBuilder::eth();
+        let state = MockEthProvider::default();
+        let hot = Address::random();
+        let other = Address::random();
+        state.add_account(hot, ExtendedAccount::new(0, U256::from(1)));
+        state.add_account(other, ExtendedAccount::new(0, U256::from(1)));
+
+        let genesis = B256::random();
+        let cache = LatestStateCache::new(16, 16, Some(genesis));
+
+        // block 1 changes the hot account
+        let (chain1, block1) = chain(&mut builder, 1, genesis, &[(hot, 2)]);
+        state.add_account(hot, ExtendedAccount::new(0, U256::from(2)));
+        cache.on_canon_state_notification(&CanonStateNotification::Commit { new: chain1.clone() });
+        assert_eq!(balance(&cache, &state, hot), U256::from(2));
+        assert_eq!(balance(&cache, &state, other), U256::from(1));
+
+        // reads are served from the cache
+        state.add_account(other, ExtendedAccount::new(0, U256::from(100)));
+        assert_eq!(balance(&cache, &state, other), U256::from(1));
+        state.add_account(other, ExtendedAccount::new(0, U256::from(1)));
+
+        // block 1 is reorged out by a block that doesn't touch the hot account, so only the
+        // reverted chain changed it
+        let (chain1b, _) = chain(&mut builder, 1, genesis, &[]);
+        state.add_account(hot, ExtendedAccount::new(0, U256::from(1)));
+        cache.on_canon_state_notification(&CanonStateNotification::Reorg {
+            old: chain1,
+            new: chain1b.clone(),
+        });
+        assert_eq!(balance(&cache, &state, hot), U256::from(1));
+        assert_eq!(balance(&cache, &state, other), U256::from(1));
+
+        // the new block 2 changes the other account
+        let (chain2, _) = chain(&mut builder, 2, chain1b.tip().hash(), &[(other, 3)]);
+        state.add_account(other, ExtendedAccount::new(0, U256::from(3)));
+        cache.on_canon_state_notification(&CanonStateNotification::Commit { new: chain2 });
+        assert_eq!(balance(&cache, &state, other), U256::from(3));
+
+        // providers of a previous head bypass the cache
+        state.add_account(other, ExtendedAccount::new(0, U256::from(4)));
+        let previous = cache.provider(Box::new(state.clone()), block1.hash());
+        assert_eq!(previous.account_balance(&other).unwrap(), Some(U256::from(4)));
+        assert_eq!(balance(&cache, &state, other), U256::from(3));
+    }
+
+    #[test]
+    fn missed_notification_clears_cache() {
+        let mut builder = TestBlockBuilder::eth();
+        let state = MockEthProvider::default();
+        let address = Address::random();
+        state.add_account(address, ExtendedAccount::new(0, U256::from(1)));
+
+        let genesis = B256::random();
+        let cache = LatestStateCache::new(16, 16, Some(genesis));
+        assert_eq!(balance(&cache, &state, address), U256::from(1));
+
+        // the notification of block 1 that changed the account is missed
+        let (chain1, _) = chain(&mut builder, 1, genesis, &[(address, 2)]);
+        let (chain2, _) = chain(&mut builder, 2, chain1.tip().hash(), &[]);
+        state.add_account(address, ExtendedAccount::new(0, U256::from(2)));
+        cache.on_canon_state_notification(&CanonStateNotification::Commit { new: chain2 });
+        assert_eq!(balance(&cache, &state, address), U256::from(2));
+    }
+}
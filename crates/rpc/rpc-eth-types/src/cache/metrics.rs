@@ -15,3 +15,31 @@ pub(crate) struct CacheMetrics {
     /// The number of cache misses.
     pub(crate) misses_total: Counter,
 }
+
+#[derive(Metrics)]
+#[metrics(scope = "rpc.latest_state_cache")]
+pub(crate) struct LatestStateCacheMetrics {
+    /// The number of account cache hits.
+    pub(crate) account_hits_total: Counter,
+    /// The number of account cache misses.
+    pub(crate) account_misses_total: Counter,
+    /// The number of storage slot cache hits.
+    pub(crate) storage_hits_total: Counter,
+    /// The number of storage slot cache misses.
+    pub(crate) storage_misses_total: Counter,
+    /// The number of accounts invalidated by canonical state changes.
+    pub(crate) invalidated_accounts_total: Counter,
+    /// The number of times the cache was cleared, because it couldn't be invalidated precisely.
+    pub(crate) clears_total: Counter,
+}
+
+#[derive(Metrics)]
+#[metrics(scope = "rpc.tx_json_cache")]
+pub(crate) struct TransactionJsonCacheMetrics {
+    /// The number of serialized transaction cache hits.
+    pub(crate) hits_total: Counter,
+    /// The number of serialized transaction cache misses.
+    pub(crate) misses_total: Counter,
+    /// The number of blocks removed, because they were reorged out.
+    pub(crate) invalidated_total: Counter,
+}
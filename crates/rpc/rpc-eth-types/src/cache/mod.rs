@@ -26,8 +26,10 @@ use tokio_stream::wrappers::UnboundedReceiverStream;
 
 pub mod config;
 pub mod db;
+pub mod latest_state;
 pub mod metrics;
 pub mod multi_consumer;
+pub mod tx_json;
 
 /// The type that can send the response to a requested [`SealedBlockWithSenders`]
 type BlockTransactionsResponseSender<T> = oneshot::Sender<ProviderResult<Option<Vec<T>>>>;
@@ -178,6 +180,7 @@ impl<B: Block, R: Send + Sync> EthStateCache<B, R> {
             max_receipts,
             max_headers,
             max_concurrent_db_requests,
+            ..
         } = config;
         let (this, service) = Self::create(
             provider,
@@ -0,0 +1,354 @@
+//! Cache for the serialized RPC objects of mined transactions.
+//!
+//! Most of the time spent serving full blocks goes into converting every transaction into its RPC
+//! object and serializing it. The serialized objects are cached by block, because they include
+//! block dependent fields, and are shared by the block and transaction endpoints, so full block
+//! responses are assembled from the cached fragments.
+
+use super::metrics::TransactionJsonCacheMetrics;
+use crate::{error::FromEthApiError, EthApiError, TransactionSource};
+use alloy_consensus::BlockHeader;
+use alloy_primitives::{map::B256HashMap, TxHash, B256};
+use alloy_rpc_types_eth::{
+    Block, BlockTransactions, BlockTransactionsKind, Header, TransactionInfo,
+};
+use futures::{Stream, StreamExt};
+use parking_lot::Mutex;
+use reth_chain_state::CanonStateNotification;
+use reth_errors::RethError;
+use reth_primitives::{
+    transaction::SignedTransactionIntoRecoveredExt, NodePrimitives, SealedBlockWithSenders,
+};
+use reth_primitives_traits::{Block as BlockTrait, BlockBody, SignedTransaction};
+use reth_rpc_types_compat::{
+    block::from_block_with_tx_hashes, transaction::from_recovered_with_block_context,
+    TransactionCompat,
+};
+use schnellru::{ByLength, LruMap};
+use serde::{de::DeserializeOwned, Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::value::{to_raw_value, RawValue};
+use std::{fmt, sync::Arc};
+
+/// An RPC object, which may already be serialized.
+///
+/// Both variants serialize to the same JSON, so responses can be assembled from cached fragments
+/// without changing their output. Deserializing always yields an [`Object`](Self::Object).
+#[derive(Clone, Debug)]
+pub enum MaybeSerialized<T> {
+    /// The RPC object.
+    Object(T),
+    /// The serialized RPC object.
+    Serialized(Box<RawValue>),
+}
+
+impl<T: DeserializeOwned> MaybeSerialized<T> {
+    /// Returns the RPC object, deserializing it if it's already serialized.
+    pub fn into_object(self) -> Result<T, serde_json::Error> {
+        match self {
+            Self::Object(object) => Ok(object),
+            Self::Serialized(json) => serde_json::from_str(json.get()),
+        }
+    }
+}
+
+impl<T> From<T> for MaybeSerialized<T> {
+    fn from(object: T) -> Self {
+        Self::Object(object)
+    }
+}
+
+impl<T: Serialize> Serialize for MaybeSerialized<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Self::Object(object) => object.serialize(serializer),
+            Self::Serialized(json) => json.serialize(serializer),
+        }
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for MaybeSerialized<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        T::deserialize(deserializer).map(Self::Object)
+    }
+}
+
+/// LRU cache for the serialized RPC objects of mined transactions, grouped by the block they're
+/// included in.
+///
+/// Since the objects are cached per block, a cached object never goes stale. Blocks that are
+/// reorged out are removed anyway, because they're unlikely to be requested again.
+#[derive(Clone)]
+pub struct TransactionJsonCache {
+    inner: Arc<TransactionJsonCacheInner>,
+}
+
+struct TransactionJsonCacheInner {
+    /// The serialized transactions of a block, keyed by the block hash and then by transaction
+    /// hash.
+    blocks: Mutex<LruMap<B256, B256HashMap<Box<RawValue>>, ByLength>>,
+    metrics: TransactionJsonCacheMetrics,
+}
+
+impl TransactionJsonCache {
+    /// Creates a new cache that holds the serialized transactions of up to `max_blocks` blocks.
+    pub fn new(max_blocks: u32) -> Self {
+        Self {
+            inner: Arc::new(TransactionJsonCacheInner {
+                blocks: Mutex::new(LruMap::new(ByLength::new(max_blocks))),
+                metrics: TransactionJsonCacheMetrics::default(),
+            }),
+        }
+    }
+
+    /// Returns the number of blocks with cached transactions.
+    pub fn len(&self) -> usize {
+        self.inner.blocks.lock().len()
+    }
+
+    /// Returns `true` if no transactions are cached.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the serialized transaction of the given block, serializing and caching it on a
+    /// miss.
+    ///
+    /// The lock isn't held while serializing, so concurrent misses may serialize the same
+    /// transaction more than once.
+    pub fn get_or_insert_with<E>(
+        &self,
+        tx_hash: TxHash,
+        block_hash: B256,
+        serialize: impl FnOnce() -> Result<Box<RawValue>, E>,
+    ) -> Result<Box<RawValue>, E> {
+        let cached =
+            self.inner.blocks.lock().get(&block_hash).and_then(|txs| txs.get(&tx_hash).cloned());
+        if let Some(json) = cached {
+            self.inner.metrics.hits_total.increment(1);
+            return Ok(json)
+        }
+        self.inner.metrics.misses_total.increment(1);
+
+        let json = serialize()?;
+        if let Some(txs) = self.inner.blocks.lock().get_or_insert(block_hash, Default::default) {
+            txs.insert(tx_hash, json.clone());
+        }
+        Ok(json)
+    }
+
+    /// Removes the serialized transactions of the given blocks.
+    pub fn remove_blocks(&self, block_hashes: impl IntoIterator<Item = B256>) {
+        let mut blocks = self.inner.blocks.lock();
+        for block_hash in block_hashes {
+            if blocks.remove(&block_hash).is_some() {
+                self.inner.metrics.invalidated_total.increment(1);
+            }
+        }
+    }
+
+    /// Removes the serialized transactions of the blocks that were reverted by the notification.
+    pub fn on_canon_state_notification<N: NodePrimitives>(
+        &self,
+        notification: &CanonStateNotification<N>,
+    ) {
+        if let Some(reverted) = notification.reverted() {
+            self.remove_blocks(reverted.blocks_iter().map(|block| block.hash()));
+        }
+    }
+}
+
+impl fmt::Debug for TransactionJsonCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TransactionJsonCache").field("blocks", &self.len()).finish_non_exhaustive()
+    }
+}
+
+/// Converts the given block into a [`Block`] response with the given [`BlockTransactionsKind`].
+///
+/// With a cache, transaction objects are served as serialized fragments, so only the transactions
+/// that aren't cached yet are converted and serialized. Serializing the response yields exactly
+/// the same JSON as serializing the response of
+/// [`from_block`](reth_rpc_types_compat::block::from_block).
+#[expect(clippy::type_complexity)]
+pub fn from_block_with_tx_json<T, B>(
+    block: &SealedBlockWithSenders<B>,
+    kind: BlockTransactionsKind,
+    tx_resp_builder: &T,
+    cache: Option<&TransactionJsonCache>,
+) -> Result<Block<MaybeSerialized<T::Transaction>, Header<B::Header>>, T::Error>
+where
+    T: TransactionCompat<<<B as BlockTrait>::Body as BlockBody>::Transaction>,
+    T::Error: FromEthApiError,
+    B: BlockTrait,
+{
+    let block_hash = block.hash();
+    let transactions = match kind {
+        BlockTransactionsKind::Hashes => None,
+        BlockTransactionsKind::Full => {
+            let block_number = block.header.number();
+            let base_fee = block.header.base_fee_per_gas();
+            let transactions = block
+                .body
+                .transactions()
+                .iter()
+                .zip(&block.senders)
+                .enumerate()
+                .map(|(idx, (tx, sender))| {
+                    let tx_hash = *tx.tx_hash();
+                    let convert = || {
+                        let tx_info = TransactionInfo {
+                            hash: Some(tx_hash),
+                            block_hash: Some(block_hash),
+                            block_number: Some(block_number),
+                            base_fee: base_fee.map(u128::from),
+                            index: Some(idx as u64),
+                        };
+                        from_recovered_with_block_context(
+                            tx.clone().with_signer(*sender),
+                            tx_info,
+                            tx_resp_builder,
+                        )
+                    };
+                    match cache {
+                        Some(cache) => cache
+                            .get_or_insert_with(tx_hash, block_hash, || -> Result<_, T::Error> {
+                                serialize_tx(&convert()?)
+                            })
+                            .map(MaybeSerialized::Serialized),
+                        None => convert().map(MaybeSerialized::Object),
+                    }
+                })
+                .collect::<Result<Vec<_>, T::Error>>()?;
+            Some(transactions)
+        }
+    };
+
+    let mut rpc_block = from_block_with_tx_hashes(block.clone().unseal(), Some(block_hash));
+    if let Some(transactions) = transactions {
+        rpc_block.transactions = BlockTransactions::Full(transactions);
+    }
+    Ok(rpc_block)
+}
+
+/// Returns the transaction object of the given [`TransactionSource`].
+///
+/// With a cache, mined transactions are served as serialized fragments from the cache, which is
+/// shared with [`from_block_with_tx_json`].
+pub fn tx_json_from_source<T, Tx>(
+    source: TransactionSource<Tx>,
+    tx_resp_builder: &T,
+    cache: Option<&TransactionJsonCache>,
+) -> Result<MaybeSerialized<T::Transaction>, T::Error>
+where
+    T: TransactionCompat<Tx>,
+    T::Error: FromEthApiError,
+    Tx: SignedTransaction,
+{
+    let mined = match &source {
+        TransactionSource::Block { transaction, block_hash, .. } => {
+            Some((transaction.trie_hash(), *block_hash))
+        }
+        TransactionSource::Pool(_) => None,
+    };
+    match (cache, mined) {
+        (Some(cache), Some((tx_hash, block_hash))) => cache
+            .get_or_insert_with(tx_hash, block_hash, || -> Result<_, T::Error> {
+                serialize_tx(&source.into_transaction(tx_resp_builder)?)
+            })
+            .map(MaybeSerialized::Serialized),
+        _ => source.into_transaction(tx_resp_builder).map(MaybeSerialized::Object),
+    }
+}
+
+/// Serializes the given transaction object.
+fn serialize_tx<Tx: Serialize, E: FromEthApiError>(tx: &Tx) -> Result<Box<RawValue>, E> {
+    to_raw_value(tx).map_err(|err| E::from_eth_err(EthApiError::Internal(RethError::other(err))))
+}
+
+/// Removes the serialized transactions of reorged out blocks from the [`TransactionJsonCache`].
+pub async fn tx_json_cache_task<St, N>(cache: TransactionJsonCache, mut events: St)
+where
+    St: Stream<Item = CanonStateNotification<N>> + Unpin + 'static,
+    N: NodePrimitives,
+{
+    while let Some(event) = events.next().await {
+        cache.on_canon_state_notification(&event);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_chain_state::test_utils::TestBlockBuilder;
+    use reth_execution_types::{Chain, ExecutionOutcome};
+    use std::convert::Infallible;
+
+    fn json(value: &str) -> Result<Box<RawValue>, Infallible> {
+        Ok(RawValue::from_string(value.to_string()).unwrap())
+    }
+
+    fn cached(
+        cache: &TransactionJsonCache,
+        tx_hash: TxHash,
+        block_hash: B256,
+        value: &str,
+    ) -> String {
+        cache.get_or_insert_with(tx_hash, block_hash, || json(value)).unwrap().get().to_string()
+    }
+
+    #[test]
+    fn serves_cached_transactions() {
+        let cache = TransactionJsonCache::new(2);
+        let (tx_hash, block_hash) = (B256::random(), B256::random());
+
+        assert_eq!(cached(&cache, tx_hash, block_hash, "1"), "1");
+        assert_eq!(cached(&cache, tx_hash, block_hash, "2"), "1");
+
+        // the same transaction in another block is cached separately
+        let other_block = B256::random();
+        assert_eq!(cached(&cache, tx_hash, other_block, "3"), "3");
+        assert_eq!(cache.len(), 2);
+
+        // another transaction of a cached block doesn't evict anything
+        cached(&cache, B256::random(), block_hash, "4");
+        assert_eq!(cache.len(), 2);
+
+        // the least recently used block is evicted
+        cached(&cache, tx_hash, B256::random(), "5");
+        assert_eq!(cached(&cache, tx_hash, other_block, "6"), "6");
+    }
+
+    #[test]
+    fn reorg_removes_reverted_blocks() {
+        let mut builder = TestBlockBuilder::eth();
+        let genesis = B256::random();
+        let block1 = builder.generate_random_block(1, genesis);
+        let block1b = builder.generate_random_block(1, genesis);
+
+        let cache = TransactionJsonCache::new(16);
+        let tx_hash = B256::random();
+        cached(&cache, tx_hash, block1.hash(), "1");
+        cached(&cache, tx_hash, block1b.hash(), "2");
+
+        let chain = |block| Arc::new(Chain::new([block], ExecutionOutcome::default(), None));
+        cache.on_canon_state_notification(&CanonStateNotification::Reorg {
+            old: chain(block1.clone()),
+            new: chain(block1b.clone()),
+        });
+        assert_eq!(cache.len(), 1);
+        assert_eq!(cached(&cache, tx_hash, block1b.hash(), "3"), "2");
+        assert_eq!(cached(&cache, tx_hash, block1.hash(), "4"), "4");
+    }
+
+    #[test]
+    fn maybe_serialized_serializes_the_same() {
+        let object = MaybeSerialized::Object(vec![1u64, 2]);
+        let serialized = MaybeSerialized::<Vec<u64>>::Serialized(json("[1,2]").unwrap());
+        assert_eq!(serde_json::to_string(&object).unwrap(), "[1,2]");
+        assert_eq!(serde_json::to_string(&serialized).unwrap(), "[1,2]");
+        assert_eq!(serialized.into_object().unwrap(), vec![1, 2]);
+
+        let deserialized: MaybeSerialized<Vec<u64>> = serde_json::from_str("[1,2]").unwrap();
+        assert!(matches!(deserialized, MaybeSerialized::Object(v) if v == [1, 2]));
+    }
+}
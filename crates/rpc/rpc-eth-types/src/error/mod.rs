@@ -6,7 +6,7 @@ pub use api::{AsEthApiError, FromEthApiError, FromEvmError, IntoEthApiError};
 use core::time::Duration;
 
 use alloy_eips::BlockId;
-use alloy_primitives::{Address, Bytes, U256};
+use alloy_primitives::{Address, BlockNumber, Bytes, U256};
 use alloy_rpc_types_eth::{error::EthRpcErrorCode, request::TransactionInputError, BlockError};
 use alloy_sol_types::decode_revert_reason;
 use reth_errors::RethError;
@@ -34,6 +34,12 @@ impl ToRpcError for jsonrpsee_types::ErrorObject<'static> {
     }
 }
 
+/// Error code returned when the history required to answer a request has been pruned.
+pub const PRUNED_HISTORY_UNAVAILABLE_CODE: i32 = 4444;
+
+/// Error code returned when a request is rejected because the node is at capacity.
+pub const LIMIT_EXCEEDED_CODE: i32 = -32005;
+
 /// Result alias
 pub type EthResult<T> = Result<T, EthApiError>;
 
@@ -61,6 +67,22 @@ pub enum EthApiError {
     /// Receipts not found for block hash/number/tag
     #[error("receipts not found")]
     ReceiptsNotFound(BlockId),
+    /// The history of the block has been pruned
+    #[error("pruned history unavailable for block {0}")]
+    PrunedHistoryUnavailable(BlockNumber),
+    /// The block is below the configured history cutoff, see
+    /// [`HistoryCutoff`](crate::HistoryCutoff)
+    #[error("historical data not available below block {0}")]
+    HistoryUnavailable(BlockNumber),
+    /// The node is syncing and too far behind its sync target to accept transactions, see
+    /// [`EthConfig::max_tx_sync_distance`](crate::EthConfig::max_tx_sync_distance).
+    ///
+    /// Contains how many blocks the node is behind, if its sync target is known.
+    #[error("node is syncing{}", .0.map(|distance| format!(", {distance} blocks behind")).unwrap_or_default())]
+    NodeSyncing(Option<u64>),
+    /// The index of transaction hashes is disabled, so transactions can't be looked up by hash
+    #[error("transaction index disabled")]
+    TransactionLookupDisabled,
     /// Thrown when an unknown block or transaction index is encountered
     #[error("unknown block or tx index")]
     UnknownBlockOrTxIndex,
@@ -119,6 +141,10 @@ pub enum EthApiError {
     /// Error thrown when a spawned blocking task failed to deliver an anticipated response
     #[error("internal eth error")]
     InternalEthError,
+    /// Error thrown when a compute heavy request is rejected because too many of them are
+    /// already waiting for a thread of the compute pool.
+    #[error("too many compute heavy requests, try again later")]
+    ComputePoolSaturated,
     /// Error thrown when a (tracing) call exceeds the configured timeout
     #[error("execution aborted (timeout = {0:?})")]
     ExecutionTimedOut(Duration),
@@ -208,6 +234,14 @@ impl From<EthApiError> for jsonrpsee_types::error::ErrorObject<'static> {
                     block_id_to_str(end_id),
                 ),
             ),
+            err @ (EthApiError::PrunedHistoryUnavailable(_) |
+            EthApiError::HistoryUnavailable(_) |
+            EthApiError::TransactionLookupDisabled) => {
+                rpc_error_with_code(PRUNED_HISTORY_UNAVAILABLE_CODE, err.to_string())
+            }
+            err @ EthApiError::NodeSyncing(_) => {
+                rpc_error_with_code(EthRpcErrorCode::TransactionRejected.code(), err.to_string())
+            }
             EthApiError::Unsupported(msg) => internal_rpc_err(msg),
             EthApiError::InternalJsTracerError(msg) => internal_rpc_err(msg),
             EthApiError::InvalidParams(msg) => invalid_params_rpc_err(msg),
@@ -218,6 +252,9 @@ impl From<EthApiError> for jsonrpsee_types::error::ErrorObject<'static> {
             err @ (EthApiError::InternalBlockingTaskError | EthApiError::InternalEthError) => {
                 internal_rpc_err(err.to_string())
             }
+            err @ EthApiError::ComputePoolSaturated => {
+                rpc_error_with_code(LIMIT_EXCEEDED_CODE, err.to_string())
+            }
             err @ EthApiError::TransactionInputError(_) => invalid_params_rpc_err(err.to_string()),
             EthApiError::Other(err) => err.to_rpc_error(),
             EthApiError::MuxTracerError(msg) => internal_rpc_err(msg.to_string()),
@@ -798,4 +835,31 @@ mod tests {
             EthApiError::HeaderNotFound(BlockId::finalized()).into();
         assert_eq!(err.message(), "block not found: finalized");
     }
+
+    #[test]
+    fn pruned_history_error() {
+        let err: jsonrpsee_types::error::ErrorObject<'static> =
+            EthApiError::PrunedHistoryUnavailable(100).into();
+        assert_eq!(err.code(), PRUNED_HISTORY_UNAVAILABLE_CODE);
+        assert_eq!(err.message(), "pruned history unavailable for block 100");
+    }
+
+    #[test]
+    fn node_syncing_error() {
+        let err: jsonrpsee_types::error::ErrorObject<'static> =
+            EthApiError::NodeSyncing(Some(100)).into();
+        assert_eq!(err.code(), EthRpcErrorCode::TransactionRejected.code());
+        assert_eq!(err.message(), "node is syncing, 100 blocks behind");
+
+        let err: jsonrpsee_types::error::ErrorObject<'static> =
+            EthApiError::NodeSyncing(None).into();
+        assert_eq!(err.message(), "node is syncing");
+    }
+
+    #[test]
+    fn compute_pool_saturated_error() {
+        let err: jsonrpsee_types::error::ErrorObject<'static> =
+            EthApiError::ComputePoolSaturated.into();
+        assert_eq!(err.code(), LIMIT_EXCEEDED_CODE);
+    }
 }
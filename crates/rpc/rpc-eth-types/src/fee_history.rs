@@ -17,7 +17,7 @@ use futures::{
 use metrics::atomics::AtomicU64;
 use reth_chain_state::CanonStateNotification;
 use reth_chainspec::{ChainSpecProvider, EthChainSpec};
-use reth_primitives::{NodePrimitives, SealedBlock};
+use reth_primitives::{fee_math, NodePrimitives, SealedBlock};
 use reth_primitives_traits::BlockBody;
 use reth_rpc_server_types::constants::gas_oracle::MAX_HEADER_HISTORY;
 use reth_storage_api::BlockReaderIdExt;
@@ -367,9 +367,7 @@ impl FeeHistoryEntry {
         Self {
             base_fee_per_gas: block.base_fee_per_gas().unwrap_or_default(),
             gas_used_ratio: block.gas_used() as f64 / block.gas_limit() as f64,
-            base_fee_per_blob_gas: block
-                .excess_blob_gas()
-                .map(alloy_eips::eip4844::calc_blob_gasprice),
+            base_fee_per_blob_gas: block.excess_blob_gas().map(fee_math::saturating_blob_fee),
             blob_gas_used_ratio: block.body.blob_gas_used() as f64 /
                 alloy_eips::eip4844::MAX_DATA_GAS_PER_BLOCK as f64,
             excess_blob_gas: block.excess_blob_gas(),
@@ -398,7 +396,7 @@ impl FeeHistoryEntry {
     ///
     /// See also [`Self::next_block_excess_blob_gas`]
     pub fn next_block_blob_fee(&self) -> Option<u128> {
-        self.next_block_excess_blob_gas().map(alloy_eips::eip4844::calc_blob_gasprice)
+        self.next_block_excess_blob_gas().map(fee_math::saturating_blob_fee)
     }
 
     /// Calculate excess blob gas for the next block according to the EIP-4844 spec.
@@ -7,6 +7,7 @@ use alloy_primitives::{B256, U256};
 use alloy_rpc_types_eth::BlockId;
 use derive_more::{Deref, DerefMut, From, Into};
 use itertools::Itertools;
+use reth_primitives::fee_math;
 use reth_primitives_traits::{BlockBody, SignedTransaction};
 use reth_rpc_server_types::{
     constants,
@@ -228,7 +229,11 @@ where
         // sort the functions by ascending effective tip first
         let sorted_transactions = block.body.transactions().iter().sorted_by_cached_key(|tx| {
             if let Some(base_fee) = base_fee_per_gas {
-                (*tx).effective_tip_per_gas(base_fee)
+                fee_math::effective_gas_tip(
+                    tx.max_fee_per_gas(),
+                    tx.max_priority_fee_per_gas(),
+                    base_fee,
+                )
             } else {
                 Some((*tx).priority_fee_or_price())
             }
@@ -238,7 +243,11 @@ where
 
         for tx in sorted_transactions {
             let effective_tip = if let Some(base_fee) = base_fee_per_gas {
-                tx.effective_tip_per_gas(base_fee)
+                fee_math::effective_gas_tip(
+                    tx.max_fee_per_gas(),
+                    tx.max_priority_fee_per_gas(),
+                    base_fee,
+                )
             } else {
                 Some(tx.priority_fee_or_price())
             };
@@ -0,0 +1,127 @@
+//! Cutoff below which historical state, traces and logs are not served.
+//!
+//! Protects nodes that prune selectively from serving incomplete historical data.
+
+use alloy_primitives::BlockNumber;
+use reth_errors::ProviderResult;
+use reth_prune_types::PruneSegment;
+use reth_storage_api::{BlockNumReader, PruneCheckpointReader};
+use serde::{Deserialize, Serialize};
+use std::{fmt, str::FromStr};
+
+/// Oldest block for which historical state, traces and logs are served.
+///
+/// Headers and bodies are served regardless of the cutoff, as long as they're stored.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum HistoryCutoff {
+    /// The oldest block with complete state and receipts, derived from the prune checkpoints.
+    #[default]
+    Auto,
+    /// A fixed block number.
+    Block(BlockNumber),
+    /// A number of blocks behind the latest block.
+    Distance(u64),
+}
+
+impl HistoryCutoff {
+    /// Returns the oldest block for which historical data is served, if the history is cut off.
+    pub fn oldest_block<Provider>(&self, provider: &Provider) -> ProviderResult<Option<BlockNumber>>
+    where
+        Provider: BlockNumReader + PruneCheckpointReader,
+    {
+        match *self {
+            Self::Auto => prune_horizon(provider),
+            Self::Block(block) => Ok(Some(block)),
+            Self::Distance(distance) => {
+                Ok(Some(provider.best_block_number()?.saturating_sub(distance)))
+            }
+        }
+    }
+}
+
+/// Returns the oldest block with both complete state and receipts according to the prune
+/// checkpoints, if anything was pruned.
+///
+/// History checkpoints point to the highest block whose changesets were pruned, the state at the
+/// end of that block is still available. Receipt checkpoints point to the highest block whose
+/// receipts, and so logs, were pruned, or partially pruned in case of contract logs.
+pub fn prune_horizon<Provider>(provider: &Provider) -> ProviderResult<Option<BlockNumber>>
+where
+    Provider: PruneCheckpointReader,
+{
+    let mut horizon = None;
+    for (segment, checkpoint) in provider.get_prune_checkpoints()? {
+        let Some(block_number) = checkpoint.block_number else { continue };
+        let oldest = match segment {
+            PruneSegment::AccountHistory | PruneSegment::StorageHistory => block_number,
+            PruneSegment::Receipts | PruneSegment::ContractLogs => block_number + 1,
+            _ => continue,
+        };
+        horizon = horizon.max(Some(oldest));
+    }
+    Ok(horizon)
+}
+
+impl FromStr for HistoryCutoff {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s == "auto" {
+            return Ok(Self::Auto)
+        }
+        if let Some(distance) = s.strip_prefix("distance:") {
+            return distance
+                .parse()
+                .map(Self::Distance)
+                .map_err(|err| format!("invalid distance {distance}: {err}"))
+        }
+        s.parse().map(Self::Block).map_err(|err| format!("invalid block number {s}: {err}"))
+    }
+}
+
+impl fmt::Display for HistoryCutoff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Auto => f.write_str("auto"),
+            Self::Block(block) => write!(f, "{block}"),
+            Self::Distance(distance) => write!(f, "distance:{distance}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_provider::test_utils::MockEthProvider;
+    use reth_prune_types::{PruneCheckpoint, PruneMode};
+
+    #[test]
+    fn parse_history_cutoff() {
+        for cutoff in [HistoryCutoff::Auto, HistoryCutoff::Block(100), HistoryCutoff::Distance(5)] {
+            assert_eq!(cutoff.to_string().parse::<HistoryCutoff>(), Ok(cutoff));
+        }
+        assert_eq!("distance:10".parse::<HistoryCutoff>(), Ok(HistoryCutoff::Distance(10)));
+        assert!("distance:".parse::<HistoryCutoff>().is_err());
+        assert!("latest".parse::<HistoryCutoff>().is_err());
+    }
+
+    #[test]
+    fn prune_horizon_from_checkpoints() {
+        let provider = MockEthProvider::default();
+        assert_eq!(HistoryCutoff::Auto.oldest_block(&provider), Ok(None));
+
+        let checkpoint = |block_number| PruneCheckpoint {
+            block_number: Some(block_number),
+            tx_number: None,
+            prune_mode: PruneMode::Distance(64),
+        };
+        provider.add_prune_checkpoint(PruneSegment::SenderRecovery, checkpoint(1000));
+        provider.add_prune_checkpoint(PruneSegment::AccountHistory, checkpoint(100));
+        assert_eq!(HistoryCutoff::Auto.oldest_block(&provider), Ok(Some(100)));
+
+        provider.add_prune_checkpoint(PruneSegment::Receipts, checkpoint(100));
+        assert_eq!(HistoryCutoff::Auto.oldest_block(&provider), Ok(Some(101)));
+
+        assert_eq!(HistoryCutoff::Block(5).oldest_block(&provider), Ok(Some(5)));
+    }
+}
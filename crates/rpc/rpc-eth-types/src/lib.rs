@@ -8,26 +8,35 @@
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
 #![cfg_attr(not(test), warn(unused_crate_dependencies))]
 
+pub mod block_confirmations;
 pub mod builder;
 pub mod cache;
 pub mod error;
 pub mod fee_history;
 pub mod gas_oracle;
+pub mod history_cutoff;
 pub mod id_provider;
 pub mod logs_utils;
 pub mod pending_block;
 pub mod receipt;
+pub mod replay_cache;
 pub mod revm_utils;
 pub mod simulate;
+pub mod state_overlay;
 pub mod transaction;
 pub mod utils;
 
+pub use block_confirmations::{BlockConfirmations, ExtendedBlockId};
 pub use builder::{
     config::{EthConfig, EthFilterConfig},
     ctx::EthApiBuilderCtx,
 };
 pub use cache::{
-    config::EthStateCacheConfig, db::StateCacheDb, multi_consumer::MultiConsumerLruCache,
+    config::EthStateCacheConfig,
+    db::StateCacheDb,
+    latest_state::{LatestStateCache, LatestStateCacheProvider},
+    multi_consumer::MultiConsumerLruCache,
+    tx_json::{MaybeSerialized, TransactionJsonCache},
     EthStateCache,
 };
 pub use error::{EthApiError, EthResult, RevertError, RpcInvalidTransactionError, SignError};
@@ -35,7 +44,18 @@ pub use fee_history::{FeeHistoryCache, FeeHistoryCacheConfig, FeeHistoryEntry};
 pub use gas_oracle::{
     GasCap, GasPriceOracle, GasPriceOracleConfig, GasPriceOracleResult, RPC_DEFAULT_GAS_CAP,
 };
+pub use history_cutoff::HistoryCutoff;
 pub use id_provider::EthSubscriptionIdProvider;
-pub use pending_block::{PendingBlock, PendingBlockEnv, PendingBlockEnvOrigin};
+pub use pending_block::{
+    PendingBlock, PendingBlockEnv, PendingBlockEnvOrigin, PendingBlockGeneration,
+    PENDING_BLOCK_BUILD_TIMEOUT,
+};
 pub use receipt::EthReceiptBuilder;
+pub use replay_cache::{
+    ReplayCache, ReplayedState, DEFAULT_REPLAY_CACHE_MAX_ENTRIES, DEFAULT_REPLAY_CACHE_TTL,
+};
+pub use state_overlay::{
+    StateOverlay, StateOverlayProvider, DEFAULT_STATE_OVERLAY_MEMORY_BUDGET,
+    DEFAULT_STATE_OVERLAY_SPILL_LIMIT,
+};
 pub use transaction::TransactionSource;
@@ -185,10 +185,93 @@ pub fn get_filter_block_range(
 
 #[cfg(test)]
 mod tests {
-    use alloy_rpc_types_eth::Filter;
+    use alloy_primitives::{Address, LogData, B256};
+    use alloy_rpc_types_eth::{Filter, FilterSet};
+    use proptest::{collection::vec, prelude::*};
 
     use super::*;
 
+    /// Straightforward implementation of the `eth_getLogs` address and topics matching rules.
+    fn reference_log_matches(filter: &Filter, log: &alloy_primitives::Log) -> bool {
+        let address = filter.address.is_empty() || filter.address.iter().any(|a| *a == log.address);
+        let topics = filter.topics.iter().enumerate().all(|(idx, topic)| {
+            // an empty set is a `null` wildcard, otherwise the log topic must be one of the values
+            topic.is_empty() || log.topics().get(idx).is_some_and(|t| topic.iter().any(|v| v == t))
+        });
+        address && topics
+    }
+
+    /// Picks values out of a small pool so that filters and logs actually overlap.
+    fn pooled<T: std::fmt::Debug + Clone + 'static>(pool: Vec<T>) -> impl Strategy<Value = T> {
+        (0..pool.len()).prop_map(move |idx| pool[idx].clone())
+    }
+
+    fn address_strategy() -> impl Strategy<Value = Address> {
+        pooled((0..4u8).map(|i| Address::with_last_byte(i)).collect())
+    }
+
+    fn topic_strategy() -> impl Strategy<Value = B256> {
+        pooled((0..4u8).map(|i| B256::with_last_byte(i)).collect())
+    }
+
+    fn filter_strategy() -> impl Strategy<Value = Filter> {
+        (vec(address_strategy(), 0..3), vec(vec(topic_strategy(), 0..3), 0..=4)).prop_map(
+            |(addresses, topics)| {
+                let mut filter = Filter::new().address(addresses);
+                for (idx, topic) in topics.into_iter().enumerate() {
+                    filter.topics[idx] = FilterSet::from(topic);
+                }
+                filter
+            },
+        )
+    }
+
+    fn log_strategy() -> impl Strategy<Value = alloy_primitives::Log> {
+        (address_strategy(), vec(topic_strategy(), 0..=4)).prop_map(|(address, topics)| {
+            alloy_primitives::Log {
+                address,
+                data: LogData::new_unchecked(topics, Default::default()),
+            }
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn log_matches_filter_reference(filter in filter_strategy(), log in log_strategy()) {
+            let block = BlockNumHash::default();
+            prop_assert_eq!(
+                log_matches_filter(block, &log, &FilteredParams::new(Some(filter.clone()))),
+                reference_log_matches(&filter, &log)
+            );
+        }
+    }
+
+    #[test]
+    fn log_matches_nested_topics() {
+        let log = alloy_primitives::Log {
+            address: Address::ZERO,
+            data: LogData::new_unchecked(
+                vec![B256::with_last_byte(1), B256::with_last_byte(2)],
+                Default::default(),
+            ),
+        };
+        let block = BlockNumHash::default();
+
+        // `[null, [0x..01, 0x..02]]`: wildcard for the first position, OR for the second one
+        let mut filter = Filter::new();
+        filter.topics[1] = vec![B256::with_last_byte(1), B256::with_last_byte(2)].into();
+        assert!(log_matches_filter(block, &log, &FilteredParams::new(Some(filter.clone()))));
+
+        // `[null, [0x..01, 0x..03]]`
+        filter.topics[1] = vec![B256::with_last_byte(1), B256::with_last_byte(3)].into();
+        assert!(!log_matches_filter(block, &log, &FilteredParams::new(Some(filter.clone()))));
+
+        // a third topic is required but the log only has two
+        filter.topics[1] = FilterSet::default();
+        filter.topics[2] = B256::with_last_byte(1).into();
+        assert!(!log_matches_filter(block, &log, &FilteredParams::new(Some(filter))));
+    }
+
     #[test]
     fn test_log_range_from_and_to() {
         let from = 14000000u64;
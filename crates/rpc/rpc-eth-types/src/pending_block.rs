@@ -2,16 +2,29 @@
 //!
 //! Types used in block building.
 
-use std::time::Instant;
+use std::{
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
 
 use alloy_consensus::BlockHeader;
 use alloy_eips::{BlockId, BlockNumberOrTag};
 use alloy_primitives::B256;
 use derive_more::Constructor;
+use futures::{Stream, StreamExt};
+use reth_execution_types::ExecutionOutcome;
 use reth_primitives::{Receipt, SealedBlockWithSenders};
 use reth_primitives_traits::Block;
 use revm_primitives::{BlockEnv, CfgEnvWithHandlerCfg};
 
+/// Maximum time spent executing pool transactions when building the local pending block.
+///
+/// Transactions that didn't fit into this time are not included in the pending block.
+pub const PENDING_BLOCK_BUILD_TIMEOUT: Duration = Duration::from_millis(500);
+
 /// Configured [`BlockEnv`] and [`CfgEnvWithHandlerCfg`] for a pending block.
 #[derive(Debug, Clone, Constructor)]
 pub struct PendingBlockEnv<B: Block = reth_primitives::Block, R = Receipt> {
@@ -84,4 +97,41 @@ pub struct PendingBlock<B: Block, R> {
     pub block: SealedBlockWithSenders<B>,
     /// The receipts for the pending block
     pub receipts: Vec<R>,
+    /// The state changes of the pending block on top of its parent.
+    pub state: Arc<ExecutionOutcome>,
+    /// The [`PendingBlockGeneration`] the pending block was built for, if tracked.
+    pub generation: Option<u64>,
+}
+
+/// Counter that is bumped every time the pending block built from the pool becomes outdated,
+/// that is, on every new canonical head and every new pending pool transaction.
+#[derive(Debug, Clone, Default)]
+pub struct PendingBlockGeneration(Arc<AtomicU64>);
+
+impl PendingBlockGeneration {
+    /// Returns the current generation.
+    pub fn current(&self) -> u64 {
+        self.0.load(Ordering::Acquire)
+    }
+
+    /// Marks the current pending block as outdated.
+    pub fn bump(&self) {
+        self.0.fetch_add(1, Ordering::AcqRel);
+    }
+}
+
+/// Bumps the [`PendingBlockGeneration`] on every pool or canonical chain event until both streams
+/// are exhausted.
+pub async fn pending_block_generation_task<P, C>(
+    generation: PendingBlockGeneration,
+    pool_events: P,
+    canon_events: C,
+) where
+    P: Stream + Unpin,
+    C: Stream + Unpin,
+{
+    let mut events = futures::stream::select(pool_events.map(|_| ()), canon_events.map(|_| ()));
+    while events.next().await.is_some() {
+        generation.bump();
+    }
 }
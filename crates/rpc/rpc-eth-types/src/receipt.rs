@@ -4,7 +4,7 @@ use super::{EthApiError, EthResult};
 use alloy_consensus::{ReceiptEnvelope, TxReceipt};
 use alloy_primitives::{Address, TxKind};
 use alloy_rpc_types_eth::{Log, ReceiptWithBloom, TransactionReceipt};
-use reth_primitives::{Receipt, TransactionMeta, TransactionSigned, TxType};
+use reth_primitives::{fee_math, Receipt, TransactionMeta, TransactionSigned, TxType};
 use reth_primitives_traits::SignedTransaction;
 
 /// Builds an [`TransactionReceipt`] obtaining the inner receipt envelope from the given closure.
@@ -37,8 +37,8 @@ where
 
     let blob_gas_used = transaction.blob_gas_used();
     // Blob gas price should only be present if the transaction is a blob transaction
-    let blob_gas_price = blob_gas_used
-        .and_then(|_| meta.excess_blob_gas.map(alloy_eips::eip4844::calc_blob_gasprice));
+    let blob_gas_price =
+        blob_gas_used.and_then(|_| meta.excess_blob_gas.map(fee_math::saturating_blob_fee));
     let logs_bloom = receipt.bloom();
 
     // get number of logs in the block
@@ -84,7 +84,11 @@ where
         to,
         gas_used,
         contract_address,
-        effective_gas_price: transaction.effective_gas_price(meta.base_fee),
+        effective_gas_price: fee_math::effective_gas_price(
+            transaction.max_fee_per_gas(),
+            transaction.max_priority_fee_per_gas(),
+            meta.base_fee,
+        ),
         // EIP-4844 fields
         blob_gas_price,
         blob_gas_used,
@@ -0,0 +1,148 @@
+//! Cache of the state of partially replayed blocks.
+//!
+//! Used to execute calls right before a transaction of a historical block, without replaying the
+//! block from its first transaction for every index a tool iterates over.
+
+use alloy_primitives::{Address, B256};
+use parking_lot::Mutex;
+use reth_revm::db::{CacheDB, DbAccount};
+use revm_primitives::{Bytecode, HashMap};
+use schnellru::{ByLength, LruMap};
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+/// Default maximum number of replayed states kept in the [`ReplayCache`].
+pub const DEFAULT_REPLAY_CACHE_MAX_ENTRIES: u32 = 16;
+
+/// Default time a replayed state is kept in the [`ReplayCache`].
+pub const DEFAULT_REPLAY_CACHE_TTL: Duration = Duration::from_secs(30);
+
+/// The state of a block after its first transactions were executed on top of the state of its
+/// parent.
+#[derive(Debug, Clone)]
+pub struct ReplayedState {
+    /// Number of executed transactions.
+    tx_index: usize,
+    /// Accounts loaded or changed by the executed transactions.
+    accounts: HashMap<Address, DbAccount>,
+    /// Bytecodes loaded or deployed by the executed transactions.
+    contracts: HashMap<B256, Bytecode>,
+}
+
+impl ReplayedState {
+    /// Takes a snapshot of the given database after `tx_index` transactions were executed.
+    pub fn new<ExtDB>(tx_index: usize, db: &CacheDB<ExtDB>) -> Self {
+        Self { tx_index, accounts: db.accounts.clone(), contracts: db.contracts.clone() }
+    }
+
+    /// Returns the number of executed transactions.
+    pub const fn tx_index(&self) -> usize {
+        self.tx_index
+    }
+
+    /// Restores the snapshot into the given database, that must point to the state of the parent
+    /// of the block.
+    pub fn apply_to<ExtDB>(&self, db: &mut CacheDB<ExtDB>) {
+        db.accounts.clone_from(&self.accounts);
+        db.contracts.clone_from(&self.contracts);
+    }
+}
+
+/// A [`ReplayedState`] in the [`ReplayCache`].
+#[derive(Debug)]
+struct CachedReplay {
+    state: Arc<ReplayedState>,
+    expires_at: Instant,
+}
+
+/// Cache of [`ReplayedState`]s by block hash and number of executed transactions.
+#[derive(Debug)]
+pub struct ReplayCache {
+    entries: Mutex<LruMap<(B256, usize), CachedReplay, ByLength>>,
+    ttl: Duration,
+}
+
+impl ReplayCache {
+    /// Creates a new cache that keeps up to `max_entries` replayed states for `ttl`.
+    pub fn new(max_entries: u32, ttl: Duration) -> Self {
+        Self { entries: Mutex::new(LruMap::new(ByLength::new(max_entries))), ttl }
+    }
+
+    /// Returns the replayed state of the block with the most executed transactions, but not more
+    /// than `tx_index`.
+    pub fn get(&self, block_hash: B256, tx_index: usize) -> Option<Arc<ReplayedState>> {
+        let mut entries = self.entries.lock();
+        let now = Instant::now();
+        let key = entries
+            .iter()
+            .filter(|((hash, index), entry)| {
+                *hash == block_hash && *index <= tx_index && entry.expires_at > now
+            })
+            .map(|(key, _)| *key)
+            .max_by_key(|(_, index)| *index)?;
+        entries.get(&key).map(|entry| entry.state.clone())
+    }
+
+    /// Caches the replayed state of the block.
+    ///
+    /// States without any executed transactions are not cached.
+    pub fn insert(&self, block_hash: B256, state: ReplayedState) {
+        if state.tx_index == 0 {
+            return
+        }
+        let key = (block_hash, state.tx_index);
+        let entry = CachedReplay { state: Arc::new(state), expires_at: Instant::now() + self.ttl };
+        self.entries.lock().insert(key, entry);
+    }
+}
+
+impl Default for ReplayCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_REPLAY_CACHE_MAX_ENTRIES, DEFAULT_REPLAY_CACHE_TTL)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use revm::db::EmptyDB;
+    use revm_primitives::AccountInfo;
+
+    fn replayed(tx_index: usize) -> ReplayedState {
+        let mut db = CacheDB::new(EmptyDB::default());
+        db.insert_account_info(
+            Address::with_last_byte(1),
+            AccountInfo { nonce: tx_index as u64, ..Default::default() },
+        );
+        ReplayedState::new(tx_index, &db)
+    }
+
+    #[test]
+    fn returns_closest_prefix() {
+        let cache = ReplayCache::default();
+        let block = B256::with_last_byte(1);
+        cache.insert(block, replayed(0));
+        cache.insert(block, replayed(2));
+        cache.insert(block, replayed(5));
+
+        assert!(cache.get(block, 1).is_none());
+        assert_eq!(cache.get(block, 2).unwrap().tx_index(), 2);
+        assert_eq!(cache.get(block, 4).unwrap().tx_index(), 2);
+        assert_eq!(cache.get(block, 7).unwrap().tx_index(), 5);
+        assert!(cache.get(B256::with_last_byte(2), 7).is_none());
+
+        let mut db = CacheDB::new(EmptyDB::default());
+        cache.get(block, 7).unwrap().apply_to(&mut db);
+        assert_eq!(db.accounts[&Address::with_last_byte(1)].info.nonce, 5);
+    }
+
+    #[test]
+    fn replayed_states_expire() {
+        let cache = ReplayCache::new(DEFAULT_REPLAY_CACHE_MAX_ENTRIES, Duration::ZERO);
+        let block = B256::with_last_byte(1);
+        cache.insert(block, replayed(1));
+        assert!(cache.get(block, 1).is_none());
+    }
+}
@@ -5,6 +5,7 @@ use alloy_rpc_types_eth::{
     state::{AccountOverride, StateOverride},
     BlockOverrides,
 };
+use reth_primitives::fee_math;
 use revm::{
     db::CacheDB,
     precompile::{PrecompileSpecId, Precompiles},
@@ -43,17 +44,14 @@ where
     let balance = caller.map(|acc| acc.balance).unwrap_or_default();
     // Get transaction value.
     let value = env.value;
-    // Subtract transferred value from the caller balance. Return error if the caller has
-    // insufficient funds.
-    let balance = balance
-        .checked_sub(env.value)
-        .ok_or_else(|| RpcInvalidTransactionError::InsufficientFunds { cost: value, balance })?;
-
-    Ok(balance
-        // Calculate the amount of gas the caller can afford with the specified gas price.
-        .checked_div(env.gas_price)
-        // This will be 0 if gas price is 0. It is fine, because we check it before.
-        .unwrap_or_default())
+    // Return error if the caller has insufficient funds to transfer the value.
+    if value > balance {
+        return Err(RpcInvalidTransactionError::InsufficientFunds { cost: value, balance }.into())
+    }
+
+    // Calculate the amount of gas the caller can afford with the specified gas price.
+    // This will be 0 if gas price is 0. It is fine, because we check it before.
+    Ok(fee_math::max_affordable_gas(balance, value, env.gas_price).unwrap_or_default())
 }
 
 /// Helper type for representing the fees of a `TransactionRequest`
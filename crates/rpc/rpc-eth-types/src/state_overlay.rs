@@ -0,0 +1,722 @@
+//! State changes accumulated on top of a historical state.
+//!
+//! Used to execute a range of historical blocks one after another, without reopening the
+//! historical state of every block.
+
+use alloy_primitives::{
+    map::{AddressHashMap, B256HashMap},
+    Address, BlockNumber, StorageKey, StorageValue, B256, U256,
+};
+use reth_errors::{ProviderError, ProviderResult};
+use reth_primitives::{Account, Bytecode, StorageEntry};
+use reth_revm::{
+    db::{AccountState, BundleState, DbAccount},
+    primitives::{Bytecode as RevmBytecode, HashMap, KECCAK_EMPTY},
+};
+use reth_storage_api::{
+    AccountReader, BlockHashReader, HashedPostStateProvider, StateProofProvider, StateProvider,
+    StateRootProvider, StorageRootProvider,
+};
+use reth_trie::{
+    updates::TrieUpdates, AccountProof, HashedPostState, HashedStorage, MultiProof,
+    MultiProofTargets, StorageMultiProof, StorageProof, TrieInput,
+};
+use std::{
+    cmp::Ordering,
+    fs::File,
+    io::{self, BufWriter, Read, Seek, SeekFrom, Write},
+    ops::RangeInclusive,
+    path::Path,
+    sync::Mutex,
+};
+use tempfile::TempDir;
+
+/// Default memory budget of a [`StateOverlay`], in bytes.
+pub const DEFAULT_STATE_OVERLAY_MEMORY_BUDGET: usize = 256 * 1024 * 1024;
+
+/// Default limit of the changes a [`StateOverlay`] spills to disk, in bytes.
+pub const DEFAULT_STATE_OVERLAY_SPILL_LIMIT: u64 = 16 * 1024 * 1024 * 1024;
+
+/// Approximate memory used by an account of the overlay, excluding its storage.
+const ACCOUNT_MEMORY_SIZE: usize = 128;
+
+/// Approximate memory used by a storage slot of the overlay.
+const STORAGE_SLOT_MEMORY_SIZE: usize = 96;
+
+/// Size of a spilled account record: address, flags, nonce, balance and bytecode hash.
+const ACCOUNT_RECORD_SIZE: usize = 20 + 1 + 8 + 32 + 32;
+
+/// Size of a spilled storage record: address, slot and value.
+const STORAGE_RECORD_SIZE: usize = 20 + 32 + 32;
+
+/// Account record flag set if the account exists.
+const ACCOUNT_EXISTS: u8 = 1;
+
+/// Account record flag set if the storage of the account was wiped.
+const ACCOUNT_WIPED: u8 = 1 << 1;
+
+/// Account record flag set if the account has a bytecode hash.
+const ACCOUNT_HAS_CODE: u8 = 1 << 2;
+
+/// An account changed by the blocks applied to a [`StateOverlay`].
+#[derive(Debug, Default)]
+struct OverlayAccount {
+    /// The account, `None` if it was destroyed.
+    info: Option<Account>,
+    /// Whether the storage of the account was wiped, in which case slots that are not part of
+    /// the overlay are zero.
+    wiped: bool,
+    /// Changed storage slots.
+    storage: B256HashMap<U256>,
+}
+
+/// State changes of consecutive blocks, accumulated on top of the state of the parent of the
+/// first block.
+///
+/// Account and storage changes are kept in memory until they exceed the memory budget, at which
+/// point they are spilled to a sorted run in a temporary directory. Lookups check the in-memory
+/// changes first and then the runs, newest first. Bytecodes are always kept in memory.
+///
+/// Applying changes fails once the spilled runs would exceed the spill limit. The temporary
+/// directory is removed once the overlay is dropped.
+#[derive(Debug)]
+pub struct StateOverlay {
+    /// Changed accounts that were not spilled yet.
+    accounts: AddressHashMap<OverlayAccount>,
+    /// Deployed bytecodes.
+    bytecodes: B256HashMap<Bytecode>,
+    /// Approximate memory used by the overlay.
+    memory_size: usize,
+    /// Memory budget after which the changes are spilled to disk.
+    memory_budget: usize,
+    /// Maximum total size of the spilled runs.
+    spill_limit: u64,
+    /// Spilled changes, if the memory budget was ever exceeded.
+    spilled: Option<SpilledState>,
+}
+
+impl StateOverlay {
+    /// Creates an empty overlay that spills to disk once it exceeds the memory budget, and spills
+    /// at most the spill limit, both in bytes.
+    pub fn new(memory_budget: usize, spill_limit: u64) -> Self {
+        Self {
+            accounts: Default::default(),
+            bytecodes: Default::default(),
+            memory_size: 0,
+            memory_budget,
+            spill_limit,
+            spilled: None,
+        }
+    }
+
+    /// Returns the approximate memory used by the overlay, in bytes.
+    pub const fn memory_size(&self) -> usize {
+        self.memory_size
+    }
+
+    /// Returns the number of times the overlay was spilled to disk.
+    pub fn spilled_runs(&self) -> usize {
+        self.spilled.as_ref().map_or(0, |spilled| spilled.runs.len())
+    }
+
+    /// Applies the state changes of a block on top of the overlay.
+    pub fn apply_bundle(&mut self, bundle: &BundleState) -> io::Result<()> {
+        for (address, bundle_account) in &bundle.state {
+            self.apply_account(
+                *address,
+                bundle_account.info.as_ref().map(Into::into),
+                bundle_account.was_destroyed(),
+                bundle_account.storage.iter().map(|(slot, value)| (*slot, value.present_value)),
+            );
+        }
+
+        for (hash, bytecode) in &bundle.contracts {
+            self.insert_bytecode(*hash, bytecode);
+        }
+
+        self.spill_if_over_budget()
+    }
+
+    /// Applies the state changes committed to a [`CacheDB`](reth_revm::db::CacheDB), given its
+    /// accounts and contracts, on top of the overlay.
+    ///
+    /// Accounts that were only read are skipped, except for non-existent ones. The cached storage
+    /// slots of the applied accounts are applied as well, whether they were changed or not.
+    pub fn apply_cached_changes(
+        &mut self,
+        accounts: &HashMap<Address, DbAccount>,
+        contracts: &HashMap<B256, RevmBytecode>,
+    ) -> io::Result<()> {
+        for (address, account) in accounts {
+            let wiped = match account.account_state {
+                AccountState::None => continue,
+                AccountState::Touched => false,
+                AccountState::NotExisting | AccountState::StorageCleared => true,
+            };
+            let info = account.info();
+            if let Some(code_hash) = info.as_ref().map(|info| info.code_hash) {
+                if let Some(bytecode) = contracts.get(&code_hash) {
+                    self.insert_bytecode(code_hash, bytecode);
+                }
+            }
+            self.apply_account(
+                *address,
+                info.as_ref().map(Into::into),
+                wiped,
+                account.storage.iter().map(|(slot, value)| (*slot, *value)),
+            );
+        }
+
+        self.spill_if_over_budget()
+    }
+
+    /// Applies the changes of an account on top of the overlay.
+    fn apply_account(
+        &mut self,
+        address: Address,
+        info: Option<Account>,
+        wiped: bool,
+        storage: impl IntoIterator<Item = (U256, U256)>,
+    ) {
+        let account = self.accounts.entry(address).or_insert_with(|| {
+            self.memory_size += ACCOUNT_MEMORY_SIZE;
+            OverlayAccount::default()
+        });
+
+        if wiped {
+            self.memory_size -= account.storage.len() * STORAGE_SLOT_MEMORY_SIZE;
+            account.storage.clear();
+            account.wiped = true;
+        }
+        account.info = info;
+
+        for (slot, value) in storage {
+            if account.storage.insert(B256::from(slot), value).is_none() {
+                self.memory_size += STORAGE_SLOT_MEMORY_SIZE;
+            }
+        }
+    }
+
+    /// Adds a deployed bytecode to the overlay, if it's not part of it yet.
+    fn insert_bytecode(&mut self, hash: B256, bytecode: &RevmBytecode) {
+        if hash != KECCAK_EMPTY && !self.bytecodes.contains_key(&hash) {
+            self.memory_size += bytecode.len();
+            self.bytecodes.insert(hash, Bytecode(bytecode.clone()));
+        }
+    }
+
+    /// Spills the in-memory changes to disk if they exceed the memory budget.
+    fn spill_if_over_budget(&mut self) -> io::Result<()> {
+        if self.memory_size > self.memory_budget {
+            self.spill()?;
+        }
+        Ok(())
+    }
+
+    /// Writes all in-memory account and storage changes to a new run on disk.
+    fn spill(&mut self) -> io::Result<()> {
+        if self.accounts.is_empty() {
+            return Ok(())
+        }
+
+        let run_size = self
+            .accounts
+            .values()
+            .map(|account| ACCOUNT_RECORD_SIZE + account.storage.len() * STORAGE_RECORD_SIZE)
+            .sum::<usize>() as u64;
+        let spilled_size = self.spilled.as_ref().map_or(0, |spilled| spilled.size);
+        if spilled_size + run_size > self.spill_limit {
+            return Err(io::Error::other(format!(
+                "state changes exceed the spill limit of {} bytes",
+                self.spill_limit
+            )))
+        }
+
+        if self.spilled.is_none() {
+            self.spilled =
+                Some(SpilledState { dir: tempfile::tempdir()?, runs: Vec::new(), size: 0 });
+        }
+        let spilled = self.spilled.as_mut().expect("created above");
+        spilled.size += run_size;
+
+        let mut accounts = std::mem::take(&mut self.accounts).into_iter().collect::<Vec<_>>();
+        accounts.sort_unstable_by_key(|(address, _)| *address);
+        spilled.write_run(accounts)?;
+
+        self.memory_size = self.bytecodes.values().map(|bytecode| bytecode.len()).sum();
+        Ok(())
+    }
+
+    /// Returns the account if it was changed by the overlay, `Some(None)` if it was destroyed.
+    pub fn account(&self, address: &Address) -> ProviderResult<Option<Option<Account>>> {
+        if let Some(account) = self.accounts.get(address) {
+            return Ok(Some(account.info))
+        }
+
+        let Some(spilled) = &self.spilled else { return Ok(None) };
+        for run in spilled.runs.iter().rev() {
+            if let Some(record) = run.account(address)? {
+                return Ok(Some(record.info))
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Returns the storage value if it was changed by the overlay.
+    pub fn storage(&self, address: &Address, key: &StorageKey) -> ProviderResult<Option<U256>> {
+        if let Some(account) = self.accounts.get(address) {
+            if let Some(value) = account.storage.get(key) {
+                return Ok(Some(*value))
+            }
+            if account.wiped {
+                return Ok(Some(U256::ZERO))
+            }
+        }
+
+        let Some(spilled) = &self.spilled else { return Ok(None) };
+        for run in spilled.runs.iter().rev() {
+            if let Some(value) = run.storage(address, key)? {
+                return Ok(Some(value))
+            }
+            if run.account(address)?.is_some_and(|record| record.wiped) {
+                return Ok(Some(U256::ZERO))
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Returns the bytecode if it was deployed by the overlay.
+    pub fn bytecode(&self, code_hash: &B256) -> Option<Bytecode> {
+        self.bytecodes.get(code_hash).cloned()
+    }
+}
+
+/// Runs of spilled state changes.
+#[derive(Debug)]
+struct SpilledState {
+    /// Temporary directory of the run files, removed on drop.
+    dir: TempDir,
+    /// Runs in the order they were spilled.
+    runs: Vec<SpilledRun>,
+    /// Total size of the runs.
+    size: u64,
+}
+
+impl SpilledState {
+    /// Writes the accounts, sorted by address, and their storage to a new run.
+    fn write_run(&mut self, accounts: Vec<(Address, OverlayAccount)>) -> io::Result<()> {
+        let id = self.runs.len();
+
+        let mut records = Vec::with_capacity(accounts.len());
+        let mut storage_records = Vec::new();
+        for (address, account) in &accounts {
+            let mut record = [0; ACCOUNT_RECORD_SIZE];
+            record[..20].copy_from_slice(address.as_slice());
+            if let Some(info) = &account.info {
+                record[20] |= ACCOUNT_EXISTS;
+                record[21..29].copy_from_slice(&info.nonce.to_be_bytes());
+                record[29..61].copy_from_slice(&info.balance.to_be_bytes::<32>());
+                if let Some(code_hash) = info.bytecode_hash {
+                    record[20] |= ACCOUNT_HAS_CODE;
+                    record[61..].copy_from_slice(code_hash.as_slice());
+                }
+            }
+            if account.wiped {
+                record[20] |= ACCOUNT_WIPED;
+            }
+            records.push(record);
+
+            let mut slots = account.storage.iter().collect::<Vec<_>>();
+            slots.sort_unstable_by_key(|(slot, _)| *slot);
+            for (slot, value) in slots {
+                let mut record = [0; STORAGE_RECORD_SIZE];
+                record[..20].copy_from_slice(address.as_slice());
+                record[20..52].copy_from_slice(slot.as_slice());
+                record[52..].copy_from_slice(&value.to_be_bytes::<32>());
+                storage_records.push(record);
+            }
+        }
+
+        let accounts = RunFile::create(
+            &self.dir.path().join(format!("accounts-{id}")),
+            ACCOUNT_RECORD_SIZE,
+            records.iter().map(|record| record.as_slice()),
+        )?;
+        let storage = RunFile::create(
+            &self.dir.path().join(format!("storage-{id}")),
+            STORAGE_RECORD_SIZE,
+            storage_records.iter().map(|record| record.as_slice()),
+        )?;
+        self.runs.push(SpilledRun { accounts, storage });
+
+        Ok(())
+    }
+}
+
+/// Account of a [`SpilledRun`].
+#[derive(Debug)]
+struct SpilledAccount {
+    /// The account, `None` if it was destroyed.
+    info: Option<Account>,
+    /// Whether the storage of the account was wiped before this run.
+    wiped: bool,
+}
+
+/// Account and storage changes spilled at once.
+#[derive(Debug)]
+struct SpilledRun {
+    /// Account records, sorted by address.
+    accounts: RunFile,
+    /// Storage records, sorted by address and slot.
+    storage: RunFile,
+}
+
+impl SpilledRun {
+    /// Looks up the account in the run.
+    fn account(&self, address: &Address) -> ProviderResult<Option<SpilledAccount>> {
+        let Some(record) = self.accounts.find(address.as_slice()).map_err(into_provider_err)?
+        else {
+            return Ok(None)
+        };
+
+        let flags = record[20];
+        let info = (flags & ACCOUNT_EXISTS != 0).then(|| Account {
+            nonce: u64::from_be_bytes(record[21..29].try_into().expect("8 bytes")),
+            balance: U256::from_be_slice(&record[29..61]),
+            bytecode_hash: (flags & ACCOUNT_HAS_CODE != 0).then(|| B256::from_slice(&record[61..])),
+        });
+        Ok(Some(SpilledAccount { info, wiped: flags & ACCOUNT_WIPED != 0 }))
+    }
+
+    /// Looks up the storage slot in the run.
+    fn storage(&self, address: &Address, key: &StorageKey) -> ProviderResult<Option<U256>> {
+        let mut search = [0; 52];
+        search[..20].copy_from_slice(address.as_slice());
+        search[20..].copy_from_slice(key.as_slice());
+
+        Ok(self
+            .storage
+            .find(&search)
+            .map_err(into_provider_err)?
+            .map(|record| U256::from_be_slice(&record[52..])))
+    }
+}
+
+/// A file of fixed size records, sorted by the key the records start with.
+#[derive(Debug)]
+struct RunFile {
+    file: Mutex<File>,
+    record_size: usize,
+    len: u64,
+}
+
+impl RunFile {
+    /// Writes the sorted records to a new file at the given path.
+    fn create<'a>(
+        path: &Path,
+        record_size: usize,
+        records: impl IntoIterator<Item = &'a [u8]>,
+    ) -> io::Result<Self> {
+        let mut writer = BufWriter::new(File::create_new(path)?);
+        let mut len = 0;
+        for record in records {
+            writer.write_all(record)?;
+            len += 1;
+        }
+        writer.flush()?;
+
+        Ok(Self { file: Mutex::new(File::open(path)?), record_size, len })
+    }
+
+    /// Binary searches for the record whose key is equal to the given key.
+    fn find(&self, key: &[u8]) -> io::Result<Option<Vec<u8>>> {
+        let mut file = self.file.lock().map_err(|_| io::Error::other("run file lock poisoned"))?;
+        let mut record = vec![0; self.record_size];
+
+        let (mut low, mut high) = (0, self.len);
+        while low < high {
+            let mid = low + (high - low) / 2;
+            file.seek(SeekFrom::Start(mid * self.record_size as u64))?;
+            file.read_exact(&mut record)?;
+
+            match record[..key.len()].cmp(key) {
+                Ordering::Less => low = mid + 1,
+                Ordering::Greater => high = mid,
+                Ordering::Equal => return Ok(Some(record)),
+            }
+        }
+
+        Ok(None)
+    }
+}
+
+fn into_provider_err(err: io::Error) -> ProviderError {
+    ProviderError::FsPathError(err.to_string())
+}
+
+/// A [`StateProvider`] serving the state of a [`StateOverlay`] on top of the state it was
+/// accumulated on.
+///
+/// State roots and proofs can't be computed on top of the overlay.
+#[allow(missing_debug_implementations)]
+pub struct StateOverlayProvider<'a> {
+    state: &'a dyn StateProvider,
+    overlay: &'a StateOverlay,
+}
+
+impl<'a> StateOverlayProvider<'a> {
+    /// Creates a new provider of the overlay on top of the given state.
+    pub const fn new(state: &'a dyn StateProvider, overlay: &'a StateOverlay) -> Self {
+        Self { state, overlay }
+    }
+}
+
+impl AccountReader for StateOverlayProvider<'_> {
+    fn basic_account(&self, address: &Address) -> ProviderResult<Option<Account>> {
+        match self.overlay.account(address)? {
+            Some(account) => Ok(account),
+            None => self.state.basic_account(address),
+        }
+    }
+}
+
+impl BlockHashReader for StateOverlayProvider<'_> {
+    fn block_hash(&self, number: BlockNumber) -> ProviderResult<Option<B256>> {
+        self.state.block_hash(number)
+    }
+
+    fn canonical_hashes_range(
+        &self,
+        start: BlockNumber,
+        end: BlockNumber,
+    ) -> ProviderResult<Vec<B256>> {
+        self.state.canonical_hashes_range(start, end)
+    }
+}
+
+impl StateRootProvider for StateOverlayProvider<'_> {
+    fn state_root(&self, _hashed_state: HashedPostState) -> ProviderResult<B256> {
+        Err(ProviderError::UnsupportedProvider)
+    }
+
+    fn state_root_from_nodes(&self, _input: TrieInput) -> ProviderResult<B256> {
+        Err(ProviderError::UnsupportedProvider)
+    }
+
+    fn state_root_with_updates(
+        &self,
+        _hashed_state: HashedPostState,
+    ) -> ProviderResult<(B256, TrieUpdates)> {
+        Err(ProviderError::UnsupportedProvider)
+    }
+
+    fn state_root_from_nodes_with_updates(
+        &self,
+        _input: TrieInput,
+    ) -> ProviderResult<(B256, TrieUpdates)> {
+        Err(ProviderError::UnsupportedProvider)
+    }
+}
+
+impl StorageRootProvider for StateOverlayProvider<'_> {
+    fn storage_root(
+        &self,
+        _address: Address,
+        _hashed_storage: HashedStorage,
+    ) -> ProviderResult<B256> {
+        Err(ProviderError::UnsupportedProvider)
+    }
+
+    fn storage_proof(
+        &self,
+        _address: Address,
+        _slot: B256,
+        _hashed_storage: HashedStorage,
+    ) -> ProviderResult<StorageProof> {
+        Err(ProviderError::UnsupportedProvider)
+    }
+
+    fn storage_multiproof(
+        &self,
+        _address: Address,
+        _slots: &[B256],
+        _hashed_storage: HashedStorage,
+    ) -> ProviderResult<StorageMultiProof> {
+        Err(ProviderError::UnsupportedProvider)
+    }
+
+    fn storage_range(
+        &self,
+        _address: Address,
+        _range: RangeInclusive<B256>,
+        _limit: usize,
+        _hashed_storage: HashedStorage,
+    ) -> ProviderResult<Vec<StorageEntry>> {
+        Err(ProviderError::UnsupportedProvider)
+    }
+}
+
+impl StateProofProvider for StateOverlayProvider<'_> {
+    fn proof(
+        &self,
+        _input: TrieInput,
+        _address: Address,
+        _slots: &[B256],
+    ) -> ProviderResult<AccountProof> {
+        Err(ProviderError::UnsupportedProvider)
+    }
+
+    fn multiproof(
+        &self,
+        _input: TrieInput,
+        _targets: MultiProofTargets,
+    ) -> ProviderResult<MultiProof> {
+        Err(ProviderError::UnsupportedProvider)
+    }
+
+    fn witness(
+        &self,
+        _input: TrieInput,
+        _target: HashedPostState,
+    ) -> ProviderResult<B256HashMap<alloy_primitives::Bytes>> {
+        Err(ProviderError::UnsupportedProvider)
+    }
+}
+
+impl HashedPostStateProvider for StateOverlayProvider<'_> {
+    fn hashed_post_state(&self, bundle_state: &BundleState) -> HashedPostState {
+        self.state.hashed_post_state(bundle_state)
+    }
+}
+
+impl StateProvider for StateOverlayProvider<'_> {
+    fn storage(
+        &self,
+        account: Address,
+        storage_key: StorageKey,
+    ) -> ProviderResult<Option<StorageValue>> {
+        match self.overlay.storage(&account, &storage_key)? {
+            Some(value) => Ok(Some(value)),
+            None => self.state.storage(account, storage_key),
+        }
+    }
+
+    fn bytecode_by_hash(&self, code_hash: &B256) -> ProviderResult<Option<Bytecode>> {
+        match self.overlay.bytecode(code_hash) {
+            Some(bytecode) => Ok(Some(bytecode)),
+            None => self.state.bytecode_by_hash(code_hash),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_revm::{
+        db::{states::StorageSlot, AccountStatus, BundleAccount, CacheDB, EmptyDB},
+        Database, DatabaseCommit,
+    };
+    use revm_primitives::{Account as EvmAccount, AccountInfo, EvmStorageSlot};
+
+    fn bundle(
+        accounts: impl IntoIterator<Item = (Address, Option<u64>, AccountStatus, Vec<(u64, u64)>)>,
+    ) -> BundleState {
+        let mut bundle = BundleState::default();
+        for (address, nonce, status, storage) in accounts {
+            let storage = storage
+                .into_iter()
+                .map(|(slot, value)| {
+                    (U256::from(slot), StorageSlot::new_changed(U256::ZERO, U256::from(value)))
+                })
+                .collect::<HashMap<_, _>>();
+            bundle.state.insert(
+                address,
+                BundleAccount::new(
+                    None,
+                    nonce.map(|nonce| AccountInfo { nonce, ..Default::default() }),
+                    storage,
+                    status,
+                ),
+            );
+        }
+        bundle
+    }
+
+    fn assert_overlay(overlay: &StateOverlay, address: Address) {
+        assert_eq!(overlay.account(&address).unwrap().unwrap().unwrap().nonce, 2);
+        assert_eq!(
+            overlay.storage(&address, &B256::with_last_byte(1)).unwrap(),
+            Some(U256::from(10))
+        );
+        // wiped by the destruction in the second block
+        assert_eq!(overlay.storage(&address, &B256::with_last_byte(2)).unwrap(), Some(U256::ZERO));
+        assert_eq!(
+            overlay.storage(&address, &B256::with_last_byte(3)).unwrap(),
+            Some(U256::from(30))
+        );
+        assert_eq!(overlay.account(&Address::with_last_byte(2)).unwrap(), None);
+        assert_eq!(overlay.storage(&Address::with_last_byte(2), &B256::ZERO).unwrap(), None);
+    }
+
+    #[test]
+    fn apply_bundles() {
+        let address = Address::with_last_byte(1);
+        let blocks = [
+            bundle([(address, Some(1), AccountStatus::Changed, vec![(1, 1), (2, 2)])]),
+            bundle([(address, Some(2), AccountStatus::DestroyedChanged, vec![(1, 10)])]),
+            bundle([(address, Some(2), AccountStatus::Changed, vec![(3, 30)])]),
+        ];
+
+        // kept in memory
+        let mut overlay = StateOverlay::new(usize::MAX, 0);
+        for block in &blocks {
+            overlay.apply_bundle(block).unwrap();
+        }
+        assert_eq!(overlay.spilled_runs(), 0);
+        assert_overlay(&overlay, address);
+
+        // spilled after every block
+        let mut overlay = StateOverlay::new(0, u64::MAX);
+        for block in &blocks {
+            overlay.apply_bundle(block).unwrap();
+        }
+        assert_eq!(overlay.spilled_runs(), 3);
+        assert_overlay(&overlay, address);
+
+        // removes the spilled runs on drop
+        let dir = overlay.spilled.as_ref().unwrap().dir.path().to_path_buf();
+        assert!(dir.exists());
+        drop(overlay);
+        assert!(!dir.exists());
+
+        // fails once the spilled runs exceed the spill limit
+        let mut overlay = StateOverlay::new(0, ACCOUNT_RECORD_SIZE as u64);
+        assert!(overlay.apply_bundle(&blocks[0]).is_err());
+    }
+
+    #[test]
+    fn apply_cached_changes() {
+        let changed = Address::with_last_byte(1);
+        let read = Address::with_last_byte(2);
+
+        let mut db = CacheDB::new(EmptyDB::default());
+        let mut account = EvmAccount::from(AccountInfo { nonce: 1, ..Default::default() });
+        account.mark_touch();
+        account
+            .storage
+            .insert(U256::from(1), EvmStorageSlot::new_changed(U256::ZERO, U256::from(10)));
+        db.commit(HashMap::from_iter([(changed, account)]));
+        db.basic(read).unwrap();
+
+        let mut overlay = StateOverlay::new(usize::MAX, 0);
+        overlay.apply_cached_changes(&db.accounts, &db.contracts).unwrap();
+        assert_eq!(overlay.account(&changed).unwrap().unwrap().unwrap().nonce, 1);
+        assert_eq!(
+            overlay.storage(&changed, &B256::with_last_byte(1)).unwrap(),
+            Some(U256::from(10))
+        );
+        assert_eq!(overlay.account(&read).unwrap(), Some(None));
+    }
+}
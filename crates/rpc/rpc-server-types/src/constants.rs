@@ -48,6 +48,42 @@ pub const DEFAULT_ENGINE_API_IPC_ENDPOINT: &str = "/tmp/reth_engine_api.ipc";
 /// The default limit for blocks count in `eth_simulateV1`.
 pub const DEFAULT_MAX_SIMULATE_BLOCKS: u64 = 256;
 
+/// The maximum number of blocks a single `debug_traceBlockRange` subscription traces.
+pub const MAX_TRACE_BLOCK_RANGE: u64 = 10_000;
+
+/// The maximum size in bytes of a `debug_executionWitness` response, larger witnesses have to be
+/// streamed with `debug_executionWitnessChunks`.
+pub const MAX_EXECUTION_WITNESS_SIZE: usize = 256 * 1024 * 1024;
+
+/// The maximum size in bytes of a chunk of the `debug_executionWitnessChunks` subscription.
+pub const EXECUTION_WITNESS_CHUNK_SIZE: usize = 4 * 1024 * 1024;
+
+/// The maximum number of storage slots a single `debug_storageRangeAt` request returns.
+pub const MAX_STORAGE_RANGE_RESULTS: u64 = 1024;
+
+/// The default maximum total size in bytes of the call data of the transaction requests of a
+/// request.
+pub const DEFAULT_MAX_CALL_DATA_SIZE: usize = 4 * 1024 * 1024;
+
+/// The default maximum total number of access list entries of the transaction requests of a
+/// request.
+pub const DEFAULT_MAX_ACCESS_LIST_ENTRIES: usize = 10_000;
+
+/// The default maximum total number of accounts in the state overrides of a request.
+pub const DEFAULT_MAX_STATE_OVERRIDE_ACCOUNTS: usize = 10_000;
+
+/// The default maximum number of transactions in a bundle of `eth_callBundle` and
+/// `eth_sendBundle`.
+pub const DEFAULT_MAX_BUNDLE_TXS: usize = 100;
+
+/// The default maximum number of blocks the node may be behind its sync target, for transactions
+/// submitted via RPC to be accepted, if rejecting transactions while syncing is enabled.
+pub const DEFAULT_MAX_TX_SYNC_DISTANCE: u64 = 64;
+
+/// The default number of consecutive pings a WS client may leave unanswered before it's
+/// disconnected.
+pub const DEFAULT_WS_MAX_MISSED_PONGS: usize = 2;
+
 /// The default eth historical proof window.
 pub const DEFAULT_ETH_PROOF_WINDOW: u64 = 0;
 
@@ -118,4 +154,13 @@ pub mod cache {
 
     /// Default number of concurrent database requests.
     pub const DEFAULT_CONCURRENT_DB_REQUESTS: usize = 512;
+
+    /// Default cache size for the accounts of the latest state: 1024 accounts.
+    pub const DEFAULT_ACCOUNT_CACHE_MAX_LEN: u32 = 1024;
+
+    /// Default cache size for the storage slots of the latest state: 8192 slots.
+    pub const DEFAULT_STORAGE_CACHE_MAX_LEN: u32 = 8192;
+
+    /// Default cache size for the serialized RPC objects of mined transactions: 64 blocks.
+    pub const DEFAULT_TX_JSON_CACHE_MAX_BLOCKS: u32 = 64;
 }
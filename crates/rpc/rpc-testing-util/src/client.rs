@@ -0,0 +1,48 @@
+//! Typed clients of the RPC namespaces of an Ethereum node.
+//!
+//! The clients are generated from the `#[rpc]` traits the server implements, so calls are checked
+//! against the server signatures at compile time. Clients of namespaces that are generic over
+//! their RPC types are pinned to the Ethereum types here, so their methods can be called directly
+//! on a client bound by them:
+//!
+//! ```no_run
+//! use alloy_eips::BlockNumberOrTag;
+//! use reth_rpc_api_testing_util::client::{DebugApiClient, EthereumEthApiClient};
+//!
+//! async fn latest_block<C: EthereumEthApiClient + DebugApiClient + Sync>(client: &C) {
+//!     let block = client.block_by_number(BlockNumberOrTag::Latest, false).await.unwrap();
+//!     let raw_header = client.raw_header(BlockNumberOrTag::Latest.into()).await.unwrap();
+//!     assert_eq!(block.is_some(), !raw_header.is_empty());
+//! }
+//! ```
+
+use alloy_rpc_types_eth::{Block, Header, Transaction, TransactionReceipt};
+use reth_ethereum_engine_primitives::EthEngineTypes;
+
+pub use reth_rpc_api::clients::*;
+
+/// [`EthApiClient`] of the `eth` namespace with the Ethereum RPC types.
+pub trait EthereumEthApiClient:
+    EthApiClient<Transaction, Block, TransactionReceipt, Header>
+{
+}
+
+impl<T> EthereumEthApiClient for T where
+    T: EthApiClient<Transaction, Block, TransactionReceipt, Header>
+{
+}
+
+/// [`EthFilterApiClient`] of the `eth` filter methods with the Ethereum RPC types.
+pub trait EthereumEthFilterApiClient: EthFilterApiClient<Transaction> {}
+
+impl<T> EthereumEthFilterApiClient for T where T: EthFilterApiClient<Transaction> {}
+
+/// [`RethApiClient`] of the `reth` namespace with the Ethereum RPC types.
+pub trait EthereumRethApiClient: RethApiClient<Header> {}
+
+impl<T> EthereumRethApiClient for T where T: RethApiClient<Header> {}
+
+/// [`EngineApiClient`] of the authenticated `engine` namespace with the Ethereum engine types.
+pub trait EthereumEngineApiClient: EngineApiClient<EthEngineTypes> {}
+
+impl<T> EthereumEngineApiClient for T where T: EngineApiClient<EthEngineTypes> {}
@@ -399,6 +399,7 @@ impl<T: TraceApiClient + Sync> TraceApiExt for T {
                     request.block_id,
                     request.state_overrides.clone(),
                     request.block_overrides.clone(),
+                    None,
                 )
                 .await
             {
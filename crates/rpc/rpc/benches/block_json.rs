@@ -0,0 +1,54 @@
+#![allow(missing_docs)]
+use alloy_rpc_types_eth::BlockTransactionsKind;
+use criterion::*;
+use reth_primitives::SealedBlockWithSenders;
+use reth_rpc::eth::EthTxBuilder;
+use reth_rpc_eth_types::{cache::tx_json::from_block_with_tx_json, TransactionJsonCache};
+use reth_rpc_types_compat::block::from_block;
+use reth_testing_utils::generators::{self, random_block, random_signed_tx, BlockParams};
+
+criterion_group!(benches, block_json);
+criterion_main!(benches);
+
+/// Serializes a full block of 300 transactions repeatedly, converting and serializing every
+/// transaction on each request, and splicing the serialized transactions of a warm cache.
+fn block_json(c: &mut Criterion) {
+    let mut rng = generators::rng();
+    let mut block =
+        random_block(&mut rng, 1, BlockParams { tx_count: Some(0), ..Default::default() });
+    block.body.transactions = (0..300).map(|_| random_signed_tx(&mut rng)).collect();
+    let block: SealedBlockWithSenders = block.seal_with_senders().unwrap();
+
+    let mut group = c.benchmark_group("Full Block JSON");
+
+    group.bench_function("direct", |b| {
+        b.iter(|| {
+            let rpc_block = from_block(
+                block.clone().unseal(),
+                BlockTransactionsKind::Full,
+                Some(block.hash()),
+                &EthTxBuilder,
+            )
+            .unwrap();
+            serde_json::to_vec(&rpc_block).unwrap()
+        })
+    });
+
+    let cache = TransactionJsonCache::new(16);
+    from_block_with_tx_json(&block, BlockTransactionsKind::Full, &EthTxBuilder, Some(&cache))
+        .unwrap();
+    group.bench_function("cached", |b| {
+        b.iter(|| {
+            let rpc_block = from_block_with_tx_json(
+                &block,
+                BlockTransactionsKind::Full,
+                &EthTxBuilder,
+                Some(&cache),
+            )
+            .unwrap();
+            serde_json::to_vec(&rpc_block).unwrap()
+        })
+    });
+
+    group.finish();
+}
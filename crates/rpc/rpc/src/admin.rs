@@ -8,11 +8,11 @@ use alloy_rpc_types_admin::{
 use async_trait::async_trait;
 use jsonrpsee::core::RpcResult;
 use reth_chainspec::{EthChainSpec, EthereumHardforks, ForkCondition};
-use reth_network_api::{NetworkInfo, Peers};
+use reth_network_api::{NetworkInfo, PeerStats, Peers};
 use reth_network_peers::{id2pk, AnyNode, NodeRecord};
 use reth_network_types::PeerKind;
 use reth_primitives::EthereumHardfork;
-use reth_rpc_api::AdminApiServer;
+use reth_rpc_api::{AdminApiServer, AdminPeerInfo};
 use reth_rpc_server_types::ToRpcResult;
 
 /// `admin` API implementation.
@@ -39,8 +39,16 @@ where
     ChainSpec: EthChainSpec + EthereumHardforks + Send + Sync + 'static,
 {
     /// Handler for `admin_addPeer`
+    ///
+    /// Registers the peer as a static peer, which is dialed immediately and reconnected to
+    /// whenever the session is lost.
     fn add_peer(&self, record: NodeRecord) -> RpcResult<bool> {
-        self.network.add_peer_with_udp(record.id, record.tcp_addr(), record.udp_addr());
+        self.network.connect_peer_kind(
+            record.id,
+            PeerKind::Static,
+            record.tcp_addr(),
+            Some(record.udp_addr()),
+        );
         Ok(true)
     }
 
@@ -66,13 +74,13 @@ where
     }
 
     /// Handler for `admin_peers`
-    async fn peers(&self) -> RpcResult<Vec<PeerInfo>> {
+    async fn peers(&self) -> RpcResult<Vec<AdminPeerInfo>> {
         let peers = self.network.get_all_peers().await.to_rpc_result()?;
         let mut infos = Vec::with_capacity(peers.len());
 
         for peer in peers {
             if let Ok(pk) = id2pk(peer.remote_id) {
-                infos.push(PeerInfo {
+                let info = PeerInfo {
                     id: pk.to_string(),
                     name: peer.client_version.to_string(),
                     enode: peer.enode,
@@ -97,13 +105,19 @@ where
                         snap: None,
                         other: Default::default(),
                     },
-                })
+                };
+                infos.push(AdminPeerInfo { info, reconnect: peer.reconnect })
             }
         }
 
         Ok(infos)
     }
 
+    /// Handler for `admin_peerStats`
+    async fn peer_stats(&self) -> RpcResult<Vec<PeerStats>> {
+        self.network.get_peer_stats().await.to_rpc_result()
+    }
+
     /// Handler for `admin_nodeInfo`
     async fn node_info(&self) -> RpcResult<NodeInfo> {
         let enode = self.network.local_node_record();
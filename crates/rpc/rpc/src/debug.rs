@@ -1,11 +1,10 @@
 use alloy_consensus::BlockHeader;
 use alloy_eips::{eip2718::Encodable2718, BlockId, BlockNumberOrTag};
-use alloy_primitives::{Address, Bytes, B256, U256};
+use alloy_primitives::{keccak256, map::B256HashMap, Address, BlockNumber, Bytes, B256, U256};
 use alloy_rlp::{Decodable, Encodable};
-use alloy_rpc_types_debug::ExecutionWitness;
 use alloy_rpc_types_eth::{
     state::EvmOverrides, transaction::TransactionRequest, Block as RpcBlock, BlockError, Bundle,
-    StateContext, TransactionInfo,
+    Index, StateContext, TransactionInfo,
 };
 use alloy_rpc_types_trace::geth::{
     call::FlatCallFrame, BlockTraceResult, FourByteFrame, GethDebugBuiltInTracerType,
@@ -13,7 +12,7 @@ use alloy_rpc_types_trace::geth::{
     NoopFrame, TraceResult,
 };
 use async_trait::async_trait;
-use jsonrpsee::core::RpcResult;
+use jsonrpsee::{core::RpcResult, PendingSubscriptionSink};
 use reth_chainspec::EthereumHardforks;
 use reth_evm::{
     env::EvmEnv,
@@ -23,27 +22,45 @@ use reth_evm::{
 use reth_primitives::{BlockExt, NodePrimitives, ReceiptWithBloom, SealedBlockWithSenders};
 use reth_primitives_traits::{Block as _, BlockBody, SignedTransaction};
 use reth_provider::{
-    BlockIdReader, BlockReaderIdExt, ChainSpecProvider, HeaderProvider, ProviderBlock,
-    ReceiptProviderIdExt, StateProofProvider, TransactionVariant,
+    BlockIdReader, BlockReader, BlockReaderIdExt, ChainSpecProvider, HeaderProvider, ProviderBlock,
+    ProviderError, ReceiptProviderIdExt, StateProofProvider, StorageRootProvider,
+    TransactionVariant,
 };
 use reth_revm::{database::StateProviderDatabase, witness::ExecutionWitnessRecord};
-use reth_rpc_api::DebugApiServer;
+use reth_rpc_api::{
+    BlockRangeTraceResult, DebugApiServer, OrderedExecutionWitness, StorageRangeEntry,
+    StorageRangeResult,
+};
 use reth_rpc_eth_api::{
     helpers::{EthTransactions, TraceExt},
     EthApiTypes, FromEthApiError, RpcNodeCore,
 };
-use reth_rpc_eth_types::{EthApiError, StateCacheDb};
-use reth_rpc_server_types::{result::internal_rpc_err, ToRpcResult};
+use reth_rpc_eth_types::{
+    cache::db::StateProviderTraitObjWrapper, EthApiError, StateCacheDb, StateOverlay,
+    StateOverlayProvider, DEFAULT_STATE_OVERLAY_MEMORY_BUDGET, DEFAULT_STATE_OVERLAY_SPILL_LIMIT,
+};
+use reth_rpc_server_types::{
+    constants::{
+        EXECUTION_WITNESS_CHUNK_SIZE, MAX_EXECUTION_WITNESS_SIZE, MAX_STORAGE_RANGE_RESULTS,
+        MAX_TRACE_BLOCK_RANGE,
+    },
+    result::{internal_rpc_err, invalid_params_rpc_err},
+    ToRpcResult,
+};
 use reth_tasks::pool::BlockingTaskGuard;
+use reth_trie::HashedStorage;
 use revm::{
-    db::{CacheDB, State},
+    db::{AccountState, CacheDB, State},
     primitives::{db::DatabaseCommit, BlockEnv, CfgEnvWithHandlerCfg, Env, EnvWithHandlerCfg},
 };
 use revm_inspectors::tracing::{
     FourByteInspector, MuxInspector, TracingInspector, TracingInspectorConfig, TransactionContext,
 };
 use std::sync::Arc;
-use tokio::sync::{AcquireError, OwnedSemaphorePermit};
+use tokio::sync::{mpsc, AcquireError, OwnedSemaphorePermit};
+use tokio_stream::wrappers::ReceiverStream;
+
+use crate::eth::pubsub::pipe_from_stream;
 
 /// `debug` API implementation.
 ///
@@ -103,49 +120,70 @@ where
         let this = self.clone();
         self.eth_api()
             .spawn_with_state_at_block(block.parent_hash().into(), move |state| {
-                let mut results = Vec::with_capacity(block.body.transactions().len());
                 let mut db = CacheDB::new(StateProviderDatabase::new(state));
+                this.trace_block_with_db(&block, &cfg, &block_env, &opts, &mut db, false)
+            })
+            .await
+    }
 
-                this.eth_api().apply_pre_execution_changes(&block, &mut db, &cfg, &block_env)?;
+    /// Traces all transactions of the block on top of the given database, which must hold the
+    /// state of the parent of the block.
+    ///
+    /// If `commit_block` is set, the state changes of all transactions and the post execution
+    /// changes of the block are committed to the database, leaving it at the state of the block.
+    fn trace_block_with_db(
+        &self,
+        block: &SealedBlockWithSenders<ProviderBlock<Eth::Provider>>,
+        cfg: &CfgEnvWithHandlerCfg,
+        block_env: &BlockEnv,
+        opts: &GethDebugTracingOptions,
+        db: &mut StateCacheDb<'_>,
+        commit_block: bool,
+    ) -> Result<Vec<TraceResult>, Eth::Error> {
+        let mut results = Vec::with_capacity(block.body.transactions().len());
 
-                let mut transactions = block.transactions_with_sender().enumerate().peekable();
-                let mut inspector = None;
-                while let Some((index, (signer, tx))) = transactions.next() {
-                    let tx_hash = *tx.tx_hash();
+        self.eth_api().apply_pre_execution_changes(block, db, cfg, block_env)?;
 
-                    let env = EnvWithHandlerCfg {
-                        env: Env::boxed(
-                            cfg.cfg_env.clone(),
-                            block_env.clone(),
-                            this.eth_api().evm_config().tx_env(tx, *signer),
-                        ),
-                        handler_cfg: cfg.handler_cfg,
-                    };
-                    let (result, state_changes) = this.trace_transaction(
-                        &opts,
-                        env,
-                        &mut db,
-                        Some(TransactionContext {
-                            block_hash: Some(block.hash()),
-                            tx_hash: Some(tx_hash),
-                            tx_index: Some(index),
-                        }),
-                        &mut inspector,
-                    )?;
+        let mut transactions = block.transactions_with_sender().enumerate().peekable();
+        let mut inspector = None;
+        while let Some((index, (signer, tx))) = transactions.next() {
+            let tx_hash = *tx.tx_hash();
 
-                    inspector = inspector.map(|insp| insp.fused());
+            let env = EnvWithHandlerCfg {
+                env: Env::boxed(
+                    cfg.cfg_env.clone(),
+                    block_env.clone(),
+                    self.eth_api().evm_config().tx_env(tx, *signer),
+                ),
+                handler_cfg: cfg.handler_cfg,
+            };
+            let (result, state_changes) = self.trace_transaction(
+                opts,
+                env,
+                db,
+                Some(TransactionContext {
+                    block_hash: Some(block.hash()),
+                    tx_hash: Some(tx_hash),
+                    tx_index: Some(index),
+                }),
+                &mut inspector,
+            )?;
+
+            inspector = inspector.map(|insp| insp.fused());
+
+            results.push(TraceResult::Success { result, tx_hash: Some(tx_hash) });
+            if commit_block || transactions.peek().is_some() {
+                // need to apply the state changes of this transaction before executing the
+                // next transaction
+                db.commit(state_changes)
+            }
+        }
 
-                    results.push(TraceResult::Success { result, tx_hash: Some(tx_hash) });
-                    if transactions.peek().is_some() {
-                        // need to apply the state changes of this transaction before executing the
-                        // next transaction
-                        db.commit(state_changes)
-                    }
-                }
+        if commit_block {
+            self.eth_api().apply_post_execution_changes(block, db, cfg, block_env)?;
+        }
 
-                Ok(results)
-            })
-            .await
+        Ok(results)
     }
 
     /// Replays the given block and returns the trace of each transaction.
@@ -223,6 +261,98 @@ where
         self.trace_block(block, cfg_env_with_handler_cfg, block_env, opts).await
     }
 
+    /// Traces the blocks in the inclusive range `[start, end]` and sends the traces of each block
+    /// to the given channel.
+    ///
+    /// The historical state is only opened once, for the parent of `start`, and every block is only
+    /// executed once: the state changes of the traced transactions and the post execution changes
+    /// of every block are accumulated in a [`StateOverlay`] the following blocks are traced on.
+    /// The overlay spills to a temporary directory once it exceeds `memory_budget` bytes, and
+    /// fails once it would spill more than `spill_limit` bytes.
+    ///
+    /// Stops early if the receiver of the channel is dropped. Fails if the range spans more than
+    /// [`MAX_TRACE_BLOCK_RANGE`] blocks, or crosses the DAO hardfork block whose irregular state
+    /// change can't be carried forward.
+    pub async fn debug_trace_block_range(
+        &self,
+        start: BlockNumber,
+        end: BlockNumber,
+        opts: GethDebugTracingOptions,
+        memory_budget: usize,
+        spill_limit: u64,
+        tx: mpsc::Sender<BlockRangeTraceResult>,
+    ) -> Result<(), Eth::Error> {
+        let parent = start.checked_sub(1).ok_or_else(|| {
+            EthApiError::InvalidParams("the genesis block can't be traced".to_string())
+        })?;
+        if end < start {
+            return Err(
+                EthApiError::InvalidParams("start block is after end block".to_string()).into()
+            )
+        }
+        if end - start >= MAX_TRACE_BLOCK_RANGE {
+            return Err(EthApiError::InvalidParams(format!(
+                "block range is larger than {MAX_TRACE_BLOCK_RANGE} blocks"
+            ))
+            .into())
+        }
+
+        let this = self.clone();
+        self.eth_api()
+            .spawn_with_state_at_block(parent.into(), move |state| {
+                let mut overlay = StateOverlay::new(memory_budget, spill_limit);
+
+                for number in start..=end {
+                    let block = this
+                        .provider()
+                        .sealed_block_with_senders(number.into(), TransactionVariant::WithHash)
+                        .map_err(Eth::Error::from_eth_err)?
+                        .ok_or(EthApiError::HeaderNotFound(number.into()))?;
+                    let EvmEnv { cfg_env_with_handler_cfg, block_env } =
+                        this.eth_api().evm_config().cfg_and_block_env(block.header());
+
+                    // the state of the last block isn't needed
+                    let commit_block = number < end;
+
+                    let provider = StateOverlayProvider::new(&state, &overlay);
+                    let mut db = CacheDB::new(StateProviderDatabase::new(
+                        StateProviderTraitObjWrapper(&provider),
+                    ));
+                    let traces = this.trace_block_with_db(
+                        &block,
+                        &cfg_env_with_handler_cfg,
+                        &block_env,
+                        &opts,
+                        &mut db,
+                        commit_block,
+                    )?;
+
+                    let result = BlockRangeTraceResult {
+                        block_number: number,
+                        block_hash: block.hash(),
+                        traces,
+                    };
+                    if tx.blocking_send(result).is_err() {
+                        // the subscriber is gone
+                        break
+                    }
+
+                    if commit_block {
+                        // advance the overlay to the state of the block
+                        let CacheDB { accounts, contracts, .. } = db;
+                        overlay.apply_cached_changes(&accounts, &contracts).map_err(|err| {
+                            EthApiError::Internal(
+                                ProviderError::FsPathError(err.to_string()).into(),
+                            )
+                        })?;
+                    }
+                }
+
+                Ok(())
+            })
+            .await
+    }
+
     /// Trace the transaction according to the provided options.
     ///
     /// Ref: <https://geth.ethereum.org/docs/developers/evm-tracing/built-in-tracers>
@@ -297,6 +427,9 @@ where
     /// The `debug_traceCall` method lets you run an `eth_call` within the context of the given
     /// block execution using the final state of parent block as the base.
     ///
+    /// If a transaction index is given, the call is executed right before the transaction at that
+    /// index of the block instead.
+    ///
     /// Differences compare to `eth_call`:
     ///  - `debug_traceCall` executes with __enabled__ basefee check, `eth_call` does not: <https://github.com/paradigmxyz/reth/issues/6240>
     pub async fn debug_trace_call(
@@ -304,6 +437,7 @@ where
         call: TransactionRequest,
         block_id: Option<BlockId>,
         opts: GethDebugTracingCallOptions,
+        tx_index: Option<usize>,
     ) -> Result<GethTrace, Eth::Error> {
         let at = block_id.unwrap_or_default();
         let GethDebugTracingCallOptions { tracing_options, state_overrides, block_overrides } =
@@ -319,10 +453,16 @@ where
                         let mut inspector = FourByteInspector::default();
                         let inspector = self
                             .eth_api()
-                            .spawn_with_call_at(call, at, overrides, move |db, env| {
-                                this.eth_api().inspect(db, env, &mut inspector)?;
-                                Ok(inspector)
-                            })
+                            .spawn_with_call_at_transaction(
+                                call,
+                                at,
+                                tx_index,
+                                overrides,
+                                move |db, env| {
+                                    this.eth_api().inspect(db, env, &mut inspector)?;
+                                    Ok(inspector)
+                                },
+                            )
                             .await?;
                         return Ok(FourByteFrame::from(&inspector).into())
                     }
@@ -337,14 +477,21 @@ where
 
                         let frame = self
                             .eth_api()
-                            .spawn_with_call_at(call, at, overrides, move |db, env| {
-                                let (res, env) = this.eth_api().inspect(db, env, &mut inspector)?;
-                                let frame = inspector
-                                    .with_transaction_gas_limit(env.tx.gas_limit)
-                                    .into_geth_builder()
-                                    .geth_call_traces(call_config, res.result.gas_used());
-                                Ok(frame.into())
-                            })
+                            .spawn_with_call_at_transaction(
+                                call,
+                                at,
+                                tx_index,
+                                overrides,
+                                move |db, env| {
+                                    let (res, env) =
+                                        this.eth_api().inspect(db, env, &mut inspector)?;
+                                    let frame = inspector
+                                        .with_transaction_gas_limit(env.tx.gas_limit)
+                                        .into_geth_builder()
+                                        .geth_call_traces(call_config, res.result.gas_used());
+                                    Ok(frame.into())
+                                },
+                            )
                             .await?;
                         return Ok(frame)
                     }
@@ -358,20 +505,26 @@ where
 
                         let frame = self
                             .eth_api()
-                            .spawn_with_call_at(call, at, overrides, move |db, env| {
-                                // wrapper is hack to get around 'higher-ranked lifetime error',
-                                // see <https://github.com/rust-lang/rust/issues/100013>
-                                let db = db.0;
-
-                                let (res, env) =
-                                    this.eth_api().inspect(&mut *db, env, &mut inspector)?;
-                                let frame = inspector
-                                    .with_transaction_gas_limit(env.tx.gas_limit)
-                                    .into_geth_builder()
-                                    .geth_prestate_traces(&res, &prestate_config, db)
-                                    .map_err(Eth::Error::from_eth_err)?;
-                                Ok(frame)
-                            })
+                            .spawn_with_call_at_transaction(
+                                call,
+                                at,
+                                tx_index,
+                                overrides,
+                                move |db, env| {
+                                    // wrapper is hack to get around 'higher-ranked lifetime error',
+                                    // see <https://github.com/rust-lang/rust/issues/100013>
+                                    let db = db.0;
+
+                                    let (res, env) =
+                                        this.eth_api().inspect(&mut *db, env, &mut inspector)?;
+                                    let frame = inspector
+                                        .with_transaction_gas_limit(env.tx.gas_limit)
+                                        .into_geth_builder()
+                                        .geth_prestate_traces(&res, &prestate_config, db)
+                                        .map_err(Eth::Error::from_eth_err)?;
+                                    Ok(frame)
+                                },
+                            )
                             .await?;
                         return Ok(frame.into())
                     }
@@ -387,30 +540,36 @@ where
                         let frame = self
                             .inner
                             .eth_api
-                            .spawn_with_call_at(call, at, overrides, move |db, env| {
-                                // wrapper is hack to get around 'higher-ranked lifetime error', see
-                                // <https://github.com/rust-lang/rust/issues/100013>
-                                let db = db.0;
-
-                                let tx_info = TransactionInfo {
-                                    block_number: Some(
-                                        env.block.number.try_into().unwrap_or_default(),
-                                    ),
-                                    base_fee: Some(
-                                        env.block.basefee.try_into().unwrap_or_default(),
-                                    ),
-                                    hash: None,
-                                    block_hash: None,
-                                    index: None,
-                                };
-
-                                let (res, _) =
-                                    this.eth_api().inspect(&mut *db, env, &mut inspector)?;
-                                let frame = inspector
-                                    .try_into_mux_frame(&res, db, tx_info)
-                                    .map_err(Eth::Error::from_eth_err)?;
-                                Ok(frame.into())
-                            })
+                            .spawn_with_call_at_transaction(
+                                call,
+                                at,
+                                tx_index,
+                                overrides,
+                                move |db, env| {
+                                    // wrapper is hack to get around 'higher-ranked lifetime error',
+                                    // see <https://github.com/rust-lang/rust/issues/100013>
+                                    let db = db.0;
+
+                                    let tx_info = TransactionInfo {
+                                        block_number: Some(
+                                            env.block.number.try_into().unwrap_or_default(),
+                                        ),
+                                        base_fee: Some(
+                                            env.block.basefee.try_into().unwrap_or_default(),
+                                        ),
+                                        hash: None,
+                                        block_hash: None,
+                                        index: None,
+                                    };
+
+                                    let (res, _) =
+                                        this.eth_api().inspect(&mut *db, env, &mut inspector)?;
+                                    let frame = inspector
+                                        .try_into_mux_frame(&res, db, tx_info)
+                                        .map_err(Eth::Error::from_eth_err)?;
+                                    Ok(frame.into())
+                                },
+                            )
                             .await?;
                         return Ok(frame)
                     }
@@ -426,16 +585,22 @@ where
                         let frame: FlatCallFrame = self
                             .inner
                             .eth_api
-                            .spawn_with_call_at(call, at, overrides, move |db, env| {
-                                let (_res, env) =
-                                    this.eth_api().inspect(db, env, &mut inspector)?;
-                                let tx_info = TransactionInfo::default();
-                                let frame: FlatCallFrame = inspector
-                                    .with_transaction_gas_limit(env.tx.gas_limit)
-                                    .into_parity_builder()
-                                    .into_localized_transaction_traces(tx_info);
-                                Ok(frame)
-                            })
+                            .spawn_with_call_at_transaction(
+                                call,
+                                at,
+                                tx_index,
+                                overrides,
+                                move |db, env| {
+                                    let (_res, env) =
+                                        this.eth_api().inspect(db, env, &mut inspector)?;
+                                    let tx_info = TransactionInfo::default();
+                                    let frame: FlatCallFrame = inspector
+                                        .with_transaction_gas_limit(env.tx.gas_limit)
+                                        .into_parity_builder()
+                                        .into_localized_transaction_traces(tx_info);
+                                    Ok(frame)
+                                },
+                            )
                             .await?;
 
                         return Ok(frame.into());
@@ -453,18 +618,29 @@ where
 
                     let res = self
                         .eth_api()
-                        .spawn_with_call_at(call, at, overrides, move |db, env| {
-                            // wrapper is hack to get around 'higher-ranked lifetime error', see
-                            // <https://github.com/rust-lang/rust/issues/100013>
-                            let db = db.0;
-
-                            let mut inspector =
-                                revm_inspectors::tracing::js::JsInspector::new(code, config)
-                                    .map_err(Eth::Error::from_eth_err)?;
-                            let (res, _) =
-                                this.eth_api().inspect(&mut *db, env.clone(), &mut inspector)?;
-                            inspector.json_result(res, &env, db).map_err(Eth::Error::from_eth_err)
-                        })
+                        .spawn_with_call_at_transaction(
+                            call,
+                            at,
+                            tx_index,
+                            overrides,
+                            move |db, env| {
+                                // wrapper is hack to get around 'higher-ranked lifetime error', see
+                                // <https://github.com/rust-lang/rust/issues/100013>
+                                let db = db.0;
+
+                                let mut inspector =
+                                    revm_inspectors::tracing::js::JsInspector::new(code, config)
+                                        .map_err(Eth::Error::from_eth_err)?;
+                                let (res, _) = this.eth_api().inspect(
+                                    &mut *db,
+                                    env.clone(),
+                                    &mut inspector,
+                                )?;
+                                inspector
+                                    .json_result(res, &env, db)
+                                    .map_err(Eth::Error::from_eth_err)
+                            },
+                        )
                         .await?;
 
                     Ok(GethTrace::JS(res))
@@ -479,7 +655,7 @@ where
 
         let (res, tx_gas_limit, inspector) = self
             .eth_api()
-            .spawn_with_call_at(call, at, overrides, move |db, env| {
+            .spawn_with_call_at_transaction(call, at, tx_index, overrides, move |db, env| {
                 let (res, env) = this.eth_api().inspect(db, env, &mut inspector)?;
                 Ok((res, env.tx.gas_limit, inspector))
             })
@@ -619,10 +795,13 @@ where
     /// generating an execution witness. The witness comprises of a map of all hashed trie nodes
     /// to their preimages that were required during the execution of the block, including during
     /// state root recomputation.
+    ///
+    /// The block can be re-executed using only the witness with
+    /// [`WitnessState`](reth_revm::witness::WitnessState), which reproduces its state root.
     pub async fn debug_execution_witness(
         &self,
         block_id: BlockNumberOrTag,
-    ) -> Result<ExecutionWitness, Eth::Error> {
+    ) -> Result<OrderedExecutionWitness, Eth::Error> {
         let this = self.clone();
         let block = this
             .eth_api()
@@ -647,7 +826,108 @@ where
 
                 let state =
                     state_provider.witness(Default::default(), hashed_state).map_err(Into::into)?;
-                Ok(ExecutionWitness { state: state.into_iter().collect(), codes, keys })
+                Ok(OrderedExecutionWitness {
+                    state: state.into_iter().collect(),
+                    codes: codes.into_iter().collect(),
+                    keys: keys.into_iter().collect(),
+                })
+            })
+            .await
+    }
+
+    /// Returns up to `max_result` storage slots of the contract as of right before the
+    /// transaction with the given index of the block was executed, ordered by hashed slot and
+    /// starting at the hashed slot `key_start`.
+    ///
+    /// At most [`MAX_STORAGE_RANGE_RESULTS`] slots are returned, the remaining ones can be fetched
+    /// starting at the returned next key.
+    ///
+    /// The preimages of the returned slots are only known for slots the preceding transactions of
+    /// the block accessed.
+    pub async fn debug_storage_range_at(
+        &self,
+        block_hash: B256,
+        tx_idx: usize,
+        contract_address: Address,
+        key_start: B256,
+        max_result: u64,
+    ) -> Result<StorageRangeResult, Eth::Error> {
+        let block = self
+            .eth_api()
+            .block_with_senders(block_hash.into())
+            .await?
+            .ok_or(EthApiError::HeaderNotFound(block_hash.into()))?;
+        let transactions = block.body.transactions();
+        if tx_idx > 0 && tx_idx >= transactions.len() {
+            return Err(Eth::Error::from_eth_err(EthApiError::InvalidParams(format!(
+                "transaction index {tx_idx} out of range for block {block_hash}"
+            ))))
+        }
+        // an empty block has no transaction to replay up to
+        let target_tx_hash = transactions.get(tx_idx).map(|tx| *tx.tx_hash()).unwrap_or_default();
+
+        let (evm_env, _) = self.eth_api().evm_env_at(block_hash.into()).await?;
+        let EvmEnv { cfg_env_with_handler_cfg, block_env } = evm_env;
+
+        let this = self.clone();
+        self.eth_api()
+            .spawn_with_state_at_block(block.parent_hash().into(), move |state_provider| {
+                let mut db = CacheDB::new(StateProviderDatabase::new(&state_provider));
+
+                this.eth_api().apply_pre_execution_changes(
+                    &block,
+                    &mut db,
+                    &cfg_env_with_handler_cfg,
+                    &block_env,
+                )?;
+                this.eth_api().replay_transactions_until(
+                    &mut db,
+                    cfg_env_with_handler_cfg,
+                    block_env,
+                    block.transactions_with_sender(),
+                    target_tx_hash,
+                )?;
+
+                // apply the slots accessed by the replayed transactions on top of the state
+                let mut hashed_storage = HashedStorage::new(false);
+                let mut preimages = B256HashMap::default();
+                if let Some(account) = db.accounts.get(&contract_address) {
+                    hashed_storage.wiped = matches!(
+                        account.account_state,
+                        AccountState::StorageCleared | AccountState::NotExisting
+                    );
+                    for (slot, value) in &account.storage {
+                        let slot = B256::from(*slot);
+                        let hashed_slot = keccak256(slot);
+                        hashed_storage.storage.insert(hashed_slot, *value);
+                        preimages.insert(hashed_slot, slot);
+                    }
+                }
+
+                // fetch one more slot to know where the next page starts
+                let limit = max_result.min(MAX_STORAGE_RANGE_RESULTS) as usize;
+                let mut entries = state_provider
+                    .storage_range(
+                        contract_address,
+                        key_start..=B256::repeat_byte(0xff),
+                        limit.saturating_add(1),
+                        hashed_storage,
+                    )
+                    .map_err(Into::into)?;
+                let next_key = entries.get(limit).map(|entry| entry.key);
+                entries.truncate(limit);
+
+                let storage = entries
+                    .into_iter()
+                    .map(|entry| {
+                        let slot = StorageRangeEntry {
+                            key: preimages.get(&entry.key).copied(),
+                            value: entry.value.into(),
+                        };
+                        (entry.key, slot)
+                    })
+                    .collect();
+                Ok(StorageRangeResult { storage, next_key })
             })
             .await
     }
@@ -939,6 +1219,59 @@ where
             .map_err(Into::into)
     }
 
+    /// Handler for `debug_traceBlockRange`
+    async fn debug_trace_block_range(
+        &self,
+        pending: PendingSubscriptionSink,
+        start_block: BlockNumberOrTag,
+        end_block: BlockNumberOrTag,
+        opts: Option<GethDebugTracingOptions>,
+    ) -> jsonrpsee::core::SubscriptionResult {
+        let block_number = |block: BlockNumberOrTag| -> RpcResult<BlockNumber> {
+            self.provider()
+                .convert_block_number(block)
+                .to_rpc_result()?
+                .ok_or_else(|| EthApiError::HeaderNotFound(block.into()).into())
+        };
+        let (start, end) = match block_number(start_block)
+            .and_then(|start| Ok((start, block_number(end_block)?)))
+        {
+            Ok((start, end)) if start <= end => (start, end),
+            Ok(_) => {
+                pending.reject(invalid_params_rpc_err("start block is after end block")).await;
+                return Ok(())
+            }
+            Err(err) => {
+                pending.reject(err).await;
+                return Ok(())
+            }
+        };
+
+        let _permit = self.acquire_trace_permit().await;
+        let sink = pending.accept().await?;
+
+        // dropping the receiver once the subscriber is gone stops the tracing
+        let (tx, rx) = mpsc::channel(1);
+        let (traced, piped) = futures::join!(
+            Self::debug_trace_block_range(
+                self,
+                start,
+                end,
+                opts.unwrap_or_default(),
+                DEFAULT_STATE_OVERLAY_MEMORY_BUDGET,
+                DEFAULT_STATE_OVERLAY_SPILL_LIMIT,
+                tx,
+            ),
+            pipe_from_stream(sink, ReceiverStream::new(rx)),
+        );
+        // the subscription is closed with an error notification if tracing a block failed, which
+        // also ends the stream of traces
+        traced?;
+        piped?;
+
+        Ok(())
+    }
+
     /// Handler for `debug_traceTransaction`
     async fn debug_trace_transaction(
         &self,
@@ -957,11 +1290,18 @@ where
         request: TransactionRequest,
         block_id: Option<BlockId>,
         opts: Option<GethDebugTracingCallOptions>,
+        tx_index: Option<Index>,
     ) -> RpcResult<GethTrace> {
         let _permit = self.acquire_trace_permit().await;
-        Self::debug_trace_call(self, request, block_id, opts.unwrap_or_default())
-            .await
-            .map_err(Into::into)
+        Self::debug_trace_call(
+            self,
+            request,
+            block_id,
+            opts.unwrap_or_default(),
+            tx_index.map(Into::into),
+        )
+        .await
+        .map_err(Into::into)
     }
 
     async fn debug_trace_call_many(
@@ -978,9 +1318,38 @@ where
     async fn debug_execution_witness(
         &self,
         block: BlockNumberOrTag,
-    ) -> RpcResult<ExecutionWitness> {
+    ) -> RpcResult<OrderedExecutionWitness> {
         let _permit = self.acquire_trace_permit().await;
-        Self::debug_execution_witness(self, block).await.map_err(Into::into)
+        let witness = Self::debug_execution_witness(self, block).await?;
+        if witness.size() > MAX_EXECUTION_WITNESS_SIZE {
+            return Err(invalid_params_rpc_err(format!(
+                "execution witness is larger than {MAX_EXECUTION_WITNESS_SIZE} bytes, use \
+                 debug_executionWitnessChunks"
+            )))
+        }
+        Ok(witness)
+    }
+
+    /// Handler for `debug_executionWitnessChunks`
+    async fn debug_execution_witness_chunks(
+        &self,
+        pending: PendingSubscriptionSink,
+        block: BlockNumberOrTag,
+    ) -> jsonrpsee::core::SubscriptionResult {
+        let _permit = self.acquire_trace_permit().await;
+        let witness = match Self::debug_execution_witness(self, block).await {
+            Ok(witness) => witness,
+            Err(err) => {
+                pending.reject(err).await;
+                return Ok(())
+            }
+        };
+
+        let sink = pending.accept().await?;
+        let chunks = witness.into_chunks(EXECUTION_WITNESS_CHUNK_SIZE);
+        pipe_from_stream(sink, futures::stream::iter(chunks)).await?;
+
+        Ok(())
     }
 
     async fn debug_backtrace_at(&self, _location: &str) -> RpcResult<()> {
@@ -1155,15 +1524,26 @@ where
         Ok(())
     }
 
+    /// Handler for `debug_storageRangeAt`
     async fn debug_storage_range_at(
         &self,
-        _block_hash: B256,
-        _tx_idx: usize,
-        _contract_address: Address,
-        _key_start: B256,
-        _max_result: u64,
-    ) -> RpcResult<()> {
-        Ok(())
+        block_hash: B256,
+        tx_idx: usize,
+        contract_address: Address,
+        key_start: B256,
+        max_result: u64,
+    ) -> RpcResult<StorageRangeResult> {
+        let _permit = self.acquire_trace_permit().await;
+        Self::debug_storage_range_at(
+            self,
+            block_hash,
+            tx_idx,
+            contract_address,
+            key_start,
+            max_result,
+        )
+        .await
+        .map_err(Into::into)
     }
 
     async fn debug_trace_bad_block(
@@ -0,0 +1,198 @@
+use std::{future::Future, sync::Arc};
+
+use alloy_primitives::{hex, Bytes, U64};
+use async_trait::async_trait;
+use jsonrpsee::{core::RpcResult, types::ErrorObject};
+use reth_db::{tables::Tables, RawKey, RawTable, TableViewer};
+use reth_db_api::{
+    table::{Decode, Encode, Table},
+    transaction::DbTx,
+};
+use reth_provider::{
+    DBProvider, DatabaseProviderFactory, ProviderError, StaticFileProviderFactory,
+};
+use reth_rpc_api::{DbValue, DebugDbApiServer};
+use reth_rpc_server_types::result::{internal_rpc_err, invalid_params_rpc_err};
+use reth_static_file_types::StaticFileSegment;
+use reth_tasks::TaskSpawner;
+use tokio::sync::oneshot;
+
+/// The maximum length of a key of `debug_dbGet`, in bytes.
+pub const MAX_DB_DEBUG_KEY_SIZE: usize = 128;
+
+/// The maximum size of a value returned by `debug_dbGet` and `debug_staticFileGet`, in bytes.
+pub const MAX_DB_DEBUG_VALUE_SIZE: usize = 1024 * 1024;
+
+/// `debug` API implementation for raw access to the database and static files.
+///
+/// This type provides the functionality for handling the `debug_dbGet` and `debug_staticFileGet`
+/// RPC requests.
+pub struct DebugDbApi<Provider> {
+    inner: Arc<DebugDbApiInner<Provider>>,
+}
+
+// === impl DebugDbApi ===
+
+impl<Provider> DebugDbApi<Provider> {
+    /// Create a new instance of the [`DebugDbApi`]
+    pub fn new(provider: Provider, task_spawner: Box<dyn TaskSpawner>) -> Self {
+        Self { inner: Arc::new(DebugDbApiInner { provider, task_spawner }) }
+    }
+}
+
+impl<Provider> DebugDbApi<Provider>
+where
+    Provider: DatabaseProviderFactory + StaticFileProviderFactory + 'static,
+{
+    /// Executes the future on a new blocking task.
+    async fn on_blocking_task<C, F, R>(&self, c: C) -> RpcResult<R>
+    where
+        C: FnOnce(Self) -> F,
+        F: Future<Output = RpcResult<R>> + Send + 'static,
+        R: Send + 'static,
+    {
+        let (tx, rx) = oneshot::channel();
+        let this = self.clone();
+        let f = c(this);
+        self.inner.task_spawner.spawn_blocking(Box::pin(async move {
+            let res = f.await;
+            let _ = tx.send(res);
+        }));
+        rx.await.map_err(|_| internal_rpc_err("blocking task dropped"))?
+    }
+
+    /// Returns the value stored under the encoded key in the table.
+    pub fn db_get(&self, table: &str, key: &[u8]) -> RpcResult<Option<DbValue>> {
+        let table = table.parse::<Tables>().map_err(invalid_params_rpc_err)?;
+        if key.len() > MAX_DB_DEBUG_KEY_SIZE {
+            return Err(invalid_params_rpc_err(format!(
+                "key of {} bytes exceeds the maximum of {MAX_DB_DEBUG_KEY_SIZE} bytes",
+                key.len()
+            )))
+        }
+
+        let provider = self.inner.provider.database_provider_ro().map_err(internal_error)?;
+        table.view(&DbGetViewer { provider: &provider, key })
+    }
+
+    /// Returns the columns of the row of the static file segment.
+    pub fn static_file_get(&self, segment: &str, number: u64) -> RpcResult<Option<Vec<Bytes>>> {
+        let segment = segment.parse::<StaticFileSegment>().map_err(|_| {
+            invalid_params_rpc_err(format!("unknown static file segment: {segment:?}"))
+        })?;
+
+        let static_file_provider = self.inner.provider.static_file_provider();
+        let jar_provider = match segment {
+            StaticFileSegment::Headers => {
+                static_file_provider.get_segment_provider_from_block(segment, number, None)
+            }
+            StaticFileSegment::Transactions | StaticFileSegment::Receipts => {
+                static_file_provider.get_segment_provider_from_transaction(segment, number, None)
+            }
+        };
+        let jar_provider = match jar_provider {
+            Ok(jar_provider) => jar_provider,
+            Err(
+                ProviderError::MissingStaticFileBlock(..) | ProviderError::MissingStaticFileTx(..),
+            ) => return Ok(None),
+            Err(err) => return Err(internal_error(err)),
+        };
+
+        // select all columns of the segment
+        let mask = (1 << segment.columns()) - 1;
+        let mut cursor = jar_provider.cursor().map_err(internal_error)?;
+        let Some(columns) = cursor.get(number.into(), mask).map_err(internal_error)? else {
+            return Ok(None)
+        };
+
+        check_value_size(columns.iter().map(|column| column.len()).sum())?;
+        Ok(Some(columns.into_iter().map(Bytes::copy_from_slice).collect()))
+    }
+}
+
+#[async_trait]
+impl<Provider> DebugDbApiServer for DebugDbApi<Provider>
+where
+    Provider: DatabaseProviderFactory + StaticFileProviderFactory + 'static,
+{
+    /// Handler for `debug_dbGet`
+    async fn db_get(&self, table: String, key: Bytes) -> RpcResult<Option<DbValue>> {
+        self.on_blocking_task(|this| async move { this.db_get(&table, &key) }).await
+    }
+
+    /// Handler for `debug_staticFileGet`
+    async fn static_file_get(&self, segment: String, number: U64) -> RpcResult<Option<Vec<Bytes>>> {
+        self.on_blocking_task(|this| async move { this.static_file_get(&segment, number.to()) })
+            .await
+    }
+}
+
+impl<Provider> std::fmt::Debug for DebugDbApi<Provider> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DebugDbApi").finish_non_exhaustive()
+    }
+}
+
+impl<Provider> Clone for DebugDbApi<Provider> {
+    fn clone(&self) -> Self {
+        Self { inner: Arc::clone(&self.inner) }
+    }
+}
+
+struct DebugDbApiInner<Provider> {
+    /// The provider that can interact with the database and static files.
+    provider: Provider,
+    /// The type that can spawn tasks which would otherwise block.
+    task_spawner: Box<dyn TaskSpawner>,
+}
+
+/// Reads the value of an encoded key from the table it's viewed with.
+struct DbGetViewer<'a, Provider> {
+    provider: &'a Provider,
+    key: &'a [u8],
+}
+
+impl<Provider: DBProvider> TableViewer<Option<DbValue>> for DbGetViewer<'_, Provider> {
+    type Error = ErrorObject<'static>;
+
+    fn view<T: Table>(&self) -> Result<Option<DbValue>, Self::Error> {
+        // the key must be exactly the encoding of a key of the table
+        let is_valid_key =
+            T::Key::decode(self.key).is_ok_and(|key| key.encode().as_ref() == self.key);
+        if !is_valid_key {
+            return Err(invalid_params_rpc_err(format!(
+                "invalid key for table {}: {}",
+                T::NAME,
+                hex::encode_prefixed(self.key)
+            )))
+        }
+
+        let Some(value) = self
+            .provider
+            .tx_ref()
+            .get::<RawTable<T>>(RawKey::from_vec(self.key.to_vec()))
+            .map_err(internal_error)?
+        else {
+            return Ok(None)
+        };
+
+        check_value_size(value.raw_value().len())?;
+        let decoded = value.value().ok().and_then(|value| serde_json::to_value(value).ok());
+        Ok(Some(DbValue { raw: Bytes::copy_from_slice(value.raw_value()), decoded }))
+    }
+}
+
+/// Returns an error if the size of a value exceeds [`MAX_DB_DEBUG_VALUE_SIZE`].
+fn check_value_size(size: usize) -> RpcResult<()> {
+    if size > MAX_DB_DEBUG_VALUE_SIZE {
+        return Err(internal_rpc_err(format!(
+            "value of {size} bytes exceeds the maximum of {MAX_DB_DEBUG_VALUE_SIZE} bytes"
+        )))
+    }
+    Ok(())
+}
+
+/// Converts a database or provider error into an internal RPC error.
+fn internal_error(err: impl std::fmt::Display) -> ErrorObject<'static> {
+    internal_rpc_err(err.to_string())
+}
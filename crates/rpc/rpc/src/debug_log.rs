@@ -0,0 +1,27 @@
+use async_trait::async_trait;
+use jsonrpsee::core::RpcResult;
+use reth_rpc_api::DebugLogApiServer;
+use reth_rpc_server_types::result::{internal_rpc_err, invalid_params_rpc_err};
+
+/// `debug` API implementation for changing the logging of the node.
+///
+/// This type provides the functionality for handling the `debug_setLogFilter` RPC request.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DebugLogApi;
+
+impl DebugLogApi {
+    /// Create a new instance of the [`DebugLogApi`]
+    pub const fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl DebugLogApiServer for DebugLogApi {
+    /// Handler for `debug_setLogFilter`
+    async fn debug_set_log_filter(&self, filter: String) -> RpcResult<()> {
+        let handle = reth_tracing::stdout_filter_handle()
+            .ok_or_else(|| internal_rpc_err("the log filter of the node can't be changed"))?;
+        handle.set_filter(&filter).map_err(|err| invalid_params_rpc_err(err.to_string()))
+    }
+}
@@ -0,0 +1,29 @@
+use async_trait::async_trait;
+use jsonrpsee::core::RpcResult;
+use reth_provider::ReorgHistory;
+use reth_rpc_api::{DebugReorgApiServer, ReorgHistoryEntry};
+
+/// `debug` API implementation for the reorgs of the canonical chain.
+///
+/// This type provides the functionality for handling the `debug_getReorgHistory` RPC request.
+#[derive(Debug, Clone, Default)]
+pub struct DebugReorgApi {
+    /// Recent reorgs of the canonical chain, if tracked by the node.
+    reorg_history: Option<ReorgHistory>,
+}
+
+impl DebugReorgApi {
+    /// Create a new instance of the [`DebugReorgApi`]
+    pub const fn new(reorg_history: Option<ReorgHistory>) -> Self {
+        Self { reorg_history }
+    }
+}
+
+#[async_trait]
+impl DebugReorgApiServer for DebugReorgApi {
+    /// Handler for `debug_getReorgHistory`
+    async fn debug_get_reorg_history(&self, count: usize) -> RpcResult<Vec<ReorgHistoryEntry>> {
+        let Some(reorg_history) = &self.reorg_history else { return Ok(Vec::new()) };
+        Ok(reorg_history.latest(count).into_iter().map(Into::into).collect())
+    }
+}
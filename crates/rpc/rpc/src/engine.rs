@@ -9,7 +9,9 @@ use jsonrpsee::core::RpcResult as Result;
 use reth_rpc_api::{EngineEthApiServer, EthApiServer, EthFilterApiServer};
 /// Re-export for convenience
 pub use reth_rpc_engine_api::EngineApi;
-use reth_rpc_eth_api::{FullEthApiTypes, RpcBlock, RpcHeader, RpcReceipt, RpcTransaction};
+use reth_rpc_eth_api::{
+    FullEthApiTypes, RpcBlockJson, RpcHeader, RpcReceipt, RpcTransaction, RpcTransactionJson,
+};
 use tracing_futures::Instrument;
 
 macro_rules! engine_span {
@@ -34,12 +36,12 @@ impl<Eth, EthFilter> EngineEthApi<Eth, EthFilter> {
 }
 
 #[async_trait::async_trait]
-impl<Eth, EthFilter> EngineEthApiServer<RpcBlock<Eth::NetworkTypes>>
+impl<Eth, EthFilter> EngineEthApiServer<RpcBlockJson<Eth::NetworkTypes>>
     for EngineEthApi<Eth, EthFilter>
 where
     Eth: EthApiServer<
-            RpcTransaction<Eth::NetworkTypes>,
-            RpcBlock<Eth::NetworkTypes>,
+            RpcTransactionJson<Eth::NetworkTypes>,
+            RpcBlockJson<Eth::NetworkTypes>,
             RpcReceipt<Eth::NetworkTypes>,
             RpcHeader<Eth::NetworkTypes>,
         > + FullEthApiTypes,
@@ -90,7 +92,7 @@ where
         &self,
         hash: B256,
         full: bool,
-    ) -> Result<Option<RpcBlock<Eth::NetworkTypes>>> {
+    ) -> Result<Option<RpcBlockJson<Eth::NetworkTypes>>> {
         self.eth.block_by_hash(hash, full).instrument(engine_span!()).await
     }
 
@@ -99,7 +101,7 @@ where
         &self,
         number: BlockNumberOrTag,
         full: bool,
-    ) -> Result<Option<RpcBlock<Eth::NetworkTypes>>> {
+    ) -> Result<Option<RpcBlockJson<Eth::NetworkTypes>>> {
         self.eth.block_by_number(number, full).instrument(engine_span!()).await
     }
 
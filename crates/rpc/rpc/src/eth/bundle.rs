@@ -1,9 +1,12 @@
 //! `Eth` bundle implementation and helpers.
 
 use alloy_consensus::{BlockHeader, Transaction as _};
-use alloy_eips::eip4844::MAX_DATA_GAS_PER_BLOCK;
-use alloy_primitives::{Keccak256, U256};
-use alloy_rpc_types_mev::{EthCallBundle, EthCallBundleResponse, EthCallBundleTransactionResult};
+use alloy_eips::{eip4844::MAX_DATA_GAS_PER_BLOCK, BlockNumberOrTag};
+use alloy_primitives::{Keccak256, TxHash, U256};
+use alloy_rpc_types_mev::{
+    EthBundleHash, EthCallBundle, EthCallBundleResponse, EthCallBundleTransactionResult,
+    EthSendBundle,
+};
 use jsonrpsee::core::RpcResult;
 use reth_chainspec::EthChainSpec;
 use reth_evm::{env::EvmEnv, ConfigureEvm, ConfigureEvmEnv};
@@ -11,20 +14,41 @@ use reth_primitives_traits::SignedTransaction;
 use reth_provider::{ChainSpecProvider, HeaderProvider};
 use reth_revm::database::StateProviderDatabase;
 use reth_rpc_eth_api::{
-    helpers::{Call, EthTransactions, LoadPendingBlock},
-    EthCallBundleApiServer, FromEthApiError, FromEvmError, RpcNodeCore,
+    helpers::{Call, EthApiSpec, EthTransactions, LoadPendingBlock},
+    EthCallBundleApiServer, EthSendBundleApiServer, FromEthApiError, FromEvmError, RpcNodeCore,
+};
+use reth_rpc_eth_types::{
+    error::RpcPoolError, utils::recover_raw_transaction, EthApiError, RpcInvalidTransactionError,
 };
-use reth_rpc_eth_types::{utils::recover_raw_transaction, EthApiError, RpcInvalidTransactionError};
+use reth_rpc_server_types::constants::DEFAULT_MAX_BUNDLE_TXS;
 use reth_tasks::pool::BlockingTaskGuard;
 use reth_transaction_pool::{
-    EthBlobTransactionSidecar, EthPoolTransaction, PoolPooledTx, PoolTransaction, TransactionPool,
+    EthBlobTransactionSidecar, EthPoolTransaction, PoolPooledTx, PoolTransaction,
+    TransactionOrigin, TransactionPool,
 };
 use revm::{
     db::{CacheDB, DatabaseCommit, DatabaseRef},
     primitives::{ResultAndState, TxEnv},
 };
 use revm_primitives::{EnvKzgSettings, EnvWithHandlerCfg, SpecId};
-use std::sync::Arc;
+use serde::{Deserialize, Serialize};
+use std::{collections::HashSet, sync::Arc};
+
+/// Configuration of the bundle endpoints of the `flashbots` namespace.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
+pub struct EthBundleConfig {
+    /// Whether the transactions of bundles sent with `eth_sendBundle` are added to the local
+    /// transaction pool. Otherwise `eth_sendBundle` is unsupported.
+    pub accept_bundles_locally: bool,
+    /// Maximum number of transactions in a bundle.
+    pub max_bundle_txs: usize,
+}
+
+impl Default for EthBundleConfig {
+    fn default() -> Self {
+        Self { accept_bundles_locally: false, max_bundle_txs: DEFAULT_MAX_BUNDLE_TXS }
+    }
+}
 
 /// `Eth` bundle implementation.
 pub struct EthBundle<Eth> {
@@ -34,8 +58,12 @@ pub struct EthBundle<Eth> {
 
 impl<Eth> EthBundle<Eth> {
     /// Create a new `EthBundle` instance.
-    pub fn new(eth_api: Eth, blocking_task_guard: BlockingTaskGuard) -> Self {
-        Self { inner: Arc::new(EthBundleInner { eth_api, blocking_task_guard }) }
+    pub fn new(
+        eth_api: Eth,
+        blocking_task_guard: BlockingTaskGuard,
+        config: EthBundleConfig,
+    ) -> Self {
+        Self { inner: Arc::new(EthBundleInner { eth_api, blocking_task_guard, config }) }
     }
 
     /// Access the underlying `Eth` API.
@@ -74,6 +102,12 @@ where
             )
             .into())
         }
+        if txs.len() > self.inner.config.max_bundle_txs {
+            return Err(EthApiError::InvalidParams(
+                EthBundleError::TooManyTransactions(self.inner.config.max_bundle_txs).to_string(),
+            )
+            .into())
+        }
         if block_number == 0 {
             return Err(EthApiError::InvalidParams(
                 EthBundleError::BundleMissingBlockNumber.to_string(),
@@ -131,6 +165,18 @@ where
             block_env.gas_limit = gas_limit;
         }
 
+        let bundle_gas_limit = transactions.iter().map(|tx| tx.gas_limit()).sum::<u64>();
+        if U256::from(bundle_gas_limit) > block_env.gas_limit {
+            return Err(EthApiError::InvalidParams(
+                EthBundleError::BundleGasLimitExceeded(
+                    bundle_gas_limit,
+                    block_env.gas_limit.saturating_to(),
+                )
+                .to_string(),
+            )
+            .into())
+        }
+
         if let Some(base_fee) = base_fee {
             block_env.basefee = U256::from(base_fee);
         } else if cfg_env_with_handler_cfg.handler_cfg.spec_id.is_enabled_in(SpecId::LONDON) {
@@ -277,6 +323,112 @@ where
     }
 }
 
+impl<Eth> EthBundle<Eth>
+where
+    Eth: EthTransactions + LoadPendingBlock + Call + EthApiSpec + 'static,
+{
+    /// Adds the transactions of a bundle to the local transaction pool, if
+    /// [`EthBundleConfig::accept_bundles_locally`] is set.
+    ///
+    /// The whole bundle is validated before any of its transactions is added: it's simulated on top
+    /// of the latest block like with `eth_callBundle`, and rejected if a transaction is invalid, is
+    /// already known, or reverts without being one of the bundle's reverting transactions. Like
+    /// other transactions submitted via RPC, bundles are rejected while the node is too far behind
+    /// its sync target.
+    ///
+    /// The transactions are added as private transactions, so they aren't propagated to peers. They
+    /// are added as a group: if any of them is still rejected by the pool, the ones that were
+    /// already added are removed again. The target block and timestamps of the bundle aren't
+    /// enforced.
+    pub async fn send_bundle(&self, bundle: EthSendBundle) -> Result<EthBundleHash, Eth::Error> {
+        if !self.inner.config.accept_bundles_locally {
+            return Err(EthApiError::Unsupported(
+                "eth_sendBundle is not supported, bundles can only be simulated with eth_callBundle",
+            )
+            .into())
+        }
+        self.eth_api().ensure_tx_sync_distance().map_err(Eth::Error::from_eth_err)?;
+
+        let EthSendBundle { txs, reverting_tx_hashes, .. } = bundle;
+        if txs.is_empty() {
+            return Err(EthApiError::InvalidParams(
+                EthBundleError::EmptyBundleTransactions.to_string(),
+            )
+            .into())
+        }
+        if txs.len() > self.inner.config.max_bundle_txs {
+            return Err(EthApiError::InvalidParams(
+                EthBundleError::TooManyTransactions(self.inner.config.max_bundle_txs).to_string(),
+            )
+            .into())
+        }
+
+        let transactions = txs
+            .iter()
+            .map(|tx| recover_raw_transaction::<PoolPooledTx<Eth::Pool>>(tx))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let pool = RpcNodeCore::pool(self.eth_api());
+        let block_info = pool.block_info();
+        let block_gas_limit = block_info.block_gas_limit;
+        let bundle_gas_limit = transactions.iter().map(|tx| tx.gas_limit()).sum::<u64>();
+        if bundle_gas_limit > block_gas_limit {
+            return Err(EthApiError::InvalidParams(
+                EthBundleError::BundleGasLimitExceeded(bundle_gas_limit, block_gas_limit)
+                    .to_string(),
+            )
+            .into())
+        }
+
+        let mut hashes = HashSet::with_capacity(transactions.len());
+        for tx in &transactions {
+            if !hashes.insert(*tx.tx_hash()) || pool.contains(tx.tx_hash()) {
+                return Err(EthApiError::PoolError(RpcPoolError::AlreadyKnown).into())
+            }
+        }
+
+        let simulation = self
+            .call_bundle(EthCallBundle {
+                txs,
+                block_number: block_info.last_seen_block_number + 1,
+                state_block_number: BlockNumberOrTag::Latest,
+                ..Default::default()
+            })
+            .await?;
+        if let Some(reverted) = simulation.results.iter().find(|result| {
+            result.revert.is_some() && !reverting_tx_hashes.contains(&result.tx_hash)
+        }) {
+            return Err(EthApiError::InvalidParams(
+                EthBundleError::TransactionReverted(reverted.tx_hash).to_string(),
+            )
+            .into())
+        }
+
+        let transactions = transactions
+            .into_iter()
+            .map(<Eth::Pool as TransactionPool>::Transaction::from_pooled)
+            .collect();
+        let results = pool.add_transactions(TransactionOrigin::Private, transactions).await;
+
+        let mut added = Vec::with_capacity(results.len());
+        let mut error = None;
+        for result in results {
+            match result {
+                Ok(hash) => added.push(hash),
+                Err(err) => {
+                    error.get_or_insert(err);
+                }
+            }
+        }
+        if let Some(err) = error {
+            pool.remove_transactions(added);
+            return Err(Eth::Error::from_eth_err(err))
+        }
+
+        Ok(EthBundleHash { bundle_hash: simulation.bundle_hash })
+    }
+}
+
 #[async_trait::async_trait]
 impl<Eth> EthCallBundleApiServer for EthBundle<Eth>
 where
@@ -287,6 +439,16 @@ where
     }
 }
 
+#[async_trait::async_trait]
+impl<Eth> EthSendBundleApiServer for EthBundle<Eth>
+where
+    Eth: EthTransactions + LoadPendingBlock + Call + EthApiSpec + 'static,
+{
+    async fn send_bundle(&self, bundle: EthSendBundle) -> RpcResult<EthBundleHash> {
+        Self::send_bundle(self, bundle).await.map_err(Into::into)
+    }
+}
+
 /// Container type for  `EthBundle` internals
 #[derive(Debug)]
 struct EthBundleInner<Eth> {
@@ -295,6 +457,8 @@ struct EthBundleInner<Eth> {
     // restrict the number of concurrent tracing calls.
     #[allow(dead_code)]
     blocking_task_guard: BlockingTaskGuard,
+    /// Configuration of the bundle endpoints.
+    config: EthBundleConfig,
 }
 
 impl<Eth> std::fmt::Debug for EthBundle<Eth> {
@@ -321,4 +485,156 @@ pub enum EthBundleError {
     /// Thrown when the blob gas usage of the blob transactions in a bundle exceed the maximum.
     #[error("blob gas usage exceeds the limit of {MAX_DATA_GAS_PER_BLOCK} gas per block.")]
     Eip4844BlobGasExceeded,
+    /// Thrown if the bundle contains more transactions than allowed.
+    #[error("bundle exceeds the limit of {0} transactions")]
+    TooManyTransactions(usize),
+    /// Thrown if the gas limits of the bundle transactions exceed the block gas limit.
+    #[error("bundle gas limit {0} exceeds the block gas limit {1}")]
+    BundleGasLimitExceeded(u64, u64),
+    /// Thrown if a transaction of a sent bundle reverts, but isn't allowed to.
+    #[error("bundle transaction {0} reverted")]
+    TransactionReverted(TxHash),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::EthApi;
+    use alloy_consensus::Header;
+    use alloy_eips::eip1559::ETHEREUM_BLOCK_GAS_LIMIT;
+    use alloy_primitives::{hex_literal::hex, keccak256, Bytes, B256};
+    use reth_chainspec::ChainSpecProvider;
+    use reth_evm_ethereum::EthEvmConfig;
+    use reth_network_api::noop::NoopNetwork;
+    use reth_primitives::Block;
+    use reth_provider::test_utils::{ExtendedAccount, MockEthProvider};
+    use reth_rpc_eth_types::{
+        EthStateCache, FeeHistoryCache, FeeHistoryCacheConfig, GasPriceOracle,
+    };
+    use reth_rpc_server_types::constants::{
+        DEFAULT_ETH_PROOF_WINDOW, DEFAULT_MAX_SIMULATE_BLOCKS, DEFAULT_PROOF_PERMITS,
+    };
+    use reth_tasks::pool::BlockingTaskPool;
+    use reth_transaction_pool::test_utils::{testing_pool, TestPool};
+
+    /// Returns a bundle API on top of a Cancun block, in which the senders of the transactions
+    /// have the given nonces.
+    fn bundle_api(
+        pool: TestPool,
+        config: EthBundleConfig,
+        nonces: [u64; 2],
+    ) -> EthBundle<EthApi<MockEthProvider, TestPool, NoopNetwork, EthEvmConfig>> {
+        let provider = MockEthProvider::default();
+        let header = Header {
+            number: 20_000_000,
+            timestamp: 1_720_000_000,
+            gas_limit: ETHEREUM_BLOCK_GAS_LIMIT,
+            base_fee_per_gas: Some(1_000_000_000),
+            excess_blob_gas: Some(0),
+            ..Default::default()
+        };
+        provider.add_block(B256::with_last_byte(1), Block { header, body: Default::default() });
+        for (tx, nonce) in [&TX_1[..], &TX_2[..]].into_iter().zip(nonces) {
+            let signer = recover_raw_transaction::<PoolPooledTx<TestPool>>(tx).unwrap().signer();
+            provider.add_account(signer, ExtendedAccount::new(nonce, U256::MAX));
+        }
+
+        let cache = EthStateCache::spawn(provider.clone(), Default::default());
+        let eth_api = EthApi::new(
+            provider.clone(),
+            pool,
+            NoopNetwork::default(),
+            cache.clone(),
+            GasPriceOracle::new(provider.clone(), Default::default(), cache),
+            ETHEREUM_BLOCK_GAS_LIMIT,
+            DEFAULT_MAX_SIMULATE_BLOCKS,
+            DEFAULT_ETH_PROOF_WINDOW,
+            BlockingTaskPool::build().expect("failed to build tracing pool"),
+            FeeHistoryCache::new(FeeHistoryCacheConfig::default()),
+            EthEvmConfig::new(provider.chain_spec()),
+            DEFAULT_PROOF_PERMITS,
+        );
+        EthBundle::new(eth_api, BlockingTaskGuard::new(1), config)
+    }
+
+    fn bundle(txs: Vec<Bytes>) -> EthSendBundle {
+        EthSendBundle { txs, block_number: 1, ..Default::default() }
+    }
+
+    /// The nonces of the senders of [`TX_1`] and [`TX_2`].
+    const NONCES: [u64; 2] = [0x035798, 0x02b7];
+
+    // https://etherscan.io/tx/0xa694b71e6c128a2ed8e2e0f6770bddbe52e3bb8f10e8472f9a79ab81497a8b5d
+    const TX_1: [u8; 116] = hex!("02f871018303579880850555633d1b82520894eee27662c2b8eba3cd936a23f039f3189633e4c887ad591c62bdaeb180c080a07ea72c68abfb8fca1bd964f0f99132ed9280261bdca3e549546c0205e800f7d0a05b4ef3039e9c9b9babc179a1878fb825b5aaf5aed2fa8744854150157b08d6f3");
+
+    // https://etherscan.io/tx/0x48816c2f32c29d152b0d86ff706f39869e6c1f01dc2fe59a3c1f9ecf39384694
+    const TX_2: [u8; 1088] = hex!("02f9043c018202b7843b9aca00850c807d37a08304d21d94ef1c6e67703c7bd7107eed8303fbe6ec2554bf6b881bc16d674ec80000b903c43593564c000000000000000000000000000000000000000000000000000000000000006000000000000000000000000000000000000000000000000000000000000000a00000000000000000000000000000000000000000000000000000000063e2d99f00000000000000000000000000000000000000000000000000000000000000030b000800000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000003000000000000000000000000000000000000000000000000000000000000006000000000000000000000000000000000000000000000000000000000000000c000000000000000000000000000000000000000000000000000000000000001e0000000000000000000000000000000000000000000000000000000000000004000000000000000000000000000000000000000000000000000000000000000020000000000000000000000000000000000000000000000001bc16d674ec80000000000000000000000000000000000000000000000000000000000000000010000000000000000000000000065717fe021ea67801d1088cc80099004b05b64600000000000000000000000000000000000000000000000001bc16d674ec80000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000a00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000002bc02aaa39b223fe8d0a0e5c4f27ead9083c756cc20001f4a0b86991c6218b36c1d19d4a2e9eb0ce3606eb480000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000100000000000000000000000000000000000000000000000000000000000000000180000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000009e95fd5965fd1f1a6f0d4600000000000000000000000000000000000000000000000000000000000000a000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000002000000000000000000000000a0b86991c6218b36c1d19d4a2e9eb0ce3606eb48000000000000000000000000428dca9537116148616a5a3e44035af17238fe9dc080a0c6ec1e41f5c0b9511c49b171ad4e04c6bb419c74d99fe9891d74126ec6e4e879a032069a753d7a2cfa158df95421724d24c0e9501593c09905abf3699b4a4405ce");
+
+    #[tokio::test]
+    async fn send_bundle_unsupported() {
+        let pool = testing_pool();
+        let api = bundle_api(pool.clone(), EthBundleConfig::default(), NONCES);
+
+        let err = api.send_bundle(bundle(vec![Bytes::from(TX_1)])).await.unwrap_err();
+        assert!(matches!(err, EthApiError::Unsupported(_)));
+        assert!(pool.is_empty());
+    }
+
+    #[tokio::test]
+    async fn send_bundle_locally() {
+        let pool = testing_pool();
+        let config = EthBundleConfig { accept_bundles_locally: true, ..Default::default() };
+        let api = bundle_api(pool.clone(), config, NONCES);
+
+        let hash = api
+            .send_bundle(bundle(vec![Bytes::from(TX_1), Bytes::from(TX_2)]))
+            .await
+            .unwrap()
+            .bundle_hash;
+        assert_eq!(pool.len(), 2);
+
+        let tx_hashes = [keccak256(TX_1), keccak256(TX_2)];
+        assert!(tx_hashes.iter().all(|hash| pool.contains(hash)));
+        assert_eq!(hash, keccak256([tx_hashes[0].as_slice(), tx_hashes[1].as_slice()].concat()));
+    }
+
+    #[tokio::test]
+    async fn send_bundle_is_added_as_group() {
+        let pool = testing_pool();
+        let config = EthBundleConfig { accept_bundles_locally: true, ..Default::default() };
+        let api = bundle_api(pool.clone(), config, NONCES);
+
+        // the duplicate is rejected before anything is added
+        let err =
+            api.send_bundle(bundle(vec![Bytes::from(TX_1), Bytes::from(TX_1)])).await.unwrap_err();
+        assert!(matches!(err, EthApiError::PoolError(RpcPoolError::AlreadyKnown)));
+        assert!(pool.is_empty());
+    }
+
+    #[tokio::test]
+    async fn send_bundle_is_validated_as_a_whole() {
+        let pool = testing_pool();
+        let config = EthBundleConfig { accept_bundles_locally: true, ..Default::default() };
+        // the second transaction has a nonce that's too high
+        let api = bundle_api(pool.clone(), config, [NONCES[0], NONCES[1] - 1]);
+
+        assert!(api.send_bundle(bundle(vec![Bytes::from(TX_1), Bytes::from(TX_2)])).await.is_err());
+        assert!(pool.is_empty());
+
+        // the first transaction is valid on its own
+        api.send_bundle(bundle(vec![Bytes::from(TX_1)])).await.unwrap();
+        assert_eq!(pool.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn send_bundle_too_many_transactions() {
+        let pool = testing_pool();
+        let config = EthBundleConfig { accept_bundles_locally: true, max_bundle_txs: 1 };
+        let api = bundle_api(pool.clone(), config, NONCES);
+
+        let err =
+            api.send_bundle(bundle(vec![Bytes::from(TX_1), Bytes::from(TX_2)])).await.unwrap_err();
+        assert!(matches!(err, EthApiError::InvalidParams(_)));
+        assert!(pool.is_empty());
+    }
 }
@@ -4,14 +4,14 @@
 use std::sync::Arc;
 
 use alloy_consensus::BlockHeader;
-use alloy_eips::BlockNumberOrTag;
+use alloy_eips::{BlockNumHash, BlockNumberOrTag};
 use alloy_network::Ethereum;
-use alloy_primitives::{Bytes, U256};
+use alloy_primitives::{Address, Bytes, B256, U256};
 use derive_more::Deref;
 use reth_primitives::NodePrimitives;
 use reth_provider::{
-    BlockReader, BlockReaderIdExt, CanonStateSubscriptions, ChainSpecProvider, ProviderBlock,
-    ProviderReceipt,
+    BlockReader, BlockReaderIdExt, CanonStateSubscriptions, ChainSpecProvider, ExecutionOutcome,
+    ProviderBlock, ProviderReceipt,
 };
 use reth_rpc_eth_api::{
     helpers::{EthSigner, SpawnBlocking},
@@ -20,13 +20,15 @@ use reth_rpc_eth_api::{
 };
 use reth_rpc_eth_types::{
     EthApiBuilderCtx, EthApiError, EthStateCache, FeeHistoryCache, GasCap, GasPriceOracle,
-    PendingBlock,
+    HistoryCutoff, LatestStateCache, PendingBlock, PendingBlockGeneration, ReplayCache,
+    TransactionJsonCache,
 };
 use reth_tasks::{
     pool::{BlockingTaskGuard, BlockingTaskPool},
     TaskSpawner, TokioTaskExecutor,
 };
-use tokio::sync::{broadcast, Mutex};
+use reth_transaction_pool::TransactionPool;
+use tokio::sync::{broadcast, watch, Mutex};
 
 use crate::eth::EthTxBuilder;
 
@@ -102,7 +104,7 @@ where
 impl<Provider, Pool, EvmConfig, Network> EthApi<Provider, Pool, Network, EvmConfig>
 where
     Provider: ChainSpecProvider + BlockReaderIdExt + Clone + 'static,
-    Pool: Clone,
+    Pool: TransactionPool + Clone,
     EvmConfig: Clone,
     Network: Clone,
 {
@@ -119,10 +121,12 @@ where
             >,
         >,
     {
-        let blocking_task_pool =
-            BlockingTaskPool::build().expect("failed to build blocking task pool");
+        // compute heavy requests share the compute pool of the executor if it has one
+        let blocking_task_pool = ctx.executor.compute_pool().unwrap_or_else(|| {
+            BlockingTaskPool::build_compute(0).expect("failed to build compute pool")
+        });
 
-        let inner = EthApiInner::new(
+        let mut inner = EthApiInner::new(
             ctx.provider.clone(),
             ctx.pool.clone(),
             ctx.network.clone(),
@@ -137,6 +141,12 @@ where
             ctx.executor.clone(),
             ctx.config.proof_permits,
         );
+        inner.pending_block_generation = Some(ctx.new_pending_block_generation());
+        inner.latest_state_cache = ctx.new_latest_state_cache();
+        inner.tx_json_cache = ctx.new_tx_json_cache();
+        inner.history_cutoff = ctx.config.history_cutoff;
+        inner.max_tx_sync_distance = ctx.config.max_tx_sync_distance;
+        inner.sync_target_updates = ctx.events.subscribe_sync_target();
 
         Self { inner: Arc::new(inner), tx_resp_builder: EthTxBuilder }
     }
@@ -154,6 +164,11 @@ where
     fn tx_resp_builder(&self) -> &Self::TransactionCompat {
         &self.tx_resp_builder
     }
+
+    #[inline]
+    fn tx_json_cache(&self) -> Option<&TransactionJsonCache> {
+        self.inner.tx_json_cache()
+    }
 }
 
 impl<Provider, Pool, Network, EvmConfig> RpcNodeCore for EthApi<Provider, Pool, Network, EvmConfig>
@@ -247,8 +262,15 @@ pub struct EthApiInner<Provider: BlockReader, Pool, Network, EvmConfig> {
     network: Network,
     /// All configured Signers
     signers: parking_lot::RwLock<Vec<Box<dyn EthSigner<Provider::Transaction>>>>,
+    /// Suggested fee recipient of locally built payloads, if any
+    fee_recipient: parking_lot::RwLock<Option<watch::Receiver<Address>>>,
     /// The async cache frontend for eth related data
     eth_cache: EthStateCache<Provider::Block, Provider::Receipt>,
+    /// Cache for hot accounts and storage slots of the latest state, if enabled
+    latest_state_cache: Option<LatestStateCache>,
+    /// Cache for the serialized RPC objects of mined transactions, shared by block and
+    /// transaction endpoints, if enabled
+    tx_json_cache: Option<TransactionJsonCache>,
     /// The async gas oracle frontend for gas price suggestions
     gas_oracle: GasPriceOracle<Provider>,
     /// Maximum gas limit for `eth_call` and call tracing RPC methods.
@@ -257,16 +279,30 @@ pub struct EthApiInner<Provider: BlockReader, Pool, Network, EvmConfig> {
     max_simulate_blocks: u64,
     /// The maximum number of blocks into the past for generating state proofs.
     eth_proof_window: u64,
+    /// Oldest block for which historical state, traces and logs are served.
+    history_cutoff: HistoryCutoff,
+    /// Maximum number of blocks the node may be behind its sync target for transactions
+    /// submitted via RPC to be accepted, if set.
+    max_tx_sync_distance: Option<u64>,
+    /// Head of the latest forkchoice update received from the consensus layer, if tracked
+    sync_target_updates: Option<watch::Receiver<Option<BlockNumHash>>>,
     /// The block number at which the node started
     starting_block: U256,
     /// The type that can spawn tasks which would otherwise block.
     task_spawner: Box<dyn TaskSpawner>,
     /// Cached pending block if any
     pending_block: Mutex<Option<PendingBlock<Provider::Block, Provider::Receipt>>>,
+    /// Tracks whether the cached pending block is outdated, if spawned
+    pending_block_generation: Option<PendingBlockGeneration>,
+    /// State changes of the latest locally built pending block and the hash of the block they
+    /// apply to, readable while a new pending block is being built
+    pending_state: parking_lot::RwLock<Option<(B256, Arc<ExecutionOutcome>)>>,
     /// A pool dedicated to CPU heavy blocking tasks.
     blocking_task_pool: BlockingTaskPool,
     /// Cache for block fees history
     fee_history_cache: FeeHistoryCache,
+    /// Cache for partially replayed blocks
+    replay_cache: ReplayCache,
     /// The type that defines how to configure the EVM
     evm_config: EvmConfig,
 
@@ -316,16 +352,25 @@ where
             pool,
             network,
             signers,
+            fee_recipient: Default::default(),
             eth_cache,
+            latest_state_cache: None,
+            tx_json_cache: None,
             gas_oracle,
             gas_cap: gas_cap.into().into(),
             max_simulate_blocks,
             eth_proof_window,
+            history_cutoff: HistoryCutoff::default(),
+            max_tx_sync_distance: None,
+            sync_target_updates: None,
             starting_block,
             task_spawner: Box::new(task_spawner),
             pending_block: Default::default(),
+            pending_block_generation: None,
+            pending_state: Default::default(),
             blocking_task_pool,
             fee_history_cache,
+            replay_cache: Default::default(),
             evm_config,
             blocking_task_guard: BlockingTaskGuard::new(proof_permits),
             raw_tx_sender,
@@ -349,6 +394,19 @@ where
         &self.eth_cache
     }
 
+    /// Returns a handle to the cache of the latest state, if enabled.
+    #[inline]
+    pub const fn latest_state_cache(&self) -> Option<&LatestStateCache> {
+        self.latest_state_cache.as_ref()
+    }
+
+    /// Returns a handle to the cache of the serialized RPC objects of mined transactions, if
+    /// enabled.
+    #[inline]
+    pub const fn tx_json_cache(&self) -> Option<&TransactionJsonCache> {
+        self.tx_json_cache.as_ref()
+    }
+
     /// Returns a handle to the pending block.
     #[inline]
     pub const fn pending_block(
@@ -357,6 +415,21 @@ where
         &self.pending_block
     }
 
+    /// Returns a handle to the generation of the pending block, if tracked.
+    #[inline]
+    pub const fn pending_block_generation(&self) -> Option<&PendingBlockGeneration> {
+        self.pending_block_generation.as_ref()
+    }
+
+    /// Returns a handle to the state changes of the latest locally built pending block and the
+    /// hash of the block they apply to.
+    #[inline]
+    pub const fn pending_state(
+        &self,
+    ) -> &parking_lot::RwLock<Option<(B256, Arc<ExecutionOutcome>)>> {
+        &self.pending_state
+    }
+
     /// Returns a handle to the task spawner.
     #[inline]
     pub const fn task_spawner(&self) -> &dyn TaskSpawner {
@@ -393,6 +466,12 @@ where
         self.max_simulate_blocks
     }
 
+    /// Returns a handle to the cache of partially replayed blocks.
+    #[inline]
+    pub const fn replay_cache(&self) -> &ReplayCache {
+        &self.replay_cache
+    }
+
     /// Returns a handle to the gas oracle.
     #[inline]
     pub const fn gas_oracle(&self) -> &GasPriceOracle<Provider> {
@@ -413,6 +492,12 @@ where
         &self.signers
     }
 
+    /// Returns a handle to the suggested fee recipient of locally built payloads.
+    #[inline]
+    pub const fn fee_recipient(&self) -> &parking_lot::RwLock<Option<watch::Receiver<Address>>> {
+        &self.fee_recipient
+    }
+
     /// Returns the starting block.
     #[inline]
     pub const fn starting_block(&self) -> U256 {
@@ -431,6 +516,49 @@ where
         self.eth_proof_window
     }
 
+    /// Oldest block for which historical state, traces and logs are served.
+    #[inline]
+    pub const fn history_cutoff(&self) -> HistoryCutoff {
+        self.history_cutoff
+    }
+
+    /// Sets the oldest block for which historical state, traces and logs are served.
+    #[inline]
+    pub fn set_history_cutoff(&mut self, cutoff: HistoryCutoff) {
+        self.history_cutoff = cutoff;
+    }
+
+    /// Maximum number of blocks the node may be behind its sync target for transactions
+    /// submitted via RPC to be accepted, if set.
+    #[inline]
+    pub const fn max_tx_sync_distance(&self) -> Option<u64> {
+        self.max_tx_sync_distance
+    }
+
+    /// Sets the maximum number of blocks the node may be behind its sync target for transactions
+    /// submitted via RPC to be accepted.
+    #[inline]
+    pub fn set_max_tx_sync_distance(&mut self, max_distance: Option<u64>) {
+        self.max_tx_sync_distance = max_distance;
+    }
+
+    /// Returns a handle to the head of the latest forkchoice update received from the consensus
+    /// layer, if tracked.
+    #[inline]
+    pub const fn sync_target_updates(&self) -> Option<&watch::Receiver<Option<BlockNumHash>>> {
+        self.sync_target_updates.as_ref()
+    }
+
+    /// Sets the handle to the head of the latest forkchoice update received from the consensus
+    /// layer.
+    #[inline]
+    pub fn set_sync_target_updates(
+        &mut self,
+        updates: Option<watch::Receiver<Option<BlockNumHash>>>,
+    ) {
+        self.sync_target_updates = updates;
+    }
+
     /// Returns reference to [`BlockingTaskGuard`].
     #[inline]
     pub const fn blocking_task_guard(&self) -> &BlockingTaskGuard {
@@ -12,11 +12,11 @@ use reth_chainspec::ChainInfo;
 use reth_primitives::SealedBlockWithSenders;
 use reth_provider::{
     BlockHashReader, BlockIdReader, BlockNumReader, BlockReader, HeaderProvider, ProviderBlock,
-    ProviderError, ProviderReceipt,
+    ProviderError, ProviderReceipt, ReceiptProvider,
 };
 use reth_rpc_eth_api::{
-    EthApiTypes, EthFilterApiServer, FullEthApiTypes, RpcNodeCoreExt, RpcTransaction,
-    TransactionCompat,
+    helpers::LoadState, EthApiTypes, EthFilterApiServer, FullEthApiTypes, RpcNodeCoreExt,
+    RpcTransaction, TransactionCompat,
 };
 use reth_rpc_eth_types::{
     logs_utils::{self, append_matching_block_logs, ProviderOrBlock},
@@ -135,7 +135,7 @@ where
 
 impl<Eth> EthFilter<Eth>
 where
-    Eth: FullEthApiTypes<Provider: BlockReader + BlockIdReader> + RpcNodeCoreExt,
+    Eth: FullEthApiTypes<Provider: BlockReader + BlockIdReader> + RpcNodeCoreExt + LoadState,
 {
     /// Access the underlying provider.
     fn provider(&self) -> &Eth::Provider {
@@ -242,7 +242,7 @@ where
 #[async_trait]
 impl<Eth> EthFilterApiServer<RpcTransaction<Eth::NetworkTypes>> for EthFilter<Eth>
 where
-    Eth: FullEthApiTypes + RpcNodeCoreExt<Provider: BlockIdReader> + 'static,
+    Eth: FullEthApiTypes + RpcNodeCoreExt<Provider: BlockIdReader> + LoadState + 'static,
 {
     /// Handler for `eth_newFilter`
     async fn new_filter(&self, filter: Filter) -> RpcResult<FilterId> {
@@ -361,7 +361,7 @@ struct EthFilterInner<Eth: EthApiTypes> {
 
 impl<Eth> EthFilterInner<Eth>
 where
-    Eth: RpcNodeCoreExt<Provider: BlockIdReader, Pool: TransactionPool> + EthApiTypes,
+    Eth: RpcNodeCoreExt<Provider: BlockIdReader, Pool: TransactionPool> + EthApiTypes + LoadState,
 {
     /// Access the underlying provider.
     fn provider(&self) -> &Eth::Provider {
@@ -387,6 +387,7 @@ where
                     .ok_or_else(|| ProviderError::HeaderNotFound(block_hash.into()))?;
 
                 let block_num_hash = BlockNumHash::new(header.number(), block_hash);
+                self.ensure_logs_available(block_num_hash.number)?;
 
                 // we also need to ensure that the receipts are available and return an error if
                 // not, in case the block hash been reorged
@@ -435,6 +436,17 @@ where
         }
     }
 
+    /// Returns an error if the logs of the given block aren't served because it's below the
+    /// [`LoadState::history_cutoff`].
+    fn ensure_logs_available(&self, block_number: u64) -> Result<(), EthFilterError> {
+        match self.eth_api.history_cutoff()? {
+            Some(cutoff) if block_number < cutoff => {
+                Err(EthApiError::HistoryUnavailable(cutoff).into())
+            }
+            _ => Ok(()),
+        }
+    }
+
     /// Installs a new filter and returns the new identifier.
     async fn install_filter(
         &self,
@@ -476,6 +488,8 @@ where
             return Err(EthFilterError::QueryExceedsMaxBlocks(self.max_blocks_per_filter))
         }
 
+        self.ensure_logs_available(from_block)?;
+
         let mut all_logs = Vec::new();
         let filter_params = FilteredParams::new(Some(filter.clone()));
 
@@ -483,10 +497,40 @@ where
         let address_filter = FilteredParams::address_filter(&filter.address);
         let topics_filter = FilteredParams::topics_filter(&filter.topics);
 
+        // if the log address index is maintained, only the blocks in which the filtered addresses
+        // emitted logs have to be checked, the blocks above the indexed ones are scanned below
+        let mut scan_from = from_block;
+        if !filter.address.is_empty() {
+            let addresses = filter.address.iter().copied().collect::<Vec<_>>();
+            if let Some(indexed) =
+                self.provider().log_address_blocks(&addresses, from_block..=to_block)?
+            {
+                trace!(target: "rpc::eth::filter", blocks=indexed.blocks.len(), indexed_to=indexed.indexed_to, "using log address index");
+                for number in indexed.blocks {
+                    let header = self
+                        .provider()
+                        .sealed_header(number)?
+                        .ok_or_else(|| ProviderError::HeaderNotFound(number.into()))?;
+                    if FilteredParams::matches_topics(header.logs_bloom(), &topics_filter) {
+                        self.append_block_logs(
+                            &mut all_logs,
+                            &filter_params,
+                            header.num_hash(),
+                            header.timestamp(),
+                            chain_info,
+                            from_block,
+                            to_block,
+                        )
+                        .await?;
+                    }
+                }
+                scan_from = indexed.indexed_to + 1;
+            }
+        }
+
         // loop over the range of new blocks and check logs if the filter matches the log's bloom
         // filter
-        for (from, to) in
-            BlockRangeInclusiveIter::new(from_block..=to_block, self.max_headers_range)
+        for (from, to) in BlockRangeInclusiveIter::new(scan_from..=to_block, self.max_headers_range)
         {
             let headers = self.provider().headers_range(from..=to)?;
 
@@ -505,33 +549,16 @@ where
                             .ok_or_else(|| ProviderError::HeaderNotFound(header.number().into()))?,
                     };
 
-                    let num_hash = BlockNumHash::new(header.number(), block_hash);
-                    if let Some((receipts, maybe_block)) =
-                        self.receipts_and_maybe_block(&num_hash, chain_info.best_number).await?
-                    {
-                        append_matching_block_logs(
-                            &mut all_logs,
-                            maybe_block
-                                .map(ProviderOrBlock::Block)
-                                .unwrap_or_else(|| ProviderOrBlock::Provider(self.provider())),
-                            &filter_params,
-                            num_hash,
-                            &receipts,
-                            false,
-                            header.timestamp(),
-                        )?;
-
-                        // size check but only if range is multiple blocks, so we always return all
-                        // logs of a single block
-                        let is_multi_block_range = from_block != to_block;
-                        if is_multi_block_range && all_logs.len() > self.max_logs_per_response {
-                            return Err(EthFilterError::QueryExceedsMaxResults {
-                                max_logs: self.max_logs_per_response,
-                                from_block,
-                                to_block: num_hash.number.saturating_sub(1),
-                            });
-                        }
-                    }
+                    self.append_block_logs(
+                        &mut all_logs,
+                        &filter_params,
+                        BlockNumHash::new(header.number(), block_hash),
+                        header.timestamp(),
+                        chain_info,
+                        from_block,
+                        to_block,
+                    )
+                    .await?;
                 }
             }
         }
@@ -539,6 +566,51 @@ where
         Ok(all_logs)
     }
 
+    /// Appends the logs of the given block that match the filter.
+    ///
+    /// Returns an error if the range spans multiple blocks and more logs than allowed per response
+    /// were found.
+    #[allow(clippy::too_many_arguments)]
+    async fn append_block_logs(
+        &self,
+        all_logs: &mut Vec<Log>,
+        filter_params: &FilteredParams,
+        num_hash: BlockNumHash,
+        timestamp: u64,
+        chain_info: ChainInfo,
+        from_block: u64,
+        to_block: u64,
+    ) -> Result<(), EthFilterError> {
+        if let Some((receipts, maybe_block)) =
+            self.receipts_and_maybe_block(&num_hash, chain_info.best_number).await?
+        {
+            append_matching_block_logs(
+                all_logs,
+                maybe_block
+                    .map(ProviderOrBlock::Block)
+                    .unwrap_or_else(|| ProviderOrBlock::Provider(self.provider())),
+                filter_params,
+                num_hash,
+                &receipts,
+                false,
+                timestamp,
+            )?;
+
+            // size check but only if range is multiple blocks, so we always return all
+            // logs of a single block
+            let is_multi_block_range = from_block != to_block;
+            if is_multi_block_range && all_logs.len() > self.max_logs_per_response {
+                return Err(EthFilterError::QueryExceedsMaxResults {
+                    max_logs: self.max_logs_per_response,
+                    from_block,
+                    to_block: num_hash.number.saturating_sub(1),
+                });
+            }
+        }
+
+        Ok(())
+    }
+
     /// Retrieves receipts and block from cache if near the tip (4 blocks), otherwise only receipts.
     async fn receipts_and_maybe_block(
         &self,
@@ -782,8 +854,45 @@ impl From<ProviderError> for EthFilterError {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::EthApi;
+    use alloy_consensus::Header;
+    use alloy_eips::eip1559::ETHEREUM_BLOCK_GAS_LIMIT;
+    use alloy_primitives::{logs_bloom, Address, B256, U256};
     use rand::Rng;
-    use reth_testing_utils::generators;
+    use reth_evm_ethereum::EthEvmConfig;
+    use reth_network_api::noop::NoopNetwork;
+    use reth_primitives::{Block, BlockBody};
+    use reth_provider::{test_utils::MockEthProvider, ChainSpecProvider};
+    use reth_prune_types::{PruneCheckpoint, PruneMode, PruneSegment};
+    use reth_rpc_eth_types::{FeeHistoryCache, FeeHistoryCacheConfig, GasPriceOracle};
+    use reth_rpc_server_types::constants::{
+        DEFAULT_ETH_PROOF_WINDOW, DEFAULT_MAX_SIMULATE_BLOCKS, DEFAULT_PROOF_PERMITS,
+    };
+    use reth_stages_types::{StageCheckpoint, StageId};
+    use reth_tasks::{pool::BlockingTaskPool, TokioTaskExecutor};
+    use reth_testing_utils::generators::{self, random_log, random_receipt, random_signed_tx};
+    use reth_transaction_pool::test_utils::{testing_pool, TestPool};
+
+    fn new_eth_filter(
+        provider: MockEthProvider,
+    ) -> EthFilter<EthApi<MockEthProvider, TestPool, NoopNetwork, EthEvmConfig>> {
+        let cache = EthStateCache::spawn(provider.clone(), Default::default());
+        let eth_api = EthApi::new(
+            provider.clone(),
+            testing_pool(),
+            NoopNetwork::default(),
+            cache.clone(),
+            GasPriceOracle::new(provider.clone(), Default::default(), cache),
+            ETHEREUM_BLOCK_GAS_LIMIT,
+            DEFAULT_MAX_SIMULATE_BLOCKS,
+            DEFAULT_ETH_PROOF_WINDOW,
+            BlockingTaskPool::build().expect("failed to build tracing pool"),
+            FeeHistoryCache::new(FeeHistoryCacheConfig::default()),
+            EthEvmConfig::new(provider.chain_spec()),
+            DEFAULT_PROOF_PERMITS,
+        );
+        EthFilter::new(eth_api, EthFilterConfig::default(), Box::new(TokioTaskExecutor::default()))
+    }
 
     #[test]
     fn test_block_range_iter() {
@@ -806,4 +915,93 @@ mod tests {
 
         assert_eq!(end, *range.end());
     }
+
+    #[tokio::test]
+    async fn test_logs_history_cutoff() {
+        let provider = MockEthProvider::default();
+        let block_hash = |number: u64| B256::from(U256::from(number + 1));
+        provider.extend_headers(
+            (0..200).map(|number| (block_hash(number), Header { number, ..Default::default() })),
+        );
+        // receipts of blocks up to 100 were pruned
+        provider.add_prune_checkpoint(
+            PruneSegment::Receipts,
+            PruneCheckpoint {
+                block_number: Some(100),
+                tx_number: None,
+                prune_mode: PruneMode::Distance(99),
+            },
+        );
+
+        let eth_filter = new_eth_filter(provider);
+
+        // no block matches the address, so no receipts are needed
+        let filter = Filter::new().address(Address::random());
+
+        let logs = eth_filter.logs(filter.clone().from_block(101).to_block(150)).await.unwrap();
+        assert!(logs.is_empty());
+
+        for filter in
+            [filter.clone().from_block(50).to_block(150), filter.at_block_hash(block_hash(100))]
+        {
+            let err = eth_filter.logs(filter).await.unwrap_err();
+            assert_eq!(err.message(), "historical data not available below block 101");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_logs_with_log_address_index() {
+        let mut rng = generators::rng();
+        let provider = MockEthProvider::default();
+        let addresses = (1..=3).map(Address::with_last_byte).collect::<Vec<_>>();
+
+        // every transaction of the first 180 blocks emits a log of one of the addresses
+        let mut parent_hash = B256::ZERO;
+        for number in 0..200 {
+            let tx_count = if number < 180 { rng.gen_range(0..3) } else { 0 };
+            let transactions =
+                (0..tx_count).map(|_| random_signed_tx(&mut rng)).collect::<Vec<_>>();
+            let receipts = transactions
+                .iter()
+                .enumerate()
+                .map(|(idx, tx)| {
+                    let mut receipt = random_receipt(&mut rng, tx, Some(0));
+                    let address = addresses[(number as usize + idx) % addresses.len()];
+                    receipt.logs.push(random_log(&mut rng, Some(address), Some(1)));
+                    receipt
+                })
+                .collect::<Vec<_>>();
+            let header = Header {
+                number,
+                parent_hash,
+                logs_bloom: logs_bloom(receipts.iter().flat_map(|receipt| &receipt.logs)),
+                ..Default::default()
+            };
+            let hash = header.hash_slow();
+            provider.add_block(
+                hash,
+                Block { header, body: BlockBody { transactions, ..Default::default() } },
+            );
+            provider.add_receipts(hash, receipts);
+            parent_hash = hash;
+        }
+
+        let filters = [
+            Filter::new().address(addresses[0]).from_block(0).to_block(199),
+            Filter::new().address(addresses[1..].to_vec()).from_block(20).to_block(170),
+            Filter::new().address(addresses[2]).from_block(160).to_block(199),
+        ];
+        let eth_filter = new_eth_filter(provider.clone());
+        let mut scanned = Vec::new();
+        for filter in &filters {
+            scanned.push(eth_filter.logs(filter.clone()).await.unwrap());
+        }
+        assert!(scanned.iter().all(|logs| !logs.is_empty()));
+
+        // blocks up to 150 are looked up in the index, the blocks above it are scanned
+        provider.add_stage_checkpoint(StageId::IndexLogAddresses, StageCheckpoint::new(150));
+        for (filter, scanned) in filters.into_iter().zip(scanned) {
+            assert_eq!(eth_filter.logs(filter).await.unwrap(), scanned);
+        }
+    }
 }
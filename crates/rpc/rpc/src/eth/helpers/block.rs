@@ -72,3 +72,69 @@ where
     Provider: BlockReader,
 {
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::eth::EthTxBuilder;
+    use alloy_rpc_types_eth::{BlockTransactions, BlockTransactionsKind};
+    use reth_primitives::{transaction::SignedTransactionIntoRecoveredExt, SealedBlockWithSenders};
+    use reth_rpc_eth_types::{
+        cache::tx_json::{from_block_with_tx_json, tx_json_from_source},
+        MaybeSerialized, TransactionJsonCache, TransactionSource,
+    };
+    use reth_rpc_types_compat::block::from_block;
+    use reth_testing_utils::generators::{self, random_block, random_signed_tx, BlockParams};
+
+    fn block_with_transactions(tx_count: usize) -> SealedBlockWithSenders {
+        let mut rng = generators::rng();
+        let mut block =
+            random_block(&mut rng, 1, BlockParams { tx_count: Some(0), ..Default::default() });
+        block.body.transactions = (0..tx_count).map(|_| random_signed_tx(&mut rng)).collect();
+        block.seal_with_senders().unwrap()
+    }
+
+    #[test]
+    fn tx_json_matches_direct_serialization() {
+        let block = block_with_transactions(300);
+        let cache = TransactionJsonCache::new(16);
+
+        for kind in [BlockTransactionsKind::Hashes, BlockTransactionsKind::Full] {
+            let expected = serde_json::to_string(
+                &from_block(block.clone().unseal(), kind, Some(block.hash()), &EthTxBuilder)
+                    .unwrap(),
+            )
+            .unwrap();
+
+            // uncached, cold cache and warm cache
+            for cache in [None, Some(&cache), Some(&cache)] {
+                let rpc_block =
+                    from_block_with_tx_json(&block, kind, &EthTxBuilder, cache).unwrap();
+                if let BlockTransactions::Full(txs) = &rpc_block.transactions {
+                    // with a cache, the response is spliced from serialized transactions
+                    assert!(txs
+                        .iter()
+                        .all(|tx| matches!(tx, MaybeSerialized::Serialized(_)) == cache.is_some()));
+                }
+                assert_eq!(serde_json::to_string(&rpc_block).unwrap(), expected);
+            }
+        }
+        assert_eq!(cache.len(), 1);
+
+        // the transaction endpoints share the cached transactions of the block
+        let index = 42;
+        let source = TransactionSource::Block {
+            transaction: block.body.transactions[index].clone().with_signer(block.senders[index]),
+            index: index as u64,
+            block_hash: block.hash(),
+            block_number: block.number,
+            base_fee: block.base_fee_per_gas,
+        };
+        let expected =
+            serde_json::to_string(&source.clone().into_transaction(&EthTxBuilder).unwrap())
+                .unwrap();
+        let tx = tx_json_from_source(source, &EthTxBuilder, Some(&cache)).unwrap();
+        assert!(matches!(tx, MaybeSerialized::Serialized(_)));
+        assert_eq!(serde_json::to_string(&tx).unwrap(), expected);
+        assert_eq!(cache.len(), 1);
+    }
+}
@@ -53,6 +53,11 @@ where
         self.inner.pending_block()
     }
 
+    #[inline]
+    fn pending_block_generation(&self) -> Option<u64> {
+        self.inner.pending_block_generation().map(|generation| generation.current())
+    }
+
     fn assemble_block(
         &self,
         block_env: &BlockEnv,
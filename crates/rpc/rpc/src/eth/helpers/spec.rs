@@ -1,10 +1,12 @@
-use alloy_primitives::U256;
+use alloy_eips::BlockNumHash;
+use alloy_primitives::{Address, U256};
 use reth_chainspec::EthereumHardforks;
 use reth_network_api::NetworkInfo;
 use reth_provider::{
     BlockNumReader, BlockReader, ChainSpecProvider, ProviderTx, StageCheckpointReader,
 };
 use reth_rpc_eth_api::{helpers::EthApiSpec, RpcNodeCore};
+use tokio::sync::watch;
 
 use crate::EthApi;
 
@@ -30,4 +32,16 @@ where
     {
         self.inner.signers()
     }
+
+    fn fee_recipient(&self) -> &parking_lot::RwLock<Option<watch::Receiver<Address>>> {
+        self.inner.fee_recipient()
+    }
+
+    fn sync_target_updates(&self) -> Option<&watch::Receiver<Option<BlockNumHash>>> {
+        self.inner.sync_target_updates()
+    }
+
+    fn max_tx_sync_distance(&self) -> Option<u64> {
+        self.inner.max_tx_sync_distance()
+    }
 }
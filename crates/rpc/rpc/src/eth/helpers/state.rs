@@ -1,13 +1,20 @@
 //! Contains RPC handler implementations specific to state.
 
+use alloy_consensus::BlockHeader;
+use alloy_primitives::BlockNumber;
+use futures::Future;
 use reth_chainspec::EthereumHardforks;
-use reth_provider::{BlockReader, ChainSpecProvider, StateProviderFactory};
+use reth_provider::{
+    providers::BundleStateProvider, BlockNumReader, BlockReader, ChainSpecProvider, ProviderResult,
+    PruneCheckpointReader, StateProviderBox, StateProviderFactory,
+};
 use reth_transaction_pool::TransactionPool;
 
 use reth_rpc_eth_api::{
-    helpers::{EthState, LoadState, SpawnBlocking},
-    RpcNodeCoreExt,
+    helpers::{EthState, LoadPendingBlock, LoadState, SpawnBlocking},
+    FromEthApiError, RpcNodeCore, RpcNodeCoreExt,
 };
+use reth_rpc_eth_types::PendingBlockEnvOrigin;
 
 use crate::EthApi;
 
@@ -24,34 +31,101 @@ where
 impl<Provider, Pool, Network, EvmConfig> LoadState for EthApi<Provider, Pool, Network, EvmConfig>
 where
     Self: RpcNodeCoreExt<
-        Provider: BlockReader
-                      + StateProviderFactory
-                      + ChainSpecProvider<ChainSpec: EthereumHardforks>,
-        Pool: TransactionPool,
-    >,
+            Provider: BlockReader
+                          + StateProviderFactory
+                          + ChainSpecProvider<ChainSpec: EthereumHardforks>
+                          + PruneCheckpointReader,
+            Pool: TransactionPool,
+        > + LoadPendingBlock
+        + SpawnBlocking,
     Provider: BlockReader,
 {
+    fn history_cutoff(&self) -> ProviderResult<Option<BlockNumber>> {
+        self.inner.history_cutoff().oldest_block(RpcNodeCore::provider(self))
+    }
+
+    fn latest_state(&self) -> Result<StateProviderBox, Self::Error> {
+        let provider = RpcNodeCore::provider(self);
+        let Some(cache) = self.inner.latest_state_cache() else {
+            return provider.latest().map_err(Self::Error::from_eth_err)
+        };
+
+        // the hash must be fetched before the state, so that the state is never older than the
+        // block the cache is used for
+        let block_hash = provider.chain_info().map_err(Self::Error::from_eth_err)?.best_hash;
+        let state = provider.latest().map_err(Self::Error::from_eth_err)?;
+        Ok(Box::new(cache.provider(state, block_hash)))
+    }
+
+    fn cached_pending_state(&self) -> Result<Option<StateProviderBox>, Self::Error> {
+        let Some((parent_hash, state)) = self.inner.pending_state().read().clone() else {
+            return Ok(None)
+        };
+
+        // only use the pending state if it is built on top of the latest block
+        let latest_hash =
+            RpcNodeCore::provider(self).chain_info().map_err(Self::Error::from_eth_err)?.best_hash;
+        if parent_hash != latest_hash {
+            return Ok(None)
+        }
+
+        Ok(Some(Box::new(BundleStateProvider::new(self.latest_state()?, state))))
+    }
+
+    fn update_pending_state(&self) -> impl Future<Output = Result<bool, Self::Error>> + Send {
+        async move {
+            let pending = self.pending_block_env_and_cfg()?;
+            let PendingBlockEnvOrigin::DerivedFromLatest(parent_hash) = pending.origin else {
+                // the pending block is received from the CL, its state is served by the provider
+                *self.inner.pending_state().write() = None;
+                return Ok(false)
+            };
+
+            // concurrent requests wait for the pending block that is being built instead of
+            // building their own, the state is published while the pending block is still locked
+            // so that an outdated build never replaces a newer one
+            let pending_block =
+                self.lock_local_pending_block(pending.cfg, pending.block_env, parent_hash).await;
+            let state = pending_block.as_ref().map(|pending_block| {
+                (pending_block.block.parent_hash(), pending_block.state.clone())
+            });
+            let available = state.is_some();
+            *self.inner.pending_state().write() = state;
+            Ok(available)
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use alloy_eips::eip1559::ETHEREUM_BLOCK_GAS_LIMIT;
-    use alloy_primitives::{Address, StorageKey, StorageValue, U256};
+    use alloy_consensus::Header;
+    use alloy_eips::{eip1559::ETHEREUM_BLOCK_GAS_LIMIT, BlockId};
+    use alloy_primitives::{Address, StorageKey, StorageValue, B256, U256};
     use reth_chainspec::MAINNET;
     use reth_evm_ethereum::EthEvmConfig;
     use reth_network_api::noop::NoopNetwork;
-    use reth_provider::test_utils::{ExtendedAccount, MockEthProvider, NoopProvider};
-    use reth_rpc_eth_api::helpers::EthState;
+    use reth_primitives::Block;
+    use reth_provider::{
+        test_utils::{ExtendedAccount, MockEthProvider, NoopProvider},
+        ExecutionOutcome,
+    };
+    use reth_prune_types::{PruneCheckpoint, PruneMode, PruneSegment};
+    use reth_rpc_eth_api::helpers::{EthBlocks, EthState, Trace};
     use reth_rpc_eth_types::{
-        EthStateCache, FeeHistoryCache, FeeHistoryCacheConfig, GasPriceOracle,
+        EthApiError, EthStateCache, FeeHistoryCache, FeeHistoryCacheConfig, GasPriceOracle,
     };
     use reth_rpc_server_types::constants::{
         DEFAULT_ETH_PROOF_WINDOW, DEFAULT_MAX_SIMULATE_BLOCKS, DEFAULT_PROOF_PERMITS,
     };
     use reth_tasks::pool::BlockingTaskPool;
     use reth_transaction_pool::test_utils::{testing_pool, TestPool};
-    use std::collections::HashMap;
+    use revm::{
+        db::BundleState,
+        primitives::{AccountInfo, EnvWithHandlerCfg},
+    };
+    use revm_inspectors::tracing::TracingInspectorConfig;
+    use std::{collections::HashMap, sync::Arc};
 
     fn noop_eth_api() -> EthApi<NoopProvider, TestPool, NoopNetwork, EthEvmConfig> {
         let pool = testing_pool();
@@ -129,4 +203,93 @@ mod tests {
         let account = eth_api.get_account(address, Default::default()).await.unwrap();
         assert!(account.is_none());
     }
+
+    #[tokio::test]
+    async fn test_history_cutoff() {
+        let address = Address::random();
+        let balance = U256::from(1337);
+        let eth_api = mock_eth_api(HashMap::from([(address, ExtendedAccount::new(0, balance))]));
+
+        let provider = RpcNodeCore::provider(&eth_api);
+        let block_hash = |number: u64| B256::from(U256::from(number + 1));
+        provider.extend_blocks((0..200).map(|number| {
+            let header = Header { number, ..Default::default() };
+            (block_hash(number), Block { header, ..Default::default() })
+        }));
+        // the changesets of blocks up to 100 were pruned, so the oldest available state is the
+        // state at the end of block 100
+        provider.add_prune_checkpoint(
+            PruneSegment::AccountHistory,
+            PruneCheckpoint {
+                block_number: Some(100),
+                tx_number: None,
+                prune_mode: PruneMode::Distance(99),
+            },
+        );
+
+        // state above the cutoff
+        for block_id in [None, Some(BlockId::number(100)), Some(block_hash(150).into())] {
+            assert_eq!(eth_api.balance(address, block_id).await.unwrap(), balance);
+        }
+        let trace = eth_api
+            .spawn_trace_at_with_state(
+                EnvWithHandlerCfg::default(),
+                TracingInspectorConfig::default_parity(),
+                BlockId::number(100),
+                |_, _, _| Ok(()),
+            )
+            .await;
+        assert!(trace.is_ok());
+
+        // state below the cutoff
+        for block_id in [BlockId::number(99), block_hash(0).into()] {
+            let err = eth_api.balance(address, Some(block_id)).await.unwrap_err();
+            assert!(matches!(err, EthApiError::HistoryUnavailable(100)));
+        }
+        let err = eth_api
+            .spawn_trace_at_with_state(
+                EnvWithHandlerCfg::default(),
+                TracingInspectorConfig::default_parity(),
+                BlockId::number(99),
+                |_, _, _| Ok(()),
+            )
+            .await
+            .unwrap_err();
+        assert!(matches!(err, EthApiError::HistoryUnavailable(100)));
+
+        let err: jsonrpsee_types::ErrorObject<'static> = err.into();
+        assert_eq!(err.message(), "historical data not available below block 100");
+
+        // headers are still served below the cutoff
+        let header = eth_api.rpc_block_header(BlockId::number(50)).await.unwrap().unwrap();
+        assert_eq!(header.number, 50);
+    }
+
+    #[tokio::test]
+    async fn test_cached_pending_state_while_building() {
+        let address = Address::random();
+        let eth_api =
+            mock_eth_api(HashMap::from([(address, ExtendedAccount::new(0, U256::from(1)))]));
+        let provider = RpcNodeCore::provider(&eth_api);
+        let latest_hash = B256::with_last_byte(1);
+        provider.add_header(latest_hash, Header { number: 1, ..Default::default() });
+
+        let info = AccountInfo { balance: U256::from(2), ..Default::default() };
+        let state = ExecutionOutcome::new(
+            BundleState::builder(2..=2).state_present_account_info(address, info).build(),
+            Default::default(),
+            2,
+            Vec::new(),
+        );
+        *eth_api.inner.pending_state().write() = Some((latest_hash, Arc::new(state)));
+
+        // a pending block that is being built doesn't hide the published pending state
+        let _building = eth_api.pending_block().lock().await;
+        let pending = eth_api.cached_pending_state().unwrap().unwrap();
+        assert_eq!(pending.account_balance(&address).unwrap(), Some(U256::from(2)));
+
+        // the pending state of an outdated block is never used
+        provider.add_header(B256::with_last_byte(2), Header { number: 2, ..Default::default() });
+        assert!(eth_api.cached_pending_state().unwrap().is_none());
+    }
 }
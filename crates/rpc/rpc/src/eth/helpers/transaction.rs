@@ -2,9 +2,11 @@
 
 use crate::EthApi;
 use alloy_primitives::{Bytes, B256};
-use reth_provider::{BlockReader, BlockReaderIdExt, ProviderTx, TransactionsProvider};
+use reth_provider::{
+    BlockReader, BlockReaderIdExt, ProviderTx, PruneCheckpointReader, TransactionsProvider,
+};
 use reth_rpc_eth_api::{
-    helpers::{EthSigner, EthTransactions, LoadTransaction, SpawnBlocking},
+    helpers::{EthApiSpec, EthSigner, EthTransactions, LoadTransaction, SpawnBlocking},
     FromEthApiError, FullEthApiTypes, RpcNodeCore, RpcNodeCoreExt,
 };
 use reth_rpc_eth_types::utils::recover_raw_transaction;
@@ -13,7 +15,7 @@ use reth_transaction_pool::{PoolTransaction, TransactionOrigin, TransactionPool}
 impl<Provider, Pool, Network, EvmConfig> EthTransactions
     for EthApi<Provider, Pool, Network, EvmConfig>
 where
-    Self: LoadTransaction<Provider: BlockReaderIdExt>,
+    Self: LoadTransaction<Provider: BlockReaderIdExt> + EthApiSpec,
     Provider: BlockReader<Transaction = ProviderTx<Self::Provider>>,
 {
     #[inline]
@@ -25,6 +27,8 @@ where
     ///
     /// Returns the hash of the transaction.
     async fn send_raw_transaction(&self, tx: Bytes) -> Result<B256, Self::Error> {
+        self.ensure_tx_sync_distance()?;
+
         let recovered = recover_raw_transaction(&tx)?;
 
         // broadcast raw transaction to subscribers if there is any.
@@ -48,7 +52,10 @@ impl<Provider, Pool, Network, EvmConfig> LoadTransaction
 where
     Self: SpawnBlocking
         + FullEthApiTypes
-        + RpcNodeCoreExt<Provider: TransactionsProvider, Pool: TransactionPool>,
+        + RpcNodeCoreExt<
+            Provider: TransactionsProvider + PruneCheckpointReader,
+            Pool: TransactionPool,
+        >,
     Provider: BlockReader,
 {
 }
@@ -56,21 +63,59 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
-    use alloy_eips::eip1559::ETHEREUM_BLOCK_GAS_LIMIT;
+    use alloy_consensus::Header;
+    use alloy_eips::{eip1559::ETHEREUM_BLOCK_GAS_LIMIT, BlockNumHash};
     use alloy_primitives::{hex_literal::hex, Bytes};
     use reth_chainspec::ChainSpecProvider;
     use reth_evm_ethereum::EthEvmConfig;
-    use reth_network_api::noop::NoopNetwork;
-    use reth_provider::test_utils::NoopProvider;
+    use reth_network_api::{noop::NoopNetwork, NetworkError, NetworkInfo, NetworkStatus};
+    use reth_provider::test_utils::{MockEthProvider, NoopProvider};
+    use reth_prune_types::{PruneCheckpoint, PruneMode, PruneSegment};
     use reth_rpc_eth_api::helpers::EthTransactions;
     use reth_rpc_eth_types::{
-        EthStateCache, FeeHistoryCache, FeeHistoryCacheConfig, GasPriceOracle,
+        EthApiError, EthStateCache, FeeHistoryCache, FeeHistoryCacheConfig, GasPriceOracle,
     };
     use reth_rpc_server_types::constants::{
         DEFAULT_ETH_PROOF_WINDOW, DEFAULT_MAX_SIMULATE_BLOCKS, DEFAULT_PROOF_PERMITS,
     };
     use reth_tasks::pool::BlockingTaskPool;
     use reth_transaction_pool::{test_utils::testing_pool, TransactionPool};
+    use std::{
+        net::SocketAddr,
+        sync::{
+            atomic::{AtomicBool, Ordering},
+            Arc,
+        },
+    };
+    use tokio::sync::watch;
+
+    /// A [`NoopNetwork`] with a toggleable sync state.
+    #[derive(Debug, Clone, Default)]
+    struct SyncingNetwork {
+        is_syncing: Arc<AtomicBool>,
+    }
+
+    impl NetworkInfo for SyncingNetwork {
+        fn local_addr(&self) -> SocketAddr {
+            NoopNetwork::default().local_addr()
+        }
+
+        async fn network_status(&self) -> Result<NetworkStatus, NetworkError> {
+            NoopNetwork::default().network_status().await
+        }
+
+        fn chain_id(&self) -> u64 {
+            NoopNetwork::default().chain_id()
+        }
+
+        fn is_syncing(&self) -> bool {
+            self.is_syncing.load(Ordering::Relaxed)
+        }
+
+        fn is_initially_syncing(&self) -> bool {
+            false
+        }
+    }
 
     #[tokio::test]
     async fn send_raw_transaction() {
@@ -122,4 +167,114 @@ mod tests {
         assert!(pool.get(&tx_1_result).is_some(), "tx1 not found in the pool");
         assert!(pool.get(&tx_2_result).is_some(), "tx2 not found in the pool");
     }
+
+    #[tokio::test]
+    async fn transaction_lookup_disabled() {
+        let provider = MockEthProvider::default();
+        let evm_config = EthEvmConfig::new(provider.chain_spec());
+        let cache = EthStateCache::spawn(provider.clone(), Default::default());
+        let eth_api = EthApi::new(
+            provider.clone(),
+            testing_pool(),
+            NoopNetwork::default(),
+            cache.clone(),
+            GasPriceOracle::new(provider.clone(), Default::default(), cache),
+            ETHEREUM_BLOCK_GAS_LIMIT,
+            DEFAULT_MAX_SIMULATE_BLOCKS,
+            DEFAULT_ETH_PROOF_WINDOW,
+            BlockingTaskPool::build().expect("failed to build tracing pool"),
+            FeeHistoryCache::new(FeeHistoryCacheConfig::default()),
+            evm_config,
+            DEFAULT_PROOF_PERMITS,
+        );
+        let hash = B256::random();
+
+        // unknown transactions aren't found as long as the index exists
+        assert!(LoadTransaction::transaction_by_hash(&eth_api, hash).await.unwrap().is_none());
+        assert!(eth_api.raw_transaction_by_hash(hash).await.unwrap().is_none());
+
+        provider.add_prune_checkpoint(
+            PruneSegment::TransactionLookup,
+            PruneCheckpoint { block_number: Some(0), tx_number: None, prune_mode: PruneMode::Full },
+        );
+
+        let err = LoadTransaction::transaction_by_hash(&eth_api, hash).await.unwrap_err();
+        assert!(matches!(err, EthApiError::TransactionLookupDisabled));
+        let err = eth_api.raw_transaction_by_hash(hash).await.unwrap_err();
+        assert!(matches!(err, EthApiError::TransactionLookupDisabled));
+
+        let err: jsonrpsee_types::ErrorObject<'static> = err.into();
+        assert_eq!(err.message(), "transaction index disabled");
+    }
+
+    #[tokio::test]
+    async fn reject_transactions_while_syncing() {
+        let provider = MockEthProvider::default();
+        provider.add_header(B256::with_last_byte(1), Header { number: 100, ..Default::default() });
+        let (sync_target, sync_target_updates) = watch::channel(None);
+        let network = SyncingNetwork::default();
+        network.is_syncing.store(true, Ordering::Relaxed);
+
+        let pool = testing_pool();
+        let evm_config = EthEvmConfig::new(provider.chain_spec());
+        let cache = EthStateCache::spawn(provider.clone(), Default::default());
+        let mut eth_api = EthApi::new(
+            provider.clone(),
+            pool.clone(),
+            network.clone(),
+            cache.clone(),
+            GasPriceOracle::new(provider.clone(), Default::default(), cache),
+            ETHEREUM_BLOCK_GAS_LIMIT,
+            DEFAULT_MAX_SIMULATE_BLOCKS,
+            DEFAULT_ETH_PROOF_WINDOW,
+            BlockingTaskPool::build().expect("failed to build tracing pool"),
+            FeeHistoryCache::new(FeeHistoryCacheConfig::default()),
+            evm_config,
+            DEFAULT_PROOF_PERMITS,
+        );
+        Arc::get_mut(&mut eth_api.inner)
+            .unwrap()
+            .set_sync_target_updates(Some(sync_target_updates));
+
+        // https://etherscan.io/tx/0xa694b71e6c128a2ed8e2e0f6770bddbe52e3bb8f10e8472f9a79ab81497a8b5d
+        let tx = Bytes::from(hex!("02f871018303579880850555633d1b82520894eee27662c2b8eba3cd936a23f039f3189633e4c887ad591c62bdaeb180c080a07ea72c68abfb8fca1bd964f0f99132ed9280261bdca3e549546c0205e800f7d0a05b4ef3039e9c9b9babc179a1878fb825b5aaf5aed2fa8744854150157b08d6f3"));
+
+        // transactions are accepted while syncing unless configured otherwise
+        assert_eq!(eth_api.sync_distance(), None);
+        let hash = eth_api.send_raw_transaction(tx.clone()).await.unwrap();
+        pool.remove_transactions(vec![hash]);
+
+        Arc::get_mut(&mut eth_api.inner).unwrap().set_max_tx_sync_distance(Some(64));
+
+        // the distance is unknown until the consensus layer sent a forkchoice update
+        let err = eth_api.send_raw_transaction(tx.clone()).await.unwrap_err();
+        assert!(matches!(err, EthApiError::NodeSyncing(None)));
+        let err: jsonrpsee_types::ErrorObject<'static> = err.into();
+        assert_eq!(err.message(), "node is syncing");
+
+        sync_target.send_replace(Some(BlockNumHash::new(10_100, B256::with_last_byte(3))));
+        assert_eq!(eth_api.sync_distance(), Some(10_000));
+        let err = eth_api.send_raw_transaction(tx.clone()).await.unwrap_err();
+        assert!(matches!(err, EthApiError::NodeSyncing(Some(10_000))));
+        let err: jsonrpsee_types::ErrorObject<'static> = err.into();
+        assert_eq!(err.message(), "node is syncing, 10000 blocks behind");
+        assert!(pool.is_empty());
+
+        // within the allowed distance of the sync target
+        provider
+            .add_header(B256::with_last_byte(2), Header { number: 10_036, ..Default::default() });
+        assert_eq!(eth_api.sync_distance(), Some(64));
+        let hash = eth_api.send_raw_transaction(tx.clone()).await.unwrap();
+        pool.remove_transactions(vec![hash]);
+
+        // the consensus layer moved on
+        sync_target.send_replace(Some(BlockNumHash::new(20_000, B256::with_last_byte(4))));
+        assert!(eth_api.send_raw_transaction(tx.clone()).await.is_err());
+
+        // caught up with the network
+        network.is_syncing.store(false, Ordering::Relaxed);
+        assert_eq!(eth_api.sync_distance(), None);
+        eth_api.send_raw_transaction(tx).await.unwrap();
+        assert_eq!(pool.len(), 1);
+    }
 }
@@ -8,7 +8,7 @@ pub mod pubsub;
 pub mod sim_bundle;
 
 /// Implementation of `eth` namespace API.
-pub use bundle::EthBundle;
+pub use bundle::{EthBundle, EthBundleConfig};
 pub use core::EthApi;
 pub use filter::EthFilter;
 pub use pubsub::EthPubSub;
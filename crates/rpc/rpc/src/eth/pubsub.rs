@@ -5,7 +5,7 @@ use std::sync::Arc;
 use alloy_primitives::TxHash;
 use alloy_rpc_types_eth::{
     pubsub::{Params, PubSubSyncStatus, SubscriptionKind, SyncStatusMetadata},
-    FilteredParams, Header, Log,
+    Filter, FilterBlockOption, FilteredParams, Header, Log,
 };
 use futures::StreamExt;
 use jsonrpsee::{
@@ -76,6 +76,13 @@ where
         kind: SubscriptionKind,
         params: Option<Params>,
     ) -> jsonrpsee::core::SubscriptionResult {
+        if let (SubscriptionKind::Logs, Some(Params::Logs(filter))) = (&kind, &params) {
+            if let Err(err) = validate_logs_filter(filter) {
+                pending.reject(err).await;
+                return Ok(())
+            }
+        }
+
         let sink = pending.accept().await?;
         let pubsub = self.inner.clone();
         self.subscription_task_spawner.spawn(Box::pin(async move {
@@ -86,6 +93,23 @@ where
     }
 }
 
+/// Rejects the filter fields that can't be honored by a logs subscription.
+///
+/// A logs subscription matches the logs of new canonical blocks with the same matcher as
+/// `eth_getLogs`, so only `address` and `topics` apply. `fromBlock`, `toBlock` and `blockHash` are
+/// rejected instead of being silently ignored.
+fn validate_logs_filter(filter: &Filter) -> Result<(), ErrorObject<'static>> {
+    match filter.block_option {
+        FilterBlockOption::Range { from_block: None, to_block: None } => Ok(()),
+        FilterBlockOption::Range { .. } => Err(invalid_params_rpc_err(
+            "fromBlock and toBlock are not supported for logs subscriptions",
+        )),
+        FilterBlockOption::AtBlockHash(_) => {
+            Err(invalid_params_rpc_err("blockHash is not supported for logs subscriptions"))
+        }
+    }
+}
+
 /// The actual handler for an accepted [`EthPubSub::subscribe`] call.
 async fn handle_accepted<Eth, Events>(
     pubsub: Arc<EthPubSubInner<Eth, Events>>,
@@ -164,7 +188,18 @@ where
                 return Ok(())
             }
 
-            while canon_state.next().await.is_some() {
+            loop {
+                // Stop listening for new blocks as soon as the connection is closed, instead of
+                // only noticing it on the next sync status change.
+                tokio::select! {
+                    _ = accepted_sink.closed() => break,
+                    notification = canon_state.next() => {
+                        if notification.is_none() {
+                            break
+                        }
+                    }
+                }
+
                 let current_syncing = pubsub.eth_api.network().is_syncing();
                 // Only send a new response if the sync status has changed
                 if current_syncing != initial_sync_status {
@@ -204,7 +239,7 @@ impl From<SubscriptionSerializeError> for ErrorObject<'static> {
 }
 
 /// Pipes all stream items to the subscription sink.
-async fn pipe_from_stream<T, St>(
+pub(crate) async fn pipe_from_stream<T, St>(
     sink: SubscriptionSink,
     mut stream: St,
 ) -> Result<(), ErrorObject<'static>>
@@ -311,6 +346,8 @@ where
     }
 
     /// Returns a stream that yields all logs that match the given filter.
+    ///
+    /// Logs of blocks reverted by a reorg are emitted again with `removed: true`.
     fn log_stream(&self, filter: FilteredParams) -> impl Stream<Item = Log> {
         BroadcastStream::new(self.chain_events.subscribe_to_canonical_state())
             .map(move |canon_state| {
@@ -328,3 +365,26 @@ where
             })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{Address, B256};
+    use alloy_rpc_types_eth::BlockNumberOrTag;
+    use jsonrpsee::types::error::INVALID_PARAMS_CODE;
+
+    #[test]
+    fn logs_filter_validation() {
+        let filter = Filter::new().address(Address::ZERO).event_signature(B256::ZERO);
+        assert!(validate_logs_filter(&filter).is_ok());
+
+        let filter = Filter::new().from_block(BlockNumberOrTag::Latest);
+        assert_eq!(validate_logs_filter(&filter).unwrap_err().code(), INVALID_PARAMS_CODE);
+
+        let filter = Filter::new().to_block(1u64);
+        assert_eq!(validate_logs_filter(&filter).unwrap_err().code(), INVALID_PARAMS_CODE);
+
+        let filter = Filter::new().at_block_hash(B256::ZERO);
+        assert_eq!(validate_logs_filter(&filter).unwrap_err().code(), INVALID_PARAMS_CODE);
+    }
+}
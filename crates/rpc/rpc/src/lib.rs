@@ -34,6 +34,9 @@ use tower as _;
 
 mod admin;
 mod debug;
+mod debug_db;
+mod debug_log;
+mod debug_reorg;
 mod engine;
 pub mod eth;
 mod miner;
@@ -48,8 +51,11 @@ mod web3;
 
 pub use admin::AdminApi;
 pub use debug::DebugApi;
+pub use debug_db::{DebugDbApi, MAX_DB_DEBUG_KEY_SIZE, MAX_DB_DEBUG_VALUE_SIZE};
+pub use debug_log::DebugLogApi;
+pub use debug_reorg::DebugReorgApi;
 pub use engine::{EngineApi, EngineEthApi};
-pub use eth::{EthApi, EthBundle, EthFilter, EthPubSub};
+pub use eth::{EthApi, EthBundle, EthBundleConfig, EthFilter, EthPubSub};
 pub use miner::MinerApi;
 pub use net::NetApi;
 pub use otterscan::OtterscanApi;
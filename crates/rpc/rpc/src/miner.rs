@@ -1,13 +1,25 @@
-use alloy_primitives::{Bytes, U128};
+use alloy_primitives::{Address, Bytes, U128};
 use async_trait::async_trait;
 use jsonrpsee::core::RpcResult;
+use reth_payload_primitives::SuggestedFeeRecipient;
 use reth_rpc_api::MinerApiServer;
+use tracing::info;
 
 /// `miner` API implementation.
 ///
 /// This type provides the functionality for handling `miner` related requests.
 #[derive(Clone, Debug, Default)]
-pub struct MinerApi {}
+pub struct MinerApi {
+    /// The fee recipient of locally built payloads, if the node builds its own payloads.
+    fee_recipient: Option<SuggestedFeeRecipient>,
+}
+
+impl MinerApi {
+    /// Creates a new instance that controls the fee recipient of locally built payloads.
+    pub const fn new(fee_recipient: SuggestedFeeRecipient) -> Self {
+        Self { fee_recipient: Some(fee_recipient) }
+    }
+}
 
 #[async_trait]
 impl MinerApiServer for MinerApi {
@@ -22,4 +34,11 @@ impl MinerApiServer for MinerApi {
     fn set_gas_limit(&self, _gas_price: U128) -> RpcResult<bool> {
         Ok(false)
     }
+
+    fn set_etherbase(&self, etherbase: Address) -> RpcResult<bool> {
+        let Some(fee_recipient) = &self.fee_recipient else { return Ok(false) };
+        let previous = fee_recipient.set(etherbase);
+        info!(target: "rpc::miner", %previous, %etherbase, "Changed fee recipient");
+        Ok(true)
+    }
 }
@@ -15,7 +15,8 @@ use jsonrpsee::{core::RpcResult, types::ErrorObjectOwned};
 use reth_rpc_api::{EthApiServer, OtterscanServer};
 use reth_rpc_eth_api::{
     helpers::{EthTransactions, TraceExt},
-    FullEthApiTypes, RpcBlock, RpcHeader, RpcReceipt, RpcTransaction, TransactionCompat,
+    FullEthApiTypes, RpcBlockJson, RpcHeader, RpcReceipt, RpcTransaction, RpcTransactionJson,
+    TransactionCompat,
 };
 use reth_rpc_eth_types::{utils::binary_search, EthApiError};
 use reth_rpc_server_types::result::internal_rpc_err;
@@ -47,7 +48,7 @@ where
     /// Constructs a `BlockDetails` from a block and its receipts.
     fn block_details(
         &self,
-        block: RpcBlock<Eth::NetworkTypes>,
+        block: RpcBlockJson<Eth::NetworkTypes>,
         receipts: Vec<RpcReceipt<Eth::NetworkTypes>>,
     ) -> RpcResult<BlockDetails<RpcHeader<Eth::NetworkTypes>>> {
         // blob fee is burnt, so we don't need to calculate it
@@ -67,8 +68,8 @@ impl<Eth> OtterscanServer<RpcTransaction<Eth::NetworkTypes>, RpcHeader<Eth::Netw
     for OtterscanApi<Eth>
 where
     Eth: EthApiServer<
-            RpcTransaction<Eth::NetworkTypes>,
-            RpcBlock<Eth::NetworkTypes>,
+            RpcTransactionJson<Eth::NetworkTypes>,
+            RpcBlockJson<Eth::NetworkTypes>,
             RpcReceipt<Eth::NetworkTypes>,
             RpcHeader<Eth::NetworkTypes>,
         > + EthTransactions
@@ -218,7 +219,11 @@ where
         let receipts = self.eth.block_receipts(block_id);
         let (block, receipts) = futures::try_join!(block, receipts)?;
 
-        let mut block = block.ok_or(EthApiError::HeaderNotFound(block_id))?;
+        // transactions may be served already serialized, but are needed as objects here
+        let mut block = block
+            .ok_or(EthApiError::HeaderNotFound(block_id))?
+            .try_map_transactions(|tx| tx.into_object())
+            .map_err(|err| internal_rpc_err(err.to_string()))?;
         let mut receipts = receipts.ok_or(EthApiError::ReceiptsNotFound(block_id))?;
 
         // check if the number of transactions matches the number of receipts
@@ -1,26 +1,109 @@
-use std::{collections::HashMap, future::Future, sync::Arc};
+use std::{
+    collections::HashMap,
+    future::Future,
+    sync::Arc,
+    time::{SystemTime, UNIX_EPOCH},
+};
 
-use alloy_eips::BlockId;
-use alloy_primitives::{Address, U256};
+use alloy_consensus::{AnyReceiptEnvelope, BlockHeader, Transaction, Typed2718};
+use alloy_eips::{
+    calc_next_block_base_fee, eip1559::BaseFeeParams, eip4844::DATA_GAS_PER_BLOB,
+    eip7840::BlobParams, BlockId, BlockNumHash,
+};
+use alloy_primitives::{keccak256, map::AddressHashMap, Address, TxHash, B256, U256, U64};
+use alloy_rpc_types_eth::Header;
 use async_trait::async_trait;
-use jsonrpsee::core::RpcResult;
-use reth_errors::RethResult;
-use reth_provider::{BlockReaderIdExt, ChangeSetReader, StateProviderFactory};
-use reth_rpc_api::RethApiServer;
-use reth_rpc_eth_types::{EthApiError, EthResult};
+use futures::{Stream, StreamExt};
+use jsonrpsee::{core::RpcResult, PendingSubscriptionSink};
+use parking_lot::Mutex;
+use reth_chainspec::{
+    ChainSpecProvider, EthereumHardfork, EthereumHardforks, ForkCondition, Hardfork, Hardforks,
+    Head,
+};
+use reth_engine_primitives::EngineApiMessageVersion;
+use reth_primitives::{fee_math, TransactionMeta};
+use reth_primitives_traits::{Block, BlockBody, Receipt, SealedHeader, SignedTransaction};
+use reth_provider::{
+    BlockReader, BlockReaderIdExt, BlockTimestampDirection, ChainStatus, ChangeSetReader,
+    ChangeSetReaderIdExt, OrphanedBlocks, ProviderBlock, ProviderError, ProviderHeader,
+    ProviderReceipt, ProviderResult, ReceiptProvider, StateProofProvider, StateProviderFactory,
+};
+use reth_rpc_api::{
+    BalanceChange, BaseFeeForecast, BaseFeeForecastBlock, BlockTransactionReceipt,
+    ChainStatusBlock, ChainStatusNotification, ForkActivation, ForkReadiness, MultiProofResponse,
+    RethApiServer, StorageMultiProofResponse, TimestampDirection, UpcomingFork,
+};
+use reth_rpc_engine_api::capabilities::EngineCapabilities;
+use reth_rpc_eth_types::{receipt::build_receipt, EthApiError, EthResult};
+use reth_rpc_server_types::result::internal_rpc_err;
 use reth_tasks::TaskSpawner;
-use tokio::sync::oneshot;
+use reth_transaction_pool::{PoolTransaction, TransactionPool};
+use tokio::sync::{oneshot, watch};
+use tokio_stream::wrappers::WatchStream;
+
+use crate::eth::pubsub::pipe_from_stream;
+
+/// The maximum number of blocks `reth_baseFeeForecast` projects the fees of.
+pub const MAX_BASE_FEE_FORECAST_BLOCKS: u64 = 64;
+
+/// The maximum number of accounts and storage slots `reth_getMultiProof` proves in one request.
+pub const MAX_MULTIPROOF_TARGETS: usize = 1024;
+
+/// Returns the demand of the executable transactions of the pool.
+type PoolDemand = Arc<dyn Fn() -> Vec<TransactionDemand> + Send + Sync>;
+
+/// The gas an executable transaction uses and the fees it is willing to pay, as considered by
+/// `reth_baseFeeForecast`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TransactionDemand {
+    /// The gas limit of the transaction.
+    pub gas_limit: u64,
+    /// The maximum fee per gas of the transaction.
+    pub max_fee_per_gas: u128,
+    /// The blob gas of the transaction, zero for transactions without blobs.
+    pub blob_gas: u64,
+    /// The maximum fee per blob gas of the transaction, if it has blobs.
+    pub max_fee_per_blob_gas: Option<u128>,
+}
+
+impl TransactionDemand {
+    /// Returns the demand of the given pool transaction.
+    ///
+    /// Only blob transactions are converted to their consensus form, to count their blobs.
+    pub fn new<T: PoolTransaction>(tx: &T) -> Self {
+        let blob_gas = if tx.is_eip4844() {
+            tx.clone_into_consensus().into_signed().blob_gas_used().unwrap_or_default()
+        } else {
+            0
+        };
+        Self {
+            gas_limit: tx.gas_limit(),
+            max_fee_per_gas: tx.max_fee_per_gas(),
+            blob_gas,
+            max_fee_per_blob_gas: tx.max_fee_per_blob_gas(),
+        }
+    }
+}
 
 /// `reth` API implementation.
 ///
 /// This type provides the functionality for handling `reth` prototype RPC requests.
-pub struct RethApi<Provider> {
+pub struct RethApi<Provider: BlockReader> {
     inner: Arc<RethApiInner<Provider>>,
+    /// The blocks reorged out of the canonical chain or executed on a side chain, if they are
+    /// tracked.
+    orphaned_blocks: Option<OrphanedBlocks<ProviderBlock<Provider>, ProviderReceipt<Provider>>>,
+    /// The head, safe and finalized block of the chain, if they are tracked.
+    chain_status: Option<watch::Receiver<ChainStatus>>,
+    /// The demand of the executable pool transactions, if a pool is set.
+    pool_demand: Option<PoolDemand>,
+    /// The engine API methods served by the node, if known.
+    engine_capabilities: Option<EngineCapabilities>,
 }
 
 // === impl RethApi ===
 
-impl<Provider> RethApi<Provider> {
+impl<Provider: BlockReader> RethApi<Provider> {
     /// The provider that can interact with the chain.
     pub fn provider(&self) -> &Provider {
         &self.inner.provider
@@ -28,14 +111,89 @@ impl<Provider> RethApi<Provider> {
 
     /// Create a new instance of the [`RethApi`]
     pub fn new(provider: Provider, task_spawner: Box<dyn TaskSpawner>) -> Self {
-        let inner = Arc::new(RethApiInner { provider, task_spawner });
-        Self { inner }
+        let inner = Arc::new(RethApiInner {
+            provider,
+            task_spawner,
+            base_fee_forecast: Default::default(),
+        });
+        Self {
+            inner,
+            orphaned_blocks: None,
+            chain_status: None,
+            pool_demand: None,
+            engine_capabilities: None,
+        }
+    }
+
+    /// Sets the non-canonical blocks served by `reth_getTransactionReceiptInBlock`.
+    pub fn with_orphaned_blocks(
+        mut self,
+        orphaned_blocks: Option<OrphanedBlocks<ProviderBlock<Provider>, ProviderReceipt<Provider>>>,
+    ) -> Self {
+        self.orphaned_blocks = orphaned_blocks;
+        self
+    }
+
+    /// Sets the chain status served by `reth_subscribeChainStatus`.
+    pub fn with_chain_status(mut self, chain_status: Option<watch::Receiver<ChainStatus>>) -> Self {
+        self.chain_status = chain_status;
+        self
+    }
+
+    /// Sets the engine API methods served by the node, which `reth_forkReadiness` checks the
+    /// upcoming fork against.
+    pub fn with_engine_capabilities(mut self, engine_capabilities: EngineCapabilities) -> Self {
+        self.engine_capabilities = Some(engine_capabilities);
+        self
+    }
+
+    /// Sets the pool whose executable transactions `reth_baseFeeForecast` projects the fees from.
+    ///
+    /// These are the pending transactions, and the transactions that don't pay the current base fee
+    /// yet.
+    pub fn with_pool<Pool: TransactionPool + 'static>(mut self, pool: Pool) -> Self {
+        self.pool_demand = Some(Arc::new(move || {
+            let pending = pool.pending_transactions();
+            let basefee = pool.basefee_transactions();
+            pending
+                .iter()
+                .chain(&basefee)
+                .map(|tx| TransactionDemand::new(&tx.transaction))
+                .collect()
+        }));
+        self
+    }
+
+    /// Returns a stream of the chain status, which starts with the current status and coalesces
+    /// the changes that happen until the stream is polled again.
+    ///
+    /// Returns `None` if the chain status is not tracked.
+    pub fn chain_status_stream(
+        &self,
+    ) -> Option<impl Stream<Item = ChainStatusNotification> + Unpin + Send + 'static> {
+        let chain_status = self.chain_status.clone()?;
+        Some(WatchStream::new(chain_status).map(chain_status_notification))
+    }
+}
+
+/// Converts the [`ChainStatus`] to the item of `reth_subscribeChainStatus`.
+fn chain_status_notification(status: ChainStatus) -> ChainStatusNotification {
+    let block = |block: BlockNumHash| ChainStatusBlock { number: block.number, hash: block.hash };
+    ChainStatusNotification {
+        head: block(status.head),
+        safe: status.safe.map(block),
+        finalized: status.finalized.map(block),
+        unconfirmed: status.unconfirmed,
     }
 }
 
 impl<Provider> RethApi<Provider>
 where
-    Provider: BlockReaderIdExt + ChangeSetReader + StateProviderFactory + 'static,
+    Provider: BlockReaderIdExt
+        + ChangeSetReader
+        + StateProviderFactory
+        + ChainSpecProvider<ChainSpec: EthereumHardforks>
+        + 'static,
 {
     /// Executes the future on a new blocking task.
     async fn on_blocking_task<C, F, R>(&self, c: C) -> EthResult<R>
@@ -54,60 +212,558 @@ where
         rx.await.map_err(|_| EthApiError::InternalEthError)?
     }
 
-    /// Returns a map of addresses to changed account balanced for a particular block.
+    /// Returns a map of addresses to the balances before and after a particular block, for all
+    /// accounts whose balance changed in the block.
     pub async fn balance_changes_in_block(
         &self,
         block_id: BlockId,
-    ) -> EthResult<HashMap<Address, U256>> {
+    ) -> EthResult<HashMap<Address, BalanceChange>> {
         self.on_blocking_task(|this| async move { this.try_balance_changes_in_block(block_id) })
             .await
     }
 
-    fn try_balance_changes_in_block(&self, block_id: BlockId) -> EthResult<HashMap<Address, U256>> {
-        let Some(block_number) = self.provider().block_number_for_id(block_id)? else {
+    fn try_balance_changes_in_block(
+        &self,
+        block_id: BlockId,
+    ) -> EthResult<HashMap<Address, BalanceChange>> {
+        // Only this endpoint reports pruned changesets with the dedicated error code.
+        let pruned = |err: ProviderError| match err {
+            ProviderError::StateAtBlockPruned(number) => {
+                EthApiError::PrunedHistoryUnavailable(number)
+            }
+            err => err.into(),
+        };
+        let Some((_, accounts_before)) =
+            self.provider().account_block_changeset_by_id(block_id).map_err(pruned)?
+        else {
+            return Err(EthApiError::HeaderNotFound(block_id))
+        };
+
+        let state = self.provider().state_by_block_id(block_id).map_err(pruned)?;
+        let balances_before = accounts_before
+            .into_iter()
+            .map(|account| (account.address, account.info.map(|info| info.balance)));
+        Ok(balance_changes(balances_before, |address| state.account_balance(address))?)
+    }
+
+    /// Returns a multiproof of the given accounts and storage slots at a particular block.
+    ///
+    /// At most [`MAX_MULTIPROOF_TARGETS`] accounts and storage slots can be proven at once.
+    pub async fn multiproof(
+        &self,
+        targets: AddressHashMap<Vec<B256>>,
+        block_id: BlockId,
+    ) -> EthResult<MultiProofResponse> {
+        let len = targets.len() + targets.values().map(Vec::len).sum::<usize>();
+        if len > MAX_MULTIPROOF_TARGETS {
+            return Err(EthApiError::InvalidParams(format!(
+                "{len} targets exceed the maximum of {MAX_MULTIPROOF_TARGETS} accounts and storage \
+                 slots"
+            )))
+        }
+        self.on_blocking_task(|this| async move { this.try_multiproof(targets, block_id) }).await
+    }
+
+    fn try_multiproof(
+        &self,
+        targets: AddressHashMap<Vec<B256>>,
+        block_id: BlockId,
+    ) -> EthResult<MultiProofResponse> {
+        // Tags are resolved once, so that the state root belongs to the same block as the proof
+        // even if the chain advances in between.
+        let Some(header) = self.provider().sealed_header_by_id(block_id)? else {
             return Err(EthApiError::HeaderNotFound(block_id))
         };
 
-        let state = self.provider().state_by_block_id(block_id)?;
-        let accounts_before = self.provider().account_block_changeset(block_number)?;
-        let hash_map = accounts_before.iter().try_fold(
-            HashMap::default(),
-            |mut hash_map, account_before| -> RethResult<_> {
-                let current_balance = state.account_balance(&account_before.address)?;
-                let prev_balance = account_before.info.map(|info| info.balance);
-                if current_balance != prev_balance {
-                    hash_map.insert(account_before.address, current_balance.unwrap_or_default());
+        let state = self.provider().state_by_block_hash(header.hash())?;
+        let mut multiproof = state.address_multiproof(Default::default(), &targets)?;
+
+        let storage_proofs = targets
+            .into_keys()
+            .filter_map(|address| {
+                let storage = multiproof.storages.remove(&keccak256(address))?;
+                let proof = StorageMultiProofResponse {
+                    storage_hash: storage.root,
+                    storage_proof: storage
+                        .subtree
+                        .into_nodes_sorted()
+                        .into_iter()
+                        .map(|(_, node)| node)
+                        .collect(),
+                };
+                Some((address, proof))
+            })
+            .collect();
+
+        Ok(MultiProofResponse {
+            state_root: header.state_root(),
+            account_proof: multiproof
+                .account_subtree
+                .into_nodes_sorted()
+                .into_iter()
+                .map(|(_, node)| node)
+                .collect(),
+            storage_proofs,
+        })
+    }
+
+    /// Returns the fork schedule of the chain and the readiness for the next fork at the latest
+    /// block.
+    pub fn fork_readiness(&self) -> EthResult<ForkReadiness> {
+        let Some(header) = self.provider().latest_header()? else {
+            return Err(EthApiError::HeaderNotFound(BlockId::latest()))
+        };
+        let head = Head {
+            number: header.number(),
+            hash: header.hash(),
+            difficulty: header.difficulty(),
+            total_difficulty: self
+                .provider()
+                .header_td_by_number(header.number())?
+                .unwrap_or_default(),
+            timestamp: header.timestamp(),
+        };
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+
+        Ok(fork_readiness(
+            &self.provider().chain_spec(),
+            &head,
+            now,
+            self.engine_capabilities.as_ref(),
+        ))
+    }
+
+    /// Returns the projected fees of the `horizon_blocks` blocks following the latest block.
+    ///
+    /// The forecast is computed for [`MAX_BASE_FEE_FORECAST_BLOCKS`] blocks once per head on a
+    /// blocking task, and truncated to the requested horizon.
+    pub async fn base_fee_forecast(&self, horizon_blocks: u64) -> EthResult<BaseFeeForecast> {
+        if horizon_blocks > MAX_BASE_FEE_FORECAST_BLOCKS {
+            return Err(EthApiError::InvalidParams(format!(
+                "horizon exceeds the maximum of {MAX_BASE_FEE_FORECAST_BLOCKS} blocks"
+            )))
+        }
+        if self.pool_demand.is_none() {
+            return Err(EthApiError::Unsupported("base fee forecast requires a transaction pool"))
+        }
+        let mut forecast =
+            self.on_blocking_task(|this| async move { this.try_base_fee_forecast() }).await?;
+        forecast.blocks.truncate(horizon_blocks as usize);
+        Ok(forecast)
+    }
+
+    /// Returns the forecast of [`MAX_BASE_FEE_FORECAST_BLOCKS`] blocks following the latest block,
+    /// from the cache if it was already computed for it.
+    ///
+    /// The cache isn't locked while computing, so concurrent requests for a new head may compute
+    /// the same forecast more than once.
+    fn try_base_fee_forecast(&self) -> EthResult<BaseFeeForecast> {
+        let Some(pool_demand) = &self.pool_demand else {
+            return Err(EthApiError::Unsupported("base fee forecast requires a transaction pool"))
+        };
+        let Some(header) = self.provider().latest_header()? else {
+            return Err(EthApiError::HeaderNotFound(BlockId::latest()))
+        };
+        if let Some(forecast) = self.inner.base_fee_forecast.lock().as_ref() {
+            if forecast.head_hash == header.hash() {
+                return Ok(forecast.clone())
+            }
+        }
+
+        let chain_spec = self.provider().chain_spec();
+        let timestamp = header.timestamp() + 12;
+        let blob_params = if chain_spec.is_prague_active_at_timestamp(timestamp) {
+            BlobParams::prague()
+        } else {
+            BlobParams::cancun()
+        };
+        let forecast = BaseFeeForecast {
+            head_number: header.number(),
+            head_hash: header.hash(),
+            blocks: base_fee_forecast(
+                header.header(),
+                chain_spec.base_fee_params_at_timestamp(timestamp),
+                blob_params,
+                pool_demand(),
+                MAX_BASE_FEE_FORECAST_BLOCKS,
+            ),
+        };
+        *self.inner.base_fee_forecast.lock() = Some(forecast.clone());
+        Ok(forecast)
+    }
+
+    /// Returns the header of the last canonical block at or before the timestamp, or of the first
+    /// one at or after it.
+    pub async fn block_by_timestamp(
+        &self,
+        timestamp: u64,
+        direction: TimestampDirection,
+    ) -> EthResult<Option<SealedHeader<ProviderHeader<Provider>>>> {
+        self.on_blocking_task(
+            |this| async move { this.try_block_by_timestamp(timestamp, direction) },
+        )
+        .await
+    }
+
+    fn try_block_by_timestamp(
+        &self,
+        timestamp: u64,
+        direction: TimestampDirection,
+    ) -> EthResult<Option<SealedHeader<ProviderHeader<Provider>>>> {
+        let direction = match direction {
+            TimestampDirection::Before => BlockTimestampDirection::Before,
+            TimestampDirection::After => BlockTimestampDirection::After,
+        };
+        let Some(number) = self.provider().block_by_timestamp(timestamp, direction)? else {
+            return Ok(None)
+        };
+        Ok(self.provider().sealed_header(number)?)
+    }
+
+    /// Returns the receipt of the transaction in the block with the given hash, which may have
+    /// been reorged out of the canonical chain or executed on a side chain.
+    pub async fn transaction_receipt_in_block(
+        &self,
+        tx_hash: TxHash,
+        block_hash: B256,
+    ) -> EthResult<Option<BlockTransactionReceipt>> {
+        self.on_blocking_task(|this| async move {
+            this.try_transaction_receipt_in_block(tx_hash, block_hash)
+        })
+        .await
+    }
+
+    fn try_transaction_receipt_in_block(
+        &self,
+        tx_hash: TxHash,
+        block_hash: B256,
+    ) -> EthResult<Option<BlockTransactionReceipt>> {
+        if let Some(block) = self.provider().block_by_hash(block_hash)? {
+            let Some(receipts) = self.provider().receipts_by_block(block_hash.into())? else {
+                return Ok(None)
+            };
+            return transaction_receipt_in_block(
+                block.header(),
+                block_hash,
+                block.body().transactions(),
+                &receipts,
+                tx_hash,
+                true,
+            )
+        }
+
+        // the block is not canonical, but may have been reorged out or executed on a side chain
+        // recently
+        let Some(block) =
+            self.orphaned_blocks.as_ref().and_then(|blocks| blocks.block_by_hash(block_hash))
+        else {
+            return Err(EthApiError::HeaderNotFound(block_hash.into()))
+        };
+        transaction_receipt_in_block(
+            block.block.header(),
+            block_hash,
+            block.block.body().transactions(),
+            &block.receipts,
+            tx_hash,
+            false,
+        )
+    }
+}
+
+/// Builds the receipt of the transaction with the given hash from the block it is included in and
+/// the receipts of all transactions of the block.
+///
+/// Returns `None` if the transaction is not part of the block.
+fn transaction_receipt_in_block<T: SignedTransaction, R: Receipt>(
+    header: &impl BlockHeader,
+    block_hash: B256,
+    transactions: &[T],
+    receipts: &[R],
+    tx_hash: TxHash,
+    canonical: bool,
+) -> EthResult<Option<BlockTransactionReceipt>> {
+    let Some((index, transaction)) =
+        transactions.iter().enumerate().find(|(_, tx)| *tx.tx_hash() == tx_hash)
+    else {
+        return Ok(None)
+    };
+    let Some(receipt) = receipts.get(index) else { return Ok(None) };
+
+    let meta = TransactionMeta {
+        tx_hash,
+        index: index as u64,
+        block_hash,
+        block_number: header.number(),
+        base_fee: header.base_fee_per_gas(),
+        excess_blob_gas: header.excess_blob_gas(),
+        timestamp: header.timestamp(),
+    };
+    let receipt = build_receipt(transaction, meta, receipt, receipts, |inner| {
+        AnyReceiptEnvelope { inner, r#type: receipt.ty() }
+    })?;
+    Ok(Some(BlockTransactionReceipt { receipt, canonical }))
+}
+
+/// Pairs the balances of the changed accounts of a block before the block with their balances
+/// after it, as returned by `balance_after`, keeping only the accounts whose balance changed.
+///
+/// Accounts that don't exist before or after the block have a zero balance.
+fn balance_changes(
+    balances_before: impl IntoIterator<Item = (Address, Option<U256>)>,
+    mut balance_after: impl FnMut(&Address) -> ProviderResult<Option<U256>>,
+) -> ProviderResult<HashMap<Address, BalanceChange>> {
+    let mut changes = HashMap::default();
+    for (address, pre) in balances_before {
+        let post = balance_after(&address)?;
+        if pre != post {
+            changes.insert(
+                address,
+                BalanceChange { pre: pre.unwrap_or_default(), post: post.unwrap_or_default() },
+            );
+        }
+    }
+    Ok(changes)
+}
+
+/// Projects the fees of the `horizon` blocks following `head`, assuming the gas limit stays the
+/// same and the `demand` is included greedily, highest max fee per gas first, without any new
+/// transactions arriving.
+///
+/// A transaction is included in the first block whose base fee and blob base fee it pays for, and
+/// that has enough gas and blob gas left. Returns no blocks if `head` has no base fee.
+fn base_fee_forecast(
+    head: &impl BlockHeader,
+    base_fee_params: BaseFeeParams,
+    blob_params: BlobParams,
+    mut demand: Vec<TransactionDemand>,
+    horizon: u64,
+) -> Vec<BaseFeeForecastBlock> {
+    let Some(mut base_fee) = head.next_block_base_fee(base_fee_params) else { return Vec::new() };
+    let mut excess_blob_gas = head.excess_blob_gas().map(|excess_blob_gas| {
+        blob_params
+            .next_block_excess_blob_gas(excess_blob_gas, head.blob_gas_used().unwrap_or_default())
+    });
+    let gas_limit = head.gas_limit();
+    let max_blob_gas = blob_params.max_blob_count * DATA_GAS_PER_BLOB;
+    demand.sort_unstable_by(|a, b| b.max_fee_per_gas.cmp(&a.max_fee_per_gas));
+
+    let mut blocks = Vec::with_capacity(horizon as usize);
+    for number in (head.number() + 1..).take(horizon as usize) {
+        let blob_base_fee = excess_blob_gas.map(fee_math::saturating_blob_fee);
+        let mut gas_used = 0u64;
+        let mut blob_gas_used = 0u64;
+        demand.retain(|tx| {
+            let pays_base_fee = tx.max_fee_per_gas >= base_fee as u128;
+            let fits = gas_used.saturating_add(tx.gas_limit) <= gas_limit;
+            let fits_blobs = tx.blob_gas == 0 ||
+                (blob_base_fee
+                    .zip(tx.max_fee_per_blob_gas)
+                    .is_some_and(|(blob_base_fee, max_fee)| max_fee >= blob_base_fee) &&
+                    blob_gas_used.saturating_add(tx.blob_gas) <= max_blob_gas);
+            if pays_base_fee && fits && fits_blobs {
+                gas_used += tx.gas_limit;
+                blob_gas_used += tx.blob_gas;
+                return false
+            }
+            true
+        });
+
+        blocks.push(BaseFeeForecastBlock {
+            number,
+            base_fee,
+            blob_base_fee,
+            projected_gas_used_ratio: ratio(gas_used, gas_limit),
+            projected_blob_gas_used_ratio: ratio(blob_gas_used, max_blob_gas),
+        });
+
+        base_fee = calc_next_block_base_fee(gas_used, gas_limit, base_fee, base_fee_params);
+        excess_blob_gas = excess_blob_gas.map(|excess_blob_gas| {
+            blob_params.next_block_excess_blob_gas(excess_blob_gas, blob_gas_used)
+        });
+    }
+    blocks
+}
+
+/// Returns `used` as a share of `max`, zero if `max` is zero.
+fn ratio(used: u64, max: u64) -> f64 {
+    if max == 0 {
+        return 0.0
+    }
+    used as f64 / max as f64
+}
+
+/// Builds the [`ForkReadiness`] of the given chain spec at `head`, with countdowns relative to the
+/// unix timestamp `now`, and the engine API readiness checked against the served
+/// `engine_capabilities`.
+fn fork_readiness(
+    chain_spec: &impl Hardforks,
+    head: &Head,
+    now: u64,
+    engine_capabilities: Option<&EngineCapabilities>,
+) -> ForkReadiness {
+    let forks = chain_spec
+        .forks_iter()
+        .filter(|(_, condition)| *condition != ForkCondition::Never)
+        .map(|(fork, condition)| fork_activation(fork, condition, head))
+        .collect();
+
+    let next_fork = chain_spec.next_fork(head).map(|(fork, condition)| {
+        let engine_api_version = engine_api_version(fork);
+        let blocks_remaining = match condition {
+            ForkCondition::Block(block) | ForkCondition::TTD { fork_block: Some(block), .. } => {
+                Some(block.saturating_sub(head.number))
+            }
+            _ => None,
+        };
+        UpcomingFork {
+            fork: fork_activation(fork, condition, head),
+            blocks_remaining,
+            seconds_remaining: condition.as_timestamp().map(|time| time.saturating_sub(now)),
+            fork_id: chain_spec.next_fork_id(head),
+            engine_api_version: engine_api_version.map(|version| version as u8),
+            engine_api_ready: match engine_api_version {
+                Some(version) => {
+                    engine_capabilities.map(|capabilities| serves_version(capabilities, version))
                 }
-                Ok(hash_map)
+                // timestamp based forks are activated post-merge and always change the engine API,
+                // so a fork without a known version isn't supported
+                None if condition.is_timestamp() => Some(false),
+                None => None,
             },
-        )?;
-        Ok(hash_map)
+        }
+    });
+
+    ForkReadiness {
+        head_number: head.number,
+        head_timestamp: head.timestamp,
+        forks,
+        fork_id: chain_spec.fork_id(head),
+        next_fork,
+    }
+}
+
+/// Returns the [`ForkActivation`] of a single hardfork.
+fn fork_activation(fork: &dyn Hardfork, condition: ForkCondition, head: &Head) -> ForkActivation {
+    let (block, timestamp, total_difficulty) = match condition {
+        ForkCondition::Block(block) => (Some(block), None, None),
+        ForkCondition::TTD { fork_block, total_difficulty, .. } => {
+            (fork_block, None, Some(total_difficulty))
+        }
+        ForkCondition::Timestamp(timestamp) => (None, Some(timestamp), None),
+        ForkCondition::Never => (None, None, None),
+    };
+    ForkActivation {
+        name: fork.name().to_string(),
+        block,
+        timestamp,
+        total_difficulty,
+        active: condition.active_at_head(head),
+    }
+}
+
+/// Returns whether the payload methods of the given engine API version are served.
+fn serves_version(capabilities: &EngineCapabilities, version: EngineApiMessageVersion) -> bool {
+    let version = version as u8;
+    capabilities.contains(&format!("engine_newPayloadV{version}")) &&
+        capabilities.contains(&format!("engine_getPayloadV{version}"))
+}
+
+/// Returns the engine API version introduced by the given hardfork, if it is supported.
+fn engine_api_version(fork: &dyn Hardfork) -> Option<EngineApiMessageVersion> {
+    match fork.name().parse::<EthereumHardfork>().ok()? {
+        EthereumHardfork::Paris => Some(EngineApiMessageVersion::V1),
+        EthereumHardfork::Shanghai => Some(EngineApiMessageVersion::V2),
+        EthereumHardfork::Cancun => Some(EngineApiMessageVersion::V3),
+        EthereumHardfork::Prague => Some(EngineApiMessageVersion::V4),
+        _ => None,
     }
 }
 
 #[async_trait]
-impl<Provider> RethApiServer for RethApi<Provider>
+impl<Provider> RethApiServer<Header<ProviderHeader<Provider>>> for RethApi<Provider>
 where
-    Provider: BlockReaderIdExt + ChangeSetReader + StateProviderFactory + 'static,
+    Provider: BlockReaderIdExt
+        + ChangeSetReader
+        + StateProviderFactory
+        + ChainSpecProvider<ChainSpec: EthereumHardforks>
+        + 'static,
 {
     /// Handler for `reth_getBalanceChangesInBlock`
     async fn reth_get_balance_changes_in_block(
         &self,
         block_id: BlockId,
-    ) -> RpcResult<HashMap<Address, U256>> {
+    ) -> RpcResult<HashMap<Address, BalanceChange>> {
         Ok(Self::balance_changes_in_block(self, block_id).await?)
     }
+
+    /// Handler for `reth_getMultiProof`
+    async fn reth_get_multi_proof(
+        &self,
+        targets: AddressHashMap<Vec<B256>>,
+        block_id: Option<BlockId>,
+    ) -> RpcResult<MultiProofResponse> {
+        Ok(Self::multiproof(self, targets, block_id.unwrap_or_default()).await?)
+    }
+
+    /// Handler for `reth_forkReadiness`
+    async fn reth_fork_readiness(&self) -> RpcResult<ForkReadiness> {
+        Ok(Self::fork_readiness(self)?)
+    }
+
+    /// Handler for `reth_baseFeeForecast`
+    async fn reth_base_fee_forecast(&self, horizon_blocks: u64) -> RpcResult<BaseFeeForecast> {
+        Ok(Self::base_fee_forecast(self, horizon_blocks).await?)
+    }
+
+    /// Handler for `reth_getBlockByTimestamp`
+    async fn reth_get_block_by_timestamp(
+        &self,
+        timestamp: U64,
+        direction: TimestampDirection,
+    ) -> RpcResult<Option<Header<ProviderHeader<Provider>>>> {
+        Ok(Self::block_by_timestamp(self, timestamp.to(), direction)
+            .await?
+            .map(|header| Header::from_consensus(header.into(), None, None)))
+    }
+
+    /// Handler for `reth_getTransactionReceiptInBlock`
+    async fn reth_get_transaction_receipt_in_block(
+        &self,
+        tx_hash: TxHash,
+        block_hash: B256,
+    ) -> RpcResult<Option<BlockTransactionReceipt>> {
+        Ok(Self::transaction_receipt_in_block(self, tx_hash, block_hash).await?)
+    }
+
+    /// Handler for `reth_subscribeChainStatus`
+    async fn reth_subscribe_chain_status(
+        &self,
+        pending: PendingSubscriptionSink,
+    ) -> jsonrpsee::core::SubscriptionResult {
+        let Some(stream) = self.chain_status_stream() else {
+            pending.reject(internal_rpc_err("chain status is not tracked")).await;
+            return Ok(())
+        };
+        let sink = pending.accept().await?;
+        pipe_from_stream(sink, stream).await?;
+        Ok(())
+    }
 }
 
-impl<Provider> std::fmt::Debug for RethApi<Provider> {
+impl<Provider: BlockReader> std::fmt::Debug for RethApi<Provider> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("RethApi").finish_non_exhaustive()
     }
 }
 
-impl<Provider> Clone for RethApi<Provider> {
+impl<Provider: BlockReader> Clone for RethApi<Provider> {
     fn clone(&self) -> Self {
-        Self { inner: Arc::clone(&self.inner) }
+        Self {
+            inner: Arc::clone(&self.inner),
+            orphaned_blocks: self.orphaned_blocks.clone(),
+            chain_status: self.chain_status.clone(),
+            pool_demand: self.pool_demand.clone(),
+        }
     }
 }
 
@@ -116,4 +772,454 @@ struct RethApiInner<Provider> {
     provider: Provider,
     /// The type that can spawn tasks which would otherwise block.
     task_spawner: Box<dyn TaskSpawner>,
+    /// The forecast of `reth_baseFeeForecast` for the latest head it was requested at.
+    base_fee_forecast: Mutex<Option<BaseFeeForecast>>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_genesis::Genesis;
+    use reth_chainspec::{Chain, ChainSpec};
+    use reth_db_api::models::AccountBeforeTx;
+    use reth_primitives::Account;
+    use reth_provider::{
+        test_utils::{ExtendedAccount, MockEthProvider},
+        OrphanedBlock,
+    };
+    use reth_prune_types::{PruneCheckpoint, PruneMode, PruneSegment};
+    use reth_rpc_engine_api::capabilities::CAPABILITIES;
+    use reth_tasks::TokioTaskExecutor;
+    use reth_testing_utils::generators::{self, random_block, random_receipt, BlockParams};
+
+    #[test]
+    fn balance_changes_of_block() {
+        let beneficiary = Address::with_last_byte(1);
+        let withdrawal_recipient = Address::with_last_byte(2);
+        let selfdestructed = Address::with_last_byte(3);
+        let sender = Address::with_last_byte(4);
+        let recipient = Address::with_last_byte(5);
+        let nonce_only = Address::with_last_byte(6);
+
+        let provider = MockEthProvider::default();
+        let account_before = |address, balance: Option<u64>| AccountBeforeTx {
+            address,
+            info: balance
+                .map(|balance| Account { balance: U256::from(balance), ..Default::default() }),
+        };
+        provider.add_account_changeset(
+            1,
+            vec![
+                account_before(beneficiary, Some(10)),
+                // withdrawal to an account that did not exist
+                account_before(withdrawal_recipient, None),
+                account_before(selfdestructed, Some(50)),
+                account_before(sender, Some(1_000)),
+                account_before(recipient, Some(5)),
+                // account whose nonce changed, but not its balance
+                account_before(nonce_only, Some(7)),
+            ],
+        );
+        provider.extend_accounts([
+            (beneficiary, ExtendedAccount::new(0, U256::from(12))),
+            (withdrawal_recipient, ExtendedAccount::new(0, U256::from(32))),
+            (sender, ExtendedAccount::new(1, U256::from(898))),
+            (recipient, ExtendedAccount::new(0, U256::from(105))),
+            (nonce_only, ExtendedAccount::new(1, U256::from(7))),
+        ]);
+        let api = RethApi::new(provider, Box::new(TokioTaskExecutor::default()));
+
+        let changes = api.try_balance_changes_in_block(1.into()).unwrap();
+        assert_eq!(
+            changes,
+            HashMap::from([
+                (beneficiary, BalanceChange { pre: U256::from(10), post: U256::from(12) }),
+                (withdrawal_recipient, BalanceChange { pre: U256::ZERO, post: U256::from(32) }),
+                (selfdestructed, BalanceChange { pre: U256::from(50), post: U256::ZERO }),
+                (sender, BalanceChange { pre: U256::from(1_000), post: U256::from(898) }),
+                (recipient, BalanceChange { pre: U256::from(5), post: U256::from(105) }),
+            ])
+        );
+    }
+
+    #[test]
+    fn balance_changes_of_pruned_block() {
+        let provider = MockEthProvider::default();
+        provider.add_prune_checkpoint(
+            PruneSegment::AccountHistory,
+            PruneCheckpoint {
+                block_number: Some(10),
+                tx_number: None,
+                prune_mode: PruneMode::Before(11),
+            },
+        );
+        let api = RethApi::new(provider, Box::new(TokioTaskExecutor::default()));
+
+        assert!(matches!(
+            api.try_balance_changes_in_block(10.into()),
+            Err(EthApiError::PrunedHistoryUnavailable(10))
+        ));
+        assert!(api.try_balance_changes_in_block(11.into()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn fork_readiness_with_future_fork() {
+        let chain_spec = ChainSpec::builder()
+            .chain(Chain::mainnet())
+            .genesis(Genesis::default())
+            .cancun_activated()
+            .with_fork(EthereumHardfork::Prague, ForkCondition::Timestamp(10_000))
+            .with_fork(EthereumHardfork::Osaka, ForkCondition::Never)
+            .build();
+        let head = Head { number: 100, timestamp: 1_200, ..Default::default() };
+
+        let capabilities = EngineCapabilities::default();
+        let readiness = fork_readiness(&chain_spec, &head, 1_000, Some(&capabilities));
+        assert_eq!(readiness.fork_id, chain_spec.fork_id(&head));
+        assert!(readiness.forks.iter().all(|fork| fork.name != "Osaka"));
+        assert!(readiness
+            .forks
+            .iter()
+            .filter(|fork| fork.name != "Prague")
+            .all(|fork| fork.active));
+
+        let next = readiness.next_fork.unwrap();
+        assert_eq!(next.fork.name, "Prague");
+        assert_eq!(next.fork.timestamp, Some(10_000));
+        assert!(!next.fork.active);
+        assert_eq!(next.seconds_remaining, Some(9_000));
+        assert_eq!(next.blocks_remaining, None);
+        assert_eq!(next.fork_id, chain_spec.hardfork_fork_id(EthereumHardfork::Prague));
+        assert_eq!(next.engine_api_version, Some(4));
+        assert_eq!(next.engine_api_ready, Some(true));
+
+        // the served engine API lacks the payload methods of the fork
+        let cancun_capabilities = EngineCapabilities::new(
+            CAPABILITIES.iter().copied().filter(|method| !method.ends_with("PayloadV4")),
+        );
+        let next = fork_readiness(&chain_spec, &head, 1_000, Some(&cancun_capabilities))
+            .next_fork
+            .unwrap();
+        assert_eq!(next.engine_api_ready, Some(false));
+
+        // the served engine API is unknown
+        let next = fork_readiness(&chain_spec, &head, 1_000, None).next_fork.unwrap();
+        assert_eq!(next.engine_api_ready, None);
+
+        // unsupported engine API version
+        let chain_spec = ChainSpec::builder()
+            .chain(Chain::mainnet())
+            .genesis(Genesis::default())
+            .prague_activated()
+            .with_fork(EthereumHardfork::Osaka, ForkCondition::Timestamp(10_000))
+            .build();
+        let next =
+            fork_readiness(&chain_spec, &head, 1_000, Some(&capabilities)).next_fork.unwrap();
+        assert_eq!(next.fork.name, "Osaka");
+        assert_eq!(next.engine_api_version, None);
+        assert_eq!(next.engine_api_ready, Some(false));
+
+        // everything active
+        let head = Head { timestamp: 10_000, ..head };
+        assert!(fork_readiness(&chain_spec, &head, 10_000, Some(&capabilities))
+            .next_fork
+            .is_none());
+    }
+
+    fn forecast_head() -> alloy_consensus::Header {
+        alloy_consensus::Header {
+            number: 100,
+            gas_limit: 30_000_000,
+            gas_used: 15_000_000,
+            base_fee_per_gas: Some(1_000_000_000),
+            blob_gas_used: Some(0),
+            excess_blob_gas: Some(0),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn base_fee_forecast_with_empty_pool() {
+        let params = BaseFeeParams::ethereum();
+        let blocks =
+            base_fee_forecast(&forecast_head(), params, BlobParams::cancun(), Vec::new(), 5);
+        assert_eq!(blocks.len(), 5);
+        assert_eq!(
+            blocks.iter().map(|block| block.number).collect::<Vec<_>>(),
+            [101, 102, 103, 104, 105]
+        );
+
+        // the head used its target, after that the base fee decays with every empty block
+        assert_eq!(blocks[0].base_fee, 1_000_000_000);
+        for (block, next) in blocks.iter().zip(&blocks[1..]) {
+            assert_eq!(
+                next.base_fee,
+                calc_next_block_base_fee(0, 30_000_000, block.base_fee, params)
+            );
+            assert!(next.base_fee < block.base_fee);
+        }
+        assert!(blocks.iter().all(|block| block.projected_gas_used_ratio == 0.0 &&
+            block.projected_blob_gas_used_ratio == 0.0 &&
+            block.blob_base_fee == Some(1)));
+
+        // no forecast before london
+        let head = alloy_consensus::Header { base_fee_per_gas: None, ..forecast_head() };
+        assert!(base_fee_forecast(&head, params, BlobParams::cancun(), Vec::new(), 5).is_empty());
+    }
+
+    #[test]
+    fn base_fee_forecast_with_saturated_pool() {
+        let params = BaseFeeParams::ethereum();
+        let demand = vec![
+            TransactionDemand {
+                gas_limit: 1_000_000,
+                max_fee_per_gas: 1_000_000_000_000,
+                ..Default::default()
+            };
+            64 * 30
+        ];
+        let blocks = base_fee_forecast(&forecast_head(), params, BlobParams::cancun(), demand, 64);
+        assert_eq!(blocks.len(), 64);
+        assert!(blocks.iter().all(|block| block.projected_gas_used_ratio == 1.0));
+        for (block, next) in blocks.iter().zip(&blocks[1..]) {
+            assert_eq!(
+                next.base_fee,
+                calc_next_block_base_fee(30_000_000, 30_000_000, block.base_fee, params)
+            );
+        }
+
+        // once the base fee exceeds the max fee of the remaining transactions, they're no longer
+        // included and the base fee decays again
+        let demand = vec![
+            TransactionDemand {
+                gas_limit: 1_000_000,
+                max_fee_per_gas: 1_200_000_000,
+                ..Default::default()
+            };
+            64 * 30
+        ];
+        let blocks = base_fee_forecast(&forecast_head(), params, BlobParams::cancun(), demand, 4);
+        assert_eq!(
+            blocks.iter().map(|block| block.projected_gas_used_ratio).collect::<Vec<_>>(),
+            [1.0, 1.0, 0.0, 1.0]
+        );
+        assert_eq!(blocks[2].base_fee, 1_265_625_000);
+
+        // every block is filled with the maximum number of blobs, so the blob base fee rises
+        let demand = vec![
+            TransactionDemand {
+                gas_limit: 21_000,
+                max_fee_per_gas: 1_000_000_000_000,
+                blob_gas: 6 * DATA_GAS_PER_BLOB,
+                max_fee_per_blob_gas: Some(1_000_000_000_000),
+            };
+            64
+        ];
+        let blocks = base_fee_forecast(&forecast_head(), params, BlobParams::cancun(), demand, 64);
+        assert!(blocks.iter().all(|block| block.projected_blob_gas_used_ratio == 1.0));
+        for (block, next) in blocks.iter().zip(&blocks[1..]) {
+            assert!(next.blob_base_fee >= block.blob_base_fee);
+        }
+        assert!(blocks[63].blob_base_fee > blocks[0].blob_base_fee);
+    }
+
+    #[tokio::test]
+    async fn multiproof_targets_are_bounded() {
+        let api = RethApi::new(MockEthProvider::default(), Box::new(TokioTaskExecutor::default()));
+        let targets = |slots| AddressHashMap::from_iter([(Address::ZERO, vec![B256::ZERO; slots])]);
+        assert!(matches!(
+            api.multiproof(targets(MAX_MULTIPROOF_TARGETS), BlockId::latest()).await,
+            Err(EthApiError::InvalidParams(_))
+        ));
+        assert!(!matches!(
+            api.multiproof(targets(MAX_MULTIPROOF_TARGETS - 1), BlockId::latest()).await,
+            Err(EthApiError::InvalidParams(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn base_fee_forecast_horizon_is_bounded() {
+        let api = RethApi::new(MockEthProvider::default(), Box::new(TokioTaskExecutor::default()));
+        assert!(matches!(
+            api.base_fee_forecast(MAX_BASE_FEE_FORECAST_BLOCKS + 1).await,
+            Err(EthApiError::InvalidParams(_))
+        ));
+        assert!(matches!(
+            api.base_fee_forecast(MAX_BASE_FEE_FORECAST_BLOCKS).await,
+            Err(EthApiError::Unsupported(_))
+        ));
+    }
+
+    #[test]
+    fn block_by_timestamp_with_irregular_block_times() {
+        let provider = MockEthProvider::default();
+        let timestamps = [100, 112, 112, 112, 140, 141, 200];
+        provider.extend_headers(timestamps.iter().enumerate().map(|(number, timestamp)| {
+            let header = alloy_consensus::Header {
+                number: number as u64,
+                timestamp: *timestamp,
+                ..Default::default()
+            };
+            (header.hash_slow(), header)
+        }));
+        let api = RethApi::new(provider, Box::new(TokioTaskExecutor::default()));
+        let number = |timestamp, direction| {
+            api.try_block_by_timestamp(timestamp, direction).unwrap().map(|header| header.number)
+        };
+
+        // before genesis
+        assert_eq!(number(99, TimestampDirection::Before), None);
+        assert_eq!(number(99, TimestampDirection::After), Some(0));
+        // exact matches, equal timestamps resolve to the outermost block
+        assert_eq!(number(100, TimestampDirection::Before), Some(0));
+        assert_eq!(number(112, TimestampDirection::Before), Some(3));
+        assert_eq!(number(112, TimestampDirection::After), Some(1));
+        // between blocks
+        assert_eq!(number(120, TimestampDirection::Before), Some(3));
+        assert_eq!(number(120, TimestampDirection::After), Some(4));
+        // after the tip
+        assert_eq!(number(200, TimestampDirection::After), Some(6));
+        assert_eq!(number(201, TimestampDirection::Before), Some(6));
+        assert_eq!(number(201, TimestampDirection::After), None);
+
+        assert_eq!(
+            serde_json::from_str::<TimestampDirection>(r#""before""#).unwrap(),
+            TimestampDirection::Before
+        );
+    }
+
+    #[test]
+    fn block_by_timestamp_with_expired_headers() {
+        let provider = MockEthProvider::default();
+        // the headers of blocks 0 to 2 expired
+        provider.extend_headers([(3, 130), (4, 140), (5, 150)].into_iter().map(
+            |(number, timestamp)| {
+                let header = alloy_consensus::Header { number, timestamp, ..Default::default() };
+                (header.hash_slow(), header)
+            },
+        ));
+        let api = RethApi::new(provider, Box::new(TokioTaskExecutor::default()));
+        let number = |timestamp, direction| {
+            api.try_block_by_timestamp(timestamp, direction).unwrap().map(|header| header.number)
+        };
+
+        // the searched block may be one of the expired blocks
+        assert_eq!(number(120, TimestampDirection::Before), None);
+        assert_eq!(number(120, TimestampDirection::After), None);
+        assert_eq!(number(130, TimestampDirection::After), None);
+        // available blocks
+        assert_eq!(number(130, TimestampDirection::Before), Some(3));
+        assert_eq!(number(135, TimestampDirection::After), Some(4));
+        assert_eq!(number(200, TimestampDirection::Before), Some(5));
+    }
+
+    #[tokio::test]
+    async fn chain_status_subscription() {
+        let api = RethApi::new(MockEthProvider::default(), Box::new(TokioTaskExecutor::default()));
+        assert!(api.chain_status_stream().is_none());
+
+        let head = BlockNumHash::new(10, B256::random());
+        let (tx, rx) =
+            watch::channel(ChainStatus { head, unconfirmed: true, ..Default::default() });
+        let api = api.with_chain_status(Some(rx));
+        let mut stream = api.chain_status_stream().unwrap();
+
+        // the current status is emitted right away
+        let block =
+            |block: BlockNumHash| ChainStatusBlock { number: block.number, hash: block.hash };
+        assert_eq!(
+            stream.next().await,
+            Some(ChainStatusNotification {
+                head: block(head),
+                unconfirmed: true,
+                ..Default::default()
+            })
+        );
+
+        // rapid updates are coalesced into the latest status
+        let safe = BlockNumHash::new(9, B256::random());
+        let finalized = BlockNumHash::new(8, B256::random());
+        tx.send_modify(|status| status.head = BlockNumHash::new(11, B256::random()));
+        let head = BlockNumHash::new(12, B256::random());
+        tx.send_modify(|status| status.head = head);
+        tx.send_modify(|status| status.safe = Some(safe));
+        tx.send_modify(|status| status.finalized = Some(finalized));
+        tx.send_modify(|status| status.unconfirmed = false);
+        let expected = ChainStatusNotification {
+            head: block(head),
+            safe: Some(block(safe)),
+            finalized: Some(block(finalized)),
+            unconfirmed: false,
+        };
+        assert_eq!(stream.next().await, Some(expected));
+        assert!(futures::poll!(stream.next()).is_pending());
+
+        assert_eq!(
+            serde_json::to_value(expected.finalized).unwrap(),
+            serde_json::json!({ "number": "0x8", "hash": finalized.hash })
+        );
+
+        // the stream ends with the tracker
+        drop(tx);
+        assert_eq!(stream.next().await, None);
+    }
+
+    #[tokio::test]
+    async fn transaction_receipt_in_orphaned_block() {
+        let mut rng = generators::rng();
+        let provider = MockEthProvider::default();
+
+        // a fork at block 1, only the block that was reorged out includes the transactions
+        let canonical =
+            random_block(&mut rng, 1, BlockParams { tx_count: Some(0), ..Default::default() });
+        provider.add_block(canonical.hash(), canonical.clone().unseal());
+        let orphaned =
+            random_block(&mut rng, 1, BlockParams { tx_count: Some(2), ..Default::default() });
+        let receipts = orphaned
+            .body
+            .transactions
+            .iter()
+            .zip(1..)
+            .map(|(tx, index)| {
+                let mut receipt = random_receipt(&mut rng, tx, Some(1));
+                receipt.cumulative_gas_used = 21_000 * index;
+                receipt
+            })
+            .collect::<Vec<_>>();
+
+        let orphaned_blocks = OrphanedBlocks::default();
+        orphaned_blocks.record(vec![OrphanedBlock {
+            block: Arc::new(orphaned.clone()),
+            receipts: Arc::new(receipts.clone()),
+        }]);
+        let api = RethApi::new(provider, Box::new(TokioTaskExecutor::default()))
+            .with_orphaned_blocks(Some(orphaned_blocks));
+
+        let tx = &orphaned.body.transactions[1];
+        let receipt = api
+            .transaction_receipt_in_block(*tx.tx_hash(), orphaned.hash())
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(!receipt.canonical);
+        assert_eq!(receipt.receipt.transaction_hash, *tx.tx_hash());
+        assert_eq!(receipt.receipt.transaction_index, Some(1));
+        assert_eq!(receipt.receipt.block_hash, Some(orphaned.hash()));
+        assert_eq!(receipt.receipt.block_number, Some(1));
+        assert_eq!(receipt.receipt.gas_used, 21_000);
+        assert_eq!(receipt.receipt.inner.r#type, tx.ty());
+        assert_eq!(receipt.receipt.inner.inner.receipt.logs.len(), receipts[1].logs.len());
+
+        // the transaction is not part of the canonical block
+        assert_eq!(
+            api.transaction_receipt_in_block(*tx.tx_hash(), canonical.hash()).await.unwrap(),
+            None
+        );
+
+        // unknown block
+        assert!(matches!(
+            api.transaction_receipt_in_block(*tx.tx_hash(), B256::random()).await,
+            Err(EthApiError::HeaderNotFound(_))
+        ));
+    }
 }
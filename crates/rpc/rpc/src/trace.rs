@@ -79,9 +79,13 @@ where
     Eth: TraceExt + 'static,
 {
     /// Executes the given call and returns a number of possible traces for it.
+    ///
+    /// If a transaction index is given, the call is executed right before the transaction at that
+    /// index of the block instead of at the end of the block.
     pub async fn trace_call(
         &self,
         trace_request: TraceCallRequest,
+        tx_index: Option<usize>,
     ) -> Result<TraceResults, Eth::Error> {
         let at = trace_request.block_id.unwrap_or_default();
         let config = TracingInspectorConfig::from_parity_config(&trace_request.trace_types);
@@ -90,18 +94,24 @@ where
         let mut inspector = TracingInspector::new(config);
         let this = self.clone();
         self.eth_api()
-            .spawn_with_call_at(trace_request.call, at, overrides, move |db, env| {
-                // wrapper is hack to get around 'higher-ranked lifetime error', see
-                // <https://github.com/rust-lang/rust/issues/100013>
-                let db = db.0;
-
-                let (res, _) = this.eth_api().inspect(&mut *db, env, &mut inspector)?;
-                let trace_res = inspector
-                    .into_parity_builder()
-                    .into_trace_results_with_state(&res, &trace_request.trace_types, &db)
-                    .map_err(Eth::Error::from_eth_err)?;
-                Ok(trace_res)
-            })
+            .spawn_with_call_at_transaction(
+                trace_request.call,
+                at,
+                tx_index,
+                overrides,
+                move |db, env| {
+                    // wrapper is hack to get around 'higher-ranked lifetime error', see
+                    // <https://github.com/rust-lang/rust/issues/100013>
+                    let db = db.0;
+
+                    let (res, _) = this.eth_api().inspect(&mut *db, env, &mut inspector)?;
+                    let trace_res = inspector
+                        .into_parity_builder()
+                        .into_trace_results_with_state(&res, &trace_request.trace_types, &db)
+                        .map_err(Eth::Error::from_eth_err)?;
+                    Ok(trace_res)
+                },
+            )
             .await
     }
 
@@ -574,11 +584,12 @@ where
         block_id: Option<BlockId>,
         state_overrides: Option<StateOverride>,
         block_overrides: Option<Box<BlockOverrides>>,
+        tx_index: Option<Index>,
     ) -> RpcResult<TraceResults> {
         let _permit = self.acquire_trace_permit().await;
         let request =
             TraceCallRequest { call, trace_types, block_id, state_overrides, block_overrides };
-        Ok(Self::trace_call(self, request).await.map_err(Into::into)?)
+        Ok(Self::trace_call(self, request, tx_index.map(Into::into)).await.map_err(Into::into)?)
     }
 
     /// Handler for `trace_callMany`
@@ -90,6 +90,7 @@ impl<Provider> PipelineBuilder<Provider> {
             progress: Default::default(),
             metrics_tx,
             fail_on_unwind,
+            pause_signal: None,
         }
     }
 }
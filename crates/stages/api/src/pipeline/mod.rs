@@ -5,6 +5,7 @@ use crate::{PipelineTarget, StageCheckpoint, StageId};
 use alloy_primitives::{BlockNumber, B256};
 pub use event::*;
 use futures_util::Future;
+use reth_db_api::transaction::DbTxMut;
 use reth_primitives_traits::constants::BEACON_CONSENSUS_REORG_UNWIND_DEPTH;
 use reth_provider::{
     providers::ProviderNodeTypes, writer::UnifiedStorageWriter, ChainStateBlockReader,
@@ -81,6 +82,8 @@ pub struct Pipeline<N: ProviderNodeTypes> {
     /// Whether an unwind should fail the syncing process. Should only be set when downloading
     /// blocks from trusted sources and expecting them to be valid.
     fail_on_unwind: bool,
+    /// A receiver of the signal to pause writing to disk, e.g. when the disk is almost full.
+    pause_signal: Option<watch::Receiver<bool>>,
 }
 
 impl<N: ProviderNodeTypes> Pipeline<N> {
@@ -108,6 +111,14 @@ impl<N: ProviderNodeTypes> Pipeline<N> {
     pub fn events(&self) -> EventStream<PipelineEvent> {
         self.event_sender.new_listener()
     }
+
+    /// Sets the signal to pause writing to disk.
+    ///
+    /// While the signal is `true`, the pipeline waits before executing the next batch of a stage,
+    /// i.e. after the previous batch was committed. Unwinds are not paused.
+    pub fn set_pause_signal(&mut self, pause_signal: watch::Receiver<bool>) {
+        self.pause_signal = Some(pause_signal);
+    }
 }
 
 impl<N: ProviderNodeTypes> Pipeline<N> {
@@ -315,6 +326,7 @@ impl<N: ProviderNodeTypes> Pipeline<N> {
                 "Starting unwind"
             );
             while checkpoint.block_number > to {
+                provider_rw.tx_mut().set_stage_label(stage_id.as_str());
                 let input = UnwindInput { checkpoint, unwind_to: to, bad_block };
                 self.event_sender.notify(PipelineEvent::Unwind { stage_id, input });
 
@@ -389,6 +401,8 @@ impl<N: ProviderNodeTypes> Pipeline<N> {
         let target = self.max_block.or(previous_stage);
 
         loop {
+            wait_until_resumed(&mut self.pause_signal, stage_id).await;
+
             let prev_checkpoint = self.provider_factory.get_stage_checkpoint(stage_id)?;
 
             let stage_reached_max_block = prev_checkpoint
@@ -431,7 +445,8 @@ impl<N: ProviderNodeTypes> Pipeline<N> {
                 };
             }
 
-            let provider_rw = self.provider_factory.database_provider_rw()?;
+            let mut provider_rw = self.provider_factory.database_provider_rw()?;
+            provider_rw.tx_mut().set_stage_label(stage_id.as_str());
 
             self.event_sender.notify(PipelineEvent::Run {
                 pipeline_stages_progress: PipelineStagesProgress {
@@ -581,6 +596,17 @@ fn on_stage_error<N: ProviderNodeTypes>(
     }
 }
 
+/// Waits until the pause signal, if any, is cleared.
+async fn wait_until_resumed(pause_signal: &mut Option<watch::Receiver<bool>>, stage_id: StageId) {
+    let Some(pause_signal) = pause_signal else { return };
+    if *pause_signal.borrow_and_update() {
+        info!(target: "sync::pipeline", stage = %stage_id, "Pipeline paused");
+        // a closed channel means that nobody can pause the pipeline anymore
+        let _ = pause_signal.wait_for(|paused| !*paused).await;
+        info!(target: "sync::pipeline", stage = %stage_id, "Pipeline resumed");
+    }
+}
+
 impl<N: ProviderNodeTypes> std::fmt::Debug for Pipeline<N> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("Pipeline")
@@ -594,14 +620,30 @@ impl<N: ProviderNodeTypes> std::fmt::Debug for Pipeline<N> {
 
 #[cfg(test)]
 mod tests {
-    use std::sync::atomic::Ordering;
+    use std::sync::{atomic::Ordering, Arc};
 
     use super::*;
     use crate::{test_utils::TestStage, UnwindOutput};
     use assert_matches::assert_matches;
+    use metrics_util::debugging::{DebugValue, DebuggingRecorder};
+    use reth_chainspec::MAINNET;
     use reth_consensus::ConsensusError;
+    use reth_db::{
+        init_db,
+        mdbx::DatabaseArguments,
+        tables,
+        test_utils::{create_test_static_files_dir, tempdir_path, TempDatabase},
+    };
+    use reth_db_api::{
+        cursor::{DbCursorRO, DbCursorRW},
+        transaction::DbTx,
+    };
     use reth_errors::ProviderError;
-    use reth_provider::test_utils::{create_test_provider_factory, MockNodeTypesWithDB};
+    use reth_provider::{
+        providers::StaticFileProvider,
+        test_utils::{create_test_provider_factory, MockNodeTypesWithDB},
+        DBProvider,
+    };
     use reth_prune::PruneModes;
     use reth_testing_utils::generators::{self, random_block_with_parent};
     use tokio_stream::StreamExt;
@@ -711,6 +753,38 @@ mod tests {
         assert_eq!(post_unwind_commit_counter_b.load(Ordering::Relaxed), 0);
     }
 
+    /// Runs a pipeline that is paused until the pause signal is cleared.
+    #[tokio::test]
+    async fn run_paused_pipeline() {
+        let provider_factory = create_test_provider_factory();
+
+        let stage_a = TestStage::new(StageId::Other("A"))
+            .add_exec(Ok(ExecOutput { checkpoint: StageCheckpoint::new(10), done: true }));
+        let (stage_a, post_execute_commit_counter_a) = stage_a.with_post_execute_commit_counter();
+
+        let mut pipeline =
+            Pipeline::<MockNodeTypesWithDB>::builder().add_stage(stage_a).with_max_block(10).build(
+                provider_factory.clone(),
+                StaticFileProducer::new(provider_factory.clone(), PruneModes::default()),
+            );
+        let (pause_tx, pause_rx) = watch::channel(true);
+        pipeline.set_pause_signal(pause_rx);
+        let mut events = pipeline.events();
+
+        let handle = tokio::spawn(async move { pipeline.run_loop().await.unwrap() });
+
+        // Nothing is executed while paused
+        assert!(tokio::time::timeout(std::time::Duration::from_millis(100), events.next())
+            .await
+            .is_err());
+        assert_eq!(post_execute_commit_counter_a.load(Ordering::Relaxed), 0);
+
+        pause_tx.send(false).unwrap();
+        assert_matches!(events.next().await, Some(PipelineEvent::Prepare { .. }));
+        handle.await.unwrap();
+        assert_eq!(post_execute_commit_counter_a.load(Ordering::Relaxed), 1);
+    }
+
     /// Unwinds a simple pipeline.
     #[tokio::test]
     async fn unwind_pipeline() {
@@ -1118,4 +1192,92 @@ mod tests {
             )))
         );
     }
+
+    /// A stage that writes the canonical hashes and numbers of up to 4 blocks per batch.
+    #[derive(Debug)]
+    struct CanonicalHashesStage;
+
+    impl<Provider: DBProvider<Tx: DbTxMut>> Stage<Provider> for CanonicalHashesStage {
+        fn id(&self) -> StageId {
+            StageId::Other("CanonicalHashes")
+        }
+
+        fn execute(
+            &mut self,
+            provider: &Provider,
+            input: ExecInput,
+        ) -> Result<ExecOutput, StageError> {
+            let (range, done) = input.next_block_range_with_threshold(4);
+            let mut cursor = provider.tx_ref().cursor_write::<tables::CanonicalHeaders>()?;
+            for number in range.clone() {
+                let hash = B256::with_last_byte(number as u8);
+                cursor.append(number, hash)?;
+                provider.tx_ref().put::<tables::HeaderNumbers>(hash, number)?;
+            }
+            Ok(ExecOutput { checkpoint: StageCheckpoint::new(*range.end()), done })
+        }
+
+        fn unwind(
+            &mut self,
+            provider: &Provider,
+            input: UnwindInput,
+        ) -> Result<UnwindOutput, StageError> {
+            let mut cursor = provider.tx_ref().cursor_write::<tables::CanonicalHeaders>()?;
+            let mut walker = cursor.walk(Some(input.unwind_to + 1))?;
+            while let Some((_, hash)) = walker.next().transpose()? {
+                walker.delete_current()?;
+                provider.tx_ref().delete::<tables::HeaderNumbers>(hash, None)?;
+            }
+            Ok(UnwindOutput { checkpoint: StageCheckpoint::new(input.unwind_to) })
+        }
+    }
+
+    /// Checks that the writes of a stage are counted per table in the database metrics.
+    #[tokio::test]
+    async fn stage_write_metrics() {
+        let recorder = DebuggingRecorder::new();
+        let snapshotter = recorder.snapshotter();
+        recorder.install().unwrap();
+
+        let path = tempdir_path();
+        let db = init_db(&path, DatabaseArguments::default()).unwrap().with_metrics();
+        let (static_dir, _) = create_test_static_files_dir();
+        let provider_factory = ProviderFactory::<MockNodeTypesWithDB>::new(
+            Arc::new(TempDatabase::new(db, path)),
+            MAINNET.clone(),
+            StaticFileProvider::read_write(static_dir.into_path()).unwrap(),
+        );
+
+        let mut pipeline = Pipeline::<MockNodeTypesWithDB>::builder()
+            .add_stage(CanonicalHashesStage)
+            .with_max_block(10)
+            .build(
+                provider_factory.clone(),
+                StaticFileProducer::new(provider_factory.clone(), PruneModes::default()),
+            );
+        pipeline.run().await.unwrap();
+        pipeline.unwind(6, None).unwrap();
+
+        let snapshot = snapshotter.snapshot().into_vec();
+        let counter = |name: &str, table: &str| {
+            snapshot
+                .iter()
+                .find_map(|(key, _, _, value)| {
+                    let key = key.key();
+                    let labels = key.labels().map(|label| (label.key(), label.value()));
+                    (key.name() == name &&
+                        labels.eq([("table", table), ("stage", "CanonicalHashes")]))
+                    .then(|| assert_matches!(value, DebugValue::Counter(value) => *value))
+                })
+                .unwrap_or_default()
+        };
+
+        // 10 blocks are written in 3 batches, and the last 4 are unwound
+        assert_eq!(counter("database.writes.puts_total", "CanonicalHeaders"), 10);
+        assert_eq!(counter("database.writes.deletes_total", "CanonicalHeaders"), 4);
+        assert_eq!(counter("database.writes.bytes_total", "CanonicalHeaders"), 10 * (8 + 32));
+        assert_eq!(counter("database.writes.puts_total", "HeaderNumbers"), 10);
+        assert_eq!(counter("database.writes.deletes_total", "HeaderNumbers"), 4);
+        assert_eq!(counter("database.writes.puts_total", "StageCheckpoints"), 4);
+    }
 }
@@ -37,8 +37,9 @@
 use crate::{
     stages::{
         AccountHashingStage, BodyStage, ExecutionStage, FinishStage, HeaderStage,
-        IndexAccountHistoryStage, IndexStorageHistoryStage, MerkleStage, PruneSenderRecoveryStage,
-        PruneStage, SenderRecoveryStage, StorageHashingStage, TransactionLookupStage,
+        IndexAccountHistoryStage, IndexLogAddressesStage, IndexStorageHistoryStage, MerkleStage,
+        PruneSenderRecoveryStage, PruneStage, SenderRecoveryStage, StorageHashingStage,
+        TransactionLookupStage,
     },
     StageSet, StageSetBuilder,
 };
@@ -309,12 +310,13 @@ impl<EF> OfflineStages<EF> {
 
 impl<E, Provider> StageSet<Provider> for OfflineStages<E>
 where
+    Provider: 'static,
     E: BlockExecutorProvider,
     ExecutionStages<E>: StageSet<Provider>,
-    PruneSenderRecoveryStage: Stage<Provider>,
+    PruneSenderRecoveryStage<Provider>: Stage<Provider>,
     HashingStages: StageSet<Provider>,
     HistoryIndexingStages: StageSet<Provider>,
-    PruneStage: Stage<Provider>,
+    PruneStage<Provider>: Stage<Provider>,
 {
     fn builder(self) -> StageSetBuilder<Provider> {
         ExecutionStages::new(
@@ -412,6 +414,8 @@ where
 }
 
 /// A set containing all stages that do additional indexing for historical state.
+///
+/// The [`IndexLogAddressesStage`] is only included if it's enabled in the [`StageConfig`].
 #[derive(Debug, Default)]
 #[non_exhaustive]
 pub struct HistoryIndexingStages {
@@ -426,6 +430,7 @@ where
     TransactionLookupStage: Stage<Provider>,
     IndexStorageHistoryStage: Stage<Provider>,
     IndexAccountHistoryStage: Stage<Provider>,
+    IndexLogAddressesStage: Stage<Provider>,
 {
     fn builder(self) -> StageSetBuilder<Provider> {
         StageSetBuilder::default()
@@ -444,5 +449,12 @@ where
                 self.stages_config.etl.clone(),
                 self.prune_modes.storage_history,
             ))
+            .add_stage_opt(self.stages_config.index_log_addresses.enabled.then(|| {
+                IndexLogAddressesStage::new(
+                    self.stages_config.index_log_addresses,
+                    self.stages_config.etl.clone(),
+                    self.prune_modes.receipts,
+                )
+            }))
     }
 }
@@ -4,16 +4,19 @@ use alloy_eips::{eip1898::BlockWithParent, NumHash};
 use alloy_primitives::BlockNumber;
 use num_traits::Zero;
 use reth_config::config::ExecutionConfig;
+use reth_consensus::{ConsensusError, ReceiptSummary};
 use reth_db::{static_file::HeaderMask, tables};
 use reth_db_api::{cursor::DbCursorRO, transaction::DbTx};
 use reth_evm::{
-    execute::{BatchExecutor, BlockExecutorProvider},
+    execute::{BatchExecutor, BlockExecutionError, BlockExecutorProvider},
     metrics::ExecutorMetrics,
 };
 use reth_execution_types::Chain;
 use reth_exex::{ExExManagerHandle, ExExNotification, ExExNotificationSource};
-use reth_primitives::StaticFileSegment;
-use reth_primitives_traits::{format_gas_throughput, Block, BlockBody, NodePrimitives};
+use reth_primitives::{BlockWithSenders, StaticFileSegment};
+use reth_primitives_traits::{
+    format_gas_throughput, Block, BlockBody, NodePrimitives, SignedTransaction,
+};
 use reth_provider::{
     providers::{StaticFileProvider, StaticFileWriter},
     BlockHashReader, BlockReader, DBProvider, HeaderProvider, LatestStateProviderRef,
@@ -95,6 +98,9 @@ where
     exex_manager_handle: ExExManagerHandle<E::Primitives>,
     /// Executor metrics.
     metrics: ExecutorMetrics,
+    /// Whether to log and compare receipts against the stored ones on receipts root or logs
+    /// bloom mismatches.
+    verbose_receipts_diagnostics: bool,
 }
 
 impl<E> ExecutionStage<E>
@@ -118,6 +124,7 @@ where
             post_unwind_commit_input: None,
             exex_manager_handle,
             metrics: ExecutorMetrics::default(),
+            verbose_receipts_diagnostics: false,
         }
     }
 
@@ -148,6 +155,16 @@ where
             prune_modes,
             ExExManagerHandle::empty(),
         )
+        .with_verbose_receipts_diagnostics(config.verbose_receipts_diagnostics)
+    }
+
+    /// Enables verbose diagnostics of receipts root and logs bloom mismatches.
+    ///
+    /// On mismatch, every receipt of the block is logged and, if the block was executed before,
+    /// compared against its stored receipts to find the first one that differs.
+    pub const fn with_verbose_receipts_diagnostics(mut self, verbose: bool) -> Self {
+        self.verbose_receipts_diagnostics = verbose;
+        self
     }
 
     /// Adjusts the prune modes related to changesets.
@@ -332,6 +349,7 @@ where
         let mut cumulative_gas = 0;
         let batch_start = Instant::now();
 
+        let verbose_receipts_diagnostics = self.verbose_receipts_diagnostics;
         let mut blocks = Vec::new();
         for block_number in start_block..=max_block {
             // Fetch the block
@@ -353,7 +371,11 @@ where
             let execute_start = Instant::now();
 
             self.metrics.metered_one(&block, |input| {
-                executor.execute_and_verify_one(input).map_err(|error| {
+                executor.execute_and_verify_one(input).map_err(|mut error| {
+                    if verbose_receipts_diagnostics {
+                        diagnose_receipts_mismatch(provider, &block, &mut error);
+                    }
+
                     let header = block.header();
                     StageError::Block {
                         block: Box::new(BlockWithParent::new(
@@ -550,6 +572,52 @@ where
     }
 }
 
+/// Logs the receipts of a block that failed receipts root or logs bloom validation and, if the
+/// block was executed before, compares them against its stored receipts.
+fn diagnose_receipts_mismatch<Provider: BlockReader>(
+    provider: &Provider,
+    block: &BlockWithSenders<Provider::Block>,
+    error: &mut BlockExecutionError,
+) {
+    let BlockExecutionError::Consensus(
+        ConsensusError::BodyReceiptRootDiff { diagnostics, .. } |
+        ConsensusError::BodyBloomLogDiff { diagnostics, .. },
+    ) = error
+    else {
+        return
+    };
+
+    let number = block.header().number();
+    for (index, receipt) in diagnostics.receipts.iter().enumerate() {
+        warn!(
+            target: "sync::stages::execution",
+            number,
+            index,
+            tx_hash = ?receipt.tx_hash,
+            success = receipt.success,
+            gas_used = receipt.gas_used,
+            logs_count = receipt.logs_count,
+            "Receipt of block with mismatching receipts"
+        );
+    }
+
+    match provider.receipts_by_block(number.into()) {
+        Ok(Some(stored)) => {
+            let tx_hashes = block.body().transactions().iter().map(|tx| *tx.tx_hash());
+            let stored = ReceiptSummary::from_receipts(&stored, tx_hashes);
+            if let Some(mismatch) = diagnostics.compare_with(&stored) {
+                warn!(target: "sync::stages::execution", number, %mismatch, "Receipt differs from stored receipt");
+            }
+        }
+        Ok(None) => {
+            debug!(target: "sync::stages::execution", number, "No stored receipts to compare against")
+        }
+        Err(err) => {
+            debug!(target: "sync::stages::execution", number, %err, "Failed to read stored receipts")
+        }
+    }
+}
+
 fn execution_checkpoint<N: NodePrimitives>(
     provider: &StaticFileProvider<N>,
     start_block: BlockNumber,
@@ -81,8 +81,10 @@ where
             tip,
             consensus,
             sync_gap: None,
-            hash_collector: Collector::new(etl_config.file_size / 2, etl_config.dir.clone()),
-            header_collector: Collector::new(etl_config.file_size / 2, etl_config.dir),
+            hash_collector: Collector::new(etl_config.file_size / 2, etl_config.dir.clone())
+                .with_metrics_label("HeaderNumbers"),
+            header_collector: Collector::new(etl_config.file_size / 2, etl_config.dir)
+                .with_metrics_label("Headers"),
             is_etl_ready: false,
         }
     }
@@ -173,7 +175,14 @@ where
         }
 
         // Since ETL sorts all entries by hashes, we are either appending (first sync) or inserting
-        // in order (further syncs).
+        // in order (further syncs), until the hashes sort after the last one in the table, from
+        // where on they're appended as well.
+        let mut append = first_sync;
+        let last_hash = if append {
+            None
+        } else {
+            cursor_header_numbers.last()?.map(|(hash, _)| hash.into_key())
+        };
         for (index, hash_to_number) in self.hash_collector.iter()?.enumerate() {
             let (hash, number) = hash_to_number?;
 
@@ -181,7 +190,10 @@ where
                 info!(target: "sync::stages::headers", progress = %format!("{:.2}%", (index as f64 / total_headers as f64) * 100.0), "Writing headers hash index");
             }
 
-            if first_sync {
+            if !append && last_hash.as_ref().is_none_or(|last_hash| hash > *last_hash) {
+                append = true;
+            }
+            if append {
                 cursor_header_numbers.append(
                     RawKey::<BlockHash>::from_vec(hash),
                     RawValue::<BlockNumber>::from_vec(number),
@@ -289,6 +289,40 @@ mod tests {
         assert_eq!(table, BTreeMap::from([(shard(u64::MAX), vec![1, 2, 3])]));
     }
 
+    #[tokio::test]
+    async fn insert_index_of_new_address() {
+        // init
+        let db = TestStageDB::default();
+        let new_address = address!("0000000000000000000000000000000000000002");
+
+        // setup
+        partial_setup(&db);
+        db.commit(|tx| {
+            for block in [4, 5] {
+                tx.put::<tables::AccountChangeSets>(
+                    block,
+                    AccountBeforeTx { address: new_address, info: None },
+                )?;
+            }
+            tx.put::<tables::AccountsHistory>(shard(u64::MAX), list(&[1, 2, 3])).unwrap();
+            Ok(())
+        })
+        .unwrap();
+
+        // run
+        run(&db, 5, Some(3));
+
+        // verify
+        let table = cast(db.table::<tables::AccountsHistory>().unwrap());
+        assert_eq!(
+            table,
+            BTreeMap::from([
+                (shard(u64::MAX), vec![1, 2, 3, 4, 5]),
+                (ShardedKey::new(new_address, u64::MAX), vec![4, 5]),
+            ])
+        );
+    }
+
     #[tokio::test]
     async fn insert_index_to_full_shard() {
         // init
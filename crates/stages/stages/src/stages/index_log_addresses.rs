@@ -0,0 +1,327 @@
+use super::{collect_log_address_indices, load_history_indices};
+use alloy_primitives::Address;
+use reth_config::config::{EtlConfig, IndexLogAddressesConfig};
+use reth_db::tables;
+use reth_db_api::{models::ShardedKey, table::Decode, transaction::DbTxMut};
+use reth_provider::{
+    BlockReader, DBProvider, HistoryWriter, PruneCheckpointReader, PruneCheckpointWriter,
+};
+use reth_prune_types::{PruneCheckpoint, PruneMode, PrunePurpose, PruneSegment};
+use reth_stages_api::{
+    ExecInput, ExecOutput, Stage, StageCheckpoint, StageError, StageId, UnwindInput, UnwindOutput,
+};
+use std::fmt::Debug;
+use tracing::info;
+
+/// Stage is indexing the blocks in which each address emitted logs, using the receipts generated
+/// in [`ExecutionStage`][crate::stages::ExecutionStage]. For more information on index sharding
+/// take a look at [`tables::LogAddressIndex`].
+///
+/// The index is optional and only used to speed up log queries that filter by address, so this
+/// stage is only part of the pipeline if it's enabled in the
+/// [`IndexLogAddressesConfig`].
+#[derive(Debug)]
+pub struct IndexLogAddressesStage {
+    /// Number of blocks after which the control
+    /// flow will be returned to the pipeline for commit.
+    pub commit_threshold: u64,
+    /// Pruning configuration of the receipts the index is built from.
+    pub prune_mode: Option<PruneMode>,
+    /// ETL configuration
+    pub etl_config: EtlConfig,
+}
+
+impl IndexLogAddressesStage {
+    /// Create new instance of [`IndexLogAddressesStage`].
+    pub const fn new(
+        config: IndexLogAddressesConfig,
+        etl_config: EtlConfig,
+        prune_mode: Option<PruneMode>,
+    ) -> Self {
+        Self { commit_threshold: config.commit_threshold, etl_config, prune_mode }
+    }
+}
+
+impl Default for IndexLogAddressesStage {
+    fn default() -> Self {
+        Self { commit_threshold: 100_000, prune_mode: None, etl_config: EtlConfig::default() }
+    }
+}
+
+impl<Provider> Stage<Provider> for IndexLogAddressesStage
+where
+    Provider: DBProvider<Tx: DbTxMut>
+        + HistoryWriter
+        + BlockReader
+        + PruneCheckpointReader
+        + PruneCheckpointWriter,
+{
+    /// Return the id of the stage
+    fn id(&self) -> StageId {
+        StageId::IndexLogAddresses
+    }
+
+    /// Execute the stage.
+    fn execute(
+        &mut self,
+        provider: &Provider,
+        mut input: ExecInput,
+    ) -> Result<ExecOutput, StageError> {
+        // Receipts below the prune target are gone, so there's nothing to index there.
+        if let Some((target_prunable_block, prune_mode)) = self
+            .prune_mode
+            .map(|mode| {
+                mode.prune_target_block(input.target(), PruneSegment::Receipts, PrunePurpose::User)
+            })
+            .transpose()?
+            .flatten()
+        {
+            if target_prunable_block > input.checkpoint().block_number {
+                input.checkpoint = Some(StageCheckpoint::new(target_prunable_block));
+
+                // Save prune checkpoint only if we don't have one already.
+                // Otherwise, pruner may skip the unpruned range of blocks.
+                if provider.get_prune_checkpoint(PruneSegment::LogAddressIndex)?.is_none() {
+                    provider.save_prune_checkpoint(
+                        PruneSegment::LogAddressIndex,
+                        PruneCheckpoint {
+                            block_number: Some(target_prunable_block),
+                            tx_number: None,
+                            prune_mode,
+                        },
+                    )?;
+                }
+            }
+        }
+
+        if input.target_reached() {
+            return Ok(ExecOutput::done(input.checkpoint()))
+        }
+
+        let mut range = input.next_block_range();
+        let first_sync = input.checkpoint().block_number == 0;
+
+        // On first sync we might have an index left from a previous run. We clear the table since
+        // it's faster to rebuild from scratch.
+        if first_sync {
+            provider.tx_ref().clear::<tables::LogAddressIndex>()?;
+            range = 0..=*input.next_block_range().end();
+        }
+
+        info!(target: "sync::stages::index_log_addresses::exec", ?first_sync, "Collecting indices");
+        let collector = collect_log_address_indices(provider, range.clone(), &self.etl_config)?;
+
+        info!(target: "sync::stages::index_log_addresses::exec", "Loading indices into database");
+        load_history_indices::<_, tables::LogAddressIndex, _>(
+            provider,
+            collector,
+            first_sync,
+            ShardedKey::new,
+            ShardedKey::<Address>::decode_owned,
+            |key| key.key,
+        )?;
+
+        Ok(ExecOutput { checkpoint: StageCheckpoint::new(*range.end()), done: true })
+    }
+
+    /// Unwind the stage.
+    fn unwind(
+        &mut self,
+        provider: &Provider,
+        input: UnwindInput,
+    ) -> Result<UnwindOutput, StageError> {
+        let (range, unwind_progress, _) =
+            input.unwind_block_range_with_threshold(self.commit_threshold);
+
+        provider.unwind_log_address_index_range(range)?;
+
+        Ok(UnwindOutput { checkpoint: StageCheckpoint::new(unwind_progress) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{StorageKind, TestStageDB};
+    use alloy_primitives::{BlockNumber, B256};
+    use reth_provider::{
+        DatabaseProviderFactory, ReceiptProvider, StageCheckpointWriter, StateWriter,
+        StorageLocation,
+    };
+    use reth_testing_utils::generators::{
+        self, random_block_range, random_log, random_receipt, BlockRangeParams,
+    };
+    use std::{collections::BTreeMap, ops::RangeInclusive};
+
+    const MAX_BLOCK: BlockNumber = 300;
+
+    /// Inserts a chain in which a few addresses emit logs in random blocks and returns the
+    /// addresses along with the blocks they emitted logs in.
+    fn setup(db: &TestStageDB) -> BTreeMap<Address, Vec<BlockNumber>> {
+        let mut rng = generators::rng();
+        let addresses = (1..=4).map(Address::with_last_byte).collect::<Vec<_>>();
+
+        let blocks = random_block_range(
+            &mut rng,
+            0..=MAX_BLOCK,
+            BlockRangeParams { parent: Some(B256::ZERO), tx_count: 0..3, ..Default::default() },
+        );
+        db.insert_blocks(blocks.iter(), StorageKind::Database(None)).unwrap();
+
+        let mut expected =
+            addresses.iter().map(|address| (*address, Vec::new())).collect::<BTreeMap<_, Vec<_>>>();
+        let mut receipts = Vec::new();
+        for block in &blocks {
+            for (tx_index, transaction) in block.body.transactions.iter().enumerate() {
+                let mut receipt = random_receipt(&mut rng, transaction, Some(0));
+                let index = block.number as usize + tx_index;
+                // every fifth transaction doesn't emit logs
+                if index % 5 != 4 {
+                    let address = addresses[index % addresses.len()];
+                    receipt.logs.push(random_log(&mut rng, Some(address), Some(1)));
+                    let blocks = expected.get_mut(&address).unwrap();
+                    if blocks.last() != Some(&block.number) {
+                        blocks.push(block.number);
+                    }
+                }
+                receipts.push((receipts.len() as u64, receipt));
+            }
+        }
+        db.insert_receipts(receipts).unwrap();
+
+        expected
+    }
+
+    fn run(db: &TestStageDB, run_to: u64, input_checkpoint: Option<BlockNumber>) {
+        let input = ExecInput {
+            target: Some(run_to),
+            checkpoint: input_checkpoint.map(StageCheckpoint::new),
+        };
+        let mut stage = IndexLogAddressesStage::default();
+        let provider = db.factory.database_provider_rw().unwrap();
+        let out = stage.execute(&provider, input).unwrap();
+        assert_eq!(out, ExecOutput { checkpoint: StageCheckpoint::new(run_to), done: true });
+        provider.save_stage_checkpoint(StageId::IndexLogAddresses, out.checkpoint).unwrap();
+        provider.commit().unwrap();
+    }
+
+    fn unwind(db: &TestStageDB, unwind_from: u64, unwind_to: u64) {
+        let input = UnwindInput {
+            checkpoint: StageCheckpoint::new(unwind_from),
+            unwind_to,
+            ..Default::default()
+        };
+        let mut stage = IndexLogAddressesStage::default();
+        let provider = db.factory.database_provider_rw().unwrap();
+        let out = stage.unwind(&provider, input).unwrap();
+        assert_eq!(out, UnwindOutput { checkpoint: StageCheckpoint::new(unwind_to) });
+        provider.save_stage_checkpoint(StageId::IndexLogAddresses, out.checkpoint).unwrap();
+        provider.commit().unwrap();
+    }
+
+    /// Asserts that the index returns the same blocks as scanning all receipts in the range.
+    fn assert_equivalent(
+        db: &TestStageDB,
+        expected: &BTreeMap<Address, Vec<BlockNumber>>,
+        indexed_to: BlockNumber,
+        range: RangeInclusive<BlockNumber>,
+    ) {
+        let provider = db.factory.provider().unwrap();
+        for (address, blocks) in expected {
+            let indexed = provider.log_address_blocks(&[*address], range.clone()).unwrap().unwrap();
+            assert_eq!(indexed.indexed_to, indexed_to.min(*range.end()));
+            let scanned = blocks
+                .iter()
+                .copied()
+                .filter(|block| range.contains(block) && *block <= indexed_to)
+                .collect::<Vec<_>>();
+            assert_eq!(indexed.blocks, scanned, "address {address}, range {range:?}");
+        }
+
+        let addresses = expected.keys().copied().collect::<Vec<_>>();
+        let indexed = provider.log_address_blocks(&addresses, range.clone()).unwrap().unwrap();
+        let mut scanned = expected
+            .values()
+            .flatten()
+            .copied()
+            .filter(|block| range.contains(block) && *block <= indexed_to)
+            .collect::<Vec<_>>();
+        scanned.sort_unstable();
+        scanned.dedup();
+        assert_eq!(indexed.blocks, scanned);
+    }
+
+    #[test]
+    fn index_is_equivalent_to_receipts() {
+        let db = TestStageDB::default();
+        let expected = setup(&db);
+
+        // not built yet
+        let provider = db.factory.provider().unwrap();
+        let addresses = expected.keys().copied().collect::<Vec<_>>();
+        assert_eq!(provider.log_address_blocks(&addresses, 0..=MAX_BLOCK).unwrap(), None);
+        drop(provider);
+
+        // build in two runs
+        run(&db, 100, None);
+        run(&db, MAX_BLOCK, Some(100));
+        assert_equivalent(&db, &expected, MAX_BLOCK, 0..=MAX_BLOCK);
+        assert_equivalent(&db, &expected, MAX_BLOCK, 42..=173);
+
+        // unwind
+        unwind(&db, MAX_BLOCK, 150);
+        assert_equivalent(&db, &expected, 150, 0..=MAX_BLOCK);
+        assert_equivalent(&db, &expected, 150, 100..=150);
+
+        // rebuild the unwound blocks
+        run(&db, MAX_BLOCK, Some(150));
+        assert_equivalent(&db, &expected, MAX_BLOCK, 0..=MAX_BLOCK);
+    }
+
+    #[test]
+    fn unwind_to_genesis() {
+        let db = TestStageDB::default();
+        let expected = setup(&db);
+
+        run(&db, MAX_BLOCK, None);
+        unwind(&db, MAX_BLOCK, 0);
+
+        let table = db.table::<tables::LogAddressIndex>().unwrap();
+        assert!(table.iter().all(|(_, blocks)| blocks.iter().eq(std::iter::once(0))));
+        assert_equivalent(&db, &expected, 0, 0..=MAX_BLOCK);
+    }
+
+    #[test]
+    fn engine_maintains_index() {
+        let db = TestStageDB::default();
+        let expected = setup(&db);
+
+        // the index isn't maintained without a checkpoint
+        let provider = db.factory.database_provider_rw().unwrap();
+        provider.update_history_indices(0..=100).unwrap();
+        provider.commit().unwrap();
+        assert!(db.table::<tables::LogAddressIndex>().unwrap().is_empty());
+
+        run(&db, 100, None);
+
+        // a range that doesn't follow the indexed blocks is left to the stage
+        let provider = db.factory.database_provider_rw().unwrap();
+        provider.update_history_indices(151..=MAX_BLOCK).unwrap();
+        provider.commit().unwrap();
+        assert_equivalent(&db, &expected, 100, 0..=MAX_BLOCK);
+
+        // persisted blocks extend the index
+        let provider = db.factory.database_provider_rw().unwrap();
+        provider.update_history_indices(101..=150).unwrap();
+        provider.update_history_indices(151..=MAX_BLOCK).unwrap();
+        provider.commit().unwrap();
+        assert_equivalent(&db, &expected, MAX_BLOCK, 0..=MAX_BLOCK);
+
+        // removed blocks are unwound from the index
+        let provider = db.factory.database_provider_rw().unwrap();
+        provider.remove_state_above(150, StorageLocation::Database).unwrap();
+        provider.commit().unwrap();
+        assert_equivalent(&db, &expected, 150, 0..=MAX_BLOCK);
+        assert_equivalent(&db, &expected, 150, 120..=180);
+    }
+}
@@ -563,16 +563,12 @@ mod tests {
                 let mut storage_cursor = tx.cursor_dup_read::<tables::HashedStorages>()?;
                 for entry in accounts_cursor.walk_range(..)? {
                     let (key, account) = entry?;
-                    let mut storage_entries = Vec::new();
-                    let mut entry = storage_cursor.seek_exact(key)?;
-                    while let Some((_, storage)) = entry {
-                        storage_entries.push(storage);
-                        entry = storage_cursor.next_dup()?;
-                    }
-                    let storage = storage_entries
+                    let storage = storage_cursor
+                        .walk_dup_prefix(key)?
+                        .collect::<Result<Vec<_>, _>>()?
                         .into_iter()
-                        .filter(|v| !v.value.is_zero())
-                        .map(|v| (v.key, v.value))
+                        .filter(|(_, v)| !v.value.is_zero())
+                        .map(|(_, v)| (v.key, v.value))
                         .collect::<Vec<_>>();
                     accounts.insert(key, (account, storage));
                 }
@@ -12,6 +12,8 @@ mod hashing_storage;
 mod headers;
 /// Index history of account changes
 mod index_account_history;
+/// Index the blocks with logs of each address
+mod index_log_addresses;
 /// Index history of storage changes
 mod index_storage_history;
 /// Stage for computing state root.
@@ -29,6 +31,7 @@ pub use hashing_account::*;
 pub use hashing_storage::*;
 pub use headers::*;
 pub use index_account_history::*;
+pub use index_log_addresses::*;
 pub use index_storage_history::*;
 pub use merkle::*;
 pub use prune::*;
@@ -44,7 +47,7 @@ mod tests {
     use crate::test_utils::{StorageKind, TestStageDB};
     use alloy_primitives::{address, hex_literal::hex, keccak256, BlockNumber, B256, U256};
     use alloy_rlp::Decodable;
-    use reth_chainspec::ChainSpecBuilder;
+    use reth_chainspec::{ChainSpecBuilder, ChainSpecProvider};
     use reth_db::{
         mdbx::{cursor::Cursor, RW},
         tables, AccountsHistory,
@@ -58,7 +61,7 @@ mod tests {
     use reth_exex::ExExManagerHandle;
     use reth_primitives::{Account, Bytecode, SealedBlock, StaticFileSegment};
     use reth_provider::{
-        providers::{StaticFileProvider, StaticFileWriter},
+        providers::{ConsistencyCheck, StaticFileProvider, StaticFileWriter},
         test_utils::MockNodeTypesWithDB,
         AccountExtReader, BlockBodyIndicesProvider, DatabaseProviderFactory, ProviderFactory,
         ProviderResult, ReceiptProvider, StageCheckpointWriter, StaticFileProviderFactory,
@@ -315,13 +318,22 @@ mod tests {
 
         // We recreate the static file provider, since consistency heals are done on fetching the
         // writer for the first time.
-        let mut static_file_provider = db.factory.static_file_provider();
-        static_file_provider = StaticFileProvider::read_write(static_file_provider.path()).unwrap();
-        assert_eq!(
-            static_file_provider
-                .check_consistency(&db.factory.database_provider_ro().unwrap(), is_full_node,),
-            Ok(expected)
+        let static_file_provider =
+            StaticFileProvider::read_write(db.factory.static_file_provider().path()).unwrap();
+        let factory = ProviderFactory::<MockNodeTypesWithDB>::new(
+            db.factory.db_ref().clone(),
+            db.factory.chain_spec(),
+            static_file_provider,
         );
+        assert_eq!(check_consistency(&factory, is_full_node), Ok(expected));
+    }
+
+    /// Runs the storage consistency check and returns the target to unwind to, if any.
+    fn check_consistency(
+        factory: &ProviderFactory<MockNodeTypesWithDB>,
+        has_receipt_pruning: bool,
+    ) -> ProviderResult<Option<PipelineTarget>> {
+        Ok(ConsistencyCheck::new(has_receipt_pruning).run(factory)?.unwind_target())
     }
 
     /// Saves a checkpoint with `checkpoint_block_number` and compare the check consistency result
@@ -338,12 +350,7 @@ mod tests {
             .unwrap();
         provider_rw.commit().unwrap();
 
-        assert_eq!(
-            db.factory
-                .static_file_provider()
-                .check_consistency(&db.factory.database_provider_ro().unwrap(), false,),
-            Ok(expected)
-        );
+        assert_eq!(check_consistency(&db.factory, false), Ok(expected));
     }
 
     /// Inserts a dummy value at key and compare the check consistency result against the expected
@@ -360,23 +367,14 @@ mod tests {
         cursor.insert(key, Default::default()).unwrap();
         provider_rw.commit().unwrap();
 
-        assert_eq!(
-            db.factory
-                .static_file_provider()
-                .check_consistency(&db.factory.database_provider_ro().unwrap(), false),
-            Ok(expected)
-        );
+        assert_eq!(check_consistency(&db.factory, false), Ok(expected));
     }
 
     #[test]
     fn test_consistency() {
         let db = seed_data(90).unwrap();
-        let db_provider = db.factory.database_provider_ro().unwrap();
 
-        assert_eq!(
-            db.factory.static_file_provider().check_consistency(&db_provider, false),
-            Ok(None)
-        );
+        assert_eq!(check_consistency(&db.factory, false), Ok(None));
     }
 
     #[test]
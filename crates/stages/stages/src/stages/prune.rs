@@ -5,11 +5,13 @@ use reth_provider::{
     StaticFileProviderFactory,
 };
 use reth_prune::{
-    PruneMode, PruneModes, PruneSegment, PrunerBuilder, SegmentOutput, SegmentOutputCheckpoint,
+    segments::PruneSegmentRegistry, PruneMode, PruneModes, PruneSegment, PrunerBuilder,
+    SegmentOutput, SegmentOutputCheckpoint,
 };
 use reth_stages_api::{
     ExecInput, ExecOutput, Stage, StageCheckpoint, StageError, StageId, UnwindInput, UnwindOutput,
 };
+use std::fmt;
 use tracing::info;
 
 /// The prune stage that runs the pruner with the provided prune modes.
@@ -23,20 +25,39 @@ use tracing::info;
 ///
 /// `commit_threshold` is the maximum number of entries to prune before committing
 /// progress to the database.
-#[derive(Debug)]
-pub struct PruneStage {
+///
+/// Custom segments that are configured are pruned if they're registered with
+/// [`PruneStage::with_custom_segments`], otherwise the stage fails.
+pub struct PruneStage<Provider> {
     prune_modes: PruneModes,
     commit_threshold: usize,
+    custom_segments: PruneSegmentRegistry<Provider>,
 }
 
-impl PruneStage {
+impl<Provider> PruneStage<Provider> {
     /// Crate new prune stage with the given prune modes and commit threshold.
     pub const fn new(prune_modes: PruneModes, commit_threshold: usize) -> Self {
-        Self { prune_modes, commit_threshold }
+        Self { prune_modes, commit_threshold, custom_segments: PruneSegmentRegistry::new() }
+    }
+
+    /// Sets the registry of the custom segments that can be configured.
+    pub fn with_custom_segments(mut self, custom_segments: PruneSegmentRegistry<Provider>) -> Self {
+        self.custom_segments = custom_segments;
+        self
+    }
+}
+
+impl<Provider> fmt::Debug for PruneStage<Provider> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PruneStage")
+            .field("prune_modes", &self.prune_modes)
+            .field("commit_threshold", &self.commit_threshold)
+            .field("custom_segments", &self.custom_segments)
+            .finish()
     }
 }
 
-impl<Provider> Stage<Provider> for PruneStage
+impl<Provider> Stage<Provider> for PruneStage<Provider>
 where
     Provider: DBProvider<Tx: DbTxMut>
         + PruneCheckpointReader
@@ -52,7 +73,7 @@ where
         let mut pruner = PrunerBuilder::default()
             .segments(self.prune_modes.clone())
             .delete_limit(self.commit_threshold)
-            .build::<Provider>(provider.static_file_provider());
+            .build_with_custom_segments(provider.static_file_provider(), &self.custom_segments)?;
 
         let result = pruner.run_with_provider(provider, input.target())?;
         if result.progress.is_finished() {
@@ -104,6 +125,10 @@ where
             checkpoint.block_number = Some(input.unwind_to);
             provider.save_prune_checkpoint(segment, checkpoint)?;
         }
+        for (name, mut checkpoint) in provider.get_custom_prune_checkpoints()? {
+            checkpoint.block_number = Some(input.unwind_to);
+            provider.save_custom_prune_checkpoint(&name, checkpoint)?;
+        }
         Ok(UnwindOutput { checkpoint: StageCheckpoint::new(input.unwind_to) })
     }
 }
@@ -113,9 +138,9 @@ where
 ///
 /// Under the hood, this stage has the same functionality as [`PruneStage`].
 #[derive(Debug)]
-pub struct PruneSenderRecoveryStage(PruneStage);
+pub struct PruneSenderRecoveryStage<Provider>(PruneStage<Provider>);
 
-impl PruneSenderRecoveryStage {
+impl<Provider> PruneSenderRecoveryStage<Provider> {
     /// Create new prune sender recovery stage with the given prune mode and commit threshold.
     pub fn new(prune_mode: PruneMode, commit_threshold: usize) -> Self {
         Self(PruneStage::new(
@@ -125,7 +150,7 @@ impl PruneSenderRecoveryStage {
     }
 }
 
-impl<Provider> Stage<Provider> for PruneSenderRecoveryStage
+impl<Provider> Stage<Provider> for PruneSenderRecoveryStage<Provider>
 where
     Provider: DBProvider<Tx: DbTxMut>
         + PruneCheckpointReader
@@ -171,10 +196,12 @@ mod tests {
         TestRunnerError, TestStageDB, UnwindStageTestRunner,
     };
     use alloy_primitives::B256;
+    use reth_db::{test_utils::TempDatabase, Database, DatabaseEnv};
     use reth_primitives::SealedBlock;
     use reth_primitives_traits::SignedTransaction;
     use reth_provider::{
-        providers::StaticFileWriter, TransactionsProvider, TransactionsProviderExt,
+        providers::StaticFileWriter, test_utils::MockNodeTypesWithDB, DatabaseProvider,
+        TransactionsProvider, TransactionsProviderExt,
     };
     use reth_prune::PruneMode;
     use reth_testing_utils::generators::{self, random_block_range, BlockRangeParams};
@@ -187,20 +214,19 @@ mod tests {
     }
 
     impl StageTestRunner for PruneTestRunner {
-        type S = PruneStage;
+        type S = PruneStage<
+            DatabaseProvider<<TempDatabase<DatabaseEnv> as Database>::TXMut, MockNodeTypesWithDB>,
+        >;
 
         fn db(&self) -> &TestStageDB {
             &self.db
         }
 
         fn stage(&self) -> Self::S {
-            PruneStage {
-                prune_modes: PruneModes {
-                    sender_recovery: Some(PruneMode::Full),
-                    ..Default::default()
-                },
-                commit_threshold: usize::MAX,
-            }
+            PruneStage::new(
+                PruneModes { sender_recovery: Some(PruneMode::Full), ..Default::default() },
+                usize::MAX,
+            )
         }
     }
 
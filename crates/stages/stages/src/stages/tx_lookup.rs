@@ -30,6 +30,9 @@ use tracing::*;
 /// hash.
 ///
 /// It uses [`reth_etl::Collector`] to collect all entries before finally writing them to disk.
+///
+/// If the prune mode is [`PruneMode::Full`], the stage only advances its checkpoint and the index
+/// is disabled entirely.
 #[derive(Debug, Clone)]
 pub struct TransactionLookupStage {
     /// The maximum number of lookup entries to hold in memory before pushing them to
@@ -117,7 +120,8 @@ where
 
         // 500MB temporary files
         let mut hash_collector: Collector<TxHash, TxNumber> =
-            Collector::new(self.etl_config.file_size, self.etl_config.dir.clone());
+            Collector::new(self.etl_config.file_size, self.etl_config.dir.clone())
+                .with_metrics_label("TransactionHashNumbers");
 
         info!(
             target: "sync::stages::transaction_lookup",
@@ -149,6 +153,14 @@ where
                     .tx_ref()
                     .cursor_write::<tables::RawTable<tables::TransactionHashNumbers>>()?;
 
+                // Hashes that sort after the last one in the table are appended as well.
+                let mut append = append_only;
+                let last_hash = if append {
+                    None
+                } else {
+                    txhash_cursor.last()?.map(|(hash, _)| hash.into_key())
+                };
+
                 let total_hashes = hash_collector.len();
                 let interval = (total_hashes / 10).max(1);
                 for (index, hash_to_number) in hash_collector.iter()?.enumerate() {
@@ -162,8 +174,11 @@ where
                         );
                     }
 
+                    if !append && last_hash.as_ref().is_none_or(|last_hash| hash > *last_hash) {
+                        append = true;
+                    }
                     let key = RawKey::<TxHash>::from_vec(hash);
-                    if append_only {
+                    if append {
                         txhash_cursor.append(key, RawValue::<TxNumber>::from_vec(number))?
                     } else {
                         txhash_cursor.insert(key, RawValue::<TxNumber>::from_vec(number))?
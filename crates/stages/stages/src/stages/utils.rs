@@ -1,11 +1,12 @@
 //! Utils for `stages`.
-use alloy_primitives::{BlockNumber, TxNumber};
+use alloy_consensus::TxReceipt;
+use alloy_primitives::{Address, BlockNumber, TxNumber};
 use reth_config::config::EtlConfig;
 use reth_db::BlockNumberList;
 use reth_db_api::{
     cursor::{DbCursorRO, DbCursorRW},
-    models::sharded_key::NUM_OF_INDICES_IN_SHARD,
-    table::{Decompress, Table},
+    models::{sharded_key::NUM_OF_INDICES_IN_SHARD, ShardedKey},
+    table::{Decompress, Encode, Table},
     transaction::{DbTx, DbTxMut},
     DatabaseError,
 };
@@ -16,7 +17,11 @@ use reth_provider::{
     StaticFileProviderFactory,
 };
 use reth_stages_api::StageError;
-use std::{collections::HashMap, hash::Hash, ops::RangeBounds};
+use std::{
+    collections::HashMap,
+    hash::Hash,
+    ops::{RangeBounds, RangeInclusive},
+};
 use tracing::info;
 
 /// Number of blocks before pushing indices from cache to [`Collector`]
@@ -54,7 +59,8 @@ where
 {
     let mut changeset_cursor = provider.tx_ref().cursor_read::<CS>()?;
 
-    let mut collector = Collector::new(etl_config.file_size, etl_config.dir.clone());
+    let mut collector =
+        Collector::new(etl_config.file_size, etl_config.dir.clone()).with_metrics_label(H::NAME);
     let mut cache: HashMap<P, Vec<u64>> = HashMap::default();
 
     let mut collect = |cache: &HashMap<P, Vec<u64>>| {
@@ -98,6 +104,70 @@ where
     Ok(collector)
 }
 
+/// Collects the blocks in which each address emitted logs for a range of blocks into a
+/// [`Collector`], keyed like the [`LogAddressIndex`](reth_db::tables::LogAddressIndex).
+///
+/// The receipts are read block by block, otherwise this works like [`collect_history_indices`].
+pub(crate) fn collect_log_address_indices<Provider>(
+    provider: &Provider,
+    range: RangeInclusive<BlockNumber>,
+    etl_config: &EtlConfig,
+) -> Result<Collector<ShardedKey<Address>, BlockNumberList>, StageError>
+where
+    Provider: DBProvider + BlockReader,
+{
+    let mut collector = Collector::new(etl_config.file_size, etl_config.dir.clone())
+        .with_metrics_label("LogAddressIndex");
+    let mut cache: HashMap<Address, Vec<u64>> = HashMap::default();
+
+    let mut collect = |cache: &HashMap<Address, Vec<u64>>| {
+        for (address, indices) in cache {
+            let last = indices.last().expect("qed");
+            collector.insert(
+                ShardedKey::new(*address, *last),
+                BlockNumberList::new_pre_sorted(indices.iter().copied()),
+            )?;
+        }
+        Ok::<(), StageError>(())
+    };
+
+    // observability
+    let total_blocks = range.end().saturating_sub(*range.start()) + 1;
+    let interval = (total_blocks / 1000).max(1);
+
+    let mut flush_counter = 0;
+    for (idx, block_number) in range.enumerate() {
+        if idx > 0 && idx as u64 % interval == 0 && total_blocks > 1000 {
+            info!(target: "sync::stages::index_history", progress = %format!("{:.4}%", (idx as f64 / total_blocks as f64) * 100.0), "Collecting indices");
+        }
+
+        let Some(body) = provider.block_body_indices(block_number)? else { continue };
+        if body.tx_count == 0 {
+            continue
+        }
+
+        for receipt in provider.receipts_by_tx_range(body.tx_num_range())? {
+            for log in receipt.logs() {
+                let blocks = cache.entry(log.address).or_default();
+                if blocks.last() != Some(&block_number) {
+                    blocks.push(block_number);
+                }
+            }
+        }
+
+        // Make sure we only flush the cache every DEFAULT_CACHE_THRESHOLD blocks.
+        flush_counter += 1;
+        if flush_counter > DEFAULT_CACHE_THRESHOLD {
+            collect(&cache)?;
+            cache.clear();
+            flush_counter = 0;
+        }
+    }
+    collect(&cache)?;
+
+    Ok(collector)
+}
+
 /// Given a [`Collector`] created by [`collect_history_indices`] it iterates all entries, loading
 /// the indices into the database in shards.
 ///
@@ -123,12 +193,21 @@ where
     let mut current_partial = P::default();
     let mut current_list = Vec::<u64>::new();
 
+    // Keys that sort after the last one in the table have no shard in the table yet, so they're
+    // appended as well.
+    let mut append = append_only;
+    let last_key: Option<Vec<u8>> =
+        if append { None } else { write_cursor.last()?.map(|(key, _)| key.encode().into()) };
+
     // observability
     let total_entries = collector.len();
     let interval = (total_entries / 100).max(1);
 
     for (index, element) in collector.iter()?.enumerate() {
         let (k, v) = element?;
+        // The last shard of a partial key has the highest possible key of it, so if a key sorts
+        // after the last one in the table, its partial key has no shards in the table.
+        let is_new_partial = !append && last_key.as_ref().is_none_or(|last_key| k > *last_key);
         let sharded_key = decode_key(k)?;
         let new_list = BlockNumberList::decompress_owned(v)?;
 
@@ -148,16 +227,17 @@ where
                 current_partial,
                 &mut current_list,
                 &sharded_key_factory,
-                append_only,
+                append,
                 LoadMode::Flush,
             )?;
 
             current_partial = partial_key;
             current_list.clear();
+            append |= is_new_partial;
 
             // If it's not the first sync, there might an existing shard already, so we need to
             // merge it with the one coming from the collector
-            if !append_only {
+            if !append {
                 if let Some((_, last_database_shard)) =
                     write_cursor.seek_exact(sharded_key_factory(current_partial, u64::MAX))?
                 {
@@ -172,7 +252,7 @@ where
             current_partial,
             &mut current_list,
             &sharded_key_factory,
-            append_only,
+            append,
             LoadMode::KeepLast,
         )?;
     }
@@ -183,7 +263,7 @@ where
         current_partial,
         &mut current_list,
         &sharded_key_factory,
-        append_only,
+        append,
         LoadMode::Flush,
     )?;
 
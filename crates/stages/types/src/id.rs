@@ -1,3 +1,5 @@
+use alloc::vec::Vec;
+
 /// Stage IDs for all known stages.
 ///
 /// For custom stages, use [`StageId::Other`]
@@ -20,6 +22,10 @@ pub enum StageId {
     TransactionLookup,
     IndexStorageHistory,
     IndexAccountHistory,
+    /// Optional stage that indexes the addresses that emitted logs, not part of [`StageId::ALL`].
+    ///
+    /// The log address index is maintained as long as this stage has a checkpoint.
+    IndexLogAddresses,
     Prune,
     Finish,
     /// Other custom stage with a provided string identifier.
@@ -58,8 +64,91 @@ impl StageId {
         Self::Prune,
     ];
 
+    /// All known stages in the order the pipeline runs them, including the optional ones.
+    const PIPELINE_ORDER: [Self; 15] = [
+        Self::Headers,
+        Self::Bodies,
+        Self::SenderRecovery,
+        Self::Execution,
+        Self::PruneSenderRecovery,
+        Self::MerkleUnwind,
+        Self::AccountHashing,
+        Self::StorageHashing,
+        Self::MerkleExecute,
+        Self::TransactionLookup,
+        Self::IndexStorageHistory,
+        Self::IndexAccountHistory,
+        Self::IndexLogAddresses,
+        Self::Prune,
+        Self::Finish,
+    ];
+
+    /// Returns the stages whose data this stage reads, i.e. the stages that need to process a block
+    /// before this stage can process it.
+    ///
+    /// Custom stages have no known dependencies.
+    pub const fn dependencies(&self) -> &'static [Self] {
+        match self {
+            Self::Bodies => &[Self::Headers],
+            Self::SenderRecovery | Self::TransactionLookup => &[Self::Bodies],
+            Self::Execution => &[Self::SenderRecovery],
+            Self::PruneSenderRecovery |
+            Self::AccountHashing |
+            Self::StorageHashing |
+            Self::IndexStorageHistory |
+            Self::IndexAccountHistory |
+            Self::IndexLogAddresses => &[Self::Execution],
+            // the trie is unwound from the hashed state, and built from it by the execute half
+            Self::MerkleUnwind => &[Self::AccountHashing, Self::StorageHashing],
+            Self::MerkleExecute => {
+                &[Self::MerkleUnwind, Self::AccountHashing, Self::StorageHashing]
+            }
+            Self::Prune => &[
+                Self::PruneSenderRecovery,
+                Self::MerkleExecute,
+                Self::TransactionLookup,
+                Self::IndexStorageHistory,
+                Self::IndexAccountHistory,
+            ],
+            Self::Finish => &[Self::Prune],
+            #[allow(deprecated)]
+            Self::StaticFile => &[],
+            Self::Headers | Self::Other(_) => &[],
+        }
+    }
+
+    /// Returns the given stages along with all stages that depend on them, directly or through
+    /// other stages, in the order the pipeline runs them.
+    ///
+    /// These stages need to be unwound together: a stage can't keep the data of blocks that the
+    /// stages it depends on have unwound.
+    pub fn with_dependents(stages: &[Self]) -> Vec<Self> {
+        let mut with_dependents = stages.to_vec();
+        loop {
+            let len = with_dependents.len();
+            for stage in Self::PIPELINE_ORDER {
+                if !with_dependents.contains(&stage) &&
+                    stage
+                        .dependencies()
+                        .iter()
+                        .any(|dependency| with_dependents.contains(dependency))
+                {
+                    with_dependents.push(stage);
+                }
+            }
+            if with_dependents.len() == len {
+                break
+            }
+        }
+
+        // custom stages go first, since their position in the pipeline is unknown
+        with_dependents.sort_by_key(|stage| Self::PIPELINE_ORDER.iter().position(|s| s == stage));
+        with_dependents.dedup();
+        with_dependents
+    }
+
     /// Return stage id formatted as string.
-    pub const fn as_str(&self) -> &str {
+    pub const fn as_str(&self) -> &'static str {
         match self {
             #[allow(deprecated)]
             Self::StaticFile => "StaticFile",
@@ -75,6 +164,7 @@ impl StageId {
             Self::TransactionLookup => "TransactionLookup",
             Self::IndexAccountHistory => "IndexAccountHistory",
             Self::IndexStorageHistory => "IndexStorageHistory",
+            Self::IndexLogAddresses => "IndexLogAddresses",
             Self::Prune => "Prune",
             Self::Finish => "Finish",
             Self::Other(s) => s,
@@ -118,6 +208,7 @@ mod tests {
         assert_eq!(StageId::StorageHashing.to_string(), "StorageHashing");
         assert_eq!(StageId::MerkleExecute.to_string(), "MerkleExecute");
         assert_eq!(StageId::IndexAccountHistory.to_string(), "IndexAccountHistory");
+        assert_eq!(StageId::IndexLogAddresses.to_string(), "IndexLogAddresses");
         assert_eq!(StageId::IndexStorageHistory.to_string(), "IndexStorageHistory");
         assert_eq!(StageId::TransactionLookup.to_string(), "TransactionLookup");
         assert_eq!(StageId::Finish.to_string(), "Finish");
@@ -125,6 +216,42 @@ mod tests {
         assert_eq!(StageId::Other("Foo").to_string(), "Foo");
     }
 
+    #[test]
+    fn with_dependents() {
+        assert_eq!(
+            StageId::with_dependents(&[StageId::TransactionLookup]),
+            [StageId::TransactionLookup, StageId::Prune, StageId::Finish]
+        );
+        assert_eq!(
+            StageId::with_dependents(&[StageId::AccountHashing]),
+            [
+                StageId::MerkleUnwind,
+                StageId::AccountHashing,
+                StageId::MerkleExecute,
+                StageId::Prune,
+                StageId::Finish
+            ]
+        );
+        assert_eq!(
+            StageId::with_dependents(&[StageId::Execution]),
+            [
+                StageId::Execution,
+                StageId::PruneSenderRecovery,
+                StageId::MerkleUnwind,
+                StageId::AccountHashing,
+                StageId::StorageHashing,
+                StageId::MerkleExecute,
+                StageId::IndexStorageHistory,
+                StageId::IndexAccountHistory,
+                StageId::IndexLogAddresses,
+                StageId::Prune,
+                StageId::Finish
+            ]
+        );
+        assert_eq!(StageId::with_dependents(&[StageId::Headers]).len(), 15);
+        assert_eq!(StageId::with_dependents(&[StageId::Other("Foo")]), [StageId::Other("Foo")]);
+    }
+
     #[test]
     fn is_downloading_stage() {
         assert!(StageId::Headers.is_downloading_stage());
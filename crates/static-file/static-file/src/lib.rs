@@ -7,9 +7,15 @@
 )]
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
 
+mod scrubber;
 pub mod segments;
 mod static_file_producer;
 
+pub use scrubber::{
+    ScrubberFinding, ScrubberFindingKind, ScrubberProgress, ScrubberStep, StaticFileScrubber,
+    DEFAULT_SCRUBBER_RATE,
+};
+
 pub use static_file_producer::{
     StaticFileProducer, StaticFileProducerInner, StaticFileProducerResult,
     StaticFileProducerWithResult,
@@ -0,0 +1,738 @@
+//! Background verification of the integrity of static files.
+
+use alloy_consensus::{proofs::calculate_transaction_root, BlockHeader};
+use alloy_primitives::{BlockNumber, Sealable, TxHash, TxNumber, B256};
+use reth_db::{
+    static_file::{
+        ColumnSelectorOne, ColumnSelectorTwo, HeaderWithHashMask, ReceiptMask, TransactionMask,
+    },
+    table::{Decompress, Value},
+    tables,
+};
+use reth_db_api::{cursor::DbCursorRO, transaction::DbTx};
+use reth_metrics::{
+    metrics::{Counter, Gauge},
+    Metrics,
+};
+use reth_primitives_traits::{NodePrimitives, SignedTransaction};
+use reth_provider::{
+    providers::StaticFileProvider, BlockBodyIndicesProvider, DBProvider, DatabaseProviderFactory,
+    HeaderProvider, StaticFileProviderFactory,
+};
+use reth_static_file_types::StaticFileSegment;
+use reth_storage_errors::provider::{ProviderError, ProviderResult};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    fmt,
+    ops::RangeInclusive,
+    path::PathBuf,
+    time::{Duration, Instant},
+};
+use strum::IntoEnumIterator;
+use tokio::sync::watch;
+use tracing::{debug, error, info, warn};
+
+/// Default number of bytes of static files verified per second, 4MB.
+pub const DEFAULT_SCRUBBER_RATE: u64 = 4 * 1024 * 1024;
+
+/// Interval between the steps of the scrubber. Each step verifies a second worth of the rate.
+const STEP_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Interval at which the progress of the scrubber is saved.
+const PROGRESS_SAVE_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Number of random `TransactionHashNumbers` entries cross-checked per step.
+const TX_LOOKUP_SAMPLES: usize = 8;
+
+/// Segments in the order they're verified.
+const SEGMENTS: [StaticFileSegment; 3] =
+    [StaticFileSegment::Headers, StaticFileSegment::Transactions, StaticFileSegment::Receipts];
+
+/// Continuously verifies the static files at a limited rate, to find corrupted rows on long
+/// running nodes.
+///
+/// Every block of every segment is verified in turn:
+/// - headers are hashed and compared to the stored block hashes,
+/// - transactions are decoded and their root is compared to the transactions root of the header,
+/// - receipts are decoded.
+///
+/// On every step, random `TransactionHashNumbers` entries are also cross-checked against the
+/// hashes of the transactions they point to.
+///
+/// The progress is saved to the progress file, if any, so that the whole dataset is covered over
+/// multiple runs of the node.
+#[derive(Debug)]
+pub struct StaticFileScrubber<Provider> {
+    /// Provider factory
+    provider: Provider,
+    /// Number of bytes verified per second.
+    rate: u64,
+    /// The signal to pause the scrubber, e.g. under IO pressure.
+    pause_signal: Option<watch::Receiver<bool>>,
+    /// The file the progress is saved to.
+    progress_path: Option<PathBuf>,
+    /// Current progress, loaded from the progress file on the first step.
+    progress: Option<ScrubberProgress>,
+    metrics: ScrubberMetrics,
+}
+
+impl<Provider> StaticFileScrubber<Provider> {
+    /// Creates a new [`StaticFileScrubber`] that verifies `rate` bytes of static files per second.
+    pub fn new(provider: Provider, rate: u64) -> Self {
+        Self {
+            provider,
+            rate: rate.max(1),
+            pause_signal: None,
+            progress_path: None,
+            progress: None,
+            metrics: ScrubberMetrics::default(),
+        }
+    }
+
+    /// Sets the signal to pause the scrubber. While the signal is `true`, nothing is verified.
+    pub fn with_pause_signal(mut self, pause_signal: watch::Receiver<bool>) -> Self {
+        self.pause_signal = Some(pause_signal);
+        self
+    }
+
+    /// Sets the file the progress is saved to. Without one, every run of the node starts over from
+    /// the first block.
+    pub fn with_progress_path(mut self, progress_path: PathBuf) -> Self {
+        self.progress_path = Some(progress_path);
+        self
+    }
+
+    /// Returns the current progress of the scrubber, if it was loaded.
+    pub const fn progress(&self) -> Option<ScrubberProgress> {
+        self.progress
+    }
+}
+
+impl<Provider> StaticFileScrubber<Provider>
+where
+    Provider: StaticFileProviderFactory<
+            Primitives: NodePrimitives<SignedTx: Value, BlockHeader: Value, Receipt: Value>,
+        > + DatabaseProviderFactory<Provider: BlockBodyIndicesProvider>,
+{
+    /// Runs the scrubber until the task is dropped.
+    ///
+    /// Errors are logged and the scrubber carries on with the next step.
+    pub async fn run(mut self) {
+        info!(target: "static_file::scrubber", rate = self.rate, "Starting static file scrubber");
+        let mut last_save = Instant::now();
+        loop {
+            if let Some(pause_signal) = &mut self.pause_signal {
+                if *pause_signal.borrow() {
+                    debug!(target: "static_file::scrubber", "Static file scrubber is paused");
+                    self.metrics.scrubber.paused.set(1.0);
+                    if pause_signal.wait_for(|paused| !paused).await.is_err() {
+                        // The sender is gone, so the signal can't be cleared anymore
+                        self.pause_signal = None;
+                    }
+                    self.metrics.scrubber.paused.set(0.0);
+                }
+            }
+
+            let budget = self.rate.saturating_mul(STEP_INTERVAL.as_secs());
+            match self.step(budget) {
+                Ok(step) => {
+                    if step.pass_completed || last_save.elapsed() >= PROGRESS_SAVE_INTERVAL {
+                        if let Err(err) = self.save_progress() {
+                            warn!(
+                                target: "static_file::scrubber",
+                                %err,
+                                "Failed to save static file scrubber progress"
+                            );
+                        }
+                        last_save = Instant::now();
+                    }
+                }
+                Err(err) => {
+                    warn!(
+                        target: "static_file::scrubber",
+                        %err,
+                        "Static file scrubber step failed"
+                    );
+                }
+            }
+
+            tokio::time::sleep(STEP_INTERVAL).await;
+        }
+    }
+
+    /// Verifies the next blocks until at least `budget` bytes were read, or a pass over all
+    /// static files completed.
+    ///
+    /// Corrupted rows are logged, recorded in the metrics and returned.
+    pub fn step(&mut self, budget: u64) -> ProviderResult<ScrubberStep> {
+        let static_file_provider = self.provider.static_file_provider();
+        let provider = self.provider.database_provider_ro()?;
+        let mut progress = match self.progress {
+            Some(progress) => progress,
+            None => self.read_progress()?.unwrap_or_default(),
+        };
+
+        let mut step = ScrubberStep::default();
+        while step.bytes < budget {
+            let highest = static_file_provider.get_highest_static_file_block(progress.segment);
+            if highest.map_or(true, |highest| progress.block > highest) {
+                if progress.next_segment() {
+                    step.pass_completed = true;
+                    break
+                }
+                continue
+            }
+
+            let segment = progress.segment;
+            let block = progress.block;
+            let verified = verify_block(&static_file_provider, &provider, segment, block)?;
+
+            let metrics = &self.metrics.segments[&segment];
+            metrics.bytes_verified.increment(verified.bytes);
+            metrics.rows_verified.increment(verified.rows);
+            step.bytes += verified.bytes;
+            step.findings.extend(verified.findings);
+            progress.block += 1;
+        }
+
+        if step.pass_completed {
+            self.metrics.scrubber.passes.increment(1);
+            info!(
+                target: "static_file::scrubber",
+                passes = progress.passes,
+                "Static file scrubber completed a pass"
+            );
+        }
+
+        let tx_lookup_findings = self.check_tx_lookups(&static_file_provider, &provider)?;
+        step.findings.extend(tx_lookup_findings);
+
+        for finding in &step.findings {
+            self.metrics.segments[&finding.segment]
+                .corrupted_rows
+                .increment(finding.rows.end() - finding.rows.start() + 1);
+            error!(
+                target: "static_file::scrubber",
+                segment = %finding.segment,
+                blocks = ?finding.blocks,
+                rows = ?finding.rows,
+                "Corrupted static file rows: {}. {}",
+                finding.kind,
+                finding.repair_hint()
+            );
+        }
+
+        self.progress = Some(progress);
+        Ok(step)
+    }
+
+    /// Saves the current progress to the progress file, if any.
+    pub fn save_progress(&self) -> ProviderResult<()> {
+        let (Some(progress_path), Some(progress)) = (&self.progress_path, self.progress) else {
+            return Ok(())
+        };
+        reth_fs_util::atomic_write_file(progress_path, |file| {
+            serde_json::to_writer(file, &progress)
+        })
+        .map_err(|err| ProviderError::FsPathError(err.to_string()))
+    }
+
+    /// Reads the saved progress from the progress file, if any.
+    fn read_progress(&self) -> ProviderResult<Option<ScrubberProgress>> {
+        let Some(progress_path) = self.progress_path.as_deref().filter(|path| path.exists()) else {
+            return Ok(None)
+        };
+        reth_fs_util::read_json_file(progress_path)
+            .map(Some)
+            .map_err(|err| ProviderError::FsPathError(err.to_string()))
+    }
+
+    /// Cross-checks random `TransactionHashNumbers` entries against the hashes of the
+    /// transactions in static files they point to.
+    fn check_tx_lookups(
+        &self,
+        static_file_provider: &StaticFileProvider<Provider::Primitives>,
+        provider: &<Provider as DatabaseProviderFactory>::Provider,
+    ) -> ProviderResult<Vec<ScrubberFinding>> {
+        let Some(highest_tx) =
+            static_file_provider.get_highest_static_file_tx(StaticFileSegment::Transactions)
+        else {
+            return Ok(Vec::new())
+        };
+
+        let mut findings = Vec::new();
+        let mut cursor = provider.tx_ref().cursor_read::<tables::TransactionHashNumbers>()?;
+        for _ in 0..TX_LOOKUP_SAMPLES {
+            let entry = match cursor.seek(B256::from(rand::random::<[u8; 32]>()))? {
+                Some(entry) => Some(entry),
+                None => cursor.first()?,
+            };
+            let Some((hash, tx_number)) = entry else { break };
+            if tx_number > highest_tx {
+                continue
+            }
+
+            // Unreadable transactions are reported by the verification of their block
+            let Some(transaction) = read_transaction(static_file_provider, tx_number) else {
+                continue
+            };
+            self.metrics.scrubber.tx_lookup_checks.increment(1);
+            if *transaction.tx_hash() != hash {
+                let block = provider
+                    .tx_ref()
+                    .cursor_read::<tables::TransactionBlocks>()?
+                    .seek(tx_number)?
+                    .map(|(_, block)| block)
+                    .unwrap_or_default();
+                findings.push(ScrubberFinding {
+                    segment: StaticFileSegment::Transactions,
+                    blocks: block..=block,
+                    rows: tx_number..=tx_number,
+                    kind: ScrubberFindingKind::TxLookupMismatch {
+                        hash,
+                        actual: *transaction.tx_hash(),
+                    },
+                });
+            }
+        }
+
+        Ok(findings)
+    }
+}
+
+/// Verifies the rows of a block in a segment.
+fn verify_block<N, P>(
+    static_file_provider: &StaticFileProvider<N>,
+    provider: &P,
+    segment: StaticFileSegment,
+    block: BlockNumber,
+) -> ProviderResult<VerifiedBlock>
+where
+    N: NodePrimitives<SignedTx: Value, BlockHeader: Value, Receipt: Value>,
+    P: BlockBodyIndicesProvider,
+{
+    let mut verified = VerifiedBlock::default();
+    let unreadable = |rows: RangeInclusive<u64>, err: &dyn fmt::Display| ScrubberFinding {
+        segment,
+        blocks: block..=block,
+        rows,
+        kind: ScrubberFindingKind::Unreadable(err.to_string()),
+    };
+
+    if segment.is_headers() {
+        let jar = match static_file_provider.get_segment_provider_from_block(segment, block, None) {
+            Ok(jar) => jar,
+            Err(ProviderError::MissingStaticFileBlock(..)) => return Ok(verified),
+            Err(err) => return Err(err),
+        };
+        let mut cursor = jar.cursor()?;
+        let row = match cursor.get(block.into(), <HeaderWithHashMask<_> as ColumnSelectorTwo>::MASK)
+        {
+            Ok(Some(row)) => row,
+            Ok(None) => return Ok(verified),
+            Err(err) => {
+                verified.findings.push(unreadable(block..=block, &err));
+                return Ok(verified)
+            }
+        };
+        verified.bytes = row.iter().map(|column| column.len() as u64).sum();
+        verified.rows = 1;
+
+        match (N::BlockHeader::decompress(row[0]), B256::decompress(row[1])) {
+            (Ok(header), Ok(expected)) => {
+                let actual = header.hash_slow();
+                if actual != expected {
+                    verified.findings.push(ScrubberFinding {
+                        segment,
+                        blocks: block..=block,
+                        rows: block..=block,
+                        kind: ScrubberFindingKind::HeaderHashMismatch { expected, actual },
+                    });
+                }
+            }
+            (Err(err), _) | (_, Err(err)) => {
+                verified.findings.push(unreadable(block..=block, &err));
+            }
+        }
+        return Ok(verified)
+    }
+
+    let Some(indices) = provider.block_body_indices(block)? else { return Ok(verified) };
+    if indices.tx_count == 0 {
+        return Ok(verified)
+    }
+    let rows = indices.first_tx_num..=indices.last_tx_num();
+    let jar = match static_file_provider.get_segment_provider_from_transaction(
+        segment,
+        indices.first_tx_num,
+        None,
+    ) {
+        Ok(jar) => jar,
+        Err(ProviderError::MissingStaticFileTx(..)) => return Ok(verified),
+        Err(err) => return Err(err),
+    };
+    let mut cursor = jar.cursor()?;
+
+    let mut transactions = Vec::with_capacity(indices.tx_count as usize);
+    for tx_number in rows.clone() {
+        let mask = if segment.is_receipts() {
+            <ReceiptMask<N::Receipt> as ColumnSelectorOne>::MASK
+        } else {
+            <TransactionMask<N::SignedTx> as ColumnSelectorOne>::MASK
+        };
+        let row = match cursor.get(tx_number.into(), mask) {
+            Ok(Some(row)) => row,
+            // Rows of the block that aren't in static files, e.g. pruned ones
+            Ok(None) => return Ok(verified),
+            Err(err) => {
+                verified.findings.push(unreadable(tx_number..=tx_number, &err));
+                continue
+            }
+        };
+        verified.bytes += row[0].len() as u64;
+        verified.rows += 1;
+
+        let decoded = if segment.is_receipts() {
+            N::Receipt::decompress(row[0]).map(|_| ())
+        } else {
+            N::SignedTx::decompress(row[0]).map(|transaction| transactions.push(transaction))
+        };
+        if let Err(err) = decoded {
+            verified.findings.push(unreadable(tx_number..=tx_number, &err));
+        }
+    }
+
+    if segment == StaticFileSegment::Transactions &&
+        verified.findings.is_empty() &&
+        transactions.len() == indices.tx_count as usize
+    {
+        if let Some(header) = static_file_provider.header_by_number(block)? {
+            let actual = calculate_transaction_root(&transactions);
+            if actual != header.transactions_root() {
+                verified.findings.push(ScrubberFinding {
+                    segment,
+                    blocks: block..=block,
+                    rows,
+                    kind: ScrubberFindingKind::TransactionsRootMismatch {
+                        expected: header.transactions_root(),
+                        actual,
+                    },
+                });
+            }
+        }
+    }
+
+    Ok(verified)
+}
+
+/// Reads a transaction from static files, if it's there and can be decoded.
+fn read_transaction<N: NodePrimitives<SignedTx: Value>>(
+    static_file_provider: &StaticFileProvider<N>,
+    tx_number: TxNumber,
+) -> Option<N::SignedTx> {
+    let jar = static_file_provider
+        .get_segment_provider_from_transaction(StaticFileSegment::Transactions, tx_number, None)
+        .ok()?;
+    let mut cursor = jar.cursor().ok()?;
+    let row = cursor
+        .get(tx_number.into(), <TransactionMask<N::SignedTx> as ColumnSelectorOne>::MASK)
+        .ok()??;
+    N::SignedTx::decompress(row[0]).ok()
+}
+
+/// Rows of a block verified by [`verify_block`].
+#[derive(Debug, Default)]
+struct VerifiedBlock {
+    /// Number of bytes read.
+    bytes: u64,
+    /// Number of rows read.
+    rows: u64,
+    /// Corrupted rows.
+    findings: Vec<ScrubberFinding>,
+}
+
+/// Result of a [`StaticFileScrubber::step`].
+#[derive(Debug, Default)]
+pub struct ScrubberStep {
+    /// Number of bytes of static files read.
+    pub bytes: u64,
+    /// Corrupted rows that were found.
+    pub findings: Vec<ScrubberFinding>,
+    /// Whether a pass over all static files was completed.
+    pub pass_completed: bool,
+}
+
+/// Corrupted rows of a static file segment.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScrubberFinding {
+    /// Segment of the rows.
+    pub segment: StaticFileSegment,
+    /// Blocks of the rows.
+    pub blocks: RangeInclusive<BlockNumber>,
+    /// Block numbers of header rows, transaction numbers of transaction and receipt rows.
+    pub rows: RangeInclusive<u64>,
+    /// What's wrong with the rows.
+    pub kind: ScrubberFindingKind,
+}
+
+impl ScrubberFinding {
+    /// Returns how the rows can be repaired.
+    pub const fn repair_hint(&self) -> &'static str {
+        match self.kind {
+            ScrubberFindingKind::TxLookupMismatch { .. } => {
+                "Verify the transaction and rebuild the lookups with `reth db rebuild tx-lookup`"
+            }
+            _ => "Unwind below the blocks with `reth stage unwind to-block` and sync them again",
+        }
+    }
+}
+
+/// Kind of a [`ScrubberFinding`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScrubberFindingKind {
+    /// The row can't be read or decoded.
+    Unreadable(String),
+    /// The hash of a header doesn't match the stored block hash.
+    HeaderHashMismatch {
+        /// Stored block hash.
+        expected: B256,
+        /// Hash of the stored header.
+        actual: B256,
+    },
+    /// The root of the transactions of a block doesn't match the transactions root of its header.
+    TransactionsRootMismatch {
+        /// Transactions root of the header.
+        expected: B256,
+        /// Root of the stored transactions.
+        actual: B256,
+    },
+    /// A `TransactionHashNumbers` entry points to a transaction with another hash.
+    TxLookupMismatch {
+        /// Hash of the entry.
+        hash: TxHash,
+        /// Hash of the stored transaction.
+        actual: TxHash,
+    },
+}
+
+impl fmt::Display for ScrubberFindingKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unreadable(err) => write!(f, "unreadable row: {err}"),
+            Self::HeaderHashMismatch { expected, actual } => {
+                write!(f, "header hash {actual} doesn't match block hash {expected}")
+            }
+            Self::TransactionsRootMismatch { expected, actual } => {
+                write!(f, "transactions root {actual} doesn't match header root {expected}")
+            }
+            Self::TxLookupMismatch { hash, actual } => {
+                write!(f, "transaction lookup entry {hash} points to transaction {actual}")
+            }
+        }
+    }
+}
+
+/// Position of the [`StaticFileScrubber`] in its passes over the static files.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScrubberProgress {
+    /// Segment being verified.
+    pub segment: StaticFileSegment,
+    /// Next block to verify.
+    pub block: BlockNumber,
+    /// Number of completed passes.
+    pub passes: u64,
+}
+
+impl Default for ScrubberProgress {
+    fn default() -> Self {
+        Self { segment: SEGMENTS[0], block: 0, passes: 0 }
+    }
+}
+
+impl ScrubberProgress {
+    /// Moves to the first block of the next segment. Returns `true` if the pass completed.
+    fn next_segment(&mut self) -> bool {
+        let index = SEGMENTS.iter().position(|segment| *segment == self.segment).unwrap_or(0);
+        self.block = 0;
+        match SEGMENTS.get(index + 1) {
+            Some(segment) => {
+                self.segment = *segment;
+                false
+            }
+            None => {
+                self.segment = SEGMENTS[0];
+                self.passes += 1;
+                true
+            }
+        }
+    }
+}
+
+/// Metrics of the [`StaticFileScrubber`].
+#[derive(Debug)]
+struct ScrubberMetrics {
+    scrubber: ScrubberRunMetrics,
+    segments: HashMap<StaticFileSegment, ScrubberSegmentMetrics>,
+}
+
+impl Default for ScrubberMetrics {
+    fn default() -> Self {
+        Self {
+            scrubber: ScrubberRunMetrics::default(),
+            segments: StaticFileSegment::iter()
+                .map(|segment| {
+                    (
+                        segment,
+                        ScrubberSegmentMetrics::new_with_labels(&[("segment", segment.as_str())]),
+                    )
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Metrics of the passes of the [`StaticFileScrubber`].
+#[derive(Metrics)]
+#[metrics(scope = "static_files.scrubber")]
+struct ScrubberRunMetrics {
+    /// Number of completed passes over all static files
+    passes: Counter,
+    /// Number of cross-checked transaction lookup entries
+    tx_lookup_checks: Counter,
+    /// Whether the scrubber is paused
+    paused: Gauge,
+}
+
+/// Metrics of the [`StaticFileScrubber`] per segment.
+#[derive(Metrics)]
+#[metrics(scope = "static_files.scrubber")]
+struct ScrubberSegmentMetrics {
+    /// Number of bytes verified
+    bytes_verified: Counter,
+    /// Number of rows verified
+    rows_verified: Counter,
+    /// Number of corrupted rows found
+    corrupted_rows: Counter,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_nippy_jar::DataReader;
+    use reth_provider::{test_utils::MockNodeTypesWithDB, ProviderFactory};
+    use reth_stages::test_utils::{StorageKind, TestStageDB};
+    use reth_testing_utils::generators::{self, random_block_range, BlockRangeParams};
+    use std::{
+        fs::OpenOptions,
+        io::{Read, Seek, SeekFrom, Write},
+    };
+
+    fn setup() -> TestStageDB {
+        let mut rng = generators::rng();
+        let db = TestStageDB::default();
+
+        let blocks = random_block_range(
+            &mut rng,
+            0..=9,
+            BlockRangeParams { parent: Some(B256::ZERO), tx_count: 2..3, ..Default::default() },
+        );
+        db.insert_blocks(blocks.iter(), StorageKind::Static).expect("insert blocks");
+        db.insert_tx_hash_numbers(
+            blocks
+                .iter()
+                .flat_map(|block| &block.body.transactions)
+                .enumerate()
+                .map(|(tx_number, transaction)| (*transaction.tx_hash(), tx_number as u64)),
+        )
+        .expect("insert tx hash numbers");
+
+        db
+    }
+
+    /// Steps through a full pass, one block at a time, and returns the findings.
+    fn scrub_pass(
+        scrubber: &mut StaticFileScrubber<ProviderFactory<MockNodeTypesWithDB>>,
+    ) -> Vec<ScrubberFinding> {
+        let mut findings = Vec::new();
+        loop {
+            let step = scrubber.step(1).expect("scrubber step");
+            findings.extend(step.findings);
+            if step.pass_completed {
+                return findings
+            }
+        }
+    }
+
+    /// Flips the bits of the first signature byte of a transaction in its static file, which
+    /// still decodes but changes the transaction hash.
+    fn corrupt_transaction(db: &TestStageDB, tx_number: u64) {
+        let static_file_provider = db.factory.static_file_provider();
+        let jar = static_file_provider
+            .get_segment_provider_from_transaction(StaticFileSegment::Transactions, tx_number, None)
+            .expect("transactions static file");
+        let row = tx_number - jar.user_header().tx_start().expect("transactions");
+        let data_path = jar.data_path().to_path_buf();
+        // The signature follows the bitflags byte
+        let signature_start = DataReader::new(&data_path)
+            .and_then(|reader| reader.offset(row as usize))
+            .expect("row offset") +
+            1;
+        drop(jar);
+
+        let mut file = OpenOptions::new().read(true).write(true).open(data_path).unwrap();
+        let mut byte = [0];
+        file.seek(SeekFrom::Start(signature_start)).unwrap();
+        file.read_exact(&mut byte).unwrap();
+        file.seek(SeekFrom::Start(signature_start)).unwrap();
+        file.write_all(&[!byte[0]]).unwrap();
+        file.sync_all().unwrap();
+    }
+
+    #[test]
+    fn reports_corrupted_row_within_one_pass() {
+        let db = setup();
+        let mut scrubber = StaticFileScrubber::new(db.factory.clone(), DEFAULT_SCRUBBER_RATE);
+        assert_eq!(scrub_pass(&mut scrubber), Vec::new());
+        assert_eq!(scrubber.progress().map(|progress| progress.passes), Some(1));
+
+        let tx_number = 7;
+        corrupt_transaction(&db, tx_number);
+
+        let findings = scrub_pass(&mut scrubber);
+        assert!(
+            findings.iter().any(|finding| finding.segment == StaticFileSegment::Transactions &&
+                finding.rows.contains(&tx_number)),
+            "corrupted transaction {tx_number} not reported: {findings:?}"
+        );
+        assert!(findings.iter().all(|finding| finding.segment == StaticFileSegment::Transactions));
+    }
+
+    #[test]
+    fn resumes_from_saved_progress() {
+        let db = setup();
+        let dir = tempfile::tempdir().unwrap();
+        let progress_path = dir.path().join("static-file-scrubber.json");
+        let mut scrubber = StaticFileScrubber::new(db.factory.clone(), DEFAULT_SCRUBBER_RATE)
+            .with_progress_path(progress_path.clone());
+        for _ in 0..3 {
+            scrubber.step(1).unwrap();
+        }
+        let progress = scrubber.progress().unwrap();
+        assert_eq!(
+            progress,
+            ScrubberProgress { segment: StaticFileSegment::Headers, block: 3, passes: 0 }
+        );
+        scrubber.save_progress().unwrap();
+
+        let mut scrubber = StaticFileScrubber::new(db.factory.clone(), DEFAULT_SCRUBBER_RATE)
+            .with_progress_path(progress_path);
+        scrubber.step(1).unwrap();
+        assert_eq!(scrubber.progress(), Some(ScrubberProgress { block: 4, ..progress }));
+
+        // without a progress file, the scrubber starts over
+        let mut scrubber = StaticFileScrubber::new(db.factory.clone(), DEFAULT_SCRUBBER_RATE);
+        scrubber.step(1).unwrap();
+        assert_eq!(scrubber.progress(), Some(ScrubberProgress { block: 1, ..progress }));
+    }
+}
@@ -21,6 +21,7 @@ use std::{
     sync::Arc,
     time::Instant,
 };
+use tokio::sync::watch;
 use tracing::{debug, trace};
 
 /// Result of [`StaticFileProducerInner::run`] execution.
@@ -40,6 +41,14 @@ impl<Provider> StaticFileProducer<Provider> {
     pub fn new(provider: Provider, prune_modes: PruneModes) -> Self {
         Self(Arc::new(Mutex::new(StaticFileProducerInner::new(provider, prune_modes))))
     }
+
+    /// Sets the signal to pause producing static files, e.g. when the disk is almost full.
+    ///
+    /// CAUTION: This method locks the static file producer Mutex, hence can block the thread if the
+    /// lock is occupied.
+    pub fn set_pause_signal(&self, pause_signal: watch::Receiver<bool>) {
+        self.lock().pause_signal = Some(pause_signal);
+    }
 }
 
 impl<Provider> Clone for StaticFileProducer<Provider> {
@@ -66,12 +75,19 @@ pub struct StaticFileProducerInner<Provider> {
     /// needed in [`StaticFileProducerInner`] to prevent attempting to move prunable data to static
     /// files. See [`StaticFileProducerInner::get_static_file_targets`].
     prune_modes: PruneModes,
+    /// The signal to pause producing static files. While it's `true`, no data is copied.
+    pause_signal: Option<watch::Receiver<bool>>,
     event_sender: EventSender<StaticFileProducerEvent>,
 }
 
 impl<Provider> StaticFileProducerInner<Provider> {
     fn new(provider: Provider, prune_modes: PruneModes) -> Self {
-        Self { provider, prune_modes, event_sender: Default::default() }
+        Self { provider, prune_modes, pause_signal: None, event_sender: Default::default() }
+    }
+
+    /// Returns `true` if producing static files is paused.
+    fn is_paused(&self) -> bool {
+        self.pause_signal.as_ref().is_some_and(|paused| *paused.borrow())
     }
 }
 
@@ -113,12 +129,20 @@ where
     ///
     /// NOTE: it doesn't delete the data from database, and the actual deleting (aka pruning) logic
     /// lives in the `prune` crate.
+    ///
+    /// If producing static files is paused, no static files are produced and empty targets are
+    /// returned.
     pub fn run(&self, targets: StaticFileTargets) -> StaticFileProducerResult {
         // If there are no targets, do not produce any static files and return early
         if !targets.any() {
             return Ok(targets)
         }
 
+        if self.is_paused() {
+            debug!(target: "static_file", ?targets, "StaticFileProducer is paused");
+            return Ok(StaticFileTargets { headers: None, receipts: None, transactions: None })
+        }
+
         debug_assert!(targets.is_contiguous_to_highest_static_files(
             self.provider.static_file_provider().get_highest_static_files()
         ));
@@ -175,8 +199,14 @@ where
     /// Copies data from database to static files according to
     /// [stage checkpoints](reth_stages_types::StageCheckpoint).
     ///
-    /// Returns highest block numbers for all static file segments.
+    /// Returns highest block numbers for all static file segments. If producing static files is
+    /// paused, nothing is copied and the current highest block numbers are returned.
     pub fn copy_to_static_files(&self) -> ProviderResult<HighestStaticFiles> {
+        if self.is_paused() {
+            debug!(target: "static_file", "StaticFileProducer is paused");
+            return Ok(self.provider.static_file_provider().get_highest_static_files())
+        }
+
         let provider = self.provider.database_provider_ro()?;
         let stages_checkpoints = [StageId::Headers, StageId::Execution, StageId::Bodies]
             .into_iter()
@@ -270,6 +300,7 @@ mod tests {
     };
     use std::{sync::mpsc::channel, time::Duration};
     use tempfile::TempDir;
+    use tokio::sync::watch;
 
     fn setup() -> (ProviderFactory<MockNodeTypesWithDB>, TempDir) {
         let mut rng = generators::rng();
@@ -384,6 +415,47 @@ mod tests {
         );
     }
 
+    #[test]
+    fn run_paused() {
+        let (provider_factory, _temp_static_files_dir) = setup();
+
+        let (pause_tx, pause_rx) = watch::channel(true);
+        let static_file_producer =
+            StaticFileProducer::new(provider_factory.clone(), PruneModes::default());
+        static_file_producer.set_pause_signal(pause_rx);
+        let static_file_producer = static_file_producer.lock();
+
+        let targets = static_file_producer
+            .get_static_file_targets(HighestStaticFiles {
+                headers: Some(1),
+                receipts: Some(1),
+                transactions: Some(1),
+            })
+            .expect("get static file targets");
+        let highest_static_files =
+            provider_factory.static_file_provider().get_highest_static_files();
+
+        // Nothing is copied while paused
+        assert_eq!(
+            static_file_producer.run(targets.clone()).expect("run static file producer"),
+            StaticFileTargets { headers: None, receipts: None, transactions: None }
+        );
+        assert_eq!(
+            provider_factory.static_file_provider().get_highest_static_files(),
+            highest_static_files
+        );
+
+        pause_tx.send(false).unwrap();
+        assert_eq!(
+            static_file_producer.run(targets.clone()).expect("run static file producer"),
+            targets
+        );
+        assert_eq!(
+            provider_factory.static_file_provider().get_highest_static_files(),
+            HighestStaticFiles { headers: Some(1), receipts: Some(1), transactions: Some(1) }
+        );
+    }
+
     /// Tests that a cloneable [`StaticFileProducer`] type is not susceptible to any race condition.
     #[test]
     fn only_one() {
@@ -162,6 +162,11 @@ impl SegmentHeader {
         self.segment
     }
 
+    /// Returns the expected block range.
+    pub const fn expected_block_range(&self) -> &SegmentRangeInclusive {
+        &self.expected_block_range
+    }
+
     /// Returns the block range.
     pub const fn block_range(&self) -> Option<&SegmentRangeInclusive> {
         self.block_range.as_ref()
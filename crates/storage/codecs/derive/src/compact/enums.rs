@@ -5,14 +5,16 @@ pub struct EnumHandler<'a> {
     current_variant_index: u8,
     fields_iterator: std::iter::Peekable<std::slice::Iter<'a, FieldTypes>>,
     enum_lines: Vec<TokenStream2>,
+    reth_codecs: &'a syn::Path,
 }
 
 impl<'a> EnumHandler<'a> {
-    pub fn new(fields: &'a FieldList) -> Self {
+    pub fn new(fields: &'a FieldList, reth_codecs: &'a syn::Path) -> Self {
         EnumHandler {
             current_variant_index: 0u8,
             enum_lines: vec![],
             fields_iterator: fields.iter().peekable(),
+            reth_codecs,
         }
     }
 
@@ -20,13 +22,25 @@ impl<'a> EnumHandler<'a> {
         self.fields_iterator.next()
     }
 
+    /// Consumes all the named fields of the current struct variant.
+    fn next_named_fields(&mut self) -> Vec<&'a StructFieldDescriptor> {
+        let mut named_fields = vec![];
+        while let Some(FieldTypes::EnumNamedField(descriptor)) = self.fields_iterator.peek() {
+            named_fields.push(descriptor);
+            self.fields_iterator.next();
+        }
+        named_fields
+    }
+
     pub fn generate_to(mut self, ident: &Ident) -> Vec<TokenStream2> {
         while let Some(field) = self.next_field() {
             match field {
                 //  The following method will advance the
                 // `fields_iterator` by itself and stop right before the next variant.
                 FieldTypes::EnumVariant(name) => self.to(name, ident),
-                FieldTypes::EnumUnnamedField(_) | FieldTypes::StructField(_) => unreachable!(),
+                FieldTypes::EnumUnnamedField(_) |
+                FieldTypes::EnumNamedField(_) |
+                FieldTypes::StructField(_) => unreachable!(),
             }
         }
         self.enum_lines
@@ -38,7 +52,9 @@ impl<'a> EnumHandler<'a> {
                 //  The following method will advance the
                 // `fields_iterator` by itself and stop right before the next variant.
                 FieldTypes::EnumVariant(name) => self.from(name, ident),
-                FieldTypes::EnumUnnamedField(_) | FieldTypes::StructField(_) => unreachable!(),
+                FieldTypes::EnumUnnamedField(_) |
+                FieldTypes::EnumNamedField(_) |
+                FieldTypes::StructField(_) => unreachable!(),
             }
         }
         self.enum_lines
@@ -47,7 +63,7 @@ impl<'a> EnumHandler<'a> {
     /// Generates `from_compact` code for an enum variant.
     ///
     /// `fields_iterator` might look something like \[`VariantUnit`, `VariantUnnamedField`, Field,
-    /// `VariantUnit`...\].
+    /// `VariantUnit`, `VariantNamedFields`, Field, Field...\].
     pub fn from(&mut self, variant_name: &str, ident: &Ident) {
         let variant_name = format_ident!("{variant_name}");
         let current_variant_index = self.current_variant_index;
@@ -73,6 +89,45 @@ impl<'a> EnumHandler<'a> {
                     });
                     self.fields_iterator.next();
                 }
+                FieldTypes::EnumNamedField(_) => {
+                    // This variant is of the type `EnumVariant { a: FieldA, b: FieldB }`
+                    let reth_codecs = self.reth_codecs;
+                    let named_fields = self.next_named_fields();
+                    let names = named_fields
+                        .iter()
+                        .map(|f| format_ident!("{}", f.name))
+                        .collect::<Vec<_>>();
+                    let decode_fields = named_fields.iter().zip(&names).map(|(field, name)| {
+                        let field_type = format_ident!("{}", field.ftype);
+                        let from_compact_ident = if field.use_alt_impl {
+                            format_ident!("specialized_from_compact")
+                        } else {
+                            format_ident!("from_compact")
+                        };
+                        // Same as struct fields: only flag types rely on the returned length,
+                        // every other type reads its own field buffer.
+                        let (len_binding, len) = if is_flag_type(&field.ftype) {
+                            (quote! { len }, quote! { len })
+                        } else {
+                            (quote! { _ }, quote! { field_buf.len() })
+                        };
+                        quote! {
+                            let #name = match decoder.next_field() {
+                                Some((#len_binding, field_buf)) => #field_type::#from_compact_ident(field_buf, #len).0,
+                                None => Default::default(),
+                            };
+                        }
+                    });
+
+                    self.enum_lines.push(quote! {
+                        #current_variant_index => {
+                            let mut decoder = #reth_codecs::__private::VariantFieldsDecoder::new(buf);
+                            #(#decode_fields)*
+                            buf = decoder.finish();
+                            #ident::#variant_name { #(#names),* }
+                        }
+                    });
+                }
                 FieldTypes::EnumVariant(_) => self.enum_lines.push(quote! {
                     #current_variant_index => #ident::#variant_name,
                 }),
@@ -90,7 +145,7 @@ impl<'a> EnumHandler<'a> {
     /// Generates `to_compact` code for an enum variant.
     ///
     /// `fields_iterator` might look something like [`VariantUnit`, `VariantUnnamedField`, Field,
-    /// `VariantUnit`...].
+    /// `VariantUnit`, `VariantNamedFields`, Field, Field...].
     pub fn to(&mut self, variant_name: &str, ident: &Ident) {
         let variant_name = format_ident!("{variant_name}");
         let current_variant_index = self.current_variant_index;
@@ -113,6 +168,36 @@ impl<'a> EnumHandler<'a> {
                     });
                     self.fields_iterator.next();
                 }
+                FieldTypes::EnumNamedField(_) => {
+                    let reth_codecs = self.reth_codecs;
+                    let named_fields = self.next_named_fields();
+                    let count = named_fields.len();
+                    let names = named_fields
+                        .iter()
+                        .map(|f| format_ident!("{}", f.name))
+                        .collect::<Vec<_>>();
+                    let encode_fields = named_fields.iter().zip(&names).map(|(field, name)| {
+                        let to_compact_ident = if field.use_alt_impl {
+                            format_ident!("specialized_to_compact")
+                        } else {
+                            format_ident!("to_compact")
+                        };
+                        quote! {
+                            let mut field_buf = #reth_codecs::__private::bytes::BytesMut::new();
+                            let len = #name.#to_compact_ident(&mut field_buf);
+                            encoder.push(len, &field_buf);
+                        }
+                    });
+
+                    self.enum_lines.push(quote! {
+                        #ident::#variant_name { #(#names),* } => {
+                            let mut encoder = #reth_codecs::__private::VariantFieldsEncoder::new(#count);
+                            #(#encode_fields)*
+                            encoder.finish(&mut buffer);
+                            #current_variant_index
+                        },
+                    });
+                }
                 FieldTypes::EnumVariant(_) => self.enum_lines.push(quote! {
                     #ident::#variant_name => #current_variant_index,
                 }),
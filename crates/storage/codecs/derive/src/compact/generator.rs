@@ -18,7 +18,7 @@ pub fn generate_from_to(
     let reth_codecs = parse_reth_codecs_path(attrs).unwrap();
 
     let to_compact = generate_to_compact(fields, ident, zstd.clone(), &reth_codecs);
-    let from_compact = generate_from_compact(fields, ident, zstd);
+    let from_compact = generate_from_compact(fields, ident, zstd, &reth_codecs);
 
     let snake_case_ident = ident.to_string().to_case(Case::Snake);
 
@@ -105,6 +105,7 @@ fn generate_from_compact(
     fields: &FieldList,
     ident: &Ident,
     zstd: Option<ZstdConfig>,
+    reth_codecs: &syn::Path,
 ) -> TokenStream2 {
     let mut lines = vec![];
     let mut known_types =
@@ -120,7 +121,7 @@ fn generate_from_compact(
     let is_enum = fields.iter().any(|field| matches!(field, FieldTypes::EnumVariant(_)));
 
     if is_enum {
-        let enum_lines = EnumHandler::new(fields).generate_from(ident);
+        let enum_lines = EnumHandler::new(fields, reth_codecs).generate_from(ident);
 
         // Builds the object instantiation.
         lines.push(quote! {
@@ -200,7 +201,7 @@ fn generate_to_compact(
     let is_enum = fields.iter().any(|field| matches!(field, FieldTypes::EnumVariant(_)));
 
     if is_enum {
-        let enum_lines = EnumHandler::new(fields).generate_to(ident);
+        let enum_lines = EnumHandler::new(fields, reth_codecs).generate_to(ident);
 
         lines.push(quote! {
             flags.set_variant(match self {
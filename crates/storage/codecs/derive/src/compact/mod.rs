@@ -42,6 +42,7 @@ pub enum FieldTypes {
     StructField(StructFieldDescriptor),
     EnumVariant(String),
     EnumUnnamedField((FieldType, UseAlternative)),
+    EnumNamedField(StructFieldDescriptor),
 }
 
 /// Derives the `Compact` trait and its from/to implementations.
@@ -89,8 +90,17 @@ pub fn get_fields(data: &Data) -> FieldList {
                 fields.push(FieldTypes::EnumVariant(variant.ident.to_string()));
 
                 match &variant.fields {
-                    syn::Fields::Named(_) => {
-                        panic!("Not allowed to have Enum Variants with multiple named fields. Make it a struct instead.")
+                    syn::Fields::Named(data_fields) => {
+                        for field in &data_fields.named {
+                            load_field(field, &mut fields, false);
+                            if let Some(FieldTypes::StructField(descriptor)) = fields.pop() {
+                                assert!(
+                                    !descriptor.is_reference,
+                                    "Compact does not allow reference fields on enum variants."
+                                );
+                                fields.push(FieldTypes::EnumNamedField(descriptor));
+                            }
+                        }
                     }
                     syn::Fields::Unnamed(data_fields) => {
                         assert_eq!(
@@ -50,6 +50,13 @@ pub(crate) struct ZstdConfig {
 /// `Option<TExtension>` in the original struct. This approach allows further field extensions while
 /// maintaining backward compatibility.
 ///
+/// ### Enums:
+/// The variant index is stored in the bitflag struct. Unnamed variants (`Variant(T)`) encode their
+/// single field until the end of the buffer. Struct variants (`Variant { a: A, b: B }`) encode
+/// the field count and a presence bitmap, followed by each present field prefixed by its length.
+/// Fields can therefore be appended to a struct variant: values stored before the new field
+/// existed decode it as `Default::default()`, which every field type is required to implement.
+///
 /// ### Limitations:
 /// - Fields not listed above, or types such `Vec`, or large composite types, should manage their
 ///   own encoding and do not rely on the bitflag struct.
@@ -759,12 +759,22 @@ mod tests {
         Var0,
         Var1(TestStruct),
         Var2(u64),
+        Var3 {
+            f_u64: u64,
+            f_option: Option<B256>,
+            f_vec: Vec<Address>,
+        },
     }
 
     #[cfg(test)]
     #[allow(dead_code)]
     #[test_fuzz::test_fuzz]
-    fn compact_test_enum_all_variants(var0: TestEnum, var1: TestEnum, var2: TestEnum) {
+    fn compact_test_enum_all_variants(
+        var0: TestEnum,
+        var1: TestEnum,
+        var2: TestEnum,
+        var3: TestEnum,
+    ) {
         let mut buf = vec![];
         var0.to_compact(&mut buf);
         assert_eq!(TestEnum::from_compact(&buf, buf.len()).0, var0);
@@ -776,6 +786,10 @@ mod tests {
         let mut buf = vec![];
         var2.to_compact(&mut buf);
         assert_eq!(TestEnum::from_compact(&buf, buf.len()).0, var2);
+
+        let mut buf = vec![];
+        var3.to_compact(&mut buf);
+        assert_eq!(TestEnum::from_compact(&buf, buf.len()).0, var3);
     }
 
     #[test]
@@ -783,7 +797,73 @@ mod tests {
         let var0 = TestEnum::Var0;
         let var1 = TestEnum::Var1(TestStruct::default());
         let var2 = TestEnum::Var2(1u64);
+        let var3 = TestEnum::Var3 {
+            f_u64: 0xffff,
+            f_option: Some(B256::ZERO),
+            f_vec: vec![Address::ZERO],
+        };
+
+        compact_test_enum_all_variants(var0, var1, var2, var3);
+    }
 
-        compact_test_enum_all_variants(var0, var1, var2);
+    #[test]
+    fn compact_test_enum_struct_variant_layout() {
+        let var3 = TestEnum::Var3 { f_u64: 0xff, f_option: None, f_vec: vec![] };
+
+        let mut buf = vec![];
+        let len = var3.to_compact(&mut buf);
+        assert_eq!(len, buf.len());
+        assert_eq!(
+            buf,
+            [
+                3,     // variant
+                3,     // field count
+                0b101, // presence bitmap: `f_option` is not present
+                1, 1, 0xff, // f_u64: len, size, data
+                0, 1, 0, // f_vec: len, size, data
+            ]
+        );
+        assert_eq!(TestEnum::from_compact(&buf, len), (var3, [].as_slice()));
+    }
+
+    #[derive(Debug, PartialEq, Clone, Default, Serialize, Deserialize, Compact)]
+    #[reth_codecs(crate = "crate")]
+    enum TestEnumV1 {
+        #[default]
+        Var0,
+        Var1 {
+            f_u64: u64,
+        },
+    }
+
+    #[derive(Debug, PartialEq, Clone, Default, Serialize, Deserialize, Compact)]
+    #[reth_codecs(crate = "crate")]
+    enum TestEnumV2 {
+        #[default]
+        Var0,
+        Var1 {
+            f_u64: u64,
+            f_option: Option<u64>,
+        },
+    }
+
+    #[test]
+    fn compact_test_enum_struct_variant_trailing_fields() {
+        // Values stored before a field was appended decode it with its default value.
+        let mut buf = vec![];
+        let len = TestEnumV1::Var1 { f_u64: 2 }.to_compact(&mut buf);
+        assert_eq!(
+            TestEnumV2::from_compact(&buf, len),
+            (TestEnumV2::Var1 { f_u64: 2, f_option: None }, [].as_slice())
+        );
+
+        // Fields unknown to the current type are skipped.
+        let mut buf = vec![];
+        let len = TestEnumV2::Var1 { f_u64: 2, f_option: Some(3) }.to_compact(&mut buf);
+        buf.push(0xaa);
+        assert_eq!(
+            TestEnumV1::from_compact(&buf, len),
+            (TestEnumV1::Var1 { f_u64: 2 }, [0xaa].as_slice())
+        );
     }
 }
@@ -1,3 +1,95 @@
 pub use modular_bitfield;
 
 pub use bytes::{self, Buf};
+
+use crate::{decode_varuint, encode_varuint};
+use alloc::vec::Vec;
+use bytes::{BufMut, BytesMut};
+
+/// Encodes the named fields of an enum struct variant.
+///
+/// Layout: `varuint(field count) | presence bitmap | (varuint(len) | varuint(size) | bytes)*`.
+///
+/// Only fields that wrote any bytes or returned a non-zero `len` are marked as present. Since the
+/// field count is stored alongside the data, new fields can be appended to a variant without
+/// breaking the decoding of previously stored values.
+#[derive(Debug)]
+pub struct VariantFieldsEncoder {
+    presence: Vec<u8>,
+    fields: BytesMut,
+    index: usize,
+}
+
+impl VariantFieldsEncoder {
+    /// Creates a new encoder for a variant with `count` fields.
+    pub fn new(count: usize) -> Self {
+        Self { presence: alloc::vec![0; count.div_ceil(8)], fields: BytesMut::new(), index: 0 }
+    }
+
+    /// Pushes the next field, given the `len` returned by its `to_compact` and the written bytes.
+    pub fn push(&mut self, len: usize, bytes: &[u8]) {
+        if len != 0 || !bytes.is_empty() {
+            self.presence[self.index / 8] |= 1 << (self.index % 8);
+            encode_varuint(len, &mut self.fields);
+            encode_varuint(bytes.len(), &mut self.fields);
+            self.fields.put_slice(bytes);
+        }
+        self.index += 1;
+    }
+
+    /// Writes the encoded fields into `buf`.
+    pub fn finish<B>(self, buf: &mut B)
+    where
+        B: BufMut + AsMut<[u8]>,
+    {
+        encode_varuint(self.index, buf);
+        buf.put_slice(&self.presence);
+        buf.put(self.fields);
+    }
+}
+
+/// Decodes the named fields of an enum struct variant encoded by [`VariantFieldsEncoder`].
+#[derive(Debug)]
+pub struct VariantFieldsDecoder<'a> {
+    count: usize,
+    presence: &'a [u8],
+    buf: &'a [u8],
+    index: usize,
+}
+
+impl<'a> VariantFieldsDecoder<'a> {
+    /// Reads the field count and presence bitmap from `buf`.
+    pub fn new(buf: &'a [u8]) -> Self {
+        let (count, buf) = decode_varuint(buf);
+        let (presence, buf) = buf.split_at(count.div_ceil(8));
+        Self { count, presence, buf, index: 0 }
+    }
+
+    /// Returns the `len` and the bytes of the next field.
+    ///
+    /// Returns `None` if the field was not encoded, either because it held no data or because it
+    /// was appended to the variant after the value was stored. The caller should fall back to the
+    /// default value.
+    pub fn next_field(&mut self) -> Option<(usize, &'a [u8])> {
+        let index = self.index;
+        self.index += 1;
+
+        if index >= self.count || self.presence[index / 8] & (1 << (index % 8)) == 0 {
+            return None
+        }
+
+        let (len, buf) = decode_varuint(self.buf);
+        let (size, buf) = decode_varuint(buf);
+        let (field, buf) = buf.split_at(size);
+        self.buf = buf;
+        Some((len, field))
+    }
+
+    /// Skips any encoded fields unknown to the current type and returns the remaining buffer.
+    pub fn finish(mut self) -> &'a [u8] {
+        while self.index < self.count {
+            self.next_field();
+        }
+        self.buf
+    }
+}
@@ -1,5 +1,7 @@
 //! Test utilities for `Compact` derive macro
 
+use alloc::{format, string::String, vec::Vec};
+
 /// Macro to ensure that derived `Compact` types can be extended with new fields while maintaining
 /// backwards compatibility.
 ///
@@ -88,3 +90,69 @@ pub fn test_decode<T: crate::Compact>(buf: &[u8]) {
     decoded.to_compact(&mut encoded);
     assert_eq!(buf, &encoded[..]);
 }
+
+/// A committed `Compact` encoding of a value.
+///
+/// Fixture files are JSON arrays of `{"value": <serde value>, "encoded": "0x..."}` entries. They
+/// are generated once with the encoding of the time and must never be regenerated to make a test
+/// pass, as they are the reference of what might already be stored on disk.
+#[derive(Debug, serde::Deserialize)]
+struct CompactFixture<T> {
+    value: T,
+    encoded: String,
+}
+
+/// Ensures that every committed fixture still decodes to its value with the current `Compact`
+/// implementation of `T`.
+///
+/// On failure, the panic message contains a line diff between the committed value and the decoded
+/// one. A failure means the change is not backwards compatible with data already stored on disk.
+pub fn test_compact_fixtures<T>(fixtures: &str)
+where
+    T: crate::Compact + serde::de::DeserializeOwned + core::fmt::Debug + PartialEq,
+{
+    let type_name = core::any::type_name::<T>();
+    let fixtures: Vec<CompactFixture<T>> =
+        serde_json::from_str(fixtures).expect("failed to parse fixtures");
+
+    for (index, CompactFixture { value, encoded }) in fixtures.into_iter().enumerate() {
+        let buf = alloy_primitives::hex::decode(&encoded).expect("invalid fixture encoding");
+        let (decoded, remaining) = T::from_compact(&buf, buf.len());
+
+        assert!(
+            decoded == value,
+            "`{type_name}` fixture #{index} ({encoded}) no longer decodes to the committed value. \
+             This change is not backwards compatible.\n{}",
+            debug_diff(&value, &decoded)
+        );
+        assert!(
+            remaining.is_empty(),
+            "`{type_name}` fixture #{index} ({encoded}) left {} bytes undecoded",
+            remaining.len()
+        );
+    }
+}
+
+/// Returns a line diff of the pretty `Debug` representation of `expected` and `actual`.
+fn debug_diff<T: core::fmt::Debug>(expected: &T, actual: &T) -> String {
+    let expected = format!("{expected:#?}");
+    let actual = format!("{actual:#?}");
+    let (expected, actual) =
+        (expected.lines().collect::<Vec<_>>(), actual.lines().collect::<Vec<_>>());
+
+    let mut diff = String::from("--- committed\n+++ decoded\n");
+    for index in 0..expected.len().max(actual.len()) {
+        match (expected.get(index), actual.get(index)) {
+            (Some(e), Some(a)) if e == a => diff.push_str(&format!("  {e}\n")),
+            (e, a) => {
+                if let Some(e) = e {
+                    diff.push_str(&format!("- {e}\n"));
+                }
+                if let Some(a) = a {
+                    diff.push_str(&format!("+ {a}\n"));
+                }
+            }
+        }
+    }
+    diff
+}
@@ -98,6 +98,18 @@ pub trait DbDupCursorRO<T: DupSort> {
     ) -> Result<DupWalker<'_, T, Self>, DatabaseError>
     where
         Self: Sized;
+
+    /// Get an iterator over all duplicate values of `key`.
+    ///
+    /// Unlike [`DbCursorRO::walk`], the iterator stops at the end of the duplicate set of `key`
+    /// instead of continuing with the next key, so callers don't need to compare keys. The
+    /// iterator is empty if `key` doesn't exist.
+    fn walk_dup_prefix(&mut self, key: T::Key) -> Result<DupWalker<'_, T, Self>, DatabaseError>
+    where
+        Self: Sized,
+    {
+        self.walk_dup(Some(key), None)
+    }
 }
 
 /// Read write cursor over table.
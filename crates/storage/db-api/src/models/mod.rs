@@ -26,6 +26,7 @@ pub use blocks::*;
 pub use integer_list::IntegerList;
 pub use reth_db_models::{
     AccountBeforeTx, ClientVersion, StoredBlockBodyIndices, StoredBlockWithdrawals,
+    StoredForkchoiceState, VersionedStoredBlockBodyIndices,
 };
 pub use sharded_key::ShardedKey;
 
@@ -231,6 +232,7 @@ impl_compression_for_compact!(
     StageCheckpoint,
     PruneCheckpoint,
     ClientVersion,
+    StoredForkchoiceState,
     // Non-DB
     GenesisAccount
 );
@@ -347,6 +349,7 @@ mod tests {
         assert_eq!(StageUnitCheckpoint::bitflag_encoded_bytes(), 1);
         assert_eq!(StoredBlockBodyIndices::bitflag_encoded_bytes(), 1);
         assert_eq!(StoredBlockWithdrawals::bitflag_encoded_bytes(), 0);
+        assert_eq!(StoredForkchoiceState::bitflag_encoded_bytes(), 1);
         assert_eq!(StorageHashingCheckpoint::bitflag_encoded_bytes(), 1);
 
         validate_bitflag_backwards_compat!(Account, UnusedBits::NotZero);
@@ -367,6 +370,7 @@ mod tests {
         validate_bitflag_backwards_compat!(StageUnitCheckpoint, UnusedBits::Zero);
         validate_bitflag_backwards_compat!(StoredBlockBodyIndices, UnusedBits::Zero);
         validate_bitflag_backwards_compat!(StoredBlockWithdrawals, UnusedBits::Zero);
+        validate_bitflag_backwards_compat!(StoredForkchoiceState, UnusedBits::NotZero);
         validate_bitflag_backwards_compat!(StorageHashingCheckpoint, UnusedBits::NotZero);
     }
 }
@@ -91,6 +91,19 @@ pub trait Table: Send + Sync + Debug + 'static {
     /// Whether the table is also a `DUPSORT` table.
     const DUPSORT: bool;
 
+    /// Whether the table keys are stored as native-endian integers (`MDBX_INTEGERKEY`).
+    ///
+    /// Only applicable to tables keyed by an 8-byte big-endian encoded integer, such as
+    /// [`BlockNumber`](alloy_primitives::BlockNumber). Integer keys are compared as integers
+    /// instead of byte strings, which makes lookups and appends on them cheaper.
+    const INTEGER_KEY: bool = false;
+
+    /// Whether all values of the `DUPSORT` table have the same size, so that the values of a key
+    /// are stored packed together (`MDBX_DUPFIXED`).
+    ///
+    /// Only applicable to `DUPSORT` tables whose values aren't compressed to a variable size.
+    const DUP_FIXED: bool = false;
+
     /// Key element of `Table`.
     ///
     /// Sorting should be taken into account when encoding this.
@@ -107,6 +120,16 @@ pub trait TableInfo: Send + Sync + Debug + 'static {
 
     /// Whether the table is a `DUPSORT` table.
     fn is_dupsort(&self) -> bool;
+
+    /// Whether the table keys are stored as native-endian integers. See [`Table::INTEGER_KEY`].
+    fn is_integer_key(&self) -> bool {
+        false
+    }
+
+    /// Whether the values of the `DUPSORT` table have the same size. See [`Table::DUP_FIXED`].
+    fn is_dupfixed(&self) -> bool {
+        false
+    }
 }
 
 /// Tuple with `T::Key` and `T::Value`.
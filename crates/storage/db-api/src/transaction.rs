@@ -31,6 +31,13 @@ pub trait DbTx: Send + Sync {
     fn cursor_dup_read<T: DupSort>(&self) -> Result<Self::DupCursor<T>, DatabaseError>;
     /// Returns number of entries in the table.
     fn entries<T: Table>(&self) -> Result<usize, DatabaseError>;
+    /// Returns `true` if the table keys are stored as integers. See [`Table::INTEGER_KEY`].
+    ///
+    /// Tables created by an older version store their keys as they are encoded until they're
+    /// recreated with [`DbTxMut::recreate_table`].
+    fn is_integer_key<T: Table>(&self) -> Result<bool, DatabaseError> {
+        Ok(T::INTEGER_KEY)
+    }
     /// Disables long-lived read transaction safety guarantees.
     fn disable_long_read_transaction_safety(&mut self);
 }
@@ -54,8 +61,19 @@ pub trait DbTxMut: Send + Sync {
         -> Result<bool, DatabaseError>;
     /// Clears database.
     fn clear<T: Table>(&self) -> Result<(), DatabaseError>;
+    /// Drops the table and creates it again, empty, with the flags of its current definition,
+    /// e.g. [`Table::INTEGER_KEY`].
+    ///
+    /// Cursors of the table opened before can't be used afterwards.
+    fn recreate_table<T: Table>(&self) -> Result<(), DatabaseError> {
+        self.clear::<T>()
+    }
     /// Cursor mut
     fn cursor_write<T: Table>(&self) -> Result<Self::CursorMut<T>, DatabaseError>;
     /// `DupCursor` mut.
     fn cursor_dup_write<T: DupSort>(&self) -> Result<Self::DupCursorMut<T>, DatabaseError>;
+    /// Attributes the writes of the transaction to the stage in the database metrics.
+    ///
+    /// No-op by default.
+    fn set_stage_label(&mut self, _stage: &'static str) {}
 }
@@ -0,0 +1,682 @@
+//! Per-block statistics over a range of blocks.
+//!
+//! For every block, [`BlockStats`] are computed from the header, the receipts and the account and
+//! storage changesets. New accounts and added storage slots are the changeset entries whose value
+//! before the block is empty. Cleared storage slots and new bytecode are the entries whose value
+//! after the block, as read from the historical state, is empty or has code respectively.
+//!
+//! Blocks are processed and written one at a time, so the memory usage is bounded by a single
+//! block and the fixed size histograms of the [`BlockStatsSummary`].
+
+use alloy_consensus::{constants::KECCAK_EMPTY, BlockHeader, TxReceipt};
+use alloy_primitives::BlockNumber;
+use reth_provider::{
+    providers::ProviderNodeTypes, AccountReader, BlockBodyIndicesProvider, ChangeSetReader,
+    HeaderProvider, ProviderError, ProviderFactory, PruneCheckpointReader, ReceiptProvider,
+    StateProvider, StateProviderBox, StorageChangeSetReader,
+};
+use reth_prune_types::PruneSegment;
+use serde::{Deserialize, Serialize};
+use std::{
+    fmt,
+    io::Write,
+    ops::RangeInclusive,
+    time::{Duration, Instant},
+};
+use tracing::info;
+
+/// Interval at which the progress is logged.
+const PROGRESS_LOG_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Prune segments of the data the statistics are computed from.
+const REQUIRED_SEGMENTS: [PruneSegment; 4] = [
+    PruneSegment::Receipts,
+    PruneSegment::ContractLogs,
+    PruneSegment::AccountHistory,
+    PruneSegment::StorageHistory,
+];
+
+/// Names of the [`BlockStats`] metrics, in the order of [`BlockStats::metrics`].
+pub const METRIC_NAMES: [&str; 7] = [
+    "gas_used",
+    "tx_count",
+    "failed_tx_count",
+    "new_accounts",
+    "storage_slots_added",
+    "storage_slots_cleared",
+    "new_code_bytes",
+];
+
+/// The format the block statistics are written in.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum BlockStatsFormat {
+    /// Aligned columns followed by the summary.
+    #[default]
+    Table,
+    /// One [`BlockStats`] JSON object per line, followed by a line with the summary.
+    Json,
+    /// Comma separated values with a header line. The summary isn't written.
+    Csv,
+}
+
+/// Statistics of a single block.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlockStats {
+    /// The block number.
+    pub number: BlockNumber,
+    /// The gas used by the block.
+    pub gas_used: u64,
+    /// The number of transactions in the block.
+    pub tx_count: u64,
+    /// The number of transactions that reverted.
+    pub failed_tx_count: u64,
+    /// The number of accounts that didn't exist before the block.
+    pub new_accounts: u64,
+    /// The number of storage slots that were empty before the block.
+    pub storage_slots_added: u64,
+    /// The number of storage slots that were set before the block and are empty after it.
+    pub storage_slots_cleared: u64,
+    /// The size of the bytecode deployed to accounts that had no code before the block.
+    pub new_code_bytes: u64,
+}
+
+impl BlockStats {
+    /// Returns the values of the metrics, in the order of [`METRIC_NAMES`].
+    pub const fn metrics(&self) -> [u64; 7] {
+        [
+            self.gas_used,
+            self.tx_count,
+            self.failed_tx_count,
+            self.new_accounts,
+            self.storage_slots_added,
+            self.storage_slots_cleared,
+            self.new_code_bytes,
+        ]
+    }
+}
+
+/// Summary of a single metric over a range of blocks.
+///
+/// Percentiles are approximated by a histogram with a relative error of less than 2%.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MetricSummary {
+    /// The sum over all blocks.
+    pub total: u64,
+    /// The smallest value of a block.
+    pub min: u64,
+    /// The largest value of a block.
+    pub max: u64,
+    /// The median.
+    pub p50: u64,
+    /// The 90th percentile.
+    pub p90: u64,
+    /// The 99th percentile.
+    pub p99: u64,
+}
+
+/// Summary of the [`BlockStats`] over a range of blocks.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlockStatsSummary {
+    /// The number of blocks.
+    pub blocks: u64,
+    /// Summary of [`BlockStats::gas_used`].
+    pub gas_used: MetricSummary,
+    /// Summary of [`BlockStats::tx_count`].
+    pub tx_count: MetricSummary,
+    /// Summary of [`BlockStats::failed_tx_count`].
+    pub failed_tx_count: MetricSummary,
+    /// Summary of [`BlockStats::new_accounts`].
+    pub new_accounts: MetricSummary,
+    /// Summary of [`BlockStats::storage_slots_added`].
+    pub storage_slots_added: MetricSummary,
+    /// Summary of [`BlockStats::storage_slots_cleared`].
+    pub storage_slots_cleared: MetricSummary,
+    /// Summary of [`BlockStats::new_code_bytes`].
+    pub new_code_bytes: MetricSummary,
+}
+
+impl BlockStatsSummary {
+    /// Returns the summaries of the metrics, in the order of [`METRIC_NAMES`].
+    pub const fn metrics(&self) -> [MetricSummary; 7] {
+        [
+            self.gas_used,
+            self.tx_count,
+            self.failed_tx_count,
+            self.new_accounts,
+            self.storage_slots_added,
+            self.storage_slots_cleared,
+            self.new_code_bytes,
+        ]
+    }
+}
+
+impl fmt::Display for BlockStatsSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Summary of {} blocks", self.blocks)?;
+        writeln!(
+            f,
+            "{:<22}{:>20}{:>14}{:>14}{:>14}{:>14}{:>14}",
+            "metric", "total", "min", "max", "p50", "p90", "p99"
+        )?;
+        for (name, metric) in METRIC_NAMES.iter().zip(self.metrics()) {
+            writeln!(
+                f,
+                "{name:<22}{:>20}{:>14}{:>14}{:>14}{:>14}{:>14}",
+                metric.total, metric.min, metric.max, metric.p50, metric.p90, metric.p99
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Error returned if data the statistics are computed from has been pruned in the range.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("data of blocks {range:?} is pruned, missing segments: {}", format_segments(.segments))]
+pub struct PrunedSegmentsError {
+    /// The requested range of blocks.
+    pub range: RangeInclusive<BlockNumber>,
+    /// The pruned segments with the highest pruned block of each.
+    pub segments: Vec<(PruneSegment, BlockNumber)>,
+}
+
+fn format_segments(segments: &[(PruneSegment, BlockNumber)]) -> String {
+    segments
+        .iter()
+        .map(|(segment, pruned_to)| format!("{segment} (pruned up to block {pruned_to})"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Returns a [`PrunedSegmentsError`] listing all segments the statistics are computed from that
+/// are pruned in the range.
+pub fn ensure_block_stats_available<N: ProviderNodeTypes>(
+    factory: &ProviderFactory<N>,
+    range: &RangeInclusive<BlockNumber>,
+) -> eyre::Result<()> {
+    let provider = factory.provider()?;
+    let mut segments = Vec::new();
+    for segment in REQUIRED_SEGMENTS {
+        // The checkpoint stores the highest pruned block number, so the data exists for all
+        // blocks strictly above it.
+        if let Some(pruned_to) = provider
+            .get_prune_checkpoint(segment)?
+            .and_then(|checkpoint| checkpoint.block_number)
+            .filter(|pruned_to| range.start() <= pruned_to)
+        {
+            segments.push((segment, pruned_to));
+        }
+    }
+
+    if !segments.is_empty() {
+        return Err(PrunedSegmentsError { range: range.clone(), segments }.into())
+    }
+    Ok(())
+}
+
+/// Computes the [`BlockStats`] of the given block.
+pub fn block_stats<N: ProviderNodeTypes>(
+    factory: &ProviderFactory<N>,
+    number: BlockNumber,
+) -> eyre::Result<BlockStats> {
+    let provider = factory.provider()?;
+    let header =
+        provider.header_by_number(number)?.ok_or(ProviderError::HeaderNotFound(number.into()))?;
+    let tx_count = provider
+        .block_body_indices(number)?
+        .ok_or(ProviderError::BlockBodyIndicesNotFound(number))?
+        .tx_count;
+    let receipts = provider.receipts_by_block(number.into())?.unwrap_or_default();
+
+    let mut stats = BlockStats {
+        number,
+        gas_used: header.gas_used(),
+        tx_count,
+        failed_tx_count: receipts.iter().filter(|receipt| !receipt.status()).count() as u64,
+        ..Default::default()
+    };
+
+    // The state after the block is only needed for some of the changeset entries, so it's opened
+    // lazily.
+    let mut state = None;
+
+    for change in provider.account_block_changeset(number)? {
+        if change.info.is_none() {
+            stats.new_accounts += 1;
+        }
+
+        let had_code = change
+            .info
+            .and_then(|info| info.bytecode_hash)
+            .is_some_and(|code_hash| code_hash != KECCAK_EMPTY);
+        if had_code {
+            continue
+        }
+
+        let after = state_after_block(factory, number, &mut state)?;
+        let code_hash = after
+            .basic_account(&change.address)?
+            .and_then(|account| account.bytecode_hash)
+            .filter(|code_hash| *code_hash != KECCAK_EMPTY);
+        if let Some(code_hash) = code_hash {
+            if let Some(code) = after.bytecode_by_hash(&code_hash)? {
+                stats.new_code_bytes += code.original_bytes().len() as u64;
+            }
+        }
+    }
+
+    for (key, entry) in provider.storage_changeset(number)? {
+        if entry.value.is_zero() {
+            stats.storage_slots_added += 1;
+            continue
+        }
+
+        let after = state_after_block(factory, number, &mut state)?;
+        if after.storage(key.address(), entry.key)?.unwrap_or_default().is_zero() {
+            stats.storage_slots_cleared += 1;
+        }
+    }
+
+    Ok(stats)
+}
+
+/// Returns the state after the given block, opening it if it isn't open yet.
+fn state_after_block<'a, N: ProviderNodeTypes>(
+    factory: &ProviderFactory<N>,
+    number: BlockNumber,
+    state: &'a mut Option<StateProviderBox>,
+) -> eyre::Result<&'a StateProviderBox> {
+    match state {
+        Some(state) => Ok(state),
+        None => Ok(state.insert(factory.history_by_block_number(number)?)),
+    }
+}
+
+/// Computes the [`BlockStats`] of all blocks in the range and writes them to the writer in the
+/// given format, one block at a time.
+///
+/// Returns a [`PrunedSegmentsError`] before writing anything if data of the range is pruned.
+pub fn write_block_stats<N: ProviderNodeTypes>(
+    factory: &ProviderFactory<N>,
+    range: RangeInclusive<BlockNumber>,
+    format: BlockStatsFormat,
+    mut writer: impl Write,
+) -> eyre::Result<BlockStatsSummary> {
+    ensure_block_stats_available(factory, &range)?;
+
+    info!(target: "reth::cli", ?range, ?format, "Computing block stats");
+
+    match format {
+        BlockStatsFormat::Table => {
+            write!(writer, "{:>12}", "number")?;
+            for name in METRIC_NAMES {
+                write!(writer, "{name:>width$}", width = name.len().max(12) + 2)?;
+            }
+            writeln!(writer)?;
+        }
+        BlockStatsFormat::Json => {}
+        BlockStatsFormat::Csv => writeln!(writer, "number,{}", METRIC_NAMES.join(","))?,
+    }
+
+    let mut aggregator = BlockStatsAggregator::default();
+    let mut last_log = Instant::now();
+    for number in range.clone() {
+        let stats = block_stats(factory, number)?;
+        aggregator.record(&stats);
+
+        match format {
+            BlockStatsFormat::Table => {
+                write!(writer, "{:>12}", stats.number)?;
+                for (name, value) in METRIC_NAMES.iter().zip(stats.metrics()) {
+                    write!(writer, "{value:>width$}", width = name.len().max(12) + 2)?;
+                }
+                writeln!(writer)?;
+            }
+            BlockStatsFormat::Json => {
+                serde_json::to_writer(&mut writer, &stats)?;
+                writeln!(writer)?;
+            }
+            BlockStatsFormat::Csv => {
+                let values = stats.metrics().map(|value| value.to_string());
+                writeln!(writer, "{},{}", stats.number, values.join(","))?;
+            }
+        }
+
+        if last_log.elapsed() >= PROGRESS_LOG_INTERVAL {
+            info!(target: "reth::cli", block = number, to = range.end(), "Computing block stats");
+            last_log = Instant::now();
+        }
+    }
+
+    let summary = aggregator.summary();
+    match format {
+        BlockStatsFormat::Table => write!(writer, "\n{summary}")?,
+        BlockStatsFormat::Json => {
+            serde_json::to_writer(&mut writer, &serde_json::json!({ "summary": summary }))?;
+            writeln!(writer)?;
+        }
+        BlockStatsFormat::Csv => {}
+    }
+    writer.flush()?;
+
+    Ok(summary)
+}
+
+/// Aggregates [`BlockStats`] into a [`BlockStatsSummary`] with bounded memory.
+#[derive(Debug, Default)]
+pub struct BlockStatsAggregator {
+    blocks: u64,
+    metrics: [MetricAggregator; 7],
+}
+
+impl BlockStatsAggregator {
+    /// Records the statistics of a block.
+    pub fn record(&mut self, stats: &BlockStats) {
+        self.blocks += 1;
+        for (metric, value) in self.metrics.iter_mut().zip(stats.metrics()) {
+            metric.record(value);
+        }
+    }
+
+    /// Returns the summary of all recorded blocks.
+    pub fn summary(&self) -> BlockStatsSummary {
+        let [gas_used, tx_count, failed_tx_count, new_accounts, slots_added, slots_cleared, code] =
+            self.metrics.each_ref().map(MetricAggregator::summary);
+        BlockStatsSummary {
+            blocks: self.blocks,
+            gas_used,
+            tx_count,
+            failed_tx_count,
+            new_accounts,
+            storage_slots_added: slots_added,
+            storage_slots_cleared: slots_cleared,
+            new_code_bytes: code,
+        }
+    }
+}
+
+/// Number of mantissa bits of the histogram buckets, which bounds the relative error of the
+/// percentiles to `2^-MANTISSA_BITS`.
+const MANTISSA_BITS: u32 = 6;
+
+/// Number of buckets of the histogram: values below `2^MANTISSA_BITS` have a bucket each, larger
+/// values share a bucket per exponent and mantissa.
+const BUCKETS: usize = ((u64::BITS - MANTISSA_BITS + 1) << MANTISSA_BITS) as usize;
+
+/// Aggregates the values of a single metric.
+#[derive(Debug, Default)]
+struct MetricAggregator {
+    count: u64,
+    total: u64,
+    min: u64,
+    max: u64,
+    /// Log-linear histogram of the values, allocated on the first value.
+    buckets: Vec<u64>,
+}
+
+impl MetricAggregator {
+    fn record(&mut self, value: u64) {
+        if self.count == 0 {
+            self.min = value;
+            self.buckets = vec![0; BUCKETS];
+        }
+        self.count += 1;
+        self.total = self.total.saturating_add(value);
+        self.min = self.min.min(value);
+        self.max = self.max.max(value);
+        self.buckets[bucket_index(value)] += 1;
+    }
+
+    fn percentile(&self, percentile: u64) -> u64 {
+        let rank = (self.count * percentile).div_ceil(100).max(1);
+        let mut seen = 0;
+        for (index, count) in self.buckets.iter().enumerate() {
+            seen += count;
+            if seen >= rank {
+                return bucket_lower_bound(index).clamp(self.min, self.max)
+            }
+        }
+        self.max
+    }
+
+    fn summary(&self) -> MetricSummary {
+        if self.count == 0 {
+            return MetricSummary::default()
+        }
+        MetricSummary {
+            total: self.total,
+            min: self.min,
+            max: self.max,
+            p50: self.percentile(50),
+            p90: self.percentile(90),
+            p99: self.percentile(99),
+        }
+    }
+}
+
+/// Returns the index of the histogram bucket of the value.
+const fn bucket_index(value: u64) -> usize {
+    if value < 1 << MANTISSA_BITS {
+        return value as usize
+    }
+    let exponent = u64::BITS - 1 - value.leading_zeros();
+    let shift = exponent - MANTISSA_BITS;
+    let mantissa = (value >> shift) & ((1 << MANTISSA_BITS) - 1);
+    (((shift + 1) << MANTISSA_BITS) as u64 + mantissa) as usize
+}
+
+/// Returns the smallest value of the histogram bucket.
+const fn bucket_lower_bound(index: usize) -> u64 {
+    let index = index as u64;
+    if index < 1 << MANTISSA_BITS {
+        return index
+    }
+    let shift = (index >> MANTISSA_BITS) - 1;
+    let mantissa = index & ((1 << MANTISSA_BITS) - 1);
+    ((1 << MANTISSA_BITS) + mantissa) << shift
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{Address, Bytes, B256, U256};
+    use reth_db::tables;
+    use reth_db_api::{
+        models::{
+            storage_sharded_key::StorageShardedKey, AccountBeforeTx, BlockNumberAddress,
+            IntegerList, ShardedKey,
+        },
+        transaction::DbTxMut,
+    };
+    use reth_primitives::{Account, Bytecode, StorageEntry};
+    use reth_provider::{
+        test_utils::{MockNodeTypesWithDB, TestChainParams, TestDatadir},
+        DBProvider, PruneCheckpointWriter,
+    };
+    use reth_prune_types::{PruneCheckpoint, PruneMode};
+
+    const A: Address = Address::with_last_byte(1);
+    const B: Address = Address::with_last_byte(2);
+    const S1: B256 = B256::with_last_byte(1);
+    const S2: B256 = B256::with_last_byte(2);
+
+    fn contract() -> Bytecode {
+        Bytecode::new_raw(Bytes::from_static(&[0x60, 0x00, 0x60, 0x00]))
+    }
+
+    /// Writes the state changes of the first three blocks of the datadir:
+    ///  - block 1: contract `A` is created with 4 bytes of code and sets `S1` to 1.
+    ///  - block 2: `B` is created without code, `A` clears `S1` and sets `S2` to 2.
+    ///  - block 3: `A` sets `S2` to 3.
+    fn write_state_changes(factory: &ProviderFactory<MockNodeTypesWithDB>) {
+        let code = contract();
+        let code_hash = code.hash_slow();
+        let contract_account = Account { bytecode_hash: Some(code_hash), ..Default::default() };
+
+        let provider = factory.database_provider_rw().unwrap();
+        let tx = provider.tx_ref();
+
+        tx.put::<tables::Bytecodes>(code_hash, code).unwrap();
+        tx.put::<tables::PlainAccountState>(A, contract_account).unwrap();
+        tx.put::<tables::PlainAccountState>(B, Account::default()).unwrap();
+        tx.put::<tables::PlainStorageState>(A, StorageEntry { key: S2, value: U256::from(3) })
+            .unwrap();
+
+        for (block, address, info) in [(1, A, None), (2, B, None), (3, A, Some(contract_account))] {
+            tx.put::<tables::AccountChangeSets>(block, AccountBeforeTx { address, info }).unwrap();
+        }
+        for (block, key, value) in [(1, S1, 0), (2, S1, 1), (2, S2, 0), (3, S2, 2)] {
+            tx.put::<tables::StorageChangeSets>(
+                BlockNumberAddress((block, A)),
+                StorageEntry { key, value: U256::from(value) },
+            )
+            .unwrap();
+        }
+
+        for (address, blocks) in [(A, vec![1, 3]), (B, vec![2])] {
+            tx.put::<tables::AccountsHistory>(
+                ShardedKey::new(address, u64::MAX),
+                IntegerList::new(blocks).unwrap(),
+            )
+            .unwrap();
+        }
+        for (key, blocks) in [(S1, [1, 2]), (S2, [2, 3])] {
+            tx.put::<tables::StoragesHistory>(
+                StorageShardedKey::new(A, key, u64::MAX),
+                IntegerList::new(blocks).unwrap(),
+            )
+            .unwrap();
+        }
+
+        provider.commit().unwrap();
+    }
+
+    #[test]
+    fn stats_of_fixture_chain() {
+        let datadir =
+            TestDatadir::new(TestChainParams { blocks: 3, ..Default::default() }).unwrap();
+        let (blocks, receipts) = (datadir.blocks().to_vec(), datadir.receipts().to_vec());
+        let factory = datadir.into_provider_factory();
+        write_state_changes(&factory);
+
+        let mut output = Vec::new();
+        let summary =
+            write_block_stats(&factory, 1..=3, BlockStatsFormat::Json, &mut output).unwrap();
+        let lines = std::str::from_utf8(&output).unwrap().lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 4);
+        let stats = lines[..3]
+            .iter()
+            .map(|line| serde_json::from_str::<BlockStats>(line).unwrap())
+            .collect::<Vec<_>>();
+
+        // (new accounts, slots added, slots cleared, new code bytes) of blocks 1, 2 and 3.
+        let expected_state = [(1, 1, 0, 4), (1, 1, 1, 0), (0, 0, 0, 0)];
+        for (stats, expected) in stats.iter().zip(expected_state) {
+            let number = stats.number as usize;
+            let failed = receipts[number].iter().filter(|receipt| !receipt.success).count();
+            assert_eq!(
+                *stats,
+                BlockStats {
+                    number: number as u64,
+                    gas_used: blocks[number].header.gas_used,
+                    tx_count: blocks[number].body.transactions.len() as u64,
+                    failed_tx_count: failed as u64,
+                    new_accounts: expected.0,
+                    storage_slots_added: expected.1,
+                    storage_slots_cleared: expected.2,
+                    new_code_bytes: expected.3,
+                }
+            );
+        }
+
+        assert_eq!(summary.blocks, 3);
+        assert_eq!(summary.new_accounts.total, 2);
+        assert_eq!(summary.storage_slots_added.total, 2);
+        assert_eq!(summary.storage_slots_cleared.total, 1);
+        assert_eq!(
+            summary.new_code_bytes,
+            MetricSummary { total: 4, min: 0, max: 4, p50: 0, p90: 4, p99: 4 }
+        );
+        assert_eq!(
+            summary.gas_used.total,
+            blocks[1..].iter().map(|block| block.header.gas_used).sum::<u64>()
+        );
+        let json_summary: serde_json::Value = serde_json::from_str(lines[3]).unwrap();
+        assert_eq!(json_summary["summary"]["storage_slots_cleared"]["total"], 1);
+
+        let mut csv = Vec::new();
+        write_block_stats(&factory, 2..=2, BlockStatsFormat::Csv, &mut csv).unwrap();
+        assert_eq!(
+            std::str::from_utf8(&csv).unwrap().lines().collect::<Vec<_>>(),
+            [
+                format!("number,{}", METRIC_NAMES.join(",")),
+                format!(
+                    "2,{},{},{},1,1,1,0",
+                    stats[1].gas_used, stats[1].tx_count, stats[1].failed_tx_count
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn pruned_changesets() {
+        let datadir =
+            TestDatadir::new(TestChainParams { blocks: 3, ..Default::default() }).unwrap();
+        let factory = datadir.into_provider_factory();
+
+        let provider = factory.database_provider_rw().unwrap();
+        for segment in [PruneSegment::AccountHistory, PruneSegment::StorageHistory] {
+            provider
+                .save_prune_checkpoint(
+                    segment,
+                    PruneCheckpoint {
+                        block_number: Some(2),
+                        tx_number: None,
+                        prune_mode: PruneMode::Before(3),
+                    },
+                )
+                .unwrap();
+        }
+        provider.commit().unwrap();
+
+        let mut output = Vec::new();
+        let err = write_block_stats(&factory, 1..=3, BlockStatsFormat::Table, &mut output)
+            .unwrap_err()
+            .downcast::<PrunedSegmentsError>()
+            .unwrap();
+        assert!(output.is_empty());
+        assert_eq!(
+            err.segments,
+            [(PruneSegment::AccountHistory, 2), (PruneSegment::StorageHistory, 2)]
+        );
+        assert_eq!(
+            err.to_string(),
+            "data of blocks 1..=3 is pruned, missing segments: AccountHistory (pruned up to block 2), StorageHistory (pruned up to block 2)"
+        );
+
+        assert!(ensure_block_stats_available(&factory, &(3..=3)).is_ok());
+    }
+
+    #[test]
+    fn histogram_percentiles() {
+        let mut metric = MetricAggregator::default();
+        for value in 1..=1000 {
+            metric.record(value * 1_000);
+        }
+        let summary = metric.summary();
+        assert_eq!((summary.total, summary.min, summary.max), (500_500_000, 1_000, 1_000_000));
+        for (percentile, exact) in
+            [(summary.p50, 500_000), (summary.p90, 900_000), (summary.p99, 990_000)]
+        {
+            assert!(
+                percentile <= exact && exact - percentile <= exact / 64,
+                "{percentile} {exact}"
+            );
+        }
+
+        for value in [0, 1, 63, 64, 65, 1 << 20, u64::MAX] {
+            let lower_bound = bucket_lower_bound(bucket_index(value));
+            assert!(lower_bound <= value && value - lower_bound <= value / 64);
+        }
+        assert_eq!(bucket_index(u64::MAX), BUCKETS - 1);
+    }
+}
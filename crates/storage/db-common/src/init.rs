@@ -1,13 +1,21 @@
 //! Reth genesis initialization utility functions.
 
-use alloy_consensus::BlockHeader;
+use crate::{
+    migration::{set_schema_version, SCHEMA_VERSION},
+    state_export::ExportedAccount,
+};
+use alloy_consensus::{constants::KECCAK_EMPTY, BlockHeader};
 use alloy_genesis::GenesisAccount;
-use alloy_primitives::{Address, B256, U256};
+use alloy_primitives::{keccak256, Address, BlockNumber, B256, U256};
 use reth_chainspec::EthChainSpec;
 use reth_codecs::Compact;
 use reth_config::config::EtlConfig;
 use reth_db::tables;
-use reth_db_api::{transaction::DbTxMut, DatabaseError};
+use reth_db_api::{
+    cursor::DbCursorRO,
+    transaction::{DbTx, DbTxMut},
+    DatabaseError,
+};
 use reth_etl::Collector;
 use reth_primitives::{
     Account, Bytecode, GotExpected, NodePrimitives, Receipts, StaticFileSegment, StorageEntry,
@@ -22,8 +30,19 @@ use reth_provider::{
 use reth_stages_types::{StageCheckpoint, StageId};
 use reth_trie::{IntermediateStateRootState, StateRoot as StateRootComputer, StateRootProgress};
 use reth_trie_db::DatabaseStateRoot;
-use serde::{Deserialize, Serialize};
-use std::{collections::HashMap, io::BufRead};
+use serde::{
+    de::{self, MapAccess, Visitor},
+    Deserialize, Deserializer, Serialize,
+};
+use std::{
+    collections::HashMap,
+    fmt,
+    io::{self, BufRead, Read},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
 use tracing::{debug, error, info, trace};
 
 /// Default soft limit for number of bytes to read from state dump file, before inserting into
@@ -64,6 +83,9 @@ pub enum InitStorageError {
     /// State root doesn't match the expected one.
     #[error("state root mismatch: {_0}")]
     StateRootMismatch(GotExpected<B256>),
+    /// State import was interrupted.
+    #[error("state import was interrupted, run it again to resume")]
+    StateImportInterrupted,
 }
 
 impl From<DatabaseError> for InitStorageError {
@@ -142,6 +164,9 @@ where
         provider_rw.save_stage_checkpoint(stage, Default::default())?;
     }
 
+    // a new database starts at the latest schema version, with no migrations to apply
+    set_schema_version(&provider_rw, SCHEMA_VERSION)?;
+
     let static_file_provider = provider_rw.static_file_provider();
     // Static file segments start empty, so we need to initialize the genesis block.
     let segment = StaticFileSegment::Receipts;
@@ -348,71 +373,153 @@ where
     Ok(())
 }
 
+/// Default number of accounts that are written and committed at a time when importing a state
+/// dump.
+pub const DEFAULT_STATE_IMPORT_BATCH_SIZE: usize = AVERAGE_COUNT_ACCOUNTS_PER_GB_STATE_DUMP;
+
+/// Configuration of a state dump import, see [`init_from_state_dump`].
+#[derive(Debug, Clone)]
+pub struct StateImportConfig {
+    /// ETL configuration, the accounts of the dump are sorted by address before being written.
+    pub etl: EtlConfig,
+    /// Number of accounts that are written and committed at a time.
+    pub batch_size: usize,
+    /// Interrupts the import once set, after the batch that's being written is committed.
+    pub interrupt: Arc<AtomicBool>,
+}
+
+impl StateImportConfig {
+    /// Creates a new [`StateImportConfig`] with the [`DEFAULT_STATE_IMPORT_BATCH_SIZE`].
+    pub fn new(etl: EtlConfig) -> Self {
+        Self { etl, batch_size: DEFAULT_STATE_IMPORT_BATCH_SIZE, interrupt: Default::default() }
+    }
+
+    /// Sets the number of accounts that are written and committed at a time.
+    pub const fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size;
+        self
+    }
+
+    /// Sets the flag that interrupts the import.
+    pub fn with_interrupt(mut self, interrupt: Arc<AtomicBool>) -> Self {
+        self.interrupt = interrupt;
+        self
+    }
+
+    /// Returns an error if the import was interrupted.
+    fn ensure_not_interrupted(&self) -> Result<(), InitStorageError> {
+        if self.interrupt.load(Ordering::Relaxed) {
+            return Err(InitStorageError::StateImportInterrupted)
+        }
+        Ok(())
+    }
+}
+
+/// Returns the highest address of the accounts that were imported at the given block, by an
+/// interrupted import.
+///
+/// Accounts are imported in ascending order of their address, together with their history at the
+/// block.
+fn last_imported_address<Provider>(
+    provider: &Provider,
+    block: BlockNumber,
+) -> ProviderResult<Option<Address>>
+where
+    Provider: DBProvider,
+{
+    let mut cursor = provider.tx_ref().cursor_read::<tables::AccountsHistory>()?;
+    let mut entry = cursor.last()?;
+    while let Some((key, blocks)) = entry {
+        if blocks.0.contains(block) {
+            return Ok(Some(key.key))
+        }
+        entry = cursor.prev()?;
+    }
+    Ok(None)
+}
+
 /// Reads account state from a [`BufRead`] reader and initializes it at the highest block that can
 /// be found on database.
 ///
 /// It's similar to [`init_genesis`] but supports importing state too big to fit in memory, and can
 /// be set to the highest block present. One practical usecase is to import OP mainnet state at
 /// bedrock transition block.
-pub fn init_from_state_dump<Provider>(
-    mut reader: impl BufRead,
-    provider_rw: &Provider,
-    etl_config: EtlConfig,
+///
+/// The state can be either:
+///  - A JSONL dump, with one account per line, either with its `code` and `storage` like in a
+///    genesis alloc or as exported by `reth db export-state --storage`. The first line can be `{
+///    "root": <state-root> }`, which is checked against the state root of the block.
+///  - A genesis alloc JSON object, mapping addresses to accounts.
+///
+/// Accounts are streamed into an ETL collector, and written and committed in batches of
+/// [`StateImportConfig::batch_size`] in ascending order of their address. An interrupted import is
+/// resumed by running it again, after the highest address with history at the block. Once all
+/// accounts are written, the state root is computed, committing the trie as it's built, and
+/// verified against the state root of the block.
+pub fn init_from_state_dump<PF>(
+    reader: impl BufRead,
+    factory: &PF,
+    config: StateImportConfig,
 ) -> eyre::Result<B256>
 where
-    Provider: StaticFileProviderFactory
-        + DBProvider<Tx: DbTxMut>
-        + BlockNumReader
-        + BlockHashReader
-        + ChainSpecProvider
-        + StageCheckpointWriter
-        + HistoryWriter
-        + HeaderProvider
-        + HashingWriter
-        + TrieWriter
-        + StateWriter
-        + AsRef<Provider>,
+    PF: DatabaseProviderFactory<
+        ProviderRW: StaticFileProviderFactory
+                        + BlockNumReader
+                        + BlockHashReader
+                        + ChainSpecProvider
+                        + StageCheckpointWriter
+                        + HistoryWriter
+                        + HeaderProvider
+                        + HashingWriter
+                        + TrieWriter
+                        + StateWriter
+                        + AsRef<PF::ProviderRW>,
+    >,
 {
-    if etl_config.file_size == 0 {
+    if config.etl.file_size == 0 {
         return Err(eyre::eyre!("ETL file size cannot be zero"))
     }
+    if config.batch_size == 0 {
+        return Err(eyre::eyre!("Batch size cannot be zero"))
+    }
 
+    let provider_rw = factory.database_provider_rw()?;
     let block = provider_rw.last_block_number()?;
     let hash = provider_rw.block_hash(block)?.unwrap();
     let expected_state_root = provider_rw
         .header_by_number(block)?
         .ok_or_else(|| ProviderError::HeaderNotFound(block.into()))?
         .state_root();
+    let chain = provider_rw.chain_spec().chain();
+    let last_address = last_imported_address(&provider_rw, block)?;
+    drop(provider_rw);
 
-    // first line can be state root
-    let dump_state_root = parse_state_root(&mut reader)?;
-    if expected_state_root != dump_state_root {
-        error!(target: "reth::cli",
-            ?dump_state_root,
-            ?expected_state_root,
-            "State root from state dump does not match state root in current header."
-        );
-        return Err(InitStorageError::StateRootMismatch(GotExpected {
-            got: dump_state_root,
-            expected: expected_state_root,
-        })
-        .into())
+    if let Some(last_address) = last_address {
+        info!(target: "reth::cli", block, %last_address, "Resuming state import");
+    }
+    debug!(target: "reth::cli", block, %chain, "Initializing state at block");
+
+    let (dump_state_root, collector) = parse_state_dump(reader, config.etl.clone())?;
+    if let Some(dump_state_root) = dump_state_root {
+        if expected_state_root != dump_state_root {
+            error!(target: "reth::cli",
+                ?dump_state_root,
+                ?expected_state_root,
+                "State root from state dump does not match state root in current header."
+            );
+            return Err(InitStorageError::StateRootMismatch(GotExpected {
+                got: dump_state_root,
+                expected: expected_state_root,
+            })
+            .into())
+        }
     }
-
-    debug!(target: "reth::cli",
-        block,
-        chain=%provider_rw.chain_spec().chain(),
-        "Initializing state at block"
-    );
-
-    // remaining lines are accounts
-    let collector = parse_accounts(&mut reader, etl_config)?;
 
     // write state to db
-    dump_state(collector, provider_rw, block)?;
+    dump_state(collector, factory, block, last_address, &config)?;
 
-    // compute and compare state root. this advances the stage checkpoints.
-    let computed_state_root = compute_state_root(provider_rw)?;
+    // compute and compare state root
+    let computed_state_root = compute_state_root(factory, &config)?;
     if computed_state_root == expected_state_root {
         info!(target: "reth::cli",
             ?computed_state_root,
@@ -433,135 +540,231 @@ where
     }
 
     // insert sync stages for stages that require state
+    let provider_rw = factory.database_provider_rw()?;
     for stage in StageId::STATE_REQUIRED {
         provider_rw.save_stage_checkpoint(stage, StageCheckpoint::new(block))?;
     }
+    provider_rw.commit()?;
 
     Ok(hash)
 }
 
-/// Parses and returns expected state root.
-fn parse_state_root(reader: &mut impl BufRead) -> eyre::Result<B256> {
+/// Parses a state dump into a [`Collector`], sorting its accounts by address.
+///
+/// Returns the state root of the dump, if it has one.
+fn parse_state_dump(
+    mut reader: impl BufRead,
+    etl_config: EtlConfig,
+) -> eyre::Result<(Option<B256>, Collector<Address, GenesisAccount>)> {
+    let mut collector =
+        Collector::new(etl_config.file_size, etl_config.dir).with_metrics_label("GenesisAccounts");
+
+    // a genesis alloc object is told apart from a JSONL dump by its first key, which is an
+    // address, since its first line can be the entire alloc
+    let mut prefix = Vec::new();
+    let is_alloc = match read_non_whitespace(&mut reader, &mut prefix)? {
+        None => return Ok((None, collector)),
+        Some(b'{') => match read_non_whitespace(&mut reader, &mut prefix)? {
+            Some(b'"') => {
+                let key_start = prefix.len();
+                reader.read_until(b'"', &mut prefix)?;
+                std::str::from_utf8(&prefix[key_start..])
+                    .is_ok_and(|key| key.trim_end_matches('"').parse::<Address>().is_ok())
+            }
+            // empty object
+            _ => true,
+        },
+        Some(_) => return Err(eyre::eyre!("State dump must consist of JSON objects")),
+    };
+    let mut reader = io::Cursor::new(prefix).chain(reader);
+
+    if is_alloc {
+        let mut deserializer = serde_json::Deserializer::from_reader(reader);
+        deserializer.deserialize_map(AllocVisitor { collector: &mut collector })?;
+        deserializer.end()?;
+        return Ok((None, collector))
+    }
+
+    // the first line is either the state root or an account
     let mut line = String::new();
     reader.read_line(&mut line)?;
 
-    let expected_state_root = serde_json::from_str::<StateRoot>(&line)?.root;
-    trace!(target: "reth::cli",
-        root=%expected_state_root,
-        "Read state root from file"
-    );
-    Ok(expected_state_root)
-}
+    let mut dump_state_root = None;
+    if let Ok(StateRoot { root }) = serde_json::from_str(&line) {
+        trace!(target: "reth::cli", %root, "Read state root from file");
+        dump_state_root = Some(root);
+        line.clear();
+    }
 
-/// Parses accounts and pushes them to a [`Collector`].
-fn parse_accounts(
-    mut reader: impl BufRead,
-    etl_config: EtlConfig,
-) -> Result<Collector<Address, GenesisAccount>, eyre::Error> {
-    let mut line = String::new();
-    let mut collector = Collector::new(etl_config.file_size, etl_config.dir);
+    loop {
+        if !line.trim().is_empty() {
+            let (address, account) = serde_json::from_str::<DumpAccount>(&line)?.into_parts()?;
+            collector.insert(address, account)?;
+            log_parsed_accounts(&collector);
+        }
 
-    while let Ok(n) = reader.read_line(&mut line) {
-        if n == 0 {
+        line.clear();
+        if reader.read_line(&mut line)? == 0 {
             break
         }
+    }
 
-        let GenesisAccountWithAddress { genesis_account, address } = serde_json::from_str(&line)?;
-        collector.insert(address, genesis_account)?;
+    Ok((dump_state_root, collector))
+}
 
-        if !collector.is_empty() && collector.len() % AVERAGE_COUNT_ACCOUNTS_PER_GB_STATE_DUMP == 0
-        {
-            info!(target: "reth::cli",
-                parsed_new_accounts=collector.len(),
-            );
+/// Reads bytes into the buffer up to and including the first non-whitespace byte, which is
+/// returned.
+fn read_non_whitespace(reader: &mut impl BufRead, buf: &mut Vec<u8>) -> io::Result<Option<u8>> {
+    let mut byte = [0];
+    loop {
+        if reader.read(&mut byte)? == 0 {
+            return Ok(None)
+        }
+        buf.push(byte[0]);
+        if !byte[0].is_ascii_whitespace() {
+            return Ok(Some(byte[0]))
         }
+    }
+}
 
-        line.clear();
+/// Logs the number of parsed accounts every [`AVERAGE_COUNT_ACCOUNTS_PER_GB_STATE_DUMP`]
+/// accounts.
+fn log_parsed_accounts(collector: &Collector<Address, GenesisAccount>) {
+    if !collector.is_empty() && collector.len() % AVERAGE_COUNT_ACCOUNTS_PER_GB_STATE_DUMP == 0 {
+        info!(target: "reth::cli",
+            parsed_new_accounts=collector.len(),
+        );
     }
+}
 
-    Ok(collector)
+/// Streams the accounts of a genesis alloc JSON object into a [`Collector`], one at a time.
+struct AllocVisitor<'a> {
+    collector: &'a mut Collector<Address, GenesisAccount>,
 }
 
-/// Takes a [`Collector`] and processes all accounts.
-fn dump_state<Provider>(
+impl<'de> Visitor<'de> for AllocVisitor<'_> {
+    type Value = ();
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("a genesis alloc object")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        while let Some((address, account)) = map.next_entry::<Address, GenesisAccount>()? {
+            self.collector.insert(address, account).map_err(de::Error::custom)?;
+            log_parsed_accounts(self.collector);
+        }
+        Ok(())
+    }
+}
+
+/// Takes a [`Collector`] and writes its accounts in batches, committing each batch.
+///
+/// Accounts up to the last address that was written by an interrupted import are skipped.
+fn dump_state<PF>(
     mut collector: Collector<Address, GenesisAccount>,
-    provider_rw: &Provider,
-    block: u64,
+    factory: &PF,
+    block: BlockNumber,
+    last_address: Option<Address>,
+    config: &StateImportConfig,
 ) -> Result<(), eyre::Error>
 where
-    Provider: StaticFileProviderFactory
-        + DBProvider<Tx: DbTxMut>
-        + HeaderProvider
-        + HashingWriter
-        + HistoryWriter
-        + StateWriter
-        + AsRef<Provider>,
+    PF: DatabaseProviderFactory<
+        ProviderRW: StaticFileProviderFactory
+                        + HeaderProvider
+                        + HashingWriter
+                        + HistoryWriter
+                        + StateWriter
+                        + AsRef<PF::ProviderRW>,
+    >,
 {
-    let accounts_len = collector.len();
-    let mut accounts = Vec::with_capacity(AVERAGE_COUNT_ACCOUNTS_PER_GB_STATE_DUMP);
+    let mut accounts = Vec::with_capacity(config.batch_size.min(collector.len()));
     let mut total_inserted_accounts = 0;
 
-    for (index, entry) in collector.iter()?.enumerate() {
+    let mut write_accounts = |accounts: &mut Vec<(Address, GenesisAccount)>| -> eyre::Result<()> {
+        total_inserted_accounts += accounts.len();
+
+        info!(target: "reth::cli",
+            total_inserted_accounts,
+            "Writing accounts to db"
+        );
+
+        let provider_rw = factory.database_provider_rw()?;
+
+        insert_genesis_hashes(
+            &provider_rw,
+            accounts.iter().map(|(address, account)| (address, account)),
+        )?;
+
+        insert_history(
+            &provider_rw,
+            accounts.iter().map(|(address, account)| (address, account)),
+            block,
+        )?;
+
+        // block is already written to static files
+        insert_state(
+            &provider_rw,
+            accounts.iter().map(|(address, account)| (address, account)),
+            block,
+        )?;
+
+        provider_rw.commit()?;
+
+        accounts.clear();
+        config.ensure_not_interrupted()?;
+        Ok(())
+    };
+
+    for entry in collector.iter()? {
         let (address, account) = entry?;
         let (address, _) = Address::from_compact(address.as_slice(), address.len());
+        if last_address.is_some_and(|last_address| address <= last_address) {
+            continue
+        }
         let (account, _) = GenesisAccount::from_compact(account.as_slice(), account.len());
 
         accounts.push((address, account));
 
-        if (index > 0 && index % AVERAGE_COUNT_ACCOUNTS_PER_GB_STATE_DUMP == 0) ||
-            index == accounts_len - 1
-        {
-            total_inserted_accounts += accounts.len();
-
-            info!(target: "reth::cli",
-                total_inserted_accounts,
-                "Writing accounts to db"
-            );
-
-            // use transaction to insert genesis header
-            insert_genesis_hashes(
-                provider_rw,
-                accounts.iter().map(|(address, account)| (address, account)),
-            )?;
-
-            insert_history(
-                provider_rw,
-                accounts.iter().map(|(address, account)| (address, account)),
-                block,
-            )?;
-
-            // block is already written to static files
-            insert_state(
-                provider_rw,
-                accounts.iter().map(|(address, account)| (address, account)),
-                block,
-            )?;
-
-            accounts.clear();
+        if accounts.len() == config.batch_size {
+            write_accounts(&mut accounts)?;
         }
     }
+    if !accounts.is_empty() {
+        write_accounts(&mut accounts)?;
+    }
+
     Ok(())
 }
 
 /// Computes the state root (from scratch) based on the accounts and storages present in the
 /// database.
-fn compute_state_root<Provider>(provider: &Provider) -> eyre::Result<B256>
+///
+/// The trie is committed as it's built. It's rebuilt from scratch every time, since the trie left
+/// by an interrupted computation is incomplete.
+fn compute_state_root<PF>(factory: &PF, config: &StateImportConfig) -> eyre::Result<B256>
 where
-    Provider: DBProvider<Tx: DbTxMut> + TrieWriter,
+    PF: DatabaseProviderFactory<ProviderRW: TrieWriter>,
 {
     trace!(target: "reth::cli", "Computing state root");
 
-    let tx = provider.tx_ref();
+    let provider_rw = factory.database_provider_rw()?;
+    provider_rw.tx_ref().clear::<tables::AccountsTrie>()?;
+    provider_rw.tx_ref().clear::<tables::StoragesTrie>()?;
+    provider_rw.commit()?;
+
     let mut intermediate_state: Option<IntermediateStateRootState> = None;
     let mut total_flushed_updates = 0;
 
     loop {
-        match StateRootComputer::from_tx(tx)
+        let provider_rw = factory.database_provider_rw()?;
+        match StateRootComputer::from_tx(provider_rw.tx_ref())
             .with_intermediate_state(intermediate_state)
             .root_with_progress()?
         {
             StateRootProgress::Progress(state, _, updates) => {
-                let updated_len = provider.write_trie_updates(&updates)?;
+                let updated_len = provider_rw.write_trie_updates(&updates)?;
+                provider_rw.commit()?;
                 total_flushed_updates += updated_len;
 
                 trace!(target: "reth::cli",
@@ -579,9 +782,12 @@ where
                         "Flushing trie updates"
                     );
                 }
+
+                config.ensure_not_interrupted()?;
             }
             StateRootProgress::Complete(root, _, updates) => {
-                let updated_len = provider.write_trie_updates(&updates)?;
+                let updated_len = provider_rw.write_trie_updates(&updates)?;
+                provider_rw.commit()?;
                 total_flushed_updates += updated_len;
 
                 trace!(target: "reth::cli",
@@ -614,6 +820,52 @@ struct GenesisAccountWithAddress {
     address: Address,
 }
 
+/// An account of a JSONL state dump.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum DumpAccount {
+    /// An account exported by [`export_state`](crate::state_export::export_state).
+    Exported(ExportedAccount),
+    /// An account with its code and storage, like in a genesis alloc.
+    Genesis(GenesisAccountWithAddress),
+}
+
+impl DumpAccount {
+    /// Returns the address of the account and the account.
+    ///
+    /// Exported accounts must include their code and storage.
+    fn into_parts(self) -> eyre::Result<(Address, GenesisAccount)> {
+        match self {
+            Self::Exported(account) => {
+                let address = account.address;
+                let storage = account.storage.ok_or_else(|| {
+                    eyre::eyre!("account {address} has no storage, export the state with --storage")
+                })?;
+                if account.code_hash != KECCAK_EMPTY &&
+                    account.code.as_ref().map(keccak256) != Some(account.code_hash)
+                {
+                    return Err(eyre::eyre!(
+                        "account {address} has no code with hash {}",
+                        account.code_hash
+                    ))
+                }
+
+                let genesis_account = GenesisAccount::default()
+                    .with_nonce(Some(account.nonce))
+                    .with_balance(account.balance)
+                    .with_code(account.code)
+                    .with_storage(Some(
+                        storage.into_iter().map(|(slot, value)| (slot, value.into())).collect(),
+                    ));
+                Ok((address, genesis_account))
+            }
+            Self::Genesis(GenesisAccountWithAddress { genesis_account, address }) => {
+                Ok((address, genesis_account))
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -621,6 +873,7 @@ mod tests {
         HOLESKY_GENESIS_HASH, MAINNET_GENESIS_HASH, SEPOLIA_GENESIS_HASH,
     };
     use alloy_genesis::Genesis;
+    use alloy_primitives::Bytes;
     use reth_chainspec::{Chain, ChainSpec, HOLESKY, MAINNET, SEPOLIA};
     use reth_db::DatabaseEnv;
     use reth_db_api::{
@@ -634,7 +887,7 @@ mod tests {
         test_utils::{create_test_provider_factory_with_chain_spec, MockNodeTypesWithDB},
         ProviderFactory,
     };
-    use std::{collections::BTreeMap, sync::Arc};
+    use std::collections::BTreeMap;
 
     fn collect_table_entries<DB, T>(
         tx: &<DB as Database>::TX,
@@ -750,4 +1003,196 @@ mod tests {
             )],
         );
     }
+
+    /// Returns the `i`th synthetic account, every third of them with code and storage.
+    fn synthetic_account(i: u64) -> (Address, GenesisAccount) {
+        let mut account = GenesisAccount::default().with_nonce(Some(i)).with_balance(U256::from(i));
+        if i % 3 == 0 {
+            account = account
+                .with_code(Some(Bytes::from_static(&[0x60, 0x00, 0x60, 0x00])))
+                .with_storage(Some(BTreeMap::from([
+                    (B256::with_last_byte(1), B256::from(U256::from(i))),
+                    (B256::with_last_byte(2), B256::from(U256::from(i * 2))),
+                ])));
+        }
+        (Address::from_word(keccak256(i.to_be_bytes())), account)
+    }
+
+    /// Returns an alloc of `len` synthetic accounts.
+    fn synthetic_alloc(len: u64) -> BTreeMap<Address, GenesisAccount> {
+        (1..=len).map(synthetic_account).collect()
+    }
+
+    /// Returns a provider factory with only the genesis header of a chain with the given alloc,
+    /// so that the alloc can be imported as a state dump.
+    fn state_import_factory(
+        alloc: &BTreeMap<Address, GenesisAccount>,
+    ) -> ProviderFactory<MockNodeTypesWithDB> {
+        let chain_spec = Arc::new(ChainSpec {
+            chain: Chain::from_id(1),
+            genesis: Genesis { alloc: alloc.clone(), ..Default::default() },
+            ..Default::default()
+        });
+        let factory = create_test_provider_factory_with_chain_spec(chain_spec.clone());
+
+        let provider_rw = factory.database_provider_rw().unwrap();
+        insert_genesis_header(&provider_rw, &chain_spec).unwrap();
+        provider_rw.static_file_provider().commit().unwrap();
+        provider_rw.commit().unwrap();
+
+        factory
+    }
+
+    fn state_import_config(batch_size: usize) -> StateImportConfig {
+        StateImportConfig::new(EtlConfig::default()).with_batch_size(batch_size)
+    }
+
+    fn assert_state_imported(
+        factory: &ProviderFactory<MockNodeTypesWithDB>,
+        alloc: &BTreeMap<Address, GenesisAccount>,
+    ) {
+        let provider = factory.provider().unwrap();
+        let tx = provider.tx_ref();
+
+        let accounts =
+            collect_table_entries::<Arc<DatabaseEnv>, tables::PlainAccountState>(tx).unwrap();
+        assert_eq!(accounts.len(), alloc.len());
+        for (address, account) in accounts {
+            assert_eq!(account.balance, alloc[&address].balance);
+        }
+        assert_eq!(
+            collect_table_entries::<Arc<DatabaseEnv>, tables::PlainStorageState>(tx).unwrap().len(),
+            alloc.values().filter_map(|account| account.storage.as_ref()).map(BTreeMap::len).sum()
+        );
+
+        for stage in StageId::STATE_REQUIRED {
+            assert_eq!(
+                provider.get_stage_checkpoint(stage).unwrap(),
+                Some(StageCheckpoint::new(0))
+            );
+        }
+    }
+
+    #[test]
+    fn import_state_dump_formats() {
+        let alloc = synthetic_alloc(20);
+        let root = state_import_factory(&alloc).chain_spec().genesis_header().state_root;
+
+        // genesis alloc
+        let genesis_alloc = serde_json::to_vec_pretty(&alloc).unwrap();
+
+        // JSONL with genesis accounts
+        let mut genesis_jsonl = serde_json::to_string(&StateRoot { root }).unwrap();
+        for (address, account) in &alloc {
+            let account =
+                GenesisAccountWithAddress { genesis_account: account.clone(), address: *address };
+            genesis_jsonl.push('\n');
+            genesis_jsonl.push_str(&serde_json::to_string(&account).unwrap());
+        }
+
+        // JSONL as exported, without a state root
+        let mut exported_jsonl = String::new();
+        for (address, account) in &alloc {
+            let account = ExportedAccount {
+                address: *address,
+                balance: account.balance,
+                nonce: account.nonce.unwrap_or_default(),
+                code_hash: account.code.as_ref().map_or(KECCAK_EMPTY, keccak256),
+                storage_root: B256::ZERO,
+                storage: Some(
+                    account
+                        .storage
+                        .iter()
+                        .flatten()
+                        .map(|(slot, value)| (*slot, (*value).into()))
+                        .collect(),
+                ),
+                code: account.code.clone(),
+            };
+            exported_jsonl.push_str(&serde_json::to_string(&account).unwrap());
+            exported_jsonl.push('\n');
+        }
+
+        for dump in [genesis_alloc, genesis_jsonl.into_bytes(), exported_jsonl.into_bytes()] {
+            let factory = state_import_factory(&alloc);
+            let hash =
+                init_from_state_dump(dump.as_slice(), &factory, state_import_config(7)).unwrap();
+            assert_eq!(hash, factory.chain_spec().genesis_hash());
+            assert_state_imported(&factory, &alloc);
+        }
+    }
+
+    #[test]
+    fn reject_state_dump_with_wrong_root() {
+        let alloc = synthetic_alloc(5);
+        let factory = state_import_factory(&alloc);
+
+        let mut alloc_with_other_balance = alloc.clone();
+        alloc_with_other_balance.values_mut().next().unwrap().balance = U256::MAX;
+        let dump = serde_json::to_vec(&alloc_with_other_balance).unwrap();
+
+        let err = init_from_state_dump(dump.as_slice(), &factory, state_import_config(2))
+            .unwrap_err()
+            .downcast::<InitStorageError>()
+            .unwrap();
+        assert!(matches!(err, InitStorageError::StateRootMismatch(_)));
+    }
+
+    #[test]
+    fn resume_interrupted_state_import() {
+        let alloc = synthetic_alloc(20);
+        let factory = state_import_factory(&alloc);
+        let dump = serde_json::to_vec(&alloc).unwrap();
+
+        // interrupted after the first batch is committed
+        let config = state_import_config(7).with_interrupt(Arc::new(AtomicBool::new(true)));
+        let err = init_from_state_dump(dump.as_slice(), &factory, config)
+            .unwrap_err()
+            .downcast::<InitStorageError>()
+            .unwrap();
+        assert_eq!(err, InitStorageError::StateImportInterrupted);
+
+        let provider = factory.provider().unwrap();
+        assert_eq!(last_imported_address(&provider, 0).unwrap(), alloc.keys().nth(6).copied());
+        assert_eq!(
+            collect_table_entries::<Arc<DatabaseEnv>, tables::PlainAccountState>(provider.tx_ref())
+                .unwrap()
+                .len(),
+            7
+        );
+        drop(provider);
+
+        // resumed, skipping the imported accounts
+        let hash = init_from_state_dump(dump.as_slice(), &factory, state_import_config(7)).unwrap();
+        assert_eq!(hash, factory.chain_spec().genesis_hash());
+        assert_state_imported(&factory, &alloc);
+    }
+
+    #[test]
+    fn detect_state_dump_format() {
+        let alloc = synthetic_alloc(3);
+        let factory = state_import_factory(&alloc);
+
+        // a compact alloc is a single line, which isn't parsed as a JSONL account
+        let dump = serde_json::to_vec(&alloc).unwrap();
+        assert!(!dump.contains(&b'\n'));
+        let (root, collector) = parse_state_dump(dump.as_slice(), EtlConfig::default()).unwrap();
+        assert_eq!(root, None);
+        assert_eq!(collector.len(), alloc.len());
+
+        // leading whitespace is skipped
+        let mut dump = b"\n  ".to_vec();
+        dump.extend(serde_json::to_vec_pretty(&alloc).unwrap());
+        let (_, collector) = parse_state_dump(dump.as_slice(), EtlConfig::default()).unwrap();
+        assert_eq!(collector.len(), alloc.len());
+
+        let root = factory.chain_spec().genesis_header().state_root;
+        let dump = format!("  {}\n", serde_json::to_string(&StateRoot { root }).unwrap());
+        let (dump_root, collector) =
+            parse_state_dump(dump.as_bytes(), EtlConfig::default()).unwrap();
+        assert_eq!(dump_root, Some(root));
+        assert!(collector.is_empty());
+
+        assert!(parse_state_dump(b"[]".as_slice(), EtlConfig::default()).is_err());
+    }
 }
@@ -8,7 +8,10 @@
 #![cfg_attr(not(test), warn(unused_crate_dependencies))]
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
 
+pub mod block_stats;
 pub mod init;
+pub mod migration;
+pub mod state_export;
 
 mod db_tool;
 pub use db_tool::*;
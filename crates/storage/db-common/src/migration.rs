@@ -0,0 +1,865 @@
+//! Versioned migrations of the database schema.
+//!
+//! The schema version of a database is the id of the last [`Migration`] applied to it, stored in
+//! the [`SchemaMigrations`](tables::SchemaMigrations) table. Databases created by this binary start
+//! at [`SCHEMA_VERSION`], while databases that predate the schema registry start at version `0`.
+
+use reth_chainspec::ChainSpecProvider;
+use reth_config::{config::EtlConfig, Config};
+use reth_db::{
+    mdbx,
+    tables::{self, TableViewer, Tables},
+    RawDupSort, RawKey, RawTable, RawValue,
+};
+use reth_db_api::{
+    cursor::{DbCursorRO, DbCursorRW, DbDupCursorRW},
+    table::{DupSort, Table},
+    transaction::{DbTx, DbTxMut},
+    DatabaseError,
+};
+use reth_etl::Collector;
+use reth_provider::{
+    errors::provider::{ProviderError, ProviderResult},
+    providers::{ConsistencyCheck, ConsistencyReport, RepairAction},
+    BlockReader, DBProvider, DatabaseProviderFactory, PruneCheckpointReader, PruneCheckpointWriter,
+    StageCheckpointReader, StaticFileProviderFactory,
+};
+use std::{
+    cell::{Cell, RefCell},
+    fmt::Debug,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+use tracing::{debug, info, warn};
+
+/// The schema version of databases created by this binary, which is the id of the latest built-in
+/// [`Migration`].
+pub const SCHEMA_VERSION: u64 = 2;
+
+/// Database migration error type.
+#[derive(Debug, thiserror::Error)]
+pub enum MigrationError {
+    /// The database was migrated by a newer binary.
+    #[error(
+        "database schema version (v{version}) is newer than the latest version supported by this \
+         binary (v{latest}), please upgrade"
+    )]
+    DatabaseTooNew {
+        /// The schema version of the database.
+        version: u64,
+        /// The latest schema version known to the binary.
+        latest: u64,
+    },
+    /// Provider error.
+    #[error(transparent)]
+    Provider(#[from] reth_provider::ProviderError),
+}
+
+impl From<DatabaseError> for MigrationError {
+    fn from(error: DatabaseError) -> Self {
+        Self::Provider(error.into())
+    }
+}
+
+/// A migration of the database schema.
+///
+/// Every migration is applied in its own database transaction, together with the update of the
+/// schema version, so an interrupted migration is applied again from scratch on the next run.
+pub trait Migration<PF: DatabaseProviderFactory>: Debug + Send + Sync {
+    /// Returns the id of the migration, which is the schema version of the database once it's
+    /// applied.
+    fn id(&self) -> u64;
+
+    /// Returns a short description of the migration.
+    fn description(&self) -> &'static str;
+
+    /// Returns an estimate of the number of entries the migration has to process.
+    fn estimated_work(&self, provider: &PF::Provider) -> ProviderResult<u64>;
+
+    /// Applies the migration, reporting the number of processed entries to `progress`.
+    fn up(&self, provider: &PF::ProviderRW, progress: &mut dyn FnMut(u64)) -> ProviderResult<()>;
+
+    /// Returns `true` if the migration is too expensive to be applied on startup, e.g. because it
+    /// rewrites entire tables, so it's only applied by `reth db migrate`.
+    ///
+    /// The node has to support the database schema from before the migration until then.
+    fn is_manual(&self) -> bool {
+        false
+    }
+}
+
+/// A migration that is not applied to the database yet.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PendingMigration {
+    /// The id of the migration.
+    pub id: u64,
+    /// The description of the migration.
+    pub description: &'static str,
+    /// The estimated number of entries the migration has to process.
+    pub estimated_work: u64,
+}
+
+/// Events emitted while applying [`Migrations`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MigrationEvent {
+    /// A migration was started.
+    Started(PendingMigration),
+    /// A migration made progress.
+    Progress {
+        /// The id of the migration.
+        id: u64,
+        /// The number of entries processed so far.
+        processed: u64,
+        /// The estimated number of entries the migration has to process.
+        estimated_work: u64,
+    },
+    /// A migration was applied.
+    Finished {
+        /// The id of the migration.
+        id: u64,
+        /// The time it took to apply the migration.
+        elapsed: Duration,
+    },
+}
+
+/// The registry of database [`Migration`]s, ordered by their id.
+#[derive(Debug)]
+pub struct Migrations<PF: DatabaseProviderFactory> {
+    migrations: Vec<Box<dyn Migration<PF>>>,
+}
+
+impl<PF> Migrations<PF>
+where
+    PF: DatabaseProviderFactory<
+        Provider: ConsistencyProvider,
+        ProviderRW: ConsistencyProvider + PruneCheckpointWriter,
+    >,
+{
+    /// Returns the registry of the built-in migrations, configured by the node config.
+    ///
+    /// Migrations that rewrite tables copy their entries to temporary files in the configured ETL
+    /// directory.
+    pub fn new(config: &Config) -> Self {
+        let has_receipt_pruning =
+            config.prune.as_ref().is_some_and(|prune| prune.has_receipts_pruning());
+        Self::empty()
+            .with_migration(HealStaticFiles::new(has_receipt_pruning))
+            .with_migration(IntegerKeyTables::new(config.stages.etl.clone()))
+    }
+}
+
+impl<PF: DatabaseProviderFactory> Migrations<PF> {
+    /// Creates an empty registry.
+    pub const fn empty() -> Self {
+        Self { migrations: Vec::new() }
+    }
+
+    /// Adds a migration to the registry.
+    ///
+    /// # Panics
+    ///
+    /// If the id of the migration isn't greater than the id of all migrations already registered.
+    pub fn with_migration(mut self, migration: impl Migration<PF> + 'static) -> Self {
+        assert!(
+            migration.id() > self.latest_version(),
+            "migration {} must be registered after migration {}",
+            migration.id(),
+            self.latest_version()
+        );
+        self.migrations.push(Box::new(migration));
+        self
+    }
+
+    /// Returns the latest schema version known to the registry.
+    pub fn latest_version(&self) -> u64 {
+        self.migrations.last().map_or(0, |migration| migration.id())
+    }
+
+    /// Returns the migrations that are not applied to the database yet.
+    pub fn pending(&self, factory: &PF) -> Result<Vec<PendingMigration>, MigrationError> {
+        let provider = factory.database_provider_ro()?;
+        let version = self.ensure_supported(&provider)?;
+
+        self.migrations
+            .iter()
+            .filter(|migration| migration.id() > version)
+            .map(|migration| {
+                Ok(PendingMigration {
+                    id: migration.id(),
+                    description: migration.description(),
+                    estimated_work: migration.estimated_work(&provider)?,
+                })
+            })
+            .collect()
+    }
+
+    /// Applies all pending migrations and returns the schema version of the database.
+    pub fn run(&self, factory: &PF) -> Result<u64, MigrationError> {
+        self.run_with(factory, |_| {})
+    }
+
+    /// Applies all pending migrations, emitting [`MigrationEvent`]s, and returns the schema version
+    /// of the database.
+    ///
+    /// Fails without applying any migration if the schema version of the database is newer than
+    /// the latest one known to the registry.
+    pub fn run_with(
+        &self,
+        factory: &PF,
+        on_event: impl FnMut(MigrationEvent),
+    ) -> Result<u64, MigrationError> {
+        self.apply(factory, true, on_event)
+    }
+
+    /// Applies the pending migrations up to the first manual one (see [`Migration::is_manual`]),
+    /// and returns the schema version of the database.
+    ///
+    /// This is what the node runs on startup, the remaining migrations are applied by
+    /// `reth db migrate`.
+    pub fn run_on_startup(&self, factory: &PF) -> Result<u64, MigrationError> {
+        let version = self.apply(factory, false, |_| {})?;
+        if let Some(migration) = self.migrations.iter().find(|migration| migration.id() > version) {
+            warn!(
+                target: "reth::storage",
+                version,
+                latest = self.latest_version(),
+                id = migration.id(),
+                description = migration.description(),
+                "Database has pending migrations, run `reth db migrate` to apply them"
+            );
+        }
+        Ok(version)
+    }
+
+    /// Applies the pending migrations, stopping at the first manual one unless `manual` is set.
+    fn apply(
+        &self,
+        factory: &PF,
+        manual: bool,
+        mut on_event: impl FnMut(MigrationEvent),
+    ) -> Result<u64, MigrationError> {
+        let initial_version = self.ensure_supported(&factory.database_provider_ro()?)?;
+        debug!(target: "reth::storage", version = initial_version, latest = self.latest_version(), "Checked database schema version");
+
+        let mut version = initial_version;
+        for migration in self.migrations.iter().filter(|migration| migration.id() > initial_version)
+        {
+            if migration.is_manual() && !manual {
+                break
+            }
+            let id = migration.id();
+            let estimated_work = migration.estimated_work(&factory.database_provider_ro()?)?;
+            info!(target: "reth::storage", id, description = migration.description(), estimated_work, "Applying database migration");
+            on_event(MigrationEvent::Started(PendingMigration {
+                id,
+                description: migration.description(),
+                estimated_work,
+            }));
+
+            let start = Instant::now();
+            let provider_rw = factory.database_provider_rw()?;
+            migration.up(&provider_rw, &mut |processed| {
+                debug!(target: "reth::storage", id, processed, estimated_work, "Database migration progress");
+                on_event(MigrationEvent::Progress { id, processed, estimated_work })
+            })?;
+            set_schema_version(&provider_rw, id)?;
+            provider_rw.commit()?;
+
+            let elapsed = start.elapsed();
+            info!(target: "reth::storage", id, ?elapsed, "Applied database migration");
+            on_event(MigrationEvent::Finished { id, elapsed });
+            version = id;
+        }
+
+        Ok(version)
+    }
+
+    /// Returns the schema version of the database, if it's supported by the registry.
+    fn ensure_supported(&self, provider: &PF::Provider) -> Result<u64, MigrationError> {
+        let version = schema_version(provider)?;
+        let latest = self.latest_version();
+        if version > latest {
+            return Err(MigrationError::DatabaseTooNew { version, latest })
+        }
+        Ok(version)
+    }
+}
+
+impl<PF> Default for Migrations<PF>
+where
+    PF: DatabaseProviderFactory<
+        Provider: ConsistencyProvider,
+        ProviderRW: ConsistencyProvider + PruneCheckpointWriter,
+    >,
+{
+    /// Returns the registry of the built-in migrations, with the default [`Config`].
+    fn default() -> Self {
+        Self::new(&Config::default())
+    }
+}
+
+/// Returns the schema version of the database.
+pub fn schema_version<Provider: DBProvider>(provider: &Provider) -> ProviderResult<u64> {
+    match provider.tx_ref().cursor_read::<tables::SchemaMigrations>() {
+        Ok(mut cursor) => Ok(cursor.last()?.map(|(version, _)| version).unwrap_or_default()),
+        // Read-only transactions can't create the table in databases that predate it.
+        Err(DatabaseError::Open(err)) if err == mdbx::Error::NotFound.into() => Ok(0),
+        Err(err) => Err(err.into()),
+    }
+}
+
+/// Records that the database was migrated to the given schema version.
+pub fn set_schema_version<Provider>(provider: &Provider, version: u64) -> ProviderResult<()>
+where
+    Provider: DBProvider<Tx: DbTxMut>,
+{
+    let applied_at = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+    provider.tx_ref().put::<tables::SchemaMigrations>(version, applied_at)?;
+    Ok(())
+}
+
+/// A provider that the [`ConsistencyCheck`] can inspect.
+pub trait ConsistencyProvider:
+    DBProvider
+    + BlockReader
+    + StageCheckpointReader
+    + PruneCheckpointReader
+    + ChainSpecProvider
+    + StaticFileProviderFactory
+{
+}
+
+impl<T> ConsistencyProvider for T where
+    T: DBProvider
+        + BlockReader
+        + StageCheckpointReader
+        + PruneCheckpointReader
+        + ChainSpecProvider
+        + StaticFileProviderFactory
+{
+}
+
+/// Heals the static files and prune checkpoints that an interrupted write, prune or stage commit
+/// left inconsistent with the database, see [`ConsistencyCheck`].
+///
+/// This is the repair of the consistency check that's executed on startup, so that databases are
+/// consistent at the schema version of this migration even if they're migrated without starting
+/// the node, e.g. by `reth db migrate`. Inconsistencies that require an unwind of the pipeline
+/// are left to the node.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct HealStaticFiles {
+    /// Whether receipts are pruned, in which case they're not stored in static files.
+    has_receipt_pruning: bool,
+}
+
+impl HealStaticFiles {
+    /// Creates the migration.
+    pub const fn new(has_receipt_pruning: bool) -> Self {
+        Self { has_receipt_pruning }
+    }
+
+    /// Returns the number of planned repairs that don't require an unwind.
+    fn repairs(report: &ConsistencyReport) -> u64 {
+        report.plan().iter().filter(|action| !matches!(action, RepairAction::Unwind(_))).count()
+            as u64
+    }
+}
+
+impl<PF> Migration<PF> for HealStaticFiles
+where
+    PF: DatabaseProviderFactory<
+        Provider: ConsistencyProvider,
+        ProviderRW: ConsistencyProvider + PruneCheckpointWriter,
+    >,
+{
+    fn id(&self) -> u64 {
+        1
+    }
+
+    fn description(&self) -> &'static str {
+        "Heal the static files left inconsistent with the database"
+    }
+
+    fn estimated_work(&self, provider: &PF::Provider) -> ProviderResult<u64> {
+        let report = ConsistencyCheck::new(self.has_receipt_pruning)
+            .inspect(&provider.static_file_provider(), provider)?;
+        Ok(Self::repairs(&report))
+    }
+
+    fn up(&self, provider: &PF::ProviderRW, progress: &mut dyn FnMut(u64)) -> ProviderResult<()> {
+        let report = ConsistencyCheck::new(self.has_receipt_pruning)
+            .repair(&provider.static_file_provider(), provider)?;
+        if let Some(unwind_target) = report.unwind_target() {
+            warn!(target: "reth::storage", ?unwind_target, "Inconsistent storage. Restart node to heal.");
+        }
+        progress(Self::repairs(&report));
+        Ok(())
+    }
+}
+
+/// Recreates the tables with integer keys (see [`Table::INTEGER_KEY`]) that were created without
+/// them by an older version, copying their entries over.
+///
+/// Until then, the keys of these tables are stored as they are encoded. The tables are rewritten
+/// in a single transaction, so the migration is [manual](Migration::is_manual).
+#[derive(Debug, Clone, Default)]
+pub struct IntegerKeyTables {
+    /// Configuration of the temporary files the entries are copied to.
+    etl: EtlConfig,
+}
+
+impl IntegerKeyTables {
+    /// Creates the migration, copying the entries of the tables to temporary files in the given
+    /// ETL directory.
+    pub const fn new(etl: EtlConfig) -> Self {
+        Self { etl }
+    }
+}
+
+impl<PF: DatabaseProviderFactory> Migration<PF> for IntegerKeyTables {
+    fn id(&self) -> u64 {
+        2
+    }
+
+    fn description(&self) -> &'static str {
+        "Store the keys of block and transaction number keyed tables as integers"
+    }
+
+    fn estimated_work(&self, provider: &PF::Provider) -> ProviderResult<u64> {
+        let viewer = CountEntriesWithoutIntegerKeys { tx: provider.tx_ref() };
+        Tables::ALL.iter().try_fold(0, |entries, table| Ok(entries + viewer.view_rt(*table)?))
+    }
+
+    fn up(&self, provider: &PF::ProviderRW, progress: &mut dyn FnMut(u64)) -> ProviderResult<()> {
+        let viewer = RecreateWithIntegerKeys {
+            tx: provider.tx_ref(),
+            etl: &self.etl,
+            processed: Cell::new(0),
+            progress: RefCell::new(progress),
+        };
+        for table in Tables::ALL {
+            viewer.view_rt(*table)?;
+        }
+        Ok(())
+    }
+
+    fn is_manual(&self) -> bool {
+        true
+    }
+}
+
+/// Returns `true` if the table is defined with integer keys, but was created without them.
+fn is_missing_integer_keys<T: Table>(tx: &impl DbTx) -> Result<bool, DatabaseError> {
+    Ok(T::INTEGER_KEY && !tx.is_integer_key::<T>()?)
+}
+
+/// Counts the entries of the tables that are missing integer keys.
+#[derive(Debug)]
+struct CountEntriesWithoutIntegerKeys<'a, TX> {
+    tx: &'a TX,
+}
+
+impl<TX: DbTx> TableViewer<u64> for CountEntriesWithoutIntegerKeys<'_, TX> {
+    type Error = DatabaseError;
+
+    fn view<T: Table>(&self) -> Result<u64, Self::Error> {
+        if !is_missing_integer_keys::<T>(self.tx)? {
+            return Ok(0)
+        }
+        Ok(self.tx.entries::<T>()? as u64)
+    }
+}
+
+/// Recreates the tables that are missing integer keys.
+struct RecreateWithIntegerKeys<'a, TX> {
+    tx: &'a TX,
+    etl: &'a EtlConfig,
+    /// The number of entries copied so far.
+    processed: Cell<u64>,
+    progress: RefCell<&'a mut dyn FnMut(u64)>,
+}
+
+impl<TX: DbTxMut + DbTx> RecreateWithIntegerKeys<'_, TX> {
+    /// The number of copied entries after which progress is reported.
+    const PROGRESS_INTERVAL: u64 = 100_000;
+
+    /// Copies the entries of the table to a collector, and recreates the table.
+    ///
+    /// Returns `None` if the table isn't missing integer keys.
+    fn take_entries<T: Table>(
+        &self,
+    ) -> ProviderResult<Option<Collector<RawKey<T::Key>, RawValue<T::Value>>>> {
+        if !is_missing_integer_keys::<T>(self.tx)? {
+            return Ok(None)
+        }
+        debug!(target: "reth::storage", table = T::NAME, "Recreating table with integer keys");
+
+        let mut collector = Collector::new(self.etl.file_size, self.etl.dir.clone());
+        for entry in self.tx.cursor_read::<RawTable<T>>()?.walk(None)? {
+            let (key, value) = entry?;
+            collector
+                .insert(key, value)
+                .map_err(|err| ProviderError::FsPathError(err.to_string()))?;
+        }
+        self.tx.recreate_table::<T>()?;
+
+        Ok(Some(collector))
+    }
+
+    /// Counts another copied entry, reporting the progress every [`Self::PROGRESS_INTERVAL`]
+    /// entries.
+    fn on_entry_copied(&self) {
+        self.processed.set(self.processed.get() + 1);
+        if self.processed.get() % Self::PROGRESS_INTERVAL == 0 {
+            self.report_progress();
+        }
+    }
+
+    /// Reports the number of entries copied so far.
+    fn report_progress(&self) {
+        let mut progress = self.progress.borrow_mut();
+        (*progress)(self.processed.get());
+    }
+}
+
+impl<TX: DbTxMut + DbTx> TableViewer<()> for RecreateWithIntegerKeys<'_, TX> {
+    type Error = ProviderError;
+
+    fn view<T: Table>(&self) -> Result<(), Self::Error> {
+        let Some(mut collector) = self.take_entries::<T>()? else { return Ok(()) };
+
+        // Entries are collected in the order of the encoded keys, which is also the order of the
+        // integer keys.
+        let mut cursor = self.tx.cursor_write::<RawTable<T>>()?;
+        for entry in collector.iter().map_err(|err| ProviderError::FsPathError(err.to_string()))? {
+            let (key, value) = entry.map_err(|err| ProviderError::FsPathError(err.to_string()))?;
+            cursor.append(RawKey::from_vec(key), RawValue::from_vec(value))?;
+            self.on_entry_copied();
+        }
+        self.report_progress();
+
+        Ok(())
+    }
+
+    fn view_dupsort<T: DupSort>(&self) -> Result<(), Self::Error> {
+        let Some(mut collector) = self.take_entries::<T>()? else { return Ok(()) };
+
+        // Values of a key are collected in the order of their encoding, which is also the order
+        // they're stored in.
+        let mut cursor = self.tx.cursor_dup_write::<RawDupSort<T>>()?;
+        for entry in collector.iter().map_err(|err| ProviderError::FsPathError(err.to_string()))? {
+            let (key, value) = entry.map_err(|err| ProviderError::FsPathError(err.to_string()))?;
+            cursor.append_dup(RawKey::from_vec(key), RawValue::from_vec(value))?;
+            self.on_entry_copied();
+        }
+        self.report_progress();
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::init::init_genesis;
+    use alloy_consensus::Header;
+    use alloy_primitives::{Address, B256, U256};
+    use reth_db::{mdbx::DatabaseFlags, models::AccountBeforeTx, tables::ChainStateKey};
+    use reth_primitives::StaticFileSegment;
+    use reth_provider::{
+        test_utils::create_test_provider_factory, StageCheckpointWriter, StaticFileWriter,
+    };
+    use reth_stages_types::{StageCheckpoint, StageId};
+
+    /// Records its id as the last finalized block.
+    #[derive(Debug)]
+    struct TestMigration(u64);
+
+    impl<PF: DatabaseProviderFactory> Migration<PF> for TestMigration {
+        fn id(&self) -> u64 {
+            self.0
+        }
+
+        fn description(&self) -> &'static str {
+            "test migration"
+        }
+
+        fn estimated_work(&self, _provider: &PF::Provider) -> ProviderResult<u64> {
+            Ok(1)
+        }
+
+        fn up(
+            &self,
+            provider: &PF::ProviderRW,
+            progress: &mut dyn FnMut(u64),
+        ) -> ProviderResult<()> {
+            provider
+                .tx_ref()
+                .put::<tables::ChainState>(ChainStateKey::LastFinalizedBlock, self.0)?;
+            progress(1);
+            Ok(())
+        }
+    }
+
+    /// A [`TestMigration`] that's only applied by `reth db migrate`.
+    #[derive(Debug)]
+    struct ManualTestMigration(TestMigration);
+
+    impl<PF: DatabaseProviderFactory> Migration<PF> for ManualTestMigration {
+        fn id(&self) -> u64 {
+            Migration::<PF>::id(&self.0)
+        }
+
+        fn description(&self) -> &'static str {
+            "manual test migration"
+        }
+
+        fn estimated_work(&self, provider: &PF::Provider) -> ProviderResult<u64> {
+            Migration::<PF>::estimated_work(&self.0, provider)
+        }
+
+        fn up(
+            &self,
+            provider: &PF::ProviderRW,
+            progress: &mut dyn FnMut(u64),
+        ) -> ProviderResult<()> {
+            Migration::<PF>::up(&self.0, provider, progress)
+        }
+
+        fn is_manual(&self) -> bool {
+            true
+        }
+    }
+
+    fn finished_ids(events: &[MigrationEvent]) -> Vec<u64> {
+        events
+            .iter()
+            .filter_map(|event| match event {
+                MigrationEvent::Finished { id, .. } => Some(*id),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn default_migrations_reach_schema_version() {
+        let factory = create_test_provider_factory();
+        assert_eq!(Migrations::default().latest_version(), SCHEMA_VERSION);
+        assert_eq!(Migrations::default().run(&factory).unwrap(), SCHEMA_VERSION);
+    }
+
+    #[test]
+    fn fresh_database_has_no_pending_migrations() {
+        let factory = create_test_provider_factory();
+        init_genesis(&factory).unwrap();
+
+        assert_eq!(schema_version(&factory.provider().unwrap()).unwrap(), SCHEMA_VERSION);
+        assert!(Migrations::default().pending(&factory).unwrap().is_empty());
+
+        let mut events = Vec::new();
+        assert_eq!(
+            Migrations::default().run_with(&factory, |event| events.push(event)).unwrap(),
+            SCHEMA_VERSION
+        );
+        assert!(events.is_empty());
+    }
+
+    #[test]
+    fn applies_migrations_sequentially() {
+        let factory = create_test_provider_factory();
+        let migrations = Migrations::empty()
+            .with_migration(TestMigration(1))
+            .with_migration(TestMigration(2))
+            .with_migration(TestMigration(3));
+
+        // the database is at version 1 already
+        let provider_rw = factory.database_provider_rw().unwrap();
+        set_schema_version(&provider_rw, 1).unwrap();
+        provider_rw.commit().unwrap();
+
+        let pending = migrations.pending(&factory).unwrap();
+        assert_eq!(pending.iter().map(|migration| migration.id).collect::<Vec<_>>(), vec![2, 3]);
+
+        let mut events = Vec::new();
+        assert_eq!(migrations.run_with(&factory, |event| events.push(event)).unwrap(), 3);
+        assert_eq!(finished_ids(&events), vec![2, 3]);
+        assert_eq!(
+            events[..2],
+            [
+                MigrationEvent::Started(PendingMigration {
+                    id: 2,
+                    description: "test migration",
+                    estimated_work: 1
+                }),
+                MigrationEvent::Progress { id: 2, processed: 1, estimated_work: 1 },
+            ]
+        );
+
+        let provider = factory.provider().unwrap();
+        assert_eq!(schema_version(&provider).unwrap(), 3);
+        assert_eq!(
+            provider.tx_ref().get::<tables::ChainState>(ChainStateKey::LastFinalizedBlock).unwrap(),
+            Some(3)
+        );
+        drop(provider);
+
+        // nothing left to apply
+        events.clear();
+        assert_eq!(migrations.run_with(&factory, |event| events.push(event)).unwrap(), 3);
+        assert!(events.is_empty());
+    }
+
+    #[test]
+    fn startup_stops_at_manual_migration() {
+        let factory = create_test_provider_factory();
+        let migrations = Migrations::empty()
+            .with_migration(TestMigration(1))
+            .with_migration(ManualTestMigration(TestMigration(2)))
+            .with_migration(TestMigration(3));
+
+        assert_eq!(migrations.run_on_startup(&factory).unwrap(), 1);
+        assert_eq!(
+            migrations.pending(&factory).unwrap().iter().map(|m| m.id).collect::<Vec<_>>(),
+            vec![2, 3]
+        );
+        // startup doesn't get past it on the next run either
+        assert_eq!(migrations.run_on_startup(&factory).unwrap(), 1);
+
+        assert_eq!(migrations.run(&factory).unwrap(), 3);
+        assert_eq!(migrations.run_on_startup(&factory).unwrap(), 3);
+    }
+
+    #[test]
+    fn refuses_database_newer_than_binary() {
+        let factory = create_test_provider_factory();
+        let provider_rw = factory.database_provider_rw().unwrap();
+        set_schema_version(&provider_rw, SCHEMA_VERSION + 1).unwrap();
+        provider_rw.commit().unwrap();
+
+        let err = Migrations::default().run(&factory).unwrap_err();
+        assert!(matches!(
+            err,
+            MigrationError::DatabaseTooNew { version, latest }
+                if version == SCHEMA_VERSION + 1 && latest == SCHEMA_VERSION
+        ));
+        assert!(matches!(
+            Migrations::default().pending(&factory),
+            Err(MigrationError::DatabaseTooNew { .. })
+        ));
+    }
+
+    #[test]
+    fn heals_static_files() {
+        let factory = create_test_provider_factory();
+        let static_file_provider = factory.static_file_provider();
+        let mut writer = static_file_provider.latest_writer(StaticFileSegment::Headers).unwrap();
+        let mut header = Header::default();
+        for number in 0..=10 {
+            header.number = number;
+            writer.append_header(&header, U256::ZERO, &B256::ZERO).unwrap();
+        }
+        writer.commit().unwrap();
+        drop(writer);
+
+        // the headers stage commit failed after the static files were committed
+        let provider_rw = factory.database_provider_rw().unwrap();
+        provider_rw.save_stage_checkpoint(StageId::Headers, StageCheckpoint::new(5)).unwrap();
+        provider_rw.commit().unwrap();
+
+        assert_eq!(
+            Migrations::default().pending(&factory).unwrap()[0],
+            PendingMigration {
+                id: 1,
+                description: "Heal the static files left inconsistent with the database",
+                estimated_work: 1,
+            }
+        );
+        let mut events = Vec::new();
+        Migrations::default().run_with(&factory, |event| events.push(event)).unwrap();
+        assert!(events.contains(&MigrationEvent::Progress {
+            id: 1,
+            processed: 1,
+            estimated_work: 1
+        }));
+
+        assert_eq!(
+            static_file_provider.get_highest_static_file_block(StaticFileSegment::Headers),
+            Some(5)
+        );
+        assert!(ConsistencyCheck::default()
+            .inspect(&static_file_provider, &factory.provider().unwrap())
+            .unwrap()
+            .is_consistent());
+    }
+
+    #[test]
+    fn recreates_tables_with_integer_keys() {
+        let factory = create_test_provider_factory();
+        let headers = vec![(1, B256::with_last_byte(1)), (255, B256::ZERO), (256, B256::ZERO)];
+        let changesets = vec![
+            (1, AccountBeforeTx { address: Address::with_last_byte(1), info: None }),
+            (256, AccountBeforeTx { address: Address::with_last_byte(1), info: None }),
+            (256, AccountBeforeTx { address: Address::with_last_byte(2), info: None }),
+        ];
+
+        let provider_rw = factory.database_provider_rw().unwrap();
+        let tx = provider_rw.tx_ref();
+        // Create the tables without integer keys, as an older version would have.
+        for (table, flags) in [
+            (tables::CanonicalHeaders::NAME, DatabaseFlags::default()),
+            (tables::AccountChangeSets::NAME, DatabaseFlags::DUP_SORT),
+        ] {
+            let db = tx.inner.open_db(Some(table)).unwrap();
+            unsafe { tx.inner.drop_db(db) }.unwrap();
+            tx.inner.create_db(Some(table), flags).unwrap();
+        }
+        for (number, hash) in headers.clone() {
+            tx.put::<tables::CanonicalHeaders>(number, hash).unwrap();
+        }
+        for (number, changeset) in changesets.clone() {
+            tx.put::<tables::AccountChangeSets>(number, changeset).unwrap();
+        }
+        assert_eq!(tx.is_integer_key::<tables::CanonicalHeaders>(), Ok(false));
+        set_schema_version(&provider_rw, 1).unwrap();
+        provider_rw.commit().unwrap();
+
+        assert_eq!(
+            Migrations::default().pending(&factory).unwrap(),
+            vec![PendingMigration {
+                id: 2,
+                description: "Store the keys of block and transaction number keyed tables as \
+                              integers",
+                estimated_work: 6,
+            }]
+        );
+        let mut events = Vec::new();
+        Migrations::default().run_with(&factory, |event| events.push(event)).unwrap();
+        assert!(events.contains(&MigrationEvent::Progress {
+            id: 2,
+            processed: 6,
+            estimated_work: 6
+        }));
+
+        let provider = factory.provider().unwrap();
+        let tx = provider.tx_ref();
+        assert_eq!(tx.is_integer_key::<tables::CanonicalHeaders>(), Ok(true));
+        assert_eq!(tx.is_integer_key::<tables::AccountChangeSets>(), Ok(true));
+        assert_eq!(
+            tx.cursor_read::<tables::CanonicalHeaders>()
+                .unwrap()
+                .walk(None)
+                .unwrap()
+                .collect::<Result<Vec<_>, _>>()
+                .unwrap(),
+            headers
+        );
+        assert_eq!(
+            tx.cursor_read::<tables::AccountChangeSets>()
+                .unwrap()
+                .walk(None)
+                .unwrap()
+                .collect::<Result<Vec<_>, _>>()
+                .unwrap(),
+            changesets
+        );
+    }
+}
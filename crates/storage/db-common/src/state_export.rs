@@ -0,0 +1,374 @@
+//! Export of the state at a given block.
+//!
+//! The state can be exported in two formats:
+//!  - [`StateExportFormat::Jsonl`]: one [`ExportedAccount`] JSON object per line, ordered by
+//!    address.
+//!  - [`StateExportFormat::Genesis`]: a single JSON object mapping addresses to
+//!    [`GenesisAccount`]s, which can be used as the `alloc` of a genesis file.
+//!
+//! If the block is not the tip, the state is reconstructed from the plain state tables, the
+//! changesets and the history indices. Accounts are streamed to the writer one at a time, so the
+//! memory usage is bounded by the storage of a single account. The storage roots are computed from
+//! the exported storage slots.
+
+use alloy_consensus::constants::KECCAK_EMPTY;
+use alloy_genesis::GenesisAccount;
+use alloy_primitives::{Address, BlockNumber, Bytes, B256, U256};
+use reth_db::tables;
+use reth_db_api::{
+    cursor::{DbCursorRO, DbDupCursorRO},
+    models::storage_sharded_key::StorageShardedKey,
+    transaction::DbTx,
+    DatabaseError,
+};
+use reth_provider::{
+    providers::ProviderNodeTypes, AccountReader, DBProvider, ProviderFactory, StateProvider,
+};
+use reth_trie::{root::storage_root_unhashed, EMPTY_ROOT_HASH};
+use serde::{Deserialize, Serialize};
+use std::{
+    io::Write,
+    time::{Duration, Instant},
+};
+use tracing::info;
+
+/// Interval at which the export progress is logged.
+const PROGRESS_LOG_INTERVAL: Duration = Duration::from_secs(10);
+
+/// The format of a state export.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum StateExportFormat {
+    /// One [`ExportedAccount`] per line.
+    #[default]
+    Jsonl,
+    /// A genesis `alloc` JSON object.
+    Genesis,
+}
+
+/// An account as written by a [`StateExportFormat::Jsonl`] export.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportedAccount {
+    /// The account's address.
+    pub address: Address,
+    /// The account's balance.
+    pub balance: U256,
+    /// The account's nonce.
+    pub nonce: u64,
+    /// The hash of the account's bytecode.
+    pub code_hash: B256,
+    /// The root of the account's storage trie.
+    pub storage_root: B256,
+    /// The account's non-zero storage slots ordered by slot, if the storage was exported.
+    ///
+    /// Serialized as a JSON object mapping slots to values.
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "storage_map")]
+    pub storage: Option<Vec<(B256, U256)>>,
+    /// The account's bytecode, if it has any and the storage was exported.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code: Option<Bytes>,
+}
+
+/// Exports the state at the given block to the writer.
+///
+/// Storage and bytecode are always exported for [`StateExportFormat::Genesis`], otherwise only if
+/// `include_storage` is set, which makes the export importable by
+/// [`init_from_state_dump`](crate::init::init_from_state_dump). Returns the number of exported
+/// accounts.
+pub fn export_state<N: ProviderNodeTypes>(
+    factory: &ProviderFactory<N>,
+    block: BlockNumber,
+    format: StateExportFormat,
+    include_storage: bool,
+    mut writer: impl Write,
+) -> eyre::Result<u64> {
+    let provider = factory.provider()?;
+    let tx = provider.tx_ref();
+    let state = factory.history_by_block_number(block)?;
+    let include_storage = include_storage || format == StateExportFormat::Genesis;
+
+    info!(target: "reth::cli", block, ?format, include_storage, "Exporting state");
+
+    // Every account that exists at the block is either in the plain state, or has been changed
+    // after the block and thus is in the account history index.
+    let mut plain_accounts = tx.cursor_read::<tables::PlainAccountState>()?;
+    let mut accounts_history = tx.cursor_read::<tables::AccountsHistory>()?;
+    let addresses = merge_sorted(
+        plain_accounts.walk(None)?.map(|entry| entry.map(|(address, _)| address)),
+        accounts_history.walk(None)?.map(|entry| entry.map(|(key, _)| key.key)),
+    );
+
+    let mut plain_storage = tx.cursor_dup_read::<tables::PlainStorageState>()?;
+    let mut storages_history = tx.cursor_read::<tables::StoragesHistory>()?;
+
+    if format == StateExportFormat::Genesis {
+        writeln!(writer, "{{")?;
+    }
+
+    // The storage slots of the current account, reused across accounts. The slots are needed for
+    // the storage root even if the storage is not exported.
+    let mut storage = Vec::new();
+    let mut exported = 0u64;
+    let mut last_log = Instant::now();
+    for address in addresses {
+        let address = address?;
+        let Some(account) = state.basic_account(&address)? else { continue };
+
+        storage.clear();
+        let slots = merge_sorted(
+            plain_storage.walk_dup_prefix(address)?.map(|entry| entry.map(|(_, e)| e.key)),
+            storages_history
+                .walk(Some(StorageShardedKey::new(address, B256::ZERO, 0)))?
+                .take_while(|entry| entry.as_ref().map_or(true, |(key, _)| key.address == address))
+                .map(|entry| entry.map(|(key, _)| key.sharded_key.key)),
+        );
+        for slot in slots {
+            let slot = slot?;
+            if let Some(value) = state.storage(address, slot)?.filter(|value| !value.is_zero()) {
+                storage.push((slot, value));
+            }
+        }
+
+        let code_hash = account.bytecode_hash.unwrap_or(KECCAK_EMPTY);
+        let code = if include_storage && code_hash != KECCAK_EMPTY {
+            state.bytecode_by_hash(&code_hash)?.map(|code| code.original_bytes())
+        } else {
+            None
+        };
+        match format {
+            StateExportFormat::Jsonl => {
+                let storage_root = if storage.is_empty() {
+                    EMPTY_ROOT_HASH
+                } else {
+                    storage_root_unhashed(storage.iter().copied())
+                };
+                let mut account = ExportedAccount {
+                    address,
+                    balance: account.balance,
+                    nonce: account.nonce,
+                    code_hash,
+                    storage_root,
+                    storage: include_storage.then(|| std::mem::take(&mut storage)),
+                    code,
+                };
+                serde_json::to_writer(&mut writer, &account)?;
+                writeln!(writer)?;
+                // hand the buffer back for the next account
+                if let Some(slots) = account.storage.take() {
+                    storage = slots;
+                }
+            }
+            StateExportFormat::Genesis => {
+                let account = GenesisAccount::default()
+                    .with_nonce(Some(account.nonce))
+                    .with_balance(account.balance)
+                    .with_code(code)
+                    .with_storage(Some(
+                        storage.iter().map(|(slot, value)| (*slot, (*value).into())).collect(),
+                    ));
+                if exported > 0 {
+                    writeln!(writer, ",")?;
+                }
+                write!(writer, "  {}: ", serde_json::to_string(&address)?)?;
+                serde_json::to_writer(&mut writer, &account)?;
+            }
+        }
+
+        exported += 1;
+        if last_log.elapsed() >= PROGRESS_LOG_INTERVAL {
+            info!(target: "reth::cli", exported, %address, "Exporting state");
+            last_log = Instant::now();
+        }
+    }
+
+    if format == StateExportFormat::Genesis {
+        if exported > 0 {
+            writeln!(writer)?;
+        }
+        writeln!(writer, "}}")?;
+    }
+    writer.flush()?;
+
+    info!(target: "reth::cli", block, exported, "Exported state");
+
+    Ok(exported)
+}
+
+/// (De)serializes the storage of an [`ExportedAccount`] as a JSON object mapping slots to values.
+mod storage_map {
+    use alloy_primitives::{B256, U256};
+    use serde::{Deserialize, Deserializer, Serializer};
+    use std::collections::BTreeMap;
+
+    pub(super) fn serialize<S: Serializer>(
+        storage: &Option<Vec<(B256, U256)>>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match storage {
+            Some(storage) => {
+                serializer.collect_map(storage.iter().map(|(slot, value)| (slot, value)))
+            }
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub(super) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<Vec<(B256, U256)>>, D::Error> {
+        let storage = Option::<BTreeMap<B256, U256>>::deserialize(deserializer)?;
+        Ok(storage.map(|storage| storage.into_iter().collect()))
+    }
+}
+
+/// Merges two ascending iterators into a single ascending iterator, skipping duplicates.
+fn merge_sorted<T: Ord + Clone>(
+    left: impl Iterator<Item = Result<T, DatabaseError>>,
+    right: impl Iterator<Item = Result<T, DatabaseError>>,
+) -> impl Iterator<Item = Result<T, DatabaseError>> {
+    let mut left = left.peekable();
+    let mut right = right.peekable();
+    let mut last = None;
+    std::iter::from_fn(move || loop {
+        let take_right = match (left.peek(), right.peek()) {
+            (Some(Ok(l)), Some(Ok(r))) => r < l,
+            (Some(_), _) => false,
+            (None, _) => true,
+        };
+        let next = if take_right { right.next() } else { left.next() };
+        match next? {
+            Ok(item) if last.as_ref() == Some(&item) => continue,
+            Ok(item) => {
+                last = Some(item.clone());
+                return Some(Ok(item))
+            }
+            Err(err) => return Some(Err(err)),
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_db_api::{
+        models::{AccountBeforeTx, BlockNumberAddress, IntegerList, ShardedKey},
+        transaction::DbTxMut,
+    };
+    use reth_primitives::{Account, StorageEntry};
+    use reth_provider::test_utils::create_test_provider_factory;
+    use std::collections::BTreeMap;
+
+    const A: Address = Address::with_last_byte(1);
+    const B: Address = Address::with_last_byte(2);
+    const C: Address = Address::with_last_byte(3);
+    const SLOT: B256 = B256::with_last_byte(1);
+
+    fn account(balance: u64) -> Account {
+        Account { nonce: 0, balance: U256::from(balance), bytecode_hash: None }
+    }
+
+    /// Writes the state of a three block chain:
+    ///  - block 0: `A` has balance 10 and `SLOT` set to 1, `C` has balance 7.
+    ///  - block 1: `A` receives 10.
+    ///  - block 2: `A` receives 10 and sets `SLOT` to 2, `B` is created with balance 5 and `C` is
+    ///    destroyed.
+    fn write_chain<N: ProviderNodeTypes>(factory: &ProviderFactory<N>) {
+        let provider = factory.database_provider_rw().unwrap();
+        let tx = provider.tx_ref();
+
+        tx.put::<tables::PlainAccountState>(A, account(30)).unwrap();
+        tx.put::<tables::PlainAccountState>(B, account(5)).unwrap();
+        tx.put::<tables::PlainStorageState>(A, StorageEntry { key: SLOT, value: U256::from(2) })
+            .unwrap();
+
+        tx.put::<tables::AccountChangeSets>(
+            1,
+            AccountBeforeTx { address: A, info: Some(account(10)) },
+        )
+        .unwrap();
+        for (address, info) in [(A, Some(account(20))), (B, None), (C, Some(account(7)))] {
+            tx.put::<tables::AccountChangeSets>(2, AccountBeforeTx { address, info }).unwrap();
+        }
+        tx.put::<tables::StorageChangeSets>(
+            BlockNumberAddress((2, A)),
+            StorageEntry { key: SLOT, value: U256::from(1) },
+        )
+        .unwrap();
+
+        for (address, blocks) in [(A, vec![1, 2]), (B, vec![2]), (C, vec![2])] {
+            tx.put::<tables::AccountsHistory>(
+                ShardedKey::new(address, u64::MAX),
+                IntegerList::new(blocks).unwrap(),
+            )
+            .unwrap();
+        }
+        tx.put::<tables::StoragesHistory>(
+            StorageShardedKey::new(A, SLOT, u64::MAX),
+            IntegerList::new([2]).unwrap(),
+        )
+        .unwrap();
+
+        provider.commit().unwrap();
+    }
+
+    fn export_jsonl<N: ProviderNodeTypes>(
+        factory: &ProviderFactory<N>,
+        block: BlockNumber,
+    ) -> BTreeMap<Address, ExportedAccount> {
+        let mut output = Vec::new();
+        export_state(factory, block, StateExportFormat::Jsonl, true, &mut output).unwrap();
+        std::str::from_utf8(&output)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str::<ExportedAccount>(line).unwrap())
+            .map(|account| (account.address, account))
+            .collect()
+    }
+
+    #[test]
+    fn export_historical_state() {
+        let factory = create_test_provider_factory();
+        write_chain(&factory);
+
+        let balances = |accounts: &BTreeMap<Address, ExportedAccount>| {
+            accounts.iter().map(|(address, account)| (*address, account.balance)).collect()
+        };
+        let slot = |accounts: &BTreeMap<Address, ExportedAccount>| {
+            accounts[&A]
+                .storage
+                .as_ref()
+                .unwrap()
+                .iter()
+                .find(|(slot, _)| *slot == SLOT)
+                .map(|(_, value)| *value)
+        };
+
+        let genesis = export_jsonl(&factory, 0);
+        assert_eq!(balances(&genesis), BTreeMap::from([(A, U256::from(10)), (C, U256::from(7))]));
+        assert_eq!(slot(&genesis), Some(U256::from(1)));
+
+        let block_1 = export_jsonl(&factory, 1);
+        assert_eq!(balances(&block_1), BTreeMap::from([(A, U256::from(20)), (C, U256::from(7))]));
+        assert_eq!(slot(&block_1), Some(U256::from(1)));
+
+        let block_2 = export_jsonl(&factory, 2);
+        assert_eq!(balances(&block_2), BTreeMap::from([(A, U256::from(30)), (B, U256::from(5))]));
+        assert_eq!(slot(&block_2), Some(U256::from(2)));
+        assert_eq!(block_2[&A].storage_root, storage_root_unhashed([(SLOT, U256::from(2))]));
+        assert_eq!(block_2[&B].storage_root, EMPTY_ROOT_HASH);
+    }
+
+    #[test]
+    fn export_genesis_alloc() {
+        let factory = create_test_provider_factory();
+        write_chain(&factory);
+
+        let mut output = Vec::new();
+        export_state(&factory, 1, StateExportFormat::Genesis, false, &mut output).unwrap();
+        let alloc: BTreeMap<Address, GenesisAccount> = serde_json::from_slice(&output).unwrap();
+
+        assert_eq!(alloc.len(), 2);
+        assert_eq!(alloc[&A].balance, U256::from(20));
+        assert_eq!(alloc[&A].storage, Some(BTreeMap::from([(SLOT, B256::from(U256::from(1)))])));
+        assert_eq!(alloc[&C].balance, U256::from(7));
+    }
+}
@@ -0,0 +1,158 @@
+//! Tests for the memory usage of state dump imports.
+//!
+//! This is a separate test binary, because it replaces the global allocator.
+
+#![allow(missing_docs)]
+
+use alloy_genesis::GenesisAccount;
+use alloy_primitives::{keccak256, Address, Bytes, B256, U256};
+use reth_chainspec::{Chain, ChainSpec};
+use reth_codecs::Compact;
+use reth_config::config::EtlConfig;
+use reth_db::tables;
+use reth_db_api::transaction::DbTx;
+use reth_db_common::init::{init_from_state_dump, insert_genesis_header, StateImportConfig};
+use reth_etl::Collector;
+use reth_provider::{
+    providers::StaticFileWriter, test_utils::create_test_provider_factory_with_chain_spec,
+    DBProvider, DatabaseProviderFactory, StageCheckpointReader, StaticFileProviderFactory,
+};
+use reth_stages_types::{StageCheckpoint, StageId};
+use reth_trie::root::state_root;
+use serde::Serialize;
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    collections::BTreeMap,
+    io::{self, BufReader, BufWriter, Seek, Write},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, OnceLock,
+    },
+};
+
+/// Allocator that tracks the number of allocated bytes and their peak.
+struct CountingAllocator;
+
+static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let allocated = ALLOCATED.fetch_add(layout.size(), Ordering::Relaxed) + layout.size();
+        PEAK.fetch_max(allocated, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        ALLOCATED.fetch_sub(layout.size(), Ordering::Relaxed);
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+/// Returns the peak number of bytes allocated while running `f`, on top of the bytes that were
+/// allocated before.
+fn peak_allocated_by<T>(f: impl FnOnce() -> T) -> (T, usize) {
+    let before = ALLOCATED.load(Ordering::Relaxed);
+    PEAK.store(before, Ordering::Relaxed);
+    let out = f();
+    (out, PEAK.load(Ordering::Relaxed) - before)
+}
+
+const ACCOUNTS: u64 = 1_000_000;
+
+/// Upper bound of the memory used by the import. The dump is larger, so it can't be held in
+/// memory.
+const MAX_PEAK_ALLOCATED: usize = 192 * 1024 * 1024;
+
+/// An account of a JSONL state dump.
+#[derive(Serialize)]
+struct DumpAccount<'a> {
+    #[serde(flatten)]
+    account: &'a GenesisAccount,
+    address: Address,
+}
+
+/// Returns the `i`th synthetic account, all of them with code and every tenth of them with
+/// storage.
+fn synthetic_account(i: u64) -> (Address, GenesisAccount) {
+    let mut account = GenesisAccount::default()
+        .with_nonce(Some(i))
+        .with_balance(U256::from(i))
+        .with_code(Some(Bytes::from_static(&[0x5b; 64])));
+    if i % 10 == 0 {
+        account = account.with_storage(Some(BTreeMap::from([
+            (B256::with_last_byte(1), B256::from(U256::from(i))),
+            (B256::with_last_byte(2), B256::from(U256::from(i * 2))),
+        ])));
+    }
+    (Address::from_word(keccak256(i.to_be_bytes())), account)
+}
+
+/// Writes a JSONL dump of the synthetic accounts to a temporary file.
+///
+/// Returns the file, rewound, and the state root of the accounts, which is computed from the
+/// accounts sorted by their hashed address in an ETL collector.
+fn synthetic_dump(etl: &EtlConfig) -> io::Result<(std::fs::File, B256)> {
+    let mut hashed_accounts = Collector::<B256, GenesisAccount>::new(etl.file_size, None);
+    let mut writer = BufWriter::new(tempfile::tempfile()?);
+    for i in 1..=ACCOUNTS {
+        let (address, account) = synthetic_account(i);
+        serde_json::to_writer(&mut writer, &DumpAccount { account: &account, address })?;
+        writer.write_all(b"\n")?;
+        hashed_accounts.insert(keccak256(address), account)?;
+    }
+
+    let root = state_root(hashed_accounts.iter()?.map(|entry| {
+        let (hashed_address, account) = entry.unwrap();
+        (B256::from_slice(&hashed_address), GenesisAccount::from_compact(&account, account.len()).0)
+    }));
+
+    let mut file = writer.into_inner()?;
+    file.rewind()?;
+    Ok((file, root))
+}
+
+/// Imports a million accounts from a dump file, with a small ETL file size and batch size so that
+/// the memory used by the import stays bounded.
+#[test]
+fn import_large_state_dump() {
+    let etl = EtlConfig { dir: None, file_size: 16 * 1024 * 1024 };
+    let (dump, state_root) = synthetic_dump(&etl).unwrap();
+    let dump_len = dump.metadata().unwrap().len() as usize;
+    assert!(dump_len > MAX_PEAK_ALLOCATED, "dump of {dump_len} bytes fits the memory bound");
+
+    let mut genesis_header = ChainSpec::default().genesis_header().clone();
+    genesis_header.state_root = state_root;
+    let chain_spec = Arc::new(ChainSpec {
+        chain: Chain::from_id(1),
+        genesis_header: OnceLock::from(genesis_header),
+        ..Default::default()
+    });
+    let factory = create_test_provider_factory_with_chain_spec(chain_spec.clone());
+    let provider_rw = factory.database_provider_rw().unwrap();
+    insert_genesis_header(&provider_rw, &chain_spec).unwrap();
+    provider_rw.static_file_provider().commit().unwrap();
+    provider_rw.commit().unwrap();
+
+    let config = StateImportConfig::new(etl).with_batch_size(10_000);
+    let (hash, peak) =
+        peak_allocated_by(|| init_from_state_dump(BufReader::new(dump), &factory, config).unwrap());
+    assert_eq!(hash, chain_spec.genesis_hash());
+    assert!(peak < MAX_PEAK_ALLOCATED, "allocated up to {peak} bytes");
+
+    let provider = factory.provider().unwrap();
+    assert_eq!(
+        provider.tx_ref().entries::<tables::PlainAccountState>().unwrap(),
+        ACCOUNTS as usize
+    );
+    assert_eq!(
+        provider.tx_ref().entries::<tables::PlainStorageState>().unwrap(),
+        (ACCOUNTS / 10 * 2) as usize
+    );
+    for stage in StageId::STATE_REQUIRED {
+        assert_eq!(provider.get_stage_checkpoint(stage).unwrap(), Some(StageCheckpoint::new(0)));
+    }
+}
@@ -67,6 +67,46 @@ impl StoredBlockBodyIndices {
     }
 }
 
+/// Versioned storage representation of [`StoredBlockBodyIndices`].
+///
+/// Fields can be appended to the latest variant without a database migration: entries stored
+/// before a field existed decode it with its default value.
+#[derive(Debug, Eq, PartialEq, Clone, Copy, Serialize, Deserialize, Compact)]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[add_arbitrary_tests(compact)]
+pub enum VersionedStoredBlockBodyIndices {
+    /// Initial layout, equivalent to [`StoredBlockBodyIndices`].
+    V1 {
+        /// The number of the first transaction in this block
+        first_tx_num: TxNumber,
+        /// The total number of transactions in the block
+        tx_count: NumTransactions,
+    },
+}
+
+impl Default for VersionedStoredBlockBodyIndices {
+    fn default() -> Self {
+        StoredBlockBodyIndices::default().into()
+    }
+}
+
+impl From<StoredBlockBodyIndices> for VersionedStoredBlockBodyIndices {
+    fn from(indices: StoredBlockBodyIndices) -> Self {
+        let StoredBlockBodyIndices { first_tx_num, tx_count } = indices;
+        Self::V1 { first_tx_num, tx_count }
+    }
+}
+
+impl From<VersionedStoredBlockBodyIndices> for StoredBlockBodyIndices {
+    fn from(indices: VersionedStoredBlockBodyIndices) -> Self {
+        match indices {
+            VersionedStoredBlockBodyIndices::V1 { first_tx_num, tx_count } => {
+                Self { first_tx_num, tx_count }
+            }
+        }
+    }
+}
+
 /// The storage representation of block withdrawals.
 #[derive(Debug, Default, Eq, PartialEq, Clone, Serialize, Deserialize, Compact)]
 #[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
@@ -78,7 +118,8 @@ pub struct StoredBlockWithdrawals {
 
 #[cfg(test)]
 mod tests {
-    use crate::StoredBlockBodyIndices;
+    use crate::{StoredBlockBodyIndices, VersionedStoredBlockBodyIndices};
+    use reth_codecs::test_utils::test_compact_fixtures;
 
     #[test]
     fn block_indices() {
@@ -92,4 +133,30 @@ mod tests {
         assert_eq!(block_indices.tx_count(), tx_count);
         assert_eq!(block_indices.tx_num_range(), first_tx_num..first_tx_num + tx_count);
     }
+
+    #[test]
+    fn block_indices_fixtures() {
+        test_compact_fixtures::<StoredBlockBodyIndices>(include_str!(
+            "../testdata/block_body_indices_compact.json"
+        ));
+    }
+
+    #[test]
+    fn versioned_block_indices() {
+        let block_indices = StoredBlockBodyIndices { first_tx_num: 10, tx_count: 6 };
+        let versioned = VersionedStoredBlockBodyIndices::from(block_indices);
+
+        assert_eq!(
+            versioned,
+            VersionedStoredBlockBodyIndices::V1 { first_tx_num: 10, tx_count: 6 }
+        );
+        assert_eq!(StoredBlockBodyIndices::from(versioned), block_indices);
+    }
+
+    #[test]
+    fn versioned_block_indices_fixtures() {
+        test_compact_fixtures::<VersionedStoredBlockBodyIndices>(include_str!(
+            "../testdata/versioned_block_body_indices_compact.json"
+        ));
+    }
 }
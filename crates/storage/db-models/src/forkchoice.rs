@@ -0,0 +1,23 @@
+//! Forkchoice state model.
+
+use alloy_primitives::B256;
+use reth_codecs::{add_arbitrary_tests, Compact};
+use serde::{Deserialize, Serialize};
+
+/// The last valid forkchoice state received from the consensus layer.
+///
+/// This is persisted so that the node can restore its canonical head and block tags after a
+/// restart without waiting for the consensus layer to send a new forkchoice update.
+#[derive(Debug, Default, Eq, PartialEq, Clone, Copy, Serialize, Deserialize, Compact)]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[add_arbitrary_tests(compact)]
+pub struct StoredForkchoiceState {
+    /// Hash of the head block.
+    pub head_block_hash: B256,
+    /// Hash of the safe block.
+    pub safe_block_hash: B256,
+    /// Hash of the finalized block.
+    pub finalized_block_hash: B256,
+    /// Unix timestamp in seconds at which the forkchoice state was received.
+    pub timestamp: u64,
+}
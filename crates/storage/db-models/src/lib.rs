@@ -6,8 +6,12 @@ pub use accounts::AccountBeforeTx;
 
 /// Blocks
 pub mod blocks;
-pub use blocks::{StoredBlockBodyIndices, StoredBlockWithdrawals};
+pub use blocks::{StoredBlockBodyIndices, StoredBlockWithdrawals, VersionedStoredBlockBodyIndices};
 
 /// Client Version
 pub mod client_version;
 pub use client_version::ClientVersion;
+
+/// Forkchoice state
+pub mod forkchoice;
+pub use forkchoice::StoredForkchoiceState;
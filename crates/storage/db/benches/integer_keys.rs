@@ -0,0 +1,139 @@
+#![allow(missing_docs)]
+
+//! Measures the size and the write throughput of the block and transaction number keyed tables,
+//! with and without integer keys, on a synthetic sync of 100k blocks.
+
+use alloy_primitives::{Address, B256};
+use criterion::{criterion_group, criterion_main, Criterion};
+use pprof::criterion::{Output, PProfProfiler};
+use reth_db::{
+    create_db,
+    mdbx::{DatabaseArguments, DatabaseFlags},
+    AccountChangeSets, BlockBodyIndices, CanonicalHeaders, ClientVersion, DatabaseEnv, Tables,
+    TransactionBlocks, TransactionSenders,
+};
+use reth_db_api::{
+    cursor::{DbCursorRW, DbDupCursorRW},
+    database::Database,
+    models::{AccountBeforeTx, StoredBlockBodyIndices},
+    transaction::DbTxMut,
+};
+use reth_fs_util as fs;
+use std::path::{Path, PathBuf};
+
+mod utils;
+use utils::BENCH_DB_PATH;
+
+criterion_group! {
+    name = benches;
+    config = Criterion::default().with_profiler(PProfProfiler::new(1, Output::Flamegraph(None)));
+    targets = integer_keys
+}
+criterion_main!(benches);
+
+const BLOCKS: u64 = 100_000;
+const TRANSACTIONS_PER_BLOCK: u64 = 10;
+const ACCOUNT_CHANGES_PER_BLOCK: u8 = 5;
+
+fn integer_keys(c: &mut Criterion) {
+    let mut group = c.benchmark_group("IntegerKeys");
+    group.sample_size(10);
+
+    let path = PathBuf::from(BENCH_DB_PATH).join("integer-keys");
+    for integer_keys in [false, true] {
+        let name = if integer_keys { "integer keys" } else { "encoded keys" };
+
+        group.bench_function(format!("sync {BLOCKS} blocks with {name}"), |b| {
+            b.iter_with_setup(
+                || set_up_db(&path, integer_keys),
+                |db| {
+                    sync(&db);
+                    db
+                },
+            )
+        });
+
+        let db = set_up_db(&path, integer_keys);
+        sync(&db);
+        println!("Size of the tables with {name}: {} KiB", tables_size(&db) / 1024);
+    }
+
+    let _ = fs::remove_dir_all(&path);
+}
+
+/// Sets up an empty database at `path`, with the integer key tables created with or without
+/// integer keys.
+fn set_up_db(path: &Path, integer_keys: bool) -> DatabaseEnv {
+    let _ = fs::remove_dir_all(path);
+    let db = create_db(path, DatabaseArguments::new(ClientVersion::default())).unwrap();
+
+    if !integer_keys {
+        // Create the tables without integer keys, as an older version would have.
+        let tx = db.begin_rw_txn().unwrap();
+        for table in Tables::ALL.iter().filter(|table| table.is_integer_key()) {
+            let flags =
+                if table.is_dupsort() { DatabaseFlags::DUP_SORT } else { DatabaseFlags::default() };
+            tx.create_db(Some(table.name()), flags).unwrap();
+        }
+        tx.commit().unwrap();
+    }
+    db.create_tables().unwrap();
+
+    db
+}
+
+/// Appends the block and transaction number keyed entries of [`BLOCKS`] blocks, the way the
+/// pipeline writes them.
+fn sync(db: &DatabaseEnv) {
+    let tx = db.tx_mut().unwrap();
+    let mut canonical_headers = tx.cursor_write::<CanonicalHeaders>().unwrap();
+    let mut block_body_indices = tx.cursor_write::<BlockBodyIndices>().unwrap();
+    let mut transaction_blocks = tx.cursor_write::<TransactionBlocks>().unwrap();
+    let mut transaction_senders = tx.cursor_write::<TransactionSenders>().unwrap();
+    let mut account_changesets = tx.cursor_dup_write::<AccountChangeSets>().unwrap();
+
+    for block in 0..BLOCKS {
+        let indices = StoredBlockBodyIndices {
+            first_tx_num: block * TRANSACTIONS_PER_BLOCK,
+            tx_count: TRANSACTIONS_PER_BLOCK,
+        };
+
+        canonical_headers.append(block, B256::with_last_byte(block as u8)).unwrap();
+        block_body_indices.append(block, indices).unwrap();
+        transaction_blocks.append(indices.last_tx_num(), block).unwrap();
+        for tx_num in indices.tx_num_range() {
+            transaction_senders.append(tx_num, Address::with_last_byte(tx_num as u8)).unwrap();
+        }
+        for address in 0..ACCOUNT_CHANGES_PER_BLOCK {
+            account_changesets
+                .append_dup(
+                    block,
+                    AccountBeforeTx { address: Address::with_last_byte(address), info: None },
+                )
+                .unwrap();
+        }
+    }
+
+    drop((
+        canonical_headers,
+        block_body_indices,
+        transaction_blocks,
+        transaction_senders,
+        account_changesets,
+    ));
+    tx.commit().unwrap();
+}
+
+/// Returns the size in bytes of the pages used by the integer key tables.
+fn tables_size(db: &DatabaseEnv) -> usize {
+    let tx = db.begin_ro_txn().unwrap();
+    Tables::ALL
+        .iter()
+        .filter(|table| table.is_integer_key())
+        .map(|table| {
+            let stat = tx.db_stat(&tx.open_db(Some(table.name())).unwrap()).unwrap();
+            (stat.branch_pages() + stat.leaf_pages() + stat.overflow_pages()) *
+                stat.page_size() as usize
+        })
+        .sum()
+}
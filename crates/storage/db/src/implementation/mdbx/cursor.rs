@@ -1,7 +1,7 @@
 //! Cursor wrapper for libmdbx-sys.
 
 use crate::{
-    metrics::{DatabaseEnvMetrics, Operation},
+    metrics::{DatabaseEnvMetrics, Operation, TableWrites},
     tables::utils::*,
     DatabaseError,
 };
@@ -29,8 +29,12 @@ pub struct Cursor<K: TransactionKind, T: Table> {
     pub(crate) inner: reth_libmdbx::Cursor<K>,
     /// Cache buffer that receives compressed values.
     buf: Vec<u8>,
+    /// Whether the keys are stored as native-endian integers. See [`Table::INTEGER_KEY`].
+    integer_key: bool,
     /// Reference to metric handles in the DB environment. If `None`, metrics are not recorded.
     metrics: Option<Arc<DatabaseEnvMetrics>>,
+    /// Writes of the transaction of the cursor to the table. If `None`, writes are not counted.
+    writes: Option<Arc<TableWrites>>,
     /// Phantom data to enforce encoding/decoding.
     _dbi: PhantomData<T>,
 }
@@ -38,9 +42,16 @@ pub struct Cursor<K: TransactionKind, T: Table> {
 impl<K: TransactionKind, T: Table> Cursor<K, T> {
     pub(crate) const fn new_with_metrics(
         inner: reth_libmdbx::Cursor<K>,
+        integer_key: bool,
         metrics: Option<Arc<DatabaseEnvMetrics>>,
+        writes: Option<Arc<TableWrites>>,
     ) -> Self {
-        Self { inner, buf: Vec::new(), metrics, _dbi: PhantomData }
+        Self { inner, buf: Vec::new(), integer_key, metrics, writes, _dbi: PhantomData }
+    }
+
+    /// Converts the key into its stored representation.
+    fn stored_key(&self, key: T::Key) -> StoredKey<<T::Key as Encode>::Encoded> {
+        StoredKey::new(key.encode(), self.integer_key)
     }
 
     /// If `self.metrics` is `Some(...)`, record a metric with the provided operation and value
@@ -59,19 +70,33 @@ impl<K: TransactionKind, T: Table> Cursor<K, T> {
             f(self)
         }
     }
+
+    /// If `self.writes` is `Some(...)`, record a successful write operation with the number of
+    /// key and value bytes written.
+    fn record_write(&self, operation: Operation, size: usize) {
+        if let Some(writes) = &self.writes {
+            writes.record(operation, size);
+        }
+    }
 }
 
 /// Decodes a `(key, value)` pair from the database.
+///
+/// If `integer_key` is set, the key is converted from its native-endian stored representation
+/// before decoding.
 #[allow(clippy::type_complexity)]
 pub fn decode<T>(
     res: Result<Option<(Cow<'_, [u8]>, Cow<'_, [u8]>)>, impl Into<DatabaseErrorInfo>>,
+    integer_key: bool,
 ) -> PairResult<T>
 where
     T: Table,
     T::Key: Decode,
     T::Value: Decompress,
 {
-    res.map_err(|e| DatabaseError::Read(e.into()))?.map(decoder::<T>).transpose()
+    res.map_err(|e| DatabaseError::Read(e.into()))?
+        .map(|kv| decoder::<T>(integer_key, kv))
+        .transpose()
 }
 
 /// Some types don't support compression (eg. B256), and we don't want to be copying them to the
@@ -90,36 +115,39 @@ macro_rules! compress_to_buf_or_ref {
 
 impl<K: TransactionKind, T: Table> DbCursorRO<T> for Cursor<K, T> {
     fn first(&mut self) -> PairResult<T> {
-        decode::<T>(self.inner.first())
+        decode::<T>(self.inner.first(), self.integer_key)
     }
 
     fn seek_exact(&mut self, key: <T as Table>::Key) -> PairResult<T> {
-        decode::<T>(self.inner.set_key(key.encode().as_ref()))
+        let key = self.stored_key(key);
+        decode::<T>(self.inner.set_key(key.as_ref()), self.integer_key)
     }
 
     fn seek(&mut self, key: <T as Table>::Key) -> PairResult<T> {
-        decode::<T>(self.inner.set_range(key.encode().as_ref()))
+        let key = self.stored_key(key);
+        decode::<T>(self.inner.set_range(key.as_ref()), self.integer_key)
     }
 
     fn next(&mut self) -> PairResult<T> {
-        decode::<T>(self.inner.next())
+        decode::<T>(self.inner.next(), self.integer_key)
     }
 
     fn prev(&mut self) -> PairResult<T> {
-        decode::<T>(self.inner.prev())
+        decode::<T>(self.inner.prev(), self.integer_key)
     }
 
     fn last(&mut self) -> PairResult<T> {
-        decode::<T>(self.inner.last())
+        decode::<T>(self.inner.last(), self.integer_key)
     }
 
     fn current(&mut self) -> PairResult<T> {
-        decode::<T>(self.inner.get_current())
+        decode::<T>(self.inner.get_current(), self.integer_key)
     }
 
     fn walk(&mut self, start_key: Option<T::Key>) -> Result<Walker<'_, T, Self>, DatabaseError> {
         let start = if let Some(start_key) = start_key {
-            decode::<T>(self.inner.set_range(start_key.encode().as_ref())).transpose()
+            let start_key = self.stored_key(start_key);
+            decode::<T>(self.inner.set_range(start_key.as_ref()), self.integer_key).transpose()
         } else {
             self.first().transpose()
         };
@@ -132,13 +160,16 @@ impl<K: TransactionKind, T: Table> DbCursorRO<T> for Cursor<K, T> {
         range: impl RangeBounds<T::Key>,
     ) -> Result<RangeWalker<'_, T, Self>, DatabaseError> {
         let start = match range.start_bound().cloned() {
-            Bound::Included(key) => self.inner.set_range(key.encode().as_ref()),
+            Bound::Included(key) => {
+                let key = self.stored_key(key);
+                self.inner.set_range(key.as_ref())
+            }
             Bound::Excluded(_key) => {
                 unreachable!("Rust doesn't allow for Bound::Excluded in starting bounds");
             }
             Bound::Unbounded => self.inner.first(),
         };
-        let start = decode::<T>(start).transpose();
+        let start = decode::<T>(start, self.integer_key).transpose();
         Ok(RangeWalker::new(self, start, range.end_bound().cloned()))
     }
 
@@ -147,7 +178,8 @@ impl<K: TransactionKind, T: Table> DbCursorRO<T> for Cursor<K, T> {
         start_key: Option<T::Key>,
     ) -> Result<ReverseWalker<'_, T, Self>, DatabaseError> {
         let start = if let Some(start_key) = start_key {
-            decode::<T>(self.inner.set_range(start_key.encode().as_ref()))
+            let start_key = self.stored_key(start_key);
+            decode::<T>(self.inner.set_range(start_key.as_ref()), self.integer_key)
         } else {
             self.last()
         }
@@ -160,12 +192,12 @@ impl<K: TransactionKind, T: Table> DbCursorRO<T> for Cursor<K, T> {
 impl<K: TransactionKind, T: DupSort> DbDupCursorRO<T> for Cursor<K, T> {
     /// Returns the next `(key, value)` pair of a DUPSORT table.
     fn next_dup(&mut self) -> PairResult<T> {
-        decode::<T>(self.inner.next_dup())
+        decode::<T>(self.inner.next_dup(), self.integer_key)
     }
 
     /// Returns the next `(key, value)` pair skipping the duplicates.
     fn next_no_dup(&mut self) -> PairResult<T> {
-        decode::<T>(self.inner.next_nodup())
+        decode::<T>(self.inner.next_nodup(), self.integer_key)
     }
 
     /// Returns the next `value` of a duplicate `key`.
@@ -182,8 +214,9 @@ impl<K: TransactionKind, T: DupSort> DbDupCursorRO<T> for Cursor<K, T> {
         key: <T as Table>::Key,
         subkey: <T as DupSort>::SubKey,
     ) -> ValueOnlyResult<T> {
+        let key = self.stored_key(key);
         self.inner
-            .get_both_range(key.encode().as_ref(), subkey.encode().as_ref())
+            .get_both_range(key.as_ref(), subkey.encode().as_ref())
             .map_err(|e| DatabaseError::Read(e.into()))?
             .map(decode_one::<T>)
             .transpose()
@@ -202,26 +235,26 @@ impl<K: TransactionKind, T: DupSort> DbDupCursorRO<T> for Cursor<K, T> {
         let start = match (key, subkey) {
             (Some(key), Some(subkey)) => {
                 // encode key and decode it after.
-                let key: Vec<u8> = key.encode().into();
+                let key = self.stored_key(key);
                 self.inner
                     .get_both_range(key.as_ref(), subkey.encode().as_ref())
                     .map_err(|e| DatabaseError::Read(e.into()))?
-                    .map(|val| decoder::<T>((Cow::Owned(key), val)))
+                    .map(|val| decoder::<T>(false, (Cow::Owned(key.into_encoded()), val)))
             }
             (Some(key), None) => {
-                let key: Vec<u8> = key.encode().into();
+                let key = self.stored_key(key);
                 self.inner
                     .set(key.as_ref())
                     .map_err(|e| DatabaseError::Read(e.into()))?
-                    .map(|val| decoder::<T>((Cow::Owned(key), val)))
+                    .map(|val| decoder::<T>(false, (Cow::Owned(key.into_encoded()), val)))
             }
             (None, Some(subkey)) => {
                 if let Some((key, _)) = self.first()? {
-                    let key: Vec<u8> = key.encode().into();
+                    let key = self.stored_key(key);
                     self.inner
                         .get_both_range(key.as_ref(), subkey.encode().as_ref())
                         .map_err(|e| DatabaseError::Read(e.into()))?
-                        .map(|val| decoder::<T>((Cow::Owned(key), val)))
+                        .map(|val| decoder::<T>(false, (Cow::Owned(key.into_encoded()), val)))
                 } else {
                     Some(Err(DatabaseError::Read(MDBXError::NotFound.into())))
                 }
@@ -242,106 +275,114 @@ impl<T: Table> DbCursorRW<T> for Cursor<RW, T> {
     /// to properly upsert, you'll need to `seek_exact` & `delete_current` if the key+subkey was
     /// found, before calling `upsert`.
     fn upsert(&mut self, key: T::Key, value: T::Value) -> Result<(), DatabaseError> {
-        let key = key.encode();
+        let key = self.stored_key(key);
         let value = compress_to_buf_or_ref!(self, value);
-        self.execute_with_operation_metric(
-            Operation::CursorUpsert,
-            Some(value.unwrap_or(&self.buf).len()),
-            |this| {
-                this.inner
-                    .put(key.as_ref(), value.unwrap_or(&this.buf), WriteFlags::UPSERT)
-                    .map_err(|e| {
-                        DatabaseWriteError {
-                            info: e.into(),
-                            operation: DatabaseWriteOperation::CursorUpsert,
-                            table_name: T::NAME,
-                            key: key.into(),
-                        }
-                        .into()
-                    })
-            },
-        )
+        let value_size = value.unwrap_or(&self.buf).len();
+        let size = key.as_ref().len() + value_size;
+        self.execute_with_operation_metric(Operation::CursorUpsert, Some(value_size), |this| {
+            this.inner.put(key.as_ref(), value.unwrap_or(&this.buf), WriteFlags::UPSERT).map_err(
+                |e| {
+                    DatabaseWriteError {
+                        info: e.into(),
+                        operation: DatabaseWriteOperation::CursorUpsert,
+                        table_name: T::NAME,
+                        key: key.into_encoded(),
+                    }
+                    .into()
+                },
+            )
+        })?;
+        self.record_write(Operation::CursorUpsert, size);
+        Ok(())
     }
 
     fn insert(&mut self, key: T::Key, value: T::Value) -> Result<(), DatabaseError> {
-        let key = key.encode();
+        let key = self.stored_key(key);
         let value = compress_to_buf_or_ref!(self, value);
-        self.execute_with_operation_metric(
-            Operation::CursorInsert,
-            Some(value.unwrap_or(&self.buf).len()),
-            |this| {
-                this.inner
-                    .put(key.as_ref(), value.unwrap_or(&this.buf), WriteFlags::NO_OVERWRITE)
-                    .map_err(|e| {
-                        DatabaseWriteError {
-                            info: e.into(),
-                            operation: DatabaseWriteOperation::CursorInsert,
-                            table_name: T::NAME,
-                            key: key.into(),
-                        }
-                        .into()
-                    })
-            },
-        )
+        let value_size = value.unwrap_or(&self.buf).len();
+        let size = key.as_ref().len() + value_size;
+        self.execute_with_operation_metric(Operation::CursorInsert, Some(value_size), |this| {
+            this.inner
+                .put(key.as_ref(), value.unwrap_or(&this.buf), WriteFlags::NO_OVERWRITE)
+                .map_err(|e| {
+                    DatabaseWriteError {
+                        info: e.into(),
+                        operation: DatabaseWriteOperation::CursorInsert,
+                        table_name: T::NAME,
+                        key: key.into_encoded(),
+                    }
+                    .into()
+                })
+        })?;
+        self.record_write(Operation::CursorInsert, size);
+        Ok(())
     }
 
     /// Appends the data to the end of the table. Consequently, the append operation
     /// will fail if the inserted key is less than the last table key
     fn append(&mut self, key: T::Key, value: T::Value) -> Result<(), DatabaseError> {
-        let key = key.encode();
+        let key = self.stored_key(key);
         let value = compress_to_buf_or_ref!(self, value);
-        self.execute_with_operation_metric(
-            Operation::CursorAppend,
-            Some(value.unwrap_or(&self.buf).len()),
-            |this| {
-                this.inner
-                    .put(key.as_ref(), value.unwrap_or(&this.buf), WriteFlags::APPEND)
-                    .map_err(|e| {
-                        DatabaseWriteError {
-                            info: e.into(),
-                            operation: DatabaseWriteOperation::CursorAppend,
-                            table_name: T::NAME,
-                            key: key.into(),
-                        }
-                        .into()
-                    })
-            },
-        )
+        let value_size = value.unwrap_or(&self.buf).len();
+        let size = key.as_ref().len() + value_size;
+        self.execute_with_operation_metric(Operation::CursorAppend, Some(value_size), |this| {
+            this.inner.put(key.as_ref(), value.unwrap_or(&this.buf), WriteFlags::APPEND).map_err(
+                |e| {
+                    DatabaseWriteError {
+                        info: e.into(),
+                        operation: DatabaseWriteOperation::CursorAppend,
+                        table_name: T::NAME,
+                        key: key.into_encoded(),
+                    }
+                    .into()
+                },
+            )
+        })?;
+        self.record_write(Operation::CursorAppend, size);
+        Ok(())
     }
 
     fn delete_current(&mut self) -> Result<(), DatabaseError> {
         self.execute_with_operation_metric(Operation::CursorDeleteCurrent, None, |this| {
             this.inner.del(WriteFlags::CURRENT).map_err(|e| DatabaseError::Delete(e.into()))
-        })
+        })?;
+        self.record_write(Operation::CursorDeleteCurrent, 0);
+        Ok(())
     }
 }
 
 impl<T: DupSort> DbDupCursorRW<T> for Cursor<RW, T> {
     fn delete_current_duplicates(&mut self) -> Result<(), DatabaseError> {
-        self.execute_with_operation_metric(Operation::CursorDeleteCurrentDuplicates, None, |this| {
-            this.inner.del(WriteFlags::NO_DUP_DATA).map_err(|e| DatabaseError::Delete(e.into()))
-        })
+        self.execute_with_operation_metric(
+            Operation::CursorDeleteCurrentDuplicates,
+            None,
+            |this| {
+                this.inner.del(WriteFlags::NO_DUP_DATA).map_err(|e| DatabaseError::Delete(e.into()))
+            },
+        )?;
+        self.record_write(Operation::CursorDeleteCurrentDuplicates, 0);
+        Ok(())
     }
 
     fn append_dup(&mut self, key: T::Key, value: T::Value) -> Result<(), DatabaseError> {
-        let key = key.encode();
+        let key = self.stored_key(key);
         let value = compress_to_buf_or_ref!(self, value);
-        self.execute_with_operation_metric(
-            Operation::CursorAppendDup,
-            Some(value.unwrap_or(&self.buf).len()),
-            |this| {
-                this.inner
-                    .put(key.as_ref(), value.unwrap_or(&this.buf), WriteFlags::APPEND_DUP)
-                    .map_err(|e| {
-                        DatabaseWriteError {
-                            info: e.into(),
-                            operation: DatabaseWriteOperation::CursorAppendDup,
-                            table_name: T::NAME,
-                            key: key.into(),
-                        }
-                        .into()
-                    })
-            },
-        )
+        let value_size = value.unwrap_or(&self.buf).len();
+        let size = key.as_ref().len() + value_size;
+        self.execute_with_operation_metric(Operation::CursorAppendDup, Some(value_size), |this| {
+            this.inner
+                .put(key.as_ref(), value.unwrap_or(&this.buf), WriteFlags::APPEND_DUP)
+                .map_err(|e| {
+                    DatabaseWriteError {
+                        info: e.into(),
+                        operation: DatabaseWriteOperation::CursorAppendDup,
+                        table_name: T::NAME,
+                        key: key.into_encoded(),
+                    }
+                    .into()
+                })
+        })?;
+        self.record_write(Operation::CursorAppendDup, size);
+        Ok(())
     }
 }
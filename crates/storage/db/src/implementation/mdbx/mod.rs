@@ -21,7 +21,7 @@ use reth_libmdbx::{
     MaxReadTransactionDuration, Mode, PageSize, SyncMode, RO, RW,
 };
 use reth_storage_errors::db::LogLevel;
-use reth_tracing::tracing::error;
+use reth_tracing::tracing::{error, info};
 use std::{
     ops::{Deref, Range},
     path::Path,
@@ -97,6 +97,9 @@ pub struct DatabaseArguments {
     ///
     /// This flag affects only at environment opening but can't be changed after.
     exclusive: Option<bool>,
+    /// Whether to count the writes of read-write transactions per table when metrics are
+    /// enabled. Enabled by default.
+    write_metrics: bool,
 }
 
 impl Default for DatabaseArguments {
@@ -119,6 +122,7 @@ impl DatabaseArguments {
             log_level: None,
             max_read_transaction_duration: None,
             exclusive: None,
+            write_metrics: true,
         }
     }
 
@@ -159,6 +163,13 @@ impl DatabaseArguments {
         self
     }
 
+    /// Set whether to count the writes of read-write transactions per table when metrics are
+    /// enabled.
+    pub const fn with_write_metrics(mut self, write_metrics: bool) -> Self {
+        self.write_metrics = write_metrics;
+        self
+    }
+
     /// Returns the client version if any.
     pub const fn client_version(&self) -> &ClientVersion {
         &self.client_version
@@ -172,6 +183,9 @@ pub struct DatabaseEnv {
     inner: Environment,
     /// Cache for metric handles. If `None`, metrics are not recorded.
     metrics: Option<Arc<DatabaseEnvMetrics>>,
+    /// Whether to count the writes of read-write transactions per table when metrics are
+    /// enabled. See [`DatabaseArguments::with_write_metrics`].
+    write_metrics: bool,
     /// Write lock for when dealing with a read-write environment.
     _lock_file: Option<StorageLock>,
 }
@@ -432,6 +446,7 @@ impl DatabaseEnv {
         let env = Self {
             inner: inner_env.open(path).map_err(|e| DatabaseError::Open(e.into()))?,
             metrics: None,
+            write_metrics: args.write_metrics,
             _lock_file,
         };
 
@@ -440,7 +455,7 @@ impl DatabaseEnv {
 
     /// Enables metrics on the database.
     pub fn with_metrics(mut self) -> Self {
-        self.metrics = Some(DatabaseEnvMetrics::new().into());
+        self.metrics = Some(DatabaseEnvMetrics::new(self.write_metrics).into());
         self
     }
 
@@ -450,19 +465,44 @@ impl DatabaseEnv {
     }
 
     /// Creates all the tables defined in the given [`TableSet`], if necessary.
+    ///
+    /// Existing tables that were created with the flags of an older version, e.g. without
+    /// `MDBX_INTEGERKEY`, are kept as they are, and accessed according to the flags they were
+    /// created with until they're migrated.
     pub fn create_tables_for<TS: TableSet>(&self) -> Result<(), DatabaseError> {
         let tx = self.inner.begin_rw_txn().map_err(|e| DatabaseError::InitTx(e.into()))?;
 
+        let mut outdated_tables = Vec::new();
         for table in TS::tables() {
             let flags =
-                if table.is_dupsort() { DatabaseFlags::DUP_SORT } else { DatabaseFlags::default() };
-
-            tx.create_db(Some(table.name()), flags)
-                .map_err(|e| DatabaseError::CreateTable(e.into()))?;
+                table_flags(table.is_dupsort(), table.is_integer_key(), table.is_dupfixed());
+
+            match tx.open_db(Some(table.name())) {
+                Ok(db) => {
+                    let created_with =
+                        tx.db_flags(&db).map_err(|e| DatabaseError::Open(e.into()))?;
+                    if created_with != flags {
+                        outdated_tables.push(table.name());
+                    }
+                }
+                Err(reth_libmdbx::Error::NotFound) => {
+                    tx.create_db(Some(table.name()), flags)
+                        .map_err(|e| DatabaseError::CreateTable(e.into()))?;
+                }
+                Err(err) => return Err(DatabaseError::Open(err.into())),
+            }
         }
 
         tx.commit().map_err(|e| DatabaseError::Commit(e.into()))?;
 
+        if !outdated_tables.is_empty() {
+            info!(
+                target: "storage::db::mdbx",
+                tables = ?outdated_tables,
+                "Database tables were created with the flags of an older version and need to be migrated"
+            );
+        }
+
         Ok(())
     }
 
@@ -496,6 +536,21 @@ impl Deref for DatabaseEnv {
     }
 }
 
+/// Returns the flags of a table with the given [`Table`](reth_db_api::table::Table) definition.
+pub(crate) fn table_flags(dupsort: bool, integer_key: bool, dup_fixed: bool) -> DatabaseFlags {
+    let mut flags = DatabaseFlags::default();
+    if dupsort {
+        flags |= DatabaseFlags::DUP_SORT;
+        if dup_fixed {
+            flags |= DatabaseFlags::DUP_FIXED;
+        }
+    }
+    if integer_key {
+        flags |= DatabaseFlags::INTEGER_KEY;
+    }
+    flags
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1042,6 +1097,83 @@ mod tests {
         tx.commit().expect(ERROR_COMMIT);
     }
 
+    #[test]
+    fn db_integer_key_ordering() {
+        let db: Arc<DatabaseEnv> = create_test_db(DatabaseEnvKind::RW);
+        let keys = vec![0, 255, 256, 1 << 32, u64::MAX];
+
+        // APPEND
+        let tx = db.tx_mut().expect(ERROR_INIT_TX);
+        assert_eq!(tx.is_integer_key::<CanonicalHeaders>(), Ok(true));
+        let mut cursor = tx.cursor_write::<CanonicalHeaders>().unwrap();
+        for key in keys.clone() {
+            assert_eq!(cursor.append(key, B256::ZERO), Ok(()));
+        }
+        tx.commit().expect(ERROR_COMMIT);
+
+        // Confirm the result
+        let tx = db.tx().expect(ERROR_INIT_TX);
+        let mut cursor = tx.cursor_read::<CanonicalHeaders>().unwrap();
+        let res = cursor.walk(None).unwrap().map(|res| res.unwrap().0).collect::<Vec<_>>();
+        assert_eq!(res, keys);
+        assert_eq!(cursor.seek(257).unwrap().map(|(key, _)| key), Some(1 << 32));
+        assert_eq!(cursor.last().unwrap().map(|(key, _)| key), Some(u64::MAX));
+        assert_eq!(tx.get::<CanonicalHeaders>(256), Ok(Some(B256::ZERO)));
+        tx.commit().expect(ERROR_COMMIT);
+
+        // A transaction created without the environment reads the keys the same way
+        let tx = Tx::new(db.begin_ro_txn().expect(ERROR_INIT_TX));
+        let mut cursor = tx.cursor_read::<CanonicalHeaders>().unwrap();
+        let res = cursor.walk(None).unwrap().map(|res| res.unwrap().0).collect::<Vec<_>>();
+        assert_eq!(res, keys);
+    }
+
+    #[test]
+    fn db_legacy_integer_key_table() {
+        let path = TempDir::new().expect(ERROR_TEMPDIR).into_path();
+
+        // Create the table without `MDBX_INTEGERKEY`, as an older version would have.
+        {
+            let env = DatabaseEnv::open(&path, DatabaseEnvKind::RW, DatabaseArguments::default())
+                .expect(ERROR_DB_CREATION);
+            let tx = env.begin_rw_txn().expect(ERROR_INIT_TX);
+            tx.create_db(Some(CanonicalHeaders::NAME), DatabaseFlags::default()).unwrap();
+            tx.commit().expect(ERROR_COMMIT);
+        }
+
+        let db = create_test_db_with_path(DatabaseEnvKind::RW, &path);
+
+        let tx = db.tx_mut().expect(ERROR_INIT_TX);
+        assert_eq!(tx.is_integer_key::<CanonicalHeaders>(), Ok(false));
+        assert_eq!(tx.is_integer_key::<Headers>(), Ok(true));
+        for key in [255, 256] {
+            tx.put::<CanonicalHeaders>(key, B256::ZERO).expect(ERROR_PUT);
+        }
+        tx.commit().expect(ERROR_COMMIT);
+
+        let tx = db.tx().expect(ERROR_INIT_TX);
+        let mut cursor = tx.cursor_read::<CanonicalHeaders>().unwrap();
+        let res = cursor.walk(None).unwrap().map(|res| res.unwrap().0).collect::<Vec<_>>();
+        assert_eq!(res, vec![255, 256]);
+        tx.commit().expect(ERROR_COMMIT);
+
+        // Recreating the table switches it to integer keys
+        let tx = db.tx_mut().expect(ERROR_INIT_TX);
+        tx.recreate_table::<CanonicalHeaders>().unwrap();
+        assert_eq!(tx.is_integer_key::<CanonicalHeaders>(), Ok(true));
+        assert_eq!(tx.entries::<CanonicalHeaders>(), Ok(0));
+        tx.put::<CanonicalHeaders>(256, B256::ZERO).expect(ERROR_PUT);
+        tx.put::<CanonicalHeaders>(255, B256::ZERO).expect(ERROR_PUT);
+        tx.commit().expect(ERROR_COMMIT);
+
+        let tx = db.tx().expect(ERROR_INIT_TX);
+        assert_eq!(tx.is_integer_key::<CanonicalHeaders>(), Ok(true));
+        let mut cursor = tx.cursor_read::<CanonicalHeaders>().unwrap();
+        let res = cursor.walk(None).unwrap().map(|res| res.unwrap().0).collect::<Vec<_>>();
+        assert_eq!(res, vec![255, 256]);
+        tx.commit().expect(ERROR_COMMIT);
+    }
+
     #[test]
     fn db_cursor_append_failure() {
         let db: Arc<DatabaseEnv> = create_test_db(DatabaseEnvKind::RW);
@@ -1299,6 +1431,51 @@ mod tests {
         }
     }
 
+    #[test]
+    fn db_walk_dup_prefix_matches_full_scan() {
+        use proptest::{collection::btree_map, prelude::*};
+
+        proptest!(ProptestConfig::with_cases(32), |(
+            storages in btree_map(any::<u8>(), btree_map(any::<u8>(), any::<u64>(), 0..8), 0..8),
+            keys in proptest::collection::vec(any::<u8>(), 1..8),
+        )| {
+            let env = create_test_db(DatabaseEnvKind::RW);
+            env.update(|tx| {
+                for (address, slots) in &storages {
+                    for (slot, value) in slots {
+                        let entry = StorageEntry {
+                            key: B256::with_last_byte(*slot),
+                            value: U256::from(*value),
+                        };
+                        tx.put::<PlainStorageState>(Address::with_last_byte(*address), entry)
+                            .expect(ERROR_PUT);
+                    }
+                }
+            })
+            .unwrap();
+
+            let tx = env.tx().expect(ERROR_INIT_TX);
+            let mut cursor = tx.cursor_dup_read::<PlainStorageState>().unwrap();
+            for key in keys {
+                let key = Address::with_last_byte(key);
+                let expected = cursor
+                    .walk(None)
+                    .unwrap()
+                    .collect::<Result<Vec<_>, _>>()
+                    .unwrap()
+                    .into_iter()
+                    .filter(|(address, _)| *address == key)
+                    .collect::<Vec<_>>();
+                let walked = cursor
+                    .walk_dup_prefix(key)
+                    .unwrap()
+                    .collect::<Result<Vec<_>, _>>()
+                    .unwrap();
+                prop_assert_eq!(walked, expected);
+            }
+        });
+    }
+
     #[test]
     fn dup_value_with_same_subkey() {
         let env = create_test_db(DatabaseEnvKind::RW);
@@ -1,16 +1,20 @@
 //! Transaction wrapper for libmdbx-sys.
 
-use super::cursor::Cursor;
+use super::{cursor::Cursor, table_flags};
 use crate::{
-    metrics::{DatabaseEnvMetrics, Operation, TransactionMode, TransactionOutcome},
-    tables::utils::decode_one,
+    metrics::{
+        DatabaseEnvMetrics, Operation, TransactionMode, TransactionOutcome, TransactionWrites,
+    },
+    tables::utils::{decode_one, StoredKey},
     DatabaseError,
 };
 use reth_db_api::{
     table::{Compress, DupSort, Encode, Table, TableImporter},
     transaction::{DbTx, DbTxMut},
 };
-use reth_libmdbx::{ffi::MDBX_dbi, CommitLatency, Transaction, TransactionKind, WriteFlags, RW};
+use reth_libmdbx::{
+    ffi::MDBX_dbi, CommitLatency, DatabaseFlags, Transaction, TransactionKind, WriteFlags, RW,
+};
 use reth_storage_errors::db::{DatabaseWriteError, DatabaseWriteOperation};
 use reth_tracing::tracing::{debug, trace, warn};
 use std::{
@@ -82,16 +86,33 @@ impl<K: TransactionKind> Tx<K> {
             .map_err(|e| DatabaseError::Open(e.into()))
     }
 
+    /// Gets a table database handle, and whether the table keys are stored as native-endian
+    /// integers.
+    ///
+    /// Integer key tables (see [`Table::INTEGER_KEY`]) created by an older version store their
+    /// keys as they are encoded until they're recreated, so it's read from the flags the table was
+    /// created with.
+    fn get_dbi_with_key_mode<T: Table>(&self) -> Result<(MDBX_dbi, bool), DatabaseError> {
+        let db = self.inner.open_db(Some(T::NAME)).map_err(|e| DatabaseError::Open(e.into()))?;
+        let integer_key = T::INTEGER_KEY &&
+            self.inner
+                .db_flags(&db)
+                .map_err(|e| DatabaseError::Open(e.into()))?
+                .contains(DatabaseFlags::INTEGER_KEY);
+        Ok((db.dbi(), integer_key))
+    }
+
     /// Create db Cursor
     pub fn new_cursor<T: Table>(&self) -> Result<Cursor<K, T>, DatabaseError> {
-        let inner = self
-            .inner
-            .cursor_with_dbi(self.get_dbi::<T>()?)
-            .map_err(|e| DatabaseError::InitCursor(e.into()))?;
+        let (dbi, integer_key) = self.get_dbi_with_key_mode::<T>()?;
+        let inner =
+            self.inner.cursor_with_dbi(dbi).map_err(|e| DatabaseError::InitCursor(e.into()))?;
 
         Ok(Cursor::new_with_metrics(
             inner,
+            integer_key,
             self.metrics_handler.as_ref().map(|h| h.env_metrics.clone()),
+            self.metrics_handler.as_ref().and_then(|h| h.writes.as_ref()).map(|w| w.table(T::NAME)),
         ))
     }
 
@@ -128,6 +149,15 @@ impl<K: TransactionKind> Tx<K> {
 
             let (result, commit_latency, close_duration) = run(self);
             let open_duration = metrics_handler.start.elapsed();
+            if let Some((writes, commit_latency)) =
+                metrics_handler.writes.as_ref().zip(commit_latency.as_ref())
+            {
+                metrics_handler.env_metrics.record_committed_writes(
+                    metrics_handler.stage,
+                    writes,
+                    commit_latency,
+                );
+            }
             metrics_handler.env_metrics.record_closed_transaction(
                 metrics_handler.transaction_mode(),
                 outcome,
@@ -161,6 +191,14 @@ impl<K: TransactionKind> Tx<K> {
             f(&self.inner)
         }
     }
+
+    /// If the writes of the transaction are counted, record a successful write operation to the
+    /// table with the number of key and value bytes written.
+    fn record_write<T: Table>(&self, operation: Operation, size: usize) {
+        if let Some(writes) = self.metrics_handler.as_ref().and_then(|h| h.writes.as_ref()) {
+            writes.table(T::NAME).record(operation, size);
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -182,6 +220,11 @@ struct MetricsHandler<K: TransactionKind> {
     backtrace_recorded: AtomicBool,
     /// Shared database environment metrics.
     env_metrics: Arc<DatabaseEnvMetrics>,
+    /// Writes of a read-write transaction, shared with its cursors. [None] for read-only
+    /// transactions, or if the writes aren't counted.
+    writes: Option<TransactionWrites>,
+    /// The stage that the writes of the transaction are attributed to.
+    stage: Option<&'static str>,
     /// Backtrace of the location where the transaction has been opened. Reported only with debug
     /// assertions, because capturing the backtrace on every transaction opening is expensive.
     #[cfg(debug_assertions)]
@@ -200,6 +243,9 @@ impl<K: TransactionKind> MetricsHandler<K> {
             backtrace_recorded: AtomicBool::new(false),
             #[cfg(debug_assertions)]
             open_backtrace: Backtrace::force_capture(),
+            writes: (!K::IS_READ_ONLY && env_metrics.record_writes())
+                .then(TransactionWrites::default),
+            stage: None,
             env_metrics,
             _marker: PhantomData,
         }
@@ -290,8 +336,10 @@ impl<K: TransactionKind> DbTx for Tx<K> {
         &self,
         key: &<T::Key as Encode>::Encoded,
     ) -> Result<Option<T::Value>, DatabaseError> {
+        let (dbi, integer_key) = self.get_dbi_with_key_mode::<T>()?;
+        let key = StoredKey::new(key.as_ref(), integer_key);
         self.execute_with_operation_metric::<T, _>(Operation::Get, None, |tx| {
-            tx.get(self.get_dbi::<T>()?, key.as_ref())
+            tx.get(dbi, key.as_ref())
                 .map_err(|e| DatabaseError::Read(e.into()))?
                 .map(decode_one::<T>)
                 .transpose()
@@ -332,6 +380,10 @@ impl<K: TransactionKind> DbTx for Tx<K> {
             .entries())
     }
 
+    fn is_integer_key<T: Table>(&self) -> Result<bool, DatabaseError> {
+        Ok(self.get_dbi_with_key_mode::<T>()?.1)
+    }
+
     /// Disables long-lived read transaction safety guarantees, such as backtrace recording and
     /// timeout.
     fn disable_long_read_transaction_safety(&mut self) {
@@ -348,23 +400,27 @@ impl DbTxMut for Tx<RW> {
     type DupCursorMut<T: DupSort> = Cursor<RW, T>;
 
     fn put<T: Table>(&self, key: T::Key, value: T::Value) -> Result<(), DatabaseError> {
-        let key = key.encode();
+        let (dbi, integer_key) = self.get_dbi_with_key_mode::<T>()?;
+        let key = StoredKey::new(key.encode(), integer_key);
         let value = value.compress();
+        let size = key.as_ref().len() + value.as_ref().len();
         self.execute_with_operation_metric::<T, _>(
             Operation::Put,
             Some(value.as_ref().len()),
             |tx| {
-                tx.put(self.get_dbi::<T>()?, key.as_ref(), value, WriteFlags::UPSERT).map_err(|e| {
+                tx.put(dbi, key.as_ref(), value, WriteFlags::UPSERT).map_err(|e| {
                     DatabaseWriteError {
                         info: e.into(),
                         operation: DatabaseWriteOperation::Put,
                         table_name: T::NAME,
-                        key: key.into(),
+                        key: key.into_encoded(),
                     }
                     .into()
                 })
             },
-        )
+        )?;
+        self.record_write::<T>(Operation::Put, size);
+        Ok(())
     }
 
     fn delete<T: Table>(
@@ -379,10 +435,16 @@ impl DbTxMut for Tx<RW> {
             data = Some(value.as_ref());
         };
 
-        self.execute_with_operation_metric::<T, _>(Operation::Delete, None, |tx| {
-            tx.del(self.get_dbi::<T>()?, key.encode(), data)
-                .map_err(|e| DatabaseError::Delete(e.into()))
-        })
+        let (dbi, integer_key) = self.get_dbi_with_key_mode::<T>()?;
+        let key = StoredKey::new(key.encode(), integer_key);
+        let deleted =
+            self.execute_with_operation_metric::<T, _>(Operation::Delete, None, |tx| {
+                tx.del(dbi, key, data).map_err(|e| DatabaseError::Delete(e.into()))
+            })?;
+        if deleted {
+            self.record_write::<T>(Operation::Delete, 0);
+        }
+        Ok(deleted)
     }
 
     fn clear<T: Table>(&self) -> Result<(), DatabaseError> {
@@ -391,6 +453,18 @@ impl DbTxMut for Tx<RW> {
         Ok(())
     }
 
+    fn recreate_table<T: Table>(&self) -> Result<(), DatabaseError> {
+        let db = self.inner.open_db(Some(T::NAME)).map_err(|e| DatabaseError::Open(e.into()))?;
+        // SAFETY: the handle isn't used after it's dropped, and cursors of the table are
+        // invalidated by MDBX, so that they fail instead of reading the dropped table.
+        unsafe { self.inner.drop_db(db) }.map_err(|e| DatabaseError::Delete(e.into()))?;
+        self.inner
+            .create_db(Some(T::NAME), table_flags(T::DUPSORT, T::INTEGER_KEY, T::DUP_FIXED))
+            .map_err(|e| DatabaseError::CreateTable(e.into()))?;
+
+        Ok(())
+    }
+
     fn cursor_write<T: Table>(&self) -> Result<Self::CursorMut<T>, DatabaseError> {
         self.new_cursor()
     }
@@ -398,6 +472,12 @@ impl DbTxMut for Tx<RW> {
     fn cursor_dup_write<T: DupSort>(&self) -> Result<Self::DupCursorMut<T>, DatabaseError> {
         self.new_cursor()
     }
+
+    fn set_stage_label(&mut self, stage: &'static str) {
+        if let Some(metrics_handler) = self.metrics_handler.as_mut() {
+            metrics_handler.stage = Some(stage);
+        }
+    }
 }
 
 #[cfg(test)]
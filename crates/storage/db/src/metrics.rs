@@ -1,12 +1,22 @@
 use crate::Tables;
 use metrics::Histogram;
 use reth_metrics::{metrics::Counter, Metrics};
+use reth_stages_types::StageId;
 use rustc_hash::FxHashMap;
-use std::time::{Duration, Instant};
+use std::{
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex, MutexGuard, PoisonError,
+    },
+    time::{Duration, Instant},
+};
 use strum::{EnumCount, EnumIter, IntoEnumIterator};
 
 const LARGE_VALUE_THRESHOLD_BYTES: usize = 4096;
 
+/// Stage label of the writes of transactions that weren't attributed to a stage.
+const UNKNOWN_STAGE: &str = "none";
+
 /// Caches metric handles for database environment to make sure handles are not re-created
 /// on every operation.
 ///
@@ -23,19 +33,43 @@ pub(crate) struct DatabaseEnvMetrics {
     /// outcome. Can only be updated at tx close, as outcome is only known at that point.
     transaction_outcomes:
         FxHashMap<(TransactionMode, TransactionOutcome), TransactionOutcomeMetrics>,
+    /// If `true`, the writes of read-write transactions are counted per table and reported on
+    /// commit. See [`TransactionWrites`].
+    record_writes: bool,
+    /// Caches `TableWriteMetrics` handles for each table and known stage tuple. Empty if the
+    /// writes aren't counted.
+    table_writes: FxHashMap<(&'static str, &'static str), TableWriteMetrics>,
+    /// Caches `CommitMetrics` handles for each known stage. Empty if the writes aren't counted.
+    commits: FxHashMap<&'static str, CommitMetrics>,
 }
 
 impl DatabaseEnvMetrics {
-    pub(crate) fn new() -> Self {
+    pub(crate) fn new(record_writes: bool) -> Self {
         // Pre-populate metric handle maps with all possible combinations of labels
         // to avoid runtime locks on the map when recording metrics.
         Self {
             operations: Self::generate_operation_handles(),
             transactions: Self::generate_transaction_handles(),
             transaction_outcomes: Self::generate_transaction_outcome_handles(),
+            record_writes,
+            table_writes: if record_writes {
+                Self::generate_table_write_handles()
+            } else {
+                Default::default()
+            },
+            commits: if record_writes {
+                Self::generate_commit_handles()
+            } else {
+                Default::default()
+            },
         }
     }
 
+    /// Returns `true` if the writes of read-write transactions are counted per table.
+    pub(crate) const fn record_writes(&self) -> bool {
+        self.record_writes
+    }
+
     /// Generate a map of all possible operation handles for each table and operation tuple.
     /// Used for tracking all operation metrics.
     fn generate_operation_handles() -> FxHashMap<(&'static str, Operation), OperationMetrics> {
@@ -95,6 +129,45 @@ impl DatabaseEnvMetrics {
         transaction_outcomes
     }
 
+    /// Returns the labels of the stages that the writes of transactions are known to be
+    /// attributed to.
+    fn known_stages() -> impl Iterator<Item = &'static str> {
+        StageId::ALL
+            .iter()
+            .chain([&StageId::IndexLogAddresses])
+            .map(|stage| stage.as_str())
+            .chain([UNKNOWN_STAGE])
+    }
+
+    /// Generate a map of all table and known stage handles.
+    /// Used for tracking the committed writes to each table.
+    fn generate_table_write_handles() -> FxHashMap<(&'static str, &'static str), TableWriteMetrics>
+    {
+        Tables::ALL
+            .iter()
+            .flat_map(|table| Self::known_stages().map(move |stage| (table.name(), stage)))
+            .map(|(table, stage)| {
+                (
+                    (table, stage),
+                    TableWriteMetrics::new_with_labels(&[
+                        (Labels::Table.as_str(), table),
+                        (Labels::Stage.as_str(), stage),
+                    ]),
+                )
+            })
+            .collect()
+    }
+
+    /// Generate a map of all known stage handles.
+    /// Used for tracking the size and latency of commits.
+    fn generate_commit_handles() -> FxHashMap<&'static str, CommitMetrics> {
+        Self::known_stages()
+            .map(|stage| {
+                (stage, CommitMetrics::new_with_labels(&[(Labels::Stage.as_str(), stage)]))
+            })
+            .collect()
+    }
+
     /// Record a metric for database operation executed in `f`.
     /// Panics if a metric recorder is not found for the given table and operation.
     pub(crate) fn record_operation<R>(
@@ -139,6 +212,90 @@ impl DatabaseEnvMetrics {
             .expect("transaction outcome metric handle not found")
             .record(open_duration, close_duration, commit_latency);
     }
+
+    /// Record the writes of a committed read-write transaction, attributed to the stage that
+    /// executed it.
+    ///
+    /// Handles of custom stages aren't cached, so they're created on every commit.
+    #[cfg(feature = "mdbx")]
+    pub(crate) fn record_committed_writes(
+        &self,
+        stage: Option<&'static str>,
+        writes: &TransactionWrites,
+        commit_latency: &reth_libmdbx::CommitLatency,
+    ) {
+        let stage = stage.unwrap_or(UNKNOWN_STAGE);
+
+        let mut bytes = 0;
+        for (table, table_writes) in writes.tables().iter() {
+            let puts = table_writes.puts.load(Ordering::Relaxed);
+            let deletes = table_writes.deletes.load(Ordering::Relaxed);
+            if puts == 0 && deletes == 0 {
+                continue
+            }
+
+            let table_bytes = table_writes.bytes.load(Ordering::Relaxed);
+            bytes += table_bytes;
+            if let Some(metrics) = self.table_writes.get(&(*table, stage)) {
+                metrics.record(puts, deletes, table_bytes);
+            } else if self.operations.contains_key(&(*table, Operation::Put)) {
+                // only the tables of `Tables` are reported, which keeps the cardinality of the
+                // table label bounded
+                TableWriteMetrics::new_with_labels(&[
+                    (Labels::Table.as_str(), *table),
+                    (Labels::Stage.as_str(), stage),
+                ])
+                .record(puts, deletes, table_bytes);
+            }
+        }
+
+        if let Some(metrics) = self.commits.get(stage) {
+            metrics.record(bytes, commit_latency);
+        } else {
+            CommitMetrics::new_with_labels(&[(Labels::Stage.as_str(), stage)])
+                .record(bytes, commit_latency);
+        }
+    }
+}
+
+/// Counts the writes of a read-write transaction to each table, until they're reported on commit
+/// by [`DatabaseEnvMetrics::record_committed_writes`].
+///
+/// A table is only registered once it's first written to, or a cursor is opened on it.
+#[derive(Debug, Default)]
+pub(crate) struct TransactionWrites {
+    tables: Mutex<FxHashMap<&'static str, Arc<TableWrites>>>,
+}
+
+impl TransactionWrites {
+    /// Returns the writes to the table, registering it if needed.
+    pub(crate) fn table(&self, table: &'static str) -> Arc<TableWrites> {
+        self.tables().entry(table).or_default().clone()
+    }
+
+    fn tables(&self) -> MutexGuard<'_, FxHashMap<&'static str, Arc<TableWrites>>> {
+        self.tables.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// Writes of a transaction to a single table.
+#[derive(Debug, Default)]
+pub(crate) struct TableWrites {
+    puts: AtomicU64,
+    deletes: AtomicU64,
+    bytes: AtomicU64,
+}
+
+impl TableWrites {
+    /// Record a successful write operation, with the number of key and value bytes written.
+    pub(crate) fn record(&self, operation: Operation, size: usize) {
+        if operation.is_delete() {
+            self.deletes.fetch_add(1, Ordering::Relaxed);
+        } else {
+            self.puts.fetch_add(1, Ordering::Relaxed);
+            self.bytes.fetch_add(size as u64, Ordering::Relaxed);
+        }
+    }
 }
 
 /// Transaction mode for the database, either read-only or read-write.
@@ -230,6 +387,14 @@ impl Operation {
             Self::CursorDeleteCurrentDuplicates => "cursor-delete-current-duplicates",
         }
     }
+
+    /// Returns `true` if the operation deletes entries.
+    pub(crate) const fn is_delete(&self) -> bool {
+        matches!(
+            self,
+            Self::Delete | Self::CursorDeleteCurrent | Self::CursorDeleteCurrentDuplicates
+        )
+    }
 }
 
 /// Enum defining labels for various aspects used in metrics.
@@ -242,6 +407,8 @@ enum Labels {
     TransactionOutcome,
     /// Label representing a database operation.
     Operation,
+    /// Label representing the stage that wrote to the database.
+    Stage,
 }
 
 impl Labels {
@@ -252,6 +419,7 @@ impl Labels {
             Self::TransactionMode => "mode",
             Self::TransactionOutcome => "outcome",
             Self::Operation => "operation",
+            Self::Stage => "stage",
         }
     }
 }
@@ -360,3 +528,42 @@ impl OperationMetrics {
         }
     }
 }
+
+#[derive(Metrics, Clone)]
+#[metrics(scope = "database.writes")]
+pub(crate) struct TableWriteMetrics {
+    /// Total number of committed put operations (`put/upsert/insert/append/append_dup`)
+    puts_total: Counter,
+    /// Total number of committed delete operations
+    deletes_total: Counter,
+    /// Total number of committed key and value bytes of put operations
+    bytes_total: Counter,
+}
+
+impl TableWriteMetrics {
+    pub(crate) fn record(&self, puts: u64, deletes: u64, bytes: u64) {
+        self.puts_total.increment(puts);
+        self.deletes_total.increment(deletes);
+        self.bytes_total.increment(bytes);
+    }
+}
+
+#[derive(Metrics, Clone)]
+#[metrics(scope = "database.writes")]
+pub(crate) struct CommitMetrics {
+    /// Total number of key and value bytes written by a committed transaction
+    commit_bytes: Histogram,
+    /// The time it took to sync written data to the disk/storage during transaction commit
+    commit_sync_duration_seconds: Histogram,
+    /// The total duration of a transaction commit
+    commit_whole_duration_seconds: Histogram,
+}
+
+impl CommitMetrics {
+    #[cfg(feature = "mdbx")]
+    pub(crate) fn record(&self, bytes: u64, commit_latency: &reth_libmdbx::CommitLatency) {
+        self.commit_bytes.record(bytes as f64);
+        self.commit_sync_duration_seconds.record(commit_latency.sync());
+        self.commit_whole_duration_seconds.record(commit_latency.whole());
+    }
+}
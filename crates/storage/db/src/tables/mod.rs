@@ -27,7 +27,7 @@ use reth_db_api::{
         blocks::{HeaderHash, StoredBlockOmmers},
         storage_sharded_key::StorageShardedKey,
         AccountBeforeTx, ClientVersion, CompactU256, IntegerList, ShardedKey,
-        StoredBlockBodyIndices, StoredBlockWithdrawals,
+        StoredBlockBodyIndices, StoredBlockWithdrawals, StoredForkchoiceState,
     },
     table::{Decode, DupSort, Encode, Table, TableInfo},
 };
@@ -111,6 +111,9 @@ macro_rules! tables {
     (@bool) => { false };
     (@bool $($t:tt)+) => { true };
 
+    (@flag) => { false };
+    (@flag $flag:literal) => { $flag };
+
     (@view $name:ident $v:ident) => { $v.view::<$name>() };
     (@view $name:ident $v:ident $_subkey:ty) => { $v.view_dupsort::<$name>() };
 
@@ -122,7 +125,7 @@ macro_rules! tables {
         concat!("`", stringify!($value), "`")
     };
 
-    ($($(#[$attr:meta])* table $name:ident$(<$($generic:ident $(= $default:ty)?),*>)? { type Key = $key:ty; type Value = $value:ty; $(type SubKey = $subkey:ty;)? } )*) => {
+    ($($(#[$attr:meta])* table $name:ident$(<$($generic:ident $(= $default:ty)?),*>)? { type Key = $key:ty; type Value = $value:ty; $(type SubKey = $subkey:ty;)? $(const INTEGER_KEY: bool = $integer_key:literal;)? $(const DUP_FIXED: bool = $dup_fixed:literal;)? } )*) => {
         // Table marker types.
         $(
             $(#[$attr])*
@@ -150,6 +153,8 @@ macro_rules! tables {
             {
                 const NAME: &'static str = table_names::$name;
                 const DUPSORT: bool = tables!(@bool $($subkey)?);
+                const INTEGER_KEY: bool = tables!(@flag $($integer_key)?);
+                const DUP_FIXED: bool = tables!(@flag $($dup_fixed)?);
 
                 type Key = $key;
                 type Value = $value;
@@ -201,6 +206,24 @@ macro_rules! tables {
                 }
             }
 
+            /// Returns `true` if the table keys are stored as native-endian integers.
+            pub const fn is_integer_key(&self) -> bool {
+                match self {
+                    $(
+                        Self::$name => tables!(@flag $($integer_key)?),
+                    )*
+                }
+            }
+
+            /// Returns `true` if the values of the `DUPSORT` table have the same size.
+            pub const fn is_dupfixed(&self) -> bool {
+                match self {
+                    $(
+                        Self::$name => tables!(@flag $($dup_fixed)?),
+                    )*
+                }
+            }
+
             /// The type of the given table in database.
             pub const fn table_type(&self) -> TableType {
                 if self.is_dupsort() {
@@ -258,6 +281,14 @@ macro_rules! tables {
             fn is_dupsort(&self) -> bool {
                 self.is_dupsort()
             }
+
+            fn is_integer_key(&self) -> bool {
+                self.is_integer_key()
+            }
+
+            fn is_dupfixed(&self) -> bool {
+                self.is_dupfixed()
+            }
         }
 
         impl TableSet for Tables {
@@ -304,17 +335,22 @@ macro_rules! tables {
     };
 }
 
+// Tables keyed by block or transaction numbers store their keys as integers (`INTEGER_KEY`). None
+// of the `DUPSORT` tables packs its values (`DUP_FIXED`), as they're all compressed to a variable
+// size.
 tables! {
     /// Stores the header hashes belonging to the canonical chain.
     table CanonicalHeaders {
         type Key = BlockNumber;
         type Value = HeaderHash;
+        const INTEGER_KEY: bool = true;
     }
 
     /// Stores the total difficulty from a block header.
     table HeaderTerminalDifficulties {
         type Key = BlockNumber;
         type Value = CompactU256;
+        const INTEGER_KEY: bool = true;
     }
 
     /// Stores the block number corresponding to a header.
@@ -327,6 +363,7 @@ tables! {
     table Headers<H = Header> {
         type Key = BlockNumber;
         type Value = H;
+        const INTEGER_KEY: bool = true;
     }
 
     /// Stores block indices that contains indexes of transaction and the count of them.
@@ -335,24 +372,28 @@ tables! {
     table BlockBodyIndices {
         type Key = BlockNumber;
         type Value = StoredBlockBodyIndices;
+        const INTEGER_KEY: bool = true;
     }
 
     /// Stores the uncles/ommers of the block.
     table BlockOmmers<H = Header> {
         type Key = BlockNumber;
         type Value = StoredBlockOmmers<H>;
+        const INTEGER_KEY: bool = true;
     }
 
     /// Stores the block withdrawals.
     table BlockWithdrawals {
         type Key = BlockNumber;
         type Value = StoredBlockWithdrawals;
+        const INTEGER_KEY: bool = true;
     }
 
     /// Canonical only Stores the transaction body for canonical transactions.
     table Transactions<T = TransactionSigned> {
         type Key = TxNumber;
         type Value = T;
+        const INTEGER_KEY: bool = true;
     }
 
     /// Stores the mapping of the transaction hash to the transaction number.
@@ -367,12 +408,14 @@ tables! {
     table TransactionBlocks {
         type Key = TxNumber;
         type Value = BlockNumber;
+        const INTEGER_KEY: bool = true;
     }
 
     /// Canonical only Stores transaction receipts.
     table Receipts<R = Receipt> {
         type Key = TxNumber;
         type Value = R;
+        const INTEGER_KEY: bool = true;
     }
 
     /// Stores all smart contract bytecodes.
@@ -443,6 +486,18 @@ tables! {
         type Value = BlockNumberList;
     }
 
+    /// Stores pointers to the blocks in which an address emitted logs.
+    ///
+    /// Sharded the same way as [`AccountsHistory`], with the last shard of an address having
+    /// `u64::MAX` as its highest block number.
+    ///
+    /// This index is optional and only maintained once it has been built, either by the
+    /// `IndexLogAddresses` stage or by `reth db build-log-index`.
+    table LogAddressIndex {
+        type Key = ShardedKey<Address>;
+        type Value = BlockNumberList;
+    }
+
     /// Stores the state of an account before a certain transaction changed it.
     /// Change on state can be: account is created, selfdestructed, touched while empty
     /// or changed balance,nonce.
@@ -450,6 +505,7 @@ tables! {
         type Key = BlockNumber;
         type Value = AccountBeforeTx;
         type SubKey = Address;
+        const INTEGER_KEY: bool = true;
     }
 
     /// Stores the state of a storage key before a certain transaction changed it.
@@ -499,6 +555,7 @@ tables! {
     table TransactionSenders {
         type Key = TxNumber;
         type Value = Address;
+        const INTEGER_KEY: bool = true;
     }
 
     /// Stores the highest synced block number and stage-specific checkpoint of each stage.
@@ -519,6 +576,13 @@ tables! {
         type Value = PruneCheckpoint;
     }
 
+    /// Stores the highest pruned block number and prune mode of each custom prune segment, by the
+    /// name of the segment.
+    table CustomPruneCheckpoints {
+        type Key = String;
+        type Value = PruneCheckpoint;
+    }
+
     /// Stores the history of client versions that have accessed the database with write privileges by unix timestamp in seconds.
     table VersionHistory {
         type Key = u64;
@@ -530,6 +594,18 @@ tables! {
         type Key = ChainStateKey;
         type Value = BlockNumber;
     }
+
+    /// Stores the last valid forkchoice state received from the consensus layer.
+    table ForkchoiceStates {
+        type Key = ChainStateKey;
+        type Value = StoredForkchoiceState;
+    }
+
+    /// Stores the schema migrations applied to the database by migration id, which is the schema version after the migration, and the unix timestamp in seconds it was applied at.
+    table SchemaMigrations {
+        type Key = u64;
+        type Value = u64;
+    }
 }
 
 /// Keys for the `ChainState` table.
@@ -539,6 +615,8 @@ pub enum ChainStateKey {
     LastFinalizedBlock,
     /// Last finalized block key
     LastSafeBlockBlock,
+    /// Last valid forkchoice state key
+    LastForkchoiceState,
 }
 
 impl Encode for ChainStateKey {
@@ -548,6 +626,7 @@ impl Encode for ChainStateKey {
         match self {
             Self::LastFinalizedBlock => [0],
             Self::LastSafeBlockBlock => [1],
+            Self::LastForkchoiceState => [2],
         }
     }
 }
@@ -557,6 +636,7 @@ impl Decode for ChainStateKey {
         match value {
             [0] => Ok(Self::LastFinalizedBlock),
             [1] => Ok(Self::LastSafeBlockBlock),
+            [2] => Ok(Self::LastForkchoiceState),
             _ => Err(reth_db_api::DatabaseError::Decode),
         }
     }
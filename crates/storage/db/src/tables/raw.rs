@@ -15,6 +15,7 @@ pub struct RawTable<T: Table> {
 impl<T: Table> Table for RawTable<T> {
     const NAME: &'static str = T::NAME;
     const DUPSORT: bool = false;
+    const INTEGER_KEY: bool = T::INTEGER_KEY;
 
     type Key = RawKey<T::Key>;
     type Value = RawValue<T::Value>;
@@ -30,6 +31,8 @@ pub struct RawDupSort<T: DupSort> {
 impl<T: DupSort> Table for RawDupSort<T> {
     const NAME: &'static str = T::NAME;
     const DUPSORT: bool = true;
+    const INTEGER_KEY: bool = T::INTEGER_KEY;
+    const DUP_FIXED: bool = T::DUP_FIXED;
 
     type Key = RawKey<T::Key>;
     type Value = RawValue<T::Value>;
@@ -4,8 +4,53 @@ use crate::DatabaseError;
 use reth_db_api::table::{Decode, Decompress, Table, TableRow};
 use std::borrow::Cow;
 
+/// A table key in the representation it is stored in the database.
+///
+/// Keys of integer key tables (see [`Table::INTEGER_KEY`]) are stored as native-endian integers,
+/// while their encoded form is big-endian. All other keys are stored as they are encoded.
+#[derive(Debug)]
+pub(crate) enum StoredKey<E> {
+    /// Key stored as it is encoded.
+    Encoded(E),
+    /// Key stored as a native-endian integer.
+    Integer([u8; 8]),
+}
+
+impl<E: AsRef<[u8]> + Into<Vec<u8>>> StoredKey<E> {
+    /// Converts the encoded key into its stored representation.
+    pub(crate) fn new(encoded: E, integer_key: bool) -> Self {
+        if integer_key {
+            if let Ok(bytes) = <[u8; 8]>::try_from(encoded.as_ref()) {
+                return Self::Integer(u64::from_be_bytes(bytes).to_ne_bytes())
+            }
+        }
+        Self::Encoded(encoded)
+    }
+
+    /// Returns the encoded key.
+    pub(crate) fn into_encoded(self) -> Vec<u8> {
+        match self {
+            Self::Encoded(encoded) => encoded.into(),
+            Self::Integer(bytes) => u64::from_ne_bytes(bytes).to_be_bytes().to_vec(),
+        }
+    }
+}
+
+impl<E: AsRef<[u8]>> AsRef<[u8]> for StoredKey<E> {
+    fn as_ref(&self) -> &[u8] {
+        match self {
+            Self::Encoded(encoded) => encoded.as_ref(),
+            Self::Integer(bytes) => bytes,
+        }
+    }
+}
+
 /// Helper function to decode a `(key, value)` pair.
+///
+/// If `integer_key` is set, the key is converted from its native-endian stored representation
+/// before decoding.
 pub(crate) fn decoder<'a, T>(
+    integer_key: bool,
     (k, v): (Cow<'a, [u8]>, Cow<'a, [u8]>),
 ) -> Result<TableRow<T>, DatabaseError>
 where
@@ -13,10 +58,12 @@ where
     T::Key: Decode,
     T::Value: Decompress,
 {
+    let integer = if integer_key { <[u8; 8]>::try_from(k.as_ref()).ok() } else { None };
     Ok((
-        match k {
-            Cow::Borrowed(k) => Decode::decode(k)?,
-            Cow::Owned(k) => Decode::decode_owned(k)?,
+        match (integer, k) {
+            (Some(bytes), _) => Decode::decode(&u64::from_ne_bytes(bytes).to_be_bytes())?,
+            (None, Cow::Borrowed(k)) => Decode::decode(k)?,
+            (None, Cow::Owned(k)) => Decode::decode_owned(k)?,
         },
         match v {
             Cow::Borrowed(v) => Decompress::decompress(v)?,
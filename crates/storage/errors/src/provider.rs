@@ -102,6 +102,17 @@ pub enum ProviderError {
     /// State is not available for the given block number because it is pruned.
     #[error("state at block #{_0} is pruned")]
     StateAtBlockPruned(BlockNumber),
+    /// Changesets are not available for the start of the requested block range because they are
+    /// pruned.
+    #[error(
+        "changesets from block #{start} are pruned, lowest available block is #{lowest_available}"
+    )]
+    ChangeSetsPruned {
+        /// The first block of the requested range.
+        start: BlockNumber,
+        /// The lowest block that still has changesets.
+        lowest_available: BlockNumber,
+    },
     /// Provider does not support this particular request.
     #[error("this provider does not support this request")]
     UnsupportedProvider,
@@ -0,0 +1,73 @@
+#![allow(missing_docs)]
+
+//! Appends to a jar with and without disk space reserved ahead, and with different fsync
+//! policies.
+//!
+//! Results depend on the filesystem, so the jars are created in every directory of
+//! `NIPPY_JAR_BENCH_DIRS`, separated like `PATH`, to compare e.g. ext4, XFS and ZFS mounts in one
+//! run:
+//!
+//! ```sh
+//! NIPPY_JAR_BENCH_DIRS=/mnt/ext4:/mnt/zfs cargo bench -p reth-nippy-jar --bench append
+//! ```
+//!
+//! The jars are created in the default temporary directory if it's unset.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use reth_fs_util::FsyncPolicy;
+use reth_nippy_jar::{NippyJar, NippyJarWriter};
+use std::path::PathBuf;
+use tempfile::TempDir;
+
+criterion_group! {
+    name = benches;
+    config = Criterion::default().sample_size(10);
+    targets = append
+}
+criterion_main!(benches);
+
+const ROWS: u64 = 20_000;
+const COMMIT_INTERVAL: u64 = 1_000;
+
+fn bench_dirs() -> Vec<PathBuf> {
+    match std::env::var_os("NIPPY_JAR_BENCH_DIRS") {
+        Some(dirs) => std::env::split_paths(&dirs).collect(),
+        None => vec![std::env::temp_dir()],
+    }
+}
+
+fn append(c: &mut Criterion) {
+    let mut group = c.benchmark_group("NippyJar append");
+    let row = vec![0xaa; 4096];
+
+    for dir in bench_dirs() {
+        for (preallocation, fsync_policy) in [
+            (0, FsyncPolicy::Commit),
+            (64 * 1024 * 1024, FsyncPolicy::Commit),
+            (0, FsyncPolicy::Bytes(8 * 1024 * 1024)),
+            (64 * 1024 * 1024, FsyncPolicy::Bytes(8 * 1024 * 1024)),
+        ] {
+            let id =
+                format!("dir={} preallocation={preallocation} fsync={fsync_policy}", dir.display());
+            group.bench_function(BenchmarkId::new("rows", id), |b| {
+                b.iter_with_setup(
+                    || TempDir::new_in(&dir).unwrap(),
+                    |dir| {
+                        let jar = NippyJar::new_without_header(1, &dir.path().join("jar"));
+                        let mut writer = NippyJarWriter::new(jar)
+                            .unwrap()
+                            .with_preallocation(preallocation)
+                            .with_fsync_policy(fsync_policy);
+                        for rows in (0..ROWS).step_by(COMMIT_INTERVAL as usize) {
+                            let column = (rows..rows + COMMIT_INTERVAL).map(|_| Ok(row.as_slice()));
+                            writer.append_rows(vec![column], COMMIT_INTERVAL).unwrap();
+                            writer.commit().unwrap();
+                        }
+                        criterion::black_box(writer.take_stats());
+                        dir
+                    },
+                )
+            });
+        }
+    }
+}
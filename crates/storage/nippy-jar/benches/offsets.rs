@@ -0,0 +1,50 @@
+#![allow(missing_docs)]
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use rand::{rngs::SmallRng, Rng, SeedableRng};
+use reth_nippy_jar::{NippyJar, NippyJarCursor, NippyJarWriter, OffsetsEncoding};
+use std::path::Path;
+
+criterion_group!(benches, random_access);
+criterion_main!(benches);
+
+const ROWS: usize = 100_000;
+const LOOKUPS: usize = 10_000;
+
+/// Creates a jar with three columns shaped like the headers segment: a variable sized header, a
+/// total difficulty and a hash.
+fn create_jar(path: &Path, rng: &mut SmallRng) -> NippyJar {
+    let header = (0..ROWS).map(|_| Ok(vec![0xaa; rng.gen_range(500..600)])).collect::<Vec<_>>();
+    let difficulty = (0..ROWS).map(|_| Ok(vec![0xbb; 8])).collect::<Vec<_>>();
+    let hash = (0..ROWS).map(|_| Ok(vec![0xcc; 32])).collect::<Vec<_>>();
+
+    let mut writer = NippyJarWriter::new(NippyJar::new_without_header(3, path)).unwrap();
+    writer.append_rows(vec![header, difficulty, hash], ROWS as u64).unwrap();
+    writer.commit().unwrap();
+    writer.into_jar()
+}
+
+fn random_access(c: &mut Criterion) {
+    let mut group = c.benchmark_group("NippyJar offsets");
+    let mut rng = SmallRng::seed_from_u64(0);
+    let lookups = (0..LOOKUPS).map(|_| rng.gen_range(0..ROWS)).collect::<Vec<_>>();
+
+    for encoding in [OffsetsEncoding::Fixed, OffsetsEncoding::delta()] {
+        let file = tempfile::NamedTempFile::new().unwrap();
+        let mut jar = create_jar(file.path(), &mut rng);
+        jar.rewrite_offsets(encoding).unwrap();
+
+        let offsets_size = std::fs::metadata(jar.offsets_path()).unwrap().len();
+        let data_size = std::fs::metadata(jar.data_path()).unwrap().len();
+        println!("{encoding:?}: offsets file is {offsets_size} bytes for {data_size} data bytes");
+
+        let mut cursor = NippyJarCursor::new(&jar).unwrap();
+        group.bench_function(BenchmarkId::new("random row", format!("{encoding:?}")), |b| {
+            b.iter(|| {
+                for row in &lookups {
+                    criterion::black_box(cursor.row_by_number(*row).unwrap());
+                }
+            })
+        });
+    }
+}
@@ -50,6 +50,16 @@ pub enum Compressors {
     Lz4(Lz4),
 }
 
+impl Compressors {
+    /// Returns the name of the compression algorithm.
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Self::Zstd(_) => "zstd",
+            Self::Lz4(_) => "lz4",
+        }
+    }
+}
+
 impl Compression for Compressors {
     fn decompress_to(&self, value: &[u8], dest: &mut Vec<u8>) -> Result<(), NippyJarError> {
         match self {
@@ -54,6 +54,26 @@ impl Zstd {
         }
     }
 
+    /// Gets the state of the compressor.
+    pub const fn state(&self) -> &ZstdState {
+        &self.state
+    }
+
+    /// Gets the compression level. A level of `0` uses zstd's default.
+    pub const fn level(&self) -> i32 {
+        self.level
+    }
+
+    /// Gets the maximum size of a dictionary.
+    pub const fn max_dict_size(&self) -> usize {
+        self.max_dict_size
+    }
+
+    /// Whether the column dictionaries are loaded.
+    pub const fn has_dictionaries(&self) -> bool {
+        self.dictionaries.is_some()
+    }
+
     /// Sets the compression level for the Zstd compression instance.
     pub const fn with_level(mut self, level: i32) -> Self {
         self.level = level;
@@ -1,17 +1,23 @@
-use crate::{writer::OFFSET_SIZE_BYTES, NippyJar, NippyJarError, NippyJarHeader};
+use crate::{writer::OFFSET_SIZE_BYTES, NippyJar, NippyJarError, NippyJarHeader, OffsetsEncoding};
 use std::{
-    cmp::Ordering,
     fs::{File, OpenOptions},
-    io::{BufWriter, Seek, SeekFrom},
+    io::{BufWriter, Read, Seek, SeekFrom},
     path::Path,
 };
+use tracing::warn;
 
 /// Performs consistency checks or heals on the [`NippyJar`] file
-/// * Is the offsets file size expected?
-/// * Is the data file size expected?
+/// * Does the offsets file end with a partially written offset?
+/// * Does the data file have the data every offset points to?
+/// * Does the offsets file have the offsets of every committed row, and no more?
+/// * Does the last committed offset match the committed data length?
+/// * Does the data file have data past the last offset?
 ///
 /// This is based on the assumption that [`NippyJar`] configuration is **always** the last one
 /// to be updated when something is written, as by the `NippyJarWriter::commit()` function shows.
+/// Anything past the committed rows is a torn tail of an interrupted write, which is truncated
+/// when healing. That includes zero-filled tails, left by filesystems that extend a file before the
+/// appended bytes reach the disk, or that expose space reserved ahead of the appends.
 ///
 /// **For checks (read-only) use `check_consistency` method.**
 ///
@@ -37,118 +43,173 @@ impl<H: NippyJarHeader> NippyJarChecker<H> {
         Self { jar, data_file: None, offsets_file: None }
     }
 
-    /// It will throw an error if the [`NippyJar`] is in a inconsistent state.
+    /// It will throw an error describing the first inconsistency found if the [`NippyJar`] is in
+    /// a inconsistent state.
     pub fn check_consistency(&mut self) -> Result<(), NippyJarError> {
-        self.handle_consistency(ConsistencyFailStrategy::ThrowError)
+        self.handle_consistency(ConsistencyFailStrategy::ThrowError).map(|_| ())
     }
 
-    /// It will attempt to heal if the [`NippyJar`] is in a inconsistent state.
+    /// It will attempt to heal if the [`NippyJar`] is in a inconsistent state, and returns what
+    /// was healed.
     ///
     /// **ATTENTION**: disk commit should be handled externally by consuming `Self`
-    pub fn ensure_consistency(&mut self) -> Result<(), NippyJarError> {
-        self.handle_consistency(ConsistencyFailStrategy::Heal)
+    pub fn ensure_consistency(&mut self) -> Result<NippyJarHealReport, NippyJarError> {
+        let report = self.handle_consistency(ConsistencyFailStrategy::Heal)?;
+        if !report.is_empty() {
+            warn!(
+                target: "nippy-jar",
+                path=?self.jar.data_path(),
+                rows=self.jar.rows,
+                ?report,
+                "Healed torn tail of an interrupted write."
+            );
+        }
+        Ok(report)
     }
 
-    fn handle_consistency(&mut self, mode: ConsistencyFailStrategy) -> Result<(), NippyJarError> {
+    fn handle_consistency(
+        &mut self,
+        mode: ConsistencyFailStrategy,
+    ) -> Result<NippyJarHealReport, NippyJarError> {
         self.load_files(mode)?;
-        let mut reader = self.jar.open_data_reader()?;
+        let reader = self.jar.open_data_reader()?;
 
-        // When an offset size is smaller than the initial (8), we are dealing with immutable
-        // data.
-        if reader.offset_size() != OFFSET_SIZE_BYTES {
+        // When an offset size is smaller than the initial (8), or offsets are delta encoded, we
+        // are dealing with immutable data.
+        if reader.offset_size() != OFFSET_SIZE_BYTES ||
+            reader.offsets_encoding() != OffsetsEncoding::Fixed
+        {
             return Err(NippyJarError::FrozenJar)
         }
 
-        let expected_offsets_file_size: u64 = (1 + // first byte is the size of one offset
-                OFFSET_SIZE_BYTES as usize* self.jar.rows * self.jar.columns + // `offset size * num rows * num columns`
-                OFFSET_SIZE_BYTES as usize) as u64; // expected size of the data file
-        let actual_offsets_file_size = self.offsets_file().get_ref().metadata()?.len();
-
-        if mode.should_err() &&
-            expected_offsets_file_size.cmp(&actual_offsets_file_size) != Ordering::Equal
-        {
-            return Err(NippyJarError::InconsistentState)
-        }
+        // Windows has locked the files with the mmap handles, so we need to drop them before any
+        // truncation. Offsets are read from the file handle from here on.
+        drop(reader);
 
-        // Offsets configuration wasn't properly committed
-        match expected_offsets_file_size.cmp(&actual_offsets_file_size) {
-            Ordering::Less => {
-                // Happened during an appending job
-                // TODO: ideally we could truncate until the last offset of the last column of the
-                //  last row inserted
+        let mut report = NippyJarHealReport::default();
 
-                // Windows has locked the file with the mmap handle, so we need to drop it
-                drop(reader);
+        // An offset was only partially written, which happened during an appending job.
+        let torn_len = self.offsets_file_len()?.saturating_sub(1) % OFFSET_SIZE_BYTES as u64;
+        if torn_len > 0 {
+            mode.heal_or_err(NippyJarError::TornOffset { len: torn_len })?;
 
-                self.offsets_file().get_mut().set_len(expected_offsets_file_size)?;
-                reader = self.jar.open_data_reader()?;
-            }
-            Ordering::Greater => {
-                // Happened during a pruning job
-                // `num rows = (file size - 1 - size of one offset) / num columns`
-                self.jar.rows = ((actual_offsets_file_size.
-                        saturating_sub(1). // first byte is the size of one offset
-                        saturating_sub(OFFSET_SIZE_BYTES as u64) / // expected size of the data file
-                        (self.jar.columns as u64)) /
-                    OFFSET_SIZE_BYTES as u64) as usize;
-
-                // Freeze row count changed
-                self.jar.freeze_config()?;
-            }
-            Ordering::Equal => {}
+            let new_len = self.offsets_file_len()? - torn_len;
+            self.offsets_file().get_mut().set_len(new_len)?;
+            report.torn_offset_bytes = torn_len;
         }
 
-        // last offset should match the data_file_len
-        let last_offset = reader.reverse_offset(0)?;
-        let data_file_len = self.data_file().get_ref().metadata()?.len();
+        // Offsets point past the end of the data file, which happened during a pruning job. Drop
+        // them until we find one that points to existing data. It would only be equal if the
+        // previous row was fully pruned.
+        let data_file_len = self.data_file_len()?;
+        let mut offsets_count = self.offsets_count()?;
+        let last_offset = self.last_offset(offsets_count)?;
+        if last_offset > data_file_len {
+            mode.heal_or_err(NippyJarError::MissingData {
+                expected: last_offset,
+                actual: data_file_len,
+            })?;
 
-        if mode.should_err() && last_offset.cmp(&data_file_len) != Ordering::Equal {
-            return Err(NippyJarError::InconsistentState)
+            while offsets_count > 0 && self.last_offset(offsets_count)? > data_file_len {
+                offsets_count -= 1;
+                report.missing_data_offsets += 1;
+            }
+            self.offsets_file().get_mut().set_len(1 + offsets_count * OFFSET_SIZE_BYTES as u64)?;
         }
 
-        // Offset list wasn't properly committed
-        match last_offset.cmp(&data_file_len) {
-            Ordering::Less => {
-                // Windows has locked the file with the mmap handle, so we need to drop it
-                drop(reader);
+        // One offset per column of each row, and one for the expected size of the data file.
+        let columns = self.jar.columns as u64;
+        let committed_offsets = |rows: usize| rows as u64 * columns + 1;
+
+        // A fully pruned jar has no offsets at all.
+        if offsets_count < committed_offsets(self.jar.rows) && self.jar.rows > 0 {
+            // Offsets of committed rows are missing, which happened during a pruning job.
+            mode.heal_or_err(NippyJarError::MissingOffsets {
+                expected: committed_offsets(self.jar.rows),
+                actual: offsets_count,
+            })?;
 
-                // Happened during an appending job, so we need to truncate the data, since there's
-                // no way to recover it.
-                self.data_file().get_mut().set_len(last_offset)?;
+            let rows = (offsets_count.saturating_sub(1) / columns) as usize;
+            report.dropped_rows = (self.jar.rows - rows) as u64;
+            self.jar.rows = rows;
+            if rows == 0 {
+                self.jar.max_row_size = 0;
             }
-            Ordering::Greater => {
-                // Happened during a pruning job, so we need to reverse iterate offsets until we
-                // find the matching one.
-                for index in 0..reader.offsets_count()? {
-                    let offset = reader.reverse_offset(index + 1)?;
-                    // It would only be equal if the previous row was fully pruned.
-                    if offset <= data_file_len {
-                        let new_len = self
-                            .offsets_file()
-                            .get_ref()
-                            .metadata()?
-                            .len()
-                            .saturating_sub(OFFSET_SIZE_BYTES as u64 * (index as u64 + 1));
-
-                        // Windows has locked the file with the mmap handle, so we need to drop it
-                        drop(reader);
-
-                        self.offsets_file().get_mut().set_len(new_len)?;
-
-                        // Since we decrease the offset list, we need to check the consistency of
-                        // `self.jar.rows` again
-                        self.handle_consistency(ConsistencyFailStrategy::Heal)?;
-                        break
-                    }
-                }
+
+            // Drops the offsets of a partially pruned row as well.
+            offsets_count = offsets_count.min(committed_offsets(rows));
+            self.offsets_file().get_mut().set_len(1 + offsets_count * OFFSET_SIZE_BYTES as u64)?;
+
+            // Freeze row count changed
+            self.jar.data_len = Some(self.last_offset(offsets_count)?);
+            self.jar.freeze_config()?;
+        } else if offsets_count > committed_offsets(self.jar.rows) {
+            // Offsets of uncommitted rows are present, which happened during an appending job.
+            mode.heal_or_err(NippyJarError::UncommittedOffsets {
+                expected: committed_offsets(self.jar.rows),
+                actual: offsets_count,
+            })?;
+
+            report.uncommitted_offsets = offsets_count - committed_offsets(self.jar.rows);
+            offsets_count = committed_offsets(self.jar.rows);
+            self.offsets_file().get_mut().set_len(1 + offsets_count * OFFSET_SIZE_BYTES as u64)?;
+        }
+
+        // With a torn tail truncated, the last offset has to be the committed data length,
+        // otherwise the committed offsets themselves are corrupted.
+        let last_offset = self.last_offset(offsets_count)?;
+        if let Some(data_len) = self.jar.data_len {
+            if last_offset != data_len {
+                return Err(NippyJarError::DataLenMismatch { data_len, offset: last_offset })
             }
-            Ordering::Equal => {}
+        }
+
+        // Data was appended past the last offset, which happened during an appending job, so we
+        // need to truncate it, since there's no way to recover it.
+        let data_file_len = self.data_file_len()?;
+        if data_file_len > last_offset {
+            mode.heal_or_err(NippyJarError::UncommittedData {
+                expected: last_offset,
+                actual: data_file_len,
+            })?;
+
+            self.data_file().get_mut().set_len(last_offset)?;
+            report.uncommitted_data_bytes = data_file_len - last_offset;
         }
 
         self.offsets_file().seek(SeekFrom::End(0))?;
         self.data_file().seek(SeekFrom::End(0))?;
 
-        Ok(())
+        Ok(report)
+    }
+
+    /// Returns the length of the offsets file.
+    fn offsets_file_len(&mut self) -> Result<u64, NippyJarError> {
+        Ok(self.offsets_file().get_ref().metadata()?.len())
+    }
+
+    /// Returns the length of the data file.
+    fn data_file_len(&mut self) -> Result<u64, NippyJarError> {
+        Ok(self.data_file().get_ref().metadata()?.len())
+    }
+
+    /// Returns the number of complete offsets in the offsets file.
+    fn offsets_count(&mut self) -> Result<u64, NippyJarError> {
+        // first byte is the size of one offset
+        Ok(self.offsets_file_len()?.saturating_sub(1) / OFFSET_SIZE_BYTES as u64)
+    }
+
+    /// Returns the last of the first `offsets_count` offsets, or zero if there are none.
+    fn last_offset(&mut self, offsets_count: u64) -> Result<u64, NippyJarError> {
+        if offsets_count == 0 {
+            return Ok(0)
+        }
+
+        let file = self.offsets_file().get_mut();
+        file.seek(SeekFrom::Start(1 + (offsets_count - 1) * OFFSET_SIZE_BYTES as u64))?;
+        let mut buf = [0u8; OFFSET_SIZE_BYTES as usize];
+        file.read_exact(&mut buf)?;
+        Ok(u64::from_le_bytes(buf))
     }
 
     /// Loads data and offsets files.
@@ -195,8 +256,35 @@ impl ConsistencyFailStrategy {
         matches!(self, Self::Heal)
     }
 
-    /// Whether writer should throw an error.
-    const fn should_err(&self) -> bool {
-        matches!(self, Self::ThrowError)
+    /// Returns the inconsistency as an error if the writer should not heal it.
+    fn heal_or_err(&self, inconsistency: NippyJarError) -> Result<(), NippyJarError> {
+        if self.should_heal() {
+            Ok(())
+        } else {
+            Err(inconsistency)
+        }
+    }
+}
+
+/// Inconsistencies healed by [`NippyJarChecker::ensure_consistency`], all of which are the torn
+/// tail of an interrupted write.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct NippyJarHealReport {
+    /// Bytes of a partially written offset truncated from the offsets file.
+    pub torn_offset_bytes: u64,
+    /// Offsets truncated from the offsets file, because the data they point to was missing.
+    pub missing_data_offsets: u64,
+    /// Committed rows dropped, because their offsets were missing.
+    pub dropped_rows: u64,
+    /// Offsets of uncommitted rows truncated from the offsets file.
+    pub uncommitted_offsets: u64,
+    /// Bytes past the last offset truncated from the data file.
+    pub uncommitted_data_bytes: u64,
+}
+
+impl NippyJarHealReport {
+    /// Returns `true` if nothing was healed.
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
     }
 }
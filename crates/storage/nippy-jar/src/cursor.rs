@@ -151,14 +151,13 @@ impl<'a, H: NippyJarHeader> NippyJarCursor<'a, H> {
     ) -> Result<(), NippyJarError> {
         // Find out the offset of the column value
         let offset_pos = self.row as usize * self.jar.columns + column;
-        let value_offset = self.reader.offset(offset_pos)? as usize;
 
         let column_offset_range = if self.jar.rows * self.jar.columns == offset_pos + 1 {
             // It's the last column of the last row
-            value_offset..self.reader.size()
+            self.reader.offset(offset_pos)? as usize..self.reader.size()
         } else {
-            let next_value_offset = self.reader.offset(offset_pos + 1)? as usize;
-            value_offset..next_value_offset
+            let (value_offset, next_value_offset) = self.reader.offset_pair(offset_pos)?;
+            value_offset as usize..next_value_offset as usize
         };
 
         if let Some(compression) = self.jar.compressor() {
@@ -1,3 +1,4 @@
+use derive_more::Display;
 use std::path::PathBuf;
 use thiserror::Error;
 
@@ -65,6 +66,17 @@ pub enum NippyJarError {
         index: usize,
     },
 
+    /// Offsets are not sorted in ascending order.
+    #[error("offset at index {index} is smaller than its predecessor")]
+    UnsortedOffsets {
+        /// The index of the offending offset.
+        index: usize,
+    },
+
+    /// The restart interval of delta encoded offsets is zero.
+    #[error("the restart interval of delta encoded offsets must be greater than zero")]
+    InvalidRestartInterval,
+
     /// The output buffer is too small for the compression or decompression operation.
     #[error("compression or decompression requires a bigger destination output")]
     OutputTooSmall,
@@ -85,11 +97,97 @@ pub enum NippyJarError {
     #[error("jar has been frozen and cannot be modified.")]
     FrozenJar,
 
-    /// The file is in an inconsistent state.
-    #[error("File is in an inconsistent state.")]
-    InconsistentState,
+    /// The offsets file ends with a partially written offset.
+    #[error("offsets file ends with a partially written offset of {len} bytes")]
+    TornOffset {
+        /// The number of bytes of the partially written offset.
+        len: u64,
+    },
+
+    /// The offsets file has more offsets than the committed rows require, which happens when the
+    /// writer is interrupted after committing offsets, but before committing the configuration.
+    #[error("offsets file has {actual} offsets, but only {expected} are committed")]
+    UncommittedOffsets {
+        /// The number of offsets required by the committed rows.
+        expected: u64,
+        /// The number of offsets in the offsets file.
+        actual: u64,
+    },
+
+    /// The offsets file has fewer offsets than the committed rows require, which happens when the
+    /// writer is interrupted while pruning rows.
+    #[error("offsets file has {actual} offsets, but {expected} are committed")]
+    MissingOffsets {
+        /// The number of offsets required by the committed rows.
+        expected: u64,
+        /// The number of offsets in the offsets file.
+        actual: u64,
+    },
+
+    /// The data file is longer than the last offset, which happens when the writer is interrupted
+    /// after appending data, but before committing its offsets.
+    #[error("data file has {actual} bytes, but the last offset is {expected}")]
+    UncommittedData {
+        /// The last offset.
+        expected: u64,
+        /// The length of the data file.
+        actual: u64,
+    },
+
+    /// The data file is shorter than the last offset, which happens when the writer is interrupted
+    /// while pruning rows.
+    #[error("data file has {actual} bytes, but the last offset is {expected}")]
+    MissingData {
+        /// The last offset.
+        expected: u64,
+        /// The length of the data file.
+        actual: u64,
+    },
+
+    /// The last committed offset doesn't match the data length of the configuration. Unlike the
+    /// other inconsistencies, this can't be left behind by an interrupted writer and can't be
+    /// healed.
+    #[error("last committed offset is {offset}, but the committed data length is {data_len}")]
+    DataLenMismatch {
+        /// The data length of the configuration.
+        data_len: u64,
+        /// The last committed offset.
+        offset: u64,
+    },
+
+    /// A file of the jar doesn't match the configuration or the checksum recorded when the jar
+    /// was made immutable, see [`NippyJar::verify`](crate::NippyJar::verify).
+    #[error("{component} of the jar is corrupted")]
+    Corrupted {
+        /// The file that failed the verification.
+        component: NippyJarComponent,
+    },
+
+    /// The configuration was written with a version of the format that isn't supported.
+    #[error("unsupported nippy jar version {version}")]
+    UnsupportedVersion {
+        /// The version of the configuration.
+        version: usize,
+    },
 
     /// A specified file is missing.
     #[error("Missing file: {}", .0.display())]
     MissingFile(PathBuf),
 }
+
+/// A file of a [`NippyJar`](crate::NippyJar).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Display)]
+pub enum NippyJarComponent {
+    /// The configuration file.
+    #[display("configuration file")]
+    Config,
+    /// The data file.
+    #[display("data file")]
+    Data,
+    /// The offsets file.
+    #[display("offsets file")]
+    Offsets,
+    /// The index file, holding the inclusion filter and the perfect hashing function.
+    #[display("index file")]
+    Index,
+}
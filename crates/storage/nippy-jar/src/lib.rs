@@ -14,10 +14,11 @@
 
 use memmap2::Mmap;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use std::{
     error::Error as StdError,
     fs::File,
-    io::Read,
+    io::{BufWriter, Read, Write},
     ops::Range,
     path::{Path, PathBuf},
 };
@@ -40,25 +41,39 @@ pub enum Functions {}
 pub enum InclusionFilters {}
 
 mod error;
-pub use error::NippyJarError;
+pub use error::{NippyJarComponent, NippyJarError};
 
 mod cursor;
 pub use cursor::NippyJarCursor;
 
 mod writer;
-pub use writer::NippyJarWriter;
+pub use writer::{FsyncStats, NippyJarWriter, NippyJarWriterStats};
 
 mod consistency;
-pub use consistency::NippyJarChecker;
+pub use consistency::{NippyJarChecker, NippyJarHealReport};
+
+mod offsets;
+use offsets::DeltaOffsets;
+pub use offsets::{OffsetsEncoding, DEFAULT_RESTART_INTERVAL};
 
 /// The version number of the Nippy Jar format.
-const NIPPY_JAR_VERSION: usize = 1;
+///
+/// Its configuration ends with the committed data length, the offsets encoding and the checksums.
+const NIPPY_JAR_VERSION: usize = 3;
+/// Version of the format whose configuration ends with the maximum row size. Such jars always use
+/// fixed-width offsets.
+const NIPPY_JAR_VERSION_1: usize = 1;
+/// Version of the format with the configuration of [`NIPPY_JAR_VERSION_1`], for jars whose offsets
+/// are delta encoded.
+const NIPPY_JAR_VERSION_2: usize = 2;
 /// The file extension used for index files.
 const INDEX_FILE_EXTENSION: &str = "idx";
 /// The file extension used for offsets files.
 const OFFSETS_FILE_EXTENSION: &str = "off";
 /// The file extension used for configuration files.
 pub const CONFIG_FILE_EXTENSION: &str = "conf";
+/// Number of rows whose offsets are checked by [`NippyJar::verify`].
+const VERIFY_SAMPLE_ROWS: usize = 64;
 
 /// A [`RefRow`] is a list of column value slices pointing to either an internal buffer or a
 /// memory-mapped file.
@@ -106,6 +121,16 @@ pub struct NippyJar<H = ()> {
     /// Maximum uncompressed row size of the set. This will enable decompression without any
     /// resizing of the output buffer.
     max_row_size: usize,
+    /// Length of the data file as of the last commit, which must match the last committed offset.
+    ///
+    /// `None` for jars committed before it was tracked.
+    data_len: Option<u64>,
+    /// Whether the offsets file is delta encoded, see [`OffsetsEncoding::Delta`].
+    delta_offsets: bool,
+    /// Checksums of the data and offsets files, recorded when the jar was made immutable.
+    ///
+    /// `None` for writable jars, and immutable jars made so before they were recorded.
+    checksums: Option<NippyJarChecksums>,
     /// Data path for file. Supporting files will have a format `{path}.{extension}`.
     #[serde(skip)]
     path: PathBuf,
@@ -123,6 +148,9 @@ impl<H: NippyJarHeader> std::fmt::Debug for NippyJar<H> {
             .field("phf", &self.phf)
             .field("path", &self.path)
             .field("max_row_size", &self.max_row_size)
+            .field("data_len", &self.data_len)
+            .field("delta_offsets", &self.delta_offsets)
+            .field("checksums", &self.checksums)
             .finish_non_exhaustive()
     }
 }
@@ -148,6 +176,9 @@ impl<H: NippyJarHeader> NippyJar<H> {
             columns,
             rows: 0,
             max_row_size: 0,
+            data_len: Some(0),
+            delta_offsets: false,
+            checksums: None,
             compressor: None,
             filter: None,
             phf: None,
@@ -168,6 +199,20 @@ impl<H: NippyJarHeader> NippyJar<H> {
         self
     }
 
+    /// Gets the version of the jar format.
+    ///
+    /// Configurations of older versions are upgraded to the current one when they're loaded.
+    pub const fn version(&self) -> usize {
+        self.version
+    }
+
+    /// Whether the offsets file of the jar is delta encoded.
+    ///
+    /// Such jars are immutable, see [`OffsetsEncoding::Delta`].
+    pub const fn has_delta_offsets(&self) -> bool {
+        self.delta_offsets
+    }
+
     /// Gets a reference to the user header.
     pub const fn user_header(&self) -> &H {
         &self.user_header
@@ -183,6 +228,30 @@ impl<H: NippyJarHeader> NippyJar<H> {
         self.rows
     }
 
+    /// Gets the length of the data file as of the last commit, if tracked.
+    pub const fn data_len(&self) -> Option<u64> {
+        self.data_len
+    }
+
+    /// Gets the checksums of the data and offsets files, if the jar is immutable and they were
+    /// recorded.
+    pub const fn checksums(&self) -> Option<&NippyJarChecksums> {
+        self.checksums.as_ref()
+    }
+
+    /// Gets the maximum uncompressed row size of the jar.
+    pub const fn max_row_size(&self) -> usize {
+        self.max_row_size
+    }
+
+    /// Whether the jar has an inclusion filter.
+    ///
+    /// Inclusion filters are no longer written, so this is `false` for every jar that can be
+    /// loaded.
+    pub const fn has_filter(&self) -> bool {
+        self.filter.is_some()
+    }
+
     /// Gets a reference to the compressor.
     pub const fn compressor(&self) -> Option<&Compressors> {
         self.compressor.as_ref()
@@ -208,8 +277,22 @@ impl<H: NippyJarHeader> NippyJar<H> {
     }
 
     /// Deserializes an instance of [`Self`] from a [`Read`] type.
-    pub fn load_from_reader<R: Read>(reader: R) -> Result<Self, NippyJarError> {
-        Ok(bincode::deserialize_from(reader)?)
+    ///
+    /// Configurations of [`NIPPY_JAR_VERSION_1`] and [`NIPPY_JAR_VERSION_2`] are upgraded to the
+    /// current version, without a committed data length until the jar is committed again.
+    pub fn load_from_reader<R: Read>(mut reader: R) -> Result<Self, NippyJarError> {
+        let mut config = Vec::new();
+        reader.read_to_end(&mut config)?;
+
+        // The version is the first field of every configuration.
+        match bincode::deserialize::<usize>(&config)? {
+            NIPPY_JAR_VERSION => Ok(bincode::deserialize(&config)?),
+            version @ (NIPPY_JAR_VERSION_1 | NIPPY_JAR_VERSION_2) => {
+                let config = bincode::deserialize::<NippyJarV1<H>>(&config)?;
+                Ok(config.upgrade(version == NIPPY_JAR_VERSION_2))
+            }
+            version => Err(NippyJarError::UnsupportedVersion { version }),
+        }
     }
 
     /// Returns the path for the data file
@@ -252,6 +335,114 @@ impl<H: NippyJarHeader> NippyJar<H> {
         DataReader::new(self.data_path())
     }
 
+    /// Rewrites the offsets file with the given [`OffsetsEncoding`] and updates the configuration
+    /// accordingly.
+    ///
+    /// Rewriting to [`OffsetsEncoding::Delta`] shrinks the offsets file, but makes the jar
+    /// immutable. Rewriting back to [`OffsetsEncoding::Fixed`] makes it writable again.
+    pub fn rewrite_offsets(&mut self, encoding: OffsetsEncoding) -> Result<(), NippyJarError> {
+        let reader = self.open_data_reader()?;
+        let count = reader.offsets_count()?;
+
+        debug!(target: "nippy-jar", path=?self.offsets_path(), ?encoding, count, "Rewriting offsets.");
+
+        // The offsets file is replaced atomically, and the reader detects the encoding on its own,
+        // so the jar stays readable if we crash before the configuration is updated.
+        reth_fs_util::atomic_write_file(&self.offsets_path(), move |file| {
+            let mut file = BufWriter::new(file);
+            match encoding {
+                OffsetsEncoding::Fixed => {
+                    file.write_all(&[writer::OFFSET_SIZE_BYTES])?;
+                    for index in 0..count {
+                        file.write_all(&reader.offset(index)?.to_le_bytes())?;
+                    }
+                }
+                OffsetsEncoding::Delta { restart_interval } => {
+                    offsets::write_delta_offsets(&mut file, count, restart_interval, |index| {
+                        reader.offset(index)
+                    })?;
+                }
+            }
+            file.flush()?;
+
+            // Windows has locked the file with the mmap handle, so we need to drop it before the
+            // file is replaced.
+            drop(reader);
+            Ok::<_, NippyJarError>(())
+        })?;
+
+        // The jar can't be modified anymore once the offsets are delta encoded, so that's when the
+        // checksums are recorded.
+        (self.delta_offsets, self.checksums) = match encoding {
+            OffsetsEncoding::Fixed => (false, None),
+            OffsetsEncoding::Delta { .. } => (true, Some(self.open_data_reader()?.checksums())),
+        };
+        self.freeze_config()
+    }
+
+    /// Verifies that the data and offsets files match the configuration of the jar.
+    ///
+    /// Checks that:
+    /// * the offsets file has an offset for every column of every row, and the data length.
+    /// * the last offset is the committed data length, and within the data file.
+    /// * the offsets of a sample of rows are sorted and within the data file.
+    /// * the data and offsets files match their checksums, if the jar is immutable and they were
+    ///   recorded.
+    /// * there's no index file, since the inclusion filter and the perfect hashing function it
+    ///   holds aren't loaded anymore, and can't be checked against the data file.
+    ///
+    /// Unlike [`NippyJarChecker`], this is meant to catch files that were damaged or replaced,
+    /// rather than the torn tail of an interrupted write. It reads the whole jar if it has
+    /// checksums.
+    pub fn verify(&self) -> Result<(), NippyJarError> {
+        let corrupted = |component| NippyJarError::Corrupted { component };
+        if self.index_path().exists() {
+            return Err(corrupted(NippyJarComponent::Index))
+        }
+
+        let reader = self.open_data_reader()?;
+        let data_size = reader.size() as u64;
+
+        let offsets_count = reader.offsets_count()?;
+        if offsets_count != self.rows * self.columns + 1 {
+            return Err(corrupted(NippyJarComponent::Config))
+        }
+
+        let last_offset =
+            reader.offset(offsets_count - 1).map_err(|_| corrupted(NippyJarComponent::Offsets))?;
+        if self.data_len.is_some_and(|data_len| data_len != last_offset) {
+            return Err(corrupted(NippyJarComponent::Config))
+        }
+        if last_offset > data_size {
+            return Err(corrupted(NippyJarComponent::Data))
+        }
+
+        let step = (self.rows / VERIFY_SAMPLE_ROWS).max(1);
+        for row in (0..self.rows).step_by(step) {
+            let mut previous = 0;
+            for index in row * self.columns..=(row + 1) * self.columns {
+                let offset =
+                    reader.offset(index).map_err(|_| corrupted(NippyJarComponent::Offsets))?;
+                if offset < previous || offset > last_offset {
+                    return Err(corrupted(NippyJarComponent::Offsets))
+                }
+                previous = offset;
+            }
+        }
+
+        if let Some(expected) = &self.checksums {
+            let actual = reader.checksums();
+            if actual.data != expected.data {
+                return Err(corrupted(NippyJarComponent::Data))
+            }
+            if actual.offsets != expected.offsets {
+                return Err(corrupted(NippyJarComponent::Offsets))
+            }
+        }
+
+        Ok(())
+    }
+
     /// Writes all necessary configuration to file.
     fn freeze_config(&self) -> Result<(), NippyJarError> {
         Ok(reth_fs_util::atomic_write_file(&self.config_path(), |file| {
@@ -260,6 +451,46 @@ impl<H: NippyJarHeader> NippyJar<H> {
     }
 }
 
+/// Configuration of the jars with [`NIPPY_JAR_VERSION_1`] or [`NIPPY_JAR_VERSION_2`].
+#[derive(Deserialize)]
+struct NippyJarV1<H> {
+    _version: usize,
+    user_header: H,
+    columns: usize,
+    rows: usize,
+    compressor: Option<Compressors>,
+    max_row_size: usize,
+}
+
+impl<H> NippyJarV1<H> {
+    /// Upgrades the configuration to the current version.
+    fn upgrade(self, delta_offsets: bool) -> NippyJar<H> {
+        NippyJar {
+            version: NIPPY_JAR_VERSION,
+            user_header: self.user_header,
+            columns: self.columns,
+            rows: self.rows,
+            compressor: self.compressor,
+            filter: None,
+            phf: None,
+            max_row_size: self.max_row_size,
+            data_len: None,
+            delta_offsets,
+            checksums: None,
+            path: PathBuf::new(),
+        }
+    }
+}
+
+/// SHA-256 checksums of the data and offsets files of an immutable [`NippyJar`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NippyJarChecksums {
+    /// Checksum of the data file.
+    pub data: [u8; 32],
+    /// Checksum of the offsets file.
+    pub offsets: [u8; 32],
+}
+
 #[cfg(test)]
 impl<H: NippyJarHeader> NippyJar<H> {
     /// If required, prepares any compression algorithm to an early pass of the data.
@@ -335,6 +566,8 @@ pub struct DataReader {
     offset_mmap: Mmap,
     /// Number of bytes that represent one offset.
     offset_size: u8,
+    /// Delta encoded offsets reader, if the offsets file is not fixed-width.
+    delta_offsets: Option<DeltaOffsets>,
 }
 
 impl DataReader {
@@ -348,29 +581,80 @@ impl DataReader {
         // SAFETY: File is read-only and its descriptor is kept alive as long as the mmap handle.
         let offset_mmap = unsafe { Mmap::map(&offset_file)? };
 
-        // First byte is the size of one offset in bytes
+        // First byte is the size of one offset in bytes, or the marker of delta encoded offsets
         let offset_size = offset_mmap[0];
+        if offset_size == offsets::DELTA_OFFSETS_MARKER {
+            // Anything else starting with a zero is a fixed-width offsets file with an invalid size
+            if !offsets::has_delta_header(&offset_mmap) {
+                return Err(NippyJarError::OffsetSizeTooSmall { offset_size })
+            }
+            let delta_offsets = Some(DeltaOffsets::new(&offset_mmap)?);
+            return Ok(Self {
+                data_file,
+                data_mmap,
+                offset_file,
+                offset_size: writer::OFFSET_SIZE_BYTES,
+                offset_mmap,
+                delta_offsets,
+            })
+        }
 
         // Ensure that the size of an offset is at most 8 bytes.
         if offset_size > 8 {
             return Err(NippyJarError::OffsetSizeTooBig { offset_size })
-        } else if offset_size == 0 {
-            return Err(NippyJarError::OffsetSizeTooSmall { offset_size })
         }
 
-        Ok(Self { data_file, data_mmap, offset_file, offset_size, offset_mmap })
+        Ok(Self {
+            data_file,
+            data_mmap,
+            offset_file,
+            offset_size,
+            offset_mmap,
+            delta_offsets: None,
+        })
+    }
+
+    /// Returns the [`OffsetsEncoding`] of the offsets file.
+    pub fn offsets_encoding(&self) -> OffsetsEncoding {
+        match &self.delta_offsets {
+            Some(delta) => OffsetsEncoding::Delta { restart_interval: delta.restart_interval() },
+            None => OffsetsEncoding::Fixed,
+        }
     }
 
     /// Returns the offset for the requested data index
     pub fn offset(&self, index: usize) -> Result<u64, NippyJarError> {
+        if let Some(delta) = &self.delta_offsets {
+            return delta.get(&self.offset_mmap, index)
+        }
+
         // + 1 represents the offset_len u8 which is in the beginning of the file
         let from = index * self.offset_size as usize + 1;
 
         self.offset_at(from)
     }
 
+    /// Returns the offsets for the requested data index and the one following it.
+    ///
+    /// Cheaper than two calls to [`Self::offset`] on delta encoded offsets.
+    pub fn offset_pair(&self, index: usize) -> Result<(u64, u64), NippyJarError> {
+        if let Some(delta) = &self.delta_offsets {
+            return delta.get_pair(&self.offset_mmap, index)
+        }
+
+        Ok((self.offset(index)?, self.offset(index + 1)?))
+    }
+
     /// Returns the offset for the requested data index starting from the end
     pub fn reverse_offset(&self, index: usize) -> Result<u64, NippyJarError> {
+        if let Some(delta) = &self.delta_offsets {
+            let index = delta
+                .count()
+                .checked_sub(index + 1)
+                .ok_or(NippyJarError::OffsetOutOfBounds { index })?;
+            return delta.get(&self.offset_mmap, index)
+        }
+
         let offsets_file_size = self.offset_file.metadata()?.len() as usize;
 
         if offsets_file_size > 1 {
@@ -385,6 +669,10 @@ impl DataReader {
     /// Returns total number of offsets in the file.
     /// The size of one offset is determined by the file itself.
     pub fn offsets_count(&self) -> Result<usize, NippyJarError> {
+        if let Some(delta) = &self.delta_offsets {
+            return Ok(delta.count())
+        }
+
         Ok((self.offset_file.metadata()?.len().saturating_sub(1) / self.offset_size as u64)
             as usize)
     }
@@ -403,6 +691,8 @@ impl DataReader {
     }
 
     /// Returns number of bytes that represent one offset.
+    ///
+    /// Delta encoded offsets are always decoded to 8 bytes.
     pub const fn offset_size(&self) -> u8 {
         self.offset_size
     }
@@ -416,14 +706,27 @@ impl DataReader {
     pub fn size(&self) -> usize {
         self.data_mmap.len()
     }
+
+    /// Computes the checksums of the data and offsets files.
+    pub fn checksums(&self) -> NippyJarChecksums {
+        NippyJarChecksums {
+            data: Sha256::digest(&self.data_mmap[..]).into(),
+            offsets: Sha256::digest(&self.offset_mmap[..]).into(),
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use compression::Compression;
+    use proptest::{collection::vec, prelude::*};
     use rand::{rngs::SmallRng, seq::SliceRandom, RngCore, SeedableRng};
-    use std::{fs::OpenOptions, io::Read};
+    use reth_fs_util::FsyncPolicy;
+    use std::{
+        fs::OpenOptions,
+        io::{Read, Seek, SeekFrom, Write},
+    };
 
     type ColumnResults<T> = Vec<ColumnResult<T>>;
     type ColumnValues = Vec<Vec<u8>>;
@@ -459,15 +762,15 @@ mod tests {
 
         let mut config_file = OpenOptions::new().read(true).open(jar.config_path()).unwrap();
         let config_file_len = config_file.metadata().unwrap().len();
-        assert_eq!(config_file_len, 37);
+        assert_eq!(config_file_len, 48);
 
         let mut buf = Vec::with_capacity(config_file_len as usize);
         config_file.read_to_end(&mut buf).unwrap();
 
         assert_eq!(
             vec![
-                1, 0, 0, 0, 0, 0, 0, 0, 23, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 0,
-                0, 0, 0, 0, 0, 0, 0, 0, 0, 0
+                3, 0, 0, 0, 0, 0, 0, 0, 23, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 0,
+                0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0
             ],
             buf
         );
@@ -476,6 +779,35 @@ mod tests {
         // Path is not ser/de
         read_jar.path = file.path().to_path_buf();
         assert_eq!(jar, read_jar);
+
+        let mut read_jar = NippyJar::load_from_reader(&buf[..]).unwrap();
+        read_jar.path = file.path().to_path_buf();
+        assert_eq!(jar, read_jar);
+
+        // Configurations of the first version end with the maximum row size, and are upgraded to
+        // the current version without a data length
+        let mut v1 = buf[..37].to_vec();
+        v1[0] = NIPPY_JAR_VERSION_1 as u8;
+        let mut read_jar = NippyJar::load_from_reader(&v1[..]).unwrap();
+        read_jar.path = file.path().to_path_buf();
+        assert_eq!(read_jar.version(), NIPPY_JAR_VERSION);
+        assert_eq!(read_jar.data_len(), None);
+        assert!(!read_jar.has_delta_offsets());
+        read_jar.data_len = jar.data_len;
+        assert_eq!(jar, read_jar);
+
+        // Same configuration, but with delta encoded offsets
+        v1[0] = NIPPY_JAR_VERSION_2 as u8;
+        let read_jar = NippyJar::<()>::load_from_reader(&v1[..]).unwrap();
+        assert!(read_jar.has_delta_offsets());
+
+        // Versions written by a newer binary can't be loaded
+        let mut unknown = buf.clone();
+        unknown[0] = NIPPY_JAR_VERSION as u8 + 1;
+        assert!(matches!(
+            NippyJar::<()>::load_from_reader(&unknown[..]),
+            Err(NippyJarError::UnsupportedVersion { version }) if version == NIPPY_JAR_VERSION + 1
+        ));
     }
 
     #[test]
@@ -793,6 +1125,106 @@ mod tests {
         test_append_consistency_partial_commit(file_path.path(), &col1, &col2);
     }
 
+    /// Freezes a jar with the given column values, rewrites its offsets with the delta encoding
+    /// and compares every decoded offset and row against the fixed-width ground truth.
+    fn test_delta_offsets(columns: Vec<Vec<Vec<u8>>>, restart_interval: u32, lz4: bool) {
+        let num_rows = columns[0].len();
+        let file_path = tempfile::NamedTempFile::new().unwrap();
+
+        let mut nippy = NippyJar::new_without_header(columns.len(), file_path.path());
+        if lz4 {
+            nippy = nippy.with_lz4();
+        }
+        let mut nippy =
+            nippy.freeze(columns.iter().map(clone_with_result).collect(), num_rows as u64).unwrap();
+
+        let fixed_offsets = std::fs::read(nippy.offsets_path()).unwrap();
+        let expected = {
+            let reader = nippy.open_data_reader().unwrap();
+            assert_eq!(reader.offsets_encoding(), OffsetsEncoding::Fixed);
+            (0..reader.offsets_count().unwrap())
+                .map(|index| reader.offset(index).unwrap())
+                .collect::<Vec<_>>()
+        };
+
+        nippy.rewrite_offsets(OffsetsEncoding::Delta { restart_interval }).unwrap();
+        let nippy = NippyJar::load_without_header(file_path.path()).unwrap();
+        assert!(nippy.has_delta_offsets());
+
+        let reader = nippy.open_data_reader().unwrap();
+        assert_eq!(reader.offsets_encoding(), OffsetsEncoding::Delta { restart_interval });
+        assert_eq!(reader.offsets_count().unwrap(), expected.len());
+        for (index, offset) in expected.iter().enumerate() {
+            assert_eq!(reader.offset(index).unwrap(), *offset);
+            assert_eq!(reader.reverse_offset(expected.len() - 1 - index).unwrap(), *offset);
+        }
+        for index in 0..expected.len() - 1 {
+            assert_eq!(reader.offset_pair(index).unwrap(), (expected[index], expected[index + 1]));
+        }
+        drop(reader);
+
+        let mut cursor = NippyJarCursor::new(&nippy).unwrap();
+        for row_index in 0..num_rows {
+            let row = cursor.row_by_number(row_index).unwrap().unwrap();
+            for (column, value) in row.iter().enumerate() {
+                assert_eq!(*value, columns[column][row_index].as_slice());
+            }
+        }
+        drop(cursor);
+
+        // Delta encoded jars are immutable
+        assert!(matches!(NippyJarWriter::new(nippy), Err(NippyJarError::FrozenJar)));
+
+        // Rewriting back restores the original fixed-width offsets file
+        let mut nippy = NippyJar::load_without_header(file_path.path()).unwrap();
+        nippy.rewrite_offsets(OffsetsEncoding::Fixed).unwrap();
+        assert!(!nippy.has_delta_offsets());
+        assert_eq!(std::fs::read(nippy.offsets_path()).unwrap(), fixed_offsets);
+        let _ = NippyJarWriter::new(nippy).unwrap();
+    }
+
+    #[test]
+    fn test_delta_offsets_full_jar() {
+        let (col1, col2) = test_data(None);
+        test_delta_offsets(vec![col1.clone(), col2.clone()], DEFAULT_RESTART_INTERVAL, false);
+        test_delta_offsets(vec![col1, col2], 7, true);
+    }
+
+    #[test]
+    fn test_offset_size_too_small() {
+        let (col1, col2) = test_data(None);
+        let file_path = tempfile::NamedTempFile::new().unwrap();
+        let nippy = NippyJar::new_without_header(2, file_path.path())
+            .freeze(vec![clone_with_result(&col1), clone_with_result(&col2)], col1.len() as u64)
+            .unwrap();
+
+        // The first offset of a fixed-width offsets file is always zero, so a zeroed offset size
+        // can't be mistaken for the restart interval of a delta encoded header.
+        let mut offsets = std::fs::read(nippy.offsets_path()).unwrap();
+        offsets[0] = 0;
+        std::fs::write(nippy.offsets_path(), offsets).unwrap();
+
+        assert!(matches!(
+            nippy.open_data_reader(),
+            Err(NippyJarError::OffsetSizeTooSmall { offset_size: 0 })
+        ));
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(64))]
+
+        #[test]
+        fn fuzz_delta_offsets(
+            rows in vec(vec(vec(any::<u8>(), 0..300), 3), 1..400),
+            restart_interval in 1u32..100,
+            lz4 in any::<bool>(),
+        ) {
+            let columns =
+                (0..3).map(|column| rows.iter().map(|row| row[column].clone()).collect()).collect();
+            test_delta_offsets(columns, restart_interval, lz4);
+        }
+    }
+
     #[test]
     fn test_pruner() {
         let (col1, col2) = test_data(None);
@@ -815,6 +1247,351 @@ mod tests {
         }
     }
 
+    /// Point of [`NippyJarWriter::commit`] at which the writer is killed.
+    #[derive(Debug, Clone, Copy)]
+    enum CrashPoint {
+        /// Before anything was committed.
+        BeforeData,
+        /// After data was committed.
+        AfterData,
+        /// After data was committed, while the last offset was being written.
+        DuringOffsets,
+        /// After data and offsets were committed.
+        AfterOffsets,
+        /// After data, offsets and configuration were committed.
+        AfterConfig,
+    }
+
+    #[test]
+    fn test_crash_consistency() {
+        let (col1, col2) = test_data(None);
+        let num_columns = 2;
+        // Each row has two columns of 32 bytes
+        let row_size = 64;
+
+        for crash_point in [
+            CrashPoint::BeforeData,
+            CrashPoint::AfterData,
+            CrashPoint::DuringOffsets,
+            CrashPoint::AfterOffsets,
+            CrashPoint::AfterConfig,
+        ] {
+            let file_path = tempfile::NamedTempFile::new().unwrap();
+            append_two_rows(num_columns, file_path.path(), &col1, &col2);
+
+            let nippy = NippyJar::load_without_header(file_path.path()).unwrap();
+            let initial_data_len = nippy.data_len().unwrap();
+            assert_eq!(initial_data_len, 2 * row_size);
+
+            // Appends two rows and kills the writer at the crash point. The writer is forgotten,
+            // so its buffers aren't flushed on drop, just like a killed process.
+            let mut writer = NippyJarWriter::new(nippy).unwrap();
+            writer
+                .append_rows(
+                    vec![
+                        clone_with_result(&col1[2..4].to_vec()),
+                        clone_with_result(&col2[2..4].to_vec()),
+                    ],
+                    2,
+                )
+                .unwrap();
+
+            if !matches!(crash_point, CrashPoint::BeforeData) {
+                writer.commit_data().unwrap();
+            }
+            if matches!(crash_point, CrashPoint::DuringOffsets | CrashPoint::AfterOffsets) {
+                writer.commit_offsets().unwrap();
+            }
+            if matches!(crash_point, CrashPoint::DuringOffsets) {
+                // Tears the last offset apart, leaving 3 of its 8 bytes
+                let offsets_file =
+                    OpenOptions::new().write(true).open(writer.offsets_path()).unwrap();
+                let offsets_len = offsets_file.metadata().unwrap().len();
+                offsets_file.set_len(offsets_len - 5).unwrap();
+            }
+            if matches!(crash_point, CrashPoint::AfterConfig) {
+                writer.commit().unwrap();
+            }
+            std::mem::forget(writer);
+
+            // 5 offsets for the two committed rows, and 4 offsets for the two appended rows
+            let (expected_error, expected_report, expected_rows) = match crash_point {
+                CrashPoint::BeforeData => (None, NippyJarHealReport::default(), 2),
+                CrashPoint::AfterData => (
+                    Some(NippyJarError::UncommittedData {
+                        expected: initial_data_len,
+                        actual: initial_data_len + 2 * row_size,
+                    }),
+                    NippyJarHealReport {
+                        uncommitted_data_bytes: 2 * row_size,
+                        ..Default::default()
+                    },
+                    2,
+                ),
+                CrashPoint::DuringOffsets => (
+                    Some(NippyJarError::TornOffset { len: 3 }),
+                    NippyJarHealReport {
+                        torn_offset_bytes: 3,
+                        uncommitted_offsets: 3,
+                        uncommitted_data_bytes: 2 * row_size,
+                        ..Default::default()
+                    },
+                    2,
+                ),
+                CrashPoint::AfterOffsets => (
+                    Some(NippyJarError::UncommittedOffsets { expected: 5, actual: 9 }),
+                    NippyJarHealReport {
+                        uncommitted_offsets: 4,
+                        uncommitted_data_bytes: 2 * row_size,
+                        ..Default::default()
+                    },
+                    2,
+                ),
+                CrashPoint::AfterConfig => (None, NippyJarHealReport::default(), 4),
+            };
+
+            // Read-only checks report the inconsistency
+            let nippy = NippyJar::load_without_header(file_path.path()).unwrap();
+            assert_eq!(
+                NippyJarChecker::new(nippy).check_consistency().err().map(|err| err.to_string()),
+                expected_error.map(|err| err.to_string()),
+                "{crash_point:?}"
+            );
+
+            // Opening a writer heals it back to the last committed row
+            let nippy = NippyJar::load_without_header(file_path.path()).unwrap();
+            let writer = NippyJarWriter::new(nippy).unwrap();
+            assert_eq!(writer.heal_report(), expected_report, "{crash_point:?}");
+            assert_eq!(writer.rows(), expected_rows, "{crash_point:?}");
+            drop(writer);
+
+            let nippy = NippyJar::load_without_header(file_path.path()).unwrap();
+            assert_eq!(nippy.rows, expected_rows);
+            assert_eq!(nippy.data_len(), Some(expected_rows as u64 * row_size));
+            NippyJarChecker::new(NippyJar::load_without_header(file_path.path()).unwrap())
+                .check_consistency()
+                .unwrap();
+
+            let mut cursor = NippyJarCursor::new(&nippy).unwrap();
+            for row_num in 0..expected_rows {
+                let row = cursor.row_by_number(row_num).unwrap().unwrap();
+                assert_eq!(row, vec![col1[row_num].as_slice(), col2[row_num].as_slice()]);
+            }
+            assert!(cursor.row_by_number(expected_rows).unwrap().is_none());
+        }
+    }
+
+    #[test]
+    fn test_preallocation() {
+        let (col1, col2) = test_data(None);
+        let file_path = tempfile::NamedTempFile::new().unwrap();
+        // Each row has two columns of 32 bytes
+        let row_size = 64;
+
+        let nippy = NippyJar::new_without_header(2, file_path.path());
+        let mut writer = NippyJarWriter::new(nippy)
+            .unwrap()
+            .with_preallocation(4096)
+            .with_fsync_policy(FsyncPolicy::Bytes(row_size));
+        writer.append_rows(vec![clone_with_result(&col1), clone_with_result(&col2)], 2).unwrap();
+
+        // every row was synced on its own, and on commit the data and offsets were synced
+        writer.commit().unwrap();
+        let stats = writer.take_stats();
+        assert_eq!(stats.fsyncs.count, 4);
+        assert!(stats.fsyncs.max <= stats.fsyncs.total);
+        assert!(stats.preallocations <= 1);
+        assert_eq!(writer.take_stats(), NippyJarWriterStats::default());
+
+        // The reserved space isn't part of the data file
+        assert_eq!(File::open(writer.data_path()).unwrap().metadata().unwrap().len(), 2 * row_size);
+        writer.release_preallocation().unwrap();
+        drop(writer);
+
+        let nippy = NippyJar::load_without_header(file_path.path()).unwrap();
+        let writer = NippyJarWriter::new(nippy).unwrap();
+        assert!(writer.heal_report().is_empty());
+        assert_eq!(writer.rows(), 2);
+    }
+
+    #[test]
+    fn test_preallocated_zero_tail() {
+        let (col1, col2) = test_data(None);
+        let file_path = tempfile::NamedTempFile::new().unwrap();
+        // Each row has two columns of 32 bytes
+        let row_size = 64;
+        append_two_rows(2, file_path.path(), &col1, &col2);
+
+        // Appends two rows and kills the writer after the data and offsets were written, on a
+        // filesystem that exposed the space reserved past them as a zero-filled tail.
+        let nippy = NippyJar::load_without_header(file_path.path()).unwrap();
+        let mut writer = NippyJarWriter::new(nippy).unwrap().with_preallocation(4096);
+        writer
+            .append_rows(
+                vec![
+                    clone_with_result(&col1[2..4].to_vec()),
+                    clone_with_result(&col2[2..4].to_vec()),
+                ],
+                2,
+            )
+            .unwrap();
+        writer.commit_data().unwrap();
+        writer.commit_offsets().unwrap();
+        let data_file = OpenOptions::new().write(true).open(writer.data_path()).unwrap();
+        data_file.set_len(4096).unwrap();
+        let offsets_file = OpenOptions::new().write(true).open(writer.offsets_path()).unwrap();
+        let offsets_len = offsets_file.metadata().unwrap().len();
+        offsets_file.set_len(offsets_len + 4 * 8 + 3).unwrap();
+        std::mem::forget(writer);
+
+        // Zero-filled offsets point into the data, so only the row count tells they're not rows
+        let nippy = NippyJar::load_without_header(file_path.path()).unwrap();
+        assert!(matches!(
+            NippyJarChecker::new(nippy).check_consistency(),
+            Err(NippyJarError::TornOffset { len: 3 })
+        ));
+
+        let nippy = NippyJar::load_without_header(file_path.path()).unwrap();
+        let writer = NippyJarWriter::new(nippy).unwrap();
+        assert_eq!(
+            writer.heal_report(),
+            NippyJarHealReport {
+                torn_offset_bytes: 3,
+                uncommitted_offsets: 8,
+                uncommitted_data_bytes: 4096 - 2 * row_size,
+                ..Default::default()
+            }
+        );
+        assert_eq!(writer.rows(), 2);
+        drop(writer);
+
+        let nippy = NippyJar::load_without_header(file_path.path()).unwrap();
+        assert_eq!(nippy.data_len(), Some(2 * row_size));
+        let mut cursor = NippyJarCursor::new(&nippy).unwrap();
+        for row_num in 0..2 {
+            let row = cursor.row_by_number(row_num).unwrap().unwrap();
+            assert_eq!(row, vec![col1[row_num].as_slice(), col2[row_num].as_slice()]);
+        }
+        assert!(cursor.row_by_number(2).unwrap().is_none());
+    }
+
+    /// Freezes a jar with delta encoded offsets, so that it has checksums.
+    fn frozen_jar(file_path: &Path) -> NippyJar {
+        let (col1, col2) = test_data(None);
+        let mut nippy = NippyJar::new_without_header(2, file_path)
+            .freeze(vec![clone_with_result(&col1), clone_with_result(&col2)], col1.len() as u64)
+            .unwrap();
+        assert!(nippy.checksums().is_none());
+        nippy.verify().unwrap();
+
+        nippy.rewrite_offsets(OffsetsEncoding::Delta { restart_interval: 7 }).unwrap();
+        let nippy = NippyJar::load_without_header(file_path).unwrap();
+        assert!(nippy.checksums().is_some());
+        nippy.verify().unwrap();
+        nippy
+    }
+
+    /// Overwrites a byte of the given file with its complement.
+    fn flip_byte(path: &Path, position: u64) {
+        let mut file = OpenOptions::new().read(true).write(true).open(path).unwrap();
+        let mut byte = [0u8];
+        file.seek(SeekFrom::Start(position)).unwrap();
+        file.read_exact(&mut byte).unwrap();
+        file.seek(SeekFrom::Start(position)).unwrap();
+        file.write_all(&[!byte[0]]).unwrap();
+    }
+
+    #[test]
+    fn test_verify_truncated_data() {
+        let file_path = tempfile::NamedTempFile::new().unwrap();
+        let nippy = frozen_jar(file_path.path());
+
+        let data_file = OpenOptions::new().write(true).open(nippy.data_path()).unwrap();
+        data_file.set_len(nippy.data_len().unwrap() - 1).unwrap();
+
+        assert!(matches!(
+            nippy.verify(),
+            Err(NippyJarError::Corrupted { component: NippyJarComponent::Data })
+        ));
+    }
+
+    #[test]
+    fn test_verify_corrupted_files() {
+        let file_path = tempfile::NamedTempFile::new().unwrap();
+
+        // Same length, different content
+        let nippy = frozen_jar(file_path.path());
+        flip_byte(nippy.data_path(), 10);
+        assert!(matches!(
+            nippy.verify(),
+            Err(NippyJarError::Corrupted { component: NippyJarComponent::Data })
+        ));
+
+        // The absolute value of the second restart point, right after the header and the first
+        // restart entry, so that the offsets aren't sorted anymore
+        let nippy = frozen_jar(file_path.path());
+        flip_byte(&nippy.offsets_path(), 13 + 16);
+        assert!(matches!(
+            nippy.verify(),
+            Err(NippyJarError::Corrupted { component: NippyJarComponent::Offsets })
+        ));
+    }
+
+    #[test]
+    fn test_verify_stale_index() {
+        let file_path = tempfile::NamedTempFile::new().unwrap();
+        let nippy = frozen_jar(file_path.path());
+
+        // Filter and PHF bytes left behind by a jar that was frozen with them
+        std::fs::write(nippy.index_path(), [0xde, 0xad, 0xbe, 0xef]).unwrap();
+        assert!(matches!(
+            nippy.verify(),
+            Err(NippyJarError::Corrupted { component: NippyJarComponent::Index })
+        ));
+
+        // Deleting the jar removes the index file as well
+        nippy.delete().unwrap();
+        assert!(!file_path.path().with_extension(INDEX_FILE_EXTENSION).exists());
+    }
+
+    #[test]
+    fn test_verify_config_out_of_sync() {
+        let file_path = tempfile::NamedTempFile::new().unwrap();
+        let mut nippy = frozen_jar(file_path.path());
+
+        nippy.rows += 1;
+        assert!(matches!(
+            nippy.verify(),
+            Err(NippyJarError::Corrupted { component: NippyJarComponent::Config })
+        ));
+
+        nippy.rows -= 1;
+        nippy.data_len = Some(1);
+        assert!(matches!(
+            nippy.verify(),
+            Err(NippyJarError::Corrupted { component: NippyJarComponent::Config })
+        ));
+    }
+
+    #[test]
+    fn test_data_len_mismatch() {
+        let (col1, col2) = test_data(None);
+        let file_path = tempfile::NamedTempFile::new().unwrap();
+        append_two_rows(2, file_path.path(), &col1, &col2);
+
+        // Committed offsets pointing somewhere else than the committed data length can't be the
+        // torn tail of an interrupted write, so it's not healed.
+        let mut nippy = NippyJar::load_without_header(file_path.path()).unwrap();
+        nippy.data_len = Some(1);
+        nippy.freeze_config().unwrap();
+
+        let nippy = NippyJar::load_without_header(file_path.path()).unwrap();
+        assert!(matches!(
+            NippyJarWriter::new(nippy),
+            Err(NippyJarError::DataLenMismatch { data_len: 1, offset: 128 })
+        ));
+    }
+
     fn test_append_consistency_partial_commit(
         file_path: &Path,
         col1: &[Vec<u8>],
@@ -0,0 +1,297 @@
+use crate::NippyJarError;
+use std::io::Write;
+
+/// Marker stored in the first byte of a delta encoded offsets file.
+///
+/// Fixed-width offsets files store the size of one offset in the first byte, which is never zero.
+pub(crate) const DELTA_OFFSETS_MARKER: u8 = 0;
+
+/// Default number of offsets between two restart points of a delta encoded offsets file.
+pub const DEFAULT_RESTART_INTERVAL: u32 = 64;
+
+/// Size of the delta encoded offsets file header: marker, restart interval and number of offsets.
+const HEADER_SIZE: usize = 1 + 4 + 8;
+
+/// Size of a restart table entry: absolute offset and position of the following deltas.
+const RESTART_ENTRY_SIZE: usize = 8 + 8;
+
+/// Maximum number of bytes of a varint encoded `u64`.
+const MAX_VARINT_LEN: usize = 10;
+
+/// Encoding of the offsets file of a [`NippyJar`](crate::NippyJar).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OffsetsEncoding {
+    /// Every offset is stored as a little-endian integer with a fixed number of bytes.
+    ///
+    /// This is the only encoding that can be appended to or pruned.
+    #[default]
+    Fixed,
+    /// Offsets are stored as varint encoded deltas to their predecessor, with the absolute value
+    /// of every `restart_interval`-th offset stored in a restart table for random access.
+    ///
+    /// ## Offset file layout
+    /// * marker byte, always `0`
+    /// * restart interval: `u32` little-endian
+    /// * number of offsets `n`: `u64` little-endian
+    /// * `ceil(n / restart_interval)` restart entries, each made of the absolute offset and the
+    ///   position of the deltas that follow it: `u64` little-endian each
+    /// * deltas of every offset that is not a restart point: LEB128 varints
+    Delta {
+        /// Number of offsets between two restart points.
+        restart_interval: u32,
+    },
+}
+
+impl OffsetsEncoding {
+    /// Returns [`OffsetsEncoding::Delta`] with the [`DEFAULT_RESTART_INTERVAL`].
+    pub const fn delta() -> Self {
+        Self::Delta { restart_interval: DEFAULT_RESTART_INTERVAL }
+    }
+}
+
+/// Writes `count` offsets, as returned by `offset`, using the delta encoding.
+///
+/// Offsets are read twice: once to build the restart table and once to write the deltas, so
+/// only the restart table is held in memory.
+pub(crate) fn write_delta_offsets<W: Write>(
+    out: &mut W,
+    count: usize,
+    restart_interval: u32,
+    offset: impl Fn(usize) -> Result<u64, NippyJarError>,
+) -> Result<(), NippyJarError> {
+    if restart_interval == 0 {
+        return Err(NippyJarError::InvalidRestartInterval)
+    }
+    let interval = restart_interval as usize;
+
+    // Absolute value and deltas position of every restart point.
+    let mut restarts = Vec::with_capacity(count.div_ceil(interval));
+    let mut position = 0u64;
+    let mut previous = 0;
+    for index in 0..count {
+        let value = offset(index)?;
+        if index % interval == 0 {
+            restarts.push((value, position));
+        } else {
+            let delta =
+                value.checked_sub(previous).ok_or(NippyJarError::UnsortedOffsets { index })?;
+            position += varint_len(delta) as u64;
+        }
+        previous = value;
+    }
+
+    out.write_all(&[DELTA_OFFSETS_MARKER])?;
+    out.write_all(&restart_interval.to_le_bytes())?;
+    out.write_all(&(count as u64).to_le_bytes())?;
+    for (value, position) in restarts {
+        out.write_all(&value.to_le_bytes())?;
+        out.write_all(&position.to_le_bytes())?;
+    }
+
+    let mut buf = [0u8; MAX_VARINT_LEN];
+    for index in 0..count {
+        let value = offset(index)?;
+        if index % interval != 0 {
+            let len = encode_varint(value - previous, &mut buf);
+            out.write_all(&buf[..len])?;
+        }
+        previous = value;
+    }
+
+    Ok(())
+}
+
+/// Returns `true` if the buffer starts with a complete delta encoded offsets file header.
+pub(crate) fn has_delta_header(buf: &[u8]) -> bool {
+    buf.len() >= HEADER_SIZE &&
+        buf[0] == DELTA_OFFSETS_MARKER &&
+        u32::from_le_bytes(buf[1..5].try_into().expect("qed")) != 0
+}
+
+/// Random access reader of a delta encoded offsets file.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct DeltaOffsets {
+    /// Number of offsets between two restart points.
+    restart_interval: usize,
+    /// Total number of offsets.
+    count: usize,
+    /// Position of the first delta in the file.
+    deltas_start: usize,
+}
+
+impl DeltaOffsets {
+    /// Parses the header of a delta encoded offsets file.
+    pub(crate) fn new(buf: &[u8]) -> Result<Self, NippyJarError> {
+        if buf.len() < HEADER_SIZE || buf[0] != DELTA_OFFSETS_MARKER {
+            return Err(NippyJarError::OffsetOutOfBounds { index: 0 })
+        }
+
+        let restart_interval = u32::from_le_bytes(buf[1..5].try_into().expect("qed")) as usize;
+        if restart_interval == 0 {
+            return Err(NippyJarError::InvalidRestartInterval)
+        }
+        let count = u64::from_le_bytes(buf[5..HEADER_SIZE].try_into().expect("qed")) as usize;
+
+        let deltas_start = HEADER_SIZE + count.div_ceil(restart_interval) * RESTART_ENTRY_SIZE;
+        if deltas_start > buf.len() {
+            return Err(NippyJarError::OffsetOutOfBounds { index: count })
+        }
+
+        Ok(Self { restart_interval, count, deltas_start })
+    }
+
+    /// Returns the number of offsets between two restart points.
+    pub(crate) const fn restart_interval(&self) -> u32 {
+        self.restart_interval as u32
+    }
+
+    /// Returns the total number of offsets.
+    pub(crate) const fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Returns the offset at the given index.
+    pub(crate) fn get(&self, buf: &[u8], index: usize) -> Result<u64, NippyJarError> {
+        Ok(self.decode(buf, index)?.0)
+    }
+
+    /// Returns the offsets at the given index and the one following it.
+    pub(crate) fn get_pair(&self, buf: &[u8], index: usize) -> Result<(u64, u64), NippyJarError> {
+        let next_index = index + 1;
+        if next_index >= self.count {
+            return Err(NippyJarError::OffsetOutOfBounds { index: next_index })
+        }
+
+        let (value, mut position) = self.decode(buf, index)?;
+        let next = if next_index % self.restart_interval == 0 {
+            self.restart(buf, next_index / self.restart_interval)?.0
+        } else {
+            value +
+                decode_varint(buf, &mut position)
+                    .ok_or(NippyJarError::OffsetOutOfBounds { index: next_index })?
+        };
+
+        Ok((value, next))
+    }
+
+    /// Decodes the offset at the given index, returning it alongside the position of the delta of
+    /// the next offset.
+    fn decode(&self, buf: &[u8], index: usize) -> Result<(u64, usize), NippyJarError> {
+        if index >= self.count {
+            return Err(NippyJarError::OffsetOutOfBounds { index })
+        }
+
+        let (mut value, mut position) = self.restart(buf, index / self.restart_interval)?;
+        for _ in 0..index % self.restart_interval {
+            value += decode_varint(buf, &mut position)
+                .ok_or(NippyJarError::OffsetOutOfBounds { index })?;
+        }
+
+        Ok((value, position))
+    }
+
+    /// Returns the absolute offset of the restart point of `block` and the position of the deltas
+    /// that follow it.
+    fn restart(&self, buf: &[u8], block: usize) -> Result<(u64, usize), NippyJarError> {
+        let start = HEADER_SIZE + block * RESTART_ENTRY_SIZE;
+        let entry = buf
+            .get(start..start + RESTART_ENTRY_SIZE)
+            .ok_or(NippyJarError::OffsetOutOfBounds { index: block * self.restart_interval })?;
+
+        let value = u64::from_le_bytes(entry[..8].try_into().expect("qed"));
+        let position = u64::from_le_bytes(entry[8..].try_into().expect("qed")) as usize;
+        Ok((value, self.deltas_start + position))
+    }
+}
+
+/// Returns the number of bytes of the varint encoding of `value`.
+const fn varint_len(value: u64) -> usize {
+    let bits = u64::BITS - (value | 1).leading_zeros();
+    bits.div_ceil(7) as usize
+}
+
+/// Encodes `value` as a LEB128 varint into `buf`, returning the number of bytes written.
+fn encode_varint(mut value: u64, buf: &mut [u8; MAX_VARINT_LEN]) -> usize {
+    let mut len = 0;
+    while value >= 0x80 {
+        buf[len] = value as u8 | 0x80;
+        value >>= 7;
+        len += 1;
+    }
+    buf[len] = value as u8;
+    len + 1
+}
+
+/// Decodes a LEB128 varint from `buf` at `position`, advancing it past the varint.
+#[inline]
+fn decode_varint(buf: &[u8], position: &mut usize) -> Option<u64> {
+    let mut value = 0u64;
+    let mut shift = 0;
+    loop {
+        let byte = *buf.get(*position)?;
+        *position += 1;
+        value |= ((byte & 0x7f) as u64) << shift;
+        if byte & 0x80 == 0 {
+            return Some(value)
+        }
+        shift += 7;
+        if shift >= u64::BITS {
+            return None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn varint_roundtrip() {
+        let mut buf = [0u8; MAX_VARINT_LEN];
+        for value in [0, 1, 127, 128, 300, u32::MAX as u64, u64::MAX - 1, u64::MAX] {
+            let len = encode_varint(value, &mut buf);
+            assert_eq!(len, varint_len(value));
+
+            let mut position = 0;
+            assert_eq!(decode_varint(&buf[..len], &mut position), Some(value));
+            assert_eq!(position, len);
+        }
+    }
+
+    #[test]
+    fn delta_offsets_roundtrip() {
+        let offsets = (0..1000u64).map(|i| i * i).collect::<Vec<_>>();
+
+        for restart_interval in [1, 3, 64, 2000] {
+            let mut buf = Vec::new();
+            write_delta_offsets(&mut buf, offsets.len(), restart_interval, |i| Ok(offsets[i]))
+                .unwrap();
+
+            let reader = DeltaOffsets::new(&buf).unwrap();
+            assert_eq!(reader.count(), offsets.len());
+            for (index, offset) in offsets.iter().enumerate() {
+                assert_eq!(reader.get(&buf, index).unwrap(), *offset);
+            }
+            for index in 0..offsets.len() - 1 {
+                assert_eq!(
+                    reader.get_pair(&buf, index).unwrap(),
+                    (offsets[index], offsets[index + 1])
+                );
+            }
+            assert!(matches!(
+                reader.get(&buf, offsets.len()),
+                Err(NippyJarError::OffsetOutOfBounds { .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn delta_offsets_unsorted() {
+        let offsets = [0, 10, 5];
+        let mut buf = Vec::new();
+        assert!(matches!(
+            write_delta_offsets(&mut buf, offsets.len(), 64, |i| Ok(offsets[i])),
+            Err(NippyJarError::UnsortedOffsets { index: 2 })
+        ));
+    }
+}
@@ -1,11 +1,13 @@
 use crate::{
     compression::Compression, ColumnResult, NippyJar, NippyJarChecker, NippyJarError,
-    NippyJarHeader,
+    NippyJarHeader, NippyJarHealReport,
 };
+use reth_fs_util::{FsyncPolicy, Preallocator};
 use std::{
     fs::{File, OpenOptions},
-    io::{BufWriter, Read, Seek, SeekFrom, Write},
+    io::{self, BufWriter, Read, Seek, SeekFrom, Write},
     path::Path,
+    time::{Duration, Instant},
 };
 
 /// Size of one offset in bytes.
@@ -16,14 +18,33 @@ pub(crate) const OFFSET_SIZE_BYTES: u8 = 8;
 /// Table data is written directly to disk, while offsets and configuration need to be flushed by
 /// calling `commit()`.
 ///
+/// ## Commit protocol
+/// `commit()` persists the jar in three phases, each synced to disk before the next one starts:
+/// 1. the appended data,
+/// 2. the offsets pointing into the data,
+/// 3. the configuration, carrying the row count and data length.
+///
+/// The configuration is the commit point: if the writer is interrupted before it's updated, the
+/// data and offsets have a tail past the committed rows, which is truncated by
+/// [`NippyJarChecker`] the next time a writer is opened.
+///
 /// ## Offset file layout
 /// The first byte is the size of a single offset in bytes, `m`.
 /// Then, the file contains `n` entries, each with a size of `m`. Each entry represents an offset,
 /// except for the last entry, which represents both the total size of the data file, as well as the
 /// next offset to write new data to.
 ///
+/// Offsets can later be rewritten with [`OffsetsEncoding::Delta`](crate::OffsetsEncoding::Delta)
+/// using [`NippyJar::rewrite_offsets`], after which the jar can no longer be written to.
+///
 /// ## Data file layout
 /// The data file is represented just as a sequence of bytes of data without any delimiters
+///
+/// ## Disk space and syncing
+/// Disk space of the data file can be reserved ahead of the appends with
+/// [`Self::with_preallocation`]. The reserved space doesn't change the length of the data file, so
+/// it's never read as rows. Appended data can be synced before it's committed with
+/// [`Self::with_fsync_policy`].
 #[derive(Debug)]
 pub struct NippyJarWriter<H: NippyJarHeader = ()> {
     /// Associated [`NippyJar`], containing all necessary configurations for data
@@ -43,6 +64,46 @@ pub struct NippyJarWriter<H: NippyJarHeader = ()> {
     column: usize,
     /// Whether the writer has changed data that needs to be committed.
     dirty: bool,
+    /// Inconsistencies healed when opening the jar.
+    heal_report: NippyJarHealReport,
+    /// Reserves disk space of the data file ahead of the appends.
+    preallocator: Preallocator,
+    /// When appended data is synced to disk before it's committed.
+    fsync_policy: FsyncPolicy,
+    /// Bytes appended to the data file since it was last synced.
+    unsynced_bytes: u64,
+    /// Syncs to disk since the stats were last taken.
+    fsyncs: FsyncStats,
+}
+
+/// Disk I/O of a [`NippyJarWriter`] since it was last taken with [`NippyJarWriter::take_stats`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NippyJarWriterStats {
+    /// Number of times disk space was reserved for the data file.
+    pub preallocations: u64,
+    /// Syncs of the data and offsets files to disk.
+    pub fsyncs: FsyncStats,
+}
+
+/// Syncs of a [`NippyJarWriter`] to disk, aggregated so that they take constant space no matter
+/// how rarely the stats are taken.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FsyncStats {
+    /// Number of syncs.
+    pub count: u64,
+    /// Total duration of the syncs.
+    pub total: Duration,
+    /// Duration of the longest sync.
+    pub max: Duration,
+}
+
+impl FsyncStats {
+    /// Records a sync that took `duration`.
+    fn record(&mut self, duration: Duration) {
+        self.count += 1;
+        self.total += duration;
+        self.max = self.max.max(duration);
+    }
 }
 
 impl<H: NippyJarHeader> NippyJarWriter<H> {
@@ -53,21 +114,21 @@ impl<H: NippyJarHeader> NippyJarWriter<H> {
         let (data_file, offsets_file, is_created) =
             Self::create_or_open_files(jar.data_path(), &jar.offsets_path())?;
 
-        let (jar, data_file, offsets_file) = if is_created {
+        let (jar, data_file, offsets_file, heal_report) = if is_created {
             // Makes sure we don't have dangling data and offset files when we just created the file
             jar.freeze_config()?;
 
-            (jar, BufWriter::new(data_file), BufWriter::new(offsets_file))
+            (jar, BufWriter::new(data_file), BufWriter::new(offsets_file), Default::default())
         } else {
             // If we are opening a previously created jar, we need to check its consistency, and
             // make changes if necessary.
             let mut checker = NippyJarChecker::new(jar);
-            checker.ensure_consistency()?;
+            let heal_report = checker.ensure_consistency()?;
 
             let NippyJarChecker { jar, data_file, offsets_file } = checker;
 
             // Calling ensure_consistency, will fill data_file and offsets_file
-            (jar, data_file.expect("qed"), offsets_file.expect("qed"))
+            (jar, data_file.expect("qed"), offsets_file.expect("qed"), heal_report)
         };
 
         let mut writer = Self {
@@ -79,6 +140,11 @@ impl<H: NippyJarHeader> NippyJarWriter<H> {
             offsets: Vec::with_capacity(1_000_000),
             column: 0,
             dirty: false,
+            heal_report,
+            preallocator: Preallocator::new(0),
+            fsync_policy: FsyncPolicy::default(),
+            unsynced_bytes: 0,
+            fsyncs: FsyncStats::default(),
         };
 
         if !is_created {
@@ -89,6 +155,28 @@ impl<H: NippyJarHeader> NippyJarWriter<H> {
         Ok(writer)
     }
 
+    /// Reserves disk space of the data file `chunk_size` bytes at a time ahead of the appends.
+    /// Disabled if `0`, which is the default.
+    pub const fn with_preallocation(mut self, chunk_size: u64) -> Self {
+        self.preallocator = Preallocator::new(chunk_size);
+        self
+    }
+
+    /// Sets when appended data is synced to disk before it's committed. Defaults to
+    /// [`FsyncPolicy::Commit`].
+    pub const fn with_fsync_policy(mut self, fsync_policy: FsyncPolicy) -> Self {
+        self.fsync_policy = fsync_policy;
+        self
+    }
+
+    /// Returns the disk I/O since the stats were last taken, and resets them.
+    pub fn take_stats(&mut self) -> NippyJarWriterStats {
+        NippyJarWriterStats {
+            preallocations: self.preallocator.take_calls(),
+            fsyncs: std::mem::take(&mut self.fsyncs),
+        }
+    }
+
     /// Returns a reference to `H` of [`NippyJar`]
     pub const fn user_header(&self) -> &H {
         &self.jar.user_header
@@ -113,6 +201,11 @@ impl<H: NippyJarHeader> NippyJarWriter<H> {
         self.dirty = true
     }
 
+    /// Returns the inconsistencies healed when opening the jar.
+    pub const fn heal_report(&self) -> NippyJarHealReport {
+        self.heal_report
+    }
+
     /// Gets total writer rows in jar.
     pub const fn rows(&self) -> usize {
         self.jar.rows()
@@ -227,16 +320,27 @@ impl<H: NippyJarHeader> NippyJarWriter<H> {
     /// Writes column to data file. If it's the last column of the row, call `finalize_row()`
     fn write_column(&mut self, value: &[u8]) -> Result<usize, NippyJarError> {
         self.uncompressed_row_size += value.len();
-        let len = if let Some(compression) = &self.jar.compressor {
+        let value = if let Some(compression) = &self.jar.compressor {
             let before = self.tmp_buf.len();
             let len = compression.compress_to(value, &mut self.tmp_buf)?;
-            self.data_file.write_all(&self.tmp_buf[before..before + len])?;
-            len
+            &self.tmp_buf[before..before + len]
         } else {
-            self.data_file.write_all(value)?;
-            value.len()
+            value
         };
 
+        // The last offset is where the column is written to
+        let offset = *self.offsets.last().expect("qed");
+        self.preallocator.reserve(self.data_file.get_ref(), offset, value.len() as u64)?;
+        self.data_file.write_all(value)?;
+        let len = value.len();
+
+        self.unsynced_bytes += len as u64;
+        if self.fsync_policy.should_sync(self.unsynced_bytes) {
+            self.data_file.flush()?;
+            sync_file(self.data_file.get_ref(), &mut self.fsyncs, File::sync_data)?;
+            self.unsynced_bytes = 0;
+        }
+
         self.column += 1;
 
         if self.jar.columns == self.column {
@@ -317,6 +421,10 @@ impl<H: NippyJarHeader> NippyJarWriter<H> {
         self.offsets_file.get_ref().sync_all()?;
         self.data_file.get_ref().sync_all()?;
 
+        // Truncating released the disk space reserved past the data
+        self.preallocator.reset();
+        self.unsynced_bytes = 0;
+
         self.offsets_file.seek(SeekFrom::End(0))?;
         self.data_file.seek(SeekFrom::End(0))?;
 
@@ -324,6 +432,7 @@ impl<H: NippyJarHeader> NippyJarWriter<H> {
         if self.jar.rows == 0 {
             self.jar.max_row_size = 0;
         }
+        self.jar.data_len = Some(self.data_file.get_ref().metadata()?.len());
         self.jar.freeze_config()?;
 
         Ok(())
@@ -341,13 +450,41 @@ impl<H: NippyJarHeader> NippyJarWriter<H> {
     }
 
     /// Commits configuration and offsets to disk. It drains the internal offset list.
+    ///
+    /// See the [commit protocol](Self#commit-protocol) for the order in which files are synced.
     pub fn commit(&mut self) -> Result<(), NippyJarError> {
+        self.commit_data()?;
+        self.commit_offsets()?;
+        self.commit_config()
+    }
+
+    /// Flushes appended data to disk.
+    pub(crate) fn commit_data(&mut self) -> Result<(), NippyJarError> {
         self.data_file.flush()?;
-        self.data_file.get_ref().sync_all()?;
+        sync_file(self.data_file.get_ref(), &mut self.fsyncs, File::sync_all)?;
+        self.unsynced_bytes = 0;
 
-        self.commit_offsets()?;
+        Ok(())
+    }
+
+    /// Releases the disk space reserved past the end of the data file, once no more rows are going
+    /// to be appended to the jar.
+    pub fn release_preallocation(&mut self) -> Result<(), NippyJarError> {
+        self.data_file.flush()?;
+        let data_len = self.data_file.get_ref().metadata()?.len();
+        if self.preallocator.reserved_end() > data_len {
+            // Truncating to the same length drops the space reserved past it
+            self.data_file.get_mut().set_len(data_len)?;
+        }
+        self.preallocator.reset();
+
+        Ok(())
+    }
 
-        // Flushes `max_row_size` and total `rows` to disk.
+    /// Flushes `max_row_size`, total `rows` and the data length to disk. Must only be called once
+    /// data and offsets have been committed.
+    pub(crate) fn commit_config(&mut self) -> Result<(), NippyJarError> {
+        self.jar.data_len = Some(self.data_file.get_ref().metadata()?.len());
         self.jar.freeze_config()?;
         self.dirty = false;
 
@@ -364,17 +501,13 @@ impl<H: NippyJarHeader> NippyJarWriter<H> {
 
         self.commit_offsets_without_sync_all()?;
 
-        // Flushes `max_row_size` and total `rows` to disk.
-        self.jar.freeze_config()?;
-        self.dirty = false;
-
-        Ok(())
+        self.commit_config()
     }
 
     /// Flushes offsets to disk.
     pub(crate) fn commit_offsets(&mut self) -> Result<(), NippyJarError> {
         self.commit_offsets_inner()?;
-        self.offsets_file.get_ref().sync_all()?;
+        sync_file(self.offsets_file.get_ref(), &mut self.fsyncs, File::sync_all)?;
 
         Ok(())
     }
@@ -464,3 +597,15 @@ impl<H: NippyJarHeader> NippyJarWriter<H> {
         &self.jar
     }
 }
+
+/// Syncs the file to disk with `sync`, and records how long it took.
+fn sync_file(
+    file: &File,
+    fsyncs: &mut FsyncStats,
+    sync: impl FnOnce(&File) -> io::Result<()>,
+) -> io::Result<()> {
+    let start = Instant::now();
+    sync(file)?;
+    fsyncs.record(start.elapsed());
+    Ok(())
+}
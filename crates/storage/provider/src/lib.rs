@@ -43,7 +43,8 @@ pub mod writer;
 
 pub use reth_chain_state::{
     CanonStateNotification, CanonStateNotificationSender, CanonStateNotificationStream,
-    CanonStateNotifications, CanonStateSubscriptions,
+    CanonStateNotifications, CanonStateSubscriptions, ChainReorg, ChainStatus, OrphanedBlock,
+    OrphanedBlocks, OrphanedBlocksFor, ReorgHistory,
 };
 
 // reexport traits to avoid breaking changes
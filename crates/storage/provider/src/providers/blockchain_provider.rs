@@ -17,8 +17,8 @@ use alloy_eips::{
 use alloy_primitives::{Address, BlockHash, BlockNumber, Sealable, TxHash, TxNumber, B256, U256};
 use alloy_rpc_types_engine::ForkchoiceState;
 use reth_chain_state::{
-    BlockState, CanonicalInMemoryState, ForkChoiceNotifications, ForkChoiceSubscriptions,
-    MemoryOverlayStateProvider,
+    BlockState, CanonicalInMemoryState, ChainStatus, ForkChoiceNotifications,
+    ForkChoiceSubscriptions, MemoryOverlayStateProvider, OrphanedBlocksFor, ReorgHistory,
 };
 use reth_chainspec::{ChainInfo, EthereumHardforks};
 use reth_db::{models::BlockNumberAddress, transaction::DbTx, Database};
@@ -35,7 +35,7 @@ use reth_primitives_traits::BlockBody as _;
 use reth_prune_types::{PruneCheckpoint, PruneSegment};
 use reth_stages_types::{StageCheckpoint, StageId};
 use reth_storage_api::{
-    BlockBodyIndicesProvider, DBProvider, NodePrimitivesProvider, OmmersProvider,
+    BlockBodyIndicesProvider, DBProvider, IndexedLogBlocks, NodePrimitivesProvider, OmmersProvider,
     StateCommitmentProvider, StorageChangeSetReader,
 };
 use reth_storage_errors::provider::ProviderResult;
@@ -48,9 +48,10 @@ use revm::{
 use std::{
     ops::{Add, RangeBounds, RangeInclusive, Sub},
     sync::Arc,
-    time::Instant,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
 };
-use tracing::trace;
+use tokio::sync::watch;
+use tracing::{info, trace};
 
 use crate::providers::ProviderNodeTypes;
 
@@ -97,34 +98,66 @@ impl<N: ProviderNodeTypes> BlockchainProvider2<N> {
     ///
     /// This returns a `ProviderResult` since it tries the retrieve the last finalized header from
     /// `database`.
+    ///
+    /// If a forkchoice state was persisted before the last shutdown, its safe and finalized blocks
+    /// are used for the block tags and the state is tracked as restored until the consensus layer
+    /// confirms it with a valid forkchoice update.
     pub fn with_latest(
         storage: ProviderFactory<N>,
         latest: SealedHeader<HeaderTy<N>>,
     ) -> ProviderResult<Self> {
         let provider = storage.provider()?;
-        let finalized_header = provider
-            .last_finalized_block_number()?
-            .map(|num| provider.sealed_header(num))
-            .transpose()?
-            .flatten();
-        let safe_header = provider
-            .last_safe_block_number()?
-            .or_else(|| {
-                // for the purpose of this we can also use the finalized block if we don't have the
-                // safe block
-                provider.last_finalized_block_number().ok().flatten()
-            })
-            .map(|num| provider.sealed_header(num))
+        let restored = provider.last_forkchoice_state()?;
+        let restored_header = |hash: B256| -> ProviderResult<Option<SealedHeader<HeaderTy<N>>>> {
+            if hash.is_zero() {
+                return Ok(None)
+            }
+            Ok(provider.header(&hash)?.map(|header| SealedHeader::new(header, hash)))
+        };
+
+        let mut finalized_header = restored
+            .map(|state| restored_header(state.finalized_block_hash))
             .transpose()?
             .flatten();
-        Ok(Self {
-            database: storage,
-            canonical_in_memory_state: CanonicalInMemoryState::with_head(
-                latest,
-                finalized_header,
-                safe_header,
-            ),
-        })
+        if finalized_header.is_none() {
+            finalized_header = provider
+                .last_finalized_block_number()?
+                .map(|num| provider.sealed_header(num))
+                .transpose()?
+                .flatten();
+        }
+        let mut safe_header =
+            restored.map(|state| restored_header(state.safe_block_hash)).transpose()?.flatten();
+        if safe_header.is_none() {
+            safe_header = provider
+                .last_safe_block_number()?
+                .or_else(|| {
+                    // for the purpose of this we can also use the finalized block if we don't have
+                    // the safe block
+                    provider.last_finalized_block_number().ok().flatten()
+                })
+                .map(|num| provider.sealed_header(num))
+                .transpose()?
+                .flatten();
+        }
+
+        let canonical_in_memory_state =
+            CanonicalInMemoryState::with_head(latest, finalized_header, safe_header);
+        if let Some(state) = restored {
+            let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+            let age = Duration::from_secs(now.saturating_sub(state.timestamp));
+            info!(
+                target: "providers::blockchain",
+                head = %state.head_block_hash,
+                safe = %state.safe_block_hash,
+                finalized = %state.finalized_block_hash,
+                ?age,
+                "Restored last forkchoice state, awaiting confirmation from the consensus layer"
+            );
+            canonical_in_memory_state.set_restored_forkchoice_state(state);
+        }
+
+        Ok(Self { database: storage, canonical_in_memory_state })
     }
 
     /// Gets a clone of `canonical_in_memory_state`.
@@ -200,6 +233,10 @@ impl<N: ProviderNodeTypes> HeaderProvider for BlockchainProvider2<N> {
         self.consistent_provider()?.header_by_number(num)
     }
 
+    fn earliest_header_number(&self) -> ProviderResult<BlockNumber> {
+        self.database.earliest_header_number()
+    }
+
     fn header_td(&self, hash: &BlockHash) -> ProviderResult<Option<U256>> {
         self.consistent_provider()?.header_td(hash)
     }
@@ -265,6 +302,10 @@ impl<N: ProviderNodeTypes> BlockNumReader for BlockchainProvider2<N> {
         self.database.last_block_number()
     }
 
+    fn earliest_block_number(&self) -> ProviderResult<BlockNumber> {
+        self.database.earliest_block_number()
+    }
+
     fn block_number(&self, hash: B256) -> ProviderResult<Option<BlockNumber>> {
         self.consistent_provider()?.block_number(hash)
     }
@@ -446,6 +487,14 @@ impl<N: ProviderNodeTypes> ReceiptProvider for BlockchainProvider2<N> {
     ) -> ProviderResult<Vec<Self::Receipt>> {
         self.consistent_provider()?.receipts_by_tx_range(range)
     }
+
+    fn log_address_blocks(
+        &self,
+        addresses: &[Address],
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Option<IndexedLogBlocks>> {
+        self.consistent_provider()?.log_address_blocks(addresses, range)
+    }
 }
 
 impl<N: ProviderNodeTypes> ReceiptProviderIdExt for BlockchainProvider2<N> {
@@ -508,6 +557,14 @@ impl<N: ProviderNodeTypes> PruneCheckpointReader for BlockchainProvider2<N> {
     fn get_prune_checkpoints(&self) -> ProviderResult<Vec<(PruneSegment, PruneCheckpoint)>> {
         self.consistent_provider()?.get_prune_checkpoints()
     }
+
+    fn get_custom_prune_checkpoint(&self, name: &str) -> ProviderResult<Option<PruneCheckpoint>> {
+        self.consistent_provider()?.get_custom_prune_checkpoint(name)
+    }
+
+    fn get_custom_prune_checkpoints(&self) -> ProviderResult<Vec<(String, PruneCheckpoint)>> {
+        self.consistent_provider()?.get_custom_prune_checkpoints()
+    }
 }
 
 impl<N: NodeTypesWithDB> ChainSpecProvider for BlockchainProvider2<N> {
@@ -710,6 +767,22 @@ impl<N: ProviderNodeTypes> CanonStateSubscriptions for BlockchainProvider2<N> {
     fn subscribe_to_canonical_state(&self) -> CanonStateNotifications<Self::Primitives> {
         self.canonical_in_memory_state.subscribe_canon_state()
     }
+
+    fn reorg_history(&self) -> Option<ReorgHistory> {
+        Some(self.canonical_in_memory_state.reorg_history().clone())
+    }
+
+    fn orphaned_blocks(&self) -> Option<OrphanedBlocksFor<Self::Primitives>> {
+        Some(self.canonical_in_memory_state.orphaned_blocks().clone())
+    }
+
+    fn subscribe_chain_status(&self) -> Option<watch::Receiver<ChainStatus>> {
+        Some(self.canonical_in_memory_state.subscribe_chain_status())
+    }
+
+    fn subscribe_sync_target(&self) -> Option<watch::Receiver<Option<BlockNumHash>>> {
+        Some(self.canonical_in_memory_state.subscribe_sync_target())
+    }
 }
 
 impl<N: ProviderNodeTypes> ForkChoiceSubscriptions for BlockchainProvider2<N> {
@@ -733,6 +806,20 @@ impl<N: ProviderNodeTypes> StorageChangeSetReader for BlockchainProvider2<N> {
     ) -> ProviderResult<Vec<(BlockNumberAddress, StorageEntry)>> {
         self.consistent_provider()?.storage_changeset(block_number)
     }
+
+    fn storage_changesets_by_address_range(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<impl Iterator<Item = ProviderResult<(BlockNumberAddress, StorageEntry)>> + '_>
+    {
+        // the changesets are read up front, because the iterator borrows the consistent provider
+        let changesets = self
+            .consistent_provider()?
+            .storage_changesets_by_address_range(address, range)?
+            .collect::<ProviderResult<Vec<_>>>()?;
+        Ok(changesets.into_iter().map(Ok))
+    }
 }
 
 impl<N: ProviderNodeTypes> ChangeSetReader for BlockchainProvider2<N> {
@@ -742,6 +829,19 @@ impl<N: ProviderNodeTypes> ChangeSetReader for BlockchainProvider2<N> {
     ) -> ProviderResult<Vec<AccountBeforeTx>> {
         self.consistent_provider()?.account_block_changeset(block_number)
     }
+
+    fn account_changesets_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<impl Iterator<Item = ProviderResult<(BlockNumber, AccountBeforeTx)>> + '_>
+    {
+        // the changesets are read up front, because the iterator borrows the consistent provider
+        let changesets = self
+            .consistent_provider()?
+            .account_changesets_range(range)?
+            .collect::<ProviderResult<Vec<_>>>()?;
+        Ok(changesets.into_iter().map(Ok))
+    }
 }
 
 impl<N: ProviderNodeTypes> AccountReader for BlockchainProvider2<N> {
@@ -776,12 +876,10 @@ mod tests {
     use crate::{
         providers::BlockchainProvider2,
         test_utils::{
-            create_test_provider_factory, create_test_provider_factory_with_chain_spec,
-            MockNodeTypesWithDB,
+            create_test_provider_factory, MockNodeTypesWithDB, TestChainParams, TestDatadir,
         },
         writer::UnifiedStorageWriter,
         BlockWriter, CanonChainTracker, ProviderFactory, StaticFileProviderFactory,
-        StaticFileWriter,
     };
     use alloy_eips::{eip4895::Withdrawals, BlockHashOrNumber, BlockNumHash, BlockNumberOrTag};
     use alloy_primitives::{BlockNumber, TxNumber, B256};
@@ -794,20 +892,17 @@ mod tests {
     use reth_chainspec::{
         ChainSpec, ChainSpecBuilder, ChainSpecProvider, EthereumHardfork, MAINNET,
     };
-    use reth_db::{
-        models::{AccountBeforeTx, StoredBlockBodyIndices},
-        tables,
-    };
-    use reth_db_api::{cursor::DbCursorRO, transaction::DbTx};
+    use reth_db::models::{AccountBeforeTx, StoredBlockBodyIndices, StoredForkchoiceState};
     use reth_errors::ProviderError;
     use reth_execution_types::{Chain, ExecutionOutcome};
-    use reth_primitives::{BlockExt, EthPrimitives, Receipt, SealedBlock, StaticFileSegment};
+    use reth_primitives::{BlockExt, EthPrimitives, Receipt, SealedBlock};
     use reth_primitives_traits::{BlockBody as _, SignedTransaction};
     use reth_storage_api::{
         BlockBodyIndicesProvider, BlockHashReader, BlockIdReader, BlockNumReader, BlockReader,
-        BlockReaderIdExt, BlockSource, ChangeSetReader, DatabaseProviderFactory, HeaderProvider,
-        OmmersProvider, ReceiptProvider, ReceiptProviderIdExt, StateProviderFactory,
-        TransactionVariant, TransactionsProvider, WithdrawalsProvider,
+        BlockReaderIdExt, BlockSource, ChainStateBlockWriter, ChangeSetReader,
+        DatabaseProviderFactory, HeaderProvider, OmmersProvider, ReceiptProvider,
+        ReceiptProviderIdExt, StateProviderFactory, TransactionVariant, TransactionsProvider,
+        WithdrawalsProvider,
     };
     use reth_testing_utils::generators::{
         self, random_block, random_block_range, random_changeset_range, random_eoa_accounts,
@@ -870,57 +965,43 @@ mod tests {
         Vec<SealedBlock>,
         Vec<Vec<Receipt>>,
     )> {
-        let (database_blocks, in_memory_blocks) = random_blocks(
+        // Same as `random_blocks`, the end of the range is inclusive
+        let tx_count = block_range_params.tx_count.start..block_range_params.tx_count.end + 1;
+        let datadir = TestDatadir::with_chain_spec(
+            TestChainParams {
+                blocks: database_blocks as u64 - 1,
+                tx_count: tx_count.clone(),
+                blob_tx_count: 0..0,
+                withdrawals_count: block_range_params.withdrawals_count.clone(),
+                logs_count: 2,
+                empty_genesis: false,
+                ..Default::default()
+            },
+            chain_spec,
+        )?;
+
+        let tip = datadir.tip();
+        let in_memory_blocks = random_block_range(
             rng,
-            database_blocks,
-            in_memory_blocks,
-            block_range_params.requests_count,
-            block_range_params.withdrawals_count,
-            block_range_params.tx_count,
+            tip.number + 1..=tip.number + in_memory_blocks as u64,
+            BlockRangeParams { parent: Some(tip.hash()), tx_count, ..block_range_params },
         );
 
-        let receipts: Vec<Vec<_>> = database_blocks
+        let database_blocks =
+            datadir.blocks().iter().map(|block| block.block.clone()).collect::<Vec<_>>();
+        let receipts: Vec<Vec<_>> = datadir
+            .receipts()
             .iter()
-            .chain(in_memory_blocks.iter())
-            .map(|block| block.body.transactions.iter())
-            .map(|tx| tx.map(|tx| random_receipt(rng, tx, Some(2))).collect())
+            .cloned()
+            .chain(
+                in_memory_blocks
+                    .iter()
+                    .map(|block| block.body.transactions.iter())
+                    .map(|tx| tx.map(|tx| random_receipt(rng, tx, Some(2))).collect()),
+            )
             .collect();
 
-        let factory = create_test_provider_factory_with_chain_spec(chain_spec);
-        let provider_rw = factory.database_provider_rw()?;
-        let static_file_provider = factory.static_file_provider();
-
-        // Write transactions to static files with the right `tx_num``
-        let mut bodies_cursor = provider_rw.tx_ref().cursor_read::<tables::BlockBodyIndices>()?;
-        let mut tx_num = bodies_cursor
-            .seek_exact(database_blocks.first().as_ref().unwrap().number.saturating_sub(1))?
-            .map(|(_, indices)| indices.next_tx_num())
-            .unwrap_or_default();
-
-        // Insert blocks into the database
-        for (block, receipts) in database_blocks.iter().zip(&receipts) {
-            // TODO: this should be moved inside `insert_historical_block`: <https://github.com/paradigmxyz/reth/issues/11524>
-            let mut transactions_writer =
-                static_file_provider.latest_writer(StaticFileSegment::Transactions)?;
-            let mut receipts_writer =
-                static_file_provider.latest_writer(StaticFileSegment::Receipts)?;
-            transactions_writer.increment_block(block.number)?;
-            receipts_writer.increment_block(block.number)?;
-
-            for (tx, receipt) in block.body().transactions().zip(receipts) {
-                transactions_writer.append_transaction(tx_num, tx)?;
-                receipts_writer.append_receipt(tx_num, receipt)?;
-                tx_num += 1;
-            }
-
-            provider_rw.insert_historical_block(
-                block.clone().seal_with_senders().expect("failed to seal block with senders"),
-            )?;
-        }
-
-        // Commit to both storages: database and static files
-        UnifiedStorageWriter::commit(provider_rw)?;
-
+        let factory = datadir.into_provider_factory();
         let provider = BlockchainProvider2::new(factory)?;
 
         // Insert the rest of the blocks and receipts into the in-memory state
@@ -1599,6 +1680,46 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_restored_forkchoice_state_tags() -> eyre::Result<()> {
+        let datadir = TestDatadir::new(TestChainParams { blocks: 5, ..Default::default() })?;
+        let blocks = datadir.blocks().to_vec();
+        let factory = datadir.into_provider_factory();
+
+        // persist the forkchoice state like the engine does on a valid forkchoice update
+        let restored = StoredForkchoiceState {
+            head_block_hash: blocks[5].hash(),
+            safe_block_hash: blocks[4].hash(),
+            finalized_block_hash: blocks[3].hash(),
+            timestamp: 1,
+        };
+        let provider_rw = factory.provider_rw()?;
+        provider_rw.save_forkchoice_state(restored)?;
+        provider_rw.commit()?;
+
+        // simulate a restart
+        let provider = BlockchainProvider2::new(factory)?;
+
+        assert_eq!(
+            provider.canonical_in_memory_state().restored_forkchoice_state(),
+            Some(restored)
+        );
+        assert_eq!(
+            provider.sealed_header_by_number_or_tag(BlockNumberOrTag::Latest)?,
+            Some(blocks[5].block.header.clone())
+        );
+        assert_eq!(
+            provider.sealed_header_by_number_or_tag(BlockNumberOrTag::Safe)?,
+            Some(blocks[4].block.header.clone())
+        );
+        assert_eq!(
+            provider.sealed_header_by_number_or_tag(BlockNumberOrTag::Finalized)?,
+            Some(blocks[3].block.header.clone())
+        );
+
+        Ok(())
+    }
+
     #[test]
     fn test_block_reader_id_ext_header_by_id() -> eyre::Result<()> {
         let mut rng = generators::rng();
@@ -1879,23 +2000,37 @@ mod tests {
         };
         provider.canonical_in_memory_state.update_chain(chain);
 
+        let last_database_changeset = database_changesets
+            .into_iter()
+            .last()
+            .unwrap()
+            .into_iter()
+            .sorted_by_key(|(address, _, _)| *address)
+            .map(|(address, account, _)| AccountBeforeTx { address, info: Some(account) })
+            .collect::<Vec<_>>();
+        let in_memory_changeset = in_memory_changesets
+            .into_iter()
+            .sorted_by_key(|(address, _, _)| *address)
+            .map(|(address, account, _)| AccountBeforeTx { address, info: Some(account) })
+            .collect::<Vec<_>>();
         assert_eq!(
             provider.account_block_changeset(last_database_block).unwrap(),
-            database_changesets
-                .into_iter()
-                .last()
-                .unwrap()
-                .into_iter()
-                .sorted_by_key(|(address, _, _)| *address)
-                .map(|(address, account, _)| AccountBeforeTx { address, info: Some(account) })
-                .collect::<Vec<_>>()
+            last_database_changeset
         );
         assert_eq!(
             provider.account_block_changeset(first_in_memory_block).unwrap(),
-            in_memory_changesets
+            in_memory_changeset
+        );
+        assert_eq!(
+            provider
+                .account_changesets_range(last_database_block..=first_in_memory_block)?
+                .collect::<ProviderResult<Vec<_>>>()?,
+            last_database_changeset
                 .into_iter()
-                .sorted_by_key(|(address, _, _)| *address)
-                .map(|(address, account, _)| AccountBeforeTx { address, info: Some(account) })
+                .map(|account| (last_database_block, account))
+                .chain(
+                    in_memory_changeset.into_iter().map(|account| (first_in_memory_block, account))
+                )
                 .collect::<Vec<_>>()
         );
 
@@ -2144,6 +2279,15 @@ mod tests {
             Some(BlockNumHash { number: finalized_block.number, hash: finalized_block.hash() })
         );
 
+        // The chain status subscription and the block tags are served from the same source
+        let chain_status = *provider.subscribe_chain_status().unwrap().borrow();
+        assert_eq!(chain_status.safe, provider.safe_block_num_hash()?);
+        assert_eq!(chain_status.finalized, provider.finalized_block_num_hash()?);
+        assert_eq!(
+            provider.block_hash_for_id(BlockNumberOrTag::Finalized.into())?,
+            chain_status.finalized.map(|block| block.hash)
+        );
+
         Ok(())
     }
 
@@ -2,13 +2,14 @@ use crate::{
     AccountReader, BlockHashReader, ExecutionDataProvider, StateProvider, StateRootProvider,
 };
 use alloy_primitives::{map::B256HashMap, Address, BlockNumber, Bytes, B256};
-use reth_primitives::{Account, Bytecode};
+use reth_primitives::{Account, Bytecode, StorageEntry};
 use reth_storage_api::{HashedPostStateProvider, StateProofProvider, StorageRootProvider};
 use reth_storage_errors::provider::ProviderResult;
 use reth_trie::{
     updates::TrieUpdates, AccountProof, HashedPostState, HashedStorage, MultiProof,
     MultiProofTargets, StorageMultiProof, TrieInput,
 };
+use std::ops::RangeInclusive;
 
 /// A state provider that resolves to data from either a wrapped [`crate::ExecutionOutcome`]
 /// or an underlying state provider.
@@ -147,6 +148,18 @@ impl<SP: StateProvider, EDP: ExecutionDataProvider> StorageRootProvider
         storage.extend(&hashed_storage);
         self.state_provider.storage_multiproof(address, slots, storage)
     }
+
+    fn storage_range(
+        &self,
+        address: Address,
+        range: RangeInclusive<B256>,
+        limit: usize,
+        hashed_storage: HashedStorage,
+    ) -> ProviderResult<Vec<StorageEntry>> {
+        let mut storage = self.get_hashed_storage(address);
+        storage.extend(&hashed_storage);
+        self.state_provider.storage_range(address, range, limit, storage)
+    }
 }
 
 impl<SP: StateProvider, EDP: ExecutionDataProvider> StateProofProvider
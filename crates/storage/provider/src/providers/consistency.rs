@@ -0,0 +1,1022 @@
+//! Consistency check of the storage, executed on startup.
+//!
+//! The check reads the storage in a single pass into a [`StorageView`]: the tips of the static
+//! file segments with the database tables that continue them, the checkpoints of the stages that
+//! write the segments, and the prune checkpoints. From the view it detects every known
+//! [`Inconsistency`], and plans the [`RepairAction`]s that heal them.
+
+use crate::{
+    providers::{ProviderNodeTypes, StaticFileProvider, StaticFileWriter},
+    BlockBodyIndicesProvider, BlockReader, DBProvider, ProviderFactory, PruneCheckpointReader,
+    PruneCheckpointWriter, StageCheckpointReader, StaticFileProviderFactory,
+};
+use alloy_primitives::{BlockNumber, TxNumber};
+use reth_chainspec::ChainSpecProvider;
+use reth_db::tables;
+use reth_db_api::{cursor::DbCursorRO, table::Table, transaction::DbTx};
+use reth_node_types::NodePrimitives;
+use reth_primitives::StaticFileSegment;
+use reth_prune_types::{PruneCheckpoint, PruneSegment};
+use reth_stages_types::{PipelineTarget, StageId};
+use reth_storage_errors::provider::{ProviderError, ProviderResult};
+use strum::IntoEnumIterator;
+use tracing::{info, warn};
+
+/// Checks the consistency of the static files, the stage checkpoints and the prune checkpoints,
+/// and repairs the storage.
+///
+/// Repairs that only touch the static files or the prune checkpoints are executed by
+/// [`ConsistencyCheck::run`]. Lost data has to be synced again, which is expressed by the
+/// [`ConsistencyReport::unwind_target`] that the caller unwinds the pipeline to.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ConsistencyCheck {
+    /// Whether receipts are pruned, in which case they're not stored in static files.
+    has_receipt_pruning: bool,
+    /// Whether the inconsistencies are only reported, without repairing them.
+    check_only: bool,
+}
+
+impl ConsistencyCheck {
+    /// Creates a new consistency check.
+    pub const fn new(has_receipt_pruning: bool) -> Self {
+        Self { has_receipt_pruning, check_only: false }
+    }
+
+    /// Only reports the inconsistencies and the repair plan, without executing it.
+    pub const fn with_check_only(mut self, check_only: bool) -> Self {
+        self.check_only = check_only;
+        self
+    }
+
+    /// Reads the storage and detects its inconsistencies, without modifying it.
+    pub fn inspect<Provider, N>(
+        &self,
+        static_file_provider: &StaticFileProvider<N>,
+        provider: &Provider,
+    ) -> ProviderResult<ConsistencyReport>
+    where
+        Provider: DBProvider
+            + BlockReader
+            + StageCheckpointReader
+            + PruneCheckpointReader
+            + ChainSpecProvider,
+        N: NodePrimitives,
+    {
+        // OVM historical import is broken and does not work with this check. It's importing
+        // duplicated receipts resulting in having more receipts than the expected transaction
+        // range.
+        //
+        // If we detect an OVM import was done (block #1 <https://optimistic.etherscan.io/block/1>), skip it.
+        // More on [#11099](https://github.com/paradigmxyz/reth/pull/11099).
+        #[cfg(feature = "optimism")]
+        if reth_chainspec::EthChainSpec::chain(&provider.chain_spec()) ==
+            reth_chainspec::Chain::optimism_mainnet() &&
+            provider
+                .block_number(reth_optimism_primitives::bedrock::OVM_HEADER_1_HASH)?
+                .is_some()
+        {
+            info!(target: "reth::cli",
+                "Skipping storage verification for OP mainnet, expected inconsistency in OVM chain"
+            );
+            return Ok(ConsistencyReport::default())
+        }
+
+        info!(target: "reth::cli", "Verifying storage consistency.");
+        let view = StorageView::read(static_file_provider, provider, self.has_receipt_pruning)?;
+        let inconsistencies = view.inconsistencies();
+        Ok(ConsistencyReport { view, inconsistencies })
+    }
+
+    /// Checks the storage of the factory and repairs it, unless
+    /// [`ConsistencyCheck::with_check_only`] is set.
+    ///
+    /// The returned report describes the storage after the corrupted static files were healed.
+    /// If the report has an [`ConsistencyReport::unwind_target`], the caller must unwind the
+    /// pipeline to it.
+    ///
+    /// WARNING: No static file writer should be held before calling this function, otherwise it
+    /// will deadlock.
+    pub fn run<N: ProviderNodeTypes>(
+        &self,
+        factory: &ProviderFactory<N>,
+    ) -> ProviderResult<ConsistencyReport> {
+        let static_file_provider = factory.static_file_provider();
+        let mut report = self.inspect(&static_file_provider, &factory.provider()?)?;
+        report.log();
+        if self.check_only || report.is_consistent() {
+            return Ok(report)
+        }
+
+        if report.has_corrupted_static_files() {
+            report = self.heal_static_files(&static_file_provider, &factory.provider()?, report)?;
+            report.log();
+        }
+
+        let plan = report.plan();
+        self.prune_static_files(&static_file_provider, &plan)?;
+
+        // all prune checkpoints are reset in a single transaction
+        if plan.iter().any(|action| matches!(action, RepairAction::ResetPruneCheckpoint { .. })) {
+            let provider_rw = factory.provider_rw()?;
+            self.reset_prune_checkpoints(&provider_rw, &plan)?;
+            provider_rw.commit()?;
+        }
+
+        Ok(report)
+    }
+
+    /// Repairs the storage like [`ConsistencyCheck::run`], but within the transaction of the given
+    /// provider, which the caller has to commit.
+    ///
+    /// [`ConsistencyCheck::with_check_only`] is ignored.
+    pub fn repair<Provider, N>(
+        &self,
+        static_file_provider: &StaticFileProvider<N>,
+        provider: &Provider,
+    ) -> ProviderResult<ConsistencyReport>
+    where
+        Provider: DBProvider
+            + BlockReader
+            + StageCheckpointReader
+            + PruneCheckpointReader
+            + PruneCheckpointWriter
+            + ChainSpecProvider,
+        N: NodePrimitives,
+    {
+        let mut report = self.inspect(static_file_provider, provider)?;
+        report.log();
+        if report.is_consistent() {
+            return Ok(report)
+        }
+
+        if report.has_corrupted_static_files() {
+            report = self.heal_static_files(static_file_provider, provider, report)?;
+            report.log();
+        }
+
+        let plan = report.plan();
+        self.prune_static_files(static_file_provider, &plan)?;
+        self.reset_prune_checkpoints(provider, &plan)?;
+
+        Ok(report)
+    }
+
+    /// Heals the corrupted static files of the report, and inspects the storage again.
+    ///
+    /// Healing removes the rows of an interrupted write or prune, so segments whose tip decreased
+    /// are reported as [`Inconsistency::TruncatedStaticFile`].
+    pub(crate) fn heal_static_files<Provider, N>(
+        &self,
+        static_file_provider: &StaticFileProvider<N>,
+        provider: &Provider,
+        report: ConsistencyReport,
+    ) -> ProviderResult<ConsistencyReport>
+    where
+        Provider: DBProvider
+            + BlockReader
+            + StageCheckpointReader
+            + PruneCheckpointReader
+            + ChainSpecProvider,
+        N: NodePrimitives,
+    {
+        for action in report.plan() {
+            if let RepairAction::HealStaticFile(segment) = action {
+                info!(target: "reth::providers::static_file", ?segment, "Healing static file.");
+                // Fetching the writer heals any file level inconsistency.
+                static_file_provider.latest_writer(segment)?;
+            }
+        }
+
+        let mut healed = self.inspect(static_file_provider, provider)?;
+        let truncated = report
+            .view
+            .segments
+            .iter()
+            .zip(&healed.view.segments)
+            .filter(|(before, after)| before.highest_block != after.highest_block)
+            .map(|(before, after)| Inconsistency::TruncatedStaticFile {
+                segment: before.segment,
+                from: before.highest_block,
+                to: after.highest_block,
+            })
+            .collect::<Vec<_>>();
+        healed.inconsistencies.splice(0..0, truncated);
+        Ok(healed)
+    }
+
+    /// Removes the static file rows that are ahead of the stage checkpoints.
+    pub(crate) fn prune_static_files<N: NodePrimitives>(
+        &self,
+        static_file_provider: &StaticFileProvider<N>,
+        plan: &[RepairAction],
+    ) -> ProviderResult<()> {
+        for action in plan {
+            let RepairAction::PruneStaticFile { segment, rows, last_block } = *action else {
+                continue
+            };
+            info!(
+                target: "reth::providers",
+                ?segment,
+                rows,
+                to = last_block,
+                "Unwinding static file segment."
+            );
+            let mut writer = static_file_provider.latest_writer(segment)?;
+            match segment {
+                StaticFileSegment::Headers => writer.prune_headers(rows)?,
+                StaticFileSegment::Transactions => writer.prune_transactions(rows, last_block)?,
+                StaticFileSegment::Receipts => writer.prune_receipts(rows, last_block)?,
+            }
+            writer.commit()?;
+        }
+        Ok(())
+    }
+
+    /// Saves the prune checkpoints that are reset by the plan.
+    fn reset_prune_checkpoints<Provider: PruneCheckpointWriter>(
+        &self,
+        provider: &Provider,
+        plan: &[RepairAction],
+    ) -> ProviderResult<()> {
+        for action in plan {
+            let RepairAction::ResetPruneCheckpoint { segment, checkpoint } = *action else {
+                continue
+            };
+            info!(target: "reth::providers", ?segment, ?checkpoint, "Resetting prune checkpoint.");
+            provider.save_prune_checkpoint(segment, checkpoint)?;
+        }
+        Ok(())
+    }
+}
+
+/// The storage as read by the [`ConsistencyCheck`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StorageView {
+    /// The checked static file segments.
+    pub segments: Vec<SegmentView>,
+    /// The prune checkpoints of segments whose data is written by a stage.
+    pub prune_checkpoints: Vec<PruneCheckpointView>,
+}
+
+/// A static file segment, and the database data it depends on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SegmentView {
+    /// The static file segment.
+    pub segment: StaticFileSegment,
+    /// Error of the file level check of the latest static file, if it's corrupted.
+    pub corruption: Option<String>,
+    /// Highest block in the static files.
+    pub highest_block: Option<BlockNumber>,
+    /// Highest transaction in the static files, for transaction based segments.
+    pub highest_tx: Option<TxNumber>,
+    /// Highest block whose transactions are all in the static files, for transaction based
+    /// segments.
+    pub complete_block: Option<BlockNumber>,
+    /// First key of the database table that continues the static files.
+    pub db_first_key: Option<u64>,
+    /// Last key of the database table that continues the static files.
+    pub db_last_key: Option<u64>,
+    /// Checkpoint of the stage that writes the segment.
+    pub checkpoint: BlockNumber,
+    /// Last transaction of the checkpoint block, for transaction based segments.
+    pub checkpoint_last_tx: Option<TxNumber>,
+}
+
+impl SegmentView {
+    /// Returns the highest block for which the segment is complete.
+    fn effective_block(&self) -> BlockNumber {
+        self.complete_block.or(self.highest_block).unwrap_or_default()
+    }
+
+    /// Returns the highest entry of the segment, a block for headers and a transaction otherwise.
+    fn highest_entry(&self) -> u64 {
+        if self.segment.is_headers() {
+            self.effective_block()
+        } else {
+            self.highest_tx.unwrap_or_default()
+        }
+    }
+}
+
+/// A prune checkpoint, and the checkpoint of the stage that writes the pruned data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PruneCheckpointView {
+    /// The prune segment.
+    pub segment: PruneSegment,
+    /// The prune checkpoint of the segment.
+    pub checkpoint: PruneCheckpoint,
+    /// The stage that writes the pruned data.
+    pub stage: StageId,
+    /// Checkpoint of the stage.
+    pub stage_checkpoint: BlockNumber,
+    /// Last transaction of the stage checkpoint block.
+    pub stage_last_tx: Option<TxNumber>,
+}
+
+impl StorageView {
+    /// Reads the view of the storage.
+    pub fn read<Provider, N>(
+        static_file_provider: &StaticFileProvider<N>,
+        provider: &Provider,
+        has_receipt_pruning: bool,
+    ) -> ProviderResult<Self>
+    where
+        Provider: DBProvider + BlockReader + StageCheckpointReader + PruneCheckpointReader,
+        N: NodePrimitives,
+    {
+        let last_tx = |block| {
+            provider.block_body_indices(block).map(|indices| indices.map(|i| i.last_tx_num()))
+        };
+
+        let mut segments = Vec::new();
+        for segment in StaticFileSegment::iter() {
+            if has_receipt_pruning && segment.is_receipts() {
+                // Pruned nodes (including full node) do not store receipts as static files.
+                continue
+            }
+
+            let corruption = static_file_provider.check_segment_consistency(segment).err().map(
+                |err| match err {
+                    ProviderError::NippyJar(err) => err,
+                    err => err.to_string(),
+                },
+            );
+            let highest_block = static_file_provider.get_highest_static_file_block(segment);
+            let highest_tx = static_file_provider.get_highest_static_file_tx(segment);
+
+            // A prune interruption might have decreased the number of transactions without
+            // updating the last block of the segment.
+            let complete_block = match highest_tx {
+                Some(highest_tx) => {
+                    let mut block = highest_block.unwrap_or_default();
+                    while block > 0 && last_tx(block)?.is_some_and(|last| last > highest_tx) {
+                        block -= 1;
+                    }
+                    Some(block)
+                }
+                None => None,
+            };
+
+            let (db_first_key, db_last_key) = match segment {
+                StaticFileSegment::Headers => table_bounds::<_, tables::Headers>(provider)?,
+                StaticFileSegment::Transactions => {
+                    table_bounds::<_, tables::Transactions>(provider)?
+                }
+                StaticFileSegment::Receipts => table_bounds::<_, tables::Receipts>(provider)?,
+            };
+
+            let checkpoint = stage_checkpoint(provider, segment_stage(segment))?;
+            let checkpoint_last_tx = if segment.is_headers() { None } else { last_tx(checkpoint)? };
+
+            segments.push(SegmentView {
+                segment,
+                corruption,
+                highest_block,
+                highest_tx,
+                complete_block,
+                db_first_key,
+                db_last_key,
+                checkpoint,
+                checkpoint_last_tx,
+            });
+        }
+
+        let mut prune_checkpoints = Vec::new();
+        for (segment, checkpoint) in provider.get_prune_checkpoints()? {
+            let Some(stage) = prune_segment_stage(segment) else { continue };
+            let stage_checkpoint = stage_checkpoint(provider, stage)?;
+            prune_checkpoints.push(PruneCheckpointView {
+                segment,
+                checkpoint,
+                stage,
+                stage_checkpoint,
+                stage_last_tx: last_tx(stage_checkpoint)?,
+            });
+        }
+
+        Ok(Self { segments, prune_checkpoints })
+    }
+
+    /// Detects the inconsistencies of the storage.
+    pub fn inconsistencies(&self) -> Vec<Inconsistency> {
+        let mut inconsistencies = Vec::new();
+        for view in &self.segments {
+            let segment = view.segment;
+            if let Some(error) = &view.corruption {
+                // the segment is checked again once it's healed
+                inconsistencies
+                    .push(Inconsistency::CorruptedStaticFile { segment, error: error.clone() });
+                continue
+            }
+
+            if let Some(complete_block) =
+                view.complete_block.filter(|block| Some(*block) < view.highest_block)
+            {
+                inconsistencies.push(Inconsistency::MissingTransactions {
+                    segment,
+                    highest_block: view.highest_block.unwrap_or_default(),
+                    complete_block,
+                });
+            }
+
+            let static_file_block = view.effective_block();
+            let static_file_entry = view.highest_entry();
+            if let Some(db_first_key) = view.db_first_key {
+                // If there is a gap between the static files and the database, static file data
+                // was most likely lost.
+                if !(db_first_key <= static_file_entry || static_file_entry + 1 == db_first_key) {
+                    inconsistencies.push(Inconsistency::DatabaseGap {
+                        segment,
+                        static_file_entry,
+                        db_first_key,
+                        static_file_block,
+                    });
+                    continue
+                }
+
+                // The database is ahead of the static files, so the checkpoint refers to the
+                // database.
+                if view.db_last_key.is_some_and(|last| last > static_file_entry) {
+                    continue
+                }
+            }
+
+            let stage = segment_stage(segment);
+            if view.checkpoint > static_file_block {
+                inconsistencies.push(Inconsistency::CheckpointAheadOfStaticFile {
+                    segment,
+                    stage,
+                    checkpoint: view.checkpoint,
+                    static_file_block,
+                });
+            } else if view.checkpoint < static_file_block {
+                let rows = if segment.is_headers() {
+                    Some(static_file_block - view.checkpoint)
+                } else {
+                    view.checkpoint_last_tx.map(|last_tx| static_file_entry - last_tx)
+                };
+                if let Some(rows) = rows {
+                    inconsistencies.push(Inconsistency::StaticFileAheadOfCheckpoint {
+                        segment,
+                        stage,
+                        checkpoint: view.checkpoint,
+                        static_file_block,
+                        rows,
+                    });
+                }
+            }
+        }
+
+        for view in &self.prune_checkpoints {
+            let Some(pruned_block) = view.checkpoint.block_number else { continue };
+            if pruned_block > view.stage_checkpoint {
+                inconsistencies.push(Inconsistency::PruneCheckpointAheadOfStage {
+                    segment: view.segment,
+                    pruned_block,
+                    stage: view.stage,
+                    stage_checkpoint: view.stage_checkpoint,
+                    reset: PruneCheckpoint {
+                        block_number: Some(view.stage_checkpoint),
+                        tx_number: view.checkpoint.tx_number.and(view.stage_last_tx),
+                        prune_mode: view.checkpoint.prune_mode,
+                    },
+                });
+            }
+        }
+
+        inconsistencies
+    }
+}
+
+/// An inconsistency of the storage, see [`Inconsistency::repair`] for how it's repaired.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Inconsistency {
+    /// The latest static file of the segment is corrupted, because appending or pruning data was
+    /// interrupted before its config was committed.
+    CorruptedStaticFile {
+        /// The static file segment.
+        segment: StaticFileSegment,
+        /// The error of the file level check.
+        error: String,
+    },
+    /// Healing the latest static file of the segment removed rows, so data that was expected to
+    /// exist was lost.
+    TruncatedStaticFile {
+        /// The static file segment.
+        segment: StaticFileSegment,
+        /// Highest block before healing.
+        from: Option<BlockNumber>,
+        /// Highest block after healing.
+        to: Option<BlockNumber>,
+    },
+    /// The transaction based segment misses transactions of its highest blocks, because a prune
+    /// was interrupted before the last block of the segment was updated.
+    MissingTransactions {
+        /// The static file segment.
+        segment: StaticFileSegment,
+        /// Highest block of the segment.
+        highest_block: BlockNumber,
+        /// Highest block whose transactions are all in the segment.
+        complete_block: BlockNumber,
+    },
+    /// There's a gap between the static files of the segment and the database table that
+    /// continues them.
+    DatabaseGap {
+        /// The static file segment.
+        segment: StaticFileSegment,
+        /// Highest entry of the static files.
+        static_file_entry: u64,
+        /// First key of the database table.
+        db_first_key: u64,
+        /// Highest block of the static files.
+        static_file_block: BlockNumber,
+    },
+    /// The stage checkpoint is ahead of the static files, so static file data was lost.
+    CheckpointAheadOfStaticFile {
+        /// The static file segment.
+        segment: StaticFileSegment,
+        /// The stage that writes the segment.
+        stage: StageId,
+        /// Checkpoint of the stage.
+        checkpoint: BlockNumber,
+        /// Highest block of the static files.
+        static_file_block: BlockNumber,
+    },
+    /// The static files are ahead of the stage checkpoint, because the database commit of a stage
+    /// failed after the static files were committed, or the reverse on unwind.
+    StaticFileAheadOfCheckpoint {
+        /// The static file segment.
+        segment: StaticFileSegment,
+        /// The stage that writes the segment.
+        stage: StageId,
+        /// Checkpoint of the stage.
+        checkpoint: BlockNumber,
+        /// Highest block of the static files.
+        static_file_block: BlockNumber,
+        /// Number of static file rows after the checkpoint.
+        rows: u64,
+    },
+    /// The prune checkpoint is ahead of the checkpoint of the stage that writes the pruned data,
+    /// e.g. after an unwind. The pruner would never prune the data the stage writes again.
+    PruneCheckpointAheadOfStage {
+        /// The prune segment.
+        segment: PruneSegment,
+        /// Highest pruned block.
+        pruned_block: BlockNumber,
+        /// The stage that writes the pruned data.
+        stage: StageId,
+        /// Checkpoint of the stage.
+        stage_checkpoint: BlockNumber,
+        /// The prune checkpoint at the stage checkpoint.
+        reset: PruneCheckpoint,
+    },
+}
+
+impl Inconsistency {
+    /// Returns the action that repairs the inconsistency.
+    pub fn repair(&self) -> RepairAction {
+        match self {
+            Self::CorruptedStaticFile { segment, .. } => RepairAction::HealStaticFile(*segment),
+            Self::TruncatedStaticFile { to, .. } => RepairAction::Unwind(to.unwrap_or_default()),
+            Self::MissingTransactions { complete_block, .. } => {
+                RepairAction::Unwind(*complete_block)
+            }
+            Self::DatabaseGap { static_file_block, .. } |
+            Self::CheckpointAheadOfStaticFile { static_file_block, .. } => {
+                RepairAction::Unwind(*static_file_block)
+            }
+            Self::StaticFileAheadOfCheckpoint { segment, checkpoint, rows, .. } => {
+                RepairAction::PruneStaticFile {
+                    segment: *segment,
+                    rows: *rows,
+                    last_block: *checkpoint,
+                }
+            }
+            Self::PruneCheckpointAheadOfStage { segment, reset, .. } => {
+                RepairAction::ResetPruneCheckpoint { segment: *segment, checkpoint: *reset }
+            }
+        }
+    }
+}
+
+/// An action of the repair plan of a [`ConsistencyReport`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RepairAction {
+    /// Heals the latest static file of the segment by removing the rows of an interrupted write,
+    /// and restoring the config of an interrupted prune.
+    HealStaticFile(StaticFileSegment),
+    /// Removes the static file rows after the given block.
+    PruneStaticFile {
+        /// The static file segment.
+        segment: StaticFileSegment,
+        /// Number of rows to remove.
+        rows: u64,
+        /// The last block that's kept.
+        last_block: BlockNumber,
+    },
+    /// Replaces the prune checkpoint of the segment.
+    ResetPruneCheckpoint {
+        /// The prune segment.
+        segment: PruneSegment,
+        /// The new checkpoint.
+        checkpoint: PruneCheckpoint,
+    },
+    /// Unwinds the pipeline to the block, so the lost data is synced again.
+    Unwind(BlockNumber),
+}
+
+impl RepairAction {
+    /// The order in which the actions are executed.
+    const fn order(&self) -> u8 {
+        match self {
+            Self::HealStaticFile(_) => 0,
+            Self::PruneStaticFile { .. } => 1,
+            Self::ResetPruneCheckpoint { .. } => 2,
+            Self::Unwind(_) => 3,
+        }
+    }
+}
+
+/// The result of a [`ConsistencyCheck`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConsistencyReport {
+    /// The storage the inconsistencies were detected in.
+    pub view: StorageView,
+    /// The detected inconsistencies.
+    pub inconsistencies: Vec<Inconsistency>,
+}
+
+impl ConsistencyReport {
+    /// Returns `true` if no inconsistency was detected.
+    pub fn is_consistent(&self) -> bool {
+        self.inconsistencies.is_empty()
+    }
+
+    /// Returns `true` if any static file is corrupted.
+    pub fn has_corrupted_static_files(&self) -> bool {
+        self.inconsistencies
+            .iter()
+            .any(|inconsistency| matches!(inconsistency, Inconsistency::CorruptedStaticFile { .. }))
+    }
+
+    /// Returns the repair plan, in the order the actions are executed.
+    ///
+    /// The static files are healed first, then the rows and checkpoints ahead of the stages are
+    /// removed, and finally the pipeline unwinds to the lowest target of all inconsistencies.
+    pub fn plan(&self) -> Vec<RepairAction> {
+        let mut plan = Vec::new();
+        let mut unwind_target: Option<BlockNumber> = None;
+        for action in self.inconsistencies.iter().map(Inconsistency::repair) {
+            match action {
+                RepairAction::Unwind(target) => {
+                    unwind_target = Some(unwind_target.map_or(target, |t| t.min(target)))
+                }
+                action if !plan.contains(&action) => plan.push(action),
+                _ => {}
+            }
+        }
+        plan.sort_by_key(RepairAction::order);
+        plan.extend(unwind_target.map(RepairAction::Unwind));
+        plan
+    }
+
+    /// Returns the pipeline target to unwind to, if data was lost.
+    pub fn unwind_target(&self) -> Option<PipelineTarget> {
+        self.plan().into_iter().find_map(|action| match action {
+            RepairAction::Unwind(target) => Some(PipelineTarget::Unwind(target)),
+            _ => None,
+        })
+    }
+
+    /// Logs the inconsistencies and the repair plan.
+    pub fn log(&self) {
+        for inconsistency in &self.inconsistencies {
+            warn!(target: "reth::providers", ?inconsistency, "Inconsistent storage.");
+        }
+        for (step, action) in self.plan().into_iter().enumerate() {
+            info!(target: "reth::providers", step, ?action, "Planned storage repair.");
+        }
+    }
+}
+
+/// Returns the stage that writes the static file segment.
+const fn segment_stage(segment: StaticFileSegment) -> StageId {
+    match segment {
+        StaticFileSegment::Headers => StageId::Headers,
+        StaticFileSegment::Transactions => StageId::Bodies,
+        StaticFileSegment::Receipts => StageId::Execution,
+    }
+}
+
+/// Returns the stage that writes the data of the prune segment, if any.
+const fn prune_segment_stage(segment: PruneSegment) -> Option<StageId> {
+    match segment {
+        PruneSegment::SenderRecovery => Some(StageId::SenderRecovery),
+        PruneSegment::TransactionLookup => Some(StageId::TransactionLookup),
+        PruneSegment::Receipts | PruneSegment::ContractLogs => Some(StageId::Execution),
+        PruneSegment::AccountHistory => Some(StageId::IndexAccountHistory),
+        PruneSegment::StorageHistory => Some(StageId::IndexStorageHistory),
+        // expired from the static files, or not written by a stage
+        PruneSegment::Headers | PruneSegment::Transactions | PruneSegment::LogAddressIndex => None,
+    }
+}
+
+fn stage_checkpoint<Provider: StageCheckpointReader>(
+    provider: &Provider,
+    stage: StageId,
+) -> ProviderResult<BlockNumber> {
+    Ok(provider.get_stage_checkpoint(stage)?.unwrap_or_default().block_number)
+}
+
+/// Returns the first and last key of the table.
+fn table_bounds<Provider: DBProvider, T: Table<Key = u64>>(
+    provider: &Provider,
+) -> ProviderResult<(Option<u64>, Option<u64>)> {
+    let mut cursor = provider.tx_ref().cursor_read::<T>()?;
+    Ok((cursor.first()?.map(|(key, _)| key), cursor.last()?.map(|(key, _)| key)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test_utils::create_test_provider_factory, StageCheckpointWriter};
+    use alloy_consensus::Header;
+    use alloy_primitives::{BlockHash, U256};
+    use reth_db_api::{models::StoredBlockBodyIndices, transaction::DbTxMut};
+    use reth_primitives::TransactionSigned;
+    use reth_prune_types::PruneMode;
+    use reth_stages_types::StageCheckpoint;
+
+    type Factory = ProviderFactory<crate::test_utils::MockNodeTypesWithDB>;
+
+    fn save_checkpoint(factory: &Factory, stage: StageId, block: BlockNumber) {
+        let provider_rw = factory.provider_rw().unwrap();
+        provider_rw.save_stage_checkpoint(stage, StageCheckpoint::new(block)).unwrap();
+        provider_rw.commit().unwrap();
+    }
+
+    fn write_headers(factory: &Factory, tip: BlockNumber) {
+        let static_file_provider = factory.static_file_provider();
+        let mut writer = static_file_provider.latest_writer(StaticFileSegment::Headers).unwrap();
+        let mut header = Header::default();
+        for number in 0..=tip {
+            header.number = number;
+            writer.append_header(&header, U256::ZERO, &BlockHash::ZERO).unwrap();
+        }
+        writer.commit().unwrap();
+    }
+
+    /// Writes the body indices of blocks with a single transaction each.
+    fn write_body_indices(factory: &Factory, tip: BlockNumber) {
+        let provider_rw = factory.provider_rw().unwrap();
+        for number in 0..=tip {
+            provider_rw
+                .tx_ref()
+                .put::<tables::BlockBodyIndices>(
+                    number,
+                    StoredBlockBodyIndices { first_tx_num: number, tx_count: 1 },
+                )
+                .unwrap();
+        }
+        provider_rw.commit().unwrap();
+    }
+
+    fn inspect(factory: &Factory) -> ConsistencyReport {
+        ConsistencyCheck::default()
+            .inspect(&factory.static_file_provider(), &factory.provider().unwrap())
+            .unwrap()
+    }
+
+    #[test]
+    fn consistent_storage() {
+        let factory = create_test_provider_factory();
+        write_headers(&factory, 10);
+        save_checkpoint(&factory, StageId::Headers, 10);
+
+        let report = inspect(&factory);
+        assert!(report.is_consistent());
+        assert_eq!(report.plan(), vec![]);
+        assert_eq!(ConsistencyCheck::default().run(&factory).unwrap(), report);
+    }
+
+    #[test]
+    fn static_file_ahead_of_checkpoint() {
+        let factory = create_test_provider_factory();
+        write_headers(&factory, 10);
+        save_checkpoint(&factory, StageId::Headers, 5);
+
+        let report = inspect(&factory);
+        assert_eq!(
+            report.inconsistencies,
+            vec![Inconsistency::StaticFileAheadOfCheckpoint {
+                segment: StaticFileSegment::Headers,
+                stage: StageId::Headers,
+                checkpoint: 5,
+                static_file_block: 10,
+                rows: 5,
+            }]
+        );
+        assert_eq!(
+            report.plan(),
+            vec![RepairAction::PruneStaticFile {
+                segment: StaticFileSegment::Headers,
+                rows: 5,
+                last_block: 5
+            }]
+        );
+
+        // the check only mode doesn't repair
+        ConsistencyCheck::default().with_check_only(true).run(&factory).unwrap();
+        assert_eq!(inspect(&factory), report);
+
+        let report = ConsistencyCheck::default().run(&factory).unwrap();
+        assert_eq!(report.unwind_target(), None);
+        assert_eq!(
+            factory
+                .static_file_provider()
+                .get_highest_static_file_block(StaticFileSegment::Headers),
+            Some(5)
+        );
+        assert!(inspect(&factory).is_consistent());
+    }
+
+    #[test]
+    fn checkpoint_ahead_of_static_file() {
+        let factory = create_test_provider_factory();
+        write_headers(&factory, 5);
+        save_checkpoint(&factory, StageId::Headers, 10);
+
+        let report = inspect(&factory);
+        assert_eq!(
+            report.inconsistencies,
+            vec![Inconsistency::CheckpointAheadOfStaticFile {
+                segment: StaticFileSegment::Headers,
+                stage: StageId::Headers,
+                checkpoint: 10,
+                static_file_block: 5,
+            }]
+        );
+        assert_eq!(report.plan(), vec![RepairAction::Unwind(5)]);
+        assert_eq!(report.unwind_target(), Some(PipelineTarget::Unwind(5)));
+    }
+
+    #[test]
+    fn database_gap() {
+        let factory = create_test_provider_factory();
+        write_headers(&factory, 5);
+        save_checkpoint(&factory, StageId::Headers, 8);
+        let provider_rw = factory.provider_rw().unwrap();
+        provider_rw.tx_ref().put::<tables::Headers>(8, Header::default()).unwrap();
+        provider_rw.commit().unwrap();
+
+        let report = inspect(&factory);
+        assert_eq!(
+            report.inconsistencies,
+            vec![Inconsistency::DatabaseGap {
+                segment: StaticFileSegment::Headers,
+                static_file_entry: 5,
+                db_first_key: 8,
+                static_file_block: 5,
+            }]
+        );
+        assert_eq!(report.plan(), vec![RepairAction::Unwind(5)]);
+    }
+
+    #[test]
+    fn missing_transactions() {
+        let factory = create_test_provider_factory();
+        write_body_indices(&factory, 3);
+        save_checkpoint(&factory, StageId::Bodies, 3);
+
+        // the transaction of the last block is missing
+        let static_file_provider = factory.static_file_provider();
+        let mut writer =
+            static_file_provider.latest_writer(StaticFileSegment::Transactions).unwrap();
+        for number in 0..=3 {
+            writer.increment_block(number).unwrap();
+            if number < 3 {
+                writer.append_transaction(number, &TransactionSigned::default()).unwrap();
+            }
+        }
+        writer.commit().unwrap();
+        drop(writer);
+
+        let report = inspect(&factory);
+        assert_eq!(
+            report.inconsistencies,
+            vec![
+                Inconsistency::MissingTransactions {
+                    segment: StaticFileSegment::Transactions,
+                    highest_block: 3,
+                    complete_block: 2,
+                },
+                Inconsistency::CheckpointAheadOfStaticFile {
+                    segment: StaticFileSegment::Transactions,
+                    stage: StageId::Bodies,
+                    checkpoint: 3,
+                    static_file_block: 2,
+                }
+            ]
+        );
+        assert_eq!(report.plan(), vec![RepairAction::Unwind(2)]);
+    }
+
+    #[test]
+    fn prune_checkpoint_ahead_of_stage() {
+        let factory = create_test_provider_factory();
+        write_body_indices(&factory, 10);
+        save_checkpoint(&factory, StageId::SenderRecovery, 5);
+        let checkpoint = PruneCheckpoint {
+            block_number: Some(10),
+            tx_number: Some(10),
+            prune_mode: PruneMode::Distance(64),
+        };
+        let provider_rw = factory.provider_rw().unwrap();
+        provider_rw.save_prune_checkpoint(PruneSegment::SenderRecovery, checkpoint).unwrap();
+        provider_rw.commit().unwrap();
+
+        let reset = PruneCheckpoint { block_number: Some(5), tx_number: Some(5), ..checkpoint };
+        let report = inspect(&factory);
+        assert_eq!(
+            report.inconsistencies,
+            vec![Inconsistency::PruneCheckpointAheadOfStage {
+                segment: PruneSegment::SenderRecovery,
+                pruned_block: 10,
+                stage: StageId::SenderRecovery,
+                stage_checkpoint: 5,
+                reset,
+            }]
+        );
+        assert_eq!(
+            report.plan(),
+            vec![RepairAction::ResetPruneCheckpoint {
+                segment: PruneSegment::SenderRecovery,
+                checkpoint: reset
+            }]
+        );
+
+        ConsistencyCheck::default().run(&factory).unwrap();
+        assert_eq!(
+            factory.provider().unwrap().get_prune_checkpoint(PruneSegment::SenderRecovery).unwrap(),
+            Some(reset)
+        );
+        assert!(inspect(&factory).is_consistent());
+    }
+
+    #[test]
+    fn corrupted_static_file() {
+        let factory = create_test_provider_factory();
+        write_headers(&factory, 10);
+        save_checkpoint(&factory, StageId::Headers, 10);
+
+        // Simulate an interrupted write by removing the last row from the data file without
+        // updating its offset list and configuration.
+        {
+            let static_file_provider = factory.static_file_provider();
+            let mut writer =
+                static_file_provider.latest_writer(StaticFileSegment::Headers).unwrap();
+            let reader = writer.inner().jar().open_data_reader().unwrap();
+            let columns = writer.inner().jar().columns();
+            let data_file = writer.inner().data_file();
+            let last_offset = reader.reverse_offset(columns).unwrap();
+            data_file.get_mut().set_len(last_offset).unwrap();
+            data_file.flush().unwrap();
+            data_file.get_ref().sync_all().unwrap();
+        }
+
+        // healing is done on fetching the writer for the first time, so the provider is recreated
+        let static_file_provider =
+            StaticFileProvider::read_write(factory.static_file_provider().path()).unwrap();
+        let factory = ProviderFactory::new(
+            factory.db_ref().clone(),
+            factory.chain_spec(),
+            static_file_provider,
+        );
+
+        let report = inspect(&factory);
+        assert!(matches!(
+            report.inconsistencies.as_slice(),
+            [Inconsistency::CorruptedStaticFile { segment: StaticFileSegment::Headers, .. }]
+        ));
+        assert_eq!(report.plan(), vec![RepairAction::HealStaticFile(StaticFileSegment::Headers)]);
+
+        let report = ConsistencyCheck::default().run(&factory).unwrap();
+        assert_eq!(
+            report.inconsistencies,
+            vec![
+                Inconsistency::TruncatedStaticFile {
+                    segment: StaticFileSegment::Headers,
+                    from: Some(10),
+                    to: Some(9),
+                },
+                Inconsistency::CheckpointAheadOfStaticFile {
+                    segment: StaticFileSegment::Headers,
+                    stage: StageId::Headers,
+                    checkpoint: 10,
+                    static_file_block: 9,
+                }
+            ]
+        );
+        assert_eq!(report.unwind_target(), Some(PipelineTarget::Unwind(9)));
+    }
+}
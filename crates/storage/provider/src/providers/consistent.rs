@@ -27,8 +27,8 @@ use reth_primitives_traits::BlockBody;
 use reth_prune_types::{PruneCheckpoint, PruneSegment};
 use reth_stages_types::{StageCheckpoint, StageId};
 use reth_storage_api::{
-    BlockBodyIndicesProvider, DatabaseProviderFactory, NodePrimitivesProvider, OmmersProvider,
-    StateProvider, StorageChangeSetReader,
+    BlockBodyIndicesProvider, DatabaseProviderFactory, IndexedLogBlocks, NodePrimitivesProvider,
+    OmmersProvider, StateProvider, StorageChangeSetReader,
 };
 use reth_storage_errors::provider::ProviderResult;
 use revm::db::states::PlainStorageRevert;
@@ -395,6 +395,31 @@ impl<N: ProviderNodeTypes> ConsistentProvider<N> {
         Ok(items)
     }
 
+    /// Splits the block range of a changeset query into the range read from the database and the
+    /// range of in-memory blocks, which take precedence over the database.
+    fn split_changeset_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> (Option<RangeInclusive<BlockNumber>>, Option<RangeInclusive<BlockNumber>>) {
+        let (start, end) = range.into_inner();
+        if start > end {
+            return (None, None)
+        }
+
+        // The last block of the in-memory chain is the lowest block number.
+        let Some(lowest_memory_block) =
+            self.head_block.as_ref().and_then(|b| b.chain().last()).map(|b| b.number())
+        else {
+            return (Some(start..=end), None)
+        };
+
+        let storage_range =
+            (start < lowest_memory_block).then(|| start..=end.min(lowest_memory_block - 1));
+        let in_memory_range =
+            (lowest_memory_block <= end).then(|| lowest_memory_block.max(start)..=end);
+        (storage_range, in_memory_range)
+    }
+
     /// This uses a given [`BlockState`] to initialize a state provider for that block.
     fn block_state_provider_ref(
         &self,
@@ -643,6 +668,10 @@ impl<N: ProviderNodeTypes> HeaderProvider for ConsistentProvider<N> {
         )
     }
 
+    fn earliest_header_number(&self) -> ProviderResult<BlockNumber> {
+        self.storage_provider.earliest_header_number()
+    }
+
     fn header_td(&self, hash: &BlockHash) -> ProviderResult<Option<U256>> {
         if let Some(num) = self.block_number(*hash)? {
             self.header_td_by_number(num)
@@ -766,6 +795,10 @@ impl<N: ProviderNodeTypes> BlockNumReader for ConsistentProvider<N> {
         self.storage_provider.last_block_number()
     }
 
+    fn earliest_block_number(&self) -> ProviderResult<BlockNumber> {
+        self.storage_provider.earliest_block_number()
+    }
+
     fn block_number(&self, hash: B256) -> ProviderResult<Option<BlockNumber>> {
         self.get_in_memory_or_storage_by_block(
             hash.into(),
@@ -1080,6 +1113,33 @@ impl<N: ProviderNodeTypes> ReceiptProvider for ConsistentProvider<N> {
             },
         )
     }
+
+    fn log_address_blocks(
+        &self,
+        addresses: &[Address],
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Option<IndexedLogBlocks>> {
+        let Some(mut indexed) =
+            self.storage_provider.log_address_blocks(addresses, range.clone())?
+        else {
+            return Ok(None)
+        };
+
+        // Database blocks that overlap with the in-memory chain can be of a forked chain, so only
+        // the blocks below it are served from the index.
+        if let Some(lowest_memory_block) =
+            self.head_block.as_ref().and_then(|b| b.chain().last()).map(|b| b.number())
+        {
+            if lowest_memory_block <= *range.start() {
+                return Ok(None)
+            }
+            let indexed_to = indexed.indexed_to.min(lowest_memory_block - 1);
+            indexed.blocks.retain(|block| *block <= indexed_to);
+            indexed.indexed_to = indexed_to;
+        }
+
+        Ok(Some(indexed))
+    }
 }
 
 impl<N: ProviderNodeTypes> ReceiptProviderIdExt for ConsistentProvider<N> {
@@ -1157,7 +1217,7 @@ impl<N: ProviderNodeTypes> OmmersProvider for ConsistentProvider<N> {
             id,
             |db_provider| db_provider.ommers(id),
             |block_state| {
-                if self.chain_spec().final_paris_total_difficulty(block_state.number()).is_some() {
+                if !self.chain_spec().has_ommers(block_state.number()) {
                     return Ok(Some(Vec::new()))
                 }
 
@@ -1228,6 +1288,14 @@ impl<N: ProviderNodeTypes> PruneCheckpointReader for ConsistentProvider<N> {
     fn get_prune_checkpoints(&self) -> ProviderResult<Vec<(PruneSegment, PruneCheckpoint)>> {
         self.storage_provider.get_prune_checkpoints()
     }
+
+    fn get_custom_prune_checkpoint(&self, name: &str) -> ProviderResult<Option<PruneCheckpoint>> {
+        self.storage_provider.get_custom_prune_checkpoint(name)
+    }
+
+    fn get_custom_prune_checkpoints(&self) -> ProviderResult<Vec<(String, PruneCheckpoint)>> {
+        self.storage_provider.get_custom_prune_checkpoints()
+    }
 }
 
 impl<N: ProviderNodeTypes> ChainSpecProvider for ConsistentProvider<N> {
@@ -1377,6 +1445,31 @@ impl<N: ProviderNodeTypes> StorageChangeSetReader for ConsistentProvider<N> {
             self.storage_provider.storage_changeset(block_number)
         }
     }
+
+    fn storage_changesets_by_address_range(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<impl Iterator<Item = ProviderResult<(BlockNumberAddress, StorageEntry)>> + '_>
+    {
+        let (storage_range, in_memory_range) = self.split_changeset_range(range);
+
+        let stored = storage_range
+            .map(|range| self.storage_provider.storage_changesets_by_address_range(address, range))
+            .transpose()?;
+        let in_memory = in_memory_range.into_iter().flatten().flat_map(move |block_number| {
+            match self.storage_changeset(block_number) {
+                Ok(mut changeset) => {
+                    changeset.retain(|(block_address, _)| block_address.address() == address);
+                    changeset.sort_unstable_by_key(|(_, entry)| entry.key);
+                    changeset.into_iter().map(Ok).collect()
+                }
+                Err(err) => vec![Err(err)],
+            }
+        });
+
+        Ok(stored.into_iter().flatten().chain(in_memory))
+    }
 }
 
 impl<N: ProviderNodeTypes> ChangeSetReader for ConsistentProvider<N> {
@@ -1423,6 +1516,29 @@ impl<N: ProviderNodeTypes> ChangeSetReader for ConsistentProvider<N> {
             self.storage_provider.account_block_changeset(block_number)
         }
     }
+
+    fn account_changesets_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<impl Iterator<Item = ProviderResult<(BlockNumber, AccountBeforeTx)>> + '_>
+    {
+        let (storage_range, in_memory_range) = self.split_changeset_range(range);
+
+        let stored = storage_range
+            .map(|range| self.storage_provider.account_changesets_range(range))
+            .transpose()?;
+        let in_memory = in_memory_range.into_iter().flatten().flat_map(move |block_number| {
+            match self.account_block_changeset(block_number) {
+                Ok(mut changeset) => {
+                    changeset.sort_unstable_by_key(|account| account.address);
+                    changeset.into_iter().map(|account| Ok((block_number, account))).collect()
+                }
+                Err(err) => vec![Err(err)],
+            }
+        });
+
+        Ok(stored.into_iter().flatten().chain(in_memory))
+    }
 }
 
 impl<N: ProviderNodeTypes> AccountReader for ConsistentProvider<N> {
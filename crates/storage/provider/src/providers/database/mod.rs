@@ -25,8 +25,8 @@ use reth_primitives::{
 use reth_prune_types::{PruneCheckpoint, PruneModes, PruneSegment};
 use reth_stages_types::{StageCheckpoint, StageId};
 use reth_storage_api::{
-    BlockBodyIndicesProvider, NodePrimitivesProvider, OmmersProvider, StateCommitmentProvider,
-    TryIntoHistoricalStateProvider,
+    BlockBodyIndicesProvider, IndexedLogBlocks, NodePrimitivesProvider, OmmersProvider,
+    StateCommitmentProvider, TryIntoHistoricalStateProvider,
 };
 use reth_storage_errors::provider::ProviderResult;
 use reth_trie::HashedPostState;
@@ -261,6 +261,13 @@ impl<N: ProviderNodeTypes> HeaderProvider for ProviderFactory<N> {
         )
     }
 
+    fn earliest_header_number(&self) -> ProviderResult<BlockNumber> {
+        Ok(self
+            .static_file_provider
+            .get_lowest_static_file_block(StaticFileSegment::Headers)
+            .unwrap_or_default())
+    }
+
     fn header_td(&self, hash: &BlockHash) -> ProviderResult<Option<U256>> {
         self.provider()?.header_td(hash)
     }
@@ -365,6 +372,10 @@ impl<N: ProviderNodeTypes> BlockNumReader for ProviderFactory<N> {
         self.provider()?.last_block_number()
     }
 
+    fn earliest_block_number(&self) -> ProviderResult<BlockNumber> {
+        self.provider()?.earliest_block_number()
+    }
+
     fn block_number(&self, hash: B256) -> ProviderResult<Option<BlockNumber>> {
         self.provider()?.block_number(hash)
     }
@@ -546,6 +557,14 @@ impl<N: ProviderNodeTypes> ReceiptProvider for ProviderFactory<N> {
             |_| true,
         )
     }
+
+    fn log_address_blocks(
+        &self,
+        addresses: &[Address],
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Option<IndexedLogBlocks>> {
+        self.provider()?.log_address_blocks(addresses, range)
+    }
 }
 
 impl<N: ProviderNodeTypes> WithdrawalsProvider for ProviderFactory<N> {
@@ -609,6 +628,14 @@ impl<N: ProviderNodeTypes> PruneCheckpointReader for ProviderFactory<N> {
     fn get_prune_checkpoints(&self) -> ProviderResult<Vec<(PruneSegment, PruneCheckpoint)>> {
         self.provider()?.get_prune_checkpoints()
     }
+
+    fn get_custom_prune_checkpoint(&self, name: &str) -> ProviderResult<Option<PruneCheckpoint>> {
+        self.provider()?.get_custom_prune_checkpoint(name)
+    }
+
+    fn get_custom_prune_checkpoints(&self) -> ProviderResult<Vec<(String, PruneCheckpoint)>> {
+        self.provider()?.get_custom_prune_checkpoints()
+    }
 }
 
 impl<N: ProviderNodeTypes> HashedPostStateProvider for ProviderFactory<N> {
@@ -637,8 +664,9 @@ mod tests {
     use crate::{
         providers::{StaticFileProvider, StaticFileWriter},
         test_utils::{blocks::TEST_BLOCK, create_test_provider_factory, MockNodeTypesWithDB},
-        BlockHashReader, BlockNumReader, BlockWriter, DBProvider, HeaderSyncGapProvider,
-        StorageLocation, TransactionsProvider,
+        BlockHashReader, BlockNumReader, BlockTimestampDirection, BlockWriter, ChangeSetReader,
+        DBProvider, HeaderSyncGapProvider, HistoryWriter, PruneCheckpointWriter,
+        StageCheckpointWriter, StorageChangeSetReader, StorageLocation, TransactionsProvider,
     };
     use alloy_primitives::{TxNumber, B256, U256};
     use assert_matches::assert_matches;
@@ -649,11 +677,19 @@ mod tests {
         tables,
         test_utils::{create_test_static_files_dir, ERROR_TEMPDIR},
     };
+    use reth_db_api::{
+        cursor::{DbCursorRO, DbDupCursorRO},
+        models::{AccountBeforeTx, BlockNumberAddress},
+        transaction::DbTxMut,
+    };
     use reth_primitives::StaticFileSegment;
     use reth_primitives_traits::SignedTransaction;
     use reth_prune_types::{PruneMode, PruneModes};
     use reth_storage_errors::provider::ProviderError;
-    use reth_testing_utils::generators::{self, random_block, random_header, BlockParams};
+    use reth_testing_utils::generators::{
+        self, random_block, random_block_range, random_changeset_range, random_eoa_accounts,
+        random_header, BlockParams, BlockRangeParams,
+    };
     use std::{ops::RangeInclusive, sync::Arc};
     use tokio::sync::watch;
 
@@ -814,4 +850,210 @@ mod tests {
         assert_eq!(gap.local_head, head);
         assert_eq!(gap.target.tip(), consensus_tip.into());
     }
+
+    #[test]
+    fn block_by_timestamp() {
+        let factory = create_test_provider_factory();
+        let mut rng = generators::rng();
+
+        // irregular block times, including consecutive blocks with equal timestamps
+        let mut timestamps = vec![1_000];
+        for _ in 1..50 {
+            let gap = if rng.gen_bool(0.2) { 0 } else { rng.gen_range(1..30) };
+            timestamps.push(timestamps.last().unwrap() + gap);
+        }
+        timestamps[10] = timestamps[9];
+        timestamps[11] = timestamps[9];
+
+        // empty database
+        assert_eq!(
+            factory.block_by_timestamp(1_000, BlockTimestampDirection::Before).unwrap(),
+            None
+        );
+        assert_eq!(
+            factory.block_by_timestamp(1_000, BlockTimestampDirection::After).unwrap(),
+            None
+        );
+
+        let static_file_provider = factory.static_file_provider();
+        let mut static_file_writer =
+            static_file_provider.latest_writer(StaticFileSegment::Headers).unwrap();
+        for (number, timestamp) in timestamps.iter().enumerate() {
+            let header = alloy_consensus::Header {
+                number: number as u64,
+                timestamp: *timestamp,
+                ..Default::default()
+            };
+            static_file_writer.append_header(&header, U256::ZERO, &header.hash_slow()).unwrap();
+        }
+        static_file_writer.commit().unwrap();
+        drop(static_file_writer);
+
+        let first = timestamps[0];
+        let last = *timestamps.last().unwrap();
+        for timestamp in first - 5..=last + 5 {
+            let before = timestamps.iter().rposition(|t| *t <= timestamp).map(|n| n as u64);
+            let after = timestamps.iter().position(|t| *t >= timestamp).map(|n| n as u64);
+            assert_eq!(
+                factory.block_by_timestamp(timestamp, BlockTimestampDirection::Before).unwrap(),
+                before,
+                "{timestamp}"
+            );
+            assert_eq!(
+                factory.block_by_timestamp(timestamp, BlockTimestampDirection::After).unwrap(),
+                after,
+                "{timestamp}"
+            );
+        }
+
+        // before genesis and after the tip
+        assert_eq!(factory.block_by_timestamp(0, BlockTimestampDirection::Before).unwrap(), None);
+        assert_eq!(factory.block_by_timestamp(0, BlockTimestampDirection::After).unwrap(), Some(0));
+        assert_eq!(
+            factory.block_by_timestamp(u64::MAX, BlockTimestampDirection::Before).unwrap(),
+            Some(timestamps.len() as u64 - 1)
+        );
+        assert_eq!(
+            factory.block_by_timestamp(u64::MAX, BlockTimestampDirection::After).unwrap(),
+            None
+        );
+
+        // equal timestamps resolve to the first or last of the blocks
+        assert_eq!(
+            factory.block_by_timestamp(timestamps[9], BlockTimestampDirection::After).unwrap(),
+            timestamps.iter().position(|t| *t == timestamps[9]).map(|n| n as u64)
+        );
+        assert_eq!(
+            factory.block_by_timestamp(timestamps[9], BlockTimestampDirection::Before).unwrap(),
+            timestamps.iter().rposition(|t| *t == timestamps[9]).map(|n| n as u64)
+        );
+    }
+
+    #[test]
+    fn changesets_range_equivalent_to_table_scan() {
+        const TIP: BlockNumber = 60;
+        const INDEXED_TO: BlockNumber = 40;
+
+        let mut rng = generators::rng();
+        let factory = create_test_provider_factory();
+
+        let blocks = random_block_range(&mut rng, 1..=TIP, BlockRangeParams::default());
+        let accounts = random_eoa_accounts(&mut rng, 3);
+        let addresses = accounts.iter().map(|(address, _)| *address).collect::<Vec<_>>();
+        let (changesets, _) = random_changeset_range(
+            &mut rng,
+            &blocks,
+            accounts.into_iter().map(|(address, account)| (address, (account, Vec::new()))),
+            1..4,
+            0..8,
+        );
+
+        // the history index only covers part of the changesets
+        let provider_rw = factory.provider_rw().unwrap();
+        for (block, changeset) in blocks.iter().zip(changesets) {
+            for (address, account, storage) in changeset {
+                provider_rw
+                    .tx_ref()
+                    .put::<tables::AccountChangeSets>(
+                        block.number,
+                        AccountBeforeTx { address, info: Some(account) },
+                    )
+                    .unwrap();
+                for entry in storage {
+                    provider_rw
+                        .tx_ref()
+                        .put::<tables::StorageChangeSets>(
+                            BlockNumberAddress((block.number, address)),
+                            entry,
+                        )
+                        .unwrap();
+                }
+            }
+        }
+        provider_rw.update_history_indices(1..=INDEXED_TO).unwrap();
+        provider_rw
+            .save_stage_checkpoint(StageId::IndexStorageHistory, StageCheckpoint::new(INDEXED_TO))
+            .unwrap();
+        provider_rw.commit().unwrap();
+
+        let provider = factory.provider().unwrap();
+        let storage_changesets = provider
+            .tx_ref()
+            .cursor_dup_read::<tables::StorageChangeSets>()
+            .unwrap()
+            .walk(None)
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        let account_changesets = provider
+            .tx_ref()
+            .cursor_read::<tables::AccountChangeSets>()
+            .unwrap()
+            .walk(None)
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+
+        for range in [1..=TIP, 10..=INDEXED_TO, 25..=55, INDEXED_TO + 1..=TIP, 30..=30, 50..=10] {
+            for address in &addresses {
+                let expected = storage_changesets
+                    .iter()
+                    .filter(|(key, _)| {
+                        key.address() == *address && range.contains(&key.block_number())
+                    })
+                    .cloned()
+                    .collect::<Vec<_>>();
+                assert_eq!(
+                    provider
+                        .storage_changesets_by_address_range(*address, range.clone())
+                        .unwrap()
+                        .collect::<ProviderResult<Vec<_>>>()
+                        .unwrap(),
+                    expected,
+                    "address {address}, range {range:?}"
+                );
+            }
+
+            let expected = account_changesets
+                .iter()
+                .filter(|(block_number, _)| range.contains(block_number))
+                .cloned()
+                .collect::<Vec<_>>();
+            assert_eq!(
+                provider
+                    .account_changesets_range(range.clone())
+                    .unwrap()
+                    .collect::<ProviderResult<Vec<_>>>()
+                    .unwrap(),
+                expected
+            );
+        }
+        drop(provider);
+
+        // the changesets up to block 20 are pruned
+        let provider_rw = factory.provider_rw().unwrap();
+        for segment in [PruneSegment::AccountHistory, PruneSegment::StorageHistory] {
+            provider_rw
+                .save_prune_checkpoint(
+                    segment,
+                    PruneCheckpoint {
+                        block_number: Some(20),
+                        tx_number: None,
+                        prune_mode: PruneMode::Before(21),
+                    },
+                )
+                .unwrap();
+        }
+        provider_rw.commit().unwrap();
+
+        let provider = factory.provider().unwrap();
+        let pruned = ProviderError::ChangeSetsPruned { start: 15, lowest_available: 21 };
+        assert_eq!(
+            provider.storage_changesets_by_address_range(addresses[0], 15..=TIP).err(),
+            Some(pruned.clone())
+        );
+        assert_eq!(provider.account_changesets_range(15..=TIP).err(), Some(pruned));
+        assert!(provider.storage_changesets_by_address_range(addresses[0], 21..=TIP).is_ok());
+        assert!(provider.account_changesets_range(21..=TIP).is_ok());
+    }
 }
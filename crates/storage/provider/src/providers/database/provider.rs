@@ -2,7 +2,7 @@ use crate::{
     bundle_state::StorageRevertsIter,
     providers::{
         database::{chain::ChainStorage, metrics},
-        static_file::StaticFileWriter,
+        static_file::{calculate_hashes, StaticFileWriter, TRANSACTION_HASH_BATCH_SIZE},
         NodeTypesForProvider, StaticFileProvider,
     },
     to_range,
@@ -19,9 +19,8 @@ use crate::{
     StorageReader, StorageTrieWriter, TransactionVariant, TransactionsProvider,
     TransactionsProviderExt, TrieWriter, WithdrawalsProvider,
 };
-use alloy_consensus::{BlockHeader, Header};
+use alloy_consensus::{BlockHeader, Header, TxReceipt};
 use alloy_eips::{
-    eip2718::Encodable2718,
     eip4895::{Withdrawal, Withdrawals},
     BlockHashOrNumber,
 };
@@ -41,11 +40,10 @@ use reth_db_api::{
     database::Database,
     models::{
         sharded_key, storage_sharded_key::StorageShardedKey, AccountBeforeTx, BlockNumberAddress,
-        ShardedKey, StoredBlockBodyIndices,
+        ShardedKey, StoredBlockBodyIndices, StoredForkchoiceState,
     },
     table::Table,
     transaction::{DbTx, DbTxMut},
-    DatabaseError,
 };
 use reth_execution_types::{Chain, ExecutionOutcome};
 use reth_network_p2p::headers::downloader::SyncTarget;
@@ -59,8 +57,8 @@ use reth_primitives_traits::{Block as _, BlockBody as _, SignedTransaction};
 use reth_prune_types::{PruneCheckpoint, PruneModes, PruneSegment};
 use reth_stages_types::{StageCheckpoint, StageId};
 use reth_storage_api::{
-    BlockBodyIndicesProvider, BlockBodyReader, NodePrimitivesProvider, OmmersProvider,
-    StateProvider, StorageChangeSetReader, TryIntoHistoricalStateProvider,
+    BlockBodyIndicesProvider, BlockBodyReader, IndexedLogBlocks, NodePrimitivesProvider,
+    OmmersProvider, StateProvider, StorageChangeSetReader, TryIntoHistoricalStateProvider,
 };
 use reth_storage_errors::provider::{ProviderResult, RootMismatch};
 use reth_trie::{
@@ -257,6 +255,45 @@ impl<TX, N: NodeTypes> AsRef<Self> for DatabaseProvider<TX, N> {
 }
 
 impl<TX: DbTx + DbTxMut + 'static, N: NodeTypesForProvider> DatabaseProvider<TX, N> {
+    /// Indexes the addresses that emitted logs in the given block range if the log address index
+    /// is maintained and the range directly follows the indexed blocks.
+    ///
+    /// If the index is behind, it is left as is and has to be caught up by the
+    /// [`IndexLogAddresses`](StageId::IndexLogAddresses) stage.
+    pub fn update_log_address_index(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<()> {
+        let Some(checkpoint) = self.get_stage_checkpoint(StageId::IndexLogAddresses)? else {
+            return Ok(())
+        };
+        if checkpoint.block_number + 1 != *range.start() {
+            debug!(target: "providers::db", ?range, indexed_to = checkpoint.block_number, "Log address index is not contiguous with the range, skipping");
+            return Ok(())
+        }
+
+        let indices = self.log_addresses_and_blocks_with_range(range.clone())?;
+        self.insert_log_address_index(indices)?;
+        self.save_stage_checkpoint(StageId::IndexLogAddresses, StageCheckpoint::new(*range.end()))
+    }
+
+    /// Unwinds the log address index above the given block if the index is maintained.
+    ///
+    /// This reads the receipts of the unwound blocks, so it has to be called before they are
+    /// removed.
+    fn unwind_log_address_index_above(&self, block: BlockNumber) -> ProviderResult<()> {
+        if let Some(checkpoint) = self.get_stage_checkpoint(StageId::IndexLogAddresses)? {
+            if checkpoint.block_number > block {
+                self.unwind_log_address_index_range(block + 1..=checkpoint.block_number)?;
+                self.save_stage_checkpoint(
+                    StageId::IndexLogAddresses,
+                    StageCheckpoint::new(block),
+                )?;
+            }
+        }
+        Ok(())
+    }
+
     /// Unwinds trie state for the given range.
     ///
     /// This includes calculating the resulted state root and comparing it with the parent block
@@ -538,6 +575,32 @@ impl<TX: DbTx + 'static, N: NodeTypesForProvider> DatabaseProvider<TX, N> {
 }
 
 impl<TX: DbTx + 'static, N: NodeTypesForProvider> DatabaseProvider<TX, N> {
+    /// Returns the addresses that emitted logs in the given block range, along with the numbers of
+    /// the blocks in which they did.
+    pub fn log_addresses_and_blocks_with_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<BTreeMap<Address, Vec<BlockNumber>>> {
+        let mut addresses = BTreeMap::<Address, Vec<BlockNumber>>::new();
+        for block_number in range {
+            let Some(body) = self.block_body_indices(block_number)? else { continue };
+            let tx_range = body.tx_num_range();
+            if tx_range.is_empty() {
+                continue
+            }
+
+            for receipt in self.receipts_by_tx_range(tx_range)? {
+                for log in receipt.logs() {
+                    let blocks = addresses.entry(log.address).or_default();
+                    if blocks.last() != Some(&block_number) {
+                        blocks.push(block_number);
+                    }
+                }
+            }
+        }
+        Ok(addresses)
+    }
+
     fn transactions_by_tx_range_with_cursor<C>(
         &self,
         range: impl RangeBounds<TxNumber>,
@@ -911,6 +974,27 @@ impl<TX: DbTx, N: NodeTypes> AccountExtReader for DatabaseProvider<TX, N> {
     }
 }
 
+impl<TX: DbTx, N: NodeTypes> DatabaseProvider<TX, N> {
+    /// Returns [`ProviderError::ChangeSetsPruned`] if the given history segment is pruned at the
+    /// `start` block.
+    fn ensure_changesets_available(
+        &self,
+        segment: PruneSegment,
+        start: BlockNumber,
+    ) -> ProviderResult<()> {
+        // The checkpoint stores the highest pruned block number, so changesets exist for all
+        // blocks strictly above it.
+        let pruned_to = self
+            .tx
+            .get::<tables::PruneCheckpoints>(segment)?
+            .and_then(|checkpoint| checkpoint.block_number);
+        if let Some(pruned_to) = pruned_to.filter(|pruned_to| start <= *pruned_to) {
+            return Err(ProviderError::ChangeSetsPruned { start, lowest_available: pruned_to + 1 })
+        }
+        Ok(())
+    }
+}
+
 impl<TX: DbTx, N: NodeTypes> StorageChangeSetReader for DatabaseProvider<TX, N> {
     fn storage_changeset(
         &self,
@@ -924,6 +1008,84 @@ impl<TX: DbTx, N: NodeTypes> StorageChangeSetReader for DatabaseProvider<TX, N>
             .map(|result| -> ProviderResult<_> { Ok(result?) })
             .collect()
     }
+
+    fn storage_changesets_by_address_range(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<impl Iterator<Item = ProviderResult<(BlockNumberAddress, StorageEntry)>> + '_>
+    {
+        let (start, end) = range.into_inner();
+        let mut blocks = BTreeSet::new();
+        let mut unindexed = None;
+        if start <= end {
+            self.ensure_changesets_available(PruneSegment::StorageHistory, start)?;
+
+            // Blocks covered by the history index are only visited if the address changed a slot
+            // in them, the blocks above it are all visited.
+            let indexed_to = self
+                .get_stage_checkpoint(StageId::IndexStorageHistory)?
+                .map(|checkpoint| checkpoint.block_number)
+                .filter(|indexed_to| *indexed_to >= start);
+            if let Some(indexed_to) = indexed_to {
+                let indexed_end = end.min(indexed_to);
+                let mut history = self.tx.cursor_read::<tables::StoragesHistory>()?;
+                let mut entry = history.seek(StorageShardedKey::new(address, B256::ZERO, start))?;
+                while let Some((sharded_key, list)) = entry {
+                    if sharded_key.address != address {
+                        break
+                    }
+                    let storage_key = sharded_key.sharded_key.key;
+                    let highest_block_number = sharded_key.sharded_key.highest_block_number;
+                    if highest_block_number < start {
+                        // skip the shards of the slot below the start of the range
+                        entry =
+                            history.seek(StorageShardedKey::new(address, storage_key, start))?;
+                        continue
+                    }
+                    blocks.extend(
+                        list.iter()
+                            .skip_while(|block| *block < start)
+                            .take_while(|block| *block <= indexed_end),
+                    );
+                    entry = if highest_block_number < indexed_end {
+                        history.next()?
+                    } else {
+                        // skip the shards of the slot past the end of the range, the last shard
+                        // of a slot is keyed by `u64::MAX`
+                        history.seek(StorageShardedKey::last(address, storage_key))?;
+                        history.next()?
+                    };
+                }
+            }
+            unindexed = indexed_to.map_or(Some(start), |indexed_to| indexed_to.checked_add(1));
+        }
+        let unindexed =
+            unindexed.map(|unindexed_start| unindexed_start..=end).into_iter().flatten();
+
+        // All storage changes of an address in a block are the duplicates of a single key.
+        let mut changesets = self.tx.cursor_dup_read::<tables::StorageChangeSets>()?;
+        let mut blocks = Some(blocks.into_iter().chain(unindexed));
+        let mut entry = None;
+        Ok(std::iter::from_fn(move || loop {
+            match entry.take() {
+                Some(Ok(changeset)) => {
+                    entry = changesets.next_dup().transpose();
+                    return Some(Ok(changeset))
+                }
+                Some(Err(err)) => {
+                    blocks = None;
+                    return Some(Err(ProviderError::from(err)))
+                }
+                None => {
+                    let block_number = blocks.as_mut()?.next()?;
+                    entry = changesets
+                        .seek_exact(BlockNumberAddress((block_number, address)))
+                        .transpose();
+                }
+            }
+        }))
+    }
 }
 
 impl<TX: DbTx, N: NodeTypes> ChangeSetReader for DatabaseProvider<TX, N> {
@@ -941,6 +1103,31 @@ impl<TX: DbTx, N: NodeTypes> ChangeSetReader for DatabaseProvider<TX, N> {
             })
             .collect()
     }
+
+    fn account_changesets_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<impl Iterator<Item = ProviderResult<(BlockNumber, AccountBeforeTx)>> + '_>
+    {
+        let (start, end) = range.into_inner();
+        if start <= end {
+            self.ensure_changesets_available(PruneSegment::AccountHistory, start)?;
+        }
+
+        // Blocks without account changes have no entries, so walking the range only visits the
+        // blocks that changed accounts.
+        let mut cursor = self.tx.cursor_read::<tables::AccountChangeSets>()?;
+        let mut entry = cursor.seek(start).transpose();
+        Ok(std::iter::from_fn(move || {
+            let item = entry.take()?.map_err(ProviderError::from);
+            match &item {
+                Ok((block_number, _)) if *block_number > end => return None,
+                Ok(_) => entry = cursor.next().transpose(),
+                Err(_) => {}
+            }
+            Some(item)
+        }))
+    }
 }
 
 impl<TX: DbTx + 'static, N: NodeTypesForProvider> HeaderSyncGapProvider
@@ -1011,6 +1198,13 @@ impl<TX: DbTx + 'static, N: NodeTypesForProvider> HeaderProvider for DatabasePro
         )
     }
 
+    fn earliest_header_number(&self) -> ProviderResult<BlockNumber> {
+        Ok(self
+            .static_file_provider
+            .get_lowest_static_file_block(StaticFileSegment::Headers)
+            .unwrap_or_default())
+    }
+
     fn header_td(&self, block_hash: &BlockHash) -> ProviderResult<Option<U256>> {
         if let Some(num) = self.block_number(*block_hash)? {
             self.header_td_by_number(num)
@@ -1150,6 +1344,30 @@ impl<TX: DbTx + 'static, N: NodeTypes> BlockNumReader for DatabaseProvider<TX, N
             .unwrap_or_default())
     }
 
+    fn earliest_block_number(&self) -> ProviderResult<BlockNumber> {
+        // Receipts that were moved to static files are still available, so only the receipts that
+        // were pruned according to the configured prune modes are missing.
+        let mut earliest = 0;
+        if self.prune_modes.receipts.is_some() {
+            if let Some(checkpoint) = self.get_prune_checkpoint(PruneSegment::Receipts)? {
+                earliest = earliest.max(checkpoint.block_number.map_or(0, |block| block + 1));
+            }
+        }
+        if !self.prune_modes.receipts_log_filter.is_empty() {
+            if let Some(checkpoint) = self.get_prune_checkpoint(PruneSegment::ContractLogs)? {
+                earliest = earliest.max(checkpoint.block_number.map_or(0, |block| block + 1));
+            }
+        }
+        // Headers and bodies below the lowest static files expired, since they're written to
+        // static files before they're removed from the database.
+        for segment in [StaticFileSegment::Headers, StaticFileSegment::Transactions] {
+            if let Some(lowest) = self.static_file_provider.get_lowest_static_file_block(segment) {
+                earliest = earliest.max(lowest);
+            }
+        }
+        Ok(earliest)
+    }
+
     fn block_number(&self, hash: B256) -> ProviderResult<Option<BlockNumber>> {
         Ok(self.tx.get::<tables::HeaderNumbers>(hash)?)
     }
@@ -1326,19 +1544,6 @@ impl<TX: DbTx + 'static, N: NodeTypesForProvider> TransactionsProviderExt
                 let mut channels = Vec::with_capacity(chunk_size);
                 let mut transaction_count = 0;
 
-                #[inline]
-                fn calculate_hash<T>(
-                    entry: Result<(TxNumber, T), DatabaseError>,
-                    rlp_buf: &mut Vec<u8>,
-                ) -> Result<(B256, TxNumber), Box<ProviderError>>
-                where
-                    T: Encodable2718,
-                {
-                    let (tx_id, tx) = entry.map_err(|e| Box::new(e.into()))?;
-                    tx.encode_2718(rlp_buf);
-                    Ok((keccak256(rlp_buf), tx_id))
-                }
-
                 for chunk in &tx_walker.chunks(chunk_size) {
                     let (tx, rx) = mpsc::channel();
                     channels.push(rx);
@@ -1352,10 +1557,19 @@ impl<TX: DbTx + 'static, N: NodeTypesForProvider> TransactionsProviderExt
                     // This task will send the results through the channel after it has calculated
                     // the hash.
                     rayon::spawn(move || {
-                        let mut rlp_buf = Vec::with_capacity(128);
-                        for entry in chunk {
-                            rlp_buf.clear();
-                            let _ = tx.send(calculate_hash(entry, &mut rlp_buf));
+                        // hash in batches to bound the memory of the encoded transactions
+                        for batch in &chunk.into_iter().chunks(TRANSACTION_HASH_BATCH_SIZE) {
+                            match batch.collect::<Result<Vec<_>, _>>() {
+                                Ok(transactions) => {
+                                    for hash in calculate_hashes(transactions) {
+                                        let _ = tx.send(Ok(hash));
+                                    }
+                                }
+                                Err(err) => {
+                                    let _ = tx.send(Err(Box::new(err.into())));
+                                    return
+                                }
+                            }
                         }
                     });
                 }
@@ -1570,6 +1784,43 @@ impl<TX: DbTx + 'static, N: NodeTypesForProvider> ReceiptProvider for DatabasePr
             |_| true,
         )
     }
+
+    fn log_address_blocks(
+        &self,
+        addresses: &[Address],
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Option<IndexedLogBlocks>> {
+        let Some(checkpoint) = self.get_stage_checkpoint(StageId::IndexLogAddresses)? else {
+            return Ok(None)
+        };
+        let (start, end) = range.into_inner();
+        if start > checkpoint.block_number {
+            return Ok(None)
+        }
+        let indexed_to = end.min(checkpoint.block_number);
+
+        let mut blocks = BTreeSet::new();
+        let mut cursor = self.tx.cursor_read::<tables::LogAddressIndex>()?;
+        for &address in addresses {
+            // the first shard that can contain the start of the range
+            for entry in cursor.walk(Some(ShardedKey::new(address, start)))? {
+                let (sharded_key, list) = entry?;
+                if sharded_key.key != address {
+                    break
+                }
+                blocks.extend(
+                    list.iter()
+                        .skip_while(|block| *block < start)
+                        .take_while(|block| *block <= indexed_to),
+                );
+                if sharded_key.highest_block_number >= indexed_to {
+                    break
+                }
+            }
+        }
+
+        Ok(Some(IndexedLogBlocks { blocks: blocks.into_iter().collect(), indexed_to }))
+    }
 }
 
 impl<TX: DbTx + 'static, N: NodeTypes<ChainSpec: EthereumHardforks>> WithdrawalsProvider
@@ -1609,9 +1860,8 @@ impl<TX: DbTx + 'static, N: NodeTypesForProvider> OmmersProvider for DatabasePro
     /// If the block exists, but doesn't contain ommers, this returns `None`.
     fn ommers(&self, id: BlockHashOrNumber) -> ProviderResult<Option<Vec<Self::Header>>> {
         if let Some(number) = self.convert_hash_or_number(id)? {
-            // If the Paris (Merge) hardfork block is known and block is after it, return empty
-            // ommers.
-            if self.chain_spec.final_paris_total_difficulty(number).is_some() {
+            // Blocks after the Paris (Merge) hardfork block don't have ommers.
+            if !self.chain_spec.has_ommers(number) {
                 return Ok(Some(Vec::new()))
             }
 
@@ -1858,11 +2108,9 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider> StateWriter
                 let mut wiped_storage = Vec::new();
                 if wiped {
                     tracing::trace!(?address, "Wiping storage");
-                    if let Some((_, entry)) = storages_cursor.seek_exact(address)? {
+                    for entry in storages_cursor.walk_dup_prefix(address)? {
+                        let (_, entry) = entry?;
                         wiped_storage.push((entry.key, entry.value));
-                        while let Some(entry) = storages_cursor.next_dup_val()? {
-                            wiped_storage.push((entry.key, entry.value))
-                        }
                     }
                 }
 
@@ -2026,6 +2274,8 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider> StateWriter
             return Ok(());
         }
 
+        self.unwind_log_address_index_above(block)?;
+
         // We are not removing block meta as it is used to get block changesets.
         let block_bodies = self.get::<tables::BlockBodyIndices>(range.clone())?;
 
@@ -2122,6 +2372,8 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider> StateWriter
         }
         let start_block_number = *range.start();
 
+        self.unwind_log_address_index_above(block)?;
+
         // We are not removing block meta as it is used to get block changesets.
         let block_bodies = self.get::<tables::BlockBodyIndices>(range.clone())?;
 
@@ -2546,7 +2798,9 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypes> HashingWriter for DatabaseProvi
     }
 }
 
-impl<TX: DbTxMut + DbTx + 'static, N: NodeTypes> HistoryWriter for DatabaseProvider<TX, N> {
+impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider> HistoryWriter
+    for DatabaseProvider<TX, N>
+{
     fn unwind_account_history_indices<'a>(
         &self,
         changesets: impl Iterator<Item = &'a (BlockNumber, AccountBeforeTx)>,
@@ -2672,11 +2926,49 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypes> HistoryWriter for DatabaseProvi
 
         // storage history stage
         {
-            let indices = self.changed_storages_and_blocks_with_range(range)?;
+            let indices = self.changed_storages_and_blocks_with_range(range.clone())?;
             self.insert_storage_history_index(indices)?;
         }
 
-        Ok(())
+        // log address index, if it's maintained
+        self.update_log_address_index(range)
+    }
+
+    fn insert_log_address_index(
+        &self,
+        index_updates: impl IntoIterator<Item = (Address, impl IntoIterator<Item = u64>)>,
+    ) -> ProviderResult<()> {
+        self.append_history_index::<_, tables::LogAddressIndex>(index_updates, ShardedKey::new)
+    }
+
+    fn unwind_log_address_index_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<usize> {
+        let addresses = self.log_addresses_and_blocks_with_range(range)?;
+
+        let mut cursor = self.tx.cursor_write::<tables::LogAddressIndex>()?;
+        for (&address, blocks) in &addresses {
+            // the address didn't emit logs in the range before its first block
+            let rem_index = blocks[0];
+            let partial_shard = unwind_history_shards::<_, tables::LogAddressIndex, _>(
+                &mut cursor,
+                ShardedKey::last(address),
+                rem_index,
+                |sharded_key| sharded_key.key == address,
+            )?;
+
+            // Check the last returned partial shard.
+            // If it's not empty, the shard needs to be reinserted.
+            if !partial_shard.is_empty() {
+                cursor.insert(
+                    ShardedKey::last(address),
+                    BlockNumberList::new_pre_sorted(partial_shard),
+                )?;
+            }
+        }
+
+        Ok(addresses.len())
     }
 }
 
@@ -2747,7 +3039,8 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider + 'static> BlockWrite
     /// * [`Transactions`](tables::Transactions)
     /// * [`TransactionBlocks`](tables::TransactionBlocks)
     ///
-    /// If ommers are not empty, this will modify [`BlockOmmers`](tables::BlockOmmers).
+    /// If ommers are not empty and the block is before the merge, this will modify
+    /// [`BlockOmmers`](tables::BlockOmmers).
     /// If withdrawals are not empty, this will modify
     /// [`BlockWithdrawals`](tables::BlockWithdrawals).
     ///
@@ -3061,6 +3354,18 @@ impl<TX: DbTx + 'static, N: NodeTypes> PruneCheckpointReader for DatabaseProvide
             .walk(None)?
             .collect::<Result<_, _>>()?)
     }
+
+    fn get_custom_prune_checkpoint(&self, name: &str) -> ProviderResult<Option<PruneCheckpoint>> {
+        Ok(self.tx.get::<tables::CustomPruneCheckpoints>(name.to_string())?)
+    }
+
+    fn get_custom_prune_checkpoints(&self) -> ProviderResult<Vec<(String, PruneCheckpoint)>> {
+        Ok(self
+            .tx
+            .cursor_read::<tables::CustomPruneCheckpoints>()?
+            .walk(None)?
+            .collect::<Result<_, _>>()?)
+    }
 }
 
 impl<TX: DbTxMut, N: NodeTypes> PruneCheckpointWriter for DatabaseProvider<TX, N> {
@@ -3071,6 +3376,14 @@ impl<TX: DbTxMut, N: NodeTypes> PruneCheckpointWriter for DatabaseProvider<TX, N
     ) -> ProviderResult<()> {
         Ok(self.tx.put::<tables::PruneCheckpoints>(segment, checkpoint)?)
     }
+
+    fn save_custom_prune_checkpoint(
+        &self,
+        name: &str,
+        checkpoint: PruneCheckpoint,
+    ) -> ProviderResult<()> {
+        Ok(self.tx.put::<tables::CustomPruneCheckpoints>(name.to_string(), checkpoint)?)
+    }
 }
 
 impl<TX: DbTx + 'static, N: NodeTypesForProvider> StatsReader for DatabaseProvider<TX, N> {
@@ -3110,6 +3423,10 @@ impl<TX: DbTx + 'static, N: NodeTypes> ChainStateBlockReader for DatabaseProvide
         let last_finalized_block_number = finalized_blocks.pop_first().map(|pair| pair.1);
         Ok(last_finalized_block_number)
     }
+
+    fn last_forkchoice_state(&self) -> ProviderResult<Option<StoredForkchoiceState>> {
+        Ok(self.tx.get::<tables::ForkchoiceStates>(tables::ChainStateKey::LastForkchoiceState)?)
+    }
 }
 
 impl<TX: DbTxMut, N: NodeTypes> ChainStateBlockWriter for DatabaseProvider<TX, N> {
@@ -3124,6 +3441,12 @@ impl<TX: DbTxMut, N: NodeTypes> ChainStateBlockWriter for DatabaseProvider<TX, N
             .tx
             .put::<tables::ChainState>(tables::ChainStateKey::LastSafeBlockBlock, block_number)?)
     }
+
+    fn save_forkchoice_state(&self, state: StoredForkchoiceState) -> ProviderResult<()> {
+        Ok(self
+            .tx
+            .put::<tables::ForkchoiceStates>(tables::ChainStateKey::LastForkchoiceState, state)?)
+    }
 }
 
 impl<TX: DbTx + 'static, N: NodeTypes + 'static> DBProvider for DatabaseProvider<TX, N> {
@@ -21,7 +21,9 @@ use reth_blockchain_tree_api::{
     BlockValidationKind, BlockchainTreeEngine, BlockchainTreeViewer, CanonicalOutcome,
     InsertPayloadOk,
 };
-use reth_chain_state::{ChainInfoTracker, ForkChoiceNotifications, ForkChoiceSubscriptions};
+use reth_chain_state::{
+    ChainInfoTracker, ChainStatus, ForkChoiceNotifications, ForkChoiceSubscriptions,
+};
 use reth_chainspec::{ChainInfo, EthereumHardforks};
 use reth_db::table::Value;
 use reth_db_api::models::{AccountBeforeTx, StoredBlockBodyIndices};
@@ -35,7 +37,9 @@ use reth_primitives::{
 };
 use reth_prune_types::{PruneCheckpoint, PruneSegment};
 use reth_stages_types::{StageCheckpoint, StageId};
-use reth_storage_api::{BlockBodyIndicesProvider, CanonChainTracker, OmmersProvider};
+use reth_storage_api::{
+    BlockBodyIndicesProvider, CanonChainTracker, IndexedLogBlocks, OmmersProvider,
+};
 use reth_storage_errors::provider::ProviderResult;
 use std::{
     collections::BTreeMap,
@@ -43,6 +47,7 @@ use std::{
     sync::Arc,
     time::Instant,
 };
+use tokio::sync::watch;
 
 use tracing::trace;
 
@@ -73,6 +78,12 @@ pub use blockchain_provider::BlockchainProvider2;
 mod consistent;
 pub use consistent::ConsistentProvider;
 
+mod consistency;
+pub use consistency::{
+    ConsistencyCheck, ConsistencyReport, Inconsistency, PruneCheckpointView, RepairAction,
+    SegmentView, StorageView,
+};
+
 /// Helper trait to bound [`NodeTypes`] so that combined with database they satisfy
 /// [`ProviderNodeTypes`].
 pub trait NodeTypesForProvider
@@ -272,6 +283,10 @@ impl<N: TreeNodeTypes> HeaderProvider for BlockchainProvider<N> {
         self.database.header_by_number(num)
     }
 
+    fn earliest_header_number(&self) -> ProviderResult<BlockNumber> {
+        self.database.earliest_header_number()
+    }
+
     fn header_td(&self, hash: &BlockHash) -> ProviderResult<Option<U256>> {
         self.database.header_td(hash)
     }
@@ -331,6 +346,10 @@ impl<N: ProviderNodeTypes> BlockNumReader for BlockchainProvider<N> {
         self.database.last_block_number()
     }
 
+    fn earliest_block_number(&self) -> ProviderResult<BlockNumber> {
+        self.database.earliest_block_number()
+    }
+
     fn block_number(&self, hash: B256) -> ProviderResult<Option<BlockNumber>> {
         self.database.block_number(hash)
     }
@@ -530,6 +549,14 @@ impl<N: ProviderNodeTypes> ReceiptProvider for BlockchainProvider<N> {
     ) -> ProviderResult<Vec<Self::Receipt>> {
         self.database.receipts_by_tx_range(range)
     }
+
+    fn log_address_blocks(
+        &self,
+        addresses: &[Address],
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Option<IndexedLogBlocks>> {
+        self.database.log_address_blocks(addresses, range)
+    }
 }
 
 impl<N: TreeNodeTypes> ReceiptProviderIdExt for BlockchainProvider<N> {
@@ -610,6 +637,14 @@ impl<N: ProviderNodeTypes> PruneCheckpointReader for BlockchainProvider<N> {
     fn get_prune_checkpoints(&self) -> ProviderResult<Vec<(PruneSegment, PruneCheckpoint)>> {
         self.database.provider()?.get_prune_checkpoints()
     }
+
+    fn get_custom_prune_checkpoint(&self, name: &str) -> ProviderResult<Option<PruneCheckpoint>> {
+        self.database.provider()?.get_custom_prune_checkpoint(name)
+    }
+
+    fn get_custom_prune_checkpoints(&self) -> ProviderResult<Vec<(String, PruneCheckpoint)>> {
+        self.database.provider()?.get_custom_prune_checkpoints()
+    }
 }
 
 impl<N: ProviderNodeTypes> ChainSpecProvider for BlockchainProvider<N> {
@@ -922,6 +957,10 @@ impl<N: ProviderNodeTypes> CanonStateSubscriptions for BlockchainProvider<N> {
     fn subscribe_to_canonical_state(&self) -> CanonStateNotifications<Self::Primitives> {
         self.tree.subscribe_to_canonical_state()
     }
+
+    fn subscribe_chain_status(&self) -> Option<watch::Receiver<ChainStatus>> {
+        Some(self.chain_info.subscribe_chain_status())
+    }
 }
 
 impl<N: TreeNodeTypes> ForkChoiceSubscriptions for BlockchainProvider<N> {
@@ -943,7 +982,33 @@ impl<N: ProviderNodeTypes> ChangeSetReader for BlockchainProvider<N> {
         &self,
         block_number: BlockNumber,
     ) -> ProviderResult<Vec<AccountBeforeTx>> {
-        self.database.provider()?.account_block_changeset(block_number)
+        let provider = self.database.provider()?;
+
+        // The checkpoint stores the highest pruned block number, so history exists for all blocks
+        // strictly above it.
+        let account_history_pruned = provider
+            .get_prune_checkpoint(PruneSegment::AccountHistory)?
+            .and_then(|checkpoint| checkpoint.block_number)
+            .is_some_and(|checkpoint| block_number <= checkpoint);
+        if account_history_pruned {
+            return Err(ProviderError::StateAtBlockPruned(block_number))
+        }
+
+        provider.account_block_changeset(block_number)
+    }
+
+    fn account_changesets_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<impl Iterator<Item = ProviderResult<(BlockNumber, AccountBeforeTx)>> + '_>
+    {
+        // the changesets are read up front, because the iterator borrows the database provider
+        let changesets = self
+            .database
+            .provider()?
+            .account_changesets_range(range)?
+            .collect::<ProviderResult<Vec<_>>>()?;
+        Ok(changesets.into_iter().map(Ok))
     }
 }
 
@@ -4,7 +4,7 @@ use crate::{
 };
 use alloy_eips::merge::EPOCH_SLOTS;
 use alloy_primitives::{
-    map::B256HashMap, Address, BlockNumber, Bytes, StorageKey, StorageValue, B256,
+    keccak256, map::B256HashMap, Address, BlockNumber, Bytes, StorageKey, StorageValue, B256,
 };
 use reth_db::{tables, BlockNumberList};
 use reth_db_api::{
@@ -13,7 +13,7 @@ use reth_db_api::{
     table::Table,
     transaction::DbTx,
 };
-use reth_primitives::{Account, Bytecode};
+use reth_primitives::{Account, Bytecode, StorageEntry};
 use reth_storage_api::{
     BlockNumReader, DBProvider, StateCommitmentProvider, StateProofProvider, StorageRootProvider,
 };
@@ -29,7 +29,7 @@ use reth_trie_db::{
     DatabaseHashedPostState, DatabaseHashedStorage, DatabaseProof, DatabaseStateRoot,
     DatabaseStorageProof, DatabaseStorageRoot, DatabaseTrieWitness, StateCommitment,
 };
-use std::fmt::Debug;
+use std::{fmt::Debug, ops::RangeInclusive};
 
 /// State provider for a given block number which takes a tx reference.
 ///
@@ -359,6 +359,18 @@ impl<Provider: DBProvider + BlockNumReader + StateCommitmentProvider> StorageRoo
         StorageProof::overlay_storage_multiproof(self.tx(), address, slots, revert_storage)
             .map_err(ProviderError::from)
     }
+
+    fn storage_range(
+        &self,
+        address: Address,
+        range: RangeInclusive<B256>,
+        limit: usize,
+        hashed_storage: HashedStorage,
+    ) -> ProviderResult<Vec<StorageEntry>> {
+        let mut revert_storage = self.revert_storage(address)?;
+        revert_storage.extend(&hashed_storage);
+        Ok(revert_storage.storage_range(self.tx(), keccak256(address), range, limit)?)
+    }
 }
 
 impl<Provider: DBProvider + BlockNumReader + StateCommitmentProvider> StateProofProvider
@@ -3,11 +3,11 @@ use crate::{
     HashedPostStateProvider, StateProvider, StateRootProvider,
 };
 use alloy_primitives::{
-    map::B256HashMap, Address, BlockNumber, Bytes, StorageKey, StorageValue, B256,
+    keccak256, map::B256HashMap, Address, BlockNumber, Bytes, StorageKey, StorageValue, B256,
 };
 use reth_db::tables;
 use reth_db_api::{cursor::DbDupCursorRO, transaction::DbTx};
-use reth_primitives::{Account, Bytecode};
+use reth_primitives::{Account, Bytecode, StorageEntry};
 use reth_storage_api::{
     DBProvider, StateCommitmentProvider, StateProofProvider, StorageRootProvider,
 };
@@ -20,9 +20,10 @@ use reth_trie::{
     StorageMultiProof, StorageRoot, TrieInput,
 };
 use reth_trie_db::{
-    DatabaseProof, DatabaseStateRoot, DatabaseStorageProof, DatabaseStorageRoot,
-    DatabaseTrieWitness, StateCommitment,
+    DatabaseHashedStorage, DatabaseProof, DatabaseStateRoot, DatabaseStorageProof,
+    DatabaseStorageRoot, DatabaseTrieWitness, StateCommitment,
 };
+use std::ops::RangeInclusive;
 
 /// State provider over latest state that takes tx reference.
 ///
@@ -124,6 +125,16 @@ impl<Provider: DBProvider + StateCommitmentProvider> StorageRootProvider
         StorageProof::overlay_storage_multiproof(self.tx(), address, slots, hashed_storage)
             .map_err(ProviderError::from)
     }
+
+    fn storage_range(
+        &self,
+        address: Address,
+        range: RangeInclusive<B256>,
+        limit: usize,
+        hashed_storage: HashedStorage,
+    ) -> ProviderResult<Vec<StorageEntry>> {
+        Ok(hashed_storage.storage_range(self.tx(), keccak256(address), range, limit)?)
+    }
 }
 
 impl<Provider: DBProvider + StateCommitmentProvider> StateProofProvider
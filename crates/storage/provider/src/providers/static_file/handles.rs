@@ -0,0 +1,92 @@
+use super::{metrics::StaticFileHandleMetrics, LoadedJar};
+use alloy_primitives::BlockNumber;
+use dashmap::{mapref::entry::Entry, DashMap};
+use parking_lot::Mutex;
+use reth_primitives::StaticFileSegment;
+use std::collections::{HashSet, VecDeque};
+
+/// Key of a [`LoadedJar`]: the end of its fixed block range and its segment.
+pub(crate) type JarKey = (BlockNumber, StaticFileSegment);
+
+/// Keeps the number of open [`LoadedJar`]s of a static file provider within a budget.
+///
+/// Eviction follows the CLOCK algorithm: every access marks the jar as recently used, which is a
+/// single atomic store and doesn't take a lock on the read path. Once the budget is exceeded, jars
+/// are visited in the order they were opened and recently used jars get a second chance.
+///
+/// Evicted jars are transparently reopened by the provider on their next access. Cursors that
+/// were created from an evicted jar keep their own handle to its data until they are dropped.
+#[derive(Debug, Default)]
+pub(crate) struct OpenJars {
+    /// Maximum number of open jars, unlimited if `None`.
+    max_open: Option<usize>,
+    /// Keys of the open jars, in the order they were opened.
+    queue: Mutex<VecDeque<JarKey>>,
+    /// Keys of the jars that were evicted and not reopened yet.
+    evicted: Mutex<HashSet<JarKey>>,
+    metrics: StaticFileHandleMetrics,
+}
+
+impl OpenJars {
+    /// Creates a new tracker that keeps at most `max_open` jars open.
+    pub(crate) fn new(max_open: Option<usize>) -> Self {
+        Self { max_open: max_open.map(|max| max.max(1)), ..Default::default() }
+    }
+
+    /// Records that the jar with the given key was opened.
+    pub(crate) fn on_open(&self, key: JarKey) {
+        let mut queue = self.queue.lock();
+        if !queue.contains(&key) {
+            queue.push_back(key);
+        }
+        self.metrics.open.set(queue.len() as f64);
+        drop(queue);
+
+        if self.evicted.lock().remove(&key) {
+            self.metrics.reopens_total.increment(1);
+        }
+    }
+
+    /// Records that the jars whose keys don't match the predicate were removed from the map.
+    pub(crate) fn retain(&self, mut f: impl FnMut(&JarKey) -> bool) {
+        let mut queue = self.queue.lock();
+        queue.retain(|key| f(key));
+        self.metrics.open.set(queue.len() as f64);
+        drop(queue);
+
+        self.evicted.lock().retain(|key| f(key));
+    }
+
+    /// Evicts jars from the map until another jar can be opened within the budget.
+    ///
+    /// Jars whose shard of the map is locked, for example because the calling thread holds a
+    /// provider of a jar in the same shard, are skipped, so this never blocks on the map.
+    pub(crate) fn make_room(&self, map: &DashMap<JarKey, LoadedJar>) {
+        let Some(max_open) = self.max_open else { return };
+
+        let mut queue = self.queue.lock();
+        // every jar is visited at most twice: once to take its second chance and once to evict it
+        let mut visits = queue.len() * 2;
+        while queue.len() >= max_open && visits > 0 {
+            visits -= 1;
+            let Some(key) = queue.pop_front() else { break };
+
+            match map.try_entry(key) {
+                // the jar is in use or being modified
+                None => queue.push_back(key),
+                // the jar was removed from the map already
+                Some(Entry::Vacant(_)) => {}
+                Some(Entry::Occupied(entry)) => {
+                    if entry.get().take_accessed() {
+                        queue.push_back(key);
+                    } else {
+                        entry.remove();
+                        self.evicted.lock().insert(key);
+                        self.metrics.evictions_total.increment(1);
+                    }
+                }
+            }
+        }
+        self.metrics.open.set(queue.len() as f64);
+    }
+}
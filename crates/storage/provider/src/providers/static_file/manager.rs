@@ -1,10 +1,12 @@
 use super::{
-    metrics::StaticFileProviderMetrics, writer::StaticFileWriters, LoadedJar,
-    StaticFileJarProvider, StaticFileProviderRW, StaticFileProviderRWRefMut,
+    handles::{JarKey, OpenJars},
+    metrics::StaticFileProviderMetrics,
+    writer::StaticFileWriters,
+    LoadedJar, StaticFileJarProvider, StaticFileProviderRW, StaticFileProviderRWRefMut,
 };
 use crate::{
     to_range, BlockHashReader, BlockNumReader, BlockReader, BlockSource, HeaderProvider,
-    ReceiptProvider, StageCheckpointReader, StatsReader, TransactionVariant, TransactionsProvider,
+    ReceiptProvider, StatsReader, TransactionVariant, TransactionsProvider,
     TransactionsProviderExt, WithdrawalsProvider,
 };
 use alloy_consensus::Header;
@@ -13,11 +15,11 @@ use alloy_eips::{
     eip4895::{Withdrawal, Withdrawals},
     BlockHashOrNumber,
 };
-use alloy_primitives::{keccak256, Address, BlockHash, BlockNumber, TxHash, TxNumber, B256, U256};
+use alloy_primitives::{Address, BlockHash, BlockNumber, TxHash, TxNumber, B256, U256};
 use dashmap::DashMap;
 use notify::{RecommendedWatcher, RecursiveMode, Watcher};
 use parking_lot::RwLock;
-use reth_chainspec::{ChainInfo, ChainSpecProvider};
+use reth_chainspec::ChainInfo;
 use reth_db::{
     lockfile::StorageLock,
     static_file::{
@@ -27,12 +29,12 @@ use reth_db::{
     table::{Decompress, Value},
     tables,
 };
-use reth_db_api::{
-    cursor::DbCursorRO, models::StoredBlockBodyIndices, table::Table, transaction::DbTx,
-};
-use reth_nippy_jar::{NippyJar, NippyJarChecker, CONFIG_FILE_EXTENSION};
+use reth_db_api::{models::StoredBlockBodyIndices, table::Table};
+use reth_fs_util::FsyncPolicy;
+use reth_nippy_jar::{NippyJar, NippyJarChecker, OffsetsEncoding, CONFIG_FILE_EXTENSION};
 use reth_node_types::{FullNodePrimitives, NodePrimitives};
 use reth_primitives::{
+    keccak256_batch,
     static_file::{
         find_fixed_range, HighestStaticFiles, SegmentHeader, SegmentRangeInclusive,
         DEFAULT_BLOCKS_PER_STATIC_FILE,
@@ -42,8 +44,7 @@ use reth_primitives::{
     StaticFileSegment, TransactionMeta, TransactionSigned,
 };
 use reth_primitives_traits::SignedTransaction;
-use reth_stages_types::{PipelineTarget, StageId};
-use reth_storage_api::{BlockBodyIndicesProvider, DBProvider, OmmersProvider};
+use reth_storage_api::{BlockBodyIndicesProvider, OmmersProvider};
 use reth_storage_errors::provider::{ProviderError, ProviderResult};
 use std::{
     collections::{hash_map::Entry, BTreeMap, HashMap},
@@ -51,10 +52,12 @@ use std::{
     marker::PhantomData,
     ops::{Deref, Range, RangeBounds, RangeInclusive},
     path::{Path, PathBuf},
-    sync::{mpsc, Arc},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc, Arc,
+    },
 };
-use strum::IntoEnumIterator;
-use tracing::{info, trace, warn};
+use tracing::{debug, info, trace, warn};
 
 /// Alias type for a map that can be queried for block ranges from a transaction
 /// segment respectively. It uses `TxNumber` to represent the transaction end of a static file
@@ -105,6 +108,10 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
     ///
     /// Set `watch_directory` to `true` to track the most recent changes in static files. Otherwise,
     /// new data won't be detected or queryable.
+    ///
+    /// The watcher keeps a clone of the provider, so a provider that's configured with the `with_*`
+    /// methods should be created without it, and watch the directory with
+    /// [`Self::watch_directory`] once it's configured.
     pub fn read_only(path: impl AsRef<Path>, watch_directory: bool) -> ProviderResult<Self> {
         let provider = Self::new(path, StaticFileAccess::RO)?;
 
@@ -217,7 +224,11 @@ impl<N: NodePrimitives> Deref for StaticFileProvider<N> {
 pub struct StaticFileProviderInner<N> {
     /// Maintains a map which allows for concurrent access to different `NippyJars`, over different
     /// segments and ranges.
-    map: DashMap<(BlockNumber, StaticFileSegment), LoadedJar>,
+    map: DashMap<JarKey, LoadedJar>,
+    /// Keeps the number of jars in `map` within the open files budget.
+    open_jars: OpenJars,
+    /// Min static file block for each segment
+    static_files_min_block: RwLock<HashMap<StaticFileSegment, u64>>,
     /// Max static file block for each segment
     static_files_max_block: RwLock<HashMap<StaticFileSegment, u64>>,
     /// Available static file block ranges on disk indexed by max transactions.
@@ -232,6 +243,15 @@ pub struct StaticFileProviderInner<N> {
     access: StaticFileAccess,
     /// Number of blocks per file.
     blocks_per_file: u64,
+    /// Number of bytes of disk space reserved at a time ahead of the appends to a static file.
+    preallocation_chunk_size: u64,
+    /// When data appended to a static file is synced to disk before it's committed.
+    fsync_policy: FsyncPolicy,
+    /// Whether static files are verified against their configuration when they're opened.
+    ///
+    /// Atomic so that it can be enabled on a provider that is already shared, eg. with the
+    /// directory watcher.
+    verify_on_open: AtomicBool,
     /// Write lock for when access is [`StaticFileAccess::RW`].
     _lock_file: Option<StorageLock>,
     /// Node primitives
@@ -249,13 +269,18 @@ impl<N: NodePrimitives> StaticFileProviderInner<N> {
 
         let provider = Self {
             map: Default::default(),
+            open_jars: Default::default(),
             writers: Default::default(),
+            static_files_min_block: Default::default(),
             static_files_max_block: Default::default(),
             static_files_tx_index: Default::default(),
             path: path.as_ref().to_path_buf(),
             metrics: None,
             access,
             blocks_per_file: DEFAULT_BLOCKS_PER_STATIC_FILE,
+            preallocation_chunk_size: 0,
+            fsync_policy: FsyncPolicy::default(),
+            verify_on_open: AtomicBool::new(false),
             _lock_file,
             _pd: Default::default(),
         };
@@ -272,6 +297,17 @@ impl<N: NodePrimitives> StaticFileProviderInner<N> {
     pub const fn find_fixed_range(&self, block: BlockNumber) -> SegmentRangeInclusive {
         find_fixed_range(block, self.blocks_per_file)
     }
+
+    /// Returns the number of bytes of disk space reserved at a time ahead of the appends to a
+    /// static file.
+    pub const fn preallocation_chunk_size(&self) -> u64 {
+        self.preallocation_chunk_size
+    }
+
+    /// Returns when data appended to a static file is synced to disk before it's committed.
+    pub const fn fsync_policy(&self) -> FsyncPolicy {
+        self.fsync_policy
+    }
 }
 
 impl<N: NodePrimitives> StaticFileProvider<N> {
@@ -284,6 +320,46 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
         Self(Arc::new(provider))
     }
 
+    /// Limits the number of static files that are kept open at the same time.
+    ///
+    /// Once the limit is reached, the least recently used static files are closed, and reopened on
+    /// their next access.
+    pub fn with_max_open_files(self, max_open_files: usize) -> Self {
+        let mut provider =
+            Arc::try_unwrap(self.0).expect("should be called when initializing only");
+        provider.open_jars = OpenJars::new(Some(max_open_files));
+        Self(Arc::new(provider))
+    }
+
+    /// Reserves disk space of the static files that are written to `chunk_size` bytes at a time
+    /// ahead of the appends. Disabled if `0`, which is the default.
+    ///
+    /// See [`reth_fs_util::preallocate`] for the supported platforms and filesystems.
+    pub fn with_preallocation(self, chunk_size: u64) -> Self {
+        let mut provider =
+            Arc::try_unwrap(self.0).expect("should be called when initializing only");
+        provider.preallocation_chunk_size = chunk_size;
+        Self(Arc::new(provider))
+    }
+
+    /// Sets when data appended to a static file is synced to disk before it's committed.
+    /// Defaults to [`FsyncPolicy::Commit`].
+    pub fn with_fsync_policy(self, fsync_policy: FsyncPolicy) -> Self {
+        let mut provider =
+            Arc::try_unwrap(self.0).expect("should be called when initializing only");
+        provider.fsync_policy = fsync_policy;
+        Self(Arc::new(provider))
+    }
+
+    /// Verifies every static file with [`NippyJar::verify`] when it's opened, so that a damaged
+    /// file fails to open instead of returning wrong data.
+    ///
+    /// Static files that are already open aren't verified.
+    pub fn with_verify_on_open(self) -> Self {
+        self.verify_on_open.store(true, Ordering::Relaxed);
+        self
+    }
+
     /// Enables metrics on the [`StaticFileProvider`].
     pub fn with_metrics(self) -> Self {
         let mut provider =
@@ -409,7 +485,9 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
         segment: StaticFileSegment,
         fixed_block_range_end: BlockNumber,
     ) {
-        self.map.remove(&(fixed_block_range_end, segment));
+        let key = (fixed_block_range_end, segment);
+        self.map.remove(&key);
+        self.open_jars.retain(|open| *open != key);
     }
 
     /// Given a segment and block, it deletes the jar and all files from the respective block range.
@@ -419,6 +497,7 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
         let fixed_block_range = self.find_fixed_range(block);
         let key = (fixed_block_range.end(), segment);
         let jar = if let Some((_, jar)) = self.map.remove(&key) {
+            self.open_jars.retain(|open| *open != key);
             jar.jar
         } else {
             NippyJar::<SegmentHeader>::load(&self.path.join(segment.filename(&fixed_block_range)))
@@ -436,9 +515,62 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
         Ok(())
     }
 
+    /// Returns the finished static files that aren't sealed, see [`OffsetsEncoding::Delta`].
+    ///
+    /// The latest static file of every segment is never returned, since it's the one the writer
+    /// appends to.
+    pub fn unsealed_static_files(
+        &self,
+    ) -> ProviderResult<Vec<(StaticFileSegment, SegmentRangeInclusive)>> {
+        let static_files =
+            iter_static_files(&self.path).map_err(|e| ProviderError::NippyJar(e.to_string()))?;
+        let mut unsealed = Vec::new();
+        for (segment, ranges) in static_files {
+            let Some((_, finished)) = ranges.split_last() else { continue };
+            for (block_range, _) in finished {
+                let fixed_block_range = self.find_fixed_range(block_range.start());
+                let jar = NippyJar::<SegmentHeader>::load(
+                    &self.path.join(segment.filename(&fixed_block_range)),
+                )
+                .map_err(|e| ProviderError::NippyJar(e.to_string()))?;
+                if !jar.has_delta_offsets() {
+                    unsealed.push((segment, fixed_block_range));
+                }
+            }
+        }
+
+        Ok(unsealed)
+    }
+
+    /// Seals a finished static file, see [`OffsetsEncoding::Delta`].
+    ///
+    /// Sealing a static file that's sealed already rewrites it as it is.
+    pub fn seal_static_file(
+        &self,
+        segment: StaticFileSegment,
+        fixed_block_range: SegmentRangeInclusive,
+    ) -> ProviderResult<()> {
+        if self.access.is_read_only() {
+            return Err(ProviderError::ReadOnlyStaticFileAccess)
+        }
+
+        debug!(target: "provider::static_file", ?segment, ?fixed_block_range, "Sealing static file");
+        let mut jar =
+            NippyJar::<SegmentHeader>::load(&self.path.join(segment.filename(&fixed_block_range)))
+                .map_err(|e| ProviderError::NippyJar(e.to_string()))?;
+        self.remove_cached_provider(segment, fixed_block_range.end());
+        jar.rewrite_offsets(OffsetsEncoding::delta())
+            .map_err(|e| ProviderError::NippyJar(e.to_string()))?;
+        self.remove_cached_provider(segment, fixed_block_range.end());
+
+        Ok(())
+    }
+
     /// Given a segment and block range it returns a cached
-    /// [`StaticFileJarProvider`]. TODO(joshie): we should check the size and pop N if there's too
-    /// many.
+    /// [`StaticFileJarProvider`].
+    ///
+    /// If the jar isn't open, it's opened and other jars are closed if necessary to stay within the
+    /// open files budget, see [`StaticFileProvider::with_max_open_files`].
     fn get_or_create_jar_provider(
         &self,
         segment: StaticFileSegment,
@@ -450,12 +582,22 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
         trace!(target: "provider::static_file", ?segment, ?fixed_block_range, "Getting provider");
         let mut provider: StaticFileJarProvider<'_, N> = if let Some(jar) = self.map.get(&key) {
             trace!(target: "provider::static_file", ?segment, ?fixed_block_range, "Jar found in cache");
+            jar.mark_accessed();
             jar.into()
         } else {
             trace!(target: "provider::static_file", ?segment, ?fixed_block_range, "Creating jar from scratch");
             let path = self.path.join(segment.filename(fixed_block_range));
             let jar = NippyJar::load(&path).map_err(|e| ProviderError::NippyJar(e.to_string()))?;
-            self.map.entry(key).insert(LoadedJar::new(jar)?).downgrade().into()
+            if self.verify_on_open.load(Ordering::Relaxed) {
+                jar.verify().map_err(|e| {
+                    ProviderError::NippyJar(format!("{}: {e}", jar.data_path().display()))
+                })?;
+            }
+            let jar = LoadedJar::new(jar)?;
+            self.open_jars.make_room(&self.map);
+            let jar = self.map.entry(key).insert(jar).downgrade();
+            self.open_jars.on_open(key);
+            jar.into()
         };
 
         if let Some(metrics) = &self.metrics {
@@ -516,6 +658,7 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
         segment: StaticFileSegment,
         segment_max_block: Option<BlockNumber>,
     ) -> ProviderResult<()> {
+        let mut min_block = self.static_files_min_block.write();
         let mut max_block = self.static_files_max_block.write();
         let mut tx_index = self.static_files_tx_index.write();
 
@@ -524,6 +667,9 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
                 // Update the max block for the segment
                 max_block.insert(segment, segment_max_block);
                 let fixed_range = self.find_fixed_range(segment_max_block);
+                // The static files of a segment are only ever removed from the top, so the min
+                // block only changes if this is the first one
+                min_block.entry(segment).or_insert_with(|| fixed_range.start());
 
                 let jar = NippyJar::<SegmentHeader>::load(
                     &self.path.join(segment.filename(&fixed_range)),
@@ -571,14 +717,22 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
                 }
 
                 // Update the cached provider.
-                self.map.insert((fixed_range.end(), segment), LoadedJar::new(jar)?);
+                let key = (fixed_range.end(), segment);
+                let jar = LoadedJar::new(jar)?;
+                self.open_jars.make_room(&self.map);
+                self.map.insert(key, jar);
+                self.open_jars.on_open(key);
 
                 // Delete any cached provider that no longer has an associated jar.
-                self.map.retain(|(end, seg), _| !(*seg == segment && *end > fixed_range.end()));
+                let is_current =
+                    |(end, seg): &JarKey| !(*seg == segment && *end > fixed_range.end());
+                self.map.retain(|key, _| is_current(key));
+                self.open_jars.retain(is_current);
             }
             None => {
                 tx_index.remove(&segment);
                 max_block.remove(&segment);
+                min_block.remove(&segment);
             }
         };
 
@@ -587,15 +741,22 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
 
     /// Initializes the inner transaction and block index
     pub fn initialize_index(&self) -> ProviderResult<()> {
+        let mut min_block = self.static_files_min_block.write();
         let mut max_block = self.static_files_max_block.write();
         let mut tx_index = self.static_files_tx_index.write();
 
+        min_block.clear();
         max_block.clear();
         tx_index.clear();
 
         for (segment, ranges) in
             iter_static_files(&self.path).map_err(|e| ProviderError::NippyJar(e.to_string()))?
         {
+            // Update first block for each segment
+            if let Some((block_range, _)) = ranges.first() {
+                min_block.insert(segment, block_range.start());
+            }
+
             // Update last block for each segment
             if let Some((block_range, _)) = ranges.last() {
                 max_block.insert(segment, block_range.end());
@@ -620,175 +781,11 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
 
         // If this is a re-initialization, we need to clear this as well
         self.map.clear();
+        self.open_jars.retain(|_| false);
 
         Ok(())
     }
 
-    /// Ensures that any broken invariants which cannot be healed on the spot return a pipeline
-    /// target to unwind to.
-    ///
-    /// Two types of consistency checks are done for:
-    ///
-    /// 1) When a static file fails to commit but the underlying data was changed.
-    /// 2) When a static file was committed, but the required database transaction was not.
-    ///
-    /// For 1) it can self-heal if `self.access.is_read_only()` is set to `false`. Otherwise, it
-    /// will return an error.
-    /// For 2) the invariants below are checked, and if broken, might require a pipeline unwind
-    /// to heal.
-    ///
-    /// For each static file segment:
-    /// * the corresponding database table should overlap or have continuity in their keys
-    ///   ([`TxNumber`] or [`BlockNumber`]).
-    /// * its highest block should match the stage checkpoint block number if it's equal or higher
-    ///   than the corresponding database table last entry.
-    ///
-    /// Returns a [`Option`] of [`PipelineTarget::Unwind`] if any healing is further required.
-    ///
-    /// WARNING: No static file writer should be held before calling this function, otherwise it
-    /// will deadlock.
-    #[allow(clippy::while_let_loop)]
-    pub fn check_consistency<Provider>(
-        &self,
-        provider: &Provider,
-        has_receipt_pruning: bool,
-    ) -> ProviderResult<Option<PipelineTarget>>
-    where
-        Provider: DBProvider + BlockReader + StageCheckpointReader + ChainSpecProvider,
-    {
-        // OVM historical import is broken and does not work with this check. It's importing
-        // duplicated receipts resulting in having more receipts than the expected transaction
-        // range.
-        //
-        // If we detect an OVM import was done (block #1 <https://optimistic.etherscan.io/block/1>), skip it.
-        // More on [#11099](https://github.com/paradigmxyz/reth/pull/11099).
-        #[cfg(feature = "optimism")]
-        if reth_chainspec::EthChainSpec::chain(&provider.chain_spec()) ==
-            reth_chainspec::Chain::optimism_mainnet() &&
-            provider
-                .block_number(reth_optimism_primitives::bedrock::OVM_HEADER_1_HASH)?
-                .is_some()
-        {
-            info!(target: "reth::cli",
-                "Skipping storage verification for OP mainnet, expected inconsistency in OVM chain"
-            );
-            return Ok(None)
-        }
-
-        info!(target: "reth::cli", "Verifying storage consistency.");
-
-        let mut unwind_target: Option<BlockNumber> = None;
-        let mut update_unwind_target = |new_target: BlockNumber| {
-            if let Some(target) = unwind_target.as_mut() {
-                *target = (*target).min(new_target);
-            } else {
-                unwind_target = Some(new_target);
-            }
-        };
-
-        for segment in StaticFileSegment::iter() {
-            if has_receipt_pruning && segment.is_receipts() {
-                // Pruned nodes (including full node) do not store receipts as static files.
-                continue
-            }
-
-            let initial_highest_block = self.get_highest_static_file_block(segment);
-
-            //  File consistency is broken if:
-            //
-            // * appending data was interrupted before a config commit, then data file will be
-            //   truncated according to the config.
-            //
-            // * pruning data was interrupted before a config commit, then we have deleted data that
-            //   we are expected to still have. We need to check the Database and unwind everything
-            //   accordingly.
-            if self.access.is_read_only() {
-                self.check_segment_consistency(segment)?;
-            } else {
-                // Fetching the writer will attempt to heal any file level inconsistency.
-                self.latest_writer(segment)?;
-            }
-
-            // Only applies to block-based static files. (Headers)
-            //
-            // The updated `highest_block` may have decreased if we healed from a pruning
-            // interruption.
-            let mut highest_block = self.get_highest_static_file_block(segment);
-            if initial_highest_block != highest_block {
-                info!(
-                    target: "reth::providers::static_file",
-                    ?initial_highest_block,
-                    unwind_target = highest_block,
-                    ?segment,
-                    "Setting unwind target."
-                );
-                update_unwind_target(highest_block.unwrap_or_default());
-            }
-
-            // Only applies to transaction-based static files. (Receipts & Transactions)
-            //
-            // Make sure the last transaction matches the last block from its indices, since a heal
-            // from a pruning interruption might have decreased the number of transactions without
-            // being able to update the last block of the static file segment.
-            let highest_tx = self.get_highest_static_file_tx(segment);
-            if let Some(highest_tx) = highest_tx {
-                let mut last_block = highest_block.unwrap_or_default();
-                loop {
-                    if let Some(indices) = provider.block_body_indices(last_block)? {
-                        if indices.last_tx_num() <= highest_tx {
-                            break
-                        }
-                    } else {
-                        // If the block body indices can not be found, then it means that static
-                        // files is ahead of database, and the `ensure_invariants` check will fix
-                        // it by comparing with stage checkpoints.
-                        break
-                    }
-                    if last_block == 0 {
-                        break
-                    }
-                    last_block -= 1;
-
-                    info!(
-                        target: "reth::providers::static_file",
-                        highest_block = self.get_highest_static_file_block(segment),
-                        unwind_target = last_block,
-                        ?segment,
-                        "Setting unwind target."
-                    );
-                    highest_block = Some(last_block);
-                    update_unwind_target(last_block);
-                }
-            }
-
-            if let Some(unwind) = match segment {
-                StaticFileSegment::Headers => self.ensure_invariants::<_, tables::Headers>(
-                    provider,
-                    segment,
-                    highest_block,
-                    highest_block,
-                )?,
-                StaticFileSegment::Transactions => self
-                    .ensure_invariants::<_, tables::Transactions>(
-                        provider,
-                        segment,
-                        highest_tx,
-                        highest_block,
-                    )?,
-                StaticFileSegment::Receipts => self.ensure_invariants::<_, tables::Receipts>(
-                    provider,
-                    segment,
-                    highest_tx,
-                    highest_block,
-                )?,
-            } {
-                update_unwind_target(unwind);
-            }
-        }
-
-        Ok(unwind_target.map(PipelineTarget::Unwind))
-    }
-
     /// Checks consistency of the latest static file segment and throws an error if at fault.
     /// Read-only.
     pub fn check_segment_consistency(&self, segment: StaticFileSegment) -> ProviderResult<()> {
@@ -799,6 +796,12 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
             let jar = NippyJar::<SegmentHeader>::load(&file_path)
                 .map_err(|e| ProviderError::NippyJar(e.to_string()))?;
 
+            // Static files are only sealed once they've been fully committed, which happens to the
+            // latest one if the node stopped before anything was committed to the next one.
+            if jar.has_delta_offsets() {
+                return Ok(())
+            }
+
             NippyJarChecker::new(jar)
                 .check_consistency()
                 .map_err(|e| ProviderError::NippyJar(e.to_string()))?;
@@ -806,108 +809,13 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
         Ok(())
     }
 
-    /// Check invariants for each corresponding table and static file segment:
+    /// Gets the lowest static file block if it exists for a static file segment.
     ///
-    /// * the corresponding database table should overlap or have continuity in their keys
-    ///   ([`TxNumber`] or [`BlockNumber`]).
-    /// * its highest block should match the stage checkpoint block number if it's equal or higher
-    ///   than the corresponding database table last entry.
-    ///   * If the checkpoint block is higher, then request a pipeline unwind to the static file
-    ///     block. This is expressed by returning [`Some`] with the requested pipeline unwind
-    ///     target.
-    ///   * If the checkpoint block is lower, then heal by removing rows from the static file. In
-    ///     this case, the rows will be removed and [`None`] will be returned.
-    ///
-    /// * If the database tables overlap with static files and have contiguous keys, or the
-    ///   checkpoint block matches the highest static files block, then [`None`] will be returned.
-    fn ensure_invariants<Provider, T: Table<Key = u64>>(
-        &self,
-        provider: &Provider,
-        segment: StaticFileSegment,
-        highest_static_file_entry: Option<u64>,
-        highest_static_file_block: Option<BlockNumber>,
-    ) -> ProviderResult<Option<BlockNumber>>
-    where
-        Provider: DBProvider + BlockReader + StageCheckpointReader,
-    {
-        let highest_static_file_entry = highest_static_file_entry.unwrap_or_default();
-        let highest_static_file_block = highest_static_file_block.unwrap_or_default();
-        let mut db_cursor = provider.tx_ref().cursor_read::<T>()?;
-
-        if let Some((db_first_entry, _)) = db_cursor.first()? {
-            // If there is a gap between the entry found in static file and
-            // database, then we have most likely lost static file data and need to unwind so we can
-            // load it again
-            if !(db_first_entry <= highest_static_file_entry ||
-                highest_static_file_entry + 1 == db_first_entry)
-            {
-                info!(
-                    target: "reth::providers::static_file",
-                    ?db_first_entry,
-                    ?highest_static_file_entry,
-                    unwind_target = highest_static_file_block,
-                    ?segment,
-                    "Setting unwind target."
-                );
-                return Ok(Some(highest_static_file_block))
-            }
-
-            if let Some((db_last_entry, _)) = db_cursor.last()? {
-                if db_last_entry > highest_static_file_entry {
-                    return Ok(None)
-                }
-            }
-        }
-
-        // If static file entry is ahead of the database entries, then ensure the checkpoint block
-        // number matches.
-        let checkpoint_block_number = provider
-            .get_stage_checkpoint(match segment {
-                StaticFileSegment::Headers => StageId::Headers,
-                StaticFileSegment::Transactions => StageId::Bodies,
-                StaticFileSegment::Receipts => StageId::Execution,
-            })?
-            .unwrap_or_default()
-            .block_number;
-
-        // If the checkpoint is ahead, then we lost static file data. May be data corruption.
-        if checkpoint_block_number > highest_static_file_block {
-            info!(
-                target: "reth::providers::static_file",
-                checkpoint_block_number,
-                unwind_target = highest_static_file_block,
-                ?segment,
-                "Setting unwind target."
-            );
-            return Ok(Some(highest_static_file_block))
-        }
-
-        // If the checkpoint is behind, then we failed to do a database commit **but committed** to
-        // static files on executing a stage, or the reverse on unwinding a stage.
-        // All we need to do is to prune the extra static file rows.
-        if checkpoint_block_number < highest_static_file_block {
-            info!(
-                target: "reth::providers",
-                ?segment,
-                from = highest_static_file_block,
-                to = checkpoint_block_number,
-                "Unwinding static file segment."
-            );
-            let mut writer = self.latest_writer(segment)?;
-            if segment.is_headers() {
-                writer.prune_headers(highest_static_file_block - checkpoint_block_number)?;
-            } else if let Some(block) = provider.block_body_indices(checkpoint_block_number)? {
-                let number = highest_static_file_entry - block.last_tx_num();
-                if segment.is_receipts() {
-                    writer.prune_receipts(number, checkpoint_block_number)?;
-                } else {
-                    writer.prune_transactions(number, checkpoint_block_number)?;
-                }
-            }
-            writer.commit()?;
-        }
-
-        Ok(None)
+    /// This is above `0` only if the lower static files were removed from disk, eg. because the
+    /// history before them expired. If there is nothing on disk for the given segment, this will
+    /// return [`None`].
+    pub fn get_lowest_static_file_block(&self, segment: StaticFileSegment) -> Option<BlockNumber> {
+        self.static_files_min_block.read().get(&segment).copied()
     }
 
     /// Gets the highest static file block if it exists for a static file segment.
@@ -1264,6 +1172,10 @@ impl<N: NodePrimitives<BlockHeader: Value>> HeaderProvider for StaticFileProvide
             })
     }
 
+    fn earliest_header_number(&self) -> ProviderResult<BlockNumber> {
+        Ok(self.get_lowest_static_file_block(StaticFileSegment::Headers).unwrap_or_default())
+    }
+
     fn header_td(&self, block_hash: &BlockHash) -> ProviderResult<Option<U256>> {
         self.find_static_file(StaticFileSegment::Headers, |jar_provider| {
             Ok(jar_provider
@@ -1424,7 +1336,8 @@ impl<N: FullNodePrimitives<SignedTx: Value, Receipt: Value, BlockHeader: Value>>
             // This task will send the results through the channel after it has calculated
             // the hash.
             rayon::spawn(move || {
-                let mut rlp_buf = Vec::with_capacity(128);
+                // hash in batches to bound the memory of the encoded transactions
+                let mut batch = Vec::with_capacity(TRANSACTION_HASH_BATCH_SIZE);
                 let _ = manager.fetch_range_with_predicate(
                     StaticFileSegment::Transactions,
                     chunk_range,
@@ -1432,13 +1345,19 @@ impl<N: FullNodePrimitives<SignedTx: Value, Receipt: Value, BlockHeader: Value>>
                         Ok(cursor
                             .get_one::<TransactionMask<Self::Transaction>>(number.into())?
                             .map(|transaction| {
-                                rlp_buf.clear();
-                                let _ = channel_tx
-                                    .send(calculate_hash((number, transaction), &mut rlp_buf));
+                                batch.push((number, transaction));
+                                if batch.len() == TRANSACTION_HASH_BATCH_SIZE {
+                                    for hash in calculate_hashes(batch.drain(..)) {
+                                        let _ = channel_tx.send(Ok(hash));
+                                    }
+                                }
                             }))
                     },
                     |_| true,
                 );
+                for hash in calculate_hashes(batch) {
+                    let _ = channel_tx.send(Ok(hash));
+                }
             });
         }
 
@@ -1720,16 +1639,17 @@ impl<N: NodePrimitives> StatsReader for StaticFileProvider<N> {
     }
 }
 
-/// Calculates the tx hash for the given transaction and its id.
-#[inline]
-fn calculate_hash<T>(
-    entry: (TxNumber, T),
-    rlp_buf: &mut Vec<u8>,
-) -> Result<(B256, TxNumber), Box<ProviderError>>
+/// Maximum number of transactions to encode before hashing them.
+pub(crate) const TRANSACTION_HASH_BATCH_SIZE: usize = 32;
+
+/// Calculates the tx hashes for the given transactions and their ids.
+pub(crate) fn calculate_hashes<T>(
+    transactions: impl IntoIterator<Item = (TxNumber, T)>,
+) -> Vec<(B256, TxNumber)>
 where
     T: Encodable2718,
 {
-    let (tx_id, tx) = entry;
-    tx.encode_2718(rlp_buf);
-    Ok((keccak256(rlp_buf), tx_id))
+    let (tx_ids, encoded): (Vec<_>, Vec<_>) =
+        transactions.into_iter().map(|(tx_id, tx)| (tx_id, tx.encoded_2718())).unzip();
+    keccak256_batch(&encoded).into_iter().zip(tx_ids).collect()
 }
@@ -3,6 +3,7 @@ use std::{collections::HashMap, time::Duration};
 use itertools::Itertools;
 use metrics::{Counter, Gauge, Histogram};
 use reth_metrics::Metrics;
+use reth_nippy_jar::NippyJarWriterStats;
 use reth_primitives::StaticFileSegment;
 use strum::{EnumIter, IntoEnumIterator};
 
@@ -10,6 +11,7 @@ use strum::{EnumIter, IntoEnumIterator};
 #[derive(Debug)]
 pub struct StaticFileProviderMetrics {
     segments: HashMap<StaticFileSegment, StaticFileSegmentMetrics>,
+    writers: HashMap<StaticFileSegment, StaticFileWriterMetrics>,
     segment_operations: HashMap<
         (StaticFileSegment, StaticFileProviderOperation),
         StaticFileProviderOperationMetrics,
@@ -27,6 +29,14 @@ impl Default for StaticFileProviderMetrics {
                     )
                 })
                 .collect(),
+            writers: StaticFileSegment::iter()
+                .map(|segment| {
+                    (
+                        segment,
+                        StaticFileWriterMetrics::new_with_labels(&[("segment", segment.as_str())]),
+                    )
+                })
+                .collect(),
             segment_operations: StaticFileSegment::iter()
                 .cartesian_product(StaticFileProviderOperation::iter())
                 .map(|(segment, operation)| {
@@ -60,6 +70,19 @@ impl StaticFileProviderMetrics {
             .set(entries as f64);
     }
 
+    pub(crate) fn record_writer_stats(
+        &self,
+        segment: StaticFileSegment,
+        stats: &NippyJarWriterStats,
+    ) {
+        let metrics = self.writers.get(&segment).expect("writer metrics should exist");
+        metrics.preallocations_total.increment(stats.preallocations);
+        if stats.fsyncs.count > 0 {
+            metrics.fsyncs_total.increment(stats.fsyncs.count);
+            metrics.fsync_max_duration_seconds.record(stats.fsyncs.max.as_secs_f64());
+        }
+    }
+
     pub(crate) fn record_segment_operation(
         &self,
         segment: StaticFileSegment,
@@ -139,6 +162,18 @@ pub(crate) struct StaticFileSegmentMetrics {
     entries: Gauge,
 }
 
+/// Disk I/O metrics of the writer of a static file segment.
+#[derive(Metrics)]
+#[metrics(scope = "static_files.writer")]
+pub(crate) struct StaticFileWriterMetrics {
+    /// The number of times disk space was reserved ahead of the appends
+    preallocations_total: Counter,
+    /// The number of times a static file was synced to disk
+    fsyncs_total: Counter,
+    /// The longest time it took to sync a static file to disk during a commit
+    fsync_max_duration_seconds: Histogram,
+}
+
 #[derive(Metrics)]
 #[metrics(scope = "static_files.jar_provider")]
 pub(crate) struct StaticFileProviderOperationMetrics {
@@ -147,3 +182,15 @@ pub(crate) struct StaticFileProviderOperationMetrics {
     /// The time it took to execute the static file jar provider operation that writes data.
     write_duration_seconds: Histogram,
 }
+
+/// Metrics for the open static file handles.
+#[derive(Metrics)]
+#[metrics(scope = "static_files.handles")]
+pub(crate) struct StaticFileHandleMetrics {
+    /// The number of open static files
+    pub(crate) open: Gauge,
+    /// The number of static files closed to stay within the open files budget
+    pub(crate) evictions_total: Counter,
+    /// The number of evicted static files that were opened again
+    pub(crate) reopens_total: Counter,
+}
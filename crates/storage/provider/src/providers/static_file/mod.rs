@@ -1,4 +1,5 @@
 mod manager;
+pub(crate) use manager::{calculate_hashes, TRANSACTION_HASH_BATCH_SIZE};
 pub use manager::{StaticFileAccess, StaticFileProvider, StaticFileWriter};
 
 mod jar;
@@ -7,12 +8,19 @@ pub use jar::StaticFileJarProvider;
 mod writer;
 pub use writer::{StaticFileProviderRW, StaticFileProviderRWRefMut};
 
+mod handles;
 mod metrics;
 
 use reth_nippy_jar::NippyJar;
 use reth_primitives::{static_file::SegmentHeader, StaticFileSegment};
 use reth_storage_errors::provider::{ProviderError, ProviderResult};
-use std::{ops::Deref, sync::Arc};
+use std::{
+    ops::Deref,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
 
 /// Alias type for each specific `NippyJar`.
 type LoadedJarRef<'a> = dashmap::mapref::one::Ref<'a, (u64, StaticFileSegment), LoadedJar>;
@@ -22,6 +30,8 @@ type LoadedJarRef<'a> = dashmap::mapref::one::Ref<'a, (u64, StaticFileSegment),
 pub struct LoadedJar {
     jar: NippyJar<SegmentHeader>,
     mmap_handle: Arc<reth_nippy_jar::DataReader>,
+    /// Whether the jar was accessed since it was last considered for eviction.
+    accessed: AtomicBool,
 }
 
 impl LoadedJar {
@@ -29,7 +39,7 @@ impl LoadedJar {
         match jar.open_data_reader() {
             Ok(data_reader) => {
                 let mmap_handle = Arc::new(data_reader);
-                Ok(Self { jar, mmap_handle })
+                Ok(Self { jar, mmap_handle, accessed: AtomicBool::new(true) })
             }
             Err(e) => Err(ProviderError::NippyJar(e.to_string())),
         }
@@ -43,6 +53,16 @@ impl LoadedJar {
     const fn segment(&self) -> StaticFileSegment {
         self.jar.user_header().segment()
     }
+
+    /// Marks the jar as recently used, so it's not evicted next.
+    fn mark_accessed(&self) {
+        self.accessed.store(true, Ordering::Relaxed);
+    }
+
+    /// Returns whether the jar was accessed since the last call and resets the flag.
+    fn take_accessed(&self) -> bool {
+        self.accessed.swap(false, Ordering::Relaxed)
+    }
 }
 
 impl Deref for LoadedJar {
@@ -550,6 +570,153 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_max_open_files() {
+        let (static_dir, _) = create_test_static_files_dir();
+
+        let blocks_per_file = 2;
+        let tip = blocks_per_file * 10 - 1;
+        let sf_rw = StaticFileProvider::<EthPrimitives>::read_write(&static_dir)
+            .expect("Failed to create static file provider")
+            .with_custom_blocks_per_file(blocks_per_file)
+            .with_max_open_files(3);
+
+        let mut header_writer = sf_rw.latest_writer(StaticFileSegment::Headers).unwrap();
+        let mut header = Header::default();
+        for num in 0..=tip {
+            header.number = num;
+            header_writer.append_header(&header, U256::default(), &BlockHash::default()).unwrap();
+        }
+        header_writer.commit().unwrap();
+        drop(header_writer);
+
+        // Concurrent readers over more files than the budget reopen evicted files transparently
+        std::thread::scope(|scope| {
+            for _ in 0..4 {
+                scope.spawn(|| {
+                    let mut blocks = (0..=tip).collect::<Vec<_>>();
+                    for _ in 0..10 {
+                        blocks.shuffle(&mut generators::rng());
+                        for &num in &blocks {
+                            let header = sf_rw.header_by_number(num).unwrap().unwrap();
+                            assert_eq!(header.number, num);
+                        }
+                    }
+                });
+            }
+        });
+
+        // Once the readers are done, opening files doesn't exceed the budget anymore
+        for num in 0..=tip {
+            assert_eq!(sf_rw.header_by_number(num).unwrap().unwrap().number, num);
+            assert!(sf_rw.map.len() <= 3);
+        }
+    }
+
+    #[test]
+    fn test_seal_static_files() {
+        let (static_dir, _) = create_test_static_files_dir();
+
+        let blocks_per_file = 10;
+        let tip = blocks_per_file * 3 - 1;
+        let sf_rw = StaticFileProvider::<EthPrimitives>::read_write(&static_dir)
+            .expect("Failed to create static file provider")
+            .with_custom_blocks_per_file(blocks_per_file);
+        let load_jar = |block| {
+            NippyJar::<SegmentHeader>::load(
+                &static_dir
+                    .as_ref()
+                    .join(StaticFileSegment::Headers.filename(&sf_rw.find_fixed_range(block))),
+            )
+            .unwrap()
+        };
+
+        let mut header_writer = sf_rw.latest_writer(StaticFileSegment::Headers).unwrap();
+        let mut header = Header::default();
+        for num in 0..=tip {
+            header.number = num;
+            header_writer.append_header(&header, U256::default(), &BlockHash::default()).unwrap();
+        }
+        header_writer.commit().unwrap();
+
+        // The writer doesn't seal finished static files, the latest one is never returned
+        assert!(!load_jar(0).has_delta_offsets());
+        let unsealed = sf_rw.unsealed_static_files().unwrap();
+        assert_eq!(
+            unsealed,
+            vec![
+                (StaticFileSegment::Headers, sf_rw.find_fixed_range(0)),
+                (StaticFileSegment::Headers, sf_rw.find_fixed_range(blocks_per_file)),
+            ]
+        );
+
+        // Finished static files are sealed on demand
+        for (segment, fixed_block_range) in unsealed {
+            sf_rw.seal_static_file(segment, fixed_block_range).unwrap();
+        }
+        assert!(sf_rw.unsealed_static_files().unwrap().is_empty());
+        assert!(load_jar(0).has_delta_offsets());
+        assert!(load_jar(blocks_per_file).has_delta_offsets());
+        assert!(!load_jar(tip).has_delta_offsets());
+        for num in 0..=tip {
+            assert_eq!(sf_rw.header_by_number(num).unwrap().unwrap().number, num);
+        }
+
+        // Unwinding into a sealed static file makes it writable again
+        header_writer.prune_headers(blocks_per_file + 1).unwrap();
+        header_writer.commit().unwrap();
+        let new_tip = tip - blocks_per_file - 1;
+        assert!(!load_jar(new_tip).has_delta_offsets());
+        assert_eq!(sf_rw.get_highest_static_file_block(StaticFileSegment::Headers), Some(new_tip));
+
+        for num in new_tip + 1..=tip {
+            header.number = num;
+            header_writer.append_header(&header, U256::default(), &BlockHash::default()).unwrap();
+        }
+        header_writer.commit().unwrap();
+        assert!(!load_jar(blocks_per_file).has_delta_offsets());
+        assert!(load_jar(0).has_delta_offsets());
+        for num in 0..=tip {
+            assert_eq!(sf_rw.header_by_number(num).unwrap().unwrap().number, num);
+        }
+    }
+
+    #[test]
+    fn test_lowest_static_file_block() {
+        let (static_dir, _) = create_test_static_files_dir();
+
+        let blocks_per_file = 10;
+        let sf_rw = StaticFileProvider::<EthPrimitives>::read_write(&static_dir)
+            .expect("Failed to create static file provider")
+            .with_custom_blocks_per_file(blocks_per_file);
+        assert_eq!(sf_rw.get_lowest_static_file_block(StaticFileSegment::Headers), None);
+
+        let mut header_writer = sf_rw.latest_writer(StaticFileSegment::Headers).unwrap();
+        let mut header = Header::default();
+        for num in 0..blocks_per_file * 3 {
+            header.number = num;
+            header_writer.append_header(&header, U256::default(), &BlockHash::default()).unwrap();
+        }
+        header_writer.commit().unwrap();
+        drop(header_writer);
+        assert_eq!(sf_rw.get_lowest_static_file_block(StaticFileSegment::Headers), Some(0));
+
+        // The history of the first static file expires
+        NippyJar::<SegmentHeader>::load(
+            &static_dir
+                .as_ref()
+                .join(StaticFileSegment::Headers.filename(&sf_rw.find_fixed_range(0))),
+        )
+        .unwrap()
+        .delete()
+        .unwrap();
+        sf_rw.initialize_index().unwrap();
+        assert_eq!(
+            sf_rw.get_lowest_static_file_block(StaticFileSegment::Headers),
+            Some(blocks_per_file)
+        );
+    }
+
     /// Returns the number of files in the provided path, excluding ".lock" files.
     fn count_files_without_lockfile(path: impl AsRef<Path>) -> eyre::Result<usize> {
         let is_lockfile = |entry: &fs::DirEntry| {
@@ -7,7 +7,7 @@ use alloy_primitives::{BlockHash, BlockNumber, TxNumber, U256};
 use parking_lot::{lock_api::RwLockWriteGuard, RawRwLock, RwLock};
 use reth_codecs::Compact;
 use reth_db_api::models::CompactU256;
-use reth_nippy_jar::{NippyJar, NippyJarError, NippyJarWriter};
+use reth_nippy_jar::{NippyJar, NippyJarError, NippyJarWriter, OffsetsEncoding};
 use reth_node_types::NodePrimitives;
 use reth_primitives::{
     static_file::{SegmentHeader, SegmentRangeInclusive},
@@ -158,11 +158,22 @@ impl<N: NodePrimitives> StaticFileProviderRW<N> {
             block_range.start(),
             None,
         ) {
-            Ok(provider) => (
-                NippyJar::load(provider.data_path())
-                    .map_err(|e| ProviderError::NippyJar(e.to_string()))?,
-                provider.data_path().into(),
-            ),
+            Ok(provider) => {
+                let mut jar = NippyJar::load(provider.data_path())
+                    .map_err(|e| ProviderError::NippyJar(e.to_string()))?;
+                let path = provider.data_path().into();
+
+                // A sealed static file has to get its fixed-width offsets back before it can be
+                // appended to or pruned, eg. when unwinding into it.
+                if jar.has_delta_offsets() {
+                    drop(provider);
+                    static_file_provider.remove_cached_provider(segment, block_range.end());
+                    jar.rewrite_offsets(OffsetsEncoding::Fixed)
+                        .map_err(|e| ProviderError::NippyJar(e.to_string()))?;
+                }
+
+                (jar, path)
+            }
             Err(ProviderError::MissingStaticFileBlock(_, _)) => {
                 let path = static_file_provider.directory().join(segment.filename(&block_range));
                 (create_jar(segment, &path, block_range), path)
@@ -171,7 +182,12 @@ impl<N: NodePrimitives> StaticFileProviderRW<N> {
         };
 
         let result = match NippyJarWriter::new(jar) {
-            Ok(writer) => Ok((writer, path)),
+            Ok(writer) => Ok((
+                writer
+                    .with_preallocation(static_file_provider.preallocation_chunk_size())
+                    .with_fsync_policy(static_file_provider.fsync_policy()),
+                path,
+            )),
             Err(NippyJarError::FrozenJar) => {
                 // This static file has been frozen, so we should
                 Err(ProviderError::FinalizedStaticFile(segment, block))
@@ -237,12 +253,14 @@ impl<N: NodePrimitives> StaticFileProviderRW<N> {
             // Commits offsets and new user_header to disk
             self.writer.commit().map_err(|e| ProviderError::NippyJar(e.to_string()))?;
 
+            let stats = self.writer.take_stats();
             if let Some(metrics) = &self.metrics {
                 metrics.record_segment_operation(
                     self.writer.user_header().segment(),
                     StaticFileProviderOperation::CommitWriter,
                     Some(start.elapsed()),
                 );
+                metrics.record_writer_stats(self.writer.user_header().segment(), &stats);
             }
 
             debug!(
@@ -250,6 +268,8 @@ impl<N: NodePrimitives> StaticFileProviderRW<N> {
                 segment = ?self.writer.user_header().segment(),
                 path = ?self.data_path,
                 duration = ?start.elapsed(),
+                preallocations = stats.preallocations,
+                fsyncs = stats.fsyncs.count,
                 "Commit"
             );
 
@@ -331,6 +351,9 @@ impl<N: NodePrimitives> StaticFileProviderRW<N> {
             if last_block == self.writer.user_header().expected_block_end() {
                 // Commits offsets and new user_header to disk
                 self.commit()?;
+                self.writer
+                    .release_preallocation()
+                    .map_err(|e| ProviderError::NippyJar(e.to_string()))?;
 
                 // Opens the new static file
                 let (writer, data_path) =
@@ -0,0 +1,521 @@
+//! Prepopulated datadirs for tests.
+//!
+//! Writing a chain to storage is slow compared to most tests that read it. The datadir of a
+//! [`TestDatadir`] is only written once per machine for the same generated chain. It is cached in
+//! the `reth-test-datadirs` directory of the temp directory, keyed by the hash of the generated tip
+//! block and where the chain was written to, and every test gets its own copy of it, which it can
+//! freely write to. The files are copied with [`std::fs::copy`], which clones them without copying
+//! their contents on file systems with reflink support, e.g. Btrfs and XFS. Tests using the same
+//! parameters therefore only pay for generating the chain in memory and copying the datadir,
+//! instead of writing hundreds of blocks themselves.
+//!
+//! Since the key is derived from the generated chain, changing how chains are generated never
+//! reuses an outdated datadir. Changing how a chain is written to storage isn't detected though, so
+//! the cache directory has to be removed after such changes.
+//!
+//! ```ignore
+//! let datadir = TestDatadir::new(TestChainParams { blocks: 100, ..Default::default() })?;
+//! let provider = datadir.provider_factory().provider()?;
+//! assert_eq!(provider.sealed_header(100)?.as_ref(), Some(&datadir.tip().header));
+//! ```
+
+use crate::{
+    providers::{ProviderNodeTypes, StaticFileProvider},
+    test_utils::{MockNodeTypes, MockNodeTypesWithDB},
+    writer::UnifiedStorageWriter,
+    BlockWriter, DatabaseProviderFactory, OriginalValuesKnown, ProviderFactory,
+    StageCheckpointWriter, StateWriter, StaticFileProviderFactory, StorageLocation,
+};
+use alloy_consensus::{Transaction as _, TxEip4844};
+use alloy_eips::eip4844::DATA_GAS_PER_BLOB;
+use alloy_primitives::{BlockNumber, B256, U256};
+use reth_chainspec::{ChainSpec, ChainSpecBuilder, MAINNET};
+use reth_db::{
+    init_db,
+    mdbx::DatabaseArguments,
+    test_utils::{create_test_rw_db_with_path, tempdir_path, ERROR_DB_CREATION, ERROR_TEMPDIR},
+    ClientVersion, DatabaseEnv,
+};
+use reth_db_api::{
+    common::KeyValue,
+    cursor::DbCursorRO,
+    table::Table,
+    transaction::{DbTx, DbTxMut},
+    DatabaseError,
+};
+use reth_errors::ProviderResult;
+use reth_execution_types::ExecutionOutcome;
+use reth_node_types::NodeTypesWithDBAdapter;
+use reth_primitives::{
+    proofs, Block, EthPrimitives, Receipt, SealedBlock, SealedBlockWithSenders, SealedHeader,
+    Transaction, TransactionSigned,
+};
+use reth_testing_utils::generators::{
+    random_block, random_receipt, rng_with_seed, sign_tx_with_random_key_pair, BlockParams, Rng,
+};
+use std::{
+    fs, io,
+    ops::Range,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use tempfile::TempDir;
+
+/// Name of the directory in the temp directory where generated datadirs are cached.
+const CACHE_DIR: &str = "reth-test-datadirs";
+
+/// Parameters of the chain of a [`TestDatadir`].
+///
+/// The chain is generated deterministically from the parameters, and every distinct chain is
+/// cached separately.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TestChainParams {
+    /// Number of blocks on top of the genesis block.
+    pub blocks: u64,
+    /// Range of the number of legacy transactions per block.
+    pub tx_count: Range<u8>,
+    /// Range of the number of blob transactions per block. The blocks don't have any blob gas
+    /// fields if the range is empty.
+    pub blob_tx_count: Range<u8>,
+    /// Range of the number of withdrawals per block. The blocks don't have withdrawals if unset.
+    pub withdrawals_count: Option<Range<u8>>,
+    /// Number of logs per receipt, if the transaction succeeded.
+    pub logs_count: u8,
+    /// Whether the genesis block has no transactions, like on real chains.
+    pub empty_genesis: bool,
+    /// Where the headers, transactions and receipts are written to.
+    pub storage: StorageLocation,
+    /// Seed of the random generator.
+    pub seed: u64,
+}
+
+impl Default for TestChainParams {
+    fn default() -> Self {
+        Self {
+            blocks: 300,
+            tx_count: 0..10,
+            blob_tx_count: 0..2,
+            withdrawals_count: Some(0..4),
+            logs_count: 1,
+            empty_genesis: true,
+            storage: StorageLocation::StaticFiles,
+            seed: 0,
+        }
+    }
+}
+
+impl TestChainParams {
+    /// Returns the chain spec that the chain is read with by default. Shanghai is active from
+    /// genesis if the blocks have withdrawals.
+    pub fn chain_spec(&self) -> Arc<ChainSpec> {
+        if self.withdrawals_count.is_some() {
+            Arc::new(ChainSpecBuilder::mainnet().shanghai_activated().build())
+        } else {
+            MAINNET.clone()
+        }
+    }
+
+    /// Generates `count` blocks with their receipts on top of the parent, or starting with the
+    /// genesis block if there's no parent.
+    fn generate_blocks(
+        &self,
+        rng: &mut impl Rng,
+        parent: Option<&SealedBlock>,
+        count: u64,
+    ) -> Vec<(SealedBlockWithSenders, Vec<Receipt>)> {
+        let mut parent = parent.map(|block| (block.number, block.hash()));
+        let mut blocks = Vec::with_capacity(count as usize);
+        for _ in 0..count {
+            let number = parent.map_or(0, |(number, _)| number + 1);
+            let block = if number == 0 && self.empty_genesis {
+                random_block(
+                    rng,
+                    0,
+                    BlockParams {
+                        parent: Some(B256::ZERO),
+                        tx_count: Some(0),
+                        ommers_count: Some(0),
+                        withdrawals_count: self.withdrawals_count.as_ref().map(|_| 0),
+                        ..Default::default()
+                    },
+                )
+            } else {
+                self.random_block(rng, number, parent.map_or(B256::ZERO, |(_, hash)| hash))
+            };
+            parent = Some((number, block.hash()));
+
+            let receipts = block
+                .body
+                .transactions
+                .iter()
+                .map(|tx| random_receipt(rng, tx, Some(self.logs_count)))
+                .collect();
+            let block = block.seal_with_senders::<Block>().expect("failed to recover senders");
+            blocks.push((block, receipts));
+        }
+        blocks
+    }
+
+    /// Generates a random block with legacy and blob transactions.
+    fn random_block(&self, rng: &mut impl Rng, number: BlockNumber, parent: B256) -> SealedBlock {
+        let SealedBlock { header, mut body } = random_block(
+            rng,
+            number,
+            BlockParams {
+                parent: Some(parent),
+                tx_count: Some(sample(rng, &self.tx_count)),
+                // ommers aren't read back once the merge is active
+                ommers_count: Some(0),
+                withdrawals_count: self.withdrawals_count.as_ref().map(|count| sample(rng, count)),
+                ..Default::default()
+            },
+        );
+        if self.blob_tx_count.is_empty() {
+            return SealedBlock { header, body }
+        }
+
+        body.transactions
+            .extend((0..sample(rng, &self.blob_tx_count)).map(|_| random_blob_tx(rng)));
+        let gas = body.transactions.iter().map(|tx| tx.gas_limit()).sum();
+        let blob_gas_used = body
+            .transactions
+            .iter()
+            .filter_map(|tx| tx.blob_versioned_hashes())
+            .map(|hashes| hashes.len() as u64 * DATA_GAS_PER_BLOB)
+            .sum();
+
+        let mut header = header.unseal();
+        header.transactions_root = proofs::calculate_transaction_root(&body.transactions);
+        header.gas_used = gas;
+        header.gas_limit = gas;
+        header.blob_gas_used = Some(blob_gas_used);
+        header.excess_blob_gas = Some(0);
+        header.parent_beacon_block_root = Some(rng.gen());
+        SealedBlock { header: SealedHeader::seal(header), body }
+    }
+}
+
+/// A copy of a cached, prepopulated datadir with the chain generated from [`TestChainParams`].
+///
+/// The copy is removed when it's dropped.
+#[derive(Debug)]
+pub struct TestDatadir {
+    factory: ProviderFactory<MockNodeTypesWithDB>,
+    params: TestChainParams,
+    blocks: Vec<SealedBlockWithSenders>,
+    receipts: Vec<Vec<Receipt>>,
+    static_files_dir: TempDir,
+}
+
+impl TestDatadir {
+    /// Returns a copy of the datadir with the chain generated from the parameters, that is read
+    /// with [`TestChainParams::chain_spec`].
+    ///
+    /// The datadir is written and cached first if it doesn't exist yet.
+    pub fn new(params: TestChainParams) -> ProviderResult<Self> {
+        let chain_spec = params.chain_spec();
+        Self::with_chain_spec(params, chain_spec)
+    }
+
+    /// Returns a copy of the datadir with the chain generated from the parameters, that is read
+    /// with the given chain spec.
+    ///
+    /// The datadir is written and cached first if it doesn't exist yet.
+    pub fn with_chain_spec(
+        params: TestChainParams,
+        chain_spec: Arc<ChainSpec>,
+    ) -> ProviderResult<Self> {
+        let mut rng = rng_with_seed(&params.seed.to_be_bytes());
+        let (blocks, receipts): (Vec<_>, Vec<_>) =
+            params.generate_blocks(&mut rng, None, params.blocks + 1).into_iter().unzip();
+        let cached = cached_datadir(&params, &blocks, &receipts)?;
+
+        let static_files_dir = TempDir::with_prefix("reth-test-static-").expect(ERROR_TEMPDIR);
+        copy_dir(&cached.join("static_files"), static_files_dir.path())?;
+        let db_path = tempdir_path();
+        copy_dir(&cached.join("db"), &db_path)?;
+
+        let factory = ProviderFactory::new(
+            create_test_rw_db_with_path(db_path),
+            chain_spec,
+            StaticFileProvider::read_write(static_files_dir.path())?,
+        );
+
+        Ok(Self { factory, params, blocks, receipts, static_files_dir })
+    }
+
+    /// Returns the provider factory of the datadir.
+    pub const fn provider_factory(&self) -> &ProviderFactory<MockNodeTypesWithDB> {
+        &self.factory
+    }
+
+    /// Returns the provider factory of the datadir, consuming the handle.
+    ///
+    /// The copied static files aren't removed afterwards, like with
+    /// [`create_test_provider_factory`](crate::test_utils::create_test_provider_factory).
+    pub fn into_provider_factory(self) -> ProviderFactory<MockNodeTypesWithDB> {
+        let _ = self.static_files_dir.into_path();
+        self.factory
+    }
+
+    /// Returns the static file provider of the datadir.
+    pub fn static_file_provider(&self) -> StaticFileProvider<EthPrimitives> {
+        self.factory.static_file_provider()
+    }
+
+    /// Returns the parameters the chain was generated from.
+    pub const fn params(&self) -> &TestChainParams {
+        &self.params
+    }
+
+    /// Returns all blocks of the chain, starting with the genesis block.
+    pub fn blocks(&self) -> &[SealedBlockWithSenders] {
+        &self.blocks
+    }
+
+    /// Returns the receipts of all blocks of the chain.
+    pub fn receipts(&self) -> &[Vec<Receipt>] {
+        &self.receipts
+    }
+
+    /// Returns the last block of the chain.
+    pub fn tip(&self) -> &SealedBlockWithSenders {
+        self.blocks.last().expect("genesis block exists")
+    }
+
+    /// Generates blocks on top of the chain and writes them to this copy of the datadir only.
+    ///
+    /// Returns the new blocks.
+    pub fn extend(&mut self, count: u64) -> ProviderResult<&[SealedBlockWithSenders]> {
+        let tip = self.tip().block.clone();
+        let mut rng =
+            rng_with_seed(&[self.params.seed.to_be_bytes(), tip.number.to_be_bytes()].concat());
+        let (blocks, receipts): (Vec<_>, Vec<_>) =
+            self.params.generate_blocks(&mut rng, Some(&tip), count).into_iter().unzip();
+        write_blocks(&self.factory, &blocks, &receipts, self.params.storage)?;
+
+        let first_new = self.blocks.len();
+        self.blocks.extend(blocks);
+        self.receipts.extend(receipts);
+        Ok(&self.blocks[first_new..])
+    }
+
+    /// Returns all entries of the table.
+    pub fn table<T: Table>(&self) -> ProviderResult<Vec<KeyValue<T>>> {
+        let provider = self.factory.provider()?;
+        Ok(provider
+            .tx_ref()
+            .cursor_read::<T>()?
+            .walk(None)?
+            .collect::<Result<Vec<_>, DatabaseError>>()?)
+    }
+
+    /// Overwrites the entry of the table, e.g. to test how inconsistent data is handled.
+    pub fn corrupt<T: Table>(&self, key: T::Key, value: T::Value) -> ProviderResult<()> {
+        let provider = self.factory.provider_rw()?;
+        provider.tx_ref().put::<T>(key, value)?;
+        provider.commit()?;
+        Ok(())
+    }
+
+    /// Deletes the entry of the table, e.g. to test how missing data is handled.
+    ///
+    /// Returns `true` if the entry existed.
+    pub fn delete<T: Table>(&self, key: T::Key) -> ProviderResult<bool> {
+        let provider = self.factory.provider_rw()?;
+        let deleted = provider.tx_ref().delete::<T>(key, None)?;
+        provider.commit()?;
+        Ok(deleted)
+    }
+
+    /// Deletes all entries of the table, e.g. to test how missing data is handled.
+    pub fn clear<T: Table>(&self) -> ProviderResult<()> {
+        let provider = self.factory.provider_rw()?;
+        provider.tx_ref().clear::<T>()?;
+        provider.commit()?;
+        Ok(())
+    }
+}
+
+/// Returns the path of the cached datadir of the chain generated from the parameters, writing the
+/// chain to it if it doesn't exist yet.
+///
+/// The datadir is keyed by the hash of the tip, which commits to all blocks of the chain, the
+/// storage location and the number of logs per receipt. The receipts are generated with the same
+/// random generator as the blocks following them, so only the receipts of the tip aren't covered
+/// by its hash.
+fn cached_datadir(
+    params: &TestChainParams,
+    blocks: &[SealedBlockWithSenders],
+    receipts: &[Vec<Receipt>],
+) -> ProviderResult<PathBuf> {
+    let tip = blocks.last().expect("genesis block exists");
+    let storage = match params.storage {
+        StorageLocation::StaticFiles => "static-files",
+        StorageLocation::Database => "database",
+        StorageLocation::Both => "both",
+    };
+    let cache_dir = std::env::temp_dir().join(CACHE_DIR);
+    let path = cache_dir.join(format!("{}-{storage}-{}", tip.hash(), params.logs_count));
+    if path.exists() {
+        return Ok(path)
+    }
+
+    // Generate the datadir next to the cache and move it there once complete, so that concurrent
+    // tests never see a partially written datadir.
+    fs::create_dir_all(&cache_dir)?;
+    let tmp = tempfile::Builder::new().prefix("tmp-").tempdir_in(&cache_dir)?;
+    write_datadir(params, blocks, receipts, tmp.path())?;
+    match fs::rename(tmp.path(), &path) {
+        Ok(()) => {
+            // the directory was moved, nothing to remove
+            let _ = tmp.into_path();
+        }
+        // another test generated the same datadir in the meantime
+        Err(_) if path.exists() => {}
+        Err(err) => return Err(err.into()),
+    }
+    Ok(path)
+}
+
+/// Writes the chain to a new datadir at the path.
+fn write_datadir(
+    params: &TestChainParams,
+    blocks: &[SealedBlockWithSenders],
+    receipts: &[Vec<Receipt>],
+    path: &Path,
+) -> ProviderResult<()> {
+    let db = init_db(path.join("db"), DatabaseArguments::new(ClientVersion::default()))
+        .expect(ERROR_DB_CREATION);
+    let factory = ProviderFactory::<NodeTypesWithDBAdapter<MockNodeTypes, Arc<DatabaseEnv>>>::new(
+        Arc::new(db),
+        params.chain_spec(),
+        StaticFileProvider::read_write(path.join("static_files"))?,
+    );
+    write_blocks(&factory, blocks, receipts, params.storage)
+}
+
+/// Writes the blocks and their receipts, and sets the stage checkpoints to the last block.
+fn write_blocks<N>(
+    factory: &ProviderFactory<N>,
+    blocks: &[SealedBlockWithSenders],
+    receipts: &[Vec<Receipt>],
+    storage: StorageLocation,
+) -> ProviderResult<()>
+where
+    N: ProviderNodeTypes<Primitives = EthPrimitives>,
+{
+    let Some(last) = blocks.last() else { return Ok(()) };
+
+    let provider_rw = factory.database_provider_rw()?;
+    for (block, receipts) in blocks.iter().zip(receipts) {
+        let number = block.number;
+        provider_rw.insert_block(block.clone(), storage)?;
+        provider_rw.write_state(
+            ExecutionOutcome {
+                receipts: receipts.clone().into(),
+                first_block: number,
+                ..Default::default()
+            },
+            OriginalValuesKnown::Yes,
+            storage,
+        )?;
+    }
+    provider_rw.update_pipeline_stages(last.number, false)?;
+    UnifiedStorageWriter::commit(provider_rw)?;
+
+    Ok(())
+}
+
+/// Copies the files of the directory recursively, except for lock files.
+fn copy_dir(from: &Path, to: &Path) -> io::Result<()> {
+    fs::create_dir_all(to)?;
+    for entry in fs::read_dir(from)? {
+        let entry = entry?;
+        let name = entry.file_name();
+        if name == "lock" || name == "mdbx.lck" {
+            continue
+        }
+
+        if entry.file_type()?.is_dir() {
+            copy_dir(&entry.path(), &to.join(name))?;
+        } else {
+            fs::copy(entry.path(), to.join(name))?;
+        }
+    }
+    Ok(())
+}
+
+/// Samples a number from the range, or returns its start if it's empty.
+fn sample(rng: &mut impl Rng, range: &Range<u8>) -> u8 {
+    if range.is_empty() {
+        range.start
+    } else {
+        rng.gen_range(range.clone())
+    }
+}
+
+/// Generates a random signed blob transaction with one or two blobs.
+fn random_blob_tx(rng: &mut impl Rng) -> TransactionSigned {
+    let tx = Transaction::Eip4844(TxEip4844 {
+        chain_id: 1,
+        nonce: rng.gen::<u16>().into(),
+        gas_limit: rng.gen::<u16>().into(),
+        max_fee_per_gas: rng.gen::<u16>().into(),
+        max_priority_fee_per_gas: rng.gen::<u16>().into(),
+        to: rng.gen(),
+        value: U256::from(rng.gen::<u16>()),
+        blob_versioned_hashes: (0..rng.gen_range(1..=2)).map(|_| rng.gen()).collect(),
+        max_fee_per_blob_gas: rng.gen::<u16>().into(),
+        ..Default::default()
+    });
+    sign_tx_with_random_key_pair(rng, tx)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BlockNumReader, HeaderProvider};
+    use reth_db::tables;
+
+    fn params() -> TestChainParams {
+        TestChainParams { blocks: 20, ..Default::default() }
+    }
+
+    #[test]
+    fn copies_are_independent() {
+        let mut datadir = TestDatadir::new(params()).unwrap();
+        let other = TestDatadir::new(params()).unwrap();
+        assert_eq!(datadir.blocks(), other.blocks());
+        assert_eq!(datadir.receipts(), other.receipts());
+        assert_eq!(datadir.blocks().len(), 21);
+        assert!(datadir.blocks()[0].body.transactions.is_empty());
+
+        let provider = datadir.provider_factory().provider().unwrap();
+        assert_eq!(provider.best_block_number().unwrap(), 20);
+        assert_eq!(provider.sealed_header(20).unwrap().as_ref(), Some(&datadir.tip().header));
+        drop(provider);
+
+        // extending one copy doesn't change the other
+        let new_blocks = datadir.extend(5).unwrap().to_vec();
+        assert_eq!(new_blocks.len(), 5);
+        assert_eq!(new_blocks[0].parent_hash, other.tip().hash());
+        assert_eq!(datadir.provider_factory().provider().unwrap().best_block_number().unwrap(), 25);
+        assert_eq!(other.provider_factory().provider().unwrap().best_block_number().unwrap(), 20);
+    }
+
+    #[test]
+    fn corrupt_tables() {
+        let datadir = TestDatadir::new(params()).unwrap();
+        let block = &datadir.blocks()[10];
+
+        assert!(datadir.delete::<tables::HeaderNumbers>(block.hash()).unwrap());
+        assert_eq!(
+            datadir.provider_factory().provider().unwrap().block_number(block.hash()).unwrap(),
+            None
+        );
+
+        datadir.clear::<tables::BlockWithdrawals>().unwrap();
+        assert!(datadir.table::<tables::BlockWithdrawals>().unwrap().is_empty());
+    }
+}
@@ -23,13 +23,15 @@ use reth_execution_types::ExecutionOutcome;
 use reth_node_types::NodeTypes;
 use reth_primitives::{
     Account, Block, BlockWithSenders, Bytecode, EthPrimitives, GotExpected, Receipt, SealedBlock,
-    SealedBlockWithSenders, SealedHeader, TransactionMeta, TransactionSigned,
+    SealedBlockWithSenders, SealedHeader, StorageEntry, TransactionMeta, TransactionSigned,
 };
 use reth_primitives_traits::SignedTransaction;
+use reth_prune_types::{PruneCheckpoint, PruneSegment};
 use reth_stages_types::{StageCheckpoint, StageId};
 use reth_storage_api::{
-    BlockBodyIndicesProvider, DatabaseProviderFactory, HashedPostStateProvider, OmmersProvider,
-    StageCheckpointReader, StateCommitmentProvider, StateProofProvider, StorageRootProvider,
+    BlockBodyIndicesProvider, DatabaseProviderFactory, HashedPostStateProvider, IndexedLogBlocks,
+    OmmersProvider, PruneCheckpointReader, StageCheckpointReader, StateCommitmentProvider,
+    StateProofProvider, StorageRootProvider,
 };
 use reth_storage_errors::provider::{ConsistentViewError, ProviderError, ProviderResult};
 use reth_trie::{
@@ -56,6 +58,14 @@ pub struct MockEthProvider {
     pub chain_spec: Arc<ChainSpec>,
     /// Local state roots
     pub state_roots: Arc<Mutex<Vec<B256>>>,
+    /// Local prune checkpoints
+    pub prune_checkpoints: Arc<Mutex<HashMap<PruneSegment, PruneCheckpoint>>>,
+    /// Local stage checkpoints
+    pub stage_checkpoints: Arc<Mutex<HashMap<StageId, StageCheckpoint>>>,
+    /// Local account changesets
+    pub account_changesets: Arc<Mutex<HashMap<BlockNumber, Vec<AccountBeforeTx>>>>,
+    /// Local receipt store, keyed by block hash
+    pub receipts: Arc<Mutex<HashMap<B256, Vec<Receipt>>>>,
 }
 
 impl Default for MockEthProvider {
@@ -66,6 +76,10 @@ impl Default for MockEthProvider {
             accounts: Default::default(),
             chain_spec: Arc::new(reth_chainspec::ChainSpecBuilder::mainnet().build()),
             state_roots: Default::default(),
+            prune_checkpoints: Default::default(),
+            stage_checkpoints: Default::default(),
+            account_changesets: Default::default(),
+            receipts: Default::default(),
         }
     }
 }
@@ -150,6 +164,38 @@ impl MockEthProvider {
     pub fn add_state_root(&self, state_root: B256) {
         self.state_roots.lock().push(state_root);
     }
+
+    /// Add prune checkpoint to local prune checkpoint store
+    pub fn add_prune_checkpoint(&self, segment: PruneSegment, checkpoint: PruneCheckpoint) {
+        self.prune_checkpoints.lock().insert(segment, checkpoint);
+    }
+
+    /// Add stage checkpoint to local stage checkpoint store
+    pub fn add_stage_checkpoint(&self, id: StageId, checkpoint: StageCheckpoint) {
+        self.stage_checkpoints.lock().insert(id, checkpoint);
+    }
+
+    /// Add the account changeset of a block to local account changeset store
+    pub fn add_account_changeset(
+        &self,
+        block_number: BlockNumber,
+        changeset: Vec<AccountBeforeTx>,
+    ) {
+        self.account_changesets.lock().insert(block_number, changeset);
+    }
+
+    /// Add the receipts of a block to local receipt store
+    pub fn add_receipts(&self, block_hash: B256, receipts: Vec<Receipt>) {
+        self.receipts.lock().insert(block_hash, receipts);
+    }
+
+    /// Returns the transactions of all blocks in the order of their transaction numbers.
+    fn transactions_by_number(&self) -> Vec<TransactionSigned> {
+        let lock = self.blocks.lock();
+        let mut blocks = lock.values().collect::<Vec<_>>();
+        blocks.sort_by_key(|block| block.number);
+        blocks.into_iter().flat_map(|block| block.body.transactions.iter().cloned()).collect()
+    }
 }
 
 /// Mock node.
@@ -194,6 +240,11 @@ impl HeaderProvider for MockEthProvider {
         Ok(lock.values().find(|h| h.number == num).cloned())
     }
 
+    fn earliest_header_number(&self) -> ProviderResult<BlockNumber> {
+        let lock = self.headers.lock();
+        Ok(lock.values().map(|h| h.number).min().unwrap_or_default())
+    }
+
     fn header_td(&self, hash: &BlockHash) -> ProviderResult<Option<U256>> {
         let lock = self.headers.lock();
         Ok(lock.get(hash).map(|target| {
@@ -252,10 +303,9 @@ impl TransactionsProvider for MockEthProvider {
     type Transaction = TransactionSigned;
 
     fn transaction_id(&self, tx_hash: TxHash) -> ProviderResult<Option<TxNumber>> {
-        let lock = self.blocks.lock();
-        let tx_number = lock
-            .values()
-            .flat_map(|block| &block.body.transactions)
+        let tx_number = self
+            .transactions_by_number()
+            .iter()
             .position(|tx| tx.hash() == tx_hash)
             .map(|pos| pos as TxNumber);
 
@@ -263,22 +313,14 @@ impl TransactionsProvider for MockEthProvider {
     }
 
     fn transaction_by_id(&self, id: TxNumber) -> ProviderResult<Option<Self::Transaction>> {
-        let lock = self.blocks.lock();
-        let transaction =
-            lock.values().flat_map(|block| &block.body.transactions).nth(id as usize).cloned();
-
-        Ok(transaction)
+        Ok(self.transactions_by_number().into_iter().nth(id as usize))
     }
 
     fn transaction_by_id_unhashed(
         &self,
         id: TxNumber,
     ) -> ProviderResult<Option<Self::Transaction>> {
-        let lock = self.blocks.lock();
-        let transaction =
-            lock.values().flat_map(|block| &block.body.transactions).nth(id as usize).cloned();
-
-        Ok(transaction)
+        Ok(self.transactions_by_number().into_iter().nth(id as usize))
     }
 
     fn transaction_by_hash(&self, hash: TxHash) -> ProviderResult<Option<TransactionSigned>> {
@@ -394,8 +436,12 @@ impl ReceiptProvider for MockEthProvider {
         Ok(None)
     }
 
-    fn receipts_by_block(&self, _block: BlockHashOrNumber) -> ProviderResult<Option<Vec<Receipt>>> {
-        Ok(None)
+    fn receipts_by_block(&self, block: BlockHashOrNumber) -> ProviderResult<Option<Vec<Receipt>>> {
+        let hash = match block {
+            BlockHashOrNumber::Hash(hash) => Some(hash),
+            BlockHashOrNumber::Number(number) => self.block_hash(number)?,
+        };
+        Ok(hash.and_then(|hash| self.receipts.lock().get(&hash).cloned()))
     }
 
     fn receipts_by_tx_range(
@@ -404,6 +450,42 @@ impl ReceiptProvider for MockEthProvider {
     ) -> ProviderResult<Vec<Receipt>> {
         Ok(vec![])
     }
+
+    fn log_address_blocks(
+        &self,
+        addresses: &[Address],
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Option<IndexedLogBlocks>> {
+        let Some(checkpoint) =
+            self.stage_checkpoints.lock().get(&StageId::IndexLogAddresses).copied()
+        else {
+            return Ok(None)
+        };
+        let (start, end) = range.into_inner();
+        if start > checkpoint.block_number {
+            return Ok(None)
+        }
+        let indexed_to = end.min(checkpoint.block_number);
+
+        let blocks = self.blocks.lock();
+        let receipts = self.receipts.lock();
+        let mut indexed = blocks
+            .iter()
+            .filter(|(hash, block)| {
+                (start..=indexed_to).contains(&block.number) &&
+                    receipts.get(*hash).is_some_and(|receipts| {
+                        receipts
+                            .iter()
+                            .flat_map(|receipt| &receipt.logs)
+                            .any(|log| addresses.contains(&log.address))
+                    })
+            })
+            .map(|(_, block)| block.number)
+            .collect::<Vec<_>>();
+        indexed.sort_unstable();
+
+        Ok(Some(IndexedLogBlocks { blocks: indexed, indexed_to }))
+    }
 }
 
 impl ReceiptProviderIdExt for MockEthProvider {}
@@ -583,8 +665,8 @@ impl AccountReader for MockEthProvider {
 }
 
 impl StageCheckpointReader for MockEthProvider {
-    fn get_stage_checkpoint(&self, _id: StageId) -> ProviderResult<Option<StageCheckpoint>> {
-        Ok(None)
+    fn get_stage_checkpoint(&self, id: StageId) -> ProviderResult<Option<StageCheckpoint>> {
+        Ok(self.stage_checkpoints.lock().get(&id).copied())
     }
 
     fn get_stage_checkpoint_progress(&self, _id: StageId) -> ProviderResult<Option<Vec<u8>>> {
@@ -592,7 +674,30 @@ impl StageCheckpointReader for MockEthProvider {
     }
 
     fn get_all_checkpoints(&self) -> ProviderResult<Vec<(String, StageCheckpoint)>> {
-        Ok(vec![])
+        Ok(self
+            .stage_checkpoints
+            .lock()
+            .iter()
+            .map(|(id, checkpoint)| (id.to_string(), *checkpoint))
+            .collect())
+    }
+}
+
+impl PruneCheckpointReader for MockEthProvider {
+    fn get_prune_checkpoint(
+        &self,
+        segment: PruneSegment,
+    ) -> ProviderResult<Option<PruneCheckpoint>> {
+        Ok(self.prune_checkpoints.lock().get(&segment).copied())
+    }
+
+    fn get_prune_checkpoints(&self) -> ProviderResult<Vec<(PruneSegment, PruneCheckpoint)>> {
+        Ok(self
+            .prune_checkpoints
+            .lock()
+            .iter()
+            .map(|(segment, checkpoint)| (*segment, *checkpoint))
+            .collect())
     }
 }
 
@@ -648,6 +753,16 @@ impl StorageRootProvider for MockEthProvider {
     ) -> ProviderResult<StorageMultiProof> {
         Ok(StorageMultiProof::empty())
     }
+
+    fn storage_range(
+        &self,
+        _address: Address,
+        _range: RangeInclusive<B256>,
+        _limit: usize,
+        _hashed_storage: HashedStorage,
+    ) -> ProviderResult<Vec<StorageEntry>> {
+        Ok(Vec::new())
+    }
 }
 
 impl StateProofProvider for MockEthProvider {
@@ -778,17 +893,38 @@ impl OmmersProvider for MockEthProvider {
 }
 
 impl BlockBodyIndicesProvider for MockEthProvider {
-    fn block_body_indices(&self, _num: u64) -> ProviderResult<Option<StoredBlockBodyIndices>> {
-        Ok(None)
+    fn block_body_indices(&self, num: u64) -> ProviderResult<Option<StoredBlockBodyIndices>> {
+        let lock = self.blocks.lock();
+        let Some(block) = lock.values().find(|block| block.number == num) else { return Ok(None) };
+        let first_tx_num = lock
+            .values()
+            .filter(|block| block.number < num)
+            .map(|block| block.body.transactions.len() as u64)
+            .sum();
+
+        Ok(Some(StoredBlockBodyIndices {
+            first_tx_num,
+            tx_count: block.body.transactions.len() as u64,
+        }))
     }
 }
 
 impl ChangeSetReader for MockEthProvider {
     fn account_block_changeset(
         &self,
-        _block_number: BlockNumber,
+        block_number: BlockNumber,
     ) -> ProviderResult<Vec<AccountBeforeTx>> {
-        Ok(Vec::default())
+        let account_history_pruned = self
+            .prune_checkpoints
+            .lock()
+            .get(&PruneSegment::AccountHistory)
+            .and_then(|checkpoint| checkpoint.block_number)
+            .is_some_and(|checkpoint| block_number <= checkpoint);
+        if account_history_pruned {
+            return Err(ProviderError::StateAtBlockPruned(block_number))
+        }
+
+        Ok(self.account_changesets.lock().get(&block_number).cloned().unwrap_or_default())
     }
 }
 
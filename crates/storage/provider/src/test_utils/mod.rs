@@ -16,9 +16,11 @@ use reth_trie_db::DatabaseStateRoot;
 use std::sync::Arc;
 
 pub mod blocks;
+mod datadir;
 mod mock;
 mod noop;
 
+pub use datadir::{TestChainParams, TestDatadir};
 pub use mock::{ExtendedAccount, MockEthProvider};
 pub use noop::NoopProvider;
 pub use reth_chain_state::test_utils::TestCanonStateSubscriptions;
@@ -1,3 +1,5 @@
+use crate::BlockIdReader;
+use alloy_eips::BlockId;
 use alloy_primitives::{Address, BlockNumber};
 use auto_impl::auto_impl;
 use reth_db_models::AccountBeforeTx;
@@ -53,4 +55,47 @@ pub trait ChangeSetReader: Send + Sync {
         &self,
         block_number: BlockNumber,
     ) -> ProviderResult<Vec<AccountBeforeTx>>;
+
+    /// Returns the account changesets in the inclusive block range, with the state of each
+    /// account from before the block that changed it.
+    ///
+    /// Entries are ordered by block number and address.
+    ///
+    /// Returns
+    /// [`ProviderError::ChangeSetsPruned`](reth_storage_errors::provider::ProviderError::ChangeSetsPruned)
+    /// if the changesets at the start of the range are pruned.
+    ///
+    /// The default implementation reads the changesets of every block in the range with
+    /// [`Self::account_block_changeset`].
+    fn account_changesets_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<impl Iterator<Item = ProviderResult<(BlockNumber, AccountBeforeTx)>> + '_>
+    {
+        Ok(range.flat_map(move |block_number| match self.account_block_changeset(block_number) {
+            Ok(changeset) => {
+                changeset.into_iter().map(|account| Ok((block_number, account))).collect()
+            }
+            Err(err) => vec![Err(err)],
+        }))
+    }
 }
+
+/// Trait extension for [`ChangeSetReader`], for types that also implement [`BlockIdReader`].
+pub trait ChangeSetReaderIdExt: ChangeSetReader + BlockIdReader {
+    /// Returns the number of the block with the given id, alongside the state of the accounts it
+    /// changed from before the block.
+    ///
+    /// Returns `None` if the block is not found, and
+    /// [`ProviderError::StateAtBlockPruned`](reth_storage_errors::provider::ProviderError::StateAtBlockPruned)
+    /// if the changesets of the block are pruned.
+    fn account_block_changeset_by_id(
+        &self,
+        id: BlockId,
+    ) -> ProviderResult<Option<(BlockNumber, Vec<AccountBeforeTx>)>> {
+        let Some(block_number) = self.block_number_for_id(id)? else { return Ok(None) };
+        Ok(Some((block_number, self.account_block_changeset(block_number)?)))
+    }
+}
+
+impl<T: ChangeSetReader + BlockIdReader> ChangeSetReaderIdExt for T {}
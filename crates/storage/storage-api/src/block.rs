@@ -4,6 +4,7 @@ use crate::{
 };
 use alloy_eips::{BlockHashOrNumber, BlockId, BlockNumberOrTag};
 use alloy_primitives::{BlockNumber, B256};
+use reth_db_models::StoredForkchoiceState;
 use reth_primitives::{BlockWithSenders, SealedBlockFor, SealedBlockWithSenders, SealedHeader};
 use reth_storage_errors::provider::ProviderResult;
 use std::ops::RangeInclusive;
@@ -417,6 +418,10 @@ pub trait ChainStateBlockReader: Send + Sync {
     ///
     /// If no safe block has been written yet, this returns `None`.
     fn last_safe_block_number(&self) -> ProviderResult<Option<BlockNumber>>;
+    /// Returns the last valid forkchoice state.
+    ///
+    /// If no forkchoice state has been written yet, this returns `None`.
+    fn last_forkchoice_state(&self) -> ProviderResult<Option<StoredForkchoiceState>>;
 }
 
 /// Functionality to write the last known chain blocks to the database.
@@ -426,4 +431,7 @@ pub trait ChainStateBlockWriter: Send + Sync {
 
     /// Saves the given safe block number in the DB.
     fn save_safe_block_number(&self, block_number: BlockNumber) -> ProviderResult<()>;
+
+    /// Saves the given forkchoice state in the DB.
+    fn save_forkchoice_state(&self, state: StoredForkchoiceState) -> ProviderResult<()>;
 }
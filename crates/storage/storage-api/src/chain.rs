@@ -90,7 +90,7 @@ impl<T> Default for EthStorage<T> {
 
 impl<Provider, T> BlockBodyWriter<Provider, reth_primitives::BlockBody<T>> for EthStorage<T>
 where
-    Provider: DBProvider<Tx: DbTxMut>,
+    Provider: DBProvider<Tx: DbTxMut> + ChainSpecProvider<ChainSpec: EthereumHardforks>,
     T: SignedTransaction,
 {
     fn write_block_bodies(
@@ -99,6 +99,8 @@ where
         bodies: Vec<(u64, Option<reth_primitives::BlockBody<T>>)>,
         _write_to: StorageLocation,
     ) -> ProviderResult<()> {
+        let chain_spec = provider.chain_spec();
+
         let mut ommers_cursor = provider.tx_ref().cursor_write::<tables::BlockOmmers>()?;
         let mut withdrawals_cursor =
             provider.tx_ref().cursor_write::<tables::BlockWithdrawals>()?;
@@ -106,8 +108,8 @@ where
         for (block_number, body) in bodies {
             let Some(body) = body else { continue };
 
-            // Write ommers if any
-            if !body.ommers.is_empty() {
+            // Write ommers if any, blocks past the merge never have them
+            if chain_spec.has_ommers(block_number) && !body.ommers.is_empty() {
                 ommers_cursor.append(block_number, StoredBlockOmmers { ommers: body.ommers })?;
             }
 
@@ -168,10 +170,10 @@ where
             } else {
                 None
             };
-            let ommers = if chain_spec.final_paris_total_difficulty(header.number).is_some() {
-                Vec::new()
-            } else {
+            let ommers = if chain_spec.has_ommers(header.number) {
                 ommers_cursor.seek_exact(header.number)?.map(|(_, o)| o.ommers).unwrap_or_default()
+            } else {
+                Vec::new()
             };
 
             bodies.push(reth_primitives::BlockBody { transactions, ommers, withdrawals });
@@ -7,6 +7,15 @@ use std::ops::RangeBounds;
 /// A helper type alias to access [`HeaderProvider::Header`].
 pub type ProviderHeader<P> = <P as HeaderProvider>::Header;
 
+/// Which block [`HeaderProvider::block_by_timestamp`] looks for, relative to the timestamp.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlockTimestampDirection {
+    /// The last block with a timestamp at or before the timestamp.
+    Before,
+    /// The first block with a timestamp at or after the timestamp.
+    After,
+}
+
 /// Client trait for fetching `Header` related data.
 #[auto_impl::auto_impl(&, Arc)]
 pub trait HeaderProvider: Send + Sync {
@@ -43,6 +52,13 @@ pub trait HeaderProvider: Send + Sync {
         }
     }
 
+    /// Returns the number of the earliest block whose header is available.
+    ///
+    /// This is `0` unless the headers below it expired.
+    fn earliest_header_number(&self) -> ProviderResult<BlockNumber> {
+        Ok(0)
+    }
+
     /// Get total difficulty by block hash.
     fn header_td(&self, hash: &BlockHash) -> ProviderResult<Option<U256>>;
 
@@ -75,4 +91,57 @@ pub trait HeaderProvider: Send + Sync {
         range: impl RangeBounds<BlockNumber>,
         predicate: impl FnMut(&SealedHeader<Self::Header>) -> bool,
     ) -> ProviderResult<Vec<SealedHeader<Self::Header>>>;
+
+    /// Returns the number of the block at or around the timestamp, see
+    /// [`BlockTimestampDirection`].
+    ///
+    /// Returns `None` if the timestamp is before the genesis block when searching
+    /// [`BlockTimestampDirection::Before`], or after the latest block when searching
+    /// [`BlockTimestampDirection::After`]. Also returns `None` if the block might be one of the
+    /// blocks whose headers expired, see [`HeaderProvider::earliest_header_number`].
+    ///
+    /// This relies on timestamps not decreasing along the chain, and finds the block with a binary
+    /// search over the headers. Since the latest block is not known to this trait, the upper bound
+    /// of the search is found by probing exponentially growing block numbers first.
+    fn block_by_timestamp(
+        &self,
+        timestamp: u64,
+        direction: BlockTimestampDirection,
+    ) -> ProviderResult<Option<BlockNumber>> {
+        // whether the block is past the searched block, blocks after the latest one always are
+        let is_past = |number: BlockNumber| -> ProviderResult<bool> {
+            Ok(self.header_by_number(number)?.is_none_or(|header| match direction {
+                BlockTimestampDirection::Before => header.timestamp() > timestamp,
+                BlockTimestampDirection::After => header.timestamp() >= timestamp,
+            }))
+        };
+
+        // find a range `low..=high` that contains the first block past the searched one, starting
+        // from the earliest available header
+        let earliest = self.earliest_header_number()?;
+        let (mut low, mut high) = (earliest, earliest);
+        while !is_past(high)? {
+            low = high + 1;
+            high = earliest.saturating_add((high - earliest).saturating_mul(2).max(1));
+        }
+
+        // find the first block past the searched one
+        while low < high {
+            let mid = low + (high - low) / 2;
+            if is_past(mid)? {
+                high = mid;
+            } else {
+                low = mid + 1;
+            }
+        }
+
+        // if the earliest available header is past the searched block, the block may have expired
+        if low == earliest && earliest > 0 {
+            return Ok(None)
+        }
+        Ok(match direction {
+            BlockTimestampDirection::Before => low.checked_sub(1),
+            BlockTimestampDirection::After => self.header_by_number(low)?.is_some().then_some(low),
+        })
+    }
 }
@@ -53,5 +53,22 @@ pub trait HistoryWriter: Send + Sync {
     ) -> ProviderResult<()>;
 
     /// Read account/storage changesets and update account/storage history indices.
+    ///
+    /// This also extends the log address index if it is maintained.
     fn update_history_indices(&self, range: RangeInclusive<BlockNumber>) -> ProviderResult<()>;
+
+    /// Insert log address index to database. Used inside IndexLogAddresses stage
+    fn insert_log_address_index(
+        &self,
+        index_updates: impl IntoIterator<Item = (Address, impl IntoIterator<Item = u64>)>,
+    ) -> ProviderResult<()>;
+
+    /// Unwind and clear the log address index in a given block range, reading the addresses from
+    /// the receipts of the range.
+    ///
+    /// Returns number of addresses unwound.
+    fn unwind_log_address_index_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<usize>;
 }
@@ -39,7 +39,7 @@ pub trait BlockchainTreePendingStateProvider: Send + Sync {
 /// * [`ExecutionOutcome`] - Captures all account and storage changes in the pending chain.
 /// * Block hashes - Provides access to the block hashes of both the pending chain and canonical
 ///   blocks.
-#[auto_impl(&, Box)]
+#[auto_impl(&, Box, Arc)]
 pub trait ExecutionDataProvider: Send + Sync {
     /// Return the execution outcome.
     fn execution_outcome(&self) -> &ExecutionOutcome;
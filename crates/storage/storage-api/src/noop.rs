@@ -21,7 +21,7 @@ use reth_db_models::{AccountBeforeTx, StoredBlockBodyIndices};
 use reth_primitives::{
     BlockWithSenders, EthPrimitives, SealedBlockFor, SealedBlockWithSenders, TransactionMeta,
 };
-use reth_primitives_traits::{Account, Bytecode, NodePrimitives, SealedHeader};
+use reth_primitives_traits::{Account, Bytecode, NodePrimitives, SealedHeader, StorageEntry};
 use reth_prune_types::{PruneCheckpoint, PruneSegment};
 use reth_stages_types::{StageCheckpoint, StageId};
 use reth_storage_errors::provider::{ProviderError, ProviderResult};
@@ -421,6 +421,16 @@ impl<C: Send + Sync, N: NodePrimitives> StorageRootProvider for NoopProvider<C,
     ) -> ProviderResult<reth_trie::StorageMultiProof> {
         Ok(reth_trie::StorageMultiProof::empty())
     }
+
+    fn storage_range(
+        &self,
+        _address: Address,
+        _range: RangeInclusive<B256>,
+        _limit: usize,
+        _hashed_storage: HashedStorage,
+    ) -> ProviderResult<Vec<StorageEntry>> {
+        Ok(Vec::new())
+    }
 }
 
 impl<C: Send + Sync, N: NodePrimitives> StateProofProvider for NoopProvider<C, N> {
@@ -1,5 +1,5 @@
 use reth_prune_types::{PruneCheckpoint, PruneSegment};
-use reth_storage_errors::provider::ProviderResult;
+use reth_storage_errors::provider::{ProviderError, ProviderResult};
 
 /// The trait for fetching prune checkpoint related data.
 #[auto_impl::auto_impl(&, Arc)]
@@ -12,6 +12,20 @@ pub trait PruneCheckpointReader: Send + Sync {
 
     /// Fetch all the prune checkpoints.
     fn get_prune_checkpoints(&self) -> ProviderResult<Vec<(PruneSegment, PruneCheckpoint)>>;
+
+    /// Fetch the prune checkpoint for the custom segment with the given name.
+    ///
+    /// Returns `None` by default, for providers that don't store custom segments.
+    fn get_custom_prune_checkpoint(&self, _name: &str) -> ProviderResult<Option<PruneCheckpoint>> {
+        Ok(None)
+    }
+
+    /// Fetch the prune checkpoints of all custom segments, by the name of the segment.
+    ///
+    /// Returns no checkpoints by default, for providers that don't store custom segments.
+    fn get_custom_prune_checkpoints(&self) -> ProviderResult<Vec<(String, PruneCheckpoint)>> {
+        Ok(Vec::new())
+    }
 }
 
 /// The trait for updating prune checkpoint related data.
@@ -23,4 +37,16 @@ pub trait PruneCheckpointWriter: Send + Sync {
         segment: PruneSegment,
         checkpoint: PruneCheckpoint,
     ) -> ProviderResult<()>;
+
+    /// Save prune checkpoint of the custom segment with the given name.
+    ///
+    /// Fails with [`ProviderError::UnsupportedProvider`] by default, for providers that don't store
+    /// custom segments.
+    fn save_custom_prune_checkpoint(
+        &self,
+        _name: &str,
+        _checkpoint: PruneCheckpoint,
+    ) -> ProviderResult<()> {
+        Err(ProviderError::UnsupportedProvider)
+    }
 }
@@ -1,9 +1,9 @@
 use crate::BlockIdReader;
 use alloy_eips::{BlockHashOrNumber, BlockId, BlockNumberOrTag};
-use alloy_primitives::{TxHash, TxNumber};
+use alloy_primitives::{Address, BlockNumber, TxHash, TxNumber};
 use reth_primitives_traits::Receipt;
 use reth_storage_errors::provider::ProviderResult;
-use std::ops::RangeBounds;
+use std::ops::{RangeBounds, RangeInclusive};
 
 /// A helper type alias to access [`ReceiptProvider::Receipt`].
 pub type ProviderReceipt<P> = <P as ReceiptProvider>::Receipt;
@@ -37,6 +37,28 @@ pub trait ReceiptProvider: Send + Sync {
         &self,
         range: impl RangeBounds<TxNumber>,
     ) -> ProviderResult<Vec<Self::Receipt>>;
+
+    /// Looks up the blocks in the given range in which any of the given addresses emitted logs
+    /// in the optional log address index.
+    ///
+    /// Returns `None` if the index is not maintained or doesn't cover the start of the range.
+    fn log_address_blocks(
+        &self,
+        _addresses: &[Address],
+        _range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Option<IndexedLogBlocks>> {
+        Ok(None)
+    }
+}
+
+/// Blocks found in the log address index, see [`ReceiptProvider::log_address_blocks`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct IndexedLogBlocks {
+    /// The blocks in which any of the addresses emitted logs, in ascending order.
+    pub blocks: Vec<BlockNumber>,
+    /// The highest block covered by the index. Blocks of the range above it must be checked
+    /// without the index.
+    pub indexed_to: BlockNumber,
 }
 
 /// Trait extension for `ReceiptProvider`, for types that implement `BlockId` conversion.
@@ -40,6 +40,33 @@ pub trait StorageChangeSetReader: Send + Sync {
         &self,
         block_number: BlockNumber,
     ) -> ProviderResult<Vec<(BlockNumberAddress, StorageEntry)>>;
+
+    /// Returns the storage changesets of the given address in the inclusive block range, with the
+    /// value of each slot from before the block that changed it.
+    ///
+    /// Entries are ordered by block number and storage key.
+    ///
+    /// Returns
+    /// [`ProviderError::ChangeSetsPruned`](reth_storage_errors::provider::ProviderError::ChangeSetsPruned)
+    /// if the changesets at the start of the range are pruned.
+    ///
+    /// The default implementation reads the changesets of every block in the range with
+    /// [`Self::storage_changeset`].
+    fn storage_changesets_by_address_range(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<impl Iterator<Item = ProviderResult<(BlockNumberAddress, StorageEntry)>> + '_>
+    {
+        Ok(range.flat_map(move |block_number| match self.storage_changeset(block_number) {
+            Ok(changeset) => changeset
+                .into_iter()
+                .filter(|(block_address, _)| block_address.address() == address)
+                .map(Ok)
+                .collect(),
+            Err(err) => vec![Err(err)],
+        }))
+    }
 }
 
 /// An enum that represents the storage location for a piece of data.
@@ -1,10 +1,16 @@
-use alloy_primitives::{map::B256HashMap, Address, Bytes, B256};
+use alloy_primitives::{
+    keccak256,
+    map::{AddressHashMap, B256HashMap},
+    Address, Bytes, B256,
+};
+use reth_primitives_traits::StorageEntry;
 use reth_storage_errors::provider::ProviderResult;
 use reth_trie::{
     updates::{StorageTrieUpdates, TrieUpdates},
     AccountProof, HashedPostState, HashedStorage, MultiProof, MultiProofTargets, StorageMultiProof,
     StorageProof, TrieInput,
 };
+use std::ops::RangeInclusive;
 
 /// A type that can compute the state root of a given post state.
 #[auto_impl::auto_impl(&, Box, Arc)]
@@ -62,6 +68,19 @@ pub trait StorageRootProvider: Send + Sync {
         slots: &[B256],
         hashed_storage: HashedStorage,
     ) -> ProviderResult<StorageMultiProof>;
+
+    /// Returns up to `limit` non-zero storage slots of the target address whose hashed slot lies
+    /// within `range`, ordered by hashed slot, with the `HashedStorage` applied on top of the
+    /// current state.
+    ///
+    /// The keys of the returned entries are hashed slots.
+    fn storage_range(
+        &self,
+        address: Address,
+        range: RangeInclusive<B256>,
+        limit: usize,
+        hashed_storage: HashedStorage,
+    ) -> ProviderResult<Vec<StorageEntry>>;
 }
 
 /// A type that can generate state proof on top of a given post state.
@@ -84,6 +103,23 @@ pub trait StateProofProvider: Send + Sync {
         targets: MultiProofTargets,
     ) -> ProviderResult<MultiProof>;
 
+    /// Generate [`MultiProof`] for target accounts and their storage slots.
+    ///
+    /// Unlike requesting a [`StateProofProvider::proof`] per account, the account trie is walked
+    /// once for all targets and every storage trie once for all of its slots, so nodes shared
+    /// between the proofs are only collected once.
+    fn address_multiproof(
+        &self,
+        input: TrieInput,
+        targets: &AddressHashMap<Vec<B256>>,
+    ) -> ProviderResult<MultiProof> {
+        let targets = targets
+            .iter()
+            .map(|(address, slots)| (keccak256(address), slots.iter().map(keccak256).collect()))
+            .collect();
+        self.multiproof(input, targets)
+    }
+
     /// Get trie witness for provided state.
     fn witness(
         &self,
@@ -21,15 +21,18 @@ use futures_util::{
     future::{select, BoxFuture},
     Future, FutureExt, TryFutureExt,
 };
+#[cfg(feature = "rayon")]
+use std::sync::OnceLock;
 use std::{
     any::Any,
     fmt::{Display, Formatter},
     pin::{pin, Pin},
     sync::{
         atomic::{AtomicUsize, Ordering},
-        Arc,
+        Arc, Mutex,
     },
     task::{ready, Context, Poll},
+    time::Duration,
 };
 use tokio::{
     runtime::Handle,
@@ -102,6 +105,14 @@ pub trait TaskSpawner: Send + Sync + Unpin + std::fmt::Debug + DynClone {
         name: &'static str,
         fut: BoxFuture<'static, ()>,
     ) -> JoinHandle<()>;
+
+    /// Returns the pool for compute heavy blocking tasks, if the spawner has one.
+    ///
+    /// See also [`TaskExecutor::compute_pool`].
+    #[cfg(feature = "rayon")]
+    fn compute_pool(&self) -> Option<pool::BlockingTaskPool> {
+        None
+    }
 }
 
 dyn_clone::clone_trait_object!(TaskSpawner);
@@ -163,14 +174,17 @@ pub struct TaskManager {
     panicked_tasks_tx: UnboundedSender<PanickedTaskError>,
     /// Listens for panicked tasks
     panicked_tasks_rx: UnboundedReceiver<PanickedTaskError>,
-    /// The [Signal] to fire when all tasks should be shutdown.
+    /// The [Signal] to fire when all tasks should be shutdown, shared with the executors.
     ///
     /// This is fired when dropped.
-    signal: Option<Signal>,
+    signal: Arc<Mutex<Option<Signal>>>,
     /// Receiver of the shutdown signal.
     on_shutdown: Shutdown,
     /// How many [`GracefulShutdown`] tasks are currently active
     graceful_tasks: Arc<AtomicUsize>,
+    /// Pool for compute heavy blocking tasks, shared with the executors.
+    #[cfg(feature = "rayon")]
+    compute_pool: Arc<OnceLock<pool::BlockingTaskPool>>,
 }
 
 // === impl TaskManager ===
@@ -194,9 +208,11 @@ impl TaskManager {
             handle,
             panicked_tasks_tx,
             panicked_tasks_rx,
-            signal: Some(signal),
+            signal: Arc::new(Mutex::new(Some(signal))),
             on_shutdown,
             graceful_tasks: Arc::new(AtomicUsize::new(0)),
+            #[cfg(feature = "rayon")]
+            compute_pool: Default::default(),
         }
     }
 
@@ -209,6 +225,9 @@ impl TaskManager {
             panicked_tasks_tx: self.panicked_tasks_tx.clone(),
             metrics: Default::default(),
             graceful_tasks: Arc::clone(&self.graceful_tasks),
+            signal: Arc::clone(&self.signal),
+            #[cfg(feature = "rayon")]
+            compute_pool: Arc::clone(&self.compute_pool),
         }
     }
 
@@ -225,7 +244,7 @@ impl TaskManager {
     }
 
     fn do_graceful_shutdown(self, timeout: Option<std::time::Duration>) -> bool {
-        drop(self.signal);
+        fire_signal(&self.signal);
         let when = timeout.map(|t| std::time::Instant::now() + t);
         while self.graceful_tasks.load(Ordering::Relaxed) > 0 {
             if when.map(|when| std::time::Instant::now() > when).unwrap_or(false) {
@@ -240,6 +259,20 @@ impl TaskManager {
     }
 }
 
+impl Drop for TaskManager {
+    fn drop(&mut self) {
+        fire_signal(&self.signal);
+    }
+}
+
+/// Fires the shutdown signal, if it wasn't fired yet.
+fn fire_signal(signal: &Mutex<Option<Signal>>) {
+    let signal = signal.lock().unwrap_or_else(|err| err.into_inner()).take();
+    if let Some(signal) = signal {
+        signal.fire();
+    }
+}
+
 /// An endless future that resolves if a critical task panicked.
 ///
 /// See [`TaskExecutor::spawn_critical`]
@@ -299,6 +332,11 @@ pub struct TaskExecutor {
     metrics: TaskExecutorMetrics,
     /// How many [`GracefulShutdown`] tasks are currently active
     graceful_tasks: Arc<AtomicUsize>,
+    /// The shutdown [Signal] of the [`TaskManager`].
+    signal: Arc<Mutex<Option<Signal>>>,
+    /// Pool for compute heavy blocking tasks of the [`TaskManager`].
+    #[cfg(feature = "rayon")]
+    compute_pool: Arc<OnceLock<pool::BlockingTaskPool>>,
 }
 
 // === impl TaskExecutor ===
@@ -314,6 +352,22 @@ impl TaskExecutor {
         &self.on_shutdown
     }
 
+    /// Fires the shutdown signal of the [`TaskManager`] and returns a future that resolves once all
+    /// [`GracefulShutdown`] tasks are shutdown.
+    ///
+    /// This is the async counterpart of [`TaskManager::graceful_shutdown`] for callers that only
+    /// have access to the executor, e.g. a node that is embedded into another program.
+    pub fn initiate_graceful_shutdown(&self) -> impl Future<Output = ()> + Send + 'static {
+        fire_signal(&self.signal);
+        let graceful_tasks = Arc::clone(&self.graceful_tasks);
+        async move {
+            while graceful_tasks.load(Ordering::Relaxed) > 0 {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+            debug!("gracefully shut down");
+        }
+    }
+
     /// Spawns a future on the tokio runtime depending on the [`TaskKind`]
     fn spawn_on_rt<F>(&self, fut: F, task_kind: TaskKind) -> JoinHandle<()>
     where
@@ -563,6 +617,48 @@ impl TaskExecutor {
     }
 }
 
+#[cfg(feature = "rayon")]
+impl TaskExecutor {
+    /// Sets the pool that runs the tasks spawned with [`TaskExecutor::spawn_compute`], for all
+    /// executors of the [`TaskManager`].
+    ///
+    /// Returns the given pool as error if the compute pool was already set or used.
+    pub fn set_compute_pool(
+        &self,
+        pool: pool::BlockingTaskPool,
+    ) -> Result<(), pool::BlockingTaskPool> {
+        self.compute_pool.set(pool)
+    }
+
+    /// Returns the pool for compute heavy blocking tasks.
+    ///
+    /// The pool runs on its own threads, so saturating it doesn't delay tasks on the tokio
+    /// runtime. If no pool was set with [`TaskExecutor::set_compute_pool`], a
+    /// [`pool::BlockingTaskPool::build_compute`] pool with one thread per CPU is created.
+    pub fn compute_pool(&self) -> &pool::BlockingTaskPool {
+        self.compute_pool.get_or_init(|| {
+            pool::BlockingTaskPool::build_compute(0).expect("failed to build compute pool")
+        })
+    }
+
+    /// Spawns a compute heavy blocking function on the compute pool, like tracing or executing a
+    /// call.
+    ///
+    /// Returns an error instead of queueing the function if the pool is saturated.
+    ///
+    /// See also [`TaskExecutor::compute_pool`].
+    pub fn spawn_compute<F, R>(
+        &self,
+        func: F,
+    ) -> Result<pool::BlockingTaskHandle<R>, pool::BlockingTaskPoolSaturated>
+    where
+        F: FnOnce() -> R + Send + 'static,
+        R: Send + 'static,
+    {
+        self.compute_pool().try_spawn(func)
+    }
+}
+
 impl TaskSpawner for TaskExecutor {
     fn spawn(&self, fut: BoxFuture<'static, ()>) -> JoinHandle<()> {
         self.metrics.inc_regular_tasks();
@@ -585,6 +681,11 @@ impl TaskSpawner for TaskExecutor {
     ) -> JoinHandle<()> {
         Self::spawn_critical_blocking(self, name, fut)
     }
+
+    #[cfg(feature = "rayon")]
+    fn compute_pool(&self) -> Option<pool::BlockingTaskPool> {
+        Some(Self::compute_pool(self).clone())
+    }
 }
 
 /// `TaskSpawner` with extended behaviour
@@ -679,6 +780,69 @@ mod tests {
         })
     }
 
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_critical_unaffected_by_saturated_compute_pool() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let manager = TaskManager::new(runtime.handle().clone());
+        let executor = manager.executor();
+        let threads = std::thread::available_parallelism().map_or(1, |n| n.get());
+        let compute_pool = pool::BlockingTaskPool::new_bounded(
+            pool::BlockingTaskPool::builder().num_threads(threads).build().unwrap(),
+            "compute",
+            threads,
+        );
+        executor.set_compute_pool(compute_pool).unwrap();
+
+        // keep every compute thread busy spinning and fill the queue
+        let release = Arc::new(AtomicBool::new(false));
+        let mut compute_tasks = (0..threads)
+            .map(|_| {
+                let release = Arc::clone(&release);
+                executor
+                    .spawn_compute(move || {
+                        while !release.load(Ordering::Relaxed) {
+                            std::hint::spin_loop();
+                        }
+                    })
+                    .unwrap()
+            })
+            .collect::<Vec<_>>();
+        while executor.compute_pool().busy_threads() < threads {
+            std::thread::sleep(Duration::from_millis(1));
+        }
+        compute_tasks.extend((0..threads).map(|_| executor.spawn_compute(|| ()).unwrap()));
+        assert!(executor.compute_pool().is_saturated());
+        assert!(executor.spawn_compute(|| ()).is_err());
+
+        // critical tasks, blocking or not, still run while no compute task can make progress
+        let started = std::time::Instant::now();
+        let (tx, rx) = tokio::sync::oneshot::channel();
+        executor.spawn_critical("critical", async move {
+            let _ = tx.send(());
+        });
+        let (blocking_tx, blocking_rx) = tokio::sync::oneshot::channel();
+        executor.spawn_critical_blocking("critical blocking", async move {
+            let _ = blocking_tx.send((0..1_000u64).sum::<u64>());
+        });
+        runtime.block_on(async move {
+            rx.await.unwrap();
+            assert_eq!(blocking_rx.await.unwrap(), 499_500);
+        });
+        let latency = started.elapsed();
+        assert!(latency < Duration::from_secs(1), "critical tasks delayed by {latency:?}");
+        assert_eq!(executor.compute_pool().queued_tasks(), threads);
+        assert_eq!(executor.compute_pool().busy_threads(), threads);
+
+        release.store(true, Ordering::Relaxed);
+        runtime.block_on(async move {
+            for task in compute_tasks {
+                task.await.unwrap();
+            }
+        });
+        assert!(!executor.compute_pool().is_saturated());
+    }
+
     // Tests that spawned tasks are terminated if the `TaskManager` drops
     #[test]
     fn test_manager_shutdown_critical() {
@@ -763,6 +927,28 @@ mod tests {
         assert_eq!(counter.load(Ordering::Relaxed), num);
     }
 
+    #[test]
+    fn test_executor_graceful_shutdown() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let handle = runtime.handle().clone();
+        let manager = TaskManager::new(handle);
+        let executor = manager.executor();
+
+        let val = Arc::new(AtomicBool::new(false));
+        let c = val.clone();
+        executor.spawn_critical_with_graceful_shutdown_signal("grace", |shutdown| async move {
+            let _guard = shutdown.await;
+            tokio::time::sleep(Duration::from_millis(200)).await;
+            c.store(true, Ordering::Relaxed);
+        });
+
+        runtime.block_on(executor.initiate_graceful_shutdown());
+        assert!(val.load(Ordering::Relaxed));
+
+        // the signal was fired already
+        assert!(manager.graceful_shutdown_with_timeout(Duration::from_millis(100)));
+    }
+
     #[test]
     fn test_manager_graceful_shutdown_timeout() {
         let runtime = tokio::runtime::Runtime::new().unwrap();
@@ -2,6 +2,8 @@
 
 use core::fmt;
 
+#[cfg(feature = "rayon")]
+use reth_metrics::metrics::Gauge;
 use reth_metrics::{metrics::Counter, Metrics};
 
 /// Task Executor Metrics
@@ -30,6 +32,20 @@ impl TaskExecutorMetrics {
     }
 }
 
+/// Metrics of a [`BlockingTaskPool`](crate::pool::BlockingTaskPool), labeled by the name of the
+/// pool.
+#[cfg(feature = "rayon")]
+#[derive(Metrics, Clone)]
+#[metrics(scope = "executor.pool")]
+pub struct BlockingTaskPoolMetrics {
+    /// Number of spawned tasks that are waiting for a thread
+    pub(crate) queued_tasks: Gauge,
+    /// Number of threads that are running a task
+    pub(crate) busy_threads: Gauge,
+    /// Number of tasks rejected because the pool was saturated
+    pub(crate) rejected_tasks_total: Counter,
+}
+
 /// Helper type for increasing counters even if a task fails
 pub struct IncCounterOnDrop(Counter);
 
@@ -4,12 +4,21 @@ use std::{
     future::Future,
     panic::{catch_unwind, AssertUnwindSafe},
     pin::Pin,
-    sync::Arc,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
     task::{ready, Context, Poll},
     thread,
 };
 use tokio::sync::{oneshot, AcquireError, OwnedSemaphorePermit, Semaphore};
 
+use crate::metrics::BlockingTaskPoolMetrics;
+
+/// Number of tasks per thread that can wait for a thread of a [`BlockingTaskPool::build_compute`]
+/// pool before the pool rejects new tasks.
+pub const DEFAULT_QUEUED_TASKS_PER_THREAD: usize = 4;
+
 /// RPC Tracing call guard semaphore.
 ///
 /// This is used to restrict the number of concurrent RPC requests to tracing methods like
@@ -51,15 +60,45 @@ impl BlockingTaskGuard {
 /// See also [tokio-docs] for more information.
 ///
 /// [tokio-docs]: https://docs.rs/tokio/latest/tokio/index.html#cpu-bound-tasks-and-blocking-code
+///
+/// A pool can be bounded, in which case [`BlockingTaskPool::try_spawn`] rejects new tasks once the
+/// configured number of tasks is waiting for a thread, instead of growing the queue.
 #[derive(Clone, Debug)]
 pub struct BlockingTaskPool {
     pool: Arc<rayon::ThreadPool>,
+    state: Arc<PoolState>,
 }
 
 impl BlockingTaskPool {
-    /// Create a new `BlockingTaskPool` with the given threadpool.
+    /// Create a new unbounded `BlockingTaskPool` with the given threadpool.
+    ///
+    /// The pool has no name, so it doesn't record metrics, see [`BlockingTaskPool::new_named`].
     pub fn new(pool: rayon::ThreadPool) -> Self {
-        Self { pool: Arc::new(pool) }
+        Self::with_state(pool, PoolState::new(None, None))
+    }
+
+    /// Create a new unbounded `BlockingTaskPool` with the given threadpool.
+    ///
+    /// The name is used to label the metrics of the pool, so it should be unique.
+    pub fn new_named(pool: rayon::ThreadPool, name: &'static str) -> Self {
+        Self::with_state(pool, PoolState::new(Some(name), None))
+    }
+
+    /// Create a new `BlockingTaskPool` with the given threadpool that rejects tasks spawned with
+    /// [`BlockingTaskPool::try_spawn`] if `max_queued_tasks` tasks are already waiting for a
+    /// thread.
+    ///
+    /// The name is used to label the metrics of the pool, so it should be unique.
+    pub fn new_bounded(
+        pool: rayon::ThreadPool,
+        name: &'static str,
+        max_queued_tasks: usize,
+    ) -> Self {
+        Self::with_state(pool, PoolState::new(Some(name), Some(max_queued_tasks)))
+    }
+
+    fn with_state(pool: rayon::ThreadPool, state: PoolState) -> Self {
+        Self { pool: Arc::new(pool), state: Arc::new(state) }
     }
 
     /// Convenience function to start building a new threadpool.
@@ -75,6 +114,84 @@ impl BlockingTaskPool {
         Self::builder().build().map(Self::new)
     }
 
+    /// Builds a bounded pool for compute heavy tasks with the given number of threads, `0` uses
+    /// one thread per CPU.
+    ///
+    /// At most [`DEFAULT_QUEUED_TASKS_PER_THREAD`] tasks per thread can wait for a thread.
+    pub fn build_compute(threads: usize) -> Result<Self, rayon::ThreadPoolBuildError> {
+        let pool = Self::builder()
+            .num_threads(threads)
+            .thread_name(|idx| format!("reth-compute-{idx}"))
+            .build()?;
+        let max_queued_tasks = pool.current_num_threads() * DEFAULT_QUEUED_TASKS_PER_THREAD;
+        Ok(Self::new_bounded(pool, "compute", max_queued_tasks))
+    }
+
+    /// Returns the name of the pool, if it has one.
+    pub fn name(&self) -> Option<&'static str> {
+        self.state.name
+    }
+
+    /// Returns the number of threads of the pool.
+    pub fn num_threads(&self) -> usize {
+        self.pool.current_num_threads()
+    }
+
+    /// Returns the number of spawned tasks that are waiting for a thread.
+    pub fn queued_tasks(&self) -> usize {
+        self.state.queued.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of threads that are currently running a task.
+    pub fn busy_threads(&self) -> usize {
+        self.state.busy.load(Ordering::Relaxed)
+    }
+
+    /// Returns `true` if the pool is bounded and [`BlockingTaskPool::try_spawn`] would reject a
+    /// new task.
+    pub fn is_saturated(&self) -> bool {
+        self.state.max_queued.is_some_and(|max| self.queued_tasks() >= max)
+    }
+
+    /// Wraps the function so it delivers its result to the returned handle and keeps track of the
+    /// occupancy of the pool. The task must already be counted as queued.
+    fn task<F, R>(&self, func: F) -> (impl FnOnce() + Send + 'static, BlockingTaskHandle<R>)
+    where
+        F: FnOnce() -> R + Send + 'static,
+        R: Send + 'static,
+    {
+        let (tx, rx) = oneshot::channel();
+        let state = Arc::clone(&self.state);
+        let task = move || {
+            let _busy = state.start();
+            let _result = tx.send(catch_unwind(AssertUnwindSafe(func)));
+        };
+        (task, BlockingTaskHandle { rx })
+    }
+
+    /// Like [`BlockingTaskPool::spawn`], but returns an error instead of queueing the function if
+    /// the pool is saturated.
+    pub fn try_spawn<F, R>(
+        &self,
+        func: F,
+    ) -> Result<BlockingTaskHandle<R>, BlockingTaskPoolSaturated>
+    where
+        F: FnOnce() -> R + Send + 'static,
+        R: Send + 'static,
+    {
+        if !self.state.try_enqueue() {
+            if let Some(metrics) = &self.state.metrics {
+                metrics.rejected_tasks_total.increment(1);
+            }
+            // only bounded pools reject tasks, and they're always named
+            return Err(BlockingTaskPoolSaturated { name: self.state.name.unwrap_or_default() })
+        }
+
+        let (task, handle) = self.task(func);
+        self.pool.spawn(task);
+        Ok(handle)
+    }
+
     /// Asynchronous wrapper around Rayon's
     /// [`ThreadPool::spawn`](rayon::ThreadPool::spawn).
     ///
@@ -87,13 +204,10 @@ impl BlockingTaskPool {
         F: FnOnce() -> R + Send + 'static,
         R: Send + 'static,
     {
-        let (tx, rx) = oneshot::channel();
-
-        self.pool.spawn(move || {
-            let _result = tx.send(catch_unwind(AssertUnwindSafe(func)));
-        });
-
-        BlockingTaskHandle { rx }
+        self.state.enqueue();
+        let (task, handle) = self.task(func);
+        self.pool.spawn(task);
+        handle
     }
 
     /// Asynchronous wrapper around Rayon's
@@ -108,13 +222,87 @@ impl BlockingTaskPool {
         F: FnOnce() -> R + Send + 'static,
         R: Send + 'static,
     {
-        let (tx, rx) = oneshot::channel();
+        self.state.enqueue();
+        let (task, handle) = self.task(func);
+        self.pool.spawn_fifo(task);
+        handle
+    }
+}
 
-        self.pool.spawn_fifo(move || {
-            let _result = tx.send(catch_unwind(AssertUnwindSafe(func)));
-        });
+/// Occupancy of a [`BlockingTaskPool`], shared with its tasks.
+#[derive(Debug)]
+struct PoolState {
+    /// Name of the pool, if it has one.
+    name: Option<&'static str>,
+    /// Number of spawned tasks that are waiting for a thread.
+    queued: AtomicUsize,
+    /// Number of threads that are running a task.
+    busy: AtomicUsize,
+    /// Maximum number of queued tasks, unbounded if `None`.
+    max_queued: Option<usize>,
+    /// Metrics of the pool, only recorded if it has a name.
+    metrics: Option<BlockingTaskPoolMetrics>,
+}
+
+impl PoolState {
+    fn new(name: Option<&'static str>, max_queued: Option<usize>) -> Self {
+        Self {
+            name,
+            queued: AtomicUsize::new(0),
+            busy: AtomicUsize::new(0),
+            max_queued,
+            metrics: name.map(|name| BlockingTaskPoolMetrics::new_with_labels(&[("pool", name)])),
+        }
+    }
 
-        BlockingTaskHandle { rx }
+    /// Counts a new queued task, regardless of the bound.
+    fn enqueue(&self) {
+        self.queued.fetch_add(1, Ordering::Relaxed);
+        if let Some(metrics) = &self.metrics {
+            metrics.queued_tasks.increment(1.0);
+        }
+    }
+
+    /// Counts a new queued task if the pool isn't saturated, returns `false` otherwise.
+    fn try_enqueue(&self) -> bool {
+        let Some(max_queued) = self.max_queued else {
+            self.enqueue();
+            return true
+        };
+
+        let enqueued = self
+            .queued
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |queued| {
+                (queued < max_queued).then_some(queued + 1)
+            })
+            .is_ok();
+        if let Some(metrics) = self.metrics.as_ref().filter(|_| enqueued) {
+            metrics.queued_tasks.increment(1.0);
+        }
+        enqueued
+    }
+
+    /// Moves a queued task to a busy thread until the returned guard is dropped.
+    fn start(&self) -> BusyThreadGuard<'_> {
+        self.queued.fetch_sub(1, Ordering::Relaxed);
+        self.busy.fetch_add(1, Ordering::Relaxed);
+        if let Some(metrics) = &self.metrics {
+            metrics.queued_tasks.decrement(1.0);
+            metrics.busy_threads.increment(1.0);
+        }
+        BusyThreadGuard(self)
+    }
+}
+
+/// Marks a thread of the pool as idle again when dropped, even if the task panicked.
+struct BusyThreadGuard<'a>(&'a PoolState);
+
+impl Drop for BusyThreadGuard<'_> {
+    fn drop(&mut self) {
+        self.0.busy.fetch_sub(1, Ordering::Relaxed);
+        if let Some(metrics) = &self.0.metrics {
+            metrics.busy_threads.decrement(1.0);
+        }
     }
 }
 
@@ -150,6 +338,16 @@ impl<T> Future for BlockingTaskHandle<T> {
 #[non_exhaustive]
 pub struct TokioBlockingTaskError;
 
+/// An error returned when a task is rejected because too many tasks are already waiting for a
+/// thread of a bounded [`BlockingTaskPool`].
+#[derive(Debug, thiserror::Error)]
+#[error("{name} pool is saturated")]
+#[non_exhaustive]
+pub struct BlockingTaskPoolSaturated {
+    /// Name of the saturated pool.
+    pub name: &'static str,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -170,5 +368,34 @@ mod tests {
         });
         let res = res.await;
         assert!(res.is_err());
+        assert_eq!(pool.busy_threads(), 0);
+    }
+
+    #[tokio::test]
+    async fn bounded_pool_rejects_when_saturated() {
+        let pool = BlockingTaskPool::new_bounded(
+            BlockingTaskPool::builder().num_threads(1).build().unwrap(),
+            "test",
+            1,
+        );
+        let (release_tx, release_rx) = std::sync::mpsc::channel::<()>();
+
+        // occupy the only thread
+        let running = pool.try_spawn(move || release_rx.recv().unwrap()).unwrap();
+        while pool.busy_threads() == 0 {
+            tokio::task::yield_now().await;
+        }
+
+        // one task fits into the queue, the next one is rejected
+        let queued = pool.try_spawn(|| 1).unwrap();
+        assert_eq!(pool.queued_tasks(), 1);
+        assert!(pool.is_saturated());
+        assert!(pool.try_spawn(|| 2).is_err());
+
+        release_tx.send(()).unwrap();
+        running.await.unwrap();
+        assert_eq!(queued.await.unwrap(), 1);
+        assert!(!pool.is_saturated());
+        assert_eq!(pool.try_spawn(|| 3).unwrap().await.unwrap(), 3);
     }
 }
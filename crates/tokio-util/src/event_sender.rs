@@ -5,6 +5,11 @@ use tracing::trace;
 const DEFAULT_SIZE_BROADCAST_CHANNEL: usize = 2000;
 
 /// A bounded broadcast channel for a task.
+///
+/// Listeners don't keep the channel's events alive: once an [`EventStream`] is dropped, events it
+/// hasn't consumed yet are released and no new events are retained on its behalf. Components with
+/// a shorter lifetime than the sender can therefore hold a listener without having to unregister
+/// it on shutdown.
 #[derive(Debug, Clone)]
 pub struct EventSender<T> {
     /// The sender part of the broadcast channel
@@ -116,6 +121,33 @@ mod tests {
         assert_eq!(received_event2, Some("event6"));
     }
 
+    #[tokio::test]
+    async fn test_dropped_listener_does_not_retain_events() {
+        let sender = EventSender::default();
+        let mut listener1 = sender.new_listener();
+        let listener2 = sender.new_listener();
+
+        sender.notify("event7");
+        sender.notify("event8");
+        assert_eq!(sender.sender.len(), 2);
+
+        // events that were only left for the dropped listener are released
+        assert_eq!(listener1.next().await, Some("event7"));
+        assert_eq!(listener1.next().await, Some("event8"));
+        drop(listener2);
+        assert_eq!(sender.sender.len(), 0);
+
+        // events are only retained for the remaining listener
+        sender.notify("event9");
+        assert_eq!(sender.sender.len(), 1);
+        drop(listener1);
+        assert_eq!(sender.sender.len(), 0);
+
+        // nothing is buffered once all listeners are gone
+        sender.notify("event10");
+        assert_eq!(sender.sender.len(), 0);
+    }
+
     #[tokio::test]
     async fn test_event_listener_timeout() {
         let sender = EventSender::default();
@@ -15,3 +15,6 @@ pub use event_stream::EventStream;
 
 #[cfg(feature = "time")]
 pub mod ratelimit;
+
+#[cfg(feature = "retry")]
+pub mod retry;
@@ -0,0 +1,298 @@
+//! Retrying fallible async operations with a jittered exponential backoff.
+
+use rand::Rng;
+use std::{future::Future, pin::pin, time::Duration};
+use tracing::trace;
+
+/// How a fallible async operation is retried.
+///
+/// The delay before the `n`-th retry is `initial_backoff * 2^(n - 1)`, capped at `max_backoff`.
+/// With jitter, a uniformly random delay between half of it and all of it is used instead, so that
+/// clients that failed at the same time don't retry at the same time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Maximum number of attempts, including the first one.
+    max_attempts: u32,
+    /// Delay before the first retry.
+    initial_backoff: Duration,
+    /// Maximum delay between attempts.
+    max_backoff: Duration,
+    /// Timeout of a single attempt.
+    attempt_timeout: Option<Duration>,
+    /// Whether the delays are jittered.
+    jitter: bool,
+}
+
+impl RetryPolicy {
+    /// Creates a new [`RetryPolicy`] that makes up to `max_attempts` attempts, at least one, with a
+    /// jittered backoff starting at 100ms and capped at 10s, and no timeout per attempt.
+    pub const fn new(max_attempts: u32) -> Self {
+        Self {
+            max_attempts: if max_attempts == 0 { 1 } else { max_attempts },
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(10),
+            attempt_timeout: None,
+            jitter: true,
+        }
+    }
+
+    /// Sets the delay before the first retry.
+    pub const fn with_initial_backoff(mut self, initial_backoff: Duration) -> Self {
+        self.initial_backoff = initial_backoff;
+        self
+    }
+
+    /// Sets the maximum delay between attempts.
+    pub const fn with_max_backoff(mut self, max_backoff: Duration) -> Self {
+        self.max_backoff = max_backoff;
+        self
+    }
+
+    /// Sets the timeout of a single attempt, after which it's considered failed.
+    pub const fn with_attempt_timeout(mut self, attempt_timeout: Duration) -> Self {
+        self.attempt_timeout = Some(attempt_timeout);
+        self
+    }
+
+    /// Disables the jitter of the delays.
+    pub const fn without_jitter(mut self) -> Self {
+        self.jitter = false;
+        self
+    }
+
+    /// Returns the maximum number of attempts.
+    pub const fn max_attempts(&self) -> u32 {
+        self.max_attempts
+    }
+
+    /// Returns the delay after the given number of failed attempts.
+    pub fn backoff(&self, failed_attempts: u32) -> Duration {
+        let exponent = failed_attempts.saturating_sub(1).min(31);
+        let backoff = self.initial_backoff.saturating_mul(1 << exponent).min(self.max_backoff);
+        if self.jitter && !backoff.is_zero() {
+            rand::thread_rng().gen_range(backoff / 2..=backoff)
+        } else {
+            backoff
+        }
+    }
+
+    /// Runs the operation until it succeeds or all attempts failed.
+    ///
+    /// The operation is called with the number of the attempt, starting at 1.
+    pub async fn retry<F, Fut, T, E>(&self, op: F) -> Result<T, RetryError<E>>
+    where
+        F: FnMut(u32) -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+    {
+        self.retry_until(std::future::pending(), op).await
+    }
+
+    /// Runs the operation until it succeeds, all attempts failed, or `cancel` completes.
+    ///
+    /// Once `cancel` completes, the running attempt or the pending backoff is dropped and
+    /// [`RetryError::Cancelled`] is returned. The shutdown signal of the task executor can be
+    /// passed, so that retries stop promptly at shutdown.
+    ///
+    /// The operation is called with the number of the attempt, starting at 1.
+    pub async fn retry_until<C, F, Fut, T, E>(
+        &self,
+        cancel: C,
+        mut op: F,
+    ) -> Result<T, RetryError<E>>
+    where
+        C: Future<Output = ()>,
+        F: FnMut(u32) -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+    {
+        let mut cancel = pin!(cancel);
+        let mut attempt = 1;
+        loop {
+            let result = tokio::select! {
+                biased;
+                _ = &mut cancel => return Err(RetryError::Cancelled),
+                result = self.attempt(op(attempt)) => result,
+            };
+            let error = match result {
+                Ok(value) => return Ok(value),
+                Err(error) => error,
+            };
+            if attempt >= self.max_attempts {
+                return Err(RetryError::Exhausted { attempts: attempt, error })
+            }
+
+            let backoff = self.backoff(attempt);
+            trace!(target: "tokio_util::retry",
+                attempt,
+                ?backoff,
+                timed_out = error.is_timeout(),
+                "Attempt failed, retrying"
+            );
+            tokio::select! {
+                biased;
+                _ = &mut cancel => return Err(RetryError::Cancelled),
+                _ = tokio::time::sleep(backoff) => {}
+            }
+            attempt += 1;
+        }
+    }
+
+    /// Runs a single attempt, with the timeout if there's one.
+    async fn attempt<T, E>(
+        &self,
+        fut: impl Future<Output = Result<T, E>>,
+    ) -> Result<T, AttemptError<E>> {
+        match self.attempt_timeout {
+            Some(timeout) => match tokio::time::timeout(timeout, fut).await {
+                Ok(result) => result.map_err(AttemptError::Failed),
+                Err(_) => Err(AttemptError::TimedOut(timeout)),
+            },
+            None => fut.await.map_err(AttemptError::Failed),
+        }
+    }
+}
+
+/// Error of an operation run by a [`RetryPolicy`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RetryError<E> {
+    /// All attempts failed.
+    Exhausted {
+        /// Number of attempts that were made.
+        attempts: u32,
+        /// Error of the last attempt.
+        error: AttemptError<E>,
+    },
+    /// The retries were cancelled.
+    Cancelled,
+}
+
+/// Error of a single attempt of an operation run by a [`RetryPolicy`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AttemptError<E> {
+    /// The operation failed.
+    Failed(E),
+    /// The operation didn't complete within the timeout.
+    TimedOut(Duration),
+}
+
+impl<E> AttemptError<E> {
+    /// Returns `true` if the attempt timed out.
+    pub const fn is_timeout(&self) -> bool {
+        matches!(self, Self::TimedOut(_))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc,
+    };
+
+    /// A service that fails a number of times before it succeeds.
+    struct FlakyService {
+        failures: u32,
+        calls: AtomicU32,
+    }
+
+    impl FlakyService {
+        fn new(failures: u32) -> Arc<Self> {
+            Arc::new(Self { failures, calls: AtomicU32::new(0) })
+        }
+
+        async fn call(&self) -> Result<u32, &'static str> {
+            let calls = self.calls.fetch_add(1, Ordering::Relaxed) + 1;
+            if calls > self.failures {
+                Ok(calls)
+            } else {
+                Err("unavailable")
+            }
+        }
+    }
+
+    #[test]
+    fn exponential_backoff() {
+        let policy = RetryPolicy::new(10)
+            .with_initial_backoff(Duration::from_millis(100))
+            .with_max_backoff(Duration::from_secs(1));
+        assert_eq!(policy.without_jitter().backoff(1), Duration::from_millis(100));
+        assert_eq!(policy.without_jitter().backoff(3), Duration::from_millis(400));
+        assert_eq!(policy.without_jitter().backoff(5), Duration::from_secs(1));
+        assert_eq!(policy.without_jitter().backoff(u32::MAX), Duration::from_secs(1));
+
+        for _ in 0..100 {
+            let backoff = policy.backoff(2);
+            assert!((Duration::from_millis(100)..=Duration::from_millis(200)).contains(&backoff));
+        }
+
+        assert_eq!(RetryPolicy::new(0).max_attempts(), 1);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn eventual_success() {
+        let service = FlakyService::new(2);
+        let result = RetryPolicy::new(3).retry(|_| service.call()).await;
+        assert_eq!(result, Ok(3));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn exhaustion() {
+        let service = FlakyService::new(5);
+        let result = RetryPolicy::new(3).retry(|_| service.call()).await;
+        assert_eq!(
+            result,
+            Err(RetryError::Exhausted { attempts: 3, error: AttemptError::Failed("unavailable") })
+        );
+        assert_eq!(service.calls.load(Ordering::Relaxed), 3);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn attempt_timeout() {
+        let timeout = Duration::from_secs(1);
+        let policy = RetryPolicy::new(2).with_attempt_timeout(timeout);
+        let result = policy
+            .retry(|attempt| async move {
+                if attempt == 1 {
+                    tokio::time::sleep(timeout * 2).await;
+                }
+                Ok::<_, ()>(attempt)
+            })
+            .await;
+        assert_eq!(result, Ok(2));
+
+        let result = policy.retry(|_| std::future::pending::<Result<(), ()>>()).await;
+        assert_eq!(
+            result,
+            Err(RetryError::Exhausted { attempts: 2, error: AttemptError::TimedOut(timeout) })
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn cancellation_mid_backoff() {
+        let service = FlakyService::new(u32::MAX);
+        let policy = RetryPolicy::new(10).with_initial_backoff(Duration::from_secs(60));
+        let (tx, rx) = tokio::sync::oneshot::channel::<()>();
+
+        let retry = tokio::spawn({
+            let service = service.clone();
+            async move {
+                policy
+                    .retry_until(
+                        async move {
+                            let _ = rx.await;
+                        },
+                        |_| service.call(),
+                    )
+                    .await
+            }
+        });
+
+        // the first attempt failed, and the retry is backing off
+        tokio::time::sleep(Duration::from_secs(1)).await;
+        assert_eq!(service.calls.load(Ordering::Relaxed), 1);
+
+        tx.send(()).unwrap();
+        assert_eq!(retry.await.unwrap(), Err(RetryError::Cancelled));
+        assert_eq!(service.calls.load(Ordering::Relaxed), 1);
+    }
+}
@@ -2,7 +2,7 @@ use std::path::{Path, PathBuf};
 
 use rolling_file::{RollingConditionBasic, RollingFileAppender};
 use tracing_appender::non_blocking::WorkerGuard;
-use tracing_subscriber::{filter::Directive, EnvFilter, Layer, Registry};
+use tracing_subscriber::{filter::Directive, reload, EnvFilter, Layer, Registry};
 
 use crate::formatter::LogFormat;
 
@@ -62,6 +62,8 @@ impl Layers {
 
     /// Adds a stdout layer with specified formatting and filtering.
     ///
+    /// The filter of the layer can be changed at runtime with the returned [`LogFilterHandle`].
+    ///
     /// # Type Parameters
     /// * `S` - The type of subscriber that will use these layers.
     ///
@@ -72,18 +74,19 @@ impl Layers {
     /// * `color` - Optional color configuration for the log messages.
     ///
     /// # Returns
-    /// An `eyre::Result<()>` indicating the success or failure of the operation.
+    /// An `eyre::Result<LogFilterHandle>` for the filter of the layer.
     pub(crate) fn stdout(
         &mut self,
         format: LogFormat,
         default_directive: Directive,
         filters: &str,
         color: Option<String>,
-    ) -> eyre::Result<()> {
-        let filter = build_env_filter(Some(default_directive), filters)?;
+    ) -> eyre::Result<LogFilterHandle> {
+        let filter = build_env_filter(Some(default_directive.clone()), filters)?;
+        let (filter, handle) = reload::Layer::new(filter);
         let layer = format.apply(filter, color, None);
         self.inner.push(layer.boxed());
-        Ok(())
+        Ok(LogFilterHandle { default_directive: Some(default_directive), inner: handle })
     }
 
     /// Adds a file logging layer to the layers collection.
@@ -109,6 +112,30 @@ impl Layers {
     }
 }
 
+/// A handle to change the filter directives of a log layer at runtime.
+#[derive(Debug, Clone)]
+pub struct LogFilterHandle {
+    default_directive: Option<Directive>,
+    inner: reload::Handle<EnvFilter, Registry>,
+}
+
+impl LogFilterHandle {
+    /// Replaces the filter directives of the layer, e.g. `info,engine::tree=trace`.
+    ///
+    /// The directives are parsed like the ones the layer was created with: they're applied on top
+    /// of the default directive of the layer and `RUST_LOG`.
+    pub fn set_filter(&self, directives: &str) -> eyre::Result<()> {
+        let filter = build_env_filter(self.default_directive.clone(), directives)?;
+        self.inner.reload(filter)?;
+        Ok(())
+    }
+
+    /// Returns the current filter directives of the layer.
+    pub fn filter(&self) -> eyre::Result<String> {
+        Ok(self.inner.with_current(|filter| filter.to_string())?)
+    }
+}
+
 /// Holds configuration information for file logging.
 ///
 /// Contains details about the log file's path, name, size, and rotation strategy.
@@ -183,3 +210,94 @@ fn build_env_filter(
             Ok(env_filter.add_directive(directive.parse()?))
         })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{
+        io,
+        sync::{Arc, Mutex},
+    };
+    use tracing::level_filters::LevelFilter;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    #[derive(Clone, Default)]
+    struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+    impl io::Write for SharedBuffer {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl SharedBuffer {
+        fn json_lines(&self) -> Vec<serde_json::Value> {
+            let buf = self.0.lock().unwrap();
+            buf.split(|b| *b == b'\n')
+                .filter(|line| !line.is_empty())
+                .map(|line| serde_json::from_slice(line).unwrap())
+                .collect()
+        }
+    }
+
+    #[test]
+    fn json_log_fields() {
+        let buffer = SharedBuffer::default();
+        let (writer, guard) = tracing_appender::non_blocking(buffer.clone());
+
+        let filter = build_env_filter(Some(LevelFilter::INFO.into()), "").unwrap();
+        let subscriber =
+            Registry::default().with(LogFormat::Json.apply(filter, None, Some(writer)));
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::info!(target: "reth::test", block = 1, "hello");
+        });
+        drop(guard);
+
+        let lines = buffer.json_lines();
+        assert_eq!(lines.len(), 1);
+        let line = &lines[0];
+        assert!(line["timestamp"].is_string());
+        assert_eq!(line["level"], "INFO");
+        assert_eq!(line["target"], "reth::test");
+        assert_eq!(line["fields"]["message"], "hello");
+        assert_eq!(line["fields"]["block"], 1);
+    }
+
+    #[test]
+    fn reload_filter() {
+        let buffer = SharedBuffer::default();
+        let (writer, guard) = tracing_appender::non_blocking(buffer.clone());
+
+        let default_directive: Directive = LevelFilter::INFO.into();
+        let filter = build_env_filter(Some(default_directive.clone()), "").unwrap();
+        let (filter, handle) = reload::Layer::new(filter);
+        let handle = LogFilterHandle { default_directive: Some(default_directive), inner: handle };
+
+        let subscriber =
+            Registry::default().with(LogFormat::Json.apply(filter, None, Some(writer)));
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::debug!(target: "engine::tree", "before");
+
+            handle.set_filter("engine::tree=debug").unwrap();
+            assert!(handle.filter().unwrap().contains("engine::tree=debug"));
+
+            tracing::debug!(target: "engine::tree", "after");
+            tracing::debug!(target: "reth::other", "other");
+            tracing::info!(target: "reth::other", "info");
+        });
+        drop(guard);
+
+        let messages = buffer
+            .json_lines()
+            .into_iter()
+            .map(|line| line["fields"]["message"].as_str().unwrap().to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(messages, ["after", "info"]);
+
+        assert!(handle.set_filter("engine::tree=loud").is_err());
+    }
+}
@@ -50,7 +50,7 @@ pub use tracing_subscriber;
 
 // Re-export our types
 pub use formatter::LogFormat;
-pub use layers::{FileInfo, FileWorkerGuard};
+pub use layers::{FileInfo, FileWorkerGuard, LogFilterHandle};
 pub use test_tracer::TestTracer;
 
 mod formatter;
@@ -58,6 +58,7 @@ mod layers;
 mod test_tracer;
 
 use crate::layers::Layers;
+use std::sync::OnceLock;
 use tracing::level_filters::LevelFilter;
 use tracing_appender::non_blocking::WorkerGuard;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
@@ -193,7 +194,7 @@ impl Tracer for RethTracer {
     fn init(self) -> eyre::Result<Option<WorkerGuard>> {
         let mut layers = Layers::new();
 
-        layers.stdout(
+        let stdout_filter = layers.stdout(
             self.stdout.format,
             self.stdout.default_directive.parse()?,
             &self.stdout.filters,
@@ -212,11 +213,24 @@ impl Tracer for RethTracer {
 
         // The error is returned if the global default subscriber is already set,
         // so it's safe to ignore it
-        let _ = tracing_subscriber::registry().with(layers.into_inner()).try_init();
+        if tracing_subscriber::registry().with(layers.into_inner()).try_init().is_ok() {
+            let _ = STDOUT_FILTER.set(stdout_filter);
+        }
         Ok(file_guard)
     }
 }
 
+/// The [`LogFilterHandle`] of the stdout layer of the global subscriber.
+static STDOUT_FILTER: OnceLock<LogFilterHandle> = OnceLock::new();
+
+/// Returns the [`LogFilterHandle`] of the stdout layer, if the global subscriber was installed by
+/// [`RethTracer::init`].
+///
+/// This can be used to change the verbosity of the stdout log at runtime.
+pub fn stdout_filter_handle() -> Option<&'static LogFilterHandle> {
+    STDOUT_FILTER.get()
+}
+
 ///  Initializes a tracing subscriber for tests.
 ///
 ///  The filter is configurable via `RUST_LOG`.
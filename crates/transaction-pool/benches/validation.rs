@@ -0,0 +1,127 @@
+#![allow(missing_docs)]
+//! Load test submitting transactions at a fixed rate, comparing the p99 submission latency of
+//! validating every transaction on its own to reading the state of the senders of concurrently
+//! validated transactions in batches.
+//!
+//! Run with `cargo bench -p reth-transaction-pool --features test-utils --bench validation`.
+
+use alloy_primitives::U256;
+use reth_chainspec::MAINNET;
+use reth_provider::test_utils::{ExtendedAccount, MockEthProvider};
+use reth_transaction_pool::{
+    blobstore::InMemoryBlobStore,
+    test_utils::MockTransaction,
+    validate::{EthTransactionValidatorBuilder, ValidationTask},
+    EthTransactionValidator, PoolTransaction, TransactionOrigin, TransactionValidationOutcome,
+    TransactionValidationTaskExecutor, TransactionValidator,
+};
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use tokio::{runtime::Runtime, sync::Mutex};
+
+/// Rate at which transactions are submitted.
+const TRANSACTIONS_PER_SECOND: u32 = 500;
+
+/// Duration of a run, for each validator.
+const RUN_DURATION: Duration = Duration::from_secs(10);
+
+/// Validator that validates every transaction on its own, reading the state of its sender with a
+/// separate state provider.
+#[derive(Debug, Clone)]
+struct UnbatchedValidator(EthTransactionValidator<MockEthProvider, MockTransaction>);
+
+impl TransactionValidator for UnbatchedValidator {
+    type Transaction = MockTransaction;
+
+    async fn validate_transaction(
+        &self,
+        origin: TransactionOrigin,
+        transaction: Self::Transaction,
+    ) -> TransactionValidationOutcome<Self::Transaction> {
+        self.0.validate_one(origin, transaction)
+    }
+}
+
+/// Returns the executor of the given validator, with its validation task spawned on the runtime.
+fn validation_task_executor<V>(rt: &Runtime, validator: V) -> TransactionValidationTaskExecutor<V> {
+    let (tx, task) = ValidationTask::new();
+    rt.spawn(task.run());
+    TransactionValidationTaskExecutor { validator, to_validation_task: Arc::new(Mutex::new(tx)) }
+}
+
+/// Submits the transactions at [`TRANSACTIONS_PER_SECOND`] and returns the sorted latencies of
+/// their submissions.
+fn submission_latencies<V>(
+    rt: &Runtime,
+    validator: &TransactionValidationTaskExecutor<V>,
+    transactions: &[MockTransaction],
+) -> Vec<Duration>
+where
+    V: TransactionValidator<Transaction = MockTransaction> + Clone + 'static,
+{
+    rt.block_on(async {
+        let mut interval = tokio::time::interval(Duration::from_secs(1) / TRANSACTIONS_PER_SECOND);
+        let mut submissions = Vec::with_capacity(transactions.len());
+        for transaction in transactions.iter().cloned() {
+            interval.tick().await;
+            let validator = validator.clone();
+            submissions.push(tokio::spawn(async move {
+                let start = Instant::now();
+                let outcome =
+                    validator.validate_transaction(TransactionOrigin::External, transaction).await;
+                assert!(outcome.is_valid());
+                start.elapsed()
+            }));
+        }
+
+        let mut latencies = Vec::with_capacity(submissions.len());
+        for submission in submissions {
+            latencies.push(submission.await.unwrap());
+        }
+        latencies.sort_unstable();
+        latencies
+    })
+}
+
+/// Returns the latency below which the given share of the sorted latencies are.
+fn percentile(latencies: &[Duration], share: f64) -> Duration {
+    let index = ((latencies.len() as f64 * share).ceil() as usize).saturating_sub(1);
+    latencies[index.min(latencies.len() - 1)]
+}
+
+fn main() {
+    let rt = Runtime::new().unwrap();
+
+    let provider = MockEthProvider::default();
+    let count = (TRANSACTIONS_PER_SECOND as u64 * RUN_DURATION.as_secs()) as usize;
+    let transactions = (0..count)
+        .map(|_| {
+            let transaction = MockTransaction::eip1559().with_gas_limit(21_000);
+            provider.add_account(transaction.sender(), ExtendedAccount::new(0, U256::MAX));
+            transaction
+        })
+        .collect::<Vec<_>>();
+
+    let validator = EthTransactionValidatorBuilder::new(MAINNET.clone())
+        .build(provider, InMemoryBlobStore::default());
+
+    let unbatched = validation_task_executor(&rt, UnbatchedValidator(validator.clone()));
+    let batched = validation_task_executor(&rt, validator);
+
+    println!(
+        "Submitting {count} transactions at {TRANSACTIONS_PER_SECOND} tx/s for each validator"
+    );
+    for (name, latencies) in [
+        ("unbatched", submission_latencies(&rt, &unbatched, &transactions)),
+        ("batched", submission_latencies(&rt, &batched, &transactions)),
+    ] {
+        println!(
+            "{name:>9}: p50 {:?}, p99 {:?}, max {:?}",
+            percentile(&latencies, 0.5),
+            percentile(&latencies, 0.99),
+            latencies[latencies.len() - 1],
+        );
+    }
+}
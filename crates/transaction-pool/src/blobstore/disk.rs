@@ -28,12 +28,20 @@ impl DiskFileBlobStore {
         opts: DiskFileBlobStoreConfig,
     ) -> Result<Self, DiskFileBlobStoreError> {
         let blob_dir = blob_dir.into();
-        let DiskFileBlobStoreConfig { max_cached_entries, .. } = opts;
+        let DiskFileBlobStoreConfig { max_cached_entries, open } = opts;
         let inner = DiskFileBlobStoreInner::new(blob_dir, max_cached_entries);
 
         // initialize the blob store
-        inner.delete_all()?;
-        inner.create_blob_dir()?;
+        match open {
+            OpenDiskFileBlobStore::Clear => {
+                inner.delete_all()?;
+                inner.create_blob_dir()?;
+            }
+            OpenDiskFileBlobStore::ReIndex => {
+                inner.create_blob_dir()?;
+                inner.reindex()?;
+            }
+        }
 
         Ok(Self { inner: Arc::new(inner) })
     }
@@ -79,6 +87,13 @@ impl BlobStore for DiskFileBlobStore {
         let mut stat = BlobStoreCleanupStat::default();
         let mut subsize = 0;
         debug!(target:"txpool::blob", num_blobs=%txs_to_delete.len(), "Removing blobs from disk");
+        {
+            // deleted blobs must not be served from the cache anymore
+            let mut cache = self.inner.blob_cache.lock();
+            for tx in &txs_to_delete {
+                cache.remove(tx);
+            }
+        }
         for tx in txs_to_delete {
             let path = self.inner.blob_disk_file(tx);
             let filesize = fs::metadata(&path).map_or(0, |meta| meta.len());
@@ -200,6 +215,30 @@ impl DiskFileBlobStoreInner {
         Ok(())
     }
 
+    /// Restores the size of the blob store from the blob files that are already on disk.
+    fn reindex(&self) -> Result<(), DiskFileBlobStoreError> {
+        let open_err = |err| DiskFileBlobStoreError::Open(self.blob_dir.clone(), err);
+        let mut num_blobs = 0;
+        let mut data_size = 0;
+        for entry in fs::read_dir(&self.blob_dir).map_err(open_err)? {
+            let entry = entry.map_err(open_err)?;
+            let is_blob_file =
+                entry.file_name().to_str().is_some_and(|name| name.parse::<B256>().is_ok());
+            if !is_blob_file {
+                continue
+            }
+            let metadata = entry.metadata().map_err(open_err)?;
+            if metadata.is_file() {
+                num_blobs += 1;
+                data_size += metadata.len() as usize;
+            }
+        }
+        self.size_tracker.add_size(data_size);
+        self.size_tracker.update_len(num_blobs);
+        debug!(target:"txpool::blob", blob_dir = ?self.blob_dir, num_blobs, "Reindexed blob store");
+        Ok(())
+    }
+
     /// Ensures blob is in the blob cache and written to the disk.
     fn insert_one(&self, tx: B256, data: BlobTransactionSidecar) -> Result<(), BlobStoreError> {
         let mut buf = Vec::with_capacity(data.rlp_encoded_fields_length());
@@ -479,6 +518,12 @@ impl DiskFileBlobStoreConfig {
         self.max_cached_entries = max_cached_entries;
         self
     }
+
+    /// Set how to open the blob store.
+    pub const fn with_open(mut self, open: OpenDiskFileBlobStore) -> Self {
+        self.open = open;
+        self
+    }
 }
 
 /// How to open a disk file blob store.
@@ -576,15 +621,35 @@ mod tests {
         assert!(store.inner.txs_to_delete.read().contains(&tx));
         store.cleanup();
 
-        let result = store.get(tx).unwrap();
-        assert_eq!(
-            result,
-            Some(Arc::new(BlobTransactionSidecar {
-                blobs: vec![],
-                commitments: vec![],
-                proofs: vec![]
-            }))
-        );
+        assert!(!store.is_cached(&tx));
+        assert_eq!(store.get(tx).unwrap(), None);
+    }
+
+    #[test]
+    fn disk_reopen_reindex() {
+        let (store, dir) = tmp_store();
+
+        let blobs = rng_blobs(3);
+        store.insert_all(blobs.clone()).unwrap();
+        let data_size = store.data_size_hint();
+        drop(store);
+
+        let store = DiskFileBlobStore::open(
+            dir.path(),
+            DiskFileBlobStoreConfig::default().with_open(OpenDiskFileBlobStore::ReIndex),
+        )
+        .unwrap();
+        assert_eq!(store.blobs_len(), blobs.len());
+        assert_eq!(store.data_size_hint(), data_size);
+        for (tx, blob) in &blobs {
+            assert!(!store.is_cached(tx));
+            assert_eq!(store.get(*tx).unwrap(), Some(Arc::new(blob.clone())));
+        }
+        drop(store);
+
+        let store = DiskFileBlobStore::open(dir.path(), Default::default()).unwrap();
+        assert_eq!(store.blobs_len(), 0);
+        assert_eq!(store.get(blobs[0].0).unwrap(), None);
     }
 
     #[test]
@@ -603,15 +668,7 @@ mod tests {
         store.cleanup();
 
         for tx in txs {
-            let result = store.get(tx).unwrap();
-            assert_eq!(
-                result,
-                Some(Arc::new(BlobTransactionSidecar {
-                    blobs: vec![],
-                    commitments: vec![],
-                    proofs: vec![]
-                }))
-            );
+            assert_eq!(store.get(tx).unwrap(), None);
         }
     }
 
@@ -12,7 +12,7 @@ use std::{
         Arc,
     },
 };
-pub use tracker::{BlobStoreCanonTracker, BlobStoreUpdates};
+pub use tracker::{BlobRetentionConfig, BlobStoreCanonTracker, BlobStoreUpdates};
 
 pub mod disk;
 mod mem;
@@ -1,27 +1,89 @@
 //! Support for maintaining the blob pool.
 
-use alloy_consensus::Typed2718;
-use alloy_eips::eip2718::Encodable2718;
+use alloy_consensus::{Transaction, Typed2718};
+use alloy_eips::{eip2718::Encodable2718, merge::EPOCH_SLOTS};
 use alloy_primitives::{BlockNumber, B256};
 use reth_execution_types::ChainBlocks;
 use reth_primitives_traits::{Block, BlockBody, SignedTransaction};
 use std::collections::BTreeMap;
 
+/// Configures how long the sidecars of blob transactions are kept in the blob store after their
+/// block was finalized.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BlobRetentionConfig {
+    /// Number of blocks below the finalized block for which the sidecars are kept.
+    ///
+    /// Default: 0, sidecars are deleted as soon as their block is finalized.
+    pub blocks: u64,
+    /// Maximum number of retained blobs, the sidecars of the oldest blocks are deleted first once
+    /// this is exceeded.
+    ///
+    /// Default: unlimited
+    pub max_blobs: usize,
+}
+
+impl BlobRetentionConfig {
+    /// Retains the sidecars for the given number of epochs below the finalized block.
+    pub const fn epochs(epochs: u64) -> Self {
+        Self { blocks: epochs.saturating_mul(EPOCH_SLOTS), max_blobs: usize::MAX }
+    }
+
+    /// Sets the maximum number of retained blobs.
+    pub const fn with_max_blobs(mut self, max_blobs: usize) -> Self {
+        self.max_blobs = max_blobs;
+        self
+    }
+}
+
+impl Default for BlobRetentionConfig {
+    fn default() -> Self {
+        Self { blocks: 0, max_blobs: usize::MAX }
+    }
+}
+
 /// The type that is used to track canonical blob transactions.
 #[derive(Debug, Default, Eq, PartialEq)]
 pub struct BlobStoreCanonTracker {
     /// Keeps track of the blob transactions included in blocks.
     blob_txs_in_blocks: BTreeMap<BlockNumber, Vec<B256>>,
+    /// Number of blobs of the blob transactions in [`Self::blob_txs_in_blocks`].
+    blobs_in_blocks: BTreeMap<BlockNumber, usize>,
+    /// How long the sidecars of finalized blocks are retained.
+    retention: BlobRetentionConfig,
+    /// Blob transactions of finalized blocks that are retained, with the number of their blobs.
+    retained: BTreeMap<BlockNumber, (Vec<B256>, usize)>,
+    /// Total number of blobs in [`Self::retained`].
+    retained_blobs: usize,
 }
 
 impl BlobStoreCanonTracker {
+    /// Creates a new tracker that retains the sidecars of finalized blocks according to the given
+    /// config.
+    pub fn new(retention: BlobRetentionConfig) -> Self {
+        Self { retention, ..Default::default() }
+    }
+
+    /// Returns the number of blobs that are retained for finalized blocks.
+    pub const fn retained_blobs(&self) -> usize {
+        self.retained_blobs
+    }
+
     /// Adds a block to the blob store maintenance.
+    ///
+    /// Each transaction is counted as a single blob.
     pub fn add_block(
         &mut self,
         block_number: BlockNumber,
         blob_txs: impl IntoIterator<Item = B256>,
     ) {
-        self.blob_txs_in_blocks.insert(block_number, blob_txs.into_iter().collect());
+        let blob_txs = blob_txs.into_iter().collect::<Vec<_>>();
+        let blobs = blob_txs.len();
+        self.insert_block(block_number, blob_txs, blobs);
+    }
+
+    fn insert_block(&mut self, block_number: BlockNumber, blob_txs: Vec<B256>, blobs: usize) {
+        self.blob_txs_in_blocks.insert(block_number, blob_txs);
+        self.blobs_in_blocks.insert(block_number, blobs);
     }
 
     /// Adds all blocks to the tracked list of blocks.
@@ -44,31 +106,58 @@ impl BlobStoreCanonTracker {
     where
         B: Block<Body: BlockBody<Transaction: SignedTransaction>>,
     {
-        let blob_txs = blocks.iter().map(|(num, block)| {
-            let iter = block
-                .body
-                .transactions()
-                .iter()
-                .filter(|tx| tx.is_eip4844())
-                .map(|tx| tx.trie_hash());
-            (*num, iter)
-        });
-        self.add_blocks(blob_txs);
+        for (num, block) in blocks.iter() {
+            self.add_block_transactions(*num, block.body.transactions());
+        }
+    }
+
+    /// Adds the blob transactions among the given transactions of a block to the tracker.
+    pub fn add_block_transactions<T: SignedTransaction>(
+        &mut self,
+        block_number: BlockNumber,
+        transactions: &[T],
+    ) {
+        let blob_txs = transactions.iter().filter(|tx| tx.is_eip4844()).collect::<Vec<_>>();
+        let blobs = blob_txs
+            .iter()
+            .map(|tx| tx.blob_versioned_hashes().map_or(0, |hashes| hashes.len()))
+            .sum();
+        self.insert_block(
+            block_number,
+            blob_txs.into_iter().map(|tx| tx.trie_hash()).collect(),
+            blobs,
+        );
     }
 
     /// Invoked when a block is finalized.
     ///
-    /// This returns all blob transactions that were included in blocks that are now finalized.
+    /// This returns all blob transactions of finalized blocks whose sidecars are no longer
+    /// retained, see [`BlobRetentionConfig`].
     pub fn on_finalized_block(&mut self, finalized_block: BlockNumber) -> BlobStoreUpdates {
-        let mut finalized = Vec::new();
         while let Some(entry) = self.blob_txs_in_blocks.first_entry() {
             if *entry.key() <= finalized_block {
-                finalized.extend(entry.remove_entry().1);
+                let (block_number, blob_txs) = entry.remove_entry();
+                let blobs = self.blobs_in_blocks.remove(&block_number).unwrap_or_default();
+                self.retained_blobs += blobs;
+                self.retained.insert(block_number, (blob_txs, blobs));
             } else {
                 break
             }
         }
 
+        // release the blocks that left the retention window, and the oldest blocks while there
+        // are more blobs than allowed
+        let mut finalized = Vec::new();
+        while let Some(entry) = self.retained.first_entry() {
+            let expired = entry.key().saturating_add(self.retention.blocks) <= finalized_block;
+            if !expired && self.retained_blobs <= self.retention.max_blobs {
+                break
+            }
+            let (blob_txs, blobs) = entry.remove();
+            self.retained_blobs -= blobs;
+            finalized.extend(blob_txs);
+        }
+
         if finalized.is_empty() {
             BlobStoreUpdates::None
         } else {
@@ -117,6 +206,54 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_retained_finalized_blobs() {
+        let mut tracker =
+            BlobStoreCanonTracker::new(BlobRetentionConfig { blocks: 2, max_blobs: usize::MAX });
+
+        let block1 = vec![B256::random()];
+        let block2 = vec![B256::random(), B256::random()];
+        let block3 = vec![B256::random()];
+        tracker.add_block(1, block1.clone());
+        tracker.add_block(2, block2.clone());
+        tracker.add_block(3, block3.clone());
+
+        // finalized blocks are retained for 2 blocks
+        assert_eq!(tracker.on_finalized_block(2), BlobStoreUpdates::None);
+        assert_eq!(tracker.retained_blobs(), 3);
+        assert_eq!(tracker.on_finalized_block(3), BlobStoreUpdates::Finalized(block1));
+        assert_eq!(tracker.retained_blobs(), 3);
+        assert_eq!(
+            tracker.on_finalized_block(5),
+            BlobStoreUpdates::Finalized(block2.into_iter().chain(block3).collect::<Vec<_>>())
+        );
+        assert_eq!(tracker.retained_blobs(), 0);
+    }
+
+    #[test]
+    fn test_retained_blobs_cap() {
+        let mut tracker =
+            BlobStoreCanonTracker::new(BlobRetentionConfig::epochs(1).with_max_blobs(2));
+
+        let block1 = vec![B256::random()];
+        let block2 = vec![B256::random()];
+        let block3 = vec![B256::random()];
+        tracker.add_block(1, block1.clone());
+        tracker.add_block(2, block2.clone());
+        tracker.add_block(3, block3);
+
+        assert_eq!(tracker.on_finalized_block(2), BlobStoreUpdates::None);
+        // the oldest block is released once the cap is exceeded
+        assert_eq!(tracker.on_finalized_block(3), BlobStoreUpdates::Finalized(block1));
+        assert_eq!(tracker.retained_blobs(), 2);
+        // the retention window ends after an epoch
+        assert_eq!(
+            tracker.on_finalized_block(2 + EPOCH_SLOTS),
+            BlobStoreUpdates::Finalized(block2)
+        );
+        assert_eq!(tracker.retained_blobs(), 1);
+    }
+
     #[test]
     fn test_add_new_chain_blocks() {
         let mut tracker = BlobStoreCanonTracker::default();
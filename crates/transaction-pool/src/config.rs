@@ -5,7 +5,7 @@ use crate::{
 use alloy_consensus::constants::EIP4844_TX_TYPE_ID;
 use alloy_eips::eip1559::{ETHEREUM_BLOCK_GAS_LIMIT, MIN_PROTOCOL_BASE_FEE};
 use alloy_primitives::Address;
-use std::{collections::HashSet, ops::Mul};
+use std::{collections::HashSet, ops::Mul, time::Duration};
 
 /// Guarantees max transactions for one sender, compatible with geth/erigon
 pub const TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER: usize = 16;
@@ -30,6 +30,15 @@ pub const REPLACE_BLOB_PRICE_BUMP: u128 = 100;
 /// Default maximum new transactions for broadcasting.
 pub const MAX_NEW_PENDING_TXS_NOTIFICATIONS: usize = 200;
 
+/// Default maximum number of recently rejected transactions to remember, per kind of rejection.
+pub const DEFAULT_MAX_REJECTED_TRANSACTIONS: u32 = 10_000;
+
+/// Default maximum size in megabytes of the blob sidecars retained for finalized blocks.
+pub const DEFAULT_BLOB_RETENTION_MAX_SIZE_MB: usize = 4096;
+
+/// Default duration for which a rejected transaction is remembered.
+pub const DEFAULT_REJECTED_TRANSACTIONS_TTL: Duration = Duration::from_secs(5 * 60);
+
 /// Configuration options for the Transaction pool.
 #[derive(Debug, Clone)]
 pub struct PoolConfig {
@@ -58,6 +67,8 @@ pub struct PoolConfig {
     pub new_tx_listener_buffer_size: usize,
     /// How many new pending transactions to buffer and send iterators in progress.
     pub max_new_pending_txs_notifications: usize,
+    /// How to remember rejected external transactions to avoid validating them again.
+    pub rejected_transactions: RejectedTransactionsConfig,
 }
 
 impl PoolConfig {
@@ -86,6 +97,7 @@ impl Default for PoolConfig {
             pending_tx_listener_buffer_size: PENDING_TX_LISTENER_BUFFER_SIZE,
             new_tx_listener_buffer_size: NEW_TX_LISTENER_BUFFER_SIZE,
             max_new_pending_txs_notifications: MAX_NEW_PENDING_TXS_NOTIFICATIONS,
+            rejected_transactions: Default::default(),
         }
     }
 }
@@ -160,6 +172,26 @@ impl Default for PriceBumpConfig {
     }
 }
 
+/// Configuration of the cache of recently rejected external transactions:
+/// [`TransactionOrigin::External`](TransactionOrigin)
+///
+/// Rejected transactions are often re-announced by many peers, this avoids validating them again
+/// until they expire or, if they were rejected because of their fees, fees decrease.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct RejectedTransactionsConfig {
+    /// Max number of rejected transactions to remember, per kind of rejection. `0` disables the
+    /// cache.
+    pub max_len: u32,
+    /// How long a rejected transaction is remembered.
+    pub ttl: Duration,
+}
+
+impl Default for RejectedTransactionsConfig {
+    fn default() -> Self {
+        Self { max_len: DEFAULT_MAX_REJECTED_TRANSACTIONS, ttl: DEFAULT_REJECTED_TRANSACTIONS_TTL }
+    }
+}
+
 /// Configuration options for the locally received transactions:
 /// [`TransactionOrigin::Local`](TransactionOrigin)
 #[derive(Debug, Clone, Eq, PartialEq)]
@@ -142,6 +142,21 @@ impl PoolError {
     }
 }
 
+/// Error of an external transaction that was recently rejected as a bad transaction, returned
+/// without validating it again.
+///
+/// See [`PoolError::is_bad_transaction`].
+#[derive(Debug, thiserror::Error)]
+#[error("previously rejected: {0}")]
+pub struct PreviouslyRejectedError(pub String);
+
+impl PoolTransactionError for PreviouslyRejectedError {
+    fn is_bad_transaction(&self) -> bool {
+        // only bad transactions are remembered as invalid
+        true
+    }
+}
+
 /// Represents all errors that can happen when validating transactions for the pool for EIP-4844
 /// transactions
 #[derive(Debug, thiserror::Error)]
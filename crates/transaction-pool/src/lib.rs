@@ -153,7 +153,9 @@
 pub use crate::{
     blobstore::{BlobStore, BlobStoreError},
     config::{
-        LocalTransactionConfig, PoolConfig, PriceBumpConfig, SubPoolLimit, DEFAULT_PRICE_BUMP,
+        LocalTransactionConfig, PoolConfig, PriceBumpConfig, RejectedTransactionsConfig,
+        SubPoolLimit, DEFAULT_BLOB_RETENTION_MAX_SIZE_MB, DEFAULT_MAX_REJECTED_TRANSACTIONS,
+        DEFAULT_PRICE_BUMP, DEFAULT_REJECTED_TRANSACTIONS_TTL,
         DEFAULT_TXPOOL_ADDITIONAL_VALIDATION_TASKS, MAX_NEW_PENDING_TXS_NOTIFICATIONS,
         REPLACE_BLOB_PRICE_BUMP, TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER,
         TXPOOL_SUBPOOL_MAX_SIZE_MB_DEFAULT, TXPOOL_SUBPOOL_MAX_TXS_DEFAULT,
@@ -170,9 +172,13 @@ pub use crate::{
         TransactionValidator, ValidPoolTransaction,
     },
 };
-use crate::{identifier::TransactionId, pool::PoolInner};
+use crate::{
+    error::{PoolError, PoolErrorKind},
+    identifier::TransactionId,
+    pool::{permits::SenderPermit, PoolInner},
+};
 use alloy_eips::eip4844::{BlobAndProofV1, BlobTransactionSidecar};
-use alloy_primitives::{Address, TxHash, B256, U256};
+use alloy_primitives::{map::AddressHashMap, Address, TxHash, B256, U256};
 use aquamarine as _;
 use reth_eth_wire_types::HandleMempoolData;
 use reth_execution_types::ChangedAccount;
@@ -237,6 +243,11 @@ where
         self.inner().config()
     }
 
+    /// Returns the blob store of the pool.
+    pub fn blob_store(&self) -> &S {
+        self.inner().blob_store()
+    }
+
     /// Returns future that validates all transactions in the given iterator.
     ///
     /// This returns the validated transactions in the iterator's order.
@@ -262,6 +273,18 @@ where
         (hash, outcome)
     }
 
+    /// Waits for the permit to add the transaction, which is only granted once the transactions
+    /// of the same sender that were submitted before it were added.
+    ///
+    /// Fails if the same transaction is already being added.
+    async fn sender_permit(&self, transaction: &V::Transaction) -> PoolResult<SenderPermit<'_>> {
+        let hash = *transaction.hash();
+        if !self.pool.sender_permits().track(hash) {
+            return Err(PoolError::new(hash, PoolErrorKind::AlreadyImported))
+        }
+        Ok(self.pool.sender_permits().acquire([transaction.sender()], vec![hash]).await)
+    }
+
     /// Number of transactions in the entire pool
     pub fn len(&self) -> usize {
         self.pool.len()
@@ -343,6 +366,8 @@ where
         origin: TransactionOrigin,
         transaction: Self::Transaction,
     ) -> PoolResult<TransactionEvents> {
+        self.pool.ensure_not_rejected(origin, transaction.hash())?;
+        let _permit = self.sender_permit(&transaction).await?;
         let (_, tx) = self.validate(origin, transaction).await;
         self.pool.add_transaction_and_subscribe(origin, tx)
     }
@@ -352,6 +377,8 @@ where
         origin: TransactionOrigin,
         transaction: Self::Transaction,
     ) -> PoolResult<TxHash> {
+        self.pool.ensure_not_rejected(origin, transaction.hash())?;
+        let _permit = self.sender_permit(&transaction).await?;
         let (_, tx) = self.validate(origin, transaction).await;
         let mut results = self.pool.add_transactions(origin, std::iter::once(tx));
         results.pop().expect("result length is the same as the input")
@@ -365,9 +392,43 @@ where
         if transactions.is_empty() {
             return Vec::new()
         }
-        let validated = self.validate_all(origin, transactions).await;
 
-        self.pool.add_transactions(origin, validated.into_iter().map(|(_, tx)| tx))
+        // skip validation of recently rejected transactions and of transactions that are already
+        // being added, their errors are merged back into the results at their original position
+        let mut results = Vec::with_capacity(transactions.len());
+        let mut by_sender = AddressHashMap::<Vec<_>>::default();
+        for (idx, tx) in transactions.into_iter().enumerate() {
+            let result = self.pool.ensure_not_rejected(origin, tx.hash()).and_then(|()| {
+                if self.pool.sender_permits().track(*tx.hash()) {
+                    Ok(())
+                } else {
+                    Err(PoolError::new(*tx.hash(), PoolErrorKind::AlreadyImported))
+                }
+            });
+            match result {
+                Ok(()) => by_sender.entry(tx.sender()).or_default().push((idx, tx)),
+                Err(err) => results.push((idx, Err(err))),
+            }
+        }
+
+        // the transactions of each sender are validated and added once the sender's permit is
+        // acquired, without waiting for the permits of the other senders
+        let added = futures_util::future::join_all(by_sender.into_iter().map(
+            |(sender, transactions)| async move {
+                let (indices, transactions): (Vec<_>, Vec<_>) = transactions.into_iter().unzip();
+                let hashes = transactions.iter().map(|tx| *tx.hash()).collect();
+                let _permit = self.pool.sender_permits().acquire([sender], hashes).await;
+                let validated = self.validate_all(origin, transactions).await;
+                let added =
+                    self.pool.add_transactions(origin, validated.into_iter().map(|(_, tx)| tx));
+                indices.into_iter().zip(added).collect::<Vec<_>>()
+            },
+        ))
+        .await;
+        results.extend(added.into_iter().flatten());
+
+        results.sort_unstable_by_key(|(idx, _)| *idx);
+        results.into_iter().map(|(_, result)| result).collect()
     }
 
     fn transaction_event_listener(&self, tx_hash: TxHash) -> Option<TransactionEvents> {
@@ -456,6 +517,10 @@ where
         self.pool.queued_transactions()
     }
 
+    fn basefee_transactions(&self) -> Vec<Arc<ValidPoolTransaction<Self::Transaction>>> {
+        self.pool.basefee_transactions()
+    }
+
     fn all_transactions(&self) -> AllPoolTransactions<Self::Transaction> {
         self.pool.all_transactions()
     }
@@ -488,6 +553,13 @@ where
         self.pool.retain_unknown(announcement)
     }
 
+    fn retain_not_rejected<A>(&self, announcement: &mut A)
+    where
+        A: HandleMempoolData,
+    {
+        self.pool.retain_not_rejected(announcement)
+    }
+
     fn get(&self, tx_hash: &TxHash) -> Option<Arc<ValidPoolTransaction<Self::Transaction>>> {
         self.inner().get(tx_hash)
     }
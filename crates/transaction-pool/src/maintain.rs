@@ -1,7 +1,7 @@
 //! Support for maintaining the state of the transaction pool
 
 use crate::{
-    blobstore::{BlobStoreCanonTracker, BlobStoreUpdates},
+    blobstore::{BlobRetentionConfig, BlobStoreCanonTracker, BlobStoreUpdates},
     error::PoolError,
     metrics::MaintainPoolMetrics,
     traits::{CanonicalStateUpdate, EthPoolTransaction, TransactionPool, TransactionPoolExt},
@@ -45,11 +45,20 @@ pub struct MaintainPoolConfig {
     ///
     /// Default: 100
     pub max_reload_accounts: usize,
+    /// How long the sidecars of mined blob transactions are kept in the blob store after their
+    /// block was finalized.
+    ///
+    /// Default: deleted once finalized
+    pub blob_retention: BlobRetentionConfig,
 }
 
 impl Default for MaintainPoolConfig {
     fn default() -> Self {
-        Self { max_update_depth: 64, max_reload_accounts: 100 }
+        Self {
+            max_update_depth: 64,
+            max_reload_accounts: 100,
+            blob_retention: BlobRetentionConfig::default(),
+        }
     }
 }
 
@@ -105,7 +114,7 @@ pub async fn maintain_transaction_pool<N, Client, P, St, Tasks>(
     Tasks: TaskSpawner + 'static,
 {
     let metrics = MaintainPoolMetrics::default();
-    let MaintainPoolConfig { max_update_depth, max_reload_accounts, .. } = config;
+    let MaintainPoolConfig { max_update_depth, max_reload_accounts, blob_retention } = config;
     // ensure the pool points to latest state
     if let Ok(Some(latest)) = client.header_by_number_or_tag(BlockNumberOrTag::Latest) {
         let latest = SealedHeader::seal(latest);
@@ -125,12 +134,31 @@ pub async fn maintain_transaction_pool<N, Client, P, St, Tasks>(
     }
 
     // keeps track of mined blob transaction so we can clean finalized transactions
-    let mut blob_store_tracker = BlobStoreCanonTracker::default();
+    let mut blob_store_tracker = BlobStoreCanonTracker::new(blob_retention);
 
     // keeps track of the latest finalized block
     let mut last_finalized_block =
         FinalizedBlockTracker::new(client.finalized_block_number().ok().flatten());
 
+    // the blob store keeps the retained sidecars across restarts, so the blocks they belong to
+    // must be tracked again to delete them once they leave the retention window
+    if let Some(finalized) =
+        last_finalized_block.last_finalized_block.filter(|_| blob_retention.blocks > 0)
+    {
+        match load_retained_blob_txs(&client, &mut blob_store_tracker, blob_retention, finalized) {
+            Ok(()) => {
+                if let BlobStoreUpdates::Finalized(blobs) =
+                    blob_store_tracker.on_finalized_block(finalized)
+                {
+                    metrics.inc_deleted_tracked_blobs(blobs.len());
+                    pool.delete_blobs(blobs);
+                }
+                metrics.set_retained_blobs(blob_store_tracker.retained_blobs());
+            }
+            Err(err) => warn!(target: "txpool", %err, "Failed to load retained blob transactions"),
+        }
+    }
+
     // keeps track of any dirty accounts that we know of are out of sync with the pool
     let mut dirty_addresses = HashSet::default();
 
@@ -194,9 +222,9 @@ pub async fn maintain_transaction_pool<N, Client, P, St, Tasks>(
         if let Some(finalized) =
             last_finalized_block.update(client.finalized_block_number().ok().flatten())
         {
-            if let BlobStoreUpdates::Finalized(blobs) =
-                blob_store_tracker.on_finalized_block(finalized)
-            {
+            let updates = blob_store_tracker.on_finalized_block(finalized);
+            metrics.set_retained_blobs(blob_store_tracker.retained_blobs());
+            if let BlobStoreUpdates::Finalized(blobs) = updates {
                 metrics.inc_deleted_tracked_blobs(blobs.len());
                 // remove all finalized blobs from the blob store
                 pool.delete_blobs(blobs);
@@ -465,6 +493,32 @@ impl FinalizedBlockTracker {
     }
 }
 
+/// Number of blocks whose transactions are loaded at once by [`load_retained_blob_txs`].
+const LOAD_RETAINED_BLOB_TXS_CHUNK_SIZE: u64 = 1_000;
+
+/// Adds the blob transactions of the blocks from the retention window below the finalized block up
+/// to the tip to the tracker.
+fn load_retained_blob_txs<Client>(
+    client: &Client,
+    tracker: &mut BlobStoreCanonTracker,
+    retention: BlobRetentionConfig,
+    finalized: BlockNumber,
+) -> Result<(), ProviderError>
+where
+    Client: BlockReaderIdExt,
+{
+    let start = finalized.saturating_sub(retention.blocks) + 1;
+    let end = client.best_block_number()?;
+    for chunk_start in (start..=end).step_by(LOAD_RETAINED_BLOB_TXS_CHUNK_SIZE as usize) {
+        let chunk_end = end.min(chunk_start + LOAD_RETAINED_BLOB_TXS_CHUNK_SIZE - 1);
+        let transactions = client.transactions_by_block_range(chunk_start..=chunk_end)?;
+        for (block_number, transactions) in (chunk_start..=chunk_end).zip(transactions) {
+            tracker.add_block_transactions(block_number, &transactions);
+        }
+    }
+    Ok(())
+}
+
 /// Keeps track of the pool's state, whether the accounts in the pool are in sync with the actual
 /// state.
 #[derive(Debug, PartialEq, Eq)]
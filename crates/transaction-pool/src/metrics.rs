@@ -1,7 +1,7 @@
 //! Transaction pool metrics.
 
 use reth_metrics::{
-    metrics::{Counter, Gauge},
+    metrics::{Counter, Gauge, Histogram},
     Metrics,
 };
 
@@ -53,6 +53,37 @@ pub struct TxPoolMetrics {
     pub(crate) performed_state_updates: Counter,
 }
 
+/// Metrics of the cache of recently rejected transactions
+#[derive(Metrics)]
+#[metrics(scope = "transaction_pool")]
+pub struct RejectedTransactionsMetrics {
+    /// Number of validations skipped, because the transaction was recently rejected
+    pub(crate) rejected_cache_hits: Counter,
+    /// Number of times fee related rejections were forgotten, because fees decreased
+    pub(crate) rejected_cache_fee_invalidations: Counter,
+    /// Number of remembered transactions rejected because of their fees
+    pub(crate) rejected_cache_fee_related: Gauge,
+    /// Number of remembered transactions rejected as bad transactions
+    pub(crate) rejected_cache_invalid: Gauge,
+}
+
+/// Transaction validation metrics
+#[derive(Metrics, Clone)]
+#[metrics(scope = "transaction_pool.validation")]
+pub struct TxValidationMetrics {
+    /// Number of validation jobs executing concurrently on a validation task when a job is
+    /// picked up
+    pub(crate) job_batch_size: Histogram,
+    /// Number of distinct senders whose state was read with a single state provider
+    pub(crate) state_batch_size: Histogram,
+    /// Time spent on the checks that don't require state
+    pub(crate) stateless_duration: Histogram,
+    /// Time spent reading the state of a batch of senders
+    pub(crate) state_read_duration: Histogram,
+    /// Time spent on the checks against the sender state
+    pub(crate) stateful_duration: Histogram,
+}
+
 /// Transaction pool blobstore metrics
 #[derive(Metrics)]
 #[metrics(scope = "transaction_pool")]
@@ -82,6 +113,8 @@ pub struct MaintainPoolMetrics {
     pub(crate) reinserted_transactions: Counter,
     /// Counter for the number of finalized blob transactions that have been removed from tracking.
     pub(crate) deleted_tracked_finalized_blobs: Counter,
+    /// Number of blobs of finalized blocks that are retained in the blobstore.
+    pub(crate) retained_finalized_blobs: Gauge,
 }
 
 impl MaintainPoolMetrics {
@@ -100,6 +133,11 @@ impl MaintainPoolMetrics {
         self.deleted_tracked_finalized_blobs.increment(count as u64);
     }
 
+    #[inline]
+    pub(crate) fn set_retained_blobs(&self, count: usize) {
+        self.retained_finalized_blobs.set(count as f64);
+    }
+
     #[inline]
     pub(crate) fn inc_drift(&self) {
         self.drift_count.increment(1);
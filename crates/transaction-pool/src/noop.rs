@@ -175,6 +175,10 @@ impl TransactionPool for NoopTransactionPool {
         vec![]
     }
 
+    fn basefee_transactions(&self) -> Vec<Arc<ValidPoolTransaction<Self::Transaction>>> {
+        vec![]
+    }
+
     fn all_transactions(&self) -> AllPoolTransactions<Self::Transaction> {
         AllPoolTransactions::default()
     }
@@ -206,6 +210,12 @@ impl TransactionPool for NoopTransactionPool {
     {
     }
 
+    fn retain_not_rejected<A>(&self, _announcement: &mut A)
+    where
+        A: HandleMempoolData,
+    {
+    }
+
     fn get(&self, _tx_hash: &TxHash) -> Option<Arc<ValidPoolTransaction<Self::Transaction>>> {
         None
     }
@@ -84,6 +84,8 @@ use crate::{
 use alloy_primitives::{Address, TxHash, B256};
 use best::BestTransactions;
 use parking_lot::{Mutex, RwLock, RwLockReadGuard, RwLockWriteGuard};
+use permits::SenderPermits;
+use rejected::RejectedTransactions;
 use reth_eth_wire_types::HandleMempoolData;
 use reth_execution_types::ChangedAccount;
 
@@ -109,6 +111,7 @@ pub use events::{FullTransactionEvent, TransactionEvent};
 pub use listener::{AllTransactionsEvents, TransactionEvents};
 pub use parked::{BasefeeOrd, ParkedOrd, ParkedPool, QueuedOrd};
 pub use pending::PendingPool;
+pub use rejected::RejectionReason;
 use reth_primitives_traits::{BlockBody, BlockHeader};
 
 mod best;
@@ -116,6 +119,8 @@ mod blob;
 mod listener;
 mod parked;
 pub(crate) mod pending;
+pub(crate) mod permits;
+mod rejected;
 pub(crate) mod size;
 pub(crate) mod state;
 pub mod txpool;
@@ -153,6 +158,10 @@ where
     blob_transaction_sidecar_listener: Mutex<Vec<BlobTransactionSidecarListener>>,
     /// Metrics for the blob store
     blob_store_metrics: BlobStoreMetrics,
+    /// Recently rejected external transactions.
+    rejected_transactions: RejectedTransactions,
+    /// Serializes adding transactions of the same sender.
+    sender_permits: SenderPermits,
 }
 
 // === impl PoolInner ===
@@ -173,6 +182,8 @@ where
             pending_transaction_listener: Default::default(),
             transaction_listener: Default::default(),
             blob_transaction_sidecar_listener: Default::default(),
+            rejected_transactions: RejectedTransactions::new(config.rejected_transactions),
+            sender_permits: Default::default(),
             config,
             blob_store,
             blob_store_metrics: Default::default(),
@@ -184,6 +195,11 @@ where
         &self.blob_store
     }
 
+    /// Returns the permits that serialize adding transactions of the same sender.
+    pub(crate) const fn sender_permits(&self) -> &SenderPermits {
+        &self.sender_permits
+    }
+
     /// Returns stats about the size of the pool.
     pub fn size(&self) -> PoolSize {
         self.get_pool_data().size()
@@ -195,7 +211,9 @@ where
     }
     /// Sets the currently tracked block
     pub fn set_block_info(&self, info: BlockInfo) {
-        self.pool.write().set_block_info(info)
+        let mut pool = self.pool.write();
+        self.rejected_transactions.on_block_info_update(&pool.block_info(), &info);
+        pool.set_block_info(info)
     }
 
     /// Returns the internal [`SenderId`] for this address
@@ -395,12 +413,16 @@ where
         let changed_senders = self.changed_senders(changed_accounts.into_iter());
 
         // update the pool
-        let outcome = self.pool.write().on_canonical_state_change(
-            block_info,
-            mined_transactions,
-            changed_senders,
-            update_kind,
-        );
+        let outcome = {
+            let mut pool = self.pool.write();
+            self.rejected_transactions.on_block_info_update(&pool.block_info(), &block_info);
+            pool.on_canonical_state_change(
+                block_info,
+                mined_transactions,
+                changed_senders,
+                update_kind,
+            )
+        };
 
         // This will discard outdated transactions based on the account's nonce
         self.delete_discarded_blobs(outcome.discarded.iter());
@@ -579,9 +601,31 @@ where
             }
         }
 
+        if origin.is_external() {
+            for err in added.iter().filter_map(|res| res.as_ref().err()) {
+                self.rejected_transactions.insert(err);
+            }
+        }
+
         added
     }
 
+    /// Returns the error an external transaction was recently rejected with, so it doesn't need to
+    /// be validated again.
+    ///
+    /// Transactions of other origins are always validated, since they may be exempt from some of
+    /// the checks, see [`LocalTransactionConfig`](crate::LocalTransactionConfig).
+    pub fn ensure_not_rejected(
+        &self,
+        origin: TransactionOrigin,
+        tx_hash: &TxHash,
+    ) -> PoolResult<()> {
+        if !origin.is_external() {
+            return Ok(())
+        }
+        self.rejected_transactions.get(tx_hash).map_or(Ok(()), Err)
+    }
+
     /// Notify all listeners about a new pending transaction.
     fn on_new_pending_transaction(&self, pending: &AddedPendingTransaction<T::Transaction>) {
         let propagate_allowed = pending.is_propagate_allowed();
@@ -719,6 +763,11 @@ where
         self.get_pool_data().queued_transactions()
     }
 
+    /// Returns all transactions from the basefee sub-pool
+    pub fn basefee_transactions(&self) -> Vec<Arc<ValidPoolTransaction<T::Transaction>>> {
+        self.get_pool_data().basefee_transactions()
+    }
+
     /// Returns all transactions in the pool
     pub fn all_transactions(&self) -> AllPoolTransactions<T::Transaction> {
         let pool = self.get_pool_data();
@@ -790,6 +839,17 @@ where
         announcement.retain_by_hash(|tx| !pool.contains(tx))
     }
 
+    /// Removes all transactions from the given [`HandleMempoolData`] that were recently rejected.
+    pub fn retain_not_rejected<A>(&self, announcement: &mut A)
+    where
+        A: HandleMempoolData,
+    {
+        if announcement.is_empty() {
+            return
+        }
+        announcement.retain_by_hash(|tx| !self.rejected_transactions.contains(tx))
+    }
+
     /// Returns the transaction by hash.
     pub fn get(&self, tx_hash: &TxHash) -> Option<Arc<ValidPoolTransaction<T::Transaction>>> {
         self.get_pool_data().get(tx_hash)
@@ -6,6 +6,7 @@ use crate::{
     },
     Priority, SubPoolLimit, TransactionOrdering, ValidPoolTransaction,
 };
+use reth_primitives::fee_math;
 use rustc_hash::FxHashMap;
 use std::{
     cmp::Ordering,
@@ -217,7 +218,12 @@ impl<T: TransactionOrdering> PendingPool<T> {
         // Drain and iterate over all transactions.
         let mut transactions_iter = self.clear_transactions().into_iter().peekable();
         while let Some((id, mut tx)) = transactions_iter.next() {
-            if tx.transaction.max_fee_per_gas() < base_fee as u128 {
+            let tip = fee_math::effective_gas_tip(
+                tx.transaction.max_fee_per_gas(),
+                tx.transaction.transaction.max_priority_fee_per_gas(),
+                base_fee,
+            );
+            if tip.is_none() {
                 // Add this tx to the removed collection since it no longer satisfies the base fee
                 // condition. Decrease the total pool size.
                 removed.push(Arc::clone(&tx.transaction));
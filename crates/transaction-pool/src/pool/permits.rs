@@ -0,0 +1,130 @@
+//! Per sender permits that serialize adding transactions of the same sender.
+
+use alloy_primitives::{Address, TxHash};
+use parking_lot::Mutex;
+use rustc_hash::{FxHashMap, FxHashSet};
+use std::sync::Arc;
+use tokio::sync::{Mutex as AsyncMutex, OwnedMutexGuard};
+
+/// Serializes the validation and insertion of transactions of the same sender, and tracks the
+/// transactions that are being added.
+///
+/// Transactions of the same sender that are submitted at the same time, e.g. consecutive nonces
+/// over different connections, would otherwise race through validation and be inserted out of
+/// order. Waiting for a permit is fair, so transactions of a sender are added in the order they
+/// were submitted in. Transactions of other senders are not affected.
+#[derive(Debug, Default)]
+pub(crate) struct SenderPermits {
+    /// Locks of the senders with transactions that are being added.
+    senders: Mutex<FxHashMap<Address, Arc<AsyncMutex<()>>>>,
+    /// Hashes of the transactions that are being added.
+    in_flight: Mutex<FxHashSet<TxHash>>,
+}
+
+impl SenderPermits {
+    /// Marks the transaction as being added.
+    ///
+    /// Returns `false` if it's already being added.
+    pub(crate) fn track(&self, hash: TxHash) -> bool {
+        self.in_flight.lock().insert(hash)
+    }
+
+    /// Returns `true` if the transaction is being added.
+    #[cfg(test)]
+    pub(crate) fn is_in_flight(&self, hash: &TxHash) -> bool {
+        self.in_flight.lock().contains(hash)
+    }
+
+    /// Waits for the permits of the senders of the tracked transactions with the given hashes.
+    ///
+    /// The transactions are no longer tracked once the returned permit is dropped.
+    pub(crate) async fn acquire(
+        &self,
+        senders: impl IntoIterator<Item = Address>,
+        hashes: Vec<TxHash>,
+    ) -> SenderPermit<'_> {
+        // the locks are always acquired in the same order, so that adding two batches with the
+        // same senders doesn't deadlock
+        let mut senders = senders.into_iter().collect::<Vec<_>>();
+        senders.sort_unstable();
+        senders.dedup();
+
+        let locks = {
+            let mut locks = self.senders.lock();
+            senders
+                .iter()
+                .map(|sender| locks.entry(*sender).or_default().clone())
+                .collect::<Vec<_>>()
+        };
+
+        let mut permit = SenderPermit { permits: self, senders, guards: Vec::new(), hashes };
+        for lock in locks {
+            permit.guards.push(lock.lock_owned().await);
+        }
+        permit
+    }
+}
+
+/// Permit to add transactions of a set of senders, see [`SenderPermits`].
+#[derive(Debug)]
+pub(crate) struct SenderPermit<'a> {
+    permits: &'a SenderPermits,
+    /// The senders of the permit.
+    senders: Vec<Address>,
+    /// Guards of the sender locks that were acquired so far.
+    guards: Vec<OwnedMutexGuard<()>>,
+    /// Hashes of the tracked transactions.
+    hashes: Vec<TxHash>,
+}
+
+impl Drop for SenderPermit<'_> {
+    fn drop(&mut self) {
+        self.guards.clear();
+
+        // locks of senders that no one else is waiting for are removed
+        let mut locks = self.permits.senders.lock();
+        for sender in &self.senders {
+            if locks.get(sender).is_some_and(|lock| Arc::strong_count(lock) == 1) {
+                locks.remove(sender);
+            }
+        }
+        drop(locks);
+
+        let mut in_flight = self.permits.in_flight.lock();
+        for hash in &self.hashes {
+            in_flight.remove(hash);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn permits_by_sender() {
+        let permits = SenderPermits::default();
+        let (a, b) = (Address::with_last_byte(1), Address::with_last_byte(2));
+
+        let hash = TxHash::with_last_byte(1);
+        assert!(permits.track(hash));
+        assert!(!permits.track(hash));
+        let permit = permits.acquire([a, a], vec![hash]).await;
+        assert_eq!(permit.guards.len(), 1);
+
+        // another sender isn't blocked
+        let other = permits.acquire([b], Vec::new()).await;
+        drop(other);
+
+        // the same sender waits until the permit is dropped
+        let mut same = Box::pin(permits.acquire([a], Vec::new()));
+        assert!(tokio::time::timeout(Duration::from_millis(10), &mut same).await.is_err());
+        drop(permit);
+        assert!(!permits.is_in_flight(&hash));
+        drop(same.await);
+
+        assert!(permits.senders.lock().is_empty());
+        assert!(permits.in_flight.lock().is_empty());
+    }
+}
@@ -0,0 +1,278 @@
+//! Cache of recently rejected external transactions.
+
+use crate::{
+    error::{InvalidPoolTransactionError, PoolError, PoolErrorKind, PreviouslyRejectedError},
+    metrics::RejectedTransactionsMetrics,
+    BlockInfo, RejectedTransactionsConfig,
+};
+use alloy_primitives::TxHash;
+use parking_lot::Mutex;
+use reth_primitives::InvalidTransactionError;
+use schnellru::{ByLength, LruMap};
+use std::time::{Duration, Instant};
+
+/// Why a transaction was rejected by the pool.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RejectionReason {
+    /// See [`InvalidPoolTransactionError::Underpriced`].
+    Underpriced,
+    /// See [`PoolErrorKind::FeeCapBelowMinimumProtocolFeeCap`].
+    FeeCapBelowMinimumProtocolFeeCap(u128),
+    /// The transaction is bad regardless of the state of the pool, see
+    /// [`PoolError::is_bad_transaction`]. Contains the message of the original error.
+    Invalid(String),
+}
+
+impl RejectionReason {
+    /// Returns the reason the transaction was rejected with, if it would be rejected again for the
+    /// same reason until it expires or fees decrease.
+    ///
+    /// Rejections that depend on the state of the sender, like a nonce gap, an overdraft or a
+    /// signer with bytecode, or on the state of the pool, like an underpriced replacement or a
+    /// full pool, are not remembered.
+    pub fn from_error(err: &PoolError) -> Option<Self> {
+        match &err.kind {
+            PoolErrorKind::InvalidTransaction(InvalidPoolTransactionError::Consensus(
+                InvalidTransactionError::SignerAccountHasBytecode,
+            )) => None,
+            PoolErrorKind::FeeCapBelowMinimumProtocolFeeCap(fee_cap) => {
+                Some(Self::FeeCapBelowMinimumProtocolFeeCap(*fee_cap))
+            }
+            PoolErrorKind::InvalidTransaction(InvalidPoolTransactionError::Underpriced) => {
+                Some(Self::Underpriced)
+            }
+            _ if err.is_bad_transaction() => Some(Self::Invalid(err.kind.to_string())),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` if the transaction was rejected because of its fees, and might be accepted
+    /// once fees decrease.
+    pub const fn is_fee_related(&self) -> bool {
+        matches!(self, Self::Underpriced)
+    }
+
+    /// Converts the reason into the error of the transaction with the given hash.
+    pub fn into_error(self, hash: TxHash) -> PoolError {
+        match self {
+            Self::Underpriced => PoolError::new(hash, InvalidPoolTransactionError::Underpriced),
+            Self::FeeCapBelowMinimumProtocolFeeCap(fee_cap) => {
+                PoolError::new(hash, PoolErrorKind::FeeCapBelowMinimumProtocolFeeCap(fee_cap))
+            }
+            Self::Invalid(message) => PoolError::new(
+                hash,
+                InvalidPoolTransactionError::Other(Box::new(PreviouslyRejectedError(message))),
+            ),
+        }
+    }
+}
+
+/// A rejected transaction remembered by [`RejectedTransactions`].
+#[derive(Debug)]
+struct RejectedTransaction {
+    reason: RejectionReason,
+    expires_at: Instant,
+}
+
+/// Bounded cache of recently rejected external transactions, keyed by hash.
+///
+/// Fee related rejections are kept apart from the others, so they can be cleared when the fees of
+/// the pending block decrease.
+#[derive(Debug)]
+pub(crate) struct RejectedTransactions {
+    /// Transactions rejected because of their fees.
+    fee_related: Mutex<LruMap<TxHash, RejectedTransaction, ByLength>>,
+    /// Transactions rejected as bad transactions.
+    invalid: Mutex<LruMap<TxHash, RejectedTransaction, ByLength>>,
+    /// Whether the cache is enabled.
+    enabled: bool,
+    /// How long a rejected transaction is remembered.
+    ttl: Duration,
+    metrics: RejectedTransactionsMetrics,
+}
+
+impl RejectedTransactions {
+    /// Creates a new cache with the given configuration.
+    pub(crate) fn new(config: RejectedTransactionsConfig) -> Self {
+        let RejectedTransactionsConfig { max_len, ttl } = config;
+        Self {
+            fee_related: Mutex::new(LruMap::new(ByLength::new(max_len))),
+            invalid: Mutex::new(LruMap::new(ByLength::new(max_len))),
+            enabled: max_len > 0 && !ttl.is_zero(),
+            ttl,
+            metrics: Default::default(),
+        }
+    }
+
+    /// Returns the error the transaction was recently rejected with, if any.
+    pub(crate) fn get(&self, hash: &TxHash) -> Option<PoolError> {
+        self.reason(hash).map(|reason| reason.into_error(*hash))
+    }
+
+    /// Returns `true` if the transaction was recently rejected.
+    pub(crate) fn contains(&self, hash: &TxHash) -> bool {
+        self.reason(hash).is_some()
+    }
+
+    /// Returns the reason the transaction was recently rejected with, and records the skipped
+    /// validation.
+    fn reason(&self, hash: &TxHash) -> Option<RejectionReason> {
+        if !self.enabled {
+            return None
+        }
+
+        let now = Instant::now();
+        let reason = [&self.fee_related, &self.invalid].into_iter().find_map(|entries| {
+            let mut entries = entries.lock();
+            let entry = entries.get(hash)?;
+            if entry.expires_at <= now {
+                entries.remove(hash);
+                return None
+            }
+            Some(entry.reason.clone())
+        })?;
+
+        self.metrics.rejected_cache_hits.increment(1);
+        Some(reason)
+    }
+
+    /// Remembers the transaction of the error, if it would be rejected again for the same reason.
+    pub(crate) fn insert(&self, err: &PoolError) {
+        if !self.enabled {
+            return
+        }
+        let Some(reason) = RejectionReason::from_error(err) else { return };
+
+        let entries = if reason.is_fee_related() { &self.fee_related } else { &self.invalid };
+        entries.lock().insert(
+            err.hash,
+            RejectedTransaction { reason, expires_at: Instant::now() + self.ttl },
+        );
+        self.update_len_metrics();
+    }
+
+    /// Forgets all fee related rejections if the fees of the pending block decreased, since those
+    /// transactions might be accepted now.
+    pub(crate) fn on_block_info_update(&self, previous: &BlockInfo, new: &BlockInfo) {
+        let base_fee_decreased = new.pending_basefee < previous.pending_basefee;
+        let blob_fee_decreased = matches!(
+            (previous.pending_blob_fee, new.pending_blob_fee),
+            (Some(previous), Some(new)) if new < previous
+        );
+        if !base_fee_decreased && !blob_fee_decreased {
+            return
+        }
+
+        let mut fee_related = self.fee_related.lock();
+        if fee_related.is_empty() {
+            return
+        }
+        fee_related.clear();
+        drop(fee_related);
+
+        self.metrics.rejected_cache_fee_invalidations.increment(1);
+        self.update_len_metrics();
+    }
+
+    fn update_len_metrics(&self) {
+        self.metrics.rejected_cache_fee_related.set(self.fee_related.lock().len() as f64);
+        self.metrics.rejected_cache_invalid.set(self.invalid.lock().len() as f64);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rejected() -> RejectedTransactions {
+        RejectedTransactions::new(RejectedTransactionsConfig::default())
+    }
+
+    #[test]
+    fn remembers_rejections() {
+        let rejected = rejected();
+
+        let underpriced =
+            PoolError::new(TxHash::random(), InvalidPoolTransactionError::Underpriced);
+        rejected.insert(&underpriced);
+        assert!(matches!(
+            rejected.get(&underpriced.hash).unwrap().kind,
+            PoolErrorKind::InvalidTransaction(InvalidPoolTransactionError::Underpriced)
+        ));
+
+        let invalid =
+            PoolError::new(TxHash::random(), InvalidPoolTransactionError::IntrinsicGasTooLow);
+        rejected.insert(&invalid);
+        let err = rejected.get(&invalid.hash).unwrap();
+        assert!(err.is_bad_transaction());
+        assert_eq!(err.kind.to_string(), "previously rejected: intrinsic gas too low");
+
+        // depends on the state of the sender
+        let overdraft = PoolError::new(
+            TxHash::random(),
+            InvalidPoolTransactionError::Overdraft {
+                cost: Default::default(),
+                balance: Default::default(),
+            },
+        );
+        rejected.insert(&overdraft);
+        assert!(!rejected.contains(&overdraft.hash));
+        let has_bytecode = PoolError::new(
+            TxHash::random(),
+            InvalidPoolTransactionError::Consensus(
+                InvalidTransactionError::SignerAccountHasBytecode,
+            ),
+        );
+        assert!(has_bytecode.is_bad_transaction());
+        rejected.insert(&has_bytecode);
+        assert!(!rejected.contains(&has_bytecode.hash));
+
+        // depends on the state of the pool
+        for kind in [PoolErrorKind::ReplacementUnderpriced, PoolErrorKind::DiscardedOnInsert] {
+            let err = PoolError::new(TxHash::random(), kind);
+            rejected.insert(&err);
+            assert!(!rejected.contains(&err.hash));
+        }
+    }
+
+    #[test]
+    fn fee_decrease_forgets_fee_related_rejections() {
+        let rejected = rejected();
+        let underpriced =
+            PoolError::new(TxHash::random(), InvalidPoolTransactionError::Underpriced);
+        let invalid =
+            PoolError::new(TxHash::random(), InvalidPoolTransactionError::IntrinsicGasTooLow);
+        rejected.insert(&underpriced);
+        rejected.insert(&invalid);
+
+        let block_info =
+            BlockInfo { pending_basefee: 10, pending_blob_fee: Some(10), ..Default::default() };
+
+        // fees increased
+        rejected
+            .on_block_info_update(&block_info, &BlockInfo { pending_basefee: 11, ..block_info });
+        assert!(rejected.contains(&underpriced.hash));
+
+        // blob fee decreased
+        rejected.on_block_info_update(
+            &block_info,
+            &BlockInfo { pending_blob_fee: Some(9), ..block_info },
+        );
+        assert!(!rejected.contains(&underpriced.hash));
+        assert!(rejected.contains(&invalid.hash));
+    }
+
+    #[test]
+    fn rejections_expire() {
+        let rejected = RejectedTransactions::new(RejectedTransactionsConfig {
+            ttl: Duration::from_millis(1),
+            ..Default::default()
+        });
+        let invalid =
+            PoolError::new(TxHash::random(), InvalidPoolTransactionError::IntrinsicGasTooLow);
+        rejected.insert(&invalid);
+
+        std::thread::sleep(Duration::from_millis(2));
+        assert!(!rejected.contains(&invalid.hash));
+    }
+}
@@ -424,6 +424,11 @@ impl<T: TransactionOrdering> TxPool<T> {
         self.basefee_pool.all().chain(self.queued_pool.all()).collect()
     }
 
+    /// Returns all transactions from the basefee pool
+    pub(crate) fn basefee_transactions(&self) -> Vec<Arc<ValidPoolTransaction<T::Transaction>>> {
+        self.basefee_pool.all().collect()
+    }
+
     /// Returns an iterator over all transactions from parked pools
     pub(crate) fn queued_transactions_iter(
         &self,
@@ -2751,6 +2756,7 @@ mod tests {
 
         assert!(pool.pending_pool.is_empty());
         assert_eq!(pool.basefee_pool.len(), 1);
+        assert_eq!(pool.basefee_transactions()[0].hash(), tx.hash());
 
         assert_eq!(pool.all_transactions.txs.get(&id).unwrap().subpool, SubPool::BaseFee)
     }
@@ -29,6 +29,7 @@ use rand::{
     prelude::Distribution,
 };
 use reth_primitives::{
+    fee_math,
     transaction::{SignedTransactionIntoRecoveredExt, TryFromRecoveredTransactionError},
     PooledTransaction, PooledTransactionsElementEcRecovered, RecoveredTx, Transaction,
     TransactionSigned, TxType,
@@ -762,28 +763,11 @@ impl PoolTransaction for MockTransaction {
 
     /// Calculates the effective tip per gas given a base fee.
     fn effective_tip_per_gas(&self, base_fee: u64) -> Option<u128> {
-        // Convert base_fee to u128 for precision in calculations
-        let base_fee = base_fee as u128;
-
-        // Retrieve the maximum fee per gas
-        let max_fee_per_gas = self.max_fee_per_gas();
-
-        // If the maximum fee per gas is less than the base fee, return None
-        if max_fee_per_gas < base_fee {
-            return None
-        }
-
-        // Calculate the fee by subtracting the base fee from the maximum fee per gas
-        let fee = max_fee_per_gas - base_fee;
-
-        // If the maximum priority fee per gas is available, return the minimum of fee and priority
-        // fee
-        if let Some(priority_fee) = self.max_priority_fee_per_gas() {
-            return Some(fee.min(priority_fee))
-        }
-
-        // Otherwise, return the calculated fee
-        Some(fee)
+        fee_math::effective_gas_tip(
+            self.max_fee_per_gas(),
+            self.max_priority_fee_per_gas(),
+            base_fee,
+        )
     }
 
     /// Returns the priority fee or gas price based on the transaction type.
@@ -303,6 +303,14 @@ pub trait TransactionPool: Send + Sync + Clone {
     /// Consumer: RPC
     fn queued_transactions(&self) -> Vec<Arc<ValidPoolTransaction<Self::Transaction>>>;
 
+    /// Returns all transactions that are executable, but can't be included in the next block
+    /// because they don't pay its base fee, see [`SubPool::BaseFee`](crate::SubPool::BaseFee).
+    ///
+    /// These are a subset of [Self::queued_transactions].
+    ///
+    /// Consumer: RPC
+    fn basefee_transactions(&self) -> Vec<Arc<ValidPoolTransaction<Self::Transaction>>>;
+
     /// Returns all transactions that are currently in the pool grouped by whether they are ready
     /// for inclusion in the next block or not.
     ///
@@ -346,6 +354,14 @@ pub trait TransactionPool: Send + Sync + Clone {
     where
         A: HandleMempoolData;
 
+    /// Removes all transactions from the given set that were recently rejected by the pool and
+    /// would be rejected again, so they don't need to be fetched or validated.
+    ///
+    /// Consumer: P2P
+    fn retain_not_rejected<A>(&self, announcement: &mut A)
+    where
+        A: HandleMempoolData;
+
     /// Returns if the transaction for the given hash is already included in this pool.
     fn contains(&self, tx_hash: &TxHash) -> bool {
         self.get(tx_hash).is_some()
@@ -6,6 +6,7 @@ use crate::{
     error::{
         Eip4844PoolTransactionError, Eip7702PoolTransactionError, InvalidPoolTransactionError,
     },
+    metrics::TxValidationMetrics,
     traits::TransactionOrigin,
     validate::{ValidTransaction, ValidationTask, MAX_INIT_CODE_BYTE_SIZE},
     EthBlobTransactionSidecar, EthPoolTransaction, LocalTransactionConfig,
@@ -22,19 +23,22 @@ use alloy_eips::{
     eip1559::ETHEREUM_BLOCK_GAS_LIMIT,
     eip4844::{env_settings::EnvKzgSettings, MAX_BLOBS_PER_BLOCK},
 };
+use alloy_primitives::Address;
 use reth_chainspec::{ChainSpec, EthereumHardforks};
-use reth_primitives::{InvalidTransactionError, SealedBlock};
+use reth_primitives::{Account, InvalidTransactionError, SealedBlock};
 use reth_primitives_traits::{BlockBody, GotExpected};
-use reth_storage_api::{StateProvider, StateProviderFactory};
+use reth_storage_api::{errors::provider::ProviderError, StateProvider, StateProviderFactory};
 use reth_tasks::TaskSpawner;
 use std::{
+    collections::{HashMap, HashSet},
     marker::PhantomData,
     sync::{
         atomic::{AtomicBool, AtomicU64},
         Arc,
     },
+    time::Instant,
 };
-use tokio::sync::Mutex;
+use tokio::sync::{oneshot, Mutex};
 
 /// Validator for Ethereum transactions.
 #[derive(Debug, Clone)]
@@ -96,7 +100,7 @@ where
         origin: TransactionOrigin,
         transaction: Self::Transaction,
     ) -> TransactionValidationOutcome<Self::Transaction> {
-        self.validate_one(origin, transaction)
+        self.inner.validate_one_batched(origin, transaction).await
     }
 
     async fn validate_transactions(
@@ -157,10 +161,26 @@ pub(crate) struct EthTransactionValidatorInner<Client, T> {
     local_transactions_config: LocalTransactionConfig,
     /// Maximum size in bytes a single transaction can have in order to be accepted into the pool.
     max_tx_input_bytes: usize,
+    /// Senders of concurrently validated transactions whose state is read in the next batch.
+    pending_state_reads: parking_lot::Mutex<Vec<PendingStateRead>>,
+    /// Validation metrics
+    metrics: TxValidationMetrics,
     /// Marker for the transaction type
     _marker: PhantomData<T>,
 }
 
+/// A queued sender state read and the channel its result is sent to.
+type PendingStateRead = (Address, oneshot::Sender<Result<SenderState, ProviderError>>);
+
+/// The state of a transaction sender that is required for the stateful checks.
+#[derive(Debug, Clone, Copy)]
+struct SenderState {
+    /// The account of the sender, default if it doesn't exist.
+    account: Account,
+    /// Whether the sender has bytecode other than an EIP-7702 delegation.
+    has_bytecode: bool,
+}
+
 // === impl EthTransactionValidatorInner ===
 
 impl<Client, Tx> EthTransactionValidatorInner<Client, Tx> {
@@ -175,15 +195,15 @@ where
     Client: StateProviderFactory,
     Tx: EthPoolTransaction,
 {
-    /// Validates a single transaction using an optional cached state provider.
-    /// If no provider is passed, a new one will be created. This allows reusing
-    /// the same provider across multiple txs.
-    fn validate_one_with_provider(
+    /// Performs all checks that don't require state: transaction type, size, gas limit, fee caps,
+    /// chain id, intrinsic gas and the light blob checks.
+    ///
+    /// Returns the transaction if it passed all checks, or the outcome it failed with.
+    fn validate_stateless(
         &self,
         origin: TransactionOrigin,
-        mut transaction: Tx,
-        maybe_state: &mut Option<Box<dyn StateProvider>>,
-    ) -> TransactionValidationOutcome<Tx> {
+        transaction: Tx,
+    ) -> Result<Tx, TransactionValidationOutcome<Tx>> {
         // Checks for tx_type
         match transaction.tx_type() {
             LEGACY_TX_TYPE_ID => {
@@ -192,61 +212,61 @@ where
             EIP2930_TX_TYPE_ID => {
                 // Accept only legacy transactions until EIP-2718/2930 activates
                 if !self.eip2718 {
-                    return TransactionValidationOutcome::Invalid(
+                    return Err(TransactionValidationOutcome::Invalid(
                         transaction,
                         InvalidTransactionError::Eip2930Disabled.into(),
-                    )
+                    ))
                 }
             }
             EIP1559_TX_TYPE_ID => {
                 // Reject dynamic fee transactions until EIP-1559 activates.
                 if !self.eip1559 {
-                    return TransactionValidationOutcome::Invalid(
+                    return Err(TransactionValidationOutcome::Invalid(
                         transaction,
                         InvalidTransactionError::Eip1559Disabled.into(),
-                    )
+                    ))
                 }
             }
             EIP4844_TX_TYPE_ID => {
                 // Reject blob transactions.
                 if !self.eip4844 {
-                    return TransactionValidationOutcome::Invalid(
+                    return Err(TransactionValidationOutcome::Invalid(
                         transaction,
                         InvalidTransactionError::Eip4844Disabled.into(),
-                    )
+                    ))
                 }
             }
             EIP7702_TX_TYPE_ID => {
                 // Reject EIP-7702 transactions.
                 if !self.eip7702 {
-                    return TransactionValidationOutcome::Invalid(
+                    return Err(TransactionValidationOutcome::Invalid(
                         transaction,
                         InvalidTransactionError::Eip7702Disabled.into(),
-                    )
+                    ))
                 }
             }
 
             _ => {
-                return TransactionValidationOutcome::Invalid(
+                return Err(TransactionValidationOutcome::Invalid(
                     transaction,
                     InvalidTransactionError::TxTypeNotSupported.into(),
-                )
+                ))
             }
         };
 
         // Reject transactions over defined size to prevent DOS attacks
         let tx_input_len = transaction.input().len();
         if tx_input_len > self.max_tx_input_bytes {
-            return TransactionValidationOutcome::Invalid(
+            return Err(TransactionValidationOutcome::Invalid(
                 transaction,
                 InvalidPoolTransactionError::OversizedData(tx_input_len, self.max_tx_input_bytes),
-            )
+            ))
         }
 
         // Check whether the init code size has been exceeded.
         if self.fork_tracker.is_shanghai_activated() {
             if let Err(err) = transaction.ensure_max_init_code_size(MAX_INIT_CODE_BYTE_SIZE) {
-                return TransactionValidationOutcome::Invalid(transaction, err)
+                return Err(TransactionValidationOutcome::Invalid(transaction, err))
             }
         }
 
@@ -254,21 +274,21 @@ where
         let transaction_gas_limit = transaction.gas_limit();
         let block_gas_limit = self.max_gas_limit();
         if transaction_gas_limit > block_gas_limit {
-            return TransactionValidationOutcome::Invalid(
+            return Err(TransactionValidationOutcome::Invalid(
                 transaction,
                 InvalidPoolTransactionError::ExceedsGasLimit(
                     transaction_gas_limit,
                     block_gas_limit,
                 ),
-            )
+            ))
         }
 
         // Ensure max_priority_fee_per_gas (if EIP1559) is less than max_fee_per_gas if any.
         if transaction.max_priority_fee_per_gas() > Some(transaction.max_fee_per_gas()) {
-            return TransactionValidationOutcome::Invalid(
+            return Err(TransactionValidationOutcome::Invalid(
                 transaction,
                 InvalidTransactionError::TipAboveFeeCap.into(),
-            )
+            ))
         }
 
         // Drop non-local transactions with a fee lower than the configured fee for acceptance into
@@ -277,67 +297,67 @@ where
             transaction.is_eip1559() &&
             transaction.max_priority_fee_per_gas() < self.minimum_priority_fee
         {
-            return TransactionValidationOutcome::Invalid(
+            return Err(TransactionValidationOutcome::Invalid(
                 transaction,
                 InvalidPoolTransactionError::Underpriced,
-            )
+            ))
         }
 
         // Checks for chainid
         if let Some(chain_id) = transaction.chain_id() {
             if chain_id != self.chain_id() {
-                return TransactionValidationOutcome::Invalid(
+                return Err(TransactionValidationOutcome::Invalid(
                     transaction,
                     InvalidTransactionError::ChainIdMismatch.into(),
-                )
+                ))
             }
         }
 
         if transaction.is_eip7702() {
             // Cancun fork is required for 7702 txs
             if !self.fork_tracker.is_prague_activated() {
-                return TransactionValidationOutcome::Invalid(
+                return Err(TransactionValidationOutcome::Invalid(
                     transaction,
                     InvalidTransactionError::TxTypeNotSupported.into(),
-                )
+                ))
             }
 
             if transaction.authorization_count() == 0 {
-                return TransactionValidationOutcome::Invalid(
+                return Err(TransactionValidationOutcome::Invalid(
                     transaction,
                     Eip7702PoolTransactionError::MissingEip7702AuthorizationList.into(),
-                )
+                ))
             }
         }
 
         if let Err(err) = ensure_intrinsic_gas(&transaction, &self.fork_tracker) {
-            return TransactionValidationOutcome::Invalid(transaction, err)
+            return Err(TransactionValidationOutcome::Invalid(transaction, err))
         }
 
         // light blob tx pre-checks
         if transaction.is_eip4844() {
             // Cancun fork is required for blob txs
             if !self.fork_tracker.is_cancun_activated() {
-                return TransactionValidationOutcome::Invalid(
+                return Err(TransactionValidationOutcome::Invalid(
                     transaction,
                     InvalidTransactionError::TxTypeNotSupported.into(),
-                )
+                ))
             }
 
             let blob_count = transaction.blob_count();
             if blob_count == 0 {
                 // no blobs
-                return TransactionValidationOutcome::Invalid(
+                return Err(TransactionValidationOutcome::Invalid(
                     transaction,
                     InvalidPoolTransactionError::Eip4844(
                         Eip4844PoolTransactionError::NoEip4844Blobs,
                     ),
-                )
+                ))
             }
 
             if blob_count > MAX_BLOBS_PER_BLOCK {
                 // too many blobs
-                return TransactionValidationOutcome::Invalid(
+                return Err(TransactionValidationOutcome::Invalid(
                     transaction,
                     InvalidPoolTransactionError::Eip4844(
                         Eip4844PoolTransactionError::TooManyEip4844Blobs {
@@ -345,59 +365,112 @@ where
                             permitted: MAX_BLOBS_PER_BLOCK,
                         },
                     ),
-                )
+                ))
             }
         }
 
-        // If we don't have a state provider yet, fetch the latest state
-        if maybe_state.is_none() {
-            match self.client.latest() {
-                Ok(new_state) => {
-                    *maybe_state = Some(new_state);
-                }
-                Err(err) => {
-                    return TransactionValidationOutcome::Error(*transaction.hash(), Box::new(err))
-                }
+        Ok(transaction)
+    }
+
+    /// Reads the state of the given sender that is required by [`Self::validate_stateful`].
+    fn read_sender_state(
+        &self,
+        state: &dyn StateProvider,
+        sender: &Address,
+    ) -> Result<SenderState, ProviderError> {
+        let account = state.basic_account(sender)?.unwrap_or_default();
+
+        // Unless Prague is active, the signer account shouldn't have bytecode.
+        //
+        // If Prague is active, only EIP-7702 bytecode is allowed for the sender.
+        let has_bytecode = if let Some(code_hash) = &account.bytecode_hash {
+            let is_eip7702 = self.fork_tracker.is_prague_activated() &&
+                state.bytecode_by_hash(code_hash)?.unwrap_or_default().is_eip7702();
+            !is_eip7702
+        } else {
+            false
+        };
+
+        Ok(SenderState { account, has_bytecode })
+    }
+
+    /// Reads the state of all given senders using a single state provider.
+    ///
+    /// Failing to read the state of a sender only fails the lookups of that sender.
+    fn read_sender_states(
+        &self,
+        senders: impl IntoIterator<Item = Address>,
+    ) -> HashMap<Address, Result<SenderState, ProviderError>> {
+        let senders = senders.into_iter().collect::<HashSet<_>>();
+        if senders.is_empty() {
+            return HashMap::new()
+        }
+
+        let start = Instant::now();
+        let state = self.client.latest();
+        let states = senders
+            .into_iter()
+            .map(|sender| {
+                let sender_state = match &state {
+                    Ok(state) => self.read_sender_state(state, &sender),
+                    Err(err) => Err(err.clone()),
+                };
+                (sender, sender_state)
+            })
+            .collect::<HashMap<_, _>>();
+
+        self.metrics.state_batch_size.record(states.len() as f64);
+        self.metrics.state_read_duration.record(start.elapsed());
+        states
+    }
+
+    /// Reads the state of the given sender in a batch with the senders of all transactions that
+    /// are validated concurrently.
+    ///
+    /// Every caller queues its sender and yields once, so that concurrently polled validations
+    /// can queue theirs as well. The first caller that resumes reads the state of all queued
+    /// senders with a single state provider and answers all of them.
+    async fn batched_sender_state(&self, sender: Address) -> Result<SenderState, ProviderError> {
+        let (tx, rx) = oneshot::channel();
+        self.pending_state_reads.lock().push((sender, tx));
+
+        tokio::task::yield_now().await;
+
+        let pending = std::mem::take(&mut *self.pending_state_reads.lock());
+        if !pending.is_empty() {
+            let states = self.read_sender_states(pending.iter().map(|(sender, _)| *sender));
+            for (sender, tx) in pending {
+                let _ = tx.send(states[&sender].clone());
             }
         }
 
-        let state = maybe_state.as_deref().expect("provider is set");
+        rx.await.expect("queued sender state reads are always answered")
+    }
 
-        // Use provider to get account info
-        let account = match state.basic_account(transaction.sender_ref()) {
-            Ok(account) => account.unwrap_or_default(),
+    /// Performs all checks that require the state of the sender: bytecode, nonce, balance and the
+    /// heavy blob checks.
+    ///
+    /// Expects the transaction to have passed [`Self::validate_stateless`].
+    fn validate_stateful(
+        &self,
+        origin: TransactionOrigin,
+        mut transaction: Tx,
+        sender_state: Result<SenderState, ProviderError>,
+    ) -> TransactionValidationOutcome<Tx> {
+        let SenderState { account, has_bytecode } = match sender_state {
+            Ok(sender_state) => sender_state,
             Err(err) => {
                 return TransactionValidationOutcome::Error(*transaction.hash(), Box::new(err))
             }
         };
 
-        // Unless Prague is active, the signer account shouldn't have bytecode.
-        //
-        // If Prague is active, only EIP-7702 bytecode is allowed for the sender.
-        //
-        // Any other case means that the account is not an EOA, and should not be able to send
-        // transactions.
-        if let Some(code_hash) = &account.bytecode_hash {
-            let is_eip7702 = if self.fork_tracker.is_prague_activated() {
-                match state.bytecode_by_hash(code_hash) {
-                    Ok(bytecode) => bytecode.unwrap_or_default().is_eip7702(),
-                    Err(err) => {
-                        return TransactionValidationOutcome::Error(
-                            *transaction.hash(),
-                            Box::new(err),
-                        )
-                    }
-                }
-            } else {
-                false
-            };
-
-            if !is_eip7702 {
-                return TransactionValidationOutcome::Invalid(
-                    transaction,
-                    InvalidTransactionError::SignerAccountHasBytecode.into(),
-                )
-            }
+        // Any sender with bytecode other than an EIP-7702 delegation is not an EOA, and should not
+        // be able to send transactions.
+        if has_bytecode {
+            return TransactionValidationOutcome::Invalid(
+                transaction,
+                InvalidTransactionError::SignerAccountHasBytecode.into(),
+            )
         }
 
         let tx_nonce = transaction.nonce();
@@ -492,20 +565,69 @@ where
         origin: TransactionOrigin,
         transaction: Tx,
     ) -> TransactionValidationOutcome<Tx> {
-        let mut provider = None;
-        self.validate_one_with_provider(origin, transaction, &mut provider)
+        self.validate_batch(vec![(origin, transaction)]).pop().expect("one outcome per transaction")
+    }
+
+    /// Validates a single transaction, its sender state is read in a batch with the transactions
+    /// that are validated concurrently.
+    ///
+    /// See also [`Self::batched_sender_state`].
+    async fn validate_one_batched(
+        &self,
+        origin: TransactionOrigin,
+        transaction: Tx,
+    ) -> TransactionValidationOutcome<Tx> {
+        let start = Instant::now();
+        let transaction = self.validate_stateless(origin, transaction);
+        self.metrics.stateless_duration.record(start.elapsed());
+        let transaction = match transaction {
+            Ok(transaction) => transaction,
+            Err(outcome) => return outcome,
+        };
+
+        let sender_state = self.batched_sender_state(*transaction.sender_ref()).await;
+
+        let start = Instant::now();
+        let outcome = self.validate_stateful(origin, transaction, sender_state);
+        self.metrics.stateful_duration.record(start.elapsed());
+        outcome
     }
 
     /// Validates all given transactions.
+    ///
+    /// All transactions are checked statelessly first, the state of the senders of the remaining
+    /// transactions is then read in a single batch.
     fn validate_batch(
         &self,
         transactions: Vec<(TransactionOrigin, Tx)>,
     ) -> Vec<TransactionValidationOutcome<Tx>> {
-        let mut provider = None;
-        transactions
+        let start = Instant::now();
+        let transactions = transactions
             .into_iter()
-            .map(|(origin, tx)| self.validate_one_with_provider(origin, tx, &mut provider))
-            .collect()
+            .map(|(origin, tx)| (origin, self.validate_stateless(origin, tx)))
+            .collect::<Vec<_>>();
+        self.metrics.stateless_duration.record(start.elapsed());
+
+        let sender_states = self.read_sender_states(
+            transactions.iter().filter_map(|(_, tx)| tx.as_ref().ok().map(|tx| tx.sender())),
+        );
+
+        let start = Instant::now();
+        let outcomes = transactions
+            .into_iter()
+            .map(|(origin, tx)| match tx {
+                Ok(tx) => {
+                    let sender_state = sender_states
+                        .get(tx.sender_ref())
+                        .cloned()
+                        .expect("state of all senders is read");
+                    self.validate_stateful(origin, tx, sender_state)
+                }
+                Err(outcome) => outcome,
+            })
+            .collect();
+        self.metrics.stateful_duration.record(start.elapsed());
+        outcomes
     }
 
     fn on_new_head_block<T: BlockHeader>(&self, new_tip_block: &T) {
@@ -764,6 +886,8 @@ impl EthTransactionValidatorBuilder {
             kzg_settings,
             local_transactions_config,
             max_tx_input_bytes,
+            pending_state_reads: Default::default(),
+            metrics: Default::default(),
             _marker: Default::default(),
         };
 
@@ -878,8 +1002,8 @@ pub fn ensure_intrinsic_gas<T: EthPoolTransaction>(
 mod tests {
     use super::*;
     use crate::{
-        blobstore::InMemoryBlobStore, error::PoolErrorKind, traits::PoolTransaction,
-        CoinbaseTipOrdering, EthPooledTransaction, Pool, TransactionPool,
+        blobstore::InMemoryBlobStore, error::PoolErrorKind, test_utils::MockTransaction,
+        traits::PoolTransaction, CoinbaseTipOrdering, EthPooledTransaction, Pool, TransactionPool,
     };
     use alloy_eips::eip2718::Decodable2718;
     use alloy_primitives::{hex, U256};
@@ -966,4 +1090,68 @@ mod tests {
         let tx = pool.get(transaction.hash());
         assert!(tx.is_none());
     }
+
+    #[test]
+    fn validate_all_attributes_outcomes() {
+        let provider = MockEthProvider::default();
+
+        let funded = MockTransaction::eip1559().with_gas_limit(21_000);
+        provider.add_account(funded.sender(), ExtendedAccount::new(0, U256::MAX));
+        let unfunded = MockTransaction::eip1559().with_gas_limit(21_000);
+        let below_intrinsic_gas = MockTransaction::eip1559();
+
+        let validator = EthTransactionValidatorBuilder::new(MAINNET.clone())
+            .build(provider, InMemoryBlobStore::default());
+
+        let outcomes = validator.validate_all(vec![
+            (TransactionOrigin::External, funded.clone()),
+            (TransactionOrigin::External, unfunded),
+            (TransactionOrigin::External, below_intrinsic_gas),
+            (TransactionOrigin::External, funded.next()),
+        ]);
+
+        assert_eq!(outcomes.len(), 4);
+        assert!(outcomes[0].is_valid());
+        assert!(matches!(
+            outcomes[1],
+            TransactionValidationOutcome::Invalid(
+                _,
+                InvalidPoolTransactionError::Consensus(InvalidTransactionError::InsufficientFunds(
+                    _
+                ))
+            )
+        ));
+        assert!(matches!(
+            outcomes[2],
+            TransactionValidationOutcome::Invalid(
+                _,
+                InvalidPoolTransactionError::IntrinsicGasTooLow
+            )
+        ));
+        assert!(outcomes[3].is_valid());
+    }
+
+    #[tokio::test]
+    async fn validate_concurrently_in_batch() {
+        let provider = MockEthProvider::default();
+        let transactions = (0..10)
+            .map(|_| {
+                let transaction = MockTransaction::eip1559().with_gas_limit(21_000);
+                provider.add_account(transaction.sender(), ExtendedAccount::new(0, U256::MAX));
+                transaction
+            })
+            .collect::<Vec<_>>();
+
+        let validator = EthTransactionValidatorBuilder::new(MAINNET.clone())
+            .build(provider, InMemoryBlobStore::default());
+
+        let outcomes =
+            futures_util::future::join_all(transactions.into_iter().map(|transaction| {
+                validator.validate_transaction(TransactionOrigin::External, transaction)
+            }))
+            .await;
+
+        assert!(outcomes.iter().all(|outcome| outcome.is_valid()));
+        assert!(validator.inner.pending_state_reads.lock().is_empty());
+    }
 }
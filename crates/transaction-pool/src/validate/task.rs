@@ -2,16 +2,26 @@
 
 use crate::{
     blobstore::BlobStore,
+    metrics::TxValidationMetrics,
     validate::{EthTransactionValidatorBuilder, TransactionValidatorError},
     EthTransactionValidator, PoolTransaction, TransactionOrigin, TransactionValidationOutcome,
     TransactionValidator,
 };
-use futures_util::{lock::Mutex, StreamExt};
+use futures_util::{
+    future::{select, Either},
+    lock::Mutex,
+    stream::FuturesUnordered,
+    StreamExt,
+};
 use reth_chainspec::ChainSpec;
 use reth_primitives::SealedBlock;
 use reth_primitives_traits::{BlockBody, BlockHeader};
 use reth_tasks::TaskSpawner;
-use std::{future::Future, pin::Pin, sync::Arc};
+use std::{
+    future::Future,
+    pin::{pin, Pin},
+    sync::Arc,
+};
 use tokio::{
     sync,
     sync::{mpsc, oneshot},
@@ -24,6 +34,9 @@ type ValidationFuture = Pin<Box<dyn Future<Output = ()> + Send>>;
 /// Represents a stream of validation futures.
 type ValidationStream = ReceiverStream<ValidationFuture>;
 
+/// Maximum number of validation jobs a [`ValidationTask`] executes concurrently.
+const MAX_CONCURRENT_VALIDATION_JOBS: usize = 128;
+
 /// A service that performs validation jobs.
 ///
 /// This listens for incoming validation jobs and executes them.
@@ -32,6 +45,7 @@ type ValidationStream = ReceiverStream<ValidationFuture>;
 #[derive(Clone)]
 pub struct ValidationTask {
     validation_jobs: Arc<Mutex<ValidationStream>>,
+    metrics: TxValidationMetrics,
 }
 
 impl ValidationTask {
@@ -43,16 +57,47 @@ impl ValidationTask {
 
     /// Creates a new task with the given receiver.
     pub fn with_receiver(jobs: mpsc::Receiver<Pin<Box<dyn Future<Output = ()> + Send>>>) -> Self {
-        Self { validation_jobs: Arc::new(Mutex::new(ReceiverStream::new(jobs))) }
+        Self {
+            validation_jobs: Arc::new(Mutex::new(ReceiverStream::new(jobs))),
+            metrics: Default::default(),
+        }
     }
 
     /// Executes all new validation jobs that come in.
     ///
+    /// New jobs are picked up while previous ones are still executing, up to
+    /// [`MAX_CONCURRENT_VALIDATION_JOBS`], which allows the validator to batch work across them,
+    /// e.g. [`EthTransactionValidator`] reads the state of the senders of concurrently executing
+    /// jobs with a single state provider.
+    ///
     /// This will run as long as the channel is alive and is expected to be spawned as a task.
     pub async fn run(self) {
-        while let Some(task) = self.validation_jobs.lock().await.next().await {
-            task.await;
+        let mut jobs = FuturesUnordered::<ValidationFuture>::new();
+        loop {
+            let job = if jobs.is_empty() {
+                self.next_job().await
+            } else if jobs.len() < MAX_CONCURRENT_VALIDATION_JOBS {
+                match select(pin!(self.next_job()), jobs.next()).await {
+                    Either::Left((job, _)) => job,
+                    Either::Right(_) => continue,
+                }
+            } else {
+                jobs.next().await;
+                continue
+            };
+
+            let Some(job) = job else { break };
+            jobs.push(job);
+            self.metrics.job_batch_size.record(jobs.len() as f64);
         }
+
+        // The channel is closed, finish the remaining jobs
+        while jobs.next().await.is_some() {}
+    }
+
+    /// Returns the next validation job, or `None` if the channel is closed.
+    async fn next_job(&self) -> Option<ValidationFuture> {
+        self.validation_jobs.lock().await.next().await
     }
 }
 
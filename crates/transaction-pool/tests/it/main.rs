@@ -8,6 +8,10 @@ mod evict;
 mod listeners;
 #[cfg(feature = "test-utils")]
 mod pending;
+#[cfg(feature = "test-utils")]
+mod permits;
+#[cfg(feature = "test-utils")]
+mod rejected;
 
 mod best;
 
@@ -0,0 +1,141 @@
+//! Sender permits tests.
+
+use alloy_eips::eip1559::MIN_PROTOCOL_BASE_FEE;
+use alloy_primitives::{Address, TxHash};
+use futures_util::{future::join_all, FutureExt, StreamExt};
+use reth_transaction_pool::{
+    blobstore::InMemoryBlobStore,
+    error::PoolErrorKind,
+    noop::MockTransactionValidator,
+    test_utils::{MockOrdering, MockTransaction},
+    FullTransactionEvent, Pool, PoolTransaction, TransactionOrigin, TransactionPool,
+    TransactionPoolExt, TransactionValidationOutcome, TransactionValidator,
+};
+use std::time::Duration;
+
+const TXS: u64 = 100;
+
+/// A [`MockTransactionValidator`] that takes longer to validate transactions with lower nonces,
+/// so that concurrent validations of consecutive nonces finish in reverse order.
+#[derive(Debug, Clone, Default)]
+struct SlowValidator {
+    inner: MockTransactionValidator<MockTransaction>,
+    /// Sender whose transactions are never validated in time.
+    stuck_sender: Option<Address>,
+}
+
+impl TransactionValidator for SlowValidator {
+    type Transaction = MockTransaction;
+
+    async fn validate_transaction(
+        &self,
+        origin: TransactionOrigin,
+        transaction: Self::Transaction,
+    ) -> TransactionValidationOutcome<Self::Transaction> {
+        let delay = if self.stuck_sender == Some(transaction.sender()) {
+            Duration::from_secs(60)
+        } else {
+            Duration::from_micros(TXS.saturating_sub(transaction.nonce()) * 50)
+        };
+        tokio::time::sleep(delay).await;
+        self.inner.validate_transaction(origin, transaction).await
+    }
+}
+
+fn pool(validator: SlowValidator) -> Pool<SlowValidator, MockOrdering, InMemoryBlobStore> {
+    let pool = Pool::new(
+        validator,
+        MockOrdering::default(),
+        InMemoryBlobStore::default(),
+        Default::default(),
+    );
+    let mut block_info = pool.block_info();
+    block_info.pending_basefee = MIN_PROTOCOL_BASE_FEE;
+    pool.set_block_info(block_info);
+    pool
+}
+
+fn sorted_pending(pool: &Pool<SlowValidator, MockOrdering, InMemoryBlobStore>) -> Vec<TxHash> {
+    let mut pending = pool.pending_transactions();
+    pending.sort_by_key(|tx| tx.nonce());
+    pending.iter().map(|tx| *tx.hash()).collect()
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn concurrent_transactions_of_sender_are_added_in_order() {
+    let first = MockTransaction::eip1559();
+    let transactions = std::iter::successors(Some(first), |tx| Some(tx.next()))
+        .take(TXS as usize)
+        .collect::<Vec<_>>();
+
+    let sequential = pool(SlowValidator::default());
+    for tx in &transactions {
+        sequential.add_transaction(TransactionOrigin::External, tx.clone()).await.unwrap();
+    }
+
+    let concurrent = pool(SlowValidator::default());
+    let mut events = concurrent.all_transactions_event_listener();
+    let results = join_all(
+        transactions
+            .iter()
+            .map(|tx| concurrent.add_transaction(TransactionOrigin::External, tx.clone())),
+    )
+    .await;
+    assert!(results.iter().all(Result::is_ok));
+
+    // the same pool as adding them one by one
+    assert_eq!(sorted_pending(&concurrent), sorted_pending(&sequential));
+    assert!(concurrent.queued_transactions().is_empty());
+
+    // every transaction went straight to pending, without being queued, replaced or discarded
+    let mut pending = Vec::new();
+    while let Some(Some(event)) = events.next().now_or_never() {
+        match event {
+            FullTransactionEvent::Pending(hash) => pending.push(hash),
+            event => panic!("unexpected event {event:?}"),
+        }
+    }
+    assert_eq!(pending, transactions.iter().map(|tx| *tx.hash()).collect::<Vec<_>>());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn transactions_in_flight_are_not_added_twice() {
+    let pool = pool(SlowValidator::default());
+    let tx = MockTransaction::eip1559();
+
+    let (first, second) = tokio::join!(
+        pool.add_transaction(TransactionOrigin::External, tx.clone()),
+        pool.add_transaction(TransactionOrigin::External, tx.clone())
+    );
+    assert_eq!(first.unwrap(), *tx.hash());
+    assert!(matches!(second.unwrap_err().kind, PoolErrorKind::AlreadyImported));
+
+    // duplicates within a batch
+    let other = MockTransaction::eip1559();
+    let results = pool
+        .add_transactions(TransactionOrigin::External, vec![other.clone(), other.clone()])
+        .await;
+    assert_eq!(results[0].as_ref().unwrap(), other.hash());
+    assert!(matches!(results[1].as_ref().unwrap_err().kind, PoolErrorKind::AlreadyImported));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn unrelated_senders_are_not_serialized() {
+    let stuck = MockTransaction::eip1559();
+    let pool = pool(SlowValidator { stuck_sender: Some(stuck.sender()), ..Default::default() });
+
+    let stuck_add = pool.add_transaction(TransactionOrigin::External, stuck.clone());
+    let other = MockTransaction::eip1559();
+    let other_add = async {
+        tokio::time::timeout(
+            Duration::from_secs(5),
+            pool.add_transaction(TransactionOrigin::External, other.clone()),
+        )
+        .await
+    };
+
+    tokio::select! {
+        _ = stuck_add => panic!("stuck sender was added"),
+        result = other_add => assert_eq!(result.unwrap().unwrap(), *other.hash()),
+    }
+}
@@ -0,0 +1,104 @@
+//! Rejected transactions tests.
+
+use alloy_eips::eip1559::MIN_PROTOCOL_BASE_FEE;
+use reth_transaction_pool::{
+    blobstore::InMemoryBlobStore,
+    error::{InvalidPoolTransactionError, PoolErrorKind},
+    noop::MockTransactionValidator,
+    test_utils::{MockOrdering, MockTransaction},
+    Pool, PoolTransaction, TransactionOrigin, TransactionPool, TransactionPoolExt,
+    TransactionValidationOutcome, TransactionValidator,
+};
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+
+/// A [`MockTransactionValidator`] that counts the validated transactions and rejects transactions
+/// without a priority fee as underpriced.
+#[derive(Debug, Clone, Default)]
+struct CountingValidator {
+    inner: MockTransactionValidator<MockTransaction>,
+    validated: Arc<AtomicUsize>,
+}
+
+impl CountingValidator {
+    fn validated(&self) -> usize {
+        self.validated.load(Ordering::Relaxed)
+    }
+}
+
+impl TransactionValidator for CountingValidator {
+    type Transaction = MockTransaction;
+
+    async fn validate_transaction(
+        &self,
+        origin: TransactionOrigin,
+        transaction: Self::Transaction,
+    ) -> TransactionValidationOutcome<Self::Transaction> {
+        self.validated.fetch_add(1, Ordering::Relaxed);
+        if transaction.get_priority_fee() == Some(0) {
+            return TransactionValidationOutcome::Invalid(
+                transaction,
+                InvalidPoolTransactionError::Underpriced,
+            )
+        }
+        self.inner.validate_transaction(origin, transaction).await
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn rejected_transactions_are_not_validated_again() {
+    let validator = CountingValidator::default();
+    let pool = Pool::new(
+        validator.clone(),
+        MockOrdering::default(),
+        InMemoryBlobStore::default(),
+        Default::default(),
+    );
+    let mut block_info = pool.block_info();
+    block_info.pending_basefee = MIN_PROTOCOL_BASE_FEE;
+    pool.set_block_info(block_info);
+
+    let tx = MockTransaction::eip1559().with_priority_fee(0);
+    let is_underpriced = |kind: &PoolErrorKind| {
+        matches!(kind, PoolErrorKind::InvalidTransaction(InvalidPoolTransactionError::Underpriced))
+    };
+    let err = pool.add_transaction(TransactionOrigin::External, tx.clone()).await.unwrap_err();
+    assert!(is_underpriced(&err.kind));
+    assert_eq!(validator.validated(), 1);
+
+    // re-announced transaction is rejected without validation
+    let err = pool.add_transaction(TransactionOrigin::External, tx.clone()).await.unwrap_err();
+    assert!(is_underpriced(&err.kind));
+    assert_eq!(validator.validated(), 1);
+
+    // local transactions are always validated
+    let _ = pool.add_transaction(TransactionOrigin::Local, tx.clone()).await;
+    assert_eq!(validator.validated(), 2);
+
+    // batches keep the order of the results
+    let other = MockTransaction::eip1559();
+    let results =
+        pool.add_transactions(TransactionOrigin::External, vec![tx.clone(), other.clone()]).await;
+    assert!(is_underpriced(&results[0].as_ref().unwrap_err().kind));
+    assert_eq!(results[1].as_ref().unwrap(), other.hash());
+    assert_eq!(validator.validated(), 3);
+
+    // rejections that depend on the state of the pool are validated again
+    let replacement = other.clone().rng_hash();
+    for validated in [4, 5] {
+        let err = pool
+            .add_transaction(TransactionOrigin::External, replacement.clone())
+            .await
+            .unwrap_err();
+        assert!(matches!(err.kind, PoolErrorKind::ReplacementUnderpriced));
+        assert_eq!(validator.validated(), validated);
+    }
+
+    // a lower base fee forgets the fee related rejections
+    block_info.pending_basefee -= 1;
+    pool.set_block_info(block_info);
+    let _ = pool.add_transaction(TransactionOrigin::External, tx).await;
+    assert_eq!(validator.validated(), 6);
+}
@@ -0,0 +1,97 @@
+#![allow(missing_docs, unreachable_pub)]
+use alloy_primitives::{B256, U256};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use proptest::{prelude::*, strategy::ValueTree, test_runner::TestRunner};
+use reth_primitives::Account;
+use reth_provider::{test_utils::create_test_provider_factory, StateWriter, TrieWriter};
+use reth_trie::{proof::Proof, HashedPostState, HashedStorage, MultiProofTargets, StateRoot};
+use reth_trie_db::{DatabaseProof, DatabaseStateRoot};
+
+/// Number of proof targets sharing a common prefix.
+const TARGETS: usize = 100;
+
+/// Compares generating a single multiproof for accounts under a common prefix against generating
+/// a proof per account, which walks the shared part of the trie for every account.
+pub fn multiproof(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Multiproof");
+    group.sample_size(20);
+
+    for size in [1_000, 10_000] {
+        let (state, targets) = generate_test_data(size);
+        let provider_factory = create_test_provider_factory();
+        {
+            let provider_rw = provider_factory.provider_rw().unwrap();
+            provider_rw.write_hashed_state(&state.into_sorted()).unwrap();
+            let (_, updates) =
+                StateRoot::from_tx(provider_rw.tx_ref()).root_with_updates().unwrap();
+            provider_rw.write_trie_updates(&updates).unwrap();
+            provider_rw.commit().unwrap();
+        }
+        let provider = provider_factory.provider().unwrap();
+
+        group.bench_function(BenchmarkId::new("multiproof", size), |b| {
+            b.iter(|| Proof::from_tx(provider.tx_ref()).multiproof(targets.clone()).unwrap());
+        });
+
+        group.bench_function(BenchmarkId::new("proof per account", size), |b| {
+            b.iter(|| {
+                for (hashed_address, slots) in &targets {
+                    let targets = MultiProofTargets::from_iter([(*hashed_address, slots.clone())]);
+                    Proof::from_tx(provider.tx_ref()).multiproof(targets).unwrap();
+                }
+            });
+        });
+    }
+}
+
+/// Generates `size` random accounts with storage and [`TARGETS`] additional accounts with hashed
+/// addresses sharing a three byte prefix, which are returned as proof targets.
+fn generate_test_data(size: usize) -> (HashedPostState, MultiProofTargets) {
+    let mut runner = TestRunner::deterministic();
+
+    let account = || {
+        (any::<u64>(), any::<U256>()).prop_map(|(nonce, balance)| Account {
+            nonce,
+            balance,
+            bytecode_hash: None,
+        })
+    };
+    let storage = || {
+        proptest::collection::hash_map(
+            any::<B256>(),
+            any::<U256>().prop_filter("non zero value", |v| !v.is_zero()),
+            10,
+        )
+    };
+
+    let mut state = proptest::collection::hash_map(any::<B256>(), (account(), storage()), size)
+        .new_tree(&mut runner)
+        .unwrap()
+        .current();
+    let prefixed =
+        proptest::collection::hash_map(any::<[u8; 29]>(), (account(), storage()), TARGETS)
+            .new_tree(&mut runner)
+            .unwrap()
+            .current();
+
+    let mut targets = MultiProofTargets::default();
+    for (suffix, value) in prefixed {
+        let mut hashed_address = B256::repeat_byte(0xab);
+        hashed_address[3..].copy_from_slice(&suffix);
+        targets.insert(hashed_address, value.1.keys().take(2).copied().collect());
+        state.insert(hashed_address, value);
+    }
+
+    let state = HashedPostState {
+        accounts: state.iter().map(|(address, (account, _))| (*address, Some(*account))).collect(),
+        storages: state
+            .into_iter()
+            .map(|(address, (_, storage))| (address, HashedStorage::from_iter(false, storage)))
+            .collect(),
+    };
+
+    (state, targets)
+}
+
+criterion_group!(multiproof_benches, multiproof);
+criterion_main!(multiproof_benches);
@@ -1,7 +1,7 @@
 use alloy_primitives::{B256, U256};
 use reth_db::tables;
 use reth_db_api::{
-    cursor::{DbCursorRO, DbDupCursorRO},
+    cursor::{DbCursorRO, DbDupCursorRO, DupWalker},
     transaction::DbTx,
 };
 use reth_primitives::Account;
@@ -74,6 +74,9 @@ where
 /// The structure wrapping a database cursor for hashed storage and
 /// a target hashed address. Implements [`HashedCursor`] and [`HashedStorageCursor`]
 /// for iterating over hashed storage.
+///
+/// The storage is walked with [`DbDupCursorRO::walk_dup_prefix`] semantics, so the cursor never
+/// moves past the duplicate set of the target address.
 #[derive(Debug)]
 pub struct DatabaseHashedStorageCursor<C> {
     /// Database hashed storage cursor.
@@ -99,11 +102,14 @@ where
         &mut self,
         subkey: B256,
     ) -> Result<Option<(B256, Self::Value)>, reth_db::DatabaseError> {
-        Ok(self.cursor.seek_by_key_subkey(self.hashed_address, subkey)?.map(|e| (e.key, e.value)))
+        let entry = self.cursor.walk_dup(Some(self.hashed_address), Some(subkey))?.next();
+        Ok(entry.transpose()?.map(|(_, e)| (e.key, e.value)))
     }
 
     fn next(&mut self) -> Result<Option<(B256, Self::Value)>, reth_db::DatabaseError> {
-        Ok(self.cursor.next_dup_val()?.map(|e| (e.key, e.value)))
+        // Continue the walk of the duplicate set from the current position.
+        let entry = DupWalker { cursor: &mut self.cursor, start: None }.next();
+        Ok(entry.transpose()?.map(|(_, e)| (e.key, e.value)))
     }
 }
 
@@ -112,6 +118,6 @@ where
     C: DbCursorRO<tables::HashedStorages> + DbDupCursorRO<tables::HashedStorages>,
 {
     fn is_storage_empty(&mut self) -> Result<bool, reth_db::DatabaseError> {
-        Ok(self.cursor.seek_exact(self.hashed_address)?.is_none())
+        Ok(self.cursor.walk_dup_prefix(self.hashed_address)?.next().transpose()?.is_none())
     }
 }
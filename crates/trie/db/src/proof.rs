@@ -2,6 +2,7 @@ use crate::{DatabaseHashedCursorFactory, DatabaseTrieCursorFactory};
 use alloy_primitives::{keccak256, map::HashMap, Address, B256};
 use reth_db_api::transaction::DbTx;
 use reth_execution_errors::StateProofError;
+use reth_primitives::keccak256_batch;
 use reth_trie::{
     hashed_cursor::HashedPostStateCursorFactory,
     proof::{Proof, StorageProof},
@@ -138,7 +139,7 @@ impl<'a, TX: DbTx> DatabaseStorageProof<'a, TX>
         storage: HashedStorage,
     ) -> Result<StorageMultiProof, StateProofError> {
         let hashed_address = keccak256(address);
-        let targets = slots.iter().map(keccak256).collect();
+        let targets = keccak256_batch(slots).into_iter().collect();
         let prefix_set = storage.construct_prefix_set();
         let state_sorted = HashedPostStateSorted::new(
             Default::default(),
@@ -1,11 +1,17 @@
 use crate::{DatabaseHashedCursorFactory, DatabaseTrieCursorFactory};
 use alloy_primitives::{keccak256, map::hash_map, Address, BlockNumber, B256};
-use reth_db::{cursor::DbCursorRO, models::BlockNumberAddress, tables, DatabaseError};
+use reth_db::{
+    cursor::{DbCursorRO, DbDupCursorRO},
+    models::BlockNumberAddress,
+    tables, DatabaseError,
+};
 use reth_db_api::transaction::DbTx;
 use reth_execution_errors::StorageRootError;
+use reth_primitives::StorageEntry;
 use reth_trie::{
     hashed_cursor::HashedPostStateCursorFactory, HashedPostState, HashedStorage, StorageRoot,
 };
+use std::ops::RangeInclusive;
 
 #[cfg(feature = "metrics")]
 use reth_trie::metrics::{TrieRootMetrics, TrieType};
@@ -31,6 +37,19 @@ pub trait DatabaseHashedStorage<TX>: Sized {
     /// Initializes [`HashedStorage`] from reverts. Iterates over storage reverts from the specified
     /// block up to the current tip and aggregates them into hashed storage in reverse.
     fn from_reverts(tx: &TX, address: Address, from: BlockNumber) -> Result<Self, DatabaseError>;
+
+    /// Returns up to `limit` non-zero storage slots of the account with the given hashed address
+    /// whose hashed slot lies within `range`, ordered by hashed slot. The [`HashedStorage`] is
+    /// applied on top of the database.
+    ///
+    /// The keys of the returned entries are hashed slots.
+    fn storage_range(
+        &self,
+        tx: &TX,
+        hashed_address: B256,
+        range: RangeInclusive<B256>,
+        limit: usize,
+    ) -> Result<Vec<StorageEntry>, DatabaseError>;
 }
 
 impl<'a, TX: DbTx> DatabaseStorageRoot<'a, TX>
@@ -93,4 +112,67 @@ impl<TX: DbTx> DatabaseHashedStorage<TX> for HashedStorage {
         }
         Ok(storage)
     }
+
+    fn storage_range(
+        &self,
+        tx: &TX,
+        hashed_address: B256,
+        range: RangeInclusive<B256>,
+        limit: usize,
+    ) -> Result<Vec<StorageEntry>, DatabaseError> {
+        let mut overlay = self
+            .storage
+            .iter()
+            .filter(|(hashed_slot, _)| range.contains(hashed_slot))
+            .map(|(hashed_slot, value)| StorageEntry { key: *hashed_slot, value: *value })
+            .collect::<Vec<_>>();
+        overlay.sort_unstable_by_key(|entry| entry.key);
+        let mut overlay = overlay.into_iter();
+
+        let mut cursor = tx.cursor_dup_read::<tables::HashedStorages>()?;
+        let mut database = if self.wiped {
+            None
+        } else {
+            Some(cursor.walk_dup(Some(hashed_address), Some(*range.start()))?)
+        }
+        .into_iter()
+        .flatten()
+        .map(|entry| entry.map(|(_, entry)| entry))
+        .take_while(|entry| entry.as_ref().map_or(true, |entry| entry.key <= *range.end()));
+
+        let mut entries = Vec::new();
+        let mut next_database = database.next().transpose()?;
+        let mut next_overlay = overlay.next();
+        while entries.len() < limit {
+            let entry = match (next_database, next_overlay) {
+                (None, None) => break,
+                (Some(database_entry), Some(overlay_entry))
+                    if database_entry.key < overlay_entry.key =>
+                {
+                    next_database = database.next().transpose()?;
+                    database_entry
+                }
+                (Some(database_entry), Some(overlay_entry))
+                    if database_entry.key == overlay_entry.key =>
+                {
+                    // The overlay takes precedence over the database.
+                    next_database = database.next().transpose()?;
+                    next_overlay = overlay.next();
+                    overlay_entry
+                }
+                (_, Some(overlay_entry)) => {
+                    next_overlay = overlay.next();
+                    overlay_entry
+                }
+                (Some(database_entry), None) => {
+                    next_database = database.next().transpose()?;
+                    database_entry
+                }
+            };
+            if !entry.value.is_zero() {
+                entries.push(entry);
+            }
+        }
+        Ok(entries)
+    }
 }
@@ -0,0 +1,103 @@
+#![allow(missing_docs)]
+
+use alloy_primitives::{B256, U256};
+use proptest::{collection::vec, prelude::*, sample::Index};
+use reth_primitives::Account;
+use reth_provider::{test_utils::create_test_provider_factory, StateWriter, TrieWriter};
+use reth_trie::{
+    hashed_cursor::HashedPostStateCursorFactory,
+    trie_cursor::{CachedTrieCursorFactory, TrieNodeCache},
+    HashedPostState, HashedStorage, StateRoot,
+};
+use reth_trie_db::{DatabaseHashedCursorFactory, DatabaseStateRoot, DatabaseTrieCursorFactory};
+use std::collections::BTreeMap;
+
+/// A small state diff: new or deleted accounts, balance changes of existing accounts and storage
+/// changes of existing accounts.
+type StateDiff =
+    (BTreeMap<B256, Option<U256>>, Vec<(Index, Option<U256>)>, Vec<(Index, B256, U256)>);
+
+fn state_diff() -> impl Strategy<Value = StateDiff> {
+    (
+        proptest::collection::btree_map(any::<B256>(), any::<Option<U256>>(), 0..3),
+        vec((any::<Index>(), any::<Option<U256>>()), 0..3),
+        vec((any::<Index>(), any::<B256>(), any::<U256>()), 0..3),
+    )
+}
+
+fn account(balance: U256) -> Account {
+    Account { balance, ..Default::default() }
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig {
+        cases: 32, ..ProptestConfig::default()
+    })]
+
+    /// Computes the roots of a chain of small state diffs with and without the trie node cache,
+    /// writing the state and trie updates of every diff to the database and invalidating the cache
+    /// with them.
+    #[test]
+    fn fuzz_cached_state_root(
+        init_state in proptest::collection::btree_map(any::<B256>(), any::<U256>(), 100..500),
+        diffs in vec(state_diff(), 10),
+    ) {
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+
+        let init_state = HashedPostState::default().with_accounts(
+            init_state
+                .into_iter()
+                .map(|(hashed_address, balance)| (hashed_address, Some(account(balance)))),
+        );
+        provider.write_hashed_state(&init_state.clone().into_sorted()).unwrap();
+        let (_, updates) = StateRoot::from_tx(provider.tx_ref()).root_with_updates().unwrap();
+        provider.write_trie_updates(&updates).unwrap();
+
+        let mut addresses = init_state.accounts.keys().copied().collect::<Vec<_>>();
+        let cache = TrieNodeCache::new(1_000);
+        for (accounts, updated_accounts, updated_slots) in diffs {
+            let mut diff = HashedPostState::default().with_accounts(
+                accounts
+                    .into_iter()
+                    .map(|(hashed_address, balance)| (hashed_address, balance.map(account))),
+            );
+            for (index, balance) in updated_accounts {
+                diff.accounts.insert(*index.get(&addresses), balance.map(account));
+            }
+            for (index, slot, value) in updated_slots {
+                diff.storages
+                    .entry(*index.get(&addresses))
+                    .or_insert_with(|| HashedStorage::new(false))
+                    .storage
+                    .insert(slot, value);
+            }
+
+            let (root, updates) =
+                StateRoot::overlay_root_with_updates(provider.tx_ref(), diff.clone()).unwrap();
+
+            let diff_sorted = diff.clone().into_sorted();
+            let (cached_root, cached_updates) = StateRoot::new(
+                CachedTrieCursorFactory::new(
+                    DatabaseTrieCursorFactory::new(provider.tx_ref()),
+                    Some(cache.clone()),
+                ),
+                HashedPostStateCursorFactory::new(
+                    DatabaseHashedCursorFactory::new(provider.tx_ref()),
+                    &diff_sorted,
+                ),
+            )
+            .with_prefix_sets(diff.construct_prefix_sets().freeze())
+            .root_with_updates()
+            .unwrap();
+            assert_eq!(cached_root, root);
+            assert_eq!(cached_updates, updates);
+
+            // commit the diff and keep the cache in sync with the database
+            provider.write_hashed_state(&diff_sorted).unwrap();
+            provider.write_trie_updates(&updates).unwrap();
+            cache.invalidate(&updates);
+            addresses.extend(diff.accounts.keys());
+        }
+    }
+}
@@ -4,7 +4,11 @@ use alloy_primitives::{B256, U256};
 use proptest::prelude::*;
 use proptest_arbitrary_interop::arb;
 use reth_db::{tables, test_utils::create_test_rw_db};
-use reth_db_api::{database::Database, transaction::DbTxMut};
+use reth_db_api::{
+    cursor::DbCursorRO,
+    database::Database,
+    transaction::{DbTx, DbTxMut},
+};
 use reth_primitives::{Account, StorageEntry};
 use reth_trie::{
     hashed_cursor::{
@@ -12,7 +16,7 @@ use reth_trie::{
     },
     HashedPostState, HashedStorage,
 };
-use reth_trie_db::DatabaseHashedCursorFactory;
+use reth_trie_db::{DatabaseHashedCursorFactory, DatabaseHashedStorage};
 use std::collections::BTreeMap;
 
 fn assert_account_cursor_order(
@@ -485,3 +489,61 @@ fn fuzz_hashed_storage_cursor() {
         assert_storage_cursor_order(&factory, expected.into_iter());
     });
 }
+
+#[test]
+fn fuzz_hashed_storage_range() {
+    use proptest::collection::btree_map;
+
+    proptest!(ProptestConfig::with_cases(32),
+        |(
+            db_storages in btree_map(any::<u8>(), btree_map(any::<u8>(), any::<u8>(), 0..32), 0..4),
+            wiped: bool,
+            overlay in btree_map(any::<u8>(), any::<u8>(), 0..32),
+            bounds: (u8, u8),
+            limit in 0..40usize,
+        )|
+    {
+        let address = B256::with_last_byte(1);
+        let db = create_test_rw_db();
+        db.update(|tx| {
+            for (address, storage) in &db_storages {
+                for (slot, value) in storage {
+                    let entry =
+                        StorageEntry { key: B256::with_last_byte(*slot), value: U256::from(*value) };
+                    tx.put::<tables::HashedStorages>(B256::with_last_byte(*address), entry)
+                        .unwrap();
+                }
+            }
+        })
+        .unwrap();
+
+        let mut hashed_storage = HashedStorage::new(wiped);
+        for (slot, value) in &overlay {
+            hashed_storage.storage.insert(B256::with_last_byte(*slot), U256::from(*value));
+        }
+        let range = B256::with_last_byte(bounds.0.min(bounds.1))..=
+            B256::with_last_byte(bounds.0.max(bounds.1));
+
+        // naive reference: scan the whole table and merge the overlay
+        let tx = db.tx().unwrap();
+        let mut expected = BTreeMap::new();
+        if !wiped {
+            for entry in tx.cursor_read::<tables::HashedStorages>().unwrap().walk(None).unwrap() {
+                let (entry_address, entry) = entry.unwrap();
+                if entry_address == address {
+                    expected.insert(entry.key, entry.value);
+                }
+            }
+        }
+        expected.extend(hashed_storage.storage.iter().map(|(slot, value)| (*slot, *value)));
+        let expected = expected
+            .into_iter()
+            .filter(|(slot, value)| range.contains(slot) && !value.is_zero())
+            .map(|(key, value)| StorageEntry { key, value })
+            .take(limit)
+            .collect::<Vec<_>>();
+
+        let entries = hashed_storage.storage_range(&tx, address, range, limit).unwrap();
+        prop_assert_eq!(entries, expected);
+    });
+}
@@ -4,10 +4,13 @@ use alloy_consensus::EMPTY_ROOT_HASH;
 use alloy_primitives::{keccak256, Address, Bytes, B256, U256};
 use alloy_rlp::EMPTY_STRING_CODE;
 use reth_chainspec::{Chain, ChainSpec, HOLESKY, MAINNET};
-use reth_primitives::Account;
-use reth_provider::test_utils::{create_test_provider_factory, insert_genesis};
-use reth_trie::{proof::Proof, AccountProof, Nibbles, StorageProof};
-use reth_trie_db::DatabaseProof;
+use reth_primitives::{Account, StorageEntry};
+use reth_provider::{
+    test_utils::{create_test_provider_factory, insert_genesis},
+    HashingWriter,
+};
+use reth_trie::{proof::Proof, AccountProof, Nibbles, StateRoot, StorageProof};
+use reth_trie_db::{DatabaseProof, DatabaseStateRoot};
 use std::{
     str::FromStr,
     sync::{Arc, LazyLock},
@@ -254,3 +257,52 @@ fn holesky_deposit_contract_proof() {
     similar_asserts::assert_eq!(account_proof, expected);
     assert_eq!(account_proof.verify(root), Ok(()));
 }
+
+#[test]
+fn multiproof_contains_verifiable_account_proofs() {
+    let factory = create_test_provider_factory();
+    let provider = factory.provider_rw().unwrap();
+
+    let accounts = (0..20u8)
+        .map(|i| {
+            let account = Account { nonce: i as u64, balance: U256::from(i), bytecode_hash: None };
+            (Address::with_last_byte(i), Some(account))
+        })
+        .collect::<Vec<_>>();
+    provider.insert_account_for_hashing(accounts.clone()).unwrap();
+    provider
+        .insert_storage_for_hashing(accounts.iter().step_by(2).map(|(address, _)| {
+            let storage = (1..10u8).map(|slot| StorageEntry {
+                key: B256::with_last_byte(slot),
+                value: U256::from(slot),
+            });
+            (*address, storage)
+        }))
+        .unwrap();
+    let root = StateRoot::from_tx(provider.tx_ref()).root().unwrap();
+
+    // request existing and missing accounts as well as existing and missing slots
+    let targets = accounts
+        .iter()
+        .map(|(address, _)| *address)
+        .chain([Address::with_last_byte(0xff)])
+        .map(|address| (address, vec![B256::with_last_byte(1), B256::with_last_byte(0xff)]))
+        .collect::<Vec<_>>();
+    let multiproof = Proof::from_tx(provider.tx_ref())
+        .multiproof(
+            targets
+                .iter()
+                .map(|(address, slots)| (keccak256(address), slots.iter().map(keccak256).collect()))
+                .collect(),
+        )
+        .unwrap();
+
+    for (address, slots) in targets {
+        let account_proof = multiproof.account_proof(address, &slots).unwrap();
+        assert_eq!(account_proof.verify(root), Ok(()), "invalid proof for {address:?}");
+
+        // the proof extracted from the multiproof is identical to the individual proof
+        let expected = Proof::from_tx(provider.tx_ref()).account_proof(address, &slots).unwrap();
+        similar_asserts::assert_eq!(account_proof, expected);
+    }
+}
@@ -4,15 +4,27 @@ use alloy_consensus::EMPTY_ROOT_HASH;
 use alloy_primitives::{
     keccak256,
     map::{HashMap, HashSet},
-    Address, Bytes, B256, U256,
+    Address, Bytes, TxKind, B256, U256,
 };
 use alloy_rlp::EMPTY_STRING_CODE;
 use reth_db::{cursor::DbCursorRW, tables};
 use reth_db_api::transaction::DbTxMut;
 use reth_primitives::{Account, StorageEntry};
 use reth_provider::{test_utils::create_test_provider_factory, HashingWriter};
-use reth_trie::{proof::Proof, witness::TrieWitness, HashedPostState, HashedStorage, StateRoot};
+use reth_revm::{
+    database::StateProviderDatabase,
+    witness::{ExecutionWitnessRecord, WitnessState},
+};
+use reth_trie::{
+    proof::Proof, witness::TrieWitness, HashedPostState, HashedStorage, KeccakKeyHasher, StateRoot,
+};
 use reth_trie_db::{DatabaseProof, DatabaseStateRoot, DatabaseTrieWitness};
+use revm::{
+    db::{states::bundle_state::BundleRetention, CacheDB, EmptyDB, State},
+    primitives::{AccountInfo, Bytecode, SpecId, TxEnv},
+    Database, DatabaseRef, Evm,
+};
+use std::fmt::Debug;
 
 #[test]
 fn includes_empty_node_preimage() {
@@ -148,3 +160,107 @@ fn correctly_decodes_branch_node_values() {
         assert_eq!(witness.get(&keccak256(node)), Some(node));
     }
 }
+
+/// Increments slot 0, clears slot 1 and sets slot 2.
+const CONTRACT_CODE: [u8; 20] = [
+    0x60, 0x00, 0x54, 0x60, 0x01, 0x01, 0x60, 0x00, 0x55, // SSTORE(0, SLOAD(0) + 1)
+    0x60, 0x00, 0x60, 0x01, 0x55, // SSTORE(1, 0)
+    0x60, 0x07, 0x60, 0x02, 0x55, // SSTORE(2, 7)
+    0x00, // STOP
+];
+
+/// Executes the transactions and returns the witness record and the resulting state transition.
+fn execute<DB>(db: DB, txs: &[TxEnv]) -> (ExecutionWitnessRecord, HashedPostState)
+where
+    DB: Database<Error: Debug>,
+{
+    let mut state = State::builder().with_database(db).with_bundle_update().build();
+    let mut evm = Evm::builder().with_db(&mut state).with_spec_id(SpecId::CANCUN).build();
+    for tx in txs {
+        *evm.tx_mut() = tx.clone();
+        evm.transact_commit().unwrap();
+    }
+    drop(evm);
+
+    let record = ExecutionWitnessRecord::from_executed_state(&state);
+    state.merge_transitions(BundleRetention::PlainState);
+    let hashed_state =
+        HashedPostState::from_bundle_state::<KeccakKeyHasher>(state.take_bundle().state());
+    (record, hashed_state)
+}
+
+#[test]
+fn stateless_execution_reproduces_state_root() {
+    let factory = create_test_provider_factory();
+    let provider = factory.provider_rw().unwrap();
+    let mut db = CacheDB::new(EmptyDB::default());
+
+    let sender = Address::with_last_byte(0x5e);
+    let sender_account = Account { balance: U256::from(1_000_000), ..Default::default() };
+    let contract = Address::with_last_byte(0xc0);
+    let code = Bytecode::new_raw(Bytes::from_static(&CONTRACT_CODE));
+    let contract_account =
+        Account { nonce: 1, balance: U256::ZERO, bytecode_hash: Some(code.hash_slow()) };
+    let slots =
+        [(B256::with_last_byte(0), U256::from(1)), (B256::with_last_byte(1), U256::from(5))];
+
+    // Insert the accounts of the block along with accounts that are not touched by it.
+    provider
+        .insert_account_for_hashing(
+            (0..100u64)
+                .map(|i| {
+                    let address = Address::from_word(B256::from(U256::from(0x1000 + i)));
+                    (address, Some(Account { balance: U256::from(i), ..Default::default() }))
+                })
+                .chain([(sender, Some(sender_account)), (contract, Some(contract_account))]),
+        )
+        .unwrap();
+    provider
+        .insert_storage_for_hashing([(
+            contract,
+            slots.map(|(key, value)| StorageEntry { key, value }),
+        )])
+        .unwrap();
+    db.insert_account_info(sender, sender_account.into());
+    db.insert_account_info(
+        contract,
+        AccountInfo { code: Some(code), ..AccountInfo::from(contract_account) },
+    );
+    for (slot, value) in slots {
+        db.insert_account_storage(contract, slot.into(), value).unwrap();
+    }
+    let state_root = StateRoot::from_tx(provider.tx_ref()).root().unwrap();
+
+    let txs = [
+        TxEnv {
+            caller: sender,
+            gas_limit: 100_000,
+            transact_to: TxKind::Call(contract),
+            ..Default::default()
+        },
+        TxEnv {
+            caller: sender,
+            gas_limit: 100_000,
+            transact_to: TxKind::Call(Address::with_last_byte(0x7e)),
+            value: U256::from(1),
+            ..Default::default()
+        },
+    ];
+
+    // Execute the block against the full state and record the witness.
+    let (record, hashed_state) = execute(&mut db, &txs);
+    let witness = TrieWitness::from_tx(provider.tx_ref()).compute(record.hashed_state).unwrap();
+    let expected_root = StateRoot::overlay_root(provider.tx_ref(), hashed_state.clone()).unwrap();
+    assert_ne!(expected_root, state_root);
+
+    // Re-execute the block using only the witness.
+    let witness_state = WitnessState::new(state_root, witness, record.codes);
+    let (_, stateless_hashed_state) =
+        execute(StateProviderDatabase::new(witness_state.clone()), &txs);
+    assert_eq!(stateless_hashed_state, hashed_state);
+    assert_eq!(witness_state.state_root(&stateless_hashed_state).unwrap(), expected_root);
+
+    // State that is not part of the witness can't be read.
+    let empty_state = WitnessState::new(state_root, Default::default(), Default::default());
+    assert!(StateProviderDatabase::new(empty_state).basic_ref(sender).is_err());
+}
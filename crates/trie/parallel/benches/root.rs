@@ -8,8 +8,8 @@ use reth_provider::{
     providers::ConsistentDbView, test_utils::create_test_provider_factory, StateWriter, TrieWriter,
 };
 use reth_trie::{
-    hashed_cursor::HashedPostStateCursorFactory, HashedPostState, HashedStorage, StateRoot,
-    TrieInput,
+    hashed_cursor::HashedPostStateCursorFactory, trie_cursor::TrieNodeCache, HashedPostState,
+    HashedStorage, StateRoot, TrieInput,
 };
 use reth_trie_db::{DatabaseHashedCursorFactory, DatabaseStateRoot};
 use reth_trie_parallel::root::ParallelStateRoot;
@@ -133,5 +133,87 @@ fn generate_test_data(size: usize) -> (HashedPostState, HashedPostState) {
     )
 }
 
-criterion_group!(state_root, calculate_state_root);
+/// Calculates the state roots of a chain of blocks with a single transfer each, with and without
+/// caching the branch nodes of the database.
+pub fn calculate_state_root_of_small_blocks(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Calculate State Root of 1-tx Blocks");
+    group.sample_size(20);
+
+    let size = 10_000;
+    let (db_state, blocks) = generate_transfers(size, 100);
+    let provider_factory = create_test_provider_factory();
+    {
+        let provider_rw = provider_factory.provider_rw().unwrap();
+        provider_rw.write_hashed_state(&db_state.into_sorted()).unwrap();
+        let (_, updates) = StateRoot::from_tx(provider_rw.tx_ref()).root_with_updates().unwrap();
+        provider_rw.write_trie_updates(&updates).unwrap();
+        provider_rw.commit().unwrap();
+    }
+
+    let view = ConsistentDbView::new(provider_factory, None);
+
+    for (name, trie_node_cache) in
+        [("uncached", None), ("cached", Some(TrieNodeCache::new(100_000)))]
+    {
+        group.bench_function(BenchmarkId::new(name, size), |b| {
+            b.iter(|| {
+                for block in &blocks {
+                    let mut calculator =
+                        ParallelStateRoot::new(view.clone(), TrieInput::from_state(block.clone()));
+                    if let Some(cache) = &trie_node_cache {
+                        calculator = calculator.with_trie_node_cache(cache.clone());
+                    }
+                    calculator.incremental_root().unwrap();
+                }
+            })
+        });
+    }
+}
+
+/// Generates `size` accounts and `blocks` state diffs, each changing the balances of the sender
+/// and the recipient of a transfer.
+fn generate_transfers(size: usize, blocks: usize) -> (HashedPostState, Vec<HashedPostState>) {
+    let mut runner = TestRunner::deterministic();
+
+    let db_state = proptest::collection::hash_map(
+        any::<B256>(),
+        arb::<Account>().prop_filter("non empty account", |a| !a.is_empty()),
+        size,
+    )
+    .new_tree(&mut runner)
+    .unwrap()
+    .current();
+
+    let keys = db_state.keys().copied().collect::<Vec<_>>();
+    let transfer = (
+        proptest::sample::select(keys.clone()),
+        proptest::sample::select(keys),
+        any::<U256>(),
+        any::<U256>(),
+    );
+    let diffs = proptest::collection::vec(transfer, blocks)
+        .new_tree(&mut runner)
+        .unwrap()
+        .current()
+        .into_iter()
+        .map(|(sender, recipient, sender_balance, recipient_balance)| {
+            let mut sender_account = db_state[&sender];
+            sender_account.balance = sender_balance;
+            let mut recipient_account = db_state[&recipient];
+            recipient_account.balance = recipient_balance;
+            HashedPostState::default().with_accounts([
+                (sender, Some(sender_account)),
+                (recipient, Some(recipient_account)),
+            ])
+        })
+        .collect();
+
+    (
+        HashedPostState::default()
+            .with_accounts(db_state.into_iter().map(|(address, account)| (address, Some(account)))),
+        diffs,
+    )
+}
+
+criterion_group!(state_root, calculate_state_root, calculate_state_root_of_small_blocks);
 criterion_main!(state_root);
@@ -13,7 +13,9 @@ use reth_provider::{
 use reth_trie::{
     hashed_cursor::{HashedCursorFactory, HashedPostStateCursorFactory},
     node_iter::{TrieElement, TrieNodeIter},
-    trie_cursor::{InMemoryTrieCursorFactory, TrieCursorFactory},
+    trie_cursor::{
+        CachedTrieCursorFactory, InMemoryTrieCursorFactory, TrieCursorFactory, TrieNodeCache,
+    },
     updates::TrieUpdates,
     walker::TrieWalker,
     HashBuilder, Nibbles, StorageRoot, TrieInput, TRIE_ACCOUNT_RLP_MAX_SIZE,
@@ -40,6 +42,8 @@ pub struct ParallelStateRoot<Factory> {
     view: ConsistentDbView<Factory>,
     /// Trie input.
     input: TrieInput,
+    /// Cache of the branch nodes of the database.
+    trie_node_cache: Option<TrieNodeCache>,
     /// Parallel state root metrics.
     #[cfg(feature = "metrics")]
     metrics: ParallelStateRootMetrics,
@@ -51,10 +55,19 @@ impl<Factory> ParallelStateRoot<Factory> {
         Self {
             view,
             input,
+            trie_node_cache: None,
             #[cfg(feature = "metrics")]
             metrics: ParallelStateRootMetrics::default(),
         }
     }
+
+    /// Read the branch nodes of the database through the given cache.
+    ///
+    /// See [`TrieNodeCache`] for the requirements on keeping it in sync with the database.
+    pub fn with_trie_node_cache(mut self, trie_node_cache: TrieNodeCache) -> Self {
+        self.trie_node_cache = Some(trie_node_cache);
+        self
+    }
 }
 
 impl<Factory> ParallelStateRoot<Factory>
@@ -101,6 +114,7 @@ where
             let view = self.view.clone();
             let hashed_state_sorted = hashed_state_sorted.clone();
             let trie_nodes_sorted = trie_nodes_sorted.clone();
+            let trie_node_cache = self.trie_node_cache.clone();
             #[cfg(feature = "metrics")]
             let metrics = self.metrics.storage_trie.clone();
 
@@ -110,7 +124,10 @@ where
                 let result = (|| -> Result<_, ParallelStateRootError> {
                     let provider_ro = view.provider_ro()?;
                     let trie_cursor_factory = InMemoryTrieCursorFactory::new(
-                        DatabaseTrieCursorFactory::new(provider_ro.tx_ref()),
+                        CachedTrieCursorFactory::new(
+                            DatabaseTrieCursorFactory::new(provider_ro.tx_ref()),
+                            trie_node_cache,
+                        ),
                         &trie_nodes_sorted,
                     );
                     let hashed_state = HashedPostStateCursorFactory::new(
@@ -137,7 +154,10 @@ where
 
         let provider_ro = self.view.provider_ro()?;
         let trie_cursor_factory = InMemoryTrieCursorFactory::new(
-            DatabaseTrieCursorFactory::new(provider_ro.tx_ref()),
+            CachedTrieCursorFactory::new(
+                DatabaseTrieCursorFactory::new(provider_ro.tx_ref()),
+                self.trie_node_cache,
+            ),
             &trie_nodes_sorted,
         );
         let hashed_cursor_factory = HashedPostStateCursorFactory::new(
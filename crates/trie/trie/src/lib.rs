@@ -42,6 +42,9 @@ pub mod proof;
 /// Trie witness generation.
 pub mod witness;
 
+/// Stateless state access and state root computation using a trie witness.
+pub mod stateless;
+
 /// The implementation of the Merkle Patricia Trie.
 mod trie;
 pub use trie::{StateRoot, StorageRoot};
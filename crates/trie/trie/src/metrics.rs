@@ -1,5 +1,5 @@
 use crate::stats::TrieStats;
-use metrics::{Counter, Histogram};
+use metrics::{Counter, Gauge, Histogram};
 use reth_metrics::Metrics;
 
 /// Wrapper for state root metrics.
@@ -83,3 +83,31 @@ impl WalkerMetrics {
         self.out_of_order_subnode.increment(amount);
     }
 }
+
+/// Metrics for the trie node cache.
+#[derive(Clone, Metrics)]
+#[metrics(scope = "trie.node_cache")]
+pub struct TrieNodeCacheMetrics {
+    /// The number of branch node lookups that were served from the cache.
+    hits: Counter,
+    /// The number of branch node lookups that missed the cache.
+    misses: Counter,
+    /// The number of cached branch nodes.
+    len: Gauge,
+}
+
+impl TrieNodeCacheMetrics {
+    /// Record a lookup in the cache.
+    pub fn record_lookup(&self, hit: bool) {
+        if hit {
+            self.hits.increment(1);
+        } else {
+            self.misses.increment(1);
+        }
+    }
+
+    /// Set the number of cached branch nodes.
+    pub fn set_len(&self, len: usize) {
+        self.len.set(len as f64);
+    }
+}
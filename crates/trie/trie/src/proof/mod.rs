@@ -13,6 +13,7 @@ use alloy_primitives::{
 };
 use alloy_rlp::{BufMut, Encodable};
 use reth_execution_errors::trie::StateProofError;
+use reth_primitives::keccak256_batch;
 use reth_trie_common::{
     proof::ProofRetainer, AccountProof, MultiProof, MultiProofTargets, StorageMultiProof,
 };
@@ -95,7 +96,7 @@ where
         Ok(self
             .multiproof(HashMap::from_iter([(
                 keccak256(address),
-                slots.iter().map(keccak256).collect(),
+                keccak256_batch(slots).into_iter().collect(),
             )]))?
             .account_proof(address, slots)?)
     }
@@ -3,13 +3,12 @@ use crate::{
     Nibbles,
 };
 use alloy_primitives::{
-    keccak256,
     map::{hash_map, B256HashMap, B256HashSet, HashMap, HashSet},
     Address, B256, U256,
 };
 use itertools::Itertools;
 use rayon::prelude::{IntoParallelIterator, ParallelIterator};
-use reth_primitives::Account;
+use reth_primitives::{keccak256_batch, Account};
 use reth_trie_common::KeyHasher;
 use revm::db::{states::CacheAccount, AccountStatus, BundleAccount};
 use std::borrow::Cow;
@@ -229,10 +228,9 @@ impl HashedStorage {
         status: AccountStatus,
         storage: impl IntoIterator<Item = (&'a U256, &'a U256)>,
     ) -> Self {
-        Self::from_iter(
-            status.was_destroyed(),
-            storage.into_iter().map(|(key, value)| (keccak256(B256::from(*key)), *value)),
-        )
+        let (slots, values): (Vec<_>, Vec<_>) =
+            storage.into_iter().map(|(key, value)| (B256::from(*key), *value)).unzip();
+        Self::from_iter(status.was_destroyed(), keccak256_batch(&slots).into_iter().zip(values))
     }
 
     /// Construct [`PrefixSetMut`] from hashed storage.
@@ -354,7 +352,7 @@ impl HashedStorageSorted {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use alloy_primitives::Bytes;
+    use alloy_primitives::{keccak256, Bytes};
     use reth_trie_common::KeccakKeyHasher;
     use revm::{
         db::{
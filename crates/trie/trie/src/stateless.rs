@@ -0,0 +1,222 @@
+use crate::HashedPostState;
+use alloy_primitives::{keccak256, map::B256HashMap, Bytes, B256, U256};
+use alloy_rlp::Decodable;
+use itertools::Itertools;
+use reth_execution_errors::{
+    SparseStateTrieError, SparseStateTrieErrorKind, SparseTrieErrorKind, TrieWitnessError,
+};
+use reth_trie_common::{Nibbles, RlpNode, TrieAccount, TrieNode, EMPTY_ROOT_HASH};
+use reth_trie_sparse::{SparseStateTrie, SparseTrie};
+
+/// The state trie backed by the trie nodes of an execution witness.
+///
+/// Gives access to the accounts and storage slots proven by the witness and computes the state
+/// root after applying a state transition, without access to the database. Reaching a trie node
+/// that is missing from the witness, or that doesn't hash to its key, results in
+/// [`TrieWitnessError::MissingNode`].
+#[derive(Debug, Clone)]
+pub struct StatelessTrie {
+    /// The state root the witness was generated against.
+    state_root: B256,
+    /// The trie nodes of the witness.
+    ///
+    /// `keccak(node) => node`
+    nodes: B256HashMap<Bytes>,
+}
+
+impl StatelessTrie {
+    /// Creates a new stateless trie from the state root and the trie nodes of the witness.
+    pub const fn new(state_root: B256, nodes: B256HashMap<Bytes>) -> Self {
+        Self { state_root, nodes }
+    }
+
+    /// Returns the state root the witness was generated against.
+    pub const fn state_root(&self) -> B256 {
+        self.state_root
+    }
+
+    /// Returns the account with the given hashed address, or [`None`] if the witness proves that
+    /// it doesn't exist.
+    pub fn account(&self, hashed_address: B256) -> Result<Option<TrieAccount>, TrieWitnessError> {
+        let Some(value) = self.leaf_value(self.state_root, hashed_address)? else {
+            return Ok(None)
+        };
+        Ok(Some(TrieAccount::decode(&mut &value[..])?))
+    }
+
+    /// Returns the value of the storage slot with the given hashed slot of the account with the
+    /// given hashed address.
+    pub fn storage(
+        &self,
+        hashed_address: B256,
+        hashed_slot: B256,
+    ) -> Result<U256, TrieWitnessError> {
+        let Some(account) = self.account(hashed_address)? else { return Ok(U256::ZERO) };
+        let Some(value) = self.leaf_value(account.storage_root, hashed_slot)? else {
+            return Ok(U256::ZERO)
+        };
+        Ok(U256::decode(&mut &value[..])?)
+    }
+
+    /// Computes the state root after applying the state transition.
+    ///
+    /// All accounts and storage slots of the state transition need to be proven by the witness.
+    pub fn root_with_state(&self, state: &HashedPostState) -> Result<B256, TrieWitnessError> {
+        let mut trie = SparseStateTrie::from_state(self.reveal(self.state_root)?);
+
+        for hashed_address in
+            state.accounts.keys().chain(state.storages.keys()).copied().sorted_unstable().dedup()
+        {
+            let current = self.account(hashed_address)?;
+            let storage = state.storages.get(&hashed_address);
+
+            // Update storage trie first.
+            let mut storage_trie = if storage.is_some_and(|storage| storage.wiped) {
+                SparseTrie::revealed_empty()
+            } else {
+                self.reveal(
+                    current.as_ref().map_or(EMPTY_ROOT_HASH, |account| account.storage_root),
+                )?
+            };
+            for (hashed_slot, value) in storage
+                .into_iter()
+                .flat_map(|storage| &storage.storage)
+                .sorted_unstable_by_key(|(hashed_slot, _)| **hashed_slot)
+            {
+                let storage_nibbles = Nibbles::unpack(hashed_slot);
+                let result = if value.is_zero() {
+                    storage_trie.remove_leaf(&storage_nibbles)
+                } else {
+                    storage_trie
+                        .update_leaf(storage_nibbles, alloy_rlp::encode_fixed_size(value).to_vec())
+                };
+                result.map_err(SparseStateTrieError::from)?;
+            }
+            trie.insert_storage_trie(hashed_address, storage_trie);
+
+            let account = match state.accounts.get(&hashed_address) {
+                Some(account) => account.unwrap_or_default(),
+                // Only the storage of the account changed.
+                None => current.map(Into::into).unwrap_or_default(),
+            };
+            trie.update_account(hashed_address, account)?;
+        }
+
+        let root =
+            trie.root().ok_or(SparseStateTrieErrorKind::Sparse(SparseTrieErrorKind::Blind))?;
+        Ok(root)
+    }
+
+    /// Reveals all nodes of the trie with the given root that are part of the witness.
+    fn reveal(&self, root: B256) -> Result<SparseTrie, TrieWitnessError> {
+        if root == EMPTY_ROOT_HASH {
+            return Ok(SparseTrie::revealed_empty())
+        }
+
+        let root_node = self.node(root)?;
+        let mut stack = self.witness_children(&Nibbles::default(), &root_node)?;
+
+        let mut trie = SparseTrie::blind();
+        let revealed =
+            trie.reveal_root(root_node, None, false).map_err(SparseStateTrieError::from)?;
+        while let Some((path, node)) = stack.pop() {
+            stack.extend(self.witness_children(&path, &node)?);
+            revealed.reveal_node(path, node, None).map_err(SparseStateTrieError::from)?;
+        }
+
+        Ok(trie)
+    }
+
+    /// Returns the children of the node at the given path that are part of the witness.
+    ///
+    /// Children that are embedded in the node are revealed together with it and are not
+    /// returned.
+    fn witness_children(
+        &self,
+        path: &Nibbles,
+        node: &TrieNode,
+    ) -> Result<Vec<(Nibbles, TrieNode)>, TrieWitnessError> {
+        let children: Vec<(Nibbles, &RlpNode)> = match node {
+            TrieNode::Branch(branch) => branch
+                .as_ref()
+                .children()
+                .filter_map(|(nibble, child)| {
+                    let mut child_path = path.clone();
+                    child_path.push_unchecked(nibble);
+                    Some((child_path, child?))
+                })
+                .collect(),
+            TrieNode::Extension(extension) => {
+                let mut child_path = path.clone();
+                child_path.extend_from_slice_unchecked(&extension.key);
+                vec![(child_path, &extension.child)]
+            }
+            TrieNode::EmptyRoot | TrieNode::Leaf(_) => Vec::new(),
+        };
+
+        let mut revealed = Vec::new();
+        for (child_path, child) in children {
+            if let Some(node) = child.as_hash().and_then(|hash| self.witness_node(hash)) {
+                revealed.push((child_path, TrieNode::decode(&mut &node[..])?));
+            }
+        }
+        Ok(revealed)
+    }
+
+    /// Walks the trie with the given root down to the leaf at the given key and returns its
+    /// value, or [`None`] if the witness proves that there's no such leaf.
+    fn leaf_value(&self, root: B256, key: B256) -> Result<Option<Vec<u8>>, TrieWitnessError> {
+        if root == EMPTY_ROOT_HASH {
+            return Ok(None)
+        }
+
+        let path = Nibbles::unpack(key);
+        let mut depth = 0;
+        let mut node = self.node(root)?;
+        loop {
+            let child = match node {
+                TrieNode::EmptyRoot => return Ok(None),
+                TrieNode::Leaf(leaf) => {
+                    return Ok((path.slice(depth..) == leaf.key).then_some(leaf.value))
+                }
+                TrieNode::Extension(extension) => {
+                    if !path.slice(depth..).starts_with(&extension.key) {
+                        return Ok(None)
+                    }
+                    depth += extension.key.len();
+                    extension.child
+                }
+                TrieNode::Branch(branch) => {
+                    let Some(child) = branch
+                        .as_ref()
+                        .children()
+                        .find_map(|(nibble, child)| (nibble == path[depth]).then_some(child))
+                        .flatten()
+                        .cloned()
+                    else {
+                        return Ok(None)
+                    };
+                    depth += 1;
+                    child
+                }
+            };
+
+            node = match child.as_hash() {
+                Some(hash) => self.node(hash)?,
+                // The child is embedded in its parent.
+                None => TrieNode::decode(&mut &child[..])?,
+            };
+        }
+    }
+
+    /// Returns the decoded trie node with the given hash.
+    fn node(&self, hash: B256) -> Result<TrieNode, TrieWitnessError> {
+        let node = self.witness_node(hash).ok_or(TrieWitnessError::MissingNode(hash))?;
+        Ok(TrieNode::decode(&mut &node[..])?)
+    }
+
+    /// Returns the encoded trie node with the given hash, if it's part of the witness.
+    fn witness_node(&self, hash: B256) -> Option<&Bytes> {
+        self.nodes.get(&hash).filter(|node| keccak256(node) == hash)
+    }
+}
@@ -0,0 +1,341 @@
+use super::{TrieCursor, TrieCursorFactory};
+#[cfg(feature = "metrics")]
+use crate::metrics::TrieNodeCacheMetrics;
+use crate::updates::TrieUpdates;
+use alloy_primitives::B256;
+use parking_lot::Mutex;
+use reth_storage_errors::db::DatabaseError;
+use reth_trie_common::{BranchNodeCompact, Nibbles};
+use schnellru::{ByLength, LruMap};
+use std::{fmt, sync::Arc};
+
+/// Key of a cached branch node: the hashed address of its storage trie, or `None` for the account
+/// trie, and its path.
+type TrieNodeKey = (Option<B256>, Nibbles);
+
+/// Bounded cache of the branch nodes stored in the database, shared by all
+/// [`CachedTrieCursorFactory`]s created with it.
+///
+/// Stored branch nodes carry the hashes of their children, which is all the hash builder needs to
+/// hash a branch node of which only one child changed. Caching them saves the database lookups of
+/// the branch nodes along the changed paths, which are revisited by every incremental state root
+/// computation.
+///
+/// The cache mirrors the trie tables, so it must be kept in sync with the database:
+///  - [`TrieNodeCache::invalidate`] must be called with the trie updates that are written to the
+///    database.
+///  - [`TrieNodeCache::clear`] must be called if the trie tables are modified otherwise, for
+///    example when blocks are unwound.
+///
+/// Roots must not be computed with the cache while the database is being modified.
+#[derive(Clone)]
+pub struct TrieNodeCache {
+    nodes: Arc<Mutex<LruMap<TrieNodeKey, BranchNodeCompact>>>,
+    #[cfg(feature = "metrics")]
+    metrics: TrieNodeCacheMetrics,
+}
+
+impl TrieNodeCache {
+    /// Creates a new cache that holds at most `max_nodes` branch nodes.
+    pub fn new(max_nodes: u32) -> Self {
+        Self {
+            nodes: Arc::new(Mutex::new(LruMap::new(ByLength::new(max_nodes)))),
+            #[cfg(feature = "metrics")]
+            metrics: TrieNodeCacheMetrics::default(),
+        }
+    }
+
+    /// Returns the number of cached branch nodes.
+    pub fn len(&self) -> usize {
+        self.nodes.lock().len()
+    }
+
+    /// Returns `true` if no branch nodes are cached.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Removes the branch nodes that are updated or removed by the given trie updates.
+    pub fn invalidate(&self, updates: &TrieUpdates) {
+        let mut nodes = self.nodes.lock();
+        for path in updates.account_nodes_ref().keys().chain(updates.removed_nodes_ref()) {
+            nodes.remove(&(None, path.clone()));
+        }
+
+        for (hashed_address, storage_updates) in updates.storage_tries_ref() {
+            if storage_updates.is_deleted() {
+                // the whole storage trie is wiped
+                let keys = nodes
+                    .iter()
+                    .filter(|((address, _), _)| address.as_ref() == Some(hashed_address))
+                    .map(|(key, _)| key.clone())
+                    .collect::<Vec<_>>();
+                for key in keys {
+                    nodes.remove(&key);
+                }
+            }
+
+            for path in storage_updates
+                .storage_nodes_ref()
+                .keys()
+                .chain(storage_updates.removed_nodes_ref())
+            {
+                nodes.remove(&(Some(*hashed_address), path.clone()));
+            }
+        }
+        #[cfg(feature = "metrics")]
+        self.metrics.set_len(nodes.len());
+    }
+
+    /// Removes all branch nodes from the cache.
+    pub fn clear(&self) {
+        let mut nodes = self.nodes.lock();
+        nodes.clear();
+        #[cfg(feature = "metrics")]
+        self.metrics.set_len(nodes.len());
+    }
+
+    fn get(&self, key: &TrieNodeKey) -> Option<BranchNodeCompact> {
+        let node = self.nodes.lock().get(key).cloned();
+        #[cfg(feature = "metrics")]
+        self.metrics.record_lookup(node.is_some());
+        node
+    }
+
+    fn insert(&self, key: TrieNodeKey, node: BranchNodeCompact) {
+        let mut nodes = self.nodes.lock();
+        nodes.insert(key, node);
+        #[cfg(feature = "metrics")]
+        self.metrics.set_len(nodes.len());
+    }
+}
+
+impl fmt::Debug for TrieNodeCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TrieNodeCache").field("len", &self.len()).finish_non_exhaustive()
+    }
+}
+
+/// Trie cursor factory that serves branch nodes of the underlying factory from a
+/// [`TrieNodeCache`].
+///
+/// The underlying factory must read the trie tables of the database the cache mirrors, the cache
+/// can't be used below cursors that overlay in-memory trie updates.
+#[derive(Debug, Clone)]
+pub struct CachedTrieCursorFactory<CF> {
+    /// Underlying trie cursor factory.
+    cursor_factory: CF,
+    /// The cache, the underlying cursors are used directly if `None`.
+    cache: Option<TrieNodeCache>,
+}
+
+impl<CF> CachedTrieCursorFactory<CF> {
+    /// Create a new trie cursor factory.
+    pub const fn new(cursor_factory: CF, cache: Option<TrieNodeCache>) -> Self {
+        Self { cursor_factory, cache }
+    }
+}
+
+impl<CF: TrieCursorFactory> TrieCursorFactory for CachedTrieCursorFactory<CF> {
+    type AccountTrieCursor = CachedTrieCursor<CF::AccountTrieCursor>;
+    type StorageTrieCursor = CachedTrieCursor<CF::StorageTrieCursor>;
+
+    fn account_trie_cursor(&self) -> Result<Self::AccountTrieCursor, DatabaseError> {
+        let cursor = self.cursor_factory.account_trie_cursor()?;
+        Ok(CachedTrieCursor::new(cursor, self.cache.clone(), None))
+    }
+
+    fn storage_trie_cursor(
+        &self,
+        hashed_address: B256,
+    ) -> Result<Self::StorageTrieCursor, DatabaseError> {
+        let cursor = self.cursor_factory.storage_trie_cursor(hashed_address)?;
+        Ok(CachedTrieCursor::new(cursor, self.cache.clone(), Some(hashed_address)))
+    }
+}
+
+/// Trie cursor that looks up the sought branch node in a [`TrieNodeCache`] before seeking the
+/// underlying cursor, and caches the branch nodes returned by the underlying cursor.
+#[derive(Debug)]
+pub struct CachedTrieCursor<C> {
+    /// The underlying cursor.
+    cursor: C,
+    /// The cache.
+    cache: Option<TrieNodeCache>,
+    /// The hashed address of the storage trie, `None` for the account trie.
+    hashed_address: Option<B256>,
+    /// Key of the last node that was served from the cache. The underlying cursor isn't positioned
+    /// at it.
+    cached_key: Option<Nibbles>,
+}
+
+impl<C: TrieCursor> CachedTrieCursor<C> {
+    /// Create new cached trie cursor.
+    pub const fn new(
+        cursor: C,
+        cache: Option<TrieNodeCache>,
+        hashed_address: Option<B256>,
+    ) -> Self {
+        Self { cursor, cache, hashed_address, cached_key: None }
+    }
+
+    fn seek_inner(
+        &mut self,
+        key: Nibbles,
+        exact: bool,
+    ) -> Result<Option<(Nibbles, BranchNodeCompact)>, DatabaseError> {
+        let Some(cache) = &self.cache else {
+            return if exact { self.cursor.seek_exact(key) } else { self.cursor.seek(key) }
+        };
+
+        // An exact match is also the result of seeking a node that is greater or equal to the key.
+        if let Some(node) = cache.get(&(self.hashed_address, key.clone())) {
+            self.cached_key = Some(key.clone());
+            return Ok(Some((key, node)))
+        }
+
+        self.cached_key = None;
+        let entry = if exact { self.cursor.seek_exact(key)? } else { self.cursor.seek(key)? };
+        if let Some((key, node)) = &entry {
+            cache.insert((self.hashed_address, key.clone()), node.clone());
+        }
+        Ok(entry)
+    }
+}
+
+impl<C: TrieCursor> TrieCursor for CachedTrieCursor<C> {
+    fn seek_exact(
+        &mut self,
+        key: Nibbles,
+    ) -> Result<Option<(Nibbles, BranchNodeCompact)>, DatabaseError> {
+        self.seek_inner(key, true)
+    }
+
+    fn seek(
+        &mut self,
+        key: Nibbles,
+    ) -> Result<Option<(Nibbles, BranchNodeCompact)>, DatabaseError> {
+        self.seek_inner(key, false)
+    }
+
+    fn next(&mut self) -> Result<Option<(Nibbles, BranchNodeCompact)>, DatabaseError> {
+        // Position the underlying cursor at the node that was served from the cache.
+        if let Some(key) = self.cached_key.take() {
+            self.cursor.seek_exact(key)?;
+        }
+        self.cursor.next()
+    }
+
+    fn current(&mut self) -> Result<Option<Nibbles>, DatabaseError> {
+        match &self.cached_key {
+            Some(key) => Ok(Some(key.clone())),
+            None => self.cursor.current(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        trie_cursor::{noop::NoopTrieCursorFactory, InMemoryTrieCursorFactory},
+        updates::StorageTrieUpdates,
+    };
+    use reth_trie_common::TrieMask;
+
+    fn branch_node(hash_byte: u8) -> BranchNodeCompact {
+        BranchNodeCompact::new(
+            TrieMask::new(0b11),
+            TrieMask::new(0),
+            TrieMask::new(0b1),
+            vec![B256::repeat_byte(hash_byte)],
+            None,
+        )
+    }
+
+    /// Returns trie updates with account and storage nodes at the given paths.
+    fn trie_updates(paths: &[Nibbles], hashed_address: B256) -> TrieUpdates {
+        let mut updates = TrieUpdates::default();
+        for (idx, path) in paths.iter().enumerate() {
+            let node = branch_node(idx as u8);
+            updates.account_nodes.insert(path.clone(), node.clone());
+            updates
+                .storage_tries
+                .entry(hashed_address)
+                .or_insert_with(StorageTrieUpdates::default)
+                .storage_nodes
+                .insert(path.clone(), node);
+        }
+        updates
+    }
+
+    #[test]
+    fn serves_seeked_nodes_from_cache() {
+        let paths = [Nibbles::from_nibbles([0x1]), Nibbles::from_nibbles([0x2])];
+        let hashed_address = B256::repeat_byte(0xaa);
+        let updates = trie_updates(&paths, hashed_address).into_sorted();
+        let db = InMemoryTrieCursorFactory::new(NoopTrieCursorFactory, &updates);
+
+        let cache = TrieNodeCache::new(10);
+        let factory = CachedTrieCursorFactory::new(db, Some(cache.clone()));
+
+        // the first cursor reads the nodes from the underlying cursor
+        let mut cursor = factory.account_trie_cursor().unwrap();
+        assert_eq!(
+            cursor.seek(Nibbles::default()).unwrap(),
+            Some((paths[0].clone(), branch_node(0)))
+        );
+        assert_eq!(cursor.next().unwrap(), Some((paths[1].clone(), branch_node(1))));
+        assert_eq!(cache.len(), 1);
+
+        // the second cursor finds the first node in the cache and continues after it
+        let mut cursor = factory.account_trie_cursor().unwrap();
+        assert_eq!(
+            cursor.seek(paths[0].clone()).unwrap(),
+            Some((paths[0].clone(), branch_node(0)))
+        );
+        assert_eq!(cursor.current().unwrap(), Some(paths[0].clone()));
+        assert_eq!(cursor.next().unwrap(), Some((paths[1].clone(), branch_node(1))));
+        assert_eq!(cursor.next().unwrap(), None);
+
+        // storage nodes are cached separately
+        let mut cursor = factory.storage_trie_cursor(hashed_address).unwrap();
+        assert_eq!(
+            cursor.seek_exact(paths[1].clone()).unwrap(),
+            Some((paths[1].clone(), branch_node(1)))
+        );
+        assert_eq!(cache.len(), 2);
+    }
+
+    #[test]
+    fn invalidates_updated_nodes() {
+        let paths = [Nibbles::from_nibbles([0x1]), Nibbles::from_nibbles([0x2])];
+        let hashed_address = B256::repeat_byte(0xaa);
+        let updates = trie_updates(&paths, hashed_address);
+        let updates_sorted = updates.clone().into_sorted();
+        let db = InMemoryTrieCursorFactory::new(NoopTrieCursorFactory, &updates_sorted);
+
+        let cache = TrieNodeCache::new(10);
+        let factory = CachedTrieCursorFactory::new(db, Some(cache.clone()));
+        for path in &paths {
+            factory.account_trie_cursor().unwrap().seek_exact(path.clone()).unwrap();
+            factory.storage_trie_cursor(hashed_address).unwrap().seek_exact(path.clone()).unwrap();
+        }
+        assert_eq!(cache.len(), 4);
+
+        // only the updated account node is removed
+        let mut account_update = TrieUpdates::default();
+        account_update.removed_nodes.insert(paths[0].clone());
+        cache.invalidate(&account_update);
+        assert_eq!(cache.len(), 3);
+
+        // all nodes of a deleted storage trie are removed
+        let mut storage_update = TrieUpdates::default();
+        storage_update.storage_tries.insert(hashed_address, StorageTrieUpdates::deleted());
+        cache.invalidate(&storage_update);
+        assert_eq!(cache.len(), 1);
+
+        cache.invalidate(&updates);
+        assert!(cache.is_empty());
+    }
+}
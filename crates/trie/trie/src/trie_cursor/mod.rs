@@ -5,13 +5,16 @@ use reth_storage_errors::db::DatabaseError;
 /// In-memory implementations of trie cursors.
 mod in_memory;
 
+/// Trie cursors that cache the branch nodes of the database.
+mod cached;
+
 /// Cursor for iterating over a subtrie.
 mod subnode;
 
 /// Noop trie cursor implementations.
 pub mod noop;
 
-pub use self::{in_memory::*, subnode::CursorSubNode};
+pub use self::{cached::*, in_memory::*, subnode::CursorSubNode};
 
 /// Factory for creating trie cursors.
 pub trait TrieCursorFactory {
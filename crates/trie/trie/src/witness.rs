@@ -15,6 +15,7 @@ use reth_execution_errors::{
     SparseStateTrieError, SparseStateTrieErrorKind, SparseTrieError, SparseTrieErrorKind,
     StateProofError, TrieWitnessError,
 };
+use reth_primitives::keccak256_batch;
 use reth_trie_common::{MultiProofTargets, Nibbles};
 use reth_trie_sparse::{
     blinded::{BlindedProvider, BlindedProviderFactory},
@@ -99,14 +100,14 @@ where
                 .multiproof(proof_targets.clone())?;
 
         // Record all nodes from multiproof in the witness
-        for account_node in multiproof.account_subtree.values() {
-            if let Entry::Vacant(entry) = self.witness.entry(keccak256(account_node.as_ref())) {
-                entry.insert(account_node.clone());
-            }
-        }
-        for storage_node in multiproof.storages.values().flat_map(|s| s.subtree.values()) {
-            if let Entry::Vacant(entry) = self.witness.entry(keccak256(storage_node.as_ref())) {
-                entry.insert(storage_node.clone());
+        let nodes = multiproof
+            .account_subtree
+            .values()
+            .chain(multiproof.storages.values().flat_map(|s| s.subtree.values()))
+            .collect::<Vec<_>>();
+        for (hash, node) in keccak256_batch(&nodes).into_iter().zip(nodes) {
+            if let Entry::Vacant(entry) = self.witness.entry(hash) {
+                entry.insert(node.clone());
             }
         }
 
@@ -0,0 +1,244 @@
+//! This example shows how to drive a node with a custom consensus that runs in the same process,
+//! instead of a consensus layer client connected to the engine API.
+//!
+//! The [`Sealer`] is the only authority of a proof of authority chain. It seals a block on top of
+//! the canonical head at a fixed interval with the node's payload builder, and makes the block
+//! canonical through the node's [`ConsensusEngineHandle`]. Because nothing else drives the node,
+//! the auth server is disabled.
+//!
+//! Run with
+//!
+//! ```not_rust
+//! cargo run -p example-custom-consensus-driver
+//! ```
+
+#![cfg_attr(not(test), warn(unused_crate_dependencies))]
+
+use std::{
+    sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use alloy_genesis::Genesis;
+use alloy_primitives::{address, Address, BlockNumber, B256};
+use alloy_rpc_types::engine::{ForkchoiceState, PayloadAttributes};
+use eyre::OptionExt;
+use reth::{
+    api::{EngineApiMessageVersion, EngineTypes, PayloadBuilder},
+    builder::{embedded::ConsensusEngineHandle, EngineNodeLauncher, NodeBuilder},
+    payload::{PayloadBuilderHandle, PayloadKind},
+    primitives::SealedHeader,
+    providers::{providers::BlockchainProvider2, BlockNumReader, HeaderProvider},
+    tasks::TaskManager,
+};
+use reth_chainspec::{ChainSpec, EthereumHardforks};
+use reth_node_core::{args::RpcServerArgs, node_config::NodeConfig};
+use reth_node_ethereum::{node::EthereumAddOns, EthEngineTypes, EthereumNode};
+
+/// Interval at which the authority seals blocks.
+const BLOCK_TIME: Duration = Duration::from_secs(2);
+
+/// The authority of the chain, which receives the fees of the sealed blocks.
+const AUTHORITY: Address = address!("6Be02d1d3665660d22FF9624b7BE0551ee1Ac91b");
+
+#[tokio::main]
+async fn main() -> eyre::Result<()> {
+    run(BLOCK_TIME, None).await
+}
+
+/// Launches the node and drives it with a [`Sealer`], until the chain reaches `until` if set.
+async fn run(block_time: Duration, until: Option<BlockNumber>) -> eyre::Result<()> {
+    let tasks = TaskManager::current();
+
+    // the node is driven by the sealer only, so it doesn't need the engine API
+    let node_config = NodeConfig::test()
+        .with_chain(custom_chain())
+        .with_rpc(RpcServerArgs::default().with_unused_ports().with_http().with_auth_disabled());
+    let handle = NodeBuilder::new(node_config)
+        .testing_node(tasks.executor())
+        .with_types_and_provider::<EthereumNode, BlockchainProvider2<_>>()
+        .with_components(EthereumNode::components())
+        .with_add_ons(EthereumAddOns::default())
+        .launch_with_fn(|builder| {
+            let launcher = EngineNodeLauncher::new(
+                builder.task_executor().clone(),
+                builder.config().datadir(),
+                Default::default(),
+            );
+            builder.launch_with(launcher)
+        })
+        .await?;
+    eyre::ensure!(!handle.node.is_auth_server_enabled(), "auth server is running");
+
+    let provider = &handle.node.provider;
+    let head = provider.sealed_header(provider.best_block_number()?)?.ok_or_eyre("missing head")?;
+    let sealer = Sealer {
+        engine: handle.consensus_engine_handle().clone(),
+        payload_builder: handle.payload_builder().clone(),
+        chain_spec: handle.node.chain_spec(),
+        fee_recipient: AUTHORITY,
+        head,
+    };
+    let head = sealer.run(block_time, until).await?;
+
+    // the sealed blocks are the canonical chain of the node
+    eyre::ensure!(provider.best_block_number()? == head.number, "sealed blocks aren't canonical");
+    println!("chain advanced to block {} ({})", head.number, head.hash());
+
+    handle.shutdown().await;
+    Ok(())
+}
+
+/// Seals blocks on top of the canonical head of the node.
+///
+/// Since the sealer is the only authority of the chain, every sealed block is final right away.
+#[derive(Debug)]
+struct Sealer {
+    /// Handle to the consensus engine of the node.
+    engine: ConsensusEngineHandle<EthEngineTypes>,
+    /// Handle to the payload builder of the node.
+    payload_builder: PayloadBuilderHandle<EthEngineTypes>,
+    chain_spec: Arc<ChainSpec>,
+    /// Recipient of the fees of the sealed blocks.
+    fee_recipient: Address,
+    /// The last sealed block.
+    head: SealedHeader,
+}
+
+impl Sealer {
+    /// Seals a block every `block_time`, until the chain reaches `until` if set.
+    ///
+    /// Returns the head of the chain.
+    async fn run(
+        mut self,
+        block_time: Duration,
+        until: Option<BlockNumber>,
+    ) -> eyre::Result<SealedHeader> {
+        let mut interval = tokio::time::interval(block_time);
+        while until.is_none_or(|until| self.head.number < until) {
+            interval.tick().await;
+            self.seal_block().await?;
+            println!("sealed block {} ({})", self.head.number, self.head.hash());
+        }
+        Ok(self.head)
+    }
+
+    /// Builds a block on top of the head, and makes it the new head of the chain.
+    async fn seal_block(&mut self) -> eyre::Result<()> {
+        let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+        let timestamp = now.max(self.head.timestamp + 1);
+        let attributes = PayloadAttributes {
+            timestamp,
+            prev_randao: B256::ZERO,
+            suggested_fee_recipient: self.fee_recipient,
+            withdrawals: self.chain_spec.is_shanghai_active_at_timestamp(timestamp).then(Vec::new),
+            parent_beacon_block_root: self
+                .chain_spec
+                .is_cancun_active_at_timestamp(timestamp)
+                .then_some(B256::ZERO),
+        };
+
+        // start building a payload on top of the head
+        let updated = self
+            .engine
+            .fork_choice_updated(
+                self.forkchoice_state(),
+                Some(attributes),
+                EngineApiMessageVersion::default(),
+            )
+            .await?;
+        eyre::ensure!(
+            updated.payload_status.is_valid(),
+            "invalid forkchoice state: {:?}",
+            updated.payload_status
+        );
+        let payload_id = updated.payload_id.ok_or_eyre("no payload id")?;
+
+        let payload = self
+            .payload_builder
+            .resolve_kind(payload_id, PayloadKind::WaitForPending)
+            .await
+            .ok_or_eyre("missing payload job")??;
+        let block = payload.block().clone();
+        let header = block.header.clone();
+
+        // insert the block and make it the head of the chain
+        let (payload, sidecar) = EthEngineTypes::block_to_payload(block);
+        let status = self.engine.new_payload(payload, sidecar).await?;
+        eyre::ensure!(status.is_valid(), "invalid payload: {status:?}");
+
+        self.head = header;
+        let updated = self
+            .engine
+            .fork_choice_updated(self.forkchoice_state(), None, EngineApiMessageVersion::default())
+            .await?;
+        eyre::ensure!(
+            updated.payload_status.is_valid(),
+            "invalid forkchoice state: {:?}",
+            updated.payload_status
+        );
+
+        Ok(())
+    }
+
+    /// Returns the forkchoice state with the head as the head, safe and finalized block.
+    fn forkchoice_state(&self) -> ForkchoiceState {
+        let hash = self.head.hash();
+        ForkchoiceState { head_block_hash: hash, safe_block_hash: hash, finalized_block_hash: hash }
+    }
+}
+
+fn custom_chain() -> Arc<ChainSpec> {
+    let custom_genesis = r#"
+{
+    "nonce": "0x42",
+    "timestamp": "0x0",
+    "extraData": "0x5343",
+    "gasLimit": "0x1388",
+    "difficulty": "0x400000000",
+    "mixHash": "0x0000000000000000000000000000000000000000000000000000000000000000",
+    "coinbase": "0x0000000000000000000000000000000000000000",
+    "alloc": {
+        "0x6Be02d1d3665660d22FF9624b7BE0551ee1Ac91b": {
+            "balance": "0x4a47e3c12448f4ad000000"
+        }
+    },
+    "number": "0x0",
+    "gasUsed": "0x0",
+    "parentHash": "0x0000000000000000000000000000000000000000000000000000000000000000",
+    "config": {
+        "ethash": {},
+        "chainId": 2600,
+        "homesteadBlock": 0,
+        "eip150Block": 0,
+        "eip155Block": 0,
+        "eip158Block": 0,
+        "byzantiumBlock": 0,
+        "constantinopleBlock": 0,
+        "petersburgBlock": 0,
+        "istanbulBlock": 0,
+        "berlinBlock": 0,
+        "londonBlock": 0,
+        "terminalTotalDifficulty": 0,
+        "terminalTotalDifficultyPassed": true,
+        "shanghaiTime": 0
+    }
+}
+"#;
+    let genesis: Genesis = serde_json::from_str(custom_genesis).unwrap();
+    Arc::new(genesis.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn sealer_advances_chain() {
+        // seal 10 blocks without the engine API, which must not hang
+        tokio::time::timeout(Duration::from_secs(60), run(Duration::from_millis(100), Some(10)))
+            .await
+            .unwrap()
+            .unwrap();
+    }
+}
@@ -0,0 +1,248 @@
+//! Example of how to prune a custom table with a custom prune segment.
+//!
+//! The segment prunes the `DepositEvents` table, that's created in the node's database, and is
+//! configured by its name in the `[prune.segments.custom]` section of the config:
+//!
+//! ```toml
+//! [prune.segments.custom]
+//! deposit_events = { distance = 10064 }
+//! ```
+//!
+//! Run with
+//!
+//! ```not_rust
+//! cargo run -p example-custom-prune-segment -- node --config reth.toml
+//! ```
+
+#![cfg_attr(not(test), warn(unused_crate_dependencies))]
+
+use alloy_primitives::{BlockNumber, B256};
+use reth::cli::Cli;
+use reth_db::{
+    table::{Table, TableInfo},
+    transaction::DbTxMut,
+    TableSet,
+};
+use reth_node_ethereum::EthereumNode;
+use reth_provider::DBProvider;
+use reth_prune::{
+    segments::{PruneInput, Segment},
+    DbTxPruneExt, PruneMode, PrunePurpose, PruneSegmentId, PrunerError, SegmentOutput,
+    SegmentOutputCheckpoint,
+};
+use tracing::trace;
+
+/// Name of the deposit events prune segment, under which it's configured and its checkpoint is
+/// saved.
+const DEPOSIT_EVENTS: &str = "deposit_events";
+
+/// Table that stores the root of the deposit events of every block.
+#[derive(Debug)]
+struct DepositEvents;
+
+impl Table for DepositEvents {
+    const NAME: &'static str = "DepositEvents";
+    const DUPSORT: bool = false;
+    type Key = BlockNumber;
+    type Value = B256;
+}
+
+impl TableInfo for DepositEvents {
+    fn name(&self) -> &'static str {
+        Self::NAME
+    }
+
+    fn is_dupsort(&self) -> bool {
+        Self::DUPSORT
+    }
+}
+
+/// The custom tables that are created in the node's database.
+#[derive(Debug)]
+struct DepositTables;
+
+impl TableSet for DepositTables {
+    fn tables() -> Box<dyn Iterator<Item = Box<dyn TableInfo>>> {
+        Box::new(std::iter::once(Box::new(DepositEvents) as Box<dyn TableInfo>))
+    }
+}
+
+/// Prune segment that prunes the [`DepositEvents`] table up to the target block.
+#[derive(Debug)]
+struct DepositEventsSegment {
+    mode: PruneMode,
+}
+
+impl DepositEventsSegment {
+    const fn new(mode: PruneMode) -> Self {
+        Self { mode }
+    }
+}
+
+impl<Provider> Segment<Provider> for DepositEventsSegment
+where
+    Provider: DBProvider<Tx: DbTxMut>,
+{
+    fn segment(&self) -> PruneSegmentId {
+        PruneSegmentId::Custom(DEPOSIT_EVENTS)
+    }
+
+    fn mode(&self) -> Option<PruneMode> {
+        Some(self.mode)
+    }
+
+    fn purpose(&self) -> PrunePurpose {
+        PrunePurpose::User
+    }
+
+    fn prune(&self, provider: &Provider, input: PruneInput) -> Result<SegmentOutput, PrunerError> {
+        let Some(range) = input.get_next_block_range() else {
+            trace!(target: "pruner", "No deposit events to prune");
+            return Ok(SegmentOutput::done())
+        };
+        let range_end = *range.end();
+
+        let mut limiter = input.limiter;
+        let mut last_pruned_block = None;
+        let (pruned, done) = provider.tx_ref().prune_table_with_range::<DepositEvents>(
+            range,
+            &mut limiter,
+            |_| false,
+            |(block_number, _)| last_pruned_block = Some(block_number),
+        )?;
+        trace!(target: "pruner", %pruned, %done, "Pruned deposit events");
+
+        let last_pruned_block = last_pruned_block
+            // If there's more deposit events to prune, set the checkpoint block number to
+            // previous, so we could finish pruning its deposit events on the next run.
+            .map(|block_number| if done { block_number } else { block_number.saturating_sub(1) })
+            .unwrap_or(range_end);
+
+        Ok(SegmentOutput {
+            progress: limiter.progress(done),
+            pruned,
+            checkpoint: Some(SegmentOutputCheckpoint {
+                block_number: Some(last_pruned_block),
+                tx_number: None,
+            }),
+        })
+    }
+}
+
+fn main() {
+    Cli::parse_args()
+        .run(|builder, _| async move {
+            builder.db().create_tables_for::<DepositTables>()?;
+
+            let handle = builder
+                .node(EthereumNode::default())
+                .install_prune_segment(DEPOSIT_EVENTS, DepositEventsSegment::new)
+                .launch()
+                .await?;
+
+            handle.wait_for_node_exit().await
+        })
+        .unwrap();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_db::{cursor::DbCursorRO, transaction::DbTx};
+    use reth_provider::{
+        test_utils::create_test_provider_factory, DatabaseProviderFactory, PruneCheckpointReader,
+    };
+    use reth_prune::{
+        segments::PruneSegmentRegistry, PruneCheckpoint, PruneModes, PruneSegmentError,
+        PrunerBuilder,
+    };
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn prunes_deposit_events() {
+        let factory = create_test_provider_factory();
+        factory.db_ref().db().create_tables_for::<DepositTables>().unwrap();
+
+        let provider = factory.database_provider_rw().unwrap();
+        for block_number in 0..=20 {
+            provider
+                .tx_ref()
+                .put::<DepositEvents>(block_number, B256::with_last_byte(block_number as u8))
+                .unwrap();
+        }
+        provider.commit().unwrap();
+
+        let prune_mode = PruneMode::Before(11);
+        let segments = PruneModes {
+            custom: BTreeMap::from([(DEPOSIT_EVENTS.to_string(), prune_mode)]),
+            ..PruneModes::none()
+        };
+        let mut registry = PruneSegmentRegistry::new();
+        registry.register(DEPOSIT_EVENTS, DepositEventsSegment::new);
+        let mut pruner = PrunerBuilder::default()
+            .segments(segments)
+            .build_with_registry(factory.clone(), &registry)
+            .unwrap();
+
+        let output = pruner.run(20).unwrap();
+        assert!(output.progress.is_finished());
+        assert_eq!(
+            output.segments.iter().map(|(segment, _)| *segment).collect::<Vec<_>>(),
+            vec![PruneSegmentId::Custom(DEPOSIT_EVENTS)]
+        );
+
+        let provider = factory.database_provider_ro().unwrap();
+        let remaining = provider
+            .tx_ref()
+            .cursor_read::<DepositEvents>()
+            .unwrap()
+            .walk(None)
+            .unwrap()
+            .map(|entry| entry.unwrap().0)
+            .collect::<Vec<_>>();
+        assert_eq!(remaining, (11..=20).collect::<Vec<_>>());
+        assert_eq!(
+            provider.get_custom_prune_checkpoint(DEPOSIT_EVENTS).unwrap(),
+            Some(PruneCheckpoint { block_number: Some(10), tx_number: None, prune_mode })
+        );
+    }
+
+    #[test]
+    fn unregistered_segment() {
+        let segments = PruneModes {
+            custom: BTreeMap::from([("withdrawal_events".to_string(), PruneMode::Full)]),
+            ..PruneModes::none()
+        };
+        let mut registry = PruneSegmentRegistry::new();
+        registry.register(DEPOSIT_EVENTS, DepositEventsSegment::new);
+
+        let result = PrunerBuilder::default()
+            .segments(segments)
+            .build_with_registry(create_test_provider_factory(), &registry);
+        assert!(matches!(
+            result,
+            Err(PruneSegmentError::Unregistered(name)) if name == "withdrawal_events"
+        ));
+    }
+
+    #[test]
+    fn misidentified_segment() {
+        let segments = PruneModes {
+            custom: BTreeMap::from([("withdrawal_events".to_string(), PruneMode::Full)]),
+            ..PruneModes::none()
+        };
+        let mut registry = PruneSegmentRegistry::new();
+        registry.register("withdrawal_events", DepositEventsSegment::new);
+
+        let result = PrunerBuilder::default()
+            .segments(segments)
+            .build_with_registry(create_test_provider_factory(), &registry);
+        assert!(matches!(
+            result,
+            Err(PruneSegmentError::MismatchedId {
+                name: "withdrawal_events",
+                id: PruneSegmentId::Custom(DEPOSIT_EVENTS),
+            })
+        ));
+    }
+}
@@ -0,0 +1,133 @@
+//! This example shows how to embed a dev node into a program, and interact with it through the
+//! handles to its components instead of RPC.
+//!
+//! A transaction is submitted to the node's transaction pool, and the node is shut down once the
+//! transaction was included in the canonical chain.
+
+#![cfg_attr(not(test), warn(unused_crate_dependencies))]
+
+use std::{sync::Arc, time::Duration};
+
+use alloy_eips::eip2718::Decodable2718;
+use alloy_genesis::Genesis;
+use alloy_primitives::{b256, hex, B256};
+use futures_util::StreamExt;
+use reth::{
+    builder::NodeBuilder,
+    providers::BlockNumReader,
+    tasks::TaskManager,
+    transaction_pool::{PoolTransaction, TransactionOrigin, TransactionPool},
+};
+use reth_chainspec::ChainSpec;
+use reth_node_core::node_config::NodeConfig;
+use reth_node_ethereum::EthereumNode;
+use reth_primitives::{transaction::SignedTransactionIntoRecoveredExt, PooledTransaction};
+
+/// A transfer signed by the account that is funded in the genesis of [`custom_chain`].
+const RAW_TX: [u8; 121] = hex!("02f876820a28808477359400847735940082520894ab0840c0e43688012c1adb0f5e3fc665188f83d28a029d394a5d630544000080c080a0a044076b7e67b5deecc63f61a8d7913fab86ca365b344b5759d1fe3563b4c39ea019eab979dd000da04dfc72bb0377c092d30fd9e1cab5ae487de49586cc8b0090");
+
+/// Hash of [`RAW_TX`].
+const TX_HASH: B256 = b256!("b1c6512f4fc202c04355fbda66755e0e344b152e633010e8fd75ecec09b63398");
+
+/// Maximum time the node may take to shut down.
+const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[tokio::main]
+async fn main() -> eyre::Result<()> {
+    run().await
+}
+
+async fn run() -> eyre::Result<()> {
+    let tasks = TaskManager::current();
+
+    // launch the node in-process, it mines a block as soon as a transaction arrives
+    let node_config = NodeConfig::test().dev().with_chain(custom_chain());
+    let handle = NodeBuilder::new(node_config)
+        .testing_node(tasks.executor())
+        .node(EthereumNode::default())
+        .launch()
+        .await?;
+
+    let mut notifications = handle.canonical_state_stream();
+
+    // submit the transaction straight to the pool
+    let recovered = PooledTransaction::decode_2718(&mut &RAW_TX[..])?
+        .try_into_ecrecovered()
+        .map_err(|_| eyre::eyre!("invalid transaction signature"))?;
+    let pool_transaction = PoolTransaction::from_pooled(recovered);
+    let hash = handle.pool().add_transaction(TransactionOrigin::Local, pool_transaction).await?;
+    assert_eq!(hash, TX_HASH);
+    println!("submitted transaction: {hash}");
+
+    // wait for the block that includes the transaction
+    let block_number = loop {
+        let notification =
+            notifications.next().await.ok_or_else(|| eyre::eyre!("node stopped unexpectedly"))?;
+        let tip = notification.tip();
+        if tip.transactions().iter().any(|tx| tx.hash() == hash) {
+            break tip.number
+        }
+    };
+    println!("mined transaction in block {block_number}");
+
+    // new blocks are kept in memory first, and written to the database in batches
+    let persisted = handle.provider_factory().provider()?.best_block_number()?;
+    println!("blocks persisted to the database: {persisted}");
+
+    tokio::time::timeout(SHUTDOWN_TIMEOUT, handle.shutdown()).await?;
+    println!("node shut down");
+
+    Ok(())
+}
+
+fn custom_chain() -> Arc<ChainSpec> {
+    let custom_genesis = r#"
+{
+    "nonce": "0x42",
+    "timestamp": "0x0",
+    "extraData": "0x5343",
+    "gasLimit": "0x1388",
+    "difficulty": "0x400000000",
+    "mixHash": "0x0000000000000000000000000000000000000000000000000000000000000000",
+    "coinbase": "0x0000000000000000000000000000000000000000",
+    "alloc": {
+        "0x6Be02d1d3665660d22FF9624b7BE0551ee1Ac91b": {
+            "balance": "0x4a47e3c12448f4ad000000"
+        }
+    },
+    "number": "0x0",
+    "gasUsed": "0x0",
+    "parentHash": "0x0000000000000000000000000000000000000000000000000000000000000000",
+    "config": {
+        "ethash": {},
+        "chainId": 2600,
+        "homesteadBlock": 0,
+        "eip150Block": 0,
+        "eip155Block": 0,
+        "eip158Block": 0,
+        "byzantiumBlock": 0,
+        "constantinopleBlock": 0,
+        "petersburgBlock": 0,
+        "istanbulBlock": 0,
+        "berlinBlock": 0,
+        "londonBlock": 0,
+        "terminalTotalDifficulty": 0,
+        "terminalTotalDifficultyPassed": true,
+        "shanghaiTime": 0
+    }
+}
+"#;
+    let genesis: Genesis = serde_json::from_str(custom_genesis).unwrap();
+    Arc::new(genesis.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn embedded_node_mines_pool_transaction() {
+        // launching, mining the transaction and shutting down must not hang
+        tokio::time::timeout(Duration::from_secs(60), run()).await.unwrap().unwrap();
+    }
+}
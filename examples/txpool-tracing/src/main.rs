@@ -47,7 +47,8 @@ fn main() {
                             let callrequest = transaction_to_call_request(tx.to_consensus());
                             let tracerequest = TraceCallRequest::new(callrequest)
                                 .with_trace_type(TraceType::Trace);
-                            if let Ok(trace_result) = traceapi.trace_call(tracerequest).await {
+                            if let Ok(trace_result) = traceapi.trace_call(tracerequest, None).await
+                            {
                                 let hash = tx.hash();
                                 println!("trace result for transaction {hash}: {trace_result:?}");
                             }
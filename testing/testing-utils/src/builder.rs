@@ -0,0 +1,779 @@
+//! Deterministic builders for blocks and transactions.
+//!
+//! Unlike the [generators](crate::generators), the builders produce the same output on every run
+//! and keep the header fields consistent with the block contents and the active forks, so the
+//! built blocks pass the pre-execution consensus checks.
+
+use crate::generators::sign_tx_with_key_pair;
+use alloy_consensus::{
+    Header, Transaction as _, TxEip1559, TxEip2930, TxEip4844, TxEip7702, TxLegacy,
+};
+use alloy_eips::{
+    eip1559::{ETHEREUM_BLOCK_GAS_LIMIT, INITIAL_BASE_FEE},
+    eip2930::{AccessList, AccessListItem},
+    eip4844::{BLOB_TX_MIN_BLOB_GASPRICE, DATA_GAS_PER_BLOB, VERSIONED_HASH_VERSION_KZG},
+    eip4895::{Withdrawal, Withdrawals},
+    eip7685::EMPTY_REQUESTS_HASH,
+    eip7702::{Authorization, SignedAuthorization},
+    eip7840::BlobParams,
+};
+use alloy_genesis::Genesis;
+use alloy_primitives::{Address, BlockNumber, Bloom, Bytes, TxKind, B256, U256};
+use reth_chainspec::{ChainSpec, ChainSpecBuilder, EthereumHardfork, EthereumHardforks, Hardforks};
+use reth_primitives::{
+    proofs, public_key_to_address, sign_message, BlockBody, Receipt, RecoveredTx, SealedBlock,
+    SealedBlockWithSenders, SealedHeader, Transaction, TxType,
+};
+use secp256k1::{Keypair, Secp256k1};
+use std::sync::Arc;
+
+/// Gas limit of a plain value transfer.
+const TRANSFER_GAS: u64 = 21_000;
+
+/// Additional intrinsic gas of a contract creation.
+const CREATE_GAS: u64 = 32_000;
+
+/// Intrinsic gas of an access list address.
+const ACCESS_LIST_ADDRESS_GAS: u64 = 2_400;
+
+/// Intrinsic gas of an access list storage key.
+const ACCESS_LIST_STORAGE_KEY_GAS: u64 = 1_900;
+
+/// Intrinsic gas of an EIP-7702 authorization.
+const AUTHORIZATION_GAS: u64 = 25_000;
+
+/// Time between two blocks built by the [`TestBlockBuilder`].
+const BLOCK_TIME: u64 = 12;
+
+/// Returns the key pair with the given index.
+///
+/// The secret key is the index plus one, so the same index always yields the same key.
+pub fn test_key_pair(index: usize) -> Keypair {
+    let secret = B256::from(U256::from(index) + U256::from(1));
+    Keypair::from_seckey_slice(&Secp256k1::new(), secret.as_slice())
+        .expect("secret key is within the curve order")
+}
+
+/// Returns the address of the key pair with the given index, see [`test_key_pair`].
+pub fn test_address(index: usize) -> Address {
+    public_key_to_address(test_key_pair(index).public_key())
+}
+
+/// Signs an EIP-7702 authorization that delegates the code of `authority` to `address`.
+pub fn sign_authorization(
+    authority: &Keypair,
+    chain_id: u64,
+    address: Address,
+    nonce: u64,
+) -> SignedAuthorization {
+    let authorization = Authorization { chain_id: U256::from(chain_id), address, nonce };
+    let signature = sign_message(
+        B256::from_slice(&authority.secret_bytes()[..]),
+        authorization.signature_hash(),
+    )
+    .expect("failed to sign authorization");
+    authorization.into_signed(signature)
+}
+
+/// Fluent builder of a signed transaction of any type.
+///
+/// The transaction is signed by the [`test_key_pair`] with the configured signer index. Fields
+/// that don't exist on the built transaction type are ignored, e.g. the access list of a legacy
+/// transaction. EIP-4844 and EIP-7702 transactions can't create contracts, so they are sent to the
+/// zero address instead.
+#[derive(Debug, Clone)]
+pub struct TestTxBuilder {
+    tx_type: TxType,
+    signer: usize,
+    chain_id: Option<u64>,
+    nonce: Option<u64>,
+    to: TxKind,
+    value: U256,
+    input: Bytes,
+    gas_limit: u64,
+    max_fee_per_gas: u128,
+    max_priority_fee_per_gas: u128,
+    access_list: AccessList,
+    blob_versioned_hashes: Vec<B256>,
+    max_fee_per_blob_gas: u128,
+    authorization_list: Vec<SignedAuthorization>,
+}
+
+impl TestTxBuilder {
+    /// Creates a value transfer of the given type.
+    pub fn new(tx_type: TxType) -> Self {
+        Self {
+            tx_type,
+            signer: 0,
+            chain_id: None,
+            nonce: None,
+            to: TxKind::Call(Address::repeat_byte(0xaa)),
+            value: U256::from(1),
+            input: Bytes::default(),
+            gas_limit: TRANSFER_GAS,
+            max_fee_per_gas: 2 * INITIAL_BASE_FEE as u128,
+            max_priority_fee_per_gas: 1,
+            access_list: AccessList::default(),
+            blob_versioned_hashes: Vec::new(),
+            max_fee_per_blob_gas: BLOB_TX_MIN_BLOB_GASPRICE,
+            authorization_list: Vec::new(),
+        }
+    }
+
+    /// Creates a legacy value transfer.
+    pub fn legacy() -> Self {
+        Self::new(TxType::Legacy)
+    }
+
+    /// Creates an EIP-2930 value transfer, accessing a storage slot of the recipient.
+    pub fn eip2930() -> Self {
+        let to = Address::repeat_byte(0xaa);
+        Self::new(TxType::Eip2930)
+            .access_list(AccessList(vec![AccessListItem {
+                address: to,
+                storage_keys: vec![B256::ZERO],
+            }]))
+            .gas_limit(TRANSFER_GAS + ACCESS_LIST_ADDRESS_GAS + ACCESS_LIST_STORAGE_KEY_GAS)
+    }
+
+    /// Creates an EIP-1559 value transfer.
+    pub fn eip1559() -> Self {
+        Self::new(TxType::Eip1559)
+    }
+
+    /// Creates an EIP-4844 transaction carrying a single blob.
+    pub fn eip4844() -> Self {
+        Self::new(TxType::Eip4844).blobs(1)
+    }
+
+    /// Creates an EIP-7702 transaction, delegating the code of the second test account.
+    pub fn eip7702() -> Self {
+        Self::new(TxType::Eip7702)
+            .authorization_list(vec![sign_authorization(
+                &test_key_pair(1),
+                0,
+                Address::repeat_byte(0x77),
+                0,
+            )])
+            .gas_limit(TRANSFER_GAS + AUTHORIZATION_GAS)
+    }
+
+    /// Sets the index of the [`test_key_pair`] that signs the transaction.
+    pub const fn signer(mut self, index: usize) -> Self {
+        self.signer = index;
+        self
+    }
+
+    /// Sets the chain id, defaults to the chain of the [`TestBlockBuilder`], or mainnet.
+    pub const fn chain_id(mut self, chain_id: u64) -> Self {
+        self.chain_id = Some(chain_id);
+        self
+    }
+
+    /// Sets the nonce, defaults to the next nonce of the signer in the [`TestBlockBuilder`], or
+    /// zero.
+    pub const fn nonce(mut self, nonce: u64) -> Self {
+        self.nonce = Some(nonce);
+        self
+    }
+
+    /// Sets the recipient.
+    pub const fn to(mut self, to: Address) -> Self {
+        self.to = TxKind::Call(to);
+        self
+    }
+
+    /// Makes the transaction a contract creation.
+    pub const fn create(mut self) -> Self {
+        self.to = TxKind::Create;
+        self
+    }
+
+    /// Sets the transferred value.
+    pub const fn value(mut self, value: U256) -> Self {
+        self.value = value;
+        self
+    }
+
+    /// Sets the input data.
+    pub fn input(mut self, input: impl Into<Bytes>) -> Self {
+        self.input = input.into();
+        self
+    }
+
+    /// Sets the gas limit.
+    pub const fn gas_limit(mut self, gas_limit: u64) -> Self {
+        self.gas_limit = gas_limit;
+        self
+    }
+
+    /// Sets the max fee per gas, which is the gas price of legacy and EIP-2930 transactions.
+    pub const fn max_fee_per_gas(mut self, max_fee_per_gas: u128) -> Self {
+        self.max_fee_per_gas = max_fee_per_gas;
+        self
+    }
+
+    /// Sets the max priority fee per gas.
+    pub const fn max_priority_fee_per_gas(mut self, max_priority_fee_per_gas: u128) -> Self {
+        self.max_priority_fee_per_gas = max_priority_fee_per_gas;
+        self
+    }
+
+    /// Sets the access list.
+    pub fn access_list(mut self, access_list: AccessList) -> Self {
+        self.access_list = access_list;
+        self
+    }
+
+    /// Sets the number of blobs, each with a distinct versioned hash.
+    pub fn blobs(mut self, count: usize) -> Self {
+        self.blob_versioned_hashes = (0..count)
+            .map(|index| {
+                let mut hash = B256::from(U256::from(index));
+                hash[0] = VERSIONED_HASH_VERSION_KZG;
+                hash
+            })
+            .collect();
+        self
+    }
+
+    /// Sets the max fee per blob gas.
+    pub const fn max_fee_per_blob_gas(mut self, max_fee_per_blob_gas: u128) -> Self {
+        self.max_fee_per_blob_gas = max_fee_per_blob_gas;
+        self
+    }
+
+    /// Sets the authorization list, see [`sign_authorization`].
+    pub fn authorization_list(mut self, authorization_list: Vec<SignedAuthorization>) -> Self {
+        self.authorization_list = authorization_list;
+        self
+    }
+
+    /// Returns the type of the built transaction.
+    pub const fn tx_type(&self) -> TxType {
+        self.tx_type
+    }
+
+    /// Builds the unsigned [`Transaction`].
+    pub fn build(self) -> Transaction {
+        let chain_id = self.chain_id.unwrap_or(1);
+        let nonce = self.nonce.unwrap_or_default();
+        match self.tx_type {
+            TxType::Legacy => Transaction::Legacy(TxLegacy {
+                chain_id: Some(chain_id),
+                nonce,
+                gas_price: self.max_fee_per_gas,
+                gas_limit: self.gas_limit,
+                to: self.to,
+                value: self.value,
+                input: self.input,
+            }),
+            TxType::Eip2930 => Transaction::Eip2930(TxEip2930 {
+                chain_id,
+                nonce,
+                gas_price: self.max_fee_per_gas,
+                gas_limit: self.gas_limit,
+                to: self.to,
+                value: self.value,
+                input: self.input,
+                access_list: self.access_list,
+            }),
+            TxType::Eip1559 => Transaction::Eip1559(TxEip1559 {
+                chain_id,
+                nonce,
+                gas_limit: self.gas_limit,
+                max_fee_per_gas: self.max_fee_per_gas,
+                max_priority_fee_per_gas: self.max_priority_fee_per_gas,
+                to: self.to,
+                value: self.value,
+                input: self.input,
+                access_list: self.access_list,
+            }),
+            TxType::Eip4844 => Transaction::Eip4844(TxEip4844 {
+                chain_id,
+                nonce,
+                gas_limit: self.gas_limit,
+                max_fee_per_gas: self.max_fee_per_gas,
+                max_priority_fee_per_gas: self.max_priority_fee_per_gas,
+                to: self.to.to().copied().unwrap_or_default(),
+                value: self.value,
+                input: self.input,
+                access_list: self.access_list,
+                blob_versioned_hashes: self.blob_versioned_hashes,
+                max_fee_per_blob_gas: self.max_fee_per_blob_gas,
+            }),
+            TxType::Eip7702 => Transaction::Eip7702(TxEip7702 {
+                chain_id,
+                nonce,
+                gas_limit: self.gas_limit,
+                max_fee_per_gas: self.max_fee_per_gas,
+                max_priority_fee_per_gas: self.max_priority_fee_per_gas,
+                to: self.to.to().copied().unwrap_or_default(),
+                value: self.value,
+                input: self.input,
+                access_list: self.access_list,
+                authorization_list: self.authorization_list,
+            }),
+        }
+    }
+
+    /// Builds the transaction and signs it with the key of the signer.
+    pub fn sign(self) -> RecoveredTx {
+        let key_pair = test_key_pair(self.signer);
+        let signer = public_key_to_address(key_pair.public_key());
+        sign_tx_with_key_pair(key_pair, self.build()).with_signer(signer)
+    }
+}
+
+/// Builds chains of blocks whose header fields are consistent with their contents.
+///
+/// Every block contains the transactions queued with [`Self::push_tx`], followed by
+/// [`Self::with_txs_per_block`] value transfers of the type matching the active fork. Nonces and
+/// the chain id of the transactions are filled in by the builder, unless set explicitly.
+///
+/// The header of a block commits to its transactions, receipts, withdrawals and blob gas, and the
+/// fork specific fields, base fee, gas limit, timestamp and excess blob gas follow from the parent
+/// according to the chain spec. The receipts assume that every transaction succeeds and only uses
+/// its intrinsic gas, like a value transfer to an account without code, the state root isn't
+/// computed.
+///
+/// The default chain spec activates all forks up to Cancun at genesis, and the first block built
+/// is the child of its genesis block.
+#[derive(Debug, Clone)]
+pub struct TestBlockBuilder {
+    chain_spec: Arc<ChainSpec>,
+    parent: Option<SealedHeader>,
+    number: BlockNumber,
+    parent_hash: B256,
+    txs_per_block: usize,
+    withdrawals_per_block: usize,
+    queued_txs: Vec<TestTxBuilder>,
+    nonces: Vec<u64>,
+    withdrawal_index: u64,
+}
+
+impl Default for TestBlockBuilder {
+    fn default() -> Self {
+        Self::new(Arc::new(
+            ChainSpecBuilder::mainnet()
+                .genesis(Genesis::default().with_gas_limit(ETHEREUM_BLOCK_GAS_LIMIT))
+                .cancun_activated()
+                .build(),
+        ))
+    }
+}
+
+impl TestBlockBuilder {
+    /// Creates a builder that extends the genesis block of the given chain spec.
+    pub fn new(chain_spec: Arc<ChainSpec>) -> Self {
+        let genesis = chain_spec.sealed_genesis_header();
+        Self {
+            chain_spec,
+            number: genesis.number + 1,
+            parent_hash: genesis.hash(),
+            parent: Some(genesis),
+            txs_per_block: 0,
+            withdrawals_per_block: 0,
+            queued_txs: Vec::new(),
+            nonces: Vec::new(),
+            withdrawal_index: 0,
+        }
+    }
+
+    /// Sets the chain spec and extends its genesis block.
+    pub fn with_chain_spec(self, chain_spec: Arc<ChainSpec>) -> Self {
+        Self {
+            txs_per_block: self.txs_per_block,
+            withdrawals_per_block: self.withdrawals_per_block,
+            ..Self::new(chain_spec)
+        }
+    }
+
+    /// Sets the number of value transfers added to every block.
+    pub const fn with_txs_per_block(mut self, txs_per_block: usize) -> Self {
+        self.txs_per_block = txs_per_block;
+        self
+    }
+
+    /// Sets the number of withdrawals of every block once Shanghai is active.
+    pub const fn with_withdrawals_per_block(mut self, withdrawals_per_block: usize) -> Self {
+        self.withdrawals_per_block = withdrawals_per_block;
+        self
+    }
+
+    /// Extends the chain from the given parent.
+    pub fn with_parent(mut self, parent: SealedHeader) -> Self {
+        self.number = parent.number + 1;
+        self.parent_hash = parent.hash();
+        self.parent = Some(parent);
+        self
+    }
+
+    /// Builds the next block with the given number and parent hash.
+    ///
+    /// The parent is unknown, so the fields derived from it have their fork activation values.
+    pub fn starting_at(mut self, number: BlockNumber, parent_hash: B256) -> Self {
+        self.number = number;
+        self.parent_hash = parent_hash;
+        self.parent = None;
+        self
+    }
+
+    /// Returns the chain spec of the built blocks.
+    pub const fn chain_spec(&self) -> &Arc<ChainSpec> {
+        &self.chain_spec
+    }
+
+    /// Returns the number of the next block.
+    pub const fn next_number(&self) -> BlockNumber {
+        self.number
+    }
+
+    /// Returns the next nonce of the signer with the given index.
+    pub fn nonce(&self, signer: usize) -> u64 {
+        self.nonces.get(signer).copied().unwrap_or_default()
+    }
+
+    /// Queues a transaction for the next block.
+    pub fn push_tx(&mut self, tx: TestTxBuilder) -> &mut Self {
+        self.queued_txs.push(tx);
+        self
+    }
+
+    /// Builds the next block, see [`Self::next_block_with_receipts`].
+    pub fn next_block(&mut self) -> SealedBlockWithSenders {
+        self.next_block_with_receipts().0
+    }
+
+    /// Builds the next block of the chain and returns it with the receipts of its transactions.
+    pub fn next_block_with_receipts(&mut self) -> (SealedBlockWithSenders, Vec<Receipt>) {
+        let number = self.number;
+        let timestamp = self.parent.as_ref().map_or_else(
+            || self.chain_spec.genesis().timestamp + number * BLOCK_TIME,
+            |parent| parent.timestamp + BLOCK_TIME,
+        );
+
+        let default_tx_type =
+            if self.chain_spec.is_fork_active_at_block(EthereumHardfork::London, number) {
+                TxType::Eip1559
+            } else {
+                TxType::Legacy
+            };
+        let mut txs = std::mem::take(&mut self.queued_txs);
+        txs.extend((0..self.txs_per_block).map(|_| TestTxBuilder::new(default_tx_type)));
+        let transactions = txs.into_iter().map(|tx| self.sign(tx)).collect::<Vec<_>>();
+
+        let mut cumulative_gas_used = 0;
+        let receipts = transactions
+            .iter()
+            .map(|tx| {
+                cumulative_gas_used += self.intrinsic_gas(tx, number, timestamp);
+                #[allow(clippy::needless_update)] // side-effect of optimism fields
+                Receipt {
+                    tx_type: tx.tx_type(),
+                    success: true,
+                    cumulative_gas_used,
+                    logs: Vec::new(),
+                    ..Default::default()
+                }
+            })
+            .collect::<Vec<_>>();
+        let receipts_with_bloom =
+            receipts.iter().map(|receipt| receipt.clone().with_bloom()).collect::<Vec<_>>();
+        let logs_bloom = receipts_with_bloom
+            .iter()
+            .fold(Bloom::ZERO, |bloom, receipt| bloom | receipt.logs_bloom);
+
+        let withdrawals = self.chain_spec.is_shanghai_active_at_timestamp(timestamp).then(|| {
+            let withdrawals = (0..self.withdrawals_per_block)
+                .map(|_| {
+                    let index = self.withdrawal_index;
+                    self.withdrawal_index += 1;
+                    Withdrawal {
+                        index,
+                        validator_index: index,
+                        address: test_address(0),
+                        amount: 1,
+                    }
+                })
+                .collect::<Vec<_>>();
+            Withdrawals::new(withdrawals)
+        });
+
+        let (blob_gas_used, excess_blob_gas, parent_beacon_block_root) =
+            if self.chain_spec.is_cancun_active_at_timestamp(timestamp) {
+                let blob_params = if self.chain_spec.is_prague_active_at_timestamp(timestamp) {
+                    BlobParams::prague()
+                } else {
+                    BlobParams::cancun()
+                };
+                let blob_gas_used = transactions
+                    .iter()
+                    .filter_map(|tx| tx.blob_versioned_hashes())
+                    .map(|hashes| hashes.len() as u64 * DATA_GAS_PER_BLOB)
+                    .sum();
+                let excess_blob_gas = blob_params.next_block_excess_blob_gas(
+                    self.parent.as_ref().and_then(|parent| parent.excess_blob_gas).unwrap_or(0),
+                    self.parent.as_ref().and_then(|parent| parent.blob_gas_used).unwrap_or(0),
+                );
+                (Some(blob_gas_used), Some(excess_blob_gas), Some(B256::ZERO))
+            } else {
+                (None, None, None)
+            };
+
+        let london = self.chain_spec.fork(EthereumHardfork::London);
+        let parent_gas_limit = self
+            .parent
+            .as_ref()
+            .map_or(self.chain_spec.genesis().gas_limit, |parent| parent.gas_limit);
+        let gas_limit = if london.transitions_at_block(number) {
+            let base_fee_params = self.chain_spec.base_fee_params_at_timestamp(timestamp);
+            parent_gas_limit * base_fee_params.elasticity_multiplier as u64
+        } else {
+            parent_gas_limit
+        };
+        let base_fee_per_gas = london.active_at_block(number).then(|| {
+            self.parent
+                .as_ref()
+                .filter(|_| !london.transitions_at_block(number))
+                .and_then(|parent| {
+                    parent.next_block_base_fee(
+                        self.chain_spec.base_fee_params_at_timestamp(timestamp),
+                    )
+                })
+                .unwrap_or(INITIAL_BASE_FEE)
+        });
+
+        let transactions = transactions.into_iter().map(RecoveredTx::to_components);
+        let (transactions, senders): (Vec<_>, Vec<_>) = transactions.unzip();
+        let header = Header {
+            parent_hash: self.parent_hash,
+            ommers_hash: proofs::calculate_ommers_root(&[]),
+            transactions_root: proofs::calculate_transaction_root(&transactions),
+            receipts_root: proofs::calculate_receipt_root(&receipts_with_bloom),
+            withdrawals_root: withdrawals
+                .as_ref()
+                .map(|withdrawals| proofs::calculate_withdrawals_root(withdrawals)),
+            logs_bloom,
+            number,
+            gas_limit,
+            gas_used: cumulative_gas_used,
+            timestamp,
+            base_fee_per_gas,
+            blob_gas_used,
+            excess_blob_gas,
+            parent_beacon_block_root,
+            requests_hash: self
+                .chain_spec
+                .is_prague_active_at_timestamp(timestamp)
+                .then_some(EMPTY_REQUESTS_HASH),
+            ..Default::default()
+        };
+
+        let header = SealedHeader::seal(header);
+        self.number += 1;
+        self.parent_hash = header.hash();
+        self.parent = Some(header.clone());
+
+        let block = SealedBlock {
+            header,
+            body: BlockBody { transactions, ommers: Vec::new(), withdrawals },
+        };
+        (SealedBlockWithSenders { block, senders }, receipts)
+    }
+
+    /// Builds the next `count` blocks of the chain.
+    pub fn extend(&mut self, count: usize) -> Vec<SealedBlockWithSenders> {
+        (0..count).map(|_| self.next_block()).collect()
+    }
+
+    /// Builds a block with the given number and parent hash, without changing the tip of the
+    /// chain the builder extends.
+    ///
+    /// Useful for disconnected blocks, whose parent is unknown.
+    pub fn block_at(&mut self, number: BlockNumber, parent_hash: B256) -> SealedBlockWithSenders {
+        let tip = (self.number, self.parent_hash, self.parent.take());
+        self.number = number;
+        self.parent_hash = parent_hash;
+        let block = self.next_block();
+        (self.number, self.parent_hash, self.parent) = tip;
+        block
+    }
+
+    /// Returns the intrinsic gas of the transaction in the block with the given number and
+    /// timestamp.
+    fn intrinsic_gas(&self, tx: &RecoveredTx, number: BlockNumber, timestamp: u64) -> u64 {
+        let non_zero_byte_gas =
+            if self.chain_spec.is_fork_active_at_block(EthereumHardfork::Istanbul, number) {
+                16
+            } else {
+                68
+            };
+        let input = tx.input();
+        let zero_bytes = input.iter().filter(|byte| **byte == 0).count() as u64;
+        let mut gas =
+            TRANSFER_GAS + zero_bytes * 4 + (input.len() as u64 - zero_bytes) * non_zero_byte_gas;
+
+        if tx.kind().is_create() {
+            gas += CREATE_GAS;
+            if self.chain_spec.is_shanghai_active_at_timestamp(timestamp) {
+                // EIP-3860 initcode word cost
+                gas += 2 * input.len().div_ceil(32) as u64;
+            }
+        }
+        if let Some(access_list) = tx.access_list() {
+            gas += access_list.len() as u64 * ACCESS_LIST_ADDRESS_GAS +
+                access_list.iter().map(|item| item.storage_keys.len() as u64).sum::<u64>() *
+                    ACCESS_LIST_STORAGE_KEY_GAS;
+        }
+        if let Some(authorization_list) = tx.authorization_list() {
+            gas += authorization_list.len() as u64 * AUTHORIZATION_GAS;
+        }
+        gas
+    }
+
+    /// Fills in the nonce and chain id of the transaction and signs it.
+    fn sign(&mut self, mut tx: TestTxBuilder) -> RecoveredTx {
+        if self.nonces.len() <= tx.signer {
+            self.nonces.resize(tx.signer + 1, 0);
+        }
+        let next_nonce = &mut self.nonces[tx.signer];
+        let nonce = *tx.nonce.get_or_insert(*next_nonce);
+        *next_nonce = (*next_nonce).max(nonce + 1);
+        tx.chain_id.get_or_insert(self.chain_spec.chain().id());
+        tx.sign()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::map::HashSet;
+    use reth_chainspec::ForkCondition;
+    use reth_consensus::{Consensus, HeaderValidator};
+    use reth_ethereum_consensus::EthBeaconConsensus;
+    use reth_primitives_traits::SignedTransaction;
+
+    /// Asserts that the blocks form a chain that passes the pre-execution consensus checks.
+    fn assert_valid_chain(chain_spec: Arc<ChainSpec>, blocks: &[SealedBlockWithSenders]) {
+        let consensus = EthBeaconConsensus::new(chain_spec.clone());
+        let mut parent = chain_spec.sealed_genesis_header();
+        for block in blocks {
+            consensus.validate_header(&block.header).unwrap();
+            consensus.validate_header_against_parent(&block.header, &parent).unwrap();
+            consensus.validate_block_pre_execution(&block.block).unwrap();
+            for (tx, sender) in block.body.transactions.iter().zip(&block.senders) {
+                assert_eq!(tx.recover_signer(), Some(*sender));
+            }
+            parent = block.header.clone();
+        }
+    }
+
+    #[test]
+    fn builds_all_transaction_types() {
+        let mut builder = TestBlockBuilder::default().with_withdrawals_per_block(2);
+        builder
+            .push_tx(TestTxBuilder::legacy())
+            .push_tx(TestTxBuilder::eip2930().signer(1))
+            .push_tx(TestTxBuilder::eip1559().gas_limit(100_000))
+            .push_tx(TestTxBuilder::eip4844().blobs(3).signer(2))
+            .push_tx(TestTxBuilder::eip7702());
+        let block = builder.next_block();
+
+        assert_eq!(
+            block.body.transactions.iter().map(|tx| tx.tx_type()).collect::<Vec<_>>(),
+            [TxType::Legacy, TxType::Eip2930, TxType::Eip1559, TxType::Eip4844, TxType::Eip7702]
+        );
+        assert_eq!(
+            block.body.transactions.iter().map(|tx| tx.nonce()).collect::<Vec<_>>(),
+            [0, 0, 1, 0, 2]
+        );
+        assert_eq!(block.blob_gas_used, Some(3 * DATA_GAS_PER_BLOB));
+        // the transactions only use their intrinsic gas
+        assert_eq!(
+            block.gas_used,
+            5 * TRANSFER_GAS +
+                ACCESS_LIST_ADDRESS_GAS +
+                ACCESS_LIST_STORAGE_KEY_GAS +
+                AUTHORIZATION_GAS
+        );
+        assert_eq!(block.body.withdrawals.as_ref().map(|withdrawals| withdrawals.len()), Some(2));
+        assert_eq!(builder.nonce(0), 3);
+
+        let mut builder = builder.with_txs_per_block(3);
+        let mut blocks = vec![block];
+        blocks.extend(builder.extend(5));
+        assert_valid_chain(builder.chain_spec().clone(), &blocks);
+    }
+
+    #[test]
+    fn builds_deterministic_chains() {
+        let build = || {
+            let mut builder = TestBlockBuilder::default().with_txs_per_block(2);
+            builder.push_tx(TestTxBuilder::eip4844().blobs(6));
+            builder.extend(10)
+        };
+        let blocks = build();
+        assert_eq!(blocks, build());
+
+        let hashes = blocks.iter().map(|block| block.hash()).collect::<HashSet<_>>();
+        assert_eq!(hashes.len(), blocks.len());
+        assert_valid_chain(TestBlockBuilder::default().chain_spec().clone(), &blocks);
+        // the six blobs of the first block are three above the target
+        assert_eq!(blocks[1].excess_blob_gas, Some(3 * DATA_GAS_PER_BLOB));
+    }
+
+    #[test]
+    fn builds_across_fork_boundaries() {
+        let chain_spec = Arc::new(
+            ChainSpecBuilder::mainnet()
+                .genesis(Genesis::default().with_gas_limit(ETHEREUM_BLOCK_GAS_LIMIT))
+                .berlin_activated()
+                .with_fork(EthereumHardfork::London, ForkCondition::Block(2))
+                .with_fork(
+                    EthereumHardfork::Paris,
+                    ForkCondition::TTD {
+                        activation_block_number: 2,
+                        fork_block: None,
+                        total_difficulty: U256::ZERO,
+                    },
+                )
+                .with_fork(EthereumHardfork::Shanghai, ForkCondition::Timestamp(4 * BLOCK_TIME))
+                .with_fork(EthereumHardfork::Cancun, ForkCondition::Timestamp(6 * BLOCK_TIME))
+                .with_fork(EthereumHardfork::Prague, ForkCondition::Timestamp(8 * BLOCK_TIME))
+                .build(),
+        );
+        let mut builder = TestBlockBuilder::new(chain_spec.clone())
+            .with_txs_per_block(1)
+            .with_withdrawals_per_block(1);
+        let blocks = builder.extend(10);
+        assert_valid_chain(chain_spec, &blocks);
+
+        assert_eq!(blocks[0].body.transactions[0].tx_type(), TxType::Legacy);
+        assert_eq!(blocks[0].base_fee_per_gas, None);
+        assert_eq!(blocks[1].body.transactions[0].tx_type(), TxType::Eip1559);
+        assert_eq!(blocks[1].base_fee_per_gas, Some(INITIAL_BASE_FEE));
+        assert_eq!(blocks[1].gas_limit, 2 * ETHEREUM_BLOCK_GAS_LIMIT);
+        assert!(blocks[2].withdrawals_root.is_none());
+        assert!(blocks[3].withdrawals_root.is_some());
+        assert!(blocks[4].blob_gas_used.is_none());
+        assert!(blocks[5].blob_gas_used.is_some());
+        assert!(blocks[6].requests_hash.is_none());
+        assert!(blocks[7].requests_hash.is_some());
+    }
+
+    #[test]
+    fn builds_disconnected_blocks() {
+        let mut builder = TestBlockBuilder::default().with_txs_per_block(1);
+        let parent = B256::repeat_byte(1);
+        let block = builder.block_at(10, parent);
+        let sibling = builder.block_at(10, parent);
+        assert_eq!((block.number, block.parent_hash), (10, parent));
+        assert_ne!(block.hash(), sibling.hash());
+
+        // the tip of the chain is unchanged
+        let next = builder.next_block();
+        assert_eq!(next.number, 1);
+        assert_eq!(next.parent_hash, builder.chain_spec().genesis_hash());
+    }
+}
@@ -13,3 +13,7 @@ pub mod genesis_allocator;
 pub use genesis_allocator::GenesisAllocator;
 
 pub mod generators;
+
+pub mod builder;
+
+pub use builder::{TestBlockBuilder, TestTxBuilder};